@@ -1,3 +1,5 @@
+mod os;
+
 use std::{
     fs,
     io::{self, BufRead, Write},
@@ -5,38 +7,82 @@ use std::{
     time::Instant,
 };
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use monty::{
-    MontyObject, MontyRepl, MontyRun, NoLimitTracker, PrintWriter, ReplContinuationMode, RunProgress,
+    ExcType, ExternalFunctions, ExternalResult, MontyException, MontyObject, MontyRepl, MontyRun, NoLimitTracker,
+    OsFunction, PrintWriter, Profiler, ReplContinuationMode, ResourceTracker, RunProgress,
     detect_repl_continuation_mode,
 };
 // disabled due to format failing on https://github.com/pydantic/monty/pull/75 where CI and local wanted imports ordered differently
 // TODO re-enabled soon!
+#[cfg(feature = "type-check")]
 #[rustfmt::skip]
 use monty_type_checking::{SourceFile, type_check};
 
+use crate::os::{call_os_function, read_input};
+
 /// Monty — a sandboxed Python interpreter written in Rust.
 ///
 /// - `monty` runs `example.py`
 /// - `monty <file>` runs the file in script mode
 /// - `monty -i` starts an empty interactive REPL
 /// - `monty -i <file>` seeds the REPL with file contents
+/// - `monty --allow-os <file>` lets the file use `Path` methods and `os.getenv` on the host
+/// - `monty -O <file>` runs the file without its assert statements, like `python -O`
+/// - `monty profile <file>` runs the file and reports the functions and lines executing the
+///   most instructions
+///
+/// `input()` always reads from stdin, in both script and REPL mode.
 #[derive(Parser)]
-#[command(version)]
+#[command(version, args_conflicts_with_subcommands = true)]
 struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Start interactive REPL mode.
     #[arg(short = 'i', long = "interactive")]
     interactive: bool,
 
+    /// Execute OS calls (filesystem and environment access) on the host in script mode.
+    ///
+    /// When built for `wasm32-wasip1` these only reach what the WASI runtime grants.
+    #[arg(long = "allow-os")]
+    allow_os: bool,
+
+    /// Remove assert statements and set `__debug__` to `False` in script mode, like `python -O`.
+    #[arg(short = 'O')]
+    optimize: bool,
+
     /// Python file to execute.
     file: Option<String>,
 }
 
-const EXT_FUNCTIONS: bool = false;
+#[derive(Subcommand)]
+enum Command {
+    /// Run a file in script mode and report the functions and lines executing the most instructions.
+    Profile {
+        /// Execute OS calls (filesystem and environment access) on the host.
+        #[arg(long = "allow-os")]
+        allow_os: bool,
+
+        /// Python file to profile.
+        file: String,
+    },
+}
 
 fn main() -> ExitCode {
     let cli = Cli::parse();
 
+    if let Some(Command::Profile { allow_os, file }) = cli.command {
+        return match read_file(&file) {
+            Ok(code) => profile_script(&file, code, allow_os),
+            Err(err) => {
+                eprintln!("error: {err}");
+                ExitCode::FAILURE
+            }
+        };
+    }
+
     if let Some(file_path) = cli.file.as_deref() {
         let code = match read_file(file_path) {
             Ok(code) => code,
@@ -48,7 +94,7 @@ fn main() -> ExitCode {
         return if cli.interactive {
             run_repl(file_path, code)
         } else {
-            run_script(file_path, code)
+            run_script(file_path, code, cli.allow_os, cli.optimize)
         };
     }
 
@@ -65,33 +111,42 @@ fn main() -> ExitCode {
         }
     };
 
-    run_script(file_path, code)
+    run_script(file_path, code, cli.allow_os, cli.optimize)
 }
 
 /// Executes a Python file in one-shot CLI mode.
 ///
-/// This path keeps the existing CLI behavior: run type-checking for visibility,
-/// compile the file as a full module, and execute it either through direct
-/// execution or through the suspendable progress loop when external functions
-/// are enabled.
+/// This path keeps the existing CLI behavior: run type-checking for visibility
+/// (when the `type-check` feature is enabled), compile the file as a full module,
+/// and execute it through the suspendable progress loop so external functions,
+/// `input()` and (with `allow_os`) OS calls can be resolved on the host.
+/// With `optimize`, asserts are removed like `python -O`.
 ///
 /// Returns `ExitCode::SUCCESS` for successful execution and
 /// `ExitCode::FAILURE` for parse/type/runtime failures.
-fn run_script(file_path: &str, code: String) -> ExitCode {
-    let start = Instant::now();
-    if let Some(failure) = type_check(&SourceFile::new(&code, file_path), None).unwrap() {
-        eprintln!("type checking failed:\n{failure}");
-    } else {
-        eprintln!("type checking succeeded");
+fn run_script(file_path: &str, code: String, allow_os: bool, optimize: bool) -> ExitCode {
+    #[cfg(feature = "type-check")]
+    {
+        let start = Instant::now();
+        if let Some(failure) = type_check(&SourceFile::new(&code, file_path), None).unwrap() {
+            eprintln!("type checking failed:\n{failure}");
+        } else {
+            eprintln!("type checking succeeded");
+        }
+        let elapsed = start.elapsed();
+        println!("time taken to run typing: {elapsed:?}");
     }
-    let elapsed = start.elapsed();
-    println!("time taken to run typing: {elapsed:?}");
 
     let input_names = vec![];
     let inputs = vec![];
-    let ext_functions = vec!["add_ints".to_owned()];
+    let ext_functions = cli_external_functions();
 
-    let runner = match MontyRun::new(code, file_path, input_names, ext_functions) {
+    let runner = if optimize {
+        MontyRun::new_optimized(code, file_path, input_names, ext_functions.names(), vec![])
+    } else {
+        MontyRun::new(code, file_path, input_names, ext_functions.names())
+    };
+    let runner = match runner {
         Ok(ex) => ex,
         Err(err) => {
             eprintln!("error:\n{err}");
@@ -99,42 +154,61 @@ fn run_script(file_path: &str, code: String) -> ExitCode {
         }
     };
 
-    if EXT_FUNCTIONS {
-        let start = Instant::now();
-        let progress = match runner.start(inputs, NoLimitTracker, &mut PrintWriter::Stdout) {
-            Ok(p) => p,
-            Err(err) => {
-                let elapsed = start.elapsed();
-                eprintln!("error after: {elapsed:?}\n{err}");
-                return ExitCode::FAILURE;
-            }
-        };
+    let start = Instant::now();
+    let progress = match runner.start(inputs, NoLimitTracker, &mut PrintWriter::Stdout) {
+        Ok(p) => p,
+        Err(err) => {
+            let elapsed = start.elapsed();
+            eprintln!("error after: {elapsed:?}\n{err}");
+            return ExitCode::FAILURE;
+        }
+    };
 
-        match run_until_complete(progress) {
-            Ok(value) => {
-                let elapsed = start.elapsed();
-                eprintln!("success after: {elapsed:?}\n{value}");
-                ExitCode::SUCCESS
-            }
-            Err(err) => {
-                let elapsed = start.elapsed();
-                eprintln!("error after: {elapsed:?}\n{err}");
-                ExitCode::FAILURE
-            }
+    match run_until_complete(progress, &ext_functions, allow_os) {
+        Ok(value) => {
+            let elapsed = start.elapsed();
+            eprintln!("success after: {elapsed:?}\n{value}");
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            let elapsed = start.elapsed();
+            eprintln!("error after: {elapsed:?}\n{err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Executes a Python file like `run_script`, then prints a report of the hottest
+/// functions and lines to stderr.
+///
+/// Instructions are counted by a `Profiler` used as the resource tracker. The report is
+/// printed even if execution fails, so failing scripts can be profiled too.
+fn profile_script(file_path: &str, code: String, allow_os: bool) -> ExitCode {
+    let ext_functions = cli_external_functions();
+    let runner = match MontyRun::new(code, file_path, vec![], ext_functions.names()) {
+        Ok(ex) => ex,
+        Err(err) => {
+            eprintln!("error:\n{err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let profiler = Profiler::new();
+    let result = runner
+        .start(vec![], profiler.clone(), &mut PrintWriter::Stdout)
+        .map_err(|err| format!("{err}"))
+        .and_then(|progress| run_until_complete(progress, &ext_functions, allow_os));
+    eprintln!("{}", profiler.report());
+
+    match result {
+        Ok(value) => {
+            eprintln!("success:\n{value}");
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("error:\n{err}");
+            ExitCode::FAILURE
         }
-    } else {
-        let start = Instant::now();
-        let value = match runner.run_no_limits(inputs) {
-            Ok(p) => p,
-            Err(err) => {
-                let elapsed = start.elapsed();
-                eprintln!("error after: {elapsed:?}\n{err}");
-                return ExitCode::FAILURE;
-            }
-        };
-        let elapsed = start.elapsed();
-        eprintln!("success after: {elapsed:?}\n{value}");
-        ExitCode::SUCCESS
     }
 }
 
@@ -151,7 +225,7 @@ fn run_script(file_path: &str, code: String) -> ExitCode {
 fn run_repl(file_path: &str, code: String) -> ExitCode {
     let input_names = vec![];
     let inputs = vec![];
-    let ext_functions = vec!["add_ints".to_owned()];
+    let ext_functions = cli_external_functions().names();
 
     let (mut repl, init_output) = match MontyRepl::new(
         code,
@@ -216,7 +290,7 @@ fn run_repl(file_path: &str, code: String) -> ExitCode {
         pending_snippet.push('\n');
 
         if continuation_mode == ReplContinuationMode::IncompleteBlock && snippet.is_empty() {
-            execute_repl_snippet(&mut repl, &pending_snippet);
+            execute_repl_snippet(&mut repl, &pending_snippet, &mut stdin);
             pending_snippet.clear();
             continuation_mode = ReplContinuationMode::Complete;
             continue;
@@ -228,7 +302,7 @@ fn run_repl(file_path: &str, code: String) -> ExitCode {
                 if continuation_mode == ReplContinuationMode::IncompleteBlock {
                     continue;
                 }
-                execute_repl_snippet(&mut repl, &pending_snippet);
+                execute_repl_snippet(&mut repl, &pending_snippet, &mut stdin);
                 pending_snippet.clear();
                 continuation_mode = ReplContinuationMode::Complete;
             }
@@ -243,8 +317,24 @@ fn run_repl(file_path: &str, code: String) -> ExitCode {
 }
 
 /// Executes one collected REPL snippet and prints value/errors for interactive use.
-fn execute_repl_snippet(repl: &mut MontyRepl<NoLimitTracker>, snippet: &str) {
-    match repl.feed_no_print(snippet) {
+///
+/// `input()` calls read from `stdin`, the lock the REPL already holds for reading snippets.
+/// Other OS calls are not available in the REPL and raise `NotImplementedError`.
+fn execute_repl_snippet(repl: &mut MontyRepl<NoLimitTracker>, snippet: &str, stdin: &mut impl BufRead) {
+    let result = repl.feed_with_os_handler(
+        snippet,
+        &mut PrintWriter::Stdout,
+        |function, args, _kwargs| match function {
+            OsFunction::Input => read_input(args, stdin),
+            _ => ExternalResult::Error(MontyException::new(
+                ExcType::NotImplementedError,
+                Some(format!(
+                    "OS function '{function}' not implemented with standard execution"
+                )),
+            )),
+        },
+    );
+    match result {
         Ok(output) => {
             if output != MontyObject::None {
                 println!("{output}");
@@ -257,60 +347,78 @@ fn execute_repl_snippet(repl: &mut MontyRepl<NoLimitTracker>, snippet: &str) {
 /// Drives suspendable execution until completion.
 ///
 /// This repeatedly resumes `RunProgress` values by resolving supported
-/// external calls and returns the final value when execution reaches
-/// `RunProgress::Complete`.
+/// external calls, `input()` from stdin, and other OS calls on the host when `allow_os`
+/// is set, and returns
+/// the final value when execution reaches `RunProgress::Complete`.
+/// Values streamed with `monty_yield()` are printed to stdout as they arrive.
 ///
-/// Returns an error string for unsupported suspend points (OS calls or async
-/// futures) or invalid external-function dispatch.
-fn run_until_complete(mut progress: RunProgress<NoLimitTracker>) -> Result<MontyObject, String> {
+/// Returns an error string for unsupported suspend points (async futures, or OS
+/// calls without `allow_os`) or calls to functions missing from `ext_functions`.
+/// Wrong argument counts or types are raised as `TypeError` inside the sandbox.
+fn run_until_complete<T: ResourceTracker>(
+    mut progress: RunProgress<T>,
+    ext_functions: &ExternalFunctions,
+    allow_os: bool,
+) -> Result<MontyObject, String> {
     loop {
         match progress {
-            RunProgress::Complete(value) => return Ok(value),
+            RunProgress::Complete { value, .. } => return Ok(value),
             RunProgress::FunctionCall {
                 function_name,
                 args,
+                kwargs,
                 state,
                 ..
             } => {
-                let return_value = resolve_external_call(&function_name, &args)?;
+                let Some(return_value) = ext_functions.call(&function_name, args, kwargs) else {
+                    return Err(format!("unknown external function: {function_name}"));
+                };
                 progress = state
                     .run(return_value, &mut PrintWriter::Stdout)
                     .map_err(|err| format!("{err}"))?;
             }
-            RunProgress::ResolveFutures(state) => {
+            RunProgress::Yield { value, state } => {
+                println!("{value}");
+                progress = state
+                    .run(MontyObject::None, &mut PrintWriter::Stdout)
+                    .map_err(|err| format!("{err}"))?;
+            }
+            RunProgress::Checkpoint(state) => {
+                progress = state.run(&mut PrintWriter::Stdout).map_err(|err| format!("{err}"))?;
+            }
+            RunProgress::ResolveFutures(state) | RunProgress::FunctionCallBatch { state, .. } => {
                 return Err(format!(
                     "async futures not supported in CLI: {:?}",
                     state.pending_call_ids()
                 ));
             }
-            RunProgress::OsCall { function, args, .. } => {
-                return Err(format!("OS calls not supported in CLI: {function:?}({args:?})"));
+            RunProgress::OsCall {
+                function,
+                args,
+                kwargs,
+                state,
+                ..
+            } => {
+                if !allow_os && function != OsFunction::Input {
+                    return Err(format!(
+                        "OS calls not allowed, use --allow-os to enable them: {function:?}({args:?})"
+                    ));
+                }
+                let result = call_os_function(function, &args, &kwargs);
+                progress = state
+                    .run(result, &mut PrintWriter::Stdout)
+                    .map_err(|err| format!("{err}"))?;
             }
         }
     }
 }
 
-/// Resolves supported CLI external function calls.
+/// External functions available to code run by the CLI.
 ///
 /// The CLI currently supports only `add_ints(int, int)`, which makes it
 /// possible to exercise the suspend/resume path in a deterministic way.
-///
-/// Returns a runtime-like error string for unknown function names, wrong arity,
-/// or incorrect argument types.
-fn resolve_external_call(function_name: &str, args: &[MontyObject]) -> Result<MontyObject, String> {
-    if function_name != "add_ints" {
-        return Err(format!("unknown external function: {function_name}({args:?})"));
-    }
-
-    if args.len() != 2 {
-        return Err(format!("add_ints requires exactly 2 arguments, got {}", args.len()));
-    }
-
-    if let (MontyObject::Int(a), MontyObject::Int(b)) = (&args[0], &args[1]) {
-        Ok(MontyObject::Int(a + b))
-    } else {
-        Err(format!("add_ints requires integer arguments, got {args:?}"))
-    }
+fn cli_external_functions() -> ExternalFunctions {
+    ExternalFunctions::new().register("add_ints", |a: i64, b: i64| a + b)
 }
 
 fn read_file(file_path: &str) -> Result<String, String> {
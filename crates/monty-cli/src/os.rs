@@ -0,0 +1,535 @@
+//! Host implementation of Monty's OS calls for the CLI.
+//!
+//! `Path` methods, `tempfile` scratch space and `os.getenv`/`os.environ` are executed with
+//! `std::fs` and `std::env`, and `input()` reads a line from stdin.
+//! When the CLI is built for `wasm32-wasip1` those go through WASI, so the sandbox can only see
+//! the directories and environment variables the WASI runtime grants, e.g.
+//! `wasmtime run --dir . monty.wasm script.py` or Deno/Bun's `node:wasi` with `preopens`.
+
+use std::{
+    env, fs,
+    io::{self, BufRead, Write},
+    path::{Path, PathBuf, absolute},
+    sync::atomic::{AtomicU32, Ordering},
+    time::SystemTime,
+};
+
+use monty::{
+    DictPairs, ExcType, ExternalResult, MontyException, MontyObject, OsFunction, named_temporary_file,
+    temporary_directory,
+};
+
+/// Executes an OS call against the host, returning the value or exception to resume with.
+pub fn call_os_function(
+    function: OsFunction,
+    args: &[MontyObject],
+    kwargs: &[(MontyObject, MontyObject)],
+) -> ExternalResult {
+    match dispatch(function, args, kwargs) {
+        Ok(value) => ExternalResult::Return(value),
+        Err(exc) => ExternalResult::Error(exc),
+    }
+}
+
+/// Maps each `OsFunction` to the equivalent `std` operation.
+fn dispatch(
+    function: OsFunction,
+    args: &[MontyObject],
+    kwargs: &[(MontyObject, MontyObject)],
+) -> Result<MontyObject, MontyException> {
+    match function {
+        OsFunction::Input => return read_line(args, &mut io::stdin().lock()),
+        OsFunction::Getenv => {
+            let key = str_arg(function, args, 0)?;
+            let value = env::var(key).ok().map_or_else(
+                || args.get(1).cloned().unwrap_or(MontyObject::None),
+                MontyObject::String,
+            );
+            return Ok(value);
+        }
+        OsFunction::GetEnviron => {
+            let pairs: Vec<(MontyObject, MontyObject)> = env::vars()
+                .map(|(key, value)| (MontyObject::String(key), MontyObject::String(value)))
+                .collect();
+            return Ok(MontyObject::Dict(DictPairs::from(pairs)));
+        }
+        OsFunction::ScratchPath => return scratch_path(args),
+        _ => {}
+    }
+
+    let path = PathBuf::from(str_arg(function, args, 0)?);
+    let io_err = |err: io::Error| io_exception(&err, &path);
+    match function {
+        OsFunction::Exists => Ok(MontyObject::Bool(path.exists())),
+        OsFunction::IsFile => Ok(MontyObject::Bool(path.is_file())),
+        OsFunction::IsDir => Ok(MontyObject::Bool(path.is_dir())),
+        OsFunction::IsSymlink => Ok(MontyObject::Bool(path.is_symlink())),
+        OsFunction::ReadText => fs::read_to_string(&path).map(MontyObject::String).map_err(io_err),
+        OsFunction::ReadBytes => fs::read(&path).map(MontyObject::Bytes).map_err(io_err),
+        OsFunction::WriteText => {
+            let data = str_arg(function, args, 1)?;
+            fs::write(&path, data).map_err(io_err)?;
+            Ok(MontyObject::Int(char_count(data)))
+        }
+        OsFunction::WriteBytes => {
+            let Some(MontyObject::Bytes(data)) = args.get(1) else {
+                return Err(arg_error(function, "bytes"));
+            };
+            fs::write(&path, data).map_err(io_err)?;
+            Ok(MontyObject::Int(i64::try_from(data.len()).unwrap_or(i64::MAX)))
+        }
+        OsFunction::Mkdir => {
+            let parents = bool_kwarg(kwargs, "parents");
+            let exist_ok = bool_kwarg(kwargs, "exist_ok");
+            let result = if parents {
+                fs::create_dir_all(&path)
+            } else {
+                fs::create_dir(&path)
+            };
+            match result {
+                Err(err) if exist_ok && err.kind() == io::ErrorKind::AlreadyExists && path.is_dir() => {}
+                other => other.map_err(io_err)?,
+            }
+            Ok(MontyObject::None)
+        }
+        OsFunction::Unlink => fs::remove_file(&path).map(|()| MontyObject::None).map_err(io_err),
+        OsFunction::Rmdir => fs::remove_dir(&path).map(|()| MontyObject::None).map_err(io_err),
+        OsFunction::Iterdir => {
+            let mut entries = fs::read_dir(&path)
+                .and_then(|dir| dir.map(|entry| entry.map(|e| e.path())).collect::<io::Result<Vec<_>>>())
+                .map_err(io_err)?;
+            entries.sort();
+            Ok(MontyObject::List(
+                entries.iter().map(|p| MontyObject::Path(path_string(p))).collect(),
+            ))
+        }
+        OsFunction::Stat => fs::metadata(&path).map(|m| stat(&m)).map_err(io_err),
+        OsFunction::Lstat => fs::symlink_metadata(&path).map(|m| stat(&m)).map_err(io_err),
+        OsFunction::Rename => {
+            let target = str_arg(function, args, 1)?;
+            fs::rename(&path, target).map(|()| MontyObject::None).map_err(io_err)
+        }
+        OsFunction::Copy => {
+            let target = str_arg(function, args, 1)?;
+            fs::copy(&path, target).map_err(io_err)?;
+            Ok(MontyObject::Path(target.to_owned()))
+        }
+        OsFunction::Touch => touch(&path, kwargs).map(|()| MontyObject::None).map_err(io_err),
+        OsFunction::Chmod => {
+            let Some(MontyObject::Int(mode)) = args.get(1) else {
+                return Err(arg_error(function, "int"));
+            };
+            chmod(&path, *mode).map(|()| MontyObject::None).map_err(io_err)
+        }
+        OsFunction::Glob | OsFunction::Rglob => {
+            let pattern = str_arg(function, args, 1)?;
+            let mut parts: Vec<&str> = pattern.split('/').filter(|part| !part.is_empty()).collect();
+            if function == OsFunction::Rglob {
+                parts.insert(0, "**");
+            }
+            let mut matches = Vec::new();
+            glob(&path, &parts, &mut matches).map_err(io_err)?;
+            matches.sort();
+            matches.dedup();
+            Ok(MontyObject::List(
+                matches.iter().map(|p| MontyObject::Path(path_string(p))).collect(),
+            ))
+        }
+        OsFunction::Walk => {
+            let top_down = kwargs
+                .iter()
+                .find(|(key, _)| matches!(key, MontyObject::String(k) if k == "top_down"))
+                .is_none_or(|(_, value)| value.is_truthy());
+            let mut tree = Vec::new();
+            walk(&path, top_down, bool_kwarg(kwargs, "follow_symlinks"), &mut tree).map_err(io_err)?;
+            Ok(MontyObject::List(tree))
+        }
+        OsFunction::Readlink => fs::read_link(&path)
+            .map(|target| MontyObject::Path(path_string(&target)))
+            .map_err(io_err),
+        OsFunction::SymlinkTo => {
+            let target = str_arg(function, args, 1)?;
+            symlink(target, &path).map(|()| MontyObject::None).map_err(io_err)
+        }
+        OsFunction::Resolve => fs::canonicalize(&path)
+            .or_else(|_| absolute(&path))
+            .map(|p| MontyObject::String(path_string(&p)))
+            .map_err(io_err),
+        OsFunction::Absolute => absolute(&path)
+            .map(|p| MontyObject::String(path_string(&p)))
+            .map_err(io_err),
+        OsFunction::Input | OsFunction::Getenv | OsFunction::GetEnviron | OsFunction::ScratchPath => {
+            unreachable!("handled above")
+        }
+    }
+}
+
+/// Executes an `input()` call, reading the line from `reader`.
+///
+/// Used by the REPL, which already holds the stdin lock for reading snippets.
+pub fn read_input(args: &[MontyObject], reader: &mut impl BufRead) -> ExternalResult {
+    match read_line(args, reader) {
+        Ok(value) => ExternalResult::Return(value),
+        Err(exc) => ExternalResult::Error(exc),
+    }
+}
+
+/// Writes the `input()` prompt to stdout and reads one line without its line ending.
+///
+/// Raises `EOFError` at end of input, like CPython.
+fn read_line(args: &[MontyObject], reader: &mut impl BufRead) -> Result<MontyObject, MontyException> {
+    let os_err = |err: io::Error| MontyException::new(ExcType::OSError, Some(err.to_string()));
+    if !args.is_empty() {
+        let mut stdout = io::stdout();
+        stdout
+            .write_all(str_arg(OsFunction::Input, args, 0)?.as_bytes())
+            .map_err(os_err)?;
+        stdout.flush().map_err(os_err)?;
+    }
+    let mut line = String::new();
+    if reader.read_line(&mut line).map_err(os_err)? == 0 {
+        return Err(MontyException::new(
+            ExcType::EOFError,
+            Some("EOF when reading a line".to_owned()),
+        ));
+    }
+    let line = line.strip_suffix('\n').unwrap_or(&line);
+    Ok(MontyObject::String(line.strip_suffix('\r').unwrap_or(line).to_owned()))
+}
+
+/// Builds a `stat_result` from the host metadata, with every field the platform provides.
+#[cfg(unix)]
+fn stat(metadata: &fs::Metadata) -> MontyObject {
+    use std::os::unix::fs::MetadataExt;
+
+    use monty::stat_result;
+
+    let int = |value: u64| i64::try_from(value).unwrap_or(i64::MAX);
+    let time = |secs: i64, nsecs: i64| secs as f64 + nsecs as f64 / 1e9;
+    stat_result(
+        i64::from(metadata.mode()),
+        int(metadata.ino()),
+        int(metadata.dev()),
+        int(metadata.nlink()),
+        i64::from(metadata.uid()),
+        i64::from(metadata.gid()),
+        int(metadata.size()),
+        time(metadata.atime(), metadata.atime_nsec()),
+        time(metadata.mtime(), metadata.mtime_nsec()),
+        time(metadata.ctime(), metadata.ctime_nsec()),
+    )
+}
+
+/// Builds a `stat_result` from the host metadata.
+///
+/// Only the file type, read-only flag, size and modification time are available portably
+/// (WASI has no permission bits), so the mode is reconstructed from those.
+#[cfg(not(unix))]
+fn stat(metadata: &fs::Metadata) -> MontyObject {
+    use std::time::UNIX_EPOCH;
+
+    use monty::{dir_stat, file_stat, symlink_stat};
+
+    let mtime = metadata
+        .modified()
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map_or(0.0, |duration| duration.as_secs_f64());
+    let readonly = metadata.permissions().readonly();
+    if metadata.is_symlink() {
+        symlink_stat(0o777, mtime)
+    } else if metadata.is_dir() {
+        dir_stat(if readonly { 0o555 } else { 0o755 }, mtime)
+    } else {
+        let size = i64::try_from(metadata.len()).unwrap_or(i64::MAX);
+        file_stat(if readonly { 0o444 } else { 0o644 }, size, mtime)
+    }
+}
+
+/// Creates a file, or updates the modification time of an existing one, like `Path.touch`.
+///
+/// `exist_ok` defaults to `True`; the `mode` of a new file is only applied on unix.
+fn touch(path: &Path, kwargs: &[(MontyObject, MontyObject)]) -> io::Result<()> {
+    let kwarg = |name: &str| {
+        kwargs
+            .iter()
+            .find(|(key, _)| matches!(key, MontyObject::String(k) if k == name))
+    };
+    let exist_ok = kwarg("exist_ok").is_none_or(|(_, value)| value.is_truthy());
+    if exist_ok && path.exists() {
+        return fs::File::options()
+            .append(true)
+            .open(path)?
+            .set_modified(SystemTime::now());
+    }
+    let mut options = fs::File::options();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    if let Some((_, MontyObject::Int(mode))) = kwarg("mode") {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(u32::try_from(*mode).unwrap_or(0o666));
+    }
+    options.open(path).map(drop)
+}
+
+/// Creates a uniquely named empty directory or file for `tempfile`, in the system's temporary
+/// directory unless a `dir` is given.
+///
+/// Like `mkdtemp` and `mkstemp`, new entries are only accessible to the owner and are never
+/// removed by the CLI.
+fn scratch_path(args: &[MontyObject]) -> Result<MontyObject, MontyException> {
+    /// Attempts at finding an unused name, CPython's `TMP_MAX`.
+    const ATTEMPTS: u32 = 10_000;
+    static COUNTER: AtomicU32 = AtomicU32::new(0);
+
+    let function = OsFunction::ScratchPath;
+    let Some(&MontyObject::Bool(is_dir)) = args.first() else {
+        return Err(arg_error(function, "bool"));
+    };
+    let optional = |index| match args.get(index) {
+        None | Some(MontyObject::None) => Ok(None),
+        Some(_) => str_arg(function, args, index).map(Some),
+    };
+    let suffix = optional(1)?.unwrap_or("");
+    let prefix = optional(2)?.unwrap_or("tmp");
+    let dir = optional(3)?.map_or_else(env::temp_dir, PathBuf::from);
+
+    // `process::id()` isn't available on WASI, so names are only seeded by the clock
+    let seed = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map_or(0, |duration| duration.subsec_nanos());
+    for _ in 0..ATTEMPTS {
+        let unique = seed ^ COUNTER.fetch_add(1, Ordering::Relaxed).wrapping_mul(0x9E37_79B9);
+        let path = dir.join(format!("{prefix}{unique:08x}{suffix}"));
+        let created = if is_dir {
+            create_private_dir(&path)
+        } else {
+            create_private_file(&path)
+        };
+        match created {
+            Ok(()) if is_dir => return Ok(temporary_directory(path_string(&path))),
+            Ok(()) => return Ok(named_temporary_file(path_string(&path))),
+            Err(err) if err.kind() == io::ErrorKind::AlreadyExists => {}
+            Err(err) => return Err(io_exception(&err, &path)),
+        }
+    }
+    Err(io_exception(&io::Error::from(io::ErrorKind::AlreadyExists), &dir))
+}
+
+/// Creates a directory only its owner can access, on unix.
+fn create_private_dir(path: &Path) -> io::Result<()> {
+    let mut builder = fs::DirBuilder::new();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::DirBuilderExt;
+        builder.mode(0o700);
+    }
+    builder.create(path)
+}
+
+/// Creates a new empty file only its owner can access, on unix.
+fn create_private_file(path: &Path) -> io::Result<()> {
+    let mut options = fs::File::options();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options.open(path).map(drop)
+}
+
+/// Sets the permission bits of a file; outside unix only the owner write bit is honoured.
+fn chmod(path: &Path, mode: i64) -> io::Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(path, fs::Permissions::from_mode(u32::try_from(mode).unwrap_or(0)))
+    }
+    #[cfg(not(unix))]
+    {
+        let mut permissions = fs::metadata(path)?.permissions();
+        permissions.set_readonly(mode & 0o200 == 0);
+        fs::set_permissions(path, permissions)
+    }
+}
+
+/// Creates the symlink `link` pointing at `target`, only supported on unix.
+fn symlink(target: &str, link: &Path) -> io::Result<()> {
+    #[cfg(unix)]
+    {
+        std::os::unix::fs::symlink(target, link)
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = (target, link);
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "symlinks are not supported on this platform",
+        ))
+    }
+}
+
+/// Collects the paths below `dir` matching the glob pattern `parts`, split into components.
+///
+/// `**` matches `dir` itself and every directory below it, without following symlinks.
+fn glob(dir: &Path, parts: &[&str], matches: &mut Vec<PathBuf>) -> io::Result<()> {
+    let Some((first, rest)) = parts.split_first() else {
+        matches.push(dir.to_path_buf());
+        return Ok(());
+    };
+    if *first == "**" {
+        glob(dir, rest, matches)?;
+        for entry in fs::read_dir(dir)? {
+            let entry = entry?;
+            if entry.file_type()?.is_dir() {
+                glob(&entry.path(), parts, matches)?;
+            }
+        }
+        return Ok(());
+    }
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        if !glob_match(first.as_bytes(), entry.file_name().as_encoded_bytes()) {
+            continue;
+        }
+        if rest.is_empty() {
+            matches.push(entry.path());
+        } else if entry.path().is_dir() {
+            glob(&entry.path(), rest, matches)?;
+        }
+    }
+    Ok(())
+}
+
+/// Matches a file name against a glob pattern with `*`, `?` and `[...]` wildcards.
+fn glob_match(pattern: &[u8], name: &[u8]) -> bool {
+    match pattern.split_first() {
+        None => name.is_empty(),
+        Some((b'*', rest)) => (0..=name.len()).any(|skip| glob_match(rest, &name[skip..])),
+        Some((b'?', rest)) => !name.is_empty() && glob_match(rest, &name[1..]),
+        Some((b'[', rest)) => match split_glob_set(rest) {
+            Some((set, rest)) => name
+                .split_first()
+                .is_some_and(|(c, name)| glob_set_contains(set, *c) && glob_match(rest, name)),
+            None => name.first() == Some(&b'[') && glob_match(rest, &name[1..]),
+        },
+        Some((c, rest)) => name.first() == Some(c) && glob_match(rest, &name[1..]),
+    }
+}
+
+/// Splits the contents of a `[...]` set from the rest of the pattern following the `[`.
+///
+/// A `]` straight after the `[` or `[!` is part of the set, like `fnmatch`.
+fn split_glob_set(pattern: &[u8]) -> Option<(&[u8], &[u8])> {
+    let skip = if pattern.first() == Some(&b'!') { 2 } else { 1 };
+    let end = skip + pattern.get(skip..)?.iter().position(|&c| c == b']')?;
+    Some((&pattern[..end], &pattern[end + 1..]))
+}
+
+/// Checks whether a byte is in a glob set, which may be negated with `!` and contain ranges.
+fn glob_set_contains(set: &[u8], c: u8) -> bool {
+    let (negated, mut items) = match set.split_first() {
+        Some((b'!', items)) => (true, items),
+        _ => (false, set),
+    };
+    let mut found = false;
+    while let Some((&first, rest)) = items.split_first() {
+        if let [b'-', last, rest @ ..] = rest {
+            found |= (first..=*last).contains(&c);
+            items = rest;
+        } else {
+            found |= first == c;
+            items = rest;
+        }
+    }
+    found != negated
+}
+
+/// Walks the directory tree below `dir` like `Path.walk`, appending a
+/// `(dirpath, dirnames, filenames)` tuple for each directory.
+///
+/// Symlinks to directories are listed as files unless `follow_symlinks` is set.
+fn walk(dir: &Path, top_down: bool, follow_symlinks: bool, tree: &mut Vec<MontyObject>) -> io::Result<()> {
+    let mut dirs = Vec::new();
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let is_dir = if follow_symlinks {
+            entry.path().is_dir()
+        } else {
+            entry.file_type()?.is_dir()
+        };
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if is_dir { dirs.push(name) } else { files.push(name) }
+    }
+    dirs.sort();
+    files.sort();
+    let names = |names: &[String]| MontyObject::List(names.iter().cloned().map(MontyObject::String).collect());
+    let mut this = Some(MontyObject::Tuple(vec![
+        MontyObject::Path(path_string(dir)),
+        names(&dirs),
+        names(&files),
+    ]));
+    if top_down {
+        tree.extend(this.take());
+    }
+    for name in &dirs {
+        walk(&dir.join(name), top_down, follow_symlinks, tree)?;
+    }
+    tree.extend(this);
+    Ok(())
+}
+
+/// Converts an `io::Error` to the matching `OSError` subclass, in CPython's message format.
+fn io_exception(err: &io::Error, path: &Path) -> MontyException {
+    let exc_type = match err.kind() {
+        io::ErrorKind::NotFound => ExcType::FileNotFoundError,
+        io::ErrorKind::AlreadyExists => ExcType::FileExistsError,
+        io::ErrorKind::IsADirectory => ExcType::IsADirectoryError,
+        io::ErrorKind::NotADirectory => ExcType::NotADirectoryError,
+        _ => ExcType::OSError,
+    };
+    let path = path_string(path);
+    let message = match err.raw_os_error() {
+        Some(code) => format!("[Errno {code}] {}: '{path}'", err.kind()),
+        None => format!("{err}: '{path}'"),
+    };
+    MontyException::new(exc_type, Some(message))
+}
+
+/// Extracts a string or path positional argument.
+fn str_arg(function: OsFunction, args: &[MontyObject], index: usize) -> Result<&str, MontyException> {
+    match args.get(index) {
+        Some(MontyObject::String(s) | MontyObject::Path(s)) => Ok(s),
+        _ => Err(arg_error(function, "str")),
+    }
+}
+
+/// Reads an optional boolean keyword argument, defaulting to `False`.
+fn bool_kwarg(kwargs: &[(MontyObject, MontyObject)], name: &str) -> bool {
+    kwargs
+        .iter()
+        .find(|(key, _)| matches!(key, MontyObject::String(k) if k == name))
+        .is_some_and(|(_, value)| value.is_truthy())
+}
+
+/// Error for an OS call whose arguments don't have the expected types.
+fn arg_error(function: OsFunction, expected: &str) -> MontyException {
+    MontyException::new(
+        ExcType::TypeError,
+        Some(format!(
+            "{function}() received an unexpected argument, expected {expected}"
+        )),
+    )
+}
+
+/// Number of characters written by `write_text`, which is what CPython returns.
+fn char_count(data: &str) -> i64 {
+    i64::try_from(data.chars().count()).unwrap_or(i64::MAX)
+}
+
+/// Lossy conversion of a host path to the string Monty sees.
+fn path_string(path: &Path) -> String {
+    path.to_string_lossy().into_owned()
+}
@@ -0,0 +1,78 @@
+/*
+ * C API for the Monty sandboxed Python interpreter.
+ *
+ * All strings are NUL-terminated UTF-8. Values are exchanged as JSON, with Monty values in the
+ * externally tagged format of `MontyObject`, e.g. `{"Int": 42}`, `{"String": "hi"}` or `"None"`.
+ *
+ * Strings returned as `char *` are owned by the caller and must be freed with `monty_string_free`.
+ */
+
+#ifndef MONTY_H
+#define MONTY_H
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Parsed and compiled code, which can be run any number of times. */
+typedef struct MontyProgram MontyProgram;
+
+/* A paused, completed or failed execution. */
+typedef struct MontyProgress MontyProgress;
+
+/*
+ * Called by `monty_program_run` for each external function or OS call, and each `monty_yield()`.
+ *
+ * `event_json` is `{"kind": "function_call", "function_name": ..., "args": [...], "kwargs": [[k, v], ...],
+ * "call_id": ..., "method_call": ..., "stdout": ...}`, the `os_call` equivalent with `"function"`, or
+ * `{"kind": "yield", "value": ..., "stdout": ...}`, and is only valid during the callback.
+ *
+ * Returns `{"return": <value>}` or `{"exception": {"type": "ValueError", "message": "..."}}`, which
+ * the host keeps ownership of and must keep valid until the callback is invoked again or the run
+ * returns. Returning NULL aborts the run with an error.
+ */
+typedef const char *(*MontyCallback)(void *user_data, const char *event_json);
+
+/* The Monty version, a static string which must not be freed. */
+const char *monty_version(void);
+
+/*
+ * Parses and compiles `code`. `input_names_json` and `external_functions_json` are JSON arrays of
+ * names, or NULL. Returns NULL on failure and sets `*error_json` (if not NULL) to
+ * `{"type": ..., "message": ..., "traceback": ...}`.
+ */
+MontyProgram *monty_program_new(const char *code, const char *script_name, const char *input_names_json,
+                                const char *external_functions_json, char **error_json);
+
+/* Frees a program, NULL is ignored. */
+void monty_program_free(MontyProgram *program);
+
+/*
+ * Runs a program to completion. `inputs_json` is a JSON array of values in the order of the input
+ * names, `limits_json` an object like `{"max_duration_secs": 1.5, "max_memory": 1000000}`, either may
+ * be NULL. Returns `{"kind": "complete", "output": ..., "stdout": ...}` or
+ * `{"kind": "error", "error": {...}, "stdout": ...}`.
+ */
+char *monty_program_run(const MontyProgram *program, const char *inputs_json, const char *limits_json,
+                        MontyCallback callback, void *user_data);
+
+/* Starts running a program, pausing at the first external call. Never returns NULL. */
+MontyProgress *monty_program_start(const MontyProgram *program, const char *inputs_json, const char *limits_json);
+
+/* The event JSON describing a progress, owned by the progress and valid until it's freed or resumed. */
+const char *monty_progress_json(const MontyProgress *progress);
+
+/* Resumes a paused progress with a result as returned by `MontyCallback`, consuming it. */
+MontyProgress *monty_progress_resume(MontyProgress *progress, const char *result_json);
+
+/* Frees a progress which hasn't been resumed, NULL is ignored. */
+void monty_progress_free(MontyProgress *progress);
+
+/* Frees a string returned by this API, NULL is ignored. */
+void monty_string_free(char *s);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* MONTY_H */
@@ -0,0 +1,231 @@
+//! JSON encoding of the values exchanged over the C API.
+//!
+//! Monty values use the derived serde format of [`MontyObject`] (externally tagged, e.g.
+//! `{"Int": 42}` or `"None"`), everything else is described by the types below.
+
+use std::time::Duration;
+
+use monty::{
+    ExcType, ExternalResult, HostError, LimitedTracker, MontyException, MontyObject, ResourceLimits, RunProgress,
+};
+use serde::{Deserialize, Serialize};
+
+/// Resource limits, with the same keys as the `limits` dict of the Python bindings.
+///
+/// Missing keys keep the defaults of [`ResourceLimits::new`].
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Limits {
+    max_allocations: Option<usize>,
+    max_duration_secs: Option<f64>,
+    max_memory: Option<usize>,
+    gc_interval: Option<usize>,
+    max_recursion_depth: Option<usize>,
+    max_output_size: Option<usize>,
+    max_stack_size: Option<usize>,
+}
+
+impl Limits {
+    /// Converts the parsed limits to the core `ResourceLimits`.
+    ///
+    /// Returns a `ValueError` if `max_duration_secs` is negative or too large for a `Duration`.
+    pub fn into_resource_limits(self) -> Result<ResourceLimits, MontyException> {
+        let mut limits = ResourceLimits::new();
+        if let Some(max) = self.max_allocations {
+            limits = limits.max_allocations(max);
+        }
+        if let Some(secs) = self.max_duration_secs {
+            let duration = Duration::try_from_secs_f64(secs).map_err(|_| {
+                MontyException::new(
+                    ExcType::ValueError,
+                    Some(format!(
+                        "max_duration_secs must be a non-negative number of seconds, got {secs}"
+                    )),
+                )
+            })?;
+            limits = limits.max_duration(duration);
+        }
+        if let Some(max) = self.max_memory {
+            limits = limits.max_memory(max);
+        }
+        if let Some(interval) = self.gc_interval {
+            limits = limits.gc_interval(interval);
+        }
+        if let Some(max) = self.max_recursion_depth {
+            limits = limits.max_recursion_depth(Some(max));
+        }
+        if let Some(max) = self.max_output_size {
+            limits = limits.max_output_size(max);
+        }
+        if let Some(max) = self.max_stack_size {
+            limits = limits.max_stack_size(Some(max));
+        }
+        Ok(limits)
+    }
+}
+
+/// The result of an external function or OS call, provided by the host to resume execution.
+///
+/// Encoded as `{"return": <value>}` or `{"exception": {"type": "ValueError", "message": "..."}}`.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub enum CallResult {
+    /// The call returned a value.
+    Return(MontyObject),
+    /// The call raised an exception.
+    Exception(HostException),
+}
+
+/// An exception raised by the host.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct HostException {
+    /// Exception class name, unknown names are raised as `RuntimeError`.
+    #[serde(rename = "type")]
+    exc_type: String,
+    /// Exception message.
+    #[serde(default)]
+    message: Option<String>,
+}
+
+impl From<CallResult> for ExternalResult {
+    fn from(result: CallResult) -> Self {
+        match result {
+            CallResult::Return(value) => Self::Return(value),
+            CallResult::Exception(HostException { exc_type, message }) => {
+                let monty_type = exc_type.parse().unwrap_or(ExcType::RuntimeError);
+                let host_error = HostError::new(exc_type, message.clone());
+                Self::Error(MontyException::new(monty_type, message).with_host_error(host_error))
+            }
+        }
+    }
+}
+
+/// A Monty exception, as returned to the host.
+#[derive(Debug, Serialize)]
+pub struct ErrorJson {
+    /// Exception class name, e.g. `"ValueError"`.
+    #[serde(rename = "type")]
+    exc_type: String,
+    /// Exception message, if any.
+    message: Option<String>,
+    /// The full formatted traceback, as printed by CPython.
+    traceback: String,
+}
+
+impl From<&MontyException> for ErrorJson {
+    fn from(exc: &MontyException) -> Self {
+        Self {
+            exc_type: exc.exc_type().to_string(),
+            message: exc.message().map(str::to_owned),
+            traceback: exc.to_string(),
+        }
+    }
+}
+
+/// One step of execution, returned by `monty_progress_json` and passed to run callbacks.
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Event<'a> {
+    /// Execution finished with a value.
+    Complete { output: &'a MontyObject, stdout: &'a str },
+    /// Execution raised an exception.
+    Error { error: ErrorJson, stdout: &'a str },
+    /// Execution is paused at an external function call.
+    FunctionCall {
+        function_name: &'a str,
+        args: &'a [MontyObject],
+        kwargs: &'a [(MontyObject, MontyObject)],
+        call_id: u32,
+        method_call: bool,
+        stdout: &'a str,
+    },
+    /// Execution is paused at an OS call, e.g. `Path.read_text`.
+    OsCall {
+        function: String,
+        args: &'a [MontyObject],
+        kwargs: &'a [(MontyObject, MontyObject)],
+        call_id: u32,
+        stdout: &'a str,
+    },
+    /// Execution is paused at `monty_yield(value)`, streaming a partial result.
+    Yield { value: &'a MontyObject, stdout: &'a str },
+}
+
+impl<'a> Event<'a> {
+    /// Describes the state of execution after a start or resume.
+    pub fn from_progress(progress: &'a Result<RunProgress<LimitedTracker>, MontyException>, stdout: &'a str) -> Self {
+        match progress {
+            Ok(RunProgress::Complete { value: output, .. }) => Self::Complete { output, stdout },
+            Ok(RunProgress::FunctionCall {
+                function_name,
+                args,
+                kwargs,
+                call_id,
+                method_call,
+                ..
+            }) => Self::FunctionCall {
+                function_name,
+                args,
+                kwargs,
+                call_id: *call_id,
+                method_call: *method_call,
+                stdout,
+            },
+            Ok(RunProgress::OsCall {
+                function,
+                args,
+                kwargs,
+                call_id,
+                ..
+            }) => Self::OsCall {
+                function: function.to_string(),
+                args,
+                kwargs,
+                call_id: *call_id,
+                stdout,
+            },
+            Ok(RunProgress::Yield { value, .. }) => Self::Yield { value, stdout },
+            Ok(RunProgress::ResolveFutures(_) | RunProgress::FunctionCallBatch { .. }) => {
+                Self::error(&futures_unsupported(), stdout)
+            }
+            Ok(RunProgress::Checkpoint(_)) => Self::error(&checkpoints_unsupported(), stdout),
+            Err(exc) => Self::error(exc, stdout),
+        }
+    }
+
+    /// An error event for `exc`.
+    pub fn error(exc: &MontyException, stdout: &'a str) -> Self {
+        Self::Error {
+            error: exc.into(),
+            stdout,
+        }
+    }
+
+    /// Encodes the event as JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("events always serialize")
+    }
+}
+
+/// Error for the `ResolveFutures` state, which the C API can't produce since calls are resolved synchronously.
+pub fn futures_unsupported() -> MontyException {
+    MontyException::new(
+        ExcType::RuntimeError,
+        Some("async futures are not supported by the C API".to_owned()),
+    )
+}
+
+/// Error for the `Checkpoint` state, which the C API can't produce since its limits don't enable checkpoints.
+pub fn checkpoints_unsupported() -> MontyException {
+    MontyException::new(
+        ExcType::RuntimeError,
+        Some("checkpoints are not supported by the C API".to_owned()),
+    )
+}
+
+/// Parses a JSON argument, reporting failures as a `ValueError` naming the argument.
+pub fn parse<T: for<'de> Deserialize<'de>>(json: &str, what: &str) -> Result<T, MontyException> {
+    serde_json::from_str(json)
+        .map_err(|err| MontyException::new(ExcType::ValueError, Some(format!("invalid {what} JSON: {err}"))))
+}
@@ -0,0 +1,377 @@
+//! C ABI for embedding Monty in languages without Python or Node bindings, e.g. Go, Java or .NET.
+//!
+//! The API is handle based, with all values exchanged as NUL-terminated UTF-8 JSON strings,
+//! see `include/monty.h` for the C declarations and [`json`] for the encoding.
+//!
+//! - [`monty_program_new`] parses code into a [`MontyProgram`], freed with [`monty_program_free`].
+//! - [`monty_program_run`] runs a program to completion, invoking a callback for each external
+//!   function or OS call.
+//! - [`monty_program_start`] and [`monty_progress_resume`] step through the same suspensions
+//!   one at a time, with [`monty_progress_json`] describing each [`MontyProgress`].
+//!
+//! Strings returned by the API are owned by the caller and must be freed with
+//! [`monty_string_free`]. Panics abort the process rather than unwinding into the host.
+
+mod json;
+
+use std::{
+    ffi::{CStr, CString, c_char, c_void},
+    mem, ptr,
+};
+
+use monty::{
+    ExcType, ExternalResult, LimitedTracker, MontyException, MontyObject, MontyRun, PrintWriter, RunProgress, Snapshot,
+};
+
+use crate::json::{CallResult, ErrorJson, Event, Limits, parse};
+
+/// Callback invoked by [`monty_program_run`] when execution pauses at an external call.
+///
+/// Receives `user_data` and the JSON event describing the call, which is only valid for the duration
+/// of the callback. Returns the result JSON, see [`monty_progress_resume`]; it's copied before the
+/// callback is invoked again and ownership stays with the host, `NULL` aborts the run with an error.
+pub type MontyCallback =
+    Option<unsafe extern "C" fn(user_data: *mut c_void, event_json: *const c_char) -> *const c_char>;
+
+/// Parsed and compiled code, which can be run any number of times.
+#[derive(Debug)]
+pub struct MontyProgram {
+    runner: MontyRun,
+}
+
+/// A paused, completed or failed execution.
+#[derive(Debug)]
+pub struct MontyProgress {
+    /// JSON describing this step, see [`Event`].
+    event_json: CString,
+    /// The state to resume, `None` once execution completed or failed.
+    snapshot: Option<Snapshot<LimitedTracker>>,
+}
+
+impl MontyProgress {
+    /// Builds the handle for the result of a start or resume.
+    fn new(progress: Result<RunProgress<LimitedTracker>, MontyException>, stdout: &str) -> Self {
+        let event_json = to_c_string(Event::from_progress(&progress, stdout).to_json());
+        let snapshot = match progress {
+            Ok(
+                RunProgress::FunctionCall { state, .. }
+                | RunProgress::OsCall { state, .. }
+                | RunProgress::Yield { state, .. },
+            ) => Some(state),
+            _ => None,
+        };
+        Self { event_json, snapshot }
+    }
+}
+
+/// Returns the version of Monty as a static string, which must not be freed.
+#[unsafe(no_mangle)]
+pub extern "C" fn monty_version() -> *const c_char {
+    concat!(env!("CARGO_PKG_VERSION"), "\0").as_ptr().cast()
+}
+
+/// Parses and compiles `code`.
+///
+/// `input_names_json` and `external_functions_json` are JSON arrays of names, or `NULL` for none.
+/// Returns `NULL` on failure, setting `*error_json` (if `error_json` isn't `NULL`) to the error JSON,
+/// e.g. `{"type": "SyntaxError", "message": "...", "traceback": "..."}`.
+///
+/// # Safety
+/// String arguments must be `NULL` or valid NUL-terminated strings, `code` and `script_name` must
+/// not be `NULL`, and `error_json` must be `NULL` or valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn monty_program_new(
+    code: *const c_char,
+    script_name: *const c_char,
+    input_names_json: *const c_char,
+    external_functions_json: *const c_char,
+    error_json: *mut *mut c_char,
+) -> *mut MontyProgram {
+    // SAFETY: the caller guarantees the string arguments are valid or `NULL`
+    let result = unsafe { program_new(code, script_name, input_names_json, external_functions_json) };
+    match result {
+        Ok(program) => Box::into_raw(Box::new(program)),
+        Err(exc) => {
+            // SAFETY: the caller guarantees `error_json` is `NULL` or valid for writes
+            unsafe { set_error(error_json, &exc) };
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Frees a program, `NULL` is ignored.
+///
+/// # Safety
+/// `program` must be `NULL` or a pointer returned by [`monty_program_new`] that hasn't been freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn monty_program_free(program: *mut MontyProgram) {
+    if !program.is_null() {
+        // SAFETY: the caller guarantees `program` came from `Box::into_raw` and hasn't been freed
+        drop(unsafe { Box::from_raw(program) });
+    }
+}
+
+/// Runs a program to completion, calling `callback` for each external function or OS call.
+///
+/// `inputs_json` is a JSON array of Monty values in the order of the input names, `limits_json` a
+/// JSON object of resource limits, either may be `NULL`. Returns the final event JSON, either
+/// `{"kind": "complete", ...}` or `{"kind": "error", ...}`, which must be freed with
+/// [`monty_string_free`].
+///
+/// # Safety
+/// `program` must be a valid program, string arguments must be `NULL` or valid NUL-terminated
+/// strings, and `callback` must be safe to call with `user_data`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn monty_program_run(
+    program: *const MontyProgram,
+    inputs_json: *const c_char,
+    limits_json: *const c_char,
+    callback: MontyCallback,
+    user_data: *mut c_void,
+) -> *mut c_char {
+    let mut stdout = PrintWriter::Collect(String::new());
+    // SAFETY: the caller guarantees `program` and the string arguments are valid
+    let mut progress = unsafe { program_start(program, inputs_json, limits_json, &mut stdout) };
+    loop {
+        let output = take_output(&mut stdout);
+        let event_json = to_c_string(Event::from_progress(&progress, &output).to_json());
+        let state = match progress {
+            Ok(
+                RunProgress::FunctionCall { state, .. }
+                | RunProgress::OsCall { state, .. }
+                | RunProgress::Yield { state, .. },
+            ) => state,
+            _ => return event_json.into_raw(),
+        };
+        // SAFETY: the caller guarantees `callback` is safe to call with `user_data`
+        progress = match unsafe { call_callback(callback, user_data, &event_json) } {
+            Ok(result) => state.run(result, &mut stdout),
+            Err(exc) => Err(exc),
+        };
+    }
+}
+
+/// Starts running a program, returning at the first external call or once execution finishes.
+///
+/// Arguments are as for [`monty_program_run`]. Never returns `NULL`, failures are reported by the
+/// progress' event JSON. The progress must be freed with [`monty_progress_free`] or consumed by
+/// [`monty_progress_resume`].
+///
+/// # Safety
+/// `program` must be a valid program and string arguments must be `NULL` or valid NUL-terminated
+/// strings.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn monty_program_start(
+    program: *const MontyProgram,
+    inputs_json: *const c_char,
+    limits_json: *const c_char,
+) -> *mut MontyProgress {
+    let mut stdout = PrintWriter::Collect(String::new());
+    // SAFETY: the caller guarantees `program` and the string arguments are valid
+    let progress = unsafe { program_start(program, inputs_json, limits_json, &mut stdout) };
+    let output = take_output(&mut stdout);
+    Box::into_raw(Box::new(MontyProgress::new(progress, &output)))
+}
+
+/// Returns the event JSON describing a progress: `complete`, `error`, `function_call`, `os_call` or
+/// `yield`.
+///
+/// The string is owned by the progress and valid until it's freed or resumed.
+///
+/// # Safety
+/// `progress` must be a valid progress.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn monty_progress_json(progress: *const MontyProgress) -> *const c_char {
+    // SAFETY: the caller guarantees `progress` is valid
+    unsafe { &*progress }.event_json.as_ptr()
+}
+
+/// Resumes a paused progress with the result of its call, consuming it.
+///
+/// `result_json` is `{"return": <value>}` or `{"exception": {"type": "ValueError", "message": "..."}}`.
+/// Returns the next progress; resuming a completed or failed progress returns an error progress.
+///
+/// # Safety
+/// `progress` must be a valid progress, which mustn't be used again, and `result_json` a valid
+/// NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn monty_progress_resume(
+    progress: *mut MontyProgress,
+    result_json: *const c_char,
+) -> *mut MontyProgress {
+    // SAFETY: the caller guarantees `progress` came from `Box::into_raw` and hands over ownership
+    let progress = unsafe { Box::from_raw(progress) };
+    let mut stdout = PrintWriter::Collect(String::new());
+    let next = match progress.snapshot {
+        Some(snapshot) => {
+            // SAFETY: the caller guarantees `result_json` is valid
+            match unsafe { read_json::<CallResult>(result_json, "result") } {
+                Ok(result) => snapshot.run(ExternalResult::from(result), &mut stdout),
+                Err(exc) => Err(exc),
+            }
+        }
+        None => Err(MontyException::new(
+            ExcType::RuntimeError,
+            Some("progress has already finished".to_owned()),
+        )),
+    };
+    let output = take_output(&mut stdout);
+    Box::into_raw(Box::new(MontyProgress::new(next, &output)))
+}
+
+/// Frees a progress, `NULL` is ignored.
+///
+/// # Safety
+/// `progress` must be `NULL` or a valid progress that hasn't been freed or resumed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn monty_progress_free(progress: *mut MontyProgress) {
+    if !progress.is_null() {
+        // SAFETY: the caller guarantees `progress` came from `Box::into_raw` and hasn't been freed
+        drop(unsafe { Box::from_raw(progress) });
+    }
+}
+
+/// Frees a string returned by the API, `NULL` is ignored.
+///
+/// # Safety
+/// `s` must be `NULL` or a string returned by this API that hasn't been freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn monty_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        // SAFETY: the caller guarantees `s` came from `CString::into_raw` and hasn't been freed
+        drop(unsafe { CString::from_raw(s) });
+    }
+}
+
+/// Parses the arguments of [`monty_program_new`] and compiles the code.
+///
+/// # Safety
+/// See [`monty_program_new`].
+unsafe fn program_new(
+    code: *const c_char,
+    script_name: *const c_char,
+    input_names_json: *const c_char,
+    external_functions_json: *const c_char,
+) -> Result<MontyProgram, MontyException> {
+    // SAFETY: the caller guarantees the string arguments are valid or `NULL`
+    let (code, script_name, input_names, external_functions) = unsafe {
+        (
+            read_str(code, "code")?.ok_or_else(|| null_error("code"))?,
+            read_str(script_name, "script_name")?.ok_or_else(|| null_error("script_name"))?,
+            read_json::<Option<Vec<String>>>(input_names_json, "input names")?,
+            read_json::<Option<Vec<String>>>(external_functions_json, "external functions")?,
+        )
+    };
+    let runner = MontyRun::new(
+        code.to_owned(),
+        script_name,
+        input_names.unwrap_or_default(),
+        external_functions.unwrap_or_default(),
+    )?;
+    Ok(MontyProgram { runner })
+}
+
+/// Parses the arguments of [`monty_program_start`] and starts execution.
+///
+/// # Safety
+/// See [`monty_program_start`].
+unsafe fn program_start(
+    program: *const MontyProgram,
+    inputs_json: *const c_char,
+    limits_json: *const c_char,
+    stdout: &mut PrintWriter<'_>,
+) -> Result<RunProgress<LimitedTracker>, MontyException> {
+    // SAFETY: the caller guarantees `program` and the string arguments are valid
+    let (program, inputs, limits) = unsafe {
+        (
+            &*program,
+            read_json::<Option<Vec<MontyObject>>>(inputs_json, "inputs")?,
+            read_json::<Option<Limits>>(limits_json, "limits")?,
+        )
+    };
+    let tracker = LimitedTracker::new(limits.unwrap_or_default().into_resource_limits()?);
+    program
+        .runner
+        .clone()
+        .start(inputs.unwrap_or_default(), tracker, stdout)
+}
+
+/// Invokes the run callback and parses the result it returns.
+///
+/// # Safety
+/// `callback` must be safe to call with `user_data`.
+unsafe fn call_callback(
+    callback: MontyCallback,
+    user_data: *mut c_void,
+    event_json: &CStr,
+) -> Result<ExternalResult, MontyException> {
+    let Some(callback) = callback else {
+        return Err(null_error("callback"));
+    };
+    // SAFETY: the caller guarantees `callback` is safe to call with `user_data`
+    let result_json = unsafe { callback(user_data, event_json.as_ptr()) };
+    if result_json.is_null() {
+        return Err(MontyException::new(
+            ExcType::RuntimeError,
+            Some("callback returned NULL".to_owned()),
+        ));
+    }
+    // SAFETY: the callback returns a valid NUL-terminated string
+    let result = unsafe { read_json::<CallResult>(result_json, "result") }?;
+    Ok(result.into())
+}
+
+/// Reads an optional string argument.
+///
+/// # Safety
+/// `s` must be `NULL` or a valid NUL-terminated string which outlives `'a`.
+unsafe fn read_str<'a>(s: *const c_char, what: &str) -> Result<Option<&'a str>, MontyException> {
+    if s.is_null() {
+        return Ok(None);
+    }
+    // SAFETY: the caller guarantees `s` is a valid NUL-terminated string
+    let s = unsafe { CStr::from_ptr(s) };
+    s.to_str()
+        .map(Some)
+        .map_err(|_| MontyException::new(ExcType::ValueError, Some(format!("{what} is not valid UTF-8"))))
+}
+
+/// Reads and parses a JSON argument, `NULL` is parsed as JSON `null`.
+///
+/// # Safety
+/// `s` must be `NULL` or a valid NUL-terminated string.
+unsafe fn read_json<T: for<'de> serde::Deserialize<'de>>(s: *const c_char, what: &str) -> Result<T, MontyException> {
+    // SAFETY: the caller guarantees `s` is valid or `NULL`
+    let json = unsafe { read_str(s, what) }?.unwrap_or("null");
+    parse(json, what)
+}
+
+/// Writes the error JSON for `exc` to `error_json`, if it isn't `NULL`.
+///
+/// # Safety
+/// `error_json` must be `NULL` or valid for writes.
+unsafe fn set_error(error_json: *mut *mut c_char, exc: &MontyException) {
+    if !error_json.is_null() {
+        let json = serde_json::to_string(&ErrorJson::from(exc)).expect("errors always serialize");
+        // SAFETY: the caller guarantees `error_json` is valid for writes
+        unsafe { *error_json = to_c_string(json).into_raw() };
+    }
+}
+
+/// Error for a required pointer argument that's `NULL`.
+fn null_error(what: &str) -> MontyException {
+    MontyException::new(ExcType::ValueError, Some(format!("{what} must not be NULL")))
+}
+
+/// Takes the output printed since the last call.
+fn take_output(stdout: &mut PrintWriter<'_>) -> String {
+    match stdout {
+        PrintWriter::Collect(output) => mem::take(output),
+        _ => String::new(),
+    }
+}
+
+/// Converts JSON to a C string, JSON never contains NUL bytes since they're escaped.
+fn to_c_string(json: String) -> CString {
+    CString::new(json).expect("JSON contains no NUL bytes")
+}
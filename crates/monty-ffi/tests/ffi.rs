@@ -0,0 +1,217 @@
+//! Tests for the C API, called from Rust through the same `extern "C"` functions.
+// every unsafe block here just calls the C API with arguments built in the test
+#![expect(clippy::undocumented_unsafe_blocks)]
+
+use std::{
+    ffi::{CStr, CString, c_char, c_void},
+    ptr,
+};
+
+use monty_ffi::{
+    MontyProgram, MontyProgress, monty_program_free, monty_program_new, monty_program_run, monty_program_start,
+    monty_progress_free, monty_progress_json, monty_progress_resume, monty_string_free, monty_version,
+};
+
+/// Compiles a program, panicking with the error JSON on failure.
+fn program(code: &str, inputs: &str, external_functions: &str) -> *mut MontyProgram {
+    let code = CString::new(code).unwrap();
+    let inputs = CString::new(inputs).unwrap();
+    let external_functions = CString::new(external_functions).unwrap();
+    let mut error = ptr::null_mut();
+    let program = unsafe {
+        monty_program_new(
+            code.as_ptr(),
+            c"main.py".as_ptr(),
+            inputs.as_ptr(),
+            external_functions.as_ptr(),
+            &raw mut error,
+        )
+    };
+    assert!(!program.is_null(), "{}", unsafe { take_string(error) });
+    program
+}
+
+/// Copies and frees a string returned by the API.
+unsafe fn take_string(s: *mut c_char) -> String {
+    let owned = unsafe { CStr::from_ptr(s) }.to_str().unwrap().to_owned();
+    unsafe { monty_string_free(s) };
+    owned
+}
+
+/// Parses the event JSON of a progress.
+fn event(progress: *const MontyProgress) -> serde_json::Value {
+    let json = unsafe { CStr::from_ptr(monty_progress_json(progress)) };
+    serde_json::from_str(json.to_str().unwrap()).unwrap()
+}
+
+#[test]
+fn version() {
+    let version = unsafe { CStr::from_ptr(monty_version()) };
+    assert_eq!(version.to_str().unwrap(), env!("CARGO_PKG_VERSION"));
+}
+
+#[test]
+fn run_to_completion() {
+    let program = program("print('hi')\nx * 2", r#"["x"]"#, "[]");
+    let result = unsafe { monty_program_run(program, c"[{\"Int\": 21}]".as_ptr(), ptr::null(), None, ptr::null_mut()) };
+    assert_eq!(
+        unsafe { take_string(result) },
+        r#"{"kind":"complete","output":{"Int":42},"stdout":"hi\n"}"#
+    );
+    unsafe { monty_program_free(program) };
+}
+
+#[test]
+fn syntax_error() {
+    let mut error = ptr::null_mut();
+    let program = unsafe {
+        monty_program_new(
+            c"1 +".as_ptr(),
+            c"main.py".as_ptr(),
+            ptr::null(),
+            ptr::null(),
+            &raw mut error,
+        )
+    };
+    assert!(program.is_null());
+    let error: serde_json::Value = serde_json::from_str(&unsafe { take_string(error) }).unwrap();
+    assert_eq!(error["type"], "SyntaxError");
+}
+
+#[test]
+fn runtime_error() {
+    let program = program("1 / 0", "[]", "[]");
+    let result = unsafe { monty_program_run(program, ptr::null(), ptr::null(), None, ptr::null_mut()) };
+    let result: serde_json::Value = serde_json::from_str(&unsafe { take_string(result) }).unwrap();
+    assert_eq!(result["kind"], "error");
+    assert_eq!(result["error"]["type"], "ZeroDivisionError");
+    assert_eq!(result["error"]["message"], "division by zero");
+    unsafe { monty_program_free(program) };
+}
+
+#[test]
+fn limits() {
+    let program = program("x = []\nwhile True:\n    x.append(1)", "[]", "[]");
+    let result = unsafe {
+        monty_program_run(
+            program,
+            ptr::null(),
+            c"{\"max_allocations\": 100}".as_ptr(),
+            None,
+            ptr::null_mut(),
+        )
+    };
+    let result: serde_json::Value = serde_json::from_str(&unsafe { take_string(result) }).unwrap();
+    assert_eq!(result["error"]["type"], "MemoryError");
+    unsafe { monty_program_free(program) };
+}
+
+#[test]
+fn invalid_max_duration() {
+    let program = program("1", "[]", "[]");
+    for limits in [c"{\"max_duration_secs\": -1}", c"{\"max_duration_secs\": 1e300}"] {
+        let result = unsafe { monty_program_run(program, ptr::null(), limits.as_ptr(), None, ptr::null_mut()) };
+        let result: serde_json::Value = serde_json::from_str(&unsafe { take_string(result) }).unwrap();
+        assert_eq!(result["kind"], "error");
+        assert_eq!(result["error"]["type"], "ValueError");
+        assert!(
+            result["error"]["message"]
+                .as_str()
+                .unwrap()
+                .starts_with("max_duration_secs must be a non-negative number of seconds")
+        );
+    }
+    unsafe { monty_program_free(program) };
+}
+
+#[test]
+fn invalid_inputs_json() {
+    let program = program("x", r#"["x"]"#, "[]");
+    let result = unsafe { monty_program_run(program, c"[1".as_ptr(), ptr::null(), None, ptr::null_mut()) };
+    let result: serde_json::Value = serde_json::from_str(&unsafe { take_string(result) }).unwrap();
+    assert_eq!(result["error"]["type"], "ValueError");
+    assert!(
+        result["error"]["message"]
+            .as_str()
+            .unwrap()
+            .starts_with("invalid inputs JSON")
+    );
+    unsafe { monty_program_free(program) };
+}
+
+/// Callback doubling the first argument, recording every event it receives in `user_data`.
+unsafe extern "C" fn double(user_data: *mut c_void, event_json: *const c_char) -> *const c_char {
+    let events = unsafe { &mut *user_data.cast::<Vec<serde_json::Value>>() };
+    let event: serde_json::Value =
+        serde_json::from_str(unsafe { CStr::from_ptr(event_json) }.to_str().unwrap()).unwrap();
+    let result = if event["args"][0]["Int"] == 3 {
+        c"{\"exception\": {\"type\": \"ValueError\", \"message\": \"no threes\"}}"
+    } else {
+        c"{\"return\": {\"Int\": 10}}"
+    };
+    events.push(event);
+    result.as_ptr()
+}
+
+#[test]
+fn run_with_callback() {
+    let code = "
+results = [double(1)]
+try:
+    double(3)
+except ValueError as e:
+    results.append(str(e))
+results
+";
+    let program = program(code, "[]", r#"["double"]"#);
+    let mut events: Vec<serde_json::Value> = Vec::new();
+    let result = unsafe {
+        monty_program_run(
+            program,
+            ptr::null(),
+            ptr::null(),
+            Some(double),
+            (&raw mut events).cast(),
+        )
+    };
+    assert_eq!(
+        unsafe { take_string(result) },
+        r#"{"kind":"complete","output":{"List":[{"Int":10},{"String":"no threes"}]},"stdout":""}"#
+    );
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0]["kind"], "function_call");
+    assert_eq!(events[0]["function_name"], "double");
+    assert_eq!(events[0]["args"], serde_json::json!([{"Int": 1}]));
+    unsafe { monty_program_free(program) };
+}
+
+#[test]
+fn step_through_suspensions() {
+    let program = program(
+        "from pathlib import Path\nPath('/a.txt').read_text() + fetch(x=1)",
+        "[]",
+        r#"["fetch"]"#,
+    );
+    let progress = unsafe { monty_program_start(program, ptr::null(), ptr::null()) };
+    let first = event(progress);
+    assert_eq!(first["kind"], "os_call");
+    assert_eq!(first["function"], "Path.read_text");
+    assert_eq!(first["args"], serde_json::json!([{"Path": "/a.txt"}]));
+
+    let progress = unsafe { monty_progress_resume(progress, c"{\"return\": {\"String\": \"a\"}}".as_ptr()) };
+    let second = event(progress);
+    assert_eq!(second["kind"], "function_call");
+    assert_eq!(second["kwargs"], serde_json::json!([[{"String": "x"}, {"Int": 1}]]));
+
+    let progress = unsafe { monty_progress_resume(progress, c"{\"return\": {\"String\": \"b\"}}".as_ptr()) };
+    assert_eq!(
+        event(progress),
+        serde_json::json!({"kind": "complete", "output": {"String": "ab"}, "stdout": ""})
+    );
+
+    // resuming a finished progress reports an error rather than crashing
+    let progress = unsafe { monty_progress_resume(progress, c"{\"return\": \"None\"}".as_ptr()) };
+    assert_eq!(event(progress)["error"]["message"], "progress has already finished");
+    unsafe { monty_progress_free(progress) };
+    unsafe { monty_program_free(program) };
+}
@@ -87,6 +87,36 @@ test('runMontyAsync with args and kwargs', async (t) => {
   t.is(result, 'test: 3')
 })
 
+test('runMontyAsync without kwargs passes only positional args', async (t) => {
+  const m = new Monty('process(1, 2)', { externalFunctions: ['process'] })
+
+  const result = await runMontyAsync(m, {
+    externalFunctions: {
+      process: async (...args: unknown[]) => {
+        t.deepEqual(args, [1, 2])
+        return 'ok'
+      },
+    },
+  })
+
+  t.is(result, 'ok')
+})
+
+test('runMontyAsync with kwargs only', async (t) => {
+  const m = new Monty('process(items=[1, 2], flag=None)', { externalFunctions: ['process'] })
+
+  const result = await runMontyAsync(m, {
+    externalFunctions: {
+      process: async (...args: unknown[]) => {
+        t.deepEqual(args, [{ items: [1, 2], flag: null }])
+        return 'ok'
+      },
+    },
+  })
+
+  t.is(result, 'ok')
+})
+
 // =============================================================================
 // Error handling tests
 // =============================================================================
@@ -132,6 +162,29 @@ test('runMontyAsync async function throws exception', async (t) => {
   t.true(error instanceof MontyRuntimeError)
 })
 
+test('runMontyAsync keeps the original host error', async (t) => {
+  const m = new Monty('fail_async()', { externalFunctions: ['fail_async'] })
+
+  class ValueError extends Error {
+    override name = 'ValueError'
+  }
+  const original = new ValueError('async error')
+
+  const error = await t.throwsAsync(
+    runMontyAsync(m, {
+      externalFunctions: {
+        fail_async: async () => {
+          throw original
+        },
+      },
+    }),
+  )
+
+  t.true(error instanceof MontyRuntimeError)
+  t.is((error as MontyRuntimeError).hostError, original)
+  t.is((error as MontyRuntimeError).hostErrorInfo?.stack, original.stack)
+})
+
 test('runMontyAsync exception caught in try/except', async (t) => {
   const m = new Monty(
     `
@@ -296,3 +349,96 @@ factorial(5)
 
   t.is(result, 120)
 })
+
+// =============================================================================
+// Call policy tests
+// =============================================================================
+
+test('runMontyAsync call policy timeout raises ExternalCallTimeout', async (t) => {
+  const m = new Monty(
+    `
+try:
+    slow()
+except ExternalCallTimeout as e:
+    result = f'timed out: {e}'
+result
+`,
+    { externalFunctions: ['slow'] },
+  )
+
+  const result = await runMontyAsync(m, {
+    externalFunctions: {
+      slow: () => new Promise((resolve) => setTimeout(() => resolve('done'), 1000)),
+    },
+    callPolicies: { slow: { timeoutSecs: 0.05 } },
+  })
+
+  t.is(result, "timed out: external function 'slow' timed out after 0.05s")
+})
+
+test('runMontyAsync call policy timeout is a TimeoutError', async (t) => {
+  const m = new Monty('slow()', { externalFunctions: ['slow'] })
+
+  const error = await t.throwsAsync(
+    runMontyAsync(m, {
+      externalFunctions: {
+        slow: () => new Promise((resolve) => setTimeout(resolve, 1000)),
+      },
+      callPolicies: { slow: { timeoutSecs: 0.01, retries: 1 } },
+    }),
+    { instanceOf: MontyRuntimeError },
+  )
+  t.is(error.exception.typeName, 'ExternalCallTimeout')
+  t.is(error.message, "ExternalCallTimeout: external function 'slow' timed out after 0.01s on all 2 attempts")
+})
+
+test('runMontyAsync call policy retries timed out attempts', async (t) => {
+  const m = new Monty('flaky()', { externalFunctions: ['flaky'] })
+  let attempts = 0
+
+  const result = await runMontyAsync(m, {
+    externalFunctions: {
+      flaky: () => {
+        attempts++
+        const delay = attempts < 3 ? 1000 : 0
+        return new Promise((resolve) => setTimeout(() => resolve(attempts), delay))
+      },
+    },
+    callPolicies: { flaky: { timeoutSecs: 0.05, retries: 3, backoffSecs: 0.01 } },
+  })
+
+  t.is(result, 3)
+  t.is(attempts, 3)
+})
+
+test('runMontyAsync call policy retryOn', async (t) => {
+  const m = new Monty(
+    `
+try:
+    flaky()
+except ValueError as e:
+    result = f'caught: {e}'
+result
+`,
+    { externalFunctions: ['flaky'] },
+  )
+  let attempts = 0
+
+  const result = await runMontyAsync(m, {
+    externalFunctions: {
+      flaky: async () => {
+        attempts++
+        if (attempts < 3) {
+          throw new Error('try again')
+        }
+        const err = new Error('bad value')
+        err.name = 'ValueError'
+        throw err
+      },
+    },
+    callPolicies: { flaky: { retries: 5, retryOn: (error) => (error as Error).message === 'try again' } },
+  })
+
+  t.is(result, 'caught: bad value')
+  t.is(attempts, 3)
+})
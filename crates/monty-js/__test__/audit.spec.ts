@@ -0,0 +1,53 @@
+import test from 'ava'
+
+import { Monty, MontyRuntimeError } from '../wrapper'
+
+type AuditEvent = Record<string, unknown>
+
+/** Removes the timestamp and args digest, which vary between runs. */
+function stripVolatile(events: AuditEvent[]): AuditEvent[] {
+  return events.map(({ timestamp, argsDigest, ...rest }) => {
+    if (typeof timestamp !== 'number') {
+      throw new Error('timestamp should be a number')
+    }
+    if (argsDigest !== undefined && (argsDigest as string).length !== 16) {
+      throw new Error('argsDigest should be a 16 character hex string')
+    }
+    return rest
+  })
+}
+
+test('audit external calls and imports', (t) => {
+  const events: AuditEvent[] = []
+  const m = new Monty('import sys\nfetch(1)', { externalFunctions: ['fetch'] })
+  const result = m.run({
+    externalFunctions: { fetch: (x: number) => x + 1 },
+    auditCallback: (event: AuditEvent) => events.push(event),
+  })
+  t.is(result, 2)
+  t.deepEqual(stripVolatile(events), [
+    { event: 'import', module: 'sys', allowed: true },
+    { event: 'external_call', function: 'fetch', methodCall: false },
+  ])
+})
+
+test('audit denied import', (t) => {
+  const events: AuditEvent[] = []
+  const m = new Monty('import socket')
+  t.throws(() => m.run({ auditCallback: (event: AuditEvent) => events.push(event) }), {
+    instanceOf: MontyRuntimeError,
+  })
+  t.deepEqual(stripVolatile(events), [{ event: 'import', module: 'socket', allowed: false }])
+})
+
+test('audit resource limit', (t) => {
+  const events: AuditEvent[] = []
+  const m = new Monty('x = []\nwhile True:\n    x.append(1)')
+  t.throws(
+    () => m.run({ limits: { maxAllocations: 10 }, auditCallback: (event: AuditEvent) => events.push(event) }),
+    { instanceOf: MontyRuntimeError },
+  )
+  t.is(events.length, 1)
+  t.is(events[0].event, 'resource_limit')
+  t.is(events[0].excType, 'MemoryError')
+})
@@ -178,6 +178,34 @@ test('runtime display', (t) => {
   t.true(traceback.includes('ValueError: test message'))
 })
 
+test('display traceback options', (t) => {
+  const code = `def ratio(total, count):
+    return total // count
+
+ratio(3, 0)
+`
+  const m = new Monty(code)
+  const error = t.throws(() => m.run(), isRuntimeError)
+  t.is(
+    error.display('traceback', { style: 'short' }),
+    `Traceback (most recent call last):
+  File "main.py", line 4, in <module>
+    ratio(3, 0)
+  File "main.py", line 2, in ratio
+    return total // count
+ZeroDivisionError: integer division or modulo by zero`,
+  )
+  t.is(error.display('traceback', { style: 'line' }), 'main.py:2: ZeroDivisionError: integer division or modulo by zero')
+  t.is(error.display('traceback', { maxFrames: 0 }), 'ZeroDivisionError: integer division or modulo by zero')
+  t.is(
+    error.display('traceback', { style: 'line', internalFiles: ['main.py'] }),
+    'main.py:2: ZeroDivisionError: integer division or modulo by zero',
+  )
+  t.throws(() => error.display('traceback', { style: 'native' }), {
+    message: "Invalid traceback style: 'native'. Expected 'long', 'short', or 'line'",
+  })
+})
+
 test('str returns type msg', (t) => {
   const m = new Monty('raise ValueError("test message")')
   const error = t.throws(() => m.run(), isRuntimeError)
@@ -213,6 +241,32 @@ outer()
   t.true(display.includes('ValueError: error'))
 })
 
+test('structured fields', (t) => {
+  const code = `def foo():
+    raise ValueError('test')
+
+foo()
+`
+  const m = new Monty(code)
+  const error = t.throws(() => m.run(), isRuntimeError)
+  t.is(error.typeName, 'ValueError')
+  t.is(error.exception.message, 'test')
+  t.deepEqual(
+    error.frames.map((f) => [f.line, f.functionName, f.sourceLine]),
+    [
+      [4, '<module>', 'foo()'],
+      [2, 'foo', "    raise ValueError('test')"],
+    ],
+  )
+  t.deepEqual(error.frames, error.traceback())
+})
+
+test('syntax error structured fields', (t) => {
+  const error = t.throws(() => new Monty('def'), { instanceOf: MontySyntaxError })
+  t.is(error.typeName, 'SyntaxError')
+  t.deepEqual(error.frames, [])
+})
+
 // =============================================================================
 // MontyError base class tests
 // =============================================================================
@@ -218,6 +218,65 @@ test('external function exception type preserved', (t) => {
   t.true(error.message.includes('type error message'))
 })
 
+class CustomHostError extends Error {
+  constructor(message: string) {
+    super(message)
+    this.name = 'ValueError'
+  }
+}
+
+test('external function original error preserved', (t) => {
+  const m = new Monty('fail()', { externalFunctions: ['fail'] })
+  const original = new CustomHostError('from host')
+
+  const fail = () => {
+    throw original
+  }
+
+  const error = t.throws(() => m.run({ externalFunctions: { fail } }), isRuntimeError)
+  t.is(error.hostError, original)
+  t.is(error.hostErrorInfo?.name, 'ValueError')
+  t.is(error.hostErrorInfo?.message, 'from host')
+  t.is(error.hostErrorInfo?.stack, original.stack)
+})
+
+test('external function original error kept when re-raised in monty', (t) => {
+  const code = `
+try:
+    fail()
+except ValueError as e:
+    err = e
+raise err
+`
+  const m = new Monty(code, { externalFunctions: ['fail'] })
+  const original = new CustomHostError('from host')
+
+  const fail = () => {
+    throw original
+  }
+
+  const error = t.throws(() => m.run({ externalFunctions: { fail } }), isRuntimeError)
+  t.is(error.hostError, original)
+})
+
+test('external function original error not attached to new exception', (t) => {
+  const code = `
+try:
+    fail()
+except ValueError:
+    raise TypeError('replaced')
+`
+  const m = new Monty(code, { externalFunctions: ['fail'] })
+
+  const fail = () => {
+    throw new CustomHostError('from host')
+  }
+
+  const error = t.throws(() => m.run({ externalFunctions: { fail } }), isRuntimeError)
+  t.is(error.hostError, undefined)
+  t.is(error.hostErrorInfo, null)
+})
+
 // =============================================================================
 // Exception hierarchy tests
 // =============================================================================
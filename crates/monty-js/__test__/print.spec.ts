@@ -229,3 +229,15 @@ test('with snapshot dump load', (t) => {
   t.is((result as MontyComplete).output, null)
   t.is(output.join(''), '42\n')
 })
+
+test('stderr', (t) => {
+  const m = new Monty('import sys\nprint("out")\nprint("err", file=sys.stderr)')
+  const output: [string, string][] = []
+  m.run({ printCallback: (stream: string, text: string) => output.push([stream, text]) })
+  t.deepEqual(output, [
+    ['stdout', 'out'],
+    ['stdout', '\n'],
+    ['stderr', 'err'],
+    ['stderr', '\n'],
+  ])
+})
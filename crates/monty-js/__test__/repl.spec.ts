@@ -1,6 +1,6 @@
 import test from 'ava'
 
-import { MontyRepl } from '../wrapper'
+import { MontyRepl, MontyRuntimeError } from '../wrapper'
 
 test('create and feed preserve state without replay', (t) => {
   const repl = MontyRepl.create('counter = 0')
@@ -29,3 +29,13 @@ test('repl dump/load roundtrip', (t) => {
 
   t.is(loaded.feed('x + 1'), 42)
 })
+
+test('feed runs snippets in isolated namespaces', (t) => {
+  const repl = MontyRepl.create('x = 1')
+
+  t.is(repl.feed('x = 2', 'analysis'), null)
+  t.is(repl.feed('x'), 1)
+  t.is(repl.feed('x', 'analysis'), 2)
+
+  t.throws(() => repl.feed('x', 'other'), { instanceOf: MontyRuntimeError, message: "NameError: name 'x' is not defined" })
+})
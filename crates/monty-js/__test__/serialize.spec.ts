@@ -2,6 +2,7 @@ import test from 'ava'
 
 import { Monty, MontySnapshot, MontyComplete, type ResourceLimits } from '../wrapper'
 import { Buffer } from 'node:buffer'
+import { createHash } from 'node:crypto'
 
 // =============================================================================
 // Monty dump/load tests
@@ -18,6 +19,23 @@ test('monty dump load roundtrip', (t) => {
   t.is(m2.run({ inputs: { x: 41 } }), 42)
 })
 
+test('monty signed dump load', (t) => {
+  const m = new Monty('x + 1', { inputs: ['x'] })
+  const key = Buffer.from('secret')
+  const data = m.dump(key)
+  t.is(data.length, m.dump().length + 32)
+  t.is(Monty.load(data, key).run({ inputs: { x: 41 } }), 42)
+
+  t.throws(() => Monty.load(data, Buffer.from('other')), { message: /invalid signature/ })
+  t.throws(() => Monty.load(m.dump(), key), { message: /invalid signature/ })
+})
+
+test('monty code hash', (t) => {
+  const m = new Monty('x + 1', { inputs: ['x'] })
+  t.is(m.codeHash, createHash('sha256').update('x + 1').digest('hex'))
+  t.is(Monty.load(m.dump()).codeHash, m.codeHash)
+})
+
 test('monty dump load preserves script name', (t) => {
   const m = new Monty('1', { scriptName: 'custom.py' })
   const data = m.dump()
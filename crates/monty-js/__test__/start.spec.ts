@@ -37,6 +37,16 @@ test('start returns complete for various types', (t) => {
   }
 })
 
+test('complete has memory profile', (t) => {
+  const m = new Monty('items = [[i] for i in range(5)]\nlen(items)')
+  const result = m.start()
+  t.true(result instanceof MontyComplete)
+  const complete = result as MontyComplete
+  t.is(complete.output, 5)
+  const lists = complete.memoryProfile?.types.find((usage) => usage.typeName === 'list')
+  t.true(lists !== undefined && lists.count >= 6)
+})
+
 // =============================================================================
 // start() returns MontySnapshot tests
 // =============================================================================
@@ -89,6 +99,16 @@ test('start progress with mixed args kwargs', (t) => {
   t.deepEqual(snapshot.kwargs, { x: 'hello', y: true })
 })
 
+test('start progress with unpacked kwargs', (t) => {
+  const m = new Monty("opts = {'size': (1, 2), 'meta': {'k': 1}}\nfunc(**opts)", { externalFunctions: ['func'] })
+  const progress = m.start()
+  t.true(progress instanceof MontySnapshot)
+  const snapshot = progress as MontySnapshot
+  t.deepEqual(snapshot.args, [])
+  t.deepEqual(Object.keys(snapshot.kwargs), ['size', 'meta'])
+  t.deepEqual(snapshot.kwargs.meta, new Map([['k', 1]]))
+})
+
 // =============================================================================
 // resume() tests
 // =============================================================================
@@ -248,6 +268,39 @@ test('resume exception propagates uncaught', (t) => {
   t.true(error.message.includes('uncaught error'))
 })
 
+test('resume exception with notes', (t) => {
+  const m = new Monty('external_func()', { externalFunctions: ['external_func'] })
+  const snapshot = m.start() as MontySnapshot
+
+  const error = t.throws(
+    () => snapshot.resume({ exception: { type: 'ValueError', message: 'failed', notes: ['retry later'] } }),
+    { instanceOf: MontyRuntimeError },
+  )
+  t.deepEqual(error.notes, ['retry later'])
+  t.true(error.display('traceback').endsWith('ValueError: failed\nretry later'))
+})
+
+test('resume with a JS error', (t) => {
+  const m = new Monty('external_func()', { externalFunctions: ['external_func'] })
+
+  let snapshot = m.start() as MontySnapshot
+  let error = t.throws(() => snapshot.resume({ error: new TypeError('bad input') }), {
+    instanceOf: MontyRuntimeError,
+  })
+  t.is(error.exception.typeName, 'TypeError')
+  t.is(error.exception.message, 'bad input')
+
+  snapshot = m.start() as MontySnapshot
+  error = t.throws(() => snapshot.resume({ error: new Error('plain error') }), { instanceOf: MontyRuntimeError })
+  t.is(error.exception.typeName, 'RuntimeError')
+
+  snapshot = m.start() as MontySnapshot
+  t.throws(
+    () => snapshot.resume({ error: new Error('x'), exception: { type: 'ValueError', message: 'y' } }),
+    { message: 'resume() accepts either exception or error, not both' },
+  )
+})
+
 test('resume exception in nested try', (t) => {
   const code = `
 outer_caught = False
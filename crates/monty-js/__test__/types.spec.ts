@@ -87,6 +87,45 @@ test('bytes result', (t) => {
   t.deepEqual([...result], [104, 101, 108, 108, 111])
 })
 
+test('bytes from Uint8Array', (t) => {
+  const m = new Monty('x', { inputs: ['x'] })
+  const result = m.run({ inputs: { x: new Uint8Array([1, 2, 255]) } })
+  t.true(Buffer.isBuffer(result))
+  t.deepEqual([...result], [1, 2, 255])
+})
+
+test('bytes from Uint8Array view respects offset', (t) => {
+  const m = new Monty('x', { inputs: ['x'] })
+  const view = new Uint8Array([0, 1, 2, 3, 4]).subarray(1, 4)
+  t.deepEqual([...m.run({ inputs: { x: view } })], [1, 2, 3])
+})
+
+test('bytes from wider TypedArray', (t) => {
+  const m = new Monty('len(x)', { inputs: ['x'] })
+  t.is(m.run({ inputs: { x: new Uint16Array([1, 2, 3]) } }), 6)
+  t.is(m.run({ inputs: { x: new Float64Array(2) } }), 16)
+})
+
+test('bytes from TypedArray ignores redefined BYTES_PER_ELEMENT', (t) => {
+  const m = new Monty('x', { inputs: ['x'] })
+  const arr = new Uint8Array([1, 2, 3])
+  Object.defineProperty(arr, 'BYTES_PER_ELEMENT', { value: 1e6 })
+  t.deepEqual([...m.run({ inputs: { x: arr } })], [1, 2, 3])
+})
+
+test('bytes from ArrayBuffer and DataView', (t) => {
+  const m = new Monty('x', { inputs: ['x'] })
+  const buffer = new Uint8Array([10, 20, 30, 40]).buffer
+  t.deepEqual([...m.run({ inputs: { x: buffer } })], [10, 20, 30, 40])
+  t.deepEqual([...m.run({ inputs: { x: new DataView(buffer, 1, 2) } })], [20, 30])
+  t.deepEqual([...m.run({ inputs: { x: new ArrayBuffer(0) } })], [])
+})
+
+test('bytes from external function Uint8Array', (t) => {
+  const m = new Monty('data = read()\ndata[0] + len(data)', { externalFunctions: ['read'] })
+  t.is(m.run({ externalFunctions: { read: () => new Uint8Array([7, 8]) } }), 9)
+})
+
 // =============================================================================
 // List tests
 // =============================================================================
@@ -145,6 +184,28 @@ test('dict empty', (t) => {
   t.is(result.size, 0)
 })
 
+test('dict from Map input keeps key types', (t) => {
+  const m = new Monty('(x[1], x["a"], list(x.keys()))', { inputs: ['x'] })
+  const result = m.run({
+    inputs: {
+      x: new Map<unknown, unknown>([
+        [1, 'one'],
+        ['a', [1, 2]],
+      ]),
+    },
+  })
+  t.deepEqual([...result], ['one', [1, 2], [1, 'a']])
+})
+
+test('dict Map round trip', (t) => {
+  const m = new Monty('x', { inputs: ['x'] })
+  const input = new Map<unknown, unknown>([
+    [2, true],
+    ['b', null],
+  ])
+  t.deepEqual(m.run({ inputs: { x: input } }), input)
+})
+
 // =============================================================================
 // Set tests
 // =============================================================================
@@ -161,6 +222,11 @@ test('set empty', (t) => {
   t.deepEqual(result, new Set())
 })
 
+test('set from Set input', (t) => {
+  const m = new Monty('(type(x).__name__, 2 in x, len(x))', { inputs: ['x'] })
+  t.deepEqual([...m.run({ inputs: { x: new Set([1, 2, 2, 'a']) } })], ['set', true, 3])
+})
+
 // =============================================================================
 // Frozenset tests
 // =============================================================================
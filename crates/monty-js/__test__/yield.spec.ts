@@ -0,0 +1,91 @@
+import test from 'ava'
+
+import { Monty, MontyComplete, MontyRuntimeError, MontyYield, runMontyAsync } from '../wrapper'
+
+const CODE = `
+total = 0
+for i in range(3):
+    total += i
+    monty_yield(total)
+total
+`
+
+// =============================================================================
+// start() returns MontyYield tests
+// =============================================================================
+
+test('start returns yield', (t) => {
+  const m = new Monty(CODE)
+  let progress = m.start()
+  const values: unknown[] = []
+  while (progress instanceof MontyYield) {
+    values.push(progress.value)
+    progress = progress.resume()
+  }
+  t.true(progress instanceof MontyComplete)
+  t.deepEqual(values, [0, 1, 3])
+  t.is((progress as MontyComplete).output, 3)
+})
+
+test('yield resume return value', (t) => {
+  const m = new Monty("monty_yield('ready?') + '!'")
+  const progress = m.start()
+  t.true(progress instanceof MontyYield)
+  const yielded = progress as MontyYield
+  t.is(yielded.value, 'ready?')
+  t.is(yielded.scriptName, 'main.py')
+  const result = yielded.resume({ returnValue: 'go' })
+  t.is((result as MontyComplete).output, 'go!')
+})
+
+test('yield resume exception', (t) => {
+  const m = new Monty('monty_yield(1)')
+  const yielded = m.start() as MontyYield
+  const error = t.throws(() => yielded.resume({ exception: { type: 'ValueError', message: 'stop' } }), {
+    instanceOf: MontyRuntimeError,
+  })
+  t.true(error.message.includes('ValueError: stop'))
+})
+
+test('yield dump and load', (t) => {
+  const m = new Monty('x = [1, 2]\nmonty_yield(x)\nx.append(3)\nx')
+  const yielded = m.start() as MontyYield
+  const loaded = MontyYield.load(yielded.dump())
+  t.deepEqual(loaded.value, [1, 2])
+  const result = loaded.resume()
+  t.deepEqual((result as MontyComplete).output, [1, 2, 3])
+})
+
+// =============================================================================
+// run() and runMontyAsync() yieldCallback tests
+// =============================================================================
+
+test('run yield callback', (t) => {
+  const values: unknown[] = []
+  const m = new Monty(CODE)
+  t.is(m.run({ yieldCallback: (value) => values.push(value) }), 3)
+  t.deepEqual(values, [0, 1, 3])
+})
+
+test('run yield callback return value', (t) => {
+  const m = new Monty('monty_yield(2) * 10')
+  t.is(m.run({ yieldCallback: (value) => (value as number) + 1 }), 30)
+})
+
+test('run yield without callback', (t) => {
+  const m = new Monty('monty_yield(1)')
+  const error = t.throws(() => m.run(), { instanceOf: MontyRuntimeError })
+  t.is(error.exception.typeName, 'NotImplementedError')
+})
+
+test('runMontyAsync yield callback', async (t) => {
+  const values: unknown[] = []
+  const m = new Monty(CODE)
+  const result = await runMontyAsync(m, {
+    yieldCallback: async (value) => {
+      values.push(value)
+    },
+  })
+  t.is(result, 3)
+  t.deepEqual(values, [0, 1, 3])
+})
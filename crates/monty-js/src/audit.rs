@@ -0,0 +1,83 @@
+//! `auditCallback` support for the JavaScript bindings.
+//!
+//! Passes Monty's audit events for external calls, OS calls, imports, resource limit breaches
+//! and denied capabilities to a JavaScript function as plain objects.
+
+use std::fmt;
+
+use monty::{AuditEvent, AuditEventKind, AuditHook};
+use napi::bindgen_prelude::*;
+
+/// JavaScript function receiving audit events.
+pub type JsAuditCallback<'env> = Function<'env, Object<'env>, ()>;
+
+/// Calls an optional `auditCallback` with an object describing each audit event.
+///
+/// Only used by `Monty.run()`, where execution happens synchronously on the JS thread
+/// so the callback can be borrowed for the duration of the run.
+pub struct JsAuditHook<'env> {
+    env: &'env Env,
+    callback: Option<JsAuditCallback<'env>>,
+}
+
+impl<'env> JsAuditHook<'env> {
+    /// Creates a hook calling `callback`, or doing nothing if it's `None`.
+    pub fn new(env: &'env Env, callback: Option<JsAuditCallback<'env>>) -> Self {
+        Self { env, callback }
+    }
+}
+
+impl fmt::Debug for JsAuditHook<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JsAuditHook")
+            .field("callback", &self.callback.is_some())
+            .finish()
+    }
+}
+
+impl AuditHook for JsAuditHook<'_> {
+    fn on_event(&self, event: &AuditEvent<'_>) {
+        let Some(callback) = &self.callback else {
+            return;
+        };
+        // audit hooks can't stop execution, so errors thrown by the callback are ignored
+        let _ = event_object(self.env, event).and_then(|obj| callback.call(obj));
+    }
+}
+
+/// Converts an audit event into an object with `event` (the kind of event), `timestamp`
+/// (seconds since the Unix epoch) and the fields of the event.
+///
+/// Call arguments are represented by `argsDigest`, a hex digest, rather than the values.
+fn event_object<'env>(env: &'env Env, event: &AuditEvent<'_>) -> Result<Object<'env>> {
+    let mut obj = Object::new(env)?;
+    obj.set_named_property("event", event.name())?;
+    obj.set_named_property("timestamp", event.unix_timestamp())?;
+    match &event.kind {
+        AuditEventKind::ExternalCall {
+            function_name,
+            method_call,
+            ..
+        } => {
+            obj.set_named_property("function", *function_name)?;
+            obj.set_named_property("methodCall", *method_call)?;
+        }
+        AuditEventKind::OsCall { function, .. } => obj.set_named_property("function", function.to_string())?,
+        AuditEventKind::Import { module, allowed } => {
+            obj.set_named_property("module", *module)?;
+            obj.set_named_property("allowed", *allowed)?;
+        }
+        AuditEventKind::ResourceLimit { exc_type, message } => {
+            obj.set_named_property("excType", exc_type.to_string())?;
+            obj.set_named_property("message", *message)?;
+        }
+        AuditEventKind::CapabilityDenied { capability, name } => {
+            obj.set_named_property("capability", capability.to_string())?;
+            obj.set_named_property("name", *name)?;
+        }
+    }
+    if let Some(digest) = event.args_digest() {
+        obj.set_named_property("argsDigest", format!("{digest:016x}"))?;
+    }
+    Ok(obj)
+}
@@ -3,6 +3,7 @@
 //! This module provides bidirectional conversion using native napi-rs APIs:
 //! - `monty_to_js`: Convert Monty's `MontyObject` to a JavaScript value
 //! - `js_to_monty`: Convert a JavaScript value to Monty's `MontyObject`
+//! - `kwargs_to_js`: Convert external function keyword arguments to a plain JS object
 //!
 //! ## Type Mappings
 //!
@@ -13,7 +14,8 @@
 //! - `MontyObject::BigInt` ↔ `BigInt`
 //! - `MontyObject::Float` ↔ `number` (including `NaN`, `Infinity`, `-Infinity`)
 //! - `MontyObject::String` ↔ `string`
-//! - `MontyObject::Bytes` ↔ `Buffer` (Node.js)
+//! - `MontyObject::Bytes` ↔ `Buffer` (Node.js); `Uint8Array`, other `TypedArray`s, `DataView`
+//!   and `ArrayBuffer` are also accepted as input
 //! - `MontyObject::List` ↔ `Array`
 //! - `MontyObject::Dict` ↔ `Map` (preserves key types and insertion order)
 //! - `MontyObject::Set` ↔ `Set`
@@ -28,6 +30,7 @@
 //! - `MontyObject::Dataclass` → `{ __monty_type__: 'Dataclass', name, fields, ... }`
 //! - `MontyObject::Repr` → plain `string`
 //! - `MontyObject::Cycle` → placeholder `string`
+//! - `MontyObject::LazySequence` → repr `string`
 
 use std::collections::HashMap;
 
@@ -77,6 +80,7 @@ pub fn monty_to_js<'e>(obj: &MontyObject, env: &'e Env) -> Result<JsMontyObject<
         MontyObject::BigInt(bi) => create_js_bigint(bi, env)?,
         MontyObject::Float(f) => env.create_double(*f)?.into_unknown(env)?,
         MontyObject::String(s) => env.create_string(s)?.into_unknown(env)?,
+        MontyObject::SharedString(s) => env.create_string(s)?.into_unknown(env)?,
         MontyObject::Bytes(bytes) => create_js_buffer(bytes, env)?,
         MontyObject::List(items) => create_js_array(items, env)?.into_unknown(env)?,
         MontyObject::Tuple(items) => create_js_tuple(items, env)?,
@@ -95,11 +99,29 @@ pub fn monty_to_js<'e>(obj: &MontyObject, env: &'e Env) -> Result<JsMontyObject<
             frozen,
         } => create_js_dataclass(name, *type_id, field_names, attrs, *frozen, env)?,
         MontyObject::Path(p) => env.create_string(p)?.into_unknown(env)?,
+        MontyObject::LazySequence { .. } => env.create_string(&obj.py_repr())?.into_unknown(env)?,
         MontyObject::Repr(s) | MontyObject::Cycle(_, s) => env.create_string(s)?.into_unknown(env)?,
     };
     Ok(JsMontyObject(unknown))
 }
 
+/// Converts keyword arguments of an external function call to a plain JS object.
+///
+/// Keyword names are always strings in Monty, so they become the object's property names,
+/// and values are converted with [`monty_to_js`].
+pub fn kwargs_to_js<'e>(kwargs: &[(MontyObject, MontyObject)], env: &'e Env) -> Result<Object<'e>> {
+    let mut obj = Object::new(env)?;
+    for (key, value) in kwargs {
+        let MontyObject::String(key) = key else {
+            return Err(Error::from_reason(format!(
+                "keyword argument names must be strings, got {key:?}"
+            )));
+        };
+        obj.set_named_property(key, monty_to_js(value, env)?)?;
+    }
+    Ok(obj)
+}
+
 /// Creates a JS null value.
 fn create_js_null(env: &Env) -> Result<Unknown<'_>> {
     // Use raw napi to create null
@@ -337,6 +359,7 @@ fn create_js_dataclass<'e>(
 /// - `bigint` → `Int` (if fits in i64) or `BigInt`
 /// - `string` → `String`
 /// - `Buffer`/`Uint8Array` → `Bytes`
+/// - other `TypedArray`s, `DataView` and `ArrayBuffer` → `Bytes` of their underlying memory
 /// - `Array` with `__tuple__` → `Tuple`
 /// - `Array` → `List`
 /// - `Map` → `Dict`
@@ -399,10 +422,9 @@ pub fn js_to_monty(value: Unknown<'_>, env: Env) -> Result<MontyObject> {
         ValueType::Object => {
             let obj: Object = value.coerce_to_object()?;
 
-            // Check if it's a Buffer (Uint8Array)
-            if obj.is_buffer()? {
-                let buffer: BufferSlice = BufferSlice::from_unknown(value)?;
-                return Ok(MontyObject::Bytes(buffer.to_vec()));
+            // Check if it's binary data (Buffer, any TypedArray, DataView or ArrayBuffer)
+            if let Some(bytes) = js_binary_to_bytes(&value, env)? {
+                return Ok(MontyObject::Bytes(bytes));
             }
 
             // Check if it's a Map
@@ -439,6 +461,116 @@ pub fn js_to_monty(value: Unknown<'_>, env: Env) -> Result<MontyObject> {
     }
 }
 
+/// Returns the size in bytes of one element of a typed array of the given type.
+///
+/// Comes from the type napi reports rather than the array's `BYTES_PER_ELEMENT` property,
+/// which JS code can redefine, since the result bounds the raw read in `js_binary_to_bytes`.
+fn typed_array_element_size(array_type: sys::napi_typedarray_type) -> Option<usize> {
+    match array_type {
+        sys::TypedarrayType::int8_array
+        | sys::TypedarrayType::uint8_array
+        | sys::TypedarrayType::uint8_clamped_array => Some(1),
+        sys::TypedarrayType::int16_array | sys::TypedarrayType::uint16_array => Some(2),
+        sys::TypedarrayType::int32_array | sys::TypedarrayType::uint32_array | sys::TypedarrayType::float32_array => {
+            Some(4)
+        }
+        sys::TypedarrayType::float64_array
+        | sys::TypedarrayType::bigint64_array
+        | sys::TypedarrayType::biguint64_array => Some(8),
+        _ => None,
+    }
+}
+
+/// Copies the bytes viewed by a JS `TypedArray` (including `Buffer`), `DataView` or `ArrayBuffer`.
+///
+/// Returns `None` if the value is none of those. Typed arrays with elements wider than a byte
+/// are copied as raw memory in the platform's byte order, like `Buffer.from(arr.buffer)` would.
+fn js_binary_to_bytes(value: &Unknown<'_>, env: Env) -> Result<Option<Vec<u8>>> {
+    let raw_env = env.raw();
+    let raw_value = value.raw();
+    let mut data: *mut std::ffi::c_void = std::ptr::null_mut();
+    let mut byte_length: usize = 0;
+
+    if napi_is(|result| {
+        // SAFETY: [DH] - all arguments are valid
+        unsafe { sys::napi_is_typedarray(raw_env, raw_value, result) }
+    })? {
+        let mut array_type: sys::napi_typedarray_type = 0;
+        let mut length: usize = 0;
+        let mut arraybuffer = std::ptr::null_mut();
+        let mut byte_offset: usize = 0;
+        // SAFETY: [DH] - value is a typed array, `data` is adjusted by the byte offset on success
+        let status = unsafe {
+            sys::napi_get_typedarray_info(
+                raw_env,
+                raw_value,
+                &raw mut array_type,
+                &raw mut length,
+                &raw mut data,
+                &raw mut arraybuffer,
+                &raw mut byte_offset,
+            )
+        };
+        if status != sys::Status::napi_ok {
+            return Err(Error::from_reason("Failed to read TypedArray"));
+        }
+        // `length` counts elements, the byte length depends on the element type
+        let bytes_per_element = typed_array_element_size(array_type)
+            .ok_or_else(|| Error::from_reason(format!("Unsupported TypedArray type {array_type}")))?;
+        byte_length = length
+            .checked_mul(bytes_per_element)
+            .ok_or_else(|| Error::from_reason("TypedArray byte length overflows usize"))?;
+    } else if napi_is(|result| {
+        // SAFETY: [DH] - all arguments are valid
+        unsafe { sys::napi_is_dataview(raw_env, raw_value, result) }
+    })? {
+        let mut arraybuffer = std::ptr::null_mut();
+        let mut byte_offset: usize = 0;
+        // SAFETY: [DH] - value is a DataView, `data` is adjusted by the byte offset on success
+        let status = unsafe {
+            sys::napi_get_dataview_info(
+                raw_env,
+                raw_value,
+                &raw mut byte_length,
+                &raw mut data,
+                &raw mut arraybuffer,
+                &raw mut byte_offset,
+            )
+        };
+        if status != sys::Status::napi_ok {
+            return Err(Error::from_reason("Failed to read DataView"));
+        }
+    } else if napi_is(|result| {
+        // SAFETY: [DH] - all arguments are valid
+        unsafe { sys::napi_is_arraybuffer(raw_env, raw_value, result) }
+    })? {
+        // SAFETY: [DH] - value is an ArrayBuffer, data and length are valid on success
+        let status = unsafe { sys::napi_get_arraybuffer_info(raw_env, raw_value, &raw mut data, &raw mut byte_length) };
+        if status != sys::Status::napi_ok {
+            return Err(Error::from_reason("Failed to read ArrayBuffer"));
+        }
+    } else {
+        return Ok(None);
+    }
+
+    if byte_length == 0 || data.is_null() {
+        return Ok(Some(Vec::new()));
+    }
+    // SAFETY: [DH] - napi guarantees `data` points to at least `byte_length` bytes, which are
+    // copied immediately, before any JS code could run and detach the buffer
+    let bytes = unsafe { std::slice::from_raw_parts(data.cast::<u8>(), byte_length) };
+    Ok(Some(bytes.to_vec()))
+}
+
+/// Runs one of the `napi_is_*` type checks, e.g. `napi_is_typedarray`, returning its result.
+fn napi_is(check: impl FnOnce(*mut bool) -> sys::napi_status) -> Result<bool> {
+    let mut result = false;
+    if check(&raw mut result) != sys::Status::napi_ok {
+        return Err(Error::from_reason("Failed to check JS value type"));
+    }
+    Ok(result)
+}
+
 /// Checks if a JS object is an instance of Set.
 fn is_js_set(obj: &Object, env: Env) -> Result<bool> {
     let global = env.get_global()?;
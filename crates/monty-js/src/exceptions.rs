@@ -16,7 +16,7 @@
 
 use std::fmt;
 
-use monty::StackFrame;
+use monty::{StackFrame, TracebackOptions, TracebackStyle};
 use monty_type_checking::TypeCheckingDiagnostics;
 use napi::bindgen_prelude::*;
 use napi_derive::napi;
@@ -62,6 +62,25 @@ impl JsMontyException {
         self.0.message().unwrap_or_default().to_string()
     }
 
+    /// Returns the notes attached to the exception with `add_note()`, in the order they were added.
+    #[napi(getter)]
+    #[must_use]
+    pub fn notes(&self) -> Vec<String> {
+        self.0.notes().to_vec()
+    }
+
+    /// Returns the JS error raised by an external function, if that's where this exception came from.
+    #[napi(getter)]
+    #[must_use]
+    pub fn host_error(&self) -> Option<HostErrorInfo> {
+        self.0.host_error().map(|host_error| HostErrorInfo {
+            name: host_error.class_name().to_string(),
+            message: host_error.message().map(str::to_string),
+            stack: host_error.traceback().map(str::to_string),
+            id: host_error.original::<u32>().copied(),
+        })
+    }
+
     /// Returns the Monty traceback as an array of Frame objects.
     ///
     /// For syntax errors, this will be an empty array.
@@ -77,11 +96,12 @@ impl JsMontyException {
     ///   - 'traceback' - Full traceback (default)
     ///   - 'type-msg' - 'ExceptionType: message' format
     ///   - 'msg' - just the message
+    /// @param options - Traceback style, frame limit and internal files, used with 'traceback'
     #[napi]
-    pub fn display(&self, format: Option<String>) -> Result<String> {
+    pub fn display(&self, format: Option<String>, options: Option<TracebackDisplayOptions>) -> Result<String> {
         let format = format.as_deref().unwrap_or("traceback");
         match format {
-            "traceback" => Ok(self.0.to_string()),
+            "traceback" => Ok(self.0.render(&options.unwrap_or_default().try_into()?)),
             "type-msg" => {
                 let type_name = self.0.exc_type().to_string();
                 let message = self.0.message().unwrap_or_default();
@@ -106,6 +126,41 @@ impl JsMontyException {
     }
 }
 
+/// Options for rendering a traceback with `display('traceback', options)`.
+#[napi(object)]
+#[derive(Debug, Clone, Default)]
+pub struct TracebackDisplayOptions {
+    /// 'long' for CPython's format (default), 'short' without caret markers, or 'line'
+    /// for a single `file.py:3: ExceptionType: message` line.
+    pub style: Option<String>,
+    /// Maximum number of frames to show, the innermost frames are kept.
+    pub max_frames: Option<u32>,
+    /// Filenames whose frames are hidden.
+    pub internal_files: Option<Vec<String>>,
+}
+
+impl TryFrom<TracebackDisplayOptions> for TracebackOptions {
+    type Error = Error;
+
+    fn try_from(options: TracebackDisplayOptions) -> Result<Self> {
+        let style = match options.style.as_deref().unwrap_or("long") {
+            "long" => TracebackStyle::Long,
+            "short" => TracebackStyle::Short,
+            "line" => TracebackStyle::Line,
+            style => {
+                return Err(Error::from_reason(format!(
+                    "Invalid traceback style: '{style}'. Expected 'long', 'short', or 'line'"
+                )));
+            }
+        };
+        Ok(Self {
+            style,
+            max_frames: options.max_frames.map(|max| max as usize),
+            internal_files: options.internal_files.unwrap_or_default(),
+        })
+    }
+}
+
 impl JsMontyException {
     /// Creates a new JsMontyException from a core MontyException.
     #[must_use]
@@ -215,6 +270,20 @@ pub struct ExceptionInfo {
     pub message: String,
 }
 
+/// Description of the JS error an external function threw.
+#[napi(object)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HostErrorInfo {
+    /// The error's `name`, e.g. "TypeError" or a custom error class name.
+    pub name: String,
+    /// The error's `message`.
+    pub message: Option<String>,
+    /// The error's `stack`, if it had one.
+    pub stack: Option<String>,
+    /// Id assigned by the JS wrapper to the original error, used to rethrow it.
+    pub id: Option<u32>,
+}
+
 /// A single frame in a Monty traceback.
 ///
 /// Contains all the information needed to display a traceback line:
@@ -236,6 +305,8 @@ pub struct Frame {
     pub function_name: Option<String>,
     /// The source code line for preview in the traceback.
     pub source_line: Option<String>,
+    /// Call depth of the frame, 0 for the outermost frame.
+    pub depth: u32,
 }
 
 impl Frame {
@@ -250,6 +321,7 @@ impl Frame {
             end_column: u32::from(frame.end.column),
             function_name: frame.frame_name.clone(),
             source_line: frame.preview_line.clone(),
+            depth: u32::try_from(frame.depth).unwrap_or(u32::MAX),
         }
     }
 }
@@ -28,6 +28,7 @@
 //! }
 //! ```
 
+mod audit;
 mod convert;
 mod exceptions;
 mod limits;
@@ -5,7 +5,7 @@
 
 use std::time::Duration;
 
-use monty::{ResourceLimits, DEFAULT_MAX_RECURSION_DEPTH};
+use monty::{ResourceLimits, DEFAULT_MAX_RECURSION_DEPTH, DEFAULT_MAX_STACK_SIZE};
 use napi_derive::napi;
 
 /// Resource limits configuration from JavaScript.
@@ -24,6 +24,13 @@ pub struct JsResourceLimits {
     pub gc_interval: Option<u32>,
     /// Maximum function call stack depth (default: 1000).
     pub max_recursion_depth: Option<u32>,
+    /// Maximum size in bytes of the result and of external function call arguments.
+    pub max_output_size: Option<u32>,
+    /// Maximum size in bytes of interned literals, which accumulate across REPL snippets.
+    /// Only enforced by `MontyRepl`.
+    pub max_interned_size: Option<u32>,
+    /// Maximum Rust stack usage in bytes before raising `RecursionError` (default: 1MiB).
+    pub max_stack_size: Option<u32>,
 }
 
 impl From<JsResourceLimits> for ResourceLimits {
@@ -33,7 +40,14 @@ impl From<JsResourceLimits> for ResourceLimits {
             .map(|v| v as usize)
             .or(Some(DEFAULT_MAX_RECURSION_DEPTH));
 
-        let mut limits = Self::new().max_recursion_depth(max_recursion_depth);
+        let max_stack_size = js_limits
+            .max_stack_size
+            .map(|v| v as usize)
+            .or(Some(DEFAULT_MAX_STACK_SIZE));
+
+        let mut limits = Self::new()
+            .max_recursion_depth(max_recursion_depth)
+            .max_stack_size(max_stack_size);
 
         if let Some(max) = js_limits.max_allocations {
             limits = limits.max_allocations(max as usize);
@@ -47,6 +61,12 @@ impl From<JsResourceLimits> for ResourceLimits {
         if let Some(interval) = js_limits.gc_interval {
             limits = limits.gc_interval(interval as usize);
         }
+        if let Some(max) = js_limits.max_output_size {
+            limits = limits.max_output_size(max as usize);
+        }
+        if let Some(max) = js_limits.max_interned_size {
+            limits = limits.max_interned_size(max as usize);
+        }
 
         limits
     }
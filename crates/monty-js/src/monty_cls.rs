@@ -21,10 +21,10 @@
 //! ## Iterative Execution
 //!
 //! ```text
-//! Monty.start() -> MontySnapshot | MontyComplete
+//! Monty.start() -> MontySnapshot | MontyYield | MontyComplete
 //!                       |
 //!                       v
-//! MontySnapshot.resume() -> MontySnapshot | MontyComplete
+//! MontySnapshot.resume() -> MontySnapshot | MontyYield | MontyComplete
 //!                                |
 //!                                v
 //!                          (repeat until complete)
@@ -46,7 +46,8 @@
 use std::borrow::Cow;
 
 use monty::{
-    ExcType, ExternalResult, LimitedTracker, MontyException, MontyObject, MontyRepl as CoreMontyRepl, MontyRun,
+    sign_serialized, verify_serialized, AuditEvent, AuditEventKind, AuditTracker, Capability, ExcType, ExternalResult,
+    HostError, LimitedTracker, MemoryProfile, MontyException, MontyObject, MontyRepl as CoreMontyRepl, MontyRun,
     NoLimitTracker, PrintWriter, PrintWriterCallback, ResourceTracker, RunProgress, Snapshot,
 };
 use monty_type_checking::{type_check, SourceFile};
@@ -54,7 +55,8 @@ use napi::bindgen_prelude::*;
 use napi_derive::napi;
 
 use crate::{
-    convert::{js_to_monty, monty_to_js, JsMontyObject},
+    audit::{JsAuditCallback, JsAuditHook},
+    convert::{js_to_monty, kwargs_to_js, monty_to_js, JsMontyObject},
     exceptions::{exc_js_to_monty, JsMontyException, MontyTypingError},
     limits::JsResourceLimits,
 };
@@ -108,6 +110,15 @@ pub struct RunOptions<'env> {
     /// Dict of external function callbacks.
     /// Keys are function names, values are callable functions.
     pub external_functions: Option<Object<'env>>,
+    /// Optional callback for security-relevant events: external calls, OS calls, imports,
+    /// resource limit breaches and denied capabilities.
+    pub audit_callback: Option<JsAuditCallback<'env>>,
+    /// Optional callback for values streamed with `monty_yield(value)`.
+    ///
+    /// Called with each value as it's yielded, its return value is returned by `monty_yield()`
+    /// and errors it throws are raised there. Without a callback, `monty_yield()` raises
+    /// `NotImplementedError`.
+    pub yield_callback: Option<JsYieldCallback<'env>>,
 }
 
 /// Options for starting execution.
@@ -201,22 +212,26 @@ impl Monty {
             None => PrintWriter::Stdout,
         };
 
-        // If we have external functions declared, use the start/resume loop
-        if !self.external_function_names.is_empty() {
+        let audit = AuditTracker::new(JsAuditHook::new(env, options.audit_callback));
+
+        // If we have external functions declared or a yield callback, use the start/resume loop
+        if !self.external_function_names.is_empty() || options.yield_callback.is_some() {
             return self.run_with_external_functions(
                 env,
                 input_values,
                 options.limits,
                 external_functions,
+                options.yield_callback,
+                audit,
                 print_writer,
             );
         }
 
         let result = if let Some(limits) = options.limits {
-            let tracker = LimitedTracker::new(limits.into());
+            let tracker = LimitedTracker::new(limits.into()).chain(audit);
             self.runner.run(input_values, tracker, &mut print_writer)
         } else {
-            let tracker = NoLimitTracker;
+            let tracker = NoLimitTracker.chain(audit);
             self.runner.run(input_values, tracker, &mut print_writer)
         };
 
@@ -226,13 +241,16 @@ impl Monty {
         }
     }
 
-    /// Internal helper to run code with external function callbacks.
+    /// Internal helper to run code with external function and yield callbacks.
+    #[expect(clippy::too_many_arguments)]
     fn run_with_external_functions<'env>(
         &self,
         env: &'env Env,
         input_values: Vec<MontyObject>,
         limits: Option<JsResourceLimits>,
         external_functions: Option<Object<'env>>,
+        yield_callback: Option<JsYieldCallback<'env>>,
+        audit: AuditTracker<JsAuditHook<'env>>,
         mut print_output: PrintWriter<'_>,
     ) -> Result<Either<JsMontyObject<'env>, JsMontyException>> {
         let runner = self.runner.clone();
@@ -249,7 +267,7 @@ impl Monty {
 
                 loop {
                     match progress {
-                        RunProgress::Complete(result) => {
+                        RunProgress::Complete { value: result, .. } => {
                             return Ok(Either::A(monty_to_js(&result, env)?));
                         }
                         RunProgress::FunctionCall {
@@ -272,12 +290,43 @@ impl Monty {
                                 Err(exc) => return Ok(Either::B(JsMontyException::new(exc))),
                             };
                         }
-                        RunProgress::ResolveFutures(_) => {
+                        RunProgress::ResolveFutures(_) | RunProgress::FunctionCallBatch { .. } => {
                             return Err(Error::from_reason(
                                 "Async futures are not supported in synchronous run(). Use start() for async execution.",
                             ));
                         }
-                        RunProgress::OsCall { function, .. } => {
+                        RunProgress::Yield { value, state } => {
+                            let result = if let Some(callback) = &yield_callback {
+                                match callback.call(monty_to_js(&value, env)?) {
+                                    Ok(result) => ExternalResult::Return(js_to_monty(result, *env)?),
+                                    Err(err) => ExternalResult::Error(exc_js_to_monty(err)),
+                                }
+                            } else {
+                                state.tracker().audit(&AuditEvent::now(AuditEventKind::CapabilityDenied {
+                                    capability: Capability::Yield,
+                                    name: "monty_yield",
+                                }));
+                                ExternalResult::Error(MontyException::new(
+                                    ExcType::NotImplementedError,
+                                    Some("monty_yield() called but no yieldCallback provided".to_owned()),
+                                ))
+                            };
+                            progress = match state.run(result, &mut print_output) {
+                                Ok(p) => p,
+                                Err(exc) => return Ok(Either::B(JsMontyException::new(exc))),
+                            };
+                        }
+                        RunProgress::Checkpoint(state) => {
+                            progress = match state.run(&mut print_output) {
+                                Ok(p) => p,
+                                Err(exc) => return Ok(Either::B(JsMontyException::new(exc))),
+                            };
+                        }
+                        RunProgress::OsCall { function, state, .. } => {
+                            state.tracker().audit(&AuditEvent::now(AuditEventKind::CapabilityDenied {
+                                capability: Capability::OsFunction,
+                                name: &function.to_string(),
+                            }));
                             return Err(Error::from_reason(format!(
                                 "OS calls are not supported: {function:?}",
                             )));
@@ -288,10 +337,10 @@ impl Monty {
         }
 
         if let Some(limits) = limits {
-            let tracker = LimitedTracker::new(limits.into());
+            let tracker = LimitedTracker::new(limits.into()).chain(audit);
             run_loop!(tracker)
         } else {
-            run_loop!(NoLimitTracker)
+            run_loop!(NoLimitTracker.chain(audit))
         }
     }
 
@@ -301,13 +350,14 @@ impl Monty {
     /// calls, allowing the host to provide return values or exceptions before resuming.
     ///
     /// @param options - Execution options (inputs, limits)
-    /// @returns MontySnapshot if paused, MontyComplete if done, or MontyException if failed
+    /// @returns MontySnapshot if paused at an external call, MontyYield if paused at `monty_yield()`,
+    ///   MontyComplete if done, or MontyException if failed
     #[napi]
     pub fn start<'env>(
         &self,
         env: &'env Env,
         options: Option<StartOptions<'env>>,
-    ) -> Result<Either3<MontySnapshot, MontyComplete, JsMontyException>> {
+    ) -> Result<Either4<MontySnapshot, MontyComplete, JsMontyException, MontyYield>> {
         let options = options.unwrap_or_default();
         let input_values = self.extract_input_values(options.inputs, *env)?;
 
@@ -330,14 +380,14 @@ impl Monty {
             let tracker = LimitedTracker::new(limits.into());
             let progress = match runner.start(input_values, tracker, &mut print_writer) {
                 Ok(p) => p,
-                Err(exc) => return Ok(Either3::C(JsMontyException::new(exc))),
+                Err(exc) => return Ok(Either4::C(JsMontyException::new(exc))),
             };
             Ok(progress_to_result(progress, print_callback_ref, self.script_name()))
         } else {
             let tracker = NoLimitTracker;
             let progress = match runner.start(input_values, tracker, &mut print_writer) {
                 Ok(p) => p,
-                Err(exc) => return Ok(Either3::C(JsMontyException::new(exc))),
+                Err(exc) => return Ok(Either4::C(JsMontyException::new(exc))),
             };
             Ok(progress_to_result(progress, print_callback_ref, self.script_name()))
         }
@@ -348,28 +398,37 @@ impl Monty {
     /// The serialized data can be stored and later restored with `Monty.load()`.
     /// This allows caching parsed code to avoid re-parsing on subsequent runs.
     ///
+    /// @param signingKey - Optional key to sign the data with, see `load()`
     /// @returns Buffer containing the serialized Monty instance
     #[napi]
-    pub fn dump(&self) -> Result<Buffer> {
+    pub fn dump(&self, signing_key: Option<Buffer>) -> Result<Buffer> {
         let serialized = SerializedMonty {
             runner: self.runner.clone(),
             script_name: self.script_name.clone(),
             input_names: self.input_names.clone(),
             external_function_names: self.external_function_names.clone(),
         };
-        let bytes =
+        let mut bytes =
             postcard::to_allocvec(&serialized).map_err(|e| Error::from_reason(format!("Serialization failed: {e}")))?;
+        if let Some(key) = signing_key {
+            bytes = sign_serialized(bytes, &key);
+        }
         Ok(Buffer::from(bytes))
     }
 
     /// Deserializes a Monty instance from binary format.
     ///
     /// @param data - The serialized Monty data from `dump()`
+    /// @param signingKey - If set, the data must have been signed with this key by `dump()`
     /// @returns A new Monty instance
     #[napi(factory)]
-    pub fn load(data: Buffer) -> Result<Self> {
+    pub fn load(data: Buffer, signing_key: Option<Buffer>) -> Result<Self> {
+        let mut bytes: &[u8] = &data;
+        if let Some(key) = &signing_key {
+            bytes = verify_serialized(bytes, key).map_err(|e| Error::from_reason(e.to_string()))?;
+        }
         let serialized: SerializedMonty =
-            postcard::from_bytes(&data).map_err(|e| Error::from_reason(format!("Deserialization failed: {e}")))?;
+            postcard::from_bytes(bytes).map_err(|e| Error::from_reason(format!("Deserialization failed: {e}")))?;
 
         Ok(Self {
             runner: serialized.runner,
@@ -379,6 +438,16 @@ impl Monty {
         })
     }
 
+    /// SHA-256 hash of the source code as a hex string, preserved by `dump()` and `load()`.
+    #[napi(getter)]
+    pub fn code_hash(&self) -> String {
+        self.runner
+            .code_hash()
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect()
+    }
+
     /// Returns the script name.
     #[napi(getter)]
     pub fn script_name(&self) -> String {
@@ -553,15 +622,22 @@ impl MontyRepl {
     }
 
     /// Executes one incremental snippet against persistent REPL state.
+    ///
+    /// With `namespace`, the snippet runs against that named namespace's globals
+    /// instead of the default ones.
     #[napi]
     pub fn feed<'env>(
         &mut self,
         env: &'env Env,
         code: String,
+        namespace: Option<String>,
     ) -> Result<Either<JsMontyObject<'env>, JsMontyException>> {
-        let output = match &mut self.repl {
-            EitherRepl::NoLimit(repl) => repl.feed(&code, &mut PrintWriter::Stdout),
-            EitherRepl::Limited(repl) => repl.feed(&code, &mut PrintWriter::Stdout),
+        let print = &mut PrintWriter::Stdout;
+        let output = match (&mut self.repl, namespace) {
+            (EitherRepl::NoLimit(repl), None) => repl.feed(&code, print),
+            (EitherRepl::NoLimit(repl), Some(namespace)) => repl.feed_in_namespace(&namespace, &code, print),
+            (EitherRepl::Limited(repl), None) => repl.feed(&code, print),
+            (EitherRepl::Limited(repl), Some(namespace)) => repl.feed_in_namespace(&namespace, &code, print),
         };
 
         match output {
@@ -715,6 +791,11 @@ pub struct ResumeOptions<'env> {
     /// An exception to raise in the interpreter.
     /// Format: { type: string, message: string }
     pub exception: Option<ExceptionInput>,
+    /// A JS `Error` to raise in the interpreter instead of `exception`.
+    ///
+    /// Its `name` picks the Python exception type, e.g. `TypeError`, and falls back to
+    /// `RuntimeError`; `message` and `stack` are kept.
+    pub error: Option<Object<'env>>,
 }
 
 /// Input for raising an exception during resume.
@@ -724,6 +805,14 @@ pub struct ExceptionInput {
     pub r#type: String,
     /// The exception message.
     pub message: String,
+    /// Optional notes to attach to the exception, visible to Monty code via `__notes__`.
+    pub notes: Option<Vec<String>>,
+    /// Optional stack of the original JS error, returned in `hostError.stack` if the exception
+    /// escapes the sandbox.
+    pub stack: Option<String>,
+    /// Optional opaque id of the original JS error, returned in `hostError.id` if the exception
+    /// escapes the sandbox so the caller can rethrow the original.
+    pub host_error_id: Option<u32>,
 }
 
 /// Options for loading a serialized snapshot.
@@ -757,87 +846,30 @@ impl MontySnapshot {
     /// Returns the keyword arguments passed to the external function as an object.
     #[napi(getter)]
     pub fn kwargs<'env>(&self, env: &'env Env) -> Result<Object<'env>> {
-        let mut obj = Object::new(env)?;
-        for (k, v) in &self.kwargs {
-            // Keys should be strings
-            let key = match k {
-                MontyObject::String(s) => s.clone(),
-                _ => format!("{k:?}"),
-            };
-            let js_value = monty_to_js(v, env)?;
-            obj.set_named_property(&key, js_value)?;
-        }
-        Ok(obj)
+        kwargs_to_js(&self.kwargs, env)
     }
 
     /// Resumes execution with either a return value or an exception.
     ///
-    /// Exactly one of `returnValue` or `exception` must be provided.
+    /// Exactly one of `returnValue` or `exception` must be provided, a JS `Error` can be
+    /// passed as `error` in place of `exception`.
     ///
-    /// @param options - Object with either `returnValue` or `exception`
-    /// @returns MontySnapshot if paused, MontyComplete if done, or MontyException if failed
+    /// @param options - Object with either `returnValue`, `exception` or `error`
+    /// @returns MontySnapshot if paused at an external call, MontyYield if paused at `monty_yield()`,
+    ///   MontyComplete if done, or MontyException if failed
     #[napi]
     pub fn resume<'env>(
         &mut self,
         env: &'env Env,
         options: ResumeOptions<'env>,
-    ) -> Result<Either3<Self, MontyComplete, JsMontyException>> {
-        // Validate that exactly one of returnValue or exception is provided
-        let external_result = match (options.return_value, options.exception) {
-            (Some(value), None) => {
-                let monty_value = js_to_monty(value, *env)?;
-                ExternalResult::Return(monty_value)
-            }
-            (None, Some(exc)) => {
-                let monty_exc = MontyException::new(string_to_exc_type(&exc.r#type)?, Some(exc.message));
-                ExternalResult::Error(monty_exc)
-            }
-            (Some(_), Some(_)) => {
-                return Err(Error::from_reason(
-                    "resume() accepts either returnValue or exception, not both",
-                ));
-            }
-            (None, None) => {
-                return Err(Error::from_reason("resume() requires either returnValue or exception"));
-            }
+    ) -> Result<Either4<MontySnapshot, MontyComplete, JsMontyException, MontyYield>> {
+        let Some(external_result) = resume_options_to_result(options, env)? else {
+            return Err(Error::from_reason("resume() requires either returnValue or exception"));
         };
-
-        // Take the snapshot, replacing with Done
+        // Take the snapshot and print callback, replacing them with Done and None
         let snapshot = std::mem::replace(&mut self.snapshot, EitherSnapshot::Done);
-
-        // Take the print callback
-        // This is necessary to move out of `&mut self` to please the borrow checker.
-        // Unless the entire snapshot generator is refactored we have to do this.
         let print_callback = std::mem::take(&mut self.print_callback);
-
-        // Build print writer from the callback ref
-        let mut print_cb;
-        let mut print_writer = match &print_callback {
-            Some(func) => {
-                print_cb = CallbackStringPrint::new_js_ref(env, func)?;
-                PrintWriter::Callback(&mut print_cb)
-            }
-            None => PrintWriter::Stdout,
-        };
-
-        // Resume execution based on the snapshot type
-        match snapshot {
-            EitherSnapshot::NoLimit(state) => {
-                let progress = match state.run(external_result, &mut print_writer) {
-                    Ok(p) => p,
-                    Err(exc) => return Ok(Either3::C(JsMontyException::new(exc))),
-                };
-                Ok(progress_to_result(progress, print_callback, self.script_name.clone()))
-            }
-            EitherSnapshot::Limited(state) => {
-                let progress = match state.run(external_result, &mut print_writer) {
-                    Ok(p) => p,
-                    Err(exc) => return Ok(Either3::C(JsMontyException::new(exc))),
-                };
-                Ok(progress_to_result(progress, print_callback, self.script_name.clone()))
-            }
-            EitherSnapshot::Done => Err(Error::from_reason("Snapshot has already been resumed")),
-        }
+        resume_snapshot(env, snapshot, external_result, print_callback, self.script_name.clone())
     }
 
     /// Serializes the MontySnapshot to a binary format.
@@ -899,6 +931,175 @@ impl MontySnapshot {
     }
 }
 
+// =============================================================================
+// MontyYield - Paused execution at monty_yield()
+// =============================================================================
+
+/// Represents paused execution that streamed a partial result with `monty_yield(value)`.
+///
+/// Resuming continues execution, `monty_yield()` returns the value passed to `resume()`.
+#[napi]
+pub struct MontyYield {
+    /// The execution state that can be resumed.
+    snapshot: EitherSnapshot,
+    /// Name of the script being executed.
+    script_name: String,
+    /// The value passed to `monty_yield()`.
+    value: MontyObject,
+    /// Optional print callback function.
+    print_callback: Option<JsPrintCallbackRef>,
+}
+
+#[napi]
+impl MontyYield {
+    /// Returns the name of the script being executed.
+    #[napi(getter)]
+    pub fn script_name(&self) -> String {
+        self.script_name.clone()
+    }
+
+    /// Returns the value passed to `monty_yield()`.
+    #[napi(getter)]
+    pub fn value<'env>(&self, env: &'env Env) -> Result<JsMontyObject<'env>> {
+        monty_to_js(&self.value, env)
+    }
+
+    /// Resumes execution, `monty_yield()` returns `returnValue`, or `null` if it's omitted.
+    ///
+    /// Alternatively `exception` or `error` is raised from the `monty_yield()` call.
+    ///
+    /// @param options - Optional object with either `returnValue`, `exception` or `error`
+    /// @returns MontySnapshot if paused at an external call, MontyYield if paused at `monty_yield()`,
+    ///   MontyComplete if done, or MontyException if failed
+    #[napi]
+    pub fn resume<'env>(
+        &mut self,
+        env: &'env Env,
+        options: Option<ResumeOptions<'env>>,
+    ) -> Result<Either4<MontySnapshot, MontyComplete, JsMontyException, MontyYield>> {
+        let external_result = match options {
+            Some(options) => resume_options_to_result(options, env)?,
+            None => None,
+        };
+        let external_result = external_result.unwrap_or(ExternalResult::Return(MontyObject::None));
+        let snapshot = std::mem::replace(&mut self.snapshot, EitherSnapshot::Done);
+        let print_callback = std::mem::take(&mut self.print_callback);
+        resume_snapshot(env, snapshot, external_result, print_callback, self.script_name.clone())
+    }
+
+    /// Serializes the MontyYield to a binary format, see `MontySnapshot.dump()`.
+    ///
+    /// @returns Buffer containing the serialized snapshot
+    #[napi]
+    pub fn dump(&self) -> Result<Buffer> {
+        if matches!(self.snapshot, EitherSnapshot::Done) {
+            return Err(Error::from_reason("Cannot dump snapshot that has already been resumed"));
+        }
+
+        let serialized = SerializedYield {
+            snapshot: &self.snapshot,
+            script_name: &self.script_name,
+            value: &self.value,
+        };
+
+        let bytes =
+            postcard::to_allocvec(&serialized).map_err(|e| Error::from_reason(format!("Serialization failed: {e}")))?;
+        Ok(Buffer::from(bytes))
+    }
+
+    /// Deserializes a MontyYield from binary format.
+    ///
+    /// @param data - The serialized snapshot data from `dump()`
+    /// @param options - Optional load options
+    /// @returns A new MontyYield instance
+    #[napi(factory)]
+    pub fn load(data: Buffer, options: Option<SnapshotLoadOptions>) -> Result<Self> {
+        let serialized: SerializedYieldOwned =
+            postcard::from_bytes(&data).map_err(|e| Error::from_reason(format!("Deserialization failed: {e}")))?;
+
+        Ok(Self {
+            snapshot: serialized.snapshot,
+            script_name: serialized.script_name,
+            value: serialized.value,
+            print_callback: options
+                .as_ref()
+                .and_then(|t| t.print_callback.as_ref())
+                .map(Function::create_ref)
+                .transpose()?,
+        })
+    }
+
+    /// Returns a string representation of the MontyYield.
+    #[napi]
+    #[must_use]
+    pub fn repr(&self) -> String {
+        format!("MontyYield(scriptName='{}', value={:?})", self.script_name, self.value)
+    }
+}
+
+/// Converts the options of a `resume()` call to the external result to resume with.
+///
+/// Returns `None` if neither a return value nor an exception is provided.
+fn resume_options_to_result(options: ResumeOptions<'_>, env: &Env) -> Result<Option<ExternalResult>> {
+    let exception = match (options.exception, options.error) {
+        (Some(exc), None) => Some(exception_input_to_monty(exc)?),
+        (None, Some(error)) => Some(extract_js_exception(error)),
+        (None, None) => None,
+        (Some(_), Some(_)) => {
+            return Err(Error::from_reason(
+                "resume() accepts either exception or error, not both",
+            ));
+        }
+    };
+    // Validate that at most one of returnValue or exception is provided
+    match (options.return_value, exception) {
+        (Some(value), None) => Ok(Some(ExternalResult::Return(js_to_monty(value, *env)?))),
+        (None, Some(exc)) => Ok(Some(ExternalResult::Error(exc))),
+        (Some(_), Some(_)) => Err(Error::from_reason(
+            "resume() accepts either returnValue or exception, not both",
+        )),
+        (None, None) => Ok(None),
+    }
+}
+
+/// Resumes a snapshot taken from `MontySnapshot` or `MontyYield` with `external_result`.
+fn resume_snapshot(
+    env: &Env,
+    snapshot: EitherSnapshot,
+    external_result: ExternalResult,
+    print_callback: Option<JsPrintCallbackRef>,
+    script_name: String,
+) -> Result<Either4<MontySnapshot, MontyComplete, JsMontyException, MontyYield>> {
+    // Build print writer from the callback ref
+    let mut print_cb;
+    let mut print_writer = match &print_callback {
+        Some(func) => {
+            print_cb = CallbackStringPrint::new_js_ref(env, func)?;
+            PrintWriter::Callback(&mut print_cb)
+        }
+        None => PrintWriter::Stdout,
+    };
+
+    // Resume execution based on the snapshot type
+    match snapshot {
+        EitherSnapshot::NoLimit(state) => {
+            let progress = match state.run(external_result, &mut print_writer) {
+                Ok(p) => p,
+                Err(exc) => return Ok(Either4::C(JsMontyException::new(exc))),
+            };
+            Ok(progress_to_result(progress, print_callback, script_name))
+        }
+        EitherSnapshot::Limited(state) => {
+            let progress = match state.run(external_result, &mut print_writer) {
+                Ok(p) => p,
+                Err(exc) => return Ok(Either4::C(JsMontyException::new(exc))),
+            };
+            Ok(progress_to_result(progress, print_callback, script_name))
+        }
+        EitherSnapshot::Done => Err(Error::from_reason("Snapshot has already been resumed")),
+    }
+}
+
 // =============================================================================
 // MontyComplete - Completed execution
 // =============================================================================
@@ -910,6 +1111,8 @@ impl MontySnapshot {
 pub struct MontyComplete {
     /// The final output value from the executed code.
     output_value: MontyObject,
+    /// Live heap objects when execution finished.
+    profile: Option<MemoryProfile>,
 }
 
 #[napi]
@@ -920,6 +1123,14 @@ impl MontyComplete {
         monty_to_js(&self.output_value, env)
     }
 
+    /// Returns the live heap objects when execution finished, including the output and global
+    /// variables, or `null` if no profile was recorded for the run.
+    #[napi(getter)]
+    #[must_use]
+    pub fn memory_profile(&self) -> Option<JsMemoryProfile> {
+        self.profile.as_ref().map(JsMemoryProfile::from)
+    }
+
     /// Returns a string representation of the MontyComplete.
     #[napi]
     #[must_use]
@@ -928,6 +1139,44 @@ impl MontyComplete {
     }
 }
 
+/// Live heap objects per type, see `MontyComplete.memoryProfile`.
+///
+/// Sizes are estimates, the same ones checked against `maxMemory`.
+#[napi(object, js_name = "MemoryProfile")]
+pub struct JsMemoryProfile {
+    /// Live objects per heap type, largest total size first.
+    pub types: Vec<JsTypeUsage>,
+}
+
+/// Live objects of one heap type, e.g. `"list"` or `"str"`.
+#[napi(object, js_name = "TypeUsage")]
+pub struct JsTypeUsage {
+    /// Name of the heap representation.
+    pub type_name: String,
+    /// Number of live objects.
+    pub count: u32,
+    /// Estimated total size in bytes.
+    pub bytes: u32,
+}
+
+impl From<&MemoryProfile> for JsMemoryProfile {
+    fn from(profile: &MemoryProfile) -> Self {
+        let types = profile
+            .types
+            .iter()
+            .map(|usage| JsTypeUsage {
+                type_name: usage.type_name.to_owned(),
+                count: u32::try_from(usage.count).unwrap_or(u32::MAX),
+                bytes: u32::try_from(usage.bytes).unwrap_or(u32::MAX),
+            })
+            .collect();
+        Self { types }
+    }
+}
+
+// Function type for the JS `yieldCallback` of `Monty.run()`.
+type JsYieldCallback<'env> = Function<'env, JsMontyObject<'env>, Unknown<'env>>;
+
 // Function type for JS callback used in `CallbackStringPrint`.
 type JsPrintCallback<'env> = Function<'env, FnArgs<(&'static str, String)>, ()>;
 type JsPrintCallbackRef = FunctionRef<FnArgs<(&'static str, String)>, ()>;
@@ -965,13 +1214,27 @@ impl PrintWriterCallback for CallbackStringPrint<'_> {
             .map_err(exc_js_to_monty)?;
         Ok(())
     }
+
+    fn stderr_write(&mut self, output: Cow<'_, str>) -> std::result::Result<(), MontyException> {
+        self.0
+            .call(("stderr", output.as_ref().to_owned()).into())
+            .map_err(exc_js_to_monty)?;
+        Ok(())
+    }
+
+    fn stderr_push(&mut self, end: char) -> std::result::Result<(), MontyException> {
+        self.0
+            .call(("stderr", end.to_string()).into())
+            .map_err(exc_js_to_monty)?;
+        Ok(())
+    }
 }
 
 // =============================================================================
 // Helper functions for progress conversion
 // =============================================================================
 
-/// Converts a `RunProgress` to either a `MontySnapshot`, `MontyComplete`, or `JsMontyException`.
+/// Converts a `RunProgress` to either a `MontySnapshot`, `MontyComplete`, `JsMontyException` or `MontyYield`.
 ///
 /// # Panics
 /// Panics if the progress is `ResolveFutures` - async futures are not yet supported in the JS bindings.
@@ -979,13 +1242,20 @@ fn progress_to_result<T>(
     progress: RunProgress<T>,
     print_callback: Option<JsPrintCallbackRef>,
     script_name: String,
-) -> Either3<MontySnapshot, MontyComplete, JsMontyException>
+) -> Either4<MontySnapshot, MontyComplete, JsMontyException, MontyYield>
 where
     T: ResourceTracker + serde::Serialize + serde::de::DeserializeOwned,
     EitherSnapshot: FromSnapshot<T>,
 {
     match progress {
-        RunProgress::Complete(result) => Either3::B(MontyComplete { output_value: result }),
+        RunProgress::Complete {
+            value: result,
+            memory_profile,
+            ..
+        } => Either4::B(MontyComplete {
+            output_value: result,
+            profile: memory_profile,
+        }),
         RunProgress::FunctionCall {
             function_name,
             args,
@@ -994,7 +1264,7 @@ where
             ..
         } => {
             // Store args/kwargs as MontyObject directly for serialization
-            Either3::A(MontySnapshot {
+            Either4::A(MontySnapshot {
                 snapshot: EitherSnapshot::from_snapshot(state),
                 script_name,
                 function_name,
@@ -1003,12 +1273,21 @@ where
                 print_callback,
             })
         }
-        RunProgress::ResolveFutures(_) => {
+        RunProgress::ResolveFutures(_) | RunProgress::FunctionCallBatch { .. } => {
             panic!("Async futures (ResolveFutures) are not yet supported in the JS bindings")
         }
         RunProgress::OsCall { function, .. } => {
             panic!("OS calls are not yet supported in the JS bindings: {function:?}")
         }
+        RunProgress::Yield { value, state } => Either4::D(MontyYield {
+            snapshot: EitherSnapshot::from_snapshot(state),
+            script_name,
+            value,
+            print_callback,
+        }),
+        RunProgress::Checkpoint(_) => {
+            panic!("Checkpoints are not yet supported in the JS bindings")
+        }
     }
 }
 
@@ -1029,6 +1308,16 @@ impl FromSnapshot<LimitedTracker> for EitherSnapshot {
     }
 }
 
+/// Converts an `ExceptionInput` to the exception raised inside Monty.
+fn exception_input_to_monty(exc: ExceptionInput) -> Result<MontyException> {
+    let mut monty_exc = MontyException::new(string_to_exc_type(&exc.r#type)?, Some(exc.message.clone()));
+    for note in exc.notes.unwrap_or_default() {
+        monty_exc.add_note(note);
+    }
+    let host_error = host_error(exc.r#type, Some(exc.message), exc.stack, exc.host_error_id);
+    Ok(monty_exc.with_host_error(host_error))
+}
+
 /// Converts a string exception type to `ExcType`.
 fn string_to_exc_type(type_name: &str) -> Result<ExcType> {
     type_name
@@ -1083,6 +1372,22 @@ struct SerializedSnapshotOwned {
     kwargs: Vec<(MontyObject, MontyObject)>,
 }
 
+/// Serialization wrapper for `MontyYield` using borrowed references.
+#[derive(serde::Serialize)]
+struct SerializedYield<'a> {
+    snapshot: &'a EitherSnapshot,
+    script_name: &'a str,
+    value: &'a MontyObject,
+}
+
+/// Owned version of `SerializedYield` for deserialization.
+#[derive(serde::Deserialize)]
+struct SerializedYieldOwned {
+    snapshot: EitherSnapshot,
+    script_name: String,
+    value: MontyObject,
+}
+
 // =============================================================================
 // External function support
 // =============================================================================
@@ -1125,15 +1430,7 @@ fn call_external_function(
 
     // If we have kwargs, add them as a final object argument
     if !kwargs.is_empty() {
-        let mut kwargs_obj = Object::new(env)?;
-        for (key, value) in kwargs {
-            let key_str = match key {
-                MontyObject::String(s) => s.clone(),
-                _ => format!("{key:?}"),
-            };
-            kwargs_obj.set_named_property(&key_str, monty_to_js(value, env)?)?;
-        }
-        js_args.push(kwargs_obj.raw());
+        js_args.push(kwargs_to_js(kwargs, env)?.raw());
     }
 
     // Get undefined for the 'this' argument
@@ -1200,11 +1497,37 @@ fn extract_js_exception(exception_obj: Object<'_>) -> MontyException {
     // Try to get the 'message' property
     let message: std::result::Result<String, _> = exception_obj.get_named_property("message");
 
+    // The stack, and the id the JS wrapper assigns to errors it records
+    let stack: std::result::Result<String, _> = exception_obj.get_named_property("stack");
+    let host_error_id: std::result::Result<u32, _> = exception_obj.get_named_property("hostErrorId");
+
+    let name = name.ok();
     let exc_type = name
-        .ok()
-        .and_then(|n| string_to_exc_type(&n).ok())
+        .as_deref()
+        .and_then(|n| string_to_exc_type(n).ok())
         .unwrap_or(ExcType::RuntimeError);
     let msg = message.ok();
 
-    MontyException::new(exc_type, msg)
+    let host_error = host_error(
+        name.unwrap_or_else(|| "Error".to_string()),
+        msg.clone(),
+        stack.ok(),
+        host_error_id.ok(),
+    );
+    MontyException::new(exc_type, msg).with_host_error(host_error)
+}
+
+/// Builds the `HostError` describing a JS error raised by an external function.
+///
+/// The original `Error` can't be stored in the core exception, so only the id the JS wrapper
+/// assigned to it is kept, and the wrapper maps it back when the exception escapes.
+fn host_error(name: String, message: Option<String>, stack: Option<String>, id: Option<u32>) -> HostError {
+    let mut host_error = HostError::new(name, message);
+    if let Some(stack) = stack {
+        host_error = host_error.with_traceback(stack);
+    }
+    if let Some(id) = id {
+        host_error = host_error.with_original(id);
+    }
+    host_error
 }
@@ -5,13 +5,16 @@ import type {
   ExceptionInfo,
   ExceptionInput,
   Frame,
+  HostErrorInfo,
   JsMontyObject,
+  MemoryProfile,
   MontyOptions,
   ResourceLimits,
   ResumeOptions,
   RunOptions,
   SnapshotLoadOptions,
   StartOptions,
+  TracebackDisplayOptions,
 } from './index.js'
 
 import {
@@ -19,6 +22,7 @@ import {
   MontyRepl as NativeMontyRepl,
   MontySnapshot as NativeMontySnapshot,
   MontyComplete as NativeMontyComplete,
+  MontyYield as NativeMontyYield,
   MontyException as NativeMontyException,
   MontyTypingError as NativeMontyTypingError,
 } from './index.js'
@@ -32,8 +36,11 @@ export type {
   StartOptions,
   ResumeOptions,
   ExceptionInput,
+  HostErrorInfo,
   SnapshotLoadOptions,
+  TracebackDisplayOptions,
   JsMontyObject,
+  MemoryProfile,
 }
 
 /**
@@ -72,6 +79,23 @@ export class MontyError extends Error {
     }
   }
 
+  /**
+   * The name of the inner Python exception type, e.g. `'ValueError'`.
+   */
+  get typeName(): string {
+    return this._typeName
+  }
+
+  /**
+   * The traceback frames where the error occurred, outermost first.
+   *
+   * Empty for syntax and typing errors; `MontyRuntimeError` overrides this with its traceback.
+   * Use together with `typeName` and `exception.message` to build custom error UIs.
+   */
+  get frames(): Frame[] {
+    return []
+  }
+
   /**
    * Returns formatted exception string.
    * @param format - 'type-msg' for 'ExceptionType: message', 'msg' for just the message
@@ -135,6 +159,13 @@ export class MontyRuntimeError extends MontyError {
   private _tracebackString: string | null
   private _frames: Frame[] | null
 
+  /**
+   * The original value thrown by an external function, if that's where this error came from.
+   *
+   * Set by `Monty.run()` and `runMontyAsync()`, so the caller can `throw err.hostError`.
+   */
+  hostError: unknown = undefined
+
   constructor(
     nativeOrTypeName: NativeMontyException | string,
     message?: string,
@@ -171,13 +202,37 @@ export class MontyRuntimeError extends MontyError {
     return this._frames || []
   }
 
+  /**
+   * Notes attached to the exception with `add_note()`, in the order they were added.
+   */
+  get notes(): string[] {
+    return this._native ? this._native.notes : []
+  }
+
+  /**
+   * Name, message and stack of the JS error thrown by an external function, if that's where this error came from.
+   */
+  get hostErrorInfo(): HostErrorInfo | null {
+    return this._native ? (this._native.hostError ?? null) : null
+  }
+
+  /**
+   * The traceback frames where the error occurred, outermost first.
+   *
+   * Equivalent to `traceback()`.
+   */
+  override get frames(): Frame[] {
+    return this.traceback()
+  }
+
   /**
    * Returns formatted exception string.
    * @param format - 'traceback' for full traceback, 'type-msg' for 'ExceptionType: message', 'msg' for just the message
+   * @param options - Traceback style ('long', 'short' or 'line'), frame limit and internal files, used with 'traceback'
    */
-  display(format: 'traceback' | 'type-msg' | 'msg' = 'traceback'): string {
+  display(format: 'traceback' | 'type-msg' | 'msg' = 'traceback', options?: TracebackDisplayOptions): string {
     if (this._native && typeof this._native.display === 'function') {
-      return this._native.display(format)
+      return this._native.display(format, options)
     }
     // Fallback for legacy constructor
     switch (format) {
@@ -295,9 +350,11 @@ export class Monty {
    * @throws {MontyRuntimeError} If the code raises an exception
    */
   run(options?: RunOptions): JsMontyObject {
-    const result = this._native.run(options)
+    const hostErrors = new HostErrorRegistry()
+    const externalFunctions = options?.externalFunctions && hostErrors.wrap(options.externalFunctions)
+    const result = this._native.run(externalFunctions ? { ...options, externalFunctions } : options)
     if (result instanceof NativeMontyException) {
-      throw new MontyRuntimeError(result)
+      throw hostErrors.attach(new MontyRuntimeError(result))
     }
     return result
   }
@@ -306,30 +363,41 @@ export class Monty {
    * Starts execution and returns either a snapshot (paused at external call) or completion.
    *
    * @param options - Execution options (inputs, limits)
-   * @returns MontySnapshot if an external function call is pending, MontyComplete if done
+   * @returns MontySnapshot if an external function call is pending, MontyYield if the code
+   *   streamed a value with `monty_yield()`, MontyComplete if done
    * @throws {MontyRuntimeError} If the code raises an exception
    */
-  start(options?: StartOptions): MontySnapshot | MontyComplete {
+  start(options?: StartOptions): MontySnapshot | MontyYield | MontyComplete {
     const result = this._native.start(options)
     return wrapStartResult(result)
   }
 
   /**
    * Serializes the Monty instance to a binary format.
+   *
+   * @param signingKey - Optional key to sign the data with using HMAC-SHA256
    */
-  dump(): Buffer {
-    return this._native.dump()
+  dump(signingKey?: Buffer): Buffer {
+    return this._native.dump(signingKey)
   }
 
   /**
    * Deserializes a Monty instance from binary format.
+   *
+   * @param signingKey - If set, the data must have been dumped with the same key
+   * @throws {Error} If the data can't be deserialized or the signature is invalid
    */
-  static load(data: Buffer): Monty {
+  static load(data: Buffer, signingKey?: Buffer): Monty {
     const instance = Object.create(Monty.prototype) as Monty
-    instance._native = NativeMonty.load(data)
+    instance._native = NativeMonty.load(data, signingKey)
     return instance
   }
 
+  /** SHA-256 hash of the source code as a hex string, preserved by `dump()` and `load()`. */
+  get codeHash(): string {
+    return this._native.codeHash
+  }
+
   /** Returns the script name. */
   get scriptName(): string {
     return this._native.scriptName
@@ -387,11 +455,13 @@ export class MontyRepl {
    * Executes one incremental snippet.
    *
    * @param code - Snippet code to execute
+   * @param namespace - Run the snippet in this named namespace, which has its own globals but
+   *   shares the session heap. Namespaces are created on first use; omit for the default namespace.
    * @returns Snippet output
    * @throws {MontyRuntimeError} If execution raises an exception
    */
-  feed(code: string): JsMontyObject {
-    const result = this._native.feed(code)
+  feed(code: string, namespace?: string): JsMontyObject {
+    const result = this._native.feed(code, namespace)
     if (result instanceof NativeMontyException) {
       throw new MontyRuntimeError(result)
     }
@@ -414,18 +484,79 @@ export class MontyRepl {
   }
 }
 
+/**
+ * Remembers errors thrown by external functions, so the original can be attached to the
+ * `MontyRuntimeError` as `hostError` if it propagates out of the sandbox.
+ *
+ * Monty only keeps the id of each recorded error, the errors themselves stay in JS.
+ */
+class HostErrorRegistry {
+  private _errors: unknown[] = []
+
+  /**
+   * Records a thrown value, returning the exception to raise inside Monty in its place.
+   */
+  record(error: unknown): ExceptionInput {
+    const err = error as Partial<Error> | null | undefined
+    this._errors.push(error)
+    return {
+      type: err?.name || 'RuntimeError',
+      message: err?.message || String(error),
+      stack: typeof err?.stack === 'string' ? err.stack : undefined,
+      hostErrorId: this._errors.length - 1,
+    }
+  }
+
+  /**
+   * Wraps external functions so that errors they throw are recorded.
+   */
+  wrap(functions: object): Record<string, unknown> {
+    const wrapped: Record<string, unknown> = {}
+    for (const [name, func] of Object.entries(functions)) {
+      if (typeof func !== 'function') {
+        wrapped[name] = func
+        continue
+      }
+      wrapped[name] = (...args: unknown[]) => {
+        try {
+          return func(...args)
+        } catch (error) {
+          const exc = this.record(error)
+          // thrown in place of the original, the native layer reads these properties
+          throw { name: exc.type, message: exc.message, stack: exc.stack, hostErrorId: exc.hostErrorId }
+        }
+      }
+    }
+    return wrapped
+  }
+
+  /**
+   * Sets `hostError` on a runtime error from the original error recorded for it, if any.
+   */
+  attach(error: MontyRuntimeError): MontyRuntimeError {
+    const id = error.hostErrorInfo?.id
+    if (id !== undefined && id !== null && id < this._errors.length) {
+      error.hostError = this._errors[id]
+    }
+    return error
+  }
+}
+
 /**
  * Helper to wrap native start/resume results, throwing errors as needed.
  */
 function wrapStartResult(
-  result: NativeMontySnapshot | NativeMontyComplete | NativeMontyException,
-): MontySnapshot | MontyComplete {
+  result: NativeMontySnapshot | NativeMontyComplete | NativeMontyException | NativeMontyYield,
+): MontySnapshot | MontyYield | MontyComplete {
   if (result instanceof NativeMontyException) {
     throw new MontyRuntimeError(result)
   }
   if (result instanceof NativeMontySnapshot) {
     return new MontySnapshot(result)
   }
+  if (result instanceof NativeMontyYield) {
+    return new MontyYield(result)
+  }
   if (result instanceof NativeMontyComplete) {
     return new MontyComplete(result)
   }
@@ -469,10 +600,11 @@ export class MontySnapshot {
    * Resumes execution with either a return value or an exception.
    *
    * @param options - Object with either `returnValue` or `exception`
-   * @returns MontySnapshot if another external call is pending, MontyComplete if done
+   * @returns MontySnapshot if another external call is pending, MontyYield if the code
+   *   streamed a value with `monty_yield()`, MontyComplete if done
    * @throws {MontyRuntimeError} If the code raises an exception
    */
-  resume(options: ResumeOptions): MontySnapshot | MontyComplete {
+  resume(options: ResumeOptions): MontySnapshot | MontyYield | MontyComplete {
     const result = this._native.resume(options)
     return wrapStartResult(result)
   }
@@ -498,6 +630,62 @@ export class MontySnapshot {
   }
 }
 
+/**
+ * Represents paused execution that streamed a partial result with `monty_yield(value)`.
+ *
+ * Resuming continues execution, `monty_yield()` returns the value passed to `resume()`.
+ */
+export class MontyYield {
+  private _native: NativeMontyYield
+
+  constructor(nativeYield: NativeMontyYield) {
+    this._native = nativeYield
+  }
+
+  /** Returns the name of the script being executed. */
+  get scriptName(): string {
+    return this._native.scriptName
+  }
+
+  /** Returns the value passed to `monty_yield()`. */
+  get value(): JsMontyObject {
+    return this._native.value
+  }
+
+  /**
+   * Resumes execution, `monty_yield()` returns `returnValue`, or `null` if no options are given.
+   *
+   * @param options - Optional object with either `returnValue` or `exception`
+   * @returns MontySnapshot if an external call is pending, MontyYield if the code streamed
+   *   another value, MontyComplete if done
+   * @throws {MontyRuntimeError} If the code raises an exception
+   */
+  resume(options?: ResumeOptions): MontySnapshot | MontyYield | MontyComplete {
+    const result = this._native.resume(options)
+    return wrapStartResult(result)
+  }
+
+  /**
+   * Serializes the MontyYield to a binary format.
+   */
+  dump(): Buffer {
+    return this._native.dump()
+  }
+
+  /**
+   * Deserializes a MontyYield from binary format.
+   */
+  static load(data: Buffer, options?: SnapshotLoadOptions): MontyYield {
+    const nativeYield = NativeMontyYield.load(data, options)
+    return new MontyYield(nativeYield)
+  }
+
+  /** Returns a string representation of the MontyYield. */
+  repr(): string {
+    return this._native.repr()
+  }
+}
+
 /**
  * Represents completed execution with a final output value.
  */
@@ -513,6 +701,14 @@ export class MontyComplete {
     return this._native.output
   }
 
+  /**
+   * Returns the live heap objects when execution finished, including the output and global
+   * variables, or `null` if no profile was recorded for the run.
+   */
+  get memoryProfile(): MemoryProfile | null {
+    return this._native.memoryProfile
+  }
+
   /** Returns a string representation of the MontyComplete. */
   repr(): string {
     return this._native.repr()
@@ -525,10 +721,48 @@ export class MontyComplete {
 export interface RunMontyAsyncOptions {
   /** Input values for the script. */
   inputs?: Record<string, JsMontyObject>
-  /** External function implementations (sync or async). */
+  /**
+   * External function implementations (sync or async).
+   *
+   * Functions receive the positional arguments, followed by an object of keyword arguments
+   * if any were passed.
+   */
   externalFunctions?: Record<string, (...args: unknown[]) => unknown>
   /** Resource limits. */
   limits?: ResourceLimits
+  /**
+   * Timeouts and retries for external functions, by function name.
+   *
+   * When the last attempt times out, `ExternalCallTimeout` is raised inside the sandbox,
+   * where it can be caught like any `TimeoutError`.
+   */
+  callPolicies?: Record<string, CallPolicy>
+  /**
+   * Callback for values streamed with `monty_yield(value)`, sync or async.
+   *
+   * Its (awaited) return value is returned by `monty_yield()` and errors it throws are raised
+   * there. Without a callback, `monty_yield()` raises `NotImplementedError`.
+   */
+  yieldCallback?: (value: JsMontyObject) => unknown
+}
+
+/**
+ * Timeout and retry settings for calls to an external function, see `RunMontyAsyncOptions.callPolicies`.
+ */
+export interface CallPolicy {
+  /**
+   * Maximum time in seconds to wait for each attempt.
+   *
+   * Only promises can time out: a synchronous function blocks the event loop until it returns.
+   * An attempt which times out isn't cancelled, its result is discarded.
+   */
+  timeoutSecs?: number
+  /** Number of attempts after the first, made when an attempt times out or throws an error accepted by `retryOn`. */
+  retries?: number
+  /** Delay in seconds before the first retry, doubled before each further retry. */
+  backoffSecs?: number
+  /** Whether an error thrown by the function should be retried, rather than raised in the sandbox straight away. */
+  retryOn?: (error: unknown) => boolean
 }
 
 /**
@@ -561,14 +795,68 @@ export interface RunMontyAsyncOptions {
  * });
  */
 export async function runMontyAsync(montyRunner: Monty, options: RunMontyAsyncOptions = {}): Promise<JsMontyObject> {
-  const { inputs, externalFunctions = {}, limits } = options
+  const { inputs, externalFunctions = {}, limits, callPolicies = {}, yieldCallback } = options
+  const hostErrors = new HostErrorRegistry()
+
+  try {
+    return await runMontyAsyncLoop(
+      montyRunner,
+      inputs,
+      externalFunctions,
+      limits,
+      callPolicies,
+      yieldCallback,
+      hostErrors,
+    )
+  } catch (error) {
+    if (error instanceof MontyRuntimeError) {
+      throw hostErrors.attach(error)
+    }
+    throw error
+  }
+}
 
-  let progress: MontySnapshot | MontyComplete = montyRunner.start({
+/**
+ * The start/resume loop of `runMontyAsync`, recording errors thrown by external functions in `hostErrors`.
+ */
+async function runMontyAsyncLoop(
+  montyRunner: Monty,
+  inputs: Record<string, JsMontyObject> | undefined,
+  externalFunctions: Record<string, (...args: unknown[]) => unknown>,
+  limits: ResourceLimits | undefined,
+  callPolicies: Record<string, CallPolicy>,
+  yieldCallback: ((value: JsMontyObject) => unknown) | undefined,
+  hostErrors: HostErrorRegistry,
+): Promise<JsMontyObject> {
+  let progress: MontySnapshot | MontyYield | MontyComplete = montyRunner.start({
     inputs,
     limits,
   })
 
-  while (progress instanceof MontySnapshot) {
+  while (!(progress instanceof MontyComplete)) {
+    if (progress instanceof MontyYield) {
+      const yielded = progress
+      if (!yieldCallback) {
+        progress = yielded.resume({
+          exception: {
+            type: 'NotImplementedError',
+            message: 'monty_yield() called but no yieldCallback provided',
+          },
+        })
+        continue
+      }
+      let result: unknown
+      try {
+        result = await yieldCallback(yielded.value)
+      } catch (error) {
+        // The callback threw an exception - record it and raise it from monty_yield()
+        progress = yielded.resume({ exception: hostErrors.record(error) })
+        continue
+      }
+      progress = yielded.resume({ returnValue: result })
+      continue
+    }
+
     const snapshot = progress
     const funcName = snapshot.functionName
     const extFunction = externalFunctions[funcName]
@@ -585,29 +873,78 @@ export async function runMontyAsync(montyRunner: Monty, options: RunMontyAsyncOp
     }
 
     try {
-      // Call the external function
-      let result = extFunction(...snapshot.args, snapshot.kwargs)
-
-      // If the result is a Promise, await it
-      if (result && typeof (result as Promise<unknown>).then === 'function') {
-        result = await result
-      }
+      // Call the external function, with kwargs as a final object argument like `Monty.run()`
+      const kwargs = snapshot.kwargs
+      const args = Object.keys(kwargs).length > 0 ? [...snapshot.args, kwargs] : snapshot.args
+      const result = await callWithPolicy(funcName, () => extFunction(...args), callPolicies[funcName])
 
       // Resume with the return value
       progress = snapshot.resume({ returnValue: result })
     } catch (error) {
-      // External function threw an exception - convert to Monty exception
-      const err = error as Error
-      const excType = err.name || 'RuntimeError'
-      const excMessage = err.message || String(error)
-      progress = snapshot.resume({
-        exception: {
-          type: excType,
-          message: excMessage,
-        },
-      })
+      if (error instanceof ExternalCallTimeout) {
+        progress = snapshot.resume({ exception: { type: 'ExternalCallTimeout', message: error.message } })
+      } else {
+        // External function threw an exception - record it and raise it inside Monty
+        progress = snapshot.resume({ exception: hostErrors.record(error) })
+      }
     }
   }
 
   return progress.output
 }
+
+/**
+ * Thrown by `callWithPolicy` when the last attempt allowed by a call policy times out.
+ */
+class ExternalCallTimeout extends Error {}
+
+/** Rejection reason of an attempt which timed out, see `callWithTimeout`. */
+const TIMED_OUT = Symbol('timed out')
+
+/**
+ * Calls an external function, awaiting its result and retrying attempts as its call policy allows.
+ */
+async function callWithPolicy(funcName: string, call: () => unknown, policy: CallPolicy | undefined): Promise<unknown> {
+  if (!policy) {
+    return await call()
+  }
+  const attempts = (policy.retries ?? 0) + 1
+  let backoffSecs = policy.backoffSecs ?? 0
+  for (let attempt = 1; ; attempt++) {
+    try {
+      return await callWithTimeout(call, policy.timeoutSecs)
+    } catch (error) {
+      const timedOut = error === TIMED_OUT
+      if (attempt === attempts && timedOut) {
+        const suffix = attempts > 1 ? ` on all ${attempts} attempts` : ''
+        throw new ExternalCallTimeout(
+          `external function '${funcName}' timed out after ${policy.timeoutSecs}s${suffix}`,
+        )
+      }
+      if (attempt === attempts || !(timedOut || policy.retryOn?.(error))) {
+        throw error
+      }
+    }
+    await new Promise((resolve) => setTimeout(resolve, backoffSecs * 1000))
+    backoffSecs *= 2
+  }
+}
+
+/**
+ * Calls `call` and awaits its result, rejecting with `TIMED_OUT` if it takes longer than `timeoutSecs`.
+ */
+async function callWithTimeout(call: () => unknown, timeoutSecs: number | undefined): Promise<unknown> {
+  const result = call()
+  if (timeoutSecs === undefined) {
+    return await result
+  }
+  let timer: ReturnType<typeof setTimeout> | undefined
+  const timeout = new Promise<never>((_, reject) => {
+    timer = setTimeout(() => reject(TIMED_OUT), timeoutSecs * 1000)
+  })
+  try {
+    return await Promise.race([result, timeout])
+  } finally {
+    clearTimeout(timer)
+  }
+}
@@ -7,36 +7,46 @@ if TYPE_CHECKING:
     from types import EllipsisType
 
 from ._monty import (
+    ExternalCallTimeout,
     Frame,
     Monty,
     MontyComplete,
     MontyError,
     MontyFutureSnapshot,
     MontyRepl,
+    MontyReplFutureSnapshot,
+    MontyReplSnapshot,
     MontyRuntimeError,
     MontySnapshot,
     MontySyntaxError,
     MontyTypingError,
+    MontyYield,
     __version__,
 )
 from .os_access import AbstractFile, AbstractOS, CallbackFile, MemoryFile, OSAccess, OsFunction, StatResult
+from .type_stubs import build_type_stubs
 
 __all__ = (
     # this file
     'run_monty_async',
     'ExternalResult',
     'ResourceLimits',
+    'CallPolicy',
     # _monty
     '__version__',
     'Monty',
     'MontyRepl',
+    'MontyReplSnapshot',
+    'MontyReplFutureSnapshot',
     'MontyComplete',
     'MontySnapshot',
     'MontyFutureSnapshot',
+    'MontyYield',
     'MontyError',
     'MontySyntaxError',
     'MontyRuntimeError',
     'MontyTypingError',
+    'ExternalCallTimeout',
     'Frame',
     # os_access
     'StatResult',
@@ -46,6 +56,8 @@ __all__ = (
     'MemoryFile',
     'CallbackFile',
     'OSAccess',
+    # type_stubs
+    'build_type_stubs',
 )
 T = TypeVar('T')
 
@@ -56,8 +68,9 @@ async def run_monty_async(
     inputs: dict[str, Any] | None = None,
     external_functions: dict[str, Callable[..., Any]] | None = None,
     limits: ResourceLimits | None = None,
-    print_callback: Callable[[Literal['stdout'], str], None] | None = None,
+    print_callback: Callable[[Literal['stdout', 'stderr'], str], None] | None = None,
     os: AbstractOS | None = None,
+    yield_callback: Callable[[Any], Any] | None = None,
 ) -> Any:
     """Run a Monty script with async external functions and optional OS access.
 
@@ -71,6 +84,7 @@ async def run_monty_async(
         limits: The resource limits to use.
         print_callback: A callback to use for printing.
         os: Optional OS access handler for filesystem operations (e.g., OSAccess instance).
+        yield_callback: Optional callback for values streamed with `monty_yield()`, see `Monty.run()`.
 
     Returns:
         The output of the Monty script.
@@ -130,6 +144,17 @@ async def run_monty_async(
                     else:
                         e = KeyError(f'Function {progress.function_name} not found')
                         progress = await run_in_pool(partial(progress.resume, exception=e))
+                elif isinstance(progress, MontyYield):
+                    if yield_callback is None:
+                        e = NotImplementedError('monty_yield() called but no yield_callback provided')
+                        progress = await run_in_pool(partial(progress.resume, exception=e))
+                    else:
+                        try:
+                            result = yield_callback(progress.value)
+                        except Exception as exc:
+                            progress = await run_in_pool(partial(progress.resume, exception=exc))
+                        else:
+                            progress = await run_in_pool(partial(progress.resume, return_value=result))
                 else:
                     assert isinstance(progress, MontyFutureSnapshot), f'Unexpected progress type {progress!r}'
 
@@ -188,6 +213,62 @@ class ResourceLimits(TypedDict, total=False):
     max_recursion_depth: int
     """Maximum function call stack depth (default: 1000)."""
 
+    max_output_size: int
+    """Maximum size in bytes of the result and of arguments passed to external functions.
+
+    Sizes are estimated from the values' contents, exceeding the limit raises `MemoryError`.
+    """
+
+    max_interned_size: int
+    """Maximum size in bytes of interned string, bytes and integer literals.
+
+    Interned literals accumulate across REPL snippets, snippets exceeding the limit raise `MemoryError`.
+    Only enforced by `MontyRepl`.
+    """
+
+    max_stack_size: int
+    """Maximum stack usage in bytes of the interpreter before raising `RecursionError` (default: 1MiB).
+
+    Guards against overflowing the stack of the thread running Monty, which would crash the process.
+    """
+
+    max_ndarray_size: int
+    """Maximum number of elements of numpy array inputs, which are converted to nested lists.
+
+    numpy arrays are rejected unless this is set.
+    """
+
+    trace_allocations: bool
+    """Record the source lines allocating heap objects, reported by `MontyRepl.memory_profile()`."""
+
+
+class CallPolicy(TypedDict, total=False):
+    """
+    Timeout and retry settings for calls to an external function, passed to `Monty.run(call_policies=...)`.
+
+    All settings are optional, a call without a timeout waits for the function to return.
+    """
+
+    timeout: float
+    """Maximum time in seconds to wait for each attempt.
+
+    Calls with a timeout run on the `executor`, or on a thread of their own without one.
+    Python threads can't be interrupted, so a call which times out keeps running in the background
+    and its result is discarded.
+    """
+
+    retries: int
+    """Number of attempts after the first, made when an attempt times out or raises a `retry_on` exception."""
+
+    backoff: float
+    """Delay in seconds before the first retry, doubled before each further retry."""
+
+    retry_on: type[BaseException] | tuple[type[BaseException], ...]
+    """Exception types raised by the function which are retried, rather than raised in the sandbox straight away.
+
+    When the last attempt raises, its exception is raised in the sandbox.
+    """
+
 
 class ExternalReturnValue(TypedDict):
     return_value: Any
@@ -1,9 +1,11 @@
+from collections.abc import MutableMapping
+from concurrent.futures import Executor
 from types import EllipsisType
-from typing import Any, Callable, Literal, final, overload
+from typing import Any, Callable, Literal, Sequence, final, overload
 
 from typing_extensions import Self
 
-from . import ExternalResult, ResourceLimits
+from . import CallPolicy, ExternalResult, ResourceLimits
 from .os_access import OsFunction
 
 __all__ = [
@@ -13,10 +15,12 @@ __all__ = [
     'MontyComplete',
     'MontySnapshot',
     'MontyFutureSnapshot',
+    'MontyYield',
     'MontyError',
     'MontySyntaxError',
     'MontyRuntimeError',
     'MontyTypingError',
+    'ExternalCallTimeout',
     'Frame',
 ]
 __version__: str
@@ -36,28 +40,37 @@ class Monty:
         code: str,
         *,
         script_name: str = 'main.py',
+        module_name: str | None = None,
         inputs: list[str] | None = None,
         external_functions: list[str] | None = None,
         type_check: bool = False,
         type_check_stubs: str | None = None,
         dataclass_registry: list[type] | None = None,
+        constants: dict[str, Any] | None = None,
+        optimize: bool = False,
     ) -> Self:
         """
         Create a new Monty interpreter by parsing the given code.
 
         Arguments:
             code: Python code to execute
-            script_name: Name used in tracebacks and error messages
+            script_name: Name used in tracebacks and error messages, also the value of `__file__`
+            module_name: Value of `__name__` in the code, `'__main__'` if not set
             inputs: List of input variable names available in the code
             external_functions: List of external function names the code can call
             type_check: Whether to perform type checking on the code (default: True)
             type_check_stubs: Optional code to prepend before type checking,
-                e.g. with input variable declarations or external function signatures
+                e.g. with input variable declarations or external function signatures,
+                see `build_type_stubs()` to generate these from sample inputs and callables
             dataclass_registry: Optional list of dataclass types to register for proper
                 isinstance() support on output, see `register_dataclass()` above.
+            constants: Names and values baked into the code when it is compiled. Unlike inputs they
+                can't be reassigned; values must be `None`, `bool`, `int`, `float`, `str` or `bytes`.
+            optimize: Whether to remove assert statements and set `__debug__` to `False`, like `python -O`,
+                e.g. for production runs where asserts are only diagnostics.
 
         Raises:
-            MontySyntaxError: If the code cannot be parsed
+            MontySyntaxError: If the code cannot be parsed or assigns to a constant
             MontyTypingError: If type_check is True and type errors are found
         """
 
@@ -84,8 +97,15 @@ class Monty:
         inputs: dict[str, Any] | None = None,
         limits: ResourceLimits | None = None,
         external_functions: dict[str, Callable[..., Any]] | None = None,
-        print_callback: Callable[[Literal['stdout'], str], None] | None = None,
+        print_callback: Callable[[Literal['stdout', 'stderr'], str], None] | None = None,
         os: Callable[[OsFunction, tuple[Any, ...]], Any] | None = None,
+        zero_copy: bool = False,
+        executor: Executor | None = None,
+        audit_callback: Callable[[dict[str, Any]], None] | None = None,
+        call_policies: dict[str, CallPolicy] | None = None,
+        pure: list[str] | None = None,
+        cache: MutableMapping[str, Any] | None = None,
+        yield_callback: Callable[[Any], Any] | None = None,
     ) -> Any:
         """
         Execute the code and return the result.
@@ -101,6 +121,32 @@ class Monty:
                 Called with (function_name, args) where function_name is like 'Path.exists'
                 and args is a tuple of arguments. Must return the appropriate value for the
                 OS function (e.g., bool for exists(), stat_result for stat()).
+            zero_copy: Read top-level `str` inputs from the strings' own UTF-8 buffers instead
+                of copying them into the interpreter, useful for large documents. The strings
+                are released when the run finishes. Strings nested in containers are always copied.
+            executor: Optional `concurrent.futures.Executor` to dispatch external function calls to.
+                Calls are submitted to the executor and the run waits for their results, so
+                external functions run on its worker threads. One executor can be shared by
+                many concurrent runs, e.g. to bound how many callbacks run at once.
+            audit_callback: Optional callback for security-relevant events, e.g. to feed a SIEM.
+                Called with a dict with `event` (`'external_call'`, `'os_call'`, `'import'`,
+                `'resource_limit'` or `'capability_denied'`), `timestamp` (seconds since the epoch)
+                and the event's fields. Calls include `args_digest`, a stable hex digest of the
+                arguments, rather than the arguments themselves. Exceptions raised by the callback
+                are reported as unraisable and don't stop execution.
+            call_policies: Optional timeouts and retries for external functions, by function name.
+                When the last attempt times out, `ExternalCallTimeout` is raised inside the
+                sandbox, where it can be caught like any `TimeoutError`.
+            pure: Names of external functions whose results only depend on their arguments.
+                Their results are memoized for the rest of the run, so repeating a call with
+                equal arguments doesn't call the function again. Exceptions aren't memoized.
+            cache: Optional mapping to keep the results of `pure` functions in across runs.
+                Keys are the calls rendered like source code, e.g. `"lookup('a', limit=2)"`,
+                values are the functions' return values.
+            yield_callback: Optional callback for values streamed with `monty_yield(value)`.
+                Called with each value as it's yielded, its return value is returned by
+                `monty_yield()` and exceptions it raises are raised there. Without a callback,
+                `monty_yield()` raises `NotImplementedError`.
 
         Returns:
             The result of the last expression in the code
@@ -114,8 +160,9 @@ class Monty:
         *,
         inputs: dict[str, Any] | None = None,
         limits: ResourceLimits | None = None,
-        print_callback: Callable[[Literal['stdout'], str], None] | None = None,
-    ) -> MontySnapshot | MontyFutureSnapshot | MontyComplete:
+        print_callback: Callable[[Literal['stdout', 'stderr'], str], None] | None = None,
+        audit_callback: Callable[[dict[str, Any]], None] | None = None,
+    ) -> MontySnapshot | MontyFutureSnapshot | MontyYield | MontyComplete:
         """
         Start the code execution and return a progress object, or completion.
 
@@ -127,23 +174,30 @@ class Monty:
             inputs: Dict of input variable values (must match names from __init__)
             limits: Optional resource limits configuration
             print_callback: Optional callback for print output
+            audit_callback: Optional callback for security-relevant events, see `run()`.
+                It isn't preserved when snapshots are dumped and loaded.
 
         Returns:
             MontySnapshot if an external function call is pending,
             MontyFutureSnapshot if futures need to be resolved,
+            MontyYield if the code streamed a value with `monty_yield()`,
             MontyComplete if execution finished without external calls.
 
         Raises:
             MontyRuntimeError: If the code raises an exception during execution
         """
 
-    def dump(self) -> bytes:
+    def dump(self, *, signing_key: bytes | None = None) -> bytes:
         """
         Serialize the Monty instance to a binary format.
 
         The serialized data can be stored and later restored with `Monty.load()`.
         This allows caching parsed code to avoid re-parsing on subsequent runs.
 
+        Arguments:
+            signing_key: Optional key to sign the data with using HMAC-SHA256, so `load()`
+                can check the data wasn't modified.
+
         Returns:
             Bytes containing the serialized Monty instance.
 
@@ -155,13 +209,18 @@ class Monty:
     def load(
         data: bytes,
         *,
+        signing_key: bytes | None = None,
         dataclass_registry: list[type] | None = None,
     ) -> 'Monty':
         """
         Deserialize a Monty instance from binary format.
 
+        Serialized data includes compiled bytecode which is run without further checks, so
+        data from untrusted storage should be signed and loaded with `signing_key`.
+
         Arguments:
             data: The serialized Monty data from `dump()`
+            signing_key: If set, the data must have been dumped with the same `signing_key`.
             dataclass_registry: Optional list of dataclass types to register for proper
                 isinstance() support on output, see `register_dataclass()` above.
 
@@ -169,7 +228,16 @@ class Monty:
             A new Monty instance.
 
         Raises:
-            ValueError: If deserialization fails.
+            ValueError: If deserialization fails or the signature is invalid.
+        """
+
+    @property
+    def code_hash(self) -> str:
+        """
+        SHA-256 hash of the source code as a hex string.
+
+        Depends only on the code, so it's preserved by `dump()` and `load()`, useful for
+        cache keys and recording which code was run.
         """
 
     def register_dataclass(self, cls: type) -> None:
@@ -180,8 +248,12 @@ class Monty:
         an `UnknownDataclass`. By registering the original type, we can use it to
         instantiate a real instance of that dataclass.
 
+        Pydantic `BaseModel` subclasses can be registered too, they're rebuilt with
+        `model_construct()` on output. Model instances passed as inputs are registered
+        automatically and are frozen inside Monty.
+
         Arguments:
-            cls: The dataclass type to register.
+            cls: The dataclass or pydantic model type to register.
 
         Raises:
             TypeError: If the argument is not a dataclass type.
@@ -207,12 +279,15 @@ class MontyRepl:
         external_functions: list[str] | None = None,
         start_inputs: dict[str, Any] | None = None,
         limits: ResourceLimits | None = None,
-        print_callback: Callable[[Literal['stdout'], str], None] | None = None,
+        print_callback: Callable[[Literal['stdout', 'stderr'], str], None] | None = None,
         dataclass_registry: list[type] | None = None,
+        audit_callback: Callable[[dict[str, Any]], None] | None = None,
     ) -> tuple['MontyRepl', Any]:
         """
         Create a REPL session directly from source code.
 
+        `audit_callback` receives security-relevant events of every snippet, see `Monty.run()`.
+
         Returns `(repl, output)` where `output` is the initial execution result.
         """
 
@@ -224,10 +299,69 @@ class MontyRepl:
         self,
         code: str,
         *,
-        print_callback: Callable[[Literal['stdout'], str], None] | None = None,
+        namespace: str | None = None,
+        print_callback: Callable[[Literal['stdout', 'stderr'], str], None] | None = None,
     ) -> Any:
         """
         Execute one incremental snippet and return its output.
+
+        Arguments:
+            code: The snippet to execute.
+            namespace: Run the snippet in this named namespace, which has its own globals but shares the
+                session heap and limits. Namespaces are created on first use; `None` uses the default namespace.
+            print_callback: Optional callback for print output, kept for later snippets.
+        """
+
+    def feed_start(
+        self,
+        code: str,
+        *,
+        namespace: str | None = None,
+        print_callback: Callable[[Literal['stdout', 'stderr'], str], None] | None = None,
+    ) -> MontyReplSnapshot | MontyReplFutureSnapshot | MontyComplete:
+        """
+        Start executing a snippet, pausing at external function and OS calls.
+
+        Unlike `feed()`, external calls return a snapshot to resume instead of failing. The session
+        moves into the snapshot while the snippet runs, so `feed()`, `dump()` and the other methods
+        raise `RuntimeError` until the snippet completes or raises. Values passed to `monty_yield()`
+        are ignored.
+
+        Arguments are the same as for `feed()`.
+
+        Returns:
+            MontyReplSnapshot if an external function or OS call is pending,
+            MontyReplFutureSnapshot if futures need to be resolved,
+            MontyComplete if the snippet finished.
+
+        Raises:
+            MontySyntaxError: If the snippet doesn't compile, the session is kept.
+            MontyRuntimeError: If the snippet raises, the session is kept.
+        """
+
+    @staticmethod
+    def is_complete(code: str) -> bool:
+        """Whether `code` can run as is, rather than needing more lines in an interactive prompt."""
+
+    @staticmethod
+    def continuation_mode(code: str) -> Literal['complete', 'incomplete_implicit', 'incomplete_block']:
+        """
+        Whether `code` is complete, or what kind of input an interactive prompt should wait for.
+
+        `'incomplete_implicit'` means unclosed brackets or strings, and `'incomplete_block'` a block
+        header like `if x:`, where CPython waits for the block to end with a blank line.
+        """
+
+    @property
+    def namespaces(self) -> list[str]:
+        """Sorted names of the namespaces created by `feed(..., namespace=...)`."""
+
+    def memory_profile(self) -> dict[str, list[dict[str, Any]]]:
+        """Live heap objects per type, and allocation sites if `trace_allocations` is enabled.
+
+        Returns a dict with `types` (dicts with `type`, `count` and `bytes`) and `allocation_sites`
+        (dicts with `filename`, `line`, `count` and `bytes`), each sorted by total size, largest first.
+        Sizes are estimates, the same ones checked against `max_memory`.
         """
 
     def dump(self) -> bytes:
@@ -237,11 +371,81 @@ class MontyRepl:
     def load(
         data: bytes,
         *,
-        print_callback: Callable[[Literal['stdout'], str], None] | None = None,
+        print_callback: Callable[[Literal['stdout', 'stderr'], str], None] | None = None,
         dataclass_registry: list[type] | None = None,
     ) -> 'MontyRepl':
         """Restore a REPL session from bytes."""
 
+@final
+class MontyReplSnapshot:
+    """
+    A snippet started with `MontyRepl.feed_start()`, paused at an external function or OS call.
+
+    Unlike `MontySnapshot` it can't be serialized, the REPL session it holds returns to its
+    `MontyRepl` once the snippet completes or raises.
+    """
+
+    @property
+    def is_os_function(self) -> bool:
+        """Whether this snapshot is for an OS function call (e.g., Path.stat)."""
+
+    @property
+    def function_name(self) -> str | OsFunction:
+        """The name of the function being called, an `OsFunction` if `is_os_function` is `True`."""
+
+    @property
+    def args(self) -> tuple[Any, ...]:
+        """The positional arguments passed to the external function."""
+
+    @property
+    def kwargs(self) -> dict[str, Any]:
+        """The keyword arguments passed to the external function."""
+
+    @property
+    def call_id(self) -> int:
+        """The unique identifier for this external function call."""
+
+    @overload
+    def resume(self, *, return_value: Any) -> MontyReplSnapshot | MontyReplFutureSnapshot | MontyComplete:
+        """Resume the snippet with a return value, see `MontySnapshot.resume()`."""
+
+    @overload
+    def resume(
+        self, *, exception: BaseException | type[BaseException]
+    ) -> MontyReplSnapshot | MontyReplFutureSnapshot | MontyComplete:
+        """Resume the snippet by raising the exception in the Monty interpreter."""
+
+    @overload
+    def resume(
+        self, *, error: BaseException | type[BaseException]
+    ) -> MontyReplSnapshot | MontyReplFutureSnapshot | MontyComplete:
+        """Resume the snippet by raising the exception in the Monty interpreter, alias of `exception`."""
+
+    @overload
+    def resume(self, *, future: EllipsisType) -> MontyReplSnapshot | MontyReplFutureSnapshot | MontyComplete:
+        """Resume the snippet with a pending future."""
+
+    def __repr__(self) -> str: ...
+
+@final
+class MontyReplFutureSnapshot:
+    """A snippet started with `MontyRepl.feed_start()`, waiting for futures to be resolved."""
+
+    @property
+    def pending_call_ids(self) -> list[int]:
+        """The call IDs of the pending futures.
+
+        Raises an error if the snapshot has already been resumed.
+        """
+
+    def resume(
+        self,
+        results: dict[int, ExternalResult],
+    ) -> MontyReplSnapshot | MontyReplFutureSnapshot | MontyComplete:
+        """Resume the snippet with results for one or more futures, see `MontyFutureSnapshot.resume()`."""
+
+    def __repr__(self) -> str: ...
+
 @final
 class MontySnapshot:
     """
@@ -279,7 +483,7 @@ class MontySnapshot:
         """The unique identifier for this external function call."""
 
     @overload
-    def resume(self, *, return_value: Any) -> MontySnapshot | MontyFutureSnapshot | MontyComplete:
+    def resume(self, *, return_value: Any) -> MontySnapshot | MontyFutureSnapshot | MontyYield | MontyComplete:
         """Resume execution with a return value from the external function.
 
         `resume` may only be called once on each MontySnapshot instance.
@@ -288,12 +492,16 @@ class MontySnapshot:
 
         Arguments:
             return_value: The value to return from the external function call.
-            exception: An exception to raise in the Monty interpreter.
+            exception: An exception to raise in the Monty interpreter, an instance or a class.
+                Its type, message and notes are kept, and it's available again as the original
+                if it propagates out of the sandbox.
+            error: Alias of `exception`.
             future: A future to await in the Monty interpreter.
 
         Returns:
             MontySnapshot if another external function call is pending,
             MontyFutureSnapshot if futures need to be resolved,
+            MontyYield if the code streamed a value with `monty_yield()`,
             MontyComplete if execution finished.
 
         Raises:
@@ -303,14 +511,25 @@ class MontySnapshot:
         """
 
     @overload
-    def resume(self, *, exception: BaseException) -> MontySnapshot | MontyFutureSnapshot | MontyComplete:
+    def resume(
+        self, *, exception: BaseException | type[BaseException]
+    ) -> MontySnapshot | MontyFutureSnapshot | MontyYield | MontyComplete:
         """Resume execution by raising the exception in the Monty interpreter.
 
         See docstring for the first overload for more information.
         """
 
     @overload
-    def resume(self, *, future: EllipsisType) -> MontySnapshot | MontyFutureSnapshot | MontyComplete:
+    def resume(
+        self, *, error: BaseException | type[BaseException]
+    ) -> MontySnapshot | MontyFutureSnapshot | MontyYield | MontyComplete:
+        """Resume execution by raising the exception in the Monty interpreter, alias of `exception`.
+
+        See docstring for the first overload for more information.
+        """
+
+    @overload
+    def resume(self, *, future: EllipsisType) -> MontySnapshot | MontyFutureSnapshot | MontyYield | MontyComplete:
         """Resume execution by returning a pending future.
 
         No result is provided, we simply resume execution stating that a future is pending.
@@ -340,7 +559,7 @@ class MontySnapshot:
     def load(
         data: bytes,
         *,
-        print_callback: Callable[[Literal['stdout'], str], None] | None = None,
+        print_callback: Callable[[Literal['stdout', 'stderr'], str], None] | None = None,
         dataclass_registry: list[type] | None = None,
     ) -> 'MontySnapshot':
         """
@@ -364,6 +583,79 @@ class MontySnapshot:
 
     def __repr__(self) -> str: ...
 
+@final
+class MontyYield:
+    """
+    Represents a paused execution that streamed a partial result with `monty_yield(value)`.
+
+    Resuming continues execution, `monty_yield()` returns the value passed to `resume()`.
+    """
+
+    @property
+    def script_name(self) -> str:
+        """The name of the script being executed."""
+
+    @property
+    def value(self) -> Any:
+        """The value passed to `monty_yield()`."""
+
+    @overload
+    def resume(self, *, return_value: Any = None) -> MontySnapshot | MontyFutureSnapshot | MontyYield | MontyComplete:
+        """Resume execution, `monty_yield()` returns `return_value`.
+
+        `resume` may only be called once on each MontyYield instance.
+
+        The GIL is released allowing parallel execution.
+
+        Arguments:
+            return_value: The value for `monty_yield()` to return, `None` by default.
+
+        Returns:
+            MontySnapshot if an external function call is pending,
+            MontyFutureSnapshot if futures need to be resolved,
+            MontyYield if the code streamed another value,
+            MontyComplete if execution finished.
+
+        Raises:
+            TypeError: If more than one argument is provided.
+            RuntimeError: If execution has already completed.
+            MontyRuntimeError: If the code raises an exception during execution
+        """
+
+    @overload
+    def resume(
+        self, *, exception: BaseException | type[BaseException]
+    ) -> MontySnapshot | MontyFutureSnapshot | MontyYield | MontyComplete:
+        """Resume execution by raising `exception` from the `monty_yield()` call.
+
+        See docstring for the first overload for more information.
+        """
+
+    def dump(self) -> bytes:
+        """
+        Serialize the MontyYield instance to a binary format, see `MontySnapshot.dump()`.
+
+        Raises:
+            ValueError: If serialization fails.
+            RuntimeError: If the progress has already been resumed.
+        """
+
+    @staticmethod
+    def load(
+        data: bytes,
+        *,
+        print_callback: Callable[[Literal['stdout', 'stderr'], str], None] | None = None,
+        dataclass_registry: list[type] | None = None,
+    ) -> 'MontyYield':
+        """
+        Deserialize a MontyYield instance from binary format, see `MontySnapshot.load()`.
+
+        Raises:
+            ValueError: If deserialization fails.
+        """
+
+    def __repr__(self) -> str: ...
+
 @final
 class MontyFutureSnapshot:
     """
@@ -387,7 +679,7 @@ class MontyFutureSnapshot:
     def resume(
         self,
         results: dict[int, ExternalResult],
-    ) -> MontySnapshot | MontyFutureSnapshot | MontyComplete:
+    ) -> MontySnapshot | MontyFutureSnapshot | MontyYield | MontyComplete:
         """Resume execution with results for one or more futures.
 
         `resume` may only be called once on each MontyFutureSnapshot instance.
@@ -401,6 +693,7 @@ class MontyFutureSnapshot:
         Returns:
             MontySnapshot if an external function call is pending,
             MontyFutureSnapshot if more futures need to be resolved,
+            MontyYield if the code streamed a value with `monty_yield()`,
             MontyComplete if execution finished.
 
         Raises:
@@ -431,7 +724,7 @@ class MontyFutureSnapshot:
     def load(
         data: bytes,
         *,
-        print_callback: Callable[[Literal['stdout'], str], None] | None = None,
+        print_callback: Callable[[Literal['stdout', 'stderr'], str], None] | None = None,
         dataclass_registry: list[type] | None = None,
     ) -> 'MontyFutureSnapshot':
         """
@@ -463,6 +756,14 @@ class MontyComplete:
     def output(self) -> Any:
         """The final output value from the executed code."""
 
+    @property
+    def memory_profile(self) -> dict[str, list[dict[str, Any]]] | None:
+        """Live heap objects when execution finished, including the output and global variables.
+
+        Same format as `MontyRepl.memory_profile()`, with allocation sites if `trace_allocations`
+        is enabled. `None` if no profile was recorded for the run.
+        """
+
     def __repr__(self) -> str: ...
 
 class MontyError(Exception):
@@ -475,6 +776,33 @@ class MontyError(Exception):
     def exception(self) -> BaseException:
         """Returns the inner exception as a Python exception object."""
 
+    @property
+    def type_name(self) -> str:
+        """The name of the inner exception type, e.g. `'ValueError'`."""
+
+    @property
+    def message(self) -> str | None:
+        """The inner exception message without the type name, or `None` if there is no message."""
+
+    @property
+    def notes(self) -> list[str]:
+        """Notes attached to the inner exception with `add_note()`, in the order they were added."""
+
+    @property
+    def host_exception(self) -> BaseException | None:
+        """The original exception raised by an external function, if that's where this error came from.
+
+        This is the exact object the host function raised, so it can be re-raised with
+        `raise e.host_exception`. It is also set as `__cause__` of `MontyRuntimeError`.
+        """
+
+    @property
+    def frames(self) -> list[Frame]:
+        """The traceback frames where the error occurred, outermost first.
+
+        Empty for syntax and typing errors.
+        """
+
     def __str__(self) -> str:
         """Returns the exception message."""
 
@@ -482,7 +810,7 @@ class MontyError(Exception):
 class MontySyntaxError(MontyError):
     """Raised when Python code has syntax errors or cannot be parsed by Monty.
 
-    Inherits exception(), __str__() from MontyError.
+    Inherits exception(), type_name, message, notes, host_exception, frames and __str__() from MontyError.
     """
 
     def display(self, format: Literal['type-msg', 'msg'] = 'msg') -> str:
@@ -501,7 +829,7 @@ class MontyTypingError(MontyError):
     before execution. Use `.display(format, color)` to render the diagnostics
     in different formats.
 
-    Inherits exception(), __str__() from MontyError.
+    Inherits exception(), type_name, message, notes, host_exception, frames and __str__() from MontyError.
     Cannot be constructed directly from Python.
     """
 
@@ -523,22 +851,43 @@ class MontyTypingError(MontyError):
 class MontyRuntimeError(MontyError):
     """Raised when Monty code fails during execution.
 
-    Inherits exception(), __str__() from MontyError.
+    Inherits exception(), type_name, message, notes, host_exception, frames and __str__() from MontyError.
     Additionally provides traceback() and display() methods.
     """
 
     def traceback(self) -> list[Frame]:
         """Returns the Monty traceback as a list of Frame objects."""
 
-    def display(self, format: Literal['traceback', 'type-msg', 'msg'] = 'traceback') -> str:
+    def display(
+        self,
+        format: Literal['traceback', 'type-msg', 'msg'] = 'traceback',
+        *,
+        style: Literal['long', 'short', 'line'] = 'long',
+        max_frames: int | None = None,
+        internal_files: Sequence[str] = (),
+    ) -> str:
         """Returns formatted exception string.
 
         Args:
             format: 'traceback' - full traceback with exception
                   'type-msg' - 'ExceptionType: message' format
                   'msg' - just the message
+            style: Traceback style, like pytest's `--tb` option, used with format='traceback':
+                  'long' - CPython's format (default)
+                  'short' - like 'long' without the caret markers
+                  'line' - a single `file.py:3: ExceptionType: message` line
+            max_frames: Maximum number of frames to show, the innermost frames are kept.
+                0 shows only the exception type and message.
+            internal_files: Filenames whose frames are hidden, the innermost frame is kept
+                if every frame is internal.
         """
 
+class ExternalCallTimeout(TimeoutError):
+    """Raised when an external function call times out on every attempt allowed by its call policy.
+
+    Raised inside the sandbox, so Monty code can catch it as `ExternalCallTimeout` or `TimeoutError`.
+    """
+
 @final
 class Frame:
     """A single frame in a Monty traceback."""
@@ -571,5 +920,9 @@ class Frame:
     def source_line(self) -> str | None:
         """The source code line for preview in the traceback."""
 
+    @property
+    def depth(self) -> int:
+        """Call depth of the frame, 0 for the outermost frame (usually module-level code)."""
+
     def dict(self) -> dict[str, int | str | None]:
         """dict of attributes."""
@@ -1,6 +1,8 @@
 from __future__ import annotations
 
+import secrets
 from abc import ABC, abstractmethod
+from fnmatch import fnmatchcase
 from pathlib import PurePosixPath
 from typing import TYPE_CHECKING, Any, Callable, Literal, NamedTuple, Protocol, Sequence, TypeAlias, TypeGuard
 
@@ -24,11 +26,22 @@ OsFunction = Literal[
     'Path.rmdir',
     'Path.iterdir',
     'Path.stat',
+    'Path.lstat',
     'Path.rename',
+    'Path.copy',
+    'Path.touch',
+    'Path.chmod',
+    'Path.glob',
+    'Path.rglob',
+    'Path.walk',
+    'Path.readlink',
+    'Path.symlink_to',
     'Path.resolve',
     'Path.absolute',
     'os.getenv',
     'os.environ',
+    'tempfile.scratch_path',
+    'input',
 ]
 
 
@@ -77,6 +90,28 @@ class StatResult(NamedTuple):
         mtime = time.time() if mtime is None else mtime
         return cls(mode, 0, 0, 2, 0, 0, 4096, mtime, mtime, mtime)
 
+    @classmethod
+    def symlink_stat(cls, mode: int = 0o777, mtime: float | None = None) -> Self:
+        """Creates a stat_result namedtuple for a symbolic link.
+
+        Use this when responding to Path.lstat() OS calls on symlinks.
+
+        Args:
+            mode: Symlink permissions as octal (e.g., 0o777) or full mode with file type
+            mtime: Modification time as Unix timestamp, defaults to Now.
+
+        Returns:
+            A namedtuple with stat_result fields
+        """
+        import time
+
+        # If only permission bits provided (no file type), add symlink type
+        if mode < 0o1000:
+            mode = mode | 0o120_000
+
+        mtime = time.time() if mtime is None else mtime
+        return cls(mode, 0, 0, 1, 0, 0, 0, mtime, mtime, mtime)
+
     st_mode: int
     """protection bits"""
 
@@ -162,8 +197,26 @@ class AbstractOS(ABC):
                 return self.path_iterdir(*args)
             case 'Path.stat':
                 return self.path_stat(*args)
+            case 'Path.lstat':
+                return self.path_lstat(*args)
             case 'Path.rename':
                 return self.path_rename(*args)
+            case 'Path.copy':
+                return self.path_copy(*args)
+            case 'Path.touch':
+                return self.path_touch(*args, **kwargs)
+            case 'Path.chmod':
+                return self.path_chmod(*args)
+            case 'Path.glob':
+                return self.path_glob(*args)
+            case 'Path.rglob':
+                return self.path_rglob(*args)
+            case 'Path.walk':
+                return self.path_walk(*args, **kwargs)
+            case 'Path.readlink':
+                return self.path_readlink(*args)
+            case 'Path.symlink_to':
+                return self.path_symlink_to(*args, **kwargs)
             case 'Path.resolve':
                 return self.path_resolve(*args)
             case 'Path.absolute':
@@ -172,6 +225,12 @@ class AbstractOS(ABC):
                 return self.getenv(*args)
             case 'os.environ':
                 return self.get_environ()
+            case 'tempfile.scratch_path':
+                is_dir, suffix, prefix, dir = args
+                path = self.scratch_path(is_dir, suffix, prefix, None if dir is None else PurePosixPath(dir))
+                return _TemporaryDirectory(str(path)) if is_dir else _NamedTemporaryFile(str(path))
+            case 'input':
+                return self.input(*args)
 
     @abstractmethod
     def path_exists(self, path: PurePosixPath) -> bool:
@@ -376,6 +435,138 @@ class AbstractOS(ABC):
         """
         raise NotImplementedError
 
+    def path_lstat(self, path: PurePosixPath) -> StatResult:
+        """Get file status information without following a final symlink.
+
+        Use symlink_stat() to describe a symlink; the default implementation calls
+        `path_stat`, which is correct for filesystems without symlinks.
+
+        Args:
+            path: The path to stat.
+
+        Returns:
+            A StatResult with metadata of the path itself.
+
+        Raises:
+            FileNotFoundError: If the path does not exist.
+        """
+        return self.path_stat(path)
+
+    def path_copy(self, path: PurePosixPath, target: PurePosixPath) -> PurePosixPath:
+        """Copy a file's content and permissions, like `shutil.copy`.
+
+        Args:
+            path: The file to copy.
+            target: The full path of the copy, which is overwritten if it exists.
+
+        Returns:
+            The target path.
+
+        Raises:
+            FileNotFoundError: If the file or the target's parent directory does not exist.
+            IsADirectoryError: If the path is a directory.
+        """
+        raise NotImplementedError(f'{type(self).__name__} does not support Path.copy()')
+
+    def path_touch(self, path: PurePosixPath, mode: int = 0o666, exist_ok: bool = True) -> None:
+        """Create an empty file, or update the modification time of an existing one.
+
+        Args:
+            path: The file to create.
+            mode: Permissions of a new file, before the umask is applied.
+            exist_ok: If False, raise an error if the path already exists.
+
+        Raises:
+            FileNotFoundError: If the parent directory does not exist.
+            FileExistsError: If exist_ok is False and the path already exists.
+        """
+        raise NotImplementedError(f'{type(self).__name__} does not support Path.touch()')
+
+    def path_chmod(self, path: PurePosixPath, mode: int) -> None:
+        """Change the permission bits of a path.
+
+        Args:
+            path: The path to change.
+            mode: The new permission bits, e.g. 0o600.
+
+        Raises:
+            FileNotFoundError: If the path does not exist.
+        """
+        raise NotImplementedError(f'{type(self).__name__} does not support Path.chmod()')
+
+    def path_glob(self, path: PurePosixPath, pattern: str) -> list[PurePosixPath]:
+        """Find the paths below a directory matching a glob pattern.
+
+        Patterns are relative to the directory; `*`, `?` and `[...]` match within a
+        component and `**` matches any number of directories, as for `Path.glob()`.
+
+        Args:
+            path: The directory to search.
+            pattern: The glob pattern.
+
+        Returns:
+            The matching paths, in any order. Empty if the directory does not exist.
+        """
+        raise NotImplementedError(f'{type(self).__name__} does not support Path.glob()')
+
+    def path_rglob(self, path: PurePosixPath, pattern: str) -> list[PurePosixPath]:
+        """Find the paths in a directory and all its subdirectories matching a glob pattern.
+
+        The default implementation calls `path_glob` with the pattern prefixed by `**/`.
+
+        Args:
+            path: The directory to search.
+            pattern: The glob pattern.
+
+        Returns:
+            The matching paths, in any order.
+        """
+        return self.path_glob(path, f'**/{pattern}')
+
+    def path_walk(
+        self, path: PurePosixPath, top_down: bool = True, follow_symlinks: bool = False
+    ) -> list[tuple[PurePosixPath, list[str], list[str]]]:
+        """Walk a directory tree, like `Path.walk()`.
+
+        Args:
+            path: The directory at the top of the tree.
+            top_down: If True, a directory comes before its subdirectories, otherwise after.
+            follow_symlinks: If True, walk into symlinks to directories rather than listing
+                them as files.
+
+        Returns:
+            A `(dirpath, dirnames, filenames)` tuple for each directory in the tree.
+        """
+        raise NotImplementedError(f'{type(self).__name__} does not support Path.walk()')
+
+    def path_readlink(self, path: PurePosixPath) -> PurePosixPath:
+        """Read the target of a symbolic link.
+
+        Args:
+            path: The symlink.
+
+        Returns:
+            The path the link points to.
+
+        Raises:
+            FileNotFoundError: If the path does not exist.
+            OSError: If the path is not a symlink.
+        """
+        raise NotImplementedError(f'{type(self).__name__} does not support Path.readlink()')
+
+    def path_symlink_to(self, path: PurePosixPath, target: PurePosixPath, target_is_directory: bool = False) -> None:
+        """Make a path a symbolic link to target.
+
+        Args:
+            path: The link to create.
+            target: The path the link points to, which doesn't need to exist.
+            target_is_directory: Whether the target is a directory, only relevant on Windows.
+
+        Raises:
+            FileExistsError: If the path already exists.
+        """
+        raise NotImplementedError(f'{type(self).__name__} does not support Path.symlink_to()')
+
     @abstractmethod
     def path_resolve(self, path: PurePosixPath) -> str:
         """Resolve a path to an absolute path, resolving any symlinks.
@@ -422,6 +613,54 @@ class AbstractOS(ABC):
         """
         raise NotImplementedError
 
+    def scratch_path(
+        self, is_dir: bool, suffix: str | None, prefix: str | None, dir: PurePosixPath | None
+    ) -> PurePosixPath:
+        """Create scratch space for `tempfile.TemporaryDirectory()` or `tempfile.NamedTemporaryFile()`.
+
+        Monty code gets the path back as the `name` of the result. Scratch space belongs
+        to the host, Monty code has no way to clean it up.
+
+        Args:
+            is_dir: Whether to create an empty directory, rather than an empty file.
+            suffix: End of the new name, or None for no suffix.
+            prefix: Start of the new name, or None for a default like `'tmp'`.
+            dir: Directory to create the path in, or None to use the host's scratch directory.
+
+        Returns:
+            The path of the new directory or file, named `prefix` + a unique part + `suffix`.
+
+        Raises:
+            FileNotFoundError: If `dir` does not exist.
+        """
+        raise NotImplementedError(f'{type(self).__name__} does not support tempfile')
+
+    def input(self, prompt: str = '') -> str:
+        """Read a line of input for the `input()` builtin.
+
+        The default implementation has no input to read, so it raises `EOFError`
+        like CPython does at end of input. Override this to supply interactive input.
+
+        Args:
+            prompt: The prompt passed to `input()`, already converted to a string.
+
+        Returns:
+            The line read, without a trailing newline.
+        """
+        raise EOFError('EOF when reading a line')
+
+
+class _TemporaryDirectory(NamedTuple):
+    """What Monty code gets from `tempfile.TemporaryDirectory()`."""
+
+    name: str
+
+
+class _NamedTemporaryFile(NamedTuple):
+    """What Monty code gets from `tempfile.NamedTemporaryFile()`."""
+
+    name: str
+
 
 class AbstractFile(Protocol):
     """Protocol defining the interface for files used with OSAccess.
@@ -476,6 +715,27 @@ def _is_dir(entry: None | AbstractFile | Tree) -> TypeGuard[Tree]:
     return isinstance(entry, dict)
 
 
+def _tree_entries(tree: Tree, prefix: tuple[str, ...]) -> list[tuple[tuple[str, ...], AbstractFile | Tree]]:
+    """List every entry below a directory, with its path components relative to the directory."""
+    entries: list[tuple[tuple[str, ...], AbstractFile | Tree]] = []
+    for name, entry in tree.items():
+        parts = (*prefix, name)
+        entries.append((parts, entry))
+        if _is_dir(entry):
+            entries.extend(_tree_entries(entry, parts))
+    return entries
+
+
+def _glob_match(pattern: Sequence[str], parts: Sequence[str]) -> bool:
+    """Match path components against glob pattern components, where `**` matches any number of directories."""
+    if not pattern:
+        return not parts
+    first, *rest = pattern
+    if first == '**':
+        return any(_glob_match(rest, parts[skip:]) for skip in range(len(parts) + 1))
+    return bool(parts) and fnmatchcase(parts[0], first) and _glob_match(rest, parts[1:])
+
+
 class MemoryFile:
     """An in-memory virtual file for use with OSAccess.
 
@@ -862,6 +1122,61 @@ class OSAccess(AbstractOS):
             # Update paths for all files in the renamed directory
             self._update_paths_recursive(src_entry, PurePosixPath(path), PurePosixPath(target))
 
+    def path_copy(self, path: PurePosixPath, target: PurePosixPath) -> PurePosixPath:
+        file = self._get_file(path)
+        self._write_file(target, file.read_content())
+        copy = self._get_entry(target)
+        assert _is_file(copy), f'Expected the copy to be a file, got {copy}'
+        copy.permissions = file.permissions
+        return PurePosixPath(target)
+
+    def path_touch(self, path: PurePosixPath, mode: int = 0o666, exist_ok: bool = True) -> None:
+        if self._get_entry(path) is not None:
+            if not exist_ok:
+                raise FileExistsError(f'[Errno 17] File exists: {str(path)!r}')
+            return
+        self._write_file(path, '')
+        file = self._get_entry(path)
+        assert _is_file(file), f'Expected a new file, got {file}'
+        # apply the usual umask of 022
+        file.permissions = mode & ~0o022
+
+    def path_chmod(self, path: PurePosixPath, mode: int) -> None:
+        entry = self._get_entry_exists(path)
+        # directories don't have permissions in OSAccess
+        if _is_file(entry):
+            entry.permissions = mode
+
+    def path_glob(self, path: PurePosixPath, pattern: str) -> list[PurePosixPath]:
+        entry = self._get_entry(path)
+        if not _is_dir(entry):
+            return []
+        pattern_parts = [part for part in pattern.split('/') if part]
+        return [
+            PurePosixPath(path, *parts)
+            for parts, _ in _tree_entries(entry, ())
+            if _glob_match(pattern_parts, parts)
+        ]
+
+    def path_walk(
+        self, path: PurePosixPath, top_down: bool = True, follow_symlinks: bool = False
+    ) -> list[tuple[PurePosixPath, list[str], list[str]]]:
+        entry = self._get_entry(path)
+        if not _is_dir(entry):
+            return []
+        dirnames = [name for name, child in entry.items() if _is_dir(child)]
+        filenames = [name for name, child in entry.items() if _is_file(child)]
+        subtrees = [
+            tree for name in dirnames for tree in self.path_walk(PurePosixPath(path) / name, top_down, follow_symlinks)
+        ]
+        this = (PurePosixPath(path), dirnames, filenames)
+        return [this, *subtrees] if top_down else [*subtrees, this]
+
+    def path_readlink(self, path: PurePosixPath) -> PurePosixPath:
+        # there are no symlinks in OSAccess, so any existing path is an invalid argument
+        self._get_entry_exists(path)
+        raise OSError(f'[Errno 22] Invalid argument: {str(path)!r}')
+
     def path_resolve(self, path: PurePosixPath) -> str:
         # No symlinks in OSAccess, so resolve is same as absolute with normalization
         return self.path_absolute(path)
@@ -879,6 +1194,28 @@ class OSAccess(AbstractOS):
     def get_environ(self) -> dict[str, str]:
         return self.environ
 
+    def scratch_path(
+        self, is_dir: bool, suffix: str | None, prefix: str | None, dir: PurePosixPath | None
+    ) -> PurePosixPath:
+        # scratch space defaults to /tmp, which is created when first used
+        parent = PurePosixPath('/tmp') if dir is None else dir
+        if dir is None:
+            self.path_mkdir(parent, parents=True, exist_ok=True)
+        elif not _is_dir(self._get_entry(parent)):
+            raise FileNotFoundError(f'[Errno 2] No such file or directory: {str(parent)!r}')
+        while True:
+            path = parent / f'{"tmp" if prefix is None else prefix}{secrets.token_hex(4)}{suffix or ""}'
+            if self._get_entry(path) is None:
+                break
+        if is_dir:
+            self.path_mkdir(path, parents=False, exist_ok=False)
+        else:
+            self._write_file(path, b'')
+            file = self._get_entry(path)
+            assert _is_file(file), f'Expected a new file, got {file}'
+            file.permissions = 0o600
+        return path
+
     def _get_entry(self, path: PurePosixPath) -> Tree | AbstractFile | None:
         dir = self._tree
 
@@ -0,0 +1,96 @@
+from __future__ import annotations
+
+import inspect
+from typing import Any, Callable
+
+__all__ = ('build_type_stubs',)
+
+# Types whose name can be used directly in stubs, everything else is annotated as `Any`
+_BUILTIN_TYPES: tuple[type, ...] = (bool, int, float, str, bytes, list, tuple, dict, set, frozenset)
+
+
+def build_type_stubs(
+    inputs: dict[str, Any] | None = None,
+    external_functions: dict[str, Callable[..., Any]] | None = None,
+) -> str:
+    """Build a `type_check_stubs` prefix declaring inputs and external functions.
+
+    Input names are annotated with the type of the sample value, external functions are declared
+    from `inspect.signature` of the callable, so code referencing them type checks without
+    `unresolved-reference` errors.
+
+    Args:
+        inputs: Mapping of input names to sample values, e.g. the same dict passed to `Monty.run()`.
+        external_functions: Mapping of external function names to the callables implementing them.
+
+    Returns:
+        Stub source code suitable for `Monty(..., type_check_stubs=...)` or `Monty.type_check()`.
+    """
+    lines = ['from typing import Any', '']
+    for name, value in (inputs or {}).items():
+        lines.append(f'{name}: {_value_annotation(value)}')
+    for name, func in (external_functions or {}).items():
+        lines.append(_function_stub(name, func))
+    return '\n'.join(lines) + '\n'
+
+
+def _value_annotation(value: Any) -> str:
+    """Annotation for an input, based on the type of its sample value."""
+    if value is None:
+        return 'None'
+    value_type = type(value)
+    if value_type in _BUILTIN_TYPES:
+        return value_type.__name__
+    return 'Any'
+
+
+def _function_stub(name: str, func: Callable[..., Any]) -> str:
+    """`def` stub line for an external function, falling back to a permissive signature."""
+    prefix = 'async def' if inspect.iscoroutinefunction(func) else 'def'
+    try:
+        signature = inspect.signature(func)
+    except (TypeError, ValueError):
+        return f'{prefix} {name}(*args: Any, **kwargs: Any) -> Any: ...'
+
+    params: list[str] = []
+    previous: inspect.Parameter | None = None
+    for param in signature.parameters.values():
+        previous_kind = previous.kind if previous else None
+        if previous_kind == param.POSITIONAL_ONLY and param.kind != param.POSITIONAL_ONLY:
+            params.append('/')
+        if param.kind == param.KEYWORD_ONLY and previous_kind not in (param.KEYWORD_ONLY, param.VAR_POSITIONAL):
+            params.append('*')
+        params.append(_param_stub(param))
+        previous = param
+    if previous and previous.kind == previous.POSITIONAL_ONLY:
+        params.append('/')
+
+    return_annotation = _annotation(signature.return_annotation)
+    return f'{prefix} {name}({", ".join(params)}) -> {return_annotation}: ...'
+
+
+def _param_stub(param: inspect.Parameter) -> str:
+    """Render one parameter, replacing any default value with `...`."""
+    if param.kind == param.VAR_POSITIONAL:
+        stub = f'*{param.name}'
+    elif param.kind == param.VAR_KEYWORD:
+        stub = f'**{param.name}'
+    else:
+        stub = param.name
+    stub = f'{stub}: {_annotation(param.annotation)}'
+    if param.default is not param.empty:
+        stub += ' = ...'
+    return stub
+
+
+def _annotation(annotation: Any) -> str:
+    """Render an annotation, treating missing annotations as `Any`."""
+    if annotation is inspect.Parameter.empty:
+        return 'Any'
+    if isinstance(annotation, str):
+        # postponed annotations (`from __future__ import annotations`) are already source code
+        return annotation
+    if isinstance(annotation, type) and annotation.__module__ != 'builtins':
+        # host classes (e.g. dataclasses) aren't defined in the stubs
+        return 'Any'
+    return inspect.formatannotation(annotation)
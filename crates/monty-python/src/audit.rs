@@ -0,0 +1,86 @@
+//! Python `audit_callback` support.
+//!
+//! Passes Monty's audit events for external calls, OS calls, imports, resource limit breaches
+//! and denied capabilities to a Python callable as dicts.
+
+use ::monty::{AuditEvent, AuditEventKind, AuditHook, Capability, ResourceTracker, Snapshot};
+use pyo3::{prelude::*, types::PyDict};
+
+/// Calls a Python `audit_callback` with a dict describing each audit event.
+///
+/// The callback isn't serialized with snapshots, so runs resumed from a loaded snapshot
+/// don't report audit events.
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+pub struct PyAuditHook {
+    #[serde(skip)]
+    callback: Option<Py<PyAny>>,
+}
+
+impl PyAuditHook {
+    /// Creates a hook calling `callback`, or doing nothing if it's `None`.
+    pub fn new(callback: Option<&Bound<'_, PyAny>>) -> Self {
+        Self {
+            callback: callback.map(|cb| cb.clone().unbind()),
+        }
+    }
+}
+
+impl AuditHook for PyAuditHook {
+    fn on_event(&self, event: &AuditEvent<'_>) {
+        let Some(callback) = &self.callback else {
+            return;
+        };
+        Python::attach(|py| {
+            // audit hooks can't stop execution, so errors from the callback are reported like
+            // exceptions in `__del__` rather than raised
+            if let Err(err) = event_dict(py, event).and_then(|dict| callback.call1(py, (dict,))) {
+                err.write_unraisable(py, Some(callback.bind(py)));
+            }
+        });
+    }
+}
+
+/// Reports that the call `state` is paused at was refused because the host didn't provide
+/// `capability`, e.g. an OS call without an `os` callback.
+pub fn audit_denied<T: ResourceTracker>(state: &Snapshot<T>, capability: Capability, name: &str) {
+    state
+        .tracker()
+        .audit(&AuditEvent::now(AuditEventKind::CapabilityDenied { capability, name }));
+}
+
+/// Converts an audit event into a dict with `event` (the kind of event), `timestamp` (seconds
+/// since the Unix epoch) and the fields of the event.
+///
+/// Call arguments are represented by `args_digest`, a hex digest, rather than the values.
+fn event_dict<'py>(py: Python<'py>, event: &AuditEvent<'_>) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    dict.set_item("event", event.name())?;
+    dict.set_item("timestamp", event.unix_timestamp())?;
+    match &event.kind {
+        AuditEventKind::ExternalCall {
+            function_name,
+            method_call,
+            ..
+        } => {
+            dict.set_item("function", function_name)?;
+            dict.set_item("method_call", method_call)?;
+        }
+        AuditEventKind::OsCall { function, .. } => dict.set_item("function", function.to_string())?,
+        AuditEventKind::Import { module, allowed } => {
+            dict.set_item("module", module)?;
+            dict.set_item("allowed", allowed)?;
+        }
+        AuditEventKind::ResourceLimit { exc_type, message } => {
+            dict.set_item("exc_type", exc_type.to_string())?;
+            dict.set_item("message", message)?;
+        }
+        AuditEventKind::CapabilityDenied { capability, name } => {
+            dict.set_item("capability", capability.to_string())?;
+            dict.set_item("name", name)?;
+        }
+    }
+    if let Some(digest) = event.args_digest() {
+        dict.set_item("args_digest", format!("{digest:016x}"))?;
+    }
+    Ok(dict)
+}
@@ -3,6 +3,9 @@
 //! This module provides bidirectional conversion:
 //! - `py_to_monty`: Convert Python objects to Monty's `MontyObject` for input
 //! - `monty_to_py`: Convert Monty's `MontyObject` back to Python objects for output
+//!
+//! Standard library types without a Monty equivalent (`datetime`, `Decimal`, `UUID`, ...)
+//! are handled in `std_types`, numpy scalars and arrays in `numpy`.
 
 use ::monty::MontyObject;
 use monty::MontyException;
@@ -15,8 +18,12 @@ use pyo3::{
 };
 
 use crate::{
-    dataclass::{DcRegistry, dataclass_to_monty, dataclass_to_py, is_dataclass},
+    dataclass::{
+        DcRegistry, dataclass_to_monty, dataclass_to_py, is_dataclass, is_pydantic_model, pydantic_model_to_monty,
+    },
     exceptions::{exc_monty_to_py, exc_to_monty_object},
+    numpy::numpy_to_monty,
+    std_types::std_type_to_monty,
 };
 
 /// Converts a Python object to Monty's `MontyObject` representation.
@@ -107,10 +114,19 @@ pub fn py_to_monty(obj: &Bound<'_, PyAny>, dc_registry: &DcRegistry) -> PyResult
         // Auto-register the dataclass type so it can be reconstructed on output
         dc_registry.insert(&obj.get_type())?;
         dataclass_to_monty(obj, dc_registry)
+    } else if is_pydantic_model(obj) {
+        // Registered the same way as dataclasses, rebuilt with `model_construct` on output
+        dc_registry.insert(&obj.get_type())?;
+        pydantic_model_to_monty(obj, dc_registry)
     } else if obj.is_instance(get_pure_posix_path(obj.py())?)? {
         // Handle pathlib.PurePosixPath and thereby pathlib.PosixPath objects
         let path_str: String = obj.str()?.extract()?;
         Ok(MontyObject::Path(path_str))
+    } else if let Some(value) = std_type_to_monty(obj, dc_registry)? {
+        // datetime, Decimal, UUID etc. become dataclass-like objects
+        Ok(value)
+    } else if let Some(value) = numpy_to_monty(obj, dc_registry)? {
+        Ok(value)
     } else if let Ok(name) = obj.get_type().name() {
         Err(PyTypeError::new_err(format!("Cannot convert {name} to Monty value")))
     } else {
@@ -132,6 +148,7 @@ pub fn monty_to_py(py: Python<'_>, obj: &MontyObject, dc_registry: &DcRegistry)
         MontyObject::BigInt(bi) => Ok(bi.into_pyobject(py)?.clone().into_any().unbind()),
         MontyObject::Float(f) => Ok(f.into_pyobject(py)?.clone().into_any().unbind()),
         MontyObject::String(s) => Ok(PyString::new(py, s).into_any().unbind()),
+        MontyObject::SharedString(s) => Ok(PyString::new(py, s).into_any().unbind()),
         MontyObject::Bytes(b) => Ok(PyBytes::new(py, b).into_any().unbind()),
         MontyObject::List(items) => {
             let py_items: PyResult<Vec<Py<PyAny>>> =
@@ -218,6 +235,8 @@ pub fn monty_to_py(py: Python<'_>, obj: &MontyObject, dc_registry: &DcRegistry)
             let path_obj = pure_posix_path.call1((p,))?;
             Ok(path_obj.into_any().unbind())
         }
+        // Lazy sequences are only iterated by Rust hosts, show their repr
+        MontyObject::LazySequence { .. } => Ok(PyString::new(py, &obj.py_repr()).into_any().unbind()),
         // Output-only types - convert to string representation
         MontyObject::Repr(s) => Ok(PyString::new(py, s).into_any().unbind()),
         MontyObject::Cycle(_, placeholder) => Ok(PyString::new(py, placeholder).into_any().unbind()),
@@ -2,6 +2,7 @@
 //!
 //! This module handles:
 //! - Converting Python dataclass instances to `MontyObject::Dataclass`
+//! - Converting pydantic `BaseModel` instances to frozen `MontyObject::Dataclass`
 //! - Converting `MontyObject::Dataclass` back to Python via `PyUnknownDataclass`
 //! - `PyUnknownDataclass`: A Python class that mimics dataclass behavior
 
@@ -84,10 +85,75 @@ pub fn dataclass_to_monty(value: &Bound<'_, PyAny>, dc_registry: &DcRegistry) ->
     })
 }
 
+/// Checks if a Python object is a pydantic `BaseModel` instance (not a type).
+///
+/// Detected by the `__pydantic_fields__` class attribute of pydantic v2 models, so pydantic
+/// doesn't need to be installed or imported for other inputs.
+pub fn is_pydantic_model(value: &Bound<'_, PyAny>) -> bool {
+    !value.is_instance_of::<PyType>() && is_pydantic_model_type(&value.get_type())
+}
+
+/// Checks if a Python type is a pydantic `BaseModel` subclass.
+fn is_pydantic_model_type(value: &Bound<'_, PyType>) -> bool {
+    let py = value.py();
+    value.hasattr(intern!(py, "__pydantic_fields__")).unwrap_or(false)
+        && value.hasattr(intern!(py, "model_construct")).unwrap_or(false)
+}
+
+/// Converts a pydantic model instance to a frozen `MontyObject::Dataclass`.
+///
+/// Declared fields come first in definition order, followed by any extra fields of models
+/// with `extra='allow'`. The model is always frozen inside Monty since assignments there
+/// would bypass validation. Like `dataclass_to_monty`, the `type_id` is `id(type(model))`.
+pub fn pydantic_model_to_monty(value: &Bound<'_, PyAny>, dc_registry: &DcRegistry) -> PyResult<MontyObject> {
+    let py = value.py();
+
+    let model_type = value.get_type();
+    let name: String = model_type.getattr(intern!(py, "__name__"))?.extract()?;
+    let type_id = model_type.as_ptr() as u64;
+
+    let fields_dict = model_type
+        .getattr(intern!(py, "__pydantic_fields__"))?
+        .cast_into::<PyDict>()?;
+
+    let mut field_names = Vec::new();
+    let mut attrs = Vec::new();
+    for field_name_obj in fields_dict.keys() {
+        let field_name = field_name_obj.cast::<PyString>()?;
+        let field_value = value.getattr(field_name)?;
+        field_names.push(field_name.to_str()?.to_string());
+        attrs.push((
+            py_to_monty(&field_name_obj, dc_registry)?,
+            py_to_monty(&field_value, dc_registry)?,
+        ));
+    }
+
+    let extra = value.getattr(intern!(py, "model_extra"))?;
+    if let Ok(extra) = extra.cast::<PyDict>() {
+        for (field_name_obj, field_value) in extra.iter() {
+            field_names.push(field_name_obj.cast::<PyString>()?.to_str()?.to_string());
+            attrs.push((
+                py_to_monty(&field_name_obj, dc_registry)?,
+                py_to_monty(&field_value, dc_registry)?,
+            ));
+        }
+    }
+
+    Ok(MontyObject::Dataclass {
+        name,
+        type_id,
+        field_names,
+        attrs: attrs.into(),
+        frozen: true,
+    })
+}
+
 /// Converts a `MontyObject::Dataclass` to a Python object.
 ///
 /// If the `type_id` is found in the dc_registry, creates an instance of the original
 /// Python dataclass type (so `isinstance(result, OriginalClass)` works).
+/// Pydantic models are rebuilt with `model_construct`, since their field values were
+/// already validated when the model was passed in.
 /// Otherwise, falls back to creating a `PyUnknownDataclass`.
 pub fn dataclass_to_py(
     py: Python<'_>,
@@ -114,8 +180,14 @@ pub fn dataclass_to_py(
             }
         }
 
-        // Call the dataclass constructor with kwargs
-        original_type.call((), Some(&kwargs)).map(Bound::unbind)
+        if is_pydantic_model_type(original_type) {
+            original_type
+                .call_method(intern!(py, "model_construct"), (), Some(&kwargs))
+                .map(Bound::unbind)
+        } else {
+            // Call the dataclass constructor with kwargs
+            original_type.call((), Some(&kwargs)).map(Bound::unbind)
+        }
     } else {
         // Fall back to PyUnknownDataclass
         let dc = PyUnknownDataclass::new(py, name.to_string(), field_names.to_vec(), attrs, frozen, dc_registry)?;
@@ -132,10 +204,16 @@ pub fn dataclass_to_py(
 ///
 /// Wraps a `Py<PyDict>` so that `clone_ref` produces a shared handle to the same
 /// underlying dict — all clones see the same data without needing `Arc<Mutex>`.
-/// The GIL already serializes access, making additional locking unnecessary.
+/// The GIL serializes access on regular builds, and on free-threaded builds dict operations
+/// take the dict's own per-object lock, so additional locking is unnecessary either way.
+///
+/// The registry is threaded through every conversion, so it also carries the per-run
+/// `max_ndarray_size` conversion limit.
 #[derive(Debug)]
 pub struct DcRegistry {
     registry: Py<PyDict>,
+    /// Maximum number of elements of numpy arrays converted to lists, `None` rejects arrays.
+    max_ndarray_size: Option<usize>,
 }
 
 impl DcRegistry {
@@ -143,6 +221,7 @@ impl DcRegistry {
     pub fn new(py: Python<'_>) -> Self {
         Self {
             registry: PyDict::new(py).unbind(),
+            max_ndarray_size: None,
         }
     }
 
@@ -168,9 +247,22 @@ impl DcRegistry {
     pub fn clone_ref(&self, py: Python<'_>) -> Self {
         Self {
             registry: self.registry.clone_ref(py),
+            max_ndarray_size: self.max_ndarray_size,
         }
     }
 
+    /// Sets the maximum number of elements of numpy arrays converted to lists.
+    #[must_use]
+    pub fn with_max_ndarray_size(mut self, max_ndarray_size: Option<usize>) -> Self {
+        self.max_ndarray_size = max_ndarray_size;
+        self
+    }
+
+    /// Maximum number of elements of numpy arrays converted to lists, `None` if arrays are rejected.
+    pub fn max_ndarray_size(&self) -> Option<usize> {
+        self.max_ndarray_size
+    }
+
     /// Registers a Python type in the dataclass registry, keyed by pointer identity.
     ///
     /// This is idempotent — calling it multiple times with the same type is safe and
@@ -198,7 +290,7 @@ impl DcRegistry {
 /// - Equality comparison (`__eq__`)
 /// - Hashing for frozen instances (`__hash__`)
 /// - `dataclasses` module compatibility (`__dataclass_fields__`)
-#[pyclass(name = "UnknownDataclass")]
+#[pyclass(name = "UnknownDataclass", frozen)]
 pub struct PyUnknownDataclass {
     /// Class name (e.g., "Point", "User")
     name: String,
@@ -12,18 +12,30 @@
 //! ├── MontyRuntimeError        # Raised when code fails during execution
 //! └── MontyTypingError         # Raised when type checking finds errors in the code
 //! ```
+//!
+//! `ExternalCallTimeout(TimeoutError)` is the Python side of the sandbox exception raised when an
+//! external function call runs out of time, see `external::CallPolicy`.
 
-use ::monty::{ExcType, MontyException, StackFrame};
+use ::monty::{ExcType, HostError, MontyException, StackFrame, TracebackOptions, TracebackStyle};
 use monty_type_checking::TypeCheckingDiagnostics;
 use pyo3::{
     PyClassInitializer, PyTypeCheck,
     exceptions::{self},
+    intern,
     prelude::*,
+    sync::PyOnceLock,
     types::{PyDict, PyList, PyString},
 };
 
 use crate::dataclass::get_frozen_instance_error;
 
+pyo3::create_exception!(
+    pydantic_monty,
+    ExternalCallTimeout,
+    exceptions::PyTimeoutError,
+    "Raised when an external function call times out on every attempt allowed by its call policy."
+);
+
 /// Base exception for all Monty interpreter errors.
 ///
 /// This is the parent class for both `MontySyntaxError` and `MontyRuntimeError`.
@@ -81,6 +93,44 @@ impl MontyError {
         py_err.into_value(py).into_any()
     }
 
+    /// The name of the inner exception type, e.g. `'ValueError'`.
+    #[getter]
+    fn type_name(&self) -> String {
+        self.exc_type().to_string()
+    }
+
+    /// The inner exception message, or `None` if the exception was raised without one.
+    ///
+    /// Unlike `str(exc)`, this never includes the exception type name.
+    #[getter(message)]
+    fn py_message(&self) -> Option<String> {
+        self.message().map(str::to_string)
+    }
+
+    /// Notes attached to the inner exception with `add_note()`, in the order they were added.
+    #[getter]
+    fn notes(&self) -> Vec<String> {
+        self.exc.notes().to_vec()
+    }
+
+    /// The original exception raised by an external function, if that's where this error came from.
+    ///
+    /// This is the exact object the host function raised, so it can be re-raised with
+    /// `raise e.host_exception`. Notes added inside Monty are not copied onto it.
+    #[getter]
+    fn host_exception(&self, py: Python<'_>) -> Option<Py<exceptions::PyBaseException>> {
+        host_exception(py, &self.exc)
+    }
+
+    /// The traceback frames where the error occurred, outermost first.
+    ///
+    /// Empty for syntax and typing errors. Each call builds fresh `Frame` objects,
+    /// so hosts building their own error UIs should read this once and reuse the list.
+    #[getter]
+    fn frames(&self) -> Vec<PyFrame> {
+        self.exc.traceback().iter().map(PyFrame::from_stack_frame).collect()
+    }
+
     fn __str__(&self) -> String {
         self.message().unwrap_or_default().to_string()
     }
@@ -227,13 +277,22 @@ impl MontyRuntimeError {
             Err(e) => return e,
         };
 
+        let cause = host_exception(py, &exc);
         let base_error = MontyError::new(exc);
         // Create the MontyRuntimeError with proper initialization
         let runtime_error = Self { frames };
 
         let init = pyo3::PyClassInitializer::from(base_error).add_subclass(runtime_error);
         match Py::new(py, init) {
-            Ok(err) => PyErr::from_value(err.into_bound(py).into_any()),
+            Ok(err) => {
+                let py_err = PyErr::from_value(err.into_bound(py).into_any());
+                // chain the original host exception so its traceback is shown with ours
+                py_err.set_cause(
+                    py,
+                    cause.map(|cause| PyErr::from_value(cause.into_bound(py).into_any())),
+                );
+                py_err
+            }
             Err(e) => e,
         }
     }
@@ -251,11 +310,32 @@ impl MontyRuntimeError {
     /// Returns formatted exception string.
     ///
     /// Overrides the base class to provide the full traceback when format='traceback'.
-    #[pyo3(signature = (format = "traceback"))]
+    /// `style`, `max_frames` and `internal_files` control how the traceback is rendered.
+    #[pyo3(signature = (format = "traceback", *, style = "long", max_frames = None, internal_files = vec![]))]
     #[expect(clippy::needless_pass_by_value, reason = "required by macro")]
-    fn display(slf: PyRef<'_, Self>, format: &str) -> PyResult<String> {
+    fn display(
+        slf: PyRef<'_, Self>,
+        format: &str,
+        style: &str,
+        max_frames: Option<usize>,
+        internal_files: Vec<String>,
+    ) -> PyResult<String> {
+        let style = match style {
+            "long" => TracebackStyle::Long,
+            "short" => TracebackStyle::Short,
+            "line" => TracebackStyle::Line,
+            _ => {
+                return Err(exceptions::PyValueError::new_err(format!(
+                    "Invalid traceback style: '{style}'. Expected 'long', 'short', or 'line'"
+                )));
+            }
+        };
         match format {
-            "traceback" => Ok(slf.as_super().exc.to_string()),
+            "traceback" => Ok(slf.as_super().exc.render(&TracebackOptions {
+                style,
+                max_frames,
+                internal_files,
+            })),
             "type-msg" => Ok(slf.as_super().exc.summary()),
             "msg" => Ok(slf.as_super().message().unwrap_or_default().to_string()),
             _ => Err(exceptions::PyValueError::new_err(format!(
@@ -317,6 +397,9 @@ pub struct PyFrame {
     /// The source code line for preview in the traceback.
     #[pyo3(get)]
     pub source_line: Option<String>,
+    /// Call depth of the frame, 0 for the outermost frame.
+    #[pyo3(get)]
+    pub depth: usize,
 }
 
 #[pymethods]
@@ -330,6 +413,7 @@ impl PyFrame {
         dict.set_item("end_column", self.end_column).unwrap();
         dict.set_item("function_name", self.function_name.clone()).unwrap();
         dict.set_item("source_line", self.source_line.clone()).unwrap();
+        dict.set_item("depth", self.depth).unwrap();
         dict.unbind()
     }
 
@@ -354,6 +438,7 @@ impl PyFrame {
             end_column: frame.end.column,
             function_name: frame.frame_name.clone(),
             source_line: frame.preview_line.clone(),
+            depth: frame.depth,
         }
     }
 }
@@ -364,6 +449,20 @@ impl PyFrame {
 /// The traceback information is included in the exception message
 /// since PyO3 doesn't provide direct traceback manipulation.
 pub fn exc_monty_to_py(py: Python<'_>, exc: MontyException) -> PyErr {
+    let notes = exc.notes().to_vec();
+    let py_err = exc_type_monty_to_py(py, exc);
+    // PEP 678 notes are re-attached with `add_note` so they show up in Python tracebacks
+    let value = py_err.value(py);
+    for note in notes {
+        if let Err(e) = value.call_method1(intern!(py, "add_note"), (note,)) {
+            return e;
+        }
+    }
+    py_err
+}
+
+/// Creates the Python exception matching the exception's type and message, ignoring notes.
+fn exc_type_monty_to_py(py: Python<'_>, exc: MontyException) -> PyErr {
     let exc_type = exc.exc_type();
     let msg = exc.into_message().unwrap_or_default();
 
@@ -382,6 +481,7 @@ pub fn exc_monty_to_py(py: Python<'_>, exc: MontyException) -> PyErr {
         ExcType::NotImplementedError => exceptions::PyNotImplementedError::new_err(msg),
         ExcType::RecursionError => exceptions::PyRecursionError::new_err(msg),
         ExcType::AssertionError => exceptions::PyAssertionError::new_err(msg),
+        ExcType::EOFError => exceptions::PyEOFError::new_err(msg),
         ExcType::AttributeError => exceptions::PyAttributeError::new_err(msg),
         ExcType::FrozenInstanceError => {
             if let Ok(exc_cls) = get_frozen_instance_error(py)
@@ -398,9 +498,19 @@ pub fn exc_monty_to_py(py: Python<'_>, exc: MontyException) -> PyErr {
         ExcType::StopIteration => exceptions::PyStopIteration::new_err(msg),
         ExcType::SyntaxError => exceptions::PySyntaxError::new_err(msg),
         ExcType::TimeoutError => exceptions::PyTimeoutError::new_err(msg),
+        ExcType::ExternalCallTimeout => ExternalCallTimeout::new_err(msg),
         ExcType::TypeError => exceptions::PyTypeError::new_err(msg),
         ExcType::ValueError => exceptions::PyValueError::new_err(msg),
         ExcType::UnicodeDecodeError => exceptions::PyUnicodeDecodeError::new_err(msg),
+        ExcType::StatisticsError => {
+            if let Ok(exc_cls) = get_statistics_error(py)
+                && let Ok(exc_instance) = exc_cls.call1((PyString::new(py, &msg),))
+            {
+                return PyErr::from_value(exc_instance);
+            }
+            // fall back to ValueError, StatisticsError's base class
+            exceptions::PyValueError::new_err(msg)
+        }
         ExcType::ImportError => exceptions::PyImportError::new_err(msg),
         ExcType::ModuleNotFoundError => exceptions::PyModuleNotFoundError::new_err(msg),
         ExcType::OSError => exceptions::PyOSError::new_err(msg),
@@ -408,18 +518,125 @@ pub fn exc_monty_to_py(py: Python<'_>, exc: MontyException) -> PyErr {
         ExcType::FileExistsError => exceptions::PyFileExistsError::new_err(msg),
         ExcType::IsADirectoryError => exceptions::PyIsADirectoryError::new_err(msg),
         ExcType::NotADirectoryError => exceptions::PyNotADirectoryError::new_err(msg),
+        ExcType::BadGzipFile => {
+            if let Ok(exc_cls) = get_bad_gzip_file(py)
+                && let Ok(exc_instance) = exc_cls.call1((PyString::new(py, &msg),))
+            {
+                return PyErr::from_value(exc_instance);
+            }
+            // fall back to OSError, BadGzipFile's base class
+            exceptions::PyOSError::new_err(msg)
+        }
+        ExcType::CsvError => {
+            if let Ok(exc_cls) = get_csv_error(py)
+                && let Ok(exc_instance) = exc_cls.call1((PyString::new(py, &msg),))
+            {
+                return PyErr::from_value(exc_instance);
+            }
+            // fall back to Exception, csv.Error's base class
+            exceptions::PyException::new_err(msg)
+        }
+        ExcType::ZlibError => {
+            if let Ok(exc_cls) = get_zlib_error(py)
+                && let Ok(exc_instance) = exc_cls.call1((PyString::new(py, &msg),))
+            {
+                return PyErr::from_value(exc_instance);
+            }
+            // fall back to Exception, zlib.error's base class
+            exceptions::PyException::new_err(msg)
+        }
     }
 }
 
+/// Cached import of the `csv.Error` exception class.
+fn get_csv_error(py: Python<'_>) -> PyResult<&Bound<'_, PyAny>> {
+    static CSV_ERROR: PyOnceLock<Py<PyAny>> = PyOnceLock::new();
+
+    CSV_ERROR.import(py, "csv", "Error")
+}
+
+/// Cached import of the `zlib.error` exception class.
+fn get_zlib_error(py: Python<'_>) -> PyResult<&Bound<'_, PyAny>> {
+    static ZLIB_ERROR: PyOnceLock<Py<PyAny>> = PyOnceLock::new();
+
+    ZLIB_ERROR.import(py, "zlib", "error")
+}
+
+/// Cached import of the `statistics.StatisticsError` exception class.
+fn get_statistics_error(py: Python<'_>) -> PyResult<&Bound<'_, PyAny>> {
+    static STATISTICS_ERROR: PyOnceLock<Py<PyAny>> = PyOnceLock::new();
+
+    STATISTICS_ERROR.import(py, "statistics", "StatisticsError")
+}
+
+/// Cached import of the `gzip.BadGzipFile` exception class.
+fn get_bad_gzip_file(py: Python<'_>) -> PyResult<&Bound<'_, PyAny>> {
+    static BAD_GZIP_FILE: PyOnceLock<Py<PyAny>> = PyOnceLock::new();
+
+    BAD_GZIP_FILE.import(py, "gzip", "BadGzipFile")
+}
+
 /// Converts a python exception to monty.
 ///
-/// Used when resuming execution with an exception from Python.
+/// Used when resuming execution with an exception from Python. The original exception object,
+/// its class name and formatted traceback are attached as a [`HostError`], so if the exception
+/// escapes the sandbox it's available as `MontyError.host_exception` and chained as `__cause__`.
 pub fn exc_py_to_monty(py: Python<'_>, py_err: &PyErr) -> MontyException {
     let exc = py_err.value(py);
     let exc_type = py_err_to_exc_type(exc);
     let arg = exc.str().ok().map(|s| s.to_string_lossy().into_owned());
 
-    MontyException::new(exc_type, arg)
+    let mut monty_exc = MontyException::new(exc_type, arg.clone());
+    for note in py_exc_notes(exc) {
+        monty_exc.add_note(note);
+    }
+    let class_name = exc
+        .get_type()
+        .name()
+        .map_or_else(|_| exc_type.to_string(), |name| name.to_string());
+    let mut host_error = HostError::new(class_name, arg);
+    if let Some(traceback) = py_exc_traceback(exc) {
+        host_error = host_error.with_traceback(traceback);
+    }
+    monty_exc.with_host_error(host_error.with_original(exc.clone().unbind()))
+}
+
+/// Returns the original Python exception attached to a Monty exception by [`exc_py_to_monty`].
+fn host_exception(py: Python<'_>, exc: &MontyException) -> Option<Py<exceptions::PyBaseException>> {
+    exc.host_error()?
+        .original::<Py<exceptions::PyBaseException>>()
+        .map(|original| original.clone_ref(py))
+}
+
+/// Formats a Python exception's traceback the way Python would print it.
+///
+/// Returns `None` if formatting fails, since the traceback is purely informational.
+fn py_exc_traceback(exc: &Bound<'_, exceptions::PyBaseException>) -> Option<String> {
+    let py = exc.py();
+    let lines = py
+        .import(intern!(py, "traceback"))
+        .and_then(|traceback| traceback.call_method1(intern!(py, "format_exception"), (exc,)))
+        .ok()?;
+    PyString::new(py, "")
+        .call_method1(intern!(py, "join"), (lines,))
+        .and_then(|joined| joined.extract())
+        .ok()
+}
+
+/// Reads the PEP 678 `__notes__` of a Python exception.
+///
+/// Non-string notes are converted with `str()`, and a missing or non-sequence `__notes__`
+/// is treated as having no notes, since notes are purely informational.
+fn py_exc_notes(exc: &Bound<'_, exceptions::PyBaseException>) -> Vec<String> {
+    let Ok(notes) = exc.getattr(intern!(exc.py(), "__notes__")) else {
+        return Vec::new();
+    };
+    let Ok(iter) = notes.try_iter() else {
+        return Vec::new();
+    };
+    iter.flatten()
+        .filter_map(|note| note.str().ok().map(|s| s.to_string_lossy().into_owned()))
+        .collect()
 }
 
 /// Converts a Python exception to Monty's `MontyObject::Exception`.
@@ -440,10 +657,12 @@ fn py_err_to_exc_type(exc: &Bound<'_, exceptions::PyBaseException>) -> ExcType {
         // put the most commonly used exceptions first
         if exceptions::PyTypeError::type_check(exc) {
             ExcType::TypeError
-        // ValueError hierarchy (check UnicodeDecodeError first as it's a subclass)
+        // ValueError hierarchy (check subclasses first)
         } else if exceptions::PyValueError::type_check(exc) {
             if exceptions::PyUnicodeDecodeError::type_check(exc) {
                 ExcType::UnicodeDecodeError
+            } else if is_statistics_error(exc) {
+                ExcType::StatisticsError
             } else {
                 ExcType::ValueError
             }
@@ -494,7 +713,11 @@ fn py_err_to_exc_type(exc: &Bound<'_, exceptions::PyBaseException>) -> ExcType {
             }
         // OSError hierarchy (check specific subclasses first)
         } else if exceptions::PyOSError::type_check(exc) {
-            if exceptions::PyFileNotFoundError::type_check(exc) {
+            if ExternalCallTimeout::type_check(exc) {
+                ExcType::ExternalCallTimeout
+            } else if exceptions::PyTimeoutError::type_check(exc) {
+                ExcType::TimeoutError
+            } else if exceptions::PyFileNotFoundError::type_check(exc) {
                 ExcType::FileNotFoundError
             } else if exceptions::PyFileExistsError::type_check(exc) {
                 ExcType::FileExistsError
@@ -502,14 +725,20 @@ fn py_err_to_exc_type(exc: &Bound<'_, exceptions::PyBaseException>) -> ExcType {
                 ExcType::IsADirectoryError
             } else if exceptions::PyNotADirectoryError::type_check(exc) {
                 ExcType::NotADirectoryError
+            } else if is_bad_gzip_file(exc) {
+                ExcType::BadGzipFile
             } else {
                 ExcType::OSError
             }
         // other standalone exception types
-        } else if exceptions::PyTimeoutError::type_check(exc) {
-            ExcType::TimeoutError
+        } else if exceptions::PyEOFError::type_check(exc) {
+            ExcType::EOFError
         } else if exceptions::PyMemoryError::type_check(exc) {
             ExcType::MemoryError
+        } else if is_csv_error(exc) {
+            ExcType::CsvError
+        } else if is_zlib_error(exc) {
+            ExcType::ZlibError
         } else {
             ExcType::Exception
         }
@@ -535,3 +764,39 @@ fn is_frozen_instance_error(exc: &Bound<'_, exceptions::PyBaseException>) -> boo
         false
     }
 }
+
+/// Checks if an exception is an instance of `csv.Error`.
+fn is_csv_error(exc: &Bound<'_, exceptions::PyBaseException>) -> bool {
+    if let Ok(csv_error_cls) = get_csv_error(exc.py()) {
+        exc.is_instance(csv_error_cls).unwrap_or(false)
+    } else {
+        false
+    }
+}
+
+/// Checks if an exception is an instance of `zlib.error`.
+fn is_zlib_error(exc: &Bound<'_, exceptions::PyBaseException>) -> bool {
+    if let Ok(zlib_error_cls) = get_zlib_error(exc.py()) {
+        exc.is_instance(zlib_error_cls).unwrap_or(false)
+    } else {
+        false
+    }
+}
+
+/// Checks if an exception is an instance of `statistics.StatisticsError`.
+fn is_statistics_error(exc: &Bound<'_, exceptions::PyBaseException>) -> bool {
+    if let Ok(statistics_error_cls) = get_statistics_error(exc.py()) {
+        exc.is_instance(statistics_error_cls).unwrap_or(false)
+    } else {
+        false
+    }
+}
+
+/// Checks if an exception is an instance of `gzip.BadGzipFile`.
+fn is_bad_gzip_file(exc: &Bound<'_, exceptions::PyBaseException>) -> bool {
+    if let Ok(bad_gzip_file_cls) = get_bad_gzip_file(exc.py()) {
+        exc.is_instance(bad_gzip_file_cls).unwrap_or(false)
+    } else {
+        false
+    }
+}
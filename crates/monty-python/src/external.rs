@@ -4,17 +4,22 @@
 //! External functions are registered by name and called when Monty execution
 //! reaches a call to that function.
 
+use std::{collections::HashMap, fmt::Write, time::Duration};
+
 use ::monty::{ExternalResult, MontyObject};
 use pyo3::{
-    exceptions::PyKeyError,
+    conversion::FromPyObjectOwned,
+    exceptions::{PyKeyError, PyValueError},
+    intern,
     prelude::*,
+    sync::PyOnceLock,
     types::{PyDict, PyTuple},
 };
 
 use crate::{
     convert::{monty_to_py, py_to_monty},
     dataclass::DcRegistry,
-    exceptions::exc_py_to_monty,
+    exceptions::{ExternalCallTimeout, exc_py_to_monty},
 };
 
 /// Dispatches a dataclass method call back to the original Python object.
@@ -82,6 +87,71 @@ fn dispatch_method_call_inner(
     py_to_monty(&result, dc_registry)
 }
 
+/// Timeout and retry settings for calls to one external function, from `run(call_policies=...)`.
+#[derive(Debug, Default)]
+pub struct CallPolicy {
+    /// How long to wait for each attempt, `None` waits indefinitely.
+    timeout: Option<Duration>,
+    /// Attempts after the first, made when an attempt times out or raises a `retry_on` exception.
+    retries: u32,
+    /// Delay before the first retry, doubled before each further retry.
+    backoff: Duration,
+    /// Exception class or tuple of classes which are retried rather than raised straight away.
+    retry_on: Option<Py<PyAny>>,
+}
+
+impl CallPolicy {
+    /// Whether a failed attempt raising `err` should be retried, if any retries are left.
+    fn retries_error(&self, py: Python<'_>, err: &PyErr) -> bool {
+        self.retry_on
+            .as_ref()
+            .is_some_and(|retry_on| err.value(py).is_instance(retry_on.bind(py)).unwrap_or(false))
+    }
+}
+
+/// Extracts the `call_policies` dict of `run()`, mapping function names to [`CallPolicy`]s.
+///
+/// Each policy is a dict with the optional keys `timeout` (seconds), `retries`, `backoff`
+/// (seconds) and `retry_on` (an exception class or tuple of classes).
+///
+/// Raises `TypeError` if a value has the wrong type, and `ValueError` for negative durations.
+pub fn extract_call_policies(dict: Option<&Bound<'_, PyDict>>) -> PyResult<HashMap<String, CallPolicy>> {
+    let Some(dict) = dict else {
+        return Ok(HashMap::new());
+    };
+    dict.iter()
+        .map(|(name, policy)| {
+            let policy = policy.cast_into::<PyDict>()?;
+            let policy = CallPolicy {
+                timeout: extract_optional_duration(&policy, "timeout")?,
+                retries: extract_optional(&policy, "retries")?.unwrap_or(0),
+                backoff: extract_optional_duration(&policy, "backoff")?.unwrap_or_default(),
+                retry_on: extract_optional(&policy, "retry_on")?,
+            };
+            Ok((name.extract()?, policy))
+        })
+        .collect()
+}
+
+/// Extracts an optional value from a dict, treating `None` like a missing key.
+fn extract_optional<'py, T: FromPyObjectOwned<'py>>(dict: &Bound<'py, PyDict>, key: &str) -> PyResult<Option<T>> {
+    match dict.get_item(key)? {
+        Some(value) if !value.is_none() => Ok(Some(value.extract().map_err(Into::<PyErr>::into)?)),
+        _ => Ok(None),
+    }
+}
+
+/// Extracts an optional duration in seconds from a dict.
+fn extract_optional_duration(dict: &Bound<'_, PyDict>, key: &str) -> PyResult<Option<Duration>> {
+    extract_optional::<f64>(dict, key)?
+        .map(|secs| {
+            Duration::try_from_secs_f64(secs).map_err(|_| {
+                PyValueError::new_err(format!("{key} must be a non-negative number of seconds, got {secs}"))
+            })
+        })
+        .transpose()
+}
+
 /// Registry that maps external function names to Python callables.
 ///
 /// Passed to the execution loop and used to dispatch calls when Monty
@@ -92,6 +162,14 @@ pub struct ExternalFunctionRegistry<'a, 'py> {
     py: Python<'py>,
     functions: &'py Bound<'py, PyDict>,
     dc_registry: &'a DcRegistry,
+    /// `concurrent.futures.Executor` to submit calls to, instead of calling them directly.
+    executor: Option<&'py Bound<'py, PyAny>>,
+    /// Timeout and retry settings by function name.
+    policies: Option<&'a HashMap<String, CallPolicy>>,
+    /// Mapping keeping the results of pure functions across runs, keyed by `call_cache_key`.
+    cache: Option<&'py Bound<'py, PyAny>>,
+    /// Functions whose results are kept in `cache`.
+    pure: &'a [String],
 }
 
 impl<'a, 'py> ExternalFunctionRegistry<'a, 'py> {
@@ -101,9 +179,41 @@ impl<'a, 'py> ExternalFunctionRegistry<'a, 'py> {
             py,
             functions,
             dc_registry,
+            executor: None,
+            policies: None,
+            cache: None,
+            pure: &[],
         }
     }
 
+    /// Submits calls to `executor` and waits for their results, instead of calling functions directly.
+    ///
+    /// Functions then run on the executor's worker threads, so one pool can serve many concurrent
+    /// runs. Waiting for the result releases the GIL like any blocking `Future.result()` call.
+    #[must_use]
+    pub fn with_executor(mut self, executor: Option<&'py Bound<'py, PyAny>>) -> Self {
+        self.executor = executor;
+        self
+    }
+
+    /// Applies timeouts and retries to calls of the functions with a [`CallPolicy`].
+    #[must_use]
+    pub fn with_policies(mut self, policies: &'a HashMap<String, CallPolicy>) -> Self {
+        self.policies = Some(policies);
+        self
+    }
+
+    /// Looks up and stores the results of calls to the `pure` functions in `cache`.
+    ///
+    /// The interpreter already memoizes pure calls within a run, the cache is consulted for
+    /// the first call with some arguments so their results are reused across runs.
+    #[must_use]
+    pub fn with_cache(mut self, cache: Option<&'py Bound<'py, PyAny>>, pure: &'a [String]) -> Self {
+        self.cache = cache;
+        self.pure = pure;
+        self
+    }
+
     /// Calls an external function by name with Monty arguments.
     ///
     /// Converts args/kwargs from Monty format, calls the Python callable
@@ -117,19 +227,84 @@ impl<'a, 'py> ExternalFunctionRegistry<'a, 'py> {
         args: &[MontyObject],
         kwargs: &[(MontyObject, MontyObject)],
     ) -> ExternalResult {
-        match self.call_inner(function_name, args, kwargs) {
+        let result = match self.cache {
+            Some(cache) if self.pure.iter().any(|name| name == function_name) => {
+                self.call_cached(cache, function_name, args, kwargs)
+            }
+            _ => self.call_with_policy(function_name, args, kwargs),
+        };
+        match result {
             Ok(result) => ExternalResult::Return(result),
             Err(err) => ExternalResult::Error(exc_py_to_monty(self.py, &err)),
         }
     }
 
-    /// Inner implementation that returns `PyResult` for error handling.
-    fn call_inner(
+    /// Returns the result stored in `cache` for this call, or makes the call and stores its result.
+    fn call_cached(
         &self,
+        cache: &Bound<'py, PyAny>,
         function_name: &str,
         args: &[MontyObject],
         kwargs: &[(MontyObject, MontyObject)],
     ) -> PyResult<MontyObject> {
+        let key = call_cache_key(function_name, args, kwargs);
+        if cache.contains(&key)? {
+            return py_to_monty(&cache.get_item(&key)?, self.dc_registry);
+        }
+        let result = self.call_with_policy(function_name, args, kwargs)?;
+        cache.set_item(key, monty_to_py(self.py, &result, self.dc_registry)?)?;
+        Ok(result)
+    }
+
+    /// Calls the function, retrying timed out and failed attempts as its [`CallPolicy`] allows.
+    ///
+    /// Raises `ExternalCallTimeout` once the last attempt times out, or the last attempt's
+    /// exception if it raised one.
+    fn call_with_policy(
+        &self,
+        function_name: &str,
+        args: &[MontyObject],
+        kwargs: &[(MontyObject, MontyObject)],
+    ) -> PyResult<MontyObject> {
+        let Some(policy) = self.policies.and_then(|policies| policies.get(function_name)) else {
+            return self
+                .call_inner(function_name, args, kwargs, None)
+                .map(|result| result.expect("calls without a timeout can't time out"));
+        };
+        let attempts = policy.retries.saturating_add(1);
+        let mut backoff = policy.backoff;
+        for attempt in 1..=attempts {
+            match self.call_inner(function_name, args, kwargs, policy.timeout) {
+                Ok(Some(result)) => return Ok(result),
+                Ok(None) if attempt == attempts => {
+                    let timeout = policy.timeout.unwrap_or_default().as_secs_f64();
+                    let mut msg = format!("external function '{function_name}' timed out after {timeout}s");
+                    if attempts > 1 {
+                        write!(msg, " on all {attempts} attempts").unwrap();
+                    }
+                    return Err(ExternalCallTimeout::new_err(msg));
+                }
+                Err(err) if attempt == attempts || !policy.retries_error(self.py, &err) => return Err(err),
+                _ => {}
+            }
+            self.py.detach(|| std::thread::sleep(backoff));
+            backoff = backoff.saturating_mul(2);
+        }
+        unreachable!("the last attempt always returns")
+    }
+
+    /// Makes a single call, returning `None` if it didn't finish within `timeout`.
+    ///
+    /// Calls with a timeout run on the executor, or on a thread of their own without one, and
+    /// wait for the result for at most `timeout`. Python threads can't be interrupted, so a call
+    /// which times out keeps running in the background and its result is discarded.
+    fn call_inner(
+        &self,
+        function_name: &str,
+        args: &[MontyObject],
+        kwargs: &[(MontyObject, MontyObject)],
+        timeout: Option<Duration>,
+    ) -> PyResult<Option<MontyObject>> {
         // Look up the callable
         let callable = self
             .functions
@@ -152,14 +327,102 @@ impl<'a, 'py> ExternalFunctionRegistry<'a, 'py> {
             py_kwargs.set_item(py_key, py_value)?;
         }
 
-        // Call the function with unpacked *args, **kwargs
-        let result = if py_kwargs.is_empty() {
+        let result = if let Some(timeout) = timeout {
+            let Some(result) = self.call_with_timeout(&callable, &py_args_tuple, &py_kwargs, timeout)? else {
+                return Ok(None);
+            };
+            result
+        } else if let Some(executor) = self.executor {
+            submit(executor, &callable, &py_args_tuple, &py_kwargs)?.call_method0(intern!(self.py, "result"))?
+        } else if py_kwargs.is_empty() {
+            // Call the function with unpacked *args, **kwargs
             callable.call1(&py_args_tuple)?
         } else {
             callable.call(&py_args_tuple, Some(&py_kwargs))?
         };
 
         // Convert result back to Monty format
-        py_to_monty(&result, self.dc_registry)
+        py_to_monty(&result, self.dc_registry).map(Some)
     }
+
+    /// Submits the call to the executor, or a new single thread pool, and waits up to `timeout`.
+    fn call_with_timeout(
+        &self,
+        callable: &Bound<'py, PyAny>,
+        args: &Bound<'py, PyTuple>,
+        kwargs: &Bound<'py, PyDict>,
+        timeout: Duration,
+    ) -> PyResult<Option<Bound<'py, PyAny>>> {
+        let py = self.py;
+        let (executor, owned_pool) = match self.executor {
+            Some(executor) => (executor.clone(), false),
+            None => (thread_pool_executor(py)?.call1((1,))?, true),
+        };
+        let future = submit(&executor, callable, args, kwargs)?;
+        let result = match future.call_method1(intern!(py, "result"), (timeout.as_secs_f64(),)) {
+            Ok(result) => Ok(Some(result)),
+            // the call itself may raise `TimeoutError` too, only a pending future means we gave up waiting
+            Err(err)
+                if err.is_instance(py, futures_timeout_error(py)?)
+                    && !future.call_method0(intern!(py, "done"))?.is_truthy()? =>
+            {
+                future.call_method0(intern!(py, "cancel"))?;
+                Ok(None)
+            }
+            Err(err) => Err(err),
+        };
+        if owned_pool {
+            executor.call_method1(intern!(py, "shutdown"), (false,))?;
+        }
+        result
+    }
+}
+
+/// Renders a call like Python source, e.g. `lookup('a', 1, limit=2)`, to key the host's cache.
+///
+/// Strings are stable across runs and processes, and work with any mapping, e.g. one backed by
+/// a key-value store.
+fn call_cache_key(function_name: &str, args: &[MontyObject], kwargs: &[(MontyObject, MontyObject)]) -> String {
+    let mut key = format!("{function_name}(");
+    let args = args.iter().map(MontyObject::py_repr);
+    let kwargs = kwargs.iter().map(|(name, value)| match name {
+        MontyObject::String(name) => format!("{name}={}", value.py_repr()),
+        name => format!("{}={}", name.py_repr(), value.py_repr()),
+    });
+    for (i, arg) in args.chain(kwargs).enumerate() {
+        if i > 0 {
+            key.push_str(", ");
+        }
+        key.push_str(&arg);
+    }
+    key.push(')');
+    key
+}
+
+/// Calls `executor.submit(callable, *args, **kwargs)`, returning the future.
+fn submit<'py>(
+    executor: &Bound<'py, PyAny>,
+    callable: &Bound<'py, PyAny>,
+    args: &Bound<'py, PyTuple>,
+    kwargs: &Bound<'py, PyDict>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let py = executor.py();
+    let submit_args: Vec<Bound<'_, PyAny>> = [callable.clone()].into_iter().chain(args.iter()).collect();
+    let submit_args = PyTuple::new(py, submit_args)?;
+    let kwargs = if kwargs.is_empty() { None } else { Some(kwargs) };
+    executor.call_method(intern!(py, "submit"), &submit_args, kwargs)
+}
+
+/// Cached import of `concurrent.futures.ThreadPoolExecutor`.
+fn thread_pool_executor(py: Python<'_>) -> PyResult<&Bound<'_, PyAny>> {
+    static THREAD_POOL_EXECUTOR: PyOnceLock<Py<PyAny>> = PyOnceLock::new();
+
+    THREAD_POOL_EXECUTOR.import(py, "concurrent.futures", "ThreadPoolExecutor")
+}
+
+/// Cached import of `concurrent.futures.TimeoutError`, an alias of the builtin from Python 3.11.
+fn futures_timeout_error(py: Python<'_>) -> PyResult<&Bound<'_, PyAny>> {
+    static FUTURES_TIMEOUT_ERROR: PyOnceLock<Py<PyAny>> = PyOnceLock::new();
+
+    FUTURES_TIMEOUT_ERROR.import(py, "concurrent.futures", "TimeoutError")
 }
@@ -4,21 +4,30 @@
 //! sandboxed Python code with configurable resource limits and external
 //! function callbacks.
 
+mod audit;
 mod convert;
 mod dataclass;
 mod exceptions;
 mod external;
 mod limits;
 mod monty_cls;
+mod numpy;
+mod std_types;
 
 use std::sync::OnceLock;
 
 // Use `::monty` to refer to the external crate (not the pymodule)
-pub use exceptions::{MontyError, MontyRuntimeError, MontySyntaxError, MontyTypingError, PyFrame};
-pub use monty_cls::{PyMonty, PyMontyComplete, PyMontyFutureSnapshot, PyMontyRepl, PyMontySnapshot};
+pub use exceptions::{ExternalCallTimeout, MontyError, MontyRuntimeError, MontySyntaxError, MontyTypingError, PyFrame};
+pub use monty_cls::{
+    PyMonty, PyMontyComplete, PyMontyFutureSnapshot, PyMontyRepl, PyMontyReplFutureSnapshot, PyMontyReplSnapshot,
+    PyMontySnapshot, PyMontyYield,
+};
 use pyo3::prelude::*;
 
 /// Copied from `get_pydantic_core_version` in pydantic
+///
+/// `std::sync::OnceLock` is safe to initialise concurrently, including on free-threaded builds where
+/// the module may be imported from several threads at once.
 fn get_version() -> &'static str {
     static VERSION: OnceLock<String> = OnceLock::new();
 
@@ -34,7 +43,11 @@ fn get_version() -> &'static str {
 }
 
 /// Monty - A sandboxed Python interpreter written in Rust.
-#[pymodule]
+///
+/// The module supports free-threaded (no-GIL) CPython builds: classes without mutable state are
+/// `frozen`, the remaining ones rely on PyO3's borrow checking, and shared state is either
+/// immutable, behind a `Mutex` or stored in Python objects with their own per-object locking.
+#[pymodule(gil_used = false)]
 mod _monty {
     use pyo3::prelude::*;
 
@@ -57,12 +70,19 @@ mod _monty {
     #[pymodule_export]
     use super::PyMontyRepl as MontyRepl;
     #[pymodule_export]
+    use super::PyMontyReplFutureSnapshot as MontyReplFutureSnapshot;
+    #[pymodule_export]
+    use super::PyMontyReplSnapshot as MontyReplSnapshot;
+    #[pymodule_export]
     use super::PyMontySnapshot as MontySnapshot;
-    use super::get_version;
+    #[pymodule_export]
+    use super::PyMontyYield as MontyYield;
+    use super::{ExternalCallTimeout, get_version};
 
     #[pymodule_init]
     fn init(m: &Bound<'_, PyModule>) -> PyResult<()> {
         m.add("__version__", get_version())?;
+        m.add("ExternalCallTimeout", m.py().get_type::<ExternalCallTimeout>())?;
         Ok(())
     }
 }
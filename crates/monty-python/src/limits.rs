@@ -3,15 +3,15 @@
 //! Provides a TypedDict interface to configure resource limits for code execution,
 //! including time limits, memory limits, and recursion depth.
 
-use std::{
-    sync::atomic::{AtomicU16, Ordering},
-    time::Duration,
-};
+use std::time::Duration;
 
-use monty::{DEFAULT_MAX_RECURSION_DEPTH, ResourceError, ResourceTracker};
+use monty::{
+    AuditTracker, ComposedTracker, DEFAULT_MAX_RECURSION_DEPTH, DEFAULT_MAX_STACK_SIZE, InterruptCheck,
+    InterruptTracker, ResourceError, ResourceTracker,
+};
 use pyo3::{prelude::*, types::PyDict};
 
-use crate::exceptions::exc_py_to_monty;
+use crate::{audit::PyAuditHook, exceptions::exc_py_to_monty};
 
 /// Extracts resource limits from a Python dict.
 ///
@@ -21,9 +21,15 @@ use crate::exceptions::exc_py_to_monty;
 /// - `max_memory`: Maximum heap memory in bytes (int)
 /// - `gc_interval`: Run garbage collection every N allocations (int)
 /// - `max_recursion_depth`: Maximum function call stack depth (int, default: 1000)
+/// - `max_output_size`: Maximum size in bytes of the result and external call arguments (int)
+/// - `max_interned_size`: Maximum size in bytes of interned literals, checked by REPL snippets (int)
+/// - `max_stack_size`: Maximum Rust stack usage in bytes (int, default: 1MiB)
+/// - `trace_allocations`: Record allocation sites for `MontyRepl.memory_profile()` (bool)
+///
+/// `max_ndarray_size` is also accepted but read separately by [`extract_max_ndarray_size`].
 ///
 /// If a key is missing or set to `None`, that limit is not applied
-/// (except `max_recursion_depth` which defaults to 1000).
+/// (except `max_recursion_depth` which defaults to 1000 and `max_stack_size` which defaults to 1MiB).
 ///
 /// Raises `TypeError` if a value is present but has the wrong type.
 pub fn extract_limits(dict: &Bound<'_, PyDict>) -> PyResult<monty::ResourceLimits> {
@@ -33,8 +39,15 @@ pub fn extract_limits(dict: &Bound<'_, PyDict>) -> PyResult<monty::ResourceLimit
     let gc_interval = extract_optional_usize(dict, "gc_interval")?;
     let max_recursion_depth =
         extract_optional_usize(dict, "max_recursion_depth")?.or(Some(DEFAULT_MAX_RECURSION_DEPTH));
+    let max_output_size = extract_optional_usize(dict, "max_output_size")?;
+    let max_interned_size = extract_optional_usize(dict, "max_interned_size")?;
+    let max_stack_size = extract_optional_usize(dict, "max_stack_size")?.or(Some(DEFAULT_MAX_STACK_SIZE));
+    let trace_allocations = extract_optional_bool(dict, "trace_allocations")?.unwrap_or(false);
 
-    let mut limits = monty::ResourceLimits::new().max_recursion_depth(max_recursion_depth);
+    let mut limits = monty::ResourceLimits::new()
+        .max_recursion_depth(max_recursion_depth)
+        .max_stack_size(max_stack_size)
+        .trace_allocations(trace_allocations);
 
     if let Some(max) = max_allocations {
         limits = limits.max_allocations(max);
@@ -48,10 +61,26 @@ pub fn extract_limits(dict: &Bound<'_, PyDict>) -> PyResult<monty::ResourceLimit
     if let Some(interval) = gc_interval {
         limits = limits.gc_interval(interval);
     }
+    if let Some(max) = max_output_size {
+        limits = limits.max_output_size(max);
+    }
+    if let Some(max) = max_interned_size {
+        limits = limits.max_interned_size(max);
+    }
 
     Ok(limits)
 }
 
+/// Extracts the `max_ndarray_size` limit, which only applies to input conversion.
+///
+/// numpy arrays are only converted to lists when this is set, see `numpy::numpy_to_monty`.
+pub fn extract_max_ndarray_size(limits: Option<&Bound<'_, PyDict>>) -> PyResult<Option<usize>> {
+    match limits {
+        Some(dict) => extract_optional_usize(dict, "max_ndarray_size"),
+        None => Ok(None),
+    }
+}
+
 /// Extracts an optional usize from a dict, raising `TypeError` if the value has the wrong type.
 fn extract_optional_usize(dict: &Bound<'_, PyDict>, key: &str) -> PyResult<Option<usize>> {
     match dict.get_item(key)? {
@@ -70,6 +99,15 @@ fn extract_optional_f64(dict: &Bound<'_, PyDict>, key: &str) -> PyResult<Option<
     }
 }
 
+/// Extracts an optional bool from a dict, raising `TypeError` if the value has the wrong type.
+fn extract_optional_bool(dict: &Bound<'_, PyDict>, key: &str) -> PyResult<Option<bool>> {
+    match dict.get_item(key)? {
+        None => Ok(None),
+        Some(value) if value.is_none() => Ok(None),
+        Some(value) => Ok(Some(value.extract()?)),
+    }
+}
+
 /// How often to check Python signals (every N calls to `check_time`).
 ///
 /// This balances responsiveness to Ctrl+C against performance overhead.
@@ -77,66 +115,32 @@ fn extract_optional_f64(dict: &Bound<'_, PyDict>, key: &str) -> PyResult<Option<
 /// responding to interrupts within a reasonable timeframe.
 const SIGNAL_CHECK_INTERVAL: u16 = 1000;
 
-/// A resource tracker that wraps another ResourceTracker and periodically checks Python signals.
-///
-/// This allows Ctrl+C and other Python signals to interrupt long-running code
-/// executed through the monty interpreter. Signals are checked every
-/// `SIGNAL_CHECK_INTERVAL` calls to `check_time` (at statement boundaries).
+/// Checks for Python signals so Ctrl+C and other signals can interrupt running code.
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
-pub struct PySignalTracker<T: ResourceTracker> {
-    inner: T,
-    /// Counter for check_time calls, used to rate-limit signal checks.
-    ///
-    /// Uses `AtomicU16` for interior mutability so `check_time` can take `&self`
-    /// (required by the `ResourceTracker` trait) while remaining `Sync` for PyO3.
-    check_counter: AtomicU16,
-}
-
-impl<T: ResourceTracker> PySignalTracker<T> {
-    /// Creates a new signal-checking tracker wrapping the given tracker.
-    pub fn new(inner: T) -> Self {
-        Self {
-            inner,
-            check_counter: AtomicU16::new(0),
-        }
-    }
-
-    fn check_python_signals(&self) -> Result<(), ResourceError> {
-        // Periodically check Python signals
-        let count = self.check_counter.fetch_add(1, Ordering::Relaxed).wrapping_add(1);
-
-        if count.is_multiple_of(SIGNAL_CHECK_INTERVAL) {
-            Python::attach(|py| {
-                py.check_signals()
-                    .map_err(|e| ResourceError::Exception(exc_py_to_monty(py, &e)))
-            })?;
-        }
-        Ok(())
+pub struct PySignalCheck;
+
+impl InterruptCheck for PySignalCheck {
+    fn check(&self) -> Result<(), ResourceError> {
+        Python::attach(|py| {
+            py.check_signals()
+                .map_err(|e| ResourceError::Exception(exc_py_to_monty(py, &e)))
+        })
     }
 }
 
-impl<T: ResourceTracker> ResourceTracker for PySignalTracker<T> {
-    fn on_allocate(&mut self, get_size: impl FnOnce() -> usize) -> Result<(), ResourceError> {
-        self.inner.on_allocate(get_size)
-    }
-
-    fn on_free(&mut self, get_size: impl FnOnce() -> usize) {
-        self.inner.on_free(get_size);
-    }
-
-    fn check_time(&self) -> Result<(), ResourceError> {
-        // First check inner tracker's time limit
-        self.inner.check_time()?;
-
-        // then periodically check for Python signals
-        self.check_python_signals()
-    }
-
-    fn check_recursion_depth(&self, current_depth: usize) -> Result<(), ResourceError> {
-        self.inner.check_recursion_depth(current_depth)
-    }
-
-    fn check_large_result(&self, estimated_bytes: usize) -> Result<(), ResourceError> {
-        self.inner.check_large_result(estimated_bytes)
-    }
+/// The resource tracker used by the bindings, wrapping another ResourceTracker.
+///
+/// Periodically checks Python signals, so Ctrl+C and other signals can interrupt long-running
+/// code executed through the monty interpreter. Signals are checked every
+/// `SIGNAL_CHECK_INTERVAL` calls to `check_time` (at statement boundaries), after the inner
+/// tracker's own checks.
+///
+/// Also passes audit events to the `audit_callback`, if any, see [`PyAuditHook`].
+pub type PyTracker<T> = ComposedTracker<ComposedTracker<T, InterruptTracker<PySignalCheck>>, AuditTracker<PyAuditHook>>;
+
+/// Wraps `inner` in a [`PyTracker`].
+pub fn py_tracker<T: ResourceTracker>(inner: T, audit_callback: Option<&Bound<'_, PyAny>>) -> PyTracker<T> {
+    inner
+        .chain(InterruptTracker::new(PySignalCheck, SIGNAL_CHECK_INTERVAL))
+        .chain(AuditTracker::new(PyAuditHook::new(audit_callback)))
 }
@@ -1,27 +1,31 @@
-use std::{borrow::Cow, fmt::Write};
+use std::{borrow::Cow, collections::HashMap, fmt::Write};
 
 // Use `::monty` to refer to the external crate (not the pymodule)
 use ::monty::{
     ExternalResult, LimitedTracker, MontyException, MontyObject, MontyRepl as CoreMontyRepl, MontyRun, NoLimitTracker,
-    PrintWriter, PrintWriterCallback, ResourceTracker, RunProgress, Snapshot,
+    PrintWriter, PrintWriterCallback, ResourceTracker, RunProgress, SharedStr, Snapshot,
+};
+use monty::{
+    Capability, ExcType, FutureSnapshot, MemoryProfile, OsFunction, ReplContinuationMode, ReplFutureSnapshot,
+    ReplProgress, ReplSnapshot, ReplStartError, detect_repl_continuation_mode, sign_serialized, verify_serialized,
 };
-use monty::{ExcType, FutureSnapshot, OsFunction};
 use monty_type_checking::{SourceFile, type_check};
 use pyo3::{
     IntoPyObjectExt,
-    exceptions::{PyKeyError, PyRuntimeError, PyTypeError, PyValueError},
+    exceptions::{PyBaseException, PyKeyError, PyRuntimeError, PyTypeError, PyValueError},
     intern,
     prelude::*,
-    types::{PyBytes, PyDict, PyList, PyTuple, PyType},
+    types::{PyBytes, PyDict, PyList, PyString, PyTuple, PyType},
 };
 use send_wrapper::SendWrapper;
 
 use crate::{
+    audit::audit_denied,
     convert::{monty_to_py, py_to_monty},
     dataclass::DcRegistry,
     exceptions::{MontyError, MontyTypingError, exc_py_to_monty},
-    external::{ExternalFunctionRegistry, dispatch_method_call},
-    limits::{PySignalTracker, extract_limits},
+    external::{CallPolicy, ExternalFunctionRegistry, dispatch_method_call, extract_call_policies},
+    limits::{PyTracker, extract_limits, extract_max_ndarray_size, py_tracker},
 };
 
 /// A sandboxed Python interpreter instance.
@@ -29,7 +33,7 @@ use crate::{
 /// Parses and compiles Python code on initialization, then can be run
 /// multiple times with different input values. This separates the parsing
 /// cost from execution, making repeated runs more efficient.
-#[pyclass(name = "Monty", module = "pydantic_monty")]
+#[pyclass(name = "Monty", module = "pydantic_monty", frozen)]
 #[derive(Debug)]
 pub struct PyMonty {
     /// The compiled code snapshot, ready to execute.
@@ -53,41 +57,69 @@ impl PyMonty {
     ///
     /// # Arguments
     /// * `code` - Python code to execute
+    /// * `module_name` - Value of `__name__` in the code, `"__main__"` if not set
     /// * `inputs` - List of input variable names available in the code
     /// * `external_functions` - List of external function names the code can call
     /// * `type_check` - Whether to perform type checking on the code
     /// * `type_check_stubs` - Prefix code to be executed before type checking
     /// * `dataclass_registry` - Registry of dataclass types for reconstructing original types on output.
+    /// * `constants` - Names and values baked into the code, which can't be reassigned
+    /// * `optimize` - Whether to remove assert statements and set `__debug__` to `False`, like `python -O`
     #[new]
-    #[pyo3(signature = (code, *, script_name="main.py", inputs=None, external_functions=None, type_check=false, type_check_stubs=None, dataclass_registry=None))]
+    #[pyo3(signature = (code, *, script_name="main.py", module_name=None, inputs=None, external_functions=None, type_check=false, type_check_stubs=None, dataclass_registry=None, constants=None, optimize=false))]
     #[expect(clippy::too_many_arguments)]
     fn new(
         py: Python<'_>,
         code: String,
         script_name: &str,
+        module_name: Option<&str>,
         inputs: Option<&Bound<'_, PyList>>,
         external_functions: Option<&Bound<'_, PyList>>,
         type_check: bool,
         type_check_stubs: Option<&str>,
         dataclass_registry: Option<&Bound<'_, PyList>>,
+        constants: Option<&Bound<'_, PyDict>>,
+        optimize: bool,
     ) -> PyResult<Self> {
         let input_names = list_str(inputs, "inputs")?;
         let external_function_names = list_str(external_functions, "external_functions")?;
+        let dc_registry = DcRegistry::from_list(py, dataclass_registry)?;
+        let constants = match constants {
+            Some(constants) => constants
+                .iter()
+                .map(|(name, value)| Ok((name.extract::<String>()?, py_to_monty(&value, &dc_registry)?)))
+                .collect::<PyResult<Vec<_>>>()?,
+            None => vec![],
+        };
 
         if type_check {
             py_type_check(py, &code, script_name, type_check_stubs)?;
         }
 
         // Create the snapshot (parses the code)
-        let runner = MontyRun::new(code, script_name, input_names.clone(), external_function_names.clone())
-            .map_err(|e| MontyError::new_err(py, e))?;
+        let new_runner = if optimize {
+            MontyRun::new_optimized
+        } else {
+            MontyRun::new_with_constants
+        };
+        let mut runner = new_runner(
+            code,
+            script_name,
+            input_names.clone(),
+            external_function_names.clone(),
+            constants,
+        )
+        .map_err(|e| MontyError::new_err(py, e))?;
+        if let Some(module_name) = module_name {
+            runner = runner.with_module_name(module_name);
+        }
 
         Ok(Self {
             runner,
             script_name: script_name.to_string(),
             input_names,
             external_function_names,
-            dc_registry: DcRegistry::from_list(py, dataclass_registry)?,
+            dc_registry,
         })
     }
 
@@ -124,12 +156,20 @@ impl PyMonty {
 
     /// Executes the code and returns the result.
     ///
+    /// With `zero_copy`, Monty reads top-level `str` inputs from the UTF-8 buffers of the Python
+    /// strings instead of copying them, see `PyStrBuffer`. The strings are released when the
+    /// run finishes.
+    ///
+    /// Values streamed with `monty_yield()` are passed to `yield_callback`, whose return value
+    /// `monty_yield()` returns. Without a callback, `monty_yield()` raises `NotImplementedError`.
+    ///
     /// # Returns
     /// The result of the last expression in the code
     ///
     /// # Raises
     /// Various Python exceptions matching what the code would raise
-    #[pyo3(signature = (*, inputs=None, limits=None, external_functions=None, print_callback=None, os=None))]
+    #[pyo3(signature = (*, inputs=None, limits=None, external_functions=None, print_callback=None, os=None, zero_copy=false, executor=None, audit_callback=None, call_policies=None, pure=None, cache=None, yield_callback=None))]
+    #[expect(clippy::too_many_arguments)]
     fn run(
         &self,
         py: Python<'_>,
@@ -138,10 +178,21 @@ impl PyMonty {
         external_functions: Option<&Bound<'_, PyDict>>,
         print_callback: Option<&Bound<'_, PyAny>>,
         os: Option<&Bound<'_, PyAny>>,
+        zero_copy: bool,
+        executor: Option<&Bound<'_, PyAny>>,
+        audit_callback: Option<&Bound<'_, PyAny>>,
+        call_policies: Option<&Bound<'_, PyDict>>,
+        pure: Option<&Bound<'_, PyList>>,
+        cache: Option<&Bound<'_, PyAny>>,
+        yield_callback: Option<&Bound<'_, PyAny>>,
     ) -> PyResult<Py<PyAny>> {
         // Clone the Arc handle — all clones share the same underlying registry,
         // so auto-registrations during execution are visible to all users.
-        let input_values = self.extract_input_values(inputs, &self.dc_registry)?;
+        let dc_registry = self
+            .dc_registry
+            .clone_ref(py)
+            .with_max_ndarray_size(extract_max_ndarray_size(limits)?);
+        let input_values = self.extract_input_values(inputs, &dc_registry, zero_copy)?;
 
         if let Some(os_callback) = os
             && !os_callback.is_callable()
@@ -149,6 +200,34 @@ impl PyMonty {
             let msg = format!("TypeError: '{}' object is not callable", os_callback.get_type().name()?);
             return Err(PyTypeError::new_err(msg));
         }
+        if let Some(yield_callback) = yield_callback
+            && !yield_callback.is_callable()
+        {
+            let msg = format!(
+                "TypeError: '{}' object is not callable",
+                yield_callback.get_type().name()?
+            );
+            return Err(PyTypeError::new_err(msg));
+        }
+        if let Some(executor) = executor
+            && !executor.hasattr(intern!(py, "submit"))?
+        {
+            let msg = format!(
+                "TypeError: '{}' object is not an executor, expected a concurrent.futures.Executor",
+                executor.get_type().name()?
+            );
+            return Err(PyTypeError::new_err(msg));
+        }
+        let call_policies = extract_call_policies(call_policies)?;
+        let pure = list_str(pure, "pure")?;
+        let calls = ExternalCalls {
+            functions: external_functions,
+            executor,
+            policies: &call_policies,
+            pure: &pure,
+            cache,
+            yield_callback,
+        };
 
         // Build print writer
         let mut print_cb;
@@ -162,25 +241,29 @@ impl PyMonty {
 
         // Run with appropriate tracker type (must branch due to different generic types)
         if let Some(limits) = limits {
-            let tracker = PySignalTracker::new(LimitedTracker::new(extract_limits(limits)?));
-            self.run_impl(py, input_values, tracker, external_functions, os, print_writer)
+            let tracker = py_tracker(LimitedTracker::new(extract_limits(limits)?), audit_callback);
+            self.run_impl(py, input_values, tracker, &calls, os, print_writer, &dc_registry)
         } else {
-            let tracker = PySignalTracker::new(NoLimitTracker);
-            self.run_impl(py, input_values, tracker, external_functions, os, print_writer)
+            let tracker = py_tracker(NoLimitTracker, audit_callback);
+            self.run_impl(py, input_values, tracker, &calls, os, print_writer, &dc_registry)
         }
     }
 
-    #[pyo3(signature = (*, inputs=None, limits=None, print_callback=None))]
+    #[pyo3(signature = (*, inputs=None, limits=None, print_callback=None, audit_callback=None))]
     fn start<'py>(
         &self,
         py: Python<'py>,
         inputs: Option<&Bound<'py, PyDict>>,
         limits: Option<&Bound<'py, PyDict>>,
         print_callback: Option<Bound<'_, PyAny>>,
+        audit_callback: Option<&Bound<'_, PyAny>>,
     ) -> PyResult<Bound<'py, PyAny>> {
         // Clone the Arc handle — shares the same underlying registry
-        let dc_registry = self.dc_registry.clone_ref(py);
-        let input_values = self.extract_input_values(inputs, &dc_registry)?;
+        let dc_registry = self
+            .dc_registry
+            .clone_ref(py)
+            .with_max_ndarray_size(extract_max_ndarray_size(limits)?);
+        let input_values = self.extract_input_values(inputs, &dc_registry, false)?;
 
         // Build print writer - CallbackStringPrint is Send so GIL can be released
         let mut print_cb;
@@ -205,10 +288,10 @@ impl PyMonty {
 
         // Branch on limits (different generic types)
         let progress = if let Some(limits) = limits {
-            let tracker = PySignalTracker::new(LimitedTracker::new(extract_limits(limits)?));
+            let tracker = py_tracker(LimitedTracker::new(extract_limits(limits)?), audit_callback);
             EitherProgress::Limited(start_impl!(tracker))
         } else {
-            let tracker = PySignalTracker::new(NoLimitTracker);
+            let tracker = py_tracker(NoLimitTracker, audit_callback);
             EitherProgress::NoLimit(start_impl!(tracker))
         };
         progress.progress_or_complete(
@@ -224,19 +307,26 @@ impl PyMonty {
     /// The serialized data can be stored and later restored with `Monty.load()`.
     /// This allows caching parsed code to avoid re-parsing on subsequent runs.
     ///
+    /// # Arguments
+    /// * `signing_key` - Optional key to sign the data with, see `Monty.load()`
+    ///
     /// # Returns
     /// Bytes containing the serialized Monty instance.
     ///
     /// # Raises
     /// `ValueError` if serialization fails.
-    fn dump<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
+    #[pyo3(signature = (*, signing_key=None))]
+    fn dump<'py>(&self, py: Python<'py>, signing_key: Option<&[u8]>) -> PyResult<Bound<'py, PyBytes>> {
         let serialized = SerializedMonty {
             runner: self.runner.clone(),
             script_name: self.script_name.clone(),
             input_names: self.input_names.clone(),
             external_function_names: self.external_function_names.clone(),
         };
-        let bytes = postcard::to_allocvec(&serialized).map_err(|e| PyValueError::new_err(e.to_string()))?;
+        let mut bytes = postcard::to_allocvec(&serialized).map_err(|e| PyValueError::new_err(e.to_string()))?;
+        if let Some(key) = signing_key {
+            bytes = sign_serialized(bytes, key);
+        }
         Ok(PyBytes::new(py, &bytes))
     }
 
@@ -244,21 +334,26 @@ impl PyMonty {
     ///
     /// # Arguments
     /// * `data` - The serialized Monty data from `dump()`
+    /// * `signing_key` - If set, the data must have been signed with this key by `dump()`
     /// * `dataclass_registry` - Optional list of dataclasses to register
     ///
     /// # Returns
     /// A new Monty instance.
     ///
     /// # Raises
-    /// `ValueError` if deserialization fails.
+    /// `ValueError` if deserialization fails or the signature is invalid.
     #[staticmethod]
-    #[pyo3(signature = (data, *, dataclass_registry=None))]
+    #[pyo3(signature = (data, *, signing_key=None, dataclass_registry=None))]
     fn load(
         py: Python<'_>,
         data: &Bound<'_, PyBytes>,
+        signing_key: Option<&[u8]>,
         dataclass_registry: Option<&Bound<'_, PyList>>,
     ) -> PyResult<Self> {
-        let bytes = data.as_bytes();
+        let mut bytes = data.as_bytes();
+        if let Some(key) = signing_key {
+            bytes = verify_serialized(bytes, key).map_err(|e| PyValueError::new_err(e.to_string()))?;
+        }
         let serialized: SerializedMonty =
             postcard::from_bytes(bytes).map_err(|e| PyValueError::new_err(e.to_string()))?;
 
@@ -271,6 +366,20 @@ impl PyMonty {
         })
     }
 
+    /// SHA-256 hash of the source code as a hex string, e.g. for cache keys.
+    ///
+    /// Depends only on the code, so it's preserved by `dump()` and `load()`.
+    #[getter]
+    fn code_hash(&self) -> String {
+        self.runner
+            .code_hash()
+            .iter()
+            .fold(String::with_capacity(64), |mut hex, byte| {
+                write!(hex, "{byte:02x}").unwrap();
+                hex
+            })
+    }
+
     fn __repr__(&self) -> String {
         let lines = self.runner.code().lines().count();
         let mut s = format!(
@@ -303,16 +412,58 @@ fn py_type_check(py: Python<'_>, code: &str, script_name: &str, type_stubs: Opti
     }
 }
 
+/// The UTF-8 buffer of a Python `str`, borrowed for as long as the string is kept alive.
+///
+/// Passed to Monty as a `MontyObject::SharedString`, so the heap references the buffer CPython
+/// keeps with the string instead of a copy. The string is released when Monty drops the last
+/// reference, at the latest when the run finishes.
+struct PyStrBuffer {
+    /// Keeps the string, and with it the buffer, alive.
+    _owner: Py<PyString>,
+    /// Start of the buffer returned by `PyString::to_str`.
+    ptr: *const u8,
+    /// Length of the buffer in bytes.
+    len: usize,
+}
+
+impl PyStrBuffer {
+    /// Borrows the UTF-8 buffer of `string`, creating it if CPython hasn't yet.
+    fn new(string: &Bound<'_, PyString>) -> PyResult<Self> {
+        let buffer = string.to_str()?;
+        Ok(Self {
+            ptr: buffer.as_ptr(),
+            len: buffer.len(),
+            _owner: string.clone().unbind(),
+        })
+    }
+}
+
+// SAFETY: the buffer is immutable and lives as long as `_owner`, which is `Send + Sync`
+unsafe impl Send for PyStrBuffer {}
+// SAFETY: as for `Send`, reading the buffer needs neither the GIL nor exclusive access
+unsafe impl Sync for PyStrBuffer {}
+
+impl AsRef<str> for PyStrBuffer {
+    fn as_ref(&self) -> &str {
+        // SAFETY: `ptr` and `len` describe the UTF-8 buffer returned by `to_str()`, which CPython
+        // caches on the string object and only frees with it. `str` objects are immutable and
+        // `_owner` keeps this one alive, so the buffer is valid and unchanged.
+        unsafe { std::str::from_utf8_unchecked(std::slice::from_raw_parts(self.ptr, self.len)) }
+    }
+}
+
 impl PyMonty {
     /// Extracts input values from a Python dict in the order they were declared.
     ///
     /// Validates that all required inputs are provided. Any dataclass inputs are
     /// automatically registered in `dc_registry` via `py_to_monty` so they can be
-    /// properly reconstructed on output.
+    /// properly reconstructed on output. With `zero_copy`, top-level strings are passed
+    /// as borrowed buffers, see `PyStrBuffer`.
     fn extract_input_values(
         &self,
         inputs: Option<&Bound<'_, PyDict>>,
         dc_registry: &DcRegistry,
+        zero_copy: bool,
     ) -> PyResult<Vec<::monty::MontyObject>> {
         if self.input_names.is_empty() {
             if inputs.is_some() {
@@ -337,6 +488,10 @@ impl PyMonty {
                 let value = inputs
                     .get_item(name)?
                     .ok_or_else(|| PyKeyError::new_err(format!("Missing required input: '{name}'")))?;
+                if zero_copy && let Ok(string) = value.cast_exact::<PyString>() {
+                    let buffer = PyStrBuffer::new(string)?;
+                    return Ok(MontyObject::SharedString(SharedStr::new(buffer)));
+                }
                 py_to_monty(&value, dc_registry)
             })
             .collect::<PyResult<_>>()
@@ -345,14 +500,16 @@ impl PyMonty {
     ///
     /// Takes explicit field references instead of `&mut self` so that `run()` can
     /// remain `&self` (required for concurrent thread access in PyO3).
+    #[expect(clippy::too_many_arguments)]
     fn run_impl(
         &self,
         py: Python<'_>,
         input_values: Vec<MontyObject>,
         tracker: impl ResourceTracker + Send,
-        external_functions: Option<&Bound<'_, PyDict>>,
+        calls: &ExternalCalls<'_, '_>,
         os: Option<&Bound<'_, PyAny>>,
         mut print_output: PrintWriter<'_>,
+        dc_registry: &DcRegistry,
     ) -> PyResult<Py<PyAny>> {
         // wrap print_output in SendWrapper so that it can be accessed inside the py.detach calls despite
         // no `Send` bound - py.detach() is overly restrictive to prevent `Bound` types going inside
@@ -363,21 +520,31 @@ impl PyMonty {
         // and need to be dispatched to the host.
         let has_dataclass_inputs = || input_values.iter().any(contains_dataclass);
 
-        if self.external_function_names.is_empty() && os.is_none() && !has_dataclass_inputs() {
+        if self.external_function_names.is_empty()
+            && calls.pure.is_empty()
+            && calls.yield_callback.is_none()
+            && os.is_none()
+            && !has_dataclass_inputs()
+        {
             return match py.detach(|| self.runner.run(input_values, tracker, &mut print_output)) {
-                Ok(v) => monty_to_py(py, &v, &self.dc_registry),
+                Ok(v) => monty_to_py(py, &v, dc_registry),
                 Err(err) => Err(MontyError::new_err(py, err)),
             };
         }
         // Clone the runner since start() consumes it - allows reuse of the parsed code
-        let runner = self.runner.clone();
+        let mut runner = self.runner.clone();
+        for name in calls.pure {
+            runner
+                .mark_pure(name)
+                .map_err(|e| PyValueError::new_err(e.message().unwrap_or_default().to_owned()))?;
+        }
         let mut progress = py
             .detach(|| runner.start(input_values, tracker, &mut print_output))
             .map_err(|e| MontyError::new_err(py, e))?;
 
         loop {
             match progress {
-                RunProgress::Complete(result) => return monty_to_py(py, &result, &self.dc_registry),
+                RunProgress::Complete { value: result, .. } => return monty_to_py(py, &result, dc_registry),
                 RunProgress::FunctionCall {
                     function_name,
                     args,
@@ -388,11 +555,15 @@ impl PyMonty {
                 } => {
                     // Dataclass method calls have method_call=true and the first arg is the instance
                     let return_value = if method_call {
-                        dispatch_method_call(py, &function_name, &args, &kwargs, &self.dc_registry)
-                    } else if let Some(ext_fns) = external_functions {
-                        let registry = ExternalFunctionRegistry::new(py, ext_fns, &self.dc_registry);
+                        dispatch_method_call(py, &function_name, &args, &kwargs, dc_registry)
+                    } else if let Some(ext_fns) = calls.functions {
+                        let registry = ExternalFunctionRegistry::new(py, ext_fns, dc_registry)
+                            .with_executor(calls.executor)
+                            .with_policies(calls.policies)
+                            .with_cache(calls.cache, calls.pure);
                         registry.call(&function_name, &args, &kwargs)
                     } else {
+                        audit_denied(&state, Capability::ExternalFunction, &function_name);
                         return Err(PyRuntimeError::new_err(format!(
                             "External function '{function_name}' called but no external_functions provided"
                         )));
@@ -402,9 +573,33 @@ impl PyMonty {
                         .detach(|| state.run(return_value, &mut print_output))
                         .map_err(|e| MontyError::new_err(py, e))?;
                 }
-                RunProgress::ResolveFutures { .. } => {
+                RunProgress::ResolveFutures { .. } | RunProgress::FunctionCallBatch { .. } => {
                     return Err(PyRuntimeError::new_err("async futures not supported with `Monty.run`"));
                 }
+                RunProgress::Yield { value, state } => {
+                    let result: ExternalResult = if let Some(yield_callback) = calls.yield_callback {
+                        match yield_callback.call1((monty_to_py(py, &value, dc_registry)?,)) {
+                            Ok(result) => py_to_monty(&result, dc_registry)?.into(),
+                            Err(err) => exc_py_to_monty(py, &err).into(),
+                        }
+                    } else {
+                        audit_denied(&state, Capability::Yield, "monty_yield");
+                        MontyException::new(
+                            ExcType::NotImplementedError,
+                            Some("monty_yield() called but no yield_callback provided".to_owned()),
+                        )
+                        .into()
+                    };
+
+                    progress = py
+                        .detach(|| state.run(result, &mut print_output))
+                        .map_err(|e| MontyError::new_err(py, e))?;
+                }
+                RunProgress::Checkpoint(state) => {
+                    progress = py
+                        .detach(|| state.run(&mut print_output))
+                        .map_err(|e| MontyError::new_err(py, e))?;
+                }
                 RunProgress::OsCall {
                     function,
                     args,
@@ -416,25 +611,23 @@ impl PyMonty {
                         // Convert args to Python
                         let py_args: Vec<Py<PyAny>> = args
                             .iter()
-                            .map(|arg| monty_to_py(py, arg, &self.dc_registry))
+                            .map(|arg| monty_to_py(py, arg, dc_registry))
                             .collect::<PyResult<_>>()?;
                         let py_args_tuple = PyTuple::new(py, py_args)?;
 
                         // Convert kwargs to Python dict
                         let py_kwargs = PyDict::new(py);
                         for (k, v) in &kwargs {
-                            py_kwargs.set_item(
-                                monty_to_py(py, k, &self.dc_registry)?,
-                                monty_to_py(py, v, &self.dc_registry)?,
-                            )?;
+                            py_kwargs.set_item(monty_to_py(py, k, dc_registry)?, monty_to_py(py, v, dc_registry)?)?;
                         }
 
                         // call the os callback, if an exception is raised, return it to monty
                         match os_callback.call1((function.to_string(), py_args_tuple, py_kwargs)) {
-                            Ok(result) => py_to_monty(&result, &self.dc_registry)?.into(),
+                            Ok(result) => py_to_monty(&result, dc_registry)?.into(),
                             Err(err) => exc_py_to_monty(py, &err).into(),
                         }
                     } else {
+                        audit_denied(&state, Capability::OsFunction, &function.to_string());
                         MontyException::new(
                             ExcType::NotImplementedError,
                             Some(format!("OS function '{function}' not implemented")),
@@ -451,11 +644,27 @@ impl PyMonty {
     }
 }
 
+/// How `Monty.run()` calls external functions, bundled to keep `run_impl`'s signature manageable.
+struct ExternalCalls<'a, 'py> {
+    /// The `external_functions` dict of callables by name.
+    functions: Option<&'a Bound<'py, PyDict>>,
+    /// `concurrent.futures.Executor` to submit calls to.
+    executor: Option<&'a Bound<'py, PyAny>>,
+    /// Timeouts and retries by function name.
+    policies: &'a HashMap<String, CallPolicy>,
+    /// Functions whose results are memoized.
+    pure: &'a [String],
+    /// Mapping to keep results of pure functions in across runs.
+    cache: Option<&'a Bound<'py, PyAny>>,
+    /// Callback receiving the values streamed with `monty_yield()`.
+    yield_callback: Option<&'a Bound<'py, PyAny>>,
+}
+
 /// pyclass doesn't support generic types, hence hard coding the generics
 #[derive(Debug)]
 enum EitherProgress {
-    NoLimit(RunProgress<PySignalTracker<NoLimitTracker>>),
-    Limited(RunProgress<PySignalTracker<LimitedTracker>>),
+    NoLimit(RunProgress<PyTracker<NoLimitTracker>>),
+    Limited(RunProgress<PyTracker<LimitedTracker>>),
 }
 
 impl EitherProgress {
@@ -468,7 +677,11 @@ impl EitherProgress {
     ) -> PyResult<Bound<'_, PyAny>> {
         match self {
             Self::NoLimit(p) => match p {
-                RunProgress::Complete(result) => PyMontyComplete::create(py, &result, &dc_registry),
+                RunProgress::Complete {
+                    value: result,
+                    memory_profile,
+                    ..
+                } => PyMontyComplete::create(py, &result, memory_profile, &dc_registry),
                 RunProgress::FunctionCall {
                     function_name,
                     args,
@@ -511,9 +724,27 @@ impl EitherProgress {
                     print_callback,
                     dc_registry,
                 ),
+                RunProgress::Yield { value, state } => Self::yield_snapshot(
+                    py,
+                    &value,
+                    EitherSnapshot::NoLimit(state),
+                    script_name,
+                    print_callback,
+                    dc_registry,
+                ),
+                RunProgress::Checkpoint(_) => Err(PyRuntimeError::new_err(
+                    "checkpoints are not yet supported by `Monty.start`",
+                )),
+                RunProgress::FunctionCallBatch { .. } => Err(PyRuntimeError::new_err(
+                    "batched calls are not yet supported by `Monty.start`",
+                )),
             },
             Self::Limited(p) => match p {
-                RunProgress::Complete(result) => PyMontyComplete::create(py, &result, &dc_registry),
+                RunProgress::Complete {
+                    value: result,
+                    memory_profile,
+                    ..
+                } => PyMontyComplete::create(py, &result, memory_profile, &dc_registry),
                 RunProgress::FunctionCall {
                     function_name,
                     args,
@@ -556,6 +787,20 @@ impl EitherProgress {
                     print_callback,
                     dc_registry,
                 ),
+                RunProgress::Yield { value, state } => Self::yield_snapshot(
+                    py,
+                    &value,
+                    EitherSnapshot::Limited(state),
+                    script_name,
+                    print_callback,
+                    dc_registry,
+                ),
+                RunProgress::Checkpoint(_) => Err(PyRuntimeError::new_err(
+                    "checkpoints are not yet supported by `Monty.start`",
+                )),
+                RunProgress::FunctionCallBatch { .. } => Err(PyRuntimeError::new_err(
+                    "batched calls are not yet supported by `Monty.start`",
+                )),
             },
         }
     }
@@ -626,6 +871,24 @@ impl EitherProgress {
         slf.into_bound_py_any(py)
     }
 
+    fn yield_snapshot<'py>(
+        py: Python<'py>,
+        value: &MontyObject,
+        snapshot: EitherSnapshot,
+        script_name: String,
+        print_callback: Option<Py<PyAny>>,
+        dc_registry: DcRegistry,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let slf = PyMontyYield {
+            value: monty_to_py(py, value, &dc_registry)?,
+            snapshot,
+            print_callback,
+            dc_registry,
+            script_name,
+        };
+        slf.into_bound_py_any(py)
+    }
+
     fn future_snapshot(
         py: Python<'_>,
         snapshot: EitherFutureSnapshot,
@@ -649,8 +912,17 @@ impl EitherProgress {
 /// resource tracker variants.
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 enum EitherRepl {
-    NoLimit(CoreMontyRepl<PySignalTracker<NoLimitTracker>>),
-    Limited(CoreMontyRepl<PySignalTracker<LimitedTracker>>),
+    NoLimit(CoreMontyRepl<PyTracker<NoLimitTracker>>),
+    Limited(CoreMontyRepl<PyTracker<LimitedTracker>>),
+    /// The session was moved into a snapshot by `feed_start()` and is restored when the
+    /// snippet completes or raises.
+    #[serde(skip)]
+    Running,
+}
+
+/// Error for using a REPL while a snippet started with `feed_start()` is suspended.
+fn repl_running_error() -> PyErr {
+    PyRuntimeError::new_err("REPL is busy running a snippet started with feed_start(), resume its snapshot first")
 }
 
 #[pyclass(name = "MontyRepl", module = "pydantic_monty")]
@@ -675,7 +947,7 @@ impl PyMontyRepl {
     /// # Returns
     /// `(repl, output)` where `output` is the initial execution result.
     #[staticmethod]
-    #[pyo3(signature = (code, *, script_name="main.py", inputs=None, external_functions=None, start_inputs=None, limits=None, print_callback=None, dataclass_registry=None))]
+    #[pyo3(signature = (code, *, script_name="main.py", inputs=None, external_functions=None, start_inputs=None, limits=None, print_callback=None, dataclass_registry=None, audit_callback=None))]
     #[expect(clippy::too_many_arguments)]
     fn create(
         py: Python<'_>,
@@ -687,10 +959,12 @@ impl PyMontyRepl {
         limits: Option<&Bound<'_, PyDict>>,
         print_callback: Option<&Bound<'_, PyAny>>,
         dataclass_registry: Option<&Bound<'_, PyList>>,
+        audit_callback: Option<&Bound<'_, PyAny>>,
     ) -> PyResult<(Self, Py<PyAny>)> {
         let input_names = list_str(inputs, "inputs")?;
         let external_function_names = list_str(external_functions, "external_functions")?;
-        let dc_registry = DcRegistry::from_list(py, dataclass_registry)?;
+        let dc_registry =
+            DcRegistry::from_list(py, dataclass_registry)?.with_max_ndarray_size(extract_max_ndarray_size(limits)?);
         let input_values = Self::extract_repl_input_values(&input_names, start_inputs, &dc_registry)?;
         let print_callback = print_callback.map(|c| c.clone().unbind());
         let print_callback_for_create = print_callback.as_ref();
@@ -704,6 +978,7 @@ impl PyMontyRepl {
             input_values,
             limits,
             print_callback_for_create,
+            audit_callback,
         )?;
 
         let output = monty_to_py(py, &output, &dc_registry)?;
@@ -719,12 +994,14 @@ impl PyMontyRepl {
     /// Feeds and executes a single incremental REPL snippet.
     ///
     /// The snippet is compiled against existing session state and executed once
-    /// without replaying previously fed snippets.
-    #[pyo3(signature = (code, *, print_callback=None))]
+    /// without replaying previously fed snippets. With `namespace`, the snippet runs
+    /// against that named namespace's globals instead of the default ones.
+    #[pyo3(signature = (code, *, namespace=None, print_callback=None))]
     fn feed<'py>(
         &mut self,
         py: Python<'py>,
         code: &str,
+        namespace: Option<&str>,
         print_callback: Option<&Bound<'_, PyAny>>,
     ) -> PyResult<Bound<'py, PyAny>> {
         if let Some(callback) = print_callback {
@@ -740,15 +1017,121 @@ impl PyMontyRepl {
             None => PrintWriter::Stdout,
         };
 
-        let output = match &mut self.repl {
-            EitherRepl::NoLimit(repl) => repl.feed(code, &mut print_writer),
-            EitherRepl::Limited(repl) => repl.feed(code, &mut print_writer),
+        let output = match (&mut self.repl, namespace) {
+            (EitherRepl::NoLimit(repl), None) => repl.feed(code, &mut print_writer),
+            (EitherRepl::NoLimit(repl), Some(namespace)) => repl.feed_in_namespace(namespace, code, &mut print_writer),
+            (EitherRepl::Limited(repl), None) => repl.feed(code, &mut print_writer),
+            (EitherRepl::Limited(repl), Some(namespace)) => repl.feed_in_namespace(namespace, code, &mut print_writer),
+            (EitherRepl::Running, _) => return Err(repl_running_error()),
         }
         .map_err(|e| MontyError::new_err(py, e))?;
 
         Ok(monty_to_py(py, &output, &self.dc_registry)?.into_bound(py))
     }
 
+    /// Starts executing a snippet, suspending at external function and OS calls.
+    ///
+    /// Returns a `MontyReplSnapshot` for external and OS calls, a `MontyReplFutureSnapshot` when
+    /// futures need resolving, or `MontyComplete` once the snippet finishes. The session moves
+    /// into the snapshot while the snippet runs and returns to this REPL when it completes or
+    /// raises, so `feed()` can't be called in between. Values passed to `monty_yield()` are
+    /// ignored.
+    #[pyo3(signature = (code, *, namespace=None, print_callback=None))]
+    fn feed_start<'py>(
+        slf: &Bound<'py, Self>,
+        code: &str,
+        namespace: Option<&str>,
+        print_callback: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let py = slf.py();
+        let (repl, print_callback, dc_registry) = {
+            let mut this = slf.try_borrow_mut()?;
+            if let Some(callback) = print_callback {
+                this.print_callback = Some(callback.clone().unbind());
+            }
+            let repl = std::mem::replace(&mut this.repl, EitherRepl::Running);
+            let print_callback = this.print_callback.as_ref().map(|cb| cb.clone_ref(py));
+            (repl, print_callback, this.dc_registry.clone_ref(py))
+        };
+
+        let mut print_cb;
+        let print_writer = match &print_callback {
+            Some(cb) => {
+                print_cb = CallbackStringPrint::from_py(cb.clone_ref(py));
+                PrintWriter::Callback(&mut print_cb)
+            }
+            None => PrintWriter::Stdout,
+        };
+        let mut print_writer = SendWrapper::new(print_writer);
+
+        macro_rules! start_impl {
+            ($repl:expr) => {{
+                let result = py.detach(|| match namespace {
+                    Some(namespace) => $repl.start_in_namespace(namespace, code, &mut print_writer),
+                    None => $repl.start(code, &mut print_writer),
+                });
+                repl_progress(
+                    py,
+                    result,
+                    slf.clone().unbind(),
+                    &mut print_writer,
+                    print_callback,
+                    dc_registry,
+                )
+            }};
+        }
+
+        match repl {
+            EitherRepl::NoLimit(repl) => start_impl!(repl),
+            EitherRepl::Limited(repl) => start_impl!(repl),
+            EitherRepl::Running => Err(repl_running_error()),
+        }
+    }
+
+    /// Whether `code` can run as is, rather than needing more lines in an interactive prompt.
+    #[staticmethod]
+    fn is_complete(code: &str) -> bool {
+        detect_repl_continuation_mode(code) == ReplContinuationMode::Complete
+    }
+
+    /// Whether `code` is complete, or what kind of input an interactive prompt should wait for.
+    ///
+    /// Returns `'complete'`, `'incomplete_implicit'` for unclosed brackets and strings, or
+    /// `'incomplete_block'` after a block header like `if x:`, where CPython waits for the
+    /// block to end with a blank line.
+    #[staticmethod]
+    fn continuation_mode(code: &str) -> &'static str {
+        match detect_repl_continuation_mode(code) {
+            ReplContinuationMode::Complete => "complete",
+            ReplContinuationMode::IncompleteImplicit => "incomplete_implicit",
+            ReplContinuationMode::IncompleteBlock => "incomplete_block",
+        }
+    }
+
+    /// Names of the namespaces created by `feed(..., namespace=...)`, sorted.
+    #[getter]
+    fn namespaces(&self) -> PyResult<Vec<String>> {
+        let mut names = match &self.repl {
+            EitherRepl::NoLimit(repl) => repl.namespace_names(),
+            EitherRepl::Limited(repl) => repl.namespace_names(),
+            EitherRepl::Running => return Err(repl_running_error()),
+        };
+        names.sort();
+        Ok(names)
+    }
+
+    /// Live heap objects by type, and allocation sites if `trace_allocations` is enabled.
+    ///
+    /// Returns a dict with `types` and `allocation_sites` lists, largest total size first.
+    fn memory_profile<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let profile = match &self.repl {
+            EitherRepl::NoLimit(repl) => repl.memory_profile(),
+            EitherRepl::Limited(repl) => repl.memory_profile(),
+            EitherRepl::Running => return Err(repl_running_error()),
+        };
+        memory_profile_to_py(py, &profile)
+    }
+
     /// Serializes this REPL session to bytes.
     fn dump<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
         #[derive(serde::Serialize)]
@@ -757,6 +1140,10 @@ impl PyMontyRepl {
             script_name: &'a str,
         }
 
+        if matches!(self.repl, EitherRepl::Running) {
+            return Err(repl_running_error());
+        }
+
         let serialized = SerializedRepl {
             repl: &self.repl,
             script_name: &self.script_name,
@@ -796,6 +1183,32 @@ impl PyMontyRepl {
     }
 }
 
+/// Converts a [`MemoryProfile`] to the dict returned by `MontyRepl.memory_profile()` and
+/// `MontyComplete.memory_profile`.
+fn memory_profile_to_py<'py>(py: Python<'py>, profile: &MemoryProfile) -> PyResult<Bound<'py, PyDict>> {
+    let types = PyList::empty(py);
+    for usage in &profile.types {
+        let item = PyDict::new(py);
+        item.set_item("type", usage.type_name)?;
+        item.set_item("count", usage.count)?;
+        item.set_item("bytes", usage.bytes)?;
+        types.append(item)?;
+    }
+    let sites = PyList::empty(py);
+    for site in &profile.allocation_sites {
+        let item = PyDict::new(py);
+        item.set_item("filename", &site.filename)?;
+        item.set_item("line", site.line)?;
+        item.set_item("count", site.count)?;
+        item.set_item("bytes", site.bytes)?;
+        sites.append(item)?;
+    }
+    let dict = PyDict::new(py);
+    dict.set_item("types", types)?;
+    dict.set_item("allocation_sites", sites)?;
+    Ok(dict)
+}
+
 impl PyMontyRepl {
     /// Creates a core REPL and returns both the stored REPL state enum and initial output.
     ///
@@ -810,6 +1223,7 @@ impl PyMontyRepl {
         input_values: Vec<MontyObject>,
         limits: Option<&Bound<'_, PyDict>>,
         print_callback: Option<&Py<PyAny>>,
+        audit_callback: Option<&Bound<'_, PyAny>>,
     ) -> PyResult<(EitherRepl, MontyObject)> {
         let mut print_cb;
         let mut print_writer = match print_callback {
@@ -821,7 +1235,7 @@ impl PyMontyRepl {
         };
 
         if let Some(limits) = limits {
-            let tracker = PySignalTracker::new(LimitedTracker::new(extract_limits(limits)?));
+            let tracker = py_tracker(LimitedTracker::new(extract_limits(limits)?), audit_callback);
             let print_writer = SendWrapper::new(&mut print_writer);
             let (repl, output) = py
                 .detach(move || {
@@ -838,7 +1252,7 @@ impl PyMontyRepl {
                 .map_err(|e| MontyError::new_err(py, e))?;
             Ok((EitherRepl::Limited(repl), output))
         } else {
-            let tracker = PySignalTracker::new(NoLimitTracker);
+            let tracker = py_tracker(NoLimitTracker, audit_callback);
             let print_writer = SendWrapper::new(&mut print_writer);
             let (repl, output) = py
                 .detach(move || {
@@ -894,14 +1308,340 @@ impl PyMontyRepl {
     }
 }
 
+/// Wraps the generic core REPL state in the enums stored by the REPL pyclasses.
+trait PyReplTracker: ResourceTracker + Sized {
+    fn either_repl(repl: CoreMontyRepl<Self>) -> EitherRepl;
+    fn either_snapshot(snapshot: ReplSnapshot<Self>) -> EitherReplSnapshot;
+    fn either_future_snapshot(snapshot: ReplFutureSnapshot<Self>) -> EitherReplFutureSnapshot;
+}
+
+impl PyReplTracker for PyTracker<NoLimitTracker> {
+    fn either_repl(repl: CoreMontyRepl<Self>) -> EitherRepl {
+        EitherRepl::NoLimit(repl)
+    }
+
+    fn either_snapshot(snapshot: ReplSnapshot<Self>) -> EitherReplSnapshot {
+        EitherReplSnapshot::NoLimit(snapshot)
+    }
+
+    fn either_future_snapshot(snapshot: ReplFutureSnapshot<Self>) -> EitherReplFutureSnapshot {
+        EitherReplFutureSnapshot::NoLimit(snapshot)
+    }
+}
+
+impl PyReplTracker for PyTracker<LimitedTracker> {
+    fn either_repl(repl: CoreMontyRepl<Self>) -> EitherRepl {
+        EitherRepl::Limited(repl)
+    }
+
+    fn either_snapshot(snapshot: ReplSnapshot<Self>) -> EitherReplSnapshot {
+        EitherReplSnapshot::Limited(snapshot)
+    }
+
+    fn either_future_snapshot(snapshot: ReplFutureSnapshot<Self>) -> EitherReplFutureSnapshot {
+        EitherReplFutureSnapshot::Limited(snapshot)
+    }
+}
+
+/// Puts the session back into the `MontyRepl` it was taken from by `feed_start()`.
+fn restore_repl(py: Python<'_>, owner: &Py<PyMontyRepl>, repl: EitherRepl) -> PyResult<()> {
+    owner.bind(py).try_borrow_mut()?.repl = repl;
+    Ok(())
+}
+
+/// Converts the progress of a snippet started with `feed_start()` into its Python object.
+///
+/// Completion and errors hand the session back to `owner`. `monty_yield()` values are skipped
+/// by resuming with `None`, since REPL snapshots don't stream partial results.
+fn repl_progress<'py, T: PyReplTracker + Send>(
+    py: Python<'py>,
+    mut result: Result<ReplProgress<T>, Box<ReplStartError<T>>>,
+    owner: Py<PyMontyRepl>,
+    print_writer: &mut SendWrapper<PrintWriter<'_>>,
+    print_callback: Option<Py<PyAny>>,
+    dc_registry: DcRegistry,
+) -> PyResult<Bound<'py, PyAny>> {
+    loop {
+        let progress = match result {
+            Ok(progress) => progress,
+            Err(err) => {
+                let ReplStartError { repl, error } = *err;
+                restore_repl(py, &owner, T::either_repl(repl))?;
+                return Err(MontyError::new_err(py, error));
+            }
+        };
+        return match progress {
+            ReplProgress::Complete { repl, value } => {
+                let memory_profile = repl.memory_profile();
+                restore_repl(py, &owner, T::either_repl(repl))?;
+                PyMontyComplete::create(py, &value, Some(memory_profile), &dc_registry)
+            }
+            ReplProgress::FunctionCall {
+                function_name,
+                args,
+                kwargs,
+                call_id,
+                state,
+                ..
+            } => PyMontyReplSnapshot::create(
+                py,
+                T::either_snapshot(state),
+                owner,
+                false,
+                function_name,
+                &args,
+                &kwargs,
+                call_id,
+                print_callback,
+                dc_registry,
+            ),
+            ReplProgress::OsCall {
+                function,
+                args,
+                kwargs,
+                call_id,
+                state,
+            } => PyMontyReplSnapshot::create(
+                py,
+                T::either_snapshot(state),
+                owner,
+                true,
+                function.to_string(),
+                &args,
+                &kwargs,
+                call_id,
+                print_callback,
+                dc_registry,
+            ),
+            ReplProgress::ResolveFutures(state) => PyMontyReplFutureSnapshot {
+                snapshot: T::either_future_snapshot(state),
+                repl: owner,
+                print_callback,
+                dc_registry,
+            }
+            .into_bound_py_any(py),
+            ReplProgress::Yield { state, .. } => {
+                result = py.detach(|| state.run(MontyObject::None, print_writer));
+                continue;
+            }
+        };
+    }
+}
+
+/// Builds the print writer for resuming a REPL snapshot.
+fn repl_print_writer<'a>(
+    py: Python<'_>,
+    print_callback: Option<&Py<PyAny>>,
+    print_cb: &'a mut Option<CallbackStringPrint>,
+) -> SendWrapper<PrintWriter<'a>> {
+    let print_writer = match print_callback {
+        Some(cb) => PrintWriter::Callback(print_cb.insert(CallbackStringPrint::from_py(cb.clone_ref(py)))),
+        None => PrintWriter::Stdout,
+    };
+    SendWrapper::new(print_writer)
+}
+
+/// REPL snippet suspended at an external call, pyclass counterpart of `ReplSnapshot`.
+#[derive(Debug)]
+enum EitherReplSnapshot {
+    NoLimit(ReplSnapshot<PyTracker<NoLimitTracker>>),
+    Limited(ReplSnapshot<PyTracker<LimitedTracker>>),
+    /// The snapshot has been resumed
+    Done,
+}
+
+/// A snippet started with `MontyRepl.feed_start()`, paused at an external function or OS call.
+///
+/// Unlike `MontySnapshot` it can't be serialized: the REPL session it holds is handed back
+/// to its `MontyRepl` once the snippet completes or raises.
+#[pyclass(name = "MontyReplSnapshot", module = "pydantic_monty")]
+#[derive(Debug)]
+pub struct PyMontyReplSnapshot {
+    snapshot: EitherReplSnapshot,
+    repl: Py<PyMontyRepl>,
+    print_callback: Option<Py<PyAny>>,
+    dc_registry: DcRegistry,
+
+    /// Whether this call refers to an OS function
+    #[pyo3(get)]
+    pub is_os_function: bool,
+    /// The name of the function being called.
+    #[pyo3(get)]
+    pub function_name: String,
+    /// The positional arguments passed to the function.
+    #[pyo3(get)]
+    pub args: Py<PyTuple>,
+    /// The keyword arguments passed to the function (key, value pairs).
+    #[pyo3(get)]
+    pub kwargs: Py<PyDict>,
+    /// The unique identifier for this call
+    #[pyo3(get)]
+    pub call_id: u32,
+}
+
+impl PyMontyReplSnapshot {
+    #[expect(clippy::too_many_arguments)]
+    fn create<'py>(
+        py: Python<'py>,
+        snapshot: EitherReplSnapshot,
+        repl: Py<PyMontyRepl>,
+        is_os_function: bool,
+        function_name: String,
+        args: &[MontyObject],
+        kwargs: &[(MontyObject, MontyObject)],
+        call_id: u32,
+        print_callback: Option<Py<PyAny>>,
+        dc_registry: DcRegistry,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let items: PyResult<Vec<Py<PyAny>>> = args.iter().map(|item| monty_to_py(py, item, &dc_registry)).collect();
+
+        let dict = PyDict::new(py);
+        for (k, v) in kwargs {
+            dict.set_item(monty_to_py(py, k, &dc_registry)?, monty_to_py(py, v, &dc_registry)?)?;
+        }
+
+        let slf = Self {
+            snapshot,
+            repl,
+            print_callback,
+            dc_registry,
+            is_os_function,
+            function_name,
+            args: PyTuple::new(py, items?)?.unbind(),
+            kwargs: dict.unbind(),
+            call_id,
+        };
+        slf.into_bound_py_any(py)
+    }
+}
+
+#[pymethods]
+impl PyMontyReplSnapshot {
+    /// Resumes the snippet with either a return value, an exception or a future.
+    ///
+    /// Takes the same keyword arguments as `MontySnapshot.resume()`.
+    #[pyo3(signature = (**kwargs))]
+    pub fn resume<'py>(&mut self, py: Python<'py>, kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<Bound<'py, PyAny>> {
+        const ARGS_ERROR: &str = "resume() accepts either return_value or exception, not both";
+        let Some(kwargs) = kwargs else {
+            return Err(PyTypeError::new_err(ARGS_ERROR));
+        };
+        let external_result = extract_external_result(py, kwargs, ARGS_ERROR, &self.dc_registry)?;
+
+        let snapshot = std::mem::replace(&mut self.snapshot, EitherReplSnapshot::Done);
+        let mut print_cb = None;
+        let mut print_writer = repl_print_writer(py, self.print_callback.as_ref(), &mut print_cb);
+        let owner = self.repl.clone_ref(py);
+        let print_callback = self.print_callback.take();
+        let dc_registry = self.dc_registry.clone_ref(py);
+
+        match snapshot {
+            EitherReplSnapshot::NoLimit(snapshot) => {
+                let result = py.detach(|| snapshot.run(external_result, &mut print_writer));
+                repl_progress(py, result, owner, &mut print_writer, print_callback, dc_registry)
+            }
+            EitherReplSnapshot::Limited(snapshot) => {
+                let result = py.detach(|| snapshot.run(external_result, &mut print_writer));
+                repl_progress(py, result, owner, &mut print_writer, print_callback, dc_registry)
+            }
+            EitherReplSnapshot::Done => Err(PyRuntimeError::new_err("Progress already resumed")),
+        }
+    }
+
+    fn __repr__(&self, py: Python<'_>) -> PyResult<String> {
+        Ok(format!(
+            "MontyReplSnapshot(function_name='{}', args={}, kwargs={})",
+            self.function_name,
+            self.args.bind(py).repr()?,
+            self.kwargs.bind(py).repr()?
+        ))
+    }
+}
+
+/// REPL snippet blocked on unresolved futures, pyclass counterpart of `ReplFutureSnapshot`.
+#[derive(Debug)]
+enum EitherReplFutureSnapshot {
+    NoLimit(ReplFutureSnapshot<PyTracker<NoLimitTracker>>),
+    Limited(ReplFutureSnapshot<PyTracker<LimitedTracker>>),
+    /// The snapshot has been resumed
+    Done,
+}
+
+/// A snippet started with `MontyRepl.feed_start()`, waiting for futures to resolve.
+#[pyclass(name = "MontyReplFutureSnapshot", module = "pydantic_monty")]
+#[derive(Debug)]
+pub struct PyMontyReplFutureSnapshot {
+    snapshot: EitherReplFutureSnapshot,
+    repl: Py<PyMontyRepl>,
+    print_callback: Option<Py<PyAny>>,
+    dc_registry: DcRegistry,
+}
+
+#[pymethods]
+impl PyMontyReplFutureSnapshot {
+    /// Resumes the snippet with results for one or more futures.
+    #[pyo3(signature = (results))]
+    pub fn resume<'py>(&mut self, py: Python<'py>, results: &Bound<'_, PyDict>) -> PyResult<Bound<'py, PyAny>> {
+        const ARGS_ERROR: &str = "results values must be a dict with either 'return_value' or 'exception', not both";
+        let external_results = results
+            .iter()
+            .map(|(key, value)| {
+                let call_id = key.extract::<u32>()?;
+                let dict = value.cast::<PyDict>()?;
+                let value = extract_external_result(py, dict, ARGS_ERROR, &self.dc_registry)?;
+                Ok((call_id, value))
+            })
+            .collect::<PyResult<Vec<_>>>()?;
+
+        let snapshot = std::mem::replace(&mut self.snapshot, EitherReplFutureSnapshot::Done);
+        let mut print_cb = None;
+        let mut print_writer = repl_print_writer(py, self.print_callback.as_ref(), &mut print_cb);
+        let owner = self.repl.clone_ref(py);
+        let print_callback = self.print_callback.take();
+        let dc_registry = self.dc_registry.clone_ref(py);
+
+        match snapshot {
+            EitherReplFutureSnapshot::NoLimit(snapshot) => {
+                let result = py.detach(|| snapshot.resume(external_results, &mut print_writer));
+                repl_progress(py, result, owner, &mut print_writer, print_callback, dc_registry)
+            }
+            EitherReplFutureSnapshot::Limited(snapshot) => {
+                let result = py.detach(|| snapshot.resume(external_results, &mut print_writer));
+                repl_progress(py, result, owner, &mut print_writer, print_callback, dc_registry)
+            }
+            EitherReplFutureSnapshot::Done => Err(PyRuntimeError::new_err("Progress already resumed")),
+        }
+    }
+
+    /// Returns the call IDs of the futures still to resolve.
+    #[getter]
+    fn pending_call_ids(&self) -> PyResult<&[u32]> {
+        match &self.snapshot {
+            EitherReplFutureSnapshot::NoLimit(snapshot) => Ok(snapshot.pending_call_ids()),
+            EitherReplFutureSnapshot::Limited(snapshot) => Ok(snapshot.pending_call_ids()),
+            EitherReplFutureSnapshot::Done => Err(PyRuntimeError::new_err("MontyReplFutureSnapshot already resumed")),
+        }
+    }
+
+    fn __repr__(&self) -> String {
+        let pending_call_ids = if let Ok(ids) = self.pending_call_ids() {
+            let ids = ids.iter().map(ToString::to_string).collect::<Vec<_>>().join(", ");
+            Cow::Owned(format!("[{ids}]"))
+        } else {
+            "None".into()
+        };
+        format!("MontyReplFutureSnapshot(pending_call_ids={pending_call_ids})")
+    }
+}
+
 /// Runtime execution snapshot, holds multiple resource tracker types since pyclass structs can't be generic.
 ///
 /// Used internally by `PyMontySnapshot` to store execution state.
 /// The `Done` variant indicates the snapshot has been consumed.
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 enum EitherSnapshot {
-    NoLimit(Snapshot<PySignalTracker<NoLimitTracker>>),
-    Limited(Snapshot<PySignalTracker<LimitedTracker>>),
+    NoLimit(Snapshot<PyTracker<NoLimitTracker>>),
+    Limited(Snapshot<PyTracker<LimitedTracker>>),
     /// Done is used when taking the snapshot to run it
     /// should only be done after execution is complete
     Done,
@@ -939,21 +1679,36 @@ pub struct PyMontySnapshot {
 /// Extract an external result (object or exception) from a dictionary.
 ///
 /// Any dataclass return values are automatically registered in the `dc_registry` via `py_to_monty`
-/// so they can be properly reconstructed on output.
+/// so they can be properly reconstructed on output. Exceptions can be given as `exception` or
+/// `error`, either as an instance or a class, and keep their type, message and notes.
 fn extract_external_result(
     py: Python<'_>,
     dict: &Bound<'_, PyDict>,
     error_msg: &'static str,
     dc_registry: &DcRegistry,
 ) -> PyResult<ExternalResult> {
+    let exception = match dict.get_item(intern!(py, "exception"))? {
+        Some(exc) => Some(exc),
+        None => dict.get_item(intern!(py, "error"))?,
+    };
     if dict.len() != 1 {
         Err(PyTypeError::new_err(error_msg))
     } else if let Some(rv) = dict.get_item(intern!(py, "return_value"))? {
         // Return value provided
         Ok(py_to_monty(&rv, dc_registry)?.into())
-    } else if let Some(exc) = dict.get_item(intern!(py, "exception"))? {
-        // Exception provided
-        let py_err = PyErr::from_value(exc.into_any());
+    } else if let Some(exc) = exception {
+        // Exception provided, `PyErr::from_value` also instantiates exception classes
+        let is_exception = exc.is_instance_of::<PyBaseException>()
+            || exc
+                .cast::<PyType>()
+                .is_ok_and(|cls| cls.is_subclass_of::<PyBaseException>().unwrap_or_default());
+        if !is_exception {
+            return Err(PyTypeError::new_err(format!(
+                "exception must be a BaseException instance or subclass, not '{}'",
+                exc.get_type().name()?
+            )));
+        }
+        let py_err = PyErr::from_value(exc);
         Ok(exc_py_to_monty(py, &py_err).into())
     } else if let Some(exc) = dict.get_item(intern!(py, "future"))? {
         if exc.eq(py.Ellipsis()).unwrap_or_default() {
@@ -1156,10 +1911,144 @@ impl PyMontySnapshot {
     }
 }
 
+/// Execution paused by `monty_yield()`, holding the streamed value until the host resumes.
+#[pyclass(name = "MontyYield", module = "pydantic_monty")]
+#[derive(Debug)]
+pub struct PyMontyYield {
+    snapshot: EitherSnapshot,
+    print_callback: Option<Py<PyAny>>,
+    dc_registry: DcRegistry,
+
+    /// Name of the script being executed
+    #[pyo3(get)]
+    pub script_name: String,
+
+    /// The value passed to `monty_yield()`.
+    #[pyo3(get)]
+    pub value: Py<PyAny>,
+}
+
+#[pymethods]
+impl PyMontyYield {
+    /// Resumes execution, `return_value` (default `None`) becomes the result of `monty_yield()`.
+    ///
+    /// Alternatively `exception` (or `error`) is raised from the `monty_yield()` call.
+    ///
+    /// # Raises
+    /// * `TypeError` if more than one argument is provided
+    /// * `RuntimeError` if the snapshot has already been resumed
+    #[pyo3(signature = (**kwargs))]
+    pub fn resume<'py>(&mut self, py: Python<'py>, kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<Bound<'py, PyAny>> {
+        const ARGS_ERROR: &str = "resume() accepts either return_value or exception, not both";
+        let external_result = match kwargs {
+            Some(kwargs) if !kwargs.is_empty() => extract_external_result(py, kwargs, ARGS_ERROR, &self.dc_registry)?,
+            _ => MontyObject::None.into(),
+        };
+        if matches!(external_result, ExternalResult::Future) {
+            return Err(PyTypeError::new_err(ARGS_ERROR));
+        }
+
+        let snapshot = std::mem::replace(&mut self.snapshot, EitherSnapshot::Done);
+
+        let mut print_cb;
+        let print_writer = match &self.print_callback {
+            Some(cb) => {
+                print_cb = CallbackStringPrint::from_py(cb.clone_ref(py));
+                PrintWriter::Callback(&mut print_cb)
+            }
+            None => PrintWriter::Stdout,
+        };
+        let mut print_writer = SendWrapper::new(print_writer);
+
+        let progress = match snapshot {
+            EitherSnapshot::NoLimit(snapshot) => {
+                let result = py.detach(|| snapshot.run(external_result, &mut print_writer));
+                EitherProgress::NoLimit(result.map_err(|e| MontyError::new_err(py, e))?)
+            }
+            EitherSnapshot::Limited(snapshot) => {
+                let result = py.detach(|| snapshot.run(external_result, &mut print_writer));
+                EitherProgress::Limited(result.map_err(|e| MontyError::new_err(py, e))?)
+            }
+            EitherSnapshot::Done => return Err(PyRuntimeError::new_err("Progress already resumed")),
+        };
+
+        let dc_registry = self.dc_registry.clone_ref(py);
+        progress.progress_or_complete(py, self.script_name.clone(), self.print_callback.take(), dc_registry)
+    }
+
+    /// Serializes the MontyYield instance to a binary format, see `MontySnapshot.dump()`.
+    ///
+    /// # Raises
+    /// `ValueError` if serialization fails.
+    /// `RuntimeError` if the progress has already been resumed.
+    fn dump<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
+        #[derive(serde::Serialize)]
+        struct SerializedYield<'a> {
+            snapshot: &'a EitherSnapshot,
+            script_name: &'a str,
+            value: MontyObject,
+        }
+
+        if matches!(self.snapshot, EitherSnapshot::Done) {
+            return Err(PyRuntimeError::new_err(
+                "Cannot dump progress that has already been resumed",
+            ));
+        }
+
+        let serialized = SerializedYield {
+            snapshot: &self.snapshot,
+            script_name: &self.script_name,
+            value: py_to_monty(self.value.bind(py), &self.dc_registry)?,
+        };
+        let bytes = postcard::to_allocvec(&serialized).map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok(PyBytes::new(py, &bytes))
+    }
+
+    /// Deserializes a MontyYield instance from binary format, see `MontySnapshot.load()`.
+    ///
+    /// # Raises
+    /// `ValueError` if deserialization fails.
+    #[staticmethod]
+    #[pyo3(signature = (data, *, print_callback=None, dataclass_registry=None))]
+    fn load(
+        py: Python<'_>,
+        data: &Bound<'_, PyBytes>,
+        print_callback: Option<Py<PyAny>>,
+        dataclass_registry: Option<&Bound<'_, PyList>>,
+    ) -> PyResult<Self> {
+        #[derive(serde::Deserialize)]
+        struct SerializedYieldOwned {
+            snapshot: EitherSnapshot,
+            script_name: String,
+            value: MontyObject,
+        }
+
+        let serialized: SerializedYieldOwned =
+            postcard::from_bytes(data.as_bytes()).map_err(|e| PyValueError::new_err(e.to_string()))?;
+        let dc_registry = DcRegistry::from_list(py, dataclass_registry)?;
+
+        Ok(Self {
+            value: monty_to_py(py, &serialized.value, &dc_registry)?,
+            snapshot: serialized.snapshot,
+            print_callback,
+            dc_registry,
+            script_name: serialized.script_name,
+        })
+    }
+
+    fn __repr__(&self, py: Python<'_>) -> PyResult<String> {
+        Ok(format!(
+            "MontyYield(script_name='{}', value={})",
+            self.script_name,
+            self.value.bind(py).repr()?
+        ))
+    }
+}
+
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 enum EitherFutureSnapshot {
-    NoLimit(FutureSnapshot<PySignalTracker<NoLimitTracker>>),
-    Limited(FutureSnapshot<PySignalTracker<LimitedTracker>>),
+    NoLimit(FutureSnapshot<PyTracker<NoLimitTracker>>),
+    Limited(FutureSnapshot<PyTracker<LimitedTracker>>),
     /// Done is used when taking the snapshot to run it
     /// should only be done after execution is complete
     Done,
@@ -1326,23 +2215,39 @@ impl PyMontyFutureSnapshot {
     }
 }
 
-#[pyclass(name = "MontyComplete", module = "pydantic_monty")]
+#[pyclass(name = "MontyComplete", module = "pydantic_monty", frozen)]
 pub struct PyMontyComplete {
     #[pyo3(get)]
     pub output: Py<PyAny>,
-    // TODO we might want to add stats on execution here like time, allocations, etc.
+    profile: Option<MemoryProfile>,
 }
 
 impl PyMontyComplete {
-    fn create<'py>(py: Python<'py>, output: &MontyObject, dc_registry: &DcRegistry) -> PyResult<Bound<'py, PyAny>> {
+    fn create<'py>(
+        py: Python<'py>,
+        output: &MontyObject,
+        profile: Option<MemoryProfile>,
+        dc_registry: &DcRegistry,
+    ) -> PyResult<Bound<'py, PyAny>> {
         let output = monty_to_py(py, output, dc_registry)?;
-        let slf = Self { output };
+        let slf = Self { output, profile };
         slf.into_bound_py_any(py)
     }
 }
 
 #[pymethods]
 impl PyMontyComplete {
+    /// Live heap objects when execution finished, in the same format as `MontyRepl.memory_profile()`.
+    ///
+    /// `None` if no profile was recorded for the run.
+    #[getter]
+    fn memory_profile<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyDict>>> {
+        self.profile
+            .as_ref()
+            .map(|profile| memory_profile_to_py(py, profile))
+            .transpose()
+    }
+
     fn __repr__(&self, py: Python<'_>) -> PyResult<String> {
         Ok(format!("MontyComplete(output={})", self.output.bind(py).repr()?))
     }
@@ -1396,6 +2301,14 @@ impl PrintWriterCallback for CallbackStringPrint {
         })
         .map_err(|e| Python::attach(|py| exc_py_to_monty(py, &e)))
     }
+
+    fn stderr_push(&mut self, end: char) -> Result<(), MontyException> {
+        Python::attach(|py| {
+            self.0.bind(py).call1(("stderr", end.to_string()))?;
+            Ok::<_, PyErr>(())
+        })
+        .map_err(|e| Python::attach(|py| exc_py_to_monty(py, &e)))
+    }
 }
 
 /// Recursively checks whether a `MontyObject` contains a dataclass, including
@@ -0,0 +1,77 @@
+//! Conversion of numpy scalars and arrays.
+//!
+//! numpy isn't a dependency: values are only recognised once the embedder has imported numpy
+//! themselves, found via `sys.modules`.
+//!
+//! - `numpy.bool_`, `numpy.integer` and `numpy.floating` scalars become `bool`, `int` and `float`.
+//!   (`numpy.float64` is a `float` subclass so it's already handled by the generic conversion.)
+//! - `numpy.ndarray`s of those dtypes become nested lists, but only when the `max_ndarray_size`
+//!   limit is set and the array has at most that many elements.
+//!
+//! Any other numpy value raises a `TypeError` explaining what's supported.
+
+use ::monty::MontyObject;
+use pyo3::{
+    exceptions::{PyTypeError, PyValueError},
+    intern,
+    prelude::*,
+    types::PyDict,
+};
+
+use crate::{convert::py_to_monty, dataclass::DcRegistry};
+
+/// Converts `obj` if it's a numpy scalar or array.
+///
+/// Returns `Ok(None)` if numpy hasn't been imported or `obj` isn't a numpy value.
+pub fn numpy_to_monty(obj: &Bound<'_, PyAny>, dc_registry: &DcRegistry) -> PyResult<Option<MontyObject>> {
+    let py = obj.py();
+    let modules = py
+        .import(intern!(py, "sys"))?
+        .getattr(intern!(py, "modules"))?
+        .cast_into::<PyDict>()?;
+    let Some(numpy) = modules.get_item(intern!(py, "numpy"))? else {
+        return Ok(None);
+    };
+
+    if obj.is_instance(&numpy.getattr(intern!(py, "generic"))?)? {
+        let supported = ["bool_", "integer", "floating"]
+            .into_iter()
+            .map(|name| obj.is_instance(&numpy.getattr(name)?))
+            .collect::<PyResult<Vec<bool>>>()?;
+        if !supported.contains(&true) {
+            let name = obj.get_type().name()?;
+            return Err(PyTypeError::new_err(format!(
+                "Cannot convert numpy.{name} to Monty value, only bool, integer and floating numpy scalars are supported"
+            )));
+        }
+        let item = obj.call_method0(intern!(py, "item"))?;
+        return py_to_monty(&item, dc_registry).map(Some);
+    }
+
+    if obj.is_instance(&numpy.getattr(intern!(py, "ndarray"))?)? {
+        let Some(max_size) = dc_registry.max_ndarray_size() else {
+            return Err(PyTypeError::new_err(
+                "Cannot convert numpy.ndarray to Monty value, set the `max_ndarray_size` limit to convert arrays to lists",
+            ));
+        };
+        let size: usize = obj.getattr(intern!(py, "size"))?.extract()?;
+        if size > max_size {
+            return Err(PyValueError::new_err(format!(
+                "Cannot convert numpy.ndarray with {size} elements to Monty value, exceeds max_ndarray_size of {max_size}"
+            )));
+        }
+        let dtype = obj.getattr(intern!(py, "dtype"))?;
+        let kind: String = dtype.getattr(intern!(py, "kind"))?.extract()?;
+        // bool, signed int, unsigned int and float
+        if !matches!(kind.as_str(), "b" | "i" | "u" | "f") {
+            return Err(PyTypeError::new_err(format!(
+                "Cannot convert numpy.ndarray with dtype {} to Monty value, only bool, integer and floating arrays are supported",
+                dtype.str()?
+            )));
+        }
+        let list = obj.call_method0(intern!(py, "tolist"))?;
+        return py_to_monty(&list, dc_registry).map(Some);
+    }
+
+    Ok(None)
+}
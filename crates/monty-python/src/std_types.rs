@@ -0,0 +1,125 @@
+//! Conversion of common standard library value types that Monty has no native equivalent for.
+//!
+//! `datetime.datetime`, `datetime.date`, `datetime.timedelta`, `datetime.timezone`,
+//! `decimal.Decimal` and `uuid.UUID` inputs are passed into Monty as frozen dataclass-like
+//! objects whose fields are keyword arguments of the type's constructor, e.g.
+//! `Decimal(value='1.5')` or `timedelta(days=1, seconds=0, microseconds=0)`.
+//!
+//! The standard library type is registered in the [`DcRegistry`], so on output the generic
+//! dataclass reconstruction calls it with those fields and returns a real instance again.
+//! Subclasses (e.g. third party datetime types) come back as their standard library base type.
+
+use ::monty::MontyObject;
+use pyo3::{exceptions::PyTypeError, intern, prelude::*, sync::PyOnceLock, types::PyType};
+
+use crate::{convert::py_to_monty, dataclass::DcRegistry};
+
+/// Converts `obj` if it's one of the supported standard library types.
+///
+/// Returns `Ok(None)` for any other object so the caller can try other conversions.
+pub fn std_type_to_monty(obj: &Bound<'_, PyAny>, dc_registry: &DcRegistry) -> PyResult<Option<MontyObject>> {
+    let py = obj.py();
+    // `datetime` must be checked before `date` since it's a subclass
+    let datetime = import_type(py, &DATETIME, "datetime", "datetime")?;
+    if obj.is_instance(datetime)? {
+        let mut fields = int_fields(
+            obj,
+            &["year", "month", "day", "hour", "minute", "second", "microsecond"],
+        )?;
+        fields.push(("tzinfo", tzinfo_to_monty(obj, dc_registry)?));
+        fields.extend(int_fields(obj, &["fold"])?);
+        return tagged(datetime, fields, dc_registry).map(Some);
+    }
+    let date = import_type(py, &DATE, "datetime", "date")?;
+    if obj.is_instance(date)? {
+        let fields = int_fields(obj, &["year", "month", "day"])?;
+        return tagged(date, fields, dc_registry).map(Some);
+    }
+    let timedelta = import_type(py, &TIMEDELTA, "datetime", "timedelta")?;
+    if obj.is_instance(timedelta)? {
+        let fields = int_fields(obj, &["days", "seconds", "microseconds"])?;
+        return tagged(timedelta, fields, dc_registry).map(Some);
+    }
+    let timezone = import_type(py, &TIMEZONE, "datetime", "timezone")?;
+    if obj.is_instance(timezone)? {
+        // the name isn't preserved, only the fixed offset
+        let offset = obj.call_method1(intern!(py, "utcoffset"), (py.None(),))?;
+        let fields = vec![("offset", py_to_monty(&offset, dc_registry)?)];
+        return tagged(timezone, fields, dc_registry).map(Some);
+    }
+    let decimal = import_type(py, &DECIMAL, "decimal", "Decimal")?;
+    if obj.is_instance(decimal)? {
+        let fields = vec![("value", MontyObject::String(obj.str()?.extract()?))];
+        return tagged(decimal, fields, dc_registry).map(Some);
+    }
+    let uuid = import_type(py, &UUID, "uuid", "UUID")?;
+    if obj.is_instance(uuid)? {
+        let fields = vec![("hex", MontyObject::String(obj.getattr(intern!(py, "hex"))?.extract()?))];
+        return tagged(uuid, fields, dc_registry).map(Some);
+    }
+    Ok(None)
+}
+
+/// Converts the `tzinfo` of a datetime, which must be `None` or a fixed-offset `datetime.timezone`.
+fn tzinfo_to_monty(obj: &Bound<'_, PyAny>, dc_registry: &DcRegistry) -> PyResult<MontyObject> {
+    let tzinfo = obj.getattr(intern!(obj.py(), "tzinfo"))?;
+    if tzinfo.is_none() {
+        return Ok(MontyObject::None);
+    }
+    let timezone = import_type(obj.py(), &TIMEZONE, "datetime", "timezone")?;
+    if tzinfo.is_instance(timezone)? {
+        py_to_monty(&tzinfo, dc_registry)
+    } else {
+        let name = tzinfo.get_type().name()?;
+        Err(PyTypeError::new_err(format!(
+            "Cannot convert datetime with tzinfo {name} to Monty value, only datetime.timezone is supported"
+        )))
+    }
+}
+
+/// Reads integer attributes of `obj` as fields.
+fn int_fields(obj: &Bound<'_, PyAny>, names: &[&'static str]) -> PyResult<Vec<(&'static str, MontyObject)>> {
+    names
+        .iter()
+        .map(|&name| Ok((name, MontyObject::Int(obj.getattr(name)?.extract()?))))
+        .collect()
+}
+
+/// Builds the frozen dataclass-like object for a standard library value and registers its type.
+fn tagged(
+    py_type: &Bound<'_, PyType>,
+    fields: Vec<(&'static str, MontyObject)>,
+    dc_registry: &DcRegistry,
+) -> PyResult<MontyObject> {
+    dc_registry.insert(py_type)?;
+    let name: String = py_type.getattr(intern!(py_type.py(), "__name__"))?.extract()?;
+    let field_names = fields.iter().map(|(name, _)| (*name).to_owned()).collect();
+    let attrs: Vec<(MontyObject, MontyObject)> = fields
+        .into_iter()
+        .map(|(name, value)| (MontyObject::String(name.to_owned()), value))
+        .collect();
+    Ok(MontyObject::Dataclass {
+        name,
+        type_id: py_type.as_ptr() as u64,
+        field_names,
+        attrs: attrs.into(),
+        frozen: true,
+    })
+}
+
+static DATETIME: PyOnceLock<Py<PyType>> = PyOnceLock::new();
+static DATE: PyOnceLock<Py<PyType>> = PyOnceLock::new();
+static TIMEDELTA: PyOnceLock<Py<PyType>> = PyOnceLock::new();
+static TIMEZONE: PyOnceLock<Py<PyType>> = PyOnceLock::new();
+static DECIMAL: PyOnceLock<Py<PyType>> = PyOnceLock::new();
+static UUID: PyOnceLock<Py<PyType>> = PyOnceLock::new();
+
+/// Cached import of a standard library type.
+fn import_type<'py>(
+    py: Python<'py>,
+    cell: &'static PyOnceLock<Py<PyType>>,
+    module: &str,
+    name: &str,
+) -> PyResult<&'py Bound<'py, PyType>> {
+    cell.import(py, module, name)
+}
@@ -0,0 +1,99 @@
+from typing import Any
+
+import pytest
+from inline_snapshot import snapshot
+
+import pydantic_monty
+
+
+def strip_volatile(events: list[dict[str, Any]]) -> list[dict[str, Any]]:
+    """Checks and removes the timestamp and args digest, which vary between runs."""
+    for event in events:
+        assert isinstance(event.pop('timestamp'), float)
+        if 'args_digest' in event:
+            assert len(event.pop('args_digest')) == 16
+    return events
+
+
+def test_external_and_os_calls():
+    code = """
+from pathlib import Path
+Path('/secret.txt').read_text() + fetch('a')
+"""
+    events: list[dict[str, Any]] = []
+    m = pydantic_monty.Monty(code, external_functions=['fetch'])
+    result = m.run(
+        external_functions={'fetch': lambda x: x * 2},
+        os=lambda function, args, kwargs: 'x',
+        audit_callback=events.append,
+    )
+    assert result == snapshot('xaa')
+    assert strip_volatile(events) == snapshot(
+        [
+            {'event': 'import', 'module': 'pathlib', 'allowed': True},
+            {'event': 'os_call', 'function': 'Path.read_text'},
+            {'event': 'external_call', 'function': 'fetch', 'method_call': False},
+        ]
+    )
+
+
+def test_args_digest_is_stable():
+    events: list[dict[str, Any]] = []
+    m = pydantic_monty.Monty('fetch(1, [2]); fetch(1, [2]); fetch(3)', external_functions=['fetch'])
+    m.run(external_functions={'fetch': lambda *args: None}, audit_callback=events.append)
+    digests = [e['args_digest'] for e in events]
+    assert digests[0] == digests[1]
+    assert digests[0] != digests[2]
+
+
+def test_denied_import():
+    events: list[dict[str, Any]] = []
+    m = pydantic_monty.Monty('import socket')
+    with pytest.raises(pydantic_monty.MontyRuntimeError):
+        m.run(audit_callback=events.append)
+    assert strip_volatile(events) == snapshot([{'event': 'import', 'module': 'socket', 'allowed': False}])
+
+
+def test_resource_limit():
+    events: list[dict[str, Any]] = []
+    m = pydantic_monty.Monty('x = []\nwhile True:\n    x.append(1)')
+    with pytest.raises(pydantic_monty.MontyRuntimeError):
+        m.run(limits=pydantic_monty.ResourceLimits(max_allocations=10), audit_callback=events.append)
+    assert len(events) == 1
+    assert events[0]['event'] == 'resource_limit'
+    assert events[0]['exc_type'] == 'MemoryError'
+
+
+def test_os_call_without_handler():
+    events: list[dict[str, Any]] = []
+    m = pydantic_monty.Monty("from pathlib import Path\nPath('/x').exists()", external_functions=['fetch'])
+    with pytest.raises(pydantic_monty.MontyRuntimeError):
+        m.run(external_functions={'fetch': lambda: None}, audit_callback=events.append)
+    assert strip_volatile(events)[-1] == snapshot(
+        {'event': 'capability_denied', 'capability': 'os_function', 'name': 'Path.exists'}
+    )
+
+
+def test_start_and_resume():
+    events: list[dict[str, Any]] = []
+    m = pydantic_monty.Monty('fetch(1)', external_functions=['fetch'])
+    progress = m.start(audit_callback=events.append)
+    assert isinstance(progress, pydantic_monty.MontySnapshot)
+    assert [e['event'] for e in events] == snapshot(['external_call'])
+    assert progress.resume(return_value=2).output == snapshot(2)
+
+
+def test_repl():
+    events: list[dict[str, Any]] = []
+    repl, _ = pydantic_monty.MontyRepl.create('', audit_callback=events.append)
+    repl.feed('import sys')
+    assert strip_volatile(events) == snapshot([{'event': 'import', 'module': 'sys', 'allowed': True}])
+
+
+def test_callback_errors_are_unraisable():
+    def callback(event: dict[str, Any]) -> None:
+        raise ValueError('broken hook')
+
+    m = pydantic_monty.Monty('import sys\n1 + 1')
+    with pytest.warns(pytest.PytestUnraisableExceptionWarning):
+        assert m.run(audit_callback=callback) == snapshot(2)
@@ -66,3 +66,20 @@ add(3, 4)
 """
     m = pydantic_monty.Monty(code)
     assert m.run() == snapshot(7)
+
+
+def test_module_dunders():
+    m = pydantic_monty.Monty('"""Docs."""\n(__name__, __file__, __doc__)', script_name='script.py')
+    assert m.run() == snapshot(('__main__', 'script.py', 'Docs.'))
+
+
+def test_module_name():
+    code = """
+if __name__ == '__main__':
+    x = 'main'
+else:
+    x = __name__
+x
+"""
+    m = pydantic_monty.Monty(code, module_name='my_module')
+    assert m.run() == snapshot('my_module')
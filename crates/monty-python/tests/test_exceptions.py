@@ -207,6 +207,49 @@ ValueError: test message\
 """)
 
 
+def test_display_traceback_options():
+    code = """\
+def ratio(total, count):
+    return total // count
+
+
+ratio(3, 0)
+"""
+    m = pydantic_monty.Monty(code)
+    with pytest.raises(pydantic_monty.MontyRuntimeError) as exc_info:
+        m.run()
+    exc = exc_info.value
+    assert exc.display(style='short') == snapshot("""\
+Traceback (most recent call last):
+  File "main.py", line 5, in <module>
+    ratio(3, 0)
+  File "main.py", line 2, in ratio
+    return total // count
+ZeroDivisionError: integer division or modulo by zero\
+""")
+    assert exc.display(style='line') == snapshot('main.py:2: ZeroDivisionError: integer division or modulo by zero')
+    assert exc.display(max_frames=1) == snapshot("""\
+Traceback (most recent call last):
+  File "main.py", line 2, in ratio
+    return total // count
+           ~~~~~~^^~~~~~~
+ZeroDivisionError: integer division or modulo by zero\
+""")
+    assert exc.display(max_frames=0) == snapshot('ZeroDivisionError: integer division or modulo by zero')
+    assert exc.display(style='line', internal_files=['main.py']) == snapshot(
+        'main.py:2: ZeroDivisionError: integer division or modulo by zero'
+    )
+
+
+def test_display_invalid_traceback_style():
+    m = pydantic_monty.Monty('1 / 0')
+    with pytest.raises(pydantic_monty.MontyRuntimeError) as exc_info:
+        m.run()
+    with pytest.raises(ValueError) as style_exc:
+        exc_info.value.display(style='native')  # pyright: ignore[reportArgumentType]
+    assert str(style_exc.value) == snapshot("Invalid traceback style: 'native'. Expected 'long', 'short', or 'line'")
+
+
 def test_str_returns_msg():
     m = pydantic_monty.Monty("raise ValueError('test message')")
     with pytest.raises(pydantic_monty.MontyRuntimeError) as exc_info:
@@ -252,10 +295,10 @@ outer()
 Traceback (most recent call last):
   File "main.py", line 7, in <module>
     outer()
-    ~~~~~~~
+    ~~~~~^^
   File "main.py", line 5, in outer
     inner()
-    ~~~~~~~
+    ~~~~~^^
   File "main.py", line 2, in inner
     raise ValueError('error')
 ValueError: error\
@@ -271,6 +314,7 @@ ValueError: error\
                 'end_column': 8,
                 'function_name': '<module>',
                 'source_line': 'outer()',
+                'depth': 0,
             },
             {
                 'filename': 'main.py',
@@ -280,6 +324,7 @@ ValueError: error\
                 'end_column': 12,
                 'function_name': 'outer',
                 'source_line': '    inner()',
+                'depth': 1,
             },
             {
                 'filename': 'main.py',
@@ -289,6 +334,7 @@ ValueError: error\
                 'end_column': 30,
                 'function_name': 'inner',
                 'source_line': "    raise ValueError('error')",
+                'depth': 2,
             },
         ]
     )
@@ -316,6 +362,7 @@ foo()
                 'end_column': 6,
                 'function_name': '<module>',
                 'source_line': 'foo()',
+                'depth': 0,
             },
             {
                 'filename': 'main.py',
@@ -325,11 +372,44 @@ foo()
                 'end_column': 29,
                 'function_name': 'foo',
                 'source_line': "    raise ValueError('test')",
+                'depth': 1,
             },
         ]
     )
 
 
+def test_structured_fields():
+    code = """
+def foo():
+    raise ValueError('test')
+
+foo()
+"""
+    m = pydantic_monty.Monty(code)
+    with pytest.raises(pydantic_monty.MontyRuntimeError) as exc_info:
+        m.run()
+    exc = exc_info.value
+    assert exc.type_name == snapshot('ValueError')
+    assert exc.message == snapshot('test')
+    assert [f.dict() for f in exc.frames] == [f.dict() for f in exc.traceback()]
+
+
+def test_structured_fields_no_message():
+    m = pydantic_monty.Monty('raise KeyboardInterrupt')
+    with pytest.raises(pydantic_monty.MontyRuntimeError) as exc_info:
+        m.run()
+    assert exc_info.value.type_name == snapshot('KeyboardInterrupt')
+    assert exc_info.value.message is None
+
+
+def test_syntax_error_structured_fields():
+    with pytest.raises(pydantic_monty.MontySyntaxError) as exc_info:
+        pydantic_monty.Monty('def')
+    assert exc_info.value.type_name == snapshot('SyntaxError')
+    assert exc_info.value.message == snapshot('Expected an identifier at byte range 3..3')
+    assert exc_info.value.frames == []
+
+
 # === Repr tests ===
 
 
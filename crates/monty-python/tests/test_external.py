@@ -1,3 +1,5 @@
+import threading
+from concurrent.futures import ThreadPoolExecutor
 from typing import Any
 
 import pytest
@@ -157,6 +159,92 @@ def test_external_function_raises_exception():
     assert inner.args[0] == snapshot('intentional error')
 
 
+def test_external_function_exception_notes():
+    """Test that PEP 678 notes on host exceptions are visible inside Monty and preserved on the way out."""
+    code = """
+try:
+    fail()
+except ValueError as e:
+    e.add_note('added in monty')
+    raise
+"""
+    m = pydantic_monty.Monty(code, external_functions=['fail'])
+
+    def fail(*args: Any, **kwargs: Any) -> None:
+        exc = ValueError('with notes')
+        exc.add_note('added by host')
+        raise exc
+
+    with pytest.raises(pydantic_monty.MontyRuntimeError) as exc_info:
+        m.run(external_functions={'fail': fail})
+    assert exc_info.value.notes == snapshot(['added by host', 'added in monty'])
+    inner = exc_info.value.exception()
+    assert inner.__notes__ == snapshot(['added by host', 'added in monty'])
+    assert exc_info.value.display('traceback').endswith('ValueError: with notes\nadded by host\nadded in monty')
+
+
+class CustomHostError(ValueError):
+    pass
+
+
+def test_external_function_host_exception_preserved():
+    """Test that the original host exception object is available when it escapes Monty."""
+    m = pydantic_monty.Monty('fail()', external_functions=['fail'])
+    original = CustomHostError('from host')
+
+    def fail(*args: Any, **kwargs: Any) -> None:
+        raise original
+
+    with pytest.raises(pydantic_monty.MontyRuntimeError) as exc_info:
+        m.run(external_functions={'fail': fail})
+    assert exc_info.value.host_exception is original
+    assert exc_info.value.__cause__ is original
+    assert exc_info.value.type_name == snapshot('ValueError')
+
+    with pytest.raises(CustomHostError) as reraised:
+        raise exc_info.value.host_exception
+    assert reraised.value is original
+
+
+def test_external_function_host_exception_reraised_in_monty():
+    """Test that the host exception is kept when Monty code catches and re-raises it."""
+    code = """
+try:
+    fail()
+except ValueError as e:
+    err = e
+raise err
+"""
+    m = pydantic_monty.Monty(code, external_functions=['fail'])
+    original = CustomHostError('from host')
+
+    def fail(*args: Any, **kwargs: Any) -> None:
+        raise original
+
+    with pytest.raises(pydantic_monty.MontyRuntimeError) as exc_info:
+        m.run(external_functions={'fail': fail})
+    assert exc_info.value.host_exception is original
+
+
+def test_external_function_host_exception_replaced():
+    """Test that a new exception raised in Monty has no host exception."""
+    code = """
+try:
+    fail()
+except ValueError:
+    raise TypeError('replaced')
+"""
+    m = pydantic_monty.Monty(code, external_functions=['fail'])
+
+    def fail(*args: Any, **kwargs: Any) -> None:
+        raise CustomHostError('from host')
+
+    with pytest.raises(pydantic_monty.MontyRuntimeError) as exc_info:
+        m.run(external_functions={'fail': fail})
+    assert exc_info.value.host_exception is None
+    assert exc_info.value.__cause__ is None
+
+
 def test_external_function_wrong_name_raises():
     """Test that calling a missing external function raises KeyError."""
     m = pydantic_monty.Monty('foo()', external_functions=['foo'])
@@ -362,3 +450,258 @@ finally_ran
 
     result = m.run(external_functions={'fail': fail})
     assert result == snapshot(True)
+
+
+def test_external_function_executor():
+    """External functions are dispatched to the executor's worker threads."""
+    m = pydantic_monty.Monty('add(1, b=2)', external_functions=['add'])
+    threads: list[str] = []
+
+    def add(a: int, b: int) -> int:
+        threads.append(threading.current_thread().name)
+        return a + b
+
+    with ThreadPoolExecutor(thread_name_prefix='monty-worker') as executor:
+        assert m.run(external_functions={'add': add}, executor=executor) == snapshot(3)
+    assert len(threads) == 1
+    assert threads[0].startswith('monty-worker')
+
+
+def test_external_function_executor_exception():
+    """Exceptions raised on the executor propagate into Monty."""
+    code = """
+try:
+    fail()
+except ValueError as e:
+    result = f'caught: {e}'
+result
+"""
+    m = pydantic_monty.Monty(code, external_functions=['fail'])
+
+    def fail() -> None:
+        raise ValueError('from worker')
+
+    with ThreadPoolExecutor() as executor:
+        assert m.run(external_functions={'fail': fail}, executor=executor) == snapshot('caught: from worker')
+
+
+def test_external_function_executor_shared_between_runs():
+    """One executor can serve many concurrent runs."""
+    m = pydantic_monty.Monty('[double(x) for x in range(n)]', inputs=['n'], external_functions=['double'])
+
+    def double(x: int) -> int:
+        return x * 2
+
+    def run(n: int) -> list[int]:
+        return m.run(inputs={'n': n}, external_functions={'double': double}, executor=executor)
+
+    with ThreadPoolExecutor(max_workers=2) as executor, ThreadPoolExecutor(max_workers=4) as runners:
+        results = list(runners.map(run, range(8)))
+    assert results == [[x * 2 for x in range(n)] for n in range(8)]
+
+
+def test_external_function_executor_invalid():
+    m = pydantic_monty.Monty('f()', external_functions=['f'])
+    with pytest.raises(TypeError) as exc_info:
+        m.run(external_functions={'f': lambda: 1}, executor=42)  # pyright: ignore[reportArgumentType]
+    assert str(exc_info.value) == snapshot(
+        "TypeError: 'int' object is not an executor, expected a concurrent.futures.Executor"
+    )
+
+
+def test_call_policy_timeout():
+    """A call outliving its timeout raises `ExternalCallTimeout` inside the sandbox."""
+    code = """
+try:
+    slow()
+except ExternalCallTimeout as e:
+    result = f'timed out: {e}'
+result
+"""
+    m = pydantic_monty.Monty(code, external_functions=['slow'])
+    release = threading.Event()
+
+    def slow() -> str:
+        release.wait(5)
+        return 'done'
+
+    try:
+        result = m.run(external_functions={'slow': slow}, call_policies={'slow': {'timeout': 0.05}})
+    finally:
+        release.set()
+    assert result == snapshot("timed out: external function 'slow' timed out after 0.05s")
+
+
+def test_call_policy_timeout_escapes():
+    """An uncaught `ExternalCallTimeout` is a `TimeoutError` on the host too."""
+    m = pydantic_monty.Monty('slow()', external_functions=['slow'])
+    release = threading.Event()
+
+    try:
+        with pytest.raises(pydantic_monty.MontyRuntimeError) as exc_info:
+            m.run(
+                external_functions={'slow': lambda: release.wait(5)},
+                call_policies={'slow': {'timeout': 0.01, 'retries': 1}},
+            )
+    finally:
+        release.set()
+    inner = exc_info.value.exception()
+    assert isinstance(inner, pydantic_monty.ExternalCallTimeout)
+    assert isinstance(inner, TimeoutError)
+    assert str(inner) == snapshot("external function 'slow' timed out after 0.01s on all 2 attempts")
+
+
+def test_call_policy_retry_after_timeout():
+    """Timed out attempts are retried, the first attempt to finish in time wins."""
+    m = pydantic_monty.Monty('flaky()', external_functions=['flaky'])
+    release = threading.Event()
+    attempts: list[int] = []
+
+    def flaky() -> int:
+        attempts.append(len(attempts) + 1)
+        if len(attempts) < 3:
+            release.wait(5)
+        return len(attempts)
+
+    try:
+        result = m.run(
+            external_functions={'flaky': flaky},
+            call_policies={'flaky': {'timeout': 0.05, 'retries': 3, 'backoff': 0.01}},
+        )
+    finally:
+        release.set()
+    assert result == snapshot(3)
+    assert attempts == snapshot([1, 2, 3])
+
+
+def test_call_policy_retry_on():
+    """Exceptions matching `retry_on` are retried, others are raised straight away."""
+    code = """
+try:
+    flaky()
+except KeyError as e:
+    result = f'caught: {e}'
+result
+"""
+    m = pydantic_monty.Monty(code, external_functions=['flaky'])
+    calls: list[str] = []
+
+    def flaky() -> str:
+        calls.append('call')
+        if len(calls) < 3:
+            raise ConnectionError('try again')
+        raise KeyError('missing')
+
+    policy: pydantic_monty.CallPolicy = {'retries': 5, 'retry_on': ConnectionError}
+    assert m.run(external_functions={'flaky': flaky}, call_policies={'flaky': policy}) == snapshot("caught: 'missing'")
+    assert len(calls) == snapshot(3)
+
+
+def test_call_policy_retries_exhausted():
+    """The last attempt's exception is raised once no retries are left."""
+    m = pydantic_monty.Monty('flaky()', external_functions=['flaky'])
+    calls: list[str] = []
+
+    def flaky() -> str:
+        calls.append('call')
+        raise ConnectionError(f'attempt {len(calls)}')
+
+    with pytest.raises(pydantic_monty.MontyRuntimeError) as exc_info:
+        m.run(
+            external_functions={'flaky': flaky},
+            call_policies={'flaky': {'retries': 2, 'retry_on': (ConnectionError, TimeoutError)}},
+        )
+    assert exc_info.value.display('type-msg') == snapshot('OSError: attempt 3')
+    assert len(calls) == snapshot(3)
+
+
+def test_call_policy_with_executor():
+    """Timeouts wait on the executor's future when an executor is given."""
+    m = pydantic_monty.Monty('[fast(), slow()]', external_functions=['fast', 'slow'])
+    release = threading.Event()
+
+    with ThreadPoolExecutor(max_workers=2) as executor:
+        try:
+            with pytest.raises(pydantic_monty.MontyRuntimeError) as exc_info:
+                m.run(
+                    external_functions={'fast': lambda: 1, 'slow': lambda: release.wait(5)},
+                    executor=executor,
+                    call_policies={'fast': {'timeout': 5}, 'slow': {'timeout': 0.01}},
+                )
+        finally:
+            release.set()
+    assert exc_info.value.display('type-msg') == snapshot(
+        "ExternalCallTimeout: external function 'slow' timed out after 0.01s"
+    )
+
+
+def test_call_policy_invalid():
+    m = pydantic_monty.Monty('f()', external_functions=['f'])
+    with pytest.raises(ValueError) as exc_info:
+        m.run(external_functions={'f': lambda: 1}, call_policies={'f': {'timeout': -1}})
+    assert str(exc_info.value) == snapshot('timeout must be a non-negative number of seconds, got -1')
+
+
+def test_pure_function_memoized():
+    """Calls to pure functions with equal arguments are only made once per run."""
+    m = pydantic_monty.Monty('[lookup(i % 2) for i in range(6)] + [other(1), other(1)]', external_functions=['lookup', 'other'])
+    calls: list[str] = []
+
+    def lookup(x: int) -> int:
+        calls.append(f'lookup({x})')
+        return x * 10
+
+    def other(x: int) -> int:
+        calls.append(f'other({x})')
+        return x
+
+    result = m.run(external_functions={'lookup': lookup, 'other': other}, pure=['lookup'])
+    assert result == snapshot([0, 10, 0, 10, 0, 10, 1, 1])
+    assert calls == snapshot(['lookup(0)', 'lookup(1)', 'other(1)', 'other(1)'])
+
+
+def test_pure_function_exceptions_not_memoized():
+    code = """
+results = []
+for _ in range(3):
+    try:
+        results.append(lookup('a'))
+    except ValueError as e:
+        results.append(str(e))
+results
+"""
+    m = pydantic_monty.Monty(code, external_functions=['lookup'])
+    calls: list[str] = []
+
+    def lookup(key: str) -> str:
+        calls.append(key)
+        if len(calls) == 1:
+            raise ValueError('flaky')
+        return key.upper()
+
+    assert m.run(external_functions={'lookup': lookup}, pure=['lookup']) == snapshot(['flaky', 'A', 'A'])
+    assert len(calls) == snapshot(2)
+
+
+def test_pure_function_cache_across_runs():
+    """Results in the host cache are reused by later runs."""
+    m = pydantic_monty.Monty('[lookup(k, scale=2) for k in keys]', inputs=['keys'], external_functions=['lookup'])
+    calls: list[str] = []
+
+    def lookup(key: str, scale: int) -> str:
+        calls.append(key)
+        return key * scale
+
+    cache: dict[str, Any] = {}
+    kwargs: dict[str, Any] = {'external_functions': {'lookup': lookup}, 'pure': ['lookup'], 'cache': cache}
+    assert m.run(inputs={'keys': ['a', 'b', 'a']}, **kwargs) == snapshot(['aa', 'bb', 'aa'])
+    assert m.run(inputs={'keys': ['b', 'c']}, **kwargs) == snapshot(['bb', 'cc'])
+    assert calls == snapshot(['a', 'b', 'c'])
+    assert cache == snapshot({"lookup('a', scale=2)": 'aa', "lookup('b', scale=2)": 'bb', "lookup('c', scale=2)": 'cc'})
+
+
+def test_pure_function_unknown():
+    m = pydantic_monty.Monty('f()', external_functions=['f'])
+    with pytest.raises(ValueError) as exc_info:
+        m.run(external_functions={'f': lambda: 1}, pure=['g'])
+    assert str(exc_info.value) == snapshot("'g' is not an external function")
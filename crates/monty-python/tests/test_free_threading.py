@@ -0,0 +1,76 @@
+import sys
+import sysconfig
+import threading
+from concurrent.futures import ThreadPoolExecutor
+from dataclasses import dataclass
+
+import pytest
+from inline_snapshot import snapshot
+
+import pydantic_monty
+
+# unlike `test_threading.py` these tests check correctness rather than speed, so they can run anywhere
+free_threaded_build = bool(sysconfig.get_config_var('Py_GIL_DISABLED'))
+
+
+@dataclass
+class Point:
+    x: int
+    y: int
+
+
+@pytest.mark.skipif(not free_threaded_build, reason='requires a free-threaded build')
+def test_gil_stays_disabled():
+    """Importing the module must not re-enable the GIL on free-threaded builds."""
+    assert not sys._is_gil_enabled()  # pyright: ignore[reportPrivateUsage]
+
+
+def test_shared_monty_concurrent_runs():
+    """One `Monty` instance can be run from many threads at once."""
+    m = pydantic_monty.Monty('sum(range(n))', inputs=['n'])
+    barrier = threading.Barrier(8)
+
+    def run(n: int) -> int:
+        barrier.wait()
+        return m.run(inputs={'n': n})
+
+    with ThreadPoolExecutor(max_workers=8) as pool:
+        results = list(pool.map(run, range(8)))
+    assert results == snapshot([0, 0, 1, 3, 6, 10, 15, 21])
+
+
+def test_shared_dataclass_registry():
+    """Dataclass types registered concurrently are all reconstructed on output."""
+    m = pydantic_monty.Monty('p', inputs=['p'])
+    barrier = threading.Barrier(8)
+
+    def run(i: int) -> Point:
+        barrier.wait()
+        return m.run(inputs={'p': Point(x=i, y=-i)})
+
+    with ThreadPoolExecutor(max_workers=8) as pool:
+        results = list(pool.map(run, range(8)))
+    assert all(isinstance(p, Point) for p in results)
+    assert results == [Point(x=i, y=-i) for i in range(8)]
+
+
+def test_snapshot_concurrent_resume():
+    """Resuming one snapshot from several threads succeeds at most once."""
+    m = pydantic_monty.Monty('fetch()', external_functions=['fetch'])
+    progress = m.start()
+    assert isinstance(progress, pydantic_monty.MontySnapshot)
+    barrier = threading.Barrier(4)
+
+    def resume() -> object:
+        barrier.wait()
+        try:
+            return progress.resume(return_value=42)
+        except RuntimeError as e:
+            return e
+
+    with ThreadPoolExecutor(max_workers=4) as pool:
+        results = list(pool.map(lambda _: resume(), range(4)))
+    completed = [r for r in results if isinstance(r, pydantic_monty.MontyComplete)]
+    assert len(completed) == 1
+    assert completed[0].output == snapshot(42)
+    assert all(isinstance(r, RuntimeError) for r in results if r is not completed[0])
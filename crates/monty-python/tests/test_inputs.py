@@ -1,3 +1,5 @@
+import sys
+
 import pytest
 from inline_snapshot import snapshot
 
@@ -124,3 +126,72 @@ foo(10)
     m = pydantic_monty.Monty(code, inputs=['x'])
     # x=5 (input), foo(10) with y=10, returns x + y = 5 + 10 = 15
     assert m.run(inputs={'x': 5}) == snapshot(15)
+
+
+def test_zero_copy_string_input():
+    """Top-level strings passed with `zero_copy=True` behave like normal strings."""
+    document = 'word ' * 100_000
+    m = pydantic_monty.Monty('len(doc.split()), doc[:9]', inputs=['doc'])
+    assert m.run(inputs={'doc': document}, zero_copy=True) == snapshot((100_000, 'word word'))
+    assert m.run(inputs={'doc': document}, zero_copy=True) == snapshot((100_000, 'word word'))
+
+
+def test_zero_copy_releases_strings():
+    """The interpreter's references to `zero_copy` strings are dropped when the run finishes."""
+    document = 'héllo wörld ✓ ' * 1_000
+    before = sys.getrefcount(document)
+    m = pydantic_monty.Monty('doc.count("✓"), doc[:5]', inputs=['doc'])
+    assert m.run(inputs={'doc': document}, zero_copy=True) == snapshot((1_000, 'héllo'))
+    assert sys.getrefcount(document) == before
+
+
+def test_zero_copy_mutation_is_local():
+    """Modifying a shared string inside Monty doesn't affect the next run."""
+    code = """
+doc += '!'
+doc
+"""
+    m = pydantic_monty.Monty(code, inputs=['doc'])
+    text = 'hello'
+    assert m.run(inputs={'doc': text}, zero_copy=True) == snapshot('hello!')
+    assert m.run(inputs={'doc': text}, zero_copy=True) == snapshot('hello!')
+    assert text == 'hello'
+
+
+def test_zero_copy_other_inputs():
+    """Non-string and new string inputs are converted as usual with `zero_copy=True`."""
+    m = pydantic_monty.Monty('f"{name}: {data}"', inputs=['name', 'data'])
+    assert m.run(inputs={'name': 'a', 'data': ['x', 1]}, zero_copy=True) == snapshot("a: ['x', 1]")
+    assert m.run(inputs={'name': 'b', 'data': {'k': 'v'}}, zero_copy=True) == snapshot("b: {'k': 'v'}")
+
+
+def test_constants():
+    constants = {'LIMIT': 3, 'SCALE': 2.5, 'NAME': 'n'}
+    m = pydantic_monty.Monty('[x * SCALE for x in range(LIMIT)], NAME', constants=constants)
+    assert m.run() == snapshot(([0.0, 2.5, 5.0], 'n'))
+
+
+def test_constants_cannot_be_reassigned():
+    with pytest.raises(pydantic_monty.MontySyntaxError) as exc_info:
+        pydantic_monty.Monty('LIMIT = 10', constants={'LIMIT': 3})
+    assert str(exc_info.value.exception()) == snapshot("cannot assign to constant 'LIMIT'")
+
+
+def test_optimize_removes_asserts():
+    m = pydantic_monty.Monty("assert False, 'never raised'\n__debug__", optimize=True)
+    assert m.run() is False
+
+
+def test_asserts_run_without_optimize():
+    m = pydantic_monty.Monty("assert __debug__\nassert False, 'checked'")
+    with pytest.raises(pydantic_monty.MontyRuntimeError) as exc_info:
+        m.run()
+    assert str(exc_info.value) == snapshot('AssertionError: checked')
+
+
+def test_constants_must_be_scalars():
+    with pytest.raises(pydantic_monty.MontyRuntimeError) as exc_info:
+        pydantic_monty.Monty('ITEMS', constants={'ITEMS': [1, 2]})
+    assert str(exc_info.value) == snapshot(
+        "TypeError: constant 'ITEMS' must be None, bool, int, float, str or bytes, not list"
+    )
@@ -53,6 +53,35 @@ recurse(10)
     assert isinstance(exc_info.value.exception(), RecursionError)
 
 
+def test_recursion_limit_frame_depth():
+    code = """
+def recurse(n):
+    if n <= 0:
+        return 0
+    return 1 + recurse(n - 1)
+
+recurse(10)
+"""
+    m = pydantic_monty.Monty(code)
+    limits = pydantic_monty.ResourceLimits(max_recursion_depth=5)
+    with pytest.raises(pydantic_monty.MontyRuntimeError) as exc_info:
+        m.run(limits=limits)
+    assert [f.depth for f in exc_info.value.traceback()] == snapshot([0, 1, 2, 3, 4, 5])
+
+
+def test_stack_size_limit():
+    code = """
+def f():
+    return 1
+
+f()
+"""
+    m = pydantic_monty.Monty(code)
+    with pytest.raises(pydantic_monty.MontyRuntimeError) as exc_info:
+        m.run(limits={'max_stack_size': 0})
+    assert isinstance(exc_info.value.exception(), RecursionError)
+
+
 def test_recursion_limit_ok():
     code = """
 def recurse(n):
@@ -228,6 +257,32 @@ def test_small_operations_within_limit():
     assert result > 0
 
 
+def test_output_size_limit():
+    """Results larger than `max_output_size` raise MemoryError instead of being converted."""
+    m = pydantic_monty.Monty("[('x' * 1000)] * 1000")
+    limits = pydantic_monty.ResourceLimits(max_output_size=100_000)
+    with pytest.raises(pydantic_monty.MontyRuntimeError) as exc_info:
+        m.run(limits=limits)
+    inner = exc_info.value.exception()
+    assert isinstance(inner, MemoryError)
+    assert str(inner) == snapshot('output size limit exceeded: value is larger than 100000 bytes')
+
+
+def test_output_size_limit_external_call():
+    """Arguments to external functions are also limited by `max_output_size`."""
+    m = pydantic_monty.Monty("send('x' * 2000)", external_functions=['send'])
+    limits = pydantic_monty.ResourceLimits(max_output_size=1000)
+    with pytest.raises(pydantic_monty.MontyRuntimeError) as exc_info:
+        m.run(external_functions={'send': lambda s: None}, limits=limits)
+    assert isinstance(exc_info.value.exception(), MemoryError)
+
+
+def test_output_size_within_limit():
+    m = pydantic_monty.Monty("'x' * 100")
+    limits = pydantic_monty.ResourceLimits(max_output_size=1000)
+    assert m.run(limits=limits) == 'x' * 100
+
+
 @pytest.mark.parametrize(
     'code',
     [
@@ -0,0 +1,98 @@
+import numpy as np
+import pytest
+from inline_snapshot import snapshot
+
+import pydantic_monty
+
+
+@pytest.mark.parametrize(
+    'value,expected',
+    [
+        (np.int64(42), 42),
+        (np.int8(-3), -3),
+        (np.uint64(2**64 - 1), 2**64 - 1),
+        (np.float64(1.5), 1.5),
+        (np.float32(0.25), 0.25),
+        (np.bool_(True), True),
+    ],
+)
+def test_scalar_input(value: object, expected: object):
+    """numpy scalars become the equivalent Python int, float or bool."""
+    m = pydantic_monty.Monty('x', inputs=['x'])
+    result = m.run(inputs={'x': value})
+    assert result == expected
+    assert type(result) is type(expected)
+
+
+def test_scalar_arithmetic():
+    """Converted scalars behave like Python numbers inside Monty."""
+    m = pydantic_monty.Monty('(x + 1, isinstance(x, int))', inputs=['x'])
+    assert m.run(inputs={'x': np.int32(41)}) == snapshot((42, True))
+
+
+def test_unsupported_scalar():
+    """numpy scalars without a Monty equivalent raise a clear error."""
+    m = pydantic_monty.Monty('x', inputs=['x'])
+    with pytest.raises(TypeError) as exc_info:
+        m.run(inputs={'x': np.complex128(1 + 2j)})
+    assert str(exc_info.value) == snapshot(
+        'Cannot convert numpy.complex128 to Monty value, only bool, integer and floating numpy scalars are supported'
+    )
+
+
+def test_scalar_from_external_function():
+    """numpy scalars returned by external functions are converted too."""
+    m = pydantic_monty.Monty('mean() * 2', external_functions=['mean'])
+    assert m.run(external_functions={'mean': lambda: np.mean(np.array([1, 2, 3]))}) == snapshot(4.0)
+
+
+def test_array_rejected_by_default():
+    """Arrays aren't converted unless the `max_ndarray_size` limit is set."""
+    m = pydantic_monty.Monty('x', inputs=['x'])
+    with pytest.raises(TypeError) as exc_info:
+        m.run(inputs={'x': np.array([1, 2, 3])})
+    assert str(exc_info.value) == snapshot(
+        'Cannot convert numpy.ndarray to Monty value, set the `max_ndarray_size` limit to convert arrays to lists'
+    )
+
+
+def test_array_to_nested_lists():
+    """Arrays within the size limit become nested lists."""
+    m = pydantic_monty.Monty('x', inputs=['x'])
+    result = m.run(inputs={'x': np.arange(6).reshape(2, 3)}, limits={'max_ndarray_size': 6})
+    assert result == snapshot([[0, 1, 2], [3, 4, 5]])
+
+
+def test_array_in_container():
+    """The limit applies to arrays nested in other inputs."""
+    m = pydantic_monty.Monty('sum(data["values"])', inputs=['data'])
+    result = m.run(inputs={'data': {'values': np.array([0.5, 1.5])}}, limits={'max_ndarray_size': 10})
+    assert result == snapshot(2.0)
+
+
+def test_array_too_large():
+    """Arrays over the size limit raise a clear error."""
+    m = pydantic_monty.Monty('x', inputs=['x'])
+    with pytest.raises(ValueError) as exc_info:
+        m.run(inputs={'x': np.zeros(11)}, limits={'max_ndarray_size': 10})
+    assert str(exc_info.value) == snapshot(
+        'Cannot convert numpy.ndarray with 11 elements to Monty value, exceeds max_ndarray_size of 10'
+    )
+
+
+def test_array_unsupported_dtype():
+    """Arrays of dtypes without a Monty equivalent raise a clear error."""
+    m = pydantic_monty.Monty('x', inputs=['x'])
+    with pytest.raises(TypeError) as exc_info:
+        m.run(inputs={'x': np.array(['a', 'b'])}, limits={'max_ndarray_size': 10})
+    assert str(exc_info.value) == snapshot(
+        'Cannot convert numpy.ndarray with dtype <U1 to Monty value, only bool, integer and floating arrays are supported'
+    )
+
+
+def test_array_start():
+    """The limit is also honoured by `Monty.start()`."""
+    m = pydantic_monty.Monty('len(x)', inputs=['x'])
+    result = m.start(inputs={'x': np.ones(4)}, limits={'max_ndarray_size': 4})
+    assert isinstance(result, pydantic_monty.MontyComplete)
+    assert result.output == snapshot(4)
@@ -871,6 +871,183 @@ def test_rename_directory_updates_file_paths_direct():
     assert fs.path_exists(P('/old/dir/file1.txt')) is False
 
 
+# =============================================================================
+# Copy, Touch and Chmod (via Monty)
+# =============================================================================
+
+
+def test_copy_file_via_monty():
+    """Path.copy() copies content and permissions, returning the target."""
+    fs = OSAccess([MemoryFile('/test/src.txt', content='content', permissions=0o600)])
+
+    code = """
+from pathlib import Path
+str(Path('/test/src.txt').copy(Path('/test/dst.txt')))
+"""
+    assert Monty(code).run(os=fs) == snapshot('/test/dst.txt')
+
+    assert fs.path_read_text(P('/test/src.txt')) == 'content'
+    assert fs.path_read_text(P('/test/dst.txt')) == 'content'
+    assert fs.path_stat(P('/test/dst.txt')).st_mode == snapshot(0o100600)
+
+
+def test_copy_directory_via_monty():
+    """Path.copy() raises IsADirectoryError for a directory."""
+    fs = OSAccess([MemoryFile('/test/dir/file.txt', content='content')])
+
+    with pytest.raises(MontyRuntimeError) as exc_info:
+        Monty("from pathlib import Path; Path('/test/dir').copy(Path('/test/other'))").run(os=fs)
+    assert str(exc_info.value) == snapshot("IsADirectoryError: [Errno 21] Is a directory: '/test/dir'")
+
+
+def test_touch_via_monty():
+    """Path.touch() creates an empty file and leaves existing files alone."""
+    fs = OSAccess([MemoryFile('/test/existing.txt', content='keep')])
+
+    code = """
+from pathlib import Path
+Path('/test/new.txt').touch()
+Path('/test/existing.txt').touch()
+Path('/test/new.txt').read_text(), Path('/test/existing.txt').read_text()
+"""
+    assert Monty(code).run(os=fs) == snapshot(('', 'keep'))
+    assert fs.path_stat(P('/test/new.txt')).st_mode == snapshot(0o100644)
+
+
+def test_touch_exist_ok_false_via_monty():
+    """Path.touch(exist_ok=False) raises FileExistsError for an existing file."""
+    fs = OSAccess([MemoryFile('/test/file.txt', content='content')])
+
+    with pytest.raises(MontyRuntimeError) as exc_info:
+        Monty("from pathlib import Path; Path('/test/file.txt').touch(exist_ok=False)").run(os=fs)
+    assert str(exc_info.value) == snapshot("FileExistsError: [Errno 17] File exists: '/test/file.txt'")
+
+
+def test_chmod_via_monty():
+    """Path.chmod() changes the permissions reported by stat()."""
+    fs = OSAccess([MemoryFile('/test/file.txt', content='content')])
+
+    code = """
+from pathlib import Path
+Path('/test/file.txt').chmod(0o400)
+oct(Path('/test/file.txt').stat().st_mode)
+"""
+    assert Monty(code).run(os=fs) == snapshot('0o100400')
+
+
+# =============================================================================
+# Glob and Walk (via Monty)
+# =============================================================================
+
+
+def test_glob_via_monty():
+    """Path.glob() and Path.rglob() find matching paths."""
+    fs = OSAccess(
+        [
+            MemoryFile('/data/a.txt', content=''),
+            MemoryFile('/data/b.py', content=''),
+            MemoryFile('/data/sub/c.txt', content=''),
+            MemoryFile('/data/sub/deep/d.txt', content=''),
+        ]
+    )
+
+    code = """
+from pathlib import Path
+root = Path('/data')
+(
+    sorted(str(p) for p in root.glob('*.txt')),
+    sorted(str(p) for p in root.glob('*/*.txt')),
+    sorted(str(p) for p in root.rglob('*.txt')),
+)
+"""
+    assert Monty(code).run(os=fs) == snapshot(
+        (
+            ['/data/a.txt'],
+            ['/data/sub/c.txt'],
+            ['/data/a.txt', '/data/sub/c.txt', '/data/sub/deep/d.txt'],
+        )
+    )
+
+
+def test_glob_missing_directory_via_monty():
+    """Path.glob() on a missing directory finds nothing."""
+    fs = OSAccess()
+    assert Monty("from pathlib import Path; list(Path('/missing').glob('*'))").run(os=fs) == snapshot([])
+
+
+def test_walk_via_monty():
+    """Path.walk() yields a tuple per directory, top down by default."""
+    fs = OSAccess(
+        [
+            MemoryFile('/data/a.txt', content=''),
+            MemoryFile('/data/sub/b.txt', content=''),
+        ]
+    )
+
+    code = """
+from pathlib import Path
+top_down = [(str(root), dirs, files) for root, dirs, files in Path('/data').walk()]
+bottom_up = [str(root) for root, _, _ in Path('/data').walk(top_down=False)]
+top_down, bottom_up
+"""
+    assert Monty(code).run(os=fs) == snapshot(
+        (
+            [('/data', ['sub'], ['a.txt']), ('/data/sub', [], ['b.txt'])],
+            ['/data/sub', '/data'],
+        )
+    )
+
+
+def test_readlink_not_a_symlink_via_monty():
+    """Path.readlink() raises OSError, OSAccess has no symlinks."""
+    fs = OSAccess([MemoryFile('/test/file.txt', content='content')])
+
+    with pytest.raises(MontyRuntimeError) as exc_info:
+        Monty("from pathlib import Path; Path('/test/file.txt').readlink()").run(os=fs)
+    assert str(exc_info.value) == snapshot("OSError: [Errno 22] Invalid argument: '/test/file.txt'")
+
+
+# =============================================================================
+# tempfile (via Monty)
+# =============================================================================
+
+
+def test_temporary_directory_via_monty():
+    """tempfile.TemporaryDirectory() creates a directory under /tmp to stage files in."""
+    fs = OSAccess()
+
+    code = """
+import tempfile
+from pathlib import Path
+tmp = Path(tempfile.TemporaryDirectory(prefix='job-').name)
+(tmp / 'stage.txt').write_text('intermediate')
+str(tmp.parent), tmp.name.startswith('job-'), [p.name for p in tmp.iterdir()]
+"""
+    assert Monty(code).run(os=fs) == snapshot(('/tmp', True, ['stage.txt']))
+
+
+def test_named_temporary_file_via_monty():
+    """tempfile.NamedTemporaryFile() creates an empty private file in dir."""
+    fs = OSAccess([MemoryFile('/data/input.csv', content='a,b')])
+
+    code = """
+import tempfile
+from pathlib import Path
+path = Path(tempfile.NamedTemporaryFile(suffix='.csv', dir='/data').name)
+str(path.parent), path.suffix, path.read_text(), oct(path.stat().st_mode & 0o777)
+"""
+    assert Monty(code).run(os=fs) == snapshot(('/data', '.csv', '', '0o600'))
+
+
+def test_named_temporary_file_missing_dir_via_monty():
+    """tempfile.NamedTemporaryFile() raises FileNotFoundError for a missing dir."""
+    fs = OSAccess()
+
+    with pytest.raises(MontyRuntimeError) as exc_info:
+        Monty("import tempfile; tempfile.NamedTemporaryFile(dir='/missing')").run(os=fs)
+    assert str(exc_info.value) == snapshot("FileNotFoundError: [Errno 2] No such file or directory: '/missing'")
+
+
 # =============================================================================
 # Path Resolution (via Monty)
 # =============================================================================
@@ -320,7 +320,7 @@ def test_abstract_filesystem_stat_missing():
 Traceback (most recent call last):
   File "main.py", line 2, in <module>
     Path("/missing").stat()
-    ~~~~~~~~~~~~~~~~~~~~~~~
+    ~~~~~~~~~~~~~~~~~~~~~^^
 FileNotFoundError: No such file or directory: /missing\
 """)
 
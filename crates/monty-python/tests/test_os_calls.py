@@ -817,3 +817,50 @@ Path('/tmp/mydir/file.txt').read_text()
             ('Path.read_text', (PurePosixPath('/tmp/mydir/file.txt'),)),
         ]
     )
+
+
+# =============================================================================
+# input() tests
+# =============================================================================
+
+
+def test_input_yields_oscall():
+    """input() yields an OS call with the prompt converted to a string."""
+    m = pydantic_monty.Monty('input(42)')
+    result = m.start()
+
+    assert isinstance(result, pydantic_monty.MontySnapshot)
+    assert result.is_os_function is True
+    assert result.function_name == snapshot('input')
+    assert result.args == snapshot(('42',))
+
+
+def test_input_callback():
+    """input() returns the line supplied by the os callback."""
+
+    def os_handler(function_name: str, args: tuple[Any, ...], kwargs: dict[str, Any] | None = None) -> str | None:
+        if function_name == 'input':
+            return 'Alice'
+        return None
+
+    m = pydantic_monty.Monty("'Hello ' + input('name: ')")
+    result = m.run(os=os_handler)
+    assert result == snapshot('Hello Alice')
+
+
+def test_input_eof_error():
+    """An EOFError raised by the os callback can be caught in Monty code."""
+
+    def os_handler(function_name: str, args: tuple[Any, ...], kwargs: dict[str, Any] | None = None) -> str | None:
+        raise EOFError('EOF when reading a line')
+
+    code = """
+try:
+    input()
+except EOFError as e:
+    result = str(e)
+result
+"""
+    m = pydantic_monty.Monty(code)
+    result = m.run(os=os_handler)
+    assert result == snapshot('EOF when reading a line')
@@ -0,0 +1,103 @@
+from dataclasses import FrozenInstanceError
+
+import pytest
+from inline_snapshot import snapshot
+from pydantic import BaseModel, ConfigDict, Field
+
+import pydantic_monty
+
+
+class Address(BaseModel):
+    city: str
+    postcode: str
+
+
+class User(BaseModel):
+    name: str
+    age: int
+    address: Address | None = None
+    tags: list[str] = []
+
+
+def test_model_input_round_trip():
+    """Model inputs are auto-registered and come back as real model instances."""
+    m = pydantic_monty.Monty('user', inputs=['user'])
+    result = m.run(inputs={'user': User(name='Alice', age=30, tags=['admin'])})
+    assert isinstance(result, User)
+    assert result == User(name='Alice', age=30, tags=['admin'])
+
+
+def test_model_field_access():
+    """Fields of models, including nested models, can be read inside Monty."""
+    code = "f'{user.name} ({user.age}) from {user.address.city}'"
+    m = pydantic_monty.Monty(code, inputs=['user'])
+    user = User(name='Bob', age=41, address=Address(city='Leeds', postcode='LS1'))
+    assert m.run(inputs={'user': user}) == snapshot('Bob (41) from Leeds')
+
+
+def test_model_repr():
+    """Models are shown like dataclasses inside Monty."""
+    m = pydantic_monty.Monty('repr(address)', inputs=['address'])
+    assert m.run(inputs={'address': Address(city='Leeds', postcode='LS1')}) == snapshot(
+        "Address(city='Leeds', postcode='LS1')"
+    )
+
+
+def test_nested_model_output():
+    """Nested models are reconstructed with their original types."""
+    m = pydantic_monty.Monty('[user.address, user]', inputs=['user'])
+    user = User(name='Bob', age=41, address=Address(city='Leeds', postcode='LS1'))
+    address, result = m.run(inputs={'user': user})
+    assert isinstance(address, Address)
+    assert isinstance(result, User)
+    assert isinstance(result.address, Address)
+    assert result == user
+
+
+def test_model_is_frozen():
+    """Models can't be modified inside Monty, as that would bypass validation."""
+    m = pydantic_monty.Monty('user.age = -1', inputs=['user'])
+    with pytest.raises(pydantic_monty.MontyRuntimeError) as exc_info:
+        m.run(inputs={'user': User(name='Alice', age=30)})
+    inner = exc_info.value.exception()
+    assert isinstance(inner, FrozenInstanceError)
+    assert inner.args[0] == snapshot("cannot assign to field 'age'")
+
+
+def test_model_alias_round_trip():
+    """Fields are exposed by name, not alias, and rebuilt without validation by alias."""
+
+    class Item(BaseModel):
+        item_id: int = Field(alias='itemId')
+
+    m = pydantic_monty.Monty('item', inputs=['item'])
+    result = m.run(inputs={'item': Item(itemId=7)})
+    assert isinstance(result, Item)
+    assert result.item_id == snapshot(7)
+
+
+def test_model_extra_fields():
+    """Extra fields of models with `extra='allow'` are available and preserved."""
+
+    class Event(BaseModel):
+        model_config = ConfigDict(extra='allow')
+
+        kind: str
+
+    m = pydantic_monty.Monty('(event.source, event)', inputs=['event'])
+    source, result = m.run(inputs={'event': Event(kind='click', source='button')})
+    assert source == snapshot('button')
+    assert isinstance(result, Event)
+    assert result.model_extra == snapshot({'source': 'button'})
+
+
+def test_registered_model_from_external_function():
+    """Models returned by external functions are reconstructed on output too."""
+
+    def get_user() -> User:
+        return User(name='Carol', age=25)
+
+    m = pydantic_monty.Monty('get_user()', external_functions=['get_user'])
+    result = m.run(external_functions={'get_user': get_user})
+    assert isinstance(result, User)
+    assert result.name == snapshot('Carol')
@@ -1,3 +1,4 @@
+import pytest
 from inline_snapshot import snapshot
 
 import pydantic_monty
@@ -32,3 +33,87 @@ def test_repl_create_with_start_inputs_feed_stateful():
     assert output == snapshot(None)
     assert repl.feed('counter = counter + 1') == snapshot(None)
     assert repl.feed('counter') == snapshot(1)
+
+
+def test_repl_interned_size_limit():
+    limits = pydantic_monty.ResourceLimits(max_interned_size=100)
+    repl, _ = pydantic_monty.MontyRepl.create('x = 1', limits=limits)
+
+    with pytest.raises(pydantic_monty.MontyRuntimeError) as exc_info:
+        repl.feed(f"x = '{'z' * 200}'")
+    assert isinstance(exc_info.value.exception(), MemoryError)
+    assert repl.feed('x') == snapshot(1)
+
+
+def test_repl_feed_namespaces_are_isolated():
+    repl, _ = pydantic_monty.MontyRepl.create('x = 1')
+
+    assert repl.feed('x = 2', namespace='analysis') == snapshot(None)
+    assert repl.feed('x') == snapshot(1)
+    assert repl.feed('x', namespace='analysis') == snapshot(2)
+    assert repl.namespaces == snapshot(['analysis'])
+
+    with pytest.raises(pydantic_monty.MontyRuntimeError) as exc_info:
+        repl.feed('x', namespace='other')
+    assert str(exc_info.value) == snapshot("NameError: name 'x' is not defined")
+    assert repl.namespaces == snapshot(['analysis', 'other'])
+
+
+def test_repl_memory_profile():
+    repl, _ = pydantic_monty.MontyRepl.create('x = 1')
+    before = {t['type']: t['count'] for t in repl.memory_profile()['types']}
+
+    repl.feed('items = [[i] for i in range(10)]')
+    profile = repl.memory_profile()
+    counts = {t['type']: t['count'] for t in profile['types']}
+    assert counts['list'] - before.get('list', 0) == 11
+    assert profile['allocation_sites'] == []
+
+
+def test_repl_memory_profile_allocation_sites():
+    limits = pydantic_monty.ResourceLimits(trace_allocations=True)
+    repl, _ = pydantic_monty.MontyRepl.create('x = 1', limits=limits)
+
+    repl.feed('items = [[i] for i in range(10)]')
+    sites = repl.memory_profile()['allocation_sites']
+    assert sites[0]['filename'] == snapshot('<python-input-0>')
+    assert sites[0]['line'] == snapshot(1)
+    assert sites[0]['count'] >= 11
+
+
+def test_repl_continuation_mode():
+    assert pydantic_monty.MontyRepl.is_complete('x = 1')
+    assert not pydantic_monty.MontyRepl.is_complete('if True:\n')
+    assert pydantic_monty.MontyRepl.continuation_mode('foo(1,') == snapshot('incomplete_implicit')
+    assert pydantic_monty.MontyRepl.continuation_mode('for i in x:\n') == snapshot('incomplete_block')
+
+
+def test_repl_feed_start_external_call():
+    repl, _ = pydantic_monty.MontyRepl.create('x = 1', external_functions=['fetch'])
+
+    progress = repl.feed_start('y = fetch(x, key=2)\nx + y')
+    assert isinstance(progress, pydantic_monty.MontyReplSnapshot)
+    assert progress.function_name == snapshot('fetch')
+    assert progress.args == snapshot((1,))
+    assert progress.kwargs == snapshot({'key': 2})
+    with pytest.raises(RuntimeError):
+        repl.feed('x')
+
+    result = progress.resume(return_value=10)
+    assert isinstance(result, pydantic_monty.MontyComplete)
+    assert result.output == snapshot(11)
+    assert repl.feed('y') == snapshot(10)
+
+
+def test_repl_feed_start_error_keeps_session():
+    repl, _ = pydantic_monty.MontyRepl.create('x = 1', external_functions=['fetch'])
+
+    progress = repl.feed_start('fetch()')
+    assert isinstance(progress, pydantic_monty.MontyReplSnapshot)
+    with pytest.raises(pydantic_monty.MontyRuntimeError) as exc_info:
+        progress.resume(exception=ValueError('boom'))
+    assert str(exc_info.value) == snapshot('ValueError: boom')
+
+    with pytest.raises(pydantic_monty.MontySyntaxError):
+        repl.feed_start('x = (')
+    assert repl.feed('x') == snapshot(1)
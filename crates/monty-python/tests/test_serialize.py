@@ -1,3 +1,4 @@
+import hashlib
 from dataclasses import dataclass, is_dataclass
 from typing import Any
 
@@ -43,6 +44,38 @@ def test_monty_dump_load_preserves_external_functions():
     assert result == snapshot(42)
 
 
+def test_monty_signed_dump_load():
+    m = pydantic_monty.Monty('x + 1', inputs=['x'])
+    data = m.dump(signing_key=b'secret')
+    assert len(data) == len(m.dump()) + 32
+
+    loaded = pydantic_monty.Monty.load(data, signing_key=b'secret')
+    assert loaded.run(inputs={'x': 41}) == snapshot(42)
+
+
+def test_monty_signed_load_rejects_bad_signature():
+    m = pydantic_monty.Monty('x + 1', inputs=['x'])
+    data = m.dump(signing_key=b'secret')
+
+    with pytest.raises(ValueError) as exc_info:
+        pydantic_monty.Monty.load(data, signing_key=b'other')
+    assert str(exc_info.value) == snapshot('invalid signature, data was modified or signed with another key')
+
+    tampered = bytes([data[0] ^ 1]) + data[1:]
+    with pytest.raises(ValueError, match='invalid signature'):
+        pydantic_monty.Monty.load(tampered, signing_key=b'secret')
+
+    with pytest.raises(ValueError, match='invalid signature'):
+        pydantic_monty.Monty.load(m.dump(), signing_key=b'secret')
+
+
+def test_monty_code_hash():
+    m = pydantic_monty.Monty('x + 1', inputs=['x'])
+    assert m.code_hash == hashlib.sha256(b'x + 1').hexdigest()
+    assert pydantic_monty.Monty.load(m.dump()).code_hash == m.code_hash
+    assert pydantic_monty.Monty('x + 2', inputs=['x']).code_hash != m.code_hash
+
+
 def test_monty_load_invalid_data():
     with pytest.raises(ValueError) as exc_info:
         pydantic_monty.Monty.load(b'invalid data')
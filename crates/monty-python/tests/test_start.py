@@ -186,6 +186,18 @@ def test_complete_repr():
     assert repr(result) == snapshot('MontyComplete(output=42)')
 
 
+def test_complete_memory_profile():
+    m = pydantic_monty.Monty('items = [[i] for i in range(5)]\nlen(items)')
+    result = m.start()
+    assert isinstance(result, pydantic_monty.MontyComplete)
+    assert result.output == 5
+    profile = result.memory_profile
+    assert profile is not None
+    counts = {t['type']: t['count'] for t in profile['types']}
+    assert counts['list'] >= 6
+    assert profile['allocation_sites'] == []
+
+
 def test_start_can_reuse_monty_instance():
     m = pydantic_monty.Monty('func(x)', inputs=['x'], external_functions=['func'])
 
@@ -258,6 +270,35 @@ def test_start_progress_resume_exception_propagates_uncaught():
     assert inner.args[0] == snapshot('uncaught error')
 
 
+def test_start_progress_resume_error_alias():
+    """`error=` takes a native exception instance or class, keeping its type, message and notes."""
+    code = 'external_func()'
+    m = pydantic_monty.Monty(code, external_functions=['external_func'])
+
+    exc = KeyError('missing')
+    exc.add_note('from the host')
+    progress = m.start()
+    assert isinstance(progress, pydantic_monty.MontySnapshot)
+    with pytest.raises(pydantic_monty.MontyRuntimeError) as exc_info:
+        progress.resume(error=exc)
+    inner = exc_info.value.exception()
+    assert isinstance(inner, KeyError)
+    assert inner.args[0] == snapshot('missing')
+    assert inner.__notes__ == snapshot(['from the host'])
+
+    progress = m.start()
+    assert isinstance(progress, pydantic_monty.MontySnapshot)
+    with pytest.raises(pydantic_monty.MontyRuntimeError) as exc_info:
+        progress.resume(error=TimeoutError)
+    assert isinstance(exc_info.value.exception(), TimeoutError)
+
+    progress = m.start()
+    assert isinstance(progress, pydantic_monty.MontySnapshot)
+    with pytest.raises(TypeError) as exc_info:
+        progress.resume(error='not an exception')  # pyright: ignore[reportArgumentType]
+    assert exc_info.value.args[0] == snapshot("exception must be a BaseException instance or subclass, not 'str'")
+
+
 def test_resume_none():
     code = 'external_func()'
     m = pydantic_monty.Monty(code, external_functions=['external_func'])
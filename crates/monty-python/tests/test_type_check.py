@@ -346,3 +346,63 @@ info: Function defined here
 info: rule `invalid-argument-type` is enabled by default
 
 """)
+
+
+def test_build_type_stubs():
+    """build_type_stubs declares inputs from sample values and external functions from signatures."""
+
+    def fetch(url: str, /, timeout: float = 1.0, *, retries: int = 0) -> bytes:
+        return b''
+
+    async def notify(message, *args: int, **kwargs: str) -> None:
+        pass
+
+    stubs = pydantic_monty.build_type_stubs(
+        inputs={'count': 1, 'name': 'x', 'items': [1, 2], 'missing': None, 'obj': object()},
+        external_functions={'fetch': fetch, 'notify': notify},
+    )
+    assert stubs == snapshot("""\
+from typing import Any
+
+count: int
+name: str
+items: list
+missing: None
+obj: Any
+def fetch(url: str, /, timeout: float = ..., *, retries: int = ...) -> bytes: ...
+async def notify(message: Any, *args: int, **kwargs: str) -> None: ...
+""")
+
+
+def test_build_type_stubs_empty():
+    """build_type_stubs with no inputs or functions only contains the typing import."""
+    assert pydantic_monty.build_type_stubs() == 'from typing import Any\n\n'
+
+
+def test_build_type_stubs_type_check():
+    """Stubs from build_type_stubs make inputs and external functions known to the type checker."""
+
+    def fetch(url: str) -> str:
+        return url
+
+    inputs = {'x': 41}
+    external_functions = {'fetch': fetch}
+    stubs = pydantic_monty.build_type_stubs(inputs, external_functions)
+    m = pydantic_monty.Monty(
+        'fetch(str(x + 1))',
+        inputs=list(inputs),
+        external_functions=list(external_functions),
+        type_check=True,
+        type_check_stubs=stubs,
+    )
+    assert m.run(inputs=inputs, external_functions=external_functions) == '42'
+
+    with pytest.raises(pydantic_monty.MontyTypingError) as exc_info:
+        pydantic_monty.Monty(
+            'fetch(x)',
+            inputs=list(inputs),
+            external_functions=list(external_functions),
+            type_check=True,
+            type_check_stubs=stubs,
+        )
+    assert str(exc_info.value).startswith('error[invalid-argument-type]: Argument to function `fetch` is incorrect')
@@ -1,3 +1,7 @@
+import datetime
+import decimal
+import uuid
+
 import pytest
 from inline_snapshot import snapshot
 
@@ -551,3 +555,79 @@ def test_namedtuple_custom_missing_attr_error():
         m.run(inputs={'s': Simple(value=42)})
     # Monty uses the full qualified name (module.ClassName) for the type
     assert "AttributeError: 'test_types.Simple' object has no attribute 'nonexistent'" in str(exc_info.value)
+
+
+def test_datetime_roundtrip():
+    value = datetime.datetime(2024, 2, 29, 13, 45, 30, 123456)
+    m = pydantic_monty.Monty('x', inputs=['x'])
+    result = m.run(inputs={'x': value})
+    assert type(result) is datetime.datetime
+    assert result == value
+
+
+def test_datetime_with_timezone_roundtrip():
+    value = datetime.datetime(2024, 1, 1, 9, 30, tzinfo=datetime.timezone(datetime.timedelta(hours=-5)))
+    m = pydantic_monty.Monty('x', inputs=['x'])
+    result = m.run(inputs={'x': value})
+    assert result == value
+    assert result.utcoffset() == datetime.timedelta(hours=-5)
+
+    utc = datetime.datetime(2024, 1, 1, tzinfo=datetime.timezone.utc)
+    assert m.run(inputs={'x': utc}).tzinfo is datetime.timezone.utc
+
+
+def test_datetime_attribute_access():
+    m = pydantic_monty.Monty('(x.year, x.month, x.day, x.hour, x.tzinfo)', inputs=['x'])
+    assert m.run(inputs={'x': datetime.datetime(2024, 5, 17, 8)}) == snapshot((2024, 5, 17, 8, None))
+
+
+def test_datetime_unsupported_tzinfo():
+    class CustomTz(datetime.tzinfo):
+        def utcoffset(self, dt: datetime.datetime | None) -> datetime.timedelta:
+            return datetime.timedelta(hours=1)
+
+    m = pydantic_monty.Monty('x', inputs=['x'])
+    with pytest.raises(TypeError, match='Cannot convert datetime with tzinfo CustomTz to Monty value'):
+        m.run(inputs={'x': datetime.datetime(2024, 1, 1, tzinfo=CustomTz())})
+
+
+def test_date_roundtrip():
+    m = pydantic_monty.Monty('(x.year, x)', inputs=['x'])
+    year, result = m.run(inputs={'x': datetime.date(1999, 12, 31)})
+    assert year == 1999
+    assert type(result) is datetime.date
+    assert result == datetime.date(1999, 12, 31)
+
+
+def test_timedelta_roundtrip():
+    value = datetime.timedelta(days=-1, seconds=5, microseconds=7)
+    m = pydantic_monty.Monty('(x.days, x.seconds, x)', inputs=['x'])
+    assert m.run(inputs={'x': value}) == snapshot((-1, 5, value))
+
+
+def test_decimal_roundtrip():
+    m = pydantic_monty.Monty('(x.value, x)', inputs=['x'])
+    text, result = m.run(inputs={'x': decimal.Decimal('1.10')})
+    assert text == snapshot('1.10')
+    assert type(result) is decimal.Decimal
+    assert str(result) == '1.10'
+
+
+def test_decimal_repr_in_monty():
+    m = pydantic_monty.Monty('repr(x)', inputs=['x'])
+    assert m.run(inputs={'x': decimal.Decimal('-0.5')}) == snapshot("Decimal(value='-0.5')")
+
+
+def test_uuid_roundtrip():
+    value = uuid.UUID('12345678-1234-5678-1234-567812345678')
+    m = pydantic_monty.Monty('(x.hex, x)', inputs=['x'])
+    hex_value, result = m.run(inputs={'x': value})
+    assert hex_value == snapshot('12345678123456781234567812345678')
+    assert type(result) is uuid.UUID
+    assert result == value
+
+
+def test_std_types_nested_in_containers():
+    value = {'when': datetime.date(2020, 1, 1), 'ids': [uuid.UUID(int=1)]}
+    m = pydantic_monty.Monty('x', inputs=['x'])
+    assert m.run(inputs={'x': value}) == value
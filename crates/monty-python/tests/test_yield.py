@@ -0,0 +1,119 @@
+import pytest
+from inline_snapshot import snapshot
+
+import pydantic_monty
+
+CODE = """
+total = 0
+for i in range(3):
+    total += i
+    monty_yield(total)
+total
+"""
+
+
+def test_start_returns_yield():
+    m = pydantic_monty.Monty(CODE)
+    progress = m.start()
+    values: list[int] = []
+    while isinstance(progress, pydantic_monty.MontyYield):
+        values.append(progress.value)
+        progress = progress.resume()
+    assert isinstance(progress, pydantic_monty.MontyComplete)
+    assert values == snapshot([0, 1, 3])
+    assert progress.output == snapshot(3)
+
+
+def test_yield_repr():
+    m = pydantic_monty.Monty('monty_yield([1, 2])')
+    progress = m.start()
+    assert repr(progress) == snapshot("MontyYield(script_name='main.py', value=[1, 2])")
+
+
+def test_yield_resume_return_value():
+    m = pydantic_monty.Monty("monty_yield('ready?') + '!'")
+    progress = m.start()
+    assert isinstance(progress, pydantic_monty.MontyYield)
+    assert progress.value == snapshot('ready?')
+    result = progress.resume(return_value='go')
+    assert isinstance(result, pydantic_monty.MontyComplete)
+    assert result.output == snapshot('go!')
+
+
+def test_yield_resume_exception():
+    code = """
+try:
+    monty_yield(1)
+except ValueError as e:
+    r = f'caught {e}'
+r
+"""
+    m = pydantic_monty.Monty(code)
+    progress = m.start()
+    assert isinstance(progress, pydantic_monty.MontyYield)
+    result = progress.resume(exception=ValueError('stop'))
+    assert isinstance(result, pydantic_monty.MontyComplete)
+    assert result.output == snapshot('caught stop')
+
+
+def test_yield_resume_twice():
+    m = pydantic_monty.Monty('monty_yield(1)')
+    progress = m.start()
+    assert isinstance(progress, pydantic_monty.MontyYield)
+    progress.resume()
+    with pytest.raises(RuntimeError) as exc_info:
+        progress.resume()
+    assert exc_info.value.args[0] == snapshot('Progress already resumed')
+
+
+def test_yield_dump_load():
+    m = pydantic_monty.Monty('x = [1, 2]\nmonty_yield(x)\nx.append(3)\nx')
+    progress = m.start()
+    assert isinstance(progress, pydantic_monty.MontyYield)
+    loaded = pydantic_monty.MontyYield.load(progress.dump())
+    assert loaded.value == snapshot([1, 2])
+    result = loaded.resume()
+    assert isinstance(result, pydantic_monty.MontyComplete)
+    assert result.output == snapshot([1, 2, 3])
+
+
+def test_run_yield_callback():
+    values: list[int] = []
+    m = pydantic_monty.Monty(CODE)
+    assert m.run(yield_callback=values.append) == snapshot(3)
+    assert values == snapshot([0, 1, 3])
+
+
+def test_run_yield_callback_return_value():
+    m = pydantic_monty.Monty('monty_yield(2) * 10')
+    assert m.run(yield_callback=lambda value: value + 1) == snapshot(30)
+
+
+def test_run_yield_callback_exception():
+    def callback(value: int) -> None:
+        raise ValueError(f'bad {value}')
+
+    code = """
+try:
+    monty_yield(1)
+except ValueError as e:
+    r = str(e)
+r
+"""
+    m = pydantic_monty.Monty(code)
+    assert m.run(yield_callback=callback) == snapshot('bad 1')
+
+
+def test_run_yield_without_callback():
+    m = pydantic_monty.Monty('monty_yield(1)')
+    with pytest.raises(pydantic_monty.MontyRuntimeError) as exc_info:
+        m.run()
+    inner = exc_info.value.exception()
+    assert isinstance(inner, NotImplementedError)
+    assert inner.args[0] == snapshot('monty_yield() not supported by standard execution.')
+
+
+def test_run_yield_callback_not_callable():
+    m = pydantic_monty.Monty('monty_yield(1)')
+    with pytest.raises(TypeError, match="TypeError: 'int' object is not callable"):
+        m.run(yield_callback=1)  # type: ignore
@@ -225,10 +225,17 @@ check_int(id(object()))
 ak = id(get_object())
 assert_type(ak, int)
 
+# input
+check_str(input())
+check_str(input('name: '))
+check_str(input(42))
+
 # isinstance
 check_bool(isinstance(42, int))
 al = isinstance(get_object(), int)
 assert_type(al, bool)
+check_bool(isinstance(42, int | str))
+check_bool(issubclass(bool, (int, str)))
 
 # len
 check_int(len([1, 2, 3]))
@@ -239,6 +246,10 @@ assert_type(an, int)
 check_int(max(1, 2, 3))
 check_int(min(1, 2, 3))
 
+# monty_yield
+monty_yield([1, 2])
+check_str(monty_yield('ready?'))
+
 # pow
 check_int(pow(2, 3))
 check_float(pow(2.0, 3.0))
@@ -315,6 +326,7 @@ e19 = TimeoutError('error')
 e20 = TypeError('error')
 e21 = ValueError('error')
 e22 = StopIteration()
+e23 = EOFError('error')
 
 
 # === Exception Inheritance ===
@@ -22,15 +22,25 @@ ALLOWED_FUNCTIONS = {
     'all',
     'any',
     'bin',
+    'callable',
     'chr',
+    'dir',
     'divmod',
+    'format',
+    'globals',
     'hash',
     'hex',
     'id',
+    'input',
     'isinstance',
+    'issubclass',
+    'iter',
     'len',
+    'locals',
     'max',
     'min',
+    'monty_yield',
+    'next',
     'oct',
     'ord',
     'pow',
@@ -84,6 +94,7 @@ ALLOWED_CLASSES = {
     'RecursionError',
     'AttributeError',
     'AssertionError',
+    'EOFError',
     'MemoryError',
     'NameError',
     'SyntaxError',
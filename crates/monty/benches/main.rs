@@ -137,6 +137,16 @@ const LIST_COMP: &str = "len([x * 2 for x in range(1000)])";
 /// Dict comprehension benchmark - creates 500 unique keys (i // 2 deduplicates pairs).
 const DICT_COMP: &str = "len({i // 2: i * 2 for i in range(1000)})";
 
+/// Dict access with string literal keys in a hot loop - exercises the interned string
+/// key fast path (cached hashes and id comparison).
+const DICT_STR_KEYS: &str = "
+point = {'x': 0, 'y': 0, 'label': 'origin'}
+for i in range(10_000):
+    point['x'] = point['x'] + 1
+    point['y'] = point['y'] + point['x']
+point['x'] + len(point['label'])
+";
+
 /// Empty tuple creation benchmark - creates 100,000 empty tuples in a list.
 const EMPTY_TUPLES: &str = "len([() for _ in range(100_000)])";
 
@@ -223,6 +233,10 @@ fn criterion_benchmark(c: &mut Criterion) {
     #[cfg(not(codspeed))]
     c.bench_function("dict_comp__cpython", |b| run_cpython(b, DICT_COMP, 500));
 
+    c.bench_function("dict_str_keys__monty", |b| run_monty(b, DICT_STR_KEYS, 10_006));
+    #[cfg(not(codspeed))]
+    c.bench_function("dict_str_keys__cpython", |b| run_cpython(b, DICT_STR_KEYS, 10_006));
+
     c.bench_function("empty_tuples__monty", |b| run_monty(b, EMPTY_TUPLES, 100_000));
     #[cfg(not(codspeed))]
     c.bench_function("empty_tuples__cpython", |b| run_cpython(b, EMPTY_TUPLES, 100_000));
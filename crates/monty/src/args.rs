@@ -6,7 +6,9 @@ use crate::{
     expressions::{ExprLoc, Identifier},
     heap::{DropWithHeap, Heap, HeapGuard},
     intern::{Interns, StringId},
+    object::OutputSize,
     parse::ParseError,
+    resource::ResourceError,
     types::{Dict, dict::DictIntoIter},
     value::Value,
 };
@@ -26,6 +28,21 @@ pub(crate) enum ArgValues {
 }
 
 impl ArgValues {
+    /// Appends the heap references held by the arguments to `ids`, for heap validation.
+    #[cfg(feature = "heap-validate")]
+    pub fn collect_heap_ids(&self, ids: &mut Vec<crate::heap::HeapId>) {
+        match self {
+            Self::Empty => {}
+            Self::One(v) => ids.extend(v.ref_id()),
+            Self::Two(v1, v2) => ids.extend(v1.ref_id().into_iter().chain(v2.ref_id())),
+            Self::Kwargs(kwargs) => kwargs.collect_heap_ids(ids),
+            Self::ArgsKargs { args, kwargs } => {
+                ids.extend(args.iter().filter_map(Value::ref_id));
+                kwargs.collect_heap_ids(ids);
+            }
+        }
+    }
+
     /// Checks that zero arguments were passed.
     ///
     /// On error, properly drops all contained values to maintain reference counts.
@@ -99,9 +116,13 @@ impl ArgValues {
             Self::Empty => Ok(None),
             Self::One(a) => Ok(Some(a)),
             other => {
-                let count = other.count();
-                other.drop_with_heap(heap);
-                Err(ExcType::type_error_at_most(name, 1, count))
+                let mut pos = other.into_pos_only(name, heap)?;
+                if pos.len() > 1 {
+                    let count = pos.len();
+                    pos.drop_with_heap(heap);
+                    return Err(ExcType::type_error_at_most(name, 1, count));
+                }
+                Ok(pos.next())
             }
         }
     }
@@ -181,9 +202,7 @@ impl ArgValues {
             } else if key_str == kwarg2 {
                 val2.replace(value.into_inner())
             } else {
-                return Err(ExcType::type_error(format!(
-                    "'{key_str}' is an invalid keyword argument for {method_name}()"
-                )));
+                return Err(ExcType::type_error_invalid_keyword(method_name, key_str));
             };
 
             old.drop_with_heap(heap);
@@ -285,10 +304,52 @@ impl ArgValues {
         }
     }
 
+    /// Converts the arguments like `into_py_objects`, without consuming them.
+    pub fn to_py_objects(
+        &self,
+        heap: &Heap<impl ResourceTracker>,
+        interns: &Interns,
+    ) -> (Vec<MontyObject>, Vec<(MontyObject, MontyObject)>) {
+        let convert = |value: &Value| MontyObject::from_value(value, heap, interns);
+        match self {
+            Self::Empty => (vec![], vec![]),
+            Self::One(a) => (vec![convert(a)], vec![]),
+            Self::Two(a1, a2) => (vec![convert(a1), convert(a2)], vec![]),
+            Self::Kwargs(kwargs) => (vec![], kwargs.to_py_objects(heap, interns)),
+            Self::ArgsKargs { args, kwargs } => {
+                (args.iter().map(convert).collect(), kwargs.to_py_objects(heap, interns))
+            }
+        }
+    }
+
+    /// Checks that the arguments together don't exceed the `max_output_size` limit.
+    ///
+    /// Called before the arguments are converted with `into_py_objects` to be passed to the host.
+    pub fn check_output_size(&self, heap: &Heap<impl ResourceTracker>, interns: &Interns) -> Result<(), ResourceError> {
+        let Some(mut budget) = OutputSize::new(heap.tracker()) else {
+            return Ok(());
+        };
+        match self {
+            Self::Empty => Ok(()),
+            Self::One(a) => budget.add_value(a, heap, interns),
+            Self::Two(a1, a2) => {
+                budget.add_value(a1, heap, interns)?;
+                budget.add_value(a2, heap, interns)
+            }
+            Self::Kwargs(kwargs) => kwargs.add_output_size(&mut budget, heap, interns),
+            Self::ArgsKargs { args, kwargs } => {
+                for arg in args {
+                    budget.add_value(arg, heap, interns)?;
+                }
+                kwargs.add_output_size(&mut budget, heap, interns)
+            }
+        }
+    }
+
     /// Returns the number of positional arguments.
     ///
     /// For `Kwargs` returns 0, for `ArgsKargs` returns only the positional args count.
-    fn count(&self) -> usize {
+    pub fn count(&self) -> usize {
         match self {
             Self::Empty => 0,
             Self::One(_) => 1,
@@ -406,6 +467,62 @@ pub(crate) enum KwargsValues {
 }
 
 impl KwargsValues {
+    /// Appends the heap references held by the keyword arguments to `ids`.
+    #[cfg(feature = "heap-validate")]
+    fn collect_heap_ids(&self, ids: &mut Vec<crate::heap::HeapId>) {
+        match self {
+            Self::Empty => {}
+            Self::Inline(kvs) => ids.extend(kvs.iter().filter_map(|(_, v)| v.ref_id())),
+            Self::Dict(dict) => ids.extend(
+                dict.into_iter()
+                    .flat_map(|(k, v)| k.ref_id().into_iter().chain(v.ref_id())),
+            ),
+        }
+    }
+
+    /// Adds the size of the keyword arguments to an output size budget.
+    fn add_output_size(
+        &self,
+        budget: &mut OutputSize,
+        heap: &Heap<impl ResourceTracker>,
+        interns: &Interns,
+    ) -> Result<(), ResourceError> {
+        match self {
+            Self::Empty => Ok(()),
+            Self::Inline(kvs) => kvs.iter().try_for_each(|(key, value)| {
+                budget.add_value(&Value::InternString(*key), heap, interns)?;
+                budget.add_value(value, heap, interns)
+            }),
+            Self::Dict(dict) => dict.into_iter().try_for_each(|(key, value)| {
+                budget.add_value(key, heap, interns)?;
+                budget.add_value(value, heap, interns)
+            }),
+        }
+    }
+
+    /// Converts the keyword arguments like `into_py_objects`, without consuming them.
+    fn to_py_objects(&self, heap: &Heap<impl ResourceTracker>, interns: &Interns) -> Vec<(MontyObject, MontyObject)> {
+        match self {
+            Self::Empty => vec![],
+            Self::Inline(kvs) => kvs
+                .iter()
+                .map(|(k, v)| {
+                    let key = MontyObject::String(interns.get_str(*k).to_owned());
+                    (key, MontyObject::from_value(v, heap, interns))
+                })
+                .collect(),
+            Self::Dict(dict) => dict
+                .iter()
+                .map(|(k, v)| {
+                    (
+                        MontyObject::from_value(k, heap, interns),
+                        MontyObject::from_value(v, heap, interns),
+                    )
+                })
+                .collect(),
+        }
+    }
+
     /// Returns the number of keyword arguments.
     #[must_use]
     pub fn len(&self) -> usize {
@@ -447,6 +564,86 @@ impl KwargsValues {
         }
     }
 
+    /// Extracts one optional keyword argument by name, consuming the kwargs.
+    ///
+    /// Returns `None` if the keyword is missing. Any other keyword raises CPython's
+    /// `{func_name}() got an unexpected keyword argument '{key}'` TypeError.
+    pub fn extract_one(
+        self,
+        func_name: &str,
+        kwarg: &str,
+        heap: &mut Heap<impl ResourceTracker>,
+        interns: &Interns,
+    ) -> RunResult<Option<Value>> {
+        let kwargs = self.into_iter();
+        defer_drop_mut!(kwargs, heap);
+
+        let mut val_guard = HeapGuard::new(None, heap);
+        let (val, heap) = val_guard.as_parts_mut();
+
+        for (key, value) in kwargs {
+            defer_drop!(key, heap);
+            let mut value = HeapGuard::new(value, heap);
+
+            let Some(keyword_name) = key.as_either_str(value.heap()) else {
+                return Err(ExcType::type_error("keywords must be strings"));
+            };
+
+            let key_str = keyword_name.as_str(interns);
+            if key_str != kwarg {
+                return Err(ExcType::type_error_unexpected_keyword(func_name, key_str));
+            }
+            let old = val.replace(value.into_inner());
+            old.drop_with_heap(heap);
+        }
+
+        Ok(val_guard.into_inner())
+    }
+
+    /// Extracts two optional keyword arguments by name, consuming the kwargs.
+    ///
+    /// Returns `(None, None)` for missing kwargs. Any other keyword raises CPython's
+    /// `{func_name}() got an unexpected keyword argument '{key}'` TypeError.
+    pub fn extract_two(
+        self,
+        func_name: &str,
+        kwarg1: &str,
+        kwarg2: &str,
+        heap: &mut Heap<impl ResourceTracker>,
+        interns: &Interns,
+    ) -> RunResult<(Option<Value>, Option<Value>)> {
+        let kwargs = self.into_iter();
+        defer_drop_mut!(kwargs, heap);
+
+        // Guards are reversed so that destructure can pull them
+        let mut val2_guard = HeapGuard::new(None, heap);
+        let (val2, heap) = val2_guard.as_parts_mut();
+        let mut val1_guard = HeapGuard::new(None, heap);
+        let (val1, heap) = val1_guard.as_parts_mut();
+
+        for (key, value) in kwargs {
+            defer_drop!(key, heap);
+            let mut value = HeapGuard::new(value, heap);
+
+            let Some(keyword_name) = key.as_either_str(value.heap()) else {
+                return Err(ExcType::type_error("keywords must be strings"));
+            };
+
+            let key_str = keyword_name.as_str(interns);
+            let old = if key_str == kwarg1 {
+                val1.replace(value.into_inner())
+            } else if key_str == kwarg2 {
+                val2.replace(value.into_inner())
+            } else {
+                return Err(ExcType::type_error_unexpected_keyword(func_name, key_str));
+            };
+
+            old.drop_with_heap(heap);
+        }
+
+        Ok((val1_guard.into_inner(), val2_guard.into_inner()))
+    }
+
     /// Helper for functions which do not yet support kwargs, returns an `Err` if there are kwargs.
     pub fn not_supported_yet(self, method_name: &str, heap: &mut Heap<impl ResourceTracker>) -> RunResult<()> {
         if self.is_empty() {
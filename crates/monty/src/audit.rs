@@ -0,0 +1,216 @@
+//! Audit events for security-relevant activity inside the sandbox.
+//!
+//! Embedders that need a record of what sandboxed code tried to do (e.g. to feed a SIEM) implement
+//! [`AuditHook`] and run code with an [`AuditTracker`], usually chained onto the tracker enforcing
+//! limits: `LimitedTracker::new(limits).chain(AuditTracker::new(hook))`.
+//!
+//! Events are reported through [`ResourceTracker::audit`], so hooks see every event of a run
+//! regardless of how the host drives it (`run`, `start`/`resume` or the REPL).
+
+use std::{
+    fmt,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::{ExcType, MontyObject, OsFunction, ResourceError, ResourceTracker};
+
+/// A security-relevant event, passed to [`AuditHook::on_event`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditEvent<'a> {
+    /// When the event happened.
+    pub timestamp: SystemTime,
+    /// What happened.
+    pub kind: AuditEventKind<'a>,
+}
+
+/// The different kinds of [`AuditEvent`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum AuditEventKind<'a> {
+    /// Sandboxed code called an external function or a dataclass method, execution is
+    /// about to be handed to the host.
+    ExternalCall {
+        function_name: &'a str,
+        method_call: bool,
+        args: &'a [MontyObject],
+        kwargs: &'a [(MontyObject, MontyObject)],
+    },
+    /// Sandboxed code called a filesystem or environment function, execution is about to be
+    /// handed to the host.
+    OsCall {
+        function: OsFunction,
+        args: &'a [MontyObject],
+        kwargs: &'a [(MontyObject, MontyObject)],
+    },
+    /// Sandboxed code imported a module. `allowed` is false for modules unavailable in the
+    /// sandbox, which raise `ModuleNotFoundError`.
+    Import { module: &'a str, allowed: bool },
+    /// A resource limit was exceeded, terminating execution with an uncatchable exception.
+    ResourceLimit { exc_type: ExcType, message: &'a str },
+    /// Sandboxed code tried to use a capability the host didn't provide, e.g. an external
+    /// function called during standard execution with `MontyRun::run`.
+    CapabilityDenied { capability: Capability, name: &'a str },
+}
+
+/// Capabilities whose use can be denied, see [`AuditEventKind::CapabilityDenied`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Capability {
+    /// Calling an external function.
+    ExternalFunction,
+    /// Calling a filesystem or environment function.
+    OsFunction,
+    /// Calling a method on a dataclass provided by the host.
+    MethodCall,
+    /// Awaiting futures of external calls.
+    AsyncFutures,
+    /// Streaming partial results to the host with `monty_yield()`.
+    Yield,
+    /// Using randomness, e.g. `secrets.token_hex()`, see [`EntropyPolicy`](crate::EntropyPolicy).
+    Entropy,
+}
+
+impl fmt::Display for Capability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::ExternalFunction => "external_function",
+            Self::OsFunction => "os_function",
+            Self::MethodCall => "method_call",
+            Self::AsyncFutures => "async_futures",
+            Self::Yield => "yield",
+            Self::Entropy => "entropy",
+        })
+    }
+}
+
+impl<'a> AuditEvent<'a> {
+    /// Creates an event of the given kind, timestamped now.
+    #[must_use]
+    pub fn now(kind: AuditEventKind<'a>) -> Self {
+        Self {
+            timestamp: SystemTime::now(),
+            kind,
+        }
+    }
+
+    /// Short snake_case name of the event kind, e.g. `"external_call"`.
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        match self.kind {
+            AuditEventKind::ExternalCall { .. } => "external_call",
+            AuditEventKind::OsCall { .. } => "os_call",
+            AuditEventKind::Import { .. } => "import",
+            AuditEventKind::ResourceLimit { .. } => "resource_limit",
+            AuditEventKind::CapabilityDenied { .. } => "capability_denied",
+        }
+    }
+
+    /// Seconds since the Unix epoch of `timestamp`, as commonly used in log records.
+    #[must_use]
+    pub fn unix_timestamp(&self) -> f64 {
+        self.timestamp
+            .duration_since(UNIX_EPOCH)
+            .map_or(0.0, |duration| duration.as_secs_f64())
+    }
+
+    /// Stable digest of the call arguments for external and OS calls, `None` for other events.
+    ///
+    /// Lets logs correlate calls with identical arguments without recording the arguments
+    /// themselves, which may be sensitive. The digest is a 64-bit FNV-1a hash of the
+    /// arguments in Monty's binary serialization, so it's the same across processes and
+    /// platforms but isn't cryptographically secure.
+    #[must_use]
+    pub fn args_digest(&self) -> Option<u64> {
+        match self.kind {
+            AuditEventKind::ExternalCall { args, kwargs, .. } | AuditEventKind::OsCall { args, kwargs, .. } => {
+                let bytes = postcard::to_allocvec(&(args, kwargs)).expect("serializing MontyObjects can't fail");
+                Some(fnv1a(&bytes))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// 64-bit FNV-1a hash.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+    bytes
+        .iter()
+        .fold(OFFSET_BASIS, |hash, byte| (hash ^ u64::from(*byte)).wrapping_mul(PRIME))
+}
+
+/// Receives [`AuditEvent`]s, see [`AuditTracker`].
+///
+/// Hooks are called synchronously while the interpreter is paused, so they should be quick,
+/// e.g. pushing the event onto a channel. They can't stop execution; enforce policies with
+/// resource limits or by refusing external calls instead.
+pub trait AuditHook: fmt::Debug {
+    /// Called for every event.
+    fn on_event(&self, event: &AuditEvent<'_>);
+}
+
+/// A resource tracker with no limits of its own which passes audit events to an [`AuditHook`].
+///
+/// Combine it with a tracker enforcing limits using [`ResourceTracker::chain`]. To snapshot a
+/// run with this tracker, the hook must be serializable; skip fields that can't be serialized
+/// and restore them after loading.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct AuditTracker<H> {
+    hook: H,
+}
+
+impl<H: AuditHook> AuditTracker<H> {
+    /// Creates a tracker passing events to `hook`.
+    #[must_use]
+    pub fn new(hook: H) -> Self {
+        Self { hook }
+    }
+
+    /// Returns the hook.
+    pub fn hook(&self) -> &H {
+        &self.hook
+    }
+
+    /// Returns the hook mutably, e.g. to restore state after loading a snapshot.
+    pub fn hook_mut(&mut self) -> &mut H {
+        &mut self.hook
+    }
+}
+
+impl<H: AuditHook> ResourceTracker for AuditTracker<H> {
+    #[inline]
+    fn on_allocate(&mut self, _: impl FnOnce() -> usize) -> Result<(), ResourceError> {
+        Ok(())
+    }
+
+    #[inline]
+    fn on_free(&mut self, _: impl FnOnce() -> usize) {}
+
+    #[inline]
+    fn check_time(&self) -> Result<(), ResourceError> {
+        Ok(())
+    }
+
+    #[inline]
+    fn check_recursion_depth(&self, _current_depth: usize) -> Result<(), ResourceError> {
+        Ok(())
+    }
+
+    #[inline]
+    fn check_large_result(&self, _estimated_bytes: usize) -> Result<(), ResourceError> {
+        Ok(())
+    }
+
+    #[inline]
+    fn max_output_size(&self) -> Option<usize> {
+        None
+    }
+
+    #[inline]
+    fn max_stack_size(&self) -> Option<usize> {
+        None
+    }
+
+    fn audit(&self, event: &AuditEvent<'_>) {
+        self.hook.on_event(event);
+    }
+}
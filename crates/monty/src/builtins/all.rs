@@ -15,6 +15,9 @@ use crate::{
 ///
 /// Returns True if all elements of the iterable are true (or if the iterable is empty).
 /// Short-circuits on the first falsy value.
+///
+/// Items are pulled one at a time, so when given an iterator (e.g. from `iter()`)
+/// everything after the first falsy item is left unconsumed.
 pub fn builtin_all(heap: &mut Heap<impl ResourceTracker>, args: ArgValues, interns: &Interns) -> RunResult<Value> {
     let iterable = args.get_one_arg("all", heap)?;
     let iter = MontyIter::new(iterable, heap, interns)?;
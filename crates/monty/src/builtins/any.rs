@@ -15,6 +15,9 @@ use crate::{
 ///
 /// Returns True if any element of the iterable is true.
 /// Returns False for an empty iterable. Short-circuits on the first truthy value.
+///
+/// Items are pulled one at a time, so when given an iterator (e.g. from `iter()`)
+/// everything after the first truthy item is left unconsumed.
 pub fn builtin_any(heap: &mut Heap<impl ResourceTracker>, args: ArgValues, interns: &Interns) -> RunResult<Value> {
     let iterable = args.get_one_arg("any", heap)?;
     let iter = MontyIter::new(iterable, heap, interns)?;
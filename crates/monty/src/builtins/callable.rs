@@ -0,0 +1,15 @@
+//! Implementation of the callable() builtin function.
+
+use crate::{
+    args::ArgValues, defer_drop, exception_private::RunResult, heap::Heap, resource::ResourceTracker, value::Value,
+};
+
+/// Implementation of the callable() builtin function.
+///
+/// Returns True if the argument can be called, e.g. functions, closures, builtins,
+/// types and external functions.
+pub fn builtin_callable(heap: &mut Heap<impl ResourceTracker>, args: ArgValues) -> RunResult<Value> {
+    let value = args.get_one_arg("callable", heap)?;
+    defer_drop!(value, heap);
+    Ok(Value::Bool(value.is_callable(heap)))
+}
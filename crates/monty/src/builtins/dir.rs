@@ -0,0 +1,72 @@
+//! Implementation of the dir() builtin function with an argument.
+//!
+//! `dir()` without an argument lists the calling frame's names, so the VM handles that
+//! form (see `bytecode/vm/introspection.rs`) and hands `dir(obj)` to this module.
+
+use crate::{
+    args::ArgValues,
+    builtins::Builtins,
+    defer_drop,
+    exception_private::{ExcType, RunResult},
+    heap::{Heap, HeapData, HeapGuard},
+    intern::Interns,
+    resource::ResourceTracker,
+    types::{Dict, List, PyTrait, Str},
+    value::{EitherStr, Value},
+};
+
+/// Implementation of the dir() builtin function with an argument.
+///
+/// Returns the sorted names of the attributes and methods Monty supports on the object's
+/// type, plus its instance attributes. Unlike CPython, the type's dunder methods aren't listed.
+pub fn builtin_dir(heap: &mut Heap<impl ResourceTracker>, args: ArgValues, interns: &Interns) -> RunResult<Value> {
+    let Some(obj) = args.get_zero_one_arg("dir", heap)? else {
+        return Err(ExcType::not_implemented("dir() without an argument cannot be called from another builtin").into());
+    };
+    defer_drop!(obj, heap);
+
+    let mut names = attribute_names(obj, heap);
+    names.sort_by(|a, b| a.as_str(interns).cmp(b.as_str(interns)));
+    names.dedup();
+
+    let mut items = HeapGuard::new(Vec::with_capacity(names.len()), heap);
+    {
+        let (items, heap) = items.as_parts_mut();
+        for name in names {
+            items.push(match name {
+                EitherStr::Interned(id) => Value::InternString(id),
+                EitherStr::Heap(s) => Value::Ref(heap.allocate(HeapData::Str(Str::from(s)))?),
+            });
+        }
+    }
+    let (items, heap) = items.into_parts();
+    let list_id = heap.allocate(HeapData::List(List::new(items)))?;
+    Ok(Value::Ref(list_id))
+}
+
+/// Returns the names `dir(obj)` lists for `obj`, unsorted and possibly with duplicates.
+///
+/// That's the attributes and methods Monty supports on the value's type (or on the type
+/// itself for type objects like `list`), plus dataclass attributes, namedtuple fields and
+/// module attributes.
+fn attribute_names(obj: &Value, heap: &Heap<impl ResourceTracker>) -> Vec<EitherStr> {
+    let ty = match obj {
+        Value::Builtin(Builtins::Type(ty)) => *ty,
+        other => other.py_type(heap),
+    };
+    let mut names: Vec<EitherStr> = ty.attribute_names().iter().map(|&name| name.into()).collect();
+    if let Value::Ref(id) = obj {
+        match heap.get(*id) {
+            HeapData::Dataclass(dc) => names.extend(str_keys(dc.attrs(), heap)),
+            HeapData::Module(module) => names.extend(str_keys(module.attrs(), heap)),
+            HeapData::NamedTuple(nt) => names.extend(nt.field_names().iter().cloned()),
+            _ => {}
+        }
+    }
+    names
+}
+
+/// Returns the str keys of an attribute dict.
+fn str_keys<'a>(dict: &'a Dict, heap: &'a Heap<impl ResourceTracker>) -> impl Iterator<Item = EitherStr> + 'a {
+    dict.iter().filter_map(|(key, _)| key.as_either_str(heap))
+}
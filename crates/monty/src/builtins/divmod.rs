@@ -8,16 +8,19 @@ use crate::{
     args::ArgValues,
     defer_drop,
     exception_private::{ExcType, RunResult, SimpleException},
-    heap::{Heap, HeapData},
+    heap::{DropWithHeap, Heap, HeapData},
     resource::{ResourceTracker, check_div_size},
-    types::{LongInt, PyTrait, allocate_tuple},
-    value::{Value, floor_divmod},
+    types::{
+        LongInt, PyTrait, allocate_tuple,
+        fraction::{FractionOp, fraction_binary_op},
+    },
+    value::{Value, float_divmod, floor_divmod, long_int_float_operands},
 };
 
 /// Implementation of the divmod() builtin function.
 ///
-/// Returns a tuple (quotient, remainder) from integer division.
-/// Equivalent to (a // b, a % b).
+/// Returns a tuple (quotient, remainder) for int, bool, float and fraction operands.
+/// Equivalent to (a // b, a % b), with floor semantics for negative operands.
 pub fn builtin_divmod(heap: &mut Heap<impl ResourceTracker>, args: ArgValues) -> RunResult<Value> {
     let (a, b) = args.get_two_args("divmod", heap)?;
     let a = super::round::normalize_bool_to_int(a);
@@ -25,6 +28,17 @@ pub fn builtin_divmod(heap: &mut Heap<impl ResourceTracker>, args: ArgValues) ->
     defer_drop!(a, heap);
     defer_drop!(b, heap);
 
+    if let Some(quot) = fraction_binary_op(a, b, FractionOp::FloorDiv, heap)? {
+        let rem = match fraction_binary_op(a, b, FractionOp::Mod, heap) {
+            Ok(rem) => rem.expect("fraction operands support modulo"),
+            Err(err) => {
+                quot.drop_with_heap(heap);
+                return Err(err);
+            }
+        };
+        return Ok(allocate_tuple(smallvec![quot, rem], heap)?);
+    }
+
     match (a, b) {
         (Value::Int(x), Value::Int(y)) => {
             if *y == 0 {
@@ -113,33 +127,21 @@ pub fn builtin_divmod(heap: &mut Heap<impl ResourceTracker>, args: ArgValues) ->
                 .into())
             }
         }
-        (Value::Float(x), Value::Float(y)) => {
-            if *y == 0.0 {
-                Err(ExcType::divmod_by_zero())
-            } else {
-                let quot = (x / y).floor();
-                let rem = x - quot * y;
-                Ok(allocate_tuple(smallvec![Value::Float(quot), Value::Float(rem)], heap)?)
-            }
-        }
-        (Value::Int(x), Value::Float(y)) => {
-            if *y == 0.0 {
-                Err(ExcType::divmod_by_zero())
+        (Value::Float(x), Value::Float(y)) => float_divmod_tuple(*x, *y, heap),
+        (Value::Int(x), Value::Float(y)) => float_divmod_tuple(*x as f64, *y, heap),
+        (Value::Float(x), Value::Int(y)) => float_divmod_tuple(*x, *y as f64, heap),
+        // LongInt with float
+        (Value::Ref(_), Value::Float(_)) | (Value::Float(_), Value::Ref(_)) => {
+            if let Some((x, y)) = long_int_float_operands(a, b, heap)? {
+                float_divmod_tuple(x, y, heap)
             } else {
-                let xf = *x as f64;
-                let quot = (xf / y).floor();
-                let rem = xf - quot * y;
-                Ok(allocate_tuple(smallvec![Value::Float(quot), Value::Float(rem)], heap)?)
-            }
-        }
-        (Value::Float(x), Value::Int(y)) => {
-            if *y == 0 {
-                Err(ExcType::divmod_by_zero())
-            } else {
-                let yf = *y as f64;
-                let quot = (x / yf).floor();
-                let rem = x - quot * yf;
-                Ok(allocate_tuple(smallvec![Value::Float(quot), Value::Float(rem)], heap)?)
+                let a_type = a.py_type(heap);
+                let b_type = b.py_type(heap);
+                Err(SimpleException::new_msg(
+                    ExcType::TypeError,
+                    format!("unsupported operand type(s) for divmod(): '{a_type}' and '{b_type}'"),
+                )
+                .into())
             }
         }
         _ => {
@@ -154,6 +156,15 @@ pub fn builtin_divmod(heap: &mut Heap<impl ResourceTracker>, args: ArgValues) ->
     }
 }
 
+/// Computes `divmod()` for two floats and allocates the `(quotient, remainder)` tuple.
+fn float_divmod_tuple(x: f64, y: f64, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
+    if y == 0.0 {
+        return Err(ExcType::divmod_by_zero());
+    }
+    let (quot, rem) = float_divmod(x, y);
+    Ok(allocate_tuple(smallvec![Value::Float(quot), Value::Float(rem)], heap)?)
+}
+
 /// Computes Python-style floor division and modulo for BigInts.
 ///
 /// Uses `div_mod_floor` from num_integer for correct floor semantics.
@@ -3,10 +3,10 @@
 use smallvec::smallvec;
 
 use crate::{
-    args::ArgValues,
+    args::{ArgValues, KwargsValues},
     defer_drop, defer_drop_mut,
     exception_private::{ExcType, RunResult, SimpleException},
-    heap::{Heap, HeapData},
+    heap::{DropWithHeap, Heap, HeapData, HeapGuard},
     intern::Interns,
     resource::ResourceTracker,
     types::{List, MontyIter, PyTrait, allocate_tuple},
@@ -15,16 +15,27 @@ use crate::{
 
 /// Implementation of the enumerate() builtin function.
 ///
-/// Returns a list of (index, value) tuples.
+/// Returns a list of (index, value) tuples, counting from `start` which may be passed
+/// positionally or by keyword and defaults to 0.
 /// Note: In Python this returns an iterator, but we return a list for simplicity.
 pub fn builtin_enumerate(
     heap: &mut Heap<impl ResourceTracker>,
     args: ArgValues,
     interns: &Interns,
 ) -> RunResult<Value> {
-    let (iterable, start) = args.get_one_two_args("enumerate", heap)?;
-    let iter = MontyIter::new(iterable, heap, interns)?;
-    defer_drop_mut!(iter, heap);
+    let (positional, kwargs) = args.into_parts();
+    defer_drop_mut!(positional, heap);
+    let start_kwarg = extract_start(kwargs, heap, interns)?;
+    defer_drop_mut!(start_kwarg, heap);
+
+    let arg_count = positional.len() + usize::from(start_kwarg.is_some());
+    if positional.len() == 0 {
+        return Err(ExcType::type_error_at_least("enumerate", 1, 0));
+    } else if arg_count > 2 {
+        return Err(ExcType::type_error_at_most("enumerate", 2, arg_count));
+    }
+    let iterable = positional.next().expect("checked length above");
+    let start = positional.next().or_else(|| start_kwarg.take());
     defer_drop!(start, heap);
 
     // Get start index (default 0)
@@ -42,6 +53,8 @@ pub fn builtin_enumerate(
         None => 0,
     };
 
+    let iter = MontyIter::new(iterable, heap, interns)?;
+    defer_drop_mut!(iter, heap);
     let mut result: Vec<Value> = Vec::new();
 
     while let Some(item) = iter.for_next(heap, interns)? {
@@ -54,3 +67,35 @@ pub fn builtin_enumerate(
     let heap_id = heap.allocate(HeapData::List(List::new(result)))?;
     Ok(Value::Ref(heap_id))
 }
+
+/// Extracts the optional `start` keyword argument.
+///
+/// Other keywords raise CPython's `'{key}' is an invalid keyword argument for enumerate()`.
+fn extract_start(
+    kwargs: KwargsValues,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<Option<Value>> {
+    let kwargs = kwargs.into_iter();
+    defer_drop_mut!(kwargs, heap);
+
+    let mut start_guard = HeapGuard::new(None, heap);
+    let (start, heap) = start_guard.as_parts_mut();
+
+    for (key, value) in kwargs {
+        defer_drop!(key, heap);
+        let mut value = HeapGuard::new(value, heap);
+
+        let Some(keyword_name) = key.as_either_str(value.heap()) else {
+            return Err(ExcType::type_error("keywords must be strings"));
+        };
+        let key_str = keyword_name.as_str(interns);
+        if key_str != "start" {
+            return Err(ExcType::type_error_invalid_keyword("enumerate", key_str));
+        }
+        let old = start.replace(value.into_inner());
+        old.drop_with_heap(heap);
+    }
+
+    Ok(start_guard.into_inner())
+}
@@ -0,0 +1,53 @@
+//! Implementation of the format() builtin function.
+
+use crate::{
+    args::ArgValues,
+    defer_drop,
+    exception_private::{ExcType, RunError, RunResult, SimpleException},
+    fstring::{FormatSpecError, ParsedFormatSpec, format_with_spec},
+    heap::Heap,
+    intern::Interns,
+    resource::{DepthGuard, ResourceTracker},
+    types::{PyTrait, str::allocate_string},
+    value::Value,
+};
+
+/// Implementation of the format() builtin function.
+///
+/// Converts a value to a formatted string using the same format-spec machinery as
+/// f-strings, so `format(value, spec)` always equals `f'{value:{spec}}'`.
+/// The spec defaults to `''`, which is equivalent to `str(value)`.
+pub fn builtin_format(heap: &mut Heap<impl ResourceTracker>, args: ArgValues, interns: &Interns) -> RunResult<Value> {
+    let (value, spec_value) = args.get_one_two_args("format", heap)?;
+    defer_drop!(value, heap);
+
+    let mut guard = DepthGuard::default();
+    let spec = match spec_value {
+        Some(spec_value) => {
+            defer_drop!(spec_value, heap);
+            if !spec_value.is_str(heap) {
+                let spec_type = spec_value.py_type(heap);
+                return Err(ExcType::type_error(format!(
+                    "format() argument 2 must be str, not {spec_type}"
+                )));
+            }
+            let spec_str = spec_value.py_str(heap, &mut guard, interns);
+            spec_str.parse::<ParsedFormatSpec>().map_err(|err| match err {
+                FormatSpecError::Invalid(invalid) => {
+                    let value_type = value.py_type(heap);
+                    RunError::from(SimpleException::new_msg(
+                        ExcType::ValueError,
+                        format!("Invalid format specifier '{invalid}' for object of type '{value_type}'"),
+                    ))
+                }
+                FormatSpecError::Grouping(err) => err.into(),
+            })?
+        }
+        None => ""
+            .parse::<ParsedFormatSpec>()
+            .expect("empty format spec is always valid"),
+    };
+
+    let formatted = format_with_spec(value, &spec, heap, &mut guard, interns)?;
+    allocate_string(formatted, heap)
+}
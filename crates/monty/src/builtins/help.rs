@@ -0,0 +1,247 @@
+//! Implementation of the help() builtin function.
+
+use std::fmt::Write;
+
+use super::{Builtins, BuiltinsFunctions};
+use crate::{
+    args::ArgValues,
+    defer_drop,
+    exception_private::RunResult,
+    function::Function,
+    heap::{Heap, HeapData},
+    intern::Interns,
+    io::PrintWriter,
+    resource::{DepthGuard, ResourceTracker},
+    types::{Dataclass, PyTrait},
+    value::Value,
+};
+
+/// Implementation of the help() builtin function.
+///
+/// Prints the signature and docstring of functions defined in the sandbox, signatures of
+/// builtins and registered external functions, and the fields of dataclasses. Other values
+/// describe their type. Unlike CPython there is no interactive help, `help()` without an
+/// argument prints how to use it.
+pub fn builtin_help(
+    heap: &mut Heap<impl ResourceTracker>,
+    args: ArgValues,
+    interns: &Interns,
+    print_writer: &mut PrintWriter<'_>,
+) -> RunResult<Value> {
+    let Some(value) = args.get_zero_one_arg("help", heap)? else {
+        print_writer.stdout_write("Call help(obj) to describe a function, type or value, e.g. help(len).\n".into())?;
+        return Ok(Value::None);
+    };
+    defer_drop!(value, heap);
+    let text = describe(value, heap, interns);
+    print_writer.stdout_write(text.into())?;
+    Ok(Value::None)
+}
+
+/// Builds the help text for `value`, ending with a newline.
+fn describe(value: &Value, heap: &Heap<impl ResourceTracker>, interns: &Interns) -> String {
+    let mut text = String::new();
+    match value {
+        Value::Builtin(Builtins::Function(func)) => {
+            let (signature, doc) = builtin_function_doc(*func);
+            writeln!(text, "Help on built-in function {func}:\n\n{signature}").unwrap();
+            write_doc(&mut text, doc);
+        }
+        Value::Builtin(Builtins::Type(t)) => writeln!(text, "Help on class {t}:\n\nclass {t}").unwrap(),
+        Value::Builtin(Builtins::ExcType(exc)) => writeln!(text, "Help on class {exc}:\n\nclass {exc}").unwrap(),
+        Value::ModuleFunction(func) => writeln!(text, "Help on built-in function {func}:\n\n{func}(...)").unwrap(),
+        Value::DefFunction(id) => describe_function(&mut text, interns.get_function(*id), &[], heap, interns),
+        Value::ExtFunction(id) => {
+            let name = interns.get_external_function_name(*id);
+            writeln!(text, "Help on external function {name}:\n").unwrap();
+            match interns.get_external_signature(*id) {
+                Some(signature) => writeln!(text, "{}", signature.type_stub()).unwrap(),
+                None => writeln!(text, "{name}(...)").unwrap(),
+            }
+        }
+        Value::Ref(id) => match heap.get(*id) {
+            HeapData::Closure(func_id, _, defaults) | HeapData::FunctionDefaults(func_id, defaults) => {
+                describe_function(&mut text, interns.get_function(*func_id), defaults, heap, interns);
+            }
+            HeapData::Dataclass(dc) => describe_dataclass(&mut text, dc, interns),
+            _ => describe_instance(&mut text, value, heap),
+        },
+        _ => describe_instance(&mut text, value, heap),
+    }
+    text
+}
+
+/// Describes a function defined in the sandbox, with default values rendered as reprs.
+fn describe_function(
+    text: &mut String,
+    function: &Function,
+    defaults: &[Value],
+    heap: &Heap<impl ResourceTracker>,
+    interns: &Interns,
+) {
+    let name = interns.get_str(function.name.name_id);
+    let mut guard = DepthGuard::default();
+    let defaults: Vec<String> = defaults
+        .iter()
+        .map(|value| value.py_repr(heap, &mut guard, interns).into_owned())
+        .collect();
+    let params = function.signature.params_source(&defaults, interns);
+    let prefix = if function.is_async { "async " } else { "" };
+    writeln!(text, "Help on function {name}:\n\n{prefix}{name}({params})").unwrap();
+    if let Some(doc) = function.doc {
+        write_doc(text, &clean_doc(interns.get_str(doc)));
+    }
+}
+
+/// Describes a dataclass by its fields, like the signature of its generated `__init__`.
+fn describe_dataclass(text: &mut String, dc: &Dataclass, interns: &Interns) {
+    let name = dc.name(interns);
+    let fields = dc.field_names().join(", ");
+    writeln!(text, "Help on dataclass {name}:\n\nclass {name}({fields})").unwrap();
+    if dc.is_frozen() {
+        write_doc(text, "Frozen: fields can't be assigned.");
+    }
+}
+
+/// Describes a value without its own documentation by its type, as CPython does.
+fn describe_instance(text: &mut String, value: &Value, heap: &Heap<impl ResourceTracker>) {
+    let t = value.py_type(heap);
+    writeln!(text, "Help on {t} object:\n\nclass {t}").unwrap();
+}
+
+/// Appends `doc` indented by four spaces, as pydoc lays out docstrings.
+fn write_doc(text: &mut String, doc: &str) {
+    for line in doc.lines() {
+        if line.is_empty() {
+            text.push('\n');
+        } else {
+            writeln!(text, "    {line}").unwrap();
+        }
+    }
+}
+
+/// Strips a docstring's indentation and surrounding blank lines, like `inspect.cleandoc`.
+///
+/// The first line is stripped on its own, since it follows the opening quotes; the common
+/// indentation of the remaining lines is removed.
+fn clean_doc(doc: &str) -> String {
+    let mut lines = doc.lines();
+    let first = lines.next().unwrap_or_default().trim();
+    let rest: Vec<&str> = lines.map(str::trim_end).collect();
+    let indent = rest
+        .iter()
+        .filter(|line| !line.is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    let mut cleaned: Vec<&str> = std::iter::once(first)
+        .chain(rest.iter().map(|line| line.get(indent..).unwrap_or_default()))
+        .collect();
+    while cleaned.last().is_some_and(|line| line.is_empty()) {
+        cleaned.pop();
+    }
+    let start = cleaned
+        .iter()
+        .position(|line| !line.is_empty())
+        .unwrap_or(cleaned.len());
+    cleaned[start..].join("\n")
+}
+
+/// Returns the signature and a one-line description of a builtin function.
+fn builtin_function_doc(func: BuiltinsFunctions) -> (&'static str, &'static str) {
+    match func {
+        BuiltinsFunctions::Abs => ("abs(x, /)", "Return the absolute value of the argument."),
+        BuiltinsFunctions::All => (
+            "all(iterable, /)",
+            "Return True if bool(x) is True for all values x in the iterable.",
+        ),
+        BuiltinsFunctions::Any => (
+            "any(iterable, /)",
+            "Return True if bool(x) is True for any x in the iterable.",
+        ),
+        BuiltinsFunctions::Bin => ("bin(number, /)", "Return the binary representation of an integer."),
+        BuiltinsFunctions::Callable => ("callable(obj, /)", "Return whether the object is callable."),
+        BuiltinsFunctions::Chr => (
+            "chr(i, /)",
+            "Return a string of one character with the given code point.",
+        ),
+        BuiltinsFunctions::Dir => ("dir()", "Return the sorted names in the current scope."),
+        BuiltinsFunctions::Divmod => ("divmod(x, y, /)", "Return the tuple (x//y, x%y)."),
+        BuiltinsFunctions::Enumerate => (
+            "enumerate(iterable, start=0)",
+            "Return an iterator of (index, value) pairs.",
+        ),
+        BuiltinsFunctions::Format => (
+            "format(value, format_spec='', /)",
+            "Return value formatted by format_spec.",
+        ),
+        BuiltinsFunctions::Globals => ("globals()", "Return a dict of the module's global variables."),
+        BuiltinsFunctions::Hash => ("hash(obj, /)", "Return the hash value of the object."),
+        BuiltinsFunctions::Help => (
+            "help(obj=None, /)",
+            "Print the signature and docstring of a function, type or value.",
+        ),
+        BuiltinsFunctions::Hex => ("hex(number, /)", "Return the hexadecimal representation of an integer."),
+        BuiltinsFunctions::Id => ("id(obj, /)", "Return the identity of an object."),
+        BuiltinsFunctions::Input => ("input(prompt='', /)", "Read a string from the host."),
+        BuiltinsFunctions::Isinstance => (
+            "isinstance(obj, class_or_tuple, /)",
+            "Return whether obj is an instance of a class or of a subclass thereof.",
+        ),
+        BuiltinsFunctions::Issubclass => (
+            "issubclass(cls, class_or_tuple, /)",
+            "Return whether cls is derived from another class or is the same class.",
+        ),
+        BuiltinsFunctions::Len => ("len(obj, /)", "Return the number of items in a container."),
+        BuiltinsFunctions::Locals => ("locals()", "Return a dict of the current scope's local variables."),
+        BuiltinsFunctions::Map => (
+            "map(function, iterable, /, *iterables)",
+            "Return an iterator applying function to the items of the iterables.",
+        ),
+        BuiltinsFunctions::Max => (
+            "max(iterable, *, default, key=None)\nmax(arg1, arg2, *args, key=None)",
+            "Return the largest item.",
+        ),
+        BuiltinsFunctions::Min => (
+            "min(iterable, *, default, key=None)\nmin(arg1, arg2, *args, key=None)",
+            "Return the smallest item.",
+        ),
+        BuiltinsFunctions::MontyYield => (
+            "monty_yield(value, /)",
+            "Stream a partial result to the host and continue with its reply.",
+        ),
+        BuiltinsFunctions::Next => ("next(iterator, default, /)", "Return the next item from the iterator."),
+        BuiltinsFunctions::Oct => ("oct(number, /)", "Return the octal representation of an integer."),
+        BuiltinsFunctions::Ord => ("ord(c, /)", "Return the Unicode code point for a one-character string."),
+        BuiltinsFunctions::Pow => ("pow(base, exp, mod=None)", "Return base**exp, modulo mod if given."),
+        BuiltinsFunctions::Print => (
+            "print(*args, sep=' ', end='\\n', file=None, flush=False)",
+            "Print the values, separated by sep and followed by end.",
+        ),
+        BuiltinsFunctions::Repr => (
+            "repr(obj, /)",
+            "Return the canonical string representation of the object.",
+        ),
+        BuiltinsFunctions::Reversed => (
+            "reversed(sequence, /)",
+            "Return a reverse iterator over the values of the sequence.",
+        ),
+        BuiltinsFunctions::Round => (
+            "round(number, ndigits=None)",
+            "Round a number to a given precision in decimal digits.",
+        ),
+        BuiltinsFunctions::Sorted => (
+            "sorted(iterable, /, *, key=None, reverse=False)",
+            "Return a new list containing all items from the iterable in ascending order.",
+        ),
+        BuiltinsFunctions::Sum => (
+            "sum(iterable, /, start=0)",
+            "Return the sum of a 'start' value plus an iterable of numbers.",
+        ),
+        BuiltinsFunctions::Type => ("type(object, /)", "Return the object's type."),
+        BuiltinsFunctions::Zip => (
+            "zip(*iterables, strict=False)",
+            "Return an iterator of tuples pairing up items of the iterables.",
+        ),
+    }
+}
@@ -0,0 +1,33 @@
+//! Implementation of the input() builtin function.
+
+use crate::{
+    args::ArgValues,
+    defer_drop,
+    exception_private::RunResult,
+    heap::Heap,
+    intern::Interns,
+    os::OsFunction,
+    resource::{DepthGuard, ResourceTracker},
+    types::{AttrCallResult, PyTrait, str::allocate_string},
+};
+
+/// Implementation of the input() builtin function.
+///
+/// The sandbox can't read from stdin, so this yields `OsFunction::Input` to the host
+/// with the prompt converted to a string (`str(prompt)`) as its only argument, or no
+/// arguments if no prompt was given. The host shows the prompt, reads a line and
+/// resumes with it as a `str` without the trailing newline, or raises `EOFError`.
+pub fn builtin_input(
+    heap: &mut Heap<impl ResourceTracker>,
+    args: ArgValues,
+    interns: &Interns,
+) -> RunResult<AttrCallResult> {
+    let Some(prompt) = args.get_zero_one_arg("input", heap)? else {
+        return Ok(AttrCallResult::OsCall(OsFunction::Input, ArgValues::Empty));
+    };
+    defer_drop!(prompt, heap);
+    let mut guard = DepthGuard::default();
+    let prompt = prompt.py_str(heap, &mut guard, interns).into_owned();
+    let prompt = allocate_string(prompt, heap)?;
+    Ok(AttrCallResult::OsCall(OsFunction::Input, ArgValues::One(prompt)))
+}
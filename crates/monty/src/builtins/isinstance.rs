@@ -1,4 +1,4 @@
-//! Implementation of the isinstance() builtin function.
+//! Implementation of the isinstance() and issubclass() builtin functions.
 
 use super::Builtins;
 use crate::{
@@ -13,7 +13,8 @@ use crate::{
 
 /// Implementation of the isinstance() builtin function.
 ///
-/// Checks if an object is an instance of a class or a tuple of classes.
+/// Checks if an object is an instance of a class, a tuple of classes, or a union
+/// such as `int | None`.
 pub fn builtin_isinstance(heap: &mut Heap<impl ResourceTracker>, args: ArgValues) -> RunResult<Value> {
     let (obj, classinfo) = args.get_two_args("isinstance", heap)?;
     defer_drop!(obj, heap);
@@ -21,46 +22,78 @@ pub fn builtin_isinstance(heap: &mut Heap<impl ResourceTracker>, args: ArgValues
 
     let obj_type = obj.py_type(heap);
 
-    match isinstance_check(obj_type, classinfo, heap) {
+    match classinfo_check(obj_type, classinfo, heap) {
         Ok(result) => Ok(Value::Bool(result)),
         Err(()) => Err(ExcType::isinstance_arg2_error()),
     }
 }
 
-/// Recursively checks if obj_type matches classinfo for isinstance().
+/// Implementation of the issubclass() builtin function.
+///
+/// Checks if a class is a subclass of a class, a tuple of classes, or a union.
+/// Only builtin types and exception types are classes here; dataclasses and other
+/// host-defined types have no class object to pass as the first argument.
+pub fn builtin_issubclass(heap: &mut Heap<impl ResourceTracker>, args: ArgValues) -> RunResult<Value> {
+    let (cls, classinfo) = args.get_two_args("issubclass", heap)?;
+    defer_drop!(cls, heap);
+    defer_drop!(classinfo, heap);
+
+    let cls_type = match cls {
+        Value::Builtin(Builtins::Type(t)) => *t,
+        Value::Builtin(Builtins::ExcType(exc_type)) => Type::Exception(*exc_type),
+        _ => return Err(ExcType::issubclass_arg1_error()),
+    };
+
+    match classinfo_check(cls_type, classinfo, heap) {
+        Ok(result) => Ok(Value::Bool(result)),
+        Err(()) => Err(ExcType::issubclass_arg2_error()),
+    }
+}
+
+/// Recursively checks if `ty` matches classinfo, shared by isinstance() and issubclass().
 ///
 /// Returns `Ok(true)` if the type matches, `Ok(false)` if it doesn't,
-/// or `Err(())` if classinfo is invalid (not a type or tuple of types).
+/// or `Err(())` if classinfo is invalid (not a type, tuple of types, or union).
 ///
 /// Supports:
 /// - Single types: `isinstance(x, int)`
 /// - Exception types: `isinstance(err, ValueError)`
 /// - Exception hierarchy: `isinstance(err, LookupError)` for KeyError/IndexError
 /// - Nested tuples: `isinstance(x, (int, (str, bytes)))`
-fn isinstance_check(obj_type: Type, classinfo: &Value, heap: &Heap<impl ResourceTracker>) -> Result<bool, ()> {
+/// - Unions: `isinstance(x, int | None)`
+fn classinfo_check(ty: Type, classinfo: &Value, heap: &Heap<impl ResourceTracker>) -> Result<bool, ()> {
     match classinfo {
         // Single type: isinstance(x, int)
-        Value::Builtin(Builtins::Type(t)) => Ok(obj_type.is_instance_of(*t)),
+        Value::Builtin(Builtins::Type(t)) => Ok(is_subclass(ty, *t)),
 
         // Exception type: isinstance(err, ValueError) or isinstance(err, LookupError)
-        Value::Builtin(Builtins::ExcType(handler_type)) => {
-            // Check exception hierarchy using is_subclass_of
-            Ok(matches!(obj_type, Type::Exception(exc_type) if exc_type.is_subclass_of(*handler_type)))
-        }
+        Value::Builtin(Builtins::ExcType(handler_type)) => Ok(is_subclass(ty, Type::Exception(*handler_type))),
 
-        // Tuple of types (possibly nested): isinstance(x, (int, (str, bytes)))
-        Value::Ref(id) => {
-            if let HeapData::Tuple(tuple) = heap.get(*id) {
+        Value::Ref(id) => match heap.get(*id) {
+            // Tuple of types (possibly nested): isinstance(x, (int, (str, bytes)))
+            HeapData::Tuple(tuple) => {
                 for v in tuple.as_slice() {
-                    if isinstance_check(obj_type, v, heap)? {
+                    if classinfo_check(ty, v, heap)? {
                         return Ok(true);
                     }
                 }
                 Ok(false)
-            } else {
-                Err(()) // Not a tuple - invalid
             }
-        }
+            // Union of types: isinstance(x, int | str)
+            HeapData::UnionType(union) => Ok(union.members().iter().any(|member| is_subclass(ty, *member))),
+            _ => Err(()), // Not a tuple or union - invalid
+        },
         _ => Err(()), // Invalid classinfo
     }
 }
+
+/// Checks whether `ty` is `class` or one of its subclasses.
+///
+/// Exception types follow the exception hierarchy, other types use `Type::is_instance_of`.
+fn is_subclass(ty: Type, class: Type) -> bool {
+    match (ty, class) {
+        (Type::Exception(exc_type), Type::Exception(handler_type)) => exc_type.is_subclass_of(handler_type),
+        (Type::Exception(_), _) | (_, Type::Exception(_)) => false,
+        _ => ty.is_instance_of(class),
+    }
+}
@@ -2,11 +2,12 @@
 
 use std::cmp::Ordering;
 
+use super::KeyCaller;
 use crate::{
     args::ArgValues,
-    defer_drop_mut,
+    defer_drop, defer_drop_mut,
     exception_private::{ExcType, RunError, RunResult, SimpleException},
-    heap::{Heap, HeapGuard},
+    heap::{DropWithHeap, Heap, HeapGuard},
     intern::Interns,
     resource::{DepthGuard, ResourceTracker},
     types::{MontyIter, PyTrait},
@@ -17,104 +18,149 @@ use crate::{
 ///
 /// Returns the smallest item in an iterable or the smallest of two or more arguments.
 /// Supports two forms:
-/// - `min(iterable)` - returns smallest item from iterable
-/// - `min(arg1, arg2, ...)` - returns smallest of the arguments
-pub fn builtin_min(heap: &mut Heap<impl ResourceTracker>, args: ArgValues, interns: &Interns) -> RunResult<Value> {
-    builtin_min_max(heap, args, interns, true)
+/// - `min(iterable, *, key=None, default=...)` - returns smallest item from iterable
+/// - `min(arg1, arg2, *args, key=None)` - returns smallest of the arguments
+pub fn builtin_min(caller: &mut impl KeyCaller, args: ArgValues, interns: &Interns) -> RunResult<Value> {
+    builtin_min_max(caller, args, interns, true)
 }
 
 /// Implementation of the max() builtin function.
 ///
 /// Returns the largest item in an iterable or the largest of two or more arguments.
 /// Supports two forms:
-/// - `max(iterable)` - returns largest item from iterable
-/// - `max(arg1, arg2, ...)` - returns largest of the arguments
-pub fn builtin_max(heap: &mut Heap<impl ResourceTracker>, args: ArgValues, interns: &Interns) -> RunResult<Value> {
-    builtin_min_max(heap, args, interns, false)
+/// - `max(iterable, *, key=None, default=...)` - returns largest item from iterable
+/// - `max(arg1, arg2, *args, key=None)` - returns largest of the arguments
+pub fn builtin_max(caller: &mut impl KeyCaller, args: ArgValues, interns: &Interns) -> RunResult<Value> {
+    builtin_min_max(caller, args, interns, false)
 }
 
 /// Shared implementation for min() and max().
 ///
 /// When `is_min` is true, returns the minimum; otherwise returns the maximum.
-fn builtin_min_max(
-    heap: &mut Heap<impl ResourceTracker>,
-    args: ArgValues,
-    interns: &Interns,
-    is_min: bool,
-) -> RunResult<Value> {
+/// Key functions are called through `caller`, which lets the VM run user-defined functions.
+fn builtin_min_max<C: KeyCaller>(caller: &mut C, args: ArgValues, interns: &Interns, is_min: bool) -> RunResult<Value> {
     let func_name = if is_min { "min" } else { "max" };
     let (positional, kwargs) = args.into_parts();
-    defer_drop_mut!(positional, heap);
-
-    // TODO: support kwargs (key, default)
-    kwargs.not_supported_yet(func_name, heap)?;
+    defer_drop_mut!(positional, caller);
+
+    let heap = caller.heap_mut();
+    let (key_arg, default) = kwargs.extract_two(func_name, "key", "default", heap, interns)?;
+    // key=None means compare the items themselves
+    let key_fn = match key_arg {
+        Some(v) if matches!(v, Value::None) => {
+            v.drop_with_heap(heap);
+            None
+        }
+        other => other,
+    };
+    defer_drop!(key_fn, caller);
+    let mut default_guard = HeapGuard::new(default, caller);
+    let (default, caller) = default_guard.as_parts_mut();
 
-    let Some(first_arg) = positional.next() else {
-        return Err(SimpleException::new_msg(
+    match positional.len() {
+        0 => Err(SimpleException::new_msg(
             ExcType::TypeError,
             format!("{func_name}() expected at least 1 argument, got 0"),
         )
-        .into());
-    };
-
-    // decide what to do based on remaining arguments
-    if positional.len() == 0 {
+        .into()),
         // Single argument: iterate over it
-        let iter = MontyIter::new(first_arg, heap, interns)?;
-        defer_drop_mut!(iter, heap);
-
-        let Some(result) = iter.for_next(heap, interns)? else {
-            return Err(SimpleException::new_msg(
-                ExcType::ValueError,
-                format!("{func_name}() iterable argument is empty"),
-            )
-            .into());
-        };
-
-        let mut result_guard = HeapGuard::new(result, heap);
-        let (result, heap) = result_guard.as_parts_mut();
-        let mut guard = DepthGuard::default();
-
-        while let Some(item) = iter.for_next(heap, interns)? {
-            defer_drop_mut!(item, heap);
-
-            let Some(ordering) = result.py_cmp(item, heap, &mut guard, interns)? else {
-                return Err(ord_not_supported(result, item, heap));
-            };
-
-            if (is_min && ordering == Ordering::Greater) || (!is_min && ordering == Ordering::Less) {
-                std::mem::swap(result, item);
+        1 => {
+            let iterable = positional.next().expect("checked length above");
+            let iter = MontyIter::new(iterable, caller.heap_mut(), interns)?;
+            defer_drop_mut!(iter, caller);
+
+            let next_item = |caller: &mut C| iter.for_next(caller.heap_mut(), interns);
+            match select_extreme(caller, next_item, key_fn.as_ref(), interns, is_min)? {
+                Some(result) => Ok(result),
+                None => default.take().ok_or_else(|| {
+                    SimpleException::new_msg(ExcType::ValueError, format!("{func_name}() iterable argument is empty"))
+                        .into()
+                }),
             }
         }
-
-        Ok(result_guard.into_inner())
-    } else {
         // Multiple arguments: compare them directly
-        let mut result_guard = HeapGuard::new(first_arg, heap);
-        let (result, heap) = result_guard.as_parts_mut();
-        let mut guard = DepthGuard::default();
-
-        for item in positional {
-            defer_drop_mut!(item, heap);
+        _ => {
+            if default.is_some() {
+                return Err(ExcType::type_error(format!(
+                    "Cannot specify a default for {func_name}() with multiple positional arguments"
+                )));
+            }
+            let next_item = |_: &mut C| Ok(positional.next());
+            let result = select_extreme(caller, next_item, key_fn.as_ref(), interns, is_min)?;
+            Ok(result.expect("at least two positional arguments"))
+        }
+    }
+}
 
-            let Some(ordering) = result.py_cmp(item, heap, &mut guard, interns)? else {
-                return Err(ord_not_supported(result, item, heap));
-            };
+/// Returns the smallest (`is_min`) or largest item produced by `next_item`.
+///
+/// Returns `Ok(None)` when there are no items. When `key_fn` is given, items are compared
+/// by `key_fn(item)` and each item's key is computed exactly once. Ties keep the first
+/// item seen, matching CPython.
+fn select_extreme<C: KeyCaller>(
+    caller: &mut C,
+    mut next_item: impl FnMut(&mut C) -> RunResult<Option<Value>>,
+    key_fn: Option<&Value>,
+    interns: &Interns,
+    is_min: bool,
+) -> RunResult<Option<Value>> {
+    let Some(first) = next_item(caller)? else {
+        return Ok(None);
+    };
+    let mut best_guard = HeapGuard::new(first, caller);
+    let (best, caller) = best_guard.as_parts_mut();
+    let best_key = item_key(caller, key_fn, best, interns)?;
+    let mut best_key_guard = HeapGuard::new(best_key, caller);
+    let (best_key, caller) = best_key_guard.as_parts_mut();
+    let mut guard = DepthGuard::default();
+
+    while let Some(item) = next_item(caller)? {
+        defer_drop_mut!(item, caller);
+        let item_key = item_key(caller, key_fn, item, interns)?;
+        defer_drop_mut!(item_key, caller);
+
+        let heap = caller.heap_mut();
+        let lhs = item_key.as_ref().unwrap_or(item);
+        let rhs = best_key.as_ref().unwrap_or(best);
+        let Some(ordering) = lhs.py_cmp(rhs, heap, &mut guard, interns)? else {
+            return Err(ord_not_supported(lhs, rhs, heap, is_min));
+        };
 
-            if (is_min && ordering == Ordering::Greater) || (!is_min && ordering == Ordering::Less) {
-                std::mem::swap(result, item);
-            }
+        if (is_min && ordering == Ordering::Less) || (!is_min && ordering == Ordering::Greater) {
+            std::mem::swap(best, item);
+            std::mem::swap(best_key, item_key);
         }
+    }
+
+    drop(best_key_guard);
+    Ok(Some(best_guard.into_inner()))
+}
 
-        Ok(result_guard.into_inner())
+/// Computes `key_fn(item)` if a key function was given.
+fn item_key(
+    caller: &mut impl KeyCaller,
+    key_fn: Option<&Value>,
+    item: &Value,
+    interns: &Interns,
+) -> RunResult<Option<Value>> {
+    match key_fn {
+        Some(key_fn) => {
+            let elem = item.clone_with_heap(caller.heap_mut());
+            caller.call_key(key_fn, elem, interns).map(Some)
+        }
+        None => Ok(None),
     }
 }
 
+/// Creates the TypeError for items that can't be ordered.
+///
+/// CPython evaluates `item < best` for min() and `item > best` for max().
 #[cold]
-fn ord_not_supported(left: &Value, right: &Value, heap: &Heap<impl ResourceTracker>) -> RunError {
+fn ord_not_supported(left: &Value, right: &Value, heap: &Heap<impl ResourceTracker>, is_min: bool) -> RunError {
+    let op = if is_min { '<' } else { '>' };
     let left_type = left.py_type(heap);
     let right_type = right.py_type(heap);
     ExcType::type_error(format!(
-        "'<' not supported between instances of '{left_type}' and '{right_type}'"
+        "'{op}' not supported between instances of '{left_type}' and '{right_type}'"
     ))
 }
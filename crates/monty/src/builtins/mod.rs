@@ -7,16 +7,22 @@ mod abs;
 mod all;
 mod any;
 mod bin;
+mod callable;
 mod chr;
+mod dir;
 mod divmod;
 mod enumerate;
+mod format;
 mod hash;
+mod help;
 mod hex;
 mod id;
+mod input;
 mod isinstance;
 mod len;
 mod map;
 mod min_max; // min and max share implementation
+mod monty_yield;
 mod next;
 mod oct;
 mod ord;
@@ -36,12 +42,12 @@ use strum::{Display, EnumString, FromRepr, IntoStaticStr};
 
 use crate::{
     args::ArgValues,
-    exception_private::{ExcType, RunResult},
-    heap::Heap,
+    exception_private::{ExcType, RunError, RunResult},
+    heap::{ContainsHeap, DropWithHeap, Heap},
     intern::Interns,
     io::PrintWriter,
     resource::ResourceTracker,
-    types::Type,
+    types::{AttrCallResult, PyTrait, Type},
     value::Value,
 };
 
@@ -155,41 +161,44 @@ pub enum BuiltinsFunctions {
     // Breakpoint,
     // bytearray - handled by Type enum
     // bytes - handled by Type enum
-    // Callable,
+    Callable,
     Chr,
     // Classmethod,
     // Compile,
     // complex - handled by Type enum
     // Delattr,
     // dict - handled by Type enum
-    // Dir,
+    Dir,
     Divmod,
     Enumerate,
     // Eval,
     // Exec,
     // Filter,
     // float - handled by Type enum
-    // Format,
+    Format,
     // frozenset - handled by Type enum
     // Getattr,
-    // Globals,
+    Globals,
     // Hasattr,
     Hash,
-    // Help,
+    Help,
     Hex,
     Id,
-    // Input,
+    Input,
     // int - handled by Type enum
     Isinstance,
-    // Issubclass,
+    Issubclass,
     // Iter - handled by Type enum
     Len,
     // list - handled by Type enum
-    // Locals,
+    Locals,
     Map,
     Max,
     // memoryview - handled by Type enum
     Min,
+    /// Monty-specific: streams a partial result to the host.
+    #[strum(serialize = "monty_yield")]
+    MontyYield,
     Next,
     // object - handled by Type enum
     Oct,
@@ -218,6 +227,72 @@ pub enum BuiltinsFunctions {
 }
 
 impl BuiltinsFunctions {
+    /// Returns true for builtins that read the calling frame's namespace.
+    ///
+    /// These (`dir`, `globals`, `locals`) are dispatched by the VM rather than through
+    /// [`Self::call`], since they need access to the current frame. `dir(obj)` doesn't,
+    /// so the VM passes it on to [`Self::call`].
+    pub(crate) fn is_introspection(self) -> bool {
+        matches!(self, Self::Dir | Self::Globals | Self::Locals)
+    }
+
+    /// Returns true for builtins that accept a `key=` function.
+    ///
+    /// These (`max`, `min`, `sorted`) are dispatched by the VM through
+    /// [`Self::call_with_keys`] so user-defined key functions can run in a nested frame.
+    pub(crate) fn takes_key_function(self) -> bool {
+        matches!(self, Self::Max | Self::Min | Self::Sorted)
+    }
+
+    /// Returns true for builtins that suspend execution to get their result from the host.
+    ///
+    /// These (`input`, `monty_yield`) are dispatched by the VM through [`Self::call_host`],
+    /// since [`Self::call`] can only return a value.
+    pub(crate) fn yields_to_host(self) -> bool {
+        matches!(self, Self::Input | Self::MontyYield)
+    }
+
+    /// Executes a builtin that gets its result from the host, returning what to yield to it.
+    ///
+    /// Only valid for builtins where [`Self::yields_to_host`] is true.
+    pub(crate) fn call_host(
+        self,
+        heap: &mut Heap<impl ResourceTracker>,
+        args: ArgValues,
+        interns: &Interns,
+    ) -> RunResult<AttrCallResult> {
+        match self {
+            Self::Input => input::builtin_input(heap, args, interns),
+            Self::MontyYield => monty_yield::builtin_monty_yield(heap, args),
+            _ => {
+                args.drop_with_heap(heap);
+                Err(RunError::internal("call_host: builtin does not yield to the host"))
+            }
+        }
+    }
+
+    /// Executes a builtin that accepts a `key=` function, calling keys via `caller`.
+    ///
+    /// Only valid for builtins where [`Self::takes_key_function`] is true.
+    pub(crate) fn call_with_keys(
+        self,
+        caller: &mut impl KeyCaller,
+        args: ArgValues,
+        interns: &Interns,
+    ) -> RunResult<Value> {
+        match self {
+            Self::Max => min_max::builtin_max(caller, args, interns),
+            Self::Min => min_max::builtin_min(caller, args, interns),
+            Self::Sorted => sorted::builtin_sorted(caller, args, interns),
+            _ => {
+                args.drop_with_heap(caller.heap_mut());
+                Err(RunError::internal(
+                    "call_with_keys: builtin does not take a key function",
+                ))
+            }
+        }
+    }
+
     /// Executes the builtin with the provided positional arguments.
     ///
     /// The `interns` parameter provides access to interned string content for py_str and py_repr.
@@ -234,17 +309,29 @@ impl BuiltinsFunctions {
             Self::All => all::builtin_all(heap, args, interns),
             Self::Any => any::builtin_any(heap, args, interns),
             Self::Bin => bin::builtin_bin(heap, args),
+            Self::Callable => callable::builtin_callable(heap, args),
             Self::Chr => chr::builtin_chr(heap, args),
+            Self::Dir => dir::builtin_dir(heap, args, interns),
+            // Calls from Python code go through the VM's `Callable` dispatch, which handles these
+            // (aliases like `f = locals; f()` included). Only builtins calling other builtins,
+            // e.g. `map(locals, ...)`, end up here.
+            Self::Globals | Self::Input | Self::Locals | Self::MontyYield => {
+                args.drop_with_heap(heap);
+                Err(ExcType::not_implemented(format!("{self}() cannot be called indirectly")).into())
+            }
             Self::Divmod => divmod::builtin_divmod(heap, args),
             Self::Enumerate => enumerate::builtin_enumerate(heap, args, interns),
+            Self::Format => format::builtin_format(heap, args, interns),
             Self::Hash => hash::builtin_hash(heap, args, interns),
+            Self::Help => help::builtin_help(heap, args, interns, print_writer),
             Self::Hex => hex::builtin_hex(heap, args),
             Self::Id => id::builtin_id(heap, args),
             Self::Isinstance => isinstance::builtin_isinstance(heap, args),
+            Self::Issubclass => isinstance::builtin_issubclass(heap, args),
             Self::Len => len::builtin_len(heap, args, interns),
             Self::Map => map::builtin_map(heap, args, interns, print_writer),
-            Self::Max => min_max::builtin_max(heap, args, interns),
-            Self::Min => min_max::builtin_min(heap, args, interns),
+            Self::Max => min_max::builtin_max(&mut BuiltinKeyCaller::new(heap, print_writer), args, interns),
+            Self::Min => min_max::builtin_min(&mut BuiltinKeyCaller::new(heap, print_writer), args, interns),
             Self::Next => next::builtin_next(heap, args, interns),
             Self::Oct => oct::builtin_oct(heap, args),
             Self::Ord => ord::builtin_ord(heap, args, interns),
@@ -252,11 +339,61 @@ impl BuiltinsFunctions {
             Self::Print => print::builtin_print(heap, args, interns, print_writer),
             Self::Repr => repr::builtin_repr(heap, args, interns),
             Self::Reversed => reversed::builtin_reversed(heap, args, interns),
-            Self::Round => round::builtin_round(heap, args),
-            Self::Sorted => sorted::builtin_sorted(heap, args, interns),
+            Self::Round => round::builtin_round(heap, args, interns),
+            Self::Sorted => sorted::builtin_sorted(&mut BuiltinKeyCaller::new(heap, print_writer), args, interns),
             Self::Sum => sum::builtin_sum(heap, args, interns),
             Self::Type => type_::builtin_type(heap, args),
             Self::Zip => zip::builtin_zip(heap, args, interns),
         }
     }
 }
+
+/// Calls Python key functions on behalf of builtins such as `sorted()` and `min()`.
+///
+/// The VM implements this by running user-defined functions to completion in a nested
+/// frame. [`BuiltinKeyCaller`] covers code paths without VM access.
+pub(crate) trait KeyCaller: ContainsHeap {
+    /// Calls `key(item)` and returns the result, taking ownership of `item`.
+    fn call_key(&mut self, key: &Value, item: Value, interns: &Interns) -> RunResult<Value>;
+}
+
+/// [`KeyCaller`] for builtins invoked without access to the VM, e.g. `map(sorted, ...)`.
+///
+/// Only builtin functions and types can be called here; user-defined functions need
+/// the VM to run their frames, so they raise a `TypeError`.
+pub(crate) struct BuiltinKeyCaller<'h, 'w, 'p, T: ResourceTracker> {
+    heap: &'h mut Heap<T>,
+    print_writer: &'w mut PrintWriter<'p>,
+}
+
+impl<'h, 'w, 'p, T: ResourceTracker> BuiltinKeyCaller<'h, 'w, 'p, T> {
+    /// Creates a caller borrowing the heap and print writer.
+    pub fn new(heap: &'h mut Heap<T>, print_writer: &'w mut PrintWriter<'p>) -> Self {
+        Self { heap, print_writer }
+    }
+}
+
+impl<T: ResourceTracker> ContainsHeap for BuiltinKeyCaller<'_, '_, '_, T> {
+    type ResourceTracker = T;
+    fn heap_mut(&mut self) -> &mut Heap<T> {
+        self.heap
+    }
+}
+
+impl<T: ResourceTracker> KeyCaller for BuiltinKeyCaller<'_, '_, '_, T> {
+    fn call_key(&mut self, key: &Value, item: Value, interns: &Interns) -> RunResult<Value> {
+        match key {
+            Value::Builtin(builtin) => builtin.call(self.heap, ArgValues::One(item), interns, self.print_writer),
+            Value::DefFunction(_) | Value::ExtFunction(_) | Value::Ref(_) => {
+                item.drop_with_heap(self.heap);
+                Err(ExcType::type_error(
+                    "only builtin key functions are supported when the builtin is not called directly",
+                ))
+            }
+            _ => {
+                item.drop_with_heap(self.heap);
+                Err(ExcType::type_error_not_callable_object(key.py_type(self.heap)))
+            }
+        }
+    }
+}
@@ -0,0 +1,15 @@
+//! Implementation of the monty_yield() builtin function.
+
+use crate::{
+    args::ArgValues, exception_private::RunResult, heap::Heap, resource::ResourceTracker, types::AttrCallResult,
+};
+
+/// Implementation of the monty_yield() builtin function.
+///
+/// Monty-specific: pauses execution and hands `value` to the host as a partial result, so long
+/// computations can stream output before they finish. The host resumes with the value that
+/// `monty_yield()` returns, usually `None`.
+pub fn builtin_monty_yield(heap: &mut Heap<impl ResourceTracker>, args: ArgValues) -> RunResult<AttrCallResult> {
+    let value = args.get_one_arg("monty_yield", heap)?;
+    Ok(AttrCallResult::Yield(value))
+}
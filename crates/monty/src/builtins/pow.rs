@@ -9,7 +9,10 @@ use crate::{
     exception_private::{ExcType, RunResult, SimpleException},
     heap::{Heap, HeapData},
     resource::{ResourceTracker, check_pow_size},
-    types::{LongInt, PyTrait},
+    types::{
+        LongInt, PyTrait,
+        fraction::{FractionOp, fraction_binary_op},
+    },
     value::Value,
 };
 
@@ -133,6 +136,9 @@ fn checked_pow_i64(mut base: i64, mut exp: u32) -> Option<i64> {
 ///
 /// On overflow, promotes to LongInt instead of returning an error.
 fn two_arg_pow(base: &Value, exp: &Value, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
+    if let Some(result) = fraction_binary_op(base, exp, FractionOp::Pow, heap)? {
+        return Ok(result);
+    }
     match (base, exp) {
         (Value::Int(b), Value::Int(e)) => int_pow_int(*b, *e, heap),
         (Value::Int(b), Value::Ref(id)) => {
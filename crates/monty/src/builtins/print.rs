@@ -1,15 +1,17 @@
 //! Implementation of the print() builtin function.
 
+use std::borrow::Cow;
+
 use crate::{
     args::{ArgValues, KwargsValues},
     defer_drop,
     exception_private::{ExcType, RunError, RunResult, SimpleException},
     heap::{Heap, HeapData},
-    intern::Interns,
+    intern::{Interns, StaticStrings},
     io::PrintWriter,
     resource::{DepthGuard, ResourceTracker},
     types::PyTrait,
-    value::Value,
+    value::{Marker, Value},
 };
 
 /// Implementation of the print() builtin function.
@@ -17,9 +19,8 @@ use crate::{
 /// Supports the following keyword arguments:
 /// - `sep`: separator between values (default: " ")
 /// - `end`: string appended after the last value (default: "\n")
-/// - `flush`: whether to flush the stream (accepted but ignored)
-///
-/// The `file` kwarg is not supported.
+/// - `file`: `sys.stdout` or `sys.stderr` (default: `None`, meaning stdout)
+/// - `flush`: if true, calls [`PrintWriter::flush`] once the output is written
 pub fn builtin_print(
     heap: &mut Heap<impl ResourceTracker>,
     args: ArgValues,
@@ -31,7 +32,11 @@ pub fn builtin_print(
     defer_drop!(positional, heap);
 
     // Extract kwargs first
-    let (sep, end) = extract_print_kwargs(kwargs, heap, interns)?;
+    let options = extract_print_kwargs(kwargs, heap, interns)?;
+    let mut stream = PrintStream {
+        print,
+        file: options.file,
+    };
 
     // Print positional args with separator, dropping each value after use
     let mut first = true;
@@ -39,35 +44,78 @@ pub fn builtin_print(
     for value in positional.as_slice() {
         if first {
             first = false;
-        } else if let Some(sep) = &sep {
-            print.stdout_write(sep.as_str().into())?;
+        } else if let Some(sep) = &options.sep {
+            stream.write(sep.as_str().into())?;
         } else {
-            print.stdout_push(' ')?;
+            stream.push(' ')?;
         }
-        print.stdout_write(value.py_str(heap, &mut guard, interns))?;
+        stream.write(value.py_str(heap, &mut guard, interns))?;
     }
 
     // Append end string
-    if let Some(end) = end {
-        print.stdout_write(end.into())?;
+    if let Some(end) = options.end {
+        stream.write(end.into())?;
     } else {
-        print.stdout_push('\n')?;
+        stream.push('\n')?;
+    }
+
+    if options.flush {
+        print.flush()?;
     }
 
     Ok(Value::None)
 }
 
-/// Extracts sep and end kwargs from print() arguments.
+/// The stream selected by print()'s `file` argument.
+#[derive(Debug, Clone, Copy, Default)]
+enum PrintFile {
+    #[default]
+    Stdout,
+    Stderr,
+}
+
+/// Writes print() output to the selected stream of a [`PrintWriter`].
+struct PrintStream<'p, 'w> {
+    print: &'p mut PrintWriter<'w>,
+    file: PrintFile,
+}
+
+impl PrintStream<'_, '_> {
+    fn write(&mut self, output: Cow<'_, str>) -> RunResult<()> {
+        match self.file {
+            PrintFile::Stdout => self.print.stdout_write(output)?,
+            PrintFile::Stderr => self.print.stderr_write(output)?,
+        }
+        Ok(())
+    }
+
+    fn push(&mut self, end: char) -> RunResult<()> {
+        match self.file {
+            PrintFile::Stdout => self.print.stdout_push(end)?,
+            PrintFile::Stderr => self.print.stderr_push(end)?,
+        }
+        Ok(())
+    }
+}
+
+/// Keyword arguments accepted by print().
+#[derive(Debug, Default)]
+struct PrintOptions {
+    sep: Option<String>,
+    end: Option<String>,
+    file: PrintFile,
+    flush: bool,
+}
+
+/// Extracts sep, end, file and flush kwargs from print() arguments.
 ///
 /// Consumes the kwargs, dropping all values after extraction.
-/// Returns (sep, end, error) where error is Some if a kwarg error occurred.
 fn extract_print_kwargs(
     kwargs: KwargsValues,
     heap: &mut Heap<impl ResourceTracker>,
     interns: &Interns,
-) -> RunResult<(Option<String>, Option<String>)> {
-    let mut sep: Option<String> = None;
-    let mut end: Option<String> = None;
+) -> RunResult<PrintOptions> {
+    let mut options = PrintOptions::default();
     let mut error: Option<RunError> = None;
 
     for (key, value) in kwargs {
@@ -89,19 +137,20 @@ fn extract_print_kwargs(
         let key_str = keyword_name.as_str(interns);
         match key_str {
             "sep" => match extract_string_kwarg(value, "sep", heap, interns) {
-                Ok(custom_sep) => sep = custom_sep,
+                Ok(custom_sep) => options.sep = custom_sep,
                 Err(e) => error = Some(e),
             },
             "end" => match extract_string_kwarg(value, "end", heap, interns) {
-                Ok(custom_end) => end = custom_end,
+                Ok(custom_end) => options.end = custom_end,
                 Err(e) => error = Some(e),
             },
-            "flush" => {} // Accepted but ignored (we don't buffer output)
-            "file" => {
-                error = Some(
-                    SimpleException::new_msg(ExcType::TypeError, "print() 'file' argument is not supported").into(),
-                );
-            }
+            "flush" => options.flush = value.py_bool(heap, interns),
+            "file" => match value {
+                Value::None | Value::Marker(Marker(StaticStrings::Stdout)) => options.file = PrintFile::Stdout,
+                Value::Marker(Marker(StaticStrings::Stderr)) => options.file = PrintFile::Stderr,
+                // CPython looks up `write` on the file object
+                _ => error = Some(ExcType::attribute_error(value.py_type(heap), "write")),
+            },
             _ => {
                 error = Some(ExcType::type_error_unexpected_keyword("print", key_str));
             }
@@ -111,7 +160,7 @@ fn extract_print_kwargs(
     if let Some(error) = error {
         Err(error)
     } else {
-        Ok((sep, end))
+        Ok(options)
     }
 }
 
@@ -3,7 +3,7 @@
 use crate::{
     args::ArgValues,
     exception_private::RunResult,
-    heap::{Heap, HeapData},
+    heap::{DropWithHeap, Heap, HeapData},
     intern::Interns,
     resource::ResourceTracker,
     types::{List, MontyIter},
@@ -14,9 +14,18 @@ use crate::{
 ///
 /// Returns a list with elements in reverse order.
 /// Note: In Python this returns an iterator, but we return a list for simplicity.
+/// Ranges are the exception: they're reversed without materializing their elements.
 pub fn builtin_reversed(heap: &mut Heap<impl ResourceTracker>, args: ArgValues, interns: &Interns) -> RunResult<Value> {
     let value = args.get_one_arg("reversed", heap)?;
 
+    if let Value::Ref(id) = &value
+        && let HeapData::Range(range) = heap.get(*id)
+    {
+        let iter = MontyIter::new_reversed_range(range);
+        value.drop_with_heap(heap);
+        return Ok(Value::Ref(heap.allocate(HeapData::Iter(iter))?));
+    }
+
     // Collect all items
     let mut items: Vec<_> = MontyIter::new(value, heap, interns)?.collect(heap, interns)?;
 
@@ -1,15 +1,30 @@
 //! Implementation of the round() builtin function.
 
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{FromPrimitive, Signed, ToPrimitive, Zero};
+
 use crate::{
     args::ArgValues,
     defer_drop,
     exception_private::{ExcType, RunResult, SimpleException},
-    heap::Heap,
+    heap::{Heap, HeapData},
+    intern::Interns,
     resource::ResourceTracker,
-    types::PyTrait,
+    types::{LongInt, PyTrait},
     value::Value,
 };
 
+/// Largest `ndigits` that can change an `f64`; beyond it `round()` returns the value unchanged.
+///
+/// Matches CPython's `NDIGITS_MAX`: `(DBL_MANT_DIG - DBL_MIN_EXP) * log10(2)`.
+const FLOAT_NDIGITS_MAX: i64 = 323;
+
+/// Smallest `ndigits` that can leave a nonzero `f64`; below it `round()` returns a signed zero.
+///
+/// Matches CPython's `NDIGITS_MIN`: `-(DBL_MAX_EXP + 1) * log10(2)`.
+const FLOAT_NDIGITS_MIN: i64 = -308;
+
 pub fn normalize_bool_to_int(value: Value) -> Value {
     match value {
         Value::Bool(b) => Value::Int(i64::from(b)),
@@ -21,76 +36,89 @@ pub fn normalize_bool_to_int(value: Value) -> Value {
 ///
 /// Rounds a number to a given precision in decimal digits.
 /// If ndigits is omitted or None, returns the nearest integer.
-/// Uses banker's rounding (round half to even).
-pub fn builtin_round(heap: &mut Heap<impl ResourceTracker>, args: ArgValues) -> RunResult<Value> {
+/// Uses banker's rounding (round half to even) on the exact value of the number, so
+/// `round(2.675, 2)` gives `2.67` like CPython (2.675 is stored as 2.67499999...).
+pub fn builtin_round(heap: &mut Heap<impl ResourceTracker>, args: ArgValues, interns: &Interns) -> RunResult<Value> {
     let (number, ndigits) = args.get_one_two_args("round", heap)?;
     let number = normalize_bool_to_int(number);
     defer_drop!(number, heap);
     defer_drop!(ndigits, heap);
 
-    // Determine the number of digits (None means round to integer)
-    // Extract digits value before potentially consuming ndigits for error handling
+    // Determine the number of digits (None means round to integer).
+    // Out-of-range ints saturate: they behave the same as any huge ndigits.
     let digits: Option<i64> = match ndigits {
-        Some(Value::None) => None,
+        Some(Value::None) | None => None,
         Some(Value::Int(n)) => Some(*n),
         Some(Value::Bool(b)) => Some(i64::from(*b)),
-        Some(v) => {
-            let type_name = v.py_type(heap);
-            return Err(SimpleException::new_msg(
-                ExcType::TypeError,
-                format!("'{type_name}' object cannot be interpreted as an integer"),
-            )
-            .into());
-        }
-        None => None,
+        Some(v) => match int_operand(v, heap, interns) {
+            Some(n) => Some(if n.is_negative() { i64::MIN } else { i64::MAX }),
+            None => {
+                let type_name = v.py_type(heap);
+                return Err(SimpleException::new_msg(
+                    ExcType::TypeError,
+                    format!("'{type_name}' object cannot be interpreted as an integer"),
+                )
+                .into());
+            }
+        },
     };
 
-    match number {
-        Value::Int(n) => {
-            if let Some(d) = digits {
-                if d >= 0 {
-                    // Positive or zero digits: return the integer unchanged
-                    Ok(Value::Int(*n))
-                } else {
-                    // Negative digits: round to tens, hundreds, etc. using banker's rounding
-                    // -d is positive since d < 0; use try_from to safely convert
-                    let exp = u32::try_from(-d).unwrap_or(u32::MAX);
-                    let factor = 10_i64.saturating_pow(exp);
-                    let rounded_f = bankers_round(*n as f64 / factor as f64);
-                    let rounded = f64_to_i64(rounded_f) * factor;
-                    Ok(Value::Int(rounded))
-                }
-            } else {
-                // No digits specified: return the integer unchanged
-                Ok(Value::Int(*n))
-            }
-        }
-        Value::Float(f) => {
-            if let Some(d) = digits {
-                // Round to `d` decimal places using banker's rounding.
-                Ok(Value::Float(round_float_to_digits(*f, d)))
-            } else {
-                // No digits: round to nearest integer and return int (banker's rounding)
-                if f.is_nan() {
-                    Err(SimpleException::new_msg(ExcType::ValueError, "cannot convert float NaN to integer").into())
-                } else if f.is_infinite() {
-                    Err(
-                        SimpleException::new_msg(ExcType::OverflowError, "cannot convert float infinity to integer")
-                            .into(),
-                    )
-                } else {
-                    Ok(Value::Int(f64_to_i64(bankers_round(*f))))
+    if let Value::Float(f) = number {
+        return if let Some(d) = digits {
+            // Round to `d` decimal places, always returning a float
+            Ok(Value::Float(round_float_to_digits(*f, d)?))
+        } else if f.is_nan() {
+            Err(SimpleException::new_msg(ExcType::ValueError, "cannot convert float NaN to integer").into())
+        } else if f.is_infinite() {
+            Err(SimpleException::new_msg(ExcType::OverflowError, "cannot convert float infinity to integer").into())
+        } else {
+            // No digits: round to nearest integer and return int
+            let rounded = bankers_round(*f);
+            match f64_to_i64_exact(rounded) {
+                Some(n) => Ok(Value::Int(n)),
+                None => {
+                    let bi = BigInt::from_f64(rounded).expect("finite float converts to BigInt");
+                    Ok(LongInt::new(bi).into_value(heap)?)
                 }
             }
+        };
+    }
+
+    if let Value::Ref(id) = number
+        && let HeapData::Fraction(fraction) = heap.get(*id)
+    {
+        return fraction.clone().round(digits, heap);
+    }
+
+    let Some(n) = int_operand(number, heap, interns) else {
+        let type_name = number.py_type(heap);
+        return Err(SimpleException::new_msg(
+            ExcType::TypeError,
+            format!("type {type_name} doesn't define __round__ method"),
+        )
+        .into());
+    };
+    match digits {
+        // Negative digits: round to tens, hundreds, etc. using banker's rounding
+        Some(d) if d < 0 => {
+            let rounded = round_int_to_pow10(&n, false, d.unsigned_abs());
+            Ok(LongInt::new(rounded).into_value(heap)?)
         }
-        _ => {
-            let type_name = number.py_type(heap);
-            Err(SimpleException::new_msg(
-                ExcType::TypeError,
-                format!("type {type_name} doesn't define __round__ method"),
-            )
-            .into())
-        }
+        // No digits or non-negative digits: the integer is returned unchanged
+        _ => Ok(number.clone_with_heap(heap)),
+    }
+}
+
+/// Returns the value of an `int` operand (small, interned or heap long int) as a `BigInt`.
+fn int_operand(value: &Value, heap: &Heap<impl ResourceTracker>, interns: &Interns) -> Option<BigInt> {
+    match value {
+        Value::Int(n) => Some(BigInt::from(*n)),
+        Value::InternLongInt(id) => Some(interns.get_long_int(*id).clone()),
+        Value::Ref(id) => match heap.get(*id) {
+            HeapData::LongInt(li) => Some(li.inner().clone()),
+            _ => None,
+        },
+        _ => None,
     }
 }
 
@@ -108,67 +136,92 @@ fn bankers_round(value: f64) -> f64 {
         floor + 1.0
     } else {
         // Exactly 0.5 - round to even
-        if f64_to_i64(floor) % 2 == 0 { floor } else { floor + 1.0 }
+        if (floor / 2.0).fract() == 0.0 {
+            floor
+        } else {
+            floor + 1.0
+        }
+    }
+}
+
+/// Rounds `n + fraction` to the nearest multiple of `10**exp`, ties to even.
+///
+/// `has_fraction` signals a nonzero fractional part with the same sign as `n` (as left
+/// by truncating a float), which only matters for breaking an exact tie in `n`.
+fn round_int_to_pow10(n: &BigInt, has_fraction: bool, exp: u64) -> BigInt {
+    // 10**exp > 2*|n| + 1 means the result is zero; skip building a huge power of ten
+    let max_digits = n.bits() * 31 / 100 + 2;
+    if exp > max_digits {
+        return BigInt::zero();
     }
+    let exp = u32::try_from(exp).expect("exp bounded by the digit count of n");
+    let pow = BigInt::from(10u32).pow(exp);
+    let (quotient, remainder) = n.abs().div_rem(&pow);
+    let twice = remainder * 2u32;
+    let round_up = match twice.cmp(&pow) {
+        std::cmp::Ordering::Greater => true,
+        std::cmp::Ordering::Equal => has_fraction || quotient.is_odd(),
+        std::cmp::Ordering::Less => false,
+    };
+    let quotient = if round_up { quotient + 1u32 } else { quotient };
+    let magnitude = quotient * pow;
+    if n.is_negative() { -magnitude } else { magnitude }
 }
 
-/// Rounds a finite float to a given number of decimal digits using banker's rounding.
+/// Rounds a float to a given number of decimal digits using banker's rounding.
 ///
 /// This is used for `round(x, ndigits)` where Python always returns a float.
 ///
-/// For large `ndigits` values where scaling by `10**ndigits` would overflow/underflow `f64`,
-/// CPython returns either the original value (large positive `ndigits`) or a signed zero
-/// (large negative `ndigits`). We mirror that behavior and also preserve the sign of `0.0`.
-fn round_float_to_digits(value: f64, digits: i64) -> f64 {
-    if !value.is_finite() {
-        return value;
+/// Rounding is correctly rounded on the exact binary value, like CPython's dtoa-based
+/// implementation: positive `ndigits` go through Rust's exact decimal formatting, and
+/// negative `ndigits` round the exact integer part with `BigInt` arithmetic.
+///
+/// For `ndigits` too large to matter CPython returns the original value, and for `ndigits`
+/// too small it returns a signed zero; we mirror that and also preserve the sign of `0.0`.
+fn round_float_to_digits(value: f64, digits: i64) -> RunResult<f64> {
+    if !value.is_finite() || digits > FLOAT_NDIGITS_MAX {
+        return Ok(value);
+    }
+    if digits < FLOAT_NDIGITS_MIN {
+        return Ok(0.0_f64.copysign(value));
     }
 
     let rounded = if digits >= 0 {
-        let Ok(exp) = i32::try_from(digits) else {
-            return value;
-        };
-        let multiplier = 10_f64.powi(exp);
-        if !multiplier.is_finite() {
-            return value;
-        }
-        let scaled = value * multiplier;
-        if !scaled.is_finite() {
-            return value;
-        }
-        bankers_round(scaled) / multiplier
+        let precision = usize::try_from(digits).expect("digits bounded by FLOAT_NDIGITS_MAX");
+        format!("{value:.precision$}")
+            .parse::<f64>()
+            .expect("formatted float parses back")
     } else {
-        let Ok(exp) = i32::try_from(digits) else {
-            return 0.0_f64.copysign(value);
-        };
-        let multiplier = 10_f64.powi(exp);
-        if multiplier == 0.0 {
-            return 0.0_f64.copysign(value);
+        let truncated = value.trunc();
+        let int_part = BigInt::from_f64(truncated).expect("finite float converts to BigInt");
+        let rounded = round_int_to_pow10(&int_part, value != truncated, digits.unsigned_abs());
+        let rounded = rounded.to_f64().unwrap_or(f64::INFINITY);
+        if rounded.is_infinite() {
+            return Err(
+                SimpleException::new_msg(ExcType::OverflowError, "rounded value too large to represent").into(),
+            );
         }
-        let scaled = value * multiplier;
-        bankers_round(scaled) / multiplier
+        rounded
     };
 
-    if rounded == 0.0 {
+    Ok(if rounded == 0.0 {
         0.0_f64.copysign(value)
     } else {
         rounded
-    }
+    })
 }
 
-/// Converts `f64` to `i64` using saturating float-to-int casting.
-///
-/// Monty uses `i64` for integer values, so float-to-int conversion must pick a
-/// bounded representation:
-/// - Values outside the `i64` range saturate to `i64::MIN`/`i64::MAX`
-/// - `NaN` converts to `0`
-///
-/// This behavior is provided by Rust's `as` casting rules for float-to-int.
-fn f64_to_i64(value: f64) -> i64 {
-    #[expect(
-        clippy::cast_possible_truncation,
-        reason = "intentional truncation; float-to-int casts saturate and map NaN to 0"
-    )]
-    let result = value as i64;
-    result
+/// Converts an integral `f64` to `i64` if it is within range.
+fn f64_to_i64_exact(value: f64) -> Option<i64> {
+    // 2**63 is exactly representable; anything at or above it overflows i64
+    if (-9_223_372_036_854_775_808.0..9_223_372_036_854_775_808.0).contains(&value) {
+        #[expect(
+            clippy::cast_possible_truncation,
+            reason = "value is integral and range-checked to fit in i64"
+        )]
+        let result = value as i64;
+        Some(result)
+    } else {
+        None
+    }
 }
@@ -1,27 +1,33 @@
 //! Implementation of the sorted() builtin function.
 
-use std::cmp::Ordering;
-
+use super::KeyCaller;
 use crate::{
     args::ArgValues,
-    defer_drop_mut,
+    defer_drop, defer_drop_mut,
     exception_private::{ExcType, RunResult, SimpleException},
-    heap::{Heap, HeapData},
+    heap::{DropWithHeap, HeapData},
     intern::Interns,
-    resource::{DepthGuard, ResourceTracker},
-    types::{List, MontyIter, PyTrait},
+    types::{
+        List, MontyIter,
+        list::{sort_options, sort_values},
+    },
     value::Value,
 };
 
 /// Implementation of the sorted() builtin function.
 ///
-/// Returns a new sorted list from the items in an iterable.
-/// Note: Currently does not support key or reverse arguments.
-pub fn builtin_sorted(heap: &mut Heap<impl ResourceTracker>, args: ArgValues, interns: &Interns) -> RunResult<Value> {
+/// Returns a new sorted list from the items in an iterable, supporting the keyword-only
+/// `key` and `reverse` arguments. Key functions are called through `caller`, which lets
+/// the VM run user-defined functions.
+pub fn builtin_sorted(caller: &mut impl KeyCaller, args: ArgValues, interns: &Interns) -> RunResult<Value> {
     let (positional, kwargs) = args.into_parts();
-    defer_drop_mut!(positional, heap);
+    defer_drop_mut!(positional, caller);
 
-    kwargs.not_supported_yet("sorted", heap)?;
+    // CPython's sorted() delegates to list.sort(), so keyword errors name `sort`
+    let heap = caller.heap_mut();
+    let (key_arg, reverse_arg) = kwargs.extract_two("sort", "key", "reverse", heap, interns)?;
+    let (key_fn, reverse) = sort_options(key_arg, reverse_arg, heap, interns);
+    defer_drop!(key_fn, caller);
 
     let positional_len = positional.len();
     if positional_len != 1 {
@@ -33,39 +39,15 @@ pub fn builtin_sorted(heap: &mut Heap<impl ResourceTracker>, args: ArgValues, in
     }
 
     let iterable = positional.next().unwrap();
+    let heap = caller.heap_mut();
     let iter = MontyIter::new(iterable, heap, interns)?;
     let mut items: Vec<_> = iter.collect(heap, interns)?;
 
-    // Sort using insertion sort (simple, stable, works with py_cmp)
-    // For small lists this is fine; for large lists we'd want a better algorithm
-    let mut guard = DepthGuard::default();
-    for i in 1..items.len() {
-        let mut j = i;
-        while j > 0 {
-            // Enforce timeout inside the O(n^2) comparison loop
-            heap.check_time()?;
-            match items[j - 1].py_cmp(&items[j], heap, &mut guard, interns)? {
-                Some(Ordering::Greater) => {
-                    items.swap(j - 1, j);
-                    j -= 1;
-                }
-                Some(_) => break,
-                None => {
-                    let left_type = items[j - 1].py_type(heap);
-                    let right_type = items[j].py_type(heap);
-                    for item in items {
-                        item.drop_with_heap(heap);
-                    }
-                    return Err(SimpleException::new_msg(
-                        ExcType::TypeError,
-                        format!("'<' not supported between instances of '{left_type}' and '{right_type}'"),
-                    )
-                    .into());
-                }
-            }
-        }
+    if let Err(err) = sort_values(&mut items, key_fn.as_ref(), reverse, caller, interns) {
+        items.drop_with_heap(caller.heap_mut());
+        return Err(err);
     }
 
-    let heap_id = heap.allocate(HeapData::List(List::new(items)))?;
+    let heap_id = caller.heap_mut().allocate(HeapData::List(List::new(items)))?;
     Ok(Value::Ref(heap_id))
 }
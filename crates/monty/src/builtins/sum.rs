@@ -7,33 +7,52 @@ use crate::{
     heap::{Heap, HeapGuard},
     intern::Interns,
     resource::ResourceTracker,
-    types::{MontyIter, PyTrait, Type},
+    types::{
+        MontyIter, PyTrait, Type,
+        fraction::{FractionOp, fraction_binary_op},
+    },
     value::Value,
 };
 
 /// Implementation of the sum() builtin function.
 ///
-/// Sums the items of an iterable from left to right with an optional start value.
-/// The default start value is 0. String start values are explicitly rejected
-/// (use `''.join(seq)` instead for string concatenation).
+/// Sums the items of an iterable from left to right, starting from `start` which may be
+/// passed positionally or by keyword and defaults to 0. String and bytes start values
+/// are explicitly rejected (use `''.join(seq)` instead for string concatenation).
+///
+/// While the running total is a float, float items are added with Neumaier's compensated
+/// summation and int/bool items are added directly, as CPython does, so results such as
+/// `sum([0.1] * 10) == 1.0` match exactly.
 pub fn builtin_sum(heap: &mut Heap<impl ResourceTracker>, args: ArgValues, interns: &Interns) -> RunResult<Value> {
-    let (iterable, start) = args.get_one_two_args("sum", heap)?;
-    defer_drop_mut!(start, heap);
+    let (positional, kwargs) = args.into_parts();
+    defer_drop_mut!(positional, heap);
+    let start_kwarg = kwargs.extract_one("sum", "start", heap, interns)?;
+    defer_drop_mut!(start_kwarg, heap);
+
+    let arg_count = positional.len() + usize::from(start_kwarg.is_some());
+    if positional.len() == 0 {
+        return Err(ExcType::type_error_at_least("sum", 1, 0));
+    } else if arg_count > 2 {
+        return Err(ExcType::type_error_at_most("sum", 2, arg_count));
+    }
+    let iterable = positional.next().expect("checked length above");
+    let start = positional.next().or_else(|| start_kwarg.take());
 
     let iter = MontyIter::new(iterable, heap, interns)?;
     defer_drop_mut!(iter, heap);
 
     // Get the start value, defaulting to 0
-    let accumulator = match start.take() {
+    let accumulator = match start {
         Some(v) => {
-            // Reject string start values - Python explicitly forbids this
-            if matches!(v.py_type(heap), Type::Str) {
+            // Reject string and bytes start values - Python explicitly forbids this
+            let hint = match v.py_type(heap) {
+                Type::Str => Some("strings [use ''.join(seq) instead]"),
+                Type::Bytes => Some("bytes [use b''.join(seq) instead]"),
+                _ => None,
+            };
+            if let Some(hint) = hint {
                 v.drop_with_heap(heap);
-                return Err(SimpleException::new_msg(
-                    ExcType::TypeError,
-                    "sum() can't sum strings [use ''.join(seq) instead]",
-                )
-                .into());
+                return Err(SimpleException::new_msg(ExcType::TypeError, format!("sum() can't sum {hint}")).into());
             }
             v
         }
@@ -44,16 +63,39 @@ pub fn builtin_sum(heap: &mut Heap<impl ResourceTracker>, args: ArgValues, inter
     // on any error path it's dropped automatically
     let mut acc_guard = HeapGuard::new(accumulator, heap);
     let (accumulator, heap) = acc_guard.as_parts_mut();
+    // Running compensation for float accumulation, folded in before any non-float add
+    let mut compensation = 0.0;
 
     // Sum all items
     while let Some(item) = iter.for_next(heap, interns)? {
         defer_drop!(item, heap);
 
+        if let Value::Float(total) = accumulator {
+            match item {
+                Value::Float(x) => {
+                    *total = neumaier_add(*total, *x, &mut compensation);
+                    continue;
+                }
+                Value::Int(i) => {
+                    *total += *i as f64;
+                    continue;
+                }
+                Value::Bool(b) => {
+                    *total += f64::from(u8::from(*b));
+                    continue;
+                }
+                _ => *total = apply_compensation(*total, &mut compensation),
+            }
+        }
+
         // Try to add the item to accumulator
         if let Some(new_value) = accumulator.py_add(item, heap, interns)? {
             // Replace the old accumulator with the new value, dropping the old one
             let old = std::mem::replace(accumulator, new_value);
             old.drop_with_heap(heap);
+        } else if let Some(new_value) = fraction_binary_op(accumulator, item, FractionOp::Add, heap)? {
+            let old = std::mem::replace(accumulator, new_value);
+            old.drop_with_heap(heap);
         } else {
             // Types don't support addition
             let acc_type = accumulator.py_type(heap);
@@ -62,5 +104,29 @@ pub fn builtin_sum(heap: &mut Heap<impl ResourceTracker>, args: ArgValues, inter
         }
     }
 
+    if let Value::Float(total) = accumulator {
+        *total = apply_compensation(*total, &mut compensation);
+    }
     Ok(acc_guard.into_inner())
 }
+
+/// Adds `x` to `total`, accumulating the rounding error in `compensation`.
+///
+/// This is the improved Kahan-Babuska algorithm by Arnold Neumaier, as used by CPython's `sum()`.
+fn neumaier_add(total: f64, x: f64, compensation: &mut f64) -> f64 {
+    let t = total + x;
+    if total.abs() >= x.abs() {
+        *compensation += (total - t) + x;
+    } else {
+        *compensation += (x - t) + total;
+    }
+    t
+}
+
+/// Folds the accumulated compensation into `total` and resets it.
+///
+/// Non-finite compensation (from infinite or NaN items) is discarded, matching CPython.
+fn apply_compensation(total: f64, compensation: &mut f64) -> f64 {
+    let c = std::mem::take(compensation);
+    if c != 0.0 && c.is_finite() { total + c } else { total }
+}
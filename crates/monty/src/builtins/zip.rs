@@ -3,11 +3,11 @@
 use crate::{
     args::ArgValues,
     defer_drop_mut,
-    exception_private::RunResult,
-    heap::{Heap, HeapData},
+    exception_private::{ExcType, RunResult},
+    heap::{DropWithHeap, Heap, HeapData, HeapGuard},
     intern::Interns,
     resource::ResourceTracker,
-    types::{List, MontyIter, allocate_tuple, tuple::TupleVec},
+    types::{List, MontyIter, PyTrait, allocate_tuple, tuple::TupleVec},
     value::Value,
 };
 
@@ -15,19 +15,20 @@ use crate::{
 ///
 /// Returns a list of tuples, where the i-th tuple contains the i-th element
 /// from each of the argument iterables. Stops when the shortest iterable is exhausted.
+/// With `strict=True`, raises a `ValueError` instead if the iterables have different lengths.
 /// Note: In Python this returns an iterator, but we return a list for simplicity.
 pub fn builtin_zip(heap: &mut Heap<impl ResourceTracker>, args: ArgValues, interns: &Interns) -> RunResult<Value> {
     let (positional, kwargs) = args.into_parts();
     defer_drop_mut!(positional, heap);
 
-    // TODO: support kwargs (strict)
-    kwargs.not_supported_yet("zip", heap)?;
-
-    if positional.len() == 0 {
-        // zip() with no arguments returns empty list
-        let heap_id = heap.allocate(HeapData::List(List::new(Vec::new())))?;
-        return Ok(Value::Ref(heap_id));
-    }
+    let strict = match kwargs.extract_one("zip", "strict", heap, interns)? {
+        Some(v) => {
+            let strict = v.py_bool(heap, interns);
+            v.drop_with_heap(heap);
+            strict
+        }
+        None => false,
+    };
 
     // Create iterators for each iterable
     let mut iterators: Vec<MontyIter> = Vec::with_capacity(positional.len());
@@ -36,42 +37,80 @@ pub fn builtin_zip(heap: &mut Heap<impl ResourceTracker>, args: ArgValues, inter
             Ok(iter) => iterators.push(iter),
             Err(e) => {
                 // Clean up already-created iterators
-                for iter in iterators {
-                    iter.drop_with_heap(heap);
-                }
+                iterators.drop_with_heap(heap);
                 return Err(e);
             }
         }
     }
+    defer_drop_mut!(iterators, heap);
 
-    let mut result: Vec<Value> = Vec::new();
+    let mut result_guard = HeapGuard::new(Vec::new(), heap);
+    let (result, heap) = result_guard.as_parts_mut();
 
-    // Zip until shortest iterator is exhausted
-    'outer: loop {
-        let mut tuple_items = TupleVec::with_capacity(iterators.len());
+    // zip() with no arguments returns empty list
+    if !iterators.is_empty() {
+        // Zip until shortest iterator is exhausted
+        loop {
+            let mut tuple_items = TupleVec::with_capacity(iterators.len());
+            let mut exhausted = None;
 
-        for iter in &mut iterators {
-            if let Some(item) = iter.for_next(heap, interns)? {
-                tuple_items.push(item);
-            } else {
+            for (index, iter) in iterators.iter_mut().enumerate() {
+                match iter.for_next(heap, interns) {
+                    Ok(Some(item)) => tuple_items.push(item),
+                    Ok(None) => {
+                        exhausted = Some(index);
+                        break;
+                    }
+                    Err(e) => {
+                        for item in tuple_items {
+                            item.drop_with_heap(heap);
+                        }
+                        return Err(e);
+                    }
+                }
+            }
+
+            if let Some(index) = exhausted {
                 // This iterator is exhausted - drop partial tuple items and stop
                 for item in tuple_items {
                     item.drop_with_heap(heap);
                 }
-                break 'outer;
+                if strict {
+                    check_strict_lengths(iterators, index, heap, interns)?;
+                }
+                break;
             }
-        }
 
-        // Create tuple from collected items
-        let tuple_val = allocate_tuple(tuple_items, heap)?;
-        result.push(tuple_val);
-    }
-
-    // Clean up iterators
-    for iter in iterators {
-        iter.drop_with_heap(heap);
+            // Create tuple from collected items
+            let tuple_val = allocate_tuple(tuple_items, heap)?;
+            result.push(tuple_val);
+        }
     }
 
+    let (result, heap) = result_guard.into_parts();
     let heap_id = heap.allocate(HeapData::List(List::new(result)))?;
     Ok(Value::Ref(heap_id))
 }
+
+/// Checks that all iterators ran out together once iterator `exhausted` has no more items.
+///
+/// If `exhausted` isn't the first iterator, it is shorter than the ones before it. Otherwise
+/// each later iterator is advanced once, as in CPython, and the first that still yields an
+/// item is longer than the ones before it.
+fn check_strict_lengths(
+    iterators: &mut [MontyIter],
+    exhausted: usize,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<()> {
+    if exhausted > 0 {
+        return Err(ExcType::value_error_zip_length_mismatch(exhausted, false));
+    }
+    for (index, iter) in iterators.iter_mut().enumerate().skip(1) {
+        if let Some(item) = iter.for_next(heap, interns)? {
+            item.drop_with_heap(heap);
+            return Err(ExcType::value_error_zip_length_mismatch(index, true));
+        }
+    }
+    Ok(())
+}
@@ -60,6 +60,12 @@ pub struct CodeBuilder {
     /// when accessing undefined local variables.
     local_names: Vec<Option<StringId>>,
 
+    /// Closure cell variable names indexed by cells array index.
+    ///
+    /// Populated during compilation to enable proper NameError messages
+    /// when accessing unbound free variables.
+    cell_names: Vec<Option<StringId>>,
+
     /// Local variable slots that are assigned somewhere in this function.
     ///
     /// Used to determine whether to raise `UnboundLocalError` or `NameError`
@@ -145,6 +151,7 @@ impl CodeBuilder {
     ///
     /// Used for MakeFunction: func_id (u16) + defaults_count (u8)
     /// Used for CallAttr: attr_name_id (u16) + arg_count (u8)
+    /// Used for CallAttrExtended: attr_name_id (u16) + flags (u8)
     pub fn emit_u16_u8(&mut self, op: Opcode, operand1: u16, operand2: u8) {
         self.record_location();
         self.bytecode.push(op as u8);
@@ -160,6 +167,10 @@ impl CodeBuilder {
                 // pops obj + args, pushes result: 1 - (1 + arg_count) = -arg_count
                 self.adjust_stack(-i16::from(operand2));
             }
+            Opcode::CallAttrExtended => {
+                // pops obj, args tuple and kwargs dict if flagged (0x01), pushes result
+                self.adjust_stack(-1 - i16::from(operand2 & 0x01));
+            }
             _ => {
                 if let Some(effect) = op.stack_effect() {
                     self.adjust_stack(effect);
@@ -187,6 +198,19 @@ impl CodeBuilder {
         }
     }
 
+    /// Emits an instruction with two u16 operands (little-endian).
+    ///
+    /// Used for LoadGlobalOrBuiltin: slot (u16) + builtin const_idx (u16)
+    pub fn emit_u16_u16(&mut self, op: Opcode, operand1: u16, operand2: u16) {
+        self.record_location();
+        self.bytecode.push(op as u8);
+        self.bytecode.extend_from_slice(&operand1.to_le_bytes());
+        self.bytecode.extend_from_slice(&operand2.to_le_bytes());
+        if let Some(effect) = op.stack_effect() {
+            self.adjust_stack(effect);
+        }
+    }
+
     /// Emits `CallBuiltinFunction` instruction.
     ///
     /// Operands: builtin_id (u8) + arg_count (u8)
@@ -365,6 +389,20 @@ impl CodeBuilder {
         }
     }
 
+    /// Registers a closure variable name for a given cells array index.
+    ///
+    /// Cell indices are a separate index space from namespace slots, so these are
+    /// tracked apart from `local_names`.
+    pub fn register_cell_name(&mut self, cell_index: u16, name: StringId) {
+        let idx = cell_index as usize;
+        if idx >= self.cell_names.len() {
+            self.cell_names.resize(idx + 1, None);
+        }
+        if self.cell_names[idx].is_none() {
+            self.cell_names[idx] = Some(name);
+        }
+    }
+
     /// Registers a local variable slot as "assigned" (vs undefined reference).
     ///
     /// Called during compilation for variables that are assigned somewhere in the function.
@@ -436,6 +474,7 @@ impl CodeBuilder {
         // Convert local_names from Vec<Option<StringId>> to Vec<StringId>,
         // using StringId::default() for slots with no recorded name
         let local_names: Vec<StringId> = self.local_names.into_iter().map(Option::unwrap_or_default).collect();
+        let cell_names: Vec<StringId> = self.cell_names.into_iter().map(Option::unwrap_or_default).collect();
 
         Code::new(
             self.bytecode,
@@ -445,6 +484,7 @@ impl CodeBuilder {
             num_locals,
             self.max_stack_depth,
             local_names,
+            cell_names,
             self.assigned_locals,
         )
     }
@@ -495,6 +535,10 @@ impl CodeBuilder {
             Opcode::ListAppend | Opcode::SetAdd => -1,
             // DictSetItem pops key and value: -2
             Opcode::DictSetItem => -2,
+            // CallFunctionExtended pops callable, args tuple and kwargs dict if flagged (0x01), pushes result
+            Opcode::CallFunctionExtended => -1 - i16::from(operand & 0x01),
+            // FormatValue pops the value and the format spec if flagged (0x04), pushes the string
+            Opcode::FormatValue => -i16::from(operand & 0x04 != 0),
             // Default: use fixed effect if available
             _ => op.stack_effect().unwrap_or(0),
         };
@@ -57,10 +57,17 @@ pub struct Code {
     /// messages when accessing undefined local variables (e.g., "name 'x' is not defined").
     local_names: Vec<StringId>,
 
+    /// Closure cell variable names, indexed by cells array index.
+    ///
+    /// Kept separate from `local_names` because cell indices and namespace slots
+    /// are different index spaces. Used for NameError messages on unbound free variables.
+    cell_names: Vec<StringId>,
+
     /// Local variable slots that are assigned somewhere in this function.
     ///
     /// Used to determine whether to raise `UnboundLocalError` (slot is assigned somewhere
     /// but accessed before assignment) or `NameError` (name doesn't exist in any scope).
+    #[serde(serialize_with = "crate::sorted_serde::serialize_set")]
     assigned_locals: HashSet<u16>,
 }
 
@@ -78,6 +85,7 @@ impl Code {
         num_locals: u16,
         stack_size: u16,
         local_names: Vec<StringId>,
+        cell_names: Vec<StringId>,
         assigned_locals: HashSet<u16>,
     ) -> Self {
         Self {
@@ -88,6 +96,7 @@ impl Code {
             num_locals,
             stack_size,
             local_names,
+            cell_names,
             assigned_locals,
         }
     }
@@ -112,6 +121,14 @@ impl Code {
         self.local_names.get(slot as usize).copied()
     }
 
+    /// Returns the closure variable name for a given cells array index.
+    ///
+    /// Used to generate proper NameError messages when accessing unbound free variables.
+    #[must_use]
+    pub fn cell_name(&self, cell_index: u16) -> Option<StringId> {
+        self.cell_names.get(cell_index as usize).copied()
+    }
+
     /// Returns whether the slot is an assigned local (vs an undefined reference).
     ///
     /// Used to determine whether to raise `UnboundLocalError` (true) or `NameError` (false)
@@ -133,10 +150,10 @@ impl Code {
         // Location entries are in order by bytecode offset.
         // Find the last entry where bytecode_offset <= offset.
         let offset_u32 = u32::try_from(offset).expect("bytecode offset exceeds u32");
-        self.location_table
-            .iter()
-            .rev()
-            .find(|entry| entry.bytecode_offset <= offset_u32)
+        let index = self
+            .location_table
+            .partition_point(|entry| entry.bytecode_offset <= offset_u32);
+        index.checked_sub(1).map(|index| &self.location_table[index])
     }
 
     /// Finds an exception handler for the given bytecode offset.
@@ -21,7 +21,7 @@ use crate::{
     exception_private::ExcType,
     exception_public::{MontyException, StackFrame},
     expressions::{
-        Callable, CmpOperator, Comprehension, Expr, ExprLoc, Identifier, Literal, NameScope, Node, Operator,
+        Callable, CmpOperator, Comprehension, DictItem, Expr, ExprLoc, Identifier, Literal, NameScope, Node, Operator,
         PreparedFunctionDef, PreparedNode, UnpackTarget,
     },
     fstring::{ConversionFlag, FStringPart, FormatSpec, ParsedFormatSpec, encode_format_spec},
@@ -29,6 +29,7 @@ use crate::{
     intern::{Interns, StringId},
     modules::BuiltinModule,
     parse::{CodeRange, ExceptHandler, Try},
+    signature::Signature,
     value::{EitherStr, Value},
 };
 
@@ -74,18 +75,19 @@ pub struct Compiler<'a> {
     /// to convert to the cells array index.
     cell_base: u16,
 
-    /// Stack of finally targets for handling returns inside try-finally.
+    /// Stack of finally targets for handling returns/break/continue inside try-finally.
     ///
-    /// When a return statement is compiled inside a try-finally block, instead
-    /// of immediately returning, we store the return value and jump to the
-    /// finally block. The finally block will then execute the return.
+    /// When one of these statements is compiled inside a try-finally block, instead
+    /// of leaving immediately we jump to a copy of the finally block, which then
+    /// carries on with the original exit.
     finally_targets: Vec<FinallyTarget>,
 
     /// Tracks nesting depth inside exception handlers.
     ///
-    /// When break/continue/return is inside an except handler, we need to
+    /// When break/continue/return leaves an except handler, we need to
     /// clear the current exception (`ClearException`) and pop the exception
     /// value from the stack before jumping to the finally path or loop target.
+    /// The finally copy that runs while an exception propagates counts as a handler too.
     except_handler_depth: usize,
 }
 
@@ -98,6 +100,8 @@ pub struct Compiler<'a> {
 ///   to jump past the loop's else block
 /// - `has_iterator_on_stack`: whether this loop has an iterator on the stack that
 ///   needs to be popped on break (true for `for` loops, false for `while` loops)
+/// - `except_depth_at_entry`: how many except handlers enclose the loop, so break and
+///   continue only clean up the handlers opened inside it
 struct LoopInfo {
     /// Bytecode offset of loop start (for continue).
     start: usize,
@@ -106,34 +110,34 @@ struct LoopInfo {
     /// Whether this loop has an iterator on the stack.
     /// True for `for` loops, false for `while` loops.
     has_iterator_on_stack: bool,
-}
-
-/// A break or continue that needs to go through a finally block.
-///
-/// When break/continue is inside a try-finally, we need to run the finally block
-/// before executing the break/continue. This struct tracks the jump and which
-/// loop it targets.
-struct BreakContinueThruFinally {
-    /// The jump instruction that needs to be patched.
-    jump: JumpLabel,
-    /// The loop depth (index in loop_stack) being targeted.
-    target_loop_depth: usize,
+    /// The except handler depth when the loop started.
+    except_depth_at_entry: usize,
 }
 
 /// Tracks a finally block for handling returns/break/continue inside try-finally.
 ///
 /// When compiling a try-finally, we push a `FinallyTarget` to track jumps
 /// from return/break/continue statements that need to go through the finally block.
+///
+/// Each exit only unwinds what was opened inside the try before jumping here, so
+/// every path enters its finally copy with the stack as it was at try entry (plus
+/// the return value for returns). After the finally copy, the exit is compiled again
+/// in the context around the try, which unwinds the next level.
 struct FinallyTarget {
     /// Jump labels for returns inside the try block that need to go to finally.
     return_jumps: Vec<JumpLabel>,
-    /// Break statements that need to go through this finally block.
-    break_jumps: Vec<BreakContinueThruFinally>,
-    /// Continue statements that need to go through this finally block.
-    continue_jumps: Vec<BreakContinueThruFinally>,
+    /// Jump labels for breaks that need to go through this finally block.
+    ///
+    /// Break and continue always target the innermost loop, so these all target
+    /// the loop enclosing the try.
+    break_jumps: Vec<JumpLabel>,
+    /// Jump labels for continues that need to go through this finally block.
+    continue_jumps: Vec<JumpLabel>,
     /// The loop depth when this finally was entered.
     /// Used to determine if break/continue targets a loop outside this finally.
     loop_depth_at_entry: usize,
+    /// The except handler depth when this finally was entered.
+    except_depth_at_entry: usize,
 }
 
 /// Result of module compilation: the module code and all compiled functions.
@@ -220,14 +224,22 @@ impl<'a> Compiler<'a> {
     ///
     /// The `functions` parameter receives any previously compiled functions, and
     /// any nested functions found in the body will be added to it.
+    ///
+    /// Parameter names from `signature` are registered up front so that every
+    /// parameter slot is named, even if the body never references it.
     fn compile_function_body(
         body: &[PreparedNode],
         interns: &Interns,
         functions: Vec<Function>,
         num_locals: u16,
         cell_base: u16,
+        signature: &Signature,
     ) -> Result<(Code, Vec<Function>), CompileError> {
         let mut compiler = Compiler::new_with_cell_base(interns, functions, cell_base);
+        for (slot, name) in signature.param_names().enumerate() {
+            let slot = u16::try_from(slot).expect("parameter slot exceeds u16");
+            compiler.code.register_local_name(slot, name);
+        }
         compiler.compile_block(body)?;
 
         // Implicit return None if no explicit return
@@ -367,6 +379,14 @@ impl<'a> Compiler<'a> {
                 names,
                 position,
             } => self.compile_import_from(*module_name, names, *position),
+            Node::Delete { targets } => {
+                for target in targets {
+                    // Load and discard first so deleting an unbound name raises NameError/UnboundLocalError
+                    self.compile_name_with_position(target);
+                    self.code.emit(Opcode::Pop);
+                    self.compile_delete(target);
+                }
+            }
             Node::Break { position } => self.compile_break(*position)?,
             Node::Continue { position } => self.compile_continue(*position)?,
             // These are handled during the prepare phase and produce no bytecode
@@ -381,7 +401,8 @@ impl<'a> Compiler<'a> {
     /// 1. Recursively compiling the function body to bytecode
     /// 2. Creating a Function struct with the compiled Code
     /// 3. Adding the Function to the compiler's functions vector
-    /// 4. Emitting bytecode to evaluate defaults and create the function at runtime
+    /// 4. Emitting bytecode to evaluate decorators and defaults and create the function at runtime
+    /// 5. Applying decorators (innermost first) and storing the result to the function name
     fn compile_function_def(&mut self, func_def: &PreparedFunctionDef) -> Result<(), CompileError> {
         let func_pos = func_def.name.position;
 
@@ -405,7 +426,14 @@ impl<'a> Compiler<'a> {
         let cell_base = u16::try_from(func_def.signature.param_count()).expect("function parameter count exceeds u16");
         let namespace_size = u16::try_from(func_def.namespace_size).expect("function namespace size exceeds u16");
         let (body_code, mut functions) =
-            Self::compile_function_body(&func_def.body, self.interns, functions, namespace_size, cell_base)?;
+            Self::compile_function_body(
+                &func_def.body,
+                self.interns,
+                functions,
+                namespace_size,
+                cell_base,
+                &func_def.signature,
+            )?;
 
         // 2. Create the compiled Function and add to the vector
         let func_id = functions.len();
@@ -419,13 +447,17 @@ impl<'a> Compiler<'a> {
             func_def.default_exprs.len(),
             func_def.is_async,
             body_code,
+            Node::docstring(&func_def.body),
         );
         functions.push(function);
 
         // Restore functions to self
         self.functions = functions;
 
-        // 3. Compile and push default values (evaluated at definition time)
+        // 3. Push decorators, then default values (both evaluated at definition time, in this order)
+        for decorator in &func_def.decorators {
+            self.compile_expr(decorator)?;
+        }
         for default_expr in &func_def.default_exprs {
             self.compile_expr(default_expr)?;
         }
@@ -451,7 +483,14 @@ impl<'a> Compiler<'a> {
                 .emit_u16_u8_u8(Opcode::MakeClosure, func_id_u16, defaults_count, cell_count);
         }
 
-        // 5. Store the function object to its name slot
+        // 5. Apply decorators bottom-up: each call consumes the decorator below the
+        // function on the stack and leaves its result in place
+        for decorator in func_def.decorators.iter().rev() {
+            self.code.set_location(decorator.position, None);
+            self.code.emit_u8(Opcode::CallFunction, 1);
+        }
+
+        // Store the function object to its name slot
         self.compile_store(&func_def.name);
 
         Ok(())
@@ -485,7 +524,14 @@ impl<'a> Compiler<'a> {
         let cell_base = u16::try_from(func_def.signature.param_count()).expect("function parameter count exceeds u16");
         let namespace_size = u16::try_from(func_def.namespace_size).expect("function namespace size exceeds u16");
         let (body_code, mut functions) =
-            Self::compile_function_body(&func_def.body, self.interns, functions, namespace_size, cell_base)?;
+            Self::compile_function_body(
+                &func_def.body,
+                self.interns,
+                functions,
+                namespace_size,
+                cell_base,
+                &func_def.signature,
+            )?;
 
         // 2. Create the compiled Function and add to the vector
         let func_id = functions.len();
@@ -499,6 +545,7 @@ impl<'a> Compiler<'a> {
             func_def.default_exprs.len(),
             func_def.is_async,
             body_code,
+            None,
         );
         functions.push(function);
 
@@ -664,6 +711,10 @@ impl<'a> Compiler<'a> {
                 self.code.emit(Opcode::UnaryInvert);
             }
 
+            Expr::List(elements) if has_starred(elements) => {
+                self.compile_starred_elements(elements, Opcode::BuildList, Opcode::ListExtend)?;
+            }
+
             Expr::List(elements) => {
                 for elem in elements {
                     self.compile_expr(elem)?;
@@ -674,6 +725,11 @@ impl<'a> Compiler<'a> {
                 );
             }
 
+            Expr::Tuple(elements) if has_starred(elements) => {
+                self.compile_starred_elements(elements, Opcode::BuildList, Opcode::ListExtend)?;
+                self.code.emit(Opcode::ListToTuple);
+            }
+
             Expr::Tuple(elements) => {
                 for elem in elements {
                     self.compile_expr(elem)?;
@@ -684,15 +740,12 @@ impl<'a> Compiler<'a> {
                 );
             }
 
-            Expr::Dict(pairs) => {
-                for (key, value) in pairs {
-                    self.compile_expr(key)?;
-                    self.compile_expr(value)?;
-                }
-                self.code.emit_u16(
-                    Opcode::BuildDict,
-                    u16::try_from(pairs.len()).expect("pairs count exceeds u16"),
-                );
+            Expr::Dict(items) => {
+                self.compile_dict_items(items)?;
+            }
+
+            Expr::Set(elements) if has_starred(elements) => {
+                self.compile_starred_elements(elements, Opcode::BuildSet, Opcode::SetUpdate)?;
             }
 
             Expr::Set(elements) => {
@@ -770,6 +823,12 @@ impl<'a> Compiler<'a> {
                 self.compile_lambda(func_def)?;
             }
 
+            Expr::Starred(_) => {
+                // The parser only produces Starred directly inside list/tuple/set displays,
+                // which are compiled by `compile_starred_elements`
+                unreachable!("Expr::Starred outside of a collection display")
+            }
+
             Expr::LambdaRaw { .. } => {
                 // LambdaRaw should be converted to Lambda during prepare phase
                 unreachable!("Expr::LambdaRaw should not exist after prepare phase")
@@ -875,11 +934,22 @@ impl<'a> Compiler<'a> {
             NameScope::Global => {
                 self.code.emit_u16(Opcode::LoadGlobal, slot);
             }
+            NameScope::GlobalOrBuiltin => {
+                let builtin: Builtins = self
+                    .interns
+                    .get_str(ident.name_id)
+                    .parse()
+                    .expect("GlobalOrBuiltin names are always builtins");
+                let const_idx = self.code.add_const(Value::Builtin(builtin));
+                self.code.emit_u16_u16(Opcode::LoadGlobalOrBuiltin, slot, const_idx);
+            }
             NameScope::Cell => {
                 // Convert namespace slot to cells array index
                 let cell_index = slot.saturating_sub(self.cell_base);
                 // Register the name for NameError messages (unbound free variable)
-                self.code.register_local_name(cell_index, ident.name_id);
+                // and for namespace introspection (`locals()`)
+                self.code.register_cell_name(cell_index, ident.name_id);
+                self.code.register_local_name(slot, ident.name_id);
                 self.code.emit_u16(Opcode::LoadCell, cell_index);
             }
         }
@@ -903,12 +973,14 @@ impl<'a> Compiler<'a> {
                 self.code.register_local_name(slot, target.name_id);
                 self.code.emit_store_local(slot);
             }
-            NameScope::Global => {
+            NameScope::Global | NameScope::GlobalOrBuiltin => {
                 self.code.emit_u16(Opcode::StoreGlobal, slot);
             }
             NameScope::Cell => {
                 // Convert namespace slot to cells array index
                 let cell_index = slot.saturating_sub(self.cell_base);
+                self.code.register_cell_name(cell_index, target.name_id);
+                self.code.register_local_name(slot, target.name_id);
                 self.code.emit_u16(Opcode::StoreCell, cell_index);
             }
         }
@@ -1075,13 +1147,89 @@ impl<'a> Compiler<'a> {
         Ok(())
     }
 
+    /// Compiles a list, tuple or set display containing `*iterable` elements.
+    ///
+    /// The leading run of plain elements is built with `build`, then each `*iterable`
+    /// is merged in with `extend`. Later runs of plain elements are built into their own
+    /// collection and merged the same way, so evaluation and insertion order match Python.
+    /// Leaves a list (for `BuildList`) or set (for `BuildSet`) on the stack.
+    fn compile_starred_elements(
+        &mut self,
+        elements: &[ExprLoc],
+        build: Opcode,
+        extend: Opcode,
+    ) -> Result<(), CompileError> {
+        let mut built = false;
+        let mut run = 0u16;
+        for elem in elements {
+            if let Expr::Starred(value) = &elem.expr {
+                self.finish_element_run(&mut built, &mut run, build, extend);
+                self.compile_expr(value)?;
+                self.code.set_location(elem.position, None);
+                self.code.emit(extend);
+            } else {
+                self.compile_expr(elem)?;
+                run = run.checked_add(1).expect("elements count exceeds u16");
+            }
+        }
+        self.finish_element_run(&mut built, &mut run, build, extend);
+        Ok(())
+    }
+
+    /// Builds the pending run of plain elements for `compile_starred_elements`.
+    ///
+    /// The first run becomes the target collection (even when empty); later runs are
+    /// only emitted when non-empty and are merged into the target with `extend`.
+    fn finish_element_run(&mut self, built: &mut bool, run: &mut u16, build: Opcode, extend: Opcode) {
+        if !*built {
+            self.code.emit_u16(build, *run);
+            *built = true;
+        } else if *run > 0 {
+            self.code.emit_u16(build, *run);
+            self.code.emit(extend);
+        }
+        *run = 0;
+    }
+
+    /// Compiles a dict display, including `**mapping` unpacking.
+    ///
+    /// Follows the same scheme as `compile_starred_elements`: the leading pairs are built
+    /// with `BuildDict`, and each `**mapping` (or later run of pairs) is merged in with
+    /// `DictUpdate`, so later keys overwrite earlier ones.
+    fn compile_dict_items(&mut self, items: &[DictItem]) -> Result<(), CompileError> {
+        let mut built = false;
+        let mut run = 0u16;
+        for item in items {
+            match item {
+                DictItem::Pair(key, value) => {
+                    self.compile_expr(key)?;
+                    self.compile_expr(value)?;
+                    run = run.checked_add(1).expect("pairs count exceeds u16");
+                }
+                DictItem::Unpack(mapping) => {
+                    self.finish_element_run(&mut built, &mut run, Opcode::BuildDict, Opcode::DictUpdate);
+                    self.compile_expr(mapping)?;
+                    self.code.set_location(mapping.position, None);
+                    self.code.emit(Opcode::DictUpdate);
+                }
+            }
+        }
+        self.finish_element_run(&mut built, &mut run, Opcode::BuildDict, Opcode::DictUpdate);
+        Ok(())
+    }
+
     /// Compiles a ternary conditional expression.
+    ///
+    /// Only one branch runs, so the `orelse` branch starts from the stack depth before `body`
+    /// was compiled, and both branches leave exactly one value on the stack.
     fn compile_if_else_expr(&mut self, test: &ExprLoc, body: &ExprLoc, orelse: &ExprLoc) -> Result<(), CompileError> {
         self.compile_expr(test)?;
         let else_jump = self.code.emit_jump(Opcode::JumpIfFalse);
+        let branch_depth = self.code.stack_depth();
         self.compile_expr(body)?;
         let end_jump = self.code.emit_jump(Opcode::Jump);
         self.code.patch_jump(else_jump);
+        self.code.set_stack_depth(branch_depth);
         self.compile_expr(orelse)?;
         self.code.patch_jump(end_jump);
         Ok(())
@@ -1772,6 +1920,8 @@ impl<'a> Compiler<'a> {
         body: &[PreparedNode],
         or_else: &[PreparedNode],
     ) -> Result<(), CompileError> {
+        let base_depth = self.code.stack_depth();
+
         // Compile iterator expression
         self.compile_expr(iter)?;
         // Convert to iterator
@@ -1785,6 +1935,7 @@ impl<'a> Compiler<'a> {
             start: loop_start,
             break_jumps: Vec::new(),
             has_iterator_on_stack: true,
+            except_depth_at_entry: self.except_handler_depth,
         });
 
         // ForIter: advance iterator or jump to end
@@ -1799,8 +1950,9 @@ impl<'a> Compiler<'a> {
         // Jump back to loop start
         self.code.emit_jump_to(Opcode::Jump, loop_start);
 
-        // End of loop - ForIter jumps here when iterator is exhausted
+        // End of loop - ForIter jumps here when iterator is exhausted, having popped the iterator
         self.code.patch_jump(end_jump);
+        self.code.set_stack_depth(base_depth);
 
         // Pop loop info before compiling else block
         let loop_info = self.loop_stack.pop().expect("loop stack underflow");
@@ -1849,6 +2001,7 @@ impl<'a> Compiler<'a> {
             start: loop_start,
             break_jumps: Vec::new(),
             has_iterator_on_stack: false,
+            except_depth_at_entry: self.except_handler_depth,
         });
 
         self.compile_expr(test)?;
@@ -1877,69 +2030,20 @@ impl<'a> Compiler<'a> {
     /// try-finally, the finally block must run first.
     ///
     /// The bytecode without finally:
-    /// 1. Clean up exception state if inside except handler
+    /// 1. Clean up exception state of handlers opened inside the loop
     /// 2. Pop the iterator if in a `for` loop (still on stack during loop body)
     /// 3. Jump to after the else block
     ///
     /// With finally:
-    /// 1. Clean up exception state if inside except handler
-    /// 2. Pop the iterator if in a `for` loop
-    /// 3. Jump to "finally with break" path (patched when try compilation completes)
-    /// 4. That path runs finally, then jumps to after the else block
+    /// 1. Clean up exception state of handlers opened inside the try
+    /// 2. Jump to "finally with break" path (patched when try compilation completes)
+    /// 3. That path runs finally, then compiles the break again in the context
+    ///    around the try (see `compile_loop_exit`)
     fn compile_break(&mut self, position: CodeRange) -> Result<(), CompileError> {
         if self.loop_stack.is_empty() {
             return Err(CompileError::new("'break' outside loop", position));
         }
-
-        let target_loop_depth = self.loop_stack.len() - 1;
-
-        // If inside except handlers, clean up ALL exception states
-        // Each nested except handler has pushed an exception onto the stack,
-        // so we need to clear/pop each one when breaking out
-        for _ in 0..self.except_handler_depth {
-            self.code.emit(Opcode::ClearException);
-            self.code.emit(Opcode::Pop); // Pop the exception value
-        }
-
-        // Pop the iterator only for `for` loops (has iterator on stack)
-        // `while` loops don't have an iterator to pop
-        if self.loop_stack[target_loop_depth].has_iterator_on_stack {
-            self.code.emit(Opcode::Pop);
-        }
-
-        // Check if we need to go through any finally blocks
-        // We need to run finally if break crosses the try boundary, i.e., if
-        // we're breaking from a loop that existed before the try started.
-        if let Some(finally_target) = self.finally_targets.last_mut()
-            && target_loop_depth < finally_target.loop_depth_at_entry
-        {
-            // Breaking from a loop that's outside (or at the start of) this try-finally,
-            // so finally must run before the break
-            let jump = self.code.emit_jump(Opcode::Jump);
-            finally_target.break_jumps.push(BreakContinueThruFinally {
-                jump,
-                target_loop_depth,
-            });
-            // Set stack depth for unreachable cleanup code (see comment below)
-            if self.except_handler_depth > 0 {
-                self.code
-                    .set_stack_depth(u16::try_from(self.except_handler_depth).unwrap_or(u16::MAX));
-            }
-            return Ok(());
-        }
-
-        // No finally to go through, jump directly to loop end
-        let jump = self.code.emit_jump(Opcode::Jump);
-        self.loop_stack[target_loop_depth].break_jumps.push(jump);
-
-        // The code following this break is unreachable at runtime, but the compiler
-        // will still emit cleanup code for each enclosing except handler (ClearException + Pop).
-        // Set stack depth so those unreachable pops don't cause negative stack tracking.
-        if self.except_handler_depth > 0 {
-            self.code
-                .set_stack_depth(u16::try_from(self.except_handler_depth).unwrap_or(u16::MAX));
-        }
-
+        self.compile_loop_exit(true);
         Ok(())
     }
 
@@ -1952,98 +2056,65 @@ impl<'a> Compiler<'a> {
         if self.loop_stack.is_empty() {
             return Err(CompileError::new("'continue' not properly in loop", position));
         }
-
-        let target_loop_depth = self.loop_stack.len() - 1;
-
-        // If inside except handlers, clean up ALL exception states
-        // Each nested except handler has pushed an exception onto the stack,
-        // so we need to clear/pop each one when continuing
-        for _ in 0..self.except_handler_depth {
-            self.code.emit(Opcode::ClearException);
-            self.code.emit(Opcode::Pop); // Pop the exception value
-        }
-
-        // Check if we need to go through any finally blocks
-        // We need to run finally if continue crosses the try boundary
-        if let Some(finally_target) = self.finally_targets.last_mut()
-            && target_loop_depth < finally_target.loop_depth_at_entry
-        {
-            // Continuing a loop that's outside (or at the start of) this try-finally,
-            // so finally must run before the continue
-            let jump = self.code.emit_jump(Opcode::Jump);
-            finally_target.continue_jumps.push(BreakContinueThruFinally {
-                jump,
-                target_loop_depth,
-            });
-            // Set stack depth for unreachable cleanup code (see comment below)
-            if self.except_handler_depth > 0 {
-                self.code
-                    .set_stack_depth(u16::try_from(self.except_handler_depth).unwrap_or(u16::MAX));
-            }
-            return Ok(());
-        }
-
-        // No finally to go through, jump directly to loop start
-        let loop_start = self.loop_stack[target_loop_depth].start;
-        self.code.emit_jump_to(Opcode::Jump, loop_start);
-
-        // The code following this continue is unreachable at runtime, but the compiler
-        // will still emit cleanup code for each enclosing except handler (ClearException + Pop).
-        // Set stack depth so those unreachable pops don't cause negative stack tracking.
-        if self.except_handler_depth > 0 {
-            self.code
-                .set_stack_depth(u16::try_from(self.except_handler_depth).unwrap_or(u16::MAX));
-        }
-
+        self.compile_loop_exit(false);
         Ok(())
     }
 
-    /// Compiles break or continue after a finally block has run.
-    ///
-    /// Called from `compile_try` after the finally block code. Each control flow
-    /// statement may target a different loop, so we check if there's another finally
-    /// to go through or if we can jump directly to the loop's target.
+    /// Emits the jump for a break or continue out of the innermost loop.
     ///
-    /// Note: All items in the list jumped to the same finally block, so they all
-    /// have the same starting point. After finally runs, we need to route each
-    /// to its target loop, potentially through more finally blocks.
-    fn compile_control_flow_after_finally(&mut self, items: &[BreakContinueThruFinally], is_break: bool) {
-        // All items went through the same finally, now we need to dispatch to
-        // potentially different loops. For simplicity, we assume all items in
-        // a single finally target the same loop (the innermost one at the time).
-        // This is always true since break/continue only targets the innermost loop.
-        let Some(first) = items.first() else {
-            return;
-        };
-        let target_loop_depth = first.target_loop_depth;
+    /// Also called from `compile_try` after a finally copy has run, where the
+    /// compiler state is that of the code around the try: the loop stack and
+    /// handler depth then describe the next level to unwind, which may be
+    /// another finally block or the loop itself.
+    fn compile_loop_exit(&mut self, is_break: bool) {
+        let depth = self.code.stack_depth();
+        let target_loop_depth = self.loop_stack.len() - 1;
 
-        // Check if there's another finally between us and the target loop
-        if let Some(finally_target) = self.finally_targets.last_mut()
+        // Check if we need to go through a finally block first: we do if the
+        // loop existed before the innermost try-finally started.
+        if let Some(finally_target) = self.finally_targets.last()
             && target_loop_depth < finally_target.loop_depth_at_entry
         {
-            // Need to go through another finally
+            // Only unwind what was opened inside the try; the rest is unwound after finally runs
+            let except_depth_at_entry = finally_target.except_depth_at_entry;
+            self.emit_exit_handlers(except_depth_at_entry);
             let jump = self.code.emit_jump(Opcode::Jump);
-            let jump_info = BreakContinueThruFinally {
-                jump,
-                target_loop_depth,
-            };
+            let finally_target = self.finally_targets.last_mut().expect("finally target checked above");
             if is_break {
-                finally_target.break_jumps.push(jump_info);
+                finally_target.break_jumps.push(jump);
             } else {
-                // else continue
-                finally_target.continue_jumps.push(jump_info);
+                finally_target.continue_jumps.push(jump);
+            }
+        } else {
+            let except_depth_at_entry = self.loop_stack[target_loop_depth].except_depth_at_entry;
+            self.emit_exit_handlers(except_depth_at_entry);
+            if is_break {
+                // Pop the iterator only for `for` loops, `while` loops don't have one
+                if self.loop_stack[target_loop_depth].has_iterator_on_stack {
+                    self.code.emit(Opcode::Pop);
+                }
+                let jump = self.code.emit_jump(Opcode::Jump);
+                self.loop_stack[target_loop_depth].break_jumps.push(jump);
+            } else {
+                let loop_start = self.loop_stack[target_loop_depth].start;
+                self.code.emit_jump_to(Opcode::Jump, loop_start);
             }
-            return;
         }
 
-        // No more finally blocks, jump directly to the loop target
-        if is_break {
-            let jump = self.code.emit_jump(Opcode::Jump);
-            self.loop_stack[target_loop_depth].break_jumps.push(jump);
-        } else {
-            // else continue
-            let loop_start = self.loop_stack[target_loop_depth].start;
-            self.code.emit_jump_to(Opcode::Jump, loop_start);
+        // The code directly following the jump is unreachable, but code after the enclosing
+        // block (e.g. after `if cond: break`, or the cleanup of enclosing except handlers) runs
+        // with the stack as it was before the break, so restore the depth the pops above removed.
+        self.code.set_stack_depth(depth);
+    }
+
+    /// Clears the exception state of every except handler opened since `except_depth_at_entry`.
+    ///
+    /// Each handler has pushed its exception onto the stack, and at statement level
+    /// nothing else sits above those values, so each one is cleared and popped.
+    fn emit_exit_handlers(&mut self, except_depth_at_entry: usize) {
+        for _ in except_depth_at_entry..self.except_handler_depth {
+            self.code.emit(Opcode::ClearException);
+            self.code.emit(Opcode::Pop); // Pop the exception value
         }
     }
 
@@ -2140,6 +2211,7 @@ impl<'a> Compiler<'a> {
         body_fn: impl FnOnce(&mut Self) -> Result<(), CompileError>,
     ) -> Result<(), CompileError> {
         let generator = &generators[index];
+        let base_depth = self.code.stack_depth();
 
         // Compile iterator expression
         self.compile_expr(&generator.iter)?;
@@ -2173,8 +2245,9 @@ impl<'a> Compiler<'a> {
         // Jump back to loop start
         self.code.emit_jump_to(Opcode::Jump, loop_start);
 
-        // End of loop
+        // End of loop - ForIter has popped the iterator
         self.code.patch_jump(end_jump);
+        self.code.set_stack_depth(base_depth);
 
         Ok(())
     }
@@ -2305,7 +2378,7 @@ impl<'a> Compiler<'a> {
     /// Compiles format value flags and optionally pushes format spec to stack.
     ///
     /// Returns the flags byte encoding conversion and format spec presence.
-    /// If a format spec is present, it's pushed to the stack before the value.
+    /// If a format spec is present, it's pushed to the stack after the value.
     fn compile_format_value(
         &mut self,
         conversion: ConversionFlag,
@@ -2361,19 +2434,45 @@ impl<'a> Compiler<'a> {
 
     /// Compiles a return statement, handling finally blocks properly.
     ///
-    /// If we're inside a try-finally block, the return value is kept on the stack
-    /// and we jump to a "finally with return" section that runs finally then returns.
-    /// Otherwise, we emit a direct `ReturnValue`.
+    /// The return value is already on the stack. Except handlers being left have
+    /// their exception state cleared. If we're inside a try-finally block, the values
+    /// opened inside the try (loop iterators and handler exceptions) are popped from
+    /// under the return value and we jump to a "finally with return" section that runs
+    /// finally then compiles the return again for the next level.
+    /// Otherwise, we emit a direct `ReturnValue`, which discards the rest of the frame's stack.
     fn compile_return(&mut self) {
-        if let Some(finally_target) = self.finally_targets.last_mut() {
+        let depth = self.code.stack_depth();
+        if let Some(finally_target) = self.finally_targets.last() {
+            let except_depth_at_entry = finally_target.except_depth_at_entry;
+            let open_iterators = self.loop_stack[finally_target.loop_depth_at_entry..]
+                .iter()
+                .filter(|loop_info| loop_info.has_iterator_on_stack)
+                .count();
+            let open_handlers = self.except_handler_depth - except_depth_at_entry;
+            for _ in 0..open_handlers {
+                self.code.emit(Opcode::ClearException);
+            }
+            for _ in 0..open_iterators + open_handlers {
+                self.code.emit(Opcode::Rot2);
+                self.code.emit(Opcode::Pop);
+            }
             // Inside a try-finally: jump to finally, then return
-            // Return value is already on stack
             let jump = self.code.emit_jump(Opcode::Jump);
-            finally_target.return_jumps.push(jump);
+            self.finally_targets
+                .last_mut()
+                .expect("finally target checked above")
+                .return_jumps
+                .push(jump);
         } else {
             // Normal return
+            for _ in 0..self.except_handler_depth {
+                self.code.emit(Opcode::ClearException);
+            }
             self.code.emit(Opcode::ReturnValue);
         }
+        // As with break, code after the enclosing block runs with the stack as it was
+        // before the return value was pushed.
+        self.code.set_stack_depth(depth - 1);
     }
 
     /// Compiles a try/except/else/finally block.
@@ -2381,33 +2480,41 @@ impl<'a> Compiler<'a> {
     /// The bytecode structure is:
     /// ```text
     /// <try_body>                     # protected range
-    /// JUMP to_else_or_finally        # skip handlers if no exception
+    /// JUMP else_block                # skip handlers if no exception
     /// handler_dispatch:              # exception pushed by VM
     ///   # for each handler:
     ///   <check exception type>
     ///   <handler body>
     ///   CLEAR_EXCEPTION
-    ///   JUMP to_finally
+    ///   JUMP finally_block
     /// reraise:
     ///   RERAISE                      # no handler matched
     /// else_block:
     ///   <else_body>
     /// finally_block:
     ///   <finally_body>
+    ///   JUMP end
+    /// finally_cleanup:               # exception pushed by VM
+    ///   <finally_body>
+    ///   POP
+    ///   RERAISE
+    /// <finally_body for each of return/break/continue, then the exit>
     /// end:
     /// ```
     ///
     /// For finally blocks, exceptions that propagate through the handler dispatch
-    /// (including RERAISE when no handler matches) are caught by a second exception
-    /// entry that ensures finally runs before propagation.
+    /// (including RERAISE when no handler matches) or the else block are caught by
+    /// further exception entries that ensure finally runs before propagation.
     ///
-    /// Returns inside try/except/else jump to a "finally with return" path that
-    /// runs the finally code then returns the value.
+    /// Returns, breaks and continues inside try/except/else jump to a path that
+    /// runs the finally code then carries on with the exit. An exit from the finally
+    /// code itself leaves directly: on the exception path it is compiled like an exit
+    /// from an except handler, which discards the pending exception as CPython does.
     ///
     /// **Note:** The finally block code is emitted multiple times (once for each
     /// control flow path: normal, exception, return, break, continue). This is the
     /// same approach CPython uses - each path has different stack state at entry
-    /// (e.g., return has a value on stack, break has popped the iterator), so we
+    /// (e.g., return has a value on stack, the exception path has the exception), so we
     /// can't easily share a single copy. The duplication is intentional.
     fn compile_try(&mut self, try_block: &Try<PreparedNode>) -> Result<(), CompileError> {
         let has_finally = !try_block.finally.is_empty();
@@ -2424,6 +2531,7 @@ impl<'a> Compiler<'a> {
                 break_jumps: Vec::new(),
                 continue_jumps: Vec::new(),
                 loop_depth_at_entry: self.loop_stack.len(),
+                except_depth_at_entry: self.except_handler_depth,
             });
         }
 
@@ -2461,86 +2569,23 @@ impl<'a> Compiler<'a> {
         // The handlers' Pop instructions already account for the exception,
         // so no additional stack depth adjustment is needed here.
 
-        // Mark end of handler dispatch (for finally exception entry)
-        let handler_dispatch_end = self.code.current_offset();
-
-        // === Finally cleanup handler (for exceptions during handler dispatch) ===
-        // This catches exceptions from RERAISE (and any other exceptions in handlers)
-        // and ensures finally runs before the exception propagates.
-        let finally_cleanup_start = if has_finally {
-            let cleanup_start = self.code.current_offset();
-            // Exception value is on stack (pushed by VM), so stack = stack_depth + 1
-            self.code.set_stack_depth(stack_depth + 1);
-            // We need to pop it, run finally, then reraise
-            // But we can't easily save the exception, so we use a different approach:
-            // The exception is already on the exception_stack from handle_exception,
-            // so we can just pop from operand stack, run finally, then reraise.
-            self.code.emit(Opcode::Pop); // Pop exception from operand stack
-            self.compile_block(&try_block.finally)?;
-            self.code.emit(Opcode::Reraise); // Re-raise from exception_stack
-            Some(cleanup_start)
-        } else {
-            None
-        };
-
-        // === Finally with return/break/continue paths ===
-        // Pop finally target and get all the jumps that need to go through finally
-        let finally_with_return_start = if has_finally {
-            let finally_target = self.finally_targets.pop().expect("finally_targets should not be empty");
-
-            // === Finally with return path ===
-            let return_start = if finally_target.return_jumps.is_empty() {
-                None
-            } else {
-                let start = self.code.current_offset();
-                for jump in finally_target.return_jumps {
-                    self.code.patch_jump(jump);
-                }
-                // Return value is on stack, stack = stack_depth + 1
-                self.code.set_stack_depth(stack_depth + 1);
-                self.compile_block(&try_block.finally)?;
-                self.compile_return();
-                Some(start)
-            };
-
-            // === Finally with break path ===
-            // For each break, run finally then either:
-            // - Jump to outer finally's break path (if there's an outer finally between us and the loop)
-            // - Jump directly to the loop's break target
-            if !finally_target.break_jumps.is_empty() {
-                for break_info in &finally_target.break_jumps {
-                    self.code.patch_jump(break_info.jump);
-                }
-                // Break already popped the iterator, so stack = stack_depth - 1
-                // (the iterator was on stack at try entry, break removed it)
-                self.code.set_stack_depth(stack_depth.saturating_sub(1));
-                self.compile_block(&try_block.finally)?;
-                // After finally, compile the break again (handles nested finally or direct jump)
-                self.compile_control_flow_after_finally(&finally_target.break_jumps, true);
-            }
-
-            // === Finally with continue path ===
-            if !finally_target.continue_jumps.is_empty() {
-                for continue_info in &finally_target.continue_jumps {
-                    self.code.patch_jump(continue_info.jump);
-                }
-                // Continue doesn't pop the iterator, stack = stack_depth
-                self.code.set_stack_depth(stack_depth);
-                self.compile_block(&try_block.finally)?;
-                // After finally, compile the continue again (handles nested finally or direct jump)
-                self.compile_control_flow_after_finally(&finally_target.continue_jumps, false);
-            }
+        // === Add exception table entries ===
+        // Order matters: entries are searched in order, so inner entries must come first.
+        // Entries for try blocks nested in the try body were added while compiling it.
 
-            return_start
-        } else {
-            None
-        };
+        // Entry 1: Try body -> handler dispatch
+        self.code.add_exception_entry(ExceptionEntry::new(
+            u32::try_from(try_start).expect("bytecode offset exceeds u32"),
+            u32::try_from(try_end).expect("bytecode offset exceeds u32") + 3, // +3 to include the JUMP instruction
+            u32::try_from(handler_start).expect("bytecode offset exceeds u32"),
+            stack_depth,
+        ));
 
         // === Else block (runs if no exception) ===
+        // Compiled while the finally target is still active, so exits from it run finally
         self.code.patch_jump(after_try_jump);
         // Normal path from try body, stack = stack_depth
         self.code.set_stack_depth(stack_depth);
-        let else_start = self.code.current_offset();
         if has_else {
             self.compile_block(&try_block.or_else)?;
         }
@@ -2552,57 +2597,78 @@ impl<'a> Compiler<'a> {
             self.code.patch_jump(jump);
         }
 
-        if has_finally {
-            // Stack = stack_depth (no exception, no return value)
-            self.code.set_stack_depth(stack_depth);
-            self.compile_block(&try_block.finally)?;
+        if !has_finally {
+            return Ok(());
         }
+        let finally_target = self.finally_targets.pop().expect("finally_targets should not be empty");
 
-        // === Add exception table entries ===
-        // Order matters: entries are searched in order, so inner entries must come first.
+        // Stack = stack_depth (no exception, no return value)
+        self.code.set_stack_depth(stack_depth);
+        self.compile_block(&try_block.finally)?;
+        let end_jump = self.code.emit_jump(Opcode::Jump);
 
-        // Entry 1: Try body -> handler dispatch
-        if has_handlers || has_finally {
-            self.code.add_exception_entry(ExceptionEntry::new(
-                u32::try_from(try_start).expect("bytecode offset exceeds u32"),
-                u32::try_from(try_end).expect("bytecode offset exceeds u32") + 3, // +3 to include the JUMP instruction
-                u32::try_from(handler_start).expect("bytecode offset exceeds u32"),
-                stack_depth,
-            ));
+        // === Finally cleanup handler (for exceptions during handler dispatch or else) ===
+        // This catches exceptions from RERAISE (and any other exceptions in handlers)
+        // and ensures finally runs before the exception propagates.
+        let cleanup_start = self.code.current_offset();
+        // Exception value is on stack (pushed by VM), so stack = stack_depth + 1.
+        // It stays there while finally runs, so a return/break/continue in the finally
+        // block can discard the exception like an exit from an except handler.
+        self.code.set_stack_depth(stack_depth + 1);
+        self.except_handler_depth += 1;
+        self.compile_block(&try_block.finally)?;
+        self.except_handler_depth -= 1;
+        // The exception is still on the exception_stack from handle_exception,
+        // so we can pop it from the operand stack and reraise.
+        self.code.emit(Opcode::Pop);
+        self.code.emit(Opcode::Reraise);
+
+        // === Finally with return path ===
+        // Exceptions raised by finally on these paths propagate to the enclosing handlers
+        // directly, so they don't get exception entries of their own.
+        if !finally_target.return_jumps.is_empty() {
+            for jump in finally_target.return_jumps {
+                self.code.patch_jump(jump);
+            }
+            // Return value is on stack, stack = stack_depth + 1
+            self.code.set_stack_depth(stack_depth + 1);
+            self.compile_block(&try_block.finally)?;
+            self.compile_return();
         }
 
-        // Entry 2: Handler dispatch -> finally cleanup (only if has_finally)
-        // This ensures finally runs when RERAISE is executed or any exception occurs in handlers
-        if let Some(cleanup_start) = finally_cleanup_start {
-            self.code.add_exception_entry(ExceptionEntry::new(
-                u32::try_from(handler_start).expect("bytecode offset exceeds u32"),
-                u32::try_from(handler_dispatch_end).expect("bytecode offset exceeds u32"),
-                u32::try_from(cleanup_start).expect("bytecode offset exceeds u32"),
-                stack_depth,
-            ));
+        // === Finally with break path ===
+        // After finally, compile the break again (handles an outer finally or the direct jump)
+        if !finally_target.break_jumps.is_empty() {
+            for jump in finally_target.break_jumps {
+                self.code.patch_jump(jump);
+            }
+            self.code.set_stack_depth(stack_depth);
+            self.compile_block(&try_block.finally)?;
+            self.compile_loop_exit(true);
         }
 
-        // Entry 3: Finally with return -> finally cleanup
-        // If an exception occurs while running finally (in the return path), catch it
-        if let (Some(return_start), Some(cleanup_start)) = (finally_with_return_start, finally_cleanup_start) {
-            self.code.add_exception_entry(ExceptionEntry::new(
-                u32::try_from(return_start).expect("bytecode offset exceeds u32"),
-                u32::try_from(else_start).expect("bytecode offset exceeds u32"), // End at else_start (before else block)
-                u32::try_from(cleanup_start).expect("bytecode offset exceeds u32"),
-                stack_depth,
-            ));
+        // === Finally with continue path ===
+        if !finally_target.continue_jumps.is_empty() {
+            for jump in finally_target.continue_jumps {
+                self.code.patch_jump(jump);
+            }
+            self.code.set_stack_depth(stack_depth);
+            self.compile_block(&try_block.finally)?;
+            self.compile_loop_exit(false);
         }
 
-        // Entry 4: Else block -> finally cleanup (only if has_finally and has_else)
-        // Exceptions in else block should go through finally
-        if has_else && let Some(cleanup_start) = finally_cleanup_start {
-            self.code.add_exception_entry(ExceptionEntry::new(
-                u32::try_from(else_start).expect("bytecode offset exceeds u32"),
-                u32::try_from(else_end).expect("bytecode offset exceeds u32"),
-                u32::try_from(cleanup_start).expect("bytecode offset exceeds u32"),
-                stack_depth,
-            ));
-        }
+        self.code.patch_jump(end_jump);
+        self.code.set_stack_depth(stack_depth);
+
+        // Entry 2: Handler dispatch and else block -> finally cleanup
+        // This ensures finally runs when RERAISE is executed or any exception occurs in
+        // handlers or the else block, which directly follows the handlers
+        self.code.add_exception_entry(ExceptionEntry::new(
+            u32::try_from(handler_start).expect("bytecode offset exceeds u32"),
+            u32::try_from(else_end).expect("bytecode offset exceeds u32"),
+            u32::try_from(cleanup_start).expect("bytecode offset exceeds u32"),
+            stack_depth,
+        ));
 
         Ok(())
     }
@@ -2760,12 +2826,14 @@ impl<'a> Compiler<'a> {
                 if let Ok(s) = u8::try_from(slot) {
                     self.code.emit_u8(Opcode::DeleteLocal, s);
                 } else {
-                    // Wide variant not implemented yet
-                    todo!("DeleteLocalW for slot > 255");
+                    self.code.emit_u16(Opcode::DeleteLocalW, slot);
                 }
             }
-            NameScope::Global | NameScope::Cell => {
-                // Delete global/cell not commonly needed
+            NameScope::Global | NameScope::GlobalOrBuiltin => {
+                self.code.emit_u16(Opcode::DeleteGlobal, slot);
+            }
+            NameScope::Cell => {
+                // Delete cell not commonly needed
                 // For now, just store Undefined
                 self.code.emit(Opcode::LoadNone);
                 self.compile_store(target);
@@ -2821,6 +2889,11 @@ impl CompileError {
     }
 }
 
+/// Returns true if any element of a list, tuple or set display is a `*iterable`.
+fn has_starred(elements: &[ExprLoc]) -> bool {
+    elements.iter().any(|elem| matches!(elem.expr, Expr::Starred(_)))
+}
+
 // ============================================================================
 // Operator Mapping Functions
 // ============================================================================
@@ -20,4 +20,6 @@ mod vm;
 
 pub use code::Code;
 pub use compiler::Compiler;
+#[cfg(feature = "vm-metrics")]
+pub use vm::VmMetrics;
 pub use vm::{FrameExit, VM, VMSnapshot};
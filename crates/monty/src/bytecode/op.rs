@@ -63,14 +63,21 @@ pub enum Opcode {
     StoreLocalW,
     /// Push from global namespace. Operand: u16 slot.
     LoadGlobal,
+    /// Push from global namespace, or the builtin if the slot is unbound.
+    /// Operands: u16 slot, u16 const_idx of the builtin.
+    LoadGlobalOrBuiltin,
     /// Store to global. Operand: u16 slot.
     StoreGlobal,
+    /// Delete global variable. Operand: u16 slot.
+    DeleteGlobal,
     /// Load from closure cell. Operand: u16 slot.
     LoadCell,
     /// Store to closure cell. Operand: u16 slot.
     StoreCell,
     /// Delete local variable. Operand: u8 slot.
     DeleteLocal,
+    /// Delete local (wide, slot > 255). Operand: u16 slot.
+    DeleteLocalW,
 
     // === Binary Operations (no operand) ===
     /// Add: a + b.
@@ -204,6 +211,15 @@ pub enum Opcode {
     /// Used for `**kwargs` unpacking. The func_name_id is used for error messages
     /// when the mapping contains non-string keys.
     DictMerge,
+    /// Pop iterable, pop set, add the iterable's items to the set.
+    ///
+    /// Used for `*iterable` unpacking in set literals.
+    SetUpdate,
+    /// Pop mapping, pop dict, update dict with mapping.
+    ///
+    /// Used for `**mapping` unpacking in dict literals. Unlike `DictMerge`, keys may be of
+    /// any type and later keys overwrite earlier ones.
+    DictUpdate,
 
     // === Comprehension Building ===
     /// Append TOS to list for comprehension. Operand: u8 depth (number of iterators).
@@ -410,14 +426,15 @@ impl Opcode {
             BuildSet, BuildSlice, BuildTuple, CallAttr, CallAttrExtended, CallAttrKw, CallBuiltinFunction,
             CallBuiltinType, CallFunction, CallFunctionExtended, CallFunctionKw, CheckExcMatch, ClearException,
             CompareEq, CompareGe, CompareGt, CompareIn, CompareIs, CompareIsNot, CompareLe, CompareLt, CompareModEq,
-            CompareNe, CompareNotIn, DeleteLocal, DictMerge, DictSetItem, Dup, ForIter, FormatValue, GetIter,
-            InplaceAdd, InplaceAnd, InplaceDiv, InplaceFloorDiv, InplaceLShift, InplaceMod, InplaceMul, InplaceOr,
-            InplacePow, InplaceRShift, InplaceSub, InplaceXor, Jump, JumpIfFalse, JumpIfFalseOrPop, JumpIfTrue,
-            JumpIfTrueOrPop, ListAppend, ListExtend, ListToTuple, LoadAttr, LoadAttrImport, LoadCell, LoadConst,
-            LoadFalse, LoadGlobal, LoadLocal, LoadLocal0, LoadLocal1, LoadLocal2, LoadLocal3, LoadLocalW, LoadModule,
-            LoadNone, LoadSmallInt, LoadTrue, MakeClosure, MakeFunction, Nop, Pop, Raise, RaiseImportError, Reraise,
-            ReturnValue, Rot2, Rot3, SetAdd, StoreAttr, StoreCell, StoreGlobal, StoreLocal, StoreLocalW, StoreSubscr,
-            UnaryInvert, UnaryNeg, UnaryNot, UnaryPos, UnpackEx, UnpackSequence,
+            CompareNe, CompareNotIn, DeleteGlobal, DeleteLocal, DeleteLocalW, DictMerge, DictSetItem, DictUpdate, Dup,
+            ForIter, FormatValue, GetIter, InplaceAdd, InplaceAnd, InplaceDiv, InplaceFloorDiv, InplaceLShift,
+            InplaceMod, InplaceMul, InplaceOr, InplacePow, InplaceRShift, InplaceSub, InplaceXor, Jump, JumpIfFalse,
+            JumpIfFalseOrPop, JumpIfTrue, JumpIfTrueOrPop, ListAppend, ListExtend, ListToTuple, LoadAttr,
+            LoadAttrImport, LoadCell, LoadConst, LoadFalse, LoadGlobal, LoadGlobalOrBuiltin, LoadLocal, LoadLocal0,
+            LoadLocal1, LoadLocal2, LoadLocal3, LoadLocalW, LoadModule, LoadNone, LoadSmallInt, LoadTrue, MakeClosure,
+            MakeFunction, Nop, Pop, Raise, RaiseImportError, Reraise, ReturnValue, Rot2, Rot3, SetAdd, SetUpdate,
+            StoreAttr, StoreCell, StoreGlobal, StoreLocal, StoreLocalW, StoreSubscr, UnaryInvert, UnaryNeg, UnaryNot,
+            UnaryPos, UnpackEx, UnpackSequence,
         };
         Some(match self {
             // Stack operations
@@ -430,9 +447,9 @@ impl Opcode {
 
             // Variables - loads push, stores pop
             LoadLocal0 | LoadLocal1 | LoadLocal2 | LoadLocal3 => 1,
-            LoadLocal | LoadLocalW | LoadGlobal | LoadCell => 1,
+            LoadLocal | LoadLocalW | LoadGlobal | LoadGlobalOrBuiltin | LoadCell => 1,
             StoreLocal | StoreLocalW | StoreGlobal | StoreCell => -1,
-            DeleteLocal => 0, // doesn't affect stack
+            DeleteLocal | DeleteLocalW | DeleteGlobal => 0, // doesn't affect stack
 
             // Binary operations: pop 2, push 1 = -1
             BinaryAdd | BinarySub | BinaryMul | BinaryDiv | BinaryFloorDiv | BinaryMod | BinaryPow | BinaryAnd
@@ -459,8 +476,8 @@ impl Opcode {
             ListExtend => -1,
             // ListToTuple: pop 1, push 1 = 0
             ListToTuple => 0,
-            // DictMerge: pop 2, push 1 = -1
-            DictMerge => -1,
+            // DictMerge/SetUpdate/DictUpdate: pop 2, push 1 = -1
+            DictMerge | SetUpdate | DictUpdate => -1,
 
             // Comprehension building - pops value, no push (stores in collection below)
             ListAppend | SetAdd => -1,
@@ -6,7 +6,7 @@
 //! - Task completion and failure handling
 //! - External future resolution
 
-use super::{AwaitResult, CallFrame, VM};
+use super::{AwaitResult, BatchedCall, CallFrame, VM};
 use crate::{
     InvalidInputError, MontyObject,
     args::ArgValues,
@@ -15,7 +15,7 @@ use crate::{
     defer_drop,
     exception_private::{ExcType, RunError, SimpleException},
     heap::{HeapData, HeapGuard, HeapId},
-    intern::FunctionId,
+    intern::{ExtFunctionId, FunctionId},
     resource::ResourceTracker,
     types::{List, PyTrait},
     value::Value,
@@ -307,6 +307,8 @@ impl<T: ResourceTracker> VM<'_, '_, T> {
         // Register the pre-bound namespace
         let namespace_idx = self.namespaces.register_prebuilt(namespace_values, self.heap)?;
 
+        #[cfg(feature = "vm-metrics")]
+        self.metrics.on_call(func_id);
         // Push frame to execute the coroutine
         self.frames.push(CallFrame::new_function(
             &func.code,
@@ -734,6 +736,8 @@ impl<T: ResourceTracker> VM<'_, '_, T> {
         // don't have a parent frame - the coroutine is the root)
         let func = self.interns.get_function(func_id);
         let namespace_idx = self.namespaces.register_prebuilt(namespace_values, self.heap)?;
+        #[cfg(feature = "vm-metrics")]
+        self.metrics.on_call(func_id);
         self.frames.push(CallFrame::new_function(
             &func.code,
             self.stack.len(),
@@ -941,6 +945,29 @@ impl<T: ResourceTracker> VM<'_, '_, T> {
         );
     }
 
+    /// Whether a call to `ext_function_id` is queued rather than suspending execution.
+    pub(super) fn is_batched(&self, ext_function_id: ExtFunctionId) -> bool {
+        self.call_batching
+            && self
+                .interns
+                .get_external_signature(ext_function_id)
+                .is_some_and(|signature| signature.is_async)
+    }
+
+    /// Queues a call for the host and pushes the pending future it returns.
+    pub(super) fn queue_batched_call(&mut self, ext_function_id: ExtFunctionId, args: ArgValues) {
+        let call_id = self.allocate_call_id();
+        let (args, kwargs) = args.into_py_objects(self.heap, self.interns);
+        self.batched_calls.push(BatchedCall {
+            call_id,
+            ext_function_id,
+            args,
+            kwargs,
+        });
+        self.add_pending_call(call_id);
+        self.push(Value::ExternalFuture(call_id));
+    }
+
     /// Prepares the current task to continue after futures are resolved.
     ///
     /// When the current task (main or spawned) was blocked on an external future and
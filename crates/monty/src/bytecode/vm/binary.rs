@@ -5,8 +5,13 @@ use crate::{
     defer_drop,
     exception_private::{ExcType, RunError},
     heap::HeapGuard,
+    percent_format::percent_format,
     resource::ResourceTracker,
-    types::PyTrait,
+    types::{
+        PyTrait, UnionType,
+        fraction::{FractionOp, fraction_binary_op},
+        set::{SetOp, set_binary_op, set_inplace_op},
+    },
     value::BitwiseOp,
 };
 
@@ -29,6 +34,10 @@ impl<T: ResourceTracker> VM<'_, '_, T> {
                 Ok(())
             }
             Ok(None) => {
+                if let Some(v) = fraction_binary_op(lhs, rhs, FractionOp::Add, this.heap)? {
+                    this.push(v);
+                    return Ok(());
+                }
                 let lhs_type = lhs.py_type(this.heap);
                 let rhs_type = rhs.py_type(this.heap);
                 Err(ExcType::binary_type_error("+", lhs_type, rhs_type))
@@ -54,6 +63,14 @@ impl<T: ResourceTracker> VM<'_, '_, T> {
                 Ok(())
             }
             Ok(None) => {
+                if let Some(v) = set_binary_op(lhs, rhs, SetOp::Difference, this.heap, this.interns)? {
+                    this.push(v);
+                    return Ok(());
+                }
+                if let Some(v) = fraction_binary_op(lhs, rhs, FractionOp::Sub, this.heap)? {
+                    this.push(v);
+                    return Ok(());
+                }
                 let lhs_type = lhs.py_type(this.heap);
                 let rhs_type = rhs.py_type(this.heap);
                 Err(ExcType::binary_type_error("-", lhs_type, rhs_type))
@@ -79,6 +96,10 @@ impl<T: ResourceTracker> VM<'_, '_, T> {
                 Ok(())
             }
             Ok(None) => {
+                if let Some(v) = fraction_binary_op(lhs, rhs, FractionOp::Mult, this.heap)? {
+                    this.push(v);
+                    return Ok(());
+                }
                 let lhs_type = lhs.py_type(this.heap);
                 let rhs_type = rhs.py_type(this.heap);
                 Err(ExcType::binary_type_error("*", lhs_type, rhs_type))
@@ -104,6 +125,10 @@ impl<T: ResourceTracker> VM<'_, '_, T> {
                 Ok(())
             }
             Ok(None) => {
+                if let Some(v) = fraction_binary_op(lhs, rhs, FractionOp::Div, this.heap)? {
+                    this.push(v);
+                    return Ok(());
+                }
                 let lhs_type = lhs.py_type(this.heap);
                 let rhs_type = rhs.py_type(this.heap);
                 Err(ExcType::binary_type_error("/", lhs_type, rhs_type))
@@ -129,6 +154,10 @@ impl<T: ResourceTracker> VM<'_, '_, T> {
                 Ok(())
             }
             Ok(None) => {
+                if let Some(v) = fraction_binary_op(lhs, rhs, FractionOp::FloorDiv, this.heap)? {
+                    this.push(v);
+                    return Ok(());
+                }
                 let lhs_type = lhs.py_type(this.heap);
                 let rhs_type = rhs.py_type(this.heap);
                 Err(ExcType::binary_type_error("//", lhs_type, rhs_type))
@@ -154,6 +183,14 @@ impl<T: ResourceTracker> VM<'_, '_, T> {
                 Ok(())
             }
             Ok(None) => {
+                if let Some(v) = percent_format(lhs, rhs, this.heap, this.interns)? {
+                    this.push(v);
+                    return Ok(());
+                }
+                if let Some(v) = fraction_binary_op(lhs, rhs, FractionOp::Mod, this.heap)? {
+                    this.push(v);
+                    return Ok(());
+                }
                 let lhs_type = lhs.py_type(this.heap);
                 let rhs_type = rhs.py_type(this.heap);
                 Err(ExcType::binary_type_error("%", lhs_type, rhs_type))
@@ -180,6 +217,10 @@ impl<T: ResourceTracker> VM<'_, '_, T> {
                 Ok(())
             }
             Ok(None) => {
+                if let Some(v) = fraction_binary_op(lhs, rhs, FractionOp::Pow, this.heap)? {
+                    this.push(v);
+                    return Ok(());
+                }
                 let lhs_type = lhs.py_type(this.heap);
                 let rhs_type = rhs.py_type(this.heap);
                 Err(ExcType::binary_type_error("** or pow()", lhs_type, rhs_type))
@@ -199,11 +240,62 @@ impl<T: ResourceTracker> VM<'_, '_, T> {
         let lhs = this.pop();
         defer_drop!(lhs, this);
 
+        if let Some(set_op) = SetOp::from_bitwise(op)
+            && let Some(v) = set_binary_op(lhs, rhs, set_op, this.heap, this.interns)?
+        {
+            this.push(v);
+            return Ok(());
+        }
+
+        // `int | None` and similar build a union of classes
+        if matches!(op, BitwiseOp::Or)
+            && let Some(v) = UnionType::or(lhs, rhs, this.heap)?
+        {
+            this.push(v);
+            return Ok(());
+        }
+
         let result = lhs.py_bitwise(rhs, op, this.heap)?;
         this.push(result);
         Ok(())
     }
 
+    /// In-place subtraction: `set -= set` mutates the left set, everything else
+    /// falls back to binary subtraction.
+    pub(super) fn inplace_sub(&mut self) -> Result<(), RunError> {
+        if self.inplace_set_op(SetOp::Difference)? {
+            return Ok(());
+        }
+        self.binary_sub()
+    }
+
+    /// In-place bitwise operation: `set |= set` (and `&=`, `^=`) mutates the left set,
+    /// everything else falls back to the binary operator.
+    pub(super) fn inplace_bitwise(&mut self, op: BitwiseOp) -> Result<(), RunError> {
+        if let Some(set_op) = SetOp::from_bitwise(op)
+            && self.inplace_set_op(set_op)?
+        {
+            return Ok(());
+        }
+        self.binary_bitwise(op)
+    }
+
+    /// Applies a set operator in place when the top two stack values are a set and a
+    /// set or frozenset, leaving the (mutated) left operand on the stack.
+    ///
+    /// Returns false without touching the stack if the operands are not eligible.
+    fn inplace_set_op(&mut self, op: SetOp) -> Result<bool, RunError> {
+        let [lhs, rhs] = &self.stack[self.stack.len() - 2..] else {
+            panic!("stack underflow");
+        };
+        if !set_inplace_op(lhs, rhs, op, self.heap, self.interns)? {
+            return Ok(false);
+        }
+        let rhs = self.pop();
+        rhs.drop_with_heap(self.heap);
+        Ok(true)
+    }
+
     /// In-place addition (uses py_iadd for mutable containers, falls back to py_add).
     ///
     /// For mutable types like lists, `py_iadd` mutates in place and returns true.
@@ -235,6 +327,10 @@ impl<T: ResourceTracker> VM<'_, '_, T> {
             this.push(v);
             return Ok(());
         }
+        if let Some(v) = fraction_binary_op(lhs, rhs, FractionOp::Add, this.heap)? {
+            this.push(v);
+            return Ok(());
+        }
 
         let lhs_type = lhs.py_type(this.heap);
         let rhs_type = rhs.py_type(this.heap);
@@ -13,15 +13,17 @@ use crate::{
     exception_private::{ExcType, RunError},
     heap::{DropWithHeap, Heap, HeapData, HeapGuard, HeapId},
     intern::{ExtFunctionId, FunctionId, Interns, StaticStrings, StringId},
+    modules::ModuleFunctions,
     os::OsFunction,
     resource::ResourceTracker,
     types::{
         AttrCallResult, Dict, PyTrait, Type,
         bytes::{bytes_fromhex, call_bytes_method},
+        csv_writer::call_writer_method,
         dict::dict_fromkeys,
         str::call_str_method,
     },
-    value::{EitherStr, Value},
+    value::{Callee, EitherStr, Value},
 };
 
 /// Result of executing a call opcode.
@@ -52,6 +54,8 @@ pub(super) enum CallResult {
     /// Used by `asyncio.run()` to execute a coroutine without an explicit `await`.
     /// The VM will push the value onto the stack and execute `exec_get_awaitable`.
     AwaitValue(Value),
+    /// A partial result for the host - VM should yield `FrameExit::Yield`.
+    Yield(Value),
 }
 
 impl From<AttrCallResult> for CallResult {
@@ -62,10 +66,89 @@ impl From<AttrCallResult> for CallResult {
             AttrCallResult::ExternalCall(ext_id, args) => Self::External(ext_id, args),
             AttrCallResult::MethodCall(name, args) => Self::MethodCall(name, args),
             AttrCallResult::AwaitValue(v) => Self::AwaitValue(v),
+            AttrCallResult::Yield(v) => Self::Yield(v),
         }
     }
 }
 
+/// Something the VM can call with arguments.
+///
+/// Implemented for each kind of callable (builtins, module functions, external functions
+/// and defined functions) and for `Callee`, which dispatches to the right one. Every call
+/// of a callable value goes through `VM::call_function`, which resolves the value with
+/// `Value::callee` and invokes it through this trait.
+pub(super) trait Callable {
+    /// Calls `self` with `args`, which are always consumed.
+    fn invoke<T: ResourceTracker>(self, vm: &mut VM<'_, '_, T>, args: ArgValues) -> Result<CallResult, RunError>;
+}
+
+impl Callable for Callee {
+    fn invoke<T: ResourceTracker>(self, vm: &mut VM<'_, '_, T>, args: ArgValues) -> Result<CallResult, RunError> {
+        match self {
+            Self::Builtin(builtin) => builtin.invoke(vm, args),
+            Self::ModuleFunction(mf) => mf.invoke(vm, args),
+            Self::ExtFunction(ext_id) => ext_id.invoke(vm, args),
+            Self::DefFunction(func_id) => func_id.invoke(vm, args),
+            Self::HeapFunction(heap_id) => vm.call_heap_function(heap_id, args),
+        }
+    }
+}
+
+impl Callable for Builtins {
+    fn invoke<T: ResourceTracker>(self, vm: &mut VM<'_, '_, T>, args: ArgValues) -> Result<CallResult, RunError> {
+        match self {
+            Self::Function(builtin) => builtin.invoke(vm, args),
+            _ => self
+                .call(vm.heap, args, vm.interns, vm.print_writer)
+                .map(CallResult::Push),
+        }
+    }
+}
+
+impl Callable for BuiltinsFunctions {
+    /// Builtins that call back into the VM or suspend it are routed to their own entry points.
+    fn invoke<T: ResourceTracker>(self, vm: &mut VM<'_, '_, T>, args: ArgValues) -> Result<CallResult, RunError> {
+        if self.is_introspection() {
+            vm.call_introspection_builtin(self, args).map(CallResult::Push)
+        } else if self.takes_key_function() {
+            vm.call_key_builtin(self, args).map(CallResult::Push)
+        } else if self.yields_to_host() {
+            self.call_host(vm.heap, args, vm.interns).map(CallResult::from)
+        } else {
+            self.call(vm.heap, args, vm.interns, vm.print_writer)
+                .map(CallResult::Push)
+        }
+    }
+}
+
+impl Callable for ModuleFunctions {
+    fn invoke<T: ResourceTracker>(self, vm: &mut VM<'_, '_, T>, args: ArgValues) -> Result<CallResult, RunError> {
+        let result = self.call(vm.heap, args, vm.interns)?;
+        Ok(result.into())
+    }
+}
+
+impl Callable for ExtFunctionId {
+    /// Returns `External` so the VM suspends and the host runs the function.
+    fn invoke<T: ResourceTracker>(self, vm: &mut VM<'_, '_, T>, args: ArgValues) -> Result<CallResult, RunError> {
+        // Validate against a registered signature before suspending, so arity errors raise in the sandbox
+        if let Some(signature) = vm.interns.get_external_signature(self)
+            && let Err(err) = signature.check_args(&args, vm.heap, vm.interns)
+        {
+            args.drop_with_heap(vm.heap);
+            return Err(err);
+        }
+        Ok(CallResult::External(self, args))
+    }
+}
+
+impl Callable for FunctionId {
+    /// Calls a defined function without defaults or captured variables.
+    fn invoke<T: ResourceTracker>(self, vm: &mut VM<'_, '_, T>, args: ArgValues) -> Result<CallResult, RunError> {
+        vm.call_def_function(self, &[], Vec::new(), args)
+    }
+}
+
 impl<T: ResourceTracker> VM<'_, '_, T> {
     // ========================================================================
     // Call Opcode Executors
@@ -88,11 +171,15 @@ impl<T: ResourceTracker> VM<'_, '_, T> {
     ///
     /// Calls a builtin function directly without stack manipulation for the callable.
     /// This is an optimization that avoids constant pool lookup and stack manipulation.
-    pub(super) fn exec_call_builtin_function(&mut self, builtin_id: u8, arg_count: usize) -> Result<Value, RunError> {
+    pub(super) fn exec_call_builtin_function(
+        &mut self,
+        builtin_id: u8,
+        arg_count: usize,
+    ) -> Result<CallResult, RunError> {
         // Convert u8 to BuiltinsFunctions via FromRepr
         if let Some(builtin) = BuiltinsFunctions::from_repr(builtin_id) {
             let args = self.pop_n_args(arg_count);
-            builtin.call(self.heap, args, self.interns, self.print_writer)
+            builtin.invoke(self, args)
         } else {
             Err(RunError::internal("CallBuiltinFunction: invalid builtin_id"))
         }
@@ -275,6 +362,15 @@ impl<T: ResourceTracker> VM<'_, '_, T> {
         match obj {
             Value::Ref(heap_id) => {
                 defer_drop!(obj, this);
+                // list.sort() may call a user-defined key function, which needs the VM
+                if name_id == StaticStrings::Sort && matches!(this.heap.get(heap_id), HeapData::List(_)) {
+                    return this.call_list_sort(heap_id, args).map(CallResult::Push);
+                }
+                // csv writers read the heap while formatting a row, so they stay in it during the call
+                if matches!(this.heap.get(heap_id), HeapData::CsvWriter(_)) {
+                    return call_writer_method(heap_id, name_id, args, this.heap, this.interns, this.print_writer)
+                        .map(CallResult::Push);
+                }
                 let result = this
                     .heap
                     .call_attr_raw(heap_id, &attr, args, this.interns, this.print_writer);
@@ -305,57 +401,33 @@ impl<T: ResourceTracker> VM<'_, '_, T> {
 
     /// Calls a callable value with the given arguments.
     ///
-    /// Dispatches based on the callable type:
-    /// - `Value::Builtin`: calls builtin directly, returns `Push`
-    /// - `Value::ModuleFunction`: calls module function directly, returns `Push`
-    /// - `Value::ExtFunction`: returns `External` for caller to execute
-    /// - `Value::DefFunction`: pushes a new frame, returns `FramePushed`
-    /// - `Value::Ref`: checks for closure/function on heap
-    fn call_function(&mut self, callable: Value, args: ArgValues) -> Result<CallResult, RunError> {
-        match callable {
-            Value::Builtin(builtin) => {
-                let result = builtin.call(self.heap, args, self.interns, self.print_writer)?;
-                Ok(CallResult::Push(result))
-            }
-            Value::ModuleFunction(mf) => {
-                let result = mf.call(self.heap, args)?;
-                Ok(result.into())
-            }
-            Value::ExtFunction(ext_id) => {
-                // External function - return to caller to execute
-                Ok(CallResult::External(ext_id, args))
-            }
-            Value::DefFunction(func_id) => {
-                // Defined function without defaults or captured variables
-                self.call_def_function(func_id, &[], Vec::new(), args)
-            }
-            Value::Ref(heap_id) => {
-                // Could be a closure or function with defaults - check heap
-                self.call_heap_callable(heap_id, callable, args)
-            }
-            _ => {
-                args.drop_with_heap(self.heap);
-                Err(ExcType::type_error("object is not callable"))
-            }
-        }
+    /// Resolves the value with `Value::callee` and dispatches through `Callable`, so
+    /// exactly the values `callable()` accepts can be called. The callable value is
+    /// dropped once the call has been set up.
+    pub(super) fn call_function(&mut self, callable: Value, args: ArgValues) -> Result<CallResult, RunError> {
+        let Some(callee) = callable.callee(self.heap) else {
+            args.drop_with_heap(self.heap);
+            let callable_type = callable.py_type(self.heap);
+            callable.drop_with_heap(self.heap);
+            return Err(ExcType::type_error_not_callable_object(callable_type));
+        };
+        let this = self;
+        defer_drop!(callable, this);
+        callee.invoke(this, args)
     }
 
-    /// Handles calling a heap-allocated callable (closure or function with defaults).
+    /// Handles calling a heap-allocated function (closure or function with defaults).
     ///
     /// Uses a two-phase approach to avoid borrow conflicts:
     /// 1. Copy data without incrementing refcounts
     /// 2. Increment refcounts after the borrow ends
-    fn call_heap_callable(
-        &mut self,
-        heap_id: HeapId,
-        callable: Value,
-        args: ArgValues,
-    ) -> Result<CallResult, RunError> {
-        let this = self;
-        defer_drop!(callable, this);
-
+    ///
+    /// # Panics
+    /// Panics if `heap_id` isn't a closure or function with defaults, which `Value::callee`
+    /// guarantees for `Callee::HeapFunction`.
+    fn call_heap_function(&mut self, heap_id: HeapId, args: ArgValues) -> Result<CallResult, RunError> {
         // Phase 1: Copy data (func_id, cells, defaults) without refcount changes
-        let (func_id, cells, defaults) = match this.heap.get(heap_id) {
+        let (func_id, cells, defaults) = match self.heap.get(heap_id) {
             HeapData::Closure(fid, cells, defaults) => {
                 let cloned_cells = cells.clone();
                 let cloned_defaults: Vec<Value> = defaults.iter().map(Value::copy_for_extend).collect();
@@ -365,24 +437,20 @@ impl<T: ResourceTracker> VM<'_, '_, T> {
                 let cloned_defaults: Vec<Value> = defaults.iter().map(Value::copy_for_extend).collect();
                 (*fid, Vec::new(), cloned_defaults)
             }
-            _ => {
-                args.drop_with_heap(this.heap);
-                return Err(ExcType::type_error("object is not callable"));
-            }
+            _ => unreachable!("Callee::HeapFunction must refer to a closure or function with defaults"),
         };
 
         // Phase 2: Increment refcounts now that the heap borrow has ended
         for &cell_id in &cells {
-            this.heap.inc_ref(cell_id);
+            self.heap.inc_ref(cell_id);
         }
         for default in &defaults {
             if let Value::Ref(id) = default {
-                this.heap.inc_ref(*id);
+                self.heap.inc_ref(*id);
             }
         }
 
-        // Call the defined function (callable guard drops at scope exit)
-        this.call_def_function(func_id, &cells, defaults, args)
+        self.call_def_function(func_id, &cells, defaults, args)
     }
 
     /// Calls a function with unpacked args tuple and optional kwargs dict.
@@ -721,6 +789,7 @@ impl<T: ResourceTracker> VM<'_, '_, T> {
         let func = self.interns.get_function(func_id);
 
         // 1. Create new namespace for function
+        self.check_stack_size()?;
         let namespace_idx = self.namespaces.new_namespace(func.namespace_size, self.heap)?;
 
         let namespace = self.namespaces.get_mut(namespace_idx).mut_vec();
@@ -778,6 +847,8 @@ impl<T: ResourceTracker> VM<'_, '_, T> {
         }
 
         let code = &func.code;
+        #[cfg(feature = "vm-metrics")]
+        self.metrics.on_call(func_id);
         // 6. Push new frame
         self.frames.push(CallFrame::new_function(
             code,
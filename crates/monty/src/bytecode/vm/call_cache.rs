@@ -0,0 +1,72 @@
+//! Memoization of calls to pure external functions.
+//!
+//! Calls to external functions marked pure with `MontyRun::mark_pure` are keyed by the
+//! function and its arguments converted to `MontyObject`s. The first call with a key suspends
+//! as usual, and the value the host resumes it with is recorded; later calls with the same
+//! key get that value straight away, without suspending. Exceptions and futures aren't
+//! recorded, so a failed call is retried the next time it's made.
+
+use ahash::AHashMap;
+
+use super::VM;
+use crate::{
+    MontyObject,
+    args::ArgValues,
+    exception_private::{ExcType, RunResult, SimpleException},
+    intern::ExtFunctionId,
+    resource::ResourceTracker,
+    value::Value,
+};
+
+/// Results of calls to pure external functions, kept for the whole run.
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+pub(crate) struct CallCache {
+    /// Return values by the serialized function id and arguments of the call.
+    results: AHashMap<Vec<u8>, MontyObject>,
+    /// Key of the pure call execution is suspended at.
+    ///
+    /// Taken by `VM::resume` to record the host's return value, and cleared whenever execution
+    /// continues in any other way.
+    pending: Option<Vec<u8>>,
+}
+
+impl CallCache {
+    /// Forgets the pending call, once execution continues without recording its result.
+    pub fn forget_pending(&mut self) {
+        self.pending = None;
+    }
+}
+
+impl<T: ResourceTracker> VM<'_, '_, T> {
+    /// Looks up the result of an earlier call to a pure external function with the same arguments.
+    ///
+    /// Returns `None` for functions which aren't pure, and on a miss, in which case the key is
+    /// remembered so the value the host resumes the call with is recorded.
+    pub(super) fn cached_call_result(
+        &mut self,
+        ext_function_id: ExtFunctionId,
+        args: &ArgValues,
+    ) -> Option<RunResult<Value>> {
+        if !self.interns.is_external_pure(ext_function_id) {
+            return None;
+        }
+        let (args, kwargs) = args.to_py_objects(self.heap, self.interns);
+        let key = postcard::to_allocvec(&(ext_function_id, args, kwargs)).ok()?;
+        let Some(result) = self.call_cache.results.get(&key) else {
+            self.call_cache.pending = Some(key);
+            return None;
+        };
+        Some(
+            result.clone().to_value(self.heap, self.interns).map_err(|e| {
+                SimpleException::new(ExcType::RuntimeError, Some(format!("invalid return type: {e}"))).into()
+            }),
+        )
+    }
+
+    /// Records `result` as the return value of the pure call execution is suspended at, if any.
+    pub(super) fn record_call_result(&mut self, result: &MontyObject) {
+        if let Some(key) = self.call_cache.pending.take() {
+            self.call_cache.results.insert(key, result.clone());
+        }
+    }
+}
@@ -0,0 +1,96 @@
+//! Synchronous callbacks from builtins into the VM.
+//!
+//! `min()`, `max()`, `sorted()` and `list.sort()` call their `key` function once per item
+//! while the builtin is still running, so the VM runs the key function's frame to
+//! completion in a nested run loop and hands the result straight back to the builtin.
+
+use super::{CallResult, FrameExit, VM};
+use crate::{
+    args::ArgValues,
+    builtins::{BuiltinsFunctions, KeyCaller},
+    exception_private::{ExcType, RunError, RunResult},
+    heap::{DropWithHeap, HeapId},
+    intern::Interns,
+    resource::ResourceTracker,
+    types::list::list_sort_in_place,
+    value::Value,
+};
+
+impl<T: ResourceTracker> KeyCaller for VM<'_, '_, T> {
+    fn call_key(&mut self, key: &Value, item: Value, _interns: &Interns) -> RunResult<Value> {
+        self.call_sync(key, ArgValues::One(item))
+    }
+}
+
+impl<T: ResourceTracker> VM<'_, '_, T> {
+    /// Calls `min()`, `max()` or `sorted()` with the VM as the key function caller.
+    pub(super) fn call_key_builtin(&mut self, builtin: BuiltinsFunctions, args: ArgValues) -> RunResult<Value> {
+        let interns = self.interns;
+        builtin.call_with_keys(self, args, interns)
+    }
+
+    /// Calls `list.sort()` on the list at `list_id` with the VM as the key function caller.
+    pub(super) fn call_list_sort(&mut self, list_id: HeapId, args: ArgValues) -> RunResult<Value> {
+        let interns = self.interns;
+        list_sort_in_place(list_id, args, self, interns)?;
+        Ok(Value::None)
+    }
+
+    /// Calls `callable` and runs it to completion before returning its result.
+    ///
+    /// Defined functions get a nested run loop that stops when their frame returns or
+    /// raises. Calls that would suspend the VM (external functions, OS calls, awaiting)
+    /// can't be resumed from inside a builtin, so they raise `NotImplementedError`.
+    pub(crate) fn call_sync(&mut self, callable: &Value, args: ArgValues) -> RunResult<Value> {
+        let callable = callable.clone_with_heap(self.heap);
+        let instruction_ip = self.instruction_ip;
+        let result = match self.call_function(callable, args) {
+            Ok(CallResult::Push(value)) => Ok(value),
+            Ok(CallResult::FramePushed) => self.run_nested_frame(),
+            Ok(CallResult::External(_, args) | CallResult::OsCall(_, args) | CallResult::MethodCall(_, args)) => {
+                args.drop_with_heap(self.heap);
+                Err(suspending_call_error())
+            }
+            Ok(CallResult::AwaitValue(value) | CallResult::Yield(value)) => {
+                value.drop_with_heap(self.heap);
+                Err(suspending_call_error())
+            }
+            Err(err) => Err(err),
+        };
+        self.instruction_ip = instruction_ip;
+        result
+    }
+
+    /// Runs the frame just pushed by `call_function` until it returns or raises.
+    ///
+    /// `sync_call_floor` makes `ReturnValue` and exception unwinding stop at this frame
+    /// instead of continuing into the caller's frames, which the outer run loop still owns.
+    fn run_nested_frame(&mut self) -> RunResult<Value> {
+        let floor = self.frames.len() - 1;
+        let outer_floor = self.sync_call_floor.replace(floor);
+        let result = self.run_loop();
+        self.sync_call_floor = outer_floor;
+
+        match result {
+            Ok(FrameExit::Return(value)) => return Ok(value),
+            Ok(
+                FrameExit::ExternalCall { args, .. }
+                | FrameExit::OsCall { args, .. }
+                | FrameExit::MethodCall { args, .. },
+            ) => args.drop_with_heap(self.heap),
+            Ok(FrameExit::Yield { value, .. }) => value.drop_with_heap(self.heap),
+            Ok(FrameExit::ResolveFutures(_) | FrameExit::Checkpoint) => {}
+            Err(err) => return Err(err),
+        }
+        // The key function tried to suspend: discard its frames so the caller's are on top again
+        while self.frames.len() > floor {
+            self.pop_frame();
+        }
+        Err(suspending_call_error())
+    }
+}
+
+/// Error for key functions that try to suspend the VM.
+fn suspending_call_error() -> RunError {
+    ExcType::not_implemented("external function calls are not supported inside key functions").into()
+}
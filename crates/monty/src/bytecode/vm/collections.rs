@@ -6,10 +6,13 @@ use super::VM;
 use crate::{
     defer_drop, defer_drop_mut,
     exception_private::{ExcType, RunError, SimpleException},
-    heap::{HeapData, HeapGuard},
+    heap::{DropWithHeap, HeapData, HeapGuard},
     intern::StringId,
     resource::ResourceTracker,
-    types::{Dict, List, PyTrait, Set, Slice, Type, allocate_tuple, slice::value_to_option_i64, str::allocate_char},
+    types::{
+        Dict, List, MontyIter, PyTrait, Set, Slice, Type, allocate_tuple, slice::value_to_option_i64,
+        str::allocate_char,
+    },
     value::Value,
 };
 
@@ -100,49 +103,33 @@ impl<T: ResourceTracker> VM<'_, '_, T> {
         let mut list_ref_guard = HeapGuard::new(this.pop(), this);
         let (list_ref, this) = list_ref_guard.as_parts();
 
-        // Two-phase approach to avoid borrow conflicts:
-        // Phase 1: Copy items without refcount changes
-        let copied_items: Vec<Value> = match iterable {
+        // Fast path for builtin containers: copy items without refcount changes, then
+        // increment refcounts once the borrow has ended. Everything else (strings, ranges,
+        // iterators, ...) goes through the general iterator protocol.
+        let fast_items: Option<Vec<Value>> = match iterable {
             Value::Ref(id) => match this.heap.get(*id) {
-                HeapData::List(list) => list.as_slice().iter().map(Value::copy_for_extend).collect(),
-                HeapData::Tuple(tuple) => tuple.as_slice().iter().map(Value::copy_for_extend).collect(),
-                HeapData::Set(set) => set.storage().iter().map(Value::copy_for_extend).collect(),
-                HeapData::Dict(dict) => dict.iter().map(|(k, _)| Value::copy_for_extend(k)).collect(),
-                HeapData::Str(s) => {
-                    // Need to allocate strings for each character
-                    let chars: Vec<char> = s.as_str().chars().collect();
-                    let mut items = Vec::with_capacity(chars.len());
-                    for c in chars {
-                        items.push(allocate_char(c, this.heap)?);
-                    }
-                    items
-                }
-                _ => {
-                    let type_ = iterable.py_type(this.heap);
-                    return Err(ExcType::type_error_not_iterable(type_));
-                }
+                HeapData::List(list) => Some(list.as_slice().iter().map(Value::copy_for_extend).collect()),
+                HeapData::Tuple(tuple) => Some(tuple.as_slice().iter().map(Value::copy_for_extend).collect()),
+                HeapData::Set(set) => Some(set.storage().iter().map(Value::copy_for_extend).collect()),
+                HeapData::Dict(dict) => Some(dict.iter().map(|(k, _)| Value::copy_for_extend(k)).collect()),
+                _ => None,
             },
-            Value::InternString(id) => {
-                let s = this.interns.get_str(*id);
-                let chars: Vec<char> = s.chars().collect();
-                let mut items = Vec::with_capacity(chars.len());
-                for c in chars {
-                    items.push(allocate_char(c, this.heap)?);
-                }
-                items
-            }
-            _ => {
-                let type_ = iterable.py_type(this.heap);
-                return Err(ExcType::type_error_not_iterable(type_));
-            }
+            _ => None,
         };
 
-        // Phase 2: Increment refcounts now that the borrow has ended
-        for item in &copied_items {
-            if let Value::Ref(id) = item {
-                this.heap.inc_ref(*id);
+        let copied_items: Vec<Value> = if let Some(items) = fast_items {
+            for item in &items {
+                if let Value::Ref(id) = item {
+                    this.heap.inc_ref(*id);
+                }
             }
-        }
+            items
+        } else {
+            let type_ = iterable.py_type(this.heap);
+            let iter = MontyIter::new(iterable.clone_with_heap(this.heap), this.heap, this.interns)
+                .map_err(|_| ExcType::type_error_star_not_iterable(type_))?;
+            iter.collect(this.heap, this.interns)?
+        };
 
         // Check if any copied items are refs (for updating contains_refs)
         let has_refs = copied_items.iter().any(|v| matches!(v, Value::Ref(_)));
@@ -307,6 +294,112 @@ impl<T: ResourceTracker> VM<'_, '_, T> {
         Ok(())
     }
 
+    /// Adds every item of an iterable to a set for `*iterable` unpacking in set literals.
+    ///
+    /// Stack: [set, iterable] -> [set]
+    ///
+    /// Uses `HeapGuard` for `set_ref` because it is pushed back on success.
+    pub(super) fn set_update(&mut self) -> Result<(), RunError> {
+        let iterable = self.pop();
+        let mut set_ref_guard = HeapGuard::new(self.pop(), self);
+        let (set_ref, this) = set_ref_guard.as_parts();
+
+        let Value::Ref(set_id) = *set_ref else {
+            iterable.drop_with_heap(this.heap);
+            return Err(RunError::internal("SetUpdate: expected set ref"));
+        };
+
+        let iter = MontyIter::new(iterable, this.heap, this.interns)?;
+        let items: Vec<Value> = iter.collect(this.heap, this.interns)?;
+        let mut items = items.into_iter();
+        while let Some(item) = items.next() {
+            let result = this.heap.with_entry_mut(set_id, |heap, data| {
+                if let HeapData::Set(set) = data {
+                    set.add(item, heap, this.interns)
+                } else {
+                    item.drop_with_heap(heap);
+                    Err(RunError::internal("SetUpdate: expected set on heap"))
+                }
+            });
+            if let Err(err) = result {
+                items.drop_with_heap(this.heap);
+                return Err(err);
+            }
+        }
+
+        let (set_ref, this) = set_ref_guard.into_parts();
+        this.push(set_ref);
+        Ok(())
+    }
+
+    /// Updates a dict with the items of another dict for `**mapping` unpacking in dict literals.
+    ///
+    /// Stack: [dict, mapping] -> [dict]
+    /// Later keys overwrite earlier ones, matching `{**a, **b}` semantics in Python.
+    ///
+    /// Uses `defer_drop!` for `mapping` (always dropped) and `HeapGuard` for
+    /// `dict_ref` (pushed back on success, dropped on error).
+    pub(super) fn dict_update(&mut self) -> Result<(), RunError> {
+        let this = self;
+
+        let mapping = this.pop();
+        defer_drop!(mapping, this);
+        let mut dict_ref_guard = HeapGuard::new(this.pop(), this);
+        let (dict_ref, this) = dict_ref_guard.as_parts();
+
+        // Phase 1: Copy key-value pairs without refcount changes
+        let copied_items: Vec<(Value, Value)> = if let Value::Ref(id) = mapping
+            && let HeapData::Dict(dict) = this.heap.get(*id)
+        {
+            dict.iter()
+                .map(|(k, v)| (Value::copy_for_extend(k), Value::copy_for_extend(v)))
+                .collect()
+        } else {
+            return Err(ExcType::type_error_not_mapping(mapping.py_type(this.heap)));
+        };
+
+        // Phase 2: Increment refcounts now that the borrow has ended
+        for (key, value) in &copied_items {
+            if let Value::Ref(id) = key {
+                this.heap.inc_ref(*id);
+            }
+            if let Value::Ref(id) = value {
+                this.heap.inc_ref(*id);
+            }
+        }
+
+        let Value::Ref(dict_id) = *dict_ref else {
+            copied_items.drop_with_heap(this.heap);
+            return Err(RunError::internal("DictUpdate: expected dict ref"));
+        };
+
+        // Keys were already hashable in the source dict, so `set` cannot fail on hashing
+        let mut items = copied_items.into_iter();
+        while let Some((key, value)) = items.next() {
+            let result = this.heap.with_entry_mut(dict_id, |heap, data| {
+                if let HeapData::Dict(dict) = data {
+                    dict.set(key, value, heap, this.interns)
+                } else {
+                    key.drop_with_heap(heap);
+                    value.drop_with_heap(heap);
+                    Err(RunError::internal("DictUpdate: expected dict on heap"))
+                }
+            });
+            match result {
+                Ok(Some(old_value)) => old_value.drop_with_heap(this.heap),
+                Ok(None) => {}
+                Err(err) => {
+                    items.drop_with_heap(this.heap);
+                    return Err(err);
+                }
+            }
+        }
+
+        let (dict_ref, this) = dict_ref_guard.into_parts();
+        this.push(dict_ref);
+        Ok(())
+    }
+
     // ========================================================================
     // Comprehension Building
     // ========================================================================
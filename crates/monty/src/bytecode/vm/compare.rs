@@ -4,8 +4,13 @@ use super::VM;
 use crate::{
     defer_drop,
     exception_private::{ExcType, RunError},
+    percent_format::percent_format,
     resource::{DepthGuard, ResourceTracker},
-    types::{LongInt, PyTrait},
+    types::{
+        LongInt, PyTrait,
+        fraction::{FractionOp, fraction_binary_op},
+        set::{is_set_pair, set_partial_cmp},
+    },
     value::Value,
 };
 
@@ -52,9 +57,14 @@ impl<T: ResourceTracker> VM<'_, '_, T> {
         let lhs = this.pop();
         defer_drop!(lhs, this);
 
-        let mut guard = DepthGuard::default();
-        let result = lhs.py_cmp(rhs, this.heap, &mut guard, this.interns)?.is_some_and(check);
-        this.push(Value::Bool(result));
+        // Sets are ordered by inclusion, which needs interns for membership checks
+        let ordering = if is_set_pair(lhs, rhs, this.heap) {
+            set_partial_cmp(lhs, rhs, this.heap, this.interns)?
+        } else {
+            let mut guard = DepthGuard::default();
+            lhs.py_cmp(rhs, this.heap, &mut guard, this.interns)?
+        };
+        this.push(Value::Bool(ordering.is_some_and(check)));
         Ok(())
     }
 
@@ -121,8 +131,14 @@ impl<T: ResourceTracker> VM<'_, '_, T> {
             Ok(())
         } else {
             // Fallback: compute py_mod then compare with py_eq
-            // This handles LongInt and other Ref types
-            let mod_value = lhs.py_mod(rhs, this.heap);
+            // This handles LongInt, Fraction and other Ref types
+            let mod_value = match lhs.py_mod(rhs, this.heap) {
+                Ok(None) => match percent_format(lhs, rhs, this.heap, this.interns) {
+                    Ok(None) => fraction_binary_op(lhs, rhs, FractionOp::Mod, this.heap),
+                    formatted => formatted,
+                },
+                mod_value => mod_value,
+            };
 
             match mod_value {
                 Ok(Some(v)) => {
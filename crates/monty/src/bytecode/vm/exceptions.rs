@@ -2,6 +2,7 @@
 
 use super::VM;
 use crate::{
+    audit::{AuditEvent, AuditEventKind},
     builtins::Builtins,
     defer_drop,
     exception_private::{ExcType, ExceptionRaise, RawStackFrame, RunError, SimpleException},
@@ -121,6 +122,14 @@ impl<T: ResourceTracker> VM<'_, '_, T> {
         // For uncatchable exceptions (ResourceError like RecursionError),
         // we still need to unwind the stack to collect all frames for the traceback
         if matches!(error, RunError::UncatchableExc(_) | RunError::Internal(_)) {
+            if let RunError::UncatchableExc(exc) = &error {
+                self.heap
+                    .tracker()
+                    .audit(&AuditEvent::now(AuditEventKind::ResourceLimit {
+                        exc_type: exc.exc.exc_type(),
+                        message: exc.exc.arg().map_or("", String::as_str),
+                    }));
+            }
             return Some(self.unwind_for_traceback(error));
         }
 
@@ -218,11 +227,13 @@ impl<T: ResourceTracker> VM<'_, '_, T> {
                 }
             }
 
-            // Update instruction_ip for the new frame
-            this.instruction_ip = this
-                .current_frame()
-                .call_position
-                .map_or(0, |p| p.start().line as usize);
+            // A nested call's frame raised - propagate to the builtin that made the call
+            if this.sync_call_floor == Some(this.frames.len()) {
+                return Some(error);
+            }
+
+            // Search the caller from its call instruction: the saved ip points just past it
+            this.instruction_ip = this.current_frame().ip.saturating_sub(1);
         }
     }
 
@@ -232,7 +243,7 @@ impl<T: ResourceTracker> VM<'_, '_, T> {
     /// but still need a complete traceback showing all active call frames.
     fn unwind_for_traceback(&mut self, mut error: RunError) -> RunError {
         // Pop frames and add caller frame info to the traceback
-        while self.frames.len() > 1 {
+        while self.frames.len() > self.sync_call_floor.unwrap_or(1) {
             // Get the call site position before popping frame
             let call_position = self.current_frame().call_position;
 
@@ -4,7 +4,7 @@ use super::VM;
 use crate::{
     defer_drop,
     exception_private::{ExcType, RunError, SimpleException},
-    fstring::{ParsedFormatSpec, ascii_escape, decode_format_spec, format_string, format_with_spec},
+    fstring::{FormatSpecError, ParsedFormatSpec, ascii_escape, decode_format_spec, format_string, format_with_spec},
     resource::{DepthGuard, ResourceTracker},
     types::{PyTrait, str::allocate_string},
     value::Value,
@@ -50,7 +50,7 @@ impl<T: ResourceTracker> VM<'_, '_, T> {
         let conversion = flags & 0x03;
         let has_format_spec = (flags & 0x04) != 0;
 
-        // Pop format spec if present (pushed before value, so popped after)
+        // Pop format spec if present (pushed after the value, so popped first)
         let format_spec = if has_format_spec { Some(this.pop()) } else { None };
 
         let value = this.pop();
@@ -115,16 +115,19 @@ impl<T: ResourceTracker> VM<'_, '_, T> {
                 // Dynamic format spec - parse the string
                 let mut guard = DepthGuard::default();
                 let spec_str = spec_value.py_str(self.heap, &mut guard, self.interns);
-                spec_str.parse::<ParsedFormatSpec>().map_err(|invalid| {
-                    // Only fetch type in error path
-                    let value_type = value_for_error.py_type(self.heap);
-                    RunError::Exc(
-                        SimpleException::new_msg(
-                            ExcType::ValueError,
-                            format!("Invalid format specifier '{invalid}' for object of type '{value_type}'"),
+                spec_str.parse::<ParsedFormatSpec>().map_err(|err| match err {
+                    FormatSpecError::Invalid(invalid) => {
+                        // Only fetch type in error path
+                        let value_type = value_for_error.py_type(self.heap);
+                        RunError::Exc(
+                            SimpleException::new_msg(
+                                ExcType::ValueError,
+                                format!("Invalid format specifier '{invalid}' for object of type '{value_type}'"),
+                            )
+                            .into(),
                         )
-                        .into(),
-                    )
+                    }
+                    FormatSpecError::Grouping(err) => err.into(),
                 })
             }
         }
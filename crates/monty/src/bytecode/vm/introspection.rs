@@ -0,0 +1,177 @@
+//! Namespace introspection builtins for the VM.
+//!
+//! `locals()`, `globals()` and `dir()` read the executing frame's namespace, which
+//! `BuiltinsFunctions::call` has no access to, so the VM dispatches them here instead.
+//!
+//! `globals()` returns a new dict of the module's current bindings on every call. Item
+//! assignments through it (`globals()['x'] = 1`) are written back to the module namespace
+//! for names the module has a variable slot for; other mutations, and assignments to names
+//! the module never uses, only change the dict. `locals()` inside a function is a read-only
+//! snapshot, as CPython allows.
+//!
+//! `dir(obj)` doesn't need the frame, so it's passed on to the ordinary builtin.
+
+use super::VM;
+use crate::{
+    args::ArgValues,
+    builtins::BuiltinsFunctions,
+    bytecode::code::Code,
+    exception_private::{RunError, RunResult},
+    heap::{Heap, HeapData},
+    intern::{StaticStrings, StringId},
+    namespace::{GLOBAL_NS_IDX, NamespaceId},
+    resource::ResourceTracker,
+    types::{Dict, List},
+    value::Value,
+};
+
+impl<'a, T: ResourceTracker> VM<'a, '_, T> {
+    /// Calls `locals()`, `globals()` or `dir()` against the current frame.
+    ///
+    /// The dict returned by `globals()`, or by `locals()` at module level, writes item
+    /// assignments back to the module namespace (see `globals_view_slot`). Function
+    /// `locals()` and `dir()` results are snapshots.
+    pub(super) fn call_introspection_builtin(
+        &mut self,
+        builtin: BuiltinsFunctions,
+        args: ArgValues,
+    ) -> RunResult<Value> {
+        match builtin {
+            BuiltinsFunctions::Locals => {
+                args.check_zero_args("locals", self.heap)?;
+                let (code, namespace_idx) = self.current_code_namespace();
+                self.namespace_dict(code, namespace_idx)
+            }
+            BuiltinsFunctions::Globals => {
+                args.check_zero_args("globals", self.heap)?;
+                let code = self.module_code.expect("module_code not set when calling globals()");
+                self.namespace_dict(code, GLOBAL_NS_IDX)
+            }
+            BuiltinsFunctions::Dir => {
+                if let Some(obj) = args.get_zero_one_arg("dir", self.heap)? {
+                    return builtin.call(self.heap, ArgValues::One(obj), self.interns, self.print_writer);
+                }
+                let (code, namespace_idx) = self.current_code_namespace();
+                let mut names: Vec<StringId> = self
+                    .bound_names(code, namespace_idx)
+                    .into_iter()
+                    .map(|(name, value)| {
+                        value.drop_with_heap(self.heap);
+                        name
+                    })
+                    .collect();
+                names.sort_by(|a, b| self.interns.get_str(*a).cmp(self.interns.get_str(*b)));
+                let items = names.into_iter().map(Value::InternString).collect();
+                let list_id = self.heap.allocate(HeapData::List(List::new(items)))?;
+                Ok(Value::Ref(list_id))
+            }
+            _ => Err(RunError::internal(
+                "call_introspection_builtin: not an introspection builtin",
+            )),
+        }
+    }
+
+    /// Returns the code and namespace index of the current frame.
+    fn current_code_namespace(&self) -> (&'a Code, NamespaceId) {
+        let frame = self.current_frame();
+        (frame.code, frame.namespace_idx)
+    }
+
+    /// Builds a new dict mapping each bound name in the namespace to its value.
+    ///
+    /// The module namespace's dict is marked as a globals view, so `StoreSubscr` writes
+    /// item assignments through it back to the namespace.
+    fn namespace_dict(&mut self, code: &Code, namespace_idx: NamespaceId) -> RunResult<Value> {
+        let mut dict = Dict::new();
+        for (name, value) in self.bound_names(code, namespace_idx) {
+            if let Some(old) = dict.set(Value::InternString(name), value, self.heap, self.interns)? {
+                old.drop_with_heap(self.heap);
+            }
+        }
+        if namespace_idx == GLOBAL_NS_IDX {
+            dict.mark_globals_view();
+        }
+        let dict_id = self.heap.allocate(HeapData::Dict(dict))?;
+        Ok(Value::Ref(dict_id))
+    }
+
+    /// Returns the module namespace slot an item assignment `obj[key] = ...` must also write to.
+    ///
+    /// That's the case when `obj` is a dict returned by `globals()` and `key` is a str naming
+    /// one of the module's variables. Names the module never binds have no slot, so assigning
+    /// them only changes the dict.
+    pub(super) fn globals_view_slot(&self, obj: &Value, key: &Value) -> Option<NamespaceId> {
+        let Value::Ref(id) = obj else {
+            return None;
+        };
+        if !matches!(self.heap.get(*id), HeapData::Dict(dict) if dict.is_globals_view()) {
+            return None;
+        }
+        let code = self.module_code?;
+        let key = key.as_either_str(self.heap)?;
+        let slot_count = self.namespaces.get(GLOBAL_NS_IDX).as_slice().len();
+        (0..slot_count)
+            .find(|&slot| {
+                u16::try_from(slot)
+                    .ok()
+                    .and_then(|slot| code.local_name(slot))
+                    .is_some_and(|name| key.matches(name, self.interns))
+            })
+            .map(NamespaceId::new)
+    }
+
+    /// Stores `value` in a module namespace slot on behalf of a `globals()` dict.
+    ///
+    /// Slots holding closure cells are updated through the cell, mirroring `resolve_slot`.
+    pub(super) fn write_back_global(&mut self, slot: NamespaceId, value: Value) {
+        let namespace = self.namespaces.get_mut(GLOBAL_NS_IDX);
+        if let Value::Ref(id) = namespace.get(slot)
+            && matches!(self.heap.get(*id), HeapData::Cell(_))
+        {
+            let cell_id = *id;
+            self.heap.set_cell_value(cell_id, value);
+            return;
+        }
+        let old_value = std::mem::replace(namespace.get_mut(slot), value);
+        old_value.drop_with_heap(self.heap);
+    }
+
+    /// Collects every named, bound slot of a namespace in slot order.
+    ///
+    /// Slots without a recorded name (compiler temporaries) and unbound slots are skipped.
+    /// Closure cells are dereferenced so the caller sees the variable's current value.
+    /// The returned values are owned clones.
+    fn bound_names(&mut self, code: &Code, namespace_idx: NamespaceId) -> Vec<(StringId, Value)> {
+        let empty: StringId = StaticStrings::EmptyString.into();
+        let slots = self.namespaces.get(namespace_idx).as_slice();
+        let mut bound = Vec::new();
+        for (slot, value) in slots.iter().enumerate() {
+            let Some(name) = u16::try_from(slot).ok().and_then(|slot| code.local_name(slot)) else {
+                continue;
+            };
+            if name == empty {
+                continue;
+            }
+            if let Some(value) = resolve_slot(value, self.heap) {
+                bound.push((name, value));
+            }
+        }
+        bound
+    }
+}
+
+/// Returns an owned copy of a namespace slot's value, or `None` if the slot is unbound.
+///
+/// Slots holding closure cells are dereferenced to the cell's contents.
+fn resolve_slot(value: &Value, heap: &mut Heap<impl ResourceTracker>) -> Option<Value> {
+    let resolved = match value {
+        Value::Undefined => return None,
+        Value::Ref(id) if matches!(heap.get(*id), HeapData::Cell(_)) => heap.get_cell_value(*id),
+        other => other.clone_with_heap(heap),
+    };
+    if matches!(resolved, Value::Undefined) {
+        None
+    } else {
+        Some(resolved)
+    }
+}
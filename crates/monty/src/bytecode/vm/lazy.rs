@@ -0,0 +1,88 @@
+//! Chunked fetching for host-provided lazy sequences.
+//!
+//! When `ForIter` runs past the items fetched so far, the VM suspends with a
+//! `__getitem__` method call and rewinds to the `ForIter` instruction. The host resumes
+//! with the chunk, which is on top of the stack when `ForIter` runs again; it's stored
+//! in the sequence before the iterator is advanced.
+
+use super::VM;
+use crate::{
+    args::{ArgValues, KwargsValues},
+    exception_private::{ExcType, RunResult},
+    heap::{DropWithHeap, HeapData, HeapId},
+    intern::StaticStrings,
+    resource::ResourceTracker,
+    types::{PyTrait, iter::lazy_fetch_range},
+    value::{EitherStr, Value},
+};
+
+/// A chunk of a lazy sequence that the host has been asked for.
+///
+/// Doesn't own a reference to the sequence: the iterator below the chunk on the stack
+/// keeps it alive until the chunk is stored.
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
+pub(crate) struct LazyFetch {
+    /// The sequence the chunk belongs to.
+    sequence_id: HeapId,
+    /// Index of the first requested item.
+    start: usize,
+    /// Index one past the last requested item.
+    stop: usize,
+}
+
+impl<T: ResourceTracker> VM<'_, '_, T> {
+    /// Starts a fetch if the iterator `iter_id` is over a lazy sequence that ran out of items.
+    ///
+    /// Returns the `__getitem__` method name and its `(sequence, start, stop)` arguments,
+    /// and records the pending fetch so the resumed `ForIter` knows to store the chunk.
+    /// Returns `None` if the iterator doesn't need a fetch, or while running a builtin's
+    /// callback synchronously, which can't suspend.
+    pub(super) fn start_lazy_fetch(&mut self, iter_id: HeapId) -> Option<(EitherStr, ArgValues)> {
+        if self.sync_call_floor.is_some() {
+            return None;
+        }
+        let (sequence_id, start, stop) = lazy_fetch_range(self.heap, iter_id)?;
+        // The sequence passed to the host is a new reference owned by the arguments
+        self.heap.inc_ref(sequence_id);
+        self.lazy_fetch = Some(LazyFetch {
+            sequence_id,
+            start,
+            stop,
+        });
+        let args = ArgValues::ArgsKargs {
+            args: vec![
+                Value::Ref(sequence_id),
+                Value::Int(i64::try_from(start).expect("lazy sequence index exceeds i64")),
+                Value::Int(i64::try_from(stop).expect("lazy sequence index exceeds i64")),
+            ],
+            kwargs: KwargsValues::Empty,
+        };
+        Some((StaticStrings::DunderGetitem.into(), args))
+    }
+
+    /// Stores the chunk the host returned for `fetch` in its lazy sequence.
+    ///
+    /// The chunk must be a list; anything else raises `TypeError`.
+    pub(super) fn store_lazy_chunk(&mut self, fetch: LazyFetch, chunk: Value) -> RunResult<()> {
+        let items = match &chunk {
+            Value::Ref(id) => match self.heap.get_mut(*id) {
+                HeapData::List(list) => Some(std::mem::take(list.as_vec_mut())),
+                _ => None,
+            },
+            _ => None,
+        };
+        let Some(items) = items else {
+            let type_ = chunk.py_type(self.heap);
+            chunk.drop_with_heap(self.heap);
+            return Err(ExcType::lazy_sequence_chunk_type_error(type_));
+        };
+        chunk.drop_with_heap(self.heap);
+        let result = self.heap.with_entry_mut(fetch.sequence_id, |heap, data| {
+            let HeapData::LazySequence(seq) = data else {
+                panic!("store_lazy_chunk: expected LazySequence on heap");
+            };
+            seq.store_chunk(fetch.start, fetch.stop, items, heap)
+        });
+        result
+    }
+}
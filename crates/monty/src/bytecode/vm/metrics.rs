@@ -0,0 +1,147 @@
+//! Opcode, branch and call counters, only compiled with the `vm-metrics` feature.
+//!
+//! Counting adds work to every instruction, so it's kept out of normal builds entirely.
+//! Use it for benchmarking changes to the compiler and VM, e.g. to check an optimization
+//! reduces the instructions executed, or as a regression gate in the bench suite.
+
+use ahash::AHashMap;
+
+use crate::{
+    bytecode::{Code, op::Opcode},
+    intern::{FunctionId, Interns},
+};
+
+/// Counters collected by the VM while it runs.
+#[derive(Debug)]
+pub(super) struct MetricsCollector {
+    /// Executions per opcode, indexed by the opcode's byte value.
+    opcodes: Box<[u64; 256]>,
+    /// Last outcome of each conditional jump, keyed by code address and bytecode offset.
+    last_branch: AHashMap<(usize, usize), bool>,
+    branches_taken: u64,
+    branches_not_taken: u64,
+    branch_mispredictions: u64,
+    /// Frames pushed per function.
+    calls: AHashMap<FunctionId, u64>,
+}
+
+impl Default for MetricsCollector {
+    fn default() -> Self {
+        Self {
+            opcodes: Box::new([0; 256]),
+            last_branch: AHashMap::new(),
+            branches_taken: 0,
+            branches_not_taken: 0,
+            branch_mispredictions: 0,
+            calls: AHashMap::new(),
+        }
+    }
+}
+
+impl MetricsCollector {
+    /// Counts an executed opcode.
+    #[inline]
+    pub fn on_opcode(&mut self, opcode: Opcode) {
+        self.opcodes[opcode as usize] += 1;
+    }
+
+    /// Counts the outcome of the conditional jump at offset `ip` of `code`.
+    ///
+    /// A misprediction is counted when the outcome differs from the previous outcome of the
+    /// same jump, as a one-bit branch predictor would; the first outcome is predicted not taken.
+    pub fn on_branch(&mut self, code: &Code, ip: usize, taken: bool) {
+        if taken {
+            self.branches_taken += 1;
+        } else {
+            self.branches_not_taken += 1;
+        }
+        let last = self.last_branch.insert((std::ptr::from_ref(code) as usize, ip), taken);
+        if last.unwrap_or(false) != taken {
+            self.branch_mispredictions += 1;
+        }
+    }
+
+    /// Counts a frame pushed for a call of `function_id`.
+    pub fn on_call(&mut self, function_id: FunctionId) {
+        *self.calls.entry(function_id).or_default() += 1;
+    }
+
+    /// Converts the counters to [`VmMetrics`], resolving opcode and function names.
+    pub fn into_metrics(self, interns: &Interns) -> VmMetrics {
+        let mut opcodes: Vec<(String, u64)> = self
+            .opcodes
+            .iter()
+            .enumerate()
+            .filter(|&(_, &count)| count > 0)
+            .map(|(byte, &count)| {
+                let opcode = u8::try_from(byte)
+                    .ok()
+                    .and_then(Opcode::from_repr)
+                    .expect("only valid opcodes are counted");
+                (format!("{opcode:?}"), count)
+            })
+            .collect();
+        opcodes.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+        let mut function_calls: Vec<(String, u64)> = self
+            .calls
+            .into_iter()
+            .map(|(function_id, count)| {
+                let name_id = interns.get_function(function_id).name.name_id;
+                (interns.get_str(name_id).to_owned(), count)
+            })
+            .collect();
+        function_calls.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+        VmMetrics {
+            instructions: self.opcodes.iter().sum(),
+            opcodes,
+            branches_taken: self.branches_taken,
+            branches_not_taken: self.branches_not_taken,
+            branch_mispredictions: self.branch_mispredictions,
+            function_calls,
+        }
+    }
+}
+
+/// Counters from running code with [`MontyRun::run_with_metrics`](crate::MontyRun::run_with_metrics).
+///
+/// Counts only depend on the code and inputs, so they're stable across runs and machines.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VmMetrics {
+    /// Total number of instructions executed.
+    pub instructions: u64,
+    /// Executions per opcode name, e.g. `("LoadLocal0", 120)`, most executed first.
+    pub opcodes: Vec<(String, u64)>,
+    /// Conditional jumps which jumped.
+    pub branches_taken: u64,
+    /// Conditional jumps which fell through.
+    pub branches_not_taken: u64,
+    /// Conditional jumps with a different outcome than the previous time the same jump ran.
+    pub branch_mispredictions: u64,
+    /// Calls per function name, most called first.
+    ///
+    /// Counts frames pushed for functions defined in the code, including coroutines when
+    /// they start running; builtins and external functions aren't counted.
+    pub function_calls: Vec<(String, u64)>,
+}
+
+impl VmMetrics {
+    /// Number of executions of the opcode named `name`, e.g. `"CallFunction"`.
+    #[must_use]
+    pub fn opcode_count(&self, name: &str) -> u64 {
+        self.opcodes
+            .iter()
+            .find(|(opcode, _)| opcode == name)
+            .map_or(0, |&(_, count)| count)
+    }
+
+    /// Number of calls of the function named `name`.
+    #[must_use]
+    pub fn call_count(&self, name: &str) -> u64 {
+        self.function_calls
+            .iter()
+            .find(|(function, _)| function == name)
+            .map_or(0, |&(_, count)| count)
+    }
+}
@@ -7,21 +7,34 @@ mod async_exec;
 mod attr;
 mod binary;
 mod call;
+mod call_cache;
+mod callbacks;
 mod collections;
 mod compare;
 mod exceptions;
 mod format;
+mod introspection;
+mod lazy;
+#[cfg(feature = "vm-metrics")]
+mod metrics;
 mod scheduler;
+#[cfg(feature = "heap-validate")]
+mod validate;
 
 use std::cmp::Ordering;
 
 use call::CallResult;
+use call_cache::CallCache;
+use lazy::LazyFetch;
+#[cfg(feature = "vm-metrics")]
+pub use metrics::VmMetrics;
 use scheduler::Scheduler;
 
 use crate::{
     MontyObject,
     args::ArgValues,
     asyncio::{CallId, TaskId},
+    audit::{AuditEvent, AuditEventKind},
     bytecode::{code::Code, op::Opcode},
     exception_private::{ExcType, RunError, RunResult, SimpleException},
     heap::{ContainsHeap, Heap, HeapData, HeapId},
@@ -29,9 +42,10 @@ use crate::{
     io::PrintWriter,
     modules::BuiltinModule,
     namespace::{GLOBAL_NS_IDX, NamespaceId, Namespaces},
+    object::OutputSize,
     os::OsFunction,
     parse::CodeRange,
-    resource::ResourceTracker,
+    resource::{ResourceError, ResourceTracker, stack_address},
     types::{LongInt, MontyIter, PyTrait, iter::advance_on_heap},
     value::{BitwiseOp, EitherStr, Value},
 };
@@ -150,6 +164,14 @@ macro_rules! jump_relative {
     }};
 }
 
+/// Counts the outcome of a conditional jump with the `vm-metrics` feature, does nothing otherwise.
+macro_rules! record_branch {
+    ($self:ident, $cached_frame:ident, $taken:expr) => {
+        #[cfg(feature = "vm-metrics")]
+        $self.metrics.on_branch($cached_frame.code, $cached_frame.ip, $taken);
+    };
+}
+
 /// Handles the result of a call operation that returns `CallResult`.
 ///
 /// This macro eliminates the repetitive pattern of matching on `CallResult`
@@ -163,41 +185,67 @@ macro_rules! jump_relative {
 /// - `OsCall(func, args)`: Return `FrameExit::OsCall` to yield to host
 /// - `MethodCall(name, args)`: Return `FrameExit::MethodCall` to yield to host
 /// - `AwaitValue(value)`: Push value, then implicitly await it via `exec_get_awaitable`
+/// - `Yield(value)`: Return `FrameExit::Yield` to hand a partial result to the host
 /// - `Err(err)`: Handle the exception via `catch_sync!`
+///
+/// Arguments yielded to the host are first checked against the `max_output_size` limit.
 macro_rules! handle_call_result {
     ($self:expr, $cached_frame:ident, $result:expr) => {
         match $result {
             Ok(CallResult::Push(result)) => $self.push(result),
             Ok(CallResult::FramePushed) => reload_cache!($self, $cached_frame),
             Ok(CallResult::External(ext_id, args)) => {
-                let call_id = $self.allocate_call_id();
-                // Sync cached IP back to frame before snapshot for resume
-                $self.current_frame_mut().ip = $cached_frame.ip;
-                return Ok(FrameExit::ExternalCall {
-                    ext_function_id: ext_id,
-                    args,
-                    call_id,
-                });
+                if let Err(err) = args.check_output_size($self.heap, $self.interns) {
+                    args.drop_with_heap($self.heap);
+                    catch_sync!($self, $cached_frame, err.into());
+                } else if $self.is_batched(ext_id) {
+                    $self.queue_batched_call(ext_id, args);
+                } else if let Some(result) = $self.cached_call_result(ext_id, &args) {
+                    args.drop_with_heap($self.heap);
+                    match result {
+                        Ok(value) => $self.push(value),
+                        Err(err) => catch_sync!($self, $cached_frame, err),
+                    }
+                } else {
+                    let call_id = $self.allocate_call_id();
+                    // Sync cached IP back to frame before snapshot for resume
+                    $self.current_frame_mut().ip = $cached_frame.ip;
+                    return Ok(FrameExit::ExternalCall {
+                        ext_function_id: ext_id,
+                        args,
+                        call_id,
+                    });
+                }
             }
             Ok(CallResult::OsCall(func, args)) => {
-                let call_id = $self.allocate_call_id();
-                // Sync cached IP back to frame before snapshot for resume
-                $self.current_frame_mut().ip = $cached_frame.ip;
-                return Ok(FrameExit::OsCall {
-                    function: func,
-                    args,
-                    call_id,
-                });
+                if let Err(err) = args.check_output_size($self.heap, $self.interns) {
+                    args.drop_with_heap($self.heap);
+                    catch_sync!($self, $cached_frame, err.into());
+                } else {
+                    let call_id = $self.allocate_call_id();
+                    // Sync cached IP back to frame before snapshot for resume
+                    $self.current_frame_mut().ip = $cached_frame.ip;
+                    return Ok(FrameExit::OsCall {
+                        function: func,
+                        args,
+                        call_id,
+                    });
+                }
             }
             Ok(CallResult::MethodCall(method_name, args)) => {
-                let call_id = $self.allocate_call_id();
-                // Sync cached IP back to frame before snapshot for resume
-                $self.current_frame_mut().ip = $cached_frame.ip;
-                return Ok(FrameExit::MethodCall {
-                    method_name,
-                    args,
-                    call_id,
-                });
+                if let Err(err) = args.check_output_size($self.heap, $self.interns) {
+                    args.drop_with_heap($self.heap);
+                    catch_sync!($self, $cached_frame, err.into());
+                } else {
+                    let call_id = $self.allocate_call_id();
+                    // Sync cached IP back to frame before snapshot for resume
+                    $self.current_frame_mut().ip = $cached_frame.ip;
+                    return Ok(FrameExit::MethodCall {
+                        method_name,
+                        args,
+                        call_id,
+                    });
+                }
             }
             Ok(CallResult::AwaitValue(value)) => {
                 // Push the value and implicitly await it (used by asyncio.run())
@@ -218,6 +266,17 @@ macro_rules! handle_call_result {
                     }
                 }
             }
+            Ok(CallResult::Yield(value)) => {
+                if let Err(err) = OutputSize::check(&value, $self.heap, $self.interns) {
+                    value.drop_with_heap($self.heap);
+                    catch_sync!($self, $cached_frame, err.into());
+                } else {
+                    let call_id = $self.allocate_call_id();
+                    // Sync cached IP back to frame before snapshot for resume
+                    $self.current_frame_mut().ip = $cached_frame.ip;
+                    return Ok(FrameExit::Yield { value, call_id });
+                }
+            }
             Err(err) => catch_sync!($self, $cached_frame, err),
         }
     };
@@ -277,6 +336,23 @@ pub enum FrameExit {
     /// This happens when await is called on an ExternalFuture that hasn't
     /// been resolved yet, and there are no other ready tasks to switch to.
     ResolveFutures(Vec<CallId>),
+
+    /// Execution paused to hand a partial result to the host, from `monty_yield()`.
+    ///
+    /// The caller should consume the value and call `resume()` with the value
+    /// `monty_yield()` returns.
+    Yield {
+        /// The value passed to `monty_yield()`.
+        value: Value,
+        /// Unique ID for this pause, used for async correlation.
+        call_id: CallId,
+    },
+
+    /// Execution paused at an instruction boundary so the host can serialize the state.
+    ///
+    /// Produced every `checkpoint_interval` instructions when checkpoints are enabled,
+    /// the caller resumes with `run()` without providing a value.
+    Checkpoint,
 }
 
 /// A single function activation record.
@@ -406,6 +482,19 @@ impl CallFrame<'_> {
     }
 }
 
+/// A call to an async external function queued by call batching, see `VM::enable_call_batching`.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub(crate) struct BatchedCall {
+    /// ID of the pending `ExternalFuture` returned to the caller.
+    pub call_id: CallId,
+    /// The external function called.
+    pub ext_function_id: ExtFunctionId,
+    /// The positional arguments.
+    pub args: Vec<MontyObject>,
+    /// The keyword arguments.
+    pub kwargs: Vec<(MontyObject, MontyObject)>,
+}
+
 /// VM state for pause/resume at external function calls.
 ///
 /// **Ownership:** This struct OWNS the values (refcounts were already incremented).
@@ -444,6 +533,25 @@ pub struct VMSnapshot {
     /// This enables async execution to be paused and resumed across host calls.
     /// None if no async operations have been performed yet.
     scheduler: Option<Scheduler>,
+
+    /// Lazy sequence chunk the host was asked for when execution paused.
+    #[serde(default)]
+    lazy_fetch: Option<LazyFetch>,
+
+    /// Batched calls not yet handed to the host.
+    #[serde(default)]
+    batched_calls: Vec<BatchedCall>,
+
+    /// Results of calls to pure external functions.
+    #[serde(default)]
+    call_cache: CallCache,
+}
+
+impl VMSnapshot {
+    /// Takes the batched calls queued since they were last taken, in call order.
+    pub(crate) fn take_batched_calls(&mut self) -> Vec<BatchedCall> {
+        std::mem::take(&mut self.batched_calls)
+    }
 }
 
 // ============================================================================
@@ -509,6 +617,54 @@ pub struct VM<'a, 'p, T: ResourceTracker> {
     /// Stored here because the main task's frames have `function_id: None` and
     /// need a reference to the module code when being restored after task switching.
     module_code: Option<&'a Code>,
+
+    /// Address of the Rust stack when execution started or resumed in `run()`.
+    ///
+    /// Used to measure the interpreter's stack usage against the `max_stack_size` limit.
+    rust_stack_start: usize,
+
+    /// Index of the frame a builtin is running synchronously, e.g. a `sorted()` key function.
+    ///
+    /// While set, returning from or unwinding past this frame exits the nested run loop in
+    /// `call_sync` rather than continuing in the caller's frame. `None` outside such calls.
+    sync_call_floor: Option<usize>,
+
+    /// Lazy sequence chunk requested from the host, stored by the next `ForIter`.
+    lazy_fetch: Option<LazyFetch>,
+
+    /// Whether calls to async external functions are queued rather than suspending execution.
+    call_batching: bool,
+
+    /// Calls queued by call batching, handed to the host when execution blocks on futures.
+    batched_calls: Vec<BatchedCall>,
+
+    /// Results of calls to pure external functions, see `call_cache`.
+    call_cache: CallCache,
+
+    /// Whether to pause with `FrameExit::Checkpoint` every `checkpoint_interval` instructions.
+    ///
+    /// Only set by the iterative `MontyRun` API, other callers can't hand checkpoints to the host.
+    checkpoints_enabled: bool,
+
+    /// Instructions executed since the last checkpoint, or since execution started or resumed.
+    instructions_since_checkpoint: usize,
+
+    /// Code, ip and heap allocation totals at the start of the previous instruction.
+    ///
+    /// Only used when the heap traces allocations, to attribute each instruction's
+    /// allocations to its source line.
+    allocation_site: Option<(&'a Code, usize, (usize, usize))>,
+
+    /// Whether to report each instruction to the tracker's `on_instruction`, see `Profiler`.
+    profiling: bool,
+
+    /// Opcode, branch and call counters, see [`Self::take_metrics`].
+    #[cfg(feature = "vm-metrics")]
+    metrics: metrics::MetricsCollector,
+
+    /// Whether to check the heap's invariants before every instruction, see `HeapValidator`.
+    #[cfg(feature = "heap-validate")]
+    validate_every_instruction: bool,
 }
 
 impl<'a, 'p, T: ResourceTracker> VM<'a, 'p, T> {
@@ -519,6 +675,9 @@ impl<'a, 'p, T: ResourceTracker> VM<'a, 'p, T> {
         interns: &'a Interns,
         print_writer: &'a mut PrintWriter<'p>,
     ) -> Self {
+        let profiling = heap.tracker().profile_instructions();
+        #[cfg(feature = "heap-validate")]
+        let validate_every_instruction = heap.tracker().validate_heap_every_instruction();
         Self {
             stack: Vec::with_capacity(64),
             frames: Vec::with_capacity(16),
@@ -531,6 +690,20 @@ impl<'a, 'p, T: ResourceTracker> VM<'a, 'p, T> {
             next_call_id: 0,
             scheduler: None, // Lazy - no allocation for sync code
             module_code: None,
+            rust_stack_start: stack_address(),
+            sync_call_floor: None,
+            lazy_fetch: None,
+            call_batching: false,
+            batched_calls: Vec::new(),
+            call_cache: CallCache::default(),
+            checkpoints_enabled: false,
+            instructions_since_checkpoint: 0,
+            allocation_site: None,
+            profiling,
+            #[cfg(feature = "vm-metrics")]
+            metrics: metrics::MetricsCollector::default(),
+            #[cfg(feature = "heap-validate")]
+            validate_every_instruction,
         }
     }
 
@@ -576,6 +749,9 @@ impl<'a, 'p, T: ResourceTracker> VM<'a, 'p, T> {
             })
             .collect();
 
+        let profiling = heap.tracker().profile_instructions();
+        #[cfg(feature = "heap-validate")]
+        let validate_every_instruction = heap.tracker().validate_heap_every_instruction();
         Self {
             stack: snapshot.stack,
             frames,
@@ -588,16 +764,36 @@ impl<'a, 'p, T: ResourceTracker> VM<'a, 'p, T> {
             next_call_id: snapshot.next_call_id,
             scheduler: snapshot.scheduler,
             module_code: Some(module_code),
+            rust_stack_start: stack_address(),
+            sync_call_floor: None,
+            lazy_fetch: snapshot.lazy_fetch,
+            call_batching: false,
+            batched_calls: snapshot.batched_calls,
+            call_cache: snapshot.call_cache,
+            checkpoints_enabled: false,
+            instructions_since_checkpoint: 0,
+            allocation_site: None,
+            profiling,
+            #[cfg(feature = "vm-metrics")]
+            metrics: metrics::MetricsCollector::default(),
+            #[cfg(feature = "heap-validate")]
+            validate_every_instruction,
         }
     }
     /// Consumes the VM and creates a snapshot for pause/resume if needed.
     pub fn check_snapshot(mut self, result: &RunResult<FrameExit>) -> Option<VMSnapshot> {
+        #[cfg(feature = "heap-validate")]
+        if let Ok(exit) = result {
+            self.validate_heap(&exit.heap_ids());
+        }
         if matches!(
             result,
             Ok(FrameExit::ExternalCall { .. }
                 | FrameExit::OsCall { .. }
                 | FrameExit::MethodCall { .. }
-                | FrameExit::ResolveFutures(_))
+                | FrameExit::ResolveFutures(_)
+                | FrameExit::Yield { .. }
+                | FrameExit::Checkpoint)
         ) {
             Some(self.snapshot())
         } else {
@@ -624,9 +820,70 @@ impl<'a, 'p, T: ResourceTracker> VM<'a, 'p, T> {
             instruction_ip: self.instruction_ip,
             next_call_id: self.next_call_id,
             scheduler: self.scheduler,
+            lazy_fetch: self.lazy_fetch,
+            batched_calls: self.batched_calls,
+            call_cache: self.call_cache,
+        }
+    }
+
+    /// Pauses with `FrameExit::Checkpoint` every `checkpoint_interval` instructions of the tracker.
+    pub fn enable_checkpoints(&mut self) {
+        self.checkpoints_enabled = true;
+    }
+
+    /// Queues calls to external functions with an `async` signature instead of suspending.
+    ///
+    /// Each queued call returns a pending `ExternalFuture` straight away, as if the host had
+    /// called `run_pending()`, and is recorded in the snapshot taken when execution pauses.
+    pub fn enable_call_batching(&mut self) {
+        self.call_batching = true;
+    }
+
+    /// Attributes the heap allocations of the previous instruction to its source line.
+    ///
+    /// Called before each instruction, does nothing unless the heap traces allocations.
+    #[inline]
+    fn trace_allocation_site(&mut self, code: &'a Code, ip: usize) {
+        let Some(trace) = self.heap.allocation_trace_mut() else {
+            return;
+        };
+        let totals = trace.totals();
+        if let Some((prev_code, prev_ip, prev_totals)) = self.allocation_site.replace((code, ip, totals))
+            && totals.0 > prev_totals.0
+            && let Some(entry) = prev_code.location_for_offset(prev_ip)
+        {
+            let range = entry.range();
+            trace.record_site(
+                range.filename,
+                range.start().line,
+                totals.0 - prev_totals.0,
+                totals.1 - prev_totals.1,
+            );
         }
     }
 
+    /// Reports the instruction at `ip` to the tracker with its source line and function.
+    fn profile_instruction(&mut self, code: &'a Code, ip: usize) {
+        let Some(entry) = code.location_for_offset(ip) else {
+            return;
+        };
+        let range = entry.range();
+        let interns = self.interns;
+        let function = match self.current_frame().function_id {
+            Some(function_id) => interns.get_str(interns.get_function(function_id).name.name_id),
+            None => "<module>",
+        };
+        self.heap
+            .tracker_mut()
+            .on_instruction(interns.get_str(range.filename), range.start().line, function);
+    }
+
+    /// Takes the opcode, branch and call counters collected so far.
+    #[cfg(feature = "vm-metrics")]
+    pub fn take_metrics(&mut self) -> VmMetrics {
+        std::mem::take(&mut self.metrics).into_metrics(self.interns)
+    }
+
     /// Pushes an initial frame for module-level code and runs the VM.
     pub fn run_module(&mut self, code: &'a Code) -> Result<FrameExit, RunError> {
         // Store module code for restoring main task frames during task switching
@@ -720,6 +977,13 @@ impl<'a, 'p, T: ResourceTracker> VM<'a, 'p, T> {
     /// `frames.last_mut().expect()` calls during operand fetching. The cache
     /// is reloaded after any operation that modifies the frame stack.
     pub fn run(&mut self) -> Result<FrameExit, RunError> {
+        self.rust_stack_start = stack_address();
+        self.call_cache.forget_pending();
+        self.run_loop()
+    }
+
+    /// Body of [`Self::run`], also entered by `call_sync` to run a nested frame.
+    fn run_loop(&mut self) -> Result<FrameExit, RunError> {
         // Cache frame state locally to avoid repeated frames.last_mut() calls.
         // The Code reference has lifetime 'a (lives in Interns), independent of frame borrow.
         let mut cached_frame: CachedFrame<'a> = self.new_cached_frame();
@@ -729,21 +993,48 @@ impl<'a, 'p, T: ResourceTracker> VM<'a, 'p, T> {
             // For NoLimitTracker, these are inlined no-ops that compile away.
             self.heap.check_time()?;
 
-            if self.heap.should_gc() {
+            // Values held by a builtin during a nested call aren't GC roots, so skip collection
+            if self.sync_call_floor.is_none() && self.heap.should_gc() {
                 // Sync IP before GC for safety
                 self.current_frame_mut().ip = cached_frame.ip;
                 self.run_gc();
             }
 
+            // Pause at an instruction boundary so the host can serialize the state
+            if self.checkpoints_enabled
+                && self.sync_call_floor.is_none()
+                && let Some(interval) = self.heap.tracker().checkpoint_interval()
+            {
+                self.instructions_since_checkpoint += 1;
+                if self.instructions_since_checkpoint > interval {
+                    self.instructions_since_checkpoint = 0;
+                    self.current_frame_mut().ip = cached_frame.ip;
+                    return Ok(FrameExit::Checkpoint);
+                }
+            }
+
             // Track instruction IP for exception table lookup
             self.instruction_ip = cached_frame.ip;
 
+            // Values held by a builtin during a nested call aren't visible to the validator either
+            #[cfg(feature = "heap-validate")]
+            if self.validate_every_instruction && self.sync_call_floor.is_none() {
+                self.validate_heap(&[]);
+            }
+
+            self.trace_allocation_site(cached_frame.code, cached_frame.ip);
+            if self.profiling {
+                self.profile_instruction(cached_frame.code, cached_frame.ip);
+            }
+
             // Fetch opcode using cached values (no frame access)
             let opcode = {
                 let byte = cached_frame.code.bytecode()[cached_frame.ip];
                 cached_frame.ip += 1;
                 Opcode::try_from(byte).expect("invalid opcode in bytecode")
             };
+            #[cfg(feature = "vm-metrics")]
+            self.metrics.on_opcode(opcode);
 
             match opcode {
                 // ============================================================
@@ -828,15 +1119,36 @@ impl<'a, 'p, T: ResourceTracker> VM<'a, 'p, T> {
                     let slot = u16::from(fetch_u8!(cached_frame));
                     self.delete_local(&cached_frame, slot);
                 }
+                Opcode::DeleteLocalW => {
+                    let slot = fetch_u16!(cached_frame);
+                    self.delete_local(&cached_frame, slot);
+                }
                 // Variables - Global Operations
                 Opcode::LoadGlobal => {
                     let slot = fetch_u16!(cached_frame);
                     try_catch_sync!(self, cached_frame, self.load_global(slot));
                 }
+                Opcode::LoadGlobalOrBuiltin => {
+                    let slot = fetch_u16!(cached_frame);
+                    let const_idx = fetch_u16!(cached_frame);
+                    let value = self.namespaces.get(GLOBAL_NS_IDX).get(NamespaceId::new(slot as usize));
+                    if matches!(value, Value::Undefined) {
+                        // Builtins are immediate values, so copying from the constant pool is enough
+                        let builtin = cached_frame.code.constants().get(const_idx).copy_for_extend();
+                        self.push(builtin);
+                    } else {
+                        let value = value.clone_with_heap(self.heap);
+                        self.push(value);
+                    }
+                }
                 Opcode::StoreGlobal => {
                     let slot = fetch_u16!(cached_frame);
                     self.store_global(slot);
                 }
+                Opcode::DeleteGlobal => {
+                    let slot = fetch_u16!(cached_frame);
+                    self.delete_global(slot);
+                }
                 // Variables - Cell Operations (closures)
                 Opcode::LoadCell => {
                     let slot = fetch_u16!(cached_frame);
@@ -915,6 +1227,13 @@ impl<'a, 'p, T: ResourceTracker> VM<'a, 'p, T> {
                                     Ok(v) => self.push(v),
                                     Err(e) => catch_sync!(self, cached_frame, RunError::from(e)),
                                 }
+                            } else if let HeapData::Fraction(fraction) = self.heap.get(id) {
+                                let negated = -fraction.clone();
+                                value.drop_with_heap(self.heap);
+                                match negated.into_value(self.heap) {
+                                    Ok(v) => self.push(v),
+                                    Err(e) => catch_sync!(self, cached_frame, RunError::from(e)),
+                                }
                             } else {
                                 let value_type = value.py_type(self.heap);
                                 value.drop_with_heap(self.heap);
@@ -935,8 +1254,8 @@ impl<'a, 'p, T: ResourceTracker> VM<'a, 'p, T> {
                         Value::Int(_) | Value::Float(_) => self.push(value),
                         Value::Bool(b) => self.push(Value::Int(i64::from(b))),
                         Value::Ref(id) => {
-                            if matches!(self.heap.get(id), HeapData::LongInt(_)) {
-                                // LongInt - return as-is (value already has correct refcount)
+                            if matches!(self.heap.get(id), HeapData::LongInt(_) | HeapData::Fraction(_)) {
+                                // LongInt or Fraction - return as-is (value already has correct refcount)
                                 self.push(value);
                             } else {
                                 let value_type = value.py_type(self.heap);
@@ -981,19 +1300,19 @@ impl<'a, 'p, T: ResourceTracker> VM<'a, 'p, T> {
                 }
                 // In-place Operations - route through exception handling
                 Opcode::InplaceAdd => try_catch_sync!(self, cached_frame, self.inplace_add()),
+                Opcode::InplaceSub => try_catch_sync!(self, cached_frame, self.inplace_sub()),
                 // Other in-place ops use the same logic as binary ops for now
-                Opcode::InplaceSub => try_catch_sync!(self, cached_frame, self.binary_sub()),
                 Opcode::InplaceMul => try_catch_sync!(self, cached_frame, self.binary_mult()),
                 Opcode::InplaceDiv => try_catch_sync!(self, cached_frame, self.binary_div()),
                 Opcode::InplaceFloorDiv => try_catch_sync!(self, cached_frame, self.binary_floordiv()),
                 Opcode::InplaceMod => try_catch_sync!(self, cached_frame, self.binary_mod()),
                 Opcode::InplacePow => try_catch_sync!(self, cached_frame, self.binary_pow()),
                 Opcode::InplaceAnd => {
-                    try_catch_sync!(self, cached_frame, self.binary_bitwise(BitwiseOp::And));
+                    try_catch_sync!(self, cached_frame, self.inplace_bitwise(BitwiseOp::And));
                 }
-                Opcode::InplaceOr => try_catch_sync!(self, cached_frame, self.binary_bitwise(BitwiseOp::Or)),
+                Opcode::InplaceOr => try_catch_sync!(self, cached_frame, self.inplace_bitwise(BitwiseOp::Or)),
                 Opcode::InplaceXor => {
-                    try_catch_sync!(self, cached_frame, self.binary_bitwise(BitwiseOp::Xor));
+                    try_catch_sync!(self, cached_frame, self.inplace_bitwise(BitwiseOp::Xor));
                 }
                 Opcode::InplaceLShift => {
                     try_catch_sync!(self, cached_frame, self.binary_bitwise(BitwiseOp::LShift));
@@ -1039,6 +1358,12 @@ impl<'a, 'p, T: ResourceTracker> VM<'a, 'p, T> {
                     let func_name_id = fetch_u16!(cached_frame);
                     try_catch_sync!(self, cached_frame, self.dict_merge(func_name_id));
                 }
+                Opcode::SetUpdate => {
+                    try_catch_sync!(self, cached_frame, self.set_update());
+                }
+                Opcode::DictUpdate => {
+                    try_catch_sync!(self, cached_frame, self.dict_update());
+                }
                 // Comprehension Building - append/add/set items during iteration
                 Opcode::ListAppend => {
                     let depth = fetch_u8!(cached_frame) as usize;
@@ -1069,10 +1394,21 @@ impl<'a, 'p, T: ResourceTracker> VM<'a, 'p, T> {
                     let index = self.pop();
                     let mut obj = self.pop();
                     let value = self.pop();
+                    // Assignments through `globals()` also rebind the module variable
+                    let write_back = self
+                        .globals_view_slot(&obj, &index)
+                        .map(|slot| (slot, value.clone_with_heap(self.heap)));
                     let result = obj.py_setitem(index, value, self.heap, self.interns);
                     obj.drop_with_heap(self.heap);
-                    if let Err(e) = result {
-                        catch_sync!(self, cached_frame, e);
+                    match (result, write_back) {
+                        (Ok(()), Some((slot, value))) => self.write_back_global(slot, value),
+                        (Ok(()), None) => {}
+                        (Err(e), write_back) => {
+                            if let Some((_, value)) = write_back {
+                                value.drop_with_heap(self.heap);
+                            }
+                            catch_sync!(self, cached_frame, e);
+                        }
                     }
                 }
                 Opcode::LoadAttr => {
@@ -1098,7 +1434,9 @@ impl<'a, 'p, T: ResourceTracker> VM<'a, 'p, T> {
                 Opcode::JumpIfTrue => {
                     let offset = fetch_i16!(cached_frame);
                     let cond = self.pop();
-                    if cond.py_bool(self.heap, self.interns) {
+                    let taken = cond.py_bool(self.heap, self.interns);
+                    record_branch!(self, cached_frame, taken);
+                    if taken {
                         jump_relative!(cached_frame.ip, offset);
                     }
                     cond.drop_with_heap(self.heap);
@@ -1106,14 +1444,18 @@ impl<'a, 'p, T: ResourceTracker> VM<'a, 'p, T> {
                 Opcode::JumpIfFalse => {
                     let offset = fetch_i16!(cached_frame);
                     let cond = self.pop();
-                    if !cond.py_bool(self.heap, self.interns) {
+                    let taken = !cond.py_bool(self.heap, self.interns);
+                    record_branch!(self, cached_frame, taken);
+                    if taken {
                         jump_relative!(cached_frame.ip, offset);
                     }
                     cond.drop_with_heap(self.heap);
                 }
                 Opcode::JumpIfTrueOrPop => {
                     let offset = fetch_i16!(cached_frame);
-                    if self.peek().py_bool(self.heap, self.interns) {
+                    let taken = self.peek().py_bool(self.heap, self.interns);
+                    record_branch!(self, cached_frame, taken);
+                    if taken {
                         jump_relative!(cached_frame.ip, offset);
                     } else {
                         let value = self.pop();
@@ -1122,27 +1464,37 @@ impl<'a, 'p, T: ResourceTracker> VM<'a, 'p, T> {
                 }
                 Opcode::JumpIfFalseOrPop => {
                     let offset = fetch_i16!(cached_frame);
-                    if self.peek().py_bool(self.heap, self.interns) {
+                    let taken = !self.peek().py_bool(self.heap, self.interns);
+                    record_branch!(self, cached_frame, taken);
+                    if taken {
+                        jump_relative!(cached_frame.ip, offset);
+                    } else {
                         let value = self.pop();
                         value.drop_with_heap(self.heap);
-                    } else {
-                        jump_relative!(cached_frame.ip, offset);
                     }
                 }
                 // Iteration - route through exception handling
                 Opcode::GetIter => {
                     let value = self.pop();
-                    // Create a MontyIter from the value and store on heap
-                    match MontyIter::new(value, self.heap, self.interns) {
-                        Ok(iter) => match self.heap.allocate(HeapData::Iter(iter)) {
-                            Ok(heap_id) => self.push(Value::Ref(heap_id)),
-                            Err(e) => catch_sync!(self, cached_frame, e.into()),
-                        },
+                    // Same protocol as `iter()`: existing iterators are reused, anything else
+                    // iterable gets a new MontyIter on the heap
+                    match MontyIter::get_iter(value, self.heap, self.interns) {
+                        Ok(iter) => self.push(iter),
                         Err(e) => catch_sync!(self, cached_frame, e),
                     }
                 }
                 Opcode::ForIter => {
                     let offset = fetch_i16!(cached_frame);
+                    // Resuming after a lazy sequence fetch: the host's chunk is on top of the iterator
+                    if let Some(fetch) = self.lazy_fetch.take() {
+                        let chunk = self.pop();
+                        if let Err(e) = self.store_lazy_chunk(fetch, chunk) {
+                            let iter = self.pop();
+                            iter.drop_with_heap(self.heap);
+                            catch_sync!(self, cached_frame, e);
+                            continue;
+                        }
+                    }
                     // Peek at the iterator on TOS and extract heap_id
                     let Value::Ref(heap_id) = *self.peek() else {
                         return Err(RunError::internal("ForIter: expected iterator ref on stack"));
@@ -1159,6 +1511,19 @@ impl<'a, 'p, T: ResourceTracker> VM<'a, 'p, T> {
                             jump_relative!(cached_frame.ip, offset);
                         }
                         Err(e) => {
+                            // A lazy sequence ran out of fetched items: ask the host for the next
+                            // chunk and run this instruction again once it arrives
+                            if let Some((method_name, args)) = self.start_lazy_fetch(heap_id) {
+                                drop(e);
+                                cached_frame.ip = self.instruction_ip;
+                                self.current_frame_mut().ip = cached_frame.ip;
+                                let call_id = self.allocate_call_id();
+                                return Ok(FrameExit::MethodCall {
+                                    method_name,
+                                    args,
+                                    call_id,
+                                });
+                            }
                             // Error during iteration (e.g., dict size changed)
                             let iter = self.pop();
                             iter.drop_with_heap(self.heap);
@@ -1180,11 +1545,12 @@ impl<'a, 'p, T: ResourceTracker> VM<'a, 'p, T> {
                     let builtin_id = fetch_u8!(cached_frame);
                     let arg_count = fetch_u8!(cached_frame) as usize;
 
-                    match self.exec_call_builtin_function(builtin_id, arg_count) {
-                        Ok(result) => self.push(result),
-                        // IP sync deferred to error path (no frame push possible)
-                        Err(err) => catch_sync!(self, cached_frame, err),
-                    }
+                    // IP sync deferred to the error and host-call paths (no frame push possible)
+                    handle_call_result!(
+                        self,
+                        cached_frame,
+                        self.exec_call_builtin_function(builtin_id, arg_count)
+                    );
                 }
                 Opcode::CallBuiltinType => {
                     // Fetch operands: type_id (u8) + arg_count (u8)
@@ -1361,12 +1727,23 @@ impl<'a, 'p, T: ResourceTracker> VM<'a, 'p, T> {
                 // Return - reload cache after popping frame
                 Opcode::ReturnValue => {
                     let value = self.pop();
+                    if self.sync_call_floor == Some(self.frames.len() - 1) {
+                        // Nested call finished - hand the value back to `call_sync`
+                        self.pop_frame();
+                        return Ok(FrameExit::Return(value));
+                    }
                     if self.frames.len() == 1 {
                         // Last frame - check if this is main task or spawned task
                         let is_main_task = self.is_main_task();
 
                         if is_main_task {
-                            // Module-level return - we're done
+                            // Module-level return - we're done, unless the result is too large
+                            // to hand to the host
+                            if let Err(err) = OutputSize::check(&value, self.heap, self.interns) {
+                                value.drop_with_heap(self.heap);
+                                catch_sync!(self, cached_frame, err.into());
+                                continue;
+                            }
                             return Ok(FrameExit::Return(value));
                         }
 
@@ -1446,6 +1823,10 @@ impl<'a, 'p, T: ResourceTracker> VM<'a, 'p, T> {
                         Value::InternString(id) => self.interns.get_str(*id),
                         _ => "<unknown>",
                     };
+                    self.heap.tracker().audit(&AuditEvent::now(AuditEventKind::Import {
+                        module: name_str,
+                        allowed: false,
+                    }));
                     let error = ExcType::module_not_found_error(name_str);
                     catch_sync!(self, cached_frame, error);
                 }
@@ -1456,6 +1837,10 @@ impl<'a, 'p, T: ResourceTracker> VM<'a, 'p, T> {
     /// Loads a built-in module and pushes it onto the stack.
     fn load_module(&mut self, module_id: u8) -> RunResult<()> {
         let module = BuiltinModule::from_repr(module_id).expect("unknown module id");
+        self.heap.tracker().audit(&AuditEvent::now(AuditEventKind::Import {
+            module: module.name(),
+            allowed: true,
+        }));
 
         // Create the module on the heap using pre-interned strings
         let heap_id = module.create(self.heap, self.interns)?;
@@ -1467,6 +1852,7 @@ impl<'a, 'p, T: ResourceTracker> VM<'a, 'p, T> {
     ///
     /// Pushes the return value onto the stack and continues execution.
     pub fn resume(&mut self, obj: MontyObject) -> Result<FrameExit, RunError> {
+        self.record_call_result(&obj);
         let value = obj
             .to_value(self.heap, self.interns)
             .map_err(|e| SimpleException::new(ExcType::RuntimeError, Some(format!("invalid return type: {e}"))))?;
@@ -1479,6 +1865,8 @@ impl<'a, 'p, T: ResourceTracker> VM<'a, 'p, T> {
     /// Uses the exception handling mechanism to try to catch the exception.
     /// If caught, continues execution at the handler. If not, propagates the error.
     pub fn resume_with_exception(&mut self, error: RunError) -> Result<FrameExit, RunError> {
+        // A failed lazy sequence fetch raises at the `ForIter` that requested it
+        self.lazy_fetch = None;
         // Use the normal exception handling mechanism
         // handle_exception returns None if caught, Some(error) if not caught
         if let Some(uncaught_error) = self.handle_exception(error) {
@@ -1586,6 +1974,24 @@ impl<'a, 'p, T: ResourceTracker> VM<'a, 'p, T> {
         self.heap.collect_garbage(roots);
     }
 
+    /// Checks the Rust stack used since `run()` started against the `max_stack_size` limit.
+    ///
+    /// Called with the recursion depth check before each call frame is pushed, so deep
+    /// recursion raises `RecursionError` rather than overflowing the thread's stack.
+    pub(super) fn check_stack_size(&self) -> Result<(), ResourceError> {
+        if let Some(limit) = self.heap.tracker().max_stack_size() {
+            let used = self.rust_stack_start.abs_diff(stack_address());
+            if used > limit {
+                return Err(ResourceError::Stack {
+                    limit,
+                    used,
+                    depth: self.frames.len(),
+                });
+            }
+        }
+        Ok(())
+    }
+
     /// Returns the current source position for traceback generation.
     ///
     /// Uses `instruction_ip` which is set at the start of each instruction in the run loop,
@@ -1710,6 +2116,14 @@ impl<'a, 'p, T: ResourceTracker> VM<'a, 'p, T> {
         old_value.drop_with_heap(self.heap);
     }
 
+    /// Unbinds a global variable, dropping its current value.
+    fn delete_global(&mut self, slot: u16) {
+        let namespace = self.namespaces.get_mut(GLOBAL_NS_IDX);
+        let ns_slot = NamespaceId::new(slot as usize);
+        let old_value = std::mem::replace(namespace.get_mut(ns_slot), Value::Undefined);
+        old_value.drop_with_heap(self.heap);
+    }
+
     /// Loads from a closure cell and pushes onto the stack.
     ///
     /// Returns a NameError if the cell value is undefined (free variable not bound).
@@ -1720,7 +2134,7 @@ impl<'a, 'p, T: ResourceTracker> VM<'a, 'p, T> {
 
         // Check for undefined value - raise NameError for unbound free variable
         if matches!(value, Value::Undefined) {
-            let name = self.current_frame().code.local_name(slot);
+            let name = self.current_frame().code.cell_name(slot);
             return Err(self.free_var_error(name));
         }
 
@@ -177,14 +177,18 @@ pub(crate) struct Scheduler {
     next_call_id: u32,
     /// Maps CallId -> pending call data for unresolved external calls.
     /// Populated when host calls `run_pending()`.
+    #[serde(serialize_with = "crate::sorted_serde::serialize_map")]
     pending_calls: AHashMap<CallId, PendingCallData>,
     /// Maps CallId -> resolved Value for futures that have been resolved.
     /// Entry is removed when the value is consumed by awaiting.
+    #[serde(serialize_with = "crate::sorted_serde::serialize_map")]
     resolved: AHashMap<CallId, Value>,
     /// CallIds that have been awaited (to detect double-await).
+    #[serde(serialize_with = "crate::sorted_serde::serialize_set")]
     consumed: AHashSet<CallId>,
     /// Maps CallId -> (gather_heap_id, result_index) for gathers waiting on external futures.
     /// When a CallId is resolved, the result is stored in the gather's results at the given index.
+    #[serde(serialize_with = "crate::sorted_serde::serialize_map")]
     gather_waiters: AHashMap<CallId, (HeapId, usize)>,
 }
 
@@ -594,6 +598,27 @@ impl Scheduler {
         matches!(self.tasks.get(task_id.raw() as usize), Some(task) if matches!(task.state, TaskState::Failed(_)))
     }
 
+    /// Appends the heap references owned by the scheduler to `ids`, for heap validation.
+    ///
+    /// Covers the same values as `cleanup` and the cells of saved task frames. Task
+    /// bookkeeping like a task's coroutine or gather isn't included.
+    #[cfg(feature = "heap-validate")]
+    pub fn collect_owned_heap_ids(&self, ids: &mut Vec<HeapId>) {
+        for data in self.pending_calls.values() {
+            data.args.collect_heap_ids(ids);
+        }
+        ids.extend(self.resolved.values().filter_map(Value::ref_id));
+        for task in &self.tasks {
+            ids.extend(task.stack.iter().chain(&task.exception_stack).filter_map(Value::ref_id));
+            for frame in &task.frames {
+                ids.extend(&frame.cells);
+            }
+            if let TaskState::Completed(value) = &task.state {
+                ids.extend(value.ref_id());
+            }
+        }
+    }
+
     /// Cleans up resources when dropping the scheduler.
     ///
     /// Drops any pending call arguments, resolved values, and task state.
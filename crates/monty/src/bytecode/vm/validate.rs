@@ -0,0 +1,61 @@
+//! Heap invariant checks, only compiled with the `heap-validate` feature.
+//!
+//! Reference counting bugs usually surface far from their cause, as a `ref-count-panic` on
+//! drop or a wrong value much later. Validating the heap while the program runs reports the
+//! first instruction after which the heap is inconsistent instead.
+
+use super::{FrameExit, VM};
+use crate::{heap::HeapId, resource::ResourceTracker, value::Value};
+
+impl<T: ResourceTracker> VM<'_, '_, T> {
+    /// Checks the heap's invariants, panicking with a description of the first violation.
+    ///
+    /// `held` are references owned outside the VM, e.g. by the `FrameExit` being returned.
+    ///
+    /// # Panics
+    /// Panics if the heap is inconsistent, see `Heap::validate` for what's checked.
+    pub(super) fn validate_heap(&mut self, held: &[HeapId]) {
+        let mut roots = held.to_vec();
+        roots.extend(self.stack.iter().chain(&self.exception_stack).filter_map(Value::ref_id));
+        for frame in &self.frames {
+            roots.extend(&frame.cells);
+        }
+        roots.extend(self.namespaces.iter_owned_heap_ids());
+        if let Some(scheduler) = &self.scheduler {
+            scheduler.collect_owned_heap_ids(&mut roots);
+        }
+        // Tasks refer to their coroutine and gather without counting the reference, so with
+        // async tasks around only references missing from refcounts are reported, not leaks
+        let exact_refcounts = self.scheduler.is_none();
+
+        if let Err(message) = self.heap.validate(&roots, exact_refcounts, self.interns) {
+            let location = self
+                .frames
+                .last()
+                .and_then(|frame| frame.code.location_for_offset(self.instruction_ip))
+                .map(|entry| {
+                    let range = entry.range();
+                    format!("{}:{}", self.interns.get_str(range.filename), range.start().line)
+                });
+            panic!(
+                "heap validation failed at {}: {message}",
+                location.as_deref().unwrap_or("<unknown>")
+            );
+        }
+    }
+}
+
+impl FrameExit {
+    /// The heap references held by the exit, e.g. the arguments of an external call.
+    pub(super) fn heap_ids(&self) -> Vec<HeapId> {
+        let mut ids = Vec::new();
+        match self {
+            Self::Return(value) | Self::Yield { value, .. } => ids.extend(value.ref_id()),
+            Self::ExternalCall { args, .. } | Self::OsCall { args, .. } | Self::MethodCall { args, .. } => {
+                args.collect_heap_ids(&mut ids);
+            }
+            Self::ResolveFutures(_) | Self::Checkpoint => {}
+        }
+        ids
+    }
+}
@@ -0,0 +1,92 @@
+//! Randomness for sandboxed code, e.g. the `secrets` module.
+//!
+//! Sandboxed code gets no randomness unless the host enables it with
+//! [`ResourceLimits::entropy`](crate::ResourceLimits::entropy), choosing an [`EntropyPolicy`]:
+//! real entropy from the operating system, or a deterministic stream derived from a seed for
+//! reproducible runs and tests. Like the hash seed, the policy is read once when the heap is
+//! created and is stored in snapshots.
+
+use std::hash::Hasher;
+
+use crate::hashing::StableHasher;
+
+/// Where sandboxed code's randomness comes from, see [`ResourceLimits::entropy`](crate::ResourceLimits::entropy).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum EntropyPolicy {
+    /// No randomness, functions which need it raise `NotImplementedError`. The default.
+    #[default]
+    Denied,
+    /// Real entropy from the operating system.
+    Os,
+    /// A deterministic stream derived from the seed, so runs with the same seed produce the
+    /// same values. Anyone who knows the seed can predict every value, so it's only for tests
+    /// and reproducible runs, not for secrets.
+    Seeded(u64),
+}
+
+/// Why random bytes couldn't be produced.
+#[derive(Debug)]
+pub(crate) enum EntropyError {
+    /// The host's policy doesn't allow randomness.
+    Denied,
+    /// The operating system's entropy source failed.
+    Os(getrandom::Error),
+}
+
+/// A heap's source of random bytes, created from the tracker's [`EntropyPolicy`].
+///
+/// Serialized with the heap, so a seeded stream continues where it left off on resume rather
+/// than repeating values. Only the policy is stored for operating system entropy.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub(crate) enum Entropy {
+    Denied,
+    Os,
+    /// SipHash of a counter keyed with the seed; `counter` is the next block to produce.
+    Seeded {
+        seed: u64,
+        counter: u64,
+    },
+}
+
+impl Entropy {
+    pub fn new(policy: EntropyPolicy) -> Self {
+        match policy {
+            EntropyPolicy::Denied => Self::Denied,
+            EntropyPolicy::Os => Self::Os,
+            EntropyPolicy::Seeded(seed) => Self::Seeded { seed, counter: 0 },
+        }
+    }
+
+    /// Fills `buf` with random bytes.
+    pub fn fill(&mut self, buf: &mut [u8]) -> Result<(), EntropyError> {
+        match self {
+            Self::Denied => Err(EntropyError::Denied),
+            Self::Os => getrandom::fill(buf).map_err(EntropyError::Os),
+            Self::Seeded { seed, counter } => {
+                for block in buf.chunks_mut(8) {
+                    let mut hasher = StableHasher::with_key(*seed);
+                    hasher.write_u64(*counter);
+                    *counter = counter.wrapping_add(1);
+                    block.copy_from_slice(&hasher.finish().to_le_bytes()[..block.len()]);
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Returns a uniformly distributed random integer in `0..bound`, which must be positive.
+    ///
+    /// Uses rejection sampling of `bound.bit_length()` random bits, like CPython's `randbelow()`.
+    pub fn below(&mut self, bound: u64) -> Result<u64, EntropyError> {
+        debug_assert!(bound > 0, "bound must be positive");
+        let mask = u64::MAX >> bound.leading_zeros();
+        loop {
+            let mut bytes = [0; 8];
+            self.fill(&mut bytes)?;
+            let candidate = u64::from_le_bytes(bytes) & mask;
+            if candidate < bound {
+                return Ok(candidate);
+            }
+        }
+    }
+}
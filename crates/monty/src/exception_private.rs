@@ -10,17 +10,18 @@ use strum::{Display, EnumString, IntoStaticStr};
 use crate::{
     args::ArgValues,
     defer_drop,
-    exception_public::{MontyException, StackFrame},
+    exception_public::{HostError, MontyException, StackFrame},
     fstring::FormatError,
     heap::{Heap, HeapData},
     intern::{Interns, StaticStrings, StringId},
+    object::{InvalidInputError, MontyObject},
     parse::CodeRange,
-    resource::{DepthGuard, ResourceTracker},
+    resource::ResourceTracker,
     types::{
-        AttrCallResult, PyTrait, Str, Type, allocate_tuple,
+        AttrCallResult, List, PyTrait, Str, Type, allocate_tuple,
         str::{StringRepr, string_repr_fmt},
     },
-    value::Value,
+    value::{EitherStr, Value},
 };
 
 /// Result type alias for operations that can produce a runtime error.
@@ -78,6 +79,9 @@ pub enum ExcType {
     ValueError,
     /// Subclass of ValueError - for encoding/decoding errors.
     UnicodeDecodeError,
+    /// Subclass of ValueError - `statistics.StatisticsError`, for data a statistic can't be computed from.
+    #[strum(serialize = "statistics.StatisticsError")]
+    StatisticsError,
 
     // --- ImportError hierarchy ---
     /// Import-related errors (module not found, name not in module).
@@ -96,14 +100,28 @@ pub enum ExcType {
     IsADirectoryError,
     /// Subclass of OSError - for when a path is not a directory but one was expected.
     NotADirectoryError,
+    /// Subclass of OSError - `gzip.BadGzipFile`, for data which isn't valid gzip.
+    #[strum(serialize = "gzip.BadGzipFile")]
+    BadGzipFile,
 
     // --- Standalone exception types ---
     AssertionError,
+    /// Raised by `input()` when the host reaches end of input.
+    EOFError,
     MemoryError,
     StopIteration,
     SyntaxError,
     TimeoutError,
+    /// Subclass of TimeoutError - raised by the bindings when an external function call
+    /// runs out of time on every attempt its call policy allows.
+    ExternalCallTimeout,
     TypeError,
+    /// `csv.Error`, raised for malformed CSV data and invalid dialects.
+    #[strum(serialize = "_csv.Error")]
+    CsvError,
+    /// `zlib.error`, raised for corrupt compressed data and invalid compression parameters.
+    #[strum(serialize = "zlib.error")]
+    ZlibError,
 }
 
 impl ExcType {
@@ -136,15 +154,21 @@ impl ExcType {
             Self::AttributeError => matches!(self, Self::FrozenInstanceError),
             // NameError catches UnboundLocalError
             Self::NameError => matches!(self, Self::UnboundLocalError),
-            // ValueError catches UnicodeDecodeError
-            Self::ValueError => matches!(self, Self::UnicodeDecodeError),
+            // ValueError catches UnicodeDecodeError and StatisticsError
+            Self::ValueError => matches!(self, Self::UnicodeDecodeError | Self::StatisticsError),
             // ImportError catches ModuleNotFoundError
             Self::ImportError => matches!(self, Self::ModuleNotFoundError),
-            // OSError catches FileNotFoundError, FileExistsError, IsADirectoryError, NotADirectoryError
+            // OSError catches FileNotFoundError, FileExistsError, IsADirectoryError, NotADirectoryError, BadGzipFile
             Self::OSError => matches!(
                 self,
-                Self::FileNotFoundError | Self::FileExistsError | Self::IsADirectoryError | Self::NotADirectoryError
+                Self::FileNotFoundError
+                    | Self::FileExistsError
+                    | Self::IsADirectoryError
+                    | Self::NotADirectoryError
+                    | Self::BadGzipFile
             ),
+            // TimeoutError catches ExternalCallTimeout
+            Self::TimeoutError => matches!(self, Self::ExternalCallTimeout),
             // All other types only match exactly (handled by self == handler_type above)
             _ => false,
         }
@@ -163,30 +187,12 @@ impl ExcType {
         args: ArgValues,
         interns: &Interns,
     ) -> RunResult<Value> {
-        defer_drop!(args, heap);
-        let exc = match args {
-            ArgValues::Empty => Ok(SimpleException::new_none(self)),
-            ArgValues::One(value) => match value {
-                Value::InternString(string_id) => {
-                    Ok(SimpleException::new_msg(self, interns.get_str(*string_id).to_owned()))
-                }
-                Value::Ref(heap_id) => {
-                    if let HeapData::Str(s) = heap.get(*heap_id) {
-                        Ok(SimpleException::new_msg(self, s.as_str().to_owned()))
-                    } else {
-                        Err(RunError::internal(
-                            "exceptions can only be called with zero or one string argument",
-                        ))
-                    }
-                }
-                _ => Err(RunError::internal(
-                    "exceptions can only be called with zero or one string argument",
-                )),
-            },
-            _ => Err(RunError::internal(
-                "exceptions can only be called with zero or one string argument",
-            )),
-        }?;
+        let name: &'static str = self.into();
+        let args = args
+            .into_pos_only(name, heap)?
+            .map(|arg| MontyObject::new(arg, heap, interns))
+            .collect();
+        let exc = SimpleException::with_args(self, args);
         let heap_id = heap.allocate(HeapData::Exception(exc))?;
         Ok(Value::Ref(heap_id))
     }
@@ -286,11 +292,14 @@ impl ExcType {
     ///
     /// This matches Python 3.14's error message:
     /// `TypeError: cannot use 'list' as a dict key (unhashable type: 'list')`
+    ///
+    /// `unhashable` is the type that actually prevents hashing, which differs from
+    /// `type_` for tuples containing unhashable elements.
     #[must_use]
-    pub(crate) fn type_error_unhashable_dict_key(type_: Type) -> RunError {
+    pub(crate) fn type_error_unhashable_dict_key(type_: Type, unhashable: Type) -> RunError {
         SimpleException::new_msg(
             Self::TypeError,
-            format!("cannot use '{type_}' as a dict key (unhashable type: '{type_}')"),
+            format!("cannot use '{type_}' as a dict key (unhashable type: '{unhashable}')"),
         )
         .into()
     }
@@ -299,23 +308,24 @@ impl ExcType {
     ///
     /// This matches Python 3.14's error message:
     /// `TypeError: cannot use 'list' as a set element (unhashable type: 'list')`
+    ///
+    /// `unhashable` is the type that actually prevents hashing, which differs from
+    /// `type_` for tuples containing unhashable elements.
     #[must_use]
-    pub(crate) fn type_error_unhashable_set_element(type_: Type) -> RunError {
+    pub(crate) fn type_error_unhashable_set_element(type_: Type, unhashable: Type) -> RunError {
         SimpleException::new_msg(
             Self::TypeError,
-            format!("cannot use '{type_}' as a set element (unhashable type: '{type_}')"),
+            format!("cannot use '{type_}' as a set element (unhashable type: '{unhashable}')"),
         )
         .into()
     }
 
     /// Creates a KeyError for a missing dict key.
     ///
-    /// For string keys, uses the raw string value without extra quoting.
+    /// The key is kept as the exception's only argument, so `str()` shows its repr like CPython.
     #[must_use]
     pub(crate) fn key_error(key: &Value, heap: &Heap<impl ResourceTracker>, interns: &Interns) -> RunError {
-        let mut guard = DepthGuard::default();
-        let key_str = key.py_str(heap, &mut guard, interns).into_owned();
-        SimpleException::new_msg(Self::KeyError, key_str).into()
+        SimpleException::with_args(Self::KeyError, vec![MontyObject::from_value(key, heap, interns)]).into()
     }
 
     /// Creates a KeyError for popping from an empty set.
@@ -396,10 +406,11 @@ impl ExcType {
     /// * `actual` - Number of arguments actually provided
     #[must_use]
     pub(crate) fn type_error_at_most(name: &str, max: usize, actual: usize) -> RunError {
-        // CPython: "get expected at most 2 arguments, got 3"
+        // CPython: "get expected at most 2 arguments, got 3", "list expected at most 1 argument, got 2"
+        let s = if max == 1 { "" } else { "s" };
         SimpleException::new_msg(
             Self::TypeError,
-            format!("{name} expected at most {max} arguments, got {actual}"),
+            format!("{name} expected at most {max} argument{s}, got {actual}"),
         )
         .into()
     }
@@ -450,52 +461,56 @@ impl ExcType {
 
     /// Creates a TypeError for too many positional arguments.
     ///
-    /// Matches CPython's format:
-    /// - Simple: `{name}() takes {max} positional argument(s) but {actual} were given`
-    /// - With kwonly: `{name}() takes {max} positional argument(s) but {actual} positional argument(s) (and N keyword-only argument(s)) were given`
+    /// `min` is the number of required positional parameters and `max` the total number of
+    /// positional parameters; when they differ the accepted range is shown. `kwonly_given`
+    /// counts keyword-only parameters that were passed, which CPython includes in the message.
+    ///
+    /// Matches CPython's formats:
+    /// - `{name}() takes 2 positional arguments but 3 were given`
+    /// - `{name}() takes from 1 to 2 positional arguments but 3 were given`
+    /// - `{name}() takes 1 positional argument but 2 positional arguments (and 1 keyword-only argument) were given`
     #[must_use]
     pub(crate) fn type_error_too_many_positional(
         name: &str,
+        min: usize,
         max: usize,
-        actual: usize,
+        given: usize,
         kwonly_given: usize,
     ) -> RunError {
-        let takes_word = if max == 1 { "argument" } else { "arguments" };
-
-        if kwonly_given > 0 {
-            // CPython includes keyword-only args in the "given" part when present
-            let given_word = if actual == 1 { "argument" } else { "arguments" };
-            let kwonly_word = if kwonly_given == 1 { "argument" } else { "arguments" };
-            SimpleException::new_msg(
-                Self::TypeError,
-                format!(
-                    "{name}() takes {max} positional {takes_word} but {actual} positional {given_word} (and {kwonly_given} keyword-only {kwonly_word}) were given"
-                ),
-            )
-            .into()
-        } else if max == 0 {
-            SimpleException::new_msg(
-                Self::TypeError,
-                format!("{name}() takes 0 positional arguments but {actual} were given"),
-            )
-            .into()
+        let takes = if min == max {
+            format!("{max} positional argument{}", if max == 1 { "" } else { "s" })
         } else {
-            SimpleException::new_msg(
-                Self::TypeError,
-                format!("{name}() takes {max} positional {takes_word} but {actual} were given"),
+            format!("from {min} to {max} positional arguments")
+        };
+        let given_str = if kwonly_given > 0 {
+            format!(
+                "{given} positional argument{} (and {kwonly_given} keyword-only argument{})",
+                if given == 1 { "" } else { "s" },
+                if kwonly_given == 1 { "" } else { "s" }
             )
-            .into()
-        }
+        } else {
+            given.to_string()
+        };
+        let verb = if given == 1 && kwonly_given == 0 { "was" } else { "were" };
+        SimpleException::new_msg(
+            Self::TypeError,
+            format!("{name}() takes {takes} but {given_str} {verb} given"),
+        )
+        .into()
     }
 
-    /// Creates a TypeError for positional-only parameter passed as keyword.
+    /// Creates a TypeError for positional-only parameters passed as keywords.
     ///
-    /// Matches CPython's format: `{name}() got some positional-only arguments passed as keyword arguments: '{param}'`
+    /// Matches CPython's format, which lists every offending name inside one pair of quotes:
+    /// `{name}() got some positional-only arguments passed as keyword arguments: 'a, b'`
     #[must_use]
-    pub(crate) fn type_error_positional_only(name: &str, param: &str) -> RunError {
+    pub(crate) fn type_error_positional_only(name: &str, params: &[&str]) -> RunError {
         SimpleException::new_msg(
             Self::TypeError,
-            format!("{name}() got some positional-only arguments passed as keyword arguments: '{param}'"),
+            format!(
+                "{name}() got some positional-only arguments passed as keyword arguments: '{}'",
+                params.join(", ")
+            ),
         )
         .into()
     }
@@ -594,6 +609,42 @@ impl ExcType {
         SimpleException::new_msg(Self::TypeError, format!("'{type_}' object is not iterable")).into()
     }
 
+    /// Creates a TypeError for the right operand of `in` that isn't a container.
+    ///
+    /// Matches CPython's format: `TypeError: argument of type '{type}' is not iterable`
+    #[must_use]
+    pub(crate) fn type_error_argument_not_iterable(type_: Type) -> RunError {
+        SimpleException::new_msg(Self::TypeError, format!("argument of type '{type_}' is not iterable")).into()
+    }
+
+    /// Creates a TypeError for `*value` unpacking of a non-iterable value.
+    ///
+    /// Matches CPython's format: `TypeError: Value after * must be an iterable, not {type}`
+    #[must_use]
+    pub(crate) fn type_error_star_not_iterable(type_: Type) -> RunError {
+        SimpleException::new_msg(
+            Self::TypeError,
+            format!("Value after * must be an iterable, not {type_}"),
+        )
+        .into()
+    }
+
+    /// Creates a TypeError for `**value` unpacking of a value that is not a mapping.
+    ///
+    /// Matches CPython's format: `TypeError: '{type}' object is not a mapping`
+    #[must_use]
+    pub(crate) fn type_error_not_mapping(type_: Type) -> RunError {
+        SimpleException::new_msg(Self::TypeError, format!("'{type_}' object is not a mapping")).into()
+    }
+
+    /// Creates a TypeError for calling `next()` on a value that is not an iterator.
+    ///
+    /// Matches CPython's format: `TypeError: '{type}' object is not an iterator`
+    #[must_use]
+    pub(crate) fn type_error_not_iterator(type_: Type) -> RunError {
+        SimpleException::new_msg(Self::TypeError, format!("'{type_}' object is not an iterator")).into()
+    }
+
     /// Creates a TypeError for int() constructor with invalid type.
     ///
     /// Matches CPython's format: `TypeError: int() argument must be a string, a bytes-like object or a real number, not '{type}'`
@@ -638,6 +689,26 @@ impl ExcType {
         .into()
     }
 
+    /// Creates a TypeError for issubclass() arg 1.
+    ///
+    /// Matches CPython's format: `TypeError: issubclass() arg 1 must be a class`
+    #[must_use]
+    pub(crate) fn issubclass_arg1_error() -> RunError {
+        SimpleException::new_msg(Self::TypeError, "issubclass() arg 1 must be a class").into()
+    }
+
+    /// Creates a TypeError for issubclass() arg 2.
+    ///
+    /// Matches CPython's format: `TypeError: issubclass() arg 2 must be a class, a tuple of classes, or a union`
+    #[must_use]
+    pub(crate) fn issubclass_arg2_error() -> RunError {
+        SimpleException::new_msg(
+            Self::TypeError,
+            "issubclass() arg 2 must be a class, a tuple of classes, or a union",
+        )
+        .into()
+    }
+
     /// Creates a TypeError for invalid exception type in except clause.
     ///
     /// Matches CPython's format: `TypeError: catching classes that do not inherit from BaseException is not allowed`
@@ -666,6 +737,46 @@ impl ExcType {
         SimpleException::new_msg(Self::ValueError, "slice step cannot be zero").into()
     }
 
+    /// Creates a ValueError for a list mutated by a key function while it was being sorted.
+    ///
+    /// Matches CPython's format: `ValueError: list modified during sort`
+    #[must_use]
+    pub(crate) fn value_error_list_modified_during_sort() -> RunError {
+        SimpleException::new_msg(Self::ValueError, "list modified during sort").into()
+    }
+
+    /// Creates a ValueError for `zip(strict=True)` iterables of different lengths.
+    ///
+    /// `index` is the zero-based position of the mismatched iterable, compared against all
+    /// iterables before it.
+    /// Matches CPython's format: `ValueError: zip() argument 3 is shorter than arguments 1-2`
+    #[must_use]
+    pub(crate) fn value_error_zip_length_mismatch(index: usize, longer: bool) -> RunError {
+        let relation = if longer { "longer" } else { "shorter" };
+        let previous = if index == 1 {
+            " 1".to_owned()
+        } else {
+            format!("s 1-{index}")
+        };
+        SimpleException::new_msg(
+            Self::ValueError,
+            format!("zip() argument {} is {relation} than argument{previous}", index + 1),
+        )
+        .into()
+    }
+
+    /// Creates a TypeError for a keyword argument a builtin doesn't accept.
+    ///
+    /// Matches CPython's format: `TypeError: '{key}' is an invalid keyword argument for {name}()`
+    #[must_use]
+    pub(crate) fn type_error_invalid_keyword(name: &str, key: &str) -> RunError {
+        SimpleException::new_msg(
+            Self::TypeError,
+            format!("'{key}' is an invalid keyword argument for {name}()"),
+        )
+        .into()
+    }
+
     /// Creates a TypeError for slice indices that are not integers or None.
     ///
     /// Matches CPython's format: `TypeError: slice indices must be integers or None or have an __index__ method`
@@ -833,6 +944,43 @@ impl ExcType {
         SimpleException::new_msg(Self::NotImplementedError, msg)
     }
 
+    /// Creates a NotImplementedError for a lazy sequence consumed past its fetched items
+    /// outside a `for` loop or comprehension.
+    ///
+    /// Monty-specific: builtins run synchronously, so they can't pause to fetch a chunk.
+    #[must_use]
+    pub(crate) fn lazy_sequence_sync_fetch(name: &str) -> RunError {
+        SimpleException::new_msg(
+            Self::NotImplementedError,
+            format!("lazy sequence '{name}' can only be fetched by a for loop or comprehension"),
+        )
+        .into()
+    }
+
+    /// Creates a TypeError for a lazy sequence chunk that isn't a list.
+    ///
+    /// Monty-specific: `TypeError: lazy sequence chunk must be a list, not 'int'`
+    #[must_use]
+    pub(crate) fn lazy_sequence_chunk_type_error(type_: Type) -> RunError {
+        SimpleException::new_msg(
+            Self::TypeError,
+            format!("lazy sequence chunk must be a list, not '{type_}'"),
+        )
+        .into()
+    }
+
+    /// Creates a ValueError for a lazy sequence chunk with the wrong number of items.
+    ///
+    /// Monty-specific: `ValueError: lazy sequence 'name' chunk 0:10 returned 12 items`
+    #[must_use]
+    pub(crate) fn lazy_sequence_chunk_error(name: &str, start: usize, stop: usize, count: usize) -> RunError {
+        SimpleException::new_msg(
+            Self::ValueError,
+            format!("lazy sequence '{name}' chunk {start}:{stop} returned {count} items"),
+        )
+        .into()
+    }
+
     /// Creates a ZeroDivisionError for division by zero.
     ///
     /// Matches CPython 3.14's format: `ZeroDivisionError('division by zero')`
@@ -1068,16 +1216,36 @@ impl ExcType {
         SimpleException::new_msg(Self::LookupError, format!("unknown encoding: {encoding}")).into()
     }
 
-    /// Creates a UnicodeDecodeError for invalid UTF-8 bytes in decode().
-    ///
-    /// Matches CPython's format: `UnicodeDecodeError: 'utf-8' codec can't decode bytes...`
-    #[must_use]
-    pub(crate) fn unicode_decode_error_invalid_utf8() -> RunError {
-        SimpleException::new_msg(
-            Self::UnicodeDecodeError,
-            "'utf-8' codec can't decode bytes: invalid utf-8 sequence",
-        )
-        .into()
+    /// Creates a UnicodeDecodeError for invalid UTF-8 bytes in decode() and str(bytes, encoding).
+    ///
+    /// Matches CPython's format, e.g.
+    /// `UnicodeDecodeError: 'utf-8' codec can't decode byte 0xff in position 0: invalid start byte`
+    #[must_use]
+    pub(crate) fn unicode_decode_error_invalid_utf8(bytes: &[u8], error: &std::str::Utf8Error) -> RunError {
+        let start = error.valid_up_to();
+        let first = bytes[start];
+        let msg = match error.error_len() {
+            // An invalid sequence: either a byte that can't start a character, or a valid
+            // start byte followed by a bad continuation byte
+            Some(_) => {
+                let reason = if matches!(first, 0xC2..=0xF4) {
+                    "invalid continuation byte"
+                } else {
+                    "invalid start byte"
+                };
+                format!("'utf-8' codec can't decode byte {first:#04x} in position {start}: {reason}")
+            }
+            // The input ends partway through a character
+            None => {
+                let end = bytes.len() - 1;
+                if end == start {
+                    format!("'utf-8' codec can't decode byte {first:#04x} in position {start}: unexpected end of data")
+                } else {
+                    format!("'utf-8' codec can't decode bytes in position {start}-{end}: unexpected end of data")
+                }
+            }
+        };
+        SimpleException::new_msg(Self::UnicodeDecodeError, msg).into()
     }
 
     /// Creates a ValueError for subsequence not found in bytes/str.
@@ -1104,7 +1272,26 @@ impl ExcType {
 #[derive(Debug, Clone, PartialEq, Hash, serde::Serialize, serde::Deserialize)]
 pub(crate) struct SimpleException {
     exc_type: ExcType,
+    /// The message, which is `str(exc)` for every type except `KeyError`.
     arg: Option<String>,
+    /// The arguments, when they are more than a single `str` message, e.g. `ValueError(1, 2)`.
+    ///
+    /// Empty when `exc.args` is `()` or `(arg,)`, which covers nearly every exception Monty
+    /// raises itself. Stored as `MontyObject`s since exceptions outlive the heap values.
+    #[serde(default)]
+    args: Vec<MontyObject>,
+    /// `OSError.filename`, which CPython leaves out of `args`.
+    #[serde(default)]
+    filename: Option<Box<MontyObject>>,
+    /// Notes attached via `exc.add_note(...)` (PEP 678), rendered after the message in tracebacks.
+    ///
+    /// Empty for almost all exceptions, so this costs nothing but an empty `Vec` in the common case.
+    notes: Vec<String>,
+    /// The host exception this was converted from, when raised by an external function.
+    ///
+    /// Not visible to sandboxed code, only carried so it can be returned to the embedder.
+    #[serde(default)]
+    host_error: Option<Box<HostError>>,
 }
 
 impl fmt::Display for SimpleException {
@@ -1114,10 +1301,17 @@ impl fmt::Display for SimpleException {
 }
 impl From<MontyException> for SimpleException {
     fn from(exc: MontyException) -> Self {
-        Self {
-            exc_type: exc.exc_type(),
-            arg: exc.into_message(),
-        }
+        let (exc_type, arg, notes, host_error) = exc.into_parts();
+        let mut exc = Self {
+            exc_type,
+            arg,
+            args: Vec::new(),
+            filename: None,
+            notes,
+            host_error,
+        };
+        exc.parse_errno_message();
+        exc
     }
 }
 
@@ -1125,7 +1319,14 @@ impl SimpleException {
     /// Creates a new exception with the given type and optional argument message.
     #[must_use]
     pub fn new(exc_type: ExcType, arg: Option<String>) -> Self {
-        Self { exc_type, arg }
+        Self {
+            exc_type,
+            arg,
+            args: Vec::new(),
+            filename: None,
+            notes: Vec::new(),
+            host_error: None,
+        }
     }
 
     /// Creates a new exception with the given type and argument message.
@@ -1134,13 +1335,115 @@ impl SimpleException {
         Self {
             exc_type,
             arg: Some(arg.to_string()),
+            args: Vec::new(),
+            filename: None,
+            notes: Vec::new(),
+            host_error: None,
         }
     }
 
     /// Creates a new exception with the given type and no argument message.
     #[must_use]
     pub fn new_none(exc_type: ExcType) -> Self {
-        Self { exc_type, arg: None }
+        Self {
+            exc_type,
+            arg: None,
+            args: Vec::new(),
+            filename: None,
+            notes: Vec::new(),
+            host_error: None,
+        }
+    }
+
+    /// Creates an exception from the arguments it was called with, e.g. `ValueError(1, 2)`.
+    ///
+    /// A single `str` argument is just the message, as from [`new_msg`](Self::new_msg).
+    /// Like CPython, an `OSError` with 2 to 5 arguments takes them as `errno, strerror,
+    /// filename`, and `OSError` itself becomes the subclass matching a few error numbers.
+    pub(crate) fn with_args(exc_type: ExcType, mut args: Vec<MontyObject>) -> Self {
+        match args.as_slice() {
+            [] => return Self::new_none(exc_type),
+            [MontyObject::String(_) | MontyObject::SharedString(_)] => {
+                let message = args.pop().expect("one argument").to_string();
+                return Self::new_msg(exc_type, message);
+            }
+            _ => {}
+        }
+
+        let mut exc_type = exc_type;
+        let mut filename = None;
+        if exc_type.is_subclass_of(ExcType::OSError) && (2..=5).contains(&args.len()) {
+            if exc_type == ExcType::OSError
+                && let MontyObject::Int(errno) = args[0]
+            {
+                exc_type = match errno {
+                    2 => ExcType::FileNotFoundError,
+                    17 => ExcType::FileExistsError,
+                    20 => ExcType::NotADirectoryError,
+                    21 => ExcType::IsADirectoryError,
+                    _ => ExcType::OSError,
+                };
+            }
+            if args.len() > 2 && !matches!(args[2], MontyObject::None) {
+                filename = Some(Box::new(args.remove(2)));
+                args.truncate(2);
+            }
+        }
+
+        let mut exc = Self {
+            exc_type,
+            arg: None,
+            args,
+            filename,
+            notes: Vec::new(),
+            host_error: None,
+        };
+        exc.arg = Some(match (exc.errno_args(), exc.args.as_slice()) {
+            (Some((errno, strerror)), _) => match &exc.filename {
+                Some(filename) => format!("[Errno {errno}] {strerror}: {}", filename.py_repr()),
+                None => format!("[Errno {errno}] {strerror}"),
+            },
+            (None, [arg]) => arg.to_string(),
+            (None, args) => MontyObject::Tuple(args.to_vec()).py_repr(),
+        });
+        exc
+    }
+
+    /// Recovers `errno`, `strerror` and `filename` from an `OSError` message such as
+    /// `[Errno 2] No such file or directory: 'data.txt'`, which is how host errors arrive.
+    ///
+    /// Messages in any other form, including filenames that needed escaping, are left alone.
+    fn parse_errno_message(&mut self) {
+        if !self.exc_type.is_subclass_of(ExcType::OSError) {
+            return;
+        }
+        let Some(rest) = self.arg.as_deref().and_then(|msg| msg.strip_prefix("[Errno ")) else {
+            return;
+        };
+        let Some((errno, rest)) = rest.split_once("] ") else {
+            return;
+        };
+        let Ok(errno) = errno.parse::<i64>() else {
+            return;
+        };
+        let (strerror, filename) = match rest.split_once(": '") {
+            Some((strerror, quoted)) => match quoted.strip_suffix('\'') {
+                Some(name) if !name.contains(['\'', '\\']) => (strerror, Some(name)),
+                _ => return,
+            },
+            None => (rest, None),
+        };
+        self.args = vec![MontyObject::Int(errno), MontyObject::String(strerror.to_owned())];
+        self.filename = filename.map(|name| Box::new(MontyObject::String(name.to_owned())));
+    }
+
+    /// `(errno, strerror)` for an `OSError` created with them, otherwise `None`.
+    fn errno_args(&self) -> Option<(&MontyObject, &MontyObject)> {
+        if self.exc_type.is_subclass_of(ExcType::OSError) && (2..=5).contains(&self.args.len()) {
+            Some((&self.args[0], &self.args[1]))
+        } else {
+            None
+        }
     }
 
     #[must_use]
@@ -1153,14 +1456,27 @@ impl SimpleException {
         self.arg.as_ref()
     }
 
+    /// The arguments beyond a single message, see the `args` field.
+    #[must_use]
+    pub fn args(&self) -> &[MontyObject] {
+        &self.args
+    }
+
+    /// Notes attached to this exception via `add_note()`, in the order they were added.
+    #[must_use]
+    pub fn notes(&self) -> &[String] {
+        &self.notes
+    }
+
     /// str() for an exception
     #[must_use]
     pub fn py_str(&self) -> String {
-        match (self.exc_type, &self.arg) {
+        match (self.exc_type, self.args.as_slice(), &self.arg) {
             // KeyError expecificaly uses repr of the key for str(exc)
-            (ExcType::KeyError, Some(exc)) => StringRepr(exc).to_string(),
-            (_, Some(arg)) => arg.to_owned(),
-            (_, None) => String::new(),
+            (ExcType::KeyError, [key], _) => key.py_repr(),
+            (ExcType::KeyError, [], Some(exc)) => StringRepr(exc).to_string(),
+            (_, _, Some(arg)) => arg.to_owned(),
+            (_, _, None) => String::new(),
         }
     }
 
@@ -1173,7 +1489,14 @@ impl SimpleException {
         let type_str: &'static str = self.exc_type.into();
         write!(f, "{type_str}(")?;
 
-        if let Some(arg) = &self.arg {
+        if !self.args.is_empty() {
+            for (i, arg) in self.args.iter().enumerate() {
+                if i > 0 {
+                    f.write_str(", ")?;
+                }
+                f.write_str(&arg.py_repr())?;
+            }
+        } else if let Some(arg) = &self.arg {
             string_repr_fmt(arg, f)?;
         }
 
@@ -1198,27 +1521,102 @@ impl SimpleException {
 
     /// Gets an attribute from this exception.
     ///
-    /// Handles the `.args` attribute by allocating a tuple containing the message.
-    /// Returns `Err(AttributeError)` for all other attributes.
+    /// Handles the `.args` attribute by allocating a tuple of the arguments, and
+    /// `.__notes__` by allocating a fresh list of the attached notes. Like CPython,
+    /// `__notes__` only exists once a note has been added. The `OSError` family also
+    /// has `errno`, `strerror` and `filename`, which are `None` unless it was created
+    /// with an error number.
+    /// Returns `Ok(None)` for all other attributes so the caller raises `AttributeError`.
+    ///
+    /// Note that `__notes__` is a copy: mutating the returned list does not affect the
+    /// exception, unlike CPython where the list is stored on the instance.
     pub fn py_getattr(
         &self,
         attr_id: StringId,
         heap: &mut Heap<impl ResourceTracker>,
-        _interns: &Interns,
+        interns: &Interns,
     ) -> RunResult<Option<AttrCallResult>> {
         if attr_id == StaticStrings::Args {
             // Construct tuple with 0 or 1 elements based on whether arg exists
-            let elements = if let Some(arg_str) = &self.arg {
+            let elements = if !self.args.is_empty() {
+                let mut elements = smallvec![];
+                for arg in &self.args {
+                    elements.push(arg_value(arg, heap, interns)?);
+                }
+                elements
+            } else if let Some(arg_str) = &self.arg {
                 let str_id = heap.allocate(HeapData::Str(Str::from(arg_str.clone())))?;
                 smallvec![Value::Ref(str_id)]
             } else {
                 smallvec![]
             };
             Ok(Some(AttrCallResult::Value(allocate_tuple(elements, heap)?)))
+        } else if attr_id == StaticStrings::DunderNotes && !self.notes.is_empty() {
+            let mut items = Vec::with_capacity(self.notes.len());
+            for note in &self.notes {
+                let str_id = heap.allocate(HeapData::Str(Str::from(note.clone())))?;
+                items.push(Value::Ref(str_id));
+            }
+            let list_id = heap.allocate(HeapData::List(List::new(items)))?;
+            Ok(Some(AttrCallResult::Value(Value::Ref(list_id))))
+        } else if self.exc_type.is_subclass_of(ExcType::OSError) {
+            let errno_args = self.errno_args();
+            let attr = match interns.get_str(attr_id) {
+                "errno" => errno_args.map(|(errno, _)| errno),
+                "strerror" => errno_args.map(|(_, strerror)| strerror),
+                "filename" => errno_args.and(self.filename.as_deref()),
+                _ => return Ok(None),
+            };
+            let value = match attr {
+                Some(attr) => arg_value(attr, heap, interns)?,
+                None => Value::None,
+            };
+            Ok(Some(AttrCallResult::Value(value)))
         } else {
             Ok(None)
         }
     }
+
+    /// Calls a method on this exception.
+    ///
+    /// Only `add_note(note)` is supported, which appends `note` to the exception's notes
+    /// (PEP 678). The note must be a `str`, matching CPython's `TypeError` otherwise.
+    pub fn py_call_attr(
+        &mut self,
+        heap: &mut Heap<impl ResourceTracker>,
+        attr: &EitherStr,
+        args: ArgValues,
+        interns: &Interns,
+    ) -> RunResult<Value> {
+        if attr.static_string() == Some(StaticStrings::AddNote) {
+            let note = args.get_one_arg("BaseException.add_note", heap)?;
+            defer_drop!(note, heap);
+            let Some(note_str) = note.as_either_str(heap) else {
+                return Err(ExcType::type_error(format!(
+                    "note must be a str, not '{}'",
+                    note.py_type(heap)
+                )));
+            };
+            self.notes.push(note_str.into_string(interns));
+            Ok(Value::None)
+        } else {
+            Err(ExcType::attribute_error(self.py_type(), attr.as_str(interns)))
+        }
+    }
+}
+
+/// Converts an exception argument back to a value for `exc.args` and the `OSError` attributes.
+///
+/// Objects with no input form, like functions, come back as the `str` of their repr.
+fn arg_value(arg: &MontyObject, heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<Value> {
+    match arg.clone().to_value(heap, interns) {
+        Ok(value) => Ok(value),
+        Err(InvalidInputError::Resource(err)) => Err(err.into()),
+        Err(InvalidInputError::InvalidType(_)) => {
+            let str_id = heap.allocate(HeapData::Str(Str::from(arg.py_repr())))?;
+            Ok(Value::Ref(str_id))
+        }
+    }
 }
 
 /// A raised exception with optional stack frame for traceback.
@@ -1313,11 +1711,27 @@ impl ExceptionRaise {
                 }
                 // Reverse so outermost frame is first (Python's "most recent call last" ordering)
                 frames.reverse();
+                for (depth, frame) in frames.iter_mut().enumerate() {
+                    frame.depth = depth;
+                }
                 frames
             })
             .unwrap_or_default();
 
-        MontyException::new_full(self.exc.exc_type(), self.exc.arg().cloned(), traceback)
+        // The host only sees the arguments through the message
+        let SimpleException {
+            exc_type,
+            arg,
+            notes,
+            host_error,
+            ..
+        } = self.exc;
+        let mut exc = MontyException::new_full(exc_type, arg, traceback);
+        for note in notes {
+            exc.add_note(note);
+        }
+        exc.set_host_error(host_error);
+        exc
     }
 }
 
@@ -1439,7 +1853,7 @@ impl RunError {
 /// Examples:
 /// - `["a"]` -> `'a'`
 /// - `["a", "b"]` -> `'a' and 'b'`
-/// - `["a", "b", "c"]` -> `'a', 'b' and 'c'`
+/// - `["a", "b", "c"]` -> `'a', 'b', and 'c'`
 fn format_param_names(names: &[&str]) -> String {
     match names.len() {
         0 => String::new(),
@@ -1448,7 +1862,7 @@ fn format_param_names(names: &[&str]) -> String {
         _ => {
             let last = names.last().unwrap();
             let rest: Vec<_> = names[..names.len() - 1].iter().map(|n| format!("'{n}'")).collect();
-            format!("{} and '{last}'", rest.join(", "))
+            format!("{}, and '{last}'", rest.join(", "))
         }
     }
 }
@@ -1,9 +1,14 @@
-use std::fmt::{self, Write};
+use std::{
+    any::Any,
+    fmt::{self, Write},
+    hash::{Hash, Hasher},
+    sync::Arc,
+};
 
 use crate::{
     exception_private::{ExcType, RawStackFrame},
     intern::Interns,
-    parse::CodeRange,
+    parse::{Carets, CodeRange, traceback_carets},
     types::str::StringRepr,
 };
 
@@ -16,6 +21,10 @@ pub struct MontyException {
     message: Option<String>,
     /// Stack trace of the exception, first is the outermost frame shown first in the traceback
     traceback: Vec<StackFrame>,
+    /// Notes attached with `add_note()` (PEP 678), shown after the message in the traceback.
+    notes: Vec<String>,
+    /// The original host exception, if this exception was raised by an external function.
+    host_error: Option<Box<HostError>>,
 }
 
 /// Number of identical consecutive frames to show before collapsing.
@@ -26,46 +35,7 @@ const REPEAT_FRAMES_SHOWN: usize = 3;
 /// Display implementation for MontyException should exactly match python traceback format.
 impl fmt::Display for MontyException {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        // Print the traceback header if we have frames
-        if !self.traceback.is_empty() {
-            writeln!(f, "Traceback (most recent call last):")?;
-        }
-
-        // Print frames, collapsing consecutive identical frames like CPython does
-        let mut i = 0;
-        while i < self.traceback.len() {
-            let frame = &self.traceback[i];
-
-            // Count consecutive identical frames
-            let mut repeat_count = 1;
-            while i + repeat_count < self.traceback.len()
-                && frames_are_identical(frame, &self.traceback[i + repeat_count])
-            {
-                repeat_count += 1;
-            }
-
-            if repeat_count > REPEAT_FRAMES_SHOWN {
-                // Show first REPEAT_FRAMES_SHOWN frames, then collapse the rest
-                for j in 0..REPEAT_FRAMES_SHOWN {
-                    write!(f, "{}", &self.traceback[i + j])?;
-                }
-                let collapsed = repeat_count - REPEAT_FRAMES_SHOWN;
-                writeln!(f, "  [Previous line repeated {collapsed} more times]")?;
-                i += repeat_count;
-            } else {
-                // Show all frames in this group
-                for j in 0..repeat_count {
-                    write!(f, "{}", &self.traceback[i + j])?;
-                }
-                i += repeat_count;
-            }
-        }
-
-        if let Some(msg) = &self.message {
-            write!(f, "{}: {}", self.exc_type, msg)
-        } else {
-            write!(f, "{}", self.exc_type)
-        }
+        self.write_traceback(f, &TracebackOptions::default())
     }
 }
 
@@ -81,9 +51,52 @@ impl MontyException {
             exc_type,
             message,
             traceback: vec![],
+            notes: vec![],
+            host_error: None,
         }
     }
 
+    /// Attaches a note to the exception, equivalent to python's `exc.add_note(note)`.
+    ///
+    /// Notes are visible to sandboxed code via `exc.__notes__` when the exception is raised
+    /// into Monty (e.g. when resuming an external call with an error), and are printed
+    /// after the message line in the traceback.
+    pub fn add_note(&mut self, note: impl Into<String>) {
+        self.notes.push(note.into());
+    }
+
+    /// Builder-style variant of [`add_note`](Self::add_note).
+    #[must_use]
+    pub fn with_note(mut self, note: impl Into<String>) -> Self {
+        self.add_note(note);
+        self
+    }
+
+    /// Notes attached to the exception, equivalent to python's `exc.__notes__`.
+    ///
+    /// Empty if no notes were added.
+    #[must_use]
+    pub fn notes(&self) -> &[String] {
+        &self.notes
+    }
+
+    /// Records the host exception this exception was converted from.
+    ///
+    /// Embedders set this when an external function fails, so that if the exception
+    /// propagates out of the sandbox unhandled they can re-raise the original.
+    /// It survives `except ... as e: raise e` inside the sandbox, but not `raise NewError() from e`.
+    #[must_use]
+    pub fn with_host_error(mut self, host_error: HostError) -> Self {
+        self.host_error = Some(Box::new(host_error));
+        self
+    }
+
+    /// The host exception this exception was converted from, if any.
+    #[must_use]
+    pub fn host_error(&self) -> Option<&HostError> {
+        self.host_error.as_deref()
+    }
+
     /// The exception type raised.
     #[must_use]
     pub fn exc_type(&self) -> ExcType {
@@ -114,6 +127,18 @@ impl MontyException {
         &self.traceback
     }
 
+    /// Renders the traceback with the given options.
+    ///
+    /// `to_string()` is equivalent to rendering with `TracebackOptions::default()`, which
+    /// matches CPython's output.
+    #[must_use]
+    pub fn render(&self, options: &TracebackOptions) -> String {
+        let mut out = String::new();
+        self.write_traceback(&mut out, options)
+            .expect("writing to a String cannot fail");
+        out
+    }
+
     /// Returns a compact summary of the exception.
     ///
     /// Format: `ExceptionType: message` (e.g., `NotImplementedError: feature not supported`)
@@ -141,23 +166,278 @@ impl MontyException {
         }
     }
 
+    /// Writes the traceback with the given options, see [`TracebackOptions`].
+    fn write_traceback(&self, f: &mut impl Write, options: &TracebackOptions) -> fmt::Result {
+        let frames = self.visible_frames(options);
+
+        if options.style == TracebackStyle::Line {
+            if let Some(frame) = frames.last() {
+                write!(f, "{}:{}: ", frame.filename, frame.start.line)?;
+            }
+            return f.write_str(&self.summary());
+        }
+
+        // Print the traceback header if we have frames
+        if !frames.is_empty() {
+            writeln!(f, "Traceback (most recent call last):")?;
+        }
+
+        let carets = options.style == TracebackStyle::Long;
+        // Print frames, collapsing consecutive identical frames like CPython does
+        let mut i = 0;
+        while i < frames.len() {
+            let frame = frames[i];
+
+            // Count consecutive identical frames
+            let mut repeat_count = 1;
+            while i + repeat_count < frames.len() && frames_are_identical(frame, frames[i + repeat_count]) {
+                repeat_count += 1;
+            }
+
+            if repeat_count > REPEAT_FRAMES_SHOWN {
+                // Show first REPEAT_FRAMES_SHOWN frames, then collapse the rest
+                for j in 0..REPEAT_FRAMES_SHOWN {
+                    frames[i + j].write_to(f, carets)?;
+                }
+                let collapsed = repeat_count - REPEAT_FRAMES_SHOWN;
+                writeln!(f, "  [Previous line repeated {collapsed} more times]")?;
+            } else {
+                // Show all frames in this group
+                for j in 0..repeat_count {
+                    frames[i + j].write_to(f, carets)?;
+                }
+            }
+            i += repeat_count;
+        }
+
+        f.write_str(&self.summary())?;
+
+        // PEP 678: each note is printed on its own line after the message
+        for note in &self.notes {
+            write!(f, "\n{note}")?;
+        }
+        Ok(())
+    }
+
+    /// The frames left after removing internal frames and applying the frame limit.
+    ///
+    /// The innermost frame is kept if every frame is internal, so the traceback still
+    /// shows where the exception was raised.
+    fn visible_frames(&self, options: &TracebackOptions) -> Vec<&StackFrame> {
+        let mut frames: Vec<&StackFrame> = self
+            .traceback
+            .iter()
+            .filter(|frame| !options.internal_files.contains(&frame.filename))
+            .collect();
+        if frames.is_empty()
+            && let Some(innermost) = self.traceback.last()
+        {
+            frames.push(innermost);
+        }
+        if let Some(max_frames) = options.max_frames {
+            // Like `sys.tracebacklimit`, the innermost frames are kept
+            let skipped = frames.len().saturating_sub(max_frames);
+            frames.drain(..skipped);
+        }
+        frames
+    }
+
     pub(crate) fn new_full(exc_type: ExcType, message: Option<String>, traceback: Vec<StackFrame>) -> Self {
         Self {
             exc_type,
             message,
             traceback,
+            notes: vec![],
+            host_error: None,
         }
     }
 
+    /// Splits the exception into the parts needed to raise it inside the sandbox.
+    ///
+    /// The traceback is dropped since a fresh one is built when the exception is raised.
+    pub(crate) fn into_parts(self) -> (ExcType, Option<String>, Vec<String>, Option<Box<HostError>>) {
+        (self.exc_type, self.message, self.notes, self.host_error)
+    }
+
+    /// Sets or clears the host exception, used when converting back from the sandbox.
+    pub(crate) fn set_host_error(&mut self, host_error: Option<Box<HostError>>) {
+        self.host_error = host_error;
+    }
+
     pub(crate) fn runtime_error(err: impl fmt::Display) -> Self {
         Self {
             exc_type: ExcType::RuntimeError,
             message: Some(err.to_string()),
             traceback: vec![],
+            notes: vec![],
+            host_error: None,
         }
     }
 }
 
+/// Options controlling how [`MontyException::render`] formats a traceback.
+///
+/// The defaults match CPython's output. Hosts that put tracebacks into prompts or logs can
+/// use a shorter style, cap the number of frames and hide frames from their own helper code.
+///
+/// # Example
+/// ```
+/// use monty::{TracebackOptions, TracebackStyle};
+///
+/// let options = TracebackOptions::new()
+///     .style(TracebackStyle::Short)
+///     .max_frames(5)
+///     .internal_file("helpers.py");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TracebackOptions {
+    /// How much of each frame to show.
+    pub style: TracebackStyle,
+    /// Maximum number of frames to show, the innermost frames are kept like `sys.tracebacklimit`.
+    ///
+    /// `Some(0)` shows only the exception type and message.
+    pub max_frames: Option<usize>,
+    /// Filenames whose frames are hidden, e.g. helper code the host runs before the user's code.
+    pub internal_files: Vec<String>,
+}
+
+impl TracebackOptions {
+    /// Creates options matching CPython's traceback format.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the traceback style.
+    #[must_use]
+    pub fn style(mut self, style: TracebackStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Sets the maximum number of frames to show.
+    #[must_use]
+    pub fn max_frames(mut self, limit: usize) -> Self {
+        self.max_frames = Some(limit);
+        self
+    }
+
+    /// Hides frames from the given file.
+    #[must_use]
+    pub fn internal_file(mut self, filename: impl Into<String>) -> Self {
+        self.internal_files.push(filename.into());
+        self
+    }
+}
+
+/// Traceback styles, named after pytest's `--tb` option.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum TracebackStyle {
+    /// CPython's format: each frame's location and source line with caret markers.
+    #[default]
+    Long,
+    /// Like `Long` without caret markers.
+    Short,
+    /// A single line with the innermost frame's location: `file.py:3: ValueError: message`.
+    Line,
+}
+
+/// An exception raised by the host in an external function.
+///
+/// Stores enough information to describe the original exception (its class name, message and
+/// host-side traceback), plus an optional opaque handle to the original exception object so an
+/// embedder can re-raise it unchanged. The handle only lives in memory: it is dropped when a
+/// snapshot is serialized, while the descriptive fields are kept.
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+pub struct HostError {
+    /// Class name of the host exception, e.g. `"ConnectionError"` or `"TypeError"`.
+    class_name: String,
+    /// Message of the host exception.
+    message: Option<String>,
+    /// Host traceback, formatted by the embedder (e.g. a Python traceback or a JS `stack`).
+    traceback: Option<String>,
+    /// The original exception object, owned by the embedder.
+    #[serde(skip)]
+    original: Option<Arc<dyn Any + Send + Sync>>,
+}
+
+impl HostError {
+    /// Creates a host error with the given class name and message.
+    #[must_use]
+    pub fn new(class_name: impl Into<String>, message: Option<String>) -> Self {
+        Self {
+            class_name: class_name.into(),
+            message,
+            traceback: None,
+            original: None,
+        }
+    }
+
+    /// Sets the host traceback.
+    #[must_use]
+    pub fn with_traceback(mut self, traceback: impl Into<String>) -> Self {
+        self.traceback = Some(traceback.into());
+        self
+    }
+
+    /// Attaches the original exception object so it can be retrieved with [`original`](Self::original).
+    #[must_use]
+    pub fn with_original<T: Any + Send + Sync>(mut self, original: T) -> Self {
+        self.original = Some(Arc::new(original));
+        self
+    }
+
+    /// Class name of the host exception.
+    #[must_use]
+    pub fn class_name(&self) -> &str {
+        &self.class_name
+    }
+
+    /// Message of the host exception.
+    #[must_use]
+    pub fn message(&self) -> Option<&str> {
+        self.message.as_deref()
+    }
+
+    /// Host traceback, if the embedder recorded one.
+    #[must_use]
+    pub fn traceback(&self) -> Option<&str> {
+        self.traceback.as_deref()
+    }
+
+    /// The original exception object, if one was attached and it has type `T`.
+    #[must_use]
+    pub fn original<T: Any>(&self) -> Option<&T> {
+        self.original.as_deref().and_then(|original| original.downcast_ref())
+    }
+}
+
+impl fmt::Debug for HostError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HostError")
+            .field("class_name", &self.class_name)
+            .field("message", &self.message)
+            .field("traceback", &self.traceback)
+            .field("has_original", &self.original.is_some())
+            .finish()
+    }
+}
+
+/// Host errors are equal if they describe the same exception, the original object is ignored.
+impl PartialEq for HostError {
+    fn eq(&self, other: &Self) -> bool {
+        self.class_name == other.class_name && self.message == other.message && self.traceback == other.traceback
+    }
+}
+
+impl Hash for HostError {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.class_name.hash(state);
+        self.message.hash(state);
+        self.traceback.hash(state);
+    }
+}
+
 /// Check if two stack frames are identical for the purpose of collapsing repeated frames.
 ///
 /// Two frames are identical if they have the same filename, line number, and function name.
@@ -172,9 +452,9 @@ fn frames_are_identical(a: &StackFrame, b: &StackFrame) -> bool {
 ///
 /// # Caret Markers
 ///
-/// Monty uses only `~` characters for caret markers in tracebacks, unlike CPython 3.11+
-/// which uses `~` for the function name and `^` for arguments (e.g., `~~~~~~~~~~~^^^^^^^^^^^`).
-/// This simplification is intentional - Monty marks the entire expression span uniformly.
+/// Like CPython 3.11+, carets under a binary operation, call or subscript use `~` for the
+/// operands and `^` for the operator or brackets (e.g. `~~~~~~~~~~~^^^^^^^^^^^`), while
+/// other ranges are underlined with `^` only.
 #[derive(Debug, Clone, PartialEq)]
 pub struct StackFrame {
     /// The filename where the code is located.
@@ -193,16 +473,30 @@ pub struct StackFrame {
     /// - `raise` statements (CPython doesn't show carets for raise)
     /// - `AttributeError` on attribute access (CPython doesn't show carets for these)
     pub hide_caret: bool,
+    /// The 1-based columns, end exclusive, marked with `^` inside the caret range; the rest of the
+    /// range is marked with `~`. `None` marks the whole range with `^`.
+    pub caret_anchor: Option<(u16, u16)>,
     /// Whether to hide the `, in <name>` part of the frame line.
     ///
     /// Set to `true` for `SyntaxError` where CPython doesn't show the frame name.
     /// CPython's SyntaxError format: `  File "...", line N`
     /// vs runtime error format: `  File "...", line N, in <module>`
     pub hide_frame_name: bool,
+    /// Call depth of the frame: 0 for the outermost frame of the traceback (usually
+    /// module-level code), 1 for the function it called, and so on.
+    pub depth: usize,
 }
 
 impl fmt::Display for StackFrame {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_to(f, true)
+    }
+}
+
+impl StackFrame {
+    /// Writes the frame in CPython's traceback format, with caret markers under the
+    /// source line if `carets` is true and the frame doesn't hide them.
+    fn write_to(&self, f: &mut impl Write, carets: bool) -> fmt::Result {
         // SyntaxError format: `  File "...", line N`
         // Runtime error format: `  File "...", line N, in <module>`
         if self.hide_frame_name {
@@ -222,7 +516,7 @@ impl fmt::Display for StackFrame {
             writeln!(f, "\n    {trimmed}")?;
 
             // Hide caret for raise statements, AttributeError, etc.
-            if !self.hide_caret {
+            if carets && !self.hide_caret {
                 let leading_spaces = line.len() - trimmed.len();
                 // Calculate caret position relative to the trimmed line
                 // Column is 1-indexed, so subtract 1, then subtract leading spaces we stripped
@@ -232,18 +526,23 @@ impl fmt::Display for StackFrame {
                     4
                 };
                 f.write_str(&" ".repeat(caret_start))?;
-                writeln!(f, "{}", "~".repeat((self.end.column - self.start.column) as usize))?;
+                for column in self.start.column..self.end.column {
+                    let anchored = self
+                        .caret_anchor
+                        .is_none_or(|(start, end)| (start..end).contains(&column));
+                    f.write_char(if anchored { '^' } else { '~' })?;
+                }
+                f.write_char('\n')?;
             }
         } else {
             f.write_char('\n')?;
         }
         Ok(())
     }
-}
 
-impl StackFrame {
     pub(crate) fn from_raw(f: &RawStackFrame, interns: &Interns, source: &str) -> Self {
         let filename = interns.get_str(f.position.filename).to_string();
+        let carets = traceback_carets(source, f.position);
         Self {
             filename,
             start: f.position.start(),
@@ -254,8 +553,13 @@ impl StackFrame {
                 .preview_line_number()
                 .and_then(|ln| source.lines().nth(ln as usize))
                 .map(str::to_string),
-            hide_caret: f.hide_caret,
+            hide_caret: f.hide_caret || carets == Carets::Hidden,
+            caret_anchor: match carets {
+                Carets::Anchored { start, end } => Some((start, end)),
+                Carets::Hidden | Carets::Plain => None,
+            },
             hide_frame_name: false,
+            depth: 0,
         }
     }
 
@@ -274,7 +578,9 @@ impl StackFrame {
                 .and_then(|ln| source.lines().nth(ln as usize))
                 .map(str::to_string),
             hide_caret: false,
+            caret_anchor: None,
             hide_frame_name: true,
+            depth: 0,
         }
     }
 
@@ -289,7 +595,9 @@ impl StackFrame {
                 .and_then(|ln| source.lines().nth(ln as usize))
                 .map(str::to_string),
             hide_caret: false,
+            caret_anchor: None,
             hide_frame_name: false,
+            depth: 0,
         }
     }
 
@@ -307,7 +615,9 @@ impl StackFrame {
                 .and_then(|ln| source.lines().nth(ln as usize))
                 .map(str::to_string),
             hide_caret: true,
+            caret_anchor: None,
             hide_frame_name: false,
+            depth: 0,
         }
     }
 }
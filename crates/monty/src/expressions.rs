@@ -31,6 +31,11 @@ pub enum NameScope {
     LocalUnassigned,
     /// Variable is in the module-level global namespace
     Global,
+    /// Module-level global that shares its name with a builtin.
+    ///
+    /// Loads read the global slot and fall back to the builtin while the slot is unbound,
+    /// so assigning the name shadows the builtin and `del` restores it.
+    GlobalOrBuiltin,
     /// Variable accessed through a cell (heap-allocated container).
     ///
     /// Used for both:
@@ -86,9 +91,13 @@ impl Identifier {
 /// Target of a function call expression.
 ///
 /// Represents a callable that can be either:
-/// - A builtin function or exception resolved at parse time (`print`, `len`, `ValueError`, etc.)
+/// - A builtin function or exception resolved during prepare (`print`, `len`, `ValueError`, etc.),
+///   which the compiler emits as `CallBuiltinFunction`/`CallBuiltinType` where it can
 /// - A name that will be looked up in the namespace at runtime (for callable variables)
 ///
+/// Any other callee expression becomes `Expr::IndirectCall`. At runtime every call is
+/// dispatched through `Value::callee`, whatever its target here.
+///
 /// Separate from Value to allow deriving Clone without Value's Clone restrictions.
 #[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
 pub enum Callable {
@@ -106,7 +115,7 @@ pub enum Expr {
     Name(Identifier),
     /// Function call expression.
     ///
-    /// The `callable` can be a Builtin, ExcType (resolved at parse time), or a Name
+    /// The `callable` can be a Builtin, ExcType (resolved during prepare), or a Name
     /// that will be looked up in the namespace at runtime.
     Call {
         callable: Callable,
@@ -180,7 +189,8 @@ pub enum Expr {
         upper: Option<Box<ExprLoc>>,
         step: Option<Box<ExprLoc>>,
     },
-    Dict(Vec<(ExprLoc, ExprLoc)>),
+    /// Dict literal expression: `{'a': 1, **other}`.
+    Dict(Vec<DictItem>),
     /// Set literal expression: `{1, 2, 3}`.
     ///
     /// Note: `{}` is always a dict, not an empty set. Use `set()` for empty sets.
@@ -266,6 +276,12 @@ pub enum Expr {
         /// The body is wrapped as `[Node::Return(body_expr)]` during preparation.
         func_def: Box<PreparedFunctionDef>,
     },
+    /// Starred element `*value` inside a list, tuple or set literal.
+    ///
+    /// Only produced by the parser directly inside display literals (and tuples
+    /// synthesized for call arguments); the iterable's items are spliced into the
+    /// surrounding collection.
+    Starred(Box<ExprLoc>),
     /// Named expression (walrus operator): `(target := value)`
     ///
     /// Evaluates `value`, assigns it to `target`, and returns the value as the
@@ -280,6 +296,16 @@ pub enum Expr {
     },
 }
 
+/// An item in a dict literal: either a `key: value` pair or a `**mapping` unpacking.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub enum DictItem {
+    /// A `key: value` pair.
+    Pair(ExprLoc, ExprLoc),
+    /// A `**mapping` unpacking - all of the mapping's items are inserted, overwriting
+    /// any earlier keys.
+    Unpack(ExprLoc),
+}
+
 /// Target for tuple unpacking - can be a single name, nested tuple, or starred target.
 ///
 /// Supports recursive structures like `(a, b), c` or `a, (b, c)`.
@@ -476,6 +502,14 @@ pub enum Node<F> {
         or_else: Vec<Self>,
     },
     FunctionDef(F),
+    /// `del` statement for plain names, e.g. `del x, y`.
+    ///
+    /// Unbinds each name in order. Deleting a name that isn't bound raises `NameError`
+    /// (or `UnboundLocalError` in functions). Deleting a global that shadows a builtin
+    /// makes the builtin visible again.
+    Delete {
+        targets: Vec<Identifier>,
+    },
     /// Global variable declaration. Only present in parsed form, consumed during prepare.
     ///
     /// Declares that the listed names refer to module-level (global) variables,
@@ -522,6 +556,19 @@ pub enum Node<F> {
     },
 }
 
+impl<F> Node<F> {
+    /// Returns the docstring of a module or function body, if its first statement is a string literal.
+    pub fn docstring(body: &[Self]) -> Option<StringId> {
+        match body.first() {
+            Some(Self::Expr(ExprLoc {
+                expr: Expr::Literal(Literal::Str(id)),
+                ..
+            })) => Some(*id),
+            _ => None,
+        }
+    }
+}
+
 /// A prepared function definition with resolved names and scope information.
 ///
 /// This is created during the prepare phase and contains everything needed to
@@ -565,6 +612,11 @@ pub struct PreparedFunctionDef {
     /// When true, calling this function creates a `Coroutine` object instead of
     /// immediately pushing a frame.
     pub is_async: bool,
+    /// Prepared decorator expressions in source order, resolved in the enclosing scope.
+    ///
+    /// They are evaluated before the defaults and applied innermost (last) first once
+    /// the function object exists. Always empty for lambdas.
+    pub decorators: Vec<ExprLoc>,
 }
 
 /// Type alias for prepared AST nodes (output of prepare phase).
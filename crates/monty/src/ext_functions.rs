@@ -0,0 +1,463 @@
+//! Typed Rust functions as external functions.
+//!
+//! [`ExternalFunctions`] maps names to plain Rust functions such as `fn(i64, i64) -> i64`.
+//! Arguments are converted from `MontyObject` before the call, and wrong argument counts or
+//! types are raised as a `TypeError` inside the sandbox, where the code can catch them.
+//! [`ExternalFunctions::run`] then drives execution to completion, dispatching each call.
+//!
+//! [`ExternalDispatcher`] and [`OsDispatcher`] are the more general hooks used by
+//! [`MontyRun::run_with_handlers`](crate::MontyRun::run_with_handlers).
+use std::{collections::HashMap, fmt};
+
+use indexmap::IndexMap;
+
+use crate::{
+    ExcType, MontyException, PrintWriter, ResourceTracker,
+    object::{ConversionError, MontyObject},
+    os::OsFunction,
+    run::{ExternalResult, RunProgress},
+};
+
+/// A boxed external function, taking the function name for error messages.
+type BoxedFunction = Box<dyn Fn(&str, Vec<MontyObject>) -> ExternalResult + Send + Sync>;
+
+/// A registry of typed Rust functions that sandboxed code can call.
+///
+/// # Example
+/// ```
+/// use monty::{ExternalFunctions, MontyObject, MontyRun, NoLimitTracker, PrintWriter};
+///
+/// let functions = ExternalFunctions::new()
+///     .register("add", |a: i64, b: i64| a + b)
+///     .register("greet", |name: String| format!("hello {name}"));
+///
+/// let runner = MontyRun::new("greet(str(add(1, 2)))".to_owned(), "main.py", vec![], functions.names()).unwrap();
+/// let progress = runner.start(vec![], NoLimitTracker, &mut PrintWriter::Stdout).unwrap();
+/// let result = functions.run(progress, &mut PrintWriter::Stdout).unwrap();
+/// assert_eq!(result, MontyObject::String("hello 3".to_owned()));
+/// ```
+#[derive(Default)]
+pub struct ExternalFunctions {
+    functions: IndexMap<String, BoxedFunction>,
+}
+
+impl fmt::Debug for ExternalFunctions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExternalFunctions")
+            .field("names", &self.functions.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl ExternalFunctions {
+    /// Creates an empty registry.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a Rust function under `name`, replacing any function with the same name.
+    ///
+    /// The function can take up to six arguments of any [`ExtArg`] type and return any
+    /// [`ExtReturn`] type, including `Result<T, MontyException>` to raise an exception.
+    #[must_use]
+    pub fn register<Args>(mut self, name: impl Into<String>, function: impl ExtFunction<Args>) -> Self {
+        self.functions.insert(
+            name.into(),
+            Box::new(move |name: &str, args: Vec<MontyObject>| function.call(name, args)),
+        );
+        self
+    }
+
+    /// Names of the registered functions, in registration order.
+    ///
+    /// Pass these as the `external_functions` of [`MontyRun::new`](crate::MontyRun::new).
+    #[must_use]
+    pub fn names(&self) -> Vec<String> {
+        self.functions.keys().cloned().collect()
+    }
+
+    /// Whether a function is registered under `name`.
+    #[must_use]
+    pub fn contains(&self, name: &str) -> bool {
+        self.functions.contains_key(name)
+    }
+
+    /// Calls the function registered under `name`, returning `None` if there isn't one.
+    ///
+    /// Keyword arguments are rejected with a `TypeError`, since Rust functions only take
+    /// positional arguments.
+    #[must_use]
+    pub fn call(
+        &self,
+        name: &str,
+        args: Vec<MontyObject>,
+        kwargs: Vec<(MontyObject, MontyObject)>,
+    ) -> Option<ExternalResult> {
+        let function = self.functions.get(name)?;
+        if !kwargs.is_empty() {
+            return Some(type_error(format!("{name}() takes no keyword arguments")));
+        }
+        Some(function(name, args))
+    }
+
+    /// Like [`call`](Self::call), but raises `NotImplementedError` for unregistered functions.
+    fn call_or_not_implemented(
+        &self,
+        name: &str,
+        args: Vec<MontyObject>,
+        kwargs: Vec<(MontyObject, MontyObject)>,
+    ) -> ExternalResult {
+        self.call(name, args, kwargs).unwrap_or_else(|| {
+            MontyException::new(
+                ExcType::NotImplementedError,
+                Some(format!("External function '{name}' not implemented")),
+            )
+            .into()
+        })
+    }
+
+    /// Drives execution to completion, calling the registered functions for each external call.
+    ///
+    /// Calls to unregistered functions and OS calls raise `NotImplementedError` inside the
+    /// sandbox.
+    ///
+    /// # Errors
+    /// Returns the exception that ended execution, or a `RuntimeError` if the code waits on
+    /// external futures, which synchronous functions never create.
+    pub fn run<T: ResourceTracker>(
+        &self,
+        progress: RunProgress<T>,
+        print: &mut PrintWriter<'_>,
+    ) -> Result<MontyObject, MontyException> {
+        run_with_dispatchers(progress, self, NoOsAccess, print)
+    }
+}
+
+/// Handles external function calls for [`MontyRun::run_with_handlers`](crate::MontyRun::run_with_handlers).
+///
+/// Implemented for [`ExternalFunctions`] and for closures taking the function name, positional
+/// arguments and keyword arguments.
+pub trait ExternalDispatcher {
+    /// Calls the external function `function_name` and returns its result.
+    fn call(
+        &mut self,
+        function_name: &str,
+        args: Vec<MontyObject>,
+        kwargs: Vec<(MontyObject, MontyObject)>,
+    ) -> ExternalResult;
+}
+
+impl<F> ExternalDispatcher for F
+where
+    F: FnMut(&str, Vec<MontyObject>, Vec<(MontyObject, MontyObject)>) -> ExternalResult,
+{
+    fn call(
+        &mut self,
+        function_name: &str,
+        args: Vec<MontyObject>,
+        kwargs: Vec<(MontyObject, MontyObject)>,
+    ) -> ExternalResult {
+        self(function_name, args, kwargs)
+    }
+}
+
+/// Unregistered functions raise `NotImplementedError`.
+impl ExternalDispatcher for &ExternalFunctions {
+    fn call(
+        &mut self,
+        function_name: &str,
+        args: Vec<MontyObject>,
+        kwargs: Vec<(MontyObject, MontyObject)>,
+    ) -> ExternalResult {
+        self.call_or_not_implemented(function_name, args, kwargs)
+    }
+}
+
+impl ExternalDispatcher for ExternalFunctions {
+    fn call(
+        &mut self,
+        function_name: &str,
+        args: Vec<MontyObject>,
+        kwargs: Vec<(MontyObject, MontyObject)>,
+    ) -> ExternalResult {
+        self.call_or_not_implemented(function_name, args, kwargs)
+    }
+}
+
+/// Handles OS calls for [`MontyRun::run_with_handlers`](crate::MontyRun::run_with_handlers).
+///
+/// Implemented for closures taking the OS function, positional arguments and keyword arguments.
+/// Use [`NoOsAccess`] to deny all OS calls.
+pub trait OsDispatcher {
+    /// Performs the OS call `function` and returns its result.
+    fn call(
+        &mut self,
+        function: OsFunction,
+        args: Vec<MontyObject>,
+        kwargs: Vec<(MontyObject, MontyObject)>,
+    ) -> ExternalResult;
+}
+
+impl<F> OsDispatcher for F
+where
+    F: FnMut(OsFunction, Vec<MontyObject>, Vec<(MontyObject, MontyObject)>) -> ExternalResult,
+{
+    fn call(
+        &mut self,
+        function: OsFunction,
+        args: Vec<MontyObject>,
+        kwargs: Vec<(MontyObject, MontyObject)>,
+    ) -> ExternalResult {
+        self(function, args, kwargs)
+    }
+}
+
+/// An [`OsDispatcher`] that raises `NotImplementedError` for every OS call.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoOsAccess;
+
+impl OsDispatcher for NoOsAccess {
+    fn call(
+        &mut self,
+        function: OsFunction,
+        _args: Vec<MontyObject>,
+        _kwargs: Vec<(MontyObject, MontyObject)>,
+    ) -> ExternalResult {
+        MontyException::new(
+            ExcType::NotImplementedError,
+            Some(format!("OS function '{function}' not implemented")),
+        )
+        .into()
+    }
+}
+
+/// Resumes `progress` until completion, dispatching each external and OS call.
+///
+/// Values streamed with `monty_yield()` are discarded and execution resumes with `None`, and
+/// batched calls are dispatched one after the other. Returns a `RuntimeError` if execution
+/// waits on other external futures, since the dispatchers are synchronous and can't resolve them.
+pub(crate) fn run_with_dispatchers<T: ResourceTracker>(
+    mut progress: RunProgress<T>,
+    mut ext: impl ExternalDispatcher,
+    mut os: impl OsDispatcher,
+    print: &mut PrintWriter<'_>,
+) -> Result<MontyObject, MontyException> {
+    loop {
+        progress = match progress {
+            RunProgress::Complete { value, .. } => return Ok(value),
+            RunProgress::FunctionCall {
+                function_name,
+                args,
+                kwargs,
+                state,
+                ..
+            } => {
+                let result = ext.call(&function_name, args, kwargs);
+                state.run(result, print)?
+            }
+            RunProgress::OsCall {
+                function,
+                args,
+                kwargs,
+                state,
+                ..
+            } => {
+                let result = os.call(function, args, kwargs);
+                state.run(result, print)?
+            }
+            RunProgress::Yield { state, .. } => state.run(MontyObject::None, print)?,
+            RunProgress::Checkpoint(state) => state.run(print)?,
+            RunProgress::FunctionCallBatch { calls, state } => {
+                let results = calls
+                    .into_iter()
+                    .map(|call| (call.call_id, ext.call(&call.function_name, call.args, call.kwargs)))
+                    .collect();
+                state.resume(results, print)?
+            }
+            RunProgress::ResolveFutures(state) => {
+                return Err(MontyException::runtime_error(format!(
+                    "async futures not supported by synchronous dispatchers: {:?}",
+                    state.pending_call_ids()
+                )));
+            }
+        };
+    }
+}
+
+/// A Rust function that can be registered with [`ExternalFunctions::register`].
+///
+/// Implemented for closures and functions taking up to six [`ExtArg`] arguments and returning
+/// an [`ExtReturn`] value. `Args` is the tuple of argument types, used only to tell the
+/// implementations apart.
+pub trait ExtFunction<Args>: Send + Sync + 'static {
+    /// Converts the arguments and calls the function.
+    fn call(&self, name: &str, args: Vec<MontyObject>) -> ExternalResult;
+}
+
+/// Implements `ExtFunction` for functions with the given argument types.
+macro_rules! impl_ext_function {
+    ($count:literal $(, $arg:ident)*) => {
+        impl<Func, Ret, $($arg),*> ExtFunction<($($arg,)*)> for Func
+        where
+            Func: Fn($($arg),*) -> Ret + Send + Sync + 'static,
+            Ret: ExtReturn,
+            $($arg: ExtArg,)*
+        {
+            #[allow(unused_mut, unused_variables, non_snake_case)]
+            fn call(&self, name: &str, args: Vec<MontyObject>) -> ExternalResult {
+                if args.len() != $count {
+                    let plural = if $count == 1 { "" } else { "s" };
+                    return type_error(format!("{name} expected {} argument{plural}, got {}", $count, args.len()));
+                }
+                let mut args = args.into_iter().enumerate();
+                $(
+                    let (index, arg) = args.next().expect("argument count checked");
+                    let $arg = match $arg::from_arg(arg) {
+                        Ok(value) => value,
+                        Err(err) => {
+                            return type_error(format!(
+                                "{name}() argument {} must be {}, not {}",
+                                index + 1,
+                                err.expected,
+                                err.actual
+                            ));
+                        }
+                    };
+                )*
+                self($($arg),*).into_result()
+            }
+        }
+    };
+}
+
+impl_ext_function!(0);
+impl_ext_function!(1, A);
+impl_ext_function!(2, A, B);
+impl_ext_function!(3, A, B, C);
+impl_ext_function!(4, A, B, C, D);
+impl_ext_function!(5, A, B, C, D, E);
+impl_ext_function!(6, A, B, C, D, E, F);
+
+/// A Rust type that an external function can take as an argument.
+pub trait ExtArg: Sized {
+    /// Converts a Python argument, or returns the expected and actual Python type names.
+    ///
+    /// # Errors
+    /// Returns a `ConversionError` if the argument has the wrong type.
+    fn from_arg(value: MontyObject) -> Result<Self, ConversionError>;
+}
+
+impl ExtArg for MontyObject {
+    fn from_arg(value: MontyObject) -> Result<Self, ConversionError> {
+        Ok(value)
+    }
+}
+
+/// Implements `ExtArg` for types with a `TryFrom<&MontyObject>` conversion.
+macro_rules! impl_ext_arg {
+    ($($ty:ty),+) => {
+        $(
+            impl ExtArg for $ty {
+                fn from_arg(value: MontyObject) -> Result<Self, ConversionError> {
+                    Self::try_from(&value)
+                }
+            }
+        )+
+    };
+}
+
+impl_ext_arg!(i64, f64, bool, String);
+
+/// Accepts a list or tuple.
+impl<T: ExtArg> ExtArg for Vec<T> {
+    fn from_arg(value: MontyObject) -> Result<Self, ConversionError> {
+        match value {
+            MontyObject::List(items) | MontyObject::Tuple(items) => items.into_iter().map(T::from_arg).collect(),
+            _ => Err(ConversionError::new("list", value.type_name())),
+        }
+    }
+}
+
+/// Accepts a dict with string keys.
+impl<T: ExtArg> ExtArg for HashMap<String, T> {
+    fn from_arg(value: MontyObject) -> Result<Self, ConversionError> {
+        match value {
+            MontyObject::Dict(pairs) => pairs
+                .into_iter()
+                .map(|(key, value)| Ok((String::try_from(&key)?, T::from_arg(value)?)))
+                .collect(),
+            _ => Err(ConversionError::new("dict", value.type_name())),
+        }
+    }
+}
+
+/// `None` becomes `None`, anything else is converted to `T`.
+impl<T: ExtArg> ExtArg for Option<T> {
+    fn from_arg(value: MontyObject) -> Result<Self, ConversionError> {
+        match value {
+            MontyObject::None => Ok(None),
+            value => T::from_arg(value).map(Some),
+        }
+    }
+}
+
+/// A Rust value that an external function can return.
+pub trait ExtReturn {
+    /// Converts the value to the result passed back to the sandbox.
+    fn into_result(self) -> ExternalResult;
+}
+
+impl ExtReturn for ExternalResult {
+    fn into_result(self) -> ExternalResult {
+        self
+    }
+}
+
+/// `()` returns `None`, like a Python function without a `return`.
+impl ExtReturn for () {
+    fn into_result(self) -> ExternalResult {
+        ExternalResult::Return(MontyObject::None)
+    }
+}
+
+/// `Err` raises the exception inside the sandbox.
+impl<T: ExtReturn> ExtReturn for Result<T, MontyException> {
+    fn into_result(self) -> ExternalResult {
+        match self {
+            Ok(value) => value.into_result(),
+            Err(exc) => ExternalResult::Error(exc),
+        }
+    }
+}
+
+/// Implements `ExtReturn` for types that convert into a `MontyObject`.
+macro_rules! impl_ext_return {
+    ($($ty:ty),+) => {
+        $(
+            impl ExtReturn for $ty {
+                fn into_result(self) -> ExternalResult {
+                    ExternalResult::Return(self.into())
+                }
+            }
+        )+
+    };
+}
+
+impl_ext_return!(MontyObject, i32, i64, u32, u64, usize, f64, bool, String, &'static str);
+
+impl<T: Into<MontyObject>> ExtReturn for Vec<T> {
+    fn into_result(self) -> ExternalResult {
+        ExternalResult::Return(self.into())
+    }
+}
+
+impl<T: Into<MontyObject>> ExtReturn for Option<T> {
+    fn into_result(self) -> ExternalResult {
+        ExternalResult::Return(self.into())
+    }
+}
+
+fn type_error(message: String) -> ExternalResult {
+    ExternalResult::Error(MontyException::new(ExcType::TypeError, Some(message)))
+}
@@ -0,0 +1,294 @@
+//! Optional signatures for external functions.
+//!
+//! Embedders can register an [`ExtFunctionSignature`] for any external function. Calls are then
+//! checked against the signature inside the sandbox before execution is suspended, so arity
+//! mistakes raise a `TypeError` in the Python code rather than reaching the host. The same
+//! signatures are rendered as stubs for the optional type checker.
+use std::fmt::Write;
+
+use crate::{
+    ResourceTracker,
+    args::{ArgValues, KwargsValues},
+    exception_private::{ExcType, RunResult},
+    heap::Heap,
+    intern::Interns,
+};
+
+/// How a parameter of an external function can be passed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum ExtParamKind {
+    /// Positional-only parameter (before `/`).
+    PositionalOnly,
+    /// Parameter that can be passed positionally or by keyword.
+    PositionalOrKeyword,
+    /// Variable positional parameter (`*args`).
+    VarPositional,
+    /// Keyword-only parameter (after `*` or `*args`).
+    KeywordOnly,
+    /// Variable keyword parameter (`**kwargs`).
+    VarKeyword,
+}
+
+/// A single parameter of an external function.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ExtParam {
+    /// The parameter name.
+    pub name: String,
+    /// How the parameter can be passed.
+    pub kind: ExtParamKind,
+    /// Type annotation as Python source, e.g. `"list[int]"`, used only for type checking.
+    pub type_hint: Option<String>,
+    /// Whether the parameter has a default value, making it optional.
+    pub has_default: bool,
+}
+
+impl ExtParam {
+    /// Creates a required parameter with no type hint.
+    #[must_use]
+    pub fn new(name: impl Into<String>, kind: ExtParamKind) -> Self {
+        Self {
+            name: name.into(),
+            kind,
+            type_hint: None,
+            has_default: false,
+        }
+    }
+
+    /// Sets the type annotation of the parameter.
+    #[must_use]
+    pub fn type_hint(mut self, type_hint: impl Into<String>) -> Self {
+        self.type_hint = Some(type_hint.into());
+        self
+    }
+
+    /// Marks the parameter as having a default value.
+    #[must_use]
+    pub fn with_default(mut self) -> Self {
+        self.has_default = true;
+        self
+    }
+
+    /// Whether the parameter can be filled by a positional argument.
+    fn is_positional(&self) -> bool {
+        matches!(
+            self.kind,
+            ExtParamKind::PositionalOnly | ExtParamKind::PositionalOrKeyword
+        )
+    }
+
+    /// Whether the parameter must be provided by the caller.
+    fn is_required(&self) -> bool {
+        !self.has_default && !matches!(self.kind, ExtParamKind::VarPositional | ExtParamKind::VarKeyword)
+    }
+}
+
+/// Declared signature of an external function.
+///
+/// Build with [`ExtFunctionSignature::new`] and the builder methods, then register it with
+/// [`MontyRun::register_external_signature`](crate::MontyRun::register_external_signature).
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ExtFunctionSignature {
+    /// Name of the external function, must match one of the runner's external functions.
+    pub name: String,
+    /// Parameters in declaration order.
+    pub params: Vec<ExtParam>,
+    /// Return type annotation as Python source, used only for type checking.
+    pub return_type: Option<String>,
+    /// Whether the function is declared `async` for type checking.
+    pub is_async: bool,
+}
+
+impl ExtFunctionSignature {
+    /// Creates a signature with no parameters.
+    #[must_use]
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            params: Vec::new(),
+            return_type: None,
+            is_async: false,
+        }
+    }
+
+    /// Appends a parameter.
+    #[must_use]
+    pub fn param(mut self, param: ExtParam) -> Self {
+        self.params.push(param);
+        self
+    }
+
+    /// Sets the return type annotation.
+    #[must_use]
+    pub fn return_type(mut self, return_type: impl Into<String>) -> Self {
+        self.return_type = Some(return_type.into());
+        self
+    }
+
+    /// Declares the function as `async` for type checking.
+    #[must_use]
+    pub fn is_async(mut self) -> Self {
+        self.is_async = true;
+        self
+    }
+
+    /// Renders the signature as a stub definition for the type checker,
+    /// e.g. `def fetch(url: str, /, *, timeout: float = ...) -> bytes: ...`.
+    #[must_use]
+    pub fn type_stub(&self) -> String {
+        let mut stub = String::new();
+        if self.is_async {
+            stub.push_str("async ");
+        }
+        write!(stub, "def {}(", self.name).unwrap();
+        let mut previous: Option<ExtParamKind> = None;
+        for param in &self.params {
+            if previous == Some(ExtParamKind::PositionalOnly) && param.kind != ExtParamKind::PositionalOnly {
+                stub.push_str("/, ");
+            }
+            if param.kind == ExtParamKind::KeywordOnly
+                && !matches!(previous, Some(ExtParamKind::KeywordOnly | ExtParamKind::VarPositional))
+            {
+                stub.push_str("*, ");
+            }
+            match param.kind {
+                ExtParamKind::VarPositional => stub.push('*'),
+                ExtParamKind::VarKeyword => stub.push_str("**"),
+                _ => {}
+            }
+            stub.push_str(&param.name);
+            if let Some(type_hint) = &param.type_hint {
+                write!(stub, ": {type_hint}").unwrap();
+            }
+            if param.has_default {
+                stub.push_str(" = ...");
+            }
+            stub.push_str(", ");
+            previous = Some(param.kind);
+        }
+        if previous == Some(ExtParamKind::PositionalOnly) {
+            stub.push_str("/, ");
+        }
+        if stub.ends_with(", ") {
+            stub.truncate(stub.len() - 2);
+        }
+        stub.push(')');
+        if let Some(return_type) = &self.return_type {
+            write!(stub, " -> {return_type}").unwrap();
+        }
+        stub.push_str(": ...");
+        stub
+    }
+
+    /// Checks that `args` can be bound to this signature, without consuming them.
+    ///
+    /// Errors match CPython's messages for user-defined functions, and are reported in the
+    /// same order: keyword errors first, then too many positional arguments, then missing ones.
+    pub(crate) fn check_args(
+        &self,
+        args: &ArgValues,
+        heap: &Heap<impl ResourceTracker>,
+        interns: &Interns,
+    ) -> RunResult<()> {
+        let name = self.name.as_str();
+        let positional_params: Vec<&ExtParam> = self.params.iter().filter(|p| p.is_positional()).collect();
+        let has_var_positional = self.params.iter().any(|p| p.kind == ExtParamKind::VarPositional);
+        let has_var_keyword = self.params.iter().any(|p| p.kind == ExtParamKind::VarKeyword);
+
+        let positional_given = args.count();
+        let mut bound: Vec<&str> = positional_params
+            .iter()
+            .take(positional_given)
+            .map(|p| p.name.as_str())
+            .collect();
+
+        let mut posonly_as_keyword: Vec<&str> = Vec::new();
+        let mut unexpected_keyword: Option<String> = None;
+        let mut kwonly_given = 0;
+        for keyword in keyword_names(args, heap, interns)? {
+            let param = self.params.iter().find(|p| {
+                p.name == keyword && !matches!(p.kind, ExtParamKind::VarPositional | ExtParamKind::VarKeyword)
+            });
+            match param {
+                Some(param) if param.kind == ExtParamKind::PositionalOnly => {
+                    if !has_var_keyword {
+                        posonly_as_keyword.push(&param.name);
+                    }
+                }
+                Some(param) => {
+                    if bound.contains(&param.name.as_str()) {
+                        return Err(ExcType::type_error_duplicate_arg(name, &keyword));
+                    }
+                    if param.kind == ExtParamKind::KeywordOnly {
+                        kwonly_given += 1;
+                    }
+                    bound.push(&param.name);
+                }
+                None => {
+                    if !has_var_keyword && unexpected_keyword.is_none() {
+                        unexpected_keyword = Some(keyword);
+                    }
+                }
+            }
+        }
+
+        if !posonly_as_keyword.is_empty() {
+            return Err(ExcType::type_error_positional_only(name, &posonly_as_keyword));
+        }
+        if let Some(keyword) = unexpected_keyword {
+            return Err(ExcType::type_error_unexpected_keyword(name, &keyword));
+        }
+        if positional_given > positional_params.len() && !has_var_positional {
+            let min = positional_params.iter().filter(|p| p.is_required()).count();
+            return Err(ExcType::type_error_too_many_positional(
+                name,
+                min,
+                positional_params.len(),
+                positional_given,
+                kwonly_given,
+            ));
+        }
+
+        let missing_positional: Vec<&str> = positional_params
+            .iter()
+            .filter(|p| p.is_required() && !bound.contains(&p.name.as_str()))
+            .map(|p| p.name.as_str())
+            .collect();
+        if !missing_positional.is_empty() {
+            return Err(ExcType::type_error_missing_positional_with_names(
+                name,
+                &missing_positional,
+            ));
+        }
+
+        let missing_kwonly: Vec<&str> = self
+            .params
+            .iter()
+            .filter(|p| p.kind == ExtParamKind::KeywordOnly && p.is_required() && !bound.contains(&p.name.as_str()))
+            .map(|p| p.name.as_str())
+            .collect();
+        if !missing_kwonly.is_empty() {
+            return Err(ExcType::type_error_missing_kwonly_with_names(name, &missing_kwonly));
+        }
+        Ok(())
+    }
+}
+
+/// Collects the keyword argument names of a call without consuming the arguments.
+fn keyword_names(args: &ArgValues, heap: &Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<Vec<String>> {
+    let kwargs = match args {
+        ArgValues::Kwargs(kwargs) | ArgValues::ArgsKargs { kwargs, .. } => kwargs,
+        _ => return Ok(Vec::new()),
+    };
+    match kwargs {
+        KwargsValues::Empty => Ok(Vec::new()),
+        KwargsValues::Inline(kvs) => Ok(kvs.iter().map(|(k, _)| interns.get_str(*k).to_owned()).collect()),
+        KwargsValues::Dict(dict) => dict
+            .iter()
+            .map(|(key, _)| {
+                key.as_either_str(heap)
+                    .map(|s| s.as_str(interns).to_owned())
+                    .ok_or_else(ExcType::type_error_kwargs_nonstring_key)
+            })
+            .collect(),
+    }
+}
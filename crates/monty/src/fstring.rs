@@ -8,10 +8,13 @@
 
 use std::str::FromStr;
 
+use num_bigint::{BigInt, Sign};
+use num_traits::ToPrimitive;
+
 use crate::{
-    exception_private::{ExcType, RunError, SimpleException},
+    exception_private::{ExcType, RunError},
     expressions::ExprLoc,
-    heap::Heap,
+    heap::{Heap, HeapData},
     intern::{Interns, StringId},
     resource::{DepthGuard, ResourceTracker},
     types::{PyTrait, Type},
@@ -89,62 +92,79 @@ pub enum FormatSpec {
 
 /// Parsed format specification following Python's format mini-language.
 ///
-/// Format: `[[fill]align][sign][z][#][0][width][grouping_option][.precision][type]`
+/// Format: `[[fill]align][sign][#][0][width][grouping_option][.precision][type]`
 ///
 /// This struct is parsed at parse time for static format specs, avoiding runtime
 /// string parsing. For dynamic format specs, parsing happens after evaluation.
 #[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
 pub struct ParsedFormatSpec {
-    /// Fill character for padding (default: space)
+    /// Fill character for padding (default: space, or '0' when `zero_pad` is set without a fill)
     pub fill: char,
     /// Alignment: '<' (left), '>' (right), '^' (center), '=' (sign-aware)
     pub align: Option<char>,
     /// Sign handling: '+' (always), '-' (negative only), ' ' (space for positive)
     pub sign: Option<char>,
-    /// Whether to zero-pad numbers
+    /// Whether to zero-pad numbers; numbers default to '=' alignment when set
     pub zero_pad: bool,
+    /// Alternate form (`#`): base prefixes for ints, always keep the decimal point for floats
+    pub alternate: bool,
     /// Minimum field width
     pub width: usize,
+    /// Thousands separator: ',' or '_'
+    pub grouping: Option<char>,
     /// Precision for floats or max width for strings
     pub precision: Option<usize>,
     /// Type character: 's', 'd', 'f', 'e', 'g', etc.
     pub type_char: Option<char>,
 }
 
+impl ParsedFormatSpec {
+    /// Returns true for the empty format spec, which formats every type as `str(value)`.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.fill == ' '
+            && self.align.is_none()
+            && self.sign.is_none()
+            && !self.zero_pad
+            && !self.alternate
+            && self.width == 0
+            && self.grouping.is_none()
+            && self.precision.is_none()
+            && self.type_char.is_none()
+    }
+}
+
 impl FromStr for ParsedFormatSpec {
-    type Err = String;
+    type Err = FormatSpecError;
 
     /// Parses a format specification string into its components.
     ///
-    /// Returns an error if the specifier contains invalid or unrecognized characters.
-    /// The error includes the original specifier for use in error messages.
+    /// Returns an error if the specifier contains invalid or unrecognized characters,
+    /// or a grouping option that can never be valid.
     fn from_str(spec: &str) -> Result<Self, Self::Err> {
-        if spec.is_empty() {
-            return Ok(Self {
-                fill: ' ',
-                ..Default::default()
-            });
-        }
-
         let mut result = Self {
             fill: ' ',
             ..Default::default()
         };
+        if spec.is_empty() {
+            return Ok(result);
+        }
+
         let mut chars = spec.chars().peekable();
+        let mut explicit_fill = false;
 
         // Parse fill and align: [[fill]align]
         let first = chars.peek().copied();
         let second_pos = spec.chars().nth(1);
 
-        if let Some(second) = second_pos {
-            if matches!(second, '<' | '>' | '^' | '=') {
-                // First char is fill, second is align
-                result.fill = first.unwrap_or(' ');
-                chars.next();
-                result.align = chars.next();
-            } else if matches!(first, Some('<' | '>' | '^' | '=')) {
-                result.align = chars.next();
-            }
+        if let Some(second) = second_pos
+            && matches!(second, '<' | '>' | '^' | '=')
+        {
+            // First char is fill, second is align
+            result.fill = first.unwrap_or(' ');
+            explicit_fill = true;
+            chars.next();
+            result.align = chars.next();
         } else if matches!(first, Some('<' | '>' | '^' | '=')) {
             result.align = chars.next();
         }
@@ -154,14 +174,18 @@ impl FromStr for ParsedFormatSpec {
             result.sign = chars.next();
         }
 
-        // Skip '#' (alternate form) for now
+        // Parse alternate form flag
         if chars.peek() == Some(&'#') {
+            result.alternate = true;
             chars.next();
         }
 
-        // Parse zero-padding flag (must come before width)
+        // Parse zero-padding flag (must come before width); it implies a '0' fill
         if chars.peek() == Some(&'0') {
             result.zero_pad = true;
+            if !explicit_fill {
+                result.fill = '0';
+            }
             chars.next();
         }
 
@@ -176,12 +200,19 @@ impl FromStr for ParsedFormatSpec {
             }
         }
         if !width_str.is_empty() {
-            result.width = width_str.parse().unwrap_or(0);
+            result.width = width_str
+                .parse()
+                .map_err(|_| FormatSpecError::Invalid(spec.to_owned()))?;
         }
 
-        // Skip grouping option (comma or underscore)
-        if matches!(chars.peek(), Some(',' | '_')) {
-            chars.next();
+        // Parse grouping option (comma or underscore)
+        if let Some(separator) = chars.next_if(|&c| matches!(c, ',' | '_')) {
+            result.grouping = Some(separator);
+            if chars.next_if(|&c| matches!(c, ',' | '_') && c != separator).is_some() {
+                return Err(FormatSpecError::Grouping(FormatError::ValueError(
+                    "Cannot specify both ',' and '_'.".to_owned(),
+                )));
+            }
         }
 
         // Parse precision: .N
@@ -196,9 +227,12 @@ impl FromStr for ParsedFormatSpec {
                     break;
                 }
             }
-            if !prec_str.is_empty() {
-                result.precision = Some(prec_str.parse().unwrap_or(0));
-            }
+            // A '.' must be followed by the precision digits
+            result.precision = Some(
+                prec_str
+                    .parse()
+                    .map_err(|_| FormatSpecError::Invalid(spec.to_owned()))?,
+            );
         }
 
         // Parse type character: s, d, f, e, g, etc.
@@ -212,9 +246,35 @@ impl FromStr for ParsedFormatSpec {
             chars.next();
         }
 
-        // Error if there are any unconsumed characters
-        if chars.peek().is_some() {
-            return Err(spec.to_owned());
+        // Error if there are any unconsumed characters. Like CPython, a single unknown
+        // character is taken as the type, which no grouping option is allowed with.
+        if let Some(c) = chars.next() {
+            if let Some(separator) = result.grouping
+                && chars.peek().is_none()
+            {
+                return Err(FormatSpecError::Grouping(FormatError::grouping_not_allowed(
+                    separator, c,
+                )));
+            }
+            return Err(FormatSpecError::Invalid(spec.to_owned()));
+        }
+
+        // CPython checks the grouping option against the type before the value's type,
+        // so e.g. `format(1.5, ',b')` complains about the comma rather than the `b`
+        if let Some(separator) = result.grouping
+            && let Some(c) = result.type_char
+        {
+            let allowed = match c {
+                'd' | 'e' | 'E' | 'f' | 'F' | 'g' | 'G' | '%' => true,
+                // Underscores group every four digits in binary, octal and hex
+                'b' | 'o' | 'x' | 'X' => separator == '_',
+                _ => false,
+            };
+            if !allowed {
+                return Err(FormatSpecError::Grouping(FormatError::grouping_not_allowed(
+                    separator, c,
+                )));
+            }
         }
 
         Ok(result)
@@ -225,6 +285,17 @@ impl FromStr for ParsedFormatSpec {
 // Format errors
 // ============================================================================
 
+/// Error returned when a format specification string can't be parsed.
+#[derive(Debug, Clone)]
+pub enum FormatSpecError {
+    /// The spec contains unrecognized or trailing characters; holds the whole spec
+    /// for the `Invalid format specifier '...'` message.
+    Invalid(String),
+    /// The grouping option is combined with a second separator or an unknown type,
+    /// e.g. `',_'` or `',,'`. CPython raises these when formatting, as a `ValueError`.
+    Grouping(FormatError),
+}
+
 /// Error type for format specification failures.
 ///
 /// These errors are returned from formatting functions and should be converted
@@ -249,14 +320,34 @@ impl std::fmt::Display for FormatError {
     }
 }
 
+impl FormatError {
+    /// Error for a presentation type the value's type does not support.
+    ///
+    /// Matches CPython: `Unknown format code 'd' for object of type 'float'`
+    fn unknown_code(type_char: char, value_type: Type) -> Self {
+        Self::ValueError(format!(
+            "Unknown format code '{type_char}' for object of type '{value_type}'"
+        ))
+    }
+
+    /// Error for a grouping option that is not allowed with the presentation type.
+    ///
+    /// Matches CPython: `Cannot specify ',' with 'x'.`
+    fn grouping_not_allowed(separator: char, type_char: char) -> Self {
+        Self::ValueError(format!("Cannot specify '{separator}' with '{type_char}'."))
+    }
+}
+
 /// Formats a value according to a format specification, applying type-appropriate formatting.
 ///
-/// Dispatches to the appropriate formatting function based on the value type and format spec:
-/// - Integers: `format_int`, `format_int_base`, `format_char`
-/// - Floats: `format_float_f`, `format_float_e`, `format_float_g`, `format_float_percent`
+/// This is the `__format__` protocol for builtin types, shared by f-strings and `format()`:
+/// - Integers (including bools with a non-empty spec and long ints): `format_int`
+/// - Floats: `format_float`
 /// - Strings: `format_string`
+/// - Any other type: only the empty spec is allowed, producing `str(value)`
 ///
-/// Returns a `ValueError` if the format type character is incompatible with the value type.
+/// Returns a `ValueError` if the format type character is incompatible with the value type,
+/// and a `TypeError` for non-empty specs on types without format support.
 pub fn format_with_spec(
     value: &Value,
     spec: &ParsedFormatSpec,
@@ -266,56 +357,42 @@ pub fn format_with_spec(
 ) -> Result<String, RunError> {
     let value_type = value.py_type(heap);
 
-    match (value, spec.type_char) {
-        // Integer formatting
-        (Value::Int(n), None | Some('d')) => Ok(format_int(*n, spec)),
-        (Value::Int(n), Some('b')) => Ok(format_int_base(*n, 2, spec)?),
-        (Value::Int(n), Some('o')) => Ok(format_int_base(*n, 8, spec)?),
-        (Value::Int(n), Some('x')) => Ok(format_int_base(*n, 16, spec)?),
-        (Value::Int(n), Some('X')) => Ok(format_int_base(*n, 16, spec)?.to_uppercase()),
-        (Value::Int(n), Some('c')) => Ok(format_char(*n, spec)?),
-
-        // Float formatting
-        (Value::Float(f), None | Some('g' | 'G')) => Ok(format_float_g(*f, spec)),
-        (Value::Float(f), Some('f' | 'F')) => Ok(format_float_f(*f, spec)),
-        (Value::Float(f), Some('e')) => Ok(format_float_e(*f, spec, false)),
-        (Value::Float(f), Some('E')) => Ok(format_float_e(*f, spec, true)),
-        (Value::Float(f), Some('%')) => Ok(format_float_percent(*f, spec)),
-
-        // Int to float formatting (Python allows this)
-        (Value::Int(n), Some('f' | 'F')) => Ok(format_float_f(*n as f64, spec)),
-        (Value::Int(n), Some('e')) => Ok(format_float_e(*n as f64, spec, false)),
-        (Value::Int(n), Some('E')) => Ok(format_float_e(*n as f64, spec, true)),
-        (Value::Int(n), Some('g' | 'G')) => Ok(format_float_g(*n as f64, spec)),
-        (Value::Int(n), Some('%')) => Ok(format_float_percent(*n as f64, spec)),
-
-        // String formatting (including InternString and heap strings)
-        (_, None | Some('s')) if value_type == Type::Str => {
-            let s = value.py_str(heap, guard, interns);
-            Ok(format_string(&s, spec)?)
-        }
-
-        // Bool as int
-        (Value::Bool(b), Some('d')) => Ok(format_int(i64::from(*b), spec)),
+    let int_value = match value {
+        Value::Int(n) => Some(IntValue::Small(*n)),
+        // bool uses int's __format__, except that the empty spec gives 'True'/'False'
+        Value::Bool(b) if !spec.is_empty() => Some(IntValue::Small(i64::from(*b))),
+        Value::InternLongInt(long_int_id) => Some(IntValue::Big(interns.get_long_int(*long_int_id))),
+        Value::Ref(heap_id) => match heap.get(*heap_id) {
+            HeapData::LongInt(li) => Some(IntValue::Big(li.inner())),
+            _ => None,
+        },
+        _ => None,
+    };
+    if let Some(int_value) = int_value {
+        return Ok(format_int(&int_value, spec, value_type)?);
+    }
 
-        // No type specifier: convert to string and format
-        (_, None) => {
+    match value {
+        Value::Float(f) => Ok(format_float(*f, spec, value_type)?),
+        _ if value_type == Type::Str => {
+            if let Some(c) = spec.type_char
+                && c != 's'
+            {
+                return Err(FormatError::unknown_code(c, value_type).into());
+            }
             let s = value.py_str(heap, guard, interns);
             Ok(format_string(&s, spec)?)
         }
-
-        // Type mismatch errors
-        (_, Some(c)) => Err(SimpleException::new_msg(
-            ExcType::ValueError,
-            format!("Unknown format code '{c}' for object of type '{value_type}'"),
-        )
-        .into()),
+        _ if spec.is_empty() => Ok(value.py_str(heap, guard, interns).into_owned()),
+        _ => Err(ExcType::type_error(format!(
+            "unsupported format string passed to {value_type}.__format__"
+        ))),
     }
 }
 
 /// Encodes a ParsedFormatSpec into a u64 for storage in bytecode constants.
 ///
-/// Encoding layout (fits in 48 bits):
+/// Encoding layout (fits in 54 bits):
 /// - bits 0-7: fill character (as ASCII, default space=32)
 /// - bits 8-10: align (0=none, 1='<', 2='>', 3='^', 4='=')
 /// - bits 11-12: sign (0=none, 1='+', 2='-', 3=' ')
@@ -323,6 +400,8 @@ pub fn format_with_spec(
 /// - bits 14-29: width (16 bits, max 65535)
 /// - bits 30-45: precision (16 bits, using 0xFFFF as "no precision")
 /// - bits 46-50: type_char (0=none, 1-15=explicit type mapping: b,c,d,e,E,f,F,g,G,n,o,s,x,X,%)
+/// - bit 51: alternate form
+/// - bits 52-53: grouping (0=none, 1=',', 2='_')
 pub fn encode_format_spec(spec: &ParsedFormatSpec) -> u64 {
     let fill = spec.fill as u64;
     let align = match spec.align {
@@ -361,8 +440,21 @@ pub fn encode_format_spec(spec: &ParsedFormatSpec) -> u64 {
         '%' => 15,
         _ => 0,
     });
+    let alternate = u64::from(spec.alternate);
+    let grouping = match spec.grouping {
+        Some(',') => 1u64,
+        Some('_') => 2,
+        _ => 0,
+    };
 
-    fill | (align << 8) | (sign << 11) | (zero_pad << 13) | (width << 14) | (precision << 30) | (type_char << 46)
+    fill | (align << 8)
+        | (sign << 11)
+        | (zero_pad << 13)
+        | (width << 14)
+        | (precision << 30)
+        | (type_char << 46)
+        | (alternate << 51)
+        | (grouping << 52)
 }
 
 /// Decodes a u64 back into a ParsedFormatSpec.
@@ -378,6 +470,8 @@ pub fn decode_format_spec(encoded: u64) -> ParsedFormatSpec {
     let width = ((encoded >> 14) & 0xFFFF) as usize;
     let precision_raw = ((encoded >> 30) & 0xFFFF) as usize;
     let type_bits = ((encoded >> 46) & 0x1F) as u8;
+    let alternate = ((encoded >> 51) & 0x01) != 0;
+    let grouping_bits = (encoded >> 52) & 0x03;
 
     let align = match align_bits {
         1 => Some('<'),
@@ -419,12 +513,20 @@ pub fn decode_format_spec(encoded: u64) -> ParsedFormatSpec {
         _ => None,
     };
 
+    let grouping = match grouping_bits {
+        1 => Some(','),
+        2 => Some('_'),
+        _ => None,
+    };
+
     ParsedFormatSpec {
         fill,
         align,
         sign,
         zero_pad,
+        alternate,
         width,
+        grouping,
         precision,
         type_char,
     }
@@ -440,8 +542,27 @@ pub fn decode_format_spec(encoded: u64) -> ParsedFormatSpec {
 /// 1. Truncation: If `precision` is set, limits the string to that many characters
 /// 2. Alignment: Pads to `width` using `fill` character (default left-aligned for strings)
 ///
-/// Returns an error if `=` alignment is used (sign-aware padding only valid for numbers).
+/// Returns an error for numeric-only options: a sign, `#`, a grouping option, or `=` alignment.
 pub fn format_string(value: &str, spec: &ParsedFormatSpec) -> Result<String, FormatError> {
+    if spec.sign.is_some() {
+        return Err(FormatError::ValueError(
+            "Sign not allowed in string format specifier".to_owned(),
+        ));
+    }
+    if spec.alternate {
+        return Err(FormatError::ValueError(
+            "Alternate form (#) not allowed in string format specifier".to_owned(),
+        ));
+    }
+    if spec.align == Some('=') {
+        return Err(FormatError::InvalidAlignment(
+            "'=' alignment not allowed in string format specifier".to_owned(),
+        ));
+    }
+    if let Some(separator) = spec.grouping {
+        return Err(FormatError::grouping_not_allowed(separator, 's'));
+    }
+
     // Handle precision (string truncation)
     let value = if let Some(prec) = spec.precision {
         value.chars().take(prec).collect::<String>()
@@ -449,222 +570,268 @@ pub fn format_string(value: &str, spec: &ParsedFormatSpec) -> Result<String, For
         value.to_owned()
     };
 
-    // Validate alignment for strings (= is only for numbers)
-    if spec.align == Some('=') {
-        return Err(FormatError::InvalidAlignment(
-            "'=' alignment not allowed in string format specifier".to_owned(),
-        ));
-    }
-
     // Default alignment for strings is left ('<')
     let align = spec.align.unwrap_or('<');
     Ok(pad_string(&value, spec.width, align, spec.fill))
 }
 
-/// Formats an integer in decimal with a format specification.
-///
-/// Applies the following:
-/// - Sign prefix based on `sign` spec: `+` (always show), `-` (negatives only), ` ` (space for positive)
-/// - Zero-padding: When `zero_pad` is true or `=` alignment, inserts zeros between sign and digits
-/// - Alignment: Right-aligned by default for numbers, pads to `width` with `fill` character
-pub fn format_int(n: i64, spec: &ParsedFormatSpec) -> String {
-    let is_negative = n < 0;
-    let abs_str = n.abs().to_string();
-
-    // Build the sign prefix
-    let sign = if is_negative {
-        "-"
-    } else {
-        match spec.sign {
-            Some('+') => "+",
-            Some(' ') => " ",
-            _ => "",
+/// An integer being formatted, either machine-sized or arbitrary precision.
+enum IntValue<'a> {
+    /// A machine-sized `int` (including `bool`).
+    Small(i64),
+    /// An arbitrary precision `int` borrowed from the heap or interns.
+    Big(&'a BigInt),
+}
+
+impl IntValue<'_> {
+    /// Returns true if the integer is below zero.
+    fn is_negative(&self) -> bool {
+        match self {
+            Self::Small(n) => *n < 0,
+            Self::Big(b) => b.sign() == Sign::Minus,
         }
-    };
+    }
 
-    // Default alignment for numbers is right ('>')
-    let align = spec.align.unwrap_or('>');
-
-    // Handle sign-aware zero-padding or regular padding
-    if spec.zero_pad || align == '=' {
-        let fill = if spec.zero_pad { '0' } else { spec.fill };
-        let total_len = sign.len() + abs_str.len();
-        if spec.width > total_len {
-            let padding = spec.width - total_len;
-            let pad_str: String = std::iter::repeat_n(fill, padding).collect();
-            format!("{sign}{pad_str}{abs_str}")
-        } else {
-            format!("{sign}{abs_str}")
+    /// Returns the digits of the absolute value in the given base (lowercase, no prefix).
+    fn abs_digits(&self, base: u32) -> String {
+        match self {
+            Self::Small(n) => {
+                let abs = n.unsigned_abs();
+                match base {
+                    2 => format!("{abs:b}"),
+                    8 => format!("{abs:o}"),
+                    16 => format!("{abs:x}"),
+                    _ => abs.to_string(),
+                }
+            }
+            Self::Big(b) => b.magnitude().to_str_radix(base),
+        }
+    }
+
+    /// Returns the value as an `i64`, or `None` if it does not fit.
+    fn to_i64(&self) -> Option<i64> {
+        match self {
+            Self::Small(n) => Some(*n),
+            Self::Big(b) => b.to_i64(),
+        }
+    }
+
+    /// Returns the value as a finite `f64`, or `None` if it is too large.
+    fn to_f64(&self) -> Option<f64> {
+        match self {
+            Self::Small(n) => Some(*n as f64),
+            Self::Big(b) => b.to_f64().filter(|f| f.is_finite()),
         }
-    } else {
-        let value = format!("{sign}{abs_str}");
-        pad_string(&value, spec.width, align, spec.fill)
     }
 }
 
-/// Formats an integer in binary (base 2), octal (base 8), or hexadecimal (base 16).
+/// Formats an integer with a format specification (types `d`, `n`, `b`, `o`, `x`, `X`, `c`,
+/// and the float types, which convert the integer to a float first).
 ///
-/// Used for format types `b`, `o`, `x`, and `X`. The sign is prepended for negative numbers.
-/// Does not include base prefixes like `0b`, `0o`, `0x` (those require the `#` flag which
-/// is not yet implemented). Returns an error for invalid base values.
-pub fn format_int_base(n: i64, base: u32, spec: &ParsedFormatSpec) -> Result<String, FormatError> {
-    let is_negative = n < 0;
-    let abs_val = n.unsigned_abs();
-
-    let abs_str = match base {
-        2 => format!("{abs_val:b}"),
-        8 => format!("{abs_val:o}"),
-        16 => format!("{abs_val:x}"),
-        _ => return Err(FormatError::ValueError("Invalid base".to_owned())),
+/// Supports sign, `#` base prefixes, zero padding, and `,`/`_` grouping (every 4 digits
+/// for non-decimal bases). Right-aligned by default.
+fn format_int(value: &IntValue<'_>, spec: &ParsedFormatSpec, value_type: Type) -> Result<String, FormatError> {
+    let (base, prefix) = match spec.type_char {
+        None | Some('d' | 'n') => (10, ""),
+        Some('b') => (2, "0b"),
+        Some('o') => (8, "0o"),
+        Some('x') => (16, "0x"),
+        Some('X') => (16, "0X"),
+        Some('c') => return format_char(value, spec),
+        Some('e' | 'E' | 'f' | 'F' | 'g' | 'G' | '%') => {
+            let f = value
+                .to_f64()
+                .ok_or_else(|| FormatError::Overflow("int too large to convert to float".to_owned()))?;
+            return format_float(f, spec, value_type);
+        }
+        Some(c) => return Err(FormatError::unknown_code(c, value_type)),
     };
 
-    let sign = if is_negative { "-" } else { "" };
-    let value = format!("{sign}{abs_str}");
-
-    let align = spec.align.unwrap_or('>');
-    Ok(pad_string(&value, spec.width, align, spec.fill))
+    if spec.precision.is_some() {
+        return Err(FormatError::ValueError(
+            "Precision not allowed in integer format specifier".to_owned(),
+        ));
+    }
+    let mut digits = value.abs_digits(base);
+    if spec.type_char == Some('X') {
+        digits.make_ascii_uppercase();
+    }
+    let prefix = if spec.alternate { prefix } else { "" };
+    let group_interval = if base == 10 { 3 } else { 4 };
+    Ok(format_number(
+        value.is_negative(),
+        prefix,
+        &digits,
+        "",
+        spec,
+        group_interval,
+    ))
 }
 
 /// Formats an integer as a Unicode character (format type `c`).
 ///
 /// Converts the integer to its corresponding Unicode code point. Valid range is 0 to 0x10FFFF.
 /// Returns `Overflow` error if out of range, `ValueError` if not a valid Unicode scalar value
-/// (e.g., surrogate code points). Left-aligned by default like strings.
-pub fn format_char(n: i64, spec: &ParsedFormatSpec) -> Result<String, FormatError> {
-    if !(0..=0x0010_FFFF).contains(&n) {
-        return Err(FormatError::Overflow("%c arg not in range(0x110000)".to_owned()));
-    }
-    let n_u32 = u32::try_from(n).expect("format_char n validated in 0..=0x10FFFF range");
-    let c = char::from_u32(n_u32).ok_or_else(|| FormatError::ValueError("Invalid Unicode code point".to_owned()))?;
-    let value = c.to_string();
-    let align = spec.align.unwrap_or('<');
-    Ok(pad_string(&value, spec.width, align, spec.fill))
+/// (e.g., surrogate code points) or if numeric-only options are used.
+fn format_char(value: &IntValue<'_>, spec: &ParsedFormatSpec) -> Result<String, FormatError> {
+    if spec.precision.is_some() {
+        return Err(FormatError::ValueError(
+            "Precision not allowed in integer format specifier".to_owned(),
+        ));
+    }
+    if spec.sign.is_some() {
+        return Err(FormatError::ValueError(
+            "Sign not allowed with integer format specifier 'c'".to_owned(),
+        ));
+    }
+    if spec.alternate {
+        return Err(FormatError::ValueError(
+            "Alternate form (#) not allowed with integer format specifier 'c'".to_owned(),
+        ));
+    }
+    let code = value
+        .to_i64()
+        .filter(|n| (0..=0x0010_FFFF).contains(n))
+        .ok_or_else(|| FormatError::Overflow("%c arg not in range(0x110000)".to_owned()))?;
+    let code = u32::try_from(code).expect("format_char code validated in 0..=0x10FFFF range");
+    let c = char::from_u32(code).ok_or_else(|| FormatError::ValueError("Invalid Unicode code point".to_owned()))?;
+    Ok(format_number(false, "", "", &c.to_string(), spec, 3))
 }
 
-/// Formats a float in fixed-point notation (format types `f` and `F`).
+/// Formats a float with a format specification (types `e`, `E`, `f`, `F`, `g`, `G`, `n`, `%`,
+/// or no type, which is like `g` but keeps at least one fractional digit).
 ///
-/// Always includes a decimal point with `precision` digits after it (default 6).
-/// Handles sign prefix, zero-padding between sign and digits when `zero_pad` or `=` alignment.
-/// Right-aligned by default. NaN and infinity are formatted as `nan`/`inf` (or `NAN`/`INF` for `F`).
-pub fn format_float_f(f: f64, spec: &ParsedFormatSpec) -> String {
-    let precision = spec.precision.unwrap_or(6);
-    let is_negative = f.is_sign_negative() && !f.is_nan();
+/// NaN and infinity are formatted as `nan`/`inf` (uppercase for `E`, `F`, `G`).
+/// Right-aligned by default.
+fn format_float(f: f64, spec: &ParsedFormatSpec, value_type: Type) -> Result<String, FormatError> {
+    if let Some(c) = spec.type_char
+        && !matches!(c, 'e' | 'E' | 'f' | 'F' | 'g' | 'G' | 'n' | '%')
+    {
+        return Err(FormatError::unknown_code(c, value_type));
+    }
+    let negative = f.is_sign_negative() && !f.is_nan();
     let abs_val = f.abs();
+    let alternate = spec.alternate;
 
-    let abs_str = format!("{abs_val:.precision$}");
-
-    let sign = if is_negative {
-        "-"
-    } else {
-        match spec.sign {
-            Some('+') => "+",
-            Some(' ') => " ",
-            _ => "",
-        }
-    };
-
-    let align = spec.align.unwrap_or('>');
-
-    if spec.zero_pad || align == '=' {
-        let fill = if spec.zero_pad { '0' } else { spec.fill };
-        let total_len = sign.len() + abs_str.len();
-        if spec.width > total_len {
-            let padding = spec.width - total_len;
-            let pad_str: String = std::iter::repeat_n(fill, padding).collect();
-            format!("{sign}{pad_str}{abs_str}")
+    let mut body = if !abs_val.is_finite() {
+        let special = if abs_val.is_nan() { "nan" } else { "inf" };
+        if spec.type_char == Some('%') {
+            format!("{special}%")
         } else {
-            format!("{sign}{abs_str}")
+            special.to_owned()
         }
     } else {
-        let value = format!("{sign}{abs_str}");
-        pad_string(&value, spec.width, align, spec.fill)
+        match spec.type_char {
+            Some('f' | 'F') => float_fixed(abs_val, spec.precision.unwrap_or(6), alternate),
+            Some('e' | 'E') => float_exponent(abs_val, spec.precision.unwrap_or(6), alternate),
+            Some('%') => format!(
+                "{}%",
+                float_fixed(abs_val * 100.0, spec.precision.unwrap_or(6), alternate)
+            ),
+            Some(_) => float_general(abs_val, spec.precision.unwrap_or(6), alternate, false),
+            None => match spec.precision {
+                Some(precision) => float_general(abs_val, precision, alternate, true),
+                None => float_repr(abs_val),
+            },
+        }
+    };
+    if matches!(spec.type_char, Some('E' | 'F' | 'G')) {
+        body.make_ascii_uppercase();
     }
+
+    let int_len = body.find(|c: char| !c.is_ascii_digit()).unwrap_or(body.len());
+    let (int_digits, rest) = body.split_at(int_len);
+    Ok(format_number(negative, "", int_digits, rest, spec, 3))
 }
 
-/// Formats a float in exponential/scientific notation (format types `e` and `E`).
+/// Returns the Python `repr()` of a float.
 ///
-/// Produces output like `1.234568e+03` with `precision` digits after decimal (default 6).
-/// The `uppercase` parameter controls whether to use `E` or `e` for the exponent marker.
-/// Exponent is always formatted with a sign and at least 2 digits (Python convention).
-pub fn format_float_e(f: f64, spec: &ParsedFormatSpec, uppercase: bool) -> String {
-    let precision = spec.precision.unwrap_or(6);
-    let is_negative = f.is_sign_negative() && !f.is_nan();
-    let abs_val = f.abs();
-
-    let abs_str = if uppercase {
-        format!("{abs_val:.precision$E}")
-    } else {
-        format!("{abs_val:.precision$e}")
-    };
-
-    // Fix exponent format to match Python (e+03 not e3)
-    let abs_str = fix_exp_format(&abs_str);
-
-    let sign = if is_negative {
-        "-"
+/// Uses the shortest digit string that round-trips to the same `f64` (Rust's float
+/// formatting provides this), laid out like CPython: fixed-point with at least one
+/// fractional digit when the decimal exponent is in `-4..16`, otherwise scientific
+/// notation such as `1e+16` or `1.5e-05`. The special values are `nan`, `inf` and `-inf`.
+pub fn float_repr(f: f64) -> String {
+    if f.is_nan() {
+        return "nan".to_owned();
+    }
+    if f.is_infinite() {
+        return if f > 0.0 { "inf" } else { "-inf" }.to_owned();
+    }
+    let sci = format!("{f:e}");
+    let (mantissa, exp_str) = sci.split_once('e').expect("exponential format contains 'e'");
+    let exp: i32 = exp_str.parse().expect("exponent is a valid integer");
+    if (-4..16).contains(&exp) {
+        let s = f.to_string();
+        if s.contains('.') { s } else { format!("{s}.0") }
     } else {
-        match spec.sign {
-            Some('+') => "+",
-            Some(' ') => " ",
-            _ => "",
-        }
-    };
+        fix_exp_format(&format!("{mantissa}e{exp}"))
+    }
+}
 
-    let value = format!("{sign}{abs_str}");
-    let align = spec.align.unwrap_or('>');
-    pad_string(&value, spec.width, align, spec.fill)
+/// Formats a non-negative finite float in fixed-point notation with `precision` decimals.
+fn float_fixed(abs_val: f64, precision: usize, alternate: bool) -> String {
+    let mut s = format!("{abs_val:.precision$}");
+    if alternate && precision == 0 {
+        s.push('.');
+    }
+    s
 }
 
-/// Formats a float in "general" format (format types `g` and `G`).
-///
-/// Chooses between fixed-point and exponential notation based on the magnitude:
-/// - Uses exponential if exponent < -4 or >= precision
-/// - Otherwise uses fixed-point notation
+/// Formats a non-negative finite float in exponential notation with `precision` decimals.
 ///
-/// Unlike `f` and `e` formats, trailing zeros are stripped from the result.
-/// Default precision is 6, but minimum is 1 significant digit.
-pub fn format_float_g(f: f64, spec: &ParsedFormatSpec) -> String {
-    let precision = spec.precision.unwrap_or(6).max(1);
-    let is_negative = f.is_sign_negative() && !f.is_nan();
-    let abs_val = f.abs();
-
-    // Python's g format: use exponential if exponent < -4 or >= precision
-    let exp = if abs_val == 0.0 {
-        0
-    } else {
-        // log10 of valid floats fits in i32; floor() returns a finite f64
-        f64_to_i32_trunc(abs_val.log10().floor())
-    };
-
-    // precision is typically small (default 6), safe to convert to i32
-    let prec_i32 = i32::try_from(precision).unwrap_or(i32::MAX);
-    let abs_str = if exp < -4 || exp >= prec_i32 {
-        // Use exponential notation
-        let exp_prec = precision.saturating_sub(1);
-        let formatted = format!("{abs_val:.exp_prec$e}");
-        // Python strips trailing zeros from the mantissa
-        strip_trailing_zeros_exp(&formatted)
+/// The exponent always has a sign and at least 2 digits (Python convention).
+fn float_exponent(abs_val: f64, precision: usize, alternate: bool) -> String {
+    let s = format!("{abs_val:.precision$e}");
+    let s = if alternate && precision == 0 {
+        s.replacen('e', ".e", 1)
     } else {
-        // Use fixed notation - result is non-negative due to .max(0)
-        let sig_digits_i32 = (prec_i32 - exp - 1).max(0);
-        let sig_digits = usize::try_from(sig_digits_i32).expect("sig_digits guaranteed non-negative");
-        let formatted = format!("{abs_val:.sig_digits$}");
-        strip_trailing_zeros(&formatted)
+        s
     };
+    fix_exp_format(&s)
+}
 
-    let sign = if is_negative {
-        "-"
+/// Formats a non-negative finite float in "general" notation (`g`, or no type when
+/// `repr_style` is set).
+///
+/// Uses exponential notation if the exponent (after rounding to `precision` significant
+/// digits) is < -4 or >= precision, otherwise fixed-point. Trailing zeros are stripped
+/// unless `alternate` is set. With `repr_style`, fixed-point results always keep at least
+/// one fractional digit and exponential notation is used from exponent `precision - 1`.
+fn float_general(abs_val: f64, precision: usize, alternate: bool, repr_style: bool) -> String {
+    let precision = precision.max(1);
+    let exp_prec = precision - 1;
+
+    // Format in exponential first so the exponent reflects rounding (e.g. 9.9999 -> 1e+01)
+    let sci = format!("{abs_val:.exp_prec$e}");
+    let (mantissa, exp_str) = sci.split_once('e').expect("exponential format contains 'e'");
+    let exp: i64 = exp_str.parse().expect("exponent is a valid integer");
+
+    let prec_i64 = i64::try_from(precision).unwrap_or(i64::MAX);
+    let threshold = if repr_style { prec_i64 - 1 } else { prec_i64 };
+    if exp < -4 || exp >= threshold {
+        let mantissa = if alternate {
+            if mantissa.contains('.') {
+                mantissa.to_owned()
+            } else {
+                format!("{mantissa}.")
+            }
+        } else {
+            strip_trailing_zeros(mantissa)
+        };
+        fix_exp_format(&format!("{mantissa}e{exp}"))
     } else {
-        match spec.sign {
-            Some('+') => "+",
-            Some(' ') => " ",
-            _ => "",
+        let decimals = usize::try_from(prec_i64 - 1 - exp).unwrap_or(0);
+        let fixed = format!("{abs_val:.decimals$}");
+        let mut fixed = if alternate { fixed } else { strip_trailing_zeros(&fixed) };
+        if !fixed.contains('.') {
+            if repr_style {
+                fixed.push_str(".0");
+            } else if alternate {
+                fixed.push('.');
+            }
         }
-    };
-
-    let value = format!("{sign}{abs_str}");
-    let align = spec.align.unwrap_or('>');
-    pad_string(&value, spec.width, align, spec.fill)
+        fixed
+    }
 }
 
 /// Applies ASCII conversion to a string (escapes non-ASCII characters).
@@ -692,19 +859,26 @@ pub fn ascii_escape(s: &str) -> String {
     result
 }
 
-/// Formats a float as a percentage (format type `%`).
-///
-/// Multiplies the value by 100 and appends a `%` sign. Uses fixed-point notation
-/// with `precision` decimal places (default 6). For example, `0.1234` becomes `12.340000%`.
-pub fn format_float_percent(f: f64, spec: &ParsedFormatSpec) -> String {
-    let precision = spec.precision.unwrap_or(6);
-    let percent_val = f * 100.0;
-    let is_negative = percent_val.is_sign_negative() && !percent_val.is_nan();
-    let abs_val = percent_val.abs();
-
-    let abs_str = format!("{abs_val:.precision$}%");
+// ============================================================================
+// Helper functions
+// ============================================================================
 
-    let sign = if is_negative {
+/// Assembles a formatted number from its parts and pads it to the spec's width.
+///
+/// `int_digits` is the run of integer digits that grouping separators apply to, and
+/// `rest` is everything after it (fraction, exponent, `%`, or the whole text for values
+/// without leading digits). Numbers are right-aligned by default, or sign-aware (`=`)
+/// when zero padding was requested. When zero padding is combined with grouping, the
+/// padding zeros are grouped too, matching CPython (`format(1234, '010,')` -> `00,001,234`).
+fn format_number(
+    negative: bool,
+    prefix: &str,
+    int_digits: &str,
+    rest: &str,
+    spec: &ParsedFormatSpec,
+    group_interval: usize,
+) -> String {
+    let sign = if negative {
         "-"
     } else {
         match spec.sign {
@@ -713,15 +887,51 @@ pub fn format_float_percent(f: f64, spec: &ParsedFormatSpec) -> String {
             _ => "",
         }
     };
+    let align = spec.align.unwrap_or(if spec.zero_pad { '=' } else { '>' });
 
-    let value = format!("{sign}{abs_str}");
-    let align = spec.align.unwrap_or('>');
-    pad_string(&value, spec.width, align, spec.fill)
+    let int_part = match spec.grouping {
+        Some(separator) if !int_digits.is_empty() => {
+            let min_width = if align == '=' && spec.fill == '0' {
+                spec.width
+                    .saturating_sub(sign.len() + prefix.len() + rest.chars().count())
+            } else {
+                0
+            };
+            group_digits(int_digits, separator, group_interval, min_width)
+        }
+        _ => int_digits.to_owned(),
+    };
+
+    if align == '=' {
+        let len = sign.len() + prefix.len() + int_part.chars().count() + rest.chars().count();
+        let padding: String = std::iter::repeat_n(spec.fill, spec.width.saturating_sub(len)).collect();
+        format!("{sign}{prefix}{padding}{int_part}{rest}")
+    } else {
+        pad_string(&format!("{sign}{prefix}{int_part}{rest}"), spec.width, align, spec.fill)
+    }
 }
 
-// ============================================================================
-// Helper functions
-// ============================================================================
+/// Inserts `separator` every `interval` digits, counting from the right.
+///
+/// If `min_width` is larger than the grouped result, leading zeros are added (and grouped)
+/// until it is reached, never starting the result with a separator.
+fn group_digits(digits: &str, separator: char, interval: usize, min_width: usize) -> String {
+    let grouped_len = |n: usize| n + n.saturating_sub(1) / interval;
+    let mut digit_count = digits.len();
+    while grouped_len(digit_count) < min_width {
+        digit_count += 1;
+    }
+
+    let padded = format!("{digits:0>digit_count$}");
+    let mut result = String::with_capacity(grouped_len(digit_count));
+    for (i, c) in padded.chars().enumerate() {
+        if i > 0 && (digit_count - i) % interval == 0 {
+            result.push(separator);
+        }
+        result.push(c);
+    }
+    result
+}
 
 /// Pads a string to a given width with alignment.
 ///
@@ -787,22 +997,6 @@ fn strip_trailing_zeros(s: &str) -> String {
     }
 }
 
-/// Strips trailing zeros from a float in exponential notation.
-///
-/// Splits the string at `e` or `E`, strips zeros from the mantissa part,
-/// then recombines with the exponent. Also normalizes the exponent format
-/// to Python's convention (sign and at least 2 digits).
-fn strip_trailing_zeros_exp(s: &str) -> String {
-    if let Some(e_pos) = s.find(['e', 'E']) {
-        let (mantissa, exp_part) = s.split_at(e_pos);
-        let trimmed_mantissa = strip_trailing_zeros(mantissa);
-        let fixed_exp = fix_exp_format(exp_part);
-        format!("{trimmed_mantissa}{fixed_exp}")
-    } else {
-        strip_trailing_zeros(s)
-    }
-}
-
 /// Converts Rust's exponential format to Python's format.
 ///
 /// Rust produces "e3" or "e-3" but Python expects "e+03" or "e-03".
@@ -837,20 +1031,3 @@ fn fix_exp_format(s: &str) -> String {
 
     format!("{before_e}{e_char}{sign}{padded_digits}")
 }
-
-/// Truncates f64 to i32 with clamping for out-of-range values.
-///
-/// Used for exponent calculations where the result should fit in i32.
-fn f64_to_i32_trunc(value: f64) -> i32 {
-    if value >= f64::from(i32::MAX) {
-        i32::MAX
-    } else if value <= f64::from(i32::MIN) {
-        i32::MIN
-    } else {
-        // SAFETY for clippy: value is guaranteed to be in (i32::MIN, i32::MAX)
-        // after the bounds checks above, so truncation cannot overflow
-        #[expect(clippy::cast_possible_truncation, reason = "bounds checked above")]
-        let result = value as i32;
-        result
-    }
-}
@@ -1,6 +1,12 @@
 use std::fmt::Write;
 
-use crate::{bytecode::Code, expressions::Identifier, intern::Interns, namespace::NamespaceId, signature::Signature};
+use crate::{
+    bytecode::Code,
+    expressions::Identifier,
+    intern::{Interns, StringId},
+    namespace::NamespaceId,
+    signature::Signature,
+};
 
 /// A defined function once compiled and ready for execution.
 ///
@@ -65,6 +71,8 @@ pub(crate) struct Function {
     pub is_async: bool,
     /// Compiled bytecode for this function body.
     pub code: Code,
+    /// The docstring, if the body starts with a string literal. Shown by `help()`.
+    pub doc: Option<StringId>,
 }
 
 impl Function {
@@ -82,6 +90,7 @@ impl Function {
     /// * `defaults_count` - Number of default parameter values
     /// * `is_async` - Whether this is an async function
     /// * `code` - The compiled bytecode for the function body
+    /// * `doc` - The docstring, if any
     #[expect(clippy::too_many_arguments)]
     pub fn new(
         name: Identifier,
@@ -93,6 +102,7 @@ impl Function {
         defaults_count: usize,
         is_async: bool,
         code: Code,
+        doc: Option<StringId>,
     ) -> Self {
         Self {
             name,
@@ -104,6 +114,7 @@ impl Function {
             defaults_count,
             is_async,
             code,
+            doc,
         }
     }
 
@@ -0,0 +1,139 @@
+//! Hashing for `hash()`, dict keys and set members.
+//!
+//! Hashes are cached on heap objects and so end up in snapshots, which can be resumed by a
+//! different build on a different machine. std's `DefaultHasher` doesn't fit: its algorithm
+//! may change between Rust releases, and hashing `usize`s through any `Hasher` depends on the
+//! platform's pointer width and endianness. [`StableHasher`] pins both down.
+//!
+//! Strings and bytes are additionally mixed with the heap's hash seed, like CPython's
+//! `PYTHONHASHSEED`. The default seed of zero leaves hashes unchanged, so runs are
+//! reproducible; see [`ResourceLimits::random_hash_seed`](crate::ResourceLimits::random_hash_seed)
+//! for randomizing them. Dicts and sets iterate in insertion order, so the seed never changes
+//! iteration order, only the values `hash()` returns.
+
+use std::hash::{BuildHasher, Hasher, RandomState};
+
+/// SipHash-2-4 with fixed keys, writing integers as little-endian and `usize`s as 64 bits.
+///
+/// Produces the same hashes on every platform and Rust release.
+#[derive(Debug, Clone)]
+pub(crate) struct StableHasher(
+    #[expect(deprecated, reason = "the only std hasher with a specified algorithm")] std::hash::SipHasher,
+);
+
+impl StableHasher {
+    /// Creates a hasher keyed with `key`.
+    #[expect(deprecated, reason = "the only std hasher with a specified algorithm")]
+    pub fn with_key(key: u64) -> Self {
+        Self(std::hash::SipHasher::new_with_keys(key, 0))
+    }
+}
+
+impl Default for StableHasher {
+    fn default() -> Self {
+        Self::with_key(0)
+    }
+}
+
+impl Hasher for StableHasher {
+    #[inline]
+    fn finish(&self) -> u64 {
+        self.0.finish()
+    }
+
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        self.0.write(bytes);
+    }
+
+    #[inline]
+    fn write_u8(&mut self, i: u8) {
+        self.0.write(&[i]);
+    }
+
+    #[inline]
+    fn write_u16(&mut self, i: u16) {
+        self.0.write(&i.to_le_bytes());
+    }
+
+    #[inline]
+    fn write_u32(&mut self, i: u32) {
+        self.0.write(&i.to_le_bytes());
+    }
+
+    #[inline]
+    fn write_u64(&mut self, i: u64) {
+        self.0.write(&i.to_le_bytes());
+    }
+
+    #[inline]
+    fn write_u128(&mut self, i: u128) {
+        self.0.write(&i.to_le_bytes());
+    }
+
+    #[inline]
+    fn write_usize(&mut self, i: usize) {
+        self.write_u64(i as u64);
+    }
+
+    #[inline]
+    fn write_i8(&mut self, i: i8) {
+        self.write_u8(i.cast_unsigned());
+    }
+
+    #[inline]
+    fn write_i16(&mut self, i: i16) {
+        self.write_u16(i.cast_unsigned());
+    }
+
+    #[inline]
+    fn write_i32(&mut self, i: i32) {
+        self.write_u32(i.cast_unsigned());
+    }
+
+    #[inline]
+    fn write_i64(&mut self, i: i64) {
+        self.write_u64(i.cast_unsigned());
+    }
+
+    #[inline]
+    fn write_i128(&mut self, i: i128) {
+        self.write_u128(i.cast_unsigned());
+    }
+
+    #[inline]
+    fn write_isize(&mut self, i: isize) {
+        self.write_i64(i as i64);
+    }
+}
+
+/// Hashes `value` with a [`StableHasher`].
+#[must_use]
+pub(crate) fn stable_hash(value: &(impl std::hash::Hash + ?Sized)) -> u64 {
+    let mut hasher = StableHasher::default();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Mixes `seed` into the hash of a string or bytes.
+///
+/// Seed zero returns `hash` unchanged, so the interned string hash caches stay valid whatever
+/// the seed, and equal hashes stay equal under every seed.
+#[inline]
+#[must_use]
+pub(crate) fn apply_seed(hash: u64, seed: u64) -> u64 {
+    if seed == 0 {
+        hash
+    } else {
+        let mut hasher = StableHasher::with_key(seed);
+        hasher.write_u64(hash);
+        hasher.finish()
+    }
+}
+
+/// Returns a random nonzero seed from the OS's randomness, as std seeds `HashMap`s.
+#[must_use]
+pub(crate) fn random_seed() -> u64 {
+    let seed = RandomState::new().hash_one(0u8);
+    if seed == 0 { 1 } else { seed }
+}
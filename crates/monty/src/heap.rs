@@ -1,6 +1,5 @@
 use std::{
     borrow::Cow,
-    collections::hash_map::DefaultHasher,
     fmt::Write,
     hash::{Hash, Hasher},
     mem::{ManuallyDrop, discriminant, size_of},
@@ -8,20 +7,24 @@ use std::{
     vec,
 };
 
-use ahash::AHashSet;
+use ahash::{AHashMap, AHashSet};
 use num_integer::Integer;
 use smallvec::SmallVec;
 
 use crate::{
     args::ArgValues,
     asyncio::{Coroutine, GatherFuture, GatherItem},
+    entropy::Entropy,
     exception_private::{ExcType, RunResult, SimpleException},
-    intern::{FunctionId, Interns, StringId},
+    hashing::{StableHasher, apply_seed, stable_hash},
+    intern::{FunctionId, Interns, StaticStrings, StringId},
     io::PrintWriter,
+    memory_profile::{AllocationTrace, MemoryProfile, TypeUsage},
     resource::{DepthGuard, ResourceError, ResourceTracker, check_mult_size, check_repeat_size},
     types::{
-        AttrCallResult, Bytes, Dataclass, Dict, FrozenSet, List, LongInt, Module, MontyIter, NamedTuple, Path, PyTrait,
-        Range, Set, Slice, Str, Tuple, Type, allocate_tuple,
+        Array, AttrCallResult, Bytes, CsvWriter, Dataclass, Dict, Fraction, FrozenSet, LazySequence, List, LongInt,
+        Module, MontyIter, NamedTuple, Path, PyTrait, Range, Set, Slice, Str, StringIO, Tuple, Type, UnionType,
+        allocate_tuple, str::hash_str, tuple::hash_tuple_items,
     },
     value::{EitherStr, Value},
 };
@@ -131,9 +134,54 @@ pub(crate) enum HeapData {
     /// Pure methods (name, parent, etc.) are handled directly by the VM.
     /// I/O methods (exists, read_text, etc.) yield external function calls.
     Path(Path),
+    /// A union of classes created by `|` (e.g. `int | None`), used by `isinstance()`.
+    UnionType(UnionType),
+    /// A host-provided sequence whose items are fetched in chunks while it is iterated.
+    LazySequence(LazySequence),
+    /// An in-memory text stream from `io.StringIO`.
+    StringIO(StringIO),
+    /// A row writer from `csv.writer` or `csv.DictWriter`, holding a reference to its stream.
+    CsvWriter(CsvWriter),
+    /// An exact rational number from `fractions.Fraction`.
+    Fraction(Fraction),
+    /// A typed numeric array from `array.array`, storing bare numbers rather than `Value`s.
+    Array(Array),
 }
 
 impl HeapData {
+    /// Name of the variant reported in [`MemoryProfile`], e.g. `"list"` or `"long_int"`.
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            Self::Str(_) => "str",
+            Self::Bytes(_) => "bytes",
+            Self::List(_) => "list",
+            Self::Tuple(_) => "tuple",
+            Self::NamedTuple(_) => "named_tuple",
+            Self::Dict(_) => "dict",
+            Self::Set(_) => "set",
+            Self::FrozenSet(_) => "frozenset",
+            Self::Closure(_, _, _) => "closure",
+            Self::FunctionDefaults(_, _) => "function_defaults",
+            Self::Cell(_) => "cell",
+            Self::Range(_) => "range",
+            Self::Slice(_) => "slice",
+            Self::Exception(_) => "exception",
+            Self::Dataclass(_) => "dataclass",
+            Self::Iter(_) => "iterator",
+            Self::LongInt(_) => "long_int",
+            Self::Module(_) => "module",
+            Self::Coroutine(_) => "coroutine",
+            Self::GatherFuture(_) => "gather_future",
+            Self::Path(_) => "path",
+            Self::UnionType(_) => "union_type",
+            Self::LazySequence(_) => "lazy_sequence",
+            Self::StringIO(_) => "string_io",
+            Self::CsvWriter(_) => "csv_writer",
+            Self::Fraction(_) => "fraction",
+            Self::Array(_) => "array",
+        }
+    }
+
     /// Returns whether this heap data type can participate in reference cycles.
     ///
     /// Only container types that can hold references to other heap objects need to be
@@ -160,6 +208,8 @@ impl HeapData {
                 | Self::Module(_)
                 | Self::Coroutine(_)
                 | Self::GatherFuture(_)
+                | Self::LazySequence(_)
+                | Self::CsvWriter(_)
         )
     }
 
@@ -188,6 +238,8 @@ impl HeapData {
             Self::Dataclass(dc) => dc.has_refs(),
             Self::Iter(iter) => iter.has_refs(),
             Self::Module(m) => m.has_refs(),
+            Self::LazySequence(seq) => seq.has_refs(),
+            Self::CsvWriter(writer) => writer.has_refs(),
             // Coroutines always have refs (namespace values, frame_cells)
             Self::Coroutine(coro) => {
                 !coro.frame_cells.is_empty() || coro.namespace.iter().any(|v| matches!(v, Value::Ref(_)))
@@ -210,7 +262,11 @@ impl HeapData {
             | Self::Slice(_)
             | Self::Exception(_)
             | Self::LongInt(_)
-            | Self::Path(_) => false,
+            | Self::Path(_)
+            | Self::UnionType(_)
+            | Self::StringIO(_)
+            | Self::Fraction(_)
+            | Self::Array(_) => false,
         }
     }
 
@@ -231,60 +287,43 @@ impl HeapData {
         match self {
             // Hash just the actual string or bytes content for consistency with Value::InternString/InternBytes
             // hence we don't include the discriminant
-            Self::Str(s) => {
-                let mut hasher = DefaultHasher::new();
-                s.as_str().hash(&mut hasher);
-                Some(hasher.finish())
-            }
-            Self::Bytes(b) => {
-                let mut hasher = DefaultHasher::new();
-                b.as_slice().hash(&mut hasher);
-                Some(hasher.finish())
-            }
+            Self::Str(s) => Some(apply_seed(hash_str(s.as_str()), heap.hash_seed)),
+            Self::Bytes(b) => Some(apply_seed(stable_hash(b.as_slice()), heap.hash_seed)),
             Self::FrozenSet(fs) => {
                 // FrozenSet hash is XOR of element hashes (order-independent)
                 fs.compute_hash(heap, interns)
             }
-            Self::Tuple(t) => {
-                let mut hasher = DefaultHasher::new();
-                discriminant(self).hash(&mut hasher);
-                // Tuple is hashable only if all elements are hashable
-                for obj in t.as_slice() {
-                    let h = obj.py_hash(heap, interns)?;
-                    h.hash(&mut hasher);
-                }
-                Some(hasher.finish())
-            }
-            Self::NamedTuple(nt) => {
-                let mut hasher = DefaultHasher::new();
-                discriminant(self).hash(&mut hasher);
-                // Hash only by elements (not type_name) to match equality semantics
-                for obj in nt.as_vec() {
-                    let h = obj.py_hash(heap, interns)?;
-                    h.hash(&mut hasher);
-                }
-                Some(hasher.finish())
-            }
+            // Tuple is hashable only if all elements are hashable
+            Self::Tuple(t) => hash_tuple_items(t.as_slice(), heap, interns),
+            // Hash only by elements (not type_name) to match equality semantics
+            Self::NamedTuple(nt) => hash_tuple_items(nt.as_vec(), heap, interns),
             Self::Closure(f, _, _) | Self::FunctionDefaults(f, _) => {
-                let mut hasher = DefaultHasher::new();
+                let mut hasher = StableHasher::default();
                 discriminant(self).hash(&mut hasher);
                 // TODO, this is NOT proper hashing, we should somehow hash the function properly
                 f.hash(&mut hasher);
                 Some(hasher.finish())
             }
             Self::Range(range) => {
-                let mut hasher = DefaultHasher::new();
+                // Equal ranges must hash equally, so like CPython hash the length, then the
+                // start if there is one, then the step if there's more than one element
+                let mut hasher = StableHasher::default();
                 discriminant(self).hash(&mut hasher);
-                range.start.hash(&mut hasher);
-                range.stop.hash(&mut hasher);
-                range.step.hash(&mut hasher);
+                let len = range.len();
+                len.hash(&mut hasher);
+                if len > 0 {
+                    range.start.hash(&mut hasher);
+                }
+                if len > 1 {
+                    range.step.hash(&mut hasher);
+                }
                 Some(hasher.finish())
             }
             // Dataclass hashability depends on the mutable flag
             Self::Dataclass(dc) => dc.compute_hash(heap, interns),
             // Slices are immutable and hashable (like in CPython)
             Self::Slice(slice) => {
-                let mut hasher = DefaultHasher::new();
+                let mut hasher = StableHasher::default();
                 discriminant(self).hash(&mut hasher);
                 slice.start.hash(&mut hasher);
                 slice.stop.hash(&mut hasher);
@@ -293,11 +332,18 @@ impl HeapData {
             }
             // Path is immutable and hashable
             Self::Path(path) => {
-                let mut hasher = DefaultHasher::new();
+                let mut hasher = StableHasher::default();
                 discriminant(self).hash(&mut hasher);
                 path.as_str().hash(&mut hasher);
                 Some(hasher.finish())
             }
+            // Unions are immutable, and equal regardless of member order
+            Self::UnionType(union) => {
+                let mut hasher = StableHasher::default();
+                discriminant(self).hash(&mut hasher);
+                union.compute_hash().hash(&mut hasher);
+                Some(hasher.finish())
+            }
             // Mutable types, exceptions, iterators, modules, and async types cannot be hashed
             // (Cell is handled specially in get_or_compute_hash)
             Self::List(_)
@@ -308,9 +354,15 @@ impl HeapData {
             | Self::Iter(_)
             | Self::Module(_)
             | Self::Coroutine(_)
-            | Self::GatherFuture(_) => None,
+            | Self::GatherFuture(_)
+            | Self::LazySequence(_)
+            | Self::StringIO(_)
+            | Self::CsvWriter(_)
+            | Self::Array(_) => None,
             // LongInt is immutable and hashable
             Self::LongInt(li) => Some(li.hash()),
+            // Fractions hash like equal ints and floats
+            Self::Fraction(f) => Some(f.hash()),
         }
     }
 }
@@ -342,6 +394,12 @@ impl PyTrait for HeapData {
             Self::Module(_) => Type::Module,
             Self::Coroutine(_) | Self::GatherFuture(_) => Type::Coroutine,
             Self::Path(p) => p.py_type(heap),
+            Self::UnionType(u) => u.py_type(heap),
+            Self::LazySequence(seq) => seq.py_type(heap),
+            Self::StringIO(s) => s.py_type(heap),
+            Self::CsvWriter(w) => w.py_type(heap),
+            Self::Fraction(f) => f.py_type(heap),
+            Self::Array(a) => a.py_type(heap),
         }
     }
 
@@ -360,7 +418,12 @@ impl PyTrait for HeapData {
             Self::Cell(v) => std::mem::size_of::<Value>() + v.py_estimate_size(),
             Self::Range(_) => std::mem::size_of::<Range>(),
             Self::Slice(s) => s.py_estimate_size(),
-            Self::Exception(e) => std::mem::size_of::<SimpleException>() + e.arg().map_or(0, String::len),
+            Self::Exception(e) => {
+                std::mem::size_of::<SimpleException>()
+                    + e.arg().map_or(0, String::len)
+                    + std::mem::size_of_val(e.args())
+                    + e.notes().iter().map(String::len).sum::<usize>()
+            }
             Self::Dataclass(dc) => dc.py_estimate_size(),
             Self::Iter(_) => std::mem::size_of::<MontyIter>(),
             Self::LongInt(li) => li.estimate_size(),
@@ -377,6 +440,12 @@ impl PyTrait for HeapData {
                     + gather.pending_calls.len() * std::mem::size_of::<crate::asyncio::CallId>()
             }
             Self::Path(p) => p.py_estimate_size(),
+            Self::UnionType(u) => u.py_estimate_size(),
+            Self::LazySequence(seq) => seq.py_estimate_size(),
+            Self::StringIO(s) => s.py_estimate_size(),
+            Self::CsvWriter(w) => w.py_estimate_size(),
+            Self::Fraction(f) => f.py_estimate_size(),
+            Self::Array(a) => a.py_estimate_size(),
         }
     }
 
@@ -391,7 +460,10 @@ impl PyTrait for HeapData {
             Self::Set(s) => PyTrait::py_len(s, heap, interns),
             Self::FrozenSet(fs) => PyTrait::py_len(fs, heap, interns),
             Self::Range(r) => Some(r.len()),
-            // Cells, Slices, Exceptions, Dataclasses, Iterators, LongInts, Modules, Paths, and async types don't have length
+            Self::LazySequence(seq) => PyTrait::py_len(seq, heap, interns),
+            Self::Array(a) => PyTrait::py_len(a, heap, interns),
+            // Cells, Slices, Exceptions, Dataclasses, Iterators, numbers, Modules, Paths, streams, and async types
+            // don't have length
             Self::Cell(_)
             | Self::Closure(_, _, _)
             | Self::FunctionDefaults(_, _)
@@ -403,7 +475,11 @@ impl PyTrait for HeapData {
             | Self::Module(_)
             | Self::Coroutine(_)
             | Self::GatherFuture(_)
-            | Self::Path(_) => None,
+            | Self::Path(_)
+            | Self::UnionType(_)
+            | Self::StringIO(_)
+            | Self::CsvWriter(_)
+            | Self::Fraction(_) => None,
         }
     }
 
@@ -440,6 +516,8 @@ impl PyTrait for HeapData {
             (Self::Dict(a), Self::Dict(b)) => a.py_eq(b, heap, guard, interns),
             (Self::Set(a), Self::Set(b)) => a.py_eq(b, heap, guard, interns),
             (Self::FrozenSet(a), Self::FrozenSet(b)) => a.py_eq(b, heap, guard, interns),
+            (Self::Set(a), Self::FrozenSet(b)) => a.storage().eq(b.storage(), heap, guard, interns),
+            (Self::FrozenSet(a), Self::Set(b)) => a.storage().eq(b.storage(), heap, guard, interns),
             (Self::Closure(a_id, a_cells, _), Self::Closure(b_id, b_cells, _)) => {
                 Ok(*a_id == *b_id && a_cells == b_cells)
             }
@@ -448,10 +526,16 @@ impl PyTrait for HeapData {
             (Self::Dataclass(a), Self::Dataclass(b)) => a.py_eq(b, heap, guard, interns),
             // LongInt equality
             (Self::LongInt(a), Self::LongInt(b)) => Ok(a == b),
+            (Self::Fraction(a), Self::Fraction(b)) => a.py_eq(b, heap, guard, interns),
+            (Self::Fraction(f), Self::LongInt(li)) | (Self::LongInt(li), Self::Fraction(f)) => Ok(f.eq_int(li.inner())),
+            (Self::Array(a), Self::Array(b)) => a.py_eq(b, heap, guard, interns),
             // Slice equality
             (Self::Slice(a), Self::Slice(b)) => a.py_eq(b, heap, guard, interns),
             // Path equality
             (Self::Path(a), Self::Path(b)) => a.py_eq(b, heap, guard, interns),
+            // Union equality ignores member order
+            (Self::UnionType(a), Self::UnionType(b)) => a.py_eq(b, heap, guard, interns),
+            (Self::LazySequence(a), Self::LazySequence(b)) => a.py_eq(b, heap, guard, interns),
             // Cells, Exceptions, Iterators, Modules, and async types compare by identity only (handled at Value level via HeapId comparison)
             (Self::Cell(_), Self::Cell(_))
             | (Self::Exception(_), Self::Exception(_))
@@ -491,6 +575,8 @@ impl PyTrait for HeapData {
             Self::Dataclass(dc) => dc.py_dec_ref_ids(stack),
             Self::Iter(iter) => iter.py_dec_ref_ids(stack),
             Self::Module(m) => m.py_dec_ref_ids(stack),
+            Self::LazySequence(seq) => seq.py_dec_ref_ids(stack),
+            Self::CsvWriter(w) => w.py_dec_ref_ids(stack),
             Self::Coroutine(coro) => {
                 // Decrement ref count for frame cells
                 stack.extend(coro.frame_cells.iter().copied());
@@ -511,8 +597,17 @@ impl PyTrait for HeapData {
                     result.py_dec_ref_ids(stack);
                 }
             }
-            // Range, Slice, Exception, LongInt, and Path have no nested heap references
-            Self::Range(_) | Self::Slice(_) | Self::Exception(_) | Self::LongInt(_) | Self::Path(_) => {}
+            // Range, Slice, Exception, LongInt, Fraction, Array, Path, UnionType, and StringIO have no nested
+            // heap references
+            Self::Range(_)
+            | Self::Slice(_)
+            | Self::Exception(_)
+            | Self::LongInt(_)
+            | Self::Fraction(_)
+            | Self::Array(_)
+            | Self::Path(_)
+            | Self::UnionType(_)
+            | Self::StringIO(_) => {}
         }
     }
 
@@ -538,6 +633,12 @@ impl PyTrait for HeapData {
             Self::Coroutine(_) => true,    // Coroutines are always truthy
             Self::GatherFuture(_) => true, // GatherFutures are always truthy
             Self::Path(p) => p.py_bool(heap, interns),
+            Self::UnionType(u) => u.py_bool(heap, interns),
+            Self::LazySequence(seq) => seq.py_bool(heap, interns),
+            Self::StringIO(s) => s.py_bool(heap, interns),
+            Self::CsvWriter(w) => w.py_bool(heap, interns),
+            Self::Fraction(f) => f.py_bool(heap, interns),
+            Self::Array(a) => a.py_bool(heap, interns),
         }
     }
 
@@ -577,6 +678,12 @@ impl PyTrait for HeapData {
             }
             Self::GatherFuture(gather) => write!(f, "<gather({})>", gather.item_count()),
             Self::Path(p) => p.py_repr_fmt(f, heap, heap_ids, guard, interns),
+            Self::UnionType(u) => u.py_repr_fmt(f, heap, heap_ids, guard, interns),
+            Self::LazySequence(seq) => seq.py_repr_fmt(f, heap, heap_ids, guard, interns),
+            Self::StringIO(s) => s.py_repr_fmt(f, heap, heap_ids, guard, interns),
+            Self::CsvWriter(w) => w.py_repr_fmt(f, heap, heap_ids, guard, interns),
+            Self::Fraction(fraction) => fraction.py_repr_fmt(f, heap, heap_ids, guard, interns),
+            Self::Array(a) => a.py_repr_fmt(f, heap, heap_ids, guard, interns),
         }
     }
 
@@ -591,6 +698,8 @@ impl PyTrait for HeapData {
             Self::Str(s) => s.py_str(heap, guard, interns),
             // LongInt returns its string representation
             Self::LongInt(li) => Cow::Owned(li.to_string()),
+            // Fractions display as `3/4` rather than `Fraction(3, 4)`
+            Self::Fraction(f) => Cow::Owned(f.to_string()),
             // Exceptions return just the message (or empty string if no message)
             Self::Exception(e) => Cow::Owned(e.py_str()),
             // Paths return the path string without the PosixPath() wrapper
@@ -612,6 +721,7 @@ impl PyTrait for HeapData {
             (Self::List(a), Self::List(b)) => a.py_add(b, heap, interns),
             (Self::Tuple(a), Self::Tuple(b)) => a.py_add(b, heap, interns),
             (Self::Dict(a), Self::Dict(b)) => a.py_add(b, heap, interns),
+            (Self::Array(a), Self::Array(b)) => a.py_add(b, heap, interns),
             // Cells and Dataclasses don't support arithmetic operations
             _ => Ok(None),
         }
@@ -709,6 +819,11 @@ impl PyTrait for HeapData {
             Self::FrozenSet(fs) => fs.py_call_attr(heap, attr, args, interns),
             Self::Dataclass(dc) => dc.py_call_attr(heap, attr, args, interns),
             Self::Path(p) => p.py_call_attr(heap, attr, args, interns),
+            Self::Exception(e) => e.py_call_attr(heap, attr, args, interns),
+            Self::StringIO(s) => s.py_call_attr(heap, attr, args, interns),
+            Self::Fraction(f) => f.py_call_attr(heap, attr, args, interns),
+            Self::Array(a) => a.py_call_attr(heap, attr, args, interns),
+            Self::Range(r) => r.py_call_attr(heap, attr, args, interns),
             _ => Err(ExcType::attribute_error(self.py_type(heap), attr.as_str(interns))),
         }
     }
@@ -731,6 +846,8 @@ impl PyTrait for HeapData {
             Self::Path(p) => p.py_call_attr_raw(self_id, heap, attr, args, interns, print_writer),
             // Module has special handling for OS calls (os.getenv, etc.)
             Self::Module(m) => m.py_call_attr_raw(self_id, heap, attr, args, interns, print_writer),
+            // Array intercepts extend so `a.extend(a)` doesn't read its own borrowed entry
+            Self::Array(a) => a.py_call_attr_raw(self_id, heap, attr, args, interns, print_writer),
             // All other types use the default implementation (wrap py_call_attr)
             _ => self.py_call_attr(heap, attr, args, interns).map(AttrCallResult::Value),
         }
@@ -745,6 +862,7 @@ impl PyTrait for HeapData {
             Self::NamedTuple(nt) => nt.py_getitem(key, heap, interns),
             Self::Dict(d) => d.py_getitem(key, heap, interns),
             Self::Range(r) => r.py_getitem(key, heap, interns),
+            Self::Array(a) => a.py_getitem(key, heap, interns),
             _ => Err(ExcType::type_error_not_sub(self.py_type(heap))),
         }
     }
@@ -762,6 +880,7 @@ impl PyTrait for HeapData {
             Self::List(l) => l.py_setitem(key, value, heap, interns),
             Self::Tuple(t) => t.py_setitem(key, value, heap, interns),
             Self::Dict(d) => d.py_setitem(key, value, heap, interns),
+            Self::Array(a) => a.py_setitem(key, value, heap, interns),
             _ => Err(ExcType::type_error_not_sub_assignment(self.py_type(heap))),
         }
     }
@@ -779,6 +898,16 @@ impl PyTrait for HeapData {
             Self::Slice(s) => s.py_getattr(attr_id, heap, interns),
             Self::Exception(exc) => exc.py_getattr(attr_id, heap, interns),
             Self::Path(p) => p.py_getattr(attr_id, heap, interns),
+            Self::UnionType(u) => u.py_getattr(attr_id, heap, interns),
+            Self::StringIO(s) => s.py_getattr(attr_id, heap, interns),
+            Self::CsvWriter(w) => w.py_getattr(attr_id, heap, interns),
+            Self::Fraction(f) => f.py_getattr(attr_id, heap, interns),
+            Self::Array(a) => a.py_getattr(attr_id, heap, interns),
+            Self::Range(r) => r.py_getattr(attr_id, heap, interns),
+            Self::Closure(f_id, _, _) | Self::FunctionDefaults(f_id, _) if attr_id == StaticStrings::DunderName => {
+                let name_id = interns.get_function(*f_id).name.name_id;
+                Ok(Some(AttrCallResult::Value(Value::InternString(name_id))))
+            }
             // All other types don't support attribute access via py_getattr
             _ => Ok(None),
         }
@@ -803,7 +932,7 @@ impl HashState {
             // FrozenSet is immutable and hashable
             // Range is immutable and hashable
             // Slice is immutable and hashable (like in CPython)
-            // LongInt is immutable and hashable
+            // LongInt and Fraction are immutable and hashable
             // NamedTuple is immutable and hashable (like Tuple)
             HeapData::Str(_)
             | HeapData::Bytes(_)
@@ -815,7 +944,8 @@ impl HashState {
             | HeapData::FunctionDefaults(_, _)
             | HeapData::Range(_)
             | HeapData::Slice(_)
-            | HeapData::LongInt(_) => Self::Unknown,
+            | HeapData::LongInt(_)
+            | HeapData::Fraction(_) => Self::Unknown,
             // Dataclass hashability depends on the mutable flag
             HeapData::Dataclass(dc) => {
                 if dc.is_frozen() {
@@ -824,8 +954,8 @@ impl HashState {
                     Self::Unhashable
                 }
             }
-            // Path is immutable and hashable
-            HeapData::Path(_) => Self::Unknown,
+            // Path and UnionType are immutable and hashable
+            HeapData::Path(_) | HeapData::UnionType(_) => Self::Unknown,
             // Mutable containers, exceptions, iterators, modules, and async types are unhashable
             HeapData::List(_)
             | HeapData::Dict(_)
@@ -834,7 +964,11 @@ impl HashState {
             | HeapData::Iter(_)
             | HeapData::Module(_)
             | HeapData::Coroutine(_)
-            | HeapData::GatherFuture(_) => Self::Unhashable,
+            | HeapData::GatherFuture(_)
+            | HeapData::LazySequence(_)
+            | HeapData::StringIO(_)
+            | HeapData::CsvWriter(_)
+            | HeapData::Array(_) => Self::Unhashable,
         }
     }
 }
@@ -883,21 +1017,48 @@ pub(crate) struct Heap<T: ResourceTracker> {
     may_have_cycles: bool,
     /// Number of GC applicable allocations since the last GC.
     allocations_since_gc: u32,
+    /// Seed mixed into str and bytes hashes, taken from the tracker when the heap is created.
+    ///
+    /// Serialized so cached hashes stay valid and `hash()` results don't change on resume.
+    hash_seed: u64,
+    /// Source of random bytes, created from the tracker's entropy policy with the heap.
+    ///
+    /// Serialized so a seeded stream doesn't repeat values on resume.
+    entropy: Entropy,
+    /// Allocation counters for `MemoryProfile::allocation_sites`, `Some` when the tracker
+    /// enables `trace_allocations`. Not serialized.
+    allocation_trace: Option<Box<AllocationTrace>>,
 }
 
 impl<T: ResourceTracker + serde::Serialize> serde::Serialize for Heap<T> {
     fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
         use serde::ser::SerializeStruct;
-        let mut state = serializer.serialize_struct("Heap", 6)?;
+        let mut state = serializer.serialize_struct("Heap", 7)?;
         state.serialize_field("entries", &self.entries)?;
         state.serialize_field("free_list", &self.free_list)?;
         state.serialize_field("tracker", &self.tracker)?;
         state.serialize_field("may_have_cycles", &self.may_have_cycles)?;
         state.serialize_field("allocations_since_gc", &self.allocations_since_gc)?;
+        state.serialize_field("hash_seed", &self.hash_seed)?;
+        state.serialize_field("entropy", &self.entropy)?;
         state.end()
     }
 }
 
+/// Serializable view of a heap's objects, see `Heap::state`.
+///
+/// Serialized like `Heap` but without the tracker, whose runtime state such as a start time
+/// isn't part of the program state.
+#[derive(serde::Serialize)]
+pub(crate) struct HeapState<'a> {
+    entries: &'a [Option<HeapValue>],
+    free_list: &'a [HeapId],
+    may_have_cycles: bool,
+    allocations_since_gc: u32,
+    hash_seed: u64,
+    entropy: &'a Entropy,
+}
+
 impl<'de, T: ResourceTracker + serde::Deserialize<'de>> serde::Deserialize<'de> for Heap<T> {
     fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
         #[derive(serde::Deserialize)]
@@ -907,14 +1068,20 @@ impl<'de, T: ResourceTracker + serde::Deserialize<'de>> serde::Deserialize<'de>
             tracker: T,
             may_have_cycles: bool,
             allocations_since_gc: u32,
+            hash_seed: u64,
+            entropy: Entropy,
         }
         let fields = HeapFields::<T>::deserialize(deserializer)?;
+        let allocation_trace = fields.tracker.trace_allocations().then(Box::default);
         Ok(Self {
             entries: fields.entries,
             free_list: fields.free_list,
             tracker: fields.tracker,
             may_have_cycles: fields.may_have_cycles,
             allocations_since_gc: fields.allocations_since_gc,
+            hash_seed: fields.hash_seed,
+            entropy: fields.entropy,
+            allocation_trace,
         })
     }
 }
@@ -956,20 +1123,65 @@ impl<T: ResourceTracker> Heap<T> {
     ///
     /// Use this to create heaps with custom resource limits or GC scheduling.
     pub fn new(capacity: usize, tracker: T) -> Self {
+        let allocation_trace = tracker.trace_allocations().then(Box::default);
+        let hash_seed = tracker.hash_seed();
+        let entropy = Entropy::new(tracker.entropy());
         let mut this = Self {
             entries: Vec::with_capacity(capacity),
             free_list: Vec::new(),
             tracker,
             may_have_cycles: false,
             allocations_since_gc: 0,
+            hash_seed,
+            entropy,
+            allocation_trace,
         };
+        this.allocate_empty_tuple();
+        this
+    }
+
+    /// Prepares the heap for a new run with `tracker`, like `Heap::new` but keeping the
+    /// capacity the heap has grown to, see `SandboxPool`.
+    pub fn reset(&mut self, tracker: T) {
+        self.clear();
+        self.allocation_trace = tracker.trace_allocations().then(Box::default);
+        self.hash_seed = tracker.hash_seed();
+        self.entropy = Entropy::new(tracker.entropy());
+        self.tracker = tracker;
+        self.may_have_cycles = false;
+        self.allocations_since_gc = 0;
+        self.allocate_empty_tuple();
+    }
+
+    /// Drops every object, keeping the allocated capacity for reuse.
+    ///
+    /// This also drops the empty tuple singleton, so the heap must be `reset` before it's used again.
+    pub fn clear(&mut self) {
+        self.mark_dereferenced();
+        self.entries.clear();
+        self.free_list.clear();
+    }
+
+    /// Allocates the empty tuple singleton at `EMPTY_TUPLE_ID`.
+    fn allocate_empty_tuple(&mut self) {
         // TBC: should the empty tuple contribute to the resource limits?
         // If not, can just place it in `entries` directly without going through `allocate()`.
-        let empty_tuple = this
+        let empty_tuple = self
             .allocate(HeapData::Tuple(Tuple::default()))
             .expect("Failed to allocate empty tuple singleton");
         debug_assert_eq!(empty_tuple, EMPTY_TUPLE_ID);
-        this
+    }
+
+    /// Marks all contained objects as dereferenced, so the entries can be dropped.
+    fn mark_dereferenced(&mut self) {
+        // We use py_dec_ref_ids for this since it handles the marking
+        // (we ignore the collected IDs since we're dropping everything anyway).
+        let mut dummy_stack = Vec::new();
+        for value in self.entries.iter_mut().flatten() {
+            if let Some(data) = &mut value.data {
+                data.py_dec_ref_ids(&mut dummy_stack);
+            }
+        }
     }
 
     /// Returns a reference to the resource tracker.
@@ -982,6 +1194,18 @@ impl<T: ResourceTracker> Heap<T> {
         &mut self.tracker
     }
 
+    /// Returns the seed mixed into str and bytes hashes, see `hashing::apply_seed`.
+    #[inline]
+    pub fn hash_seed(&self) -> u64 {
+        self.hash_seed
+    }
+
+    /// Returns the heap's source of random bytes.
+    #[inline]
+    pub fn entropy_mut(&mut self) -> &mut Entropy {
+        &mut self.entropy
+    }
+
     /// Checks whether the configured time limit has been exceeded.
     ///
     /// Delegates to the resource tracker's `check_time()`. For `NoLimitTracker`,
@@ -1001,6 +1225,55 @@ impl<T: ResourceTracker> Heap<T> {
         self.entries.len()
     }
 
+    /// Counts live objects and their estimated size per heap type, see [`MemoryProfile`].
+    ///
+    /// `interns` resolves the filenames of allocation sites.
+    pub fn memory_profile(&self, interns: &Interns) -> MemoryProfile {
+        let mut by_type: AHashMap<&'static str, TypeUsage> = AHashMap::new();
+        for data in self.entries.iter().flatten().filter_map(|entry| entry.data.as_ref()) {
+            let type_name = data.variant_name();
+            let usage = by_type.entry(type_name).or_insert(TypeUsage {
+                type_name,
+                count: 0,
+                bytes: 0,
+            });
+            usage.count += 1;
+            usage.bytes += data.py_estimate_size();
+        }
+        let mut types: Vec<TypeUsage> = by_type.into_values().collect();
+        types.sort_by(|a, b| b.bytes.cmp(&a.bytes).then(a.type_name.cmp(b.type_name)));
+        let allocation_sites = self
+            .allocation_trace
+            .as_ref()
+            .map(|trace| trace.allocation_sites(interns))
+            .unwrap_or_default();
+        MemoryProfile {
+            types,
+            allocation_sites,
+        }
+    }
+
+    /// The heap's objects without the resource tracker, serialized by `state_eq` on the
+    /// snapshot types to compare execution state.
+    pub fn state(&self) -> HeapState<'_> {
+        HeapState {
+            entries: &self.entries,
+            free_list: &self.free_list,
+            may_have_cycles: self.may_have_cycles,
+            allocations_since_gc: self.allocations_since_gc,
+            hash_seed: self.hash_seed,
+            entropy: &self.entropy,
+        }
+    }
+
+    /// Allocation counters if the tracker enables `trace_allocations`.
+    ///
+    /// The VM uses this to attribute allocations to the source line of each instruction.
+    #[inline]
+    pub fn allocation_trace_mut(&mut self) -> Option<&mut AllocationTrace> {
+        self.allocation_trace.as_deref_mut()
+    }
+
     /// Marks that a reference cycle may exist in the heap.
     ///
     /// Call this when a container (list, dict, tuple, etc.) stores a reference
@@ -1032,6 +1305,9 @@ impl<T: ResourceTracker> Heap<T> {
     /// cycles to enable garbage collection.
     pub fn allocate(&mut self, data: HeapData) -> Result<HeapId, ResourceError> {
         self.tracker.on_allocate(|| data.py_estimate_size())?;
+        if let Some(trace) = &mut self.allocation_trace {
+            trace.on_allocate(data.py_estimate_size());
+        }
         if data.is_gc_tracked() {
             self.allocations_since_gc = self.allocations_since_gc.wrapping_add(1);
             // Mark potential cycles if this container has heap references.
@@ -1183,9 +1459,7 @@ impl<T: ResourceTracker> Heap<T> {
 
         // Handle Cell specially - uses identity-based hashing (like Python cell objects)
         if let Some(HeapData::Cell(_)) = &entry.data {
-            let mut hasher = DefaultHasher::new();
-            id.hash(&mut hasher);
-            let hash = hasher.finish();
+            let hash = cell_hash(id);
             entry.hash_state = HashState::Cached(hash);
             return Some(hash);
         }
@@ -1670,6 +1944,120 @@ impl<T: ResourceTracker> Heap<T> {
     }
 }
 
+#[cfg(feature = "heap-validate")]
+impl<T: ResourceTracker> Heap<T> {
+    /// Checks the heap's invariants, returning a description of the first violation found.
+    ///
+    /// `roots` holds one entry per reference owned outside the heap, e.g. by the VM's stack and
+    /// namespaces. Checks that:
+    /// - the free list holds exactly the freed slots, and every reference points at a live object
+    /// - no object's data is still borrowed by `with_entry_mut` or `call_attr`
+    /// - refcounts are at least the number of references found, and equal to it when
+    ///   `exact_refcounts` is set, so leaked references are reported too
+    /// - cached hashes match a fresh computation
+    pub fn validate(&mut self, roots: &[HeapId], exact_refcounts: bool, interns: &Interns) -> Result<(), String> {
+        let mut is_free = vec![false; self.entries.len()];
+        for &id in &self.free_list {
+            match self.entries.get(id.index()) {
+                None => return Err(format!("free list holds {id:?} past the end of the heap")),
+                Some(Some(entry)) => return Err(format!("free list holds live {}", describe(id, entry))),
+                Some(None) if is_free[id.index()] => return Err(format!("free list holds {id:?} twice")),
+                Some(None) => is_free[id.index()] = true,
+            }
+        }
+
+        let mut references = vec![0usize; self.entries.len()];
+        // the heap itself keeps the empty tuple singleton alive
+        references[EMPTY_TUPLE_ID.index()] += 1;
+        let mut children = Vec::new();
+        for (index, slot) in self.entries.iter().enumerate() {
+            let id = HeapId(index);
+            let Some(entry) = slot else {
+                if !is_free[index] {
+                    return Err(format!("freed {id:?} is missing from the free list"));
+                }
+                continue;
+            };
+            let Some(data) = &entry.data else {
+                return Err(format!("{id:?} is still borrowed"));
+            };
+            collect_child_ids(data, &mut children);
+            for child in children.drain(..) {
+                count_reference(&self.entries, &mut references, child, || describe(id, entry))?;
+            }
+        }
+        for &id in roots {
+            count_reference(&self.entries, &mut references, id, || "the VM".to_owned())?;
+        }
+
+        for (index, entry) in self.entries.iter().enumerate() {
+            let Some(entry) = entry else { continue };
+            let found = references[index];
+            if entry.refcount == 0 || entry.refcount < found || (exact_refcounts && entry.refcount != found) {
+                return Err(format!(
+                    "{} has refcount {} but {found} references",
+                    describe(HeapId(index), entry),
+                    entry.refcount
+                ));
+            }
+        }
+
+        for index in 0..self.entries.len() {
+            let id = HeapId(index);
+            let Some(Some(entry)) = self.entries.get_mut(index) else {
+                continue;
+            };
+            let HashState::Cached(cached) = entry.hash_state else {
+                continue;
+            };
+            let data = entry.data.take().expect("borrowed entries were rejected above");
+            let hash = match data {
+                HeapData::Cell(_) => Some(cell_hash(id)),
+                _ => data.compute_hash_if_immutable(self, interns),
+            };
+            let entry = self.entries[index].as_mut().expect("entry freed while hashing");
+            entry.data = Some(data);
+            if hash != Some(cached) {
+                return Err(format!(
+                    "{} has cached hash {cached} but hashes to {hash:?}",
+                    describe(id, entry)
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Counts a reference to `id` made by `owner`, failing if `id` isn't a live object.
+#[cfg(feature = "heap-validate")]
+fn count_reference(
+    entries: &[Option<HeapValue>],
+    references: &mut [usize],
+    id: HeapId,
+    owner: impl FnOnce() -> String,
+) -> Result<(), String> {
+    match entries.get(id.index()) {
+        Some(Some(_)) => {
+            references[id.index()] += 1;
+            Ok(())
+        }
+        Some(None) => Err(format!("{} references freed {id:?}", owner())),
+        None => Err(format!("{} references {id:?} past the end of the heap", owner())),
+    }
+}
+
+/// Names a heap object in validation errors, e.g. `list HeapId(3)`.
+#[cfg(feature = "heap-validate")]
+fn describe(id: HeapId, entry: &HeapValue) -> String {
+    let type_name = entry.data.as_ref().map_or("borrowed object", HeapData::variant_name);
+    format!("{type_name} {id:?}")
+}
+
+/// Identity-based hash of a cell, like Python objects without `__hash__`.
+fn cell_hash(id: HeapId) -> u64 {
+    stable_hash(&id)
+}
+
 /// Computes the number of significant bits in an `i64`.
 ///
 /// Returns 0 for zero, otherwise returns the position of the highest set bit
@@ -1717,8 +2105,13 @@ fn collect_child_ids(data: &HeapData, work_list: &mut Vec<HeapId>) {
         | HeapData::Range(_)
         | HeapData::Exception(_)
         | HeapData::LongInt(_)
+        | HeapData::Fraction(_)
+        | HeapData::Array(_)
         | HeapData::Slice(_)
-        | HeapData::Path(_) => {}
+        | HeapData::Path(_)
+        | HeapData::UnionType(_)
+        | HeapData::StringIO(_) => {}
+        HeapData::CsvWriter(writer) => writer.collect_child_ids(work_list),
         HeapData::List(list) => {
             // Skip iteration if no refs - major GC optimization for lists of primitives
             if !list.contains_refs() {
@@ -1823,6 +2216,14 @@ fn collect_child_ids(data: &HeapData, work_list: &mut Vec<HeapId>) {
                 work_list.push(*id);
             }
         }
+        HeapData::LazySequence(seq) => {
+            // Only the fetched chunk can hold heap references
+            for value in seq.chunk() {
+                if let Value::Ref(id) = value {
+                    work_list.push(*id);
+                }
+            }
+        }
         HeapData::Module(m) => {
             // Module attrs can contain references to heap values
             if !m.has_refs() {
@@ -1872,14 +2273,7 @@ fn collect_child_ids(data: &HeapData, work_list: &mut Vec<HeapId>) {
 impl<T: ResourceTracker> Drop for Heap<T> {
     fn drop(&mut self) {
         // Mark all contained Objects as Dereferenced before dropping.
-        // We use py_dec_ref_ids for this since it handles the marking
-        // (we ignore the collected IDs since we're dropping everything anyway).
-        let mut dummy_stack = Vec::new();
-        for value in self.entries.iter_mut().flatten() {
-            if let Some(data) = &mut value.data {
-                data.py_dec_ref_ids(&mut dummy_stack);
-            }
-        }
+        self.mark_dereferenced();
     }
 }
 
@@ -2094,3 +2488,71 @@ macro_rules! defer_drop_mut {
         let ($value, $heap) = _guard.as_parts_mut();
     };
 }
+
+#[cfg(all(test, feature = "heap-validate"))]
+mod validate_tests {
+    use super::*;
+    use crate::{intern::InternerBuilder, resource::NoLimitTracker};
+
+    fn create_test_interns() -> Interns {
+        Interns::new(InternerBuilder::new(""), vec![], vec![])
+    }
+
+    /// Creates a heap holding a list whose only item is a string, returning the list and string ids.
+    fn create_heap_with_list() -> (Heap<NoLimitTracker>, HeapId, HeapId) {
+        let mut heap = Heap::new(16, NoLimitTracker);
+        let str_id = heap.allocate(HeapData::Str(Str::from("hello"))).unwrap();
+        let list_id = heap
+            .allocate(HeapData::List(List::new(vec![Value::Ref(str_id)])))
+            .unwrap();
+        (heap, list_id, str_id)
+    }
+
+    #[test]
+    fn consistent_heap() {
+        let interns = create_test_interns();
+        let (mut heap, list_id, str_id) = create_heap_with_list();
+        heap.get_or_compute_hash(str_id, &interns);
+        assert_eq!(heap.validate(&[list_id], true, &interns), Ok(()));
+    }
+
+    #[test]
+    fn leaked_reference() {
+        let interns = create_test_interns();
+        let (mut heap, list_id, _) = create_heap_with_list();
+        let err = heap.validate(&[], true, &interns).unwrap_err();
+        assert_eq!(err, format!("list {list_id:?} has refcount 1 but 0 references"));
+        // leaks aren't reported without exact refcounts
+        assert_eq!(heap.validate(&[], false, &interns), Ok(()));
+    }
+
+    #[test]
+    fn missing_reference() {
+        let interns = create_test_interns();
+        let (mut heap, list_id, str_id) = create_heap_with_list();
+        let err = heap.validate(&[list_id, str_id], false, &interns).unwrap_err();
+        assert_eq!(err, format!("str {str_id:?} has refcount 1 but 2 references"));
+    }
+
+    #[test]
+    fn reference_to_freed_object() {
+        let interns = create_test_interns();
+        let (mut heap, list_id, str_id) = create_heap_with_list();
+        heap.dec_ref(str_id);
+        let err = heap.validate(&[list_id], true, &interns).unwrap_err();
+        assert_eq!(err, format!("list {list_id:?} references freed {str_id:?}"));
+    }
+
+    #[test]
+    fn stale_cached_hash() {
+        let interns = create_test_interns();
+        let (mut heap, list_id, str_id) = create_heap_with_list();
+        let hash = heap.get_or_compute_hash(str_id, &interns).unwrap();
+        heap.entries[str_id.index()].as_mut().unwrap().hash_state = HashState::Cached(hash ^ 1);
+        let err = heap.validate(&[list_id], true, &interns).unwrap_err();
+        assert_eq!(
+            err,
+            format!("str {str_id:?} has cached hash {} but hashes to Some({hash})", hash ^ 1)
+        );
+    }
+}
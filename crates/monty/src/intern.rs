@@ -12,13 +12,16 @@
 //! * 1000 to count(StaticStrings) - strings StaticStrings
 //! * 10_000+ - strings interned per executor
 
-use std::{str::FromStr, sync::LazyLock};
+use std::{
+    str::FromStr,
+    sync::{LazyLock, OnceLock},
+};
 
 use ahash::AHashMap;
 use num_bigint::BigInt;
 use strum::{EnumString, FromRepr, IntoStaticStr};
 
-use crate::{function::Function, value::Value};
+use crate::{ext_signature::ExtFunctionSignature, function::Function, types::str::hash_str, value::Value};
 
 /// Index into the string interner's storage.
 ///
@@ -68,6 +71,19 @@ static ASCII_STRS: LazyLock<[&'static str; 128]> = LazyLock::new(|| {
     })
 });
 
+/// Hashes of the 128 ASCII strings, built once on first access.
+static ASCII_STR_HASHES: LazyLock<[u64; 128]> = LazyLock::new(|| std::array::from_fn(|i| hash_str(ASCII_STRS[i])));
+
+/// Hashes of the `StaticStrings` variants indexed by discriminant, built once on first access.
+///
+/// Unused discriminants hash to 0; they are never looked up.
+static STATIC_STR_HASHES: LazyLock<[u64; 256]> = LazyLock::new(|| {
+    std::array::from_fn(|i| {
+        let repr = u8::try_from(i).expect("index out of u8 range");
+        StaticStrings::from_repr(repr).map_or(0, |ss| hash_str(ss.into()))
+    })
+});
+
 /// Static string values which are known at compile time and don't need to be interned.
 #[repr(u8)]
 #[derive(
@@ -144,6 +160,7 @@ pub enum StaticStrings {
     Isupper,
     Isascii,
     Isdecimal,
+    Isprintable,
     // Search methods (some shared with bytes, list, tuple)
     Find,
     Rfind,
@@ -291,14 +308,153 @@ pub enum StaticStrings {
     Environ,
     Default,
 
+    // ==========================
+    // tempfile module strings
+    Tempfile,
+    #[strum(serialize = "TemporaryDirectory")]
+    TemporaryDirectory,
+    #[strum(serialize = "NamedTemporaryFile")]
+    NamedTemporaryFile,
+
+    // ==========================
+    // io module strings
+    Io,
+    #[strum(serialize = "StringIO")]
+    StringIOClass,
+    Write,
+    Writelines,
+    Getvalue,
+    Read,
+    Readline,
+    Readlines,
+    Seek,
+    Tell,
+    Truncate,
+    Close,
+    Closed,
+
+    // ==========================
+    // csv module strings
+    Csv,
+    Reader,
+    Writer,
+    #[strum(serialize = "DictReader")]
+    DictReader,
+    #[strum(serialize = "DictWriter")]
+    DictWriter,
+    #[strum(serialize = "QUOTE_MINIMAL")]
+    QuoteMinimal,
+    #[strum(serialize = "QUOTE_ALL")]
+    QuoteAll,
+    #[strum(serialize = "QUOTE_NONNUMERIC")]
+    QuoteNonnumeric,
+    #[strum(serialize = "QUOTE_NONE")]
+    QuoteNone,
+    #[strum(serialize = "QUOTE_STRINGS")]
+    QuoteStrings,
+    #[strum(serialize = "QUOTE_NOTNULL")]
+    QuoteNotnull,
+    #[strum(serialize = "Error")]
+    ErrorClass,
+    Writerow,
+    Writerows,
+    Writeheader,
+    Fieldnames,
+
+    // ==========================
+    // zlib and gzip module strings
+    Zlib,
+    Gzip,
+    Compress,
+    Decompress,
+    #[strum(serialize = "crc32")]
+    Crc32,
+    #[strum(serialize = "adler32")]
+    Adler32,
+    #[strum(serialize = "MAX_WBITS")]
+    MaxWbits,
+    #[strum(serialize = "DEFLATED")]
+    Deflated,
+    #[strum(serialize = "Z_BEST_COMPRESSION")]
+    ZBestCompression,
+    #[strum(serialize = "Z_BEST_SPEED")]
+    ZBestSpeed,
+    #[strum(serialize = "Z_DEFAULT_COMPRESSION")]
+    ZDefaultCompression,
+    #[strum(serialize = "Z_NO_COMPRESSION")]
+    ZNoCompression,
+    #[strum(serialize = "error")]
+    ErrorLower,
+    #[strum(serialize = "BadGzipFile")]
+    BadGzipFile,
+
+    // ==========================
+    // secrets module strings
+    Secrets,
+    TokenBytes,
+    TokenHex,
+    TokenUrlsafe,
+    Choice,
+    Randbelow,
+
+    // ==========================
+    // statistics module strings
+    Statistics,
+    Mean,
+    Median,
+    Mode,
+    Variance,
+    Stdev,
+    Quantiles,
+    #[strum(serialize = "StatisticsError")]
+    StatisticsError,
+
+    // ==========================
+    // fractions module strings
+    Fractions,
+    #[strum(serialize = "Fraction")]
+    FractionClass,
+    Numerator,
+    Denominator,
+    LimitDenominator,
+    AsIntegerRatio,
+    IsInteger,
+
+    // ==========================
+    // array module strings
+    // Also uses shared: APPEND, EXTEND, INSERT, POP, REMOVE, INDEX, COUNT, REVERSE
+    Array,
+    Tolist,
+    Typecode,
+    Itemsize,
+
     // ==========================
     // Exception attributes
     Args,
+    #[strum(serialize = "__notes__")]
+    DunderNotes,
+    AddNote,
 
     // ==========================
     // Type attributes
     #[strum(serialize = "__name__")]
     DunderName,
+    #[strum(serialize = "__args__")]
+    DunderArgs,
+
+    // ==========================
+    // Lazy sequence fetches
+    #[strum(serialize = "__getitem__")]
+    DunderGetitem,
+
+    // ==========================
+    // Module-level dunder variables
+    #[strum(serialize = "__main__")]
+    DunderMain,
+    #[strum(serialize = "__file__")]
+    DunderFile,
+    #[strum(serialize = "__doc__")]
+    DunderDoc,
 
     // ==========================
     // pathlib module strings
@@ -331,19 +487,28 @@ pub enum StaticStrings {
     IsSymlink,
     #[strum(serialize = "stat")]
     StatMethod,
+    Lstat,
     ReadBytes,
     ReadText,
     Iterdir,
+    Glob,
+    Rglob,
+    Walk,
+    Readlink,
     Resolve,
     Absolute,
 
     // Path write methods (require OsAccess - yield external calls)
+    // Also uses shared: COPY
     WriteText,
     WriteBytes,
     Mkdir,
     Unlink,
     Rmdir,
     Rename,
+    Touch,
+    Chmod,
+    SymlinkTo,
 
     // Slice attributes
     Start,
@@ -601,6 +766,15 @@ pub(crate) struct Interns {
     long_ints: Vec<BigInt>,
     functions: Vec<Function>,
     external_functions: Vec<String>,
+    /// Optional registered signatures, indexed like `external_functions` (may be shorter).
+    external_signatures: Vec<Option<ExtFunctionSignature>>,
+    /// External functions whose results are cached by their arguments, see `MontyRun::mark_pure`.
+    #[serde(default)]
+    pure_external_functions: Vec<ExtFunctionId>,
+    /// Hashes of `strings`, computed on first use so `InternString` dict keys don't rehash
+    /// their content on every lookup. Not serialized since it's cheap to rebuild.
+    #[serde(skip)]
+    string_hashes: OnceLock<Box<[u64]>>,
 }
 
 impl Interns {
@@ -611,6 +785,9 @@ impl Interns {
             long_ints: interner.long_ints,
             functions,
             external_functions,
+            external_signatures: Vec::new(),
+            pure_external_functions: Vec::new(),
+            string_hashes: OnceLock::new(),
         }
     }
 
@@ -624,6 +801,56 @@ impl Interns {
         get_str(&self.strings, id)
     }
 
+    /// Returns the hash of an interned string, equal to `hash_str` of its content.
+    ///
+    /// Like `hash_str`, the hash is unseeded so the caches below are shared by every heap.
+    ///
+    /// Hashes are cached (per `Interns` for interned strings, globally for ASCII and static
+    /// strings), so hashing an `InternString` is a table lookup rather than a pass over
+    /// the string.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `StringId` is invalid.
+    #[inline]
+    pub fn string_hash(&self, id: StringId) -> u64 {
+        if let Ok(c) = u8::try_from(id.0) {
+            ASCII_STR_HASHES[c as usize]
+        } else if let Some(intern_index) = id.index().checked_sub(INTERN_STRING_ID_OFFSET) {
+            let hashes = self
+                .string_hashes
+                .get_or_init(|| self.strings.iter().map(|s| hash_str(s)).collect());
+            hashes[intern_index]
+        } else {
+            let static_str = StaticStrings::from_string_id(id).expect("Invalid static string ID");
+            STATIC_STR_HASHES[static_str as usize]
+        }
+    }
+
+    /// Interns a string after compilation, returning its `StringId`.
+    ///
+    /// Like [`InternerBuilder::intern`] but without the deduplication map, so it's only
+    /// suitable for occasional use, e.g. configuring the module's `__name__`.
+    pub fn intern(&mut self, s: &str) -> StringId {
+        if s.len() == 1 {
+            StringId::from_ascii(s.as_bytes()[0])
+        } else if let Ok(ss) = StaticStrings::from_str(s) {
+            ss.into()
+        } else if let Some(index) = self.strings.iter().position(|existing| existing == s) {
+            StringId((index + INTERN_STRING_ID_OFFSET).try_into().expect("StringId overflow"))
+        } else {
+            let id = StringId(
+                (self.strings.len() + INTERN_STRING_ID_OFFSET)
+                    .try_into()
+                    .expect("StringId overflow"),
+            );
+            self.strings.push(s.to_owned());
+            // Invalidate the hash cache so it's rebuilt including the new string
+            self.string_hashes.take();
+            id
+        }
+    }
+
     /// Looks up bytes by their `BytesId`.
     ///
     /// # Panics
@@ -644,6 +871,20 @@ impl Interns {
         &self.long_ints[id.index()]
     }
 
+    /// Total size in bytes of the interned strings, bytes and long integers.
+    ///
+    /// Checked against the `max_interned_size` limit, see `ResourceTracker::check_interned_size`.
+    pub fn data_size(&self) -> usize {
+        let strings: usize = self.strings.iter().map(String::len).sum();
+        let bytes: usize = self.bytes.iter().map(Vec::len).sum();
+        let long_ints: usize = self
+            .long_ints
+            .iter()
+            .map(|bi| usize::try_from(bi.bits().div_ceil(8)).unwrap_or(usize::MAX))
+            .sum();
+        strings.saturating_add(bytes).saturating_add(long_ints)
+    }
+
     /// Lookup a function by its `FunctionId`
     ///
     /// # Panics
@@ -667,6 +908,56 @@ impl Interns {
             .clone()
     }
 
+    /// Lookup the registered signature of an external function, if any.
+    #[inline]
+    pub fn get_external_signature(&self, id: ExtFunctionId) -> Option<&ExtFunctionSignature> {
+        self.external_signatures.get(id.index()).and_then(Option::as_ref)
+    }
+
+    /// Registers a signature for the external function with the same name.
+    ///
+    /// Returns `false` if there is no external function with that name.
+    pub fn set_external_signature(&mut self, signature: ExtFunctionSignature) -> bool {
+        let Some(index) = self.external_functions.iter().position(|name| *name == signature.name) else {
+            return false;
+        };
+        if self.external_signatures.len() <= index {
+            self.external_signatures.resize(index + 1, None);
+        }
+        self.external_signatures[index] = Some(signature);
+        true
+    }
+
+    /// Marks the external function with this name as pure, so calls to it are memoized.
+    ///
+    /// Returns `false` if there is no external function with that name.
+    pub fn set_external_pure(&mut self, name: &str) -> bool {
+        let Some(index) = self.external_functions.iter().position(|n| n == name) else {
+            return false;
+        };
+        let id = ExtFunctionId::new(index);
+        if !self.pure_external_functions.contains(&id) {
+            self.pure_external_functions.push(id);
+        }
+        true
+    }
+
+    /// Whether calls to the external function are memoized, see `set_external_pure`.
+    #[inline]
+    pub fn is_external_pure(&self, id: ExtFunctionId) -> bool {
+        self.pure_external_functions.contains(&id)
+    }
+
+    /// Renders all registered external function signatures as type checking stubs, one per line.
+    #[cfg(feature = "type-checking")]
+    pub fn external_signature_stubs(&self) -> String {
+        self.external_signatures
+            .iter()
+            .flatten()
+            .map(|signature| signature.type_stub() + "\n")
+            .collect()
+    }
+
     /// Sets the compiled functions.
     ///
     /// This is called after compilation to populate the functions that were
@@ -1,4 +1,4 @@
-use std::borrow::Cow;
+use std::{borrow::Cow, io::Write};
 
 use crate::exception_public::MontyException;
 
@@ -11,7 +11,7 @@ use crate::exception_public::MontyException;
 /// # Variants
 /// - `Disabled` - Silently discards all output (useful for benchmarking or suppressing output)
 /// - `Stdout` - Writes to standard output (the default behavior)
-/// - `Collect` - Accumulates output into an owned `String` for programmatic access
+/// - `Collect` - Accumulates stdout and stderr output into an owned `String` for programmatic access
 /// - `Callback` - Delegates to a user-provided [`PrintWriterCallback`] implementation
 pub enum PrintWriter<'a> {
     /// Silently discard all output.
@@ -64,6 +64,56 @@ impl PrintWriter<'_> {
         }
     }
 
+    /// Writes text to stderr, used by `print(..., file=sys.stderr)`.
+    ///
+    /// `Collect` interleaves stderr output with stdout output in the same buffer.
+    pub fn stderr_write(&mut self, output: Cow<'_, str>) -> Result<(), MontyException> {
+        match self {
+            Self::Disabled => Ok(()),
+            Self::Stdout => {
+                eprint!("{output}");
+                Ok(())
+            }
+            Self::Collect(buf) => {
+                buf.push_str(&output);
+                Ok(())
+            }
+            Self::Callback(cb) => cb.stderr_write(output),
+        }
+    }
+
+    /// Appends a single character to stderr.
+    pub fn stderr_push(&mut self, end: char) -> Result<(), MontyException> {
+        match self {
+            Self::Disabled => Ok(()),
+            Self::Stdout => {
+                eprint!("{end}");
+                Ok(())
+            }
+            Self::Collect(buf) => {
+                buf.push(end);
+                Ok(())
+            }
+            Self::Callback(cb) => cb.stderr_push(end),
+        }
+    }
+
+    /// Flushes any buffered output, called by `print(..., flush=True)`.
+    ///
+    /// `Stdout` flushes the process's stdout and stderr; write errors are ignored
+    /// like those of `print!`.
+    pub fn flush(&mut self) -> Result<(), MontyException> {
+        match self {
+            Self::Disabled | Self::Collect(_) => Ok(()),
+            Self::Stdout => {
+                let _ = std::io::stdout().flush();
+                let _ = std::io::stderr().flush();
+                Ok(())
+            }
+            Self::Callback(cb) => cb.flush(),
+        }
+    }
+
     /// Returns the collected output if this is a `Collect` variant.
     ///
     /// Returns `None` for other variants.
@@ -99,4 +149,27 @@ pub trait PrintWriterCallback {
     /// # Arguments
     /// * `end` - The character to print after the formatted output.
     fn stdout_push(&mut self, end: char) -> Result<(), MontyException>;
+
+    /// Called with the text of each argument when printing to `sys.stderr`.
+    ///
+    /// Defaults to [`stdout_write`](Self::stdout_write), so callbacks that don't
+    /// distinguish streams receive stderr output too.
+    fn stderr_write(&mut self, output: Cow<'_, str>) -> Result<(), MontyException> {
+        self.stdout_write(output)
+    }
+
+    /// Add a single character to stderr.
+    ///
+    /// Defaults to [`stdout_push`](Self::stdout_push).
+    fn stderr_push(&mut self, end: char) -> Result<(), MontyException> {
+        self.stdout_push(end)
+    }
+
+    /// Called after `print(..., flush=True)` has written all of its output.
+    ///
+    /// Streaming hosts can use this to forward buffered output immediately.
+    /// Defaults to doing nothing.
+    fn flush(&mut self) -> Result<(), MontyException> {
+        Ok(())
+    }
 }
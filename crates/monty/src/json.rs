@@ -0,0 +1,478 @@
+//! Natural JSON mapping of `MontyObject`, for embedders that store or exchange values as JSON.
+//!
+//! JSON types map to the matching Python types. Python types without a JSON equivalent are
+//! encoded as an object with a single `$`-prefixed key naming the type, e.g.
+//! `{"$tuple": [1, 2]}` or `{"$bytes": "aGk="}`.
+
+use std::str::FromStr;
+
+use num_bigint::BigInt;
+use num_traits::ToPrimitive;
+use serde::de::Error as _;
+use serde_json::{Map, Number, Value as JsonValue, json};
+
+use crate::{
+    exception_private::ExcType,
+    object::{DictPairs, MontyObject},
+};
+
+impl MontyObject {
+    /// Converts the object to a natural JSON value.
+    ///
+    /// `None`, `bool`, `int`, `float`, `str`, `list` and `dict` with string keys map to the
+    /// matching JSON types. Other types are tagged objects with a single `$`-prefixed key:
+    /// `$tuple`, `$set`, `$frozenset`, `$dict` (pairs, for non-string keys), `$bytes`
+    /// (base64), `$bigint` (decimal string), `$float` (`"nan"`, `"inf"` or `"-inf"`),
+    /// `$ellipsis`, `$path`, `$namedtuple`, `$dataclass`, `$exception`, `$type` and `$repr`.
+    ///
+    /// See [`json_schema`](Self::json_schema) for the full format.
+    #[must_use]
+    pub fn to_json_value(&self) -> JsonValue {
+        match self {
+            Self::None => JsonValue::Null,
+            Self::Bool(b) => JsonValue::Bool(*b),
+            Self::Int(i) => JsonValue::from(*i),
+            Self::BigInt(bi) => tagged("$bigint", JsonValue::String(bi.to_string())),
+            Self::Float(f) => Number::from_f64(*f).map_or_else(
+                || {
+                    let name = if f.is_nan() {
+                        "nan"
+                    } else if f.is_sign_positive() {
+                        "inf"
+                    } else {
+                        "-inf"
+                    };
+                    tagged("$float", JsonValue::from(name))
+                },
+                JsonValue::Number,
+            ),
+            Self::String(s) => JsonValue::from(s.as_str()),
+            Self::SharedString(s) => JsonValue::from(&**s),
+            Self::Bytes(b) => tagged("$bytes", JsonValue::String(base64_encode(b))),
+            Self::List(items) => json_array(items),
+            Self::Tuple(items) => tagged("$tuple", json_array(items)),
+            Self::Set(items) => tagged("$set", json_array(items)),
+            Self::FrozenSet(items) => tagged("$frozenset", json_array(items)),
+            Self::Dict(pairs) => dict_to_json(pairs),
+            Self::NamedTuple {
+                type_name,
+                field_names,
+                values,
+            } => tagged(
+                "$namedtuple",
+                json!({
+                    "type_name": type_name,
+                    "field_names": field_names,
+                    "values": json_array(values),
+                }),
+            ),
+            Self::Dataclass {
+                name,
+                type_id,
+                field_names,
+                attrs,
+                frozen,
+            } => tagged(
+                "$dataclass",
+                json!({
+                    "name": name,
+                    "type_id": type_id,
+                    "field_names": field_names,
+                    "attrs": dict_to_json(attrs),
+                    "frozen": frozen,
+                }),
+            ),
+            Self::Exception { exc_type, arg } => {
+                tagged("$exception", json!({"type": exc_type.to_string(), "arg": arg}))
+            }
+            Self::Ellipsis => tagged("$ellipsis", JsonValue::Bool(true)),
+            Self::Path(path) => tagged("$path", JsonValue::from(path.as_str())),
+            Self::LazySequence {
+                name,
+                length,
+                chunk_size,
+            } => tagged(
+                "$lazy_sequence",
+                json!({"name": name, "length": length, "chunk_size": chunk_size}),
+            ),
+            Self::Type(t) => tagged("$type", JsonValue::String(t.to_string())),
+            Self::BuiltinFunction(_) | Self::Repr(_) | Self::Cycle(..) => {
+                tagged("$repr", JsonValue::String(self.py_repr()))
+            }
+        }
+    }
+
+    /// Converts a natural JSON value, as produced by [`to_json_value`](Self::to_json_value),
+    /// back to an object.
+    ///
+    /// Integers outside the `i64` range become `BigInt`.
+    ///
+    /// # Errors
+    /// Returns an error for malformed tagged objects and for the output-only `$type` and `$repr`.
+    pub fn from_json_value(value: JsonValue) -> Result<Self, serde_json::Error> {
+        match value {
+            JsonValue::Null => Ok(Self::None),
+            JsonValue::Bool(b) => Ok(Self::Bool(b)),
+            JsonValue::Number(n) => Ok(number_from_json(&n)),
+            JsonValue::String(s) => Ok(Self::String(s)),
+            JsonValue::Array(items) => Ok(Self::List(json_items(items)?)),
+            JsonValue::Object(map) => object_from_json(map),
+        }
+    }
+
+    /// JSON Schema (draft 2020-12) of the format produced by [`to_json_value`](Self::to_json_value).
+    #[must_use]
+    pub fn json_schema() -> JsonValue {
+        let value = json!({"$ref": "#/$defs/value"});
+        let values = json!({"type": "array", "items": value});
+        let string_map = json!({"type": "object", "additionalProperties": value});
+        let tag = |name: &str, schema: JsonValue| {
+            json!({
+                "type": "object",
+                "properties": {name: schema},
+                "required": [name],
+                "additionalProperties": false,
+            })
+        };
+        json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "title": "MontyObject",
+            "$ref": "#/$defs/value",
+            "$defs": {
+                "value": {
+                    "anyOf": [
+                        {"type": ["null", "boolean", "number", "string"]},
+                        values,
+                        string_map,
+                        tag("$tuple", values.clone()),
+                        tag("$set", values.clone()),
+                        tag("$frozenset", values.clone()),
+                        tag("$dict", json!({
+                            "type": "array",
+                            "items": {"type": "array", "prefixItems": [value, value], "minItems": 2, "maxItems": 2},
+                        })),
+                        tag("$bytes", json!({"type": "string", "contentEncoding": "base64"})),
+                        tag("$bigint", json!({"type": "string", "pattern": "^-?[0-9]+$"})),
+                        tag("$float", json!({"enum": ["nan", "inf", "-inf"]})),
+                        tag("$ellipsis", json!({"const": true})),
+                        tag("$path", json!({"type": "string"})),
+                        tag("$namedtuple", json!({
+                            "type": "object",
+                            "properties": {
+                                "type_name": {"type": "string"},
+                                "field_names": {"type": "array", "items": {"type": "string"}},
+                                "values": values,
+                            },
+                            "required": ["type_name", "field_names", "values"],
+                        })),
+                        tag("$dataclass", json!({
+                            "type": "object",
+                            "properties": {
+                                "name": {"type": "string"},
+                                "type_id": {"type": "integer", "minimum": 0},
+                                "field_names": {"type": "array", "items": {"type": "string"}},
+                                "attrs": string_map,
+                                "frozen": {"type": "boolean"},
+                            },
+                            "required": ["name", "field_names", "attrs"],
+                        })),
+                        tag("$lazy_sequence", json!({
+                            "type": "object",
+                            "properties": {
+                                "name": {"type": "string"},
+                                "length": {"type": ["integer", "null"], "minimum": 0},
+                                "chunk_size": {"type": "integer", "minimum": 1},
+                            },
+                            "required": ["name", "chunk_size"],
+                        })),
+                        tag("$exception", json!({
+                            "type": "object",
+                            "properties": {
+                                "type": {"type": "string"},
+                                "arg": {"type": ["string", "null"]},
+                            },
+                            "required": ["type"],
+                        })),
+                        tag("$type", json!({"type": "string"})),
+                        tag("$repr", json!({"type": "string"})),
+                    ]
+                }
+            }
+        })
+    }
+}
+
+/// Builds a tagged object `{tag: value}`.
+fn tagged(tag: &str, value: JsonValue) -> JsonValue {
+    let mut map = Map::new();
+    map.insert(tag.to_owned(), value);
+    JsonValue::Object(map)
+}
+
+fn json_array(items: &[MontyObject]) -> JsonValue {
+    JsonValue::Array(items.iter().map(MontyObject::to_json_value).collect())
+}
+
+/// Encodes a dict as a JSON object if every key is a string, otherwise as `$dict` pairs.
+///
+/// A single `$`-prefixed key is also encoded as pairs, since the object would read back as a tag.
+fn dict_to_json(pairs: &DictPairs) -> JsonValue {
+    let mut map = Map::new();
+    for (key, value) in pairs {
+        let key = match key {
+            MontyObject::String(s) => s.as_str(),
+            MontyObject::SharedString(s) => s,
+            _ => return dict_pairs_to_json(pairs),
+        };
+        map.insert(key.to_owned(), value.to_json_value());
+    }
+    if map.len() == 1 && map.keys().all(|key| key.starts_with('$')) {
+        return dict_pairs_to_json(pairs);
+    }
+    JsonValue::Object(map)
+}
+
+fn dict_pairs_to_json(pairs: &DictPairs) -> JsonValue {
+    let pairs = pairs
+        .into_iter()
+        .map(|(key, value)| JsonValue::Array(vec![key.to_json_value(), value.to_json_value()]))
+        .collect();
+    tagged("$dict", JsonValue::Array(pairs))
+}
+
+fn number_from_json(n: &Number) -> MontyObject {
+    if let Some(i) = n.as_i64() {
+        MontyObject::Int(i)
+    } else if let Some(u) = n.as_u64() {
+        MontyObject::BigInt(BigInt::from(u))
+    } else {
+        MontyObject::Float(n.as_f64().unwrap_or(f64::NAN))
+    }
+}
+
+fn json_items(items: Vec<JsonValue>) -> Result<Vec<MontyObject>, serde_json::Error> {
+    items.into_iter().map(MontyObject::from_json_value).collect()
+}
+
+/// Decodes a JSON object: a tagged value if it has a single `$`-prefixed key, otherwise a dict.
+fn object_from_json(map: Map<String, JsonValue>) -> Result<MontyObject, serde_json::Error> {
+    let tag = match map.keys().next() {
+        Some(key) if map.len() == 1 && key.starts_with('$') => key.clone(),
+        _ => {
+            let pairs = map
+                .into_iter()
+                .map(|(key, value)| Ok((MontyObject::String(key), MontyObject::from_json_value(value)?)))
+                .collect::<Result<Vec<_>, serde_json::Error>>()?;
+            return Ok(MontyObject::Dict(pairs.into()));
+        }
+    };
+    let value = map.into_iter().next().map(|(_, value)| value).unwrap_or_default();
+    match tag.as_str() {
+        "$tuple" => Ok(MontyObject::Tuple(json_items(expect_array(&tag, value)?)?)),
+        "$set" => Ok(MontyObject::Set(json_items(expect_array(&tag, value)?)?)),
+        "$frozenset" => Ok(MontyObject::FrozenSet(json_items(expect_array(&tag, value)?)?)),
+        "$dict" => Ok(MontyObject::Dict(dict_pairs_from_json(value)?)),
+        "$bytes" => {
+            let encoded = expect_string(&tag, value)?;
+            let bytes = base64_decode(&encoded).ok_or_else(|| invalid(&tag, "a base64 string"))?;
+            Ok(MontyObject::Bytes(bytes))
+        }
+        "$bigint" => {
+            let digits = expect_string(&tag, value)?;
+            let bi = BigInt::from_str(&digits).map_err(|_| invalid(&tag, "a decimal integer string"))?;
+            Ok(bi.to_i64().map_or(MontyObject::BigInt(bi), MontyObject::Int))
+        }
+        "$float" => match expect_string(&tag, value)?.as_str() {
+            "nan" => Ok(MontyObject::Float(f64::NAN)),
+            "inf" => Ok(MontyObject::Float(f64::INFINITY)),
+            "-inf" => Ok(MontyObject::Float(f64::NEG_INFINITY)),
+            _ => Err(invalid(&tag, "\"nan\", \"inf\" or \"-inf\"")),
+        },
+        "$ellipsis" => Ok(MontyObject::Ellipsis),
+        "$path" => Ok(MontyObject::Path(expect_string(&tag, value)?)),
+        "$namedtuple" => {
+            let JsonValue::Object(mut fields) = value else {
+                return Err(invalid(&tag, "an object"));
+            };
+            let type_name = expect_string(&tag, take(&mut fields, "type_name"))?;
+            let field_names = string_list(&tag, take(&mut fields, "field_names"))?;
+            let values = json_items(expect_array(&tag, take(&mut fields, "values"))?)?;
+            if field_names.len() != values.len() {
+                return Err(invalid(&tag, "as many values as field names"));
+            }
+            Ok(MontyObject::NamedTuple {
+                type_name,
+                field_names,
+                values,
+            })
+        }
+        "$dataclass" => {
+            let JsonValue::Object(mut fields) = value else {
+                return Err(invalid(&tag, "an object"));
+            };
+            let name = expect_string(&tag, take(&mut fields, "name"))?;
+            let type_id = take(&mut fields, "type_id").as_u64().unwrap_or_default();
+            let field_names = string_list(&tag, take(&mut fields, "field_names"))?;
+            let MontyObject::Dict(attrs) = MontyObject::from_json_value(take(&mut fields, "attrs"))? else {
+                return Err(invalid(&tag, "an object of attributes"));
+            };
+            let frozen = take(&mut fields, "frozen").as_bool().unwrap_or_default();
+            Ok(MontyObject::Dataclass {
+                name,
+                type_id,
+                field_names,
+                attrs,
+                frozen,
+            })
+        }
+        "$lazy_sequence" => {
+            let JsonValue::Object(mut fields) = value else {
+                return Err(invalid(&tag, "an object"));
+            };
+            let name = expect_string(&tag, take(&mut fields, "name"))?;
+            let length = match take(&mut fields, "length") {
+                JsonValue::Null => None,
+                length => Some(json_usize(&tag, &length)?),
+            };
+            let chunk_size = json_usize(&tag, &take(&mut fields, "chunk_size"))?;
+            Ok(MontyObject::LazySequence {
+                name,
+                length,
+                chunk_size,
+            })
+        }
+        "$exception" => {
+            let JsonValue::Object(mut fields) = value else {
+                return Err(invalid(&tag, "an object"));
+            };
+            let type_name = expect_string(&tag, take(&mut fields, "type"))?;
+            let exc_type = ExcType::from_str(&type_name)
+                .map_err(|_| serde_json::Error::custom(format!("unknown exception type '{type_name}'")))?;
+            let arg = match take(&mut fields, "arg") {
+                JsonValue::Null => None,
+                JsonValue::String(arg) => Some(arg),
+                _ => return Err(invalid(&tag, "a string or null arg")),
+            };
+            Ok(MontyObject::Exception { exc_type, arg })
+        }
+        "$type" | "$repr" => Err(serde_json::Error::custom(format!("'{tag}' values are output-only"))),
+        _ => Err(serde_json::Error::custom(format!("unknown tag '{tag}'"))),
+    }
+}
+
+fn dict_pairs_from_json(value: JsonValue) -> Result<DictPairs, serde_json::Error> {
+    expect_array("$dict", value)?
+        .into_iter()
+        .map(|pair| match pair {
+            JsonValue::Array(pair) if pair.len() == 2 => {
+                let [key, value]: [JsonValue; 2] = pair.try_into().expect("pair has two items");
+                Ok((MontyObject::from_json_value(key)?, MontyObject::from_json_value(value)?))
+            }
+            _ => Err(invalid("$dict", "an array of [key, value] pairs")),
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map(DictPairs::from)
+}
+
+fn take(fields: &mut Map<String, JsonValue>, name: &str) -> JsonValue {
+    fields.remove(name).unwrap_or_default()
+}
+
+fn expect_array(tag: &str, value: JsonValue) -> Result<Vec<JsonValue>, serde_json::Error> {
+    match value {
+        JsonValue::Array(items) => Ok(items),
+        _ => Err(invalid(tag, "an array")),
+    }
+}
+
+fn expect_string(tag: &str, value: JsonValue) -> Result<String, serde_json::Error> {
+    match value {
+        JsonValue::String(s) => Ok(s),
+        _ => Err(invalid(tag, "a string")),
+    }
+}
+
+fn json_usize(tag: &str, value: &JsonValue) -> Result<usize, serde_json::Error> {
+    value
+        .as_u64()
+        .and_then(|n| usize::try_from(n).ok())
+        .ok_or_else(|| invalid(tag, "a non-negative integer"))
+}
+
+fn string_list(tag: &str, value: JsonValue) -> Result<Vec<String>, serde_json::Error> {
+    expect_array(tag, value)?
+        .into_iter()
+        .map(|item| expect_string(tag, item))
+        .collect()
+}
+
+fn invalid(tag: &str, expected: &str) -> serde_json::Error {
+    serde_json::Error::custom(format!("'{tag}' value must be {expected}"))
+}
+
+const BASE64_ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Encodes bytes as standard base64 with padding.
+pub(crate) fn base64_encode(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(char::from(BASE64_ALPHABET[((n >> (18 - 6 * i)) & 0x3f) as usize]));
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+/// Decodes standard base64, padding is optional. Returns `None` for invalid input.
+pub(crate) fn base64_decode(encoded: &str) -> Option<Vec<u8>> {
+    let encoded = encoded.trim_end_matches('=');
+    if encoded.len() % 4 == 1 {
+        return None;
+    }
+    let mut out = Vec::with_capacity(encoded.len() * 3 / 4);
+    let mut n: u32 = 0;
+    let mut bits = 0;
+    for c in encoded.bytes() {
+        let digit = BASE64_ALPHABET.iter().position(|&a| a == c)?;
+        n = (n << 6) | u32::try_from(digit).expect("base64 digit fits in u32");
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            out.push(u8::try_from((n >> bits) & 0xff).expect("masked to a byte"));
+        }
+    }
+    Some(out)
+}
+
+/// Serde `with` module for bytes: a base64 string in human-readable formats like JSON, and
+/// the default sequence of bytes in binary formats like postcard.
+pub(crate) mod base64_bytes {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error};
+
+    pub(crate) fn serialize<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&super::base64_encode(bytes))
+        } else {
+            bytes.serialize(serializer)
+        }
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        if deserializer.is_human_readable() {
+            let encoded = String::deserialize(deserializer)?;
+            super::base64_decode(&encoded).ok_or_else(|| D::Error::custom("invalid base64 in bytes"))
+        } else {
+            Vec::deserialize(deserializer)
+        }
+    }
+}
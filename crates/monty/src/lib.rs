@@ -4,41 +4,78 @@ mod heap;
 
 mod args;
 mod asyncio;
+mod audit;
 mod builtins;
 mod bytecode;
+mod entropy;
 mod exception_private;
 mod exception_public;
 mod expressions;
+mod ext_functions;
+mod ext_signature;
 mod fstring;
 mod function;
+mod hashing;
 mod intern;
 mod io;
+mod json;
+mod memory_profile;
 mod modules;
 mod namespace;
 mod object;
 mod os;
 mod parse;
+mod percent_format;
+mod pool;
 mod prepare;
+mod profiler;
 mod repl;
 mod resource;
 mod run;
 mod signature;
+mod signing;
+mod sorted_serde;
+mod string_literal;
+#[cfg(feature = "type-checking")]
+mod type_checking;
 mod types;
+mod unicode_tables;
 mod value;
 
+#[cfg(feature = "type-checking")]
+pub use monty_type_checking::TypeCheckingDiagnostics;
+
+#[cfg(feature = "vm-metrics")]
+pub use crate::bytecode::VmMetrics;
+#[cfg(feature = "heap-validate")]
+pub use crate::resource::HeapValidator;
 #[cfg(feature = "ref-count-return")]
 pub use crate::run::RefCountOutput;
+#[cfg(feature = "type-checking")]
+pub use crate::type_checking::{TypeCheckError, TypeCheckMode};
 pub use crate::{
+    audit::{AuditEvent, AuditEventKind, AuditHook, AuditTracker, Capability},
+    entropy::EntropyPolicy,
     exception_private::ExcType,
-    exception_public::{CodeLoc, MontyException, StackFrame},
+    exception_public::{CodeLoc, HostError, MontyException, StackFrame, TracebackOptions, TracebackStyle},
+    ext_functions::{ExtArg, ExtFunction, ExtReturn, ExternalDispatcher, ExternalFunctions, NoOsAccess, OsDispatcher},
+    ext_signature::{ExtFunctionSignature, ExtParam, ExtParamKind},
     io::{PrintWriter, PrintWriterCallback},
-    object::{DictPairs, InvalidInputError, MontyObject},
-    os::{OsFunction, dir_stat, file_stat, stat_result, symlink_stat},
+    memory_profile::{AllocationSite, MemoryProfile, TypeUsage},
+    object::{ConversionError, DictPairs, InvalidInputError, MontyObject, SharedStr},
+    os::{OsFunction, dir_stat, file_stat, named_temporary_file, stat_result, symlink_stat, temporary_directory},
+    pool::{PoolError, PoolLease, PoolMetrics, PoolStats, SandboxPool, TenantQuota},
+    profiler::{FunctionProfile, LineProfile, ProfileReport, Profiler},
     repl::{
-        MontyRepl, ReplContinuationMode, ReplFutureSnapshot, ReplProgress, ReplSnapshot, detect_repl_continuation_mode,
+        MontyRepl, ReplContinuationMode, ReplFutureSnapshot, ReplProgress, ReplSnapshot, ReplStartError,
+        detect_repl_continuation_mode,
     },
     resource::{
-        DEFAULT_MAX_RECURSION_DEPTH, LimitedTracker, NoLimitTracker, ResourceError, ResourceLimits, ResourceTracker,
+        ComposedTracker, DEFAULT_MAX_RECURSION_DEPTH, DEFAULT_MAX_STACK_SIZE, InterruptCheck, InterruptTracker,
+        LimitedTracker, NoLimitTracker, ResourceError, ResourceLimits, ResourceTracker,
+    },
+    run::{
+        BatchCall, CheckpointSnapshot, ExternalResult, FutureSnapshot, MontyFuture, MontyRun, RunProgress, Snapshot,
     },
-    run::{ExternalResult, FutureSnapshot, MontyFuture, MontyRun, RunProgress, Snapshot},
+    signing::{LoadError, SIGNATURE_LEN, sign_serialized, verify_serialized},
 };
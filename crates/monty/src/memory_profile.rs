@@ -0,0 +1,129 @@
+//! Breakdown of heap usage by type and by allocation site.
+//!
+//! [`MemoryProfile`] is a snapshot of the live heap objects, built by `Heap::memory_profile`.
+//! It helps tune [`ResourceLimits`](crate::ResourceLimits) and find values that accumulate
+//! over a long REPL session.
+//!
+//! Allocation sites are only recorded when the resource tracker enables
+//! [`trace_allocations`](crate::ResourceTracker::trace_allocations); the VM then attributes
+//! the allocations made by each instruction to its source line.
+
+use ahash::AHashMap;
+
+use crate::intern::{Interns, StringId};
+
+/// Live heap objects grouped by type, and where objects were allocated when tracing is enabled.
+///
+/// Sizes are the same estimates used for [`ResourceLimits::max_memory`](crate::ResourceLimits),
+/// not exact process memory.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MemoryProfile {
+    /// Live objects per heap type, largest total size first.
+    pub types: Vec<TypeUsage>,
+    /// Allocations per source line since tracing started, largest total size first.
+    ///
+    /// Empty unless allocation tracing is enabled. Unlike `types`, this counts every
+    /// allocation including objects that have since been freed.
+    pub allocation_sites: Vec<AllocationSite>,
+}
+
+impl MemoryProfile {
+    /// Total number of live heap objects.
+    #[must_use]
+    pub fn total_objects(&self) -> usize {
+        self.types.iter().map(|usage| usage.count).sum()
+    }
+
+    /// Total estimated size in bytes of the live heap objects.
+    #[must_use]
+    pub fn total_bytes(&self) -> usize {
+        self.types.iter().map(|usage| usage.bytes).sum()
+    }
+
+    /// Usage of the given heap type, e.g. `"list"`, if any objects of that type are live.
+    #[must_use]
+    pub fn get(&self, type_name: &str) -> Option<&TypeUsage> {
+        self.types.iter().find(|usage| usage.type_name == type_name)
+    }
+}
+
+/// Live objects of one heap type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeUsage {
+    /// Name of the heap representation, e.g. `"list"`, `"closure"` or `"long_int"`.
+    ///
+    /// This follows the internal representation rather than the Python type, e.g. big
+    /// integers are reported as `"long_int"` separately from boxed small ones.
+    pub type_name: &'static str,
+    /// Number of live objects.
+    pub count: usize,
+    /// Estimated total size in bytes.
+    pub bytes: usize,
+}
+
+/// Allocations made by one source line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AllocationSite {
+    /// Name of the script or REPL input the line belongs to.
+    pub filename: String,
+    /// Line number, starting from 1.
+    pub line: u16,
+    /// Number of heap objects allocated.
+    pub count: usize,
+    /// Estimated total size in bytes of the allocated objects.
+    pub bytes: usize,
+}
+
+/// Allocation counters kept by the heap while tracing is enabled.
+///
+/// Not serialized, tracing starts again from zero after a snapshot is loaded.
+#[derive(Debug, Default)]
+pub(crate) struct AllocationTrace {
+    /// Total allocations since tracing started.
+    count: usize,
+    /// Total estimated bytes allocated since tracing started.
+    bytes: usize,
+    /// Allocations attributed to each `(filename, line)`.
+    sites: AHashMap<(StringId, u16), (usize, usize)>,
+}
+
+impl AllocationTrace {
+    /// Counts an allocation of `bytes`.
+    pub fn on_allocate(&mut self, bytes: usize) {
+        self.count += 1;
+        self.bytes += bytes;
+    }
+
+    /// Total allocation count and bytes so far, used by the VM to find each instruction's share.
+    pub fn totals(&self) -> (usize, usize) {
+        (self.count, self.bytes)
+    }
+
+    /// Attributes `count` allocations of `bytes` in total to a source line.
+    pub fn record_site(&mut self, filename: StringId, line: u16, count: usize, bytes: usize) {
+        let site = self.sites.entry((filename, line)).or_default();
+        site.0 += count;
+        site.1 += bytes;
+    }
+
+    /// Resolves the recorded sites to [`AllocationSite`]s, largest total size first.
+    pub fn allocation_sites(&self, interns: &Interns) -> Vec<AllocationSite> {
+        let mut sites: Vec<AllocationSite> = self
+            .sites
+            .iter()
+            .map(|(&(filename, line), &(count, bytes))| AllocationSite {
+                filename: interns.get_str(filename).to_owned(),
+                line,
+                count,
+                bytes,
+            })
+            .collect();
+        sites.sort_by(|a, b| {
+            b.bytes
+                .cmp(&a.bytes)
+                .then_with(|| a.filename.cmp(&b.filename))
+                .then(a.line.cmp(&b.line))
+        });
+        sites
+    }
+}
@@ -0,0 +1,36 @@
+//! Implementation of the `array` module.
+//!
+//! Provides a minimal implementation of Python's `array` module with:
+//! - `array`: A compact array of ints or floats with a fixed typecode
+//!
+//! `typecodes` and `ArrayType` aren't provided.
+
+use crate::{
+    builtins::Builtins,
+    heap::{Heap, HeapData, HeapId},
+    intern::{Interns, StaticStrings},
+    resource::{ResourceError, ResourceTracker},
+    types::{Module, Type},
+    value::Value,
+};
+
+/// Creates the `array` module and allocates it on the heap.
+///
+/// Returns a HeapId pointing to the newly allocated module.
+///
+/// # Panics
+///
+/// Panics if the required strings have not been pre-interned during prepare phase.
+pub fn create_module(heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> Result<HeapId, ResourceError> {
+    let mut module = Module::new(StaticStrings::Array);
+
+    // array.array - the array class (callable to create arrays)
+    module.set_attr(
+        StaticStrings::Array,
+        Value::Builtin(Builtins::Type(Type::Array)),
+        heap,
+        interns,
+    );
+
+    heap.allocate(HeapData::Module(module))
+}
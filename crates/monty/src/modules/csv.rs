@@ -0,0 +1,800 @@
+//! Implementation of the `csv` module.
+//!
+//! Provides Python's `csv` module with:
+//! - `reader(csvfile, dialect='excel', **fmtparams)`: Parse rows from an iterable of lines
+//! - `writer(csvfile, dialect='excel', **fmtparams)`: Write rows to a stream
+//! - `DictReader(f, fieldnames=None, restkey=None, restval=None, dialect='excel', **fmtparams)`
+//! - `DictWriter(f, fieldnames, restval='', extrasaction='raise', dialect='excel', **fmtparams)`
+//! - The `QUOTE_*` constants and the `Error` exception
+//!
+//! Readers take any iterable of str lines, e.g. an `io.StringIO` or `text.splitlines(keepends=True)`.
+//! Writers write to an `io.StringIO`, `sys.stdout` or `sys.stderr`.
+//!
+//! Unlike CPython, readers parse all of their input when they're created and return a plain
+//! iterator over the rows: parse errors are raised by the `reader()` or `DictReader()` call, and
+//! there are no `line_num`, `dialect` or `fieldnames` attributes. Dialects can only be given by
+//! name (`'excel'`, `'excel-tab'` or `'unix'`), and `register_dialect()` isn't provided.
+
+use crate::{
+    args::ArgValues,
+    builtins::Builtins,
+    defer_drop, defer_drop_mut,
+    exception_private::{ExcType, RunResult, SimpleException},
+    heap::{DropWithHeap, Heap, HeapData, HeapGuard, HeapId},
+    intern::{Interns, StaticStrings},
+    modules::ModuleFunctions,
+    resource::{ResourceError, ResourceTracker},
+    types::{
+        AttrCallResult, Dict, List, Module, MontyIter, PyTrait,
+        csv_writer::{CsvTarget, CsvWriter, Dialect, DictFields, Quoting, csv_error},
+        str::allocate_string,
+        r#type::parse_f64_from_str,
+    },
+    value::Value,
+};
+
+/// csv module functions.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, strum::Display, strum::IntoStaticStr, serde::Serialize, serde::Deserialize,
+)]
+pub(crate) enum CsvFunctions {
+    #[strum(serialize = "reader")]
+    Reader,
+    #[strum(serialize = "writer")]
+    Writer,
+    DictReader,
+    DictWriter,
+}
+
+/// Formatting parameters accepted by keyword, overriding those of the dialect.
+const FMT_PARAMS: [&str; 8] = [
+    "delimiter",
+    "doublequote",
+    "escapechar",
+    "lineterminator",
+    "quotechar",
+    "quoting",
+    "skipinitialspace",
+    "strict",
+];
+
+/// Largest field a reader accepts, CPython's default `field_size_limit()`.
+const FIELD_LIMIT: usize = 131_072;
+
+/// Creates the `csv` module and allocates it on the heap.
+///
+/// Returns a HeapId pointing to the newly allocated module.
+///
+/// # Panics
+///
+/// Panics if the required strings have not been pre-interned during prepare phase.
+pub fn create_module(heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> Result<HeapId, ResourceError> {
+    let mut module = Module::new(StaticStrings::Csv);
+
+    let functions = [
+        (StaticStrings::Reader, CsvFunctions::Reader),
+        (StaticStrings::Writer, CsvFunctions::Writer),
+        (StaticStrings::DictReader, CsvFunctions::DictReader),
+        (StaticStrings::DictWriter, CsvFunctions::DictWriter),
+    ];
+    for (name, function) in functions {
+        module.set_attr(
+            name,
+            Value::ModuleFunction(ModuleFunctions::Csv(function)),
+            heap,
+            interns,
+        );
+    }
+
+    let constants = [
+        (StaticStrings::QuoteMinimal, Quoting::Minimal),
+        (StaticStrings::QuoteAll, Quoting::All),
+        (StaticStrings::QuoteNonnumeric, Quoting::NonNumeric),
+        (StaticStrings::QuoteNone, Quoting::None),
+        (StaticStrings::QuoteStrings, Quoting::Strings),
+        (StaticStrings::QuoteNotnull, Quoting::NotNull),
+    ];
+    for (name, quoting) in constants {
+        module.set_attr(name, Value::Int(quoting as i64), heap, interns);
+    }
+
+    module.set_attr(
+        StaticStrings::ErrorClass,
+        Value::Builtin(Builtins::ExcType(ExcType::CsvError)),
+        heap,
+        interns,
+    );
+
+    heap.allocate(HeapData::Module(module))
+}
+
+/// Dispatches a call to a csv module function.
+///
+/// # Errors
+/// Returns `TypeError` for bad arguments or formatting parameters, `csv.Error` for an unknown
+/// dialect or input which can't be parsed, and `ValueError` for invalid dialect characters.
+pub(super) fn call(
+    heap: &mut Heap<impl ResourceTracker>,
+    functions: CsvFunctions,
+    args: ArgValues,
+    interns: &Interns,
+) -> RunResult<AttrCallResult> {
+    let value = match functions {
+        CsvFunctions::Reader => reader(heap, args, interns)?,
+        CsvFunctions::Writer => writer(heap, args, interns)?,
+        CsvFunctions::DictReader => dict_reader(heap, args, interns)?,
+        CsvFunctions::DictWriter => dict_writer(heap, args, interns)?,
+    };
+    Ok(AttrCallResult::Value(value))
+}
+
+/// Implements `csv.reader(csvfile, /, dialect='excel', **fmtparams)`.
+fn reader(heap: &mut Heap<impl ResourceTracker>, args: ArgValues, interns: &Interns) -> RunResult<Value> {
+    let (params, dialect) = bind_args("reader", &["csvfile", "dialect"], 1, 1, args, heap, interns)?;
+    let [Some(csvfile)] = <[Option<Value>; 1]>::try_from(params).expect("one parameter before dialect") else {
+        unreachable!("bind_args checks required parameters")
+    };
+
+    let rows = parse_rows(csvfile, &dialect, heap, interns)?;
+    let mut values = Vec::with_capacity(rows.len());
+    for row in rows {
+        match row_value(row, heap) {
+            Ok(value) => values.push(value),
+            Err(err) => {
+                values.drop_with_heap(heap);
+                return Err(err);
+            }
+        }
+    }
+    let list = Value::Ref(heap.allocate(HeapData::List(List::new(values)))?);
+    MontyIter::get_iter(list, heap, interns)
+}
+
+/// Implements `csv.writer(csvfile, /, dialect='excel', **fmtparams)`.
+fn writer(heap: &mut Heap<impl ResourceTracker>, args: ArgValues, interns: &Interns) -> RunResult<Value> {
+    let (params, dialect) = bind_args("writer", &["csvfile", "dialect"], 1, 1, args, heap, interns)?;
+    let [Some(csvfile)] = <[Option<Value>; 1]>::try_from(params).expect("one parameter before dialect") else {
+        unreachable!("bind_args checks required parameters")
+    };
+    defer_drop!(csvfile, heap);
+
+    let target = writer_target(csvfile, heap)?;
+    let writer = CsvWriter::new(target, dialect, None);
+    Ok(Value::Ref(heap.allocate(HeapData::CsvWriter(writer))?))
+}
+
+/// Implements `csv.DictReader(f, fieldnames=None, restkey=None, restval=None, dialect='excel', **fmtparams)`.
+///
+/// Rows are dicts keyed by `fieldnames`, which default to the first row. Blank rows are skipped,
+/// extra fields are collected in a list under `restkey`, and missing fields are set to `restval`.
+fn dict_reader(heap: &mut Heap<impl ResourceTracker>, args: ArgValues, interns: &Interns) -> RunResult<Value> {
+    let name = "DictReader.__init__";
+    let params = ["f", "fieldnames", "restkey", "restval", "dialect"];
+    let (params, dialect) = bind_args(name, &params, 1, 0, args, heap, interns)?;
+    let [Some(f), fieldnames, restkey, restval] = <[Option<Value>; 4]>::try_from(params).expect("four parameters")
+    else {
+        unreachable!("bind_args checks required parameters")
+    };
+    let mut rows = match parse_rows(f, &dialect, heap, interns) {
+        Ok(rows) => rows.into_iter(),
+        Err(err) => {
+            fieldnames.drop_with_heap(heap);
+            restkey.drop_with_heap(heap);
+            restval.drop_with_heap(heap);
+            return Err(err);
+        }
+    };
+    let restkey = restkey.unwrap_or(Value::None);
+    defer_drop!(restkey, heap);
+    let restval = restval.unwrap_or(Value::None);
+    defer_drop!(restval, heap);
+    let fieldnames = match fieldnames {
+        None | Some(Value::None) => match rows.next() {
+            Some(row) => field_values(row, heap)?,
+            None => Vec::new(),
+        },
+        Some(fieldnames) => MontyIter::new(fieldnames, heap, interns)?.collect(heap, interns)?,
+    };
+    defer_drop!(fieldnames, heap);
+
+    let mut dicts = HeapGuard::new(Vec::new(), heap);
+    {
+        let (dicts, heap) = dicts.as_parts_mut();
+        for row in rows.filter(|row| !row.is_empty()) {
+            let values = field_values(row, heap)?;
+            let dict = row_dict(fieldnames, values, restkey, restval, heap, interns)?;
+            dicts.push(Value::Ref(heap.allocate(HeapData::Dict(dict))?));
+        }
+    }
+    let (dicts, heap) = dicts.into_parts();
+    let list = Value::Ref(heap.allocate(HeapData::List(List::new(dicts)))?);
+    MontyIter::get_iter(list, heap, interns)
+}
+
+/// Builds one `DictReader` row from its field values.
+fn row_dict(
+    fieldnames: &[Value],
+    values: Vec<Value>,
+    restkey: &Value,
+    restval: &Value,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<Dict> {
+    let mut dict = HeapGuard::new(Dict::new(), heap);
+    {
+        let (dict, heap) = dict.as_parts_mut();
+        let mut values = values.into_iter();
+        defer_drop_mut!(values, heap);
+        for key in fieldnames {
+            let value = match values.next() {
+                Some(value) => value,
+                None => restval.clone_with_heap(heap),
+            };
+            let key = key.clone_with_heap(heap);
+            let old = dict.set(key, value, heap, interns)?;
+            old.drop_with_heap(heap);
+        }
+        let rest: Vec<Value> = values.collect();
+        if !rest.is_empty() {
+            let rest = Value::Ref(heap.allocate(HeapData::List(List::new(rest)))?);
+            let key = restkey.clone_with_heap(heap);
+            let old = dict.set(key, rest, heap, interns)?;
+            old.drop_with_heap(heap);
+        }
+    }
+    Ok(dict.into_inner())
+}
+
+/// Implements `csv.DictWriter(f, fieldnames, restval='', extrasaction='raise', dialect='excel', **fmtparams)`.
+fn dict_writer(heap: &mut Heap<impl ResourceTracker>, args: ArgValues, interns: &Interns) -> RunResult<Value> {
+    let name = "DictWriter.__init__";
+    let params = ["f", "fieldnames", "restval", "extrasaction", "dialect"];
+    let (params, dialect) = bind_args(name, &params, 2, 0, args, heap, interns)?;
+    let [Some(f), Some(fieldnames), restval, extrasaction] =
+        <[Option<Value>; 4]>::try_from(params).expect("four parameters")
+    else {
+        unreachable!("bind_args checks required parameters")
+    };
+    defer_drop!(f, heap);
+    defer_drop!(fieldnames, heap);
+    defer_drop!(extrasaction, heap);
+    let restval = restval.unwrap_or(Value::InternString(StaticStrings::EmptyString.into()));
+    defer_drop!(restval, heap);
+
+    let raise_on_extra = match extrasaction {
+        None => true,
+        Some(value) => {
+            let Some(action) = value.as_either_str(heap) else {
+                return Err(ExcType::attribute_error(value.py_type(heap), "lower"));
+            };
+            let action = action.as_str(interns);
+            match action.to_lowercase().as_str() {
+                "raise" => true,
+                "ignore" => false,
+                _ => {
+                    return Err(SimpleException::new_msg(
+                        ExcType::ValueError,
+                        format!("extrasaction ({action}) must be 'raise' or 'ignore'"),
+                    )
+                    .into());
+                }
+            }
+        }
+    };
+    let fieldnames: Vec<Value> =
+        MontyIter::new(fieldnames.clone_with_heap(heap), heap, interns)?.collect(heap, interns)?;
+    let target = match writer_target(f, heap) {
+        Ok(target) => target,
+        Err(err) => {
+            fieldnames.drop_with_heap(heap);
+            return Err(err);
+        }
+    };
+
+    let dict = DictFields {
+        fieldnames,
+        restval: restval.clone_with_heap(heap),
+        raise_on_extra,
+    };
+    let writer = CsvWriter::new(target, dialect, Some(dict));
+    Ok(Value::Ref(heap.allocate(HeapData::CsvWriter(writer))?))
+}
+
+/// Returns the target of a new writer, taking a reference to it if it's a `StringIO`.
+fn writer_target(csvfile: &Value, heap: &mut Heap<impl ResourceTracker>) -> RunResult<CsvTarget> {
+    let Some(target) = CsvTarget::from_value(csvfile, heap) else {
+        return Err(ExcType::type_error("argument 1 must have a \"write\" method"));
+    };
+    if let CsvTarget::StringIO(heap_id) = target {
+        heap.inc_ref(heap_id);
+    }
+    Ok(target)
+}
+
+/// Binds a csv function's arguments and builds its dialect.
+///
+/// `params` are bound by position or, after the first `pos_only`, by keyword, and must end with
+/// `dialect`. Formatting parameters are only accepted by keyword. Returns the values of the
+/// parameters before `dialect`, which are `None` when not given.
+fn bind_args(
+    name: &str,
+    params: &[&str],
+    required: usize,
+    pos_only: usize,
+    args: ArgValues,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<(Vec<Option<Value>>, Dialect)> {
+    let (pos, kwargs) = args.into_parts();
+    let given = pos.len();
+    if given > params.len() {
+        pos.drop_with_heap(heap);
+        kwargs.drop_with_heap(heap);
+        return Err(ExcType::type_error_too_many_positional(
+            name,
+            required,
+            params.len(),
+            given,
+            0,
+        ));
+    }
+
+    // Values of `params` followed by those of `FMT_PARAMS`
+    let mut values = pos.map(Some).collect::<Vec<_>>();
+    values.resize_with(params.len() + FMT_PARAMS.len(), || None);
+    let mut values_guard = HeapGuard::new(values, heap);
+    let dialect = {
+        let (values, heap) = values_guard.as_parts_mut();
+        let kwargs = kwargs.into_iter();
+        defer_drop_mut!(kwargs, heap);
+
+        for (key, value) in kwargs {
+            defer_drop!(key, heap);
+            let mut value = HeapGuard::new(value, heap);
+
+            let Some(keyword_name) = key.as_either_str(value.heap()) else {
+                return Err(ExcType::type_error_kwargs_nonstring_key());
+            };
+            let key_str = keyword_name.as_str(interns);
+            let index = if let Some(index) = params[pos_only..].iter().position(|param| *param == key_str) {
+                pos_only + index
+            } else if let Some(index) = FMT_PARAMS.iter().position(|param| *param == key_str) {
+                params.len() + index
+            } else {
+                return Err(ExcType::type_error_unexpected_keyword(name, key_str));
+            };
+            if index < given {
+                return Err(ExcType::type_error_duplicate_arg(name, key_str));
+            }
+            let old = values[index].replace(value.into_inner());
+            old.drop_with_heap(heap);
+        }
+
+        let missing: Vec<&str> = params[..required]
+            .iter()
+            .zip(values.iter())
+            .filter(|(_, value)| value.is_none())
+            .map(|(param, _)| *param)
+            .collect();
+        if !missing.is_empty() {
+            return Err(ExcType::type_error_missing_positional_with_names(name, &missing));
+        }
+        build_dialect(&values[params.len() - 1..], heap, interns)?
+    };
+
+    let (mut values, heap) = values_guard.into_parts();
+    let rest = values.split_off(params.len() - 1);
+    rest.drop_with_heap(heap);
+    Ok((values, dialect))
+}
+
+/// Builds a dialect from the `dialect` argument followed by the values of `FMT_PARAMS`,
+/// validating them as CPython does.
+fn build_dialect(values: &[Option<Value>], heap: &Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<Dialect> {
+    let [
+        dialect,
+        delimiter,
+        doublequote,
+        escapechar,
+        lineterminator,
+        quotechar,
+        quoting,
+        skipinitialspace,
+        strict,
+    ] = values
+    else {
+        unreachable!("dialect is followed by each formatting parameter")
+    };
+
+    let mut result = match dialect {
+        None => Dialect::excel(),
+        Some(value) => {
+            let Some(name) = value.as_either_str(heap) else {
+                return Err(ExcType::type_error(
+                    "dialect must be the name of a dialect, dialect classes are not supported",
+                ));
+            };
+            Dialect::by_name(name.as_str(interns)).ok_or_else(|| csv_error("unknown dialect"))?
+        }
+    };
+
+    if let Some(value) = delimiter {
+        let Some(text) = value.as_either_str(heap) else {
+            return Err(ExcType::type_error(format!(
+                "\"delimiter\" must be string, not {}",
+                value.py_type(heap)
+            )));
+        };
+        result.delimiter = single_char("delimiter", text.as_str(interns))?;
+    }
+    if let Some(value) = doublequote {
+        result.doublequote = value.py_bool(heap, interns);
+    }
+    if let Some(value) = escapechar {
+        result.escapechar = char_or_none("escapechar", value, heap, interns)?;
+    }
+    if let Some(value) = lineterminator {
+        let Some(text) = value.as_either_str(heap) else {
+            return Err(ExcType::type_error("\"lineterminator\" must be a string"));
+        };
+        result.lineterminator = text.as_str(interns).to_owned();
+    }
+    if let Some(value) = quotechar {
+        result.quotechar = char_or_none("quotechar", value, heap, interns)?;
+    }
+    if let Some(value) = quoting {
+        let Value::Int(quoting) = value else {
+            return Err(ExcType::type_error("\"quoting\" must be an integer"));
+        };
+        result.quoting = Quoting::from_i64(*quoting).ok_or_else(|| ExcType::type_error("bad \"quoting\" value"))?;
+    }
+    if let Some(value) = skipinitialspace {
+        result.skipinitialspace = value.py_bool(heap, interns);
+    }
+    if let Some(value) = strict {
+        result.strict = value.py_bool(heap, interns);
+    }
+
+    // Clearing the quotechar turns quoting off, unless a quoting style is given
+    if matches!(quotechar, Some(Value::None)) && quoting.is_none() {
+        result.quoting = Quoting::None;
+    }
+    if result.quoting != Quoting::None && result.quotechar.is_none() {
+        return Err(ExcType::type_error("quotechar must be set if quoting enabled"));
+    }
+    check_char("delimiter", Some(result.delimiter), true)?;
+    check_char("escapechar", result.escapechar, !result.skipinitialspace)?;
+    check_char("quotechar", result.quotechar, !result.skipinitialspace)?;
+    check_distinct("delimiter", Some(result.delimiter), "escapechar", result.escapechar)?;
+    check_distinct("delimiter", Some(result.delimiter), "quotechar", result.quotechar)?;
+    check_distinct("escapechar", result.escapechar, "quotechar", result.quotechar)?;
+    Ok(result)
+}
+
+/// Returns the only character of a formatting parameter.
+fn single_char(param: &str, text: &str) -> RunResult<char> {
+    let mut chars = text.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Ok(c),
+        _ => Err(ExcType::type_error(format!("\"{param}\" must be a 1-character string"))),
+    }
+}
+
+/// Converts a formatting parameter which is a single character or `None`.
+fn char_or_none(
+    param: &str,
+    value: &Value,
+    heap: &Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<Option<char>> {
+    if matches!(value, Value::None) {
+        return Ok(None);
+    }
+    let Some(text) = value.as_either_str(heap) else {
+        return Err(ExcType::type_error(format!(
+            "\"{param}\" must be string or None, not {}",
+            value.py_type(heap)
+        )));
+    };
+    single_char(param, text.as_str(interns)).map(Some)
+}
+
+/// Rejects line breaks, and spaces unless `allow_space`, as dialect characters.
+fn check_char(param: &str, c: Option<char>, allow_space: bool) -> RunResult<()> {
+    match c {
+        Some('\r' | '\n') => Err(SimpleException::new_msg(ExcType::ValueError, format!("bad {param} value")).into()),
+        Some(' ') if !allow_space => {
+            Err(SimpleException::new_msg(ExcType::ValueError, format!("bad {param} value")).into())
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Rejects two dialect characters which are the same.
+fn check_distinct(param1: &str, c1: Option<char>, param2: &str, c2: Option<char>) -> RunResult<()> {
+    if c1.is_some() && c1 == c2 {
+        Err(SimpleException::new_msg(ExcType::ValueError, format!("bad {param1} or {param2} value")).into())
+    } else {
+        Ok(())
+    }
+}
+
+/// A parsed field, converted to a value once parsing is done.
+#[derive(Debug)]
+enum Field {
+    Str(String),
+    Float(f64),
+    None,
+}
+
+/// States of the reader, named as in CPython's `_csv.c`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ParserState {
+    StartRecord,
+    StartField,
+    EscapedChar,
+    AfterEscapedCrnl,
+    InField,
+    InQuotedField,
+    EscapeInQuotedField,
+    QuoteInQuotedField,
+    EatCrnl,
+}
+
+/// Splits lines into records, a port of CPython's reader state machine.
+struct Parser<'a> {
+    dialect: &'a Dialect,
+    state: ParserState,
+    field: String,
+    /// Length of `field` in characters.
+    field_len: usize,
+    /// Cleared once a quote opens the current field.
+    unquoted_field: bool,
+    fields: Vec<Field>,
+}
+
+impl<'a> Parser<'a> {
+    fn new(dialect: &'a Dialect) -> Self {
+        Self {
+            dialect,
+            state: ParserState::StartRecord,
+            field: String::new(),
+            field_len: 0,
+            unquoted_field: true,
+            fields: Vec::new(),
+        }
+    }
+
+    /// Processes one character of a line, or `None` at the end of each line.
+    fn process_char(&mut self, c: Option<char>) -> RunResult<()> {
+        let dialect = self.dialect;
+        let is_quotechar = c.is_some() && c == dialect.quotechar && dialect.quoting != Quoting::None;
+        let is_escapechar = c.is_some() && c == dialect.escapechar;
+        let is_line_end = matches!(c, None | Some('\n' | '\r'));
+        match self.state {
+            ParserState::StartRecord => match c {
+                // An empty line is an empty record
+                None => {}
+                Some('\n' | '\r') => self.state = ParserState::EatCrnl,
+                Some(_) => {
+                    self.state = ParserState::StartField;
+                    return self.process_char(c);
+                }
+            },
+            ParserState::StartField => {
+                if is_line_end {
+                    self.save_field()?;
+                    self.end_line(c);
+                } else if is_quotechar {
+                    self.unquoted_field = false;
+                    self.state = ParserState::InQuotedField;
+                } else if is_escapechar {
+                    self.state = ParserState::EscapedChar;
+                } else if c == Some(' ') && dialect.skipinitialspace {
+                    // Spaces at the start of a field are ignored
+                } else if c == Some(dialect.delimiter) {
+                    self.save_field()?;
+                } else {
+                    self.add_char(c)?;
+                    self.state = ParserState::InField;
+                }
+            }
+            ParserState::EscapedChar => match c {
+                Some('\n' | '\r') => {
+                    self.add_char(c)?;
+                    self.state = ParserState::AfterEscapedCrnl;
+                }
+                _ => {
+                    self.add_char(c)?;
+                    self.state = ParserState::InField;
+                }
+            },
+            ParserState::AfterEscapedCrnl if c.is_none() => {}
+            ParserState::AfterEscapedCrnl | ParserState::InField => {
+                if is_line_end {
+                    self.save_field()?;
+                    self.end_line(c);
+                } else if is_escapechar {
+                    self.state = ParserState::EscapedChar;
+                } else if c == Some(dialect.delimiter) {
+                    self.save_field()?;
+                    self.state = ParserState::StartField;
+                } else {
+                    self.add_char(c)?;
+                    self.state = ParserState::InField;
+                }
+            }
+            ParserState::InQuotedField => {
+                if c.is_none() {
+                    // Quoted fields continue onto the next line
+                } else if is_escapechar {
+                    self.state = ParserState::EscapeInQuotedField;
+                } else if is_quotechar {
+                    self.state = if dialect.doublequote {
+                        ParserState::QuoteInQuotedField
+                    } else {
+                        ParserState::InField
+                    };
+                } else {
+                    self.add_char(c)?;
+                }
+            }
+            ParserState::EscapeInQuotedField => {
+                self.add_char(c)?;
+                self.state = ParserState::InQuotedField;
+            }
+            ParserState::QuoteInQuotedField => {
+                if is_quotechar {
+                    // A doubled quote is a literal quote
+                    self.add_char(c)?;
+                    self.state = ParserState::InQuotedField;
+                } else if c == Some(dialect.delimiter) {
+                    self.save_field()?;
+                    self.state = ParserState::StartField;
+                } else if is_line_end {
+                    self.save_field()?;
+                    self.end_line(c);
+                } else if !dialect.strict {
+                    self.add_char(c)?;
+                    self.state = ParserState::InField;
+                } else {
+                    let quotechar = dialect.quotechar.expect("quoting requires a quotechar");
+                    return Err(csv_error(format!(
+                        "'{}' expected after '{quotechar}'",
+                        dialect.delimiter
+                    )));
+                }
+            }
+            ParserState::EatCrnl => match c {
+                Some('\n' | '\r') => {}
+                None => self.state = ParserState::StartRecord,
+                Some(_) => {
+                    return Err(csv_error(
+                        "new-line character seen in unquoted field - do you need to open the file with newline=''?",
+                    ));
+                }
+            },
+        }
+        Ok(())
+    }
+
+    /// Moves on after a field ended by a line break, or by the end of the line.
+    fn end_line(&mut self, c: Option<char>) {
+        self.state = if c.is_none() {
+            ParserState::StartRecord
+        } else {
+            ParserState::EatCrnl
+        };
+    }
+
+    /// Adds a character to the current field, with `None` (an escaped end of line) as `\n`.
+    fn add_char(&mut self, c: Option<char>) -> RunResult<()> {
+        if self.field_len >= FIELD_LIMIT {
+            return Err(csv_error(format!("field larger than field limit ({FIELD_LIMIT})")));
+        }
+        self.field.push(c.unwrap_or('\n'));
+        self.field_len += 1;
+        Ok(())
+    }
+
+    /// Ends the current field, converting unquoted fields as the quoting style requires.
+    fn save_field(&mut self) -> RunResult<()> {
+        let text = std::mem::take(&mut self.field);
+        let quoting = self.dialect.quoting;
+        let field =
+            if self.unquoted_field && self.field_len == 0 && matches!(quoting, Quoting::NotNull | Quoting::Strings) {
+                Field::None
+            } else if self.unquoted_field
+                && self.field_len != 0
+                && matches!(quoting, Quoting::NonNumeric | Quoting::Strings)
+            {
+                Field::Float(parse_f64_from_str(&text)?)
+            } else {
+                Field::Str(text)
+            };
+        self.fields.push(field);
+        self.field_len = 0;
+        self.unquoted_field = true;
+        Ok(())
+    }
+
+    /// Returns the last record if the input ends in the middle of it.
+    fn finish(mut self) -> RunResult<Option<Vec<Field>>> {
+        if self.field_len == 0 && self.state != ParserState::InQuotedField {
+            return Ok(None);
+        }
+        if self.dialect.strict {
+            return Err(csv_error("unexpected end of data"));
+        }
+        self.save_field()?;
+        Ok(Some(self.fields))
+    }
+}
+
+/// Parses every record from an iterable of lines.
+fn parse_rows(
+    lines: Value,
+    dialect: &Dialect,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<Vec<Vec<Field>>> {
+    let iter = MontyIter::new(lines, heap, interns)?;
+    defer_drop_mut!(iter, heap);
+
+    let mut parser = Parser::new(dialect);
+    let mut rows = Vec::new();
+    while let Some(line) = iter.for_next(heap, interns)? {
+        defer_drop!(line, heap);
+        let text = match line {
+            Value::InternString(string_id) => Some(interns.get_str(*string_id)),
+            Value::Ref(heap_id) => match heap.get(*heap_id) {
+                HeapData::Str(s) => Some(s.as_str()),
+                _ => None,
+            },
+            _ => None,
+        };
+        let Some(text) = text else {
+            return Err(csv_error(format!(
+                "iterator should return strings, not {} (the file should be opened in text mode)",
+                line.py_type(heap)
+            )));
+        };
+        for c in text.chars() {
+            parser.process_char(Some(c))?;
+        }
+        parser.process_char(None)?;
+        if parser.state == ParserState::StartRecord {
+            rows.push(std::mem::take(&mut parser.fields));
+        }
+    }
+    if let Some(row) = parser.finish()? {
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+/// Converts parsed fields to values.
+fn field_values(row: Vec<Field>, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Vec<Value>> {
+    let mut values = Vec::with_capacity(row.len());
+    for field in row {
+        let value = match field {
+            Field::Str(text) => match allocate_string(text, heap) {
+                Ok(value) => value,
+                Err(err) => {
+                    values.drop_with_heap(heap);
+                    return Err(err);
+                }
+            },
+            Field::Float(f) => Value::Float(f),
+            Field::None => Value::None,
+        };
+        values.push(value);
+    }
+    Ok(values)
+}
+
+/// Converts parsed fields to a list value.
+fn row_value(row: Vec<Field>, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
+    let values = field_values(row, heap)?;
+    Ok(Value::Ref(heap.allocate(HeapData::List(List::new(values)))?))
+}
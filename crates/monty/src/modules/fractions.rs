@@ -0,0 +1,37 @@
+//! Implementation of the `fractions` module.
+//!
+//! Provides a minimal implementation of Python's `fractions` module with:
+//! - `Fraction`: An exact rational number
+//!
+//! There's no `Fraction.from_float()` or `Fraction.from_decimal()`; call `Fraction()` with a
+//! float instead.
+
+use crate::{
+    builtins::Builtins,
+    heap::{Heap, HeapData, HeapId},
+    intern::{Interns, StaticStrings},
+    resource::{ResourceError, ResourceTracker},
+    types::{Module, Type},
+    value::Value,
+};
+
+/// Creates the `fractions` module and allocates it on the heap.
+///
+/// Returns a HeapId pointing to the newly allocated module.
+///
+/// # Panics
+///
+/// Panics if the required strings have not been pre-interned during prepare phase.
+pub fn create_module(heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> Result<HeapId, ResourceError> {
+    let mut module = Module::new(StaticStrings::Fractions);
+
+    // fractions.Fraction - the Fraction class (callable to create fractions)
+    module.set_attr(
+        StaticStrings::FractionClass,
+        Value::Builtin(Builtins::Type(Type::Fraction)),
+        heap,
+        interns,
+    );
+
+    heap.allocate(HeapData::Module(module))
+}
@@ -0,0 +1,163 @@
+//! Implementation of the `gzip` module.
+//!
+//! Provides a minimal implementation of Python's `gzip` module with:
+//! - `compress(data, compresslevel=9, *, mtime=None)`: Compress data to a gzip member
+//! - `decompress(data)`: Decompress one or more concatenated gzip members
+//! - The `BadGzipFile` exception
+//!
+//! There are no file objects in the sandbox, so `open()` and `GzipFile` aren't provided.
+//! There's no clock either, so `compress()` writes a modification time of 0 unless `mtime`
+//! is given. Compression and its resource checks are shared with the `zlib` module.
+
+use crate::{
+    args::ArgValues,
+    builtins::Builtins,
+    defer_drop,
+    exception_private::{ExcType, RunError, RunResult, SimpleException},
+    heap::{Heap, HeapData, HeapId},
+    intern::{Interns, StaticStrings},
+    modules::{
+        ModuleFunctions,
+        zlib::{
+            InflateError, bind_args, bytes_arg, compression_level, decompress_error, gunzip_member, gzip_member,
+            opt_int_arg,
+        },
+    },
+    resource::{ResourceError, ResourceTracker},
+    types::{AttrCallResult, Bytes, Module, bytes::bytes_repr},
+    value::Value,
+};
+
+/// gzip module functions.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, strum::Display, strum::IntoStaticStr, serde::Serialize, serde::Deserialize,
+)]
+#[strum(serialize_all = "lowercase")]
+pub(crate) enum GzipFunctions {
+    Compress,
+    Decompress,
+}
+
+/// The operating system byte `gzip.compress()` writes, meaning "unknown".
+const OS_UNKNOWN: u8 = 255;
+
+/// Creates the `gzip` module and allocates it on the heap.
+///
+/// Returns a HeapId pointing to the newly allocated module.
+///
+/// # Panics
+///
+/// Panics if the required strings have not been pre-interned during prepare phase.
+pub fn create_module(heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> Result<HeapId, ResourceError> {
+    let mut module = Module::new(StaticStrings::Gzip);
+
+    module.set_attr(
+        StaticStrings::Compress,
+        Value::ModuleFunction(ModuleFunctions::Gzip(GzipFunctions::Compress)),
+        heap,
+        interns,
+    );
+    module.set_attr(
+        StaticStrings::Decompress,
+        Value::ModuleFunction(ModuleFunctions::Gzip(GzipFunctions::Decompress)),
+        heap,
+        interns,
+    );
+    module.set_attr(
+        StaticStrings::BadGzipFile,
+        Value::Builtin(Builtins::ExcType(ExcType::BadGzipFile)),
+        heap,
+        interns,
+    );
+
+    heap.allocate(HeapData::Module(module))
+}
+
+/// Dispatches a call to a gzip module function.
+///
+/// # Errors
+/// Returns `TypeError` for bad arguments, `zlib.error` for a bad compression level or corrupt
+/// deflate data, `BadGzipFile` or `EOFError` for invalid or truncated gzip data, and `MemoryError`
+/// if decompressed data would exceed the memory limit.
+pub(super) fn call(
+    heap: &mut Heap<impl ResourceTracker>,
+    functions: GzipFunctions,
+    args: ArgValues,
+    interns: &Interns,
+) -> RunResult<AttrCallResult> {
+    let value = match functions {
+        GzipFunctions::Compress => compress(heap, args, interns)?,
+        GzipFunctions::Decompress => decompress(heap, args, interns)?,
+    };
+    Ok(AttrCallResult::Value(value))
+}
+
+/// Implements `gzip.compress(data, compresslevel=9, *, mtime=None)`.
+fn compress(heap: &mut Heap<impl ResourceTracker>, args: ArgValues, interns: &Interns) -> RunResult<Value> {
+    let params = bind_args(
+        "compress",
+        &["data", "compresslevel", "mtime"],
+        0,
+        2,
+        args,
+        heap,
+        interns,
+    )?;
+    defer_drop!(params, heap);
+    let data = bytes_arg(params[0].as_ref(), heap, interns)?;
+    let level = compression_level(opt_int_arg(params[1].as_ref(), 9, heap)?)?;
+    let mtime = match &params[2] {
+        None | Some(Value::None) => 0,
+        Some(value) => {
+            let mtime = match value {
+                #[expect(clippy::cast_possible_truncation)]
+                Value::Float(f) => f.trunc() as i64,
+                value => opt_int_arg(Some(value), 0, heap)?,
+            };
+            u32::try_from(mtime).map_err(|_| {
+                SimpleException::new_msg(ExcType::OverflowError, "mtime must be between 0 and 4294967295")
+            })?
+        }
+    };
+
+    let compressed = gzip_member(data, level, mtime, OS_UNKNOWN);
+    Ok(Value::Ref(heap.allocate(HeapData::Bytes(Bytes::new(compressed)))?))
+}
+
+/// Implements `gzip.decompress(data)`.
+///
+/// Decompresses each gzip member in turn, skipping null bytes padding the end of a member.
+fn decompress(heap: &mut Heap<impl ResourceTracker>, args: ArgValues, interns: &Interns) -> RunResult<Value> {
+    let params = bind_args("decompress", &["data"], 0, 1, args, heap, interns)?;
+    defer_drop!(params, heap);
+    let mut data = bytes_arg(params[0].as_ref(), heap, interns)?;
+
+    let tracker = heap.tracker();
+    let mut decompressed = Vec::new();
+    while !data.is_empty() {
+        let (member, used) = gunzip_member(data, decompressed.len(), tracker).map_err(gzip_error)?;
+        decompressed.extend(member);
+        let padding = data[used..].iter().take_while(|&&b| b == 0).count();
+        data = &data[used + padding..];
+    }
+    Ok(Value::Ref(heap.allocate(HeapData::Bytes(Bytes::new(decompressed)))?))
+}
+
+/// Converts an `InflateError` to the exception `gzip.decompress()` would raise.
+fn gzip_error(err: InflateError) -> RunError {
+    let msg = match err {
+        InflateError::BadHeader(magic) => format!("Not a gzipped file ({})", bytes_repr(&magic)),
+        InflateError::UnknownMethod => "Unknown compression method".to_owned(),
+        InflateError::ChecksumMismatch => "CRC check failed".to_owned(),
+        InflateError::LengthMismatch => "Incorrect length of data produced".to_owned(),
+        InflateError::Truncated => {
+            return SimpleException::new_msg(
+                ExcType::EOFError,
+                "Compressed file ended before the end-of-stream marker was reached",
+            )
+            .into();
+        }
+        err => return decompress_error(err),
+    };
+    SimpleException::new_msg(ExcType::BadGzipFile, msg).into()
+}
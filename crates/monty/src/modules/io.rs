@@ -0,0 +1,36 @@
+//! Implementation of the `io` module.
+//!
+//! Provides a minimal implementation of Python's `io` module with:
+//! - `StringIO`: An in-memory text stream
+//!
+//! There are no file objects in the sandbox, so the other stream classes aren't provided.
+
+use crate::{
+    builtins::Builtins,
+    heap::{Heap, HeapData, HeapId},
+    intern::{Interns, StaticStrings},
+    resource::{ResourceError, ResourceTracker},
+    types::{Module, Type},
+    value::Value,
+};
+
+/// Creates the `io` module and allocates it on the heap.
+///
+/// Returns a HeapId pointing to the newly allocated module.
+///
+/// # Panics
+///
+/// Panics if the required strings have not been pre-interned during prepare phase.
+pub fn create_module(heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> Result<HeapId, ResourceError> {
+    let mut module = Module::new(StaticStrings::Io);
+
+    // io.StringIO - the StringIO class (callable to create streams)
+    module.set_attr(
+        StaticStrings::StringIOClass,
+        Value::Builtin(Builtins::Type(Type::StringIO)),
+        heap,
+        interns,
+    );
+
+    heap.allocate(HeapData::Module(module))
+}
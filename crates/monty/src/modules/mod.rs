@@ -16,11 +16,20 @@ use crate::{
     types::AttrCallResult,
 };
 
+pub(crate) mod array;
 pub(crate) mod asyncio;
+pub(crate) mod csv;
+pub(crate) mod fractions;
+pub(crate) mod gzip;
+pub(crate) mod io;
 pub(crate) mod os;
 pub(crate) mod pathlib;
+pub(crate) mod secrets;
+pub(crate) mod statistics;
 pub(crate) mod sys;
+pub(crate) mod tempfile;
 pub(crate) mod typing;
+pub(crate) mod zlib;
 
 /// Built-in modules that can be imported.
 #[repr(u8)]
@@ -36,6 +45,24 @@ pub(crate) enum BuiltinModule {
     Pathlib,
     /// The `os` module providing operating system interface (only `getenv()` implemented).
     Os,
+    /// The `tempfile` module providing scratch files and directories from the host.
+    Tempfile,
+    /// The `io` module providing in-memory text streams (only `StringIO` implemented).
+    Io,
+    /// The `csv` module providing CSV readers and writers.
+    Csv,
+    /// The `zlib` module providing deflate compression and checksums.
+    Zlib,
+    /// The `gzip` module providing one-shot gzip compression.
+    Gzip,
+    /// The `secrets` module providing random tokens from the host's entropy policy.
+    Secrets,
+    /// The `statistics` module providing averages and measures of spread.
+    Statistics,
+    /// The `fractions` module providing exact rational numbers.
+    Fractions,
+    /// The `array` module providing compact typed numeric arrays.
+    Array,
 }
 
 impl BuiltinModule {
@@ -47,10 +74,39 @@ impl BuiltinModule {
             StaticStrings::Asyncio => Some(Self::Asyncio),
             StaticStrings::Pathlib => Some(Self::Pathlib),
             StaticStrings::Os => Some(Self::Os),
+            StaticStrings::Tempfile => Some(Self::Tempfile),
+            StaticStrings::Io => Some(Self::Io),
+            StaticStrings::Csv => Some(Self::Csv),
+            StaticStrings::Zlib => Some(Self::Zlib),
+            StaticStrings::Gzip => Some(Self::Gzip),
+            StaticStrings::Secrets => Some(Self::Secrets),
+            StaticStrings::Statistics => Some(Self::Statistics),
+            StaticStrings::Fractions => Some(Self::Fractions),
+            StaticStrings::Array => Some(Self::Array),
             _ => None,
         }
     }
 
+    /// The name the module is imported by.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Sys => "sys",
+            Self::Typing => "typing",
+            Self::Asyncio => "asyncio",
+            Self::Pathlib => "pathlib",
+            Self::Os => "os",
+            Self::Tempfile => "tempfile",
+            Self::Io => "io",
+            Self::Csv => "csv",
+            Self::Zlib => "zlib",
+            Self::Gzip => "gzip",
+            Self::Secrets => "secrets",
+            Self::Statistics => "statistics",
+            Self::Fractions => "fractions",
+            Self::Array => "array",
+        }
+    }
+
     /// Creates a new instance of this module on the heap.
     ///
     /// Returns a HeapId pointing to the newly allocated module.
@@ -65,6 +121,15 @@ impl BuiltinModule {
             Self::Asyncio => asyncio::create_module(heap, interns),
             Self::Pathlib => pathlib::create_module(heap, interns),
             Self::Os => os::create_module(heap, interns),
+            Self::Tempfile => tempfile::create_module(heap, interns),
+            Self::Io => io::create_module(heap, interns),
+            Self::Csv => csv::create_module(heap, interns),
+            Self::Zlib => zlib::create_module(heap, interns),
+            Self::Gzip => gzip::create_module(heap, interns),
+            Self::Secrets => secrets::create_module(heap, interns),
+            Self::Statistics => statistics::create_module(heap, interns),
+            Self::Fractions => fractions::create_module(heap, interns),
+            Self::Array => array::create_module(heap, interns),
         }
     }
 }
@@ -74,6 +139,12 @@ impl BuiltinModule {
 pub(crate) enum ModuleFunctions {
     Asyncio(asyncio::AsyncioFunctions),
     Os(os::OsFunctions),
+    Tempfile(tempfile::TempfileFunctions),
+    Csv(csv::CsvFunctions),
+    Zlib(zlib::ZlibFunctions),
+    Gzip(gzip::GzipFunctions),
+    Secrets(secrets::SecretsFunctions),
+    Statistics(statistics::StatisticsFunctions),
 }
 
 impl fmt::Display for ModuleFunctions {
@@ -81,6 +152,12 @@ impl fmt::Display for ModuleFunctions {
         match self {
             Self::Asyncio(func) => write!(f, "{func}"),
             Self::Os(func) => write!(f, "{func}"),
+            Self::Tempfile(func) => write!(f, "{func}"),
+            Self::Csv(func) => write!(f, "{func}"),
+            Self::Zlib(func) => write!(f, "{func}"),
+            Self::Gzip(func) => write!(f, "{func}"),
+            Self::Secrets(func) => write!(f, "{func}"),
+            Self::Statistics(func) => write!(f, "{func}"),
         }
     }
 }
@@ -90,10 +167,21 @@ impl ModuleFunctions {
     ///
     /// Returns `AttrCallResult` to support both immediate values and OS calls that
     /// require host involvement (e.g., `os.getenv()` needs the host to provide environment variables).
-    pub fn call(self, heap: &mut Heap<impl ResourceTracker>, args: ArgValues) -> RunResult<AttrCallResult> {
+    pub fn call(
+        self,
+        heap: &mut Heap<impl ResourceTracker>,
+        args: ArgValues,
+        interns: &Interns,
+    ) -> RunResult<AttrCallResult> {
         match self {
             Self::Asyncio(functions) => asyncio::call(heap, functions, args),
             Self::Os(functions) => os::call(heap, functions, args),
+            Self::Tempfile(functions) => tempfile::call(heap, functions, args, interns),
+            Self::Csv(functions) => csv::call(heap, functions, args, interns),
+            Self::Zlib(functions) => zlib::call(heap, functions, args, interns),
+            Self::Gzip(functions) => gzip::call(heap, functions, args, interns),
+            Self::Secrets(functions) => secrets::call(heap, functions, args, interns),
+            Self::Statistics(functions) => statistics::call(heap, functions, args, interns),
         }
     }
 
@@ -0,0 +1,210 @@
+//! Implementation of the `secrets` module.
+//!
+//! Provides a minimal implementation of Python's `secrets` module with:
+//! - `token_bytes(nbytes=None)`, `token_hex(nbytes=None)` and `token_urlsafe(nbytes=None)`:
+//!   Random tokens of `nbytes` bytes, 32 if not given
+//! - `choice(seq)`: A random element of a non-empty sequence
+//! - `randbelow(exclusive_upper_bound)`: A random int in `range(exclusive_upper_bound)`
+//!
+//! Randomness comes from the host's [`EntropyPolicy`](crate::EntropyPolicy). When it's denied,
+//! as it is by default, every function raises `NotImplementedError` and the denial is reported
+//! to audit hooks. `SystemRandom` and `compare_digest()` aren't provided.
+
+use crate::{
+    args::ArgValues,
+    audit::{AuditEvent, AuditEventKind, Capability},
+    defer_drop,
+    entropy::EntropyError,
+    exception_private::{ExcType, RunError, RunResult, SimpleException},
+    heap::{DropWithHeap, Heap, HeapData, HeapId},
+    intern::{Interns, StaticStrings},
+    json::base64_encode,
+    modules::ModuleFunctions,
+    resource::{ResourceError, ResourceTracker, check_repeat_size},
+    types::{AttrCallResult, Bytes, Module, PyTrait, str::allocate_string},
+    value::Value,
+};
+
+/// secrets module functions.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, strum::Display, strum::IntoStaticStr, serde::Serialize, serde::Deserialize,
+)]
+#[strum(serialize_all = "lowercase")]
+pub(crate) enum SecretsFunctions {
+    #[strum(serialize = "token_bytes")]
+    TokenBytes,
+    #[strum(serialize = "token_hex")]
+    TokenHex,
+    #[strum(serialize = "token_urlsafe")]
+    TokenUrlsafe,
+    Choice,
+    Randbelow,
+}
+
+/// Number of random bytes in a token when `nbytes` isn't given, as in CPython.
+const DEFAULT_ENTROPY: usize = 32;
+
+/// Creates the `secrets` module and allocates it on the heap.
+///
+/// Returns a HeapId pointing to the newly allocated module.
+///
+/// # Panics
+///
+/// Panics if the required strings have not been pre-interned during prepare phase.
+pub fn create_module(heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> Result<HeapId, ResourceError> {
+    let mut module = Module::new(StaticStrings::Secrets);
+
+    let functions = [
+        (StaticStrings::TokenBytes, SecretsFunctions::TokenBytes),
+        (StaticStrings::TokenHex, SecretsFunctions::TokenHex),
+        (StaticStrings::TokenUrlsafe, SecretsFunctions::TokenUrlsafe),
+        (StaticStrings::Choice, SecretsFunctions::Choice),
+        (StaticStrings::Randbelow, SecretsFunctions::Randbelow),
+    ];
+    for (name, function) in functions {
+        module.set_attr(
+            name,
+            Value::ModuleFunction(ModuleFunctions::Secrets(function)),
+            heap,
+            interns,
+        );
+    }
+
+    heap.allocate(HeapData::Module(module))
+}
+
+/// Dispatches a call to a secrets module function.
+///
+/// # Errors
+/// Returns `NotImplementedError` if the host's entropy policy denies randomness, `OSError` if
+/// the operating system's entropy source fails, and `TypeError`, `ValueError` or `IndexError`
+/// for bad arguments as CPython does.
+pub(super) fn call(
+    heap: &mut Heap<impl ResourceTracker>,
+    functions: SecretsFunctions,
+    args: ArgValues,
+    interns: &Interns,
+) -> RunResult<AttrCallResult> {
+    let value = match functions {
+        SecretsFunctions::TokenBytes | SecretsFunctions::TokenHex | SecretsFunctions::TokenUrlsafe => {
+            token(functions, heap, args, interns)?
+        }
+        SecretsFunctions::Choice => {
+            let seq = args.get_one_arg("choice", heap)?;
+            defer_drop!(seq, heap);
+            choice(seq, heap, interns)?
+        }
+        SecretsFunctions::Randbelow => {
+            let bound = args.get_one_arg("randbelow", heap)?;
+            defer_drop!(bound, heap);
+            let bound = int_arg(bound, heap)?;
+            let Ok(bound @ 1..) = u64::try_from(bound) else {
+                return Err(SimpleException::new_msg(ExcType::ValueError, "Upper bound must be positive.").into());
+            };
+            let n = heap
+                .entropy_mut()
+                .below(bound)
+                .map_err(|err| entropy_error(functions, err, heap))?;
+            Value::Int(i64::try_from(n).expect("n is below an i64 bound"))
+        }
+    };
+    Ok(AttrCallResult::Value(value))
+}
+
+/// Implements `token_bytes()`, `token_hex()` and `token_urlsafe()`, which all take
+/// `nbytes=None`.
+fn token(
+    function: SecretsFunctions,
+    heap: &mut Heap<impl ResourceTracker>,
+    args: ArgValues,
+    interns: &Interns,
+) -> RunResult<Value> {
+    let name: &'static str = function.into();
+    let (mut pos, kwargs) = args.into_parts();
+    let given = pos.len();
+    if given > 1 {
+        pos.drop_with_heap(heap);
+        kwargs.drop_with_heap(heap);
+        return Err(ExcType::type_error_too_many_positional(name, 0, 1, given, 0));
+    }
+    let positional = pos.next();
+    defer_drop!(positional, heap);
+    let keyword = kwargs.extract_one(name, "nbytes", heap, interns)?;
+    defer_drop!(keyword, heap);
+    if positional.is_some() && keyword.is_some() {
+        return Err(ExcType::type_error_duplicate_arg(name, "nbytes"));
+    }
+
+    let nbytes = match positional.as_ref().or(keyword.as_ref()) {
+        None | Some(Value::None) => DEFAULT_ENTROPY,
+        Some(value) => usize::try_from(int_arg(value, heap)?)
+            .map_err(|_| SimpleException::new_msg(ExcType::ValueError, "negative argument not allowed"))?,
+    };
+
+    // Hex doubles the length and base64 grows it by a third, so check the larger size up front.
+    check_repeat_size(2, nbytes, heap.tracker())?;
+    let mut buf = vec![0; nbytes];
+    heap.entropy_mut()
+        .fill(&mut buf)
+        .map_err(|err| entropy_error(function, err, heap))?;
+
+    match function {
+        SecretsFunctions::TokenBytes => Ok(Value::Ref(heap.allocate(HeapData::Bytes(Bytes::new(buf)))?)),
+        SecretsFunctions::TokenHex => {
+            let hex = buf.iter().map(|b| format!("{b:02x}")).collect();
+            allocate_string(hex, heap)
+        }
+        _ => {
+            let token = base64_encode(&buf)
+                .trim_end_matches('=')
+                .replace('+', "-")
+                .replace('/', "_");
+            allocate_string(token, heap)
+        }
+    }
+}
+
+/// Implements `secrets.choice(seq)`, indexing `seq` with a random index below its length.
+fn choice(seq: &Value, heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<Value> {
+    let Some(len) = seq.py_len(heap, interns) else {
+        return Err(ExcType::type_error(format!(
+            "object of type '{}' has no len()",
+            seq.py_type(heap)
+        )));
+    };
+    if len == 0 {
+        return Err(SimpleException::new_msg(ExcType::IndexError, "Cannot choose from an empty sequence").into());
+    }
+    let index = heap
+        .entropy_mut()
+        .below(len as u64)
+        .map_err(|err| entropy_error(SecretsFunctions::Choice, err, heap))?;
+    let index = Value::Int(i64::try_from(index).expect("index is below a container length"));
+    seq.py_getitem(&index, heap, interns)
+}
+
+/// Returns the value of an int argument, accepting bools as CPython does.
+fn int_arg(value: &Value, heap: &Heap<impl ResourceTracker>) -> RunResult<i64> {
+    match value {
+        Value::Bool(b) => Ok(i64::from(*b)),
+        value => value.as_int(heap),
+    }
+}
+
+/// Converts an `EntropyError` to the exception raised by `function`, reporting a denial
+/// to audit hooks.
+fn entropy_error(function: SecretsFunctions, err: EntropyError, heap: &Heap<impl ResourceTracker>) -> RunError {
+    match err {
+        EntropyError::Denied => {
+            let name = format!("secrets.{function}");
+            heap.tracker().audit(&AuditEvent::now(AuditEventKind::CapabilityDenied {
+                capability: Capability::Entropy,
+                name: &name,
+            }));
+            ExcType::not_implemented(format!("{name}() needs randomness, which the host has not enabled")).into()
+        }
+        EntropyError::Os(err) => {
+            SimpleException::new_msg(ExcType::OSError, format!("failed to read random bytes: {err}")).into()
+        }
+    }
+}
@@ -0,0 +1,483 @@
+//! Implementation of the `statistics` module.
+//!
+//! Provides a minimal implementation of Python's `statistics` module with:
+//! - `mean(data)`: Arithmetic mean
+//! - `median(data)`: Middle value, or the mean of the two middle values
+//! - `mode(data)`: Most common value, the first one seen if there's a tie
+//! - `variance(data)` and `stdev(data)`: Sample variance and standard deviation
+//! - `quantiles(data, *, n=4, method='exclusive')`: Cut points dividing data into intervals
+//! - The `StatisticsError` exception
+//!
+//! Like CPython, `mean()`, `variance()` and `stdev()` work on the exact values of ints and
+//! floats, only rounding the result, so `mean([0.1, 0.2, 0.3]) == 0.2`. Results are ints when
+//! every value is an int and the result is whole. `Fraction` and `Decimal` data aren't supported.
+
+use num_bigint::BigInt;
+use num_integer::{Integer, Roots};
+use num_traits::Zero;
+
+use crate::{
+    args::ArgValues,
+    builtins::Builtins,
+    defer_drop,
+    exception_private::{ExcType, RunError, RunResult, SimpleException},
+    heap::{DropWithHeap, Heap, HeapData, HeapGuard, HeapId},
+    intern::{Interns, StaticStrings},
+    modules::{
+        ModuleFunctions,
+        zlib::{bind_args, opt_int_arg},
+    },
+    resource::{DepthGuard, ResourceError, ResourceTracker},
+    types::{
+        AttrCallResult, Dict, List, LongInt, Module, MontyIter, PyTrait, list::sort_indices, long_int::ratio_to_f64,
+    },
+    value::Value,
+};
+
+/// statistics module functions.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, strum::Display, strum::IntoStaticStr, serde::Serialize, serde::Deserialize,
+)]
+#[strum(serialize_all = "lowercase")]
+pub(crate) enum StatisticsFunctions {
+    Mean,
+    Median,
+    Mode,
+    Variance,
+    Stdev,
+    Quantiles,
+}
+
+/// Bits of precision `stdev()` computes its integer square root to, `2 * 53 + 3` as in CPython.
+const SQRT_BIT_WIDTH: i64 = 109;
+
+/// Creates the `statistics` module and allocates it on the heap.
+///
+/// Returns a HeapId pointing to the newly allocated module.
+///
+/// # Panics
+///
+/// Panics if the required strings have not been pre-interned during prepare phase.
+pub fn create_module(heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> Result<HeapId, ResourceError> {
+    let mut module = Module::new(StaticStrings::Statistics);
+
+    let functions = [
+        (StaticStrings::Mean, StatisticsFunctions::Mean),
+        (StaticStrings::Median, StatisticsFunctions::Median),
+        (StaticStrings::Mode, StatisticsFunctions::Mode),
+        (StaticStrings::Variance, StatisticsFunctions::Variance),
+        (StaticStrings::Stdev, StatisticsFunctions::Stdev),
+        (StaticStrings::Quantiles, StatisticsFunctions::Quantiles),
+    ];
+    for (name, function) in functions {
+        module.set_attr(
+            name,
+            Value::ModuleFunction(ModuleFunctions::Statistics(function)),
+            heap,
+            interns,
+        );
+    }
+
+    module.set_attr(
+        StaticStrings::StatisticsError,
+        Value::Builtin(Builtins::ExcType(ExcType::StatisticsError)),
+        heap,
+        interns,
+    );
+
+    heap.allocate(HeapData::Module(module))
+}
+
+/// Dispatches a call to a statistics module function.
+///
+/// # Errors
+/// Returns `StatisticsError` for too little data, `TypeError` for values which aren't numbers
+/// (or can't be compared or counted), and `OverflowError` if a result is too large for a float.
+pub(super) fn call(
+    heap: &mut Heap<impl ResourceTracker>,
+    functions: StatisticsFunctions,
+    args: ArgValues,
+    interns: &Interns,
+) -> RunResult<AttrCallResult> {
+    if functions == StatisticsFunctions::Quantiles {
+        return Ok(AttrCallResult::Value(quantiles(heap, args, interns)?));
+    }
+
+    let name: &'static str = functions.into();
+    let data = args.get_one_arg(name, heap)?;
+    let data: Vec<Value> = MontyIter::new(data, heap, interns)?.collect(heap, interns)?;
+    defer_drop!(data, heap);
+    let value = match functions {
+        StatisticsFunctions::Mean => mean(data, heap)?,
+        StatisticsFunctions::Median => median(data, heap, interns)?,
+        StatisticsFunctions::Mode => mode(data, heap, interns)?,
+        StatisticsFunctions::Variance => variance(data, heap)?,
+        StatisticsFunctions::Stdev => stdev(data, heap)?,
+        StatisticsFunctions::Quantiles => unreachable!("quantiles is handled above"),
+    };
+    Ok(AttrCallResult::Value(value))
+}
+
+/// Implements `statistics.mean(data)`.
+#[expect(clippy::cast_precision_loss)]
+fn mean(data: &[Value], heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
+    let numbers = Numbers::new(data, heap)?;
+    if numbers.count == 0 {
+        return Err(statistics_error("mean requires at least one data point"));
+    }
+    if let Some(total) = numbers.non_finite {
+        return Ok(Value::Float(total / numbers.count as f64));
+    }
+
+    let (numerators, shift) = numbers.scaled();
+    let total: BigInt = numerators.iter().sum();
+    numbers.convert(total, BigInt::from(numbers.count) << shift, heap)
+}
+
+/// Implements `statistics.variance(data)`, the sample variance.
+#[expect(clippy::cast_precision_loss)]
+fn variance(data: &[Value], heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
+    let numbers = Numbers::new(data, heap)?;
+    if numbers.count < 2 {
+        return Err(statistics_error("variance requires at least two data points"));
+    }
+    if let Some(total) = numbers.non_finite {
+        return Ok(Value::Float(total / (numbers.count - 1) as f64));
+    }
+
+    let (num, den) = numbers.variance();
+    numbers.convert(num, den, heap)
+}
+
+/// Implements `statistics.stdev(data)`, the square root of the sample variance.
+///
+/// Always returns a float, correctly rounded from the exact variance.
+#[expect(clippy::cast_precision_loss)]
+fn stdev(data: &[Value], heap: &Heap<impl ResourceTracker>) -> RunResult<Value> {
+    let numbers = Numbers::new(data, heap)?;
+    if numbers.count < 2 {
+        return Err(statistics_error("stdev requires at least two data points"));
+    }
+    if let Some(total) = numbers.non_finite {
+        return Ok(Value::Float((total / (numbers.count - 1) as f64).sqrt()));
+    }
+
+    let (num, den) = numbers.variance();
+    sqrt_of_ratio(&num, &den)
+        .map(Value::Float)
+        .ok_or_else(float_overflow_error)
+}
+
+/// Implements `statistics.median(data)`.
+///
+/// Data of any comparable type is sorted; for an even number of values the two middle
+/// values are combined with `(low + high) / 2`.
+fn median(data: &[Value], heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<Value> {
+    if data.is_empty() {
+        return Err(statistics_error("no median for empty data"));
+    }
+    let order = sort_indices(data, false, heap, interns)?;
+    let mid = data.len() / 2;
+    let high = &data[order[mid]];
+    if data.len() % 2 == 1 {
+        return Ok(high.clone_with_heap(heap));
+    }
+
+    let low = &data[order[mid - 1]];
+    let sum = add(low, high, heap, interns)?;
+    defer_drop!(sum, heap);
+    div(sum, &Value::Int(2), heap, interns)
+}
+
+/// Implements `statistics.mode(data)`, returning the first of the most common values.
+///
+/// Values are counted in a dict, so they must be hashable.
+fn mode(data: &[Value], heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<Value> {
+    let mut counts = HeapGuard::new(Dict::new(), heap);
+    let (counts, heap) = counts.as_parts_mut();
+    for value in data {
+        let count = match counts.get(value, heap, interns)? {
+            Some(Value::Int(count)) => count + 1,
+            _ => 1,
+        };
+        let key = value.clone_with_heap(heap);
+        let old = counts.set(key, Value::Int(count), heap, interns)?;
+        old.drop_with_heap(heap);
+    }
+
+    let mut most_common: Option<(&Value, i64)> = None;
+    for (value, count) in counts.items() {
+        let Value::Int(count) = *count else {
+            unreachable!("mode only stores int counts");
+        };
+        if most_common.is_none_or(|(_, best)| count > best) {
+            most_common = Some((value, count));
+        }
+    }
+    match most_common {
+        Some((value, _)) => Ok(value.clone_with_heap(heap)),
+        None => Err(statistics_error("no mode for empty data")),
+    }
+}
+
+/// Implements `statistics.quantiles(data, *, n=4, method='exclusive')`.
+///
+/// Cut points are interpolated between the sorted data with `(a * (n - delta) + b * delta) / n`,
+/// so they're floats for int data, and a single value is repeated `n - 1` times.
+fn quantiles(heap: &mut Heap<impl ResourceTracker>, args: ArgValues, interns: &Interns) -> RunResult<Value> {
+    let params = bind_args("quantiles", &["data", "n", "method"], 0, 1, args, heap, interns)?;
+    defer_drop!(params, heap);
+    let n = opt_int_arg(params[1].as_ref(), 4, heap)?;
+    if n < 1 {
+        return Err(statistics_error("n must be at least 1"));
+    }
+    let data = params[0].as_ref().expect("bind_args checks the data is given");
+    let data = data.clone_with_heap(heap);
+    let data: Vec<Value> = MontyIter::new(data, heap, interns)?.collect(heap, interns)?;
+    defer_drop!(data, heap);
+    let order = sort_indices(data, false, heap, interns)?;
+
+    let cuts = usize::try_from(n - 1).expect("n is positive");
+    let mut result: Vec<Value> = Vec::with_capacity(cuts);
+    match data.len() {
+        0 => return Err(statistics_error("must have at least one data point")),
+        1 => {
+            for _ in 0..cuts {
+                result.push(data[0].clone_with_heap(heap));
+            }
+            return Ok(Value::Ref(heap.allocate(HeapData::List(List::new(result)))?));
+        }
+        _ => {}
+    }
+
+    let inclusive = match &params[2] {
+        None => false,
+        Some(method) => {
+            let name = method.as_either_str(heap);
+            match name.as_ref().map(|name| name.as_str(interns)) {
+                Some("exclusive") => false,
+                Some("inclusive") => true,
+                _ => {
+                    let repr = method.py_repr(heap, &mut DepthGuard::default(), interns);
+                    let msg = format!("Unknown method: {repr}");
+                    return Err(SimpleException::new_msg(ExcType::ValueError, msg).into());
+                }
+            }
+        }
+    };
+
+    let len = i128::try_from(data.len()).expect("length fits in i128");
+    let n = i128::from(n);
+    let m = if inclusive { len - 1 } else { len + 1 };
+    let mut result = HeapGuard::new(result, heap);
+    {
+        let (result, heap) = result.as_parts_mut();
+        for i in 1..n {
+            // Exclusive cut points outside the data are extrapolated from the first or last interval
+            let j = if inclusive {
+                i * m / n
+            } else {
+                (i * m / n).clamp(1, len - 1) - 1
+            };
+            let delta = i * m - (j + i128::from(!inclusive)) * n;
+            let j = usize::try_from(j).expect("index is within the data");
+            let low = &data[order[j]];
+            let high = &data[order[j + 1]];
+            result.push(interpolate(low, high, n - delta, delta, n, heap, interns)?);
+        }
+    }
+    let result = result.into_inner();
+    Ok(Value::Ref(heap.allocate(HeapData::List(List::new(result)))?))
+}
+
+/// Numeric data as exact ratios, like the fractions CPython's `statistics` module uses.
+struct Numbers {
+    /// Each finite value as `numerator / 2**shift`.
+    ratios: Vec<(BigInt, u64)>,
+    /// Number of values, including infinities and NaNs.
+    count: usize,
+    /// Whether any value is a float, making every result a float.
+    any_float: bool,
+    /// Sum of the infinities and NaNs, which have no exact ratio and make the result one of them.
+    non_finite: Option<f64>,
+}
+
+impl Numbers {
+    /// Converts ints, bools and floats to exact ratios.
+    ///
+    /// # Errors
+    /// Returns `TypeError` for any other value, as CPython does.
+    fn new(data: &[Value], heap: &Heap<impl ResourceTracker>) -> RunResult<Self> {
+        let mut numbers = Self {
+            ratios: Vec::with_capacity(data.len()),
+            count: data.len(),
+            any_float: false,
+            non_finite: None,
+        };
+        for value in data {
+            let ratio = match value {
+                Value::Int(i) => (BigInt::from(*i), 0),
+                Value::Bool(b) => (BigInt::from(u8::from(*b)), 0),
+                Value::Float(f) => {
+                    numbers.any_float = true;
+                    if !f.is_finite() {
+                        numbers.non_finite = Some(numbers.non_finite.unwrap_or(0.0) + f);
+                        continue;
+                    }
+                    float_ratio(*f)
+                }
+                Value::Ref(id) => match heap.get(*id) {
+                    HeapData::LongInt(li) => (li.inner().clone(), 0),
+                    _ => return Err(not_a_number(value, heap)),
+                },
+                _ => return Err(not_a_number(value, heap)),
+            };
+            numbers.ratios.push(ratio);
+        }
+        Ok(numbers)
+    }
+
+    /// Returns the numerators of every value over the common denominator `2**shift`.
+    fn scaled(&self) -> (Vec<BigInt>, u64) {
+        let shift = self.ratios.iter().map(|(_, shift)| *shift).max().unwrap_or(0);
+        let numerators = self.ratios.iter().map(|(num, s)| num << (shift - s)).collect();
+        (numerators, shift)
+    }
+
+    /// Returns the exact sample variance as a numerator and denominator.
+    ///
+    /// With `n` values summing to `sx` whose squares sum to `sxx`, this is
+    /// `(n * sxx - sx**2) / (n * (n - 1))`, a formula only accurate with exact arithmetic.
+    fn variance(&self) -> (BigInt, BigInt) {
+        let (numerators, shift) = self.scaled();
+        let sx: BigInt = numerators.iter().sum();
+        let sxx: BigInt = numerators.iter().map(|num| num * num).sum();
+        let n = BigInt::from(self.count);
+        let num = &n * sxx - &sx * &sx;
+        let den = (n * BigInt::from(self.count - 1)) << (2 * shift);
+        (num, den)
+    }
+
+    /// Converts the result `num / den` to an int if every value was an int and the result is
+    /// whole, and to the nearest float otherwise.
+    fn convert(&self, num: BigInt, den: BigInt, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
+        if !self.any_float && num.is_multiple_of(&den) {
+            return Ok(LongInt::new(num / den).into_value(heap)?);
+        }
+        ratio_to_f64(&num, &den)
+            .map(Value::Float)
+            .ok_or_else(float_overflow_error)
+    }
+}
+
+/// Returns the exact value of a finite float as `numerator / 2**shift`.
+fn float_ratio(value: f64) -> (BigInt, u64) {
+    if value == 0.0 {
+        return (BigInt::zero(), 0);
+    }
+    let bits = value.to_bits();
+    let biased_exponent = (bits >> 52) & 0x7ff;
+    let fraction = bits & ((1 << 52) - 1);
+    let (mantissa, exponent) = if biased_exponent == 0 {
+        (fraction, -1074)
+    } else {
+        (
+            fraction | (1 << 52),
+            i64::try_from(biased_exponent).expect("exponent is 11 bits") - 1075,
+        )
+    };
+    let mut numerator = BigInt::from(mantissa);
+    if value.is_sign_negative() {
+        numerator = -numerator;
+    }
+    if exponent >= 0 {
+        return (numerator << exponent.unsigned_abs(), 0);
+    }
+    // Drop trailing zero bits so values like 0.5 keep small denominators
+    let zeros = u64::from(mantissa.trailing_zeros()).min(exponent.unsigned_abs());
+    (numerator >> zeros, exponent.unsigned_abs() - zeros)
+}
+
+/// Returns the square root of `num / den` correctly rounded to a float, or `None` if it's too
+/// large for one.
+///
+/// The integer square root is taken with enough extra bits, rounding to odd, that converting it
+/// to a float rounds correctly, as CPython's `statistics` module does.
+fn sqrt_of_ratio(num: &BigInt, den: &BigInt) -> Option<f64> {
+    /// Square root of `n / m` rounded to an integer, rounding inexact results to odd.
+    fn isqrt_round_to_odd(n: &BigInt, m: &BigInt) -> BigInt {
+        let root = (n / m).sqrt();
+        if root.is_even() && &root * &root * m != *n {
+            root + 1
+        } else {
+            root
+        }
+    }
+
+    #[expect(clippy::cast_possible_wrap)]
+    let q = (num.bits() as i64 - den.bits() as i64 - SQRT_BIT_WIDTH).div_euclid(2);
+    let shift = q.unsigned_abs();
+    if q >= 0 {
+        let root = isqrt_round_to_odd(num, &(den << (2 * shift)));
+        ratio_to_f64(&(root << shift), &BigInt::from(1))
+    } else {
+        let root = isqrt_round_to_odd(&(num << (2 * shift)), den);
+        ratio_to_f64(&root, &(BigInt::from(1) << shift))
+    }
+}
+
+/// Returns `(low * low_weight + high * high_weight) / n`.
+fn interpolate(
+    low: &Value,
+    high: &Value,
+    low_weight: i128,
+    high_weight: i128,
+    n: i128,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<Value> {
+    let weight = |w: i128| Value::Int(i64::try_from(w).expect("weights are within a few multiples of n"));
+    let low = mul(low, &weight(low_weight), heap, interns)?;
+    defer_drop!(low, heap);
+    let high = mul(high, &weight(high_weight), heap, interns)?;
+    defer_drop!(high, heap);
+    let sum = add(low, high, heap, interns)?;
+    defer_drop!(sum, heap);
+    div(sum, &weight(n), heap, interns)
+}
+
+/// Returns `lhs + rhs`, or the `TypeError` Python raises if they can't be added.
+fn add(lhs: &Value, rhs: &Value, heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<Value> {
+    lhs.py_add(rhs, heap, interns)?
+        .ok_or_else(|| ExcType::binary_type_error("+", lhs.py_type(heap), rhs.py_type(heap)))
+}
+
+/// Returns `lhs * rhs`, or the `TypeError` Python raises if they can't be multiplied.
+fn mul(lhs: &Value, rhs: &Value, heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<Value> {
+    lhs.py_mult(rhs, heap, interns)?
+        .ok_or_else(|| ExcType::binary_type_error("*", lhs.py_type(heap), rhs.py_type(heap)))
+}
+
+/// Returns `lhs / rhs`, or the `TypeError` Python raises if they can't be divided.
+fn div(lhs: &Value, rhs: &Value, heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<Value> {
+    lhs.py_div(rhs, heap, interns)?
+        .ok_or_else(|| ExcType::binary_type_error("/", lhs.py_type(heap), rhs.py_type(heap)))
+}
+
+/// Creates a `StatisticsError` with the given message.
+fn statistics_error(msg: &str) -> RunError {
+    SimpleException::new_msg(ExcType::StatisticsError, msg).into()
+}
+
+/// Returns the `TypeError` CPython raises for data which isn't a number.
+fn not_a_number(value: &Value, heap: &Heap<impl ResourceTracker>) -> RunError {
+    ExcType::type_error(format!(
+        "can't convert type '{}' to numerator/denominator",
+        value.py_type(heap)
+    ))
+}
+
+/// Returns the `OverflowError` CPython raises when a result is too large for a float.
+fn float_overflow_error() -> RunError {
+    SimpleException::new_msg(ExcType::OverflowError, "integer division result too large for a float").into()
+}
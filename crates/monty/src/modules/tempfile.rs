@@ -0,0 +1,150 @@
+//! Implementation of the `tempfile` module.
+//!
+//! Provides minimal shims of Python's `tempfile` module with:
+//! - `TemporaryDirectory(suffix=None, prefix=None, dir=None)`: Create a scratch directory
+//! - `NamedTemporaryFile(*, suffix=None, prefix=None, dir=None)`: Create an empty scratch file
+//!
+//! Both yield `OsFunction::ScratchPath`, so the host decides where scratch space lives - a real
+//! temporary directory or a virtual filesystem. Context managers aren't supported, so the results
+//! are namedtuples whose `name` is the new path, for use with `pathlib`. Scratch space belongs to
+//! the host, which decides when to remove it; there is no `cleanup()`.
+
+use crate::{
+    args::{ArgValues, KwargsValues},
+    defer_drop, defer_drop_mut,
+    exception_private::{ExcType, RunResult},
+    heap::{DropWithHeap, Heap, HeapData, HeapGuard, HeapId},
+    intern::{Interns, StaticStrings},
+    modules::ModuleFunctions,
+    os::OsFunction,
+    resource::{ResourceError, ResourceTracker},
+    types::{AttrCallResult, Module, PyTrait},
+    value::Value,
+};
+
+/// tempfile module functions.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, strum::Display, strum::IntoStaticStr, serde::Serialize, serde::Deserialize,
+)]
+pub(crate) enum TempfileFunctions {
+    TemporaryDirectory,
+    NamedTemporaryFile,
+}
+
+/// Parameters forwarded to the host, in `TemporaryDirectory`'s positional order.
+const PARAMS: [&str; 3] = ["suffix", "prefix", "dir"];
+
+/// Creates the `tempfile` module and allocates it on the heap.
+///
+/// Returns a HeapId pointing to the newly allocated module.
+///
+/// # Panics
+///
+/// Panics if the required strings have not been pre-interned during prepare phase.
+pub fn create_module(heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> Result<HeapId, ResourceError> {
+    let mut module = Module::new(StaticStrings::Tempfile);
+
+    module.set_attr(
+        StaticStrings::TemporaryDirectory,
+        Value::ModuleFunction(ModuleFunctions::Tempfile(TempfileFunctions::TemporaryDirectory)),
+        heap,
+        interns,
+    );
+    module.set_attr(
+        StaticStrings::NamedTemporaryFile,
+        Value::ModuleFunction(ModuleFunctions::Tempfile(TempfileFunctions::NamedTemporaryFile)),
+        heap,
+        interns,
+    );
+
+    heap.allocate(HeapData::Module(module))
+}
+
+/// Dispatches a call to a tempfile module function.
+///
+/// Both functions validate their arguments and return `AttrCallResult::OsCall` with
+/// `OsFunction::ScratchPath`, called with `(is_dir, suffix, prefix, dir)`.
+///
+/// # Errors
+/// Returns `TypeError` for unexpected or duplicated arguments, and for a `suffix` or `prefix`
+/// which isn't a str or `None`, or a `dir` which isn't a str, `Path` or `None`.
+pub(super) fn call(
+    heap: &mut Heap<impl ResourceTracker>,
+    functions: TempfileFunctions,
+    args: ArgValues,
+    interns: &Interns,
+) -> RunResult<AttrCallResult> {
+    let name: &'static str = functions.into();
+    // NamedTemporaryFile's leading parameters are `mode`, `buffering` etc. which don't apply here
+    let max_positional = match functions {
+        TempfileFunctions::TemporaryDirectory => PARAMS.len(),
+        TempfileFunctions::NamedTemporaryFile => 0,
+    };
+
+    let (pos, kwargs) = args.into_parts();
+    let given = pos.len();
+    if given > max_positional {
+        pos.drop_with_heap(heap);
+        kwargs.drop_with_heap(heap);
+        return Err(ExcType::type_error_too_many_positional(
+            name,
+            0,
+            max_positional,
+            given,
+            0,
+        ));
+    }
+
+    let mut params = pos.collect::<Vec<_>>();
+    params.resize_with(PARAMS.len(), || Value::None);
+    let mut params_guard = HeapGuard::new(params, heap);
+    {
+        let (params, heap) = params_guard.as_parts_mut();
+        let kwargs = kwargs.into_iter();
+        defer_drop_mut!(kwargs, heap);
+
+        for (key, value) in kwargs {
+            defer_drop!(key, heap);
+            let mut value = HeapGuard::new(value, heap);
+
+            let Some(keyword_name) = key.as_either_str(value.heap()) else {
+                return Err(ExcType::type_error_kwargs_nonstring_key());
+            };
+            let key_str = keyword_name.as_str(interns);
+            let Some(index) = PARAMS.iter().position(|param| *param == key_str) else {
+                return Err(ExcType::type_error_unexpected_keyword(name, key_str));
+            };
+            if index < given {
+                return Err(ExcType::type_error_duplicate_arg(name, key_str));
+            }
+            let old = std::mem::replace(&mut params[index], value.into_inner());
+            old.drop_with_heap(heap);
+        }
+
+        for (param, value) in PARAMS.iter().zip(params.iter()) {
+            let is_path =
+                *param == "dir" && matches!(value, Value::Ref(id) if matches!(heap.get(*id), HeapData::Path(_)));
+            if !matches!(value, Value::None) && !value.is_str(heap) && !is_path {
+                let expected = if *param == "dir" {
+                    "str, Path or None"
+                } else {
+                    "str or None"
+                };
+                return Err(ExcType::type_error(format!(
+                    "{name}() argument '{param}' must be {expected}, not {}",
+                    value.py_type(heap)
+                )));
+            }
+        }
+    }
+
+    let mut args = vec![Value::Bool(functions == TempfileFunctions::TemporaryDirectory)];
+    args.extend(params_guard.into_inner());
+    Ok(AttrCallResult::OsCall(
+        OsFunction::ScratchPath,
+        ArgValues::ArgsKargs {
+            args,
+            kwargs: KwargsValues::Empty,
+        },
+    ))
+}
@@ -0,0 +1,540 @@
+//! Implementation of the `zlib` module.
+//!
+//! Provides a minimal implementation of Python's `zlib` module with:
+//! - `compress(data, /, level=-1, wbits=15)`: Compress data in one shot
+//! - `decompress(data, /, wbits=15, bufsize=16384)`: Decompress data in one shot
+//! - `crc32(data, value=0, /)` and `adler32(data, value=1, /)`: Running checksums
+//! - The `MAX_WBITS`, `DEFLATED` and `Z_*_COMPRESSION` constants and the `error` exception
+//!
+//! Compression uses flate2 rather than zlib itself, so compressed data isn't byte-for-byte
+//! identical to CPython's, but each can decompress the other's. The compression window is always
+//! 32 KiB whatever `wbits` says, and messages for corrupt deflate data are less specific than
+//! zlib's. `compressobj()` and `decompressobj()` aren't provided.
+//!
+//! Decompressed output is checked against the resource limits as it's produced, so a small
+//! "decompression bomb" raises `MemoryError` instead of exhausting the host's memory.
+
+use std::io::Write;
+
+use flate2::{
+    Compression, Crc, Decompress, FlushDecompress, Status,
+    write::{DeflateEncoder, ZlibEncoder},
+};
+
+use crate::{
+    args::ArgValues,
+    builtins::Builtins,
+    defer_drop, defer_drop_mut,
+    exception_private::{ExcType, RunError, RunResult, SimpleException},
+    heap::{DropWithHeap, Heap, HeapData, HeapGuard, HeapId},
+    intern::{Interns, StaticStrings},
+    modules::ModuleFunctions,
+    resource::{ResourceError, ResourceTracker, check_decompressed_size},
+    types::{AttrCallResult, Bytes, Module, PyTrait},
+    value::Value,
+};
+
+/// zlib module functions.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, strum::Display, strum::IntoStaticStr, serde::Serialize, serde::Deserialize,
+)]
+#[strum(serialize_all = "lowercase")]
+pub(crate) enum ZlibFunctions {
+    Compress,
+    Decompress,
+    Crc32,
+    Adler32,
+}
+
+/// Largest window size, and the default `wbits`.
+const MAX_WBITS: i64 = 15;
+
+/// The deflate compression method, the only one zlib supports.
+const DEFLATED: u8 = 8;
+
+/// Size of the buffer each step of inflation writes to, so resource limits are checked
+/// at least this often.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// The two bytes every gzip member starts with.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// gzip header flags.
+const FHCRC: u8 = 0x02;
+const FEXTRA: u8 = 0x04;
+const FNAME: u8 = 0x08;
+const FCOMMENT: u8 = 0x10;
+
+/// Creates the `zlib` module and allocates it on the heap.
+///
+/// Returns a HeapId pointing to the newly allocated module.
+///
+/// # Panics
+///
+/// Panics if the required strings have not been pre-interned during prepare phase.
+pub fn create_module(heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> Result<HeapId, ResourceError> {
+    let mut module = Module::new(StaticStrings::Zlib);
+
+    let functions = [
+        (StaticStrings::Compress, ZlibFunctions::Compress),
+        (StaticStrings::Decompress, ZlibFunctions::Decompress),
+        (StaticStrings::Crc32, ZlibFunctions::Crc32),
+        (StaticStrings::Adler32, ZlibFunctions::Adler32),
+    ];
+    for (name, function) in functions {
+        module.set_attr(
+            name,
+            Value::ModuleFunction(ModuleFunctions::Zlib(function)),
+            heap,
+            interns,
+        );
+    }
+
+    let constants = [
+        (StaticStrings::MaxWbits, MAX_WBITS),
+        (StaticStrings::Deflated, i64::from(DEFLATED)),
+        (StaticStrings::ZBestCompression, 9),
+        (StaticStrings::ZBestSpeed, 1),
+        (StaticStrings::ZDefaultCompression, -1),
+        (StaticStrings::ZNoCompression, 0),
+    ];
+    for (name, value) in constants {
+        module.set_attr(name, Value::Int(value), heap, interns);
+    }
+
+    module.set_attr(
+        StaticStrings::ErrorLower,
+        Value::Builtin(Builtins::ExcType(ExcType::ZlibError)),
+        heap,
+        interns,
+    );
+
+    heap.allocate(HeapData::Module(module))
+}
+
+/// Dispatches a call to a zlib module function.
+///
+/// # Errors
+/// Returns `TypeError` for bad arguments, `zlib.error` for invalid parameters or corrupt data,
+/// and `MemoryError` if decompressed data would exceed the memory limit.
+pub(super) fn call(
+    heap: &mut Heap<impl ResourceTracker>,
+    functions: ZlibFunctions,
+    args: ArgValues,
+    interns: &Interns,
+) -> RunResult<AttrCallResult> {
+    let value = match functions {
+        ZlibFunctions::Compress => compress(heap, args, interns)?,
+        ZlibFunctions::Decompress => decompress(heap, args, interns)?,
+        ZlibFunctions::Crc32 | ZlibFunctions::Adler32 => checksum(functions, heap, args, interns)?,
+    };
+    Ok(AttrCallResult::Value(value))
+}
+
+/// Implements `zlib.compress(data, /, level=-1, wbits=15)`.
+///
+/// `wbits` of 9 to 15 produces a zlib stream, -9 to -15 raw deflate data and 25 to 31 a gzip member.
+fn compress(heap: &mut Heap<impl ResourceTracker>, args: ArgValues, interns: &Interns) -> RunResult<Value> {
+    let params = bind_args("compress", &["data", "level", "wbits"], 1, 3, args, heap, interns)?;
+    defer_drop!(params, heap);
+    let data = bytes_arg(params[0].as_ref(), heap, interns)?;
+    let level = compression_level(opt_int_arg(params[1].as_ref(), -1, heap)?)?;
+    let wbits = opt_int_arg(params[2].as_ref(), MAX_WBITS, heap)?;
+
+    let compressed = match wbits {
+        8..=15 => deflate(data, level, true),
+        -15..=-9 => deflate(data, level, false),
+        25..=31 => gzip_member(data, level, 0, 3),
+        _ => return Err(zlib_error("Bad compression level")),
+    };
+    Ok(Value::Ref(heap.allocate(HeapData::Bytes(Bytes::new(compressed)))?))
+}
+
+/// Implements `zlib.decompress(data, /, wbits=15, bufsize=16384)`.
+///
+/// `wbits` of 8 to 15 (or 0 to use the stream's own window size) expects a zlib stream, -8 to -15
+/// raw deflate data, 24 to 31 a gzip member and 40 to 47 either of the first or the last.
+/// Data after the end of the stream is ignored. `bufsize` is only validated, since the output
+/// buffer always grows as needed.
+fn decompress(heap: &mut Heap<impl ResourceTracker>, args: ArgValues, interns: &Interns) -> RunResult<Value> {
+    let params = bind_args("decompress", &["data", "wbits", "bufsize"], 1, 3, args, heap, interns)?;
+    defer_drop!(params, heap);
+    let data = bytes_arg(params[0].as_ref(), heap, interns)?;
+    let wbits = opt_int_arg(params[1].as_ref(), MAX_WBITS, heap)?;
+    if opt_int_arg(params[2].as_ref(), 0, heap)? < 0 {
+        return Err(SimpleException::new_msg(ExcType::ValueError, "bufsize must be non-negative").into());
+    }
+
+    let tracker = heap.tracker();
+    let result = match wbits {
+        0 => unzlib(data, MAX_WBITS, tracker),
+        8..=15 => unzlib(data, wbits, tracker),
+        -15..=-8 => inflate(data, 0, tracker).map(|(decompressed, _)| decompressed),
+        16 | 24..=31 => gunzip_member(data, 0, tracker).map(|(decompressed, _)| decompressed),
+        32 | 40..=47 if data.starts_with(&GZIP_MAGIC) => {
+            gunzip_member(data, 0, tracker).map(|(decompressed, _)| decompressed)
+        }
+        32 => unzlib(data, MAX_WBITS, tracker),
+        40..=47 => unzlib(data, wbits - 32, tracker),
+        _ => {
+            return Err(zlib_error(
+                "Error -2 while preparing to decompress data: inconsistent stream state",
+            ));
+        }
+    };
+    let decompressed = result.map_err(decompress_error)?;
+    Ok(Value::Ref(heap.allocate(HeapData::Bytes(Bytes::new(decompressed)))?))
+}
+
+/// Implements `zlib.crc32(data, value=0, /)` and `zlib.adler32(data, value=1, /)`.
+///
+/// Like CPython, a starting `value` outside the range of an unsigned 32-bit int is truncated.
+fn checksum(
+    functions: ZlibFunctions,
+    heap: &mut Heap<impl ResourceTracker>,
+    args: ArgValues,
+    interns: &Interns,
+) -> RunResult<Value> {
+    let name: &'static str = functions.into();
+    let params = bind_args(name, &["data", "value"], 2, 2, args, heap, interns)?;
+    defer_drop!(params, heap);
+    let data = bytes_arg(params[0].as_ref(), heap, interns)?;
+    let default = if functions == ZlibFunctions::Crc32 { 0 } else { 1 };
+    #[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let value = opt_int_arg(params[1].as_ref(), default, heap)? as u32;
+
+    let checksum = if functions == ZlibFunctions::Crc32 {
+        let mut hasher = crc32fast::Hasher::new_with_initial(value);
+        hasher.update(data);
+        hasher.finalize()
+    } else {
+        adler32(value, data)
+    };
+    Ok(Value::Int(i64::from(checksum)))
+}
+
+/// Binds the arguments of a zlib, gzip or statistics function to `params`.
+///
+/// The first `pos_only` parameters can't be passed by keyword, and only the first `max_positional`
+/// can be passed positionally. The first parameter, the data, is required and the rest are
+/// optional, so they're `None` if not given.
+pub(super) fn bind_args(
+    name: &str,
+    params: &[&str],
+    pos_only: usize,
+    max_positional: usize,
+    args: ArgValues,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<Vec<Option<Value>>> {
+    let (pos, kwargs) = args.into_parts();
+    let given = pos.len();
+    if given > max_positional {
+        pos.drop_with_heap(heap);
+        kwargs.drop_with_heap(heap);
+        return Err(ExcType::type_error_too_many_positional(
+            name,
+            1,
+            max_positional,
+            given,
+            0,
+        ));
+    }
+
+    let mut values = pos.map(Some).collect::<Vec<_>>();
+    values.resize_with(params.len(), || None);
+    let mut values_guard = HeapGuard::new(values, heap);
+    {
+        let (values, heap) = values_guard.as_parts_mut();
+        let kwargs = kwargs.into_iter();
+        defer_drop_mut!(kwargs, heap);
+
+        for (key, value) in kwargs {
+            defer_drop!(key, heap);
+            let mut value = HeapGuard::new(value, heap);
+
+            let Some(keyword_name) = key.as_either_str(value.heap()) else {
+                return Err(ExcType::type_error_kwargs_nonstring_key());
+            };
+            let key_str = keyword_name.as_str(interns);
+            let Some(index) = params[pos_only..].iter().position(|param| *param == key_str) else {
+                return Err(ExcType::type_error_unexpected_keyword(name, key_str));
+            };
+            let index = pos_only + index;
+            if index < given {
+                return Err(ExcType::type_error_duplicate_arg(name, key_str));
+            }
+            let old = values[index].replace(value.into_inner());
+            old.drop_with_heap(heap);
+        }
+
+        if values[0].is_none() {
+            return Err(ExcType::type_error_missing_positional_with_names(name, &params[..1]));
+        }
+    }
+    Ok(values_guard.into_inner())
+}
+
+/// Returns the contents of a bytes argument.
+///
+/// # Errors
+/// Returns `TypeError` if the value isn't bytes, as CPython does for other objects which
+/// don't support the buffer protocol.
+pub(super) fn bytes_arg<'a>(
+    value: Option<&Value>,
+    heap: &'a Heap<impl ResourceTracker>,
+    interns: &'a Interns,
+) -> RunResult<&'a [u8]> {
+    let value = value.expect("bind_args checks the data is given");
+    match value {
+        Value::InternBytes(id) => return Ok(interns.get_bytes(*id)),
+        Value::Ref(id) => {
+            if let HeapData::Bytes(bytes) = heap.get(*id) {
+                return Ok(bytes.as_slice());
+            }
+        }
+        _ => {}
+    }
+    Err(ExcType::type_error(format!(
+        "a bytes-like object is required, not '{}'",
+        value.py_type(heap)
+    )))
+}
+
+/// Returns the value of an optional int argument, or `default` if it wasn't given.
+pub(super) fn opt_int_arg(value: Option<&Value>, default: i64, heap: &Heap<impl ResourceTracker>) -> RunResult<i64> {
+    match value {
+        None => Ok(default),
+        Some(Value::Bool(b)) => Ok(i64::from(*b)),
+        Some(value) => value.as_int(heap),
+    }
+}
+
+/// Converts a compression level argument, where -1 is the default level.
+///
+/// # Errors
+/// Returns `zlib.error` for levels other than -1 to 9.
+pub(super) fn compression_level(level: i64) -> RunResult<Compression> {
+    match level {
+        -1 => Ok(Compression::default()),
+        0..=9 => Ok(Compression::new(u32::try_from(level).expect("level is in 0..=9"))),
+        _ => Err(zlib_error("Bad compression level")),
+    }
+}
+
+/// Creates a `zlib.error` with the given message.
+pub(super) fn zlib_error(msg: impl Into<String>) -> RunError {
+    SimpleException::new_msg(ExcType::ZlibError, msg.into()).into()
+}
+
+/// Compresses `data` to a zlib stream, or raw deflate data if `zlib_header` is false.
+fn deflate(data: &[u8], level: Compression, zlib_header: bool) -> Vec<u8> {
+    let result = if zlib_header {
+        let mut encoder = ZlibEncoder::new(Vec::new(), level);
+        encoder.write_all(data).and_then(|()| encoder.finish())
+    } else {
+        let mut encoder = DeflateEncoder::new(Vec::new(), level);
+        encoder.write_all(data).and_then(|()| encoder.finish())
+    };
+    result.expect("compressing into a Vec can't fail")
+}
+
+/// Compresses `data` to a single gzip member with no file name.
+///
+/// The extra flags byte is set from the level the way zlib sets it, and `os` is the
+/// operating system byte: zlib writes 3 (Unix) where the `gzip` module writes 255 (unknown).
+pub(super) fn gzip_member(data: &[u8], level: Compression, mtime: u32, os: u8) -> Vec<u8> {
+    let xfl = match level.level() {
+        9 => 2,
+        0 | 1 => 4,
+        _ => 0,
+    };
+    let mut member = GZIP_MAGIC.to_vec();
+    member.extend([DEFLATED, 0]);
+    member.extend(mtime.to_le_bytes());
+    member.extend([xfl, os]);
+    member.extend(deflate(data, level, false));
+
+    let mut crc = Crc::new();
+    crc.update(data);
+    member.extend(crc.sum().to_le_bytes());
+    member.extend(crc.amount().to_le_bytes());
+    member
+}
+
+/// Why compressed data couldn't be decompressed.
+#[derive(Debug)]
+pub(super) enum InflateError {
+    /// The data ended before the end of the stream.
+    Truncated,
+    /// The deflate data itself is corrupt.
+    Invalid,
+    /// The zlib header check failed, or the gzip magic number didn't match; holds the
+    /// (up to two) bytes which were found instead.
+    BadHeader(Vec<u8>),
+    /// The header names a compression method other than deflate.
+    UnknownMethod,
+    /// The zlib header's window size is larger than `wbits` allows.
+    WindowSize,
+    /// The zlib stream needs a preset dictionary, which isn't supported.
+    NeedDictionary,
+    /// The checksum in the trailer doesn't match the decompressed data.
+    ChecksumMismatch,
+    /// The length in the gzip trailer doesn't match the decompressed data.
+    LengthMismatch,
+    /// The decompressed data would exceed the resource limits.
+    Resource(ResourceError),
+}
+
+impl From<ResourceError> for InflateError {
+    fn from(err: ResourceError) -> Self {
+        Self::Resource(err)
+    }
+}
+
+/// Converts an `InflateError` to the `zlib.error` zlib would raise.
+pub(super) fn decompress_error(err: InflateError) -> RunError {
+    let (code, msg) = match err {
+        InflateError::Truncated => (-5, "incomplete or truncated stream"),
+        InflateError::Invalid => (-3, "invalid compressed data"),
+        InflateError::BadHeader(_) => (-3, "incorrect header check"),
+        InflateError::UnknownMethod => (-3, "unknown compression method"),
+        InflateError::WindowSize => (-3, "invalid window size"),
+        InflateError::ChecksumMismatch => (-3, "incorrect data check"),
+        InflateError::LengthMismatch => (-3, "incorrect length check"),
+        InflateError::NeedDictionary => return zlib_error("Error 2 while decompressing data"),
+        InflateError::Resource(err) => return err.into(),
+    };
+    zlib_error(format!("Error {code} while decompressing data: {msg}"))
+}
+
+/// Inflates the raw deflate data at the start of `data`.
+///
+/// Returns the decompressed bytes and the number of input bytes the deflate stream used.
+/// Before each chunk is inflated, the time and the output size are checked against the resource
+/// limits, counting the `already` bytes the caller has decompressed earlier.
+pub(super) fn inflate(
+    data: &[u8],
+    already: usize,
+    tracker: &impl ResourceTracker,
+) -> Result<(Vec<u8>, usize), InflateError> {
+    let mut decompressor = Decompress::new(false);
+    let mut buffer = vec![0; CHUNK_SIZE];
+    let mut decompressed = Vec::new();
+    loop {
+        check_decompressed_size(already.saturating_add(decompressed.len() + CHUNK_SIZE), tracker)?;
+        tracker.check_time()?;
+
+        let consumed = usize::try_from(decompressor.total_in()).expect("consumed input fits in usize");
+        let produced = decompressor.total_out();
+        let status = decompressor
+            .decompress(&data[consumed..], &mut buffer, FlushDecompress::None)
+            .map_err(|_| InflateError::Invalid)?;
+        let written = usize::try_from(decompressor.total_out() - produced).expect("chunk fits in usize");
+        decompressed.extend_from_slice(&buffer[..written]);
+
+        if status == Status::StreamEnd {
+            let used = usize::try_from(decompressor.total_in()).expect("consumed input fits in usize");
+            return Ok((decompressed, used));
+        }
+        if written == 0 && usize::try_from(decompressor.total_in()) == Ok(consumed) {
+            return Err(InflateError::Truncated);
+        }
+    }
+}
+
+/// Decompresses the zlib stream at the start of `data`, whose window may be at most
+/// `2**wbits` bytes.
+fn unzlib(data: &[u8], wbits: i64, tracker: &impl ResourceTracker) -> Result<Vec<u8>, InflateError> {
+    let &[cmf, flg, ..] = data else {
+        return Err(InflateError::Truncated);
+    };
+    if ((u16::from(cmf) << 8) | u16::from(flg)) % 31 != 0 {
+        return Err(InflateError::BadHeader(vec![cmf, flg]));
+    }
+    if cmf & 0x0f != DEFLATED {
+        return Err(InflateError::UnknownMethod);
+    }
+    if i64::from(cmf >> 4) + 8 > wbits {
+        return Err(InflateError::WindowSize);
+    }
+    if flg & 0x20 != 0 {
+        return Err(InflateError::NeedDictionary);
+    }
+
+    let (decompressed, used) = inflate(&data[2..], 0, tracker)?;
+    let trailer = data.get(2 + used..6 + used).ok_or(InflateError::Truncated)?;
+    if trailer != adler32(1, &decompressed).to_be_bytes() {
+        return Err(InflateError::ChecksumMismatch);
+    }
+    Ok(decompressed)
+}
+
+/// Decompresses the gzip member at the start of `data`, checking its CRC and length.
+///
+/// Returns the decompressed bytes and the number of input bytes the member used.
+/// `already` is passed on to `inflate()`.
+pub(super) fn gunzip_member(
+    data: &[u8],
+    already: usize,
+    tracker: &impl ResourceTracker,
+) -> Result<(Vec<u8>, usize), InflateError> {
+    if !data.starts_with(&GZIP_MAGIC) {
+        return Err(InflateError::BadHeader(data[..data.len().min(2)].to_vec()));
+    }
+    let header = data.get(..10).ok_or(InflateError::Truncated)?;
+    if header[2] != DEFLATED {
+        return Err(InflateError::UnknownMethod);
+    }
+    let flags = header[3];
+
+    let mut pos = 10;
+    if flags & FEXTRA != 0 {
+        let &[low, high] = data.get(pos..pos + 2).ok_or(InflateError::Truncated)? else {
+            unreachable!("slice has two bytes")
+        };
+        pos += 2 + usize::from(u16::from_le_bytes([low, high]));
+    }
+    for flag in [FNAME, FCOMMENT] {
+        if flags & flag != 0 {
+            // a null-terminated string, read up to the end of the data if there's no terminator
+            let rest = data.get(pos..).ok_or(InflateError::Truncated)?;
+            pos += rest.iter().position(|&b| b == 0).map_or(rest.len(), |len| len + 1);
+        }
+    }
+    if flags & FHCRC != 0 {
+        pos += 2;
+    }
+    let body = data.get(pos..).ok_or(InflateError::Truncated)?;
+
+    let (decompressed, used) = inflate(body, already, tracker)?;
+    pos += used;
+    let trailer = data.get(pos..pos + 8).ok_or(InflateError::Truncated)?;
+    let mut crc = Crc::new();
+    crc.update(&decompressed);
+    if trailer[..4] != crc.sum().to_le_bytes() {
+        return Err(InflateError::ChecksumMismatch);
+    }
+    if trailer[4..] != crc.amount().to_le_bytes() {
+        return Err(InflateError::LengthMismatch);
+    }
+    Ok((decompressed, pos + 8))
+}
+
+/// Computes the Adler-32 checksum of `data`, continuing from `value`.
+fn adler32(value: u32, data: &[u8]) -> u32 {
+    const MOD: u32 = 65_521;
+    // the most bytes which can be summed before `b` could overflow a u32
+    const MAX_RUN: usize = 5552;
+
+    let (mut a, mut b) = (value & 0xffff, value >> 16);
+    for run in data.chunks(MAX_RUN) {
+        for &byte in run {
+            a += u32::from(byte);
+            b += a;
+        }
+        a %= MOD;
+        b %= MOD;
+    }
+    ((b % MOD) << 16) | (a % MOD)
+}
@@ -1,7 +1,9 @@
 use crate::{
     exception_private::ExceptionRaise,
+    expressions::Node,
     heap::{Heap, HeapId},
-    parse::CodeRange,
+    intern::{StaticStrings, StringId},
+    parse::{CodeRange, ParseResult},
     resource::{ResourceError, ResourceTracker},
     value::Value,
 };
@@ -28,6 +30,57 @@ impl NamespaceId {
 /// At module level, local_idx == GLOBAL_NS_IDX (same namespace).
 pub(crate) const GLOBAL_NS_IDX: NamespaceId = NamespaceId(0);
 
+/// Names of the module-level dunder variables, registered in this order after external functions
+/// and inputs, see [`ModuleDunders`].
+pub(crate) const MODULE_DUNDERS: [&str; 3] = ["__name__", "__file__", "__doc__"];
+
+/// Values of the module-level `__name__`, `__file__` and `__doc__` variables.
+///
+/// These occupy the namespace slots starting at `slot`, directly after external functions and
+/// inputs, so scripts guarded by `if __name__ == '__main__':` run as they would under CPython.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub(crate) struct ModuleDunders {
+    /// Namespace slot of `__name__`, followed by `__file__` and `__doc__`.
+    slot: usize,
+    /// Value of `__name__`, `"__main__"` unless configured otherwise.
+    name: StringId,
+    /// Value of `__file__`, the script name.
+    file: StringId,
+    /// Value of `__doc__`, the module docstring if the first statement is a string literal.
+    doc: Option<StringId>,
+}
+
+impl ModuleDunders {
+    /// Creates the dunders for a module parsed from `script_name`, stored from namespace `slot`.
+    pub fn new(parse_result: &mut ParseResult, script_name: &str, slot: usize) -> Self {
+        Self {
+            slot,
+            name: StaticStrings::DunderMain.into(),
+            file: parse_result.interner.intern(script_name),
+            doc: Node::docstring(&parse_result.nodes),
+        }
+    }
+
+    /// Namespace slot of `__name__`, the first of the dunders.
+    pub fn slot(&self) -> usize {
+        self.slot
+    }
+
+    /// Sets the value of `__name__`.
+    pub fn set_name(&mut self, name: StringId) {
+        self.name = name;
+    }
+
+    /// Values of the dunders in [`MODULE_DUNDERS`] order.
+    pub fn values(&self) -> [Value; 3] {
+        [
+            Value::InternString(self.name),
+            Value::InternString(self.file),
+            self.doc.map_or(Value::None, Value::InternString),
+        ]
+    }
+}
+
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 pub(crate) struct Namespace(Vec<Value>);
 
@@ -52,6 +105,11 @@ impl Namespace {
     pub fn mut_vec(&mut self) -> &mut Vec<Value> {
         &mut self.0
     }
+
+    /// Returns the namespace slots in slot order.
+    pub fn as_slice(&self) -> &[Value] {
+        &self.0
+    }
 }
 
 impl IntoIterator for Namespace {
@@ -104,6 +162,12 @@ pub struct Namespaces {
     /// When set, the next call to `take_ext_return_value` will return this error,
     /// allowing it to propagate through try/except blocks.
     ext_exception: Option<ExceptionRaise>,
+    /// Global namespaces that are not currently at index 0.
+    ///
+    /// REPL sessions keep each named namespace here while snippets run in another one, see
+    /// `MontyRepl::feed_in_namespace`. They are included in GC roots like the stack.
+    #[serde(default)]
+    parked_globals: Vec<Namespace>,
 }
 
 impl Namespaces {
@@ -117,9 +181,26 @@ impl Namespaces {
             ext_return_values: vec![],
             next_ext_return_value: 0,
             ext_exception: None,
+            parked_globals: vec![],
         }
     }
 
+    /// Stores an extra global namespace, returning its index for [`Self::swap_parked_global`].
+    pub fn park_global(&mut self, namespace: Vec<Value>) -> usize {
+        self.parked_globals.push(Namespace(namespace));
+        self.parked_globals.len() - 1
+    }
+
+    /// Swaps the global namespace with the parked namespace at `index`.
+    ///
+    /// Calling this twice with the same index restores the original global namespace.
+    ///
+    /// # Panics
+    /// Panics if `index` wasn't returned by [`Self::park_global`].
+    pub fn swap_parked_global(&mut self, index: usize) {
+        std::mem::swap(&mut self.stack[GLOBAL_NS_IDX.index()], &mut self.parked_globals[index]);
+    }
+
     /// Gets an immutable slice reference to a namespace by index.
     ///
     /// Used for reading from the enclosing namespace when defining closures,
@@ -250,6 +331,11 @@ impl Namespaces {
             let v = std::mem::replace(value, Value::Undefined);
             v.drop_with_heap(heap);
         }
+        for namespace in std::mem::take(&mut self.parked_globals) {
+            for value in namespace {
+                value.drop_with_heap(heap);
+            }
+        }
         // Clean up any remaining return values from external function calls
         for (_, value) in std::mem::take(&mut self.ext_return_values) {
             value.drop_with_heap(heap);
@@ -275,6 +361,16 @@ impl Namespaces {
     pub fn iter_heap_ids(&self) -> impl Iterator<Item = HeapId> + '_ {
         self.stack
             .iter()
+            .chain(&self.parked_globals)
             .flat_map(|namespace| namespace.0.iter().filter_map(Value::ref_id))
     }
+
+    /// Returns all HeapIds owned by the namespaces, one per reference, for heap validation.
+    ///
+    /// Unlike `iter_heap_ids`, also includes return values from external function calls.
+    #[cfg(feature = "heap-validate")]
+    pub fn iter_owned_heap_ids(&self) -> impl Iterator<Item = HeapId> + '_ {
+        self.iter_heap_ids()
+            .chain(self.ext_return_values.iter().filter_map(|(_, value)| value.ref_id()))
+    }
 }
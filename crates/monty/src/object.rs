@@ -1,6 +1,9 @@
 use std::{
+    collections::{BTreeMap, HashMap},
     fmt::{self, Write},
-    hash::{Hash, Hasher},
+    hash::{BuildHasher, Hash, Hasher},
+    ops::Deref,
+    sync::Arc,
 };
 
 use ahash::AHashSet;
@@ -11,11 +14,13 @@ use num_traits::Zero;
 use crate::{
     builtins::{Builtins, BuiltinsFunctions},
     exception_private::{ExcType, SimpleException},
+    expressions::Literal,
+    fstring::float_repr,
     heap::{Heap, HeapData, HeapId},
-    intern::Interns,
+    intern::{InternerBuilder, Interns},
     resource::{DepthGuard, ResourceError, ResourceTracker},
     types::{
-        LongInt, NamedTuple, Path, PyTrait, Type, allocate_tuple,
+        LazySequence, LongInt, NamedTuple, Path, PyTrait, Type, allocate_tuple,
         bytes::{Bytes, bytes_repr},
         dict::Dict,
         list::List,
@@ -45,28 +50,20 @@ use crate::{
 ///
 /// # JSON Serialization
 ///
-/// `MontyObject` supports JSON serialization with natural mappings:
+/// [`to_json_value`](Self::to_json_value) and [`from_json_value`](Self::from_json_value)
+/// convert to and from natural JSON:
+/// - `None`, `Bool`, `Int`, `Float`, `String` and `List` ↔ the matching JSON types
+/// - `Dict` ↔ JSON object if every key is a string, otherwise `{"$dict": [[key, value], ...]}`
+/// - Other types ↔ tagged objects such as `{"$tuple": [...]}`, `{"$bytes": "<base64>"}` or
+///   `{"$dataclass": {"name": "...", "attrs": {...}, ...}}`
 ///
-/// **Bidirectional (can serialize and deserialize):**
-/// - `None` ↔ JSON `null`
-/// - `Bool` ↔ JSON `true`/`false`
-/// - `Int` ↔ JSON integer
-/// - `Float` ↔ JSON float
-/// - `String` ↔ JSON string
-/// - `List` ↔ JSON array
-/// - `Dict` ↔ JSON object (keys must be interns)
+/// [`json_schema`](Self::json_schema) describes the format as a JSON Schema.
 ///
-/// **Output-only (serialize only, cannot deserialize from JSON):**
-/// - `Ellipsis` → `{"$ellipsis": true}`
-/// - `Tuple` → `{"$tuple": [...]}`
-/// - `Bytes` → `{"$bytes": [...]}`
-/// - `Exception` → `{"$exception": {"type": "...", "arg": "..."}}`
-/// - `Repr` → `{"$repr": "..."}`
+/// # Serde
 ///
-/// # Binary Serialization
-///
-/// For binary serialization (e.g., with postcard), `MontyObject` uses derived serde
-/// with internally tagged format. This differs from the natural JSON format.
+/// The derived serde implementation is externally tagged, e.g. `{"Int": 42}` or
+/// `{"Dataclass": {"name": "Point", ...}}`. `Bytes` are a base64 string in human-readable
+/// formats like JSON and raw bytes in binary formats like postcard.
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub enum MontyObject {
     /// Python's `Ellipsis` singleton (`...`).
@@ -84,7 +81,7 @@ pub enum MontyObject {
     /// Python string (UTF-8).
     String(String),
     /// Python bytes object.
-    Bytes(Vec<u8>),
+    Bytes(#[serde(with = "crate::json::base64_bytes")] Vec<u8>),
     /// Python list (mutable sequence).
     List(Vec<Self>),
     /// Python tuple (immutable sequence).
@@ -141,6 +138,20 @@ pub enum MontyObject {
         /// Whether this dataclass instance is immutable.
         frozen: bool,
     },
+    /// A host dataset that is iterated in chunks rather than converted upfront.
+    ///
+    /// Iterating it with a `for` loop or comprehension pauses with a `__getitem__` method
+    /// call whose arguments are this sequence and the `start` and `stop` indices of the
+    /// next chunk; the host resumes with a list of at most `chunk_size` items. When
+    /// `length` is `None`, a chunk shorter than requested marks the end of the sequence.
+    LazySequence {
+        /// Name the host uses to identify the sequence.
+        name: String,
+        /// Total number of items, if known.
+        length: Option<usize>,
+        /// Maximum number of items requested per fetch.
+        chunk_size: usize,
+    },
     /// Fallback for values that cannot be represented as other variants.
     ///
     /// Contains the `repr()` string of the original value.
@@ -155,12 +166,20 @@ pub enum MontyObject {
     ///
     /// This is output-only and cannot be used as an input to `Executor::run()`.
     Cycle(HeapId, String),
+    /// Python string (UTF-8) whose buffer is shared with the embedder.
+    ///
+    /// Input-only alternative to `String` for large strings: the interpreter references the
+    /// buffer instead of copying it, and cloning the object to run again is cheap. It compares
+    /// and hashes equal to a `String` with the same content. Strings returned from the
+    /// interpreter are always `String`.
+    SharedString(SharedStr),
 }
 
 impl fmt::Display for MontyObject {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::String(s) => f.write_str(s),
+            Self::SharedString(s) => f.write_str(s),
             Self::Cycle(_, placeholder) => f.write_str(placeholder),
             Self::Type(t) => write!(f, "<class '{t}'>"),
             _ => self.repr_fmt(f),
@@ -186,6 +205,27 @@ impl MontyObject {
         Self::Dict(dict.into())
     }
 
+    /// Converts an immutable scalar into a `Literal` for the constant pool, interning its data.
+    ///
+    /// Returns `None` for containers and other values that can't be stored as literals.
+    pub(crate) fn to_literal(&self, interner: &mut InternerBuilder) -> Option<Literal> {
+        match self {
+            Self::Ellipsis => Some(Literal::Ellipsis),
+            Self::None => Some(Literal::None),
+            Self::Bool(b) => Some(Literal::Bool(*b)),
+            Self::Int(i) => Some(Literal::Int(*i)),
+            Self::BigInt(bi) => Some(match i64::try_from(bi) {
+                Ok(i) => Literal::Int(i),
+                Err(_) => Literal::LongInt(interner.intern_long_int(bi.clone())),
+            }),
+            Self::Float(f) => Some(Literal::Float(*f)),
+            Self::String(s) => Some(Literal::Str(interner.intern(s))),
+            Self::SharedString(s) => Some(Literal::Str(interner.intern(s))),
+            Self::Bytes(b) => Some(Literal::Bytes(interner.intern_bytes(b))),
+            _ => None,
+        }
+    }
+
     /// Converts this `MontyObject` into an `Value`, allocating on the heap if needed.
     ///
     /// Immediate values (None, Bool, Int, Float, Ellipsis, Exception) are created directly.
@@ -208,6 +248,7 @@ impl MontyObject {
             Self::BigInt(bi) => Ok(LongInt::new(bi).into_value(heap)?),
             Self::Float(f) => Ok(Value::Float(f)),
             Self::String(s) => Ok(Value::Ref(heap.allocate(HeapData::Str(Str::new(s)))?)),
+            Self::SharedString(s) => Ok(Value::Ref(heap.allocate(HeapData::Str(Str::shared(s)))?)),
             Self::Bytes(b) => Ok(Value::Ref(heap.allocate(HeapData::Bytes(Bytes::new(b)))?)),
             Self::List(items) => {
                 let values: Vec<Value> = items
@@ -288,6 +329,13 @@ impl MontyObject {
                 Ok(Value::Ref(heap.allocate(HeapData::Dataclass(dc))?))
             }
             Self::Path(s) => Ok(Value::Ref(heap.allocate(HeapData::Path(Path::new(s)))?)),
+            Self::LazySequence {
+                name,
+                length,
+                chunk_size,
+            } => Ok(Value::Ref(heap.allocate(HeapData::LazySequence(LazySequence::new(
+                name, length, chunk_size,
+            )))?)),
             Self::Type(t) => Ok(Value::Builtin(Builtins::Type(t))),
             Self::BuiltinFunction(f) => Ok(Value::Builtin(Builtins::Function(f))),
             Self::Repr(_) => Err(InvalidInputError::invalid_type("Repr")),
@@ -295,7 +343,8 @@ impl MontyObject {
         }
     }
 
-    fn from_value(object: &Value, heap: &Heap<impl ResourceTracker>, interns: &Interns) -> Self {
+    /// Converts a `Value` into a `MontyObject` without consuming it.
+    pub(crate) fn from_value(object: &Value, heap: &Heap<impl ResourceTracker>, interns: &Interns) -> Self {
         let mut visited = AHashSet::new();
         let mut guard = DepthGuard::default();
         Self::from_value_inner(object, heap, &mut visited, &mut guard, interns)
@@ -474,6 +523,40 @@ impl MontyObject {
                         Self::Repr(format!("<gather({})>", gather.item_count()))
                     }
                     HeapData::Path(path) => Self::Path(path.as_str().to_owned()),
+                    HeapData::LazySequence(seq) => Self::LazySequence {
+                        name: seq.name().to_owned(),
+                        length: seq.length(),
+                        chunk_size: seq.chunk_size(),
+                    },
+                    HeapData::UnionType(union) => {
+                        // Unions are represented as a repr string, e.g. `int | None`
+                        let mut s = String::new();
+                        let _ = union.py_repr_fmt(&mut s, heap, visited, guard, interns);
+                        Self::Repr(s)
+                    }
+                    HeapData::StringIO(string_io) => {
+                        let mut s = String::new();
+                        let _ = string_io.py_repr_fmt(&mut s, heap, visited, guard, interns);
+                        Self::Repr(s)
+                    }
+                    HeapData::CsvWriter(writer) => {
+                        let mut s = String::new();
+                        let _ = writer.py_repr_fmt(&mut s, heap, visited, guard, interns);
+                        Self::Repr(s)
+                    }
+                    HeapData::Fraction(fraction) => {
+                        let mut s = String::new();
+                        let _ = fraction.py_repr_fmt(&mut s, heap, visited, guard, interns);
+                        Self::Repr(s)
+                    }
+                    // Arrays cross the boundary as plain lists of ints or floats
+                    HeapData::Array(array) => Self::List(
+                        array
+                            .to_values()
+                            .iter()
+                            .map(|obj| Self::from_value_inner(obj, heap, visited, guard, interns))
+                            .collect(),
+                    ),
                 };
 
                 // Remove from visited set after processing
@@ -508,15 +591,9 @@ impl MontyObject {
             Self::Bool(false) => f.write_str("False"),
             Self::Int(v) => write!(f, "{v}"),
             Self::BigInt(v) => write!(f, "{v}"),
-            Self::Float(v) => {
-                let s = v.to_string();
-                f.write_str(&s)?;
-                if !s.contains('.') {
-                    f.write_str(".0")?;
-                }
-                Ok(())
-            }
+            Self::Float(v) => f.write_str(&float_repr(*v)),
             Self::String(s) => string_repr_fmt(s, f),
+            Self::SharedString(s) => string_repr_fmt(s, f),
             Self::Bytes(b) => f.write_str(&bytes_repr(b)),
             Self::List(l) => {
                 f.write_char('[')?;
@@ -648,6 +725,7 @@ impl MontyObject {
                 f.write_char(')')
             }
             Self::Path(p) => write!(f, "PosixPath('{p}')"),
+            Self::LazySequence { name, .. } => write!(f, "<LazySequence '{name}'>"),
             Self::Type(t) => write!(f, "<class '{t}'>"),
             Self::BuiltinFunction(func) => write!(f, "<built-in function {func}>"),
             Self::Repr(s) => write!(f, "Repr({})", StringRepr(s)),
@@ -674,6 +752,7 @@ impl MontyObject {
             Self::BigInt(bi) => !bi.is_zero(),
             Self::Float(f) => *f != 0.0,
             Self::String(s) => !s.is_empty(),
+            Self::SharedString(s) => !s.is_empty(),
             Self::Bytes(b) => !b.is_empty(),
             Self::List(l) => !l.is_empty(),
             Self::Tuple(t) => !t.is_empty(),
@@ -684,6 +763,7 @@ impl MontyObject {
             Self::Exception { .. } => true,
             Self::Path(_) => true,          // Path instances are always truthy
             Self::Dataclass { .. } => true, // Dataclass instances are always truthy
+            Self::LazySequence { length, .. } => *length != Some(0),
             Self::Type(_) | Self::BuiltinFunction(_) | Self::Repr(_) | Self::Cycle(_, _) => true,
         }
     }
@@ -699,7 +779,7 @@ impl MontyObject {
             Self::Bool(_) => "bool",
             Self::Int(_) | Self::BigInt(_) => "int",
             Self::Float(_) => "float",
-            Self::String(_) => "str",
+            Self::String(_) | Self::SharedString(_) => "str",
             Self::Bytes(_) => "bytes",
             Self::List(_) => "list",
             Self::Tuple(_) => "tuple",
@@ -710,6 +790,7 @@ impl MontyObject {
             Self::Exception { .. } => "Exception",
             Self::Path(_) => "PosixPath",
             Self::Dataclass { .. } => "dataclass",
+            Self::LazySequence { .. } => "LazySequence",
             Self::Type(_) => "type",
             Self::BuiltinFunction(_) => "builtin_function_or_method",
             Self::Repr(_) => "repr",
@@ -718,6 +799,136 @@ impl MontyObject {
     }
 }
 
+/// Approximate size in bytes counted for every value, on top of any string or bytes data.
+const OUTPUT_VALUE_SIZE: usize = 8;
+
+/// Budget for the approximate size of values converted to `MontyObject`s when leaving the sandbox.
+///
+/// Sizes are counted the way `MontyObject::new` converts values: shared references are counted
+/// every time they're reached, cycles and values nested deeper than the data recursion limit
+/// are counted as their small placeholders. Counting stops as soon as the limit is exceeded,
+/// so it never costs more than converting a value that fits.
+pub(crate) struct OutputSize {
+    /// Maximum size in bytes.
+    limit: usize,
+    /// Size counted so far.
+    size: usize,
+}
+
+impl OutputSize {
+    /// Creates a budget from the tracker's `max_output_size`, `None` if output isn't limited.
+    pub fn new(tracker: &impl ResourceTracker) -> Option<Self> {
+        tracker.max_output_size().map(|limit| Self { limit, size: 0 })
+    }
+
+    /// Checks that converting `value` doesn't exceed the size limit of a single value.
+    pub fn check(value: &Value, heap: &Heap<impl ResourceTracker>, interns: &Interns) -> Result<(), ResourceError> {
+        match Self::new(heap.tracker()) {
+            Some(mut budget) => budget.add_value(value, heap, interns),
+            None => Ok(()),
+        }
+    }
+
+    /// Adds the size of `value` to the budget, failing once the limit is exceeded.
+    pub fn add_value(
+        &mut self,
+        value: &Value,
+        heap: &Heap<impl ResourceTracker>,
+        interns: &Interns,
+    ) -> Result<(), ResourceError> {
+        let mut visited = AHashSet::new();
+        let mut guard = DepthGuard::default();
+        self.add_value_inner(value, heap, &mut visited, &mut guard, interns)
+    }
+
+    /// Counts one value, tracking nesting depth like `MontyObject::from_value_inner`.
+    fn add_value_inner(
+        &mut self,
+        value: &Value,
+        heap: &Heap<impl ResourceTracker>,
+        visited: &mut AHashSet<HeapId>,
+        guard: &mut DepthGuard,
+        interns: &Interns,
+    ) -> Result<(), ResourceError> {
+        self.add(OUTPUT_VALUE_SIZE)?;
+        if !guard.increase() {
+            return Ok(());
+        }
+        let result = self.add_value_impl(value, heap, visited, guard, interns);
+        guard.decrease();
+        result
+    }
+
+    /// Counts the data of one value, including its items for containers.
+    fn add_value_impl(
+        &mut self,
+        value: &Value,
+        heap: &Heap<impl ResourceTracker>,
+        visited: &mut AHashSet<HeapId>,
+        guard: &mut DepthGuard,
+        interns: &Interns,
+    ) -> Result<(), ResourceError> {
+        let id = match value {
+            Value::InternString(string_id) => return self.add(interns.get_str(*string_id).len()),
+            Value::InternBytes(bytes_id) => return self.add(interns.get_bytes(*bytes_id).len()),
+            Value::Ref(id) => *id,
+            _ => return Ok(()),
+        };
+        // cycles are converted to a placeholder
+        if !visited.insert(id) {
+            return Ok(());
+        }
+        let result = match heap.get(id) {
+            HeapData::Str(s) => self.add(s.len()),
+            HeapData::Bytes(b) => self.add(b.as_slice().len()),
+            HeapData::Path(path) => self.add(path.as_str().len()),
+            HeapData::LazySequence(seq) => self.add(seq.name().len()),
+            HeapData::LongInt(li) => self.add(usize::try_from(li.inner().bits() / 8).unwrap_or(usize::MAX)),
+            HeapData::List(list) => self.add_values(list.as_slice(), heap, visited, guard, interns),
+            HeapData::Tuple(tuple) => self.add_values(tuple.as_slice(), heap, visited, guard, interns),
+            HeapData::NamedTuple(nt) => self.add_values(nt.as_vec(), heap, visited, guard, interns),
+            HeapData::Set(set) => self.add_values(set.storage().iter(), heap, visited, guard, interns),
+            HeapData::FrozenSet(set) => self.add_values(set.storage().iter(), heap, visited, guard, interns),
+            HeapData::Dict(dict) => dict
+                .into_iter()
+                .try_for_each(|(k, v)| self.add_values([k, v], heap, visited, guard, interns)),
+            HeapData::Dataclass(dc) => dc
+                .attrs()
+                .into_iter()
+                .try_for_each(|(k, v)| self.add_values([k, v], heap, visited, guard, interns)),
+            HeapData::Cell(inner) => self.add_value_inner(inner, heap, visited, guard, interns),
+            // everything else is converted to a type or a short repr
+            _ => Ok(()),
+        };
+        visited.remove(&id);
+        result
+    }
+
+    /// Counts each value in `values`.
+    fn add_values<'a>(
+        &mut self,
+        values: impl IntoIterator<Item = &'a Value>,
+        heap: &Heap<impl ResourceTracker>,
+        visited: &mut AHashSet<HeapId>,
+        guard: &mut DepthGuard,
+        interns: &Interns,
+    ) -> Result<(), ResourceError> {
+        values
+            .into_iter()
+            .try_for_each(|value| self.add_value_inner(value, heap, visited, guard, interns))
+    }
+
+    /// Adds `bytes` to the size, failing if it's now over the limit.
+    fn add(&mut self, bytes: usize) -> Result<(), ResourceError> {
+        self.size = self.size.saturating_add(bytes);
+        if self.size > self.limit {
+            Err(ResourceError::OutputSize { limit: self.limit })
+        } else {
+            Ok(())
+        }
+    }
+}
+
 impl Hash for MontyObject {
     fn hash<H: Hasher>(&self, state: &mut H) {
         // Hash the discriminant first (but Int and BigInt share discriminant for consistency)
@@ -726,6 +937,10 @@ impl Hash for MontyObject {
                 // Use Int discriminant for both to maintain hash consistency
                 std::mem::discriminant(&Self::Int(0)).hash(state);
             }
+            Self::SharedString(_) => {
+                // Shared strings are equal to `String`s, so share its discriminant too
+                std::mem::discriminant(&Self::String(String::new())).hash(state);
+            }
             _ => std::mem::discriminant(self).hash(state),
         }
 
@@ -744,6 +959,7 @@ impl Hash for MontyObject {
             }
             Self::Float(f) => f.to_bits().hash(state),
             Self::String(string) => string.hash(state),
+            Self::SharedString(string) => string.hash(state),
             Self::Bytes(bytes) => bytes.hash(state),
             Self::Path(path) => path.hash(state),
             Self::Type(t) => t.to_string().hash(state),
@@ -766,6 +982,8 @@ impl PartialEq for MontyObject {
             // Use to_bits() for float comparison to be consistent with Hash
             (Self::Float(a), Self::Float(b)) => a.to_bits() == b.to_bits(),
             (Self::String(a), Self::String(b)) => a == b,
+            (Self::SharedString(a), Self::SharedString(b)) => a == b,
+            (Self::String(a), Self::SharedString(b)) | (Self::SharedString(b), Self::String(a)) => **a == **b,
             (Self::Bytes(a), Self::Bytes(b)) => a == b,
             (Self::List(a), Self::List(b)) => a == b,
             (Self::Tuple(a), Self::Tuple(b)) => a == b,
@@ -821,6 +1039,18 @@ impl PartialEq for MontyObject {
                     && a_frozen == b_frozen
             }
             (Self::Path(a), Self::Path(b)) => a == b,
+            (
+                Self::LazySequence {
+                    name: a_name,
+                    length: a_length,
+                    chunk_size: a_chunk_size,
+                },
+                Self::LazySequence {
+                    name: b_name,
+                    length: b_length,
+                    chunk_size: b_chunk_size,
+                },
+            ) => a_name == b_name && a_length == b_length && a_chunk_size == b_chunk_size,
             (Self::Repr(a), Self::Repr(b)) => a == b,
             (Self::Cycle(a, _), Self::Cycle(b, _)) => a == b,
             (Self::Type(a), Self::Type(b)) => a == b,
@@ -940,6 +1170,8 @@ impl TryFrom<&MontyObject> for String {
     fn try_from(value: &MontyObject) -> Result<Self, Self::Error> {
         if let MontyObject::String(s) = value {
             Ok(s.clone())
+        } else if let MontyObject::SharedString(s) = value {
+            Ok(s.to_string())
         } else {
             Err(ConversionError::new("str", value.type_name()))
         }
@@ -960,6 +1192,330 @@ impl TryFrom<&MontyObject> for bool {
     }
 }
 
+/// Attempts to convert a `MontyObject` to a `Vec`, converting each item.
+/// Returns an error if the object is not a list or tuple, or if any item fails to convert.
+impl<T> TryFrom<&MontyObject> for Vec<T>
+where
+    T: for<'a> TryFrom<&'a MontyObject, Error = ConversionError>,
+{
+    type Error = ConversionError;
+
+    fn try_from(value: &MontyObject) -> Result<Self, Self::Error> {
+        match value {
+            MontyObject::List(items) | MontyObject::Tuple(items) => items.iter().map(T::try_from).collect(),
+            _ => Err(ConversionError::new("list", value.type_name())),
+        }
+    }
+}
+
+/// Attempts to convert a `MontyObject` to a `HashMap`, converting each key and value.
+/// Returns an error if the object is not a dict, or if any key or value fails to convert.
+impl<K, V, S> TryFrom<&MontyObject> for HashMap<K, V, S>
+where
+    K: for<'a> TryFrom<&'a MontyObject, Error = ConversionError> + Eq + Hash,
+    V: for<'a> TryFrom<&'a MontyObject, Error = ConversionError>,
+    S: BuildHasher + Default,
+{
+    type Error = ConversionError;
+
+    fn try_from(value: &MontyObject) -> Result<Self, Self::Error> {
+        match value {
+            MontyObject::Dict(pairs) => pairs
+                .iter()
+                .map(|(key, value)| Ok((K::try_from(key)?, V::try_from(value)?)))
+                .collect(),
+            _ => Err(ConversionError::new("dict", value.type_name())),
+        }
+    }
+}
+
+/// Implements `From` for Rust tuples, creating a Python tuple, and `TryFrom` to convert
+/// a Python tuple (or list) of the same length back to a Rust tuple.
+macro_rules! impl_tuple_conversions {
+    ($len:literal: $($name:ident $index:tt),+) => {
+        impl<$($name: Into<MontyObject>),+> From<($($name,)+)> for MontyObject {
+            fn from(value: ($($name,)+)) -> Self {
+                Self::Tuple(vec![$(value.$index.into()),+])
+            }
+        }
+
+        impl<$($name),+> TryFrom<&MontyObject> for ($($name,)+)
+        where
+            $($name: for<'a> TryFrom<&'a MontyObject, Error = ConversionError>),+
+        {
+            type Error = ConversionError;
+
+            fn try_from(value: &MontyObject) -> Result<Self, Self::Error> {
+                match value {
+                    MontyObject::Tuple(items) | MontyObject::List(items) if items.len() == $len => {
+                        Ok(($($name::try_from(&items[$index])?,)+))
+                    }
+                    _ => Err(ConversionError::new(concat!("tuple of length ", $len), value.type_name())),
+                }
+            }
+        }
+    };
+}
+
+impl_tuple_conversions!(1: A 0);
+impl_tuple_conversions!(2: A 0, B 1);
+impl_tuple_conversions!(3: A 0, B 1, C 2);
+impl_tuple_conversions!(4: A 0, B 1, C 2, D 3);
+impl_tuple_conversions!(5: A 0, B 1, C 2, D 3, E 4);
+impl_tuple_conversions!(6: A 0, B 1, C 2, D 3, E 4, F 5);
+
+impl From<bool> for MontyObject {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+/// Implements `From` for integer types that always fit in an `i64`.
+macro_rules! impl_from_small_int {
+    ($($ty:ty),+) => {
+        $(
+            impl From<$ty> for MontyObject {
+                fn from(value: $ty) -> Self {
+                    Self::Int(i64::from(value))
+                }
+            }
+        )+
+    };
+}
+
+impl_from_small_int!(i8, i16, i32, i64, u8, u16, u32);
+
+/// Implements `From` for integer types that may not fit in an `i64`, using `BigInt` for large values.
+macro_rules! impl_from_large_int {
+    ($($ty:ty),+) => {
+        $(
+            impl From<$ty> for MontyObject {
+                fn from(value: $ty) -> Self {
+                    i64::try_from(value).map_or_else(|_| Self::BigInt(BigInt::from(value)), Self::Int)
+                }
+            }
+        )+
+    };
+}
+
+impl_from_large_int!(u64, usize, isize, i128, u128);
+
+impl From<BigInt> for MontyObject {
+    fn from(value: BigInt) -> Self {
+        i64::try_from(&value).map_or(Self::BigInt(value), Self::Int)
+    }
+}
+
+impl From<f64> for MontyObject {
+    fn from(value: f64) -> Self {
+        Self::Float(value)
+    }
+}
+
+impl From<f32> for MontyObject {
+    fn from(value: f32) -> Self {
+        Self::Float(f64::from(value))
+    }
+}
+
+impl From<&str> for MontyObject {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_owned())
+    }
+}
+
+impl From<String> for MontyObject {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+/// Shares the string's buffer with the interpreter, see [`MontyObject::SharedString`].
+impl From<Arc<str>> for MontyObject {
+    fn from(value: Arc<str>) -> Self {
+        Self::SharedString(value.into())
+    }
+}
+
+/// A string buffer owned by the embedder and referenced by the interpreter without copying.
+///
+/// The buffer can be owned by anything that gives out the same immutable `str` for as long
+/// as it's alive, e.g. an `Arc<str>` or a host language string object, which is dropped once
+/// the interpreter no longer references it. Compares, hashes and serializes as its content.
+#[derive(Clone)]
+pub struct SharedStr(Arc<dyn AsRef<str> + Send + Sync>);
+
+impl SharedStr {
+    /// Shares the buffer of `owner`, which must return the same string from every `as_ref()` call.
+    #[must_use]
+    pub fn new(owner: impl AsRef<str> + Send + Sync + 'static) -> Self {
+        Self(Arc::new(owner))
+    }
+
+    /// Returns the shared string.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        (*self.0).as_ref()
+    }
+}
+
+impl Deref for SharedStr {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for SharedStr {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl From<Arc<str>> for SharedStr {
+    fn from(value: Arc<str>) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<String> for SharedStr {
+    fn from(value: String) -> Self {
+        Self::new(value)
+    }
+}
+
+impl fmt::Debug for SharedStr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl PartialEq for SharedStr {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for SharedStr {}
+
+/// Hashes like the `str` it holds, so it hashes equal to a `String` with the same content.
+impl Hash for SharedStr {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state);
+    }
+}
+
+impl serde::Serialize for SharedStr {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+/// A deserialized shared string owns a copy of its content.
+impl<'de> serde::Deserialize<'de> for SharedStr {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(Self::from)
+    }
+}
+
+/// `None` becomes Python's `None`.
+impl<T: Into<MontyObject>> From<Option<T>> for MontyObject {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Self::None, Into::into)
+    }
+}
+
+/// Creates a Python list. Use `MontyObject::Bytes` for `bytes`, since `Vec<u8>` becomes a list of ints.
+impl<T: Into<MontyObject>> From<Vec<T>> for MontyObject {
+    fn from(value: Vec<T>) -> Self {
+        Self::List(value.into_iter().map(Into::into).collect())
+    }
+}
+
+/// Creates a Python dict, in the map's iteration order.
+impl<K: Into<MontyObject>, V: Into<MontyObject>, S> From<HashMap<K, V, S>> for MontyObject {
+    fn from(value: HashMap<K, V, S>) -> Self {
+        Self::Dict(value.into_iter().map(|(k, v)| (k.into(), v.into())).collect())
+    }
+}
+
+/// Creates a Python dict, in key order.
+impl<K: Into<MontyObject>, V: Into<MontyObject>> From<BTreeMap<K, V>> for MontyObject {
+    fn from(value: BTreeMap<K, V>) -> Self {
+        Self::Dict(value.into_iter().map(|(k, v)| (k.into(), v.into())).collect())
+    }
+}
+
+impl From<DictPairs> for MontyObject {
+    fn from(value: DictPairs) -> Self {
+        Self::Dict(value)
+    }
+}
+
+/// Builds a [`MontyObject`] from JSON-like syntax, similar to `serde_json::json!`.
+///
+/// Lists use `[...]`, dicts use `{key: value, ...}` and `None` is Python's `None`. Any other
+/// expression is converted with `MontyObject::from`, so Rust tuples become Python tuples.
+///
+/// # Example
+/// ```
+/// use monty::{MontyObject, object};
+///
+/// let name = "monty";
+/// let obj = object!({"name": name, "tags": ["a", "b"], "size": (1, 2.5), "parent": None});
+/// assert_eq!(obj.py_repr(), "{'name': 'monty', 'tags': ['a', 'b'], 'size': (1, 2.5), 'parent': None}");
+/// ```
+#[macro_export]
+macro_rules! object {
+    // Internal: collect list items, each item is the tokens up to the next comma
+    (@list [$($done:expr,)*] ($($item:tt)*) , $($rest:tt)*) => {
+        $crate::object!(@list [$($done,)* $crate::object!($($item)*),] () $($rest)*)
+    };
+    (@list [$($done:expr,)*] ($($item:tt)*) $next:tt $($rest:tt)*) => {
+        $crate::object!(@list [$($done,)*] ($($item)* $next) $($rest)*)
+    };
+    (@list [$($done:expr,)*] ()) => {
+        ::std::vec![$($done,)*]
+    };
+    (@list [$($done:expr,)*] ($($item:tt)+)) => {
+        ::std::vec![$($done,)* $crate::object!($($item)+)]
+    };
+    // Internal: collect dict keys, each key is the tokens up to the next colon
+    (@key [$($done:expr,)*] ($($key:tt)*) : $($rest:tt)*) => {
+        $crate::object!(@value [$($done,)*] ($($key)*) () $($rest)*)
+    };
+    (@key [$($done:expr,)*] ($($key:tt)*) $next:tt $($rest:tt)*) => {
+        $crate::object!(@key [$($done,)*] ($($key)* $next) $($rest)*)
+    };
+    (@key [$($done:expr,)*] ()) => {{
+        let pairs: ::std::vec::Vec<($crate::MontyObject, $crate::MontyObject)> = ::std::vec![$($done,)*];
+        pairs
+    }};
+    // Internal: collect dict values, each value is the tokens up to the next comma
+    (@value [$($done:expr,)*] ($($key:tt)+) ($($value:tt)*) , $($rest:tt)*) => {
+        $crate::object!(@key [$($done,)* ($crate::object!($($key)+), $crate::object!($($value)*)),] () $($rest)*)
+    };
+    (@value [$($done:expr,)*] ($($key:tt)+) ($($value:tt)*) $next:tt $($rest:tt)*) => {
+        $crate::object!(@value [$($done,)*] ($($key)+) ($($value)* $next) $($rest)*)
+    };
+    (@value [$($done:expr,)*] ($($key:tt)+) ($($value:tt)+)) => {
+        $crate::object!(@key [$($done,)* ($crate::object!($($key)+), $crate::object!($($value)+)),] ())
+    };
+    (None) => {
+        $crate::MontyObject::None
+    };
+    ([$($items:tt)*]) => {
+        $crate::MontyObject::List($crate::object!(@list [] () $($items)*))
+    };
+    ({$($items:tt)*}) => {
+        $crate::MontyObject::dict($crate::object!(@key [] () $($items)*))
+    };
+    ($other:expr) => {
+        $crate::MontyObject::from($other)
+    };
+}
+
 /// A collection of key-value pairs representing Python dictionary contents.
 ///
 /// Used internally by `MontyObject::Dict` to store dictionary entries while preserving
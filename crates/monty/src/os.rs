@@ -18,6 +18,14 @@ use crate::{MontyObject, intern::StaticStrings};
 /// they require interacting with the operating system (filesystem, network, etc.).
 /// The host application decides whether to permit and execute these operations.
 ///
+/// # Host contract
+///
+/// `Path` methods receive the path as a `MontyObject::Path` first argument, followed by the
+/// arguments and keyword arguments of the method call exactly as written in the sandbox.
+/// Each variant documents the extra arguments and what the host should resume with; the
+/// returned value becomes the result of the method call. Failures should be reported as the
+/// `OSError` subclass CPython would raise, e.g. `FileNotFoundError` for a missing path.
+///
 /// # Extension
 ///
 /// When adding new operations, add both the variant here and update the
@@ -27,60 +35,110 @@ use crate::{MontyObject, intern::StaticStrings};
     Debug, Clone, Copy, PartialEq, Eq, Hash, strum::EnumString, strum::Display, serde::Serialize, serde::Deserialize,
 )]
 pub enum OsFunction {
-    /// Check if a path exists
+    /// Check if a path exists, following symlinks. Returns a bool.
     #[strum(serialize = "Path.exists")]
     Exists,
-    /// Check if path is a file
+    /// Check if path is a file, following symlinks. Returns a bool.
     #[strum(serialize = "Path.is_file")]
     IsFile,
-    /// Check if path is a directory
+    /// Check if path is a directory, following symlinks. Returns a bool.
     #[strum(serialize = "Path.is_dir")]
     IsDir,
-    /// Check if path is a symbolic link
+    /// Check if path is a symbolic link. Returns a bool.
     #[strum(serialize = "Path.is_symlink")]
     IsSymlink,
-    /// Read file contents as text
+    /// Read file contents as text. Returns a str.
     #[strum(serialize = "Path.read_text")]
     ReadText,
-    /// Read file contents as bytes
+    /// Read file contents as bytes. Returns bytes.
     #[strum(serialize = "Path.read_bytes")]
     ReadBytes,
-    /// Write text to file
+    /// Write text to file, called with `(path, data)`. Returns the number of characters written.
     #[strum(serialize = "Path.write_text")]
     WriteText,
-    /// Write bytes to file
+    /// Write bytes to file, called with `(path, data)`. Returns the number of bytes written.
     #[strum(serialize = "Path.write_bytes")]
     WriteBytes,
-    /// Create directory
+    /// Create directory, with optional `parents` and `exist_ok` keyword arguments. Returns `None`.
     #[strum(serialize = "Path.mkdir")]
     Mkdir,
-    /// Remove file
+    /// Remove file. Returns `None`.
     #[strum(serialize = "Path.unlink")]
     Unlink,
-    /// Remove directory
+    /// Remove an empty directory. Returns `None`.
     #[strum(serialize = "Path.rmdir")]
     Rmdir,
-    /// List directory contents
+    /// List directory contents. Returns a list of the entries as full `Path`s.
     #[strum(serialize = "Path.iterdir")]
     Iterdir,
-    /// Get file stats
+    /// Get file stats, following symlinks. Returns a stat result, see [`stat_result`].
     #[strum(serialize = "Path.stat")]
     Stat,
-    /// Rename/move file
+    /// Get file stats without following a final symlink, so a link describes itself.
+    /// Returns a stat result, see [`symlink_stat`].
+    #[strum(serialize = "Path.lstat")]
+    Lstat,
+    /// Rename/move file, called with `(path, target)`. Returns `None`.
     #[strum(serialize = "Path.rename")]
     Rename,
-    /// Get resolved absolute path
+    /// Copy a file's contents and permission bits, like `shutil.copy`, called with
+    /// `(path, target)` where `target` is the full destination path. Returns the target `Path`.
+    #[strum(serialize = "Path.copy")]
+    Copy,
+    /// Create a file if it doesn't exist, with optional `mode` and `exist_ok` keyword
+    /// arguments. An existing file is left unchanged apart from its modification time, unless
+    /// `exist_ok=False` in which case `FileExistsError` should be raised. Returns `None`.
+    #[strum(serialize = "Path.touch")]
+    Touch,
+    /// Change permission bits, called with `(path, mode)`. Returns `None`.
+    #[strum(serialize = "Path.chmod")]
+    Chmod,
+    /// Match a glob pattern relative to a directory, called with `(path, pattern)`.
+    /// Patterns use `*`, `?` and `[...]` within a component and `**` for any number of
+    /// directories. Returns a list of the matching `Path`s, in any order.
+    #[strum(serialize = "Path.glob")]
+    Glob,
+    /// Match a glob pattern in a directory and all its subdirectories, like `glob` with the
+    /// pattern prefixed by `**/`, called with `(path, pattern)`. Returns a list of `Path`s.
+    #[strum(serialize = "Path.rglob")]
+    Rglob,
+    /// Walk a directory tree, with optional `top_down` and `follow_symlinks` keyword arguments.
+    /// Returns a list of `(dirpath, dirnames, filenames)` tuples, one per directory, where
+    /// `dirpath` is a `Path` and the names are lists of str, in the order of `os.walk`.
+    #[strum(serialize = "Path.walk")]
+    Walk,
+    /// Read the target of a symbolic link. Returns the target as a `Path`, or raises `OSError`
+    /// if the path isn't a link.
+    #[strum(serialize = "Path.readlink")]
+    Readlink,
+    /// Make the path a symbolic link to `target`, called with `(path, target)` and an optional
+    /// `target_is_directory` keyword argument. Returns `None`.
+    #[strum(serialize = "Path.symlink_to")]
+    SymlinkTo,
+    /// Get resolved absolute path. Returns a str.
     #[strum(serialize = "Path.resolve")]
     Resolve,
-    /// Get absolute path (without resolving symlinks)
+    /// Get absolute path (without resolving symlinks). Returns a str.
     #[strum(serialize = "Path.absolute")]
     Absolute,
-    /// Get an environment variable value
+    /// Get an environment variable value, called with `(key, default)`.
+    /// Returns the value as a str, or `default` if it isn't set.
     #[strum(serialize = "os.getenv")]
     Getenv,
-    /// Get the entire environment as a dictionary
+    /// Get the entire environment as a dictionary of str to str.
     #[strum(serialize = "os.environ")]
     GetEnviron,
+    /// Create scratch space for the `tempfile` module, called with `(is_dir, suffix, prefix, dir)`
+    /// where the last three are str, `Path` or `None`. The host picks an unused name,
+    /// `prefix` + unique part + `suffix`, in `dir` or its own scratch directory if that's `None`,
+    /// and creates an empty directory or file there. Returns [`temporary_directory`] or
+    /// [`named_temporary_file`] respectively, holding the new path.
+    #[strum(serialize = "tempfile.scratch_path")]
+    ScratchPath,
+    /// Read a line of input from the user, from the `input()` builtin, called with the prompt
+    /// if one was given. Returns the line without its newline, or raises `EOFError`.
+    #[strum(serialize = "input")]
+    Input,
 }
 
 impl TryFrom<StaticStrings> for OsFunction {
@@ -99,7 +157,12 @@ impl TryFrom<StaticStrings> for OsFunction {
             StaticStrings::ReadText => Ok(Self::ReadText),
             StaticStrings::ReadBytes => Ok(Self::ReadBytes),
             StaticStrings::StatMethod => Ok(Self::Stat),
+            StaticStrings::Lstat => Ok(Self::Lstat),
             StaticStrings::Iterdir => Ok(Self::Iterdir),
+            StaticStrings::Glob => Ok(Self::Glob),
+            StaticStrings::Rglob => Ok(Self::Rglob),
+            StaticStrings::Walk => Ok(Self::Walk),
+            StaticStrings::Readlink => Ok(Self::Readlink),
             StaticStrings::Resolve => Ok(Self::Resolve),
             StaticStrings::Absolute => Ok(Self::Absolute),
             // Write operations
@@ -109,6 +172,10 @@ impl TryFrom<StaticStrings> for OsFunction {
             StaticStrings::Unlink => Ok(Self::Unlink),
             StaticStrings::Rmdir => Ok(Self::Rmdir),
             StaticStrings::Rename => Ok(Self::Rename),
+            StaticStrings::Copy => Ok(Self::Copy),
+            StaticStrings::Touch => Ok(Self::Touch),
+            StaticStrings::Chmod => Ok(Self::Chmod),
+            StaticStrings::SymlinkTo => Ok(Self::SymlinkTo),
             _ => Err(()),
         }
     }
@@ -213,3 +280,30 @@ pub fn stat_result(
         ],
     }
 }
+
+// =============================================================================
+// tempfile results
+// =============================================================================
+// The `tempfile` shims return a namedtuple with the `name` attribute of CPython's
+// objects, the path the host created. Scratch space is owned by the host, so there
+// is no `cleanup()`; the host removes it when it sees fit.
+
+/// Creates the result of `tempfile.TemporaryDirectory()` for the directory at `name`.
+#[must_use]
+pub fn temporary_directory(name: impl Into<String>) -> MontyObject {
+    scratch_result("TemporaryDirectory", name.into())
+}
+
+/// Creates the result of `tempfile.NamedTemporaryFile()` for the file at `name`.
+#[must_use]
+pub fn named_temporary_file(name: impl Into<String>) -> MontyObject {
+    scratch_result("NamedTemporaryFile", name.into())
+}
+
+fn scratch_result(type_name: &str, name: String) -> MontyObject {
+    MontyObject::NamedTuple {
+        type_name: type_name.to_owned(),
+        field_names: vec!["name".to_owned()],
+        values: vec![MontyObject::String(name)],
+    }
+}
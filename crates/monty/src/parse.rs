@@ -6,7 +6,7 @@ use ruff_python_ast::{
     InterpolatedStringElement, Keyword, Number, Operator as AstOperator, ParameterWithDefault, Stmt, UnaryOp,
     name::Name,
 };
-use ruff_python_parser::parse_module;
+use ruff_python_parser::{ParseErrorType, parse_module};
 use ruff_text_size::{Ranged, TextRange};
 
 use crate::{
@@ -15,10 +15,13 @@ use crate::{
     exception_private::ExcType,
     exception_public::{CodeLoc, MontyException},
     expressions::{
-        Callable, CmpOperator, Comprehension, Expr, ExprLoc, Identifier, Literal, Node, Operator, UnpackTarget,
+        Callable, CmpOperator, Comprehension, DictItem, Expr, ExprLoc, Identifier, Literal, Node, Operator,
+        UnpackTarget,
     },
-    fstring::{ConversionFlag, FStringPart, FormatSpec},
+    fstring::{ConversionFlag, FStringPart, FormatSpec, FormatSpecError},
     intern::{InternerBuilder, StringId},
+    resource::ResourceLimits,
+    string_literal::escape_error,
     value::EitherStr,
 };
 
@@ -91,6 +94,8 @@ pub struct RawFunctionDef {
     pub body: Vec<ParseNode>,
     /// Whether this is an async function (`async def`).
     pub is_async: bool,
+    /// Decorator expressions in source order (outermost first).
+    pub decorators: Vec<ExprLoc>,
 }
 
 /// Type alias for parsed AST nodes (output of the parser).
@@ -133,6 +138,20 @@ pub(crate) fn parse(code: &str, filename: &str) -> Result<ParseResult, ParseErro
     parse_with_interner(code, filename, InternerBuilder::new(code))
 }
 
+/// Parses code enforcing the compile-time limits `max_parse_depth` and `max_ast_nodes`.
+pub(crate) fn parse_with_limits(
+    code: &str,
+    filename: &str,
+    limits: &ResourceLimits,
+) -> Result<ParseResult, ParseError> {
+    let mut parser = Parser::new(code, filename, InternerBuilder::new(code));
+    if let Some(max_depth) = limits.max_parse_depth {
+        parser.depth_remaining = parser.depth_remaining.min(u16::try_from(max_depth).unwrap_or(u16::MAX));
+    }
+    parser.max_nodes = limits.max_ast_nodes;
+    parse_with_parser(code, parser)
+}
+
 /// Parses code using a caller-provided interner seed.
 ///
 /// This enables incremental compilation flows (e.g. REPL) where existing
@@ -142,8 +161,39 @@ pub(crate) fn parse_with_interner(
     filename: &str,
     interner: InternerBuilder,
 ) -> Result<ParseResult, ParseError> {
-    let mut parser = Parser::new(code, filename, interner);
-    let parsed = parse_module(code).map_err(|e| ParseError::syntax(e.to_string(), parser.convert_range(e.range())))?;
+    parse_with_parser(code, Parser::new(code, filename, interner))
+}
+
+/// Parses code in strict identity mode, only to report `is` comparisons against literals.
+///
+/// CPython warns about `x is 5` with a `SyntaxWarning` because whether two equal literals are
+/// the same object is an implementation detail. Monty derives the id of ints, floats and
+/// interned strings from their value, so such comparisons are always true where CPython's
+/// result depends on caching. Here the warning is raised as a `SyntaxError` with CPython's
+/// message, as `python -W error` would.
+pub(crate) fn check_identity_literals(code: &str, filename: &str) -> Result<(), ParseError> {
+    let mut parser = Parser::new(code, filename, InternerBuilder::new(code));
+    parser.strict_identity = true;
+    parse_with_parser(code, parser).map(|_| ())
+}
+
+fn parse_with_parser(code: &str, mut parser: Parser<'_>) -> Result<ParseResult, ParseError> {
+    let parsed = parse_module(code).map_err(|e| {
+        // ruff's messages for malformed escapes differ from CPython's, rebuild them
+        if let Some(escape) = escape_error(code, e.range().start().into()) {
+            return ParseError::syntax(escape.message, parser.convert_range(escape.range));
+        }
+        // ruff rejects `b'a' 'b'` with its own wording
+        if let ParseErrorType::OtherError(msg) = &e.error
+            && msg == "Bytes literal cannot be mixed with non-bytes literals"
+        {
+            return ParseError::syntax(
+                "cannot mix bytes and nonbytes literals",
+                parser.convert_range(e.range()),
+            );
+        }
+        ParseError::syntax(e.to_string(), parser.convert_range(e.range()))
+    })?;
     let module = parsed.into_syntax();
     let nodes = parser.parse_statements(module.body)?;
     Ok(ParseResult {
@@ -167,6 +217,12 @@ pub struct Parser<'a> {
     /// Starts at MAX_NESTING_DEPTH and decrements on each nested level.
     /// When it reaches zero, we return a "too many nested parentheses" error.
     depth_remaining: u16,
+    /// Number of statements and expressions parsed so far, checked against `max_nodes`.
+    node_count: usize,
+    /// Maximum number of statements and expressions, `None` for no limit.
+    max_nodes: Option<usize>,
+    /// Whether `is` and `is not` comparisons against literals are syntax errors.
+    strict_identity: bool,
 }
 
 impl<'a> Parser<'a> {
@@ -185,6 +241,9 @@ impl<'a> Parser<'a> {
             filename_id,
             interner,
             depth_remaining: MAX_NESTING_DEPTH,
+            node_count: 0,
+            max_nodes: None,
+            strict_identity: false,
         }
     }
 
@@ -264,6 +323,11 @@ impl<'a> Parser<'a> {
                 };
 
                 let name = self.identifier(&function.name.id, function.name.range);
+                let decorators = function
+                    .decorator_list
+                    .into_iter()
+                    .map(|decorator| self.parse_expression(decorator.expression))
+                    .collect::<Result<Vec<_>, _>>()?;
                 // Parse function body recursively
                 let body = self.parse_statements(function.body)?;
                 let is_async = function.is_async;
@@ -273,6 +337,7 @@ impl<'a> Parser<'a> {
                     signature,
                     body,
                     is_async,
+                    decorators,
                 }))
             }
             Stmt::ClassDef(c) => Err(ParseError::not_implemented(
@@ -283,10 +348,19 @@ impl<'a> Parser<'a> {
                 Some(value) => Ok(Node::Return(self.parse_expression(*value)?)),
                 None => Ok(Node::ReturnNone),
             },
-            Stmt::Delete(d) => Err(ParseError::not_implemented(
-                "the 'del' statement",
-                self.convert_range(d.range),
-            )),
+            Stmt::Delete(ast::StmtDelete { targets, .. }) => {
+                let targets = targets
+                    .into_iter()
+                    .map(|target| match target {
+                        AstExpr::Name(_) => self.parse_identifier(target),
+                        other => Err(ParseError::not_implemented(
+                            "'del' of attributes and subscripts",
+                            self.convert_range(other.range()),
+                        )),
+                    })
+                    .collect::<Result<_, _>>()?;
+                Ok(Node::Delete { targets })
+            }
             Stmt::TypeAlias(t) => Err(ParseError::not_implemented("type aliases", self.convert_range(t.range))),
             Stmt::Assign(ast::StmtAssign {
                 targets, value, range, ..
@@ -710,25 +784,22 @@ impl<'a> Parser<'a> {
             )),
             AstExpr::Dict(ast::ExprDict { items, range, .. }) => {
                 let position = self.convert_range(range);
-                let mut pairs = Vec::new();
+                let mut dict_items = Vec::with_capacity(items.len());
                 for ast::DictItem { key, value } in items {
-                    // key is Option<Expr> - None represents ** unpacking which we don't support yet
+                    // key is Option<Expr> - None represents `**mapping` unpacking
                     if let Some(key_expr_ast) = key {
                         let key_expr = self.parse_expression(key_expr_ast)?;
                         let value_expr = self.parse_expression(value)?;
-                        pairs.push((key_expr, value_expr));
+                        dict_items.push(DictItem::Pair(key_expr, value_expr));
                     } else {
-                        return Err(ParseError::not_implemented(
-                            "dictionary unpacking in literals",
-                            position,
-                        ));
+                        dict_items.push(DictItem::Unpack(self.parse_expression(value)?));
                     }
                 }
-                Ok(ExprLoc::new(position, Expr::Dict(pairs)))
+                Ok(ExprLoc::new(position, Expr::Dict(dict_items)))
             }
             AstExpr::Set(ast::ExprSet { elts, range, .. }) => {
-                let elements: Result<Vec<_>, _> = elts.into_iter().map(|e| self.parse_expression(e)).collect();
-                Ok(ExprLoc::new(self.convert_range(range), Expr::Set(elements?)))
+                let elements = self.parse_display_elements(elts)?;
+                Ok(ExprLoc::new(self.convert_range(range), Expr::Set(elements)))
             }
             AstExpr::ListComp(ast::ExprListComp {
                 elt, generators, range, ..
@@ -798,6 +869,9 @@ impl<'a> Parser<'a> {
                 ..
             }) => {
                 let position = self.convert_range(range);
+                if self.strict_identity {
+                    check_identity_literal(&left, &ops, &comparators, position)?;
+                }
                 let ops_vec = ops.into_vec();
                 let comparators_vec = comparators.into_vec();
 
@@ -813,7 +887,7 @@ impl<'a> Parser<'a> {
                     ));
                 }
 
-                // Chain comparison: transform to nested And expressions
+                // Chain comparison: each operand is evaluated once, short-circuiting on the first false result
                 self.parse_chain_comparison(*left, ops_vec, comparators_vec, position)
             }
             AstExpr::Call(ast::ExprCall {
@@ -821,30 +895,31 @@ impl<'a> Parser<'a> {
             }) => {
                 let position = self.convert_range(range);
                 let ast::Arguments { args, keywords, .. } = arguments;
-                let mut positional_args = Vec::new();
-                let mut var_args_expr: Option<ExprLoc> = None;
-                let mut seen_star = false;
-
-                for arg_expr in args.into_vec() {
-                    match arg_expr {
-                        AstExpr::Starred(ast::ExprStarred { value, .. }) => {
-                            if var_args_expr.is_some() {
-                                return Err(ParseError::not_implemented("multiple *args unpacking", position));
-                            }
-                            var_args_expr = Some(self.parse_expression(*value)?);
-                            seen_star = true;
-                        }
-                        other => {
-                            if seen_star {
-                                return Err(ParseError::not_implemented(
-                                    "positional arguments after *args unpacking",
-                                    position,
-                                ));
-                            }
-                            positional_args.push(self.parse_expression(other)?);
-                        }
-                    }
-                }
+                let mut args = args.into_vec();
+                // Everything from the first `*args` onwards is passed as a single var_args expression
+                let first_star = args
+                    .iter()
+                    .position(|arg| matches!(arg, AstExpr::Starred(_)))
+                    .unwrap_or(args.len());
+                let rest = args.split_off(first_star);
+                let positional_args = args
+                    .into_iter()
+                    .map(|arg| self.parse_expression(arg))
+                    .collect::<Result<Vec<_>, ParseError>>()?;
+                let mut rest = self.parse_display_elements(rest)?;
+                let var_args_expr = if rest.len() > 1 {
+                    // `f(*a, *b)` or `f(*a, x)`: pack the remaining arguments into a tuple
+                    Some(ExprLoc::new(position, Expr::Tuple(rest)))
+                } else if let Some(ExprLoc {
+                    expr: Expr::Starred(value),
+                    ..
+                }) = rest.pop()
+                {
+                    // A single `*args` is passed through directly
+                    Some(*value)
+                } else {
+                    None
+                };
                 // Separate regular kwargs (key=value) from var_kwargs (**expr)
                 let (kwargs, var_kwargs) = self.parse_keywords(keywords.into_vec())?;
                 let args = ArgExprs::new_with_var_kwargs(positional_args, var_args_expr, kwargs, var_kwargs);
@@ -971,18 +1046,12 @@ impl<'a> Parser<'a> {
                 Ok(ExprLoc::new(position, expr))
             }
             AstExpr::List(ast::ExprList { elts, range, .. }) => {
-                let items = elts
-                    .into_iter()
-                    .map(|f| self.parse_expression(f))
-                    .collect::<Result<_, ParseError>>()?;
+                let items = self.parse_display_elements(elts)?;
 
                 Ok(ExprLoc::new(self.convert_range(range), Expr::List(items)))
             }
             AstExpr::Tuple(ast::ExprTuple { elts, range, .. }) => {
-                let items = elts
-                    .into_iter()
-                    .map(|f| self.parse_expression(f))
-                    .collect::<Result<_, ParseError>>()?;
+                let items = self.parse_display_elements(elts)?;
 
                 Ok(ExprLoc::new(self.convert_range(range), Expr::Tuple(items)))
             }
@@ -1016,6 +1085,19 @@ impl<'a> Parser<'a> {
     ///
     /// Returns `(kwargs, var_kwargs)` where kwargs is a vec of named keyword arguments
     /// and var_kwargs is an optional expression for `**expr` unpacking.
+    /// Parses the elements of a list, tuple or set display, allowing `*iterable` elements.
+    fn parse_display_elements(&mut self, elts: Vec<AstExpr>) -> Result<Vec<ExprLoc>, ParseError> {
+        elts.into_iter()
+            .map(|elt| match elt {
+                AstExpr::Starred(ast::ExprStarred { value, range, .. }) => {
+                    let value = self.parse_expression(*value)?;
+                    Ok(ExprLoc::new(self.convert_range(range), Expr::Starred(Box::new(value))))
+                }
+                other => self.parse_expression(other),
+            })
+            .collect()
+    }
+
     fn parse_keywords(&mut self, keywords: Vec<Keyword>) -> Result<(Vec<Kwarg>, Option<ExprLoc>), ParseError> {
         let mut kwargs = Vec::new();
         let mut var_kwargs = None;
@@ -1211,28 +1293,31 @@ impl<'a> Parser<'a> {
     /// F-strings in ruff AST are represented as `FStringValue` containing
     /// `FStringPart`s, which can be either literal strings or `FString`
     /// interpolated sections. Each `FString` contains `InterpolatedStringElements`.
+    ///
+    /// Implicitly concatenated strings arrive as several `FStringPart`s, e.g.
+    /// `f'a{x}' 'b' r'\d'`. Adjacent literal text is merged into one part, across
+    /// those boundaries too, so the VM only concatenates around interpolations.
     fn parse_fstring(&mut self, value: &ast::FStringValue, range: TextRange) -> Result<ExprLoc, ParseError> {
         let mut parts = Vec::new();
+        let mut literal = String::new();
 
         for fstring_part in value {
             match fstring_part {
-                ast::FStringPart::Literal(lit) => {
-                    // Literal string segment - intern for use at runtime
-                    let processed = lit.value.to_string();
-                    if !processed.is_empty() {
-                        let string_id = self.interner.intern(&processed);
-                        parts.push(FStringPart::Literal(string_id));
-                    }
-                }
+                ast::FStringPart::Literal(lit) => literal.push_str(&lit.value),
                 ast::FStringPart::FString(fstring) => {
-                    // Interpolated f-string section
                     for element in &fstring.elements {
-                        let part = self.parse_fstring_element(element)?;
-                        parts.push(part);
+                        match element {
+                            InterpolatedStringElement::Literal(lit) => literal.push_str(&lit.value),
+                            InterpolatedStringElement::Interpolation(interp) => {
+                                self.push_fstring_literal(&mut parts, &mut literal);
+                                parts.push(self.parse_interpolation(interp)?);
+                            }
+                        }
                     }
                 }
             }
         }
+        self.push_fstring_literal(&mut parts, &mut literal);
 
         // Optimization: if only one literal part, return as simple string literal
         if parts.len() == 1
@@ -1247,38 +1332,37 @@ impl<'a> Parser<'a> {
         Ok(ExprLoc::new(self.convert_range(range), Expr::FString(parts)))
     }
 
-    /// Parses a single f-string element (literal or interpolation).
-    fn parse_fstring_element(&mut self, element: &InterpolatedStringElement) -> Result<FStringPart, ParseError> {
-        match element {
-            InterpolatedStringElement::Literal(lit) => {
-                // Intern the literal string for use at runtime
-                let processed = lit.value.to_string();
-                let string_id = self.interner.intern(&processed);
-                Ok(FStringPart::Literal(string_id))
-            }
-            InterpolatedStringElement::Interpolation(interp) => {
-                let expr = Box::new(self.parse_expression((*interp.expression).clone())?);
-                let conversion = convert_conversion_flag(interp.conversion);
-                let format_spec = match &interp.format_spec {
-                    Some(spec) => Some(self.parse_format_spec(spec)?),
-                    None => None,
-                };
-                // Extract debug prefix for `=` specifier (e.g., f'{a=}' -> "a=")
-                let debug_prefix = interp.debug_text.as_ref().map(|dt| {
-                    let expr_text = &self.code[interp.expression.range()];
-                    self.interner
-                        .intern(&format!("{}{}{}", dt.leading, expr_text, dt.trailing))
-                });
-                Ok(FStringPart::Interpolation {
-                    expr,
-                    conversion,
-                    format_spec,
-                    debug_prefix,
-                })
-            }
+    /// Interns the pending literal text of an f-string as a part, leaving `literal` empty.
+    fn push_fstring_literal(&mut self, parts: &mut Vec<FStringPart>, literal: &mut String) {
+        if !literal.is_empty() {
+            let string_id = self.interner.intern(literal);
+            parts.push(FStringPart::Literal(string_id));
+            literal.clear();
         }
     }
 
+    /// Parses a single f-string interpolation, e.g. `{x!r:>10}`.
+    fn parse_interpolation(&mut self, interp: &ast::InterpolatedElement) -> Result<FStringPart, ParseError> {
+        let expr = Box::new(self.parse_expression((*interp.expression).clone())?);
+        let conversion = convert_conversion_flag(interp.conversion);
+        let format_spec = match &interp.format_spec {
+            Some(spec) => Some(self.parse_format_spec(spec)?),
+            None => None,
+        };
+        // Extract debug prefix for `=` specifier (e.g., f'{a=}' -> "a=")
+        let debug_prefix = interp.debug_text.as_ref().map(|dt| {
+            let expr_text = &self.code[interp.expression.range()];
+            self.interner
+                .intern(&format!("{}{}{}", dt.leading, expr_text, dt.trailing))
+        });
+        Ok(FStringPart::Interpolation {
+            expr,
+            conversion,
+            format_spec,
+            debug_prefix,
+        })
+    }
+
     /// Parses a format specification, which may contain nested interpolations.
     ///
     /// For static specs (no interpolations), parses the format string into a
@@ -1316,22 +1400,24 @@ impl<'a> Parser<'a> {
         } else {
             // Combine all literal parts into a single static string and parse at parse time
             let static_spec: String = parts
-                .into_iter()
+                .iter()
                 .filter_map(|p| {
                     if let FStringPart::Literal(string_id) = p {
-                        Some(self.interner.get_str(string_id).to_owned())
+                        Some(self.interner.get_str(*string_id))
                     } else {
                         None
                     }
                 })
                 .collect();
-            let parsed = static_spec.parse().map_err(|spec_str| {
-                ParseError::syntax(
+            match static_spec.parse() {
+                Ok(parsed) => Ok(FormatSpec::Static(parsed)),
+                Err(FormatSpecError::Invalid(spec_str)) => Err(ParseError::syntax(
                     format!("Invalid format specifier '{spec_str}'"),
                     self.convert_range(spec.range),
-                )
-            })?;
-            Ok(FormatSpec::Static(parsed))
+                )),
+                // CPython raises these when formatting, so leave the spec to be parsed at runtime
+                Err(FormatSpecError::Grouping(_)) => Ok(FormatSpec::Dynamic(parts)),
+            }
         }
     }
 
@@ -1367,9 +1453,19 @@ impl<'a> Parser<'a> {
         (self.line_ends.len(), line_start, None)
     }
 
-    /// Decrements the depth remaining for nested parentheses.
-    /// Returns an error if the depth remaining goes to zero.
+    /// Decrements the depth remaining for nested parentheses, and counts the node against `max_nodes`.
+    /// Returns an error if the depth remaining goes to zero or there are too many nodes.
     fn decr_depth_remaining(&mut self, get_range: impl FnOnce() -> TextRange) -> Result<(), ParseError> {
+        self.node_count += 1;
+        if let Some(max_nodes) = self.max_nodes
+            && self.node_count > max_nodes
+        {
+            let position = self.convert_range(get_range());
+            return Err(ParseError::syntax(
+                format!("code is too complex, more than {max_nodes} statements and expressions"),
+                position,
+            ));
+        }
         if let Some(depth_remaining) = self.depth_remaining.checked_sub(1) {
             self.depth_remaining = depth_remaining;
             Ok(())
@@ -1393,6 +1489,62 @@ fn first<T: fmt::Debug>(v: Vec<T>, position: CodeRange) -> Result<T, ParseError>
     }
 }
 
+/// Rejects `is` and `is not` comparisons where either operand is a literal.
+///
+/// Matches CPython's `SyntaxWarning`: `"is" with 'int' literal. Did you mean "=="?`
+fn check_identity_literal(
+    left: &AstExpr,
+    ops: &[CmpOp],
+    comparators: &[AstExpr],
+    position: CodeRange,
+) -> Result<(), ParseError> {
+    let mut left = left;
+    for (op, right) in ops.iter().zip(comparators) {
+        if matches!(op, CmpOp::Is | CmpOp::IsNot)
+            && let Some(type_name) = literal_type_name(left).or_else(|| literal_type_name(right))
+        {
+            let msg = if *op == CmpOp::Is {
+                format!("\"is\" with '{type_name}' literal. Did you mean \"==\"?")
+            } else {
+                format!("\"is not\" with '{type_name}' literal. Did you mean \"!=\"?")
+            };
+            return Err(ParseError::syntax(msg, position));
+        }
+        left = right;
+    }
+    Ok(())
+}
+
+/// Returns the type name of a literal whose identity is an implementation detail.
+///
+/// `None`, `True`, `False` and `...` are singletons so comparing them with `is` is fine. Like
+/// CPython's constant folding, signed numbers and tuples of constants count as literals.
+fn literal_type_name(expr: &AstExpr) -> Option<&'static str> {
+    match expr {
+        AstExpr::NumberLiteral(n) => Some(match n.value {
+            Number::Int(_) => "int",
+            Number::Float(_) => "float",
+            Number::Complex { .. } => "complex",
+        }),
+        AstExpr::StringLiteral(_) => Some("str"),
+        AstExpr::BytesLiteral(_) => Some("bytes"),
+        AstExpr::UnaryOp(u) if u.op != UnaryOp::Not => match &*u.operand {
+            operand @ (AstExpr::NumberLiteral(_) | AstExpr::UnaryOp(_)) => literal_type_name(operand),
+            _ => None,
+        },
+        AstExpr::Tuple(t) if t.elts.iter().all(is_constant) => Some("tuple"),
+        _ => None,
+    }
+}
+
+/// Returns whether `expr` is folded to a constant, which is what makes a tuple a literal.
+fn is_constant(expr: &AstExpr) -> bool {
+    matches!(
+        expr,
+        AstExpr::NoneLiteral(_) | AstExpr::BooleanLiteral(_) | AstExpr::EllipsisLiteral(_)
+    ) || literal_type_name(expr).is_some()
+}
+
 fn convert_op(op: AstOperator) -> Operator {
     match op {
         AstOperator::Add => Operator::Add,
@@ -1582,6 +1734,124 @@ impl ParseError {
     }
 }
 
+/// How a traceback underlines the source range of a frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Carets {
+    /// No caret line is shown.
+    Hidden,
+    /// `^` under the whole range.
+    Plain,
+    /// `~` under the range with `^` under the 1-based columns `start..end`, which mark the
+    /// operator of a binary operation or the brackets of a call or subscript.
+    Anchored { start: u16, end: u16 },
+}
+
+/// Decides how to underline `position` in a traceback, following CPython's `traceback` module.
+///
+/// Like CPython, this works from the source line: binary operations, calls and subscripts get
+/// anchors, other ranges are underlined plainly unless they cover the whole line. A call to a
+/// plain name making up the whole value of a `return` or of an assignment to a single name,
+/// like `return f(x)` or `y = f(x)`, is never underlined since that would only repeat the line.
+pub(crate) fn traceback_carets(source: &str, position: CodeRange) -> Carets {
+    let Some(line) = position
+        .preview_line_number()
+        .and_then(|line_no| source.lines().nth(line_no as usize))
+    else {
+        return Carets::Plain;
+    };
+    // Columns are 1-based byte offsets into the line
+    let start = usize::from(position.start().column).saturating_sub(1);
+    let end = usize::from(position.end().column).saturating_sub(1);
+    let Some(segment) = line.get(start..end) else {
+        return Carets::Plain;
+    };
+    if is_whole_call_value(line, start, end) {
+        return Carets::Hidden;
+    }
+    if let Some((anchor_start, anchor_end)) = caret_anchors(segment) {
+        let column = |offset: usize| u16::try_from(start + offset + 1).expect("column exceeds u16");
+        return Carets::Anchored {
+            start: column(anchor_start),
+            end: column(anchor_end),
+        };
+    }
+    if line[..start].trim().is_empty() && line[end..].trim().is_empty() {
+        Carets::Hidden
+    } else {
+        Carets::Plain
+    }
+}
+
+/// Returns whether `line[start..end]` is a call to a plain name making up the whole value of a
+/// `return` or of an assignment to a single name.
+fn is_whole_call_value(line: &str, start: usize, end: usize) -> bool {
+    let statement = line.trim_start();
+    let Ok(parsed) = parse_module(statement) else {
+        return false;
+    };
+    let value = match parsed.syntax().body.first() {
+        Some(Stmt::Return(ret)) => ret.value.as_deref(),
+        Some(Stmt::Assign(assign)) if matches!(&*assign.targets, [AstExpr::Name(_)]) => Some(&*assign.value),
+        _ => None,
+    };
+    let Some(AstExpr::Call(call)) = value else {
+        return false;
+    };
+    let indent = line.len() - statement.len();
+    matches!(*call.func, AstExpr::Name(_))
+        && indent + call.range.start().to_usize() == start
+        && indent + call.range.end().to_usize() == end
+}
+
+/// Finds the byte range of `segment` that CPython marks with `^`: the operator of a binary
+/// operation, or everything from the opening bracket of a call or subscript.
+fn caret_anchors(segment: &str) -> Option<(usize, usize)> {
+    // Parenthesize so the segment parses as a single expression, as CPython does
+    const PREFIX: usize = "(\n".len();
+    let parsed = parse_module(&format!("(\n{segment}\n)")).ok()?;
+    let [Stmt::Expr(stmt)] = parsed.syntax().body.as_slice() else {
+        return None;
+    };
+    let offset = |range: TextRange| (range.start().to_usize() - PREFIX, range.end().to_usize() - PREFIX);
+    let bytes = segment.as_bytes();
+    // Skips forward from `from` to the first byte matching `stop`, giving up at a comment or line continuation
+    let find = |from: usize, stop: &dyn Fn(u8) -> bool| {
+        (from..bytes.len())
+            .take_while(|&i| !matches!(bytes[i], b'#' | b'\\'))
+            .find(|&i| stop(bytes[i]))
+    };
+    match &*stmt.value {
+        AstExpr::BinOp(bin_op) => {
+            let (_, left_end) = offset(bin_op.left.range());
+            let (right_start, _) = offset(bin_op.right.range());
+            let op_start = find(left_end, &|b| !b.is_ascii_whitespace() && b != b')')?;
+            // Operators are one or two characters long, like `+` or `//`
+            let second = op_start + 1;
+            let op_end = if second < bytes.len()
+                && second < right_start
+                && !bytes[second].is_ascii_whitespace()
+                && !matches!(bytes[second], b'#' | b'\\')
+            {
+                second + 1
+            } else {
+                second
+            };
+            Some((op_start, op_end))
+        }
+        AstExpr::Subscript(subscript) => {
+            let (_, value_end) = offset(subscript.value.range());
+            let (_, end) = offset(subscript.range);
+            Some((find(value_end, &|b| b == b'[')?, end))
+        }
+        AstExpr::Call(call) => {
+            let (_, func_end) = offset(call.func.range());
+            let (_, end) = offset(call.range);
+            Some((find(func_end, &|b| b == b'(')?, end))
+        }
+        _ => None,
+    }
+}
+
 /// Parses an integer literal string into a `BigInt`, handling radix prefixes and underscores.
 ///
 /// Supports Python integer literal formats:
@@ -0,0 +1,599 @@
+//! printf-style `%` formatting for `str` and `bytes`, e.g. `'%s: %d' % (name, n)`.
+//!
+//! The VM calls [`percent_format`] when `%` isn't numeric modulo. Arguments are consumed
+//! the way CPython's `PyUnicode_Format` consumes them: a tuple supplies one argument per
+//! conversion, any other value is a single argument, and `%(key)s` looks the key up in
+//! the right operand when it is a mapping. The looked-up value then replaces the
+//! arguments, which is why `'%(a)s %s' % {'a': 1}` runs out of arguments as in CPython.
+//!
+//! Numbers are formatted here for the integer conversions and through
+//! [`format_with_spec`] for the float conversions, so `'%.2f' % x` and `f'{x:.2f}'` agree.
+
+use num_bigint::BigInt;
+use num_traits::{FromPrimitive, Signed, ToPrimitive};
+
+use crate::{
+    exception_private::{ExcType, RunError, RunResult, SimpleException},
+    fstring::{ParsedFormatSpec, ascii_escape, format_with_spec},
+    heap::{Heap, HeapData},
+    intern::Interns,
+    resource::{DepthGuard, ResourceTracker, check_repeat_size},
+    types::{Bytes, PyTrait, Str, Type, r#type::big_int_to_f64},
+    value::Value,
+};
+
+/// Formats `lhs % rhs` when `lhs` is a `str` or `bytes`.
+///
+/// Returns `Ok(None)` for any other left operand so the caller can try other
+/// implementations of `%` before raising the usual `TypeError`.
+pub(crate) fn percent_format(
+    lhs: &Value,
+    rhs: &Value,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<Option<Value>> {
+    // Copied because looking up `%(key)s` arguments needs the heap mutably
+    let (template, is_bytes) = match lhs {
+        Value::InternString(id) => (interns.get_str(*id).as_bytes().to_vec(), false),
+        Value::InternBytes(id) => (interns.get_bytes(*id).to_vec(), true),
+        Value::Ref(id) => match heap.get(*id) {
+            HeapData::Str(s) => (s.as_str().as_bytes().to_vec(), false),
+            HeapData::Bytes(b) => (b.as_slice().to_vec(), true),
+            _ => return Ok(None),
+        },
+        _ => return Ok(None),
+    };
+
+    let arg_len = match tuple_items(rhs, heap) {
+        Some(items) => isize::try_from(items.len()).expect("tuple length fits in isize"),
+        None => -1,
+    };
+    let mut formatter = Formatter {
+        template: &template,
+        is_bytes,
+        rhs,
+        is_mapping: is_mapping(rhs, heap),
+        looked_up: None,
+        arg_len,
+        arg_index: if arg_len < 0 { -2 } else { 0 },
+    };
+    let result = formatter.format(heap, interns);
+    if let Some(value) = formatter.looked_up.take() {
+        value.drop_with_heap(heap);
+    }
+    let out = result?;
+
+    let data = if is_bytes {
+        HeapData::Bytes(Bytes::new(out))
+    } else {
+        let s = String::from_utf8(out).expect("formatting a str only produces UTF-8");
+        HeapData::Str(Str::from(s))
+    };
+    Ok(Some(Value::Ref(heap.allocate(data)?)))
+}
+
+/// The items of a tuple right operand, which supplies one argument per conversion.
+fn tuple_items<'h>(value: &Value, heap: &'h Heap<impl ResourceTracker>) -> Option<&'h [Value]> {
+    match value {
+        Value::Ref(id) => match heap.get(*id) {
+            HeapData::Tuple(t) => Some(t.as_slice()),
+            HeapData::NamedTuple(nt) => Some(nt.as_vec()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Whether `%(key)s` may look keys up in `value`.
+///
+/// Like CPython, this is any subscriptable type other than `tuple`, `str` and `bytes`,
+/// so `'x' % []` is not an error even though nothing is formatted.
+fn is_mapping(value: &Value, heap: &Heap<impl ResourceTracker>) -> bool {
+    match value {
+        Value::Ref(id) => matches!(
+            heap.get(*id),
+            HeapData::Dict(_) | HeapData::List(_) | HeapData::Range(_) | HeapData::Array(_)
+        ),
+        _ => false,
+    }
+}
+
+/// Where the argument for a conversion comes from.
+#[derive(Debug, Clone, Copy)]
+enum Arg {
+    /// The whole right operand, or the value of the last `%(key)` lookup.
+    Whole,
+    /// An item of a tuple right operand.
+    Item(usize),
+}
+
+/// The flags, width and precision of one conversion, e.g. `-08.3` in `%-08.3f`.
+#[derive(Debug, Default)]
+struct Spec {
+    left: bool,
+    plus: bool,
+    space: bool,
+    alternate: bool,
+    zero: bool,
+    width: usize,
+    precision: Option<usize>,
+}
+
+/// State for formatting one template, mirroring CPython's argument bookkeeping.
+///
+/// `arg_len` is -1 when the arguments are a single value, and `arg_index` then starts
+/// at -2 so exactly one conversion can consume it.
+struct Formatter<'a> {
+    template: &'a [u8],
+    is_bytes: bool,
+    rhs: &'a Value,
+    is_mapping: bool,
+    /// The value of the last `%(key)` lookup, which replaces the arguments.
+    looked_up: Option<Value>,
+    arg_len: isize,
+    arg_index: isize,
+}
+
+impl Formatter<'_> {
+    fn format(&mut self, heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<Vec<u8>> {
+        let template = self.template;
+        let mut out = Vec::with_capacity(template.len());
+        let mut pos = 0;
+        while let Some(offset) = template[pos..].iter().position(|&b| b == b'%') {
+            out.extend_from_slice(&template[pos..pos + offset]);
+            pos += offset + 1;
+            if template.get(pos) == Some(&b'%') {
+                out.push(b'%');
+                pos += 1;
+            } else {
+                pos = self.format_conversion(pos, &mut out, heap, interns)?;
+            }
+        }
+        out.extend_from_slice(&template[pos..]);
+
+        if self.arg_index < self.arg_len && !self.is_mapping {
+            let kind = if self.is_bytes { "bytes" } else { "string" };
+            return Err(ExcType::type_error(format!(
+                "not all arguments converted during {kind} formatting"
+            )));
+        }
+        Ok(out)
+    }
+
+    /// Formats the conversion starting at `pos` (just after the `%`), returning the
+    /// position after it.
+    fn format_conversion(
+        &mut self,
+        mut pos: usize,
+        out: &mut Vec<u8>,
+        heap: &mut Heap<impl ResourceTracker>,
+        interns: &Interns,
+    ) -> RunResult<usize> {
+        let template = self.template;
+        if template.get(pos) == Some(&b'(') {
+            pos = self.look_up_key(pos + 1, heap, interns)?;
+        }
+
+        let mut spec = Spec::default();
+        while let Some(&c) = template.get(pos) {
+            match c {
+                b'-' => spec.left = true,
+                b'+' => spec.plus = true,
+                b' ' => spec.space = true,
+                b'#' => spec.alternate = true,
+                b'0' => spec.zero = true,
+                _ => break,
+            }
+            pos += 1;
+        }
+
+        if template.get(pos) == Some(&b'*') {
+            pos += 1;
+            let width = self.star_arg(heap)?;
+            if width < 0 {
+                spec.left = true;
+            }
+            spec.width = usize::try_from(width.unsigned_abs()).unwrap_or(usize::MAX);
+        } else {
+            (spec.width, pos) = parse_number(template, pos, "width too big")?;
+        }
+        if template.get(pos) == Some(&b'.') {
+            pos += 1;
+            if template.get(pos) == Some(&b'*') {
+                pos += 1;
+                let precision = self.star_arg(heap)?;
+                spec.precision = Some(usize::try_from(precision).unwrap_or(0));
+            } else {
+                let precision;
+                (precision, pos) = parse_number(template, pos, "precision too big")?;
+                spec.precision = Some(precision);
+            }
+        }
+        // Length modifiers are accepted and ignored, as in CPython
+        while matches!(template.get(pos), Some(b'h' | b'l' | b'L')) {
+            pos += 1;
+        }
+
+        let Some(&conv) = template.get(pos) else {
+            return Err(SimpleException::new_msg(ExcType::ValueError, "incomplete format").into());
+        };
+        let arg = self.next_arg()?;
+        let heap: &Heap<_> = heap;
+        let value = self.arg(arg, heap);
+        match conv {
+            b's' | b'r' | b'a' => self.format_text(conv, value, &spec, out, heap, interns)?,
+            b'b' if self.is_bytes => self.format_text(conv, value, &spec, out, heap, interns)?,
+            b'c' => {
+                let c = self.char_arg(value, heap, interns)?;
+                pad(out, &c, 1, &spec, heap)?;
+            }
+            b'd' | b'i' | b'u' | b'o' | b'x' | b'X' => {
+                let n = integer_arg(value, conv, heap, interns)?;
+                format_integer(&n, conv, &spec, out, heap)?;
+            }
+            b'e' | b'E' | b'f' | b'F' | b'g' | b'G' => {
+                let f = float_arg(value, heap, interns)?;
+                check_repeat_size(1, spec.width.max(spec.precision.unwrap_or(0)), heap.tracker())?;
+                let format_spec = ParsedFormatSpec {
+                    fill: ' ',
+                    align: spec.left.then_some('<'),
+                    sign: if spec.plus {
+                        Some('+')
+                    } else if spec.space {
+                        Some(' ')
+                    } else {
+                        None
+                    },
+                    zero_pad: spec.zero && !spec.left,
+                    alternate: spec.alternate,
+                    width: spec.width,
+                    grouping: None,
+                    precision: Some(spec.precision.unwrap_or(6)),
+                    type_char: Some(char::from(conv)),
+                };
+                let mut guard = DepthGuard::default();
+                let text = format_with_spec(&Value::Float(f), &format_spec, heap, &mut guard, interns)?;
+                out.extend_from_slice(text.as_bytes());
+            }
+            _ => return Err(self.unsupported_conversion(pos)),
+        }
+        Ok(pos + 1)
+    }
+
+    /// Looks up the `%(key)` starting at `pos` (just after the `(`), returning the
+    /// position after the closing parenthesis. Parentheses inside the key must balance.
+    fn look_up_key(
+        &mut self,
+        pos: usize,
+        heap: &mut Heap<impl ResourceTracker>,
+        interns: &Interns,
+    ) -> RunResult<usize> {
+        if !self.is_mapping {
+            return Err(ExcType::type_error("format requires a mapping"));
+        }
+        let template = self.template;
+        let mut depth = 1;
+        let mut end = pos;
+        while end < template.len() {
+            match template[end] {
+                b'(' => depth += 1,
+                b')' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        break;
+                    }
+                }
+                _ => {}
+            }
+            end += 1;
+        }
+        if depth > 0 {
+            return Err(SimpleException::new_msg(ExcType::ValueError, "incomplete format key").into());
+        }
+
+        let key_bytes = &template[pos..end];
+        let key_data = if self.is_bytes {
+            HeapData::Bytes(Bytes::new(key_bytes.to_vec()))
+        } else {
+            let key = std::str::from_utf8(key_bytes).expect("key is split from a str at ASCII parentheses");
+            HeapData::Str(Str::from(key))
+        };
+        let key = Value::Ref(heap.allocate(key_data)?);
+        let result = self.rhs.py_getitem(&key, heap, interns);
+        key.drop_with_heap(heap);
+        let value = result?;
+
+        if let Some(previous) = self.looked_up.replace(value) {
+            previous.drop_with_heap(heap);
+        }
+        self.arg_len = -1;
+        self.arg_index = -2;
+        Ok(end + 1)
+    }
+
+    /// Takes the next argument, raising `TypeError` when there are none left.
+    fn next_arg(&mut self) -> RunResult<Arg> {
+        if self.arg_index >= self.arg_len {
+            return Err(ExcType::type_error("not enough arguments for format string"));
+        }
+        let index = self.arg_index;
+        self.arg_index += 1;
+        if self.arg_len < 0 {
+            Ok(Arg::Whole)
+        } else {
+            Ok(Arg::Item(usize::try_from(index).expect("tuple index is not negative")))
+        }
+    }
+
+    fn arg<'h>(&'h self, arg: Arg, heap: &'h Heap<impl ResourceTracker>) -> &'h Value {
+        match arg {
+            Arg::Whole => self.looked_up.as_ref().unwrap_or(self.rhs),
+            Arg::Item(index) => &tuple_items(self.rhs, heap).expect("items come from a tuple")[index],
+        }
+    }
+
+    /// Takes the next argument as a `*` width or precision.
+    fn star_arg(&mut self, heap: &Heap<impl ResourceTracker>) -> RunResult<i64> {
+        let arg = self.next_arg()?;
+        match self.arg(arg, heap) {
+            Value::Int(n) => Ok(*n),
+            Value::Bool(b) => Ok(i64::from(*b)),
+            _ => Err(ExcType::type_error("* wants int")),
+        }
+    }
+
+    /// Formats `%s`, `%r`, `%a` and, for bytes, `%b`.
+    fn format_text(
+        &self,
+        conv: u8,
+        value: &Value,
+        spec: &Spec,
+        out: &mut Vec<u8>,
+        heap: &Heap<impl ResourceTracker>,
+        interns: &Interns,
+    ) -> RunResult<()> {
+        let mut guard = DepthGuard::default();
+        let text = match conv {
+            b's' | b'b' if self.is_bytes => {
+                let Some(bytes) = bytes_arg(value, heap, interns) else {
+                    let value_type = value.py_type(heap);
+                    return Err(ExcType::type_error(format!(
+                        "%b requires a bytes-like object, or an object that implements __bytes__, not '{value_type}'"
+                    )));
+                };
+                bytes.to_vec()
+            }
+            b's' => value.py_str(heap, &mut guard, interns).into_owned().into_bytes(),
+            b'r' if !self.is_bytes => value.py_repr(heap, &mut guard, interns).into_owned().into_bytes(),
+            _ => ascii_escape(&value.py_repr(heap, &mut guard, interns)).into_bytes(),
+        };
+
+        if self.is_bytes {
+            let len = spec.precision.map_or(text.len(), |p| p.min(text.len()));
+            pad(out, &text[..len], len, spec, heap)
+        } else {
+            let text = String::from_utf8(text).expect("str and repr are UTF-8");
+            let (end, len) = match spec.precision {
+                Some(p) => text
+                    .char_indices()
+                    .nth(p)
+                    .map_or((text.len(), text.chars().count()), |(i, _)| (i, p)),
+                None => (text.len(), text.chars().count()),
+            };
+            pad(out, &text.as_bytes()[..end], len, spec, heap)
+        }
+    }
+
+    /// The encoded character for `%c`, from an int or a one character `str` (one byte `bytes`).
+    fn char_arg(&self, value: &Value, heap: &Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<Vec<u8>> {
+        let code = integer_value(value, heap, interns);
+        if self.is_bytes {
+            if let Some(code) = code {
+                return match code.to_u8() {
+                    Some(b) => Ok(vec![b]),
+                    None => Err(SimpleException::new_msg(ExcType::OverflowError, "%c arg not in range(256)").into()),
+                };
+            }
+            match bytes_arg(value, heap, interns) {
+                Some(&[b]) => Ok(vec![b]),
+                _ => Err(ExcType::type_error(
+                    "%c requires an integer in range(256) or a single byte",
+                )),
+            }
+        } else {
+            if let Some(code) = code {
+                return match code.to_u32().and_then(char::from_u32) {
+                    Some(c) => Ok(c.to_string().into_bytes()),
+                    None => {
+                        Err(SimpleException::new_msg(ExcType::OverflowError, "%c arg not in range(0x110000)").into())
+                    }
+                };
+            }
+            if let Some(s) = value.as_either_str(heap) {
+                let s = s.as_str(interns);
+                let mut chars = s.chars();
+                if let (Some(_), None) = (chars.next(), chars.next()) {
+                    return Ok(s.as_bytes().to_vec());
+                }
+            }
+            Err(ExcType::type_error("%c requires int or char"))
+        }
+    }
+
+    /// The `ValueError` for a conversion character that isn't supported, at byte `pos`.
+    ///
+    /// CPython reports the position and character in code points, showing anything but
+    /// printable ASCII as `?`.
+    fn unsupported_conversion(&self, pos: usize) -> RunError {
+        let (code, index) = if self.is_bytes {
+            (u32::from(self.template[pos]), pos)
+        } else {
+            let template = std::str::from_utf8(self.template).expect("template is a str");
+            let c = template[pos..].chars().next().expect("pos is at a character");
+            (u32::from(c), template[..pos].chars().count())
+        };
+        let shown = char::from_u32(code).filter(|c| (' '..='~').contains(c)).unwrap_or('?');
+        SimpleException::new_msg(
+            ExcType::ValueError,
+            format!("unsupported format character '{shown}' ({code:#x}) at index {index}"),
+        )
+        .into()
+    }
+}
+
+/// Parses a decimal width or precision at `pos`, returning it and the position after it.
+fn parse_number(template: &[u8], mut pos: usize, too_big: &'static str) -> RunResult<(usize, usize)> {
+    let mut n: usize = 0;
+    while let Some(&c) = template.get(pos).filter(|c| c.is_ascii_digit()) {
+        n = n
+            .checked_mul(10)
+            .and_then(|n| n.checked_add(usize::from(c - b'0')))
+            .ok_or_else(|| SimpleException::new_msg(ExcType::ValueError, too_big))?;
+        pos += 1;
+    }
+    Ok((n, pos))
+}
+
+/// Appends `text` (`len` characters long) padded with spaces to the spec's width.
+fn pad(out: &mut Vec<u8>, text: &[u8], len: usize, spec: &Spec, heap: &Heap<impl ResourceTracker>) -> RunResult<()> {
+    let fill = spec.width.saturating_sub(len);
+    check_repeat_size(1, fill, heap.tracker())?;
+    if spec.left {
+        out.extend_from_slice(text);
+        out.resize(out.len() + fill, b' ');
+    } else {
+        out.resize(out.len() + fill, b' ');
+        out.extend_from_slice(text);
+    }
+    Ok(())
+}
+
+/// Formats an integer conversion: precision is the minimum number of digits and the
+/// `0` flag pads between the sign (or `#` base prefix) and the digits.
+fn format_integer(
+    n: &BigInt,
+    conv: u8,
+    spec: &Spec,
+    out: &mut Vec<u8>,
+    heap: &Heap<impl ResourceTracker>,
+) -> RunResult<()> {
+    let (radix, prefix) = match conv {
+        b'o' => (8, "0o"),
+        b'x' => (16, "0x"),
+        b'X' => (16, "0X"),
+        _ => (10, ""),
+    };
+    let mut digits = n.magnitude().to_str_radix(radix);
+    if conv == b'X' {
+        digits.make_ascii_uppercase();
+    }
+    let precision = spec.precision.unwrap_or(0);
+    check_repeat_size(1, precision, heap.tracker())?;
+    let digit_zeros = precision.saturating_sub(digits.len());
+
+    let sign = if n.is_negative() {
+        "-"
+    } else if spec.plus {
+        "+"
+    } else if spec.space {
+        " "
+    } else {
+        ""
+    };
+    let prefix = if spec.alternate { prefix } else { "" };
+    let len = sign.len() + prefix.len() + digit_zeros + digits.len();
+    if spec.zero && !spec.left {
+        let fill = spec.width.saturating_sub(len);
+        check_repeat_size(1, fill, heap.tracker())?;
+        out.extend_from_slice(sign.as_bytes());
+        out.extend_from_slice(prefix.as_bytes());
+        out.resize(out.len() + fill + digit_zeros, b'0');
+        out.extend_from_slice(digits.as_bytes());
+        Ok(())
+    } else {
+        let mut text = Vec::with_capacity(len);
+        text.extend_from_slice(sign.as_bytes());
+        text.extend_from_slice(prefix.as_bytes());
+        text.resize(text.len() + digit_zeros, b'0');
+        text.extend_from_slice(digits.as_bytes());
+        pad(out, &text, len, spec, heap)
+    }
+}
+
+/// The value of an `int` or `bool`.
+fn integer_value(value: &Value, heap: &Heap<impl ResourceTracker>, interns: &Interns) -> Option<BigInt> {
+    match value {
+        Value::Int(n) => Some(BigInt::from(*n)),
+        Value::Bool(b) => Some(BigInt::from(u8::from(*b))),
+        Value::InternLongInt(id) => Some(interns.get_long_int(*id).clone()),
+        Value::Ref(id) => match heap.get(*id) {
+            HeapData::LongInt(li) => Some(li.inner().clone()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// The argument of an integer conversion.
+///
+/// `%d`, `%i` and `%u` also accept floats and fractions, truncating them toward zero;
+/// `%o`, `%x` and `%X` only accept integers.
+fn integer_arg(value: &Value, conv: u8, heap: &Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<BigInt> {
+    if let Some(n) = integer_value(value, heap, interns) {
+        return Ok(n);
+    }
+    let conv = char::from(conv);
+    let value_type = value.py_type(heap);
+    if !matches!(conv, 'd' | 'i' | 'u') {
+        return Err(ExcType::type_error(format!(
+            "%{conv} format: an integer is required, not {value_type}"
+        )));
+    }
+    match value {
+        Value::Float(f) if f.is_nan() => {
+            Err(SimpleException::new_msg(ExcType::ValueError, "cannot convert float NaN to integer").into())
+        }
+        Value::Float(f) if f.is_infinite() => {
+            Err(SimpleException::new_msg(ExcType::OverflowError, "cannot convert float infinity to integer").into())
+        }
+        Value::Float(f) => Ok(BigInt::from_f64(f.trunc()).expect("finite float converts to BigInt")),
+        Value::Ref(id) => match heap.get(*id) {
+            HeapData::Fraction(fraction) => Ok(fraction.trunc()),
+            _ => Err(real_number_required(conv, value_type)),
+        },
+        _ => Err(real_number_required(conv, value_type)),
+    }
+}
+
+fn real_number_required(conv: char, value_type: Type) -> RunError {
+    ExcType::type_error(format!("%{conv} format: a real number is required, not {value_type}"))
+}
+
+/// The argument of a float conversion: any real number.
+fn float_arg(value: &Value, heap: &Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<f64> {
+    if let Value::Float(f) = value {
+        return Ok(*f);
+    }
+    if let Some(n) = integer_value(value, heap, interns) {
+        return big_int_to_f64(&n);
+    }
+    if let Value::Ref(id) = value
+        && let HeapData::Fraction(fraction) = heap.get(*id)
+    {
+        return fraction.to_f64();
+    }
+    let value_type = value.py_type(heap);
+    Err(ExcType::type_error(format!("must be real number, not {value_type}")))
+}
+
+/// The contents of a `bytes` argument.
+fn bytes_arg<'a>(value: &Value, heap: &'a Heap<impl ResourceTracker>, interns: &'a Interns) -> Option<&'a [u8]> {
+    match value {
+        Value::InternBytes(id) => Some(interns.get_bytes(*id)),
+        Value::Ref(id) => match heap.get(*id) {
+            HeapData::Bytes(b) => Some(b.as_slice()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
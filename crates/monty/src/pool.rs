@@ -0,0 +1,373 @@
+//! Shared pool of execution slots for services running code on behalf of many tenants.
+//!
+//! A [`SandboxPool`] bounds how many programs run at once, how many of those may belong to a single
+//! tenant, and how many callers may wait for a slot. Each slot is handed out as a [`PoolLease`],
+//! which runs programs with the tenant's resource limits and returns the slot when dropped.
+//!
+//! Every slot owns a heap arena which is created with the pool and reused by each run on the slot,
+//! so a busy service doesn't allocate a new heap per request. The arena keeps the capacity earlier
+//! runs grew it to, while the objects of each run are dropped as soon as the run finishes.
+
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Condvar, Mutex, MutexGuard, PoisonError},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    ExcType, LimitedTracker, MontyException, MontyObject, MontyRun, PrintWriter, ResourceLimits, RunProgress,
+    heap::Heap,
+};
+
+/// Limits applied to one tenant's use of a [`SandboxPool`].
+#[derive(Debug, Clone)]
+pub struct TenantQuota {
+    /// Maximum number of slots the tenant may hold at once, capped by the pool size.
+    ///
+    /// Zero shuts the tenant out: [`SandboxPool::acquire`] fails immediately with
+    /// [`PoolError::NoQuota`] rather than waiting for a slot it could never get.
+    pub max_concurrent: usize,
+    /// Resource limits for every run of the tenant.
+    pub limits: ResourceLimits,
+}
+
+impl TenantQuota {
+    /// Creates a quota allowing `max_concurrent` simultaneous runs with the given limits.
+    #[must_use]
+    pub fn new(max_concurrent: usize, limits: ResourceLimits) -> Self {
+        Self { max_concurrent, limits }
+    }
+}
+
+/// Why a slot couldn't be acquired.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PoolError {
+    /// Too many callers are already waiting for a slot.
+    QueueFull { max_queue: usize },
+    /// No slot became available before the timeout.
+    Timeout { waited: Duration },
+    /// The tenant's quota allows no concurrent runs at all.
+    NoQuota { tenant: String },
+}
+
+impl fmt::Display for PoolError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::QueueFull { max_queue } => write!(f, "sandbox pool queue is full (max_queue={max_queue})"),
+            Self::Timeout { waited } => write!(f, "timed out after {waited:?} waiting for a sandbox slot"),
+            Self::NoQuota { tenant } => write!(f, "tenant {tenant:?} has a sandbox quota of zero concurrent runs"),
+        }
+    }
+}
+
+impl std::error::Error for PoolError {}
+
+impl From<PoolError> for MontyException {
+    fn from(err: PoolError) -> Self {
+        Self::new(ExcType::RuntimeError, Some(err.to_string()))
+    }
+}
+
+/// Hooks for recording pool activity, e.g. to export metrics.
+///
+/// All methods default to doing nothing. They're called outside the pool's lock.
+pub trait PoolMetrics: Send + Sync + fmt::Debug {
+    /// A slot was acquired by `tenant` after waiting `waited`.
+    fn on_acquire(&self, tenant: &str, waited: Duration) {
+        let _ = (tenant, waited);
+    }
+
+    /// A slot held for `held` was returned by `tenant`.
+    fn on_release(&self, tenant: &str, held: Duration) {
+        let _ = (tenant, held);
+    }
+
+    /// `tenant` was refused a slot.
+    fn on_reject(&self, tenant: &str, error: &PoolError) {
+        let _ = (tenant, error);
+    }
+}
+
+/// Point-in-time usage of a [`SandboxPool`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoolStats {
+    /// Total number of slots.
+    pub size: usize,
+    /// Slots currently leased.
+    pub in_use: usize,
+    /// Callers currently waiting for a slot.
+    pub waiting: usize,
+}
+
+/// Mutable state shared by all callers, behind the pool's mutex.
+#[derive(Debug)]
+struct PoolState {
+    in_use: usize,
+    waiting: usize,
+    per_tenant: HashMap<String, usize>,
+    /// Heap arenas of the slots not in use, one per free slot.
+    arenas: Vec<Heap<LimitedTracker>>,
+}
+
+/// A bounded pool of execution slots with per-tenant quotas, queueing and metrics hooks.
+///
+/// ```
+/// use monty::{MontyObject, MontyRun, PrintWriter, ResourceLimits, SandboxPool, TenantQuota};
+///
+/// let quota = TenantQuota::new(2, ResourceLimits::new().max_memory(1 << 20));
+/// let pool = SandboxPool::new(4).with_tenant_quota("acme", quota);
+/// let runner = MontyRun::new("1 + 2".to_owned(), "main.py", vec![], vec![]).unwrap();
+///
+/// let mut lease = pool.acquire("acme").unwrap();
+/// let result = lease.run(&runner, vec![], &mut PrintWriter::Disabled).unwrap();
+/// assert_eq!(result, MontyObject::Int(3));
+/// ```
+#[derive(Debug)]
+pub struct SandboxPool {
+    size: usize,
+    max_queue: Option<usize>,
+    timeout: Option<Duration>,
+    default_quota: TenantQuota,
+    quotas: HashMap<String, TenantQuota>,
+    metrics: Option<Box<dyn PoolMetrics>>,
+    state: Mutex<PoolState>,
+    available: Condvar,
+}
+
+impl SandboxPool {
+    /// Creates a pool of `size` slots, creating each slot's heap arena up front.
+    ///
+    /// By default tenants may use every slot with [`ResourceLimits::new`], callers wait
+    /// indefinitely and the queue is unbounded.
+    ///
+    /// # Panics
+    /// Panics if `size` is zero.
+    #[must_use]
+    pub fn new(size: usize) -> Self {
+        assert!(size > 0, "sandbox pool size must be at least 1");
+        Self {
+            size,
+            max_queue: None,
+            timeout: None,
+            default_quota: TenantQuota::new(size, ResourceLimits::new()),
+            quotas: HashMap::new(),
+            metrics: None,
+            state: Mutex::new(PoolState {
+                in_use: 0,
+                waiting: 0,
+                per_tenant: HashMap::new(),
+                arenas: (0..size).map(|_| idle_arena()).collect(),
+            }),
+            available: Condvar::new(),
+        }
+    }
+
+    /// Sets the maximum number of callers waiting for a slot, further callers fail immediately.
+    #[must_use]
+    pub fn with_max_queue(mut self, max_queue: usize) -> Self {
+        self.max_queue = Some(max_queue);
+        self
+    }
+
+    /// Sets how long callers wait for a slot before failing.
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the quota for tenants without their own.
+    #[must_use]
+    pub fn with_default_quota(mut self, quota: TenantQuota) -> Self {
+        self.default_quota = quota;
+        self
+    }
+
+    /// Sets the quota for one tenant.
+    #[must_use]
+    pub fn with_tenant_quota(mut self, tenant: impl Into<String>, quota: TenantQuota) -> Self {
+        self.quotas.insert(tenant.into(), quota);
+        self
+    }
+
+    /// Sets the hooks notified of acquisitions, releases and rejections.
+    #[must_use]
+    pub fn with_metrics(mut self, metrics: impl PoolMetrics + 'static) -> Self {
+        self.metrics = Some(Box::new(metrics));
+        self
+    }
+
+    /// Waits for a slot for `tenant`, respecting the pool size and the tenant's quota.
+    pub fn acquire(&self, tenant: &str) -> Result<PoolLease<'_>, PoolError> {
+        let start = Instant::now();
+        let result = self.wait_for_slot(tenant, start);
+        match &result {
+            Ok(_) => {
+                if let Some(metrics) = &self.metrics {
+                    metrics.on_acquire(tenant, start.elapsed());
+                }
+            }
+            Err(err) => {
+                if let Some(metrics) = &self.metrics {
+                    metrics.on_reject(tenant, err);
+                }
+            }
+        }
+        result
+    }
+
+    /// Current usage of the pool.
+    pub fn stats(&self) -> PoolStats {
+        let state = self.lock();
+        PoolStats {
+            size: self.size,
+            in_use: state.in_use,
+            waiting: state.waiting,
+        }
+    }
+
+    /// The quota applying to `tenant`.
+    pub fn quota(&self, tenant: &str) -> &TenantQuota {
+        self.quotas.get(tenant).unwrap_or(&self.default_quota)
+    }
+
+    /// Blocks until a slot is free for `tenant`, or the queue or timeout limits are hit.
+    fn wait_for_slot(&self, tenant: &str, start: Instant) -> Result<PoolLease<'_>, PoolError> {
+        let max_concurrent = self.quota(tenant).max_concurrent;
+        if max_concurrent == 0 {
+            return Err(PoolError::NoQuota {
+                tenant: tenant.to_owned(),
+            });
+        }
+        let mut state = self.lock();
+        let has_slot = |state: &PoolState| {
+            state.in_use < self.size && state.per_tenant.get(tenant).copied().unwrap_or(0) < max_concurrent
+        };
+        if !has_slot(&state) {
+            if let Some(max_queue) = self.max_queue
+                && state.waiting >= max_queue
+            {
+                return Err(PoolError::QueueFull { max_queue });
+            }
+            state.waiting += 1;
+            while !has_slot(&state) {
+                state = match self.timeout {
+                    Some(timeout) => {
+                        let Some(remaining) = timeout.checked_sub(start.elapsed()) else {
+                            state.waiting -= 1;
+                            return Err(PoolError::Timeout {
+                                waited: start.elapsed(),
+                            });
+                        };
+                        self.available
+                            .wait_timeout(state, remaining)
+                            .unwrap_or_else(PoisonError::into_inner)
+                            .0
+                    }
+                    None => self.available.wait(state).unwrap_or_else(PoisonError::into_inner),
+                };
+            }
+            state.waiting -= 1;
+        }
+        state.in_use += 1;
+        *state.per_tenant.entry(tenant.to_owned()).or_default() += 1;
+        let arena = state.arenas.pop().expect("every free slot has an arena");
+        Ok(PoolLease {
+            pool: self,
+            tenant: tenant.to_owned(),
+            acquired: Instant::now(),
+            arena: Some(arena),
+        })
+    }
+
+    /// Returns a slot held by `tenant`, along with its arena.
+    fn release(&self, tenant: &str, arena: Heap<LimitedTracker>) {
+        let mut state = self.lock();
+        state.in_use -= 1;
+        state.arenas.push(arena);
+        if let Some(count) = state.per_tenant.get_mut(tenant) {
+            *count -= 1;
+            if *count == 0 {
+                state.per_tenant.remove(tenant);
+            }
+        }
+        drop(state);
+        // waiters may be blocked on either the pool size or their tenant's quota, so wake them all
+        self.available.notify_all();
+    }
+
+    /// Locks the state, ignoring poisoning since the counters are always left consistent.
+    fn lock(&self) -> MutexGuard<'_, PoolState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// A slot of a [`SandboxPool`] held by one tenant, returned to the pool when dropped.
+#[derive(Debug)]
+pub struct PoolLease<'p> {
+    pool: &'p SandboxPool,
+    tenant: String,
+    acquired: Instant,
+    /// The slot's heap arena, only `None` while being returned to the pool on drop.
+    arena: Option<Heap<LimitedTracker>>,
+}
+
+impl PoolLease<'_> {
+    /// The tenant holding this slot.
+    #[must_use]
+    pub fn tenant(&self) -> &str {
+        &self.tenant
+    }
+
+    /// A resource tracker enforcing the tenant's limits, for use with the iterative APIs.
+    #[must_use]
+    pub fn tracker(&self) -> LimitedTracker {
+        LimitedTracker::new(self.pool.quota(&self.tenant).limits.clone())
+    }
+
+    /// Runs a program to completion with the tenant's limits, on the slot's heap arena.
+    ///
+    /// The program's objects are dropped when it finishes, the arena keeps its capacity for the next run.
+    pub fn run(
+        &mut self,
+        runner: &MontyRun,
+        inputs: Vec<MontyObject>,
+        print: &mut PrintWriter<'_>,
+    ) -> Result<MontyObject, MontyException> {
+        let tracker = self.tracker();
+        let arena = self.arena.as_mut().expect("arena is only taken on drop");
+        let result = runner.run_in_heap(arena, inputs, tracker, print);
+        arena.clear();
+        result
+    }
+
+    /// Starts a program with the tenant's limits, for programs calling external functions.
+    ///
+    /// The returned progress owns its heap so it can be suspended and serialized, which means
+    /// it doesn't use the slot's arena. The slot is only held while the lease is alive, so keep
+    /// it until the progress completes.
+    pub fn start(
+        &self,
+        runner: &MontyRun,
+        inputs: Vec<MontyObject>,
+        print: &mut PrintWriter<'_>,
+    ) -> Result<RunProgress<LimitedTracker>, MontyException> {
+        runner.clone().start(inputs, self.tracker(), print)
+    }
+}
+
+impl Drop for PoolLease<'_> {
+    fn drop(&mut self) {
+        let arena = self.arena.take().expect("arena is only taken on drop");
+        self.pool.release(&self.tenant, arena);
+        if let Some(metrics) = &self.pool.metrics {
+            metrics.on_release(&self.tenant, self.acquired.elapsed());
+        }
+    }
+}
+
+/// Creates an empty heap arena for a slot, `reset` with the tenant's tracker before each run.
+fn idle_arena() -> Heap<LimitedTracker> {
+    Heap::new(0, LimitedTracker::new(ResourceLimits::new()))
+}
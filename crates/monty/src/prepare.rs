@@ -6,12 +6,12 @@ use crate::{
     args::ArgExprs,
     builtins::Builtins,
     expressions::{
-        Callable, CmpOperator, Comprehension, Expr, ExprLoc, Identifier, Literal, NameScope, Node, Operator,
+        Callable, CmpOperator, Comprehension, DictItem, Expr, ExprLoc, Identifier, Literal, NameScope, Node, Operator,
         PreparedFunctionDef, PreparedNode, UnpackTarget,
     },
     fstring::{FStringPart, FormatSpec},
     intern::{InternerBuilder, StringId},
-    namespace::NamespaceId,
+    namespace::{MODULE_DUNDERS, NamespaceId},
     parse::{CodeRange, ExceptHandler, ParseError, ParseNode, ParseResult, ParsedSignature, RawFunctionDef, Try},
     signature::Signature,
 };
@@ -39,6 +39,17 @@ pub struct PrepareResult {
     pub interner: InternerBuilder,
 }
 
+/// Name of the builtin constant that is `False` when code is compiled with asserts removed.
+const DEBUG_NAME: &str = "__debug__";
+
+/// Adds `__debug__` to `constants`, `False` when `optimize` is set like `python -O`.
+///
+/// CPython doesn't allow `__debug__` to be rebound either, so treating it as a constant
+/// also gives the same `SyntaxError` for assignments.
+pub(crate) fn insert_debug_constant(constants: &mut AHashMap<String, Literal>, optimize: bool) {
+    constants.insert(DEBUG_NAME.to_owned(), Literal::Bool(!optimize));
+}
+
 /// Prepares parsed nodes for compilation by resolving names and building the initial namespace.
 ///
 /// The namespace will be converted to runtime Objects when execution begins and the heap is available.
@@ -47,9 +58,26 @@ pub(crate) fn prepare(
     parse_result: ParseResult,
     input_names: Vec<String>,
     external_functions: &[String],
+) -> Result<PrepareResult, ParseError> {
+    let mut constants = AHashMap::new();
+    insert_debug_constant(&mut constants, false);
+    prepare_with_constants(parse_result, input_names, external_functions, &constants)
+}
+
+/// Prepares parsed nodes like [`prepare`], replacing loads of `constants` with their literal values.
+///
+/// Constants never get a namespace slot, so any binding of a constant name, such as an
+/// assignment, parameter or loop target, is a `SyntaxError`. Assert statements are removed
+/// when `constants` sets `__debug__` to `False`, see [`insert_debug_constant`].
+pub(crate) fn prepare_with_constants(
+    parse_result: ParseResult,
+    input_names: Vec<String>,
+    external_functions: &[String],
+    constants: &AHashMap<String, Literal>,
 ) -> Result<PrepareResult, ParseError> {
     let ParseResult { nodes, interner } = parse_result;
-    let mut p = Prepare::new_module(input_names, external_functions, &interner);
+    let mut p = Prepare::new_module(input_names, external_functions, constants, &interner);
+    p.reserve_shadowed_builtins(&nodes);
     let mut prepared_nodes = p.prepare_nodes(nodes)?;
 
     // In the root frame, the last expression is implicitly returned
@@ -80,7 +108,10 @@ pub(crate) fn prepare_with_existing_names(
     existing_name_map: AHashMap<String, NamespaceId>,
 ) -> Result<PrepareResult, ParseError> {
     let ParseResult { nodes, interner } = parse_result;
-    let mut p = Prepare::new_module_with_name_map(existing_name_map, &interner);
+    let mut constants = AHashMap::new();
+    insert_debug_constant(&mut constants, false);
+    let mut p = Prepare::new_module_with_name_map(existing_name_map, &constants, &interner);
+    p.reserve_shadowed_builtins(&nodes);
     let mut prepared_nodes = p.prepare_nodes(nodes)?;
 
     // In the root frame, the last expression is implicitly returned to match REPL behavior.
@@ -148,6 +179,10 @@ struct Prepare<'i> {
     /// that are both nonlocal and captured by nested functions), then extended as new
     /// captures are discovered during nested function preparation.
     cell_var_map: AHashMap<String, NamespaceId>,
+    /// Host-provided constants, whose loads are replaced by their literal values.
+    constants: &'i AHashMap<String, Literal>,
+    /// First binding of a constant name found, reported as a `SyntaxError` by `prepare_nodes`.
+    rebound_constant: Option<Identifier>,
 }
 
 impl<'i> Prepare<'i> {
@@ -156,18 +191,31 @@ impl<'i> Prepare<'i> {
     /// At module level, all variables are global. The `global` keyword is a no-op
     /// since all variables are already in the global namespace.
     ///
+    /// External functions are registered first, then inputs, then the module dunders in
+    /// [`MODULE_DUNDERS`] (`__name__` etc.).
+    ///
     /// # Arguments
     /// * `input_names` - Names that should be pre-registered in the namespace (e.g., external variables)
     /// * `external_functions` - Names of external functions to pre-register
+    /// * `constants` - Host-provided constants to inline
     /// * `interner` - Reference to the string interner for looking up names
-    fn new_module(input_names: Vec<String>, external_functions: &[String], interner: &'i InternerBuilder) -> Self {
-        let mut name_map = AHashMap::with_capacity(input_names.len() + external_functions.len());
+    fn new_module(
+        input_names: Vec<String>,
+        external_functions: &[String],
+        constants: &'i AHashMap<String, Literal>,
+        interner: &'i InternerBuilder,
+    ) -> Self {
+        let mut name_map = AHashMap::with_capacity(input_names.len() + external_functions.len() + MODULE_DUNDERS.len());
         for (index, name) in external_functions.iter().enumerate() {
             name_map.insert(name.clone(), NamespaceId::new(index));
         }
+        let dunders_slot = external_functions.len() + input_names.len();
         for (index, name) in input_names.into_iter().enumerate() {
             name_map.insert(name, NamespaceId::new(external_functions.len() + index));
         }
+        for (index, name) in MODULE_DUNDERS.into_iter().enumerate() {
+            name_map.insert(name.to_owned(), NamespaceId::new(dunders_slot + index));
+        }
         let namespace_size = name_map.len();
         Self {
             interner,
@@ -181,13 +229,19 @@ impl<'i> Prepare<'i> {
             enclosing_locals: None,
             free_var_map: AHashMap::new(),
             cell_var_map: AHashMap::new(),
+            constants,
+            rebound_constant: None,
         }
     }
 
     /// Creates a module-scope Prepare instance from an existing global name map.
     ///
     /// Used by incremental REPL compilation to keep stable slot assignments across snippets.
-    fn new_module_with_name_map(name_map: AHashMap<String, NamespaceId>, interner: &'i InternerBuilder) -> Self {
+    fn new_module_with_name_map(
+        name_map: AHashMap<String, NamespaceId>,
+        constants: &'i AHashMap<String, Literal>,
+        interner: &'i InternerBuilder,
+    ) -> Self {
         let namespace_size = name_map
             .values()
             .map(|id| id.index())
@@ -206,6 +260,8 @@ impl<'i> Prepare<'i> {
             enclosing_locals: None,
             free_var_map: AHashMap::new(),
             cell_var_map: AHashMap::new(),
+            constants,
+            rebound_constant: None,
         }
     }
 
@@ -224,6 +280,7 @@ impl<'i> Prepare<'i> {
     /// * `global_name_map` - Copy of the module-level name map for global resolution
     /// * `enclosing_locals` - Names that exist as locals in the enclosing function (for nonlocal resolution)
     /// * `cell_var_names` - Names that are captured by nested functions (must be stored in cells)
+    /// * `constants` - Host-provided constants to inline
     /// * `interner` - Reference to the string interner for looking up names
     #[expect(clippy::too_many_arguments)]
     fn new_function(
@@ -236,6 +293,7 @@ impl<'i> Prepare<'i> {
         global_name_map: AHashMap<String, NamespaceId>,
         enclosing_locals: Option<AHashSet<String>>,
         cell_var_names: AHashSet<String>,
+        constants: &'i AHashMap<String, Literal>,
         interner: &'i InternerBuilder,
     ) -> Self {
         let mut name_map = AHashMap::with_capacity(capacity);
@@ -292,6 +350,8 @@ impl<'i> Prepare<'i> {
             enclosing_locals,
             free_var_map,
             cell_var_map,
+            constants,
+            rebound_constant: None,
         }
     }
 
@@ -336,6 +396,8 @@ impl<'i> Prepare<'i> {
                     };
                     new_nodes.push(Node::Raise(expr));
                 }
+                // Like `python -O`, asserts are removed entirely when `__debug__` is false
+                Node::Assert { .. } if matches!(self.constants.get(DEBUG_NAME), Some(Literal::Bool(false))) => (),
                 Node::Assert { test, msg } => {
                     let test = self.prepare_expression(test)?;
                     let msg = match msg {
@@ -449,10 +511,15 @@ impl<'i> Prepare<'i> {
                     signature,
                     body,
                     is_async,
+                    decorators,
                 }) => {
-                    let func_node = self.prepare_function_def(name, &signature, body, is_async)?;
+                    let func_node = self.prepare_function_def(name, &signature, body, is_async, decorators)?;
                     new_nodes.push(func_node);
                 }
+                Node::Delete { targets } => {
+                    let targets = targets.into_iter().map(|target| self.get_id(target).0).collect();
+                    new_nodes.push(Node::Delete { targets });
+                }
                 Node::Global { names, position } => {
                     // At module level, `global` is a no-op since all variables are already global.
                     // In functions, the global declarations are already collected in the first pass
@@ -572,6 +639,10 @@ impl<'i> Prepare<'i> {
                 }
             }
         }
+
+        if let Some(ident) = self.rebound_constant.take() {
+            return Err(self.constant_rebinding_error(ident.name_id, ident.position));
+        }
         Ok(new_nodes)
     }
 
@@ -640,15 +711,25 @@ impl<'i> Prepare<'i> {
                 args.prepare_args(|expr| self.prepare_expression(expr))?;
                 // For Name callables, resolve the identifier in the namespace
                 // Don't error here if undefined - let runtime raise NameError with proper traceback
-                let callable = match callable {
+                match callable {
                     Callable::Name(ident) => match self.resolve_name_or_builtin(ident) {
-                        Expr::Builtin(b) => Callable::Builtin(b),
-                        Expr::Name(resolved) => Callable::Name(resolved),
-                        _ => unreachable!("resolve_name_or_builtin returns Name or Builtin"),
+                        Expr::Builtin(b) => Expr::Call {
+                            callable: Callable::Builtin(b),
+                            args,
+                        },
+                        Expr::Name(resolved) => Expr::Call {
+                            callable: Callable::Name(resolved),
+                            args,
+                        },
+                        // A constant, which raises `TypeError` unless the call is never made
+                        literal @ Expr::Literal(_) => Expr::IndirectCall {
+                            callable: Box::new(ExprLoc::new(ident.position, literal)),
+                            args,
+                        },
+                        _ => unreachable!("resolve_name_or_builtin returns Name, Builtin or Literal"),
                     },
-                    other @ Callable::Builtin(_) => other,
-                };
-                Expr::Call { callable, args }
+                    callable @ Callable::Builtin(_) => Expr::Call { callable, args },
+                }
             }
             Expr::AttrCall { object, attr, mut args } => {
                 // Prepare the object expression (supports chained access like a.b.c.method())
@@ -685,13 +766,19 @@ impl<'i> Prepare<'i> {
                 object: Box::new(self.prepare_expression(*object)?),
                 index: Box::new(self.prepare_expression(*index)?),
             },
-            Expr::Dict(pairs) => {
-                let prepared_pairs = pairs
+            Expr::Dict(items) => {
+                let prepared_items = items
                     .into_iter()
-                    .map(|(k, v)| Ok((self.prepare_expression(k)?, self.prepare_expression(v)?)))
+                    .map(|item| match item {
+                        DictItem::Pair(k, v) => {
+                            Ok(DictItem::Pair(self.prepare_expression(k)?, self.prepare_expression(v)?))
+                        }
+                        DictItem::Unpack(mapping) => Ok(DictItem::Unpack(self.prepare_expression(mapping)?)),
+                    })
                     .collect::<Result<_, ParseError>>()?;
-                Expr::Dict(prepared_pairs)
+                Expr::Dict(prepared_items)
             }
+            Expr::Starred(value) => Expr::Starred(Box::new(self.prepare_expression(*value)?)),
             Expr::Set(elements) => {
                 let expressions = elements
                     .into_iter()
@@ -798,7 +885,8 @@ impl<'i> Prepare<'i> {
         Ok(ExprLoc { position, expr })
     }
 
-    /// Resolves a name to either `Expr::Builtin` or `Expr::Name` with scope-aware builtin detection.
+    /// Resolves a name to either `Expr::Builtin` or `Expr::Name` with scope-aware builtin detection,
+    /// or to `Expr::Literal` for host-provided constants.
     ///
     /// Python's name resolution follows LEGB order (Local, Enclosing, Global, Builtin).
     /// Builtins are only used when the name is not found in any other scope. This method
@@ -810,35 +898,84 @@ impl<'i> Prepare<'i> {
     fn resolve_name_or_builtin(&mut self, name: Identifier) -> Expr {
         let name_str = self.interner.get_str(name.name_id);
 
-        // Check if the name is assigned in the current scope. If so, it shadows
-        // any builtin with the same name.
-        let is_locally_assigned = if self.is_module_scope {
-            // Module scope: sequential — only names assigned SO FAR shadow builtins
-            self.names_assigned_in_order.contains(name_str)
-        } else {
-            // Function scope: lexical — ANY assignment in the function body makes
-            // the name local for the entire function
-            self.assigned_names.contains(name_str)
+        // Constants can't be rebound, so they are never shadowed by another binding
+        if let Some(literal) = self.constants.get(name_str) {
+            return Expr::Literal(*literal);
+        }
+
+        let Ok(builtin) = name_str.parse::<Builtins>() else {
+            return Expr::Name(self.get_id(name).0);
         };
 
-        if !is_locally_assigned {
-            // In function scope, also check if the name is bound by other mechanisms
-            // (global declaration, parameter, closure capture, enclosing/global scope).
-            // Only fall back to builtins if the name is truly unresolved.
-            let is_otherwise_bound = !self.is_module_scope
-                && (self.global_names.contains(name_str)
-                    || self.free_var_map.contains_key(name_str)
-                    || self.cell_var_map.contains_key(name_str)
-                    || self.name_map.contains_key(name_str)
-                    || self.enclosing_locals.as_ref().is_some_and(|l| l.contains(name_str))
-                    || self.global_name_map.as_ref().is_some_and(|m| m.contains_key(name_str)));
+        // Builtins are only a fallback after local and global lookup. A builtin name with a
+        // global slot (bound anywhere in the module, see `reserve_shadowed_builtins`) loads
+        // from that slot and falls back to the builtin while the slot is unbound.
+        if self.is_module_scope {
+            return match self.name_map.get(name_str) {
+                Some(&slot) => Expr::Name(Identifier::new_with_scope(
+                    name.name_id,
+                    name.position,
+                    slot,
+                    NameScope::GlobalOrBuiltin,
+                )),
+                None => Expr::Builtin(builtin),
+            };
+        }
 
-            if !is_otherwise_bound && let Ok(builtin) = name_str.parse::<Builtins>() {
-                return Expr::Builtin(builtin);
-            }
+        // Function scope: lexical - ANY binding in the function (assignment, parameter,
+        // global/nonlocal declaration or closure capture) shadows the builtin for the whole function
+        let is_locally_bound = self.assigned_names.contains(name_str)
+            || self.global_names.contains(name_str)
+            || self.free_var_map.contains_key(name_str)
+            || self.cell_var_map.contains_key(name_str)
+            || self.name_map.contains_key(name_str)
+            || self.enclosing_locals.as_ref().is_some_and(|l| l.contains(name_str));
+        if is_locally_bound {
+            return Expr::Name(self.get_id(name).0);
+        }
+
+        match self.global_name_map.as_ref().and_then(|m| m.get(name_str)) {
+            Some(&slot) => Expr::Name(Identifier::new_with_scope(
+                name.name_id,
+                name.position,
+                slot,
+                NameScope::GlobalOrBuiltin,
+            )),
+            None => Expr::Builtin(builtin),
+        }
+    }
+
+    /// Reserves global slots for builtin names that are bound anywhere at module level.
+    ///
+    /// A module-level binding like `list = ...` may be conditional, deleted again or come after
+    /// a function that reads `list`, so every load of that name must consult the global slot
+    /// at runtime rather than being bound to the builtin at prepare time. Slots are reserved
+    /// up front so functions defined before the assignment see them in their global name map.
+    ///
+    /// Names are sorted before allocation so slot numbering is deterministic.
+    fn reserve_shadowed_builtins(&mut self, nodes: &[ParseNode]) {
+        let mut global_names = AHashSet::new();
+        let mut nonlocal_names = AHashSet::new();
+        let mut assigned_names = AHashSet::new();
+        for node in nodes {
+            collect_scope_info_from_node(
+                node,
+                &mut global_names,
+                &mut nonlocal_names,
+                &mut assigned_names,
+                self.interner,
+            );
         }
 
-        Expr::Name(self.get_id(name).0)
+        let mut shadowed: Vec<String> = assigned_names
+            .into_iter()
+            .filter(|name| name.parse::<Builtins>().is_ok() && !self.name_map.contains_key(name))
+            .collect();
+        shadowed.sort_unstable();
+        for name in shadowed {
+            self.name_map.insert(name, NamespaceId::new(self.namespace_size));
+            self.namespace_size += 1;
+        }
     }
 
     /// Prepares a comprehension with scope isolation for loop variables.
@@ -1006,6 +1143,7 @@ impl<'i> Prepare<'i> {
     fn prepare_unpack_target_for_comprehension(&mut self, target: UnpackTarget) -> UnpackTarget {
         match target {
             UnpackTarget::Name(ident) => {
+                self.check_not_constant(&ident);
                 let name_str = self.interner.get_str(ident.name_id).to_string();
                 let comp_var_id = NamespaceId::new(self.namespace_size);
                 self.namespace_size += 1;
@@ -1021,6 +1159,7 @@ impl<'i> Prepare<'i> {
                 ))
             }
             UnpackTarget::Starred(ident) => {
+                self.check_not_constant(&ident);
                 let name_str = self.interner.get_str(ident.name_id).to_string();
                 let comp_var_id = NamespaceId::new(self.namespace_size);
                 self.namespace_size += 1;
@@ -1146,12 +1285,20 @@ impl<'i> Prepare<'i> {
         parsed_sig: &ParsedSignature,
         body: Vec<ParseNode>,
         is_async: bool,
+        decorators: Vec<ExprLoc>,
     ) -> Result<PreparedNode, ParseError> {
+        // Decorators are evaluated in the enclosing scope, before the name is bound
+        let decorators = decorators
+            .into_iter()
+            .map(|decorator| self.prepare_expression(decorator))
+            .collect::<Result<Vec<_>, _>>()?;
+
         // Register the function name in the current scope
         let (name, _) = self.get_id(name);
 
         // Extract param names from the parsed signature for scope analysis
         let param_names: Vec<StringId> = parsed_sig.param_names().collect();
+        self.check_params_not_constant(&param_names, name.position)?;
 
         // Pass 1: Collect scope information from the function body
         let scope_info = collect_function_scope_info(&body, &param_names, self.interner);
@@ -1172,11 +1319,15 @@ impl<'i> Prepare<'i> {
             AHashSet::new()
         } else {
             // In a function: our params + assigned_names + existing name_map keys
-            // are all potentially available as enclosing locals
+            // are all potentially available as enclosing locals. Free vars are included
+            // so nested functions can capture pass-through variables.
             let mut locals = self.assigned_names.clone();
             for key in self.name_map.keys() {
                 locals.insert(key.clone());
             }
+            for key in self.free_var_map.keys() {
+                locals.insert(key.clone());
+            }
             locals
         };
 
@@ -1200,6 +1351,7 @@ impl<'i> Prepare<'i> {
             global_name_map,
             Some(enclosing_locals),
             scope_info.cell_var_names,
+            self.constants,
             self.interner,
         );
 
@@ -1336,6 +1488,7 @@ impl<'i> Prepare<'i> {
             cell_param_indices,
             default_exprs,
             is_async,
+            decorators,
         }))
     }
 
@@ -1368,6 +1521,7 @@ impl<'i> Prepare<'i> {
 
         // Extract param names from the parsed signature for scope analysis
         let param_names: Vec<StringId> = parsed_sig.param_names().collect();
+        self.check_params_not_constant(&param_names, position)?;
 
         // Pass 1: Collect scope information from the lambda body
         // (Lambdas can't have global/nonlocal declarations, but can have nested functions)
@@ -1414,6 +1568,7 @@ impl<'i> Prepare<'i> {
             global_name_map,
             Some(enclosing_locals),
             scope_info.cell_var_names,
+            self.constants,
             self.interner,
         );
 
@@ -1533,6 +1688,7 @@ impl<'i> Prepare<'i> {
             cell_param_indices,
             default_exprs,
             is_async: false,
+            decorators: Vec::new(),
         };
 
         Ok(ExprLoc::new(
@@ -1543,6 +1699,35 @@ impl<'i> Prepare<'i> {
         ))
     }
 
+    /// Returns a `SyntaxError` if a function parameter is named after a constant.
+    fn check_params_not_constant(&self, params: &[StringId], position: CodeRange) -> Result<(), ParseError> {
+        match params
+            .iter()
+            .find(|id| self.constants.contains_key(self.interner.get_str(**id)))
+        {
+            Some(&param) => Err(self.constant_rebinding_error(param, position)),
+            None => Ok(()),
+        }
+    }
+
+    /// Error for binding the name of a host-provided constant.
+    fn constant_rebinding_error(&self, name_id: StringId, position: CodeRange) -> ParseError {
+        let name = self.interner.get_str(name_id);
+        if name == DEBUG_NAME {
+            return ParseError::syntax(format!("cannot assign to {DEBUG_NAME}"), position);
+        }
+        ParseError::syntax(format!("cannot assign to constant '{name}'"), position)
+    }
+
+    /// Records `ident` as a rebound constant if it names one, for `prepare_nodes` to report.
+    ///
+    /// Called for every name that gets a namespace slot, since constants never do.
+    fn check_not_constant(&mut self, ident: &Identifier) {
+        if self.rebound_constant.is_none() && self.constants.contains_key(self.interner.get_str(ident.name_id)) {
+            self.rebound_constant = Some(*ident);
+        }
+    }
+
     /// Resolves an identifier to its namespace index and scope, creating a new entry if needed.
     ///
     /// TODO This whole implementation seems ugly at best.
@@ -1561,6 +1746,7 @@ impl<'i> Prepare<'i> {
     /// # Returns
     /// A tuple of (resolved Identifier with id and scope set, whether this is a new local name).
     fn get_id(&mut self, ident: Identifier) -> (Identifier, bool) {
+        self.check_not_constant(&ident);
         let name_str = self.interner.get_str(ident.name_id);
 
         // At module level, all names are local (which is also the global namespace)
@@ -1955,10 +2141,14 @@ fn collect_scope_info_from_node(
                 collect_scope_info_from_node(n, global_names, nonlocal_names, assigned_names, interner);
             }
         }
-        Node::FunctionDef(RawFunctionDef { name, .. }) => {
+        Node::FunctionDef(RawFunctionDef { name, decorators, .. }) => {
             // Function definition creates a local binding for the function name
             // But we don't recurse into the function body - that's a separate scope
             assigned_names.insert(interner.get_str(name.name_id).to_string());
+            // Decorators run in this scope, so walrus targets in them are ours
+            for decorator in decorators {
+                collect_assigned_names_from_expr(decorator, assigned_names, interner);
+            }
         }
         Node::Try(Try {
             body,
@@ -1986,6 +2176,12 @@ fn collect_scope_info_from_node(
                 collect_scope_info_from_node(n, global_names, nonlocal_names, assigned_names, interner);
             }
         }
+        // `del` makes the name local to this scope, just like an assignment
+        Node::Delete { targets } => {
+            for target in targets {
+                assigned_names.insert(interner.get_str(target.name_id).to_string());
+            }
+        }
         // Import creates a binding for the module name (or alias)
         Node::Import { binding, .. } => {
             assigned_names.insert(interner.get_str(binding.name_id).to_string());
@@ -2033,10 +2229,15 @@ fn collect_assigned_names_from_expr(expr: &ExprLoc, assigned_names: &mut AHashSe
                 collect_assigned_names_from_expr(item, assigned_names, interner);
             }
         }
-        Expr::Dict(pairs) => {
-            for (key, value) in pairs {
-                collect_assigned_names_from_expr(key, assigned_names, interner);
-                collect_assigned_names_from_expr(value, assigned_names, interner);
+        Expr::Dict(items) => {
+            for item in items {
+                match item {
+                    DictItem::Pair(key, value) => {
+                        collect_assigned_names_from_expr(key, assigned_names, interner);
+                        collect_assigned_names_from_expr(value, assigned_names, interner);
+                    }
+                    DictItem::Unpack(mapping) => collect_assigned_names_from_expr(mapping, assigned_names, interner),
+                }
             }
         }
         Expr::Op { left, right, .. } | Expr::CmpOp { left, right, .. } => {
@@ -2053,7 +2254,8 @@ fn collect_assigned_names_from_expr(expr: &ExprLoc, assigned_names: &mut AHashSe
         | Expr::UnaryMinus(operand)
         | Expr::UnaryPlus(operand)
         | Expr::UnaryInvert(operand)
-        | Expr::Await(operand) => {
+        | Expr::Await(operand)
+        | Expr::Starred(operand) => {
             collect_assigned_names_from_expr(operand, assigned_names, interner);
         }
         Expr::Subscript { object, index } => {
@@ -2185,7 +2387,17 @@ fn collect_cell_vars_from_node(
     interner: &InternerBuilder,
 ) {
     match node {
-        Node::FunctionDef(RawFunctionDef { signature, body, .. }) => {
+        Node::FunctionDef(RawFunctionDef {
+            signature,
+            body,
+            decorators,
+            ..
+        }) => {
+            // Decorators are evaluated in our scope but may contain lambdas that capture
+            for decorator in decorators {
+                collect_cell_vars_from_expr(decorator, our_locals, cell_vars, interner);
+            }
+
             // Find what names are referenced inside this nested function
             let mut referenced = AHashSet::new();
             for n in body {
@@ -2353,10 +2565,15 @@ fn collect_cell_vars_from_expr(
                 collect_cell_vars_from_expr(item, our_locals, cell_vars, interner);
             }
         }
-        Expr::Dict(pairs) => {
-            for (key, value) in pairs {
-                collect_cell_vars_from_expr(key, our_locals, cell_vars, interner);
-                collect_cell_vars_from_expr(value, our_locals, cell_vars, interner);
+        Expr::Dict(items) => {
+            for item in items {
+                match item {
+                    DictItem::Pair(key, value) => {
+                        collect_cell_vars_from_expr(key, our_locals, cell_vars, interner);
+                        collect_cell_vars_from_expr(value, our_locals, cell_vars, interner);
+                    }
+                    DictItem::Unpack(mapping) => collect_cell_vars_from_expr(mapping, our_locals, cell_vars, interner),
+                }
             }
         }
         Expr::Op { left, right, .. } | Expr::CmpOp { left, right, .. } => {
@@ -2369,7 +2586,11 @@ fn collect_cell_vars_from_expr(
                 collect_cell_vars_from_expr(expr, our_locals, cell_vars, interner);
             }
         }
-        Expr::Not(operand) | Expr::UnaryMinus(operand) | Expr::UnaryPlus(operand) | Expr::UnaryInvert(operand) => {
+        Expr::Not(operand)
+        | Expr::UnaryMinus(operand)
+        | Expr::UnaryPlus(operand)
+        | Expr::UnaryInvert(operand)
+        | Expr::Starred(operand) => {
             collect_cell_vars_from_expr(operand, our_locals, cell_vars, interner);
         }
         Expr::Subscript { object, index } => {
@@ -2550,8 +2771,32 @@ fn collect_referenced_names_from_node(node: &ParseNode, referenced: &mut AHashSe
                 collect_referenced_names_from_node(n, referenced, interner);
             }
         }
-        Node::FunctionDef(_) => {
-            // Don't recurse into nested function bodies - they have their own scope
+        Node::FunctionDef(RawFunctionDef {
+            signature,
+            body,
+            decorators,
+            ..
+        }) => {
+            // Decorators and defaults are evaluated in this scope, so their names are referenced here.
+            for decorator in decorators {
+                collect_referenced_names_from_expr(decorator, referenced, interner);
+            }
+            for param in signature
+                .pos_args
+                .iter()
+                .chain(&signature.args)
+                .chain(&signature.kwargs)
+            {
+                if let Some(ref default) = param.default {
+                    collect_referenced_names_from_expr(default, referenced, interner);
+                }
+            }
+            // The nested body has its own scope, but any free names it uses must pass through
+            // this scope so deeper closures can reach the enclosing function's cells.
+            let param_names: Vec<StringId> = signature.param_names().collect();
+            let nested_scope = collect_function_scope_info(body, &param_names, interner);
+            referenced.extend(nested_scope.potential_captures);
+            referenced.extend(nested_scope.nonlocal_names);
         }
         Node::Try(Try {
             body,
@@ -2581,6 +2826,7 @@ fn collect_referenced_names_from_node(node: &ParseNode, referenced: &mut AHashSe
         // Imports create bindings but don't reference names
         Node::Import { .. } | Node::ImportFrom { .. } => {}
         Node::Pass
+        | Node::Delete { .. }
         | Node::ReturnNone
         | Node::Global { .. }
         | Node::Nonlocal { .. }
@@ -2607,10 +2853,15 @@ fn collect_referenced_names_from_expr(
                 collect_referenced_names_from_expr(item, referenced, interner);
             }
         }
-        Expr::Dict(pairs) => {
-            for (key, value) in pairs {
-                collect_referenced_names_from_expr(key, referenced, interner);
-                collect_referenced_names_from_expr(value, referenced, interner);
+        Expr::Dict(items) => {
+            for item in items {
+                match item {
+                    DictItem::Pair(key, value) => {
+                        collect_referenced_names_from_expr(key, referenced, interner);
+                        collect_referenced_names_from_expr(value, referenced, interner);
+                    }
+                    DictItem::Unpack(mapping) => collect_referenced_names_from_expr(mapping, referenced, interner),
+                }
             }
         }
         Expr::Op { left, right, .. } | Expr::CmpOp { left, right, .. } => {
@@ -2623,7 +2874,11 @@ fn collect_referenced_names_from_expr(
                 collect_referenced_names_from_expr(expr, referenced, interner);
             }
         }
-        Expr::Not(operand) | Expr::UnaryMinus(operand) | Expr::UnaryPlus(operand) | Expr::UnaryInvert(operand) => {
+        Expr::Not(operand)
+        | Expr::UnaryMinus(operand)
+        | Expr::UnaryPlus(operand)
+        | Expr::UnaryInvert(operand)
+        | Expr::Starred(operand) => {
             collect_referenced_names_from_expr(operand, referenced, interner);
         }
         Expr::FString(parts) => {
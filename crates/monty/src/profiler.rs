@@ -0,0 +1,256 @@
+//! Counting profiler attributing executed instructions to source lines and functions.
+//!
+//! [`Profiler`] is a resource tracker with no limits of its own, chained onto the tracker
+//! enforcing limits like [`AuditTracker`](crate::AuditTracker):
+//! `LimitedTracker::new(limits).chain(profiler.clone())`. Clones share their counts, so the
+//! host keeps one clone to read the [`ProfileReport`] after the run, however it ends.
+
+use std::{
+    fmt,
+    sync::{Arc, Mutex, PoisonError},
+};
+
+use ahash::AHashMap;
+
+use crate::resource::{ResourceError, ResourceTracker};
+
+/// A resource tracker counting the instructions executed per source line and per function.
+///
+/// Instruction counts are deterministic, unlike timings, so reports are comparable between
+/// runs and machines. Profiling reports every instruction to the tracker, so expect code to
+/// run several times slower.
+///
+/// Serializing the tracker, e.g. in a snapshot, stores the counts so far; a profiler loaded
+/// from a snapshot no longer shares counts with the original, get it back from the
+/// snapshot's tracker instead.
+#[derive(Debug, Clone, Default)]
+pub struct Profiler {
+    counts: Arc<Mutex<ProfileCounts>>,
+}
+
+/// Instruction counts collected by a [`Profiler`].
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+struct ProfileCounts {
+    /// Instructions per filename, then per line.
+    lines: AHashMap<String, AHashMap<u16, u64>>,
+    /// Instructions per filename, then per function name.
+    functions: AHashMap<String, AHashMap<String, u64>>,
+}
+
+impl Profiler {
+    /// Creates a profiler with no instructions counted.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds a report from the instructions counted so far.
+    #[must_use]
+    pub fn report(&self) -> ProfileReport {
+        let counts = self.counts.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut lines: Vec<LineProfile> = counts
+            .lines
+            .iter()
+            .flat_map(|(filename, lines)| {
+                lines.iter().map(|(&line, &instructions)| LineProfile {
+                    filename: filename.clone(),
+                    line,
+                    instructions,
+                })
+            })
+            .collect();
+        lines.sort_by(|a, b| {
+            b.instructions
+                .cmp(&a.instructions)
+                .then_with(|| a.filename.cmp(&b.filename))
+                .then(a.line.cmp(&b.line))
+        });
+        let mut functions: Vec<FunctionProfile> = counts
+            .functions
+            .iter()
+            .flat_map(|(filename, functions)| {
+                functions.iter().map(|(name, &instructions)| FunctionProfile {
+                    filename: filename.clone(),
+                    name: name.clone(),
+                    instructions,
+                })
+            })
+            .collect();
+        functions.sort_by(|a, b| {
+            b.instructions
+                .cmp(&a.instructions)
+                .then_with(|| a.filename.cmp(&b.filename))
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        ProfileReport {
+            total_instructions: lines.iter().map(|line| line.instructions).sum(),
+            lines,
+            functions,
+        }
+    }
+
+    /// Clears the counts, e.g. to profile each REPL snippet separately.
+    pub fn reset(&self) {
+        *self.counts.lock().unwrap_or_else(PoisonError::into_inner) = ProfileCounts::default();
+    }
+}
+
+impl ResourceTracker for Profiler {
+    #[inline]
+    fn on_allocate(&mut self, _: impl FnOnce() -> usize) -> Result<(), ResourceError> {
+        Ok(())
+    }
+
+    #[inline]
+    fn on_free(&mut self, _: impl FnOnce() -> usize) {}
+
+    #[inline]
+    fn check_time(&self) -> Result<(), ResourceError> {
+        Ok(())
+    }
+
+    #[inline]
+    fn check_recursion_depth(&self, _current_depth: usize) -> Result<(), ResourceError> {
+        Ok(())
+    }
+
+    #[inline]
+    fn check_large_result(&self, _estimated_bytes: usize) -> Result<(), ResourceError> {
+        Ok(())
+    }
+
+    #[inline]
+    fn max_output_size(&self) -> Option<usize> {
+        None
+    }
+
+    #[inline]
+    fn max_stack_size(&self) -> Option<usize> {
+        None
+    }
+
+    fn profile_instructions(&self) -> bool {
+        true
+    }
+
+    fn on_instruction(&mut self, filename: &str, line: u16, function: &str) {
+        let mut counts = self.counts.lock().unwrap_or_else(PoisonError::into_inner);
+        let counts = &mut *counts;
+        // Look up by `&str` first so only the first instruction of a file or function allocates
+        if !counts.lines.contains_key(filename) {
+            counts.lines.insert(filename.to_owned(), AHashMap::new());
+            counts.functions.insert(filename.to_owned(), AHashMap::new());
+        }
+        if let Some(lines) = counts.lines.get_mut(filename) {
+            *lines.entry(line).or_default() += 1;
+        }
+        if let Some(functions) = counts.functions.get_mut(filename) {
+            match functions.get_mut(function) {
+                Some(count) => *count += 1,
+                None => {
+                    functions.insert(function.to_owned(), 1);
+                }
+            }
+        }
+    }
+}
+
+impl serde::Serialize for Profiler {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.counts
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .serialize(serializer)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for Profiler {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let counts = ProfileCounts::deserialize(deserializer)?;
+        Ok(Self {
+            counts: Arc::new(Mutex::new(counts)),
+        })
+    }
+}
+
+/// Instruction counts collected by a [`Profiler`], hottest first.
+///
+/// `Display` renders a plain-text summary of the hottest functions and lines.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProfileReport {
+    /// Total number of instructions executed.
+    pub total_instructions: u64,
+    /// Instructions per source line, most instructions first.
+    pub lines: Vec<LineProfile>,
+    /// Instructions per function, most instructions first.
+    ///
+    /// Only counts instructions of the function's own body, not of the functions it calls.
+    pub functions: Vec<FunctionProfile>,
+}
+
+/// Instructions executed on one source line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineProfile {
+    /// Name of the script or REPL input the line belongs to.
+    pub filename: String,
+    /// Line number, starting from 1.
+    pub line: u16,
+    /// Number of instructions executed.
+    pub instructions: u64,
+}
+
+/// Instructions executed in the body of one function.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionProfile {
+    /// Name of the script or REPL input defining the function.
+    pub filename: String,
+    /// Function name, `"<module>"` for module-level code.
+    pub name: String,
+    /// Number of instructions executed.
+    pub instructions: u64,
+}
+
+/// Number of functions and lines shown by `ProfileReport`'s `Display` implementation.
+const DISPLAY_LIMIT: usize = 20;
+
+impl ProfileReport {
+    /// Percentage of all instructions `instructions` represents.
+    fn percent(&self, instructions: u64) -> f64 {
+        if self.total_instructions == 0 {
+            0.0
+        } else {
+            instructions as f64 * 100.0 / self.total_instructions as f64
+        }
+    }
+}
+
+impl fmt::Display for ProfileReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} instructions executed", self.total_instructions)?;
+        writeln!(f)?;
+        writeln!(f, "{:>12} {:>7}  function", "instructions", "%")?;
+        for function in self.functions.iter().take(DISPLAY_LIMIT) {
+            writeln!(
+                f,
+                "{:>12} {:>6.1}%  {} ({})",
+                function.instructions,
+                self.percent(function.instructions),
+                function.name,
+                function.filename
+            )?;
+        }
+        writeln!(f)?;
+        writeln!(f, "{:>12} {:>7}  line", "instructions", "%")?;
+        for line in self.lines.iter().take(DISPLAY_LIMIT) {
+            writeln!(
+                f,
+                "{:>12} {:>6.1}%  {}:{}",
+                line.instructions,
+                self.percent(line.instructions),
+                line.filename,
+                line.line
+            )?;
+        }
+        Ok(())
+    }
+}
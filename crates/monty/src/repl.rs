@@ -11,12 +11,14 @@ use ruff_python_parser::{InterpolatedStringErrorType, LexicalErrorType, ParseErr
 use crate::{
     ExcType, MontyException,
     asyncio::CallId,
+    audit::{AuditEvent, AuditEventKind, Capability},
     bytecode::{Code, Compiler, FrameExit, VM, VMSnapshot},
     exception_private::{RunError, RunResult},
-    heap::{DropWithHeap, Heap},
-    intern::{ExtFunctionId, InternerBuilder, Interns},
+    heap::{ContainsHeap, DropWithHeap, Heap},
+    intern::{ExtFunctionId, InternerBuilder, Interns, StaticStrings},
     io::PrintWriter,
-    namespace::{GLOBAL_NS_IDX, NamespaceId, Namespaces},
+    memory_profile::MemoryProfile,
+    namespace::{GLOBAL_NS_IDX, ModuleDunders, NamespaceId, Namespaces},
     object::MontyObject,
     os::OsFunction,
     parse::{parse, parse_with_interner},
@@ -55,13 +57,20 @@ impl ReplExecutor {
     ///
     /// This is equivalent to normal module compilation but scoped to REPL
     /// infrastructure so `run.rs` can remain unchanged.
+    ///
+    /// Also returns the module dunders, which are only needed to build the initial namespace.
     fn new(
         code: String,
         script_name: &str,
         input_names: Vec<String>,
         external_functions: Vec<String>,
-    ) -> Result<Self, MontyException> {
-        let parse_result = parse(&code, script_name).map_err(|e| e.into_python_exc(script_name, &code))?;
+    ) -> Result<(Self, ModuleDunders), MontyException> {
+        let mut parse_result = parse(&code, script_name).map_err(|e| e.into_python_exc(script_name, &code))?;
+        let dunders = ModuleDunders::new(
+            &mut parse_result,
+            script_name,
+            external_functions.len() + input_names.len(),
+        );
         let prepared = prepare(parse_result, input_names, &external_functions)
             .map_err(|e| e.into_python_exc(script_name, &code))?;
 
@@ -73,14 +82,15 @@ impl ReplExecutor {
             .map_err(|e| e.into_python_exc(script_name, &code))?;
         interns.set_functions(compile_result.functions);
 
-        Ok(Self {
+        let executor = Self {
             namespace_size: prepared.namespace_size,
             name_map: prepared.name_map,
             module_code: compile_result.code,
             interns,
             external_function_ids,
             code,
-        })
+        };
+        Ok((executor, dunders))
     }
 
     /// Compiles one incremental REPL snippet against existing session metadata.
@@ -123,21 +133,29 @@ impl ReplExecutor {
         })
     }
 
+    /// Checks the size of the interned data against the tracker's `max_interned_size` limit.
+    ///
+    /// The interns include those of all previous snippets, so a session that keeps compiling
+    /// new literals is refused once the table is too large rather than growing without bound.
+    fn check_interned_size(&self, tracker: &impl ResourceTracker) -> Result<(), MontyException> {
+        tracker
+            .check_interned_size(self.interns.data_size())
+            .map_err(|err| RunError::from(err).into_python_exception(&self.interns, &self.code))
+    }
+
     /// Builds the runtime namespace stack for module execution.
     ///
-    /// External function bindings are inserted first, then input values, then
-    /// remaining slots are initialized to `Undefined`.
+    /// External function bindings are inserted first, then input values, then the
+    /// module dunders, then remaining slots are initialized to `Undefined`.
     fn prepare_namespaces(
         &self,
         inputs: Vec<MontyObject>,
+        dunders: &ModuleDunders,
         heap: &mut Heap<impl ResourceTracker>,
     ) -> Result<Namespaces, MontyException> {
-        let Some(extra) = self
-            .namespace_size
-            .checked_sub(self.external_function_ids.len() + inputs.len())
-        else {
+        if self.external_function_ids.len() + inputs.len() > dunders.slot() {
             return Err(MontyException::runtime_error("too many inputs for namespace"));
-        };
+        }
 
         let mut namespace = Vec::with_capacity(self.namespace_size);
         for f_id in &self.external_function_ids {
@@ -150,9 +168,9 @@ impl ReplExecutor {
                     .map_err(|e| MontyException::runtime_error(format!("invalid input type: {e}")))?,
             );
         }
-        if extra > 0 {
-            namespace.extend((0..extra).map(|_| Value::Undefined));
-        }
+        namespace.resize_with(dunders.slot(), || Value::Undefined);
+        namespace.extend(dunders.values());
+        namespace.resize_with(self.namespace_size, || Value::Undefined);
         Ok(Namespaces::new(namespace))
     }
 }
@@ -173,6 +191,10 @@ fn frame_exit_to_object(
         } => {
             args.drop_with_heap(heap);
             let function_name = interns.get_external_function_name(ext_function_id);
+            heap.tracker().audit(&AuditEvent::now(AuditEventKind::CapabilityDenied {
+                capability: Capability::ExternalFunction,
+                name: &function_name,
+            }));
             Err(ExcType::not_implemented(format!(
                 "External function '{function_name}' not implemented with standard execution"
             ))
@@ -180,6 +202,11 @@ fn frame_exit_to_object(
         }
         FrameExit::OsCall { function, args, .. } => {
             args.drop_with_heap(heap);
+            let name = function.to_string();
+            heap.tracker().audit(&AuditEvent::now(AuditEventKind::CapabilityDenied {
+                capability: Capability::OsFunction,
+                name: &name,
+            }));
             Err(ExcType::not_implemented(format!(
                 "OS function '{function}' not implemented with standard execution"
             ))
@@ -188,14 +215,31 @@ fn frame_exit_to_object(
         FrameExit::MethodCall { method_name, args, .. } => {
             args.drop_with_heap(heap);
             let name = method_name.as_str(interns);
+            heap.tracker().audit(&AuditEvent::now(AuditEventKind::CapabilityDenied {
+                capability: Capability::MethodCall,
+                name: name,
+            }));
             Err(
                 ExcType::not_implemented(format!("Method call '{name}' not implemented with standard execution"))
                     .into(),
             )
         }
         FrameExit::ResolveFutures(_) => {
+            heap.tracker().audit(&AuditEvent::now(AuditEventKind::CapabilityDenied {
+                capability: Capability::AsyncFutures,
+                name: "await",
+            }));
             Err(ExcType::not_implemented("async futures not supported by standard execution.").into())
         }
+        FrameExit::Yield { value, .. } => {
+            value.drop_with_heap(heap);
+            heap.tracker().audit(&AuditEvent::now(AuditEventKind::CapabilityDenied {
+                capability: Capability::Yield,
+                name: "monty_yield",
+            }));
+            Err(ExcType::not_implemented("monty_yield() not supported by standard execution.").into())
+        }
+        FrameExit::Checkpoint => Err(RunError::internal("checkpoints are only enabled by `MontyRun::start`")),
     }
 }
 
@@ -276,6 +320,38 @@ pub struct MontyRepl<T: ResourceTracker> {
     heap: Heap<T>,
     /// Persistent namespace stack across snippets.
     namespaces: Namespaces,
+    /// Named namespaces created by [`Self::feed_in_namespace`], each with its own globals.
+    #[serde(default)]
+    named_namespaces: AHashMap<String, ReplNamespace>,
+    /// Named namespace swapped into the global slot while a snippet runs in it.
+    #[serde(default)]
+    active_namespace: Option<String>,
+    /// Recent non-`None` snippet results, oldest first, see [`Self::history`].
+    #[serde(default)]
+    history: Vec<MontyObject>,
+    /// Maximum number of results kept in `history`.
+    #[serde(default = "default_history_size")]
+    history_size: usize,
+}
+
+/// Number of results kept by [`MontyRepl::history`] unless changed with
+/// [`MontyRepl::set_history_size`].
+const DEFAULT_HISTORY_SIZE: usize = 100;
+
+fn default_history_size() -> usize {
+    DEFAULT_HISTORY_SIZE
+}
+
+/// Bookkeeping for a named REPL namespace.
+///
+/// The namespace's globals are parked in `Namespaces` so they share the session heap and stay
+/// GC roots while other namespaces run.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+struct ReplNamespace {
+    /// Index of the parked globals, see `Namespaces::park_global`.
+    parked_index: usize,
+    /// Stable mapping of the namespace's global variable names to slot IDs.
+    name_map: AHashMap<String, NamespaceId>,
 }
 
 impl<T: ResourceTracker> MontyRepl<T> {
@@ -300,10 +376,11 @@ impl<T: ResourceTracker> MontyRepl<T> {
         resource_tracker: T,
         print: &mut PrintWriter<'_>,
     ) -> Result<(Self, MontyObject), MontyException> {
-        let executor = ReplExecutor::new(code, script_name, input_names, external_function_names.clone())?;
+        let (executor, dunders) = ReplExecutor::new(code, script_name, input_names, external_function_names.clone())?;
+        executor.check_interned_size(&resource_tracker)?;
 
         let mut heap = Heap::new(executor.namespace_size, resource_tracker);
-        let mut namespaces = executor.prepare_namespaces(inputs, &mut heap)?;
+        let mut namespaces = executor.prepare_namespaces(inputs, &dunders, &mut heap)?;
 
         let mut vm = VM::new(&mut heap, &mut namespaces, &executor.interns, print);
         let frame_exit_result = vm.run_module(&executor.module_code);
@@ -320,6 +397,10 @@ impl<T: ResourceTracker> MontyRepl<T> {
             interns: executor.interns,
             heap,
             namespaces,
+            named_namespaces: AHashMap::new(),
+            active_namespace: None,
+            history: Vec::new(),
+            history_size: DEFAULT_HISTORY_SIZE,
         };
 
         Ok((repl, output))
@@ -336,10 +417,13 @@ impl<T: ResourceTracker> MontyRepl<T> {
     /// safely moved into snapshot objects for serialization and cross-process resume.
     ///
     /// # Errors
-    /// Returns `MontyException` for syntax/compile/runtime failures.
-    pub fn start(self, code: &str, print: &mut PrintWriter<'_>) -> Result<ReplProgress<T>, MontyException> {
+    /// Returns a [`ReplStartError`] for syntax/compile/runtime failures, and with a `MemoryError`
+    /// without running the snippet if its literals exceed the `max_interned_size` limit. The error
+    /// holds the REPL to continue the session with, as `feed` would have left it.
+    pub fn start(self, code: &str, print: &mut PrintWriter<'_>) -> Result<ReplProgress<T>, Box<ReplStartError<T>>> {
         let mut this = self;
         if code.is_empty() {
+            this.deactivate_namespace();
             return Ok(ReplProgress::Complete {
                 repl: this,
                 value: MontyObject::None,
@@ -347,13 +431,19 @@ impl<T: ResourceTracker> MontyRepl<T> {
         }
 
         let input_script_name = this.next_input_script_name();
-        let executor = ReplExecutor::new_repl_snippet(
+        let executor = match ReplExecutor::new_repl_snippet(
             code.to_owned(),
             &input_script_name,
             this.external_function_names.clone(),
             this.global_name_map.clone(),
             &this.interns,
-        )?;
+        ) {
+            Ok(executor) => executor,
+            Err(error) => return Err(this.start_error(error)),
+        };
+        if let Err(error) = executor.check_interned_size(this.heap.tracker()) {
+            return Err(this.start_error(error));
+        }
 
         this.ensure_global_namespace_size(executor.namespace_size);
 
@@ -367,11 +457,34 @@ impl<T: ResourceTracker> MontyRepl<T> {
         handle_repl_vm_result(vm_result, vm_state, executor, this)
     }
 
+    /// Starts executing a snippet in the named namespace, like [`Self::start`].
+    ///
+    /// See [`Self::feed_in_namespace`] for how namespaces are isolated. The REPL returned by
+    /// `ReplProgress::Complete` is back in the default namespace.
+    ///
+    /// # Errors
+    /// Same as [`Self::start`].
+    pub fn start_in_namespace(
+        mut self,
+        namespace: &str,
+        code: &str,
+        print: &mut PrintWriter<'_>,
+    ) -> Result<ReplProgress<T>, Box<ReplStartError<T>>> {
+        self.activate_namespace(namespace);
+        self.start(code, print)
+    }
+
     /// Starts snippet execution with `PrintWriter::Stdout` and no additional host output wiring.
-    pub fn start_no_print(self, code: &str) -> Result<ReplProgress<T>, MontyException> {
+    pub fn start_no_print(self, code: &str) -> Result<ReplProgress<T>, Box<ReplStartError<T>>> {
         self.start(code, &mut PrintWriter::Stdout)
     }
 
+    /// Wraps an error from starting or resuming a snippet, returning to the default namespace.
+    fn start_error(mut self, error: MontyException) -> Box<ReplStartError<T>> {
+        self.deactivate_namespace();
+        Box::new(ReplStartError { repl: self, error })
+    }
+
     /// Feeds and executes a new snippet against the current REPL state.
     ///
     /// This compiles only `code` using the existing global slot map, extends the
@@ -381,8 +494,73 @@ impl<T: ResourceTracker> MontyRepl<T> {
     /// matching Python REPL semantics.
     ///
     /// # Errors
-    /// Returns `MontyException` for syntax/compile/runtime failures.
+    /// Returns `MontyException` for syntax/compile/runtime failures, and `MemoryError`
+    /// without running the snippet if its literals exceed the `max_interned_size` limit.
     pub fn feed(&mut self, code: &str, print: &mut PrintWriter<'_>) -> Result<MontyObject, MontyException> {
+        self.feed_impl(code, print, None)
+    }
+
+    /// Feeds a snippet like [`Self::feed`], running it in the named namespace.
+    ///
+    /// Each namespace has its own globals, so names defined in one aren't visible in another,
+    /// while all namespaces share the session heap and resource limits. A namespace is created
+    /// on first use with only the external functions and `__name__`/`__doc__` defined; the
+    /// default namespace used by [`Self::feed`] keeps the initial code's globals and inputs.
+    ///
+    /// # Errors
+    /// Same as [`Self::feed`].
+    pub fn feed_in_namespace(
+        &mut self,
+        namespace: &str,
+        code: &str,
+        print: &mut PrintWriter<'_>,
+    ) -> Result<MontyObject, MontyException> {
+        self.activate_namespace(namespace);
+        let result = self.feed_impl(code, print, None);
+        self.deactivate_namespace();
+        result
+    }
+
+    /// Names of the namespaces created by [`Self::feed_in_namespace`], in no particular order.
+    #[must_use]
+    pub fn namespace_names(&self) -> Vec<String> {
+        self.named_namespaces.keys().cloned().collect()
+    }
+
+    /// Live heap objects by type across the session, and allocation sites if the tracker
+    /// traces allocations.
+    ///
+    /// Useful to tune resource limits and to find values accumulating over a long session.
+    #[must_use]
+    pub fn memory_profile(&self) -> MemoryProfile {
+        self.heap.memory_profile(&self.interns)
+    }
+
+    /// Feeds a snippet like [`Self::feed`], resolving OS calls with `os_handler` instead of raising.
+    ///
+    /// `os_handler` receives each OS function with its positional and keyword arguments, and
+    /// its result is returned to the snippet. This lets interactive hosts support `input()` and
+    /// other OS calls without driving the snapshots returned by [`Self::start`].
+    /// Returning `ExternalResult::Future` raises `NotImplementedError` in the snippet.
+    ///
+    /// # Errors
+    /// Same as [`Self::feed`].
+    pub fn feed_with_os_handler(
+        &mut self,
+        code: &str,
+        print: &mut PrintWriter<'_>,
+        mut os_handler: impl FnMut(OsFunction, &[MontyObject], &[(MontyObject, MontyObject)]) -> ExternalResult,
+    ) -> Result<MontyObject, MontyException> {
+        self.feed_impl(code, print, Some(&mut os_handler))
+    }
+
+    /// Shared implementation of [`Self::feed`] and [`Self::feed_with_os_handler`].
+    fn feed_impl(
+        &mut self,
+        code: &str,
+        print: &mut PrintWriter<'_>,
+        os_handler: Option<&mut OsHandler<'_>>,
+    ) -> Result<MontyObject, MontyException> {
         if code.is_empty() {
             return Ok(MontyObject::None);
         }
@@ -395,6 +573,7 @@ impl<T: ResourceTracker> MontyRepl<T> {
             self.global_name_map.clone(),
             &self.interns,
         )?;
+        executor.check_interned_size(self.heap.tracker())?;
 
         let ReplExecutor {
             namespace_size,
@@ -408,7 +587,10 @@ impl<T: ResourceTracker> MontyRepl<T> {
         self.ensure_global_namespace_size(namespace_size);
 
         let mut vm = VM::new(&mut self.heap, &mut self.namespaces, &interns, print);
-        let frame_exit_result = vm.run_module(&module_code);
+        let mut frame_exit_result = vm.run_module(&module_code);
+        if let Some(os_handler) = os_handler {
+            frame_exit_result = resolve_os_calls(&mut vm, frame_exit_result, &interns, os_handler);
+        }
         vm.cleanup();
 
         // Commit compiler metadata even on runtime errors.
@@ -417,8 +599,67 @@ impl<T: ResourceTracker> MontyRepl<T> {
         self.global_name_map = name_map;
         self.interns = interns;
 
-        frame_exit_to_object(frame_exit_result, &mut self.heap, &self.interns)
-            .map_err(|e| e.into_python_exception(&self.interns, &code))
+        if let Ok(FrameExit::Return(value)) = &frame_exit_result {
+            self.bind_last_result(value);
+        }
+        let output = frame_exit_to_object(frame_exit_result, &mut self.heap, &self.interns)
+            .map_err(|e| e.into_python_exception(&self.interns, &code))?;
+        self.push_history(&output);
+        Ok(output)
+    }
+
+    /// The last `n` non-`None` snippet results, oldest first.
+    ///
+    /// Results are recorded whenever a snippet ends with an expression, like the values the
+    /// CPython REPL echoes. Fewer than `n` are returned if fewer were recorded or kept, see
+    /// [`Self::set_history_size`].
+    #[must_use]
+    pub fn history(&self, n: usize) -> &[MontyObject] {
+        &self.history[self.history.len().saturating_sub(n)..]
+    }
+
+    /// Sets how many results [`Self::history`] keeps, 100 by default; zero disables it.
+    ///
+    /// The history lives outside the sandbox heap, so it isn't counted by resource limits.
+    pub fn set_history_size(&mut self, size: usize) {
+        self.history_size = size;
+        self.truncate_history();
+    }
+
+    /// Binds `_` in the global namespace to a snippet's result, like CPython's `sys.displayhook`.
+    ///
+    /// `None` results leave `_` unchanged. The binding is an ordinary global, so assigning `_`
+    /// works until the next result replaces it.
+    fn bind_last_result(&mut self, value: &Value) {
+        if matches!(value, Value::None) {
+            return;
+        }
+        let slot = if let Some(&slot) = self.global_name_map.get("_") {
+            slot
+        } else {
+            let global = self.namespaces.get_mut(GLOBAL_NS_IDX).mut_vec();
+            let slot = NamespaceId::new(global.len());
+            global.push(Value::Undefined);
+            self.global_name_map.insert("_".to_owned(), slot);
+            slot
+        };
+        let value = value.clone_with_heap(&mut self.heap);
+        let old = std::mem::replace(self.namespaces.get_mut(GLOBAL_NS_IDX).get_mut(slot), value);
+        old.drop_with_heap(&mut self.heap);
+    }
+
+    /// Records a snippet's result for [`Self::history`], ignoring `None`.
+    fn push_history(&mut self, output: &MontyObject) {
+        if !matches!(output, MontyObject::None) {
+            self.history.push(output.clone());
+            self.truncate_history();
+        }
+    }
+
+    /// Drops the oldest results beyond `history_size`.
+    fn truncate_history(&mut self) {
+        let excess = self.history.len().saturating_sub(self.history_size);
+        self.history.drain(..excess);
     }
 
     /// Executes a snippet with no additional host output wiring.
@@ -426,6 +667,46 @@ impl<T: ResourceTracker> MontyRepl<T> {
         self.feed(code, &mut PrintWriter::Stdout)
     }
 
+    /// Swaps the named namespace's globals into the global slot, creating it if needed.
+    fn activate_namespace(&mut self, name: &str) {
+        debug_assert!(self.active_namespace.is_none(), "a namespace is already active");
+        if !self.named_namespaces.contains_key(name) {
+            let namespace = self.new_namespace();
+            self.named_namespaces.insert(name.to_owned(), namespace);
+        }
+        let namespace = self.named_namespaces.get_mut(name).expect("namespace was just created");
+        self.namespaces.swap_parked_global(namespace.parked_index);
+        std::mem::swap(&mut self.global_name_map, &mut namespace.name_map);
+        self.active_namespace = Some(name.to_owned());
+    }
+
+    /// Swaps the active named namespace back out, restoring the default namespace.
+    fn deactivate_namespace(&mut self) {
+        if let Some(name) = self.active_namespace.take() {
+            let namespace = self.named_namespaces.get_mut(&name).expect("active namespace exists");
+            self.namespaces.swap_parked_global(namespace.parked_index);
+            std::mem::swap(&mut self.global_name_map, &mut namespace.name_map);
+        }
+    }
+
+    /// Creates the globals of a new named namespace: the external functions, then the dunders.
+    fn new_namespace(&mut self) -> ReplNamespace {
+        let mut name_map = AHashMap::new();
+        let mut globals = Vec::with_capacity(self.external_function_names.len() + 2);
+        for (index, name) in self.external_function_names.iter().enumerate() {
+            name_map.insert(name.clone(), NamespaceId::new(index));
+            globals.push(Value::ExtFunction(ExtFunctionId::new(index)));
+        }
+        name_map.insert("__name__".to_owned(), NamespaceId::new(globals.len()));
+        globals.push(Value::InternString(StaticStrings::DunderMain.into()));
+        name_map.insert("__doc__".to_owned(), NamespaceId::new(globals.len()));
+        globals.push(Value::None);
+        ReplNamespace {
+            parked_index: self.namespaces.park_global(globals),
+            name_map,
+        }
+    }
+
     /// Grows the global namespace to at least `namespace_size`.
     ///
     /// Newly introduced slots are initialized to `Undefined` to keep slot alignment
@@ -479,6 +760,39 @@ impl<T: ResourceTracker> Drop for MontyRepl<T> {
     }
 }
 
+/// Host callback used by [`MontyRepl::feed_with_os_handler`] to execute OS calls.
+type OsHandler<'h> = dyn FnMut(OsFunction, &[MontyObject], &[(MontyObject, MontyObject)]) -> ExternalResult + 'h;
+
+/// Resumes `vm` with the host's result for each OS call until the snippet finishes
+/// or suspends for something else.
+fn resolve_os_calls<T: ResourceTracker>(
+    vm: &mut VM<'_, '_, T>,
+    mut result: RunResult<FrameExit>,
+    interns: &Interns,
+    os_handler: &mut OsHandler<'_>,
+) -> RunResult<FrameExit> {
+    loop {
+        result = match result {
+            Ok(FrameExit::OsCall { function, args, .. }) => {
+                let (args_py, kwargs_py) = args.into_py_objects(vm.heap_mut(), interns);
+                vm.heap_mut().tracker().audit(&AuditEvent::now(AuditEventKind::OsCall {
+                    function,
+                    args: &args_py,
+                    kwargs: &kwargs_py,
+                }));
+                match os_handler(function, &args_py, &kwargs_py) {
+                    ExternalResult::Return(obj) => vm.resume(obj),
+                    ExternalResult::Error(exc) => vm.resume_with_exception(exc.into()),
+                    ExternalResult::Future => vm.resume_with_exception(
+                        ExcType::not_implemented("OS calls can't return futures when feeding a REPL snippet").into(),
+                    ),
+                }
+            }
+            other => return other,
+        };
+    }
+}
+
 /// Result of a single suspendable REPL snippet execution.
 ///
 /// This mirrors `RunProgress` but returns the updated `MontyRepl` on completion
@@ -516,6 +830,13 @@ pub enum ReplProgress<T: ResourceTracker> {
     },
     /// All async tasks are blocked waiting for external futures to resolve.
     ResolveFutures(ReplFutureSnapshot<T>),
+    /// Execution paused to stream a partial result from `monty_yield(value)`.
+    Yield {
+        /// The value passed to `monty_yield()`.
+        value: MontyObject,
+        /// Repl execution state that can be resumed.
+        state: ReplSnapshot<T>,
+    },
     /// Snippet execution completed with the updated REPL and result value.
     Complete {
         /// Updated REPL session state to continue feeding snippets.
@@ -563,6 +884,15 @@ impl<T: ResourceTracker> ReplProgress<T> {
         }
     }
 
+    /// Consumes the progress and returns the yielded value and state.
+    #[must_use]
+    pub fn into_yield(self) -> Option<(MontyObject, ReplSnapshot<T>)> {
+        match self {
+            Self::Yield { value, state } => Some((value, state)),
+            _ => None,
+        }
+    }
+
     /// Consumes the progress and returns the completed REPL and value.
     #[must_use]
     pub fn into_complete(self) -> Option<(MontyRepl<T>, MontyObject)> {
@@ -593,6 +923,32 @@ impl<T: ResourceTracker + serde::de::DeserializeOwned> ReplProgress<T> {
     }
 }
 
+/// A snippet started with [`MontyRepl::start`] failed, either before running or after resuming.
+///
+/// The session isn't lost: `repl` has any globals the snippet assigned before raising, like a
+/// failed [`MontyRepl::feed`], and is back in the default namespace.
+#[derive(Debug)]
+pub struct ReplStartError<T: ResourceTracker> {
+    /// The REPL session to continue with.
+    pub repl: MontyRepl<T>,
+    /// The exception raised by the snippet.
+    pub error: MontyException,
+}
+
+impl<T: ResourceTracker> std::fmt::Display for ReplStartError<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(&self.error, f)
+    }
+}
+
+impl<T: ResourceTracker + std::fmt::Debug> std::error::Error for ReplStartError<T> {}
+
+impl<T: ResourceTracker> From<Box<ReplStartError<T>>> for MontyException {
+    fn from(error: Box<ReplStartError<T>>) -> Self {
+        error.error
+    }
+}
+
 /// REPL execution state that can be resumed after an external call.
 ///
 /// This is the REPL-aware counterpart to `Snapshot`. Resuming continues the
@@ -621,7 +977,7 @@ impl<T: ResourceTracker> ReplSnapshot<T> {
         self,
         result: impl Into<ExternalResult>,
         print: &mut PrintWriter<'_>,
-    ) -> Result<ReplProgress<T>, MontyException> {
+    ) -> Result<ReplProgress<T>, Box<ReplStartError<T>>> {
         let Self {
             mut repl,
             executor,
@@ -659,7 +1015,7 @@ impl<T: ResourceTracker> ReplSnapshot<T> {
     /// Continues snippet execution by pushing an unresolved `ExternalFuture`.
     ///
     /// This is the REPL-aware async pattern equivalent to `Snapshot::run_pending`.
-    pub fn run_pending(self, print: &mut PrintWriter<'_>) -> Result<ReplProgress<T>, MontyException> {
+    pub fn run_pending(self, print: &mut PrintWriter<'_>) -> Result<ReplProgress<T>, Box<ReplStartError<T>>> {
         self.run(MontyFuture, print)
     }
 }
@@ -693,12 +1049,12 @@ impl<T: ResourceTracker> ReplFutureSnapshot<T> {
     /// pending call IDs and continue resolving over multiple resumes.
     ///
     /// # Errors
-    /// Returns `MontyException` if an unknown call ID is provided.
+    /// Returns a [`ReplStartError`] if an unknown call ID is provided or the snippet raises.
     pub fn resume(
         self,
         results: Vec<(u32, ExternalResult)>,
         print: &mut PrintWriter<'_>,
-    ) -> Result<ReplProgress<T>, MontyException> {
+    ) -> Result<ReplProgress<T>, Box<ReplStartError<T>>> {
         let Self {
             mut repl,
             executor,
@@ -722,18 +1078,22 @@ impl<T: ResourceTracker> ReplFutureSnapshot<T> {
 
         if let Some(call_id) = invalid_call_id {
             vm.cleanup();
-            #[cfg(feature = "ref-count-panic")]
-            repl.namespaces.drop_global_with_heap(&mut repl.heap);
-            return Err(MontyException::runtime_error(format!(
+            let error = MontyException::runtime_error(format!(
                 "unknown call_id {call_id}, expected one of: {pending_call_ids:?}"
-            )));
+            ));
+            return Err(snippet_error(repl, executor, error));
         }
 
         for (call_id, ext_result) in results {
             match ext_result {
-                ExternalResult::Return(obj) => vm.resolve_future(call_id, obj).map_err(|e| {
-                    MontyException::runtime_error(format!("Invalid return type for call {call_id}: {e}"))
-                })?,
+                ExternalResult::Return(obj) => {
+                    if let Err(e) = vm.resolve_future(call_id, obj) {
+                        vm.cleanup();
+                        let error =
+                            MontyException::runtime_error(format!("Invalid return type for call {call_id}: {e}"));
+                        return Err(snippet_error(repl, executor, error));
+                    }
+                }
                 ExternalResult::Error(exc) => vm.fail_future(call_id, RunError::from(exc)),
                 ExternalResult::Future => {}
             }
@@ -741,9 +1101,8 @@ impl<T: ResourceTracker> ReplFutureSnapshot<T> {
 
         if let Some(error) = vm.take_failed_task_error() {
             vm.cleanup();
-            #[cfg(feature = "ref-count-panic")]
-            repl.namespaces.drop_global_with_heap(&mut repl.heap);
-            return Err(error.into_python_exception(&executor.interns, &executor.code));
+            let error = error.into_python_exception(&executor.interns, &executor.code);
+            return Err(snippet_error(repl, executor, error));
         }
 
         let main_task_ready = vm.prepare_current_task_after_resolve();
@@ -752,9 +1111,8 @@ impl<T: ResourceTracker> ReplFutureSnapshot<T> {
             Ok(loaded) => loaded,
             Err(e) => {
                 vm.cleanup();
-                #[cfg(feature = "ref-count-panic")]
-                repl.namespaces.drop_global_with_heap(&mut repl.heap);
-                return Err(e.into_python_exception(&executor.interns, &executor.code));
+                let error = e.into_python_exception(&executor.interns, &executor.code);
+                return Err(snippet_error(repl, executor, error));
             }
         };
 
@@ -788,7 +1146,7 @@ fn handle_repl_vm_result<T: ResourceTracker>(
     vm_state: Option<VMSnapshot>,
     executor: ReplExecutor,
     mut repl: MontyRepl<T>,
-) -> Result<ReplProgress<T>, MontyException> {
+) -> Result<ReplProgress<T>, Box<ReplStartError<T>>> {
     macro_rules! new_repl_snapshot {
         ($call_id: expr) => {
             ReplSnapshot {
@@ -802,10 +1160,13 @@ fn handle_repl_vm_result<T: ResourceTracker>(
 
     match result {
         Ok(FrameExit::Return(value)) => {
-            let output = MontyObject::new(value, &mut repl.heap, &executor.interns);
             let ReplExecutor { name_map, interns, .. } = executor;
             repl.global_name_map = name_map;
             repl.interns = interns;
+            repl.bind_last_result(&value);
+            let output = MontyObject::new(value, &mut repl.heap, &repl.interns);
+            repl.push_history(&output);
+            repl.deactivate_namespace();
             Ok(ReplProgress::Complete { repl, value: output })
         }
         Ok(FrameExit::ExternalCall {
@@ -815,6 +1176,14 @@ fn handle_repl_vm_result<T: ResourceTracker>(
         }) => {
             let function_name = executor.interns.get_external_function_name(ext_function_id);
             let (args_py, kwargs_py) = args.into_py_objects(&mut repl.heap, &executor.interns);
+            repl.heap
+                .tracker()
+                .audit(&AuditEvent::now(AuditEventKind::ExternalCall {
+                    function_name: &function_name,
+                    method_call: false,
+                    args: &args_py,
+                    kwargs: &kwargs_py,
+                }));
 
             Ok(ReplProgress::FunctionCall {
                 function_name,
@@ -831,6 +1200,11 @@ fn handle_repl_vm_result<T: ResourceTracker>(
             call_id,
         }) => {
             let (args_py, kwargs_py) = args.into_py_objects(&mut repl.heap, &executor.interns);
+            repl.heap.tracker().audit(&AuditEvent::now(AuditEventKind::OsCall {
+                function,
+                args: &args_py,
+                kwargs: &kwargs_py,
+            }));
 
             Ok(ReplProgress::OsCall {
                 function,
@@ -847,6 +1221,14 @@ fn handle_repl_vm_result<T: ResourceTracker>(
         }) => {
             let function_name = method_name.into_string(&executor.interns);
             let (args_py, kwargs_py) = args.into_py_objects(&mut repl.heap, &executor.interns);
+            repl.heap
+                .tracker()
+                .audit(&AuditEvent::now(AuditEventKind::ExternalCall {
+                    function_name: &function_name,
+                    method_call: true,
+                    args: &args_py,
+                    kwargs: &kwargs_py,
+                }));
 
             Ok(ReplProgress::FunctionCall {
                 function_name,
@@ -857,6 +1239,14 @@ fn handle_repl_vm_result<T: ResourceTracker>(
                 state: new_repl_snapshot!(call_id),
             })
         }
+        Ok(FrameExit::Yield { value, call_id }) => {
+            let value = MontyObject::new(value, &mut repl.heap, &executor.interns);
+            Ok(ReplProgress::Yield {
+                value,
+                state: new_repl_snapshot!(call_id),
+            })
+        }
+        Ok(FrameExit::Checkpoint) => unreachable!("checkpoints are only enabled by `MontyRun::start`"),
         Ok(FrameExit::ResolveFutures(pending_call_ids)) => {
             let pending_call_ids: Vec<u32> = pending_call_ids.iter().map(|id| id.raw()).collect();
             Ok(ReplProgress::ResolveFutures(ReplFutureSnapshot {
@@ -867,10 +1257,23 @@ fn handle_repl_vm_result<T: ResourceTracker>(
             }))
         }
         Err(err) => {
-            #[cfg(feature = "ref-count-panic")]
-            repl.namespaces.drop_global_with_heap(&mut repl.heap);
-
-            Err(err.into_python_exception(&executor.interns, &executor.code))
+            let error = err.into_python_exception(&executor.interns, &executor.code);
+            Err(snippet_error(repl, executor, error))
         }
     }
 }
+
+/// Ends a snippet that failed after it started running, keeping the REPL session.
+///
+/// Compiler metadata is committed as in [`MontyRepl::feed`], since the snippet may have assigned
+/// globals before failing.
+fn snippet_error<T: ResourceTracker>(
+    mut repl: MontyRepl<T>,
+    executor: ReplExecutor,
+    error: MontyException,
+) -> Box<ReplStartError<T>> {
+    let ReplExecutor { name_map, interns, .. } = executor;
+    repl.global_name_map = name_map;
+    repl.interns = interns;
+    repl.start_error(error)
+}
@@ -6,7 +6,10 @@ use std::{
 
 use crate::{
     ExcType, MontyException,
+    audit::AuditEvent,
+    entropy::EntropyPolicy,
     exception_private::{ExceptionRaise, RawStackFrame, RunError, SimpleException},
+    hashing::random_seed,
 };
 
 /// Threshold in bytes above which `check_large_result` is called.
@@ -17,6 +20,21 @@ use crate::{
 /// the allocation check can catch them.
 pub const LARGE_RESULT_THRESHOLD: usize = 100_000;
 
+/// Default maximum Rust stack usage of the interpreter, see `ResourceLimits::max_stack_size`.
+///
+/// Well below the 2MiB stack Rust gives spawned threads, so even debug builds raise
+/// `RecursionError` before the thread's stack overflows and aborts the process.
+pub const DEFAULT_MAX_STACK_SIZE: usize = 1024 * 1024;
+
+/// Returns the approximate address of the top of the current thread's stack.
+///
+/// The difference between two calls measures how much stack was used in between.
+#[inline(never)]
+pub(crate) fn stack_address() -> usize {
+    let marker = 0u8;
+    std::hint::black_box(&raw const marker).addr()
+}
+
 /// Pre-checks that a sequence repeat won't exceed resource limits before allocating.
 ///
 /// This prevents DoS via expressions like `'x' * 999_999_999` or `b'ab' * huge_int`
@@ -77,6 +95,14 @@ pub fn check_div_size(dividend_bits: u64, tracker: &impl ResourceTracker) -> Res
     check_estimated_size(estimate_bits_to_bytes(dividend_bits), tracker)
 }
 
+/// Pre-checks that decompressed data of `len` bytes won't exceed resource limits.
+///
+/// Called before each chunk is inflated so a small "decompression bomb" can't expand
+/// into gigabytes of Rust heap before the result is allocated and tracked.
+pub fn check_decompressed_size(len: usize, tracker: &impl ResourceTracker) -> Result<(), ResourceError> {
+    check_estimated_size(len, tracker)
+}
+
 /// Checks an estimated result size against the resource tracker.
 ///
 /// Only calls the tracker when the estimate exceeds `LARGE_RESULT_THRESHOLD`
@@ -191,6 +217,12 @@ pub enum ResourceError {
     Memory { limit: usize, used: usize },
     /// Maximum recursion depth exceeded.
     Recursion { limit: usize, depth: usize },
+    /// Maximum Rust stack usage exceeded, raised as `RecursionError` before the stack overflows.
+    Stack { limit: usize, used: usize, depth: usize },
+    /// A value leaving the sandbox (the result or external call arguments) is too large.
+    OutputSize { limit: usize },
+    /// The interned strings, bytes and integers of compiled code are too large.
+    InternedSize { limit: usize, size: usize },
     /// Any other error, e.g. when propagating a python exception
     Exception(MontyException),
 }
@@ -210,6 +242,18 @@ impl fmt::Display for ResourceError {
             Self::Recursion { .. } => {
                 write!(f, "maximum recursion depth exceeded")
             }
+            Self::Stack { limit, used, depth } => {
+                write!(
+                    f,
+                    "maximum recursion depth exceeded: stack usage {used} bytes > {limit} bytes at depth {depth}"
+                )
+            }
+            Self::OutputSize { limit } => {
+                write!(f, "output size limit exceeded: value is larger than {limit} bytes")
+            }
+            Self::InternedSize { limit, size } => {
+                write!(f, "interned data limit exceeded: {size} bytes > {limit} bytes")
+            }
             Self::Exception(exc) => {
                 write!(f, "{exc}")
             }
@@ -227,6 +271,9 @@ impl ResourceError {
     /// - `Memory` → `MemoryError`
     /// - `Time` → `TimeoutError`
     /// - `Recursion` → `RecursionError`
+    /// - `Stack` → `RecursionError`
+    /// - `OutputSize` → `MemoryError`
+    /// - `InternedSize` → `MemoryError`
     #[must_use]
     pub(crate) fn into_exception(self, frame: Option<RawStackFrame>) -> ExceptionRaise {
         let (exc_type, msg) = match self {
@@ -246,6 +293,22 @@ impl ResourceError {
                 ExcType::RecursionError,
                 Some("maximum recursion depth exceeded".to_string()),
             ),
+            Self::Stack { limit, used, depth } => (
+                ExcType::RecursionError,
+                Some(format!(
+                    "maximum recursion depth exceeded: stack usage {used} bytes > {limit} bytes at depth {depth}"
+                )),
+            ),
+            Self::OutputSize { limit } => (
+                ExcType::MemoryError,
+                Some(format!(
+                    "output size limit exceeded: value is larger than {limit} bytes"
+                )),
+            ),
+            Self::InternedSize { limit, size } => (
+                ExcType::MemoryError,
+                Some(format!("interned data limit exceeded: {size} bytes > {limit} bytes")),
+            ),
             Self::Exception(exc) => (exc.exc_type(), exc.into_message()),
         };
         let exc = SimpleException::new(exc_type, msg);
@@ -317,6 +380,122 @@ pub trait ResourceTracker: fmt::Debug {
     ///
     /// Returns `Ok(())` to allow the operation, or `Err(ResourceError)` to reject.
     fn check_large_result(&self, estimated_bytes: usize) -> Result<(), ResourceError>;
+
+    /// Maximum approximate size in bytes of a value leaving the sandbox, if limited.
+    ///
+    /// Checked for the result of a run and the arguments of external function, OS and
+    /// dataclass method calls before they're converted to `MontyObject`s.
+    fn max_output_size(&self) -> Option<usize>;
+
+    /// Called by `MontyRepl` after compiling a snippet to check the total size of the interned
+    /// data the session needs.
+    ///
+    /// Interned strings, bytes and long integers live outside the heap for the lifetime of
+    /// the compiled code. For a REPL session they accumulate across snippets since previously
+    /// interned IDs must stay valid, so each snippet is checked before it runs. Single runs
+    /// aren't checked, their interned data is bounded by the code's size.
+    ///
+    /// Unlimited by default.
+    ///
+    /// # Arguments
+    /// * `size` - Total size in bytes of all interned data, including previous snippets
+    #[inline]
+    fn check_interned_size(&self, _size: usize) -> Result<(), ResourceError> {
+        Ok(())
+    }
+
+    /// Maximum Rust stack in bytes the interpreter may use, if limited.
+    ///
+    /// Checked alongside `check_recursion_depth` before each call frame is pushed, measuring
+    /// stack usage since execution started or resumed. Exceeding it raises `RecursionError`
+    /// rather than letting deep Rust recursion overflow the thread's stack.
+    fn max_stack_size(&self) -> Option<usize>;
+
+    /// Number of instructions between automatic checkpoints, if enabled.
+    ///
+    /// When set, execution started with [`MontyRun::start`](crate::MontyRun::start) pauses with
+    /// `RunProgress::Checkpoint` every N instructions so the host can serialize the state.
+    #[inline]
+    fn checkpoint_interval(&self) -> Option<usize> {
+        None
+    }
+
+    /// Seed mixed into str and bytes hashes, read once when the heap is created.
+    ///
+    /// The default of zero makes `hash()` results reproducible across runs and machines.
+    /// The seed is stored in snapshots, so resumed executions keep using it.
+    #[inline]
+    fn hash_seed(&self) -> u64 {
+        0
+    }
+
+    /// Where sandboxed code's randomness comes from, read once when the heap is created.
+    ///
+    /// Randomness is denied by default. The policy is stored in snapshots, along with the
+    /// position in a seeded stream, so resumed executions don't repeat values.
+    #[inline]
+    fn entropy(&self) -> EntropyPolicy {
+        EntropyPolicy::Denied
+    }
+
+    /// Whether to record where heap allocations happen, for the allocation sites of
+    /// [`MemoryProfile`](crate::MemoryProfile).
+    ///
+    /// Tracing is off by default since it costs a lookup per executed instruction.
+    #[inline]
+    fn trace_allocations(&self) -> bool {
+        false
+    }
+
+    /// Whether the VM should report every executed instruction to [`Self::on_instruction`].
+    ///
+    /// Read once when execution starts or resumes. Off by default, see
+    /// [`Profiler`](crate::Profiler) for counting instructions per line and function.
+    #[inline]
+    fn profile_instructions(&self) -> bool {
+        false
+    }
+
+    /// Called before each instruction when [`Self::profile_instructions`] is true.
+    ///
+    /// # Arguments
+    /// * `filename` - Script or REPL input containing the instruction
+    /// * `line` - Source line of the instruction, starting from 1
+    /// * `function` - Name of the executing function, `"<module>"` for module-level code
+    #[inline]
+    fn on_instruction(&mut self, filename: &str, line: u16, function: &str) {
+        let _ = (filename, line, function);
+    }
+
+    /// Whether the VM should check the heap's invariants before every instruction.
+    ///
+    /// Only available with the `heap-validate` feature, which always checks the heap when
+    /// execution pauses. Read once when execution starts or resumes, see [`HeapValidator`].
+    #[cfg(feature = "heap-validate")]
+    #[inline]
+    fn validate_heap_every_instruction(&self) -> bool {
+        false
+    }
+
+    /// Called for security-relevant events like external calls, imports and limit breaches.
+    ///
+    /// Does nothing by default, see [`AuditTracker`](crate::AuditTracker) for passing events
+    /// to a hook.
+    #[inline]
+    fn audit(&self, event: &AuditEvent<'_>) {
+        let _ = event;
+    }
+
+    /// Combines this tracker with `other`, so the limits of both are enforced.
+    ///
+    /// See [`ComposedTracker`] for how the trackers are combined.
+    #[must_use]
+    fn chain<U: ResourceTracker>(self, other: U) -> ComposedTracker<Self, U>
+    where
+        Self: Sized,
+    {
+        ComposedTracker::new(self, other)
+    }
 }
 
 /// A resource tracker that imposes no limits except default recursion limit.
@@ -361,6 +540,17 @@ impl ResourceTracker for NoLimitTracker {
         // No limit - always allow operations regardless of result size
         Ok(())
     }
+
+    #[inline]
+    fn max_output_size(&self) -> Option<usize> {
+        None
+    }
+
+    /// Like the recursion limit, the default stack limit still applies.
+    #[inline]
+    fn max_stack_size(&self) -> Option<usize> {
+        Some(DEFAULT_MAX_STACK_SIZE)
+    }
 }
 
 /// Configuration for resource limits.
@@ -380,17 +570,57 @@ pub struct ResourceLimits {
     pub gc_interval: Option<usize>,
     /// Maximum recursion depth (function call stack depth).
     pub max_recursion_depth: Option<usize>,
+    /// Maximum approximate size in bytes of the result and of external call arguments.
+    ///
+    /// Limits values leaving the sandbox, where shared references are expanded, e.g.
+    /// `[big_list] * 1000` is counted as a thousand copies of `big_list`.
+    pub max_output_size: Option<usize>,
+    /// Maximum size in bytes of interned strings, bytes and long integers.
+    ///
+    /// Interned data comes from literals and names in compiled code, so it only grows
+    /// across the snippets of a REPL session. Only enforced by `MontyRepl`, which refuses
+    /// snippets that would exceed it; `MontyRun` doesn't check it.
+    pub max_interned_size: Option<usize>,
+    /// Maximum Rust stack in bytes used by the interpreter, measured from where execution
+    /// started or resumed.
+    ///
+    /// A safety margin against overflowing the host thread's stack, which would abort the
+    /// process, set it comfortably below the stack size of the thread running Monty.
+    pub max_stack_size: Option<usize>,
+    /// Pause with `RunProgress::Checkpoint` every N instructions.
+    ///
+    /// Lets the host serialize long computations periodically, for preemption, migration
+    /// between hosts or crash recovery. Only used by the iterative `MontyRun::start` API.
+    pub checkpoint_interval: Option<usize>,
+    /// Record the source lines allocating heap objects, reported by `memory_profile()`.
+    pub trace_allocations: bool,
+    /// Seed mixed into str and bytes hashes, zero (the default) for reproducible `hash()` results.
+    pub hash_seed: u64,
+    /// Source of randomness for sandboxed code, denied by default.
+    pub entropy: EntropyPolicy,
+    /// Maximum nesting depth of statements and expressions when parsing.
+    ///
+    /// Can only lower the built-in limit of 200 (less in debug builds) protecting the host's stack.
+    /// This and the other compile-time limits are only checked by
+    /// [`MontyRun::new_with_limits`](crate::MontyRun::new_with_limits).
+    pub max_parse_depth: Option<usize>,
+    /// Maximum number of statements and expressions in the parsed code.
+    pub max_ast_nodes: Option<usize>,
+    /// Maximum total size in bytes of the compiled bytecode, including all functions.
+    pub max_bytecode_size: Option<usize>,
 }
 
 /// Recommended maximum recursion depth if not otherwise specified.
 pub const DEFAULT_MAX_RECURSION_DEPTH: usize = 1000;
 
 impl ResourceLimits {
-    /// Creates a new ResourceLimits with all limits disabled, except max recursion which is set to 1000.
+    /// Creates a new ResourceLimits with all limits disabled, except max recursion which is set to 1000
+    /// and max stack size which is set to `DEFAULT_MAX_STACK_SIZE`.
     #[must_use]
     pub fn new() -> Self {
         Self {
             max_recursion_depth: Some(1000),
+            max_stack_size: Some(DEFAULT_MAX_STACK_SIZE),
             ..Default::default()
         }
     }
@@ -429,6 +659,94 @@ impl ResourceLimits {
         self.max_recursion_depth = limit;
         self
     }
+
+    /// Sets the maximum approximate size in bytes of the result and of external call arguments.
+    #[must_use]
+    pub fn max_output_size(mut self, limit: usize) -> Self {
+        self.max_output_size = Some(limit);
+        self
+    }
+
+    /// Sets the maximum size in bytes of interned strings, bytes and long integers.
+    #[must_use]
+    pub fn max_interned_size(mut self, limit: usize) -> Self {
+        self.max_interned_size = Some(limit);
+        self
+    }
+
+    /// Sets the maximum Rust stack usage in bytes, `None` disables the check.
+    #[must_use]
+    pub fn max_stack_size(mut self, limit: Option<usize>) -> Self {
+        self.max_stack_size = limit;
+        self
+    }
+
+    /// Sets the number of instructions between automatic checkpoints.
+    ///
+    /// # Panics
+    /// Panics if `interval` is zero.
+    #[must_use]
+    pub fn checkpoint_interval(mut self, interval: usize) -> Self {
+        assert!(interval > 0, "checkpoint interval must be greater than zero");
+        self.checkpoint_interval = Some(interval);
+        self
+    }
+
+    /// Enables or disables recording the source lines allocating heap objects.
+    #[must_use]
+    pub fn trace_allocations(mut self, enabled: bool) -> Self {
+        self.trace_allocations = enabled;
+        self
+    }
+
+    /// Sets the seed mixed into str and bytes hashes.
+    ///
+    /// Runs with the same seed, including the default of zero, produce the same `hash()` results.
+    #[must_use]
+    pub fn hash_seed(mut self, seed: u64) -> Self {
+        self.hash_seed = seed;
+        self
+    }
+
+    /// Picks a random seed for str and bytes hashes, like CPython without `PYTHONHASHSEED`.
+    ///
+    /// Use it when `hash()` results don't need to be reproducible and code is untrusted, to
+    /// make hash collisions hard to provoke. Dicts and sets keep their insertion order.
+    #[must_use]
+    pub fn random_hash_seed(mut self) -> Self {
+        self.hash_seed = random_seed();
+        self
+    }
+
+    /// Sets where sandboxed code's randomness comes from, e.g. for `secrets.token_hex()`.
+    ///
+    /// Randomness is denied unless enabled here, use `EntropyPolicy::Seeded` for reproducible runs.
+    #[must_use]
+    pub fn entropy(mut self, policy: EntropyPolicy) -> Self {
+        self.entropy = policy;
+        self
+    }
+
+    /// Sets the maximum nesting depth when parsing, capped at the built-in limit.
+    #[must_use]
+    pub fn max_parse_depth(mut self, limit: usize) -> Self {
+        self.max_parse_depth = Some(limit);
+        self
+    }
+
+    /// Sets the maximum number of statements and expressions in the parsed code.
+    #[must_use]
+    pub fn max_ast_nodes(mut self, limit: usize) -> Self {
+        self.max_ast_nodes = Some(limit);
+        self
+    }
+
+    /// Sets the maximum total size in bytes of the compiled bytecode.
+    #[must_use]
+    pub fn max_bytecode_size(mut self, limit: usize) -> Self {
+        self.max_bytecode_size = Some(limit);
+        self
+    }
 }
 
 /// How often to actually check `Instant::elapsed()` in `check_time`.
@@ -591,4 +909,326 @@ impl ResourceTracker for LimitedTracker {
         }
         Ok(())
     }
+
+    fn max_output_size(&self) -> Option<usize> {
+        self.limits.max_output_size
+    }
+
+    fn check_interned_size(&self, size: usize) -> Result<(), ResourceError> {
+        if let Some(max) = self.limits.max_interned_size
+            && size > max
+        {
+            return Err(ResourceError::InternedSize { limit: max, size });
+        }
+        Ok(())
+    }
+
+    fn max_stack_size(&self) -> Option<usize> {
+        self.limits.max_stack_size
+    }
+
+    fn checkpoint_interval(&self) -> Option<usize> {
+        self.limits.checkpoint_interval
+    }
+
+    fn trace_allocations(&self) -> bool {
+        self.limits.trace_allocations
+    }
+
+    fn hash_seed(&self) -> u64 {
+        self.limits.hash_seed
+    }
+
+    fn entropy(&self) -> EntropyPolicy {
+        self.limits.entropy
+    }
+}
+
+/// A resource tracker combining two trackers, usually created with [`ResourceTracker::chain`].
+///
+/// Allows limits, cancellation and metrics to come from separate trackers instead of writing
+/// a custom tracker that does everything, e.g.
+/// `LimitedTracker::new(limits).chain(InterruptTracker::new(check, 1000))`.
+/// Chain more than two trackers by chaining the result again.
+///
+/// - Checks run on `first` then `second`, failing with the first error.
+/// - Allocations are reported to both trackers; if `second` refuses one that `first`
+///   accepted, it's reported to `first` as freed again so both stay consistent.
+/// - Size limits (`max_output_size`, `max_stack_size`) and `checkpoint_interval` are the smaller
+///   of the two.
+/// - The hash seed is `first`'s unless it's zero, then `second`'s.
+/// - Allocations are traced and instructions profiled if either tracker asks for it.
+/// - Audit events are passed to both trackers.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ComposedTracker<A, B> {
+    first: A,
+    second: B,
+}
+
+impl<A: ResourceTracker, B: ResourceTracker> ComposedTracker<A, B> {
+    /// Creates a tracker enforcing the limits of both `first` and `second`.
+    #[must_use]
+    pub fn new(first: A, second: B) -> Self {
+        Self { first, second }
+    }
+
+    /// Returns the first tracker.
+    pub fn first(&self) -> &A {
+        &self.first
+    }
+
+    /// Returns the first tracker mutably, e.g. to call `LimitedTracker::set_max_duration`.
+    pub fn first_mut(&mut self) -> &mut A {
+        &mut self.first
+    }
+
+    /// Returns the second tracker.
+    pub fn second(&self) -> &B {
+        &self.second
+    }
+
+    /// Returns the second tracker mutably.
+    pub fn second_mut(&mut self) -> &mut B {
+        &mut self.second
+    }
+
+    /// Splits the tracker back into its parts.
+    pub fn into_parts(self) -> (A, B) {
+        (self.first, self.second)
+    }
+}
+
+impl<A: ResourceTracker, B: ResourceTracker> ResourceTracker for ComposedTracker<A, B> {
+    fn on_allocate(&mut self, get_size: impl FnOnce() -> usize) -> Result<(), ResourceError> {
+        let size = get_size();
+        self.first.on_allocate(|| size)?;
+        if let Err(err) = self.second.on_allocate(|| size) {
+            self.first.on_free(|| size);
+            return Err(err);
+        }
+        Ok(())
+    }
+
+    fn on_free(&mut self, get_size: impl FnOnce() -> usize) {
+        let size = get_size();
+        self.first.on_free(|| size);
+        self.second.on_free(|| size);
+    }
+
+    fn check_time(&self) -> Result<(), ResourceError> {
+        self.first.check_time()?;
+        self.second.check_time()
+    }
+
+    fn check_recursion_depth(&self, current_depth: usize) -> Result<(), ResourceError> {
+        self.first.check_recursion_depth(current_depth)?;
+        self.second.check_recursion_depth(current_depth)
+    }
+
+    fn check_large_result(&self, estimated_bytes: usize) -> Result<(), ResourceError> {
+        self.first.check_large_result(estimated_bytes)?;
+        self.second.check_large_result(estimated_bytes)
+    }
+
+    fn max_output_size(&self) -> Option<usize> {
+        min_limit(self.first.max_output_size(), self.second.max_output_size())
+    }
+
+    fn check_interned_size(&self, size: usize) -> Result<(), ResourceError> {
+        self.first.check_interned_size(size)?;
+        self.second.check_interned_size(size)
+    }
+
+    fn max_stack_size(&self) -> Option<usize> {
+        min_limit(self.first.max_stack_size(), self.second.max_stack_size())
+    }
+
+    fn checkpoint_interval(&self) -> Option<usize> {
+        min_limit(self.first.checkpoint_interval(), self.second.checkpoint_interval())
+    }
+
+    fn hash_seed(&self) -> u64 {
+        match self.first.hash_seed() {
+            0 => self.second.hash_seed(),
+            seed => seed,
+        }
+    }
+
+    fn entropy(&self) -> EntropyPolicy {
+        match self.first.entropy() {
+            EntropyPolicy::Denied => self.second.entropy(),
+            policy => policy,
+        }
+    }
+
+    fn trace_allocations(&self) -> bool {
+        self.first.trace_allocations() || self.second.trace_allocations()
+    }
+
+    fn profile_instructions(&self) -> bool {
+        self.first.profile_instructions() || self.second.profile_instructions()
+    }
+
+    fn on_instruction(&mut self, filename: &str, line: u16, function: &str) {
+        if self.first.profile_instructions() {
+            self.first.on_instruction(filename, line, function);
+        }
+        if self.second.profile_instructions() {
+            self.second.on_instruction(filename, line, function);
+        }
+    }
+
+    #[cfg(feature = "heap-validate")]
+    fn validate_heap_every_instruction(&self) -> bool {
+        self.first.validate_heap_every_instruction() || self.second.validate_heap_every_instruction()
+    }
+
+    fn audit(&self, event: &AuditEvent<'_>) {
+        self.first.audit(event);
+        self.second.audit(event);
+    }
+}
+
+/// A resource tracker with no limits that makes the VM check the heap before every instruction.
+///
+/// Only available with the `heap-validate` feature. Chain it onto the tracker enforcing
+/// limits, e.g. `LimitedTracker::new(limits).chain(HeapValidator)`. Validation walks the
+/// whole heap, so expect execution to be orders of magnitude slower; it's meant for tests
+/// and for tracking down reference counting bugs.
+#[cfg(feature = "heap-validate")]
+#[derive(Debug, Clone, Copy, Default, serde::Serialize, serde::Deserialize)]
+pub struct HeapValidator;
+
+#[cfg(feature = "heap-validate")]
+impl ResourceTracker for HeapValidator {
+    #[inline]
+    fn on_allocate(&mut self, _: impl FnOnce() -> usize) -> Result<(), ResourceError> {
+        Ok(())
+    }
+
+    #[inline]
+    fn on_free(&mut self, _: impl FnOnce() -> usize) {}
+
+    #[inline]
+    fn check_time(&self) -> Result<(), ResourceError> {
+        Ok(())
+    }
+
+    #[inline]
+    fn check_recursion_depth(&self, _current_depth: usize) -> Result<(), ResourceError> {
+        Ok(())
+    }
+
+    #[inline]
+    fn check_large_result(&self, _estimated_bytes: usize) -> Result<(), ResourceError> {
+        Ok(())
+    }
+
+    #[inline]
+    fn max_output_size(&self) -> Option<usize> {
+        None
+    }
+
+    #[inline]
+    fn max_stack_size(&self) -> Option<usize> {
+        None
+    }
+
+    fn validate_heap_every_instruction(&self) -> bool {
+        true
+    }
+}
+
+/// Returns the stricter of two optional limits.
+fn min_limit(a: Option<usize>, b: Option<usize>) -> Option<usize> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    }
+}
+
+/// A check run periodically by [`InterruptTracker`] to stop execution from outside.
+///
+/// Return an error to stop execution, e.g. `ResourceError::Exception` with a
+/// `KeyboardInterrupt` when a cancellation flag is set or a signal was received.
+pub trait InterruptCheck: fmt::Debug {
+    /// Checks whether execution should stop.
+    fn check(&self) -> Result<(), ResourceError>;
+}
+
+/// A resource tracker with no limits of its own which periodically runs an [`InterruptCheck`].
+///
+/// The check runs every `interval` calls to `check_time` (roughly every `interval`
+/// instructions), so it can be relatively expensive, e.g. acquiring a lock.
+/// Combine it with a tracker enforcing limits using [`ResourceTracker::chain`].
+///
+/// The Python bindings use this to check for Python signals like Ctrl+C.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct InterruptTracker<C> {
+    check: C,
+    /// Number of `check_time` calls between checks.
+    interval: u16,
+    /// Counter for `check_time` calls, used to rate-limit checks.
+    ///
+    /// Uses `AtomicU16` for interior mutability since `check_time` takes `&self`.
+    counter: AtomicU16,
+}
+
+impl<C: InterruptCheck> InterruptTracker<C> {
+    /// Creates a tracker running `check` every `interval` calls to `check_time`.
+    ///
+    /// # Panics
+    /// Panics if `interval` is zero.
+    #[must_use]
+    pub fn new(check: C, interval: u16) -> Self {
+        assert!(interval > 0, "InterruptTracker interval must be greater than zero");
+        Self {
+            check,
+            interval,
+            counter: AtomicU16::new(0),
+        }
+    }
+
+    /// Returns the interrupt check.
+    pub fn check(&self) -> &C {
+        &self.check
+    }
+}
+
+impl<C: InterruptCheck> ResourceTracker for InterruptTracker<C> {
+    #[inline]
+    fn on_allocate(&mut self, _: impl FnOnce() -> usize) -> Result<(), ResourceError> {
+        Ok(())
+    }
+
+    #[inline]
+    fn on_free(&mut self, _: impl FnOnce() -> usize) {}
+
+    fn check_time(&self) -> Result<(), ResourceError> {
+        let count = self.counter.fetch_add(1, Ordering::Relaxed).wrapping_add(1);
+        if count.is_multiple_of(self.interval) {
+            self.check.check()?;
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn check_recursion_depth(&self, _current_depth: usize) -> Result<(), ResourceError> {
+        Ok(())
+    }
+
+    #[inline]
+    fn check_large_result(&self, _estimated_bytes: usize) -> Result<(), ResourceError> {
+        Ok(())
+    }
+
+    #[inline]
+    fn max_output_size(&self) -> Option<usize> {
+        None
+    }
+
+    #[inline]
+    fn max_stack_size(&self) -> Option<usize> {
+        None
+    }
 }
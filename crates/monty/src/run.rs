@@ -1,20 +1,29 @@
 //! Public interface for running Monty code.
 use std::sync::atomic::{AtomicUsize, Ordering};
 
+use ahash::AHashMap;
+
+#[cfg(feature = "type-checking")]
+use crate::type_checking::{TypeCheckError, TypeCheckMode, check_code};
 use crate::{
     ExcType, MontyException,
     asyncio::CallId,
+    audit::{AuditEvent, AuditEventKind, Capability},
     bytecode::{Code, Compiler, FrameExit, VM, VMSnapshot},
-    exception_private::RunResult,
+    exception_private::{RunError, RunResult},
+    ext_functions::{ExternalDispatcher, OsDispatcher, run_with_dispatchers},
+    ext_signature::ExtFunctionSignature,
     heap::{DropWithHeap, Heap},
     intern::{ExtFunctionId, Interns},
     io::PrintWriter,
-    namespace::Namespaces,
+    memory_profile::MemoryProfile,
+    namespace::{ModuleDunders, Namespaces},
     object::MontyObject,
     os::OsFunction,
-    parse::parse,
-    prepare::prepare,
-    resource::{NoLimitTracker, ResourceTracker},
+    parse::{check_identity_literals, parse_with_limits},
+    prepare::{insert_debug_constant, prepare_with_constants},
+    resource::{NoLimitTracker, ResourceLimits, ResourceTracker},
+    signing::{LoadError, code_hash, sign_serialized, verify_serialized},
     value::Value,
 };
 
@@ -58,7 +67,267 @@ impl MontyRun {
         input_names: Vec<String>,
         external_functions: Vec<String>,
     ) -> Result<Self, MontyException> {
-        Executor::new(code, script_name, input_names, external_functions).map(|executor| Self { executor })
+        Self::new_with_constants(code, script_name, input_names, external_functions, vec![])
+    }
+
+    /// Creates a new run snapshot with host-provided constants baked into the code.
+    ///
+    /// Unlike inputs, which are passed to each run, constants are fixed when the code is
+    /// compiled: every read of a constant name is replaced by its value, and any assignment to
+    /// it, including as a parameter or loop target, is a `SyntaxError`. Constants must be
+    /// `None`, `bool`, `int`, `float`, `str` or `bytes`.
+    ///
+    /// # Example
+    /// ```
+    /// use monty::{MontyObject, MontyRun};
+    ///
+    /// let constants = vec![("MAX".to_owned(), MontyObject::Int(100))];
+    /// let runner = MontyRun::new_with_constants("min(500, MAX)".to_owned(), "main.py", vec![], vec![], constants).unwrap();
+    /// assert_eq!(runner.run_no_limits(vec![]).unwrap(), MontyObject::Int(100));
+    ///
+    /// let constants = vec![("MAX".to_owned(), MontyObject::Int(100))];
+    /// let err = MontyRun::new_with_constants("MAX = 1".to_owned(), "main.py", vec![], vec![], constants).unwrap_err();
+    /// assert_eq!(err.message(), Some("cannot assign to constant 'MAX'"));
+    /// ```
+    ///
+    /// # Errors
+    /// Returns `MontyException` if the code cannot be parsed or rebinds a constant, a `TypeError`
+    /// if a constant has an unsupported type, or a `ValueError` if a constant is also an input
+    /// or external function.
+    pub fn new_with_constants(
+        code: String,
+        script_name: &str,
+        input_names: Vec<String>,
+        external_functions: Vec<String>,
+        constants: Vec<(String, MontyObject)>,
+    ) -> Result<Self, MontyException> {
+        let limits = ResourceLimits::new();
+        Executor::new(
+            code,
+            script_name,
+            input_names,
+            external_functions,
+            constants,
+            &limits,
+            false,
+        )
+        .map(|executor| Self { executor })
+    }
+
+    /// Creates a new run snapshot like [`new_with_constants()`](Self::new_with_constants), compiled
+    /// like `python -O`.
+    ///
+    /// Assert statements are removed, so neither their condition nor their message is evaluated,
+    /// and `__debug__` is `False`. Useful when asserts are only diagnostics during development.
+    ///
+    /// # Example
+    /// ```
+    /// use monty::{MontyObject, MontyRun};
+    ///
+    /// let code = "assert False, 'never raised'\n__debug__";
+    /// let runner = MontyRun::new_optimized(code.to_owned(), "main.py", vec![], vec![], vec![]).unwrap();
+    /// assert_eq!(runner.run_no_limits(vec![]).unwrap(), MontyObject::Bool(false));
+    /// ```
+    ///
+    /// # Errors
+    /// Returns `MontyException` in the same cases as [`new_with_constants()`](Self::new_with_constants).
+    pub fn new_optimized(
+        code: String,
+        script_name: &str,
+        input_names: Vec<String>,
+        external_functions: Vec<String>,
+        constants: Vec<(String, MontyObject)>,
+    ) -> Result<Self, MontyException> {
+        let limits = ResourceLimits::new();
+        Executor::new(
+            code,
+            script_name,
+            input_names,
+            external_functions,
+            constants,
+            &limits,
+            true,
+        )
+        .map(|executor| Self { executor })
+    }
+
+    /// Creates a new run snapshot, enforcing the compile-time limits of `limits`.
+    ///
+    /// Deeply nested or very large code is refused with a `SyntaxError` before it can exhaust
+    /// the host's stack or memory while compiling, see `max_parse_depth`, `max_ast_nodes`
+    /// and `max_bytecode_size` of [`ResourceLimits`]. Runtime limits are ignored here, pass
+    /// them to the tracker when running the code.
+    ///
+    /// # Example
+    /// ```
+    /// use monty::{ExcType, MontyRun, ResourceLimits};
+    ///
+    /// let limits = ResourceLimits::new().max_ast_nodes(10);
+    /// let err = MontyRun::new_with_limits("[1, 2, 3, 4, 5, 6, 7, 8, 9, 10]".to_owned(), "main.py", vec![], vec![], &limits)
+    ///     .unwrap_err();
+    /// assert_eq!(err.exc_type(), ExcType::SyntaxError);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns `MontyException` if the code cannot be parsed or exceeds a compile-time limit.
+    pub fn new_with_limits(
+        code: String,
+        script_name: &str,
+        input_names: Vec<String>,
+        external_functions: Vec<String>,
+        limits: &ResourceLimits,
+    ) -> Result<Self, MontyException> {
+        Executor::new(
+            code,
+            script_name,
+            input_names,
+            external_functions,
+            vec![],
+            limits,
+            false,
+        )
+        .map(|executor| Self { executor })
+    }
+
+    /// Sets the value of `__name__` in the module namespace, `"__main__"` by default.
+    ///
+    /// Code guarded by `if __name__ == '__main__':` is skipped when another name is set, as it
+    /// would be when the module is imported by CPython.
+    #[must_use]
+    pub fn with_module_name(mut self, name: &str) -> Self {
+        let name = self.executor.interns.intern(name);
+        self.executor.dunders.set_name(name);
+        self
+    }
+
+    /// Batches calls to external functions registered with an `async` signature, see
+    /// [`ExtFunctionSignature::is_async`].
+    ///
+    /// Instead of suspending with a `FunctionCall` for each call, [`start()`](Self::start)
+    /// gives the code a pending future straight away and keeps running, so a loop starting many
+    /// fetches reaches the host as one [`RunProgress::FunctionCallBatch`] once the code awaits
+    /// a result. Calls are reported at the first point execution blocks on futures after them,
+    /// and calls whose futures are never awaited before the code completes are never reported,
+    /// like coroutines that are never awaited in CPython.
+    #[must_use]
+    pub fn batch_async_calls(mut self) -> Self {
+        self.executor.batch_async_calls = true;
+        self
+    }
+
+    /// Checks the code for `is` and `is not` comparisons against literals, such as `x is 5`.
+    ///
+    /// Monty gives equal ints, floats and interned strings the same id, so `x is 1000` is true
+    /// whenever `x == 1000`, while in CPython the result depends on which objects are cached.
+    /// CPython reports these comparisons with a `SyntaxWarning`; this opt-in check reports them
+    /// as a `SyntaxError` with the same message, like `python -W error`.
+    ///
+    /// # Errors
+    /// Returns a `SyntaxError` for the first comparison of a value's identity with a literal.
+    pub fn check_identity_literals(&self, script_name: &str) -> Result<(), MontyException> {
+        let code = &self.executor.code;
+        check_identity_literals(code, script_name).map_err(|e| e.into_python_exc(script_name, code))
+    }
+
+    /// Creates a new runner after type checking the code.
+    ///
+    /// In [`TypeCheckMode::Warn`] mode diagnostics are kept with the code and can be read with
+    /// [`type_check_warnings()`](Self::type_check_warnings), from the snapshots of a run, and
+    /// from [`RunProgress::Complete`] when it finishes; in [`TypeCheckMode::Strict`] mode
+    /// they are returned as [`TypeCheckError::Failed`] and no runner is created.
+    ///
+    /// # Arguments
+    /// * `code` - The Python code to execute
+    /// * `script_name` - The script name for error messages
+    /// * `input_names` - Names of input variables
+    /// * `external_functions` - Names of external functions
+    /// * `type_check_stubs` - Optional stub code declaring inputs and external functions for the checker
+    /// * `mode` - How type checking diagnostics are treated
+    ///
+    /// # Errors
+    /// Returns `TypeCheckError` if type checking fails in strict mode, the checker errors, or the
+    /// code cannot be parsed.
+    #[cfg(feature = "type-checking")]
+    pub fn new_checked(
+        code: String,
+        script_name: &str,
+        input_names: Vec<String>,
+        external_functions: Vec<String>,
+        type_check_stubs: Option<&str>,
+        mode: TypeCheckMode,
+    ) -> Result<Self, TypeCheckError> {
+        let mut runner = Self::new(code, script_name, input_names, external_functions)?;
+        runner.type_check(script_name, type_check_stubs, mode)?;
+        Ok(runner)
+    }
+
+    /// Type checks the code of an existing runner, see [`new_checked()`](Self::new_checked).
+    ///
+    /// Signatures registered with [`register_external_signature()`](Self::register_external_signature)
+    /// are declared to the type checker ahead of `type_check_stubs`.
+    ///
+    /// # Errors
+    /// Returns `TypeCheckError` if type checking fails in strict mode or the checker errors.
+    #[cfg(feature = "type-checking")]
+    pub fn type_check(
+        &mut self,
+        script_name: &str,
+        type_check_stubs: Option<&str>,
+        mode: TypeCheckMode,
+    ) -> Result<(), TypeCheckError> {
+        let mut stubs = self.executor.interns.external_signature_stubs();
+        if let Some(type_check_stubs) = type_check_stubs {
+            stubs.push_str(type_check_stubs);
+        }
+        let stubs = (!stubs.is_empty()).then_some(stubs);
+        self.executor.type_check_warnings = check_code(&self.executor.code, script_name, stubs.as_deref(), mode)?;
+        Ok(())
+    }
+
+    /// Returns the type checking diagnostics recorded when the runner was type checked in warn mode.
+    #[must_use]
+    pub fn type_check_warnings(&self) -> Option<&str> {
+        self.executor.type_check_warnings.as_deref()
+    }
+
+    /// Registers the signature of an external function.
+    ///
+    /// Calls to the function are then checked against the signature before execution is
+    /// suspended, raising `TypeError` inside the sandbox for arity mismatches, and the signature
+    /// is declared to the type checker by [`type_check()`](Self::type_check).
+    ///
+    /// # Errors
+    /// Returns a `ValueError` exception if `signature.name` is not one of the runner's external functions.
+    pub fn register_external_signature(&mut self, signature: ExtFunctionSignature) -> Result<(), MontyException> {
+        let name = signature.name.clone();
+        if self.executor.interns.set_external_signature(signature) {
+            Ok(())
+        } else {
+            Err(MontyException::new(
+                ExcType::ValueError,
+                Some(format!("'{name}' is not an external function")),
+            ))
+        }
+    }
+
+    /// Marks an external function as pure, so its results are memoized for the rest of the run.
+    ///
+    /// The first call with some arguments suspends with a `FunctionCall` as usual and the value
+    /// it's resumed with is recorded. Later calls with equal arguments return that value without
+    /// suspending. Exceptions and futures aren't recorded, and calls batched by
+    /// [`batch_async_calls`](Self::batch_async_calls) are never memoized.
+    ///
+    /// # Errors
+    /// Returns a `ValueError` exception if `name` is not one of the runner's external functions.
+    pub fn mark_pure(&mut self, name: &str) -> Result<(), MontyException> {
+        if self.executor.interns.set_external_pure(name) {
+            Ok(())
+        } else {
+            Err(MontyException::new(
+                ExcType::ValueError,
+                Some(format!("'{name}' is not an external function")),
+            ))
+        }
     }
 
     /// Returns the code that was parsed to create this snapshot.
@@ -91,6 +360,34 @@ impl MontyRun {
         self.executor.run(inputs, resource_tracker, print)
     }
 
+    /// Runs the code like [`run()`](Self::run) on `heap`, which is reset with `resource_tracker`
+    /// first so a heap can be reused across runs, see [`SandboxPool`](crate::SandboxPool).
+    pub(crate) fn run_in_heap<T: ResourceTracker>(
+        &self,
+        heap: &mut Heap<T>,
+        inputs: Vec<MontyObject>,
+        resource_tracker: T,
+        print: &mut PrintWriter<'_>,
+    ) -> Result<MontyObject, MontyException> {
+        heap.reset(resource_tracker);
+        self.executor.run_with_heap(heap, inputs, print)
+    }
+
+    /// Executes the code like [`Self::run`], also counting executed opcodes, conditional jump
+    /// outcomes and function calls.
+    ///
+    /// The metrics are returned even if execution fails. Only available with the `vm-metrics`
+    /// feature.
+    #[cfg(feature = "vm-metrics")]
+    pub fn run_with_metrics(
+        &self,
+        inputs: Vec<MontyObject>,
+        resource_tracker: impl ResourceTracker,
+        print: &mut PrintWriter<'_>,
+    ) -> (Result<MontyObject, MontyException>, crate::VmMetrics) {
+        self.executor.run_with_metrics(inputs, resource_tracker, print)
+    }
+
     /// Executes the code to completion with no resource limits, printing to stdout/stderr.
     pub fn run_no_limits(&self, inputs: Vec<MontyObject>) -> Result<MontyObject, MontyException> {
         self.run(inputs, NoLimitTracker, &mut PrintWriter::Stdout)
@@ -118,13 +415,42 @@ impl MontyRun {
         postcard::from_bytes(bytes)
     }
 
+    /// Serializes the runner like `dump()`, appending an HMAC-SHA256 signature computed with `key`.
+    ///
+    /// Load the result with `load_signed()` and the same key.
+    ///
+    /// # Errors
+    /// Returns an error if serialization fails.
+    pub fn dump_signed(&self, key: &[u8]) -> Result<Vec<u8>, postcard::Error> {
+        Ok(sign_serialized(self.dump()?, key))
+    }
+
+    /// Deserializes a runner from `dump_signed()` output, after checking its signature.
+    ///
+    /// # Errors
+    /// Returns `LoadError::InvalidSignature` if the data wasn't signed with `key` or was modified,
+    /// or `LoadError::Deserialize` if deserialization fails.
+    pub fn load_signed(bytes: &[u8], key: &[u8]) -> Result<Self, LoadError> {
+        Ok(Self::load(verify_serialized(bytes, key)?)?)
+    }
+
+    /// SHA-256 hash of the source code.
+    ///
+    /// Depends only on the code, so it's the same for every runner compiled from the same
+    /// code, including after `dump()` and `load()`, making it suitable for cache keys and
+    /// recording which code was run.
+    #[must_use]
+    pub fn code_hash(&self) -> [u8; 32] {
+        code_hash(&self.executor.code)
+    }
+
     /// Starts execution with the given inputs and resource tracker, consuming self.
     ///
     /// Creates the heap and namespaces, then begins execution.
     ///
     /// For iterative execution, `start()` consumes self and returns a `RunProgress`:
     /// - `RunProgress::FunctionCall { ..., state }` - external function call, call `state.run(return_value)` to resume
-    /// - `RunProgress::Complete(value)` - execution finished
+    /// - `RunProgress::Complete { value, .. }` - execution finished
     ///
     /// This enables snapshotting execution state and returning control to the host
     /// application during long-running computations.
@@ -157,6 +483,7 @@ impl MontyRun {
 
         // Create and run VM
         let mut vm = VM::new(&mut heap, &mut namespaces, &executor.interns, print);
+        executor.configure_vm(&mut vm);
 
         // Start execution
         let vm_result = vm.run_module(&executor.module_code);
@@ -166,6 +493,39 @@ impl MontyRun {
         // Handle the result using the destructured parts
         handle_vm_result(vm_result, vm_state, executor, heap, namespaces)
     }
+
+    /// Runs the code to completion, calling `ext` for external functions and `os` for OS calls.
+    ///
+    /// This is a one-call alternative to the `start()` loop for hosts that don't need to suspend
+    /// execution. Use [`ExternalFunctions`](crate::ExternalFunctions) for typed functions and
+    /// [`NoOsAccess`](crate::NoOsAccess) to deny OS calls.
+    ///
+    /// # Example
+    /// ```
+    /// use monty::{ExternalFunctions, MontyObject, MontyRun, NoLimitTracker, NoOsAccess, PrintWriter};
+    ///
+    /// let functions = ExternalFunctions::new().register("double", |x: i64| x * 2);
+    /// let runner = MontyRun::new("double(21)".to_owned(), "main.py", vec![], functions.names()).unwrap();
+    /// let result = runner
+    ///     .run_with_handlers(vec![], NoLimitTracker, &mut PrintWriter::Stdout, &functions, NoOsAccess)
+    ///     .unwrap();
+    /// assert_eq!(result, MontyObject::Int(42));
+    /// ```
+    ///
+    /// # Errors
+    /// Returns the exception that ended execution, or a `RuntimeError` if the code waits on
+    /// external futures, which synchronous handlers can't resolve.
+    pub fn run_with_handlers<T: ResourceTracker>(
+        &self,
+        inputs: Vec<MontyObject>,
+        resource_tracker: T,
+        print: &mut PrintWriter<'_>,
+        ext: impl ExternalDispatcher,
+        os: impl OsDispatcher,
+    ) -> Result<MontyObject, MontyException> {
+        let progress = self.clone().start(inputs, resource_tracker, print)?;
+        run_with_dispatchers(progress, ext, os, print)
+    }
 }
 
 /// Result of a single step of iterative execution.
@@ -173,7 +533,10 @@ impl MontyRun {
 /// This enum owns the execution state, ensuring type-safe state transitions.
 /// - `FunctionCall` contains info about an external function call and state to resume
 /// - `ResolveFutures` contains pending futures that need resolution before continuing
-/// - `Complete` contains just the final value (execution is done)
+/// - `FunctionCallBatch` is `ResolveFutures` along with batched async calls to start
+/// - `Yield` contains a partial result streamed by `monty_yield()` and state to resume
+/// - `Checkpoint` contains state to serialize and resume, see `ResourceLimits::checkpoint_interval`
+/// - `Complete` contains the final value and any type checking warnings (execution is done)
 ///
 /// # Type Parameters
 /// * `T` - Resource tracker implementation (e.g., `NoLimitTracker` or `LimitedTracker`)
@@ -192,7 +555,8 @@ pub enum RunProgress<T: ResourceTracker> {
     /// If the future isn't resolved when awaited, execution yields with `ResolveFutures`.
     ///
     /// When `method_call` is true, this represents a dataclass method call where the first
-    /// positional arg is the dataclass instance (`self`).
+    /// positional arg is the dataclass instance (`self`), or a `__getitem__` fetch of the next
+    /// chunk of a [`MontyObject::LazySequence`].
     FunctionCall {
         /// The name of the function or method being called.
         function_name: String,
@@ -232,8 +596,47 @@ pub enum RunProgress<T: ResourceTracker> {
     ///
     /// access the pending call ids with `.pending_call_ids()`
     ResolveFutures(FutureSnapshot<T>),
+    /// Like `ResolveFutures`, with the async external calls batched since the last pause,
+    /// see [`MontyRun::batch_async_calls`].
+    ///
+    /// The host should start each call and resolve them, along with any other pending
+    /// futures, through `state.resume(results)`.
+    FunctionCallBatch {
+        /// The batched calls, in the order the code made them.
+        calls: Vec<BatchCall>,
+        /// The execution state, blocked until some pending future is resolved.
+        state: FutureSnapshot<T>,
+    },
+    /// Execution paused to stream a partial result from `monty_yield(value)`.
+    ///
+    /// The host consumes the value and calls `state.run(result)` to continue; `result`
+    /// becomes the return value of `monty_yield()`, usually `MontyObject::None`.
+    Yield {
+        /// The value passed to `monty_yield()`.
+        value: MontyObject,
+        /// The execution state that can be resumed.
+        state: Snapshot<T>,
+    },
+    /// Execution paused at an automatic checkpoint, see `ResourceLimits::checkpoint_interval`.
+    ///
+    /// The host can serialize the progress with `dump()`, e.g. to recover from a crash or
+    /// continue on another host, then call `state.run()` to continue.
+    Checkpoint(CheckpointSnapshot<T>),
     /// Execution completed with a final result.
-    Complete(MontyObject),
+    Complete {
+        /// The value of the last expression.
+        value: MontyObject,
+        /// Type checking diagnostics recorded in warn mode, see [`MontyRun::type_check_warnings`].
+        ///
+        /// Carried through every snapshot of the run, so hosts that only hold the final
+        /// progress, e.g. after loading a dumped snapshot, still see them.
+        type_check_warnings: Option<String>,
+        /// Live heap objects when the run finished, including the result and global variables.
+        ///
+        /// `None` after `load()`, since the heap isn't serialized with a completed run.
+        #[serde(skip)]
+        memory_profile: Option<MemoryProfile>,
+    },
 }
 
 impl<T: ResourceTracker> RunProgress<T> {
@@ -269,7 +672,40 @@ impl<T: ResourceTracker> RunProgress<T> {
     #[must_use]
     pub fn into_complete(self) -> Option<MontyObject> {
         match self {
-            Self::Complete(value) => Some(value),
+            Self::Complete { value, .. } => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Consumes the `RunProgress` and returns the yielded value and state.
+    ///
+    /// Returns (value, state) if this is a Yield, None otherwise.
+    #[must_use]
+    pub fn into_yield(self) -> Option<(MontyObject, Snapshot<T>)> {
+        match self {
+            Self::Yield { value, state } => Some((value, state)),
+            _ => None,
+        }
+    }
+
+    /// Consumes the `RunProgress` and returns the checkpoint state.
+    ///
+    /// Returns the state if this is a Checkpoint, None otherwise.
+    #[must_use]
+    pub fn into_checkpoint(self) -> Option<CheckpointSnapshot<T>> {
+        match self {
+            Self::Checkpoint(state) => Some(state),
+            _ => None,
+        }
+    }
+
+    /// Consumes the `RunProgress` and returns the batched calls and state.
+    ///
+    /// Returns (calls, state) if this is a FunctionCallBatch, None otherwise.
+    #[must_use]
+    pub fn into_function_call_batch(self) -> Option<(Vec<BatchCall>, FutureSnapshot<T>)> {
+        match self {
+            Self::FunctionCallBatch { calls, state } => Some((calls, state)),
             _ => None,
         }
     }
@@ -284,6 +720,96 @@ impl<T: ResourceTracker> RunProgress<T> {
             _ => None,
         }
     }
+
+    /// Whether `other` is the same kind of progress with the same values and execution state.
+    ///
+    /// See [`Snapshot::state_eq`]; this also compares the values reported to the host,
+    /// e.g. the function name and arguments of a `FunctionCall`, but not the memory profile
+    /// of a `Complete`, which isn't serialized.
+    #[must_use]
+    pub fn state_eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (
+                Self::FunctionCall {
+                    function_name,
+                    args,
+                    kwargs,
+                    call_id,
+                    method_call,
+                    state,
+                },
+                Self::FunctionCall {
+                    function_name: other_function_name,
+                    args: other_args,
+                    kwargs: other_kwargs,
+                    call_id: other_call_id,
+                    method_call: other_method_call,
+                    state: other_state,
+                },
+            ) => {
+                serialized_eq(
+                    &(function_name, args, kwargs, call_id, method_call),
+                    &(
+                        other_function_name,
+                        other_args,
+                        other_kwargs,
+                        other_call_id,
+                        other_method_call,
+                    ),
+                ) && state.state_eq(other_state)
+            }
+            (
+                Self::OsCall {
+                    function,
+                    args,
+                    kwargs,
+                    call_id,
+                    state,
+                },
+                Self::OsCall {
+                    function: other_function,
+                    args: other_args,
+                    kwargs: other_kwargs,
+                    call_id: other_call_id,
+                    state: other_state,
+                },
+            ) => {
+                serialized_eq(
+                    &(function, args, kwargs, call_id),
+                    &(other_function, other_args, other_kwargs, other_call_id),
+                ) && state.state_eq(other_state)
+            }
+            (Self::ResolveFutures(state), Self::ResolveFutures(other_state)) => state.state_eq(other_state),
+            (
+                Self::FunctionCallBatch { calls, state },
+                Self::FunctionCallBatch {
+                    calls: other_calls,
+                    state: other_state,
+                },
+            ) => serialized_eq(calls, other_calls) && state.state_eq(other_state),
+            (
+                Self::Yield { value, state },
+                Self::Yield {
+                    value: other_value,
+                    state: other_state,
+                },
+            ) => serialized_eq(value, other_value) && state.state_eq(other_state),
+            (Self::Checkpoint(state), Self::Checkpoint(other_state)) => state.state_eq(other_state),
+            (
+                Self::Complete {
+                    value,
+                    type_check_warnings,
+                    ..
+                },
+                Self::Complete {
+                    value: other_value,
+                    type_check_warnings: other_warnings,
+                    ..
+                },
+            ) => serialized_eq(value, other_value) && type_check_warnings == other_warnings,
+            _ => false,
+        }
+    }
 }
 
 impl<T: ResourceTracker + serde::Serialize> RunProgress<T> {
@@ -338,6 +864,22 @@ pub struct Snapshot<T: ResourceTracker> {
 #[derive(Debug)]
 pub struct MontyFuture;
 
+/// An async external call reported by [`RunProgress::FunctionCallBatch`].
+///
+/// The code already holds a pending future for the call, so the result is passed to
+/// [`FutureSnapshot::resume`] under `call_id`.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct BatchCall {
+    /// Unique identifier for this call, used to resolve its future.
+    pub call_id: u32,
+    /// The name of the function being called.
+    pub function_name: String,
+    /// The positional arguments passed to the function.
+    pub args: Vec<MontyObject>,
+    /// The keyword arguments passed to the function (key, value pairs).
+    pub kwargs: Vec<(MontyObject, MontyObject)>,
+}
+
 /// Return value or exception from an external function.
 #[derive(Debug)]
 pub enum ExternalResult {
@@ -368,6 +910,17 @@ impl From<MontyFuture> for ExternalResult {
 }
 
 impl<T: ResourceTracker> Snapshot<T> {
+    /// Returns the type checking diagnostics recorded in warn mode, see [`MontyRun::type_check_warnings`].
+    #[must_use]
+    pub fn type_check_warnings(&self) -> Option<&str> {
+        self.executor.type_check_warnings.as_deref()
+    }
+
+    /// Returns the resource tracker, e.g. to report an audit event for the pending call.
+    pub fn tracker(&self) -> &T {
+        self.heap.tracker()
+    }
+
     /// Returns a mutable reference to the resource tracker.
     ///
     /// This allows modifying resource limits between execution phases,
@@ -376,6 +929,34 @@ impl<T: ResourceTracker> Snapshot<T> {
         self.heap.tracker_mut()
     }
 
+    /// Live heap objects by type, and allocation sites if the tracker traces allocations.
+    #[must_use]
+    pub fn memory_profile(&self) -> MemoryProfile {
+        self.heap.memory_profile(&self.executor.interns)
+    }
+
+    /// Whether `other` holds the same execution state as this snapshot.
+    ///
+    /// Deeply compares the compiled code, frames, stack, heap objects including reference
+    /// counts, and namespaces. The resource tracker isn't compared. Embedders storing
+    /// checkpoints in their own format can use this to check a restored snapshot matches
+    /// the original.
+    #[must_use]
+    pub fn state_eq(&self, other: &Self) -> bool {
+        serialized_eq(&self.state(), &other.state())
+    }
+
+    /// Execution state compared by `state_eq`.
+    fn state(&self) -> impl serde::Serialize + '_ {
+        (
+            self.executor.state(),
+            &self.vm_state,
+            self.heap.state(),
+            &self.namespaces,
+            self.pending_call_id,
+        )
+    }
+
     /// Continues execution with the return value or exception from the external function.
     ///
     /// Consumes self and returns the next execution progress.
@@ -403,6 +984,7 @@ impl<T: ResourceTracker> Snapshot<T> {
             &self.executor.interns,
             print,
         );
+        self.executor.configure_vm(&mut vm);
 
         // Convert return value or exception before creating VM (to avoid borrow conflicts)
         let vm_result = match ext_result {
@@ -485,6 +1067,35 @@ impl<T: ResourceTracker> FutureSnapshot<T> {
         &self.pending_call_ids
     }
 
+    /// Returns the type checking diagnostics recorded in warn mode, see [`MontyRun::type_check_warnings`].
+    #[must_use]
+    pub fn type_check_warnings(&self) -> Option<&str> {
+        self.executor.type_check_warnings.as_deref()
+    }
+
+    /// Live heap objects by type, and allocation sites if the tracker traces allocations.
+    #[must_use]
+    pub fn memory_profile(&self) -> MemoryProfile {
+        self.heap.memory_profile(&self.executor.interns)
+    }
+
+    /// Whether `other` holds the same execution state, see [`Snapshot::state_eq`].
+    #[must_use]
+    pub fn state_eq(&self, other: &Self) -> bool {
+        serialized_eq(&self.state(), &other.state())
+    }
+
+    /// Execution state compared by `state_eq`.
+    fn state(&self) -> impl serde::Serialize + '_ {
+        (
+            self.executor.state(),
+            &self.vm_state,
+            self.heap.state(),
+            &self.namespaces,
+            &self.pending_call_ids,
+        )
+    }
+
     /// Resumes execution with results for some or all pending futures.
     ///
     /// **Incremental resolution**: You don't need to provide all results at once.
@@ -517,8 +1128,6 @@ impl<T: ResourceTracker> FutureSnapshot<T> {
         results: Vec<(u32, ExternalResult)>,
         print: &mut PrintWriter<'_>,
     ) -> Result<RunProgress<T>, MontyException> {
-        use crate::exception_private::RunError;
-
         // Destructure self to avoid partial move issues
         let Self {
             executor,
@@ -543,6 +1152,7 @@ impl<T: ResourceTracker> FutureSnapshot<T> {
             &executor.interns,
             print,
         );
+        executor.configure_vm(&mut vm);
 
         // Now check for invalid call_ids after VM is restored
         if let Some(call_id) = invalid_call_id {
@@ -621,6 +1231,99 @@ impl<T: ResourceTracker> FutureSnapshot<T> {
     }
 }
 
+/// Execution state paused at an automatic checkpoint.
+///
+/// Unlike `Snapshot`, no value is pending: `run()` continues from the instruction
+/// where execution paused.
+///
+/// # Type Parameters
+/// * `T` - Resource tracker implementation
+///
+/// Serialization requires `T: Serialize + Deserialize`.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[serde(bound(serialize = "T: serde::Serialize", deserialize = "T: serde::de::DeserializeOwned"))]
+pub struct CheckpointSnapshot<T: ResourceTracker> {
+    /// The executor containing compiled code and interns.
+    executor: Executor,
+    /// The VM state containing stack, frames, and exception state.
+    vm_state: VMSnapshot,
+    /// The heap containing all allocated objects.
+    heap: Heap<T>,
+    /// The namespaces containing all variable bindings.
+    namespaces: Namespaces,
+}
+
+impl<T: ResourceTracker> CheckpointSnapshot<T> {
+    /// Returns the type checking diagnostics recorded in warn mode, see [`MontyRun::type_check_warnings`].
+    #[must_use]
+    pub fn type_check_warnings(&self) -> Option<&str> {
+        self.executor.type_check_warnings.as_deref()
+    }
+
+    /// Returns the resource tracker.
+    pub fn tracker(&self) -> &T {
+        self.heap.tracker()
+    }
+
+    /// Returns a mutable reference to the resource tracker, e.g. to extend the time limit.
+    pub fn tracker_mut(&mut self) -> &mut T {
+        self.heap.tracker_mut()
+    }
+
+    /// Live heap objects by type, and allocation sites if the tracker traces allocations.
+    #[must_use]
+    pub fn memory_profile(&self) -> MemoryProfile {
+        self.heap.memory_profile(&self.executor.interns)
+    }
+
+    /// Whether `other` holds the same execution state, see [`Snapshot::state_eq`].
+    ///
+    /// Useful to check a checkpoint survives the host's own storage before relying on it.
+    #[must_use]
+    pub fn state_eq(&self, other: &Self) -> bool {
+        serialized_eq(&self.state(), &other.state())
+    }
+
+    /// Execution state compared by `state_eq`.
+    fn state(&self) -> impl serde::Serialize + '_ {
+        (
+            self.executor.state(),
+            &self.vm_state,
+            self.heap.state(),
+            &self.namespaces,
+        )
+    }
+
+    /// Continues execution from the checkpoint.
+    ///
+    /// # Panics
+    /// Panics if the VM reaches an inconsistent state (indicating a bug in the interpreter).
+    pub fn run(self, print: &mut PrintWriter<'_>) -> Result<RunProgress<T>, MontyException> {
+        let Self {
+            executor,
+            vm_state,
+            mut heap,
+            mut namespaces,
+        } = self;
+
+        let mut vm = VM::restore(
+            vm_state,
+            &executor.module_code,
+            &mut heap,
+            &mut namespaces,
+            &executor.interns,
+            print,
+        );
+        executor.configure_vm(&mut vm);
+
+        let result = vm.run();
+
+        let vm_state = vm.check_snapshot(&result);
+
+        handle_vm_result(result, vm_state, executor, heap, namespaces)
+    }
+}
+
 /// Handles a FrameExit result and converts it to RunProgress for FutureSnapshot.
 ///
 /// This is a standalone function to avoid partial move issues when destructuring FutureSnapshot.
@@ -646,11 +1349,18 @@ fn handle_vm_result<T: ResourceTracker>(
 
     match result {
         Ok(FrameExit::Return(value)) => {
+            // taken before globals are dropped so it doesn't depend on the `ref-count-panic` feature
+            let memory_profile = heap.memory_profile(&executor.interns);
+
             #[cfg(feature = "ref-count-panic")]
             namespaces.drop_global_with_heap(&mut heap);
 
-            let obj = MontyObject::new(value, &mut heap, &executor.interns);
-            Ok(RunProgress::Complete(obj))
+            let value = MontyObject::new(value, &mut heap, &executor.interns);
+            Ok(RunProgress::Complete {
+                value,
+                type_check_warnings: executor.type_check_warnings,
+                memory_profile: Some(memory_profile),
+            })
         }
         Ok(FrameExit::ExternalCall {
             ext_function_id,
@@ -659,6 +1369,12 @@ fn handle_vm_result<T: ResourceTracker>(
         }) => {
             let function_name = executor.interns.get_external_function_name(ext_function_id);
             let (args_py, kwargs_py) = args.into_py_objects(&mut heap, &executor.interns);
+            heap.tracker().audit(&AuditEvent::now(AuditEventKind::ExternalCall {
+                function_name: &function_name,
+                method_call: false,
+                args: &args_py,
+                kwargs: &kwargs_py,
+            }));
 
             Ok(RunProgress::FunctionCall {
                 function_name,
@@ -675,6 +1391,11 @@ fn handle_vm_result<T: ResourceTracker>(
             call_id,
         }) => {
             let (args_py, kwargs_py) = args.into_py_objects(&mut heap, &executor.interns);
+            heap.tracker().audit(&AuditEvent::now(AuditEventKind::OsCall {
+                function,
+                args: &args_py,
+                kwargs: &kwargs_py,
+            }));
 
             Ok(RunProgress::OsCall {
                 function,
@@ -691,6 +1412,12 @@ fn handle_vm_result<T: ResourceTracker>(
         }) => {
             let function_name = method_name.into_string(&executor.interns);
             let (args_py, kwargs_py) = args.into_py_objects(&mut heap, &executor.interns);
+            heap.tracker().audit(&AuditEvent::now(AuditEventKind::ExternalCall {
+                function_name: &function_name,
+                method_call: true,
+                args: &args_py,
+                kwargs: &kwargs_py,
+            }));
 
             Ok(RunProgress::FunctionCall {
                 function_name,
@@ -701,15 +1428,53 @@ fn handle_vm_result<T: ResourceTracker>(
                 state: new_snapshot!(call_id),
             })
         }
+        Ok(FrameExit::Yield { value, call_id }) => {
+            let value = MontyObject::new(value, &mut heap, &executor.interns);
+            Ok(RunProgress::Yield {
+                value,
+                state: new_snapshot!(call_id),
+            })
+        }
+        Ok(FrameExit::Checkpoint) => Ok(RunProgress::Checkpoint(CheckpointSnapshot {
+            executor,
+            vm_state: vm_state.expect("snapshot should exist for Checkpoint"),
+            heap,
+            namespaces,
+        })),
         Ok(FrameExit::ResolveFutures(pending_call_ids)) => {
             let pending_call_ids: Vec<u32> = pending_call_ids.iter().map(|id| id.raw()).collect();
-            Ok(RunProgress::ResolveFutures(FutureSnapshot {
+            let mut vm_state = vm_state.expect("snapshot should exist for ResolveFutures");
+            let calls: Vec<BatchCall> = vm_state
+                .take_batched_calls()
+                .into_iter()
+                .map(|call| {
+                    let function_name = executor.interns.get_external_function_name(call.ext_function_id);
+                    heap.tracker().audit(&AuditEvent::now(AuditEventKind::ExternalCall {
+                        function_name: &function_name,
+                        method_call: false,
+                        args: &call.args,
+                        kwargs: &call.kwargs,
+                    }));
+                    BatchCall {
+                        call_id: call.call_id.raw(),
+                        function_name,
+                        args: call.args,
+                        kwargs: call.kwargs,
+                    }
+                })
+                .collect();
+            let state = FutureSnapshot {
                 executor,
-                vm_state: vm_state.expect("snapshot should exist for ResolveFutures"),
+                vm_state,
                 heap,
                 namespaces,
                 pending_call_ids,
-            }))
+            };
+            if calls.is_empty() {
+                Ok(RunProgress::ResolveFutures(state))
+            } else {
+                Ok(RunProgress::FunctionCallBatch { calls, state })
+            }
         }
         Err(err) => {
             #[cfg(feature = "ref-count-panic")]
@@ -737,11 +1502,19 @@ struct Executor {
     interns: Interns,
     /// IDs to create values to inject into the the namespace to represent external functions.
     external_function_ids: Vec<ExtFunctionId>,
+    /// Values of `__name__`, `__file__` and `__doc__`, injected into the namespace after inputs.
+    dunders: ModuleDunders,
     /// Source code for error reporting (extracting preview lines for tracebacks).
     code: String,
     /// Estimated heap capacity for pre-allocation on subsequent runs.
     /// Uses AtomicUsize for thread-safety (required by PyO3's Sync bound).
     heap_capacity: AtomicUsize,
+    /// Whether calls to async external functions are batched, see [`MontyRun::batch_async_calls`].
+    #[serde(default)]
+    batch_async_calls: bool,
+    /// Rendered type checking diagnostics recorded in warn mode, see [`MontyRun::type_check`].
+    #[serde(default)]
+    type_check_warnings: Option<String>,
 }
 
 impl Clone for Executor {
@@ -753,22 +1526,57 @@ impl Clone for Executor {
             module_code: self.module_code.clone(),
             interns: self.interns.clone(),
             external_function_ids: self.external_function_ids.clone(),
+            dunders: self.dunders.clone(),
             code: self.code.clone(),
             heap_capacity: AtomicUsize::new(self.heap_capacity.load(Ordering::Relaxed)),
+            batch_async_calls: self.batch_async_calls,
+            type_check_warnings: self.type_check_warnings.clone(),
         }
     }
 }
 
 impl Executor {
-    /// Creates a new executor with the given code, filename, input names, and external functions.
+    /// Creates a new executor with the given code, filename, input names, external functions and constants.
+    ///
+    /// Only the compile-time limits of `limits` are checked. With `optimize`, asserts are removed
+    /// and `__debug__` is `False`.
     fn new(
         code: String,
         script_name: &str,
         input_names: Vec<String>,
         external_functions: Vec<String>,
+        constants: Vec<(String, MontyObject)>,
+        limits: &ResourceLimits,
+        optimize: bool,
     ) -> Result<Self, MontyException> {
-        let parse_result = parse(&code, script_name).map_err(|e| e.into_python_exc(script_name, &code))?;
-        let prepared = prepare(parse_result, input_names, &external_functions)
+        let mut parse_result =
+            parse_with_limits(&code, script_name, limits).map_err(|e| e.into_python_exc(script_name, &code))?;
+        let dunders = ModuleDunders::new(
+            &mut parse_result,
+            script_name,
+            external_functions.len() + input_names.len(),
+        );
+        let mut constant_literals = AHashMap::with_capacity(constants.len());
+        for (name, value) in constants {
+            if input_names.contains(&name) || external_functions.contains(&name) {
+                return Err(MontyException::new(
+                    ExcType::ValueError,
+                    Some(format!("constant '{name}' is also an input or external function")),
+                ));
+            }
+            let Some(literal) = value.to_literal(&mut parse_result.interner) else {
+                return Err(MontyException::new(
+                    ExcType::TypeError,
+                    Some(format!(
+                        "constant '{name}' must be None, bool, int, float, str or bytes, not {}",
+                        value.type_name()
+                    )),
+                ));
+            };
+            constant_literals.insert(name, literal);
+        }
+        insert_debug_constant(&mut constant_literals, optimize);
+        let prepared = prepare_with_constants(parse_result, input_names, &external_functions, &constant_literals)
             .map_err(|e| e.into_python_exc(script_name, &code))?;
 
         // Incrementing order matches the indexes used in intern::Interns::get_external_function_name
@@ -781,6 +1589,22 @@ impl Executor {
         let namespace_size_u16 = u16::try_from(prepared.namespace_size).expect("module namespace size exceeds u16");
         let compile_result = Compiler::compile_module(&prepared.nodes, &interns, namespace_size_u16)
             .map_err(|e| e.into_python_exc(script_name, &code))?;
+        if let Some(max) = limits.max_bytecode_size {
+            let size = compile_result.code.bytecode().len()
+                + compile_result
+                    .functions
+                    .iter()
+                    .map(|function| function.code.bytecode().len())
+                    .sum::<usize>();
+            if size > max {
+                return Err(MontyException::new(
+                    ExcType::SyntaxError,
+                    Some(format!(
+                        "code is too complex, {size} bytes of bytecode exceeds the limit of {max}"
+                    )),
+                ));
+            }
+        }
 
         // Set the compiled functions in the interns
         interns.set_functions(compile_result.functions);
@@ -792,8 +1616,11 @@ impl Executor {
             module_code: compile_result.code,
             interns,
             external_function_ids,
+            dunders,
             code,
             heap_capacity: AtomicUsize::new(prepared.namespace_size),
+            batch_async_calls: false,
+            type_check_warnings: None,
         })
     }
 
@@ -813,29 +1640,65 @@ impl Executor {
         resource_tracker: impl ResourceTracker,
         print: &mut PrintWriter<'_>,
     ) -> Result<MontyObject, MontyException> {
-        let heap_capacity = self.heap_capacity.load(Ordering::Relaxed);
-        let mut heap = Heap::new(heap_capacity, resource_tracker);
-        let mut namespaces = self.prepare_namespaces(inputs, &mut heap)?;
+        let mut heap = Heap::new(self.heap_capacity.load(Ordering::Relaxed), resource_tracker);
+        self.run_with_heap(&mut heap, inputs, print)
+    }
+
+    /// Executes the code like `run`, on a heap which is new or has just been `reset`.
+    fn run_with_heap(
+        &self,
+        heap: &mut Heap<impl ResourceTracker>,
+        inputs: Vec<MontyObject>,
+        print: &mut PrintWriter<'_>,
+    ) -> Result<MontyObject, MontyException> {
+        let mut namespaces = self.prepare_namespaces(inputs, heap)?;
 
         // Create and run VM
-        let mut vm = VM::new(&mut heap, &mut namespaces, &self.interns, print);
+        let mut vm = VM::new(heap, &mut namespaces, &self.interns, print);
         let frame_exit_result = vm.run_module(&self.module_code);
 
         // Clean up VM state before it goes out of scope
         vm.cleanup();
 
-        if heap.size() > heap_capacity {
+        if heap.size() > self.heap_capacity.load(Ordering::Relaxed) {
             self.heap_capacity.store(heap.size(), Ordering::Relaxed);
         }
 
         // Clean up the global namespace before returning (only needed with ref-count-panic)
         #[cfg(feature = "ref-count-panic")]
-        namespaces.drop_global_with_heap(&mut heap);
+        namespaces.drop_global_with_heap(heap);
 
-        frame_exit_to_object(frame_exit_result, &mut heap, &self.interns)
+        frame_exit_to_object(frame_exit_result, heap, &self.interns)
             .map_err(|e| e.into_python_exception(&self.interns, &self.code))
     }
 
+    /// Executes the code like `run`, also returning the VM's opcode, branch and call counters.
+    #[cfg(feature = "vm-metrics")]
+    fn run_with_metrics(
+        &self,
+        inputs: Vec<MontyObject>,
+        resource_tracker: impl ResourceTracker,
+        print: &mut PrintWriter<'_>,
+    ) -> (Result<MontyObject, MontyException>, crate::VmMetrics) {
+        let mut heap = Heap::new(self.namespace_size, resource_tracker);
+        let mut namespaces = match self.prepare_namespaces(inputs, &mut heap) {
+            Ok(namespaces) => namespaces,
+            Err(err) => return (Err(err), crate::VmMetrics::default()),
+        };
+
+        let mut vm = VM::new(&mut heap, &mut namespaces, &self.interns, print);
+        let frame_exit_result = vm.run_module(&self.module_code);
+        let metrics = vm.take_metrics();
+        vm.cleanup();
+
+        #[cfg(feature = "ref-count-panic")]
+        namespaces.drop_global_with_heap(&mut heap);
+
+        let result = frame_exit_to_object(frame_exit_result, &mut heap, &self.interns)
+            .map_err(|e| e.into_python_exception(&self.interns, &self.code));
+        (result, metrics)
+    }
+
     /// Executes the code and returns both the result and reference count data, used for testing only.
     ///
     /// This is used for testing reference counting behavior. Returns:
@@ -904,12 +1767,9 @@ impl Executor {
         inputs: Vec<MontyObject>,
         heap: &mut Heap<impl ResourceTracker>,
     ) -> Result<Namespaces, MontyException> {
-        let Some(extra) = self
-            .namespace_size
-            .checked_sub(self.external_function_ids.len() + inputs.len())
-        else {
+        if self.external_function_ids.len() + inputs.len() > self.dunders.slot() {
             return Err(MontyException::runtime_error("too many inputs for namespace"));
-        };
+        }
         // register external functions in the namespace first, matching the logic in prepare
         let mut namespace: Vec<Value> = Vec::with_capacity(self.namespace_size);
         for f_id in &self.external_function_ids {
@@ -923,11 +1783,46 @@ impl Executor {
                     .map_err(|e| MontyException::runtime_error(format!("invalid input type: {e}")))?,
             );
         }
-        if extra > 0 {
-            namespace.extend((0..extra).map(|_| Value::Undefined));
-        }
+        // inputs which weren't provided are left undefined
+        namespace.resize_with(self.dunders.slot(), || Value::Undefined);
+        namespace.extend(self.dunders.values());
+        namespace.resize_with(self.namespace_size, || Value::Undefined);
         Ok(Namespaces::new(namespace))
     }
+
+    /// Enables the pauses only the iterative API can hand to the host: checkpoints, and call
+    /// batching if the runner opted in.
+    fn configure_vm(&self, vm: &mut VM<'_, '_, impl ResourceTracker>) {
+        vm.enable_checkpoints();
+        if self.batch_async_calls {
+            vm.enable_call_batching();
+        }
+    }
+
+    /// The executor's compiled program, serialized by `state_eq` to compare execution state.
+    ///
+    /// Leaves out the heap capacity estimate, which only affects pre-allocation.
+    fn state(&self) -> impl serde::Serialize + '_ {
+        (
+            self.namespace_size,
+            &self.module_code,
+            &self.interns,
+            &self.external_function_ids,
+            &self.dunders,
+            &self.code,
+            self.batch_async_calls,
+        )
+    }
+}
+
+/// Whether two parts of execution state serialize to the same bytes.
+///
+/// Used by `state_eq` on the snapshot types. Comparing the serialized form checks everything
+/// `dump()` stores, including state without a `PartialEq` implementation like heap entries.
+fn serialized_eq(a: &impl serde::Serialize, b: &impl serde::Serialize) -> bool {
+    let a = postcard::to_allocvec(a).expect("execution state is serializable");
+    let b = postcard::to_allocvec(b).expect("execution state is serializable");
+    a == b
 }
 
 fn frame_exit_to_object(
@@ -942,6 +1837,10 @@ fn frame_exit_to_object(
         } => {
             args.drop_with_heap(heap);
             let function_name = interns.get_external_function_name(ext_function_id);
+            heap.tracker().audit(&AuditEvent::now(AuditEventKind::CapabilityDenied {
+                capability: Capability::ExternalFunction,
+                name: &function_name,
+            }));
             Err(ExcType::not_implemented(format!(
                 "External function '{function_name}' not implemented with standard execution"
             ))
@@ -949,6 +1848,11 @@ fn frame_exit_to_object(
         }
         FrameExit::OsCall { function, args, .. } => {
             args.drop_with_heap(heap);
+            let name = function.to_string();
+            heap.tracker().audit(&AuditEvent::now(AuditEventKind::CapabilityDenied {
+                capability: Capability::OsFunction,
+                name: &name,
+            }));
             Err(ExcType::not_implemented(format!(
                 "OS function '{function}' not implemented with standard execution"
             ))
@@ -957,14 +1861,31 @@ fn frame_exit_to_object(
         FrameExit::MethodCall { method_name, args, .. } => {
             args.drop_with_heap(heap);
             let name = method_name.as_str(interns);
+            heap.tracker().audit(&AuditEvent::now(AuditEventKind::CapabilityDenied {
+                capability: Capability::MethodCall,
+                name: name,
+            }));
             Err(
                 ExcType::not_implemented(format!("Method call '{name}' not implemented with standard execution"))
                     .into(),
             )
         }
         FrameExit::ResolveFutures(_) => {
+            heap.tracker().audit(&AuditEvent::now(AuditEventKind::CapabilityDenied {
+                capability: Capability::AsyncFutures,
+                name: "await",
+            }));
             Err(ExcType::not_implemented("async futures not supported by standard execution.").into())
         }
+        FrameExit::Yield { value, .. } => {
+            value.drop_with_heap(heap);
+            heap.tracker().audit(&AuditEvent::now(AuditEventKind::CapabilityDenied {
+                capability: Capability::Yield,
+                name: "monty_yield",
+            }));
+            Err(ExcType::not_implemented("monty_yield() not supported by standard execution.").into())
+        }
+        FrameExit::Checkpoint => Err(RunError::internal("checkpoints are only enabled by `MontyRun::start`")),
     }
 }
 
@@ -7,7 +7,7 @@
 use crate::{
     args::{ArgPosIter, ArgValues},
     defer_drop_mut,
-    exception_private::{ExcType, RunResult, SimpleException},
+    exception_private::{ExcType, RunError, RunResult},
     expressions::Identifier,
     heap::{DropWithHeap, Heap, HeapData, HeapGuard},
     intern::{Interns, StringId},
@@ -212,7 +212,7 @@ impl Signature {
 
         // Convert kwargs to an iterator and guard it so remaining items are cleaned up
         // on any error path
-        let kwonly_given = keyword_args.len();
+        let keywords_given = keyword_args.len();
         let keyword_args = keyword_args.into_iter();
         defer_drop_mut!(keyword_args, heap);
 
@@ -224,7 +224,7 @@ impl Signature {
         // signatures with only positional-or-keyword params and defaults.
         // This avoids the full binding algorithm overhead for common cases.
 
-        if matches!(self.bind_mode, BindMode::Simple | BindMode::SimpleWithDefaults) && kwonly_given == 0 {
+        if matches!(self.bind_mode, BindMode::Simple | BindMode::SimpleWithDefaults) && keywords_given == 0 {
             match pos_iter {
                 ArgPosIter::Empty => {}
                 ArgPosIter::One(a) => {
@@ -261,7 +261,17 @@ impl Signature {
                 }
             }
 
-            return self.wrong_arg_count_error(actual_count, interns, func_name);
+            if actual_count > param_count {
+                return Err(self.too_many_positional_error(actual_count, 0, interns, func_name));
+            }
+            let missing: Vec<&str> = self
+                .param_names()
+                .take(self.required_positional_count())
+                .skip(actual_count)
+                .map(|param_id| interns.get_str(param_id))
+                .collect();
+            let func = interns.get_str(func_name.name_id);
+            return Err(ExcType::type_error_missing_positional_with_names(func, &missing));
         }
 
         // Full binding algorithm for complex signatures or kwargs
@@ -272,20 +282,9 @@ impl Signature {
         let pos_param_count = self.pos_arg_count();
         let arg_param_count = self.arg_count();
         let total_positional_params = pos_param_count + arg_param_count;
-
-        // Check positional argument count against maximum
-        if let Some(max) = self.max_positional_count() {
-            let positional_count = pos_iter.len();
-            if positional_count > max {
-                let func = interns.get_str(func_name.name_id);
-                return Err(ExcType::type_error_too_many_positional(
-                    func,
-                    max,
-                    positional_count,
-                    kwonly_given,
-                ));
-            }
-        }
+        // Excess positional args are reported after keywords are bound, matching
+        // CPython's error precedence (e.g. unexpected keywords are reported first)
+        let positional_given = pos_iter.len();
 
         // Initialize result namespace with Undefined values for all slots
         // Layout: [pos_args][args][*args?][kwargs][**kwargs?]
@@ -322,11 +321,9 @@ impl Signature {
         }
 
         // 2. Collect excess positional args into *args tuple
+        // Without *args any excess stays in `pos_iter` and is reported after keyword binding
         if self.var_args.is_some() {
             namespace[total_positional_params] = allocate_tuple(pos_iter.collect(), heap)?;
-        } else {
-            // If no *args, excess was already checked above via max_positional_count
-            debug_assert_eq!(pos_iter.len(), 0);
         }
 
         // 3. Bind keyword args
@@ -334,6 +331,12 @@ impl Signature {
         let mut excess_kwargs_guard = HeapGuard::new(self.var_kwargs.is_some().then(Dict::new), heap);
         let (excess_kwargs, heap) = excess_kwargs_guard.as_parts_mut();
 
+        // The first keyword that matches no parameter. CPython reports positional-only
+        // parameters passed by keyword in preference, so once a keyword is unmatched the
+        // remaining ones are only scanned for positional-only names.
+        let mut unexpected_keyword: Option<String> = None;
+        let mut posonly_as_keyword: Vec<&str> = Vec::new();
+
         'kwargs: for (key, value) in keyword_args {
             // Guard key: dropped on most paths, consumed into **kwargs via into_parts().
             let mut key_guard = HeapGuard::new(key, heap);
@@ -347,65 +350,82 @@ impl Signature {
                 return Err(ExcType::type_error("keywords must be strings"));
             };
 
-            // Check if this keyword matches a positional-only param (error)
-            if let Some(pos_args) = &self.pos_args
-                && let Some(&param_id) = pos_args
-                    .iter()
-                    .find(|&&param_id| keyword_name.matches(param_id, interns))
-            {
-                let func = interns.get_str(func_name.name_id);
-                let param = interns.get_str(param_id);
-                return Err(ExcType::type_error_positional_only(func, param));
-            }
-
-            // Try positional-or-keyword params
-            if let Some(args) = &self.args {
-                for (i, &param_id) in args.iter().enumerate() {
-                    if keyword_name.matches(param_id, interns) {
-                        let ns_idx = pos_param_count + i;
-                        if (bound_params & (1 << ns_idx)) != 0 {
-                            let func = interns.get_str(func_name.name_id);
-                            let param = interns.get_str(param_id);
-                            return Err(ExcType::type_error_duplicate_arg(func, param));
+            if unexpected_keyword.is_none() {
+                // Try positional-or-keyword params
+                if let Some(args) = &self.args {
+                    for (i, &param_id) in args.iter().enumerate() {
+                        if keyword_name.matches(param_id, interns) {
+                            let ns_idx = pos_param_count + i;
+                            if (bound_params & (1 << ns_idx)) != 0 {
+                                let func = interns.get_str(func_name.name_id);
+                                let param = interns.get_str(param_id);
+                                return Err(ExcType::type_error_duplicate_arg(func, param));
+                            }
+                            let (value, _) = value_guard.into_parts();
+                            namespace[ns_idx] = value;
+                            bound_params |= 1 << ns_idx;
+                            continue 'kwargs;
                         }
-                        let (value, _) = value_guard.into_parts();
-                        namespace[ns_idx] = value;
-                        bound_params |= 1 << ns_idx;
-                        continue 'kwargs;
                     }
                 }
-            }
 
-            // Try keyword-only params
-            if let Some(kwargs) = &self.kwargs {
-                for (i, &param_id) in kwargs.iter().enumerate() {
-                    if keyword_name.matches(param_id, interns) {
-                        let ns_idx = total_positional_params + var_args_offset + i;
-                        let bit_idx = total_positional_params + i;
-                        if (bound_params & (1 << bit_idx)) != 0 {
-                            let func = interns.get_str(func_name.name_id);
-                            let param = interns.get_str(param_id);
-                            return Err(ExcType::type_error_duplicate_arg(func, param));
+                // Try keyword-only params
+                if let Some(kwargs) = &self.kwargs {
+                    for (i, &param_id) in kwargs.iter().enumerate() {
+                        if keyword_name.matches(param_id, interns) {
+                            let ns_idx = total_positional_params + var_args_offset + i;
+                            let bit_idx = total_positional_params + i;
+                            if (bound_params & (1 << bit_idx)) != 0 {
+                                let func = interns.get_str(func_name.name_id);
+                                let param = interns.get_str(param_id);
+                                return Err(ExcType::type_error_duplicate_arg(func, param));
+                            }
+                            let (value, _) = value_guard.into_parts();
+                            namespace[ns_idx] = value;
+                            bound_params |= 1 << bit_idx;
+                            continue 'kwargs;
                         }
-                        let (value, _) = value_guard.into_parts();
-                        namespace[ns_idx] = value;
-                        bound_params |= 1 << bit_idx;
-                        continue 'kwargs;
                     }
                 }
+
+                // Positional-only names are ordinary keys for **kwargs
+                if let Some(excess_kwargs) = excess_kwargs {
+                    // Consume both value and key into **kwargs dict
+                    let (value, _) = value_guard.into_parts();
+                    let (key, heap) = key_guard.into_parts();
+                    excess_kwargs.set(key, value, heap, interns)?;
+                    continue 'kwargs;
+                }
             }
 
-            if let Some(excess_kwargs) = excess_kwargs {
-                // Consume both value and key into **kwargs dict
-                let (value, _) = value_guard.into_parts();
-                let (key, heap) = key_guard.into_parts();
-                excess_kwargs.set(key, value, heap, interns)?;
-                continue 'kwargs;
+            // No parameter accepts this keyword
+            if let Some(pos_args) = &self.pos_args
+                && let Some(&param_id) = pos_args
+                    .iter()
+                    .find(|&&param_id| keyword_name.matches(param_id, interns))
+            {
+                posonly_as_keyword.push(interns.get_str(param_id));
+            }
+            if unexpected_keyword.is_none() {
+                unexpected_keyword = Some(keyword_name.as_str(interns).to_owned());
             }
+        }
 
+        if !posonly_as_keyword.is_empty() {
             let func = interns.get_str(func_name.name_id);
-            let key_str = keyword_name.as_str(interns);
-            return Err(ExcType::type_error_unexpected_keyword(func, key_str));
+            return Err(ExcType::type_error_positional_only(func, &posonly_as_keyword));
+        }
+        if let Some(key_str) = unexpected_keyword {
+            let func = interns.get_str(func_name.name_id);
+            return Err(ExcType::type_error_unexpected_keyword(func, &key_str));
+        }
+
+        // Report excess positional args now that keyword-only args are known
+        if pos_iter.len() > 0 {
+            let kwonly_given = (0..self.kwarg_count())
+                .filter(|i| (bound_params & (1 << (total_positional_params + i))) != 0)
+                .count();
+            return Err(self.too_many_positional_error(positional_given, kwonly_given, interns, func_name));
         }
 
         // 3.5. Apply default values to unbound optional parameters
@@ -583,7 +603,7 @@ impl Signature {
     /// Returns an iterator over all parameter names in namespace slot order.
     ///
     /// Order: pos_args, args, var_args (if present), kwargs, var_kwargs (if present)
-    fn param_names(&self) -> impl Iterator<Item = StringId> + '_ {
+    pub fn param_names(&self) -> impl Iterator<Item = StringId> + '_ {
         let pos_args = self.pos_args.iter().flat_map(|v| v.iter().copied());
         let args = self.args.iter().flat_map(|v| v.iter().copied());
         let var_args = self.var_args.iter().copied();
@@ -593,63 +613,73 @@ impl Signature {
         pos_args.chain(args).chain(var_args).chain(kwargs).chain(var_kwargs)
     }
 
-    /// Returns the maximum number of positional arguments accepted.
+    /// Renders the parameter list as Python source, e.g. `a, /, b=1, *args, c, **kwargs`.
     ///
-    /// Returns None if *args is present (unlimited positional args).
-    fn max_positional_count(&self) -> Option<usize> {
-        if self.var_args.is_some() {
-            None
-        } else {
-            Some(self.pos_arg_count() + self.arg_count())
+    /// `defaults` are the reprs of the default values, laid out like the defaults array
+    /// passed to [`Self::bind`]. Missing defaults are rendered as `...`.
+    pub fn params_source(&self, defaults: &[String], interns: &Interns) -> String {
+        let default_at = |index: usize| defaults.get(index).map_or("...", String::as_str);
+        let mut params = Vec::new();
+        let mut default_index = 0;
+        let mut push_positional = |names: &[StringId], defaults_count: usize, params: &mut Vec<String>| {
+            let required = names.len() - defaults_count;
+            for (i, &name) in names.iter().enumerate() {
+                let name = interns.get_str(name);
+                if i < required {
+                    params.push(name.to_owned());
+                } else {
+                    params.push(format!("{name}={}", default_at(default_index)));
+                    default_index += 1;
+                }
+            }
+        };
+
+        if let Some(pos_args) = &self.pos_args {
+            push_positional(pos_args, self.pos_defaults_count, &mut params);
+            params.push("/".to_owned());
+        }
+        if let Some(args) = &self.args {
+            push_positional(args, self.arg_defaults_count, &mut params);
         }
+        if let Some(var_args) = self.var_args {
+            params.push(format!("*{}", interns.get_str(var_args)));
+        } else if self.kwargs.is_some() {
+            params.push("*".to_owned());
+        }
+        if let (Some(kwargs), Some(default_map)) = (&self.kwargs, &self.kwarg_default_map) {
+            let kwarg_defaults_start = self.pos_defaults_count + self.arg_defaults_count;
+            for (&name, default) in kwargs.iter().zip(default_map) {
+                let name = interns.get_str(name);
+                match default {
+                    Some(i) => params.push(format!("{name}={}", default_at(kwarg_defaults_start + i))),
+                    None => params.push(name.to_owned()),
+                }
+            }
+        }
+        if let Some(var_kwargs) = self.var_kwargs {
+            params.push(format!("**{}", interns.get_str(var_kwargs)));
+        }
+        params.join(", ")
     }
 
-    /// Creates an error for wrong number of arguments.
+    /// Creates the TypeError for passing more positional arguments than the signature accepts.
     ///
-    /// Handles both "missing required positional arguments" and "too many arguments" cases,
-    /// formatting the error message to match CPython's style.
-    ///
-    /// # Arguments
-    /// * `actual_count` - Number of arguments actually provided
-    /// * `interns` - String storage for looking up interned names
-    fn wrong_arg_count_error<T>(&self, actual_count: usize, interns: &Interns, func_name: Identifier) -> RunResult<T> {
-        let name_str = interns.get_str(func_name.name_id);
-        let param_count = self.param_count();
-        let msg = if let Some(missing_count) = param_count.checked_sub(actual_count) {
-            // Missing arguments - show actual parameter names
-            let mut msg = format!(
-                "{}() missing {} required positional argument{}: ",
-                name_str,
-                missing_count,
-                if missing_count == 1 { "" } else { "s" }
-            );
-            // Collect parameter names, skipping the ones already provided
-            let mut missing_names: Vec<_> = self
-                .param_names()
-                .skip(actual_count)
-                .map(|string_id| format!("'{}'", interns.get_str(string_id)))
-                .collect();
-            let last = missing_names.pop().unwrap();
-            if !missing_names.is_empty() {
-                msg.push_str(&missing_names.join(", "));
-                msg.push_str(", and ");
-            }
-            msg.push_str(&last);
-            msg
-        } else {
-            // Too many arguments
-            format!(
-                "{}() takes {} positional argument{} but {} {} given",
-                name_str,
-                param_count,
-                if param_count == 1 { "" } else { "s" },
-                actual_count,
-                if actual_count == 1 { "was" } else { "were" }
-            )
-        };
-        Err(SimpleException::new_msg(ExcType::TypeError, msg)
-            .with_position(func_name.position)
-            .into())
+    /// `kwonly_given` is the number of keyword-only parameters that were passed, which
+    /// CPython includes in the message.
+    fn too_many_positional_error(
+        &self,
+        given: usize,
+        kwonly_given: usize,
+        interns: &Interns,
+        func_name: Identifier,
+    ) -> RunError {
+        ExcType::type_error_too_many_positional(
+            interns.get_str(func_name.name_id),
+            self.required_positional_count(),
+            self.pos_arg_count() + self.arg_count(),
+            given,
+            kwonly_given,
+        )
     }
 }
 
@@ -0,0 +1,79 @@
+//! Signing and content hashing of serialized programs.
+//!
+//! Serialized programs contain compiled bytecode which is executed without further validation,
+//! so loading bytes from untrusted storage is only safe if they're known to come from `dump()`.
+//! [`sign_serialized()`] appends an HMAC-SHA256 tag computed with an embedder-provided key, which
+//! [`verify_serialized()`] checks before the bytes are deserialized.
+
+use std::fmt;
+
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+
+/// Length in bytes of the tag appended by [`sign_serialized()`].
+pub const SIGNATURE_LEN: usize = 32;
+
+/// HMAC using SHA-256, accepting keys of any length.
+type HmacSha256 = Hmac<Sha256>;
+
+/// Error loading signed or unsigned serialized data.
+#[derive(Debug)]
+pub enum LoadError {
+    /// The data wasn't signed with the given key, or was modified after signing.
+    InvalidSignature,
+    /// The data couldn't be deserialized.
+    Deserialize(postcard::Error),
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidSignature => f.write_str("invalid signature, data was modified or signed with another key"),
+            Self::Deserialize(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl std::error::Error for LoadError {}
+
+impl From<postcard::Error> for LoadError {
+    fn from(err: postcard::Error) -> Self {
+        Self::Deserialize(err)
+    }
+}
+
+/// Appends an HMAC-SHA256 tag of `data` computed with `key`.
+#[must_use]
+pub fn sign_serialized(mut data: Vec<u8>, key: &[u8]) -> Vec<u8> {
+    let tag = mac(key, &data).finalize().into_bytes();
+    data.extend_from_slice(&tag);
+    data
+}
+
+/// Checks the tag appended by [`sign_serialized()`] in constant time, returning the data without it.
+///
+/// # Errors
+/// Returns [`LoadError::InvalidSignature`] if the tag is missing or doesn't match.
+pub fn verify_serialized<'a>(signed: &'a [u8], key: &[u8]) -> Result<&'a [u8], LoadError> {
+    let split = signed
+        .len()
+        .checked_sub(SIGNATURE_LEN)
+        .ok_or(LoadError::InvalidSignature)?;
+    let (data, tag) = signed.split_at(split);
+    mac(key, data)
+        .verify_slice(tag)
+        .map_err(|_| LoadError::InvalidSignature)?;
+    Ok(data)
+}
+
+/// SHA-256 hash of the source code of a program, see [`MontyRun::code_hash`](crate::MontyRun::code_hash).
+pub(crate) fn code_hash(code: &str) -> [u8; 32] {
+    Sha256::digest(code.as_bytes()).into()
+}
+
+/// Creates an HMAC of `data` with `key`.
+fn mac(key: &[u8], data: &[u8]) -> HmacSha256 {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(data);
+    mac
+}
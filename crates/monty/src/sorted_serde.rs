@@ -0,0 +1,33 @@
+//! Serializers writing hash maps and sets in sorted order.
+//!
+//! Iteration order of hash maps and sets depends on random hasher state, so serializing
+//! them directly gives different bytes for equal state. Sorting keeps `dump()` output
+//! deterministic, which `state_eq` on the snapshot types relies on. Deserialization is
+//! unaffected, use these with `#[serde(serialize_with = "...")]` only.
+
+use serde::{Serialize, Serializer};
+
+/// Serializes a set as a sequence in ascending order.
+pub(crate) fn serialize_set<'a, C, K, S>(set: &'a C, serializer: S) -> Result<S::Ok, S::Error>
+where
+    &'a C: IntoIterator<Item = &'a K>,
+    K: Ord + Serialize + 'a,
+    S: Serializer,
+{
+    let mut items: Vec<&K> = set.into_iter().collect();
+    items.sort_unstable();
+    serializer.collect_seq(items)
+}
+
+/// Serializes a map with its entries in ascending key order.
+pub(crate) fn serialize_map<'a, C, K, V, S>(map: &'a C, serializer: S) -> Result<S::Ok, S::Error>
+where
+    &'a C: IntoIterator<Item = (&'a K, &'a V)>,
+    K: Ord + Serialize + 'a,
+    V: Serialize + 'a,
+    S: Serializer,
+{
+    let mut entries: Vec<(&K, &V)> = map.into_iter().collect();
+    entries.sort_unstable_by_key(|&(key, _)| key);
+    serializer.collect_map(entries)
+}
@@ -0,0 +1,304 @@
+//! CPython-compatible errors for malformed escape sequences in string and bytes literals.
+//!
+//! Literals are decoded by ruff's parser, which rejects the same escapes as CPython but
+//! reports them with its own messages (e.g. "Got unexpected unicode"). When parsing fails,
+//! [`escape_error`] scans the source for the literal containing the error and rebuilds the
+//! `SyntaxError` message CPython raises, including the codec positions it reports.
+//!
+//! Unknown escapes such as `'\d'` are kept verbatim, as in CPython. CPython also emits a
+//! `SyntaxWarning` for them; Monty has no warnings, so they are accepted silently.
+
+use std::ops::Range;
+
+use ruff_text_size::{TextRange, TextSize};
+
+/// A malformed escape sequence, reported the way CPython reports it.
+#[derive(Debug)]
+pub(crate) struct EscapeError {
+    /// CPython's message, e.g. `(value error) invalid \x escape at position 0`.
+    pub message: String,
+    /// The whole literal including prefix and quotes, which is where CPython points the error.
+    pub range: TextRange,
+}
+
+/// Finds the malformed escape that made the parser reject `code` at byte `offset`.
+///
+/// Returns `None` if `offset` is not inside a literal with a malformed escape, in which
+/// case the parser's own error should be reported.
+pub(crate) fn escape_error(code: &str, offset: usize) -> Option<EscapeError> {
+    let mut scanner = Scanner {
+        code,
+        literals: Vec::new(),
+    };
+    scanner.scan_code(0, false);
+
+    scanner.literals.iter().find_map(|literal| {
+        let segment = literal.segments.iter().find(|s| s.start <= offset && offset <= s.end)?;
+        let text = &code[segment.clone()];
+        let message = if literal.is_bytes {
+            bytes_escape_error(text)
+        } else {
+            str_escape_error(text, offset - segment.start)
+        }?;
+        let range = TextRange::new(
+            TextSize::try_from(literal.range.start).ok()?,
+            TextSize::try_from(literal.range.end).ok()?,
+        );
+        Some(EscapeError { message, range })
+    })
+}
+
+/// A non-raw string or bytes literal found in the source.
+struct Literal {
+    /// Byte range of the literal including prefix and quotes.
+    range: Range<usize>,
+    is_bytes: bool,
+    /// The text between the quotes. F-strings are split at replacement fields and doubled
+    /// braces, since CPython decodes each piece of literal text separately.
+    segments: Vec<Range<usize>>,
+}
+
+/// Minimal tokenizer that finds string literals, skipping comments and f-string fields.
+///
+/// Only used on the error path, after the parser has already rejected the code.
+struct Scanner<'a> {
+    code: &'a str,
+    literals: Vec<Literal>,
+}
+
+impl Scanner<'_> {
+    /// Scans code from `i`, to the end of the source or, inside an f-string replacement
+    /// field, to its closing brace. Returns the index after the last byte scanned.
+    fn scan_code(&mut self, mut i: usize, in_field: bool) -> usize {
+        let bytes = self.code.as_bytes();
+        let mut depth = 0usize;
+        while i < bytes.len() {
+            match bytes[i] {
+                b'#' => {
+                    while i < bytes.len() && bytes[i] != b'\n' {
+                        i += 1;
+                    }
+                }
+                b'\'' | b'"' => i = self.scan_literal(i),
+                b'(' | b'[' | b'{' if in_field => {
+                    depth += 1;
+                    i += 1;
+                }
+                b')' | b']' if in_field => {
+                    depth = depth.saturating_sub(1);
+                    i += 1;
+                }
+                b'}' if in_field => {
+                    if depth == 0 {
+                        return i + 1;
+                    }
+                    depth -= 1;
+                    i += 1;
+                }
+                // The format spec is literal text, possibly with nested fields
+                b':' if in_field && depth == 0 => return self.scan_format_spec(i + 1),
+                _ => i += 1,
+            }
+        }
+        i
+    }
+
+    /// Scans an f-string format spec up to and including the field's closing brace.
+    fn scan_format_spec(&mut self, mut i: usize) -> usize {
+        let bytes = self.code.as_bytes();
+        while i < bytes.len() {
+            match bytes[i] {
+                b'{' => i = self.scan_code(i + 1, true),
+                b'}' => return i + 1,
+                _ => i += 1,
+            }
+        }
+        i
+    }
+
+    /// Scans the literal whose opening quote is at `quote_start`, returning the index after it.
+    fn scan_literal(&mut self, quote_start: usize) -> usize {
+        let code = self.code;
+        let bytes = code.as_bytes();
+        let (start, prefix) = literal_prefix(code, quote_start);
+        let is_raw = prefix.contains(['r', 'R']);
+        let is_bytes = prefix.contains(['b', 'B']);
+        let is_fstring = prefix.contains(['f', 'F', 't', 'T']);
+
+        let quote = bytes[quote_start];
+        let triple = bytes[quote_start..].starts_with(&[quote; 3]);
+        let quote_len = if triple { 3 } else { 1 };
+
+        let mut segments = Vec::new();
+        let mut segment_start = quote_start + quote_len;
+        let mut i = segment_start;
+        let end = loop {
+            if i >= bytes.len() {
+                segments.push(segment_start..bytes.len());
+                break bytes.len();
+            }
+            match bytes[i] {
+                // Skips the escaped byte, which may be a quote or brace
+                b'\\' => i += 2,
+                b'{' | b'}' if is_fstring => {
+                    segments.push(segment_start..i);
+                    if bytes.get(i + 1) == Some(&bytes[i]) {
+                        i += 2;
+                    } else if bytes[i] == b'{' {
+                        i = self.scan_code(i + 1, true);
+                    } else {
+                        i += 1;
+                    }
+                    segment_start = i;
+                }
+                b if b == quote && (!triple || bytes[i..].starts_with(&[quote; 3])) => {
+                    segments.push(segment_start..i);
+                    break i + quote_len;
+                }
+                // Unterminated single-quoted literal
+                b'\n' if !triple => {
+                    segments.push(segment_start..i);
+                    break i;
+                }
+                _ => i += 1,
+            }
+        };
+
+        if !is_raw {
+            self.literals.push(Literal {
+                range: start..end,
+                is_bytes,
+                segments,
+            });
+        }
+        end
+    }
+}
+
+/// Returns the start of the literal whose opening quote is at `quote_start`, and its prefix.
+fn literal_prefix(code: &str, quote_start: usize) -> (usize, &str) {
+    let bytes = code.as_bytes();
+    let mut start = quote_start;
+    while start > 0 && bytes[start - 1].is_ascii_alphabetic() {
+        start -= 1;
+    }
+    let prefix = &code[start..quote_start];
+    let is_identifier_part = start > 0 && (bytes[start - 1] == b'_' || bytes[start - 1].is_ascii_digit());
+    if prefix.len() <= 2 && !is_identifier_part && prefix.chars().all(|c| "rRbBuUfFtT".contains(c)) {
+        (start, prefix)
+    } else {
+        (quote_start, "")
+    }
+}
+
+/// Decodes the escapes in the text of a str literal like CPython's `unicodeescape` codec.
+///
+/// CPython converts each non-ASCII character to a 10 byte `\UXXXXXXXX` escape before
+/// decoding, so positions count those as 10. `\N{...}` names can't be looked up here; the
+/// escape containing `error_offset` is assumed to be the unknown name the parser rejected.
+fn str_escape_error(text: &str, error_offset: usize) -> Option<String> {
+    let mut chars = text.char_indices().peekable();
+    // Position in CPython's encoded bytes
+    let mut pos = 0usize;
+    let error = |start: usize, end: usize, msg: &str| {
+        Some(format!(
+            "(unicode error) 'unicodeescape' codec can't decode bytes in position {start}-{}: {msg}",
+            end - 1
+        ))
+    };
+
+    while let Some((index, c)) = chars.next() {
+        if c != '\\' {
+            pos += if c.is_ascii() { 1 } else { 10 };
+            continue;
+        }
+        let start = pos;
+        let Some((_, escape)) = chars.next() else {
+            break;
+        };
+        pos += if escape.is_ascii() { 2 } else { 11 };
+        match escape {
+            'x' | 'u' | 'U' => {
+                let (len, msg) = match escape {
+                    'x' => (2, "truncated \\xXX escape"),
+                    'u' => (4, "truncated \\uXXXX escape"),
+                    _ => (8, "truncated \\UXXXXXXXX escape"),
+                };
+                let mut value = 0u32;
+                for _ in 0..len {
+                    let Some(digit) = chars.peek().and_then(|(_, d)| d.to_digit(16)) else {
+                        return error(start, pos, msg);
+                    };
+                    chars.next();
+                    value = value * 16 + digit;
+                    pos += 1;
+                }
+                if value > 0x0010_FFFF {
+                    return error(start, pos, "illegal Unicode character");
+                }
+            }
+            'N' => {
+                if chars.next_if(|(_, c)| *c == '{').is_none() {
+                    return error(start, pos, "malformed \\N character escape");
+                }
+                pos += 1;
+                let mut name_len = 0;
+                loop {
+                    match chars.peek() {
+                        Some((_, '}')) if name_len > 0 => {
+                            let (close, _) = chars.next().expect("peeked");
+                            pos += 1;
+                            if (index..=close).contains(&error_offset) {
+                                return error(start, pos, "unknown Unicode character name");
+                            }
+                            break;
+                        }
+                        Some((_, '}')) | None => return error(start, pos, "malformed \\N character escape"),
+                        Some((_, c)) => {
+                            pos += if c.is_ascii() { 1 } else { 10 };
+                            name_len += 1;
+                            chars.next();
+                        }
+                    }
+                }
+            }
+            '0'..='7' => {
+                for _ in 0..2 {
+                    if chars.next_if(|(_, c)| matches!(*c, '0'..='7')).is_none() {
+                        break;
+                    }
+                    pos += 1;
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Decodes the escapes in the text of a bytes literal like CPython's bytes parser.
+///
+/// Returns `None` for non-ASCII text, which the parser already reports like CPython.
+fn bytes_escape_error(text: &str) -> Option<String> {
+    if !text.is_ascii() {
+        return None;
+    }
+    let bytes = text.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] != b'\\' {
+            i += 1;
+            continue;
+        }
+        if bytes.get(i + 1) == Some(&b'x') {
+            let is_hex = |offset: usize| bytes.get(i + offset).is_some_and(u8::is_ascii_hexdigit);
+            if !(is_hex(2) && is_hex(3)) {
+                return Some(format!("(value error) invalid \\x escape at position {i}"));
+            }
+            i += 4;
+        } else {
+            i += 2;
+        }
+    }
+    None
+}
@@ -0,0 +1,76 @@
+//! Optional static type checking gate for [`MontyRun`](crate::MontyRun).
+//!
+//! Enabled with the `type-checking` feature, this lets Rust embedders run the same `ty`-based
+//! check used by the CLI and the Python bindings before any code is compiled.
+use std::fmt;
+
+use monty_type_checking::{SourceFile, TypeCheckingDiagnostics, type_check};
+
+use crate::MontyException;
+
+/// File name used for the stubs prefix passed to [`MontyRun::new_checked`](crate::MontyRun::new_checked).
+const STUBS_FILE_NAME: &str = "type_stubs.pyi";
+
+/// How strictly type checking failures are treated when creating a runner.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TypeCheckMode {
+    /// Skip type checking entirely.
+    #[default]
+    Off,
+    /// Type check, but only record diagnostics on the runner; the code still compiles.
+    Warn,
+    /// Type check and refuse to create the runner if there are any diagnostics.
+    Strict,
+}
+
+/// Error returned by [`MontyRun::new_checked`](crate::MontyRun::new_checked).
+#[derive(Debug, Clone)]
+pub enum TypeCheckError {
+    /// Type checking found problems and the mode was [`TypeCheckMode::Strict`].
+    Failed(TypeCheckingDiagnostics),
+    /// The type checker itself failed unexpectedly.
+    Internal(String),
+    /// The code could not be parsed or compiled.
+    Exception(MontyException),
+}
+
+impl fmt::Display for TypeCheckError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Failed(diagnostics) => write!(f, "type checking failed:\n{diagnostics}"),
+            Self::Internal(message) => write!(f, "internal type checking error: {message}"),
+            Self::Exception(exc) => write!(f, "{exc}"),
+        }
+    }
+}
+
+impl std::error::Error for TypeCheckError {}
+
+impl From<MontyException> for TypeCheckError {
+    fn from(exc: MontyException) -> Self {
+        Self::Exception(exc)
+    }
+}
+
+/// Runs the type checker according to `mode`.
+///
+/// Returns the rendered diagnostics in [`TypeCheckMode::Warn`] mode so they can be stored on the runner,
+/// and `Ok(None)` when checking is off or the code is clean.
+pub(crate) fn check_code(
+    code: &str,
+    script_name: &str,
+    stubs: Option<&str>,
+    mode: TypeCheckMode,
+) -> Result<Option<String>, TypeCheckError> {
+    if mode == TypeCheckMode::Off {
+        return Ok(None);
+    }
+    let stubs = stubs.map(|stubs| SourceFile::new(stubs, STUBS_FILE_NAME));
+    let diagnostics =
+        type_check(&SourceFile::new(code, script_name), stubs.as_ref()).map_err(TypeCheckError::Internal)?;
+    match (diagnostics, mode) {
+        (None, _) => Ok(None),
+        (Some(diagnostics), TypeCheckMode::Strict) => Err(TypeCheckError::Failed(diagnostics)),
+        (Some(diagnostics), _) => Ok(Some(diagnostics.to_string())),
+    }
+}
@@ -0,0 +1,638 @@
+//! Python `array.array`, a compact sequence of machine numbers.
+//!
+//! A list stores every item as a full `Value`, while an array stores bare numbers in one
+//! contiguous buffer: integer typecodes share a `Vec<i64>` and float typecodes a `Vec<f64>`.
+//! Indexing and appending stay O(1) and large numeric workloads use a fraction of the memory.
+//!
+//! The typecode still limits which values are accepted, so `array('b')` rejects 200 with
+//! CPython's `OverflowError` and `array('f')` rounds values to single precision. Unsigned
+//! 64-bit typecodes (`L` and `Q`) are capped at `i64::MAX` since items are stored as `i64`.
+//! Unicode typecodes (`u` and `w`) and the byte-level methods (`frombytes()`, `tobytes()`,
+//! `byteswap()`, ...) aren't supported.
+
+use std::fmt::Write;
+
+use ahash::AHashSet;
+
+use super::{MontyIter, PyTrait};
+use crate::{
+    args::ArgValues,
+    defer_drop,
+    exception_private::{ExcType, RunError, RunResult, SimpleException},
+    heap::{DropWithHeap, Heap, HeapData, HeapId},
+    intern::{Interns, StaticStrings, StringId},
+    io::PrintWriter,
+    resource::{DepthGuard, ResourceError, ResourceTracker},
+    types::{AttrCallResult, List, Type, list::repr_sequence_fmt, r#type::big_int_to_f64},
+    value::{EitherStr, Value},
+};
+
+/// The items of an [`Array`], stored unboxed.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+enum ArrayItems {
+    /// Items of the integer typecodes `b`, `B`, `h`, `H`, `i`, `I`, `l`, `L`, `q` and `Q`.
+    Int(Vec<i64>),
+    /// Items of the float typecodes `f` and `d`.
+    Float(Vec<f64>),
+}
+
+/// A typed numeric array created by `array.array()`.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub(crate) struct Array {
+    typecode: char,
+    items: ArrayItems,
+}
+
+impl Array {
+    /// Creates an empty array, returning `None` if `typecode` isn't supported.
+    fn new(typecode: char) -> Option<Self> {
+        let items = match typecode {
+            'b' | 'B' | 'h' | 'H' | 'i' | 'I' | 'l' | 'L' | 'q' | 'Q' => ArrayItems::Int(Vec::new()),
+            'f' | 'd' => ArrayItems::Float(Vec::new()),
+            _ => return None,
+        };
+        Some(Self { typecode, items })
+    }
+
+    /// Creates an array from the `array()` constructor call.
+    ///
+    /// - `array(typecode)` creates an empty array
+    /// - `array(typecode, iterable)` converts each item of the iterable, including another array
+    pub fn init(heap: &mut Heap<impl ResourceTracker>, args: ArgValues, interns: &Interns) -> RunResult<Value> {
+        let (typecode_arg, initializer) = args.get_one_two_args("array", heap)?;
+        let typecode = typecode_arg.as_either_str(heap).and_then(|s| {
+            let mut chars = s.as_str(interns).chars();
+            chars.next().filter(|_| chars.next().is_none())
+        });
+        let Some(typecode) = typecode else {
+            let msg = format!(
+                "array() argument 1 must be a unicode character, not {}",
+                typecode_arg.py_type(heap)
+            );
+            typecode_arg.drop_with_heap(heap);
+            initializer.drop_with_heap(heap);
+            return Err(ExcType::type_error(msg));
+        };
+        typecode_arg.drop_with_heap(heap);
+        let Some(mut array) = Self::new(typecode) else {
+            initializer.drop_with_heap(heap);
+            return Err(SimpleException::new_msg(
+                ExcType::ValueError,
+                "bad typecode (must be b, B, h, H, i, I, l, L, q, Q, f or d)",
+            )
+            .into());
+        };
+
+        if let Some(initializer) = initializer {
+            if initializer.as_either_str(heap).is_some() {
+                initializer.drop_with_heap(heap);
+                return Err(ExcType::type_error(format!(
+                    "cannot use a str to initialize an array with typecode '{typecode}'"
+                )));
+            }
+            array.extend(initializer, None, heap, interns)?;
+        }
+        let heap_id = heap.allocate(HeapData::Array(array))?;
+        Ok(Value::Ref(heap_id))
+    }
+
+    /// Returns the number of items in the array.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        match &self.items {
+            ArrayItems::Int(items) => items.len(),
+            ArrayItems::Float(items) => items.len(),
+        }
+    }
+
+    /// Returns the item at `index` as a `Value`, or `None` if it's out of range.
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<Value> {
+        match &self.items {
+            ArrayItems::Int(items) => items.get(index).map(|&i| Value::Int(i)),
+            ArrayItems::Float(items) => items.get(index).map(|&f| Value::Float(f)),
+        }
+    }
+
+    /// Returns every item as a `Value`, e.g. for `tolist()`.
+    #[must_use]
+    pub fn to_values(&self) -> Vec<Value> {
+        match &self.items {
+            ArrayItems::Int(items) => items.iter().map(|&i| Value::Int(i)).collect(),
+            ArrayItems::Float(items) => items.iter().map(|&f| Value::Float(f)).collect(),
+        }
+    }
+
+    /// Returns the size in bytes of one item in the equivalent C array, e.g. 4 for `'i'`.
+    fn itemsize(&self) -> i64 {
+        match self.typecode {
+            'b' | 'B' => 1,
+            'h' | 'H' => 2,
+            'i' | 'I' | 'f' => 4,
+            _ => 8,
+        }
+    }
+
+    /// Converts `value` to an item and appends it.
+    ///
+    /// # Errors
+    /// Returns `TypeError` if `value` isn't a number the typecode accepts, or `OverflowError`
+    /// if it is out of range for the typecode.
+    fn push(&mut self, value: &Value, heap: &Heap<impl ResourceTracker>) -> RunResult<()> {
+        match &mut self.items {
+            ArrayItems::Int(items) => items.push(int_item(self.typecode, value, heap)?),
+            ArrayItems::Float(items) => items.push(float_item(self.typecode, value, heap)?),
+        }
+        Ok(())
+    }
+
+    /// Converts `value` to an item and stores it at `index`, which must be in range.
+    fn set(&mut self, index: usize, value: &Value, heap: &Heap<impl ResourceTracker>) -> RunResult<()> {
+        match &mut self.items {
+            ArrayItems::Int(items) => items[index] = int_item(self.typecode, value, heap)?,
+            ArrayItems::Float(items) => items[index] = float_item(self.typecode, value, heap)?,
+        }
+        Ok(())
+    }
+
+    /// Converts and appends every item of `iterable`, consuming it.
+    ///
+    /// `self_id` is the array's own heap id while it is taken out of the heap for a method call,
+    /// so `a.extend(a)` copies the items directly instead of iterating the missing entry. Items
+    /// converted before an invalid one are kept, as in CPython.
+    fn extend(
+        &mut self,
+        iterable: Value,
+        self_id: Option<HeapId>,
+        heap: &mut Heap<impl ResourceTracker>,
+        interns: &Interns,
+    ) -> RunResult<()> {
+        if let Value::Ref(id) = &iterable
+            && Some(*id) == self_id
+        {
+            iterable.drop_with_heap(heap);
+            match &mut self.items {
+                ArrayItems::Int(items) => items.extend_from_within(..),
+                ArrayItems::Float(items) => items.extend_from_within(..),
+            }
+            return Ok(());
+        }
+        let mut iter = MontyIter::new(iterable, heap, interns)?;
+        let result = loop {
+            match iter.for_next(heap, interns) {
+                Ok(Some(item)) => {
+                    let pushed = self.push(&item, heap);
+                    item.drop_with_heap(heap);
+                    if let Err(e) = pushed {
+                        break Err(e);
+                    }
+                }
+                Ok(None) => break Ok(()),
+                Err(e) => break Err(e),
+            }
+        };
+        iter.drop_with_heap(heap);
+        result
+    }
+
+    /// Implements `array.extend(iterable)`, which unlike the constructor only accepts arrays
+    /// with the same typecode.
+    fn extend_method(
+        &mut self,
+        args: ArgValues,
+        self_id: Option<HeapId>,
+        heap: &mut Heap<impl ResourceTracker>,
+        interns: &Interns,
+    ) -> RunResult<Value> {
+        let iterable = args.get_one_arg("array.extend", heap)?;
+        if let Value::Ref(id) = &iterable
+            && Some(*id) != self_id
+            && let HeapData::Array(other) = heap.get(*id)
+            && other.typecode != self.typecode
+        {
+            iterable.drop_with_heap(heap);
+            return Err(ExcType::type_error("can only extend with array of same kind"));
+        }
+        self.extend(iterable, self_id, heap, interns)?;
+        Ok(Value::None)
+    }
+
+    /// Returns the index of the first item equal to `value`.
+    fn find(
+        &self,
+        value: &Value,
+        heap: &mut Heap<impl ResourceTracker>,
+        interns: &Interns,
+    ) -> Result<Option<usize>, ResourceError> {
+        let mut guard = DepthGuard::default();
+        for i in 0..self.len() {
+            heap.check_time()?;
+            let item = self.get(i).expect("index is in range");
+            if value.py_eq(&item, heap, &mut guard, interns)? {
+                return Ok(Some(i));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Handles slice-based indexing, returning a new array with the same typecode.
+    fn getitem_slice(&self, slice: &crate::types::Slice, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
+        let (start, stop, step) = slice
+            .indices(self.len())
+            .map_err(|()| ExcType::value_error_slice_step_zero())?;
+        let items = match &self.items {
+            ArrayItems::Int(items) => ArrayItems::Int(slice_items(items, start, stop, step)),
+            ArrayItems::Float(items) => ArrayItems::Float(slice_items(items, start, stop, step)),
+        };
+        let array = Self {
+            typecode: self.typecode,
+            items,
+        };
+        Ok(Value::Ref(heap.allocate(HeapData::Array(array))?))
+    }
+
+    /// Converts an index argument to a position in the array, handling negative indices.
+    fn normalize_index(&self, key: &Value, heap: &Heap<impl ResourceTracker>) -> RunResult<Option<usize>> {
+        let index = match key {
+            Value::Int(i) => *i,
+            Value::Bool(b) => i64::from(*b),
+            Value::Ref(id) if matches!(heap.get(*id), HeapData::LongInt(_)) => return Ok(None),
+            _ => return Err(ExcType::type_error("array indices must be integers")),
+        };
+        let len = i64::try_from(self.len()).expect("array length exceeds i64::MAX");
+        let index = if index < 0 { index + len } else { index };
+        Ok(usize::try_from(index).ok().filter(|&i| i < self.len()))
+    }
+}
+
+impl PyTrait for Array {
+    fn py_type(&self, _heap: &Heap<impl ResourceTracker>) -> Type {
+        Type::Array
+    }
+
+    fn py_estimate_size(&self) -> usize {
+        // Both storage kinds use 8 bytes per item whatever the typecode
+        std::mem::size_of::<Self>() + self.len() * 8
+    }
+
+    fn py_len(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> Option<usize> {
+        Some(self.len())
+    }
+
+    fn py_getitem(&self, key: &Value, heap: &mut Heap<impl ResourceTracker>, _interns: &Interns) -> RunResult<Value> {
+        if let Value::Ref(id) = key
+            && let HeapData::Slice(slice) = heap.get(*id)
+        {
+            let slice = slice.clone();
+            return self.getitem_slice(&slice, heap);
+        }
+        let index = self.normalize_index(key, heap)?;
+        index
+            .and_then(|i| self.get(i))
+            .ok_or_else(|| SimpleException::new_msg(ExcType::IndexError, "array index out of range").into())
+    }
+
+    fn py_setitem(
+        &mut self,
+        key: Value,
+        value: Value,
+        heap: &mut Heap<impl ResourceTracker>,
+        _interns: &Interns,
+    ) -> RunResult<()> {
+        defer_drop!(key, heap);
+        defer_drop!(value, heap);
+        let Some(index) = self.normalize_index(key, heap)? else {
+            return Err(SimpleException::new_msg(ExcType::IndexError, "array assignment index out of range").into());
+        };
+        self.set(index, value, heap)
+    }
+
+    fn py_eq(
+        &self,
+        other: &Self,
+        heap: &mut Heap<impl ResourceTracker>,
+        guard: &mut DepthGuard,
+        interns: &Interns,
+    ) -> Result<bool, ResourceError> {
+        if self.len() != other.len() {
+            return Ok(false);
+        }
+        match (&self.items, &other.items) {
+            (ArrayItems::Int(a), ArrayItems::Int(b)) => Ok(a == b),
+            (ArrayItems::Float(a), ArrayItems::Float(b)) => Ok(a == b),
+            // Mixed kinds compare item by item with Python's int/float equality
+            _ => {
+                for i in 0..self.len() {
+                    heap.check_time()?;
+                    let (a, b) = (self.get(i), other.get(i));
+                    if !a
+                        .expect("index is in range")
+                        .py_eq(&b.expect("index is in range"), heap, guard, interns)?
+                    {
+                        return Ok(false);
+                    }
+                }
+                Ok(true)
+            }
+        }
+    }
+
+    fn py_dec_ref_ids(&mut self, _stack: &mut Vec<HeapId>) {
+        // Arrays hold bare numbers, nothing to do
+    }
+
+    fn py_bool(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> bool {
+        self.len() > 0
+    }
+
+    fn py_repr_fmt(
+        &self,
+        f: &mut impl Write,
+        heap: &Heap<impl ResourceTracker>,
+        heap_ids: &mut AHashSet<HeapId>,
+        guard: &mut DepthGuard,
+        interns: &Interns,
+    ) -> std::fmt::Result {
+        write!(f, "array('{}'", self.typecode)?;
+        if self.len() > 0 {
+            f.write_str(", ")?;
+            repr_sequence_fmt('[', ']', &self.to_values(), f, heap, heap_ids, guard, interns)?;
+        }
+        f.write_char(')')
+    }
+
+    fn py_add(
+        &self,
+        other: &Self,
+        heap: &mut Heap<impl ResourceTracker>,
+        _interns: &Interns,
+    ) -> Result<Option<Value>, ResourceError> {
+        let items = match (&self.items, &other.items) {
+            (ArrayItems::Int(a), ArrayItems::Int(b)) if self.typecode == other.typecode => {
+                ArrayItems::Int([a.as_slice(), b].concat())
+            }
+            (ArrayItems::Float(a), ArrayItems::Float(b)) if self.typecode == other.typecode => {
+                ArrayItems::Float([a.as_slice(), b].concat())
+            }
+            _ => return Ok(None),
+        };
+        let array = Self {
+            typecode: self.typecode,
+            items,
+        };
+        Ok(Some(Value::Ref(heap.allocate(HeapData::Array(array))?)))
+    }
+
+    fn py_call_attr(
+        &mut self,
+        heap: &mut Heap<impl ResourceTracker>,
+        attr: &EitherStr,
+        args: ArgValues,
+        interns: &Interns,
+    ) -> RunResult<Value> {
+        match attr.static_string() {
+            Some(StaticStrings::Append) => {
+                let item = args.get_one_arg("array.append", heap)?;
+                defer_drop!(item, heap);
+                self.push(item, heap)?;
+                Ok(Value::None)
+            }
+            Some(StaticStrings::Extend) => self.extend_method(args, None, heap, interns),
+            Some(StaticStrings::Insert) => {
+                let (index, item) = args.get_two_args("array.insert", heap)?;
+                defer_drop!(index, heap);
+                defer_drop!(item, heap);
+                let index = index.as_int(heap)?;
+                let len = i64::try_from(self.len()).expect("array length exceeds i64::MAX");
+                // Out of range indices clamp to the ends, as in `list.insert()`
+                let index = usize::try_from(if index < 0 { index + len } else { index }.clamp(0, len))
+                    .expect("index clamped to a valid position");
+                match &mut self.items {
+                    ArrayItems::Int(items) => items.insert(index, int_item(self.typecode, item, heap)?),
+                    ArrayItems::Float(items) => items.insert(index, float_item(self.typecode, item, heap)?),
+                }
+                Ok(Value::None)
+            }
+            Some(StaticStrings::Pop) => {
+                let index = args.get_zero_one_arg("array.pop", heap)?;
+                let index = match index {
+                    Some(v) => {
+                        let result = v.as_int(heap);
+                        v.drop_with_heap(heap);
+                        result?
+                    }
+                    None => -1,
+                };
+                if self.len() == 0 {
+                    return Err(SimpleException::new_msg(ExcType::IndexError, "pop from empty array").into());
+                }
+                let len = i64::try_from(self.len()).expect("array length exceeds i64::MAX");
+                let index = if index < 0 { index + len } else { index };
+                let Some(index) = usize::try_from(index).ok().filter(|&i| i < self.len()) else {
+                    return Err(SimpleException::new_msg(ExcType::IndexError, "pop index out of range").into());
+                };
+                Ok(match &mut self.items {
+                    ArrayItems::Int(items) => Value::Int(items.remove(index)),
+                    ArrayItems::Float(items) => Value::Float(items.remove(index)),
+                })
+            }
+            Some(StaticStrings::Remove) => {
+                let value = args.get_one_arg("array.remove", heap)?;
+                defer_drop!(value, heap);
+                let Some(index) = self.find(value, heap, interns)? else {
+                    return Err(not_in_array_error("remove"));
+                };
+                match &mut self.items {
+                    ArrayItems::Int(items) => {
+                        items.remove(index);
+                    }
+                    ArrayItems::Float(items) => {
+                        items.remove(index);
+                    }
+                }
+                Ok(Value::None)
+            }
+            Some(StaticStrings::Index) => {
+                let value = args.get_one_arg("array.index", heap)?;
+                defer_drop!(value, heap);
+                let Some(index) = self.find(value, heap, interns)? else {
+                    return Err(not_in_array_error("index"));
+                };
+                Ok(Value::Int(i64::try_from(index).expect("index exceeds i64::MAX")))
+            }
+            Some(StaticStrings::Count) => {
+                let value = args.get_one_arg("array.count", heap)?;
+                defer_drop!(value, heap);
+                let mut guard = DepthGuard::default();
+                let mut count: i64 = 0;
+                for i in 0..self.len() {
+                    heap.check_time()?;
+                    let item = self.get(i).expect("index is in range");
+                    if value.py_eq(&item, heap, &mut guard, interns)? {
+                        count += 1;
+                    }
+                }
+                Ok(Value::Int(count))
+            }
+            Some(StaticStrings::Reverse) => {
+                args.check_zero_args("array.reverse", heap)?;
+                match &mut self.items {
+                    ArrayItems::Int(items) => items.reverse(),
+                    ArrayItems::Float(items) => items.reverse(),
+                }
+                Ok(Value::None)
+            }
+            Some(StaticStrings::Tolist) => {
+                args.check_zero_args("array.tolist", heap)?;
+                let heap_id = heap.allocate(HeapData::List(List::new(self.to_values())))?;
+                Ok(Value::Ref(heap_id))
+            }
+            _ => {
+                args.drop_with_heap(heap);
+                Err(ExcType::attribute_error(Type::Array, attr.as_str(interns)))
+            }
+        }
+    }
+
+    /// Intercepts `extend` so extending an array with itself doesn't read its own heap entry,
+    /// and delegates all other methods to `py_call_attr`.
+    fn py_call_attr_raw(
+        &mut self,
+        self_id: HeapId,
+        heap: &mut Heap<impl ResourceTracker>,
+        attr: &EitherStr,
+        args: ArgValues,
+        interns: &Interns,
+        _print_writer: &mut PrintWriter<'_>,
+    ) -> RunResult<AttrCallResult> {
+        if attr.static_string() == Some(StaticStrings::Extend) {
+            return self
+                .extend_method(args, Some(self_id), heap, interns)
+                .map(AttrCallResult::Value);
+        }
+        self.py_call_attr(heap, attr, args, interns).map(AttrCallResult::Value)
+    }
+
+    fn py_getattr(
+        &self,
+        attr_id: StringId,
+        _heap: &mut Heap<impl ResourceTracker>,
+        _interns: &Interns,
+    ) -> RunResult<Option<AttrCallResult>> {
+        let value = if attr_id == StaticStrings::Typecode {
+            let typecode = u8::try_from(self.typecode).expect("typecodes are ASCII");
+            Value::InternString(StringId::from_ascii(typecode))
+        } else if attr_id == StaticStrings::Itemsize {
+            Value::Int(self.itemsize())
+        } else {
+            return Ok(None);
+        };
+        Ok(Some(AttrCallResult::Value(value)))
+    }
+}
+
+/// Converts `value` to an item of the integer `typecode`.
+///
+/// Accepts ints and bools. Out of range values raise `OverflowError` with CPython's
+/// message for the typecode.
+fn int_item(typecode: char, value: &Value, heap: &Heap<impl ResourceTracker>) -> RunResult<i64> {
+    let unsigned = typecode.is_ascii_uppercase();
+    let i = match value {
+        Value::Int(i) => *i,
+        Value::Bool(b) => i64::from(*b),
+        Value::Ref(id) => {
+            let HeapData::LongInt(li) = heap.get(*id) else {
+                return Err(ExcType::type_error_not_integer(value.py_type(heap)));
+            };
+            // Heap ints never fit in an i64, so they are out of range for every typecode
+            let msg = if unsigned && li.is_negative() {
+                "can't convert negative value to unsigned int"
+            } else if unsigned {
+                "Python int too large to convert to C unsigned long"
+            } else {
+                "Python int too large to convert to C long"
+            };
+            return Err(SimpleException::new_msg(ExcType::OverflowError, msg).into());
+        }
+        _ => return Err(ExcType::type_error_not_integer(value.py_type(heap))),
+    };
+    let (min, max, name) = match typecode {
+        'b' => (i64::from(i8::MIN), i64::from(i8::MAX), "signed char"),
+        'B' => (0, i64::from(u8::MAX), "unsigned byte integer"),
+        'h' => (i64::from(i16::MIN), i64::from(i16::MAX), "signed short integer"),
+        'H' => (0, i64::from(u16::MAX), "unsigned short"),
+        'i' => (i64::from(i32::MIN), i64::from(i32::MAX), "signed integer"),
+        'I' => (0, i64::from(u32::MAX), "unsigned int"),
+        'L' | 'Q' => (0, i64::MAX, "unsigned long"),
+        _ => return Ok(i),
+    };
+    if i < min {
+        let msg = if matches!(typecode, 'I' | 'L' | 'Q') {
+            "can't convert negative value to unsigned int".to_owned()
+        } else {
+            format!("{name} is less than minimum")
+        };
+        Err(SimpleException::new_msg(ExcType::OverflowError, msg).into())
+    } else if i > max {
+        Err(SimpleException::new_msg(ExcType::OverflowError, format!("{name} is greater than maximum")).into())
+    } else {
+        Ok(i)
+    }
+}
+
+/// Converts `value` to an item of the float `typecode`.
+///
+/// Accepts ints, floats, bools and fractions. Items of `'f'` arrays are rounded to single
+/// precision, so reading them back gives what CPython would.
+fn float_item(typecode: char, value: &Value, heap: &Heap<impl ResourceTracker>) -> RunResult<f64> {
+    let f = match value {
+        Value::Float(f) => *f,
+        Value::Int(i) => *i as f64,
+        Value::Bool(b) => f64::from(u8::from(*b)),
+        Value::Ref(id) => match heap.get(*id) {
+            HeapData::LongInt(li) => big_int_to_f64(li.inner())?,
+            HeapData::Fraction(fraction) => fraction.to_f64()?,
+            _ => return Err(float_type_error(value, heap)),
+        },
+        _ => return Err(float_type_error(value, heap)),
+    };
+    if typecode == 'f' {
+        // Rounding to single precision is the point of the 'f' typecode
+        #[expect(clippy::cast_possible_truncation)]
+        let single = f as f32;
+        Ok(f64::from(single))
+    } else {
+        Ok(f)
+    }
+}
+
+/// Creates the `TypeError` for storing a non-number in a float array.
+fn float_type_error(value: &Value, heap: &Heap<impl ResourceTracker>) -> RunError {
+    ExcType::type_error(format!("must be real number, not {}", value.py_type(heap)))
+}
+
+/// Creates the `ValueError` for `array.index()` and `array.remove()` when the value is missing.
+fn not_in_array_error(method: &str) -> RunError {
+    SimpleException::new_msg(ExcType::ValueError, format!("array.{method}(x): x not in array")).into()
+}
+
+/// Copies the items selected by a normalized slice.
+///
+/// `start`, `stop` and `step` come from `Slice::indices()`, so for a negative step `start` is
+/// the highest index and a `stop` past the end means "down to the first item".
+fn slice_items<T: Copy>(items: &[T], start: usize, stop: usize, step: i64) -> Vec<T> {
+    let step_abs = usize::try_from(step.unsigned_abs()).unwrap_or(usize::MAX);
+    if step > 0 {
+        items
+            .get(start..stop.min(items.len()))
+            .map_or_else(Vec::new, |selected| {
+                selected.iter().step_by(step_abs).copied().collect()
+            })
+    } else {
+        let low = if stop > items.len() { 0 } else { stop + 1 };
+        if start >= items.len() || start < low {
+            return Vec::new();
+        }
+        items[low..=start].iter().rev().step_by(step_abs).copied().collect()
+    }
+}
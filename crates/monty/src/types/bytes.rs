@@ -74,7 +74,7 @@ use super::{MontyIter, PyTrait, Type, str::Str};
 use crate::{
     args::ArgValues,
     defer_drop, defer_drop_mut,
-    exception_private::{ExcType, RunResult, SimpleException},
+    exception_private::{ExcType, RunError, RunResult, SimpleException},
     heap::{DropWithHeap, Heap, HeapData, HeapGuard, HeapId},
     intern::{Interns, StaticStrings, StringId},
     resource::{DepthGuard, ResourceError, ResourceTracker},
@@ -512,47 +512,69 @@ fn bytes_decode(
 ) -> RunResult<Value> {
     let (encoding, errors) = args.get_zero_one_two_args("bytes.decode", heap)?;
     defer_drop!(encoding, heap);
-    defer_drop!(errors, heap); // NB we don't use errors argument yet
+    defer_drop!(errors, heap);
 
-    // Check encoding (default UTF-8)
-    let encoding = if let Some(enc) = encoding {
-        get_encoding_str(enc, heap, interns)?.to_ascii_lowercase()
-    } else {
-        "utf-8".to_owned()
+    let encoding = match encoding {
+        Some(enc) => get_codec_str_arg(enc, "decode", "encoding", heap, interns)?,
+        None => "utf-8",
+    };
+    let errors = match errors {
+        Some(errors) => get_codec_str_arg(errors, "decode", "errors", heap, interns)?,
+        None => "strict",
     };
 
-    // Only support UTF-8 family
-    if !matches!(encoding.as_str(), "utf-8" | "utf8" | "utf_8") {
-        return Err(ExcType::lookup_error_unknown_encoding(&encoding));
+    let decoded = decode_bytes(bytes, encoding, errors)?;
+    let heap_id = heap.allocate(HeapData::Str(Str::from(decoded)))?;
+    Ok(Value::Ref(heap_id))
+}
+
+/// Decodes bytes to a string, shared by `bytes.decode()` and `str(bytes, encoding, errors)`.
+///
+/// Only the UTF-8 family of encodings is supported. The `errors` handler may be
+/// `'strict'` (raise `UnicodeDecodeError`), `'replace'` (substitute U+FFFD) or
+/// `'ignore'` (drop invalid bytes). Like CPython, the handler name is only checked
+/// when the data actually contains an invalid sequence.
+pub(crate) fn decode_bytes(bytes: &[u8], encoding: &str, errors: &str) -> RunResult<String> {
+    let normalized = encoding.to_ascii_lowercase();
+    if !matches!(normalized.as_str(), "utf-8" | "utf8" | "utf_8") {
+        return Err(ExcType::lookup_error_unknown_encoding(encoding));
     }
 
-    // Decode as UTF-8
     match std::str::from_utf8(bytes) {
-        Ok(s) => {
-            let heap_id = heap.allocate(HeapData::Str(Str::from(s.to_owned())))?;
-            Ok(Value::Ref(heap_id))
-        }
-        Err(_) => Err(ExcType::unicode_decode_error_invalid_utf8()),
+        Ok(s) => Ok(s.to_owned()),
+        Err(error) => match errors {
+            "strict" => Err(ExcType::unicode_decode_error_invalid_utf8(bytes, &error)),
+            "replace" => Ok(String::from_utf8_lossy(bytes).into_owned()),
+            "ignore" => Ok(bytes.utf8_chunks().map(|chunk| chunk.valid()).collect::<String>()),
+            _ => Err(ExcType::lookup_error_unknown_error_handler(errors)),
+        },
     }
 }
 
-/// Helper function to extract encoding string from a value.
-fn get_encoding_str<'a>(
-    encoding: &Value,
+/// Extracts a `str` argument of a codec function such as `decode()` or `str()`.
+///
+/// Raises `TypeError` matching CPython's format:
+/// `decode() argument 'encoding' must be str, not int`
+pub(crate) fn get_codec_str_arg<'a>(
+    value: &Value,
+    func_name: &str,
+    arg_name: &str,
     heap: &'a Heap<impl ResourceTracker>,
     interns: &'a Interns,
 ) -> RunResult<&'a str> {
-    match encoding {
-        Value::InternString(id) => Ok(interns.get_str(*id)),
-        Value::Ref(id) => match heap.get(*id) {
-            HeapData::Str(s) => Ok(s.as_str()),
-            _ => Err(ExcType::type_error(
-                "decode() argument 'encoding' must be str, not bytes",
-            )),
-        },
-        // FIXME: should use proper encoding.py_type() here
-        _ => Err(ExcType::type_error("decode() argument 'encoding' must be str, not int")),
+    match value {
+        Value::InternString(id) => return Ok(interns.get_str(*id)),
+        Value::Ref(id) => {
+            if let HeapData::Str(s) = heap.get(*id) {
+                return Ok(s.as_str());
+            }
+        }
+        _ => {}
     }
+    Err(ExcType::type_error(format!(
+        "{func_name}() argument '{arg_name}' must be str, not {}",
+        value.py_type(heap)
+    )))
 }
 
 /// Implements Python's `bytes.count(sub[, start[, end]])` method.
@@ -619,6 +641,48 @@ fn bytes_find(
     Ok(Value::Int(idx))
 }
 
+/// Implements `item in bytes`.
+///
+/// An int is looked up as a single byte and must be in `range(0, 256)`; other bytes are
+/// looked up as a subsequence. Anything else is a TypeError, like CPython.
+pub(crate) fn bytes_contains(
+    bytes: &[u8],
+    item: &Value,
+    heap: &Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<bool> {
+    let needle = match item {
+        Value::Int(i) => {
+            return match u8::try_from(*i) {
+                Ok(byte) => Ok(bytes.contains(&byte)),
+                Err(_) => Err(byte_range_error()),
+            };
+        }
+        Value::Bool(b) => return Ok(bytes.contains(&u8::from(*b))),
+        Value::InternBytes(id) => interns.get_bytes(*id),
+        Value::Ref(id) => match heap.get(*id) {
+            HeapData::Bytes(b) => b.as_slice(),
+            HeapData::LongInt(_) => return Err(byte_range_error()),
+            _ => return Err(bytes_like_required(item, heap)),
+        },
+        _ => return Err(bytes_like_required(item, heap)),
+    };
+    Ok(needle.is_empty() || find_subsequence(bytes, needle).is_some())
+}
+
+/// Creates the ValueError for an int that isn't a valid byte.
+fn byte_range_error() -> RunError {
+    SimpleException::new_msg(ExcType::ValueError, "byte must be in range(0, 256)").into()
+}
+
+/// Creates the TypeError for a value used where bytes are needed.
+fn bytes_like_required(value: &Value, heap: &Heap<impl ResourceTracker>) -> RunError {
+    ExcType::type_error(format!(
+        "a bytes-like object is required, not '{}'",
+        value.py_type(heap)
+    ))
+}
+
 /// Finds the first occurrence of needle in haystack.
 fn find_subsequence(haystack: &[u8], needle: &[u8]) -> Option<usize> {
     haystack.windows(needle.len()).position(|window| window == needle)
@@ -0,0 +1,571 @@
+//! Writer objects returned by `csv.writer()` and `csv.DictWriter()`.
+//!
+//! Rows are formatted with CPython's quoting rules and written straight to the target stream,
+//! which is an `io.StringIO` or `sys.stdout`/`sys.stderr`.
+//!
+//! Writer methods are dispatched by the VM through [`call_writer_method`] rather than
+//! `py_call_attr`, since formatting a row may need to read the writer itself (e.g. a row
+//! holding the writer), which isn't possible while its data is taken out of the heap.
+
+use std::fmt::Write;
+
+use ahash::AHashSet;
+
+use crate::{
+    args::ArgValues,
+    defer_drop, defer_drop_mut,
+    exception_private::{ExcType, RunError, RunResult, SimpleException},
+    heap::{DropWithHeap, Heap, HeapData, HeapId},
+    intern::{Interns, StaticStrings, StringId},
+    io::PrintWriter,
+    resource::{DepthGuard, ResourceError, ResourceTracker},
+    types::{AttrCallResult, List, MontyIter, PyTrait, Type},
+    value::{Marker, Value},
+};
+
+/// Quoting styles, numbered like the `csv.QUOTE_*` constants.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub(crate) enum Quoting {
+    Minimal = 0,
+    All = 1,
+    NonNumeric = 2,
+    None = 3,
+    Strings = 4,
+    NotNull = 5,
+}
+
+impl Quoting {
+    /// Returns the quoting style for a `csv.QUOTE_*` value.
+    #[must_use]
+    pub fn from_i64(value: i64) -> Option<Self> {
+        match value {
+            0 => Some(Self::Minimal),
+            1 => Some(Self::All),
+            2 => Some(Self::NonNumeric),
+            3 => Some(Self::None),
+            4 => Some(Self::Strings),
+            5 => Some(Self::NotNull),
+            _ => None,
+        }
+    }
+}
+
+/// Formatting parameters shared by csv readers and writers.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub(crate) struct Dialect {
+    pub delimiter: char,
+    pub quotechar: Option<char>,
+    pub escapechar: Option<char>,
+    pub doublequote: bool,
+    pub skipinitialspace: bool,
+    pub lineterminator: String,
+    pub quoting: Quoting,
+    pub strict: bool,
+}
+
+impl Dialect {
+    /// The `'excel'` dialect, used by default.
+    #[must_use]
+    pub fn excel() -> Self {
+        Self {
+            delimiter: ',',
+            quotechar: Some('"'),
+            escapechar: None,
+            doublequote: true,
+            skipinitialspace: false,
+            lineterminator: "\r\n".to_owned(),
+            quoting: Quoting::Minimal,
+            strict: false,
+        }
+    }
+
+    /// Returns one of the dialects registered by default: `'excel'`, `'excel-tab'` or `'unix'`.
+    #[must_use]
+    pub fn by_name(name: &str) -> Option<Self> {
+        match name {
+            "excel" => Some(Self::excel()),
+            "excel-tab" => Some(Self {
+                delimiter: '\t',
+                ..Self::excel()
+            }),
+            "unix" => Some(Self {
+                lineterminator: "\n".to_owned(),
+                quoting: Quoting::All,
+                ..Self::excel()
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Where a writer's rows go.
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
+pub(crate) enum CsvTarget {
+    Stdout,
+    Stderr,
+    /// An `io.StringIO`, which the writer holds a reference to.
+    StringIO(HeapId),
+}
+
+impl CsvTarget {
+    /// Returns the target for a stream passed to `csv.writer()`, or `None` if it can't be written to.
+    #[must_use]
+    pub fn from_value(value: &Value, heap: &Heap<impl ResourceTracker>) -> Option<Self> {
+        match value {
+            Value::Marker(Marker(StaticStrings::Stdout)) => Some(Self::Stdout),
+            Value::Marker(Marker(StaticStrings::Stderr)) => Some(Self::Stderr),
+            Value::Ref(heap_id) if matches!(heap.get(*heap_id), HeapData::StringIO(_)) => {
+                Some(Self::StringIO(*heap_id))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// The extra state of a `csv.DictWriter`.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub(crate) struct DictFields {
+    /// Keys written in each row, in order.
+    pub fieldnames: Vec<Value>,
+    /// Written for keys missing from a row.
+    pub restval: Value,
+    /// Whether keys not in `fieldnames` raise `ValueError` (`extrasaction='raise'`) rather
+    /// than being ignored.
+    pub raise_on_extra: bool,
+}
+
+/// A csv writer created by `csv.writer()` or, with `dict` set, `csv.DictWriter()`.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub(crate) struct CsvWriter {
+    target: CsvTarget,
+    dialect: Dialect,
+    dict: Option<DictFields>,
+}
+
+impl CsvWriter {
+    /// Creates a writer, taking ownership of the target's reference and the dict fields.
+    #[must_use]
+    pub fn new(target: CsvTarget, dialect: Dialect, dict: Option<DictFields>) -> Self {
+        Self { target, dialect, dict }
+    }
+
+    /// Returns whether this writer holds any heap references.
+    #[must_use]
+    pub fn has_refs(&self) -> bool {
+        matches!(self.target, CsvTarget::StringIO(_)) || self.dict.is_some()
+    }
+
+    /// Pushes the ids of the heap values this writer refers to, for GC traversal.
+    pub fn collect_child_ids(&self, work_list: &mut Vec<HeapId>) {
+        if let CsvTarget::StringIO(heap_id) = self.target {
+            work_list.push(heap_id);
+        }
+        if let Some(dict) = &self.dict {
+            for value in dict.fieldnames.iter().chain([&dict.restval]) {
+                if let Value::Ref(id) = value {
+                    work_list.push(*id);
+                }
+            }
+        }
+    }
+
+    /// Copies the writer stored at `heap_id`, taking new references to the values it holds.
+    fn snapshot(heap_id: HeapId, heap: &mut Heap<impl ResourceTracker>) -> Self {
+        let HeapData::CsvWriter(writer) = heap.get(heap_id) else {
+            unreachable!("snapshot called on a value which isn't a csv writer")
+        };
+        let copy = Self {
+            target: writer.target,
+            dialect: writer.dialect.clone(),
+            dict: writer.dict.as_ref().map(|dict| DictFields {
+                fieldnames: dict.fieldnames.iter().map(Value::copy_for_extend).collect(),
+                restval: dict.restval.copy_for_extend(),
+                raise_on_extra: dict.raise_on_extra,
+            }),
+        };
+        if let CsvTarget::StringIO(target_id) = copy.target {
+            heap.inc_ref(target_id);
+        }
+        if let Some(dict) = &copy.dict {
+            for value in dict.fieldnames.iter().chain([&dict.restval]) {
+                if let Value::Ref(id) = value {
+                    heap.inc_ref(*id);
+                }
+            }
+        }
+        copy
+    }
+
+    /// Formats and writes one row, returning the number of characters written.
+    fn write_row(
+        &self,
+        row: Value,
+        heap: &mut Heap<impl ResourceTracker>,
+        interns: &Interns,
+        print_writer: &mut PrintWriter<'_>,
+    ) -> RunResult<usize> {
+        let fields = match &self.dict {
+            Some(dict) => dict.row_fields(row, heap, interns)?,
+            None => row_fields(row, heap, interns)?,
+        };
+        defer_drop!(fields, heap);
+        let line = self.join_fields(fields, heap, interns)?;
+        self.write_line(line, heap, print_writer)
+    }
+
+    /// Joins the fields of a row into a line, following CPython's `csv` writer.
+    fn join_fields(&self, fields: &[Value], heap: &Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<String> {
+        let dialect = &self.dialect;
+        let mut line = String::new();
+        for (index, field) in fields.iter().enumerate() {
+            let quoted = match dialect.quoting {
+                Quoting::All => true,
+                Quoting::NonNumeric => !is_number(field, heap),
+                Quoting::Strings => field.is_str(heap),
+                Quoting::NotNull => !matches!(field, Value::None),
+                Quoting::Minimal | Quoting::None => false,
+            };
+            if index > 0 {
+                line.push(dialect.delimiter);
+            }
+            let null_field = matches!(field, Value::None);
+            let text = if null_field {
+                String::new()
+            } else {
+                field.py_str(heap, &mut DepthGuard::default(), interns).into_owned()
+            };
+            self.append_field(&mut line, &text, null_field, quoted)?;
+        }
+
+        // A lone empty field is quoted so the row doesn't read back as an empty line
+        if let [field] = fields
+            && line.is_empty()
+        {
+            let null_field = matches!(field, Value::None);
+            if dialect.quoting == Quoting::None
+                || (null_field && matches!(dialect.quoting, Quoting::Strings | Quoting::NotNull))
+            {
+                return Err(csv_error("single empty field record must be quoted"));
+            }
+            self.append_field(&mut line, "", null_field, true)?;
+        }
+        line.push_str(&dialect.lineterminator);
+        Ok(line)
+    }
+
+    /// Appends one field to `line`, escaping or quoting special characters as needed.
+    ///
+    /// `null_field` is set for `None`, which is written as an empty field.
+    fn append_field(&self, line: &mut String, text: &str, null_field: bool, mut quoted: bool) -> RunResult<()> {
+        let dialect = &self.dialect;
+        // An unquoted empty field would be skipped as initial space when read back
+        if text.is_empty() && dialect.delimiter == ' ' && dialect.skipinitialspace {
+            if dialect.quoting == Quoting::None
+                || (null_field && matches!(dialect.quoting, Quoting::Strings | Quoting::NotNull))
+            {
+                return Err(csv_error(
+                    "empty field must be quoted if delimiter is a space and skipinitialspace is true",
+                ));
+            }
+            quoted = true;
+        }
+        let mut body = String::with_capacity(text.len());
+        for c in text.chars() {
+            let special = c == dialect.delimiter
+                || Some(c) == dialect.escapechar
+                || Some(c) == dialect.quotechar
+                || c == '\n'
+                || c == '\r'
+                || dialect.lineterminator.contains(c);
+            if special {
+                let mut want_escape = false;
+                if dialect.quoting == Quoting::None {
+                    want_escape = true;
+                } else {
+                    if Some(c) == dialect.quotechar {
+                        if dialect.doublequote {
+                            body.push(c);
+                        } else {
+                            want_escape = true;
+                        }
+                    } else if Some(c) == dialect.escapechar {
+                        want_escape = true;
+                    }
+                    if !want_escape {
+                        quoted = true;
+                    }
+                }
+                if want_escape {
+                    let Some(escapechar) = dialect.escapechar else {
+                        return Err(csv_error("need to escape, but no escapechar set"));
+                    };
+                    body.push(escapechar);
+                }
+            }
+            body.push(c);
+        }
+
+        match dialect.quotechar {
+            Some(quotechar) if quoted => {
+                line.push(quotechar);
+                line.push_str(&body);
+                line.push(quotechar);
+            }
+            _ => line.push_str(&body),
+        }
+        Ok(())
+    }
+
+    /// Writes a formatted line to the target, returning the number of characters written.
+    fn write_line(
+        &self,
+        line: String,
+        heap: &mut Heap<impl ResourceTracker>,
+        print_writer: &mut PrintWriter<'_>,
+    ) -> RunResult<usize> {
+        let count = line.chars().count();
+        match self.target {
+            CsvTarget::Stdout => print_writer.stdout_write(line.into())?,
+            CsvTarget::Stderr => print_writer.stderr_write(line.into())?,
+            CsvTarget::StringIO(heap_id) => {
+                heap.with_entry_mut(heap_id, |heap, data| {
+                    let HeapData::StringIO(string_io) = data else {
+                        unreachable!("csv writer target isn't a StringIO")
+                    };
+                    string_io.write_str(&line, heap.tracker())
+                })?;
+            }
+        }
+        Ok(count)
+    }
+}
+
+impl DictFields {
+    /// Looks up the fields of a dict row in `fieldnames` order, as `DictWriter` does.
+    fn row_fields(
+        &self,
+        row: Value,
+        heap: &mut Heap<impl ResourceTracker>,
+        interns: &Interns,
+    ) -> RunResult<Vec<Value>> {
+        defer_drop!(row, heap);
+        let Value::Ref(dict_id) = row else {
+            return Err(self.not_a_dict_error(row, heap));
+        };
+        if !matches!(heap.get(*dict_id), HeapData::Dict(_)) {
+            return Err(self.not_a_dict_error(row, heap));
+        }
+
+        heap.with_entry_mut(*dict_id, |heap, data| {
+            let HeapData::Dict(dict) = data else {
+                unreachable!("checked above")
+            };
+            if self.raise_on_extra {
+                let mut extra = Vec::new();
+                for (key, _) in dict.iter() {
+                    if !self.is_fieldname(key, heap, interns)? {
+                        extra.push(key.py_repr(heap, &mut DepthGuard::default(), interns).into_owned());
+                    }
+                }
+                if !extra.is_empty() {
+                    return Err(SimpleException::new_msg(
+                        ExcType::ValueError,
+                        format!("dict contains fields not in fieldnames: {}", extra.join(", ")),
+                    )
+                    .into());
+                }
+            }
+
+            let mut fields = Vec::with_capacity(self.fieldnames.len());
+            for key in &self.fieldnames {
+                let value = match dict.get(key, heap, interns) {
+                    Ok(value) => value.unwrap_or(&self.restval).copy_for_extend(),
+                    Err(err) => {
+                        fields.drop_with_heap(heap);
+                        return Err(err);
+                    }
+                };
+                if let Value::Ref(id) = &value {
+                    heap.inc_ref(*id);
+                }
+                fields.push(value);
+            }
+            Ok(fields)
+        })
+    }
+
+    /// Returns whether `key` equals one of the field names.
+    fn is_fieldname(&self, key: &Value, heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<bool> {
+        for name in &self.fieldnames {
+            if key.py_eq(name, heap, &mut DepthGuard::default(), interns)? {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Returns the `AttributeError` CPython raises when a row isn't a dict.
+    fn not_a_dict_error(&self, row: &Value, heap: &Heap<impl ResourceTracker>) -> RunError {
+        let method = if self.raise_on_extra { "keys" } else { "get" };
+        ExcType::attribute_error(row.py_type(heap), method)
+    }
+}
+
+impl DropWithHeap for CsvWriter {
+    fn drop_with_heap<T: ResourceTracker>(self, heap: &mut Heap<T>) {
+        if let CsvTarget::StringIO(heap_id) = self.target {
+            heap.dec_ref(heap_id);
+        }
+        if let Some(dict) = self.dict {
+            dict.fieldnames.drop_with_heap(heap);
+            dict.restval.drop_with_heap(heap);
+        }
+    }
+}
+
+/// Collects the fields of a plain writer's row.
+fn row_fields(row: Value, heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<Vec<Value>> {
+    let row_type = row.py_type(heap);
+    let Ok(iter) = MontyIter::new(row, heap, interns) else {
+        return Err(csv_error(format!("iterable expected, not {row_type}")));
+    };
+    iter.collect(heap, interns)
+}
+
+/// Returns whether a field is a number, which `QUOTE_NONNUMERIC` leaves unquoted.
+fn is_number(value: &Value, heap: &Heap<impl ResourceTracker>) -> bool {
+    matches!(value.py_type(heap), Type::Int | Type::Float | Type::Bool)
+}
+
+/// Creates a `csv.Error` with the given message.
+pub(crate) fn csv_error(msg: impl Into<String>) -> RunError {
+    SimpleException::new_msg(ExcType::CsvError, msg.into()).into()
+}
+
+/// Calls a method of the csv writer at `heap_id`.
+///
+/// # Errors
+/// Returns `csv.Error` for rows which can't be written, and `AttributeError` for unknown methods.
+pub(crate) fn call_writer_method(
+    heap_id: HeapId,
+    name_id: StringId,
+    args: ArgValues,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+    print_writer: &mut PrintWriter<'_>,
+) -> RunResult<Value> {
+    let writer = CsvWriter::snapshot(heap_id, heap);
+    defer_drop!(writer, heap);
+    let writer_type = if writer.dict.is_some() {
+        Type::CsvDictWriter
+    } else {
+        Type::CsvWriter
+    };
+
+    match StaticStrings::from_string_id(name_id) {
+        Some(StaticStrings::Writerow) => {
+            let row = args.get_one_arg("writerow", heap)?;
+            let count = writer.write_row(row, heap, interns, print_writer)?;
+            Ok(Value::Int(i64::try_from(count).unwrap_or(i64::MAX)))
+        }
+        Some(StaticStrings::Writerows) => {
+            let rows = args.get_one_arg("writerows", heap)?;
+            let rows = MontyIter::new(rows, heap, interns)?;
+            defer_drop_mut!(rows, heap);
+            while let Some(row) = rows.for_next(heap, interns)? {
+                writer.write_row(row, heap, interns, print_writer)?;
+            }
+            Ok(Value::None)
+        }
+        Some(StaticStrings::Writeheader) if writer.dict.is_some() => {
+            args.check_zero_args("writeheader", heap)?;
+            let Some(dict) = &writer.dict else {
+                unreachable!("checked by the match guard")
+            };
+            let line = writer.join_fields(&dict.fieldnames, heap, interns)?;
+            let count = writer.write_line(line, heap, print_writer)?;
+            Ok(Value::Int(i64::try_from(count).unwrap_or(i64::MAX)))
+        }
+        _ => {
+            args.drop_with_heap(heap);
+            Err(ExcType::attribute_error(writer_type, interns.get_str(name_id)))
+        }
+    }
+}
+
+impl PyTrait for CsvWriter {
+    fn py_type(&self, _heap: &Heap<impl ResourceTracker>) -> Type {
+        if self.dict.is_some() {
+            Type::CsvDictWriter
+        } else {
+            Type::CsvWriter
+        }
+    }
+
+    fn py_estimate_size(&self) -> usize {
+        std::mem::size_of::<Self>()
+            + self.dialect.lineterminator.len()
+            + self
+                .dict
+                .as_ref()
+                .map_or(0, |dict| dict.fieldnames.len() * std::mem::size_of::<Value>())
+    }
+
+    fn py_len(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> Option<usize> {
+        None
+    }
+
+    fn py_eq(
+        &self,
+        _other: &Self,
+        _heap: &mut Heap<impl ResourceTracker>,
+        _guard: &mut DepthGuard,
+        _interns: &Interns,
+    ) -> Result<bool, ResourceError> {
+        // Writers compare by identity, which is handled at the Value level
+        Ok(false)
+    }
+
+    fn py_bool(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> bool {
+        true
+    }
+
+    fn py_repr_fmt(
+        &self,
+        f: &mut impl Write,
+        heap: &Heap<impl ResourceTracker>,
+        _heap_ids: &mut AHashSet<HeapId>,
+        _guard: &mut DepthGuard,
+        _interns: &Interns,
+    ) -> std::fmt::Result {
+        write!(f, "<{} object>", self.py_type(heap))
+    }
+
+    fn py_dec_ref_ids(&mut self, stack: &mut Vec<HeapId>) {
+        if let CsvTarget::StringIO(heap_id) = self.target {
+            stack.push(heap_id);
+        }
+        if let Some(dict) = &mut self.dict {
+            for value in &mut dict.fieldnames {
+                value.py_dec_ref_ids(stack);
+            }
+            dict.restval.py_dec_ref_ids(stack);
+        }
+    }
+
+    fn py_getattr(
+        &self,
+        attr_id: StringId,
+        heap: &mut Heap<impl ResourceTracker>,
+        _interns: &Interns,
+    ) -> RunResult<Option<AttrCallResult>> {
+        match &self.dict {
+            Some(dict) if attr_id == StaticStrings::Fieldnames => {
+                let fieldnames = dict.fieldnames.iter().map(|name| name.clone_with_heap(heap)).collect();
+                let list_id = heap.allocate(HeapData::List(List::new(fieldnames)))?;
+                Ok(Some(AttrCallResult::Value(Value::Ref(list_id))))
+            }
+            _ => Ok(None),
+        }
+    }
+}
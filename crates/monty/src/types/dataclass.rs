@@ -150,17 +150,16 @@ impl Dataclass {
     /// Returns Some(hash) for frozen (immutable) dataclasses, None for mutable ones.
     /// The hash is computed from the class name and declared field values only.
     pub fn compute_hash(&self, heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> Option<u64> {
-        use std::{
-            collections::hash_map::DefaultHasher,
-            hash::{Hash, Hasher},
-        };
+        use std::hash::{Hash, Hasher};
+
+        use crate::hashing::StableHasher;
 
         // Only frozen (immutable) dataclasses are hashable
         if !self.frozen {
             return None;
         }
 
-        let mut hasher = DefaultHasher::new();
+        let mut hasher = StableHasher::default();
         // Hash the class name
         self.name.hash(&mut hasher);
         // Hash each declared field (name, value) pair in order
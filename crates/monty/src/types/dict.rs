@@ -1,8 +1,4 @@
-use std::{
-    collections::hash_map::DefaultHasher,
-    fmt::Write,
-    hash::{Hash, Hasher},
-};
+use std::fmt::Write;
 
 use ahash::AHashSet;
 use hashbrown::{HashTable, hash_table::Entry};
@@ -12,11 +8,12 @@ use super::{List, MontyIter, PyTrait, allocate_tuple};
 use crate::{
     args::{ArgValues, KwargsValues},
     defer_drop, defer_drop_mut,
-    exception_private::{ExcType, RunResult},
+    exception_private::{ExcType, RunError, RunResult, SimpleException},
+    hashing::apply_seed,
     heap::{DropWithHeap, Heap, HeapData, HeapGuard, HeapId},
     intern::{Interns, StaticStrings},
     resource::{DepthGuard, ResourceError, ResourceTracker},
-    types::Type,
+    types::{Type, str::hash_str},
     value::{EitherStr, Value},
 };
 
@@ -44,7 +41,9 @@ use crate::{
 /// Uses a `HashTable<usize>` for hash lookups combined with a dense `Vec<DictEntry>`
 /// to preserve insertion order (matching Python 3.7+ behavior). The hash table maps
 /// key hashes to indices in the entries vector. This design provides O(1) lookups
-/// while maintaining insertion order for iteration.
+/// while maintaining insertion order for iteration. Removing an entry shifts later
+/// entries down, so `pop()` rewrites the affected indices; a removed key that is
+/// re-inserted therefore moves to the end, as in CPython.
 ///
 /// # Reference Counting
 /// When values are added via `set()`, their reference counts are incremented.
@@ -66,6 +65,9 @@ pub(crate) struct Dict {
     /// in `collect_child_ids` and `py_dec_ref_ids` when no refs are present.
     /// Only transitions from false to true (never back) since tracking removals would be O(n).
     contains_refs: bool,
+    /// True for dicts returned by `globals()`, whose item assignments the VM writes back
+    /// to the module namespace. Copies of the dict are ordinary dicts.
+    globals_view: bool,
 }
 
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
@@ -88,6 +90,7 @@ impl Dict {
             indices: HashTable::with_capacity(capacity),
             entries: Vec::with_capacity(capacity),
             contains_refs: false,
+            globals_view: false,
         }
     }
 
@@ -107,6 +110,18 @@ impl Dict {
         self.contains_refs
     }
 
+    /// Marks this dict as the result of `globals()`, see [`Self::is_globals_view`].
+    pub fn mark_globals_view(&mut self) {
+        self.globals_view = true;
+    }
+
+    /// Returns whether item assignments on this dict are written back to the module namespace.
+    #[inline]
+    #[must_use]
+    pub fn is_globals_view(&self) -> bool {
+        self.globals_view
+    }
+
     /// Creates a dict from a vector of (key, value) pairs.
     ///
     /// Assumes the caller is transferring ownership of all keys and values in the pairs.
@@ -152,10 +167,7 @@ impl Dict {
     /// This is an O(1) lookup that doesn't require mutable heap access.
     /// Only works for string keys - returns None if the key is not found.
     pub fn get_by_str(&self, key_str: &str, heap: &Heap<impl ResourceTracker>, interns: &Interns) -> Option<&Value> {
-        // Compute hash for the string key
-        let mut hasher = DefaultHasher::new();
-        key_str.hash(&mut hasher);
-        let hash = hasher.finish();
+        let hash = apply_seed(hash_str(key_str), heap.hash_seed());
 
         // Find entry with matching hash and key
         self.indices
@@ -240,9 +252,9 @@ impl Dict {
         heap: &mut Heap<impl ResourceTracker>,
         interns: &Interns,
     ) -> RunResult<Option<(Value, Value)>> {
-        let hash = key
-            .py_hash(heap, interns)
-            .ok_or_else(|| ExcType::type_error_unhashable_dict_key(key.py_type(heap)))?;
+        let hash = key.py_hash(heap, interns).ok_or_else(|| {
+            ExcType::type_error_unhashable_dict_key(key.py_type(heap), key.unhashable_type(heap, interns))
+        })?;
 
         // Create a guard for key equality comparisons.
         let mut guard = DepthGuard::default();
@@ -256,8 +268,17 @@ impl Dict {
         );
 
         if let Entry::Occupied(occ_entry) = entry {
-            let entry = self.entries.remove(*occ_entry.get());
-            occ_entry.remove();
+            let (removed_index, _) = occ_entry.remove();
+            let entry = self.entries.remove(removed_index);
+            // Removing from the dense vec shifts every later entry down by one, so the
+            // stored indices must follow to keep lookups and insertion order consistent.
+            if removed_index < self.entries.len() {
+                for index in self.indices.iter_mut() {
+                    if *index > removed_index {
+                        *index -= 1;
+                    }
+                }
+            }
             // Don't decrement refcounts - caller now owns the values
             Ok(Some((entry.key, entry.value)))
         } else {
@@ -327,48 +348,16 @@ impl Dict {
     ///
     /// - `dict()` with no args returns an empty dict
     /// - `dict(dict)` returns a shallow copy of the dict
-    ///
-    /// Note: Full Python semantics also support dict(iterable) where iterable
-    /// yields (key, value) pairs, and dict(**kwargs) for keyword arguments.
+    /// - `dict(iterable)` takes (key, value) pairs from any iterable, e.g. `zip()` or a generator
+    /// - keyword arguments are added after the positional argument, like `dict.update()`
     pub fn init(heap: &mut Heap<impl ResourceTracker>, args: ArgValues, interns: &Interns) -> RunResult<Value> {
-        let value = args.get_zero_one_arg("dict", heap)?;
-        match value {
-            None => {
-                let heap_id = heap.allocate(HeapData::Dict(Self::new()))?;
-                Ok(Value::Ref(heap_id))
-            }
-            Some(v) => {
-                defer_drop!(v, heap);
-                let Value::Ref(id) = v else {
-                    return Err(ExcType::type_error_not_iterable(v.py_type(heap)));
-                };
-
-                // Check if it's a dict and get key-value pairs
-                let HeapData::Dict(dict) = heap.get(*id) else {
-                    return Err(ExcType::type_error_not_iterable(v.py_type(heap)));
-                };
-
-                // Copy all key-value pairs first (without incrementing refcounts)
-                let pairs: Vec<(Value, Value)> = dict
-                    .iter()
-                    .map(|(k, v)| (k.copy_for_extend(), v.copy_for_extend()))
-                    .collect();
-
-                // Now we can drop the borrow and increment refcounts
-                for (k, v) in &pairs {
-                    if let Value::Ref(key_id) = k {
-                        heap.inc_ref(*key_id);
-                    }
-                    if let Value::Ref(val_id) = v {
-                        heap.inc_ref(*val_id);
-                    }
-                }
-
-                let new_dict = Self::from_pairs(pairs, heap, interns)?;
-                let result = heap.allocate(HeapData::Dict(new_dict))?;
-                Ok(Value::Ref(result))
-            }
+        let mut dict = Self::new();
+        if let Err(err) = dict_update(&mut dict, args, "dict", heap, interns) {
+            dict.drop_with_heap(heap);
+            return Err(err);
         }
+        let heap_id = heap.allocate(HeapData::Dict(dict))?;
+        Ok(Value::Ref(heap_id))
     }
 
     fn find_index_hash(
@@ -377,9 +366,9 @@ impl Dict {
         heap: &mut Heap<impl ResourceTracker>,
         interns: &Interns,
     ) -> RunResult<(Option<usize>, u64)> {
-        let hash = key
-            .py_hash(heap, interns)
-            .ok_or_else(|| ExcType::type_error_unhashable_dict_key(key.py_type(heap)))?;
+        let hash = key.py_hash(heap, interns).ok_or_else(|| {
+            ExcType::type_error_unhashable_dict_key(key.py_type(heap), key.unhashable_type(heap, interns))
+        })?;
 
         // Create a guard for key equality comparisons. Dict keys are typically
         // shallow (strings, ints, tuples of primitives), so recursion errors
@@ -389,8 +378,14 @@ impl Dict {
         let opt_index = self
             .indices
             .find(hash, |v| {
-                key.py_eq(&self.entries[*v].key, heap, &mut guard, interns)
-                    .unwrap_or(false)
+                let entry_key = &self.entries[*v].key;
+                // Interned strings are deduplicated, so comparing ids is enough and avoids
+                // the general equality dispatch for attribute-like string keys
+                if let (Value::InternString(a), Value::InternString(b)) = (key, entry_key) {
+                    return a == b;
+                }
+                // Like CPython, the key itself matches without calling `==` (e.g. a NaN)
+                key.is(entry_key) || key.py_eq(entry_key, heap, &mut guard, interns).unwrap_or(false)
             })
             .copied();
         Ok((opt_index, hash))
@@ -652,7 +647,7 @@ impl PyTrait for Dict {
                 args.check_zero_args("dict.copy", heap)?;
                 dict_copy(self, heap, interns)
             }
-            StaticStrings::Update => dict_update(self, args, heap, interns),
+            StaticStrings::Update => dict_update(self, args, "update", heap, interns),
             StaticStrings::Setdefault => dict_setdefault(self, args, heap, interns),
             StaticStrings::Popitem => {
                 args.check_zero_args("dict.popitem", heap)?;
@@ -704,15 +699,18 @@ fn dict_copy(dict: &Dict, heap: &mut Heap<impl ResourceTracker>, interns: &Inter
     Ok(Value::Ref(heap_id))
 }
 
-/// Implements Python's `dict.update([other], **kwargs)` method.
+/// Implements Python's `dict.update([other], **kwargs)` method, also used by the `dict()` constructor.
 ///
 /// Updates the dict with key-value pairs from `other` and/or `kwargs`.
 /// If `other` is a dict, copies its key-value pairs.
-/// If `other` is an iterable, expects pairs of (key, value).
-/// Keyword arguments are also added to the dict.
+/// If `other` is an iterable, expects pairs of (key, value), each of which may be any iterable
+/// of length 2. Keyword arguments are also added to the dict.
+///
+/// `name` is the function named in argument count errors, `update` or `dict`.
 fn dict_update(
     dict: &mut Dict,
     args: ArgValues,
+    name: &str,
     heap: &mut Heap<impl ResourceTracker>,
     interns: &Interns,
 ) -> RunResult<Value> {
@@ -730,7 +728,7 @@ fn dict_update(
 
     // Check no extra positional arguments
     if pos_iter.len() != 0 {
-        return Err(ExcType::type_error_at_most("dict.update", 1, 2));
+        return Err(ExcType::type_error_at_most(name, 1, pos_iter.len() + 1));
     }
 
     // Check if it's a dict first
@@ -775,38 +773,37 @@ fn dict_update(
     let mut iter_guard = HeapGuard::new(iter, heap);
     let (iter, heap) = iter_guard.as_parts_mut();
 
+    let mut index = 0;
     while let Some(item) = iter.for_next(heap, interns)? {
-        // Each item should be a pair (iterable of 2 elements)
-        let pair_iter = MontyIter::new(item, heap, interns)?;
-        defer_drop_mut!(pair_iter, heap);
-
-        let Some(key) = pair_iter.for_next(heap, interns)? else {
-            return Err(ExcType::type_error(
-                "dictionary update sequence element has length 0; 2 is required",
-            ));
-        };
-        let mut key_guard = HeapGuard::new(key, heap);
-
-        let Some(value) = pair_iter.for_next(key_guard.heap(), interns)? else {
-            return Err(ExcType::type_error(
-                "dictionary update sequence element has length 1; 2 is required",
-            ));
+        // Each item should be a pair (iterable of 2 elements), collected first like CPython
+        // so the error can report its length
+        let pair: Vec<Value> = match MontyIter::new(item, heap, interns) {
+            Ok(pair_iter) => pair_iter.collect(heap, interns)?,
+            Err(RunError::Exc(exc)) if exc.exc.exc_type() == ExcType::TypeError => {
+                return Err(ExcType::type_error(format!(
+                    "cannot convert dictionary update sequence element #{index} to a sequence"
+                )));
+            }
+            Err(err) => return Err(err),
         };
-        let mut value_guard = HeapGuard::new(value, key_guard.heap());
-
-        if let Some(extra) = pair_iter.for_next(value_guard.heap(), interns)? {
-            extra.drop_with_heap(value_guard.heap());
-            return Err(ExcType::type_error(
-                "dictionary update sequence element has length > 2; 2 is required",
-            ));
+        if pair.len() != 2 {
+            let len = pair.len();
+            pair.drop_with_heap(heap);
+            return Err(SimpleException::new_msg(
+                ExcType::ValueError,
+                format!("dictionary update sequence element #{index} has length {len}; 2 is required"),
+            )
+            .into());
         }
-
-        let value = value_guard.into_inner();
-        let key = key_guard.into_inner();
+        let mut pair = pair.into_iter();
+        let (Some(key), Some(value)) = (pair.next(), pair.next()) else {
+            unreachable!("pair has length 2")
+        };
 
         if let Some(old_value) = dict.set(key, value, heap, interns)? {
             old_value.drop_with_heap(heap);
         }
+        index += 1;
     }
 
     // Process kwargs after the iterable update
@@ -885,14 +882,10 @@ fn dict_popitem(dict: &mut Dict, heap: &mut Heap<impl ResourceTracker>) -> RunRe
     // Remove the last entry (LIFO order)
     let entry = dict.entries.pop().expect("dict is not empty");
 
-    // Remove from indices - need to find the entry with this index
-    // Since we removed the last entry, we need to clear and rebuild indices
-    // (This is simpler than trying to find and remove the specific hash entry)
-    // TODO: This O(n) rebuild could be optimized by finding and removing the
-    // specific hash entry directly from the hashbrown table.
-    dict.indices.clear();
-    for (idx, e) in dict.entries.iter().enumerate() {
-        dict.indices.insert_unique(e.hash, idx, |&i| dict.entries[i].hash);
+    // The last entry has the highest index, so no other indices need adjusting
+    let last_index = dict.entries.len();
+    if let Ok(occ_entry) = dict.indices.find_entry(entry.hash, |&i| i == last_index) {
+        occ_entry.remove();
     }
 
     // Create tuple (key, value)
@@ -904,9 +897,10 @@ fn dict_popitem(dict: &mut Dict, heap: &mut Heap<impl ResourceTracker>) -> RunRe
 impl serde::Serialize for Dict {
     fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
         use serde::ser::SerializeStruct;
-        let mut state = serializer.serialize_struct("Dict", 2)?;
+        let mut state = serializer.serialize_struct("Dict", 3)?;
         state.serialize_field("entries", &self.entries)?;
         state.serialize_field("contains_refs", &self.contains_refs)?;
+        state.serialize_field("globals_view", &self.globals_view)?;
         state.end()
     }
 }
@@ -917,6 +911,8 @@ impl<'de> serde::Deserialize<'de> for Dict {
         struct DictFields {
             entries: Vec<DictEntry>,
             contains_refs: bool,
+            #[serde(default)]
+            globals_view: bool,
         }
         let fields = DictFields::deserialize(deserializer)?;
         // Rebuild the indices hash table from the entries
@@ -928,6 +924,7 @@ impl<'de> serde::Deserialize<'de> for Dict {
             indices,
             entries: fields.entries,
             contains_refs: fields.contains_refs,
+            globals_view: fields.globals_view,
         })
     }
 }
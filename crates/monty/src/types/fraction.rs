@@ -0,0 +1,726 @@
+//! Python `fractions.Fraction`, an exact rational number.
+//!
+//! A `Fraction` holds an arbitrary precision numerator and denominator, always in lowest terms
+//! with a positive denominator, so equal fractions have equal fields. Arithmetic with ints and
+//! fractions is exact and returns a `Fraction` (an int for `//`), while arithmetic with floats
+//! converts the fraction to a float first, as in CPython.
+//!
+//! Ints and floats know nothing about fractions, so callers of the numeric operators fall back
+//! to [`fraction_binary_op`] when an operator isn't supported, much like CPython's reflected
+//! operators. Equality, ordering and hashing are handled by `Value` itself so that sorting,
+//! `min()`, `max()` and dict keys treat fractions as numbers.
+
+use std::{
+    cmp::Ordering,
+    fmt::{self, Display, Write},
+    ops::Neg,
+};
+
+use ahash::AHashSet;
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{One, Signed, ToPrimitive, Zero};
+use smallvec::smallvec;
+
+use crate::{
+    args::ArgValues,
+    defer_drop,
+    exception_private::{ExcType, RunError, RunResult, SimpleException},
+    heap::{DropWithHeap, Heap, HeapData, HeapId},
+    intern::{Interns, StaticStrings, StringId},
+    resource::{DepthGuard, ResourceError, ResourceTracker, check_mult_size, check_pow_size},
+    types::{AttrCallResult, LongInt, PyTrait, Type, allocate_tuple, long_int::ratio_to_f64, str::StringRepr},
+    value::{EitherStr, NUMERIC_HASH_INF, NUMERIC_HASH_MODULUS, Value, bigint_pow, signed_numeric_hash},
+};
+
+/// A rational number created by `fractions.Fraction()`.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub(crate) struct Fraction {
+    numerator: BigInt,
+    /// Always positive and coprime with `numerator`.
+    denominator: BigInt,
+}
+
+/// The arithmetic operators fractions support, see [`fraction_binary_op`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum FractionOp {
+    Add,
+    Sub,
+    Mult,
+    Div,
+    FloorDiv,
+    Mod,
+    Pow,
+}
+
+/// An operand of a fraction operator: ints, bools and fractions are rational, floats aren't.
+enum Number {
+    Rational(Fraction),
+    Float(f64),
+}
+
+impl Fraction {
+    /// Creates the fraction `numerator / denominator` in lowest terms.
+    ///
+    /// # Panics
+    /// Panics if `denominator` is zero; callers raise `ZeroDivisionError` first.
+    pub fn new(numerator: BigInt, denominator: BigInt) -> Self {
+        assert!(!denominator.is_zero(), "Fraction created with zero denominator");
+        let gcd = numerator.gcd(&denominator);
+        let (numerator, denominator) = (numerator / &gcd, denominator / &gcd);
+        if denominator.is_negative() {
+            Self {
+                numerator: -numerator,
+                denominator: -denominator,
+            }
+        } else {
+            Self { numerator, denominator }
+        }
+    }
+
+    /// Converts a float to the fraction with exactly the same value.
+    ///
+    /// # Errors
+    /// Returns `ValueError` for NaN and `OverflowError` for infinities, as CPython does.
+    pub fn from_f64(value: f64) -> RunResult<Self> {
+        if value.is_nan() {
+            return Err(SimpleException::new_msg(ExcType::ValueError, "cannot convert NaN to integer ratio").into());
+        }
+        if value.is_infinite() {
+            return Err(
+                SimpleException::new_msg(ExcType::OverflowError, "cannot convert Infinity to integer ratio").into(),
+            );
+        }
+        let bits = value.to_bits();
+        let raw_exponent = i64::try_from((bits >> 52) & 0x7ff).expect("11-bit exponent fits in i64");
+        let fraction = bits & ((1 << 52) - 1);
+        let (mantissa, exponent) = if raw_exponent == 0 {
+            (fraction, -1074)
+        } else {
+            (fraction | (1 << 52), raw_exponent - 1075)
+        };
+        let mut mantissa = BigInt::from(mantissa);
+        if value.is_sign_negative() {
+            mantissa = -mantissa;
+        }
+        let shift = usize::try_from(exponent.unsigned_abs()).expect("float exponent fits in usize");
+        if exponent >= 0 {
+            Ok(Self::from(mantissa << shift))
+        } else {
+            Ok(Self::new(mantissa, BigInt::one() << shift))
+        }
+    }
+
+    /// Implements `fractions.Fraction(numerator=0, denominator=None)`.
+    ///
+    /// A single argument may be an int, a fraction, a float or a string such as `'3/4'`,
+    /// `'-1.5'` or `'2e-3'`. Two arguments must both be ints or fractions. Arguments can't
+    /// be passed by keyword.
+    pub fn init(heap: &mut Heap<impl ResourceTracker>, args: ArgValues, interns: &Interns) -> RunResult<Value> {
+        let (numerator, denominator) = args.get_zero_one_two_args("Fraction", heap)?;
+        defer_drop!(numerator, heap);
+        defer_drop!(denominator, heap);
+
+        let fraction = match (numerator, denominator) {
+            (None, _) => Self::from(BigInt::zero()),
+            (Some(value), None) => match number(value, heap) {
+                Some(Number::Rational(fraction)) => fraction,
+                Some(Number::Float(f)) => Self::from_f64(f)?,
+                None => {
+                    let Some(s) = value.as_either_str(heap) else {
+                        return Err(ExcType::type_error(
+                            "argument should be a string or a Rational instance",
+                        ));
+                    };
+                    parse_fraction(s.as_str(interns), heap)?
+                }
+            },
+            (Some(numerator), Some(denominator)) => {
+                let (Some(Number::Rational(a)), Some(Number::Rational(b))) =
+                    (number(numerator, heap), number(denominator, heap))
+                else {
+                    return Err(ExcType::type_error("both arguments should be Rational instances"));
+                };
+                let numerator = a.numerator * &b.denominator;
+                let denominator = b.numerator * a.denominator;
+                if denominator.is_zero() {
+                    return Err(zero_denominator_error(&numerator));
+                }
+                Self::new(numerator, denominator)
+            }
+        };
+        Ok(fraction.into_value(heap)?)
+    }
+
+    /// Allocates the fraction on the heap.
+    pub fn into_value(self, heap: &mut Heap<impl ResourceTracker>) -> Result<Value, ResourceError> {
+        Ok(Value::Ref(heap.allocate(HeapData::Fraction(self))?))
+    }
+
+    /// Converts to the nearest float, rounding half to even.
+    ///
+    /// # Errors
+    /// Returns `OverflowError` if the value is too large for a float.
+    pub fn to_f64(&self) -> RunResult<f64> {
+        ratio_to_f64(&self.numerator, &self.denominator).ok_or_else(|| {
+            SimpleException::new_msg(ExcType::OverflowError, "integer division result too large for a float").into()
+        })
+    }
+
+    /// Returns the integer part, rounding toward zero like `int()`.
+    pub fn trunc(&self) -> BigInt {
+        &self.numerator / &self.denominator
+    }
+
+    /// Returns true if the fraction equals the int `value`.
+    pub fn eq_int(&self, value: &BigInt) -> bool {
+        self.denominator.is_one() && self.numerator == *value
+    }
+
+    /// Returns the absolute value.
+    pub fn abs(&self) -> Self {
+        Self {
+            numerator: self.numerator.abs(),
+            denominator: self.denominator.clone(),
+        }
+    }
+
+    /// Computes CPython's numeric hash, consistent with equal ints and floats.
+    ///
+    /// The hash is `numerator * inverse(denominator)` modulo `2**61 - 1`, or the hash of
+    /// infinity when the denominator has no inverse.
+    pub fn hash(&self) -> u64 {
+        let modulus = BigInt::from(NUMERIC_HASH_MODULUS);
+        let inverse = self.denominator.modpow(&(&modulus - 2), &modulus);
+        let magnitude = if inverse.is_zero() {
+            NUMERIC_HASH_INF
+        } else {
+            let reduced = (self.numerator.abs() % &modulus) * inverse % &modulus;
+            reduced.to_i64().expect("value reduced modulo 2**61 - 1 fits in i64")
+        };
+        signed_numeric_hash(self.numerator.is_negative(), magnitude)
+    }
+
+    /// Implements `round(fraction, ndigits)`, rounding half to even.
+    ///
+    /// Returns an int when `ndigits` is `None` and a fraction otherwise.
+    pub fn round(&self, ndigits: Option<i64>, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
+        let Some(ndigits) = ndigits else {
+            let rounded = round_half_even(&self.numerator, &self.denominator);
+            return Ok(LongInt::new(rounded).into_value(heap)?);
+        };
+        let exponent = ndigits.unsigned_abs();
+        check_pow_size(4, exponent, heap.tracker())?;
+        let shift = bigint_pow(BigInt::from(10), exponent);
+        let rounded = if ndigits > 0 {
+            let rounded = round_half_even(&(&self.numerator * &shift), &self.denominator);
+            Self::new(rounded, shift)
+        } else {
+            let rounded = round_half_even(&self.numerator, &(&self.denominator * &shift));
+            Self::from(rounded * shift)
+        };
+        Ok(rounded.into_value(heap)?)
+    }
+
+    /// Implements `limit_denominator()`: the closest fraction whose denominator is at most
+    /// `max_denominator`, found from the continued fraction expansion like CPython.
+    fn limit_denominator(&self, max_denominator: &BigInt) -> Self {
+        if self.denominator <= *max_denominator {
+            return self.clone();
+        }
+        let (mut p0, mut q0, mut p1, mut q1) = (BigInt::zero(), BigInt::one(), BigInt::one(), BigInt::zero());
+        let (mut n, mut d) = (self.numerator.clone(), self.denominator.clone());
+        loop {
+            let a = n.div_floor(&d);
+            let q2 = &q0 + &a * &q1;
+            if q2 > *max_denominator {
+                break;
+            }
+            let p2 = &p0 + &a * &p1;
+            (p0, q0, p1, q1) = (p1, q1, p2, q2);
+            let remainder = n - &a * &d;
+            (n, d) = (d, remainder);
+        }
+        let k = (max_denominator - &q0).div_floor(&q1);
+        // The candidates are (p0 + k*p1)/(q0 + k*q1) and p1/q1, which is closer when
+        // 2*d*(q0 + k*q1) <= denominator
+        let bound_denominator = &q0 + &k * &q1;
+        if BigInt::from(2) * &d * &bound_denominator <= self.denominator {
+            Self {
+                numerator: p1,
+                denominator: q1,
+            }
+        } else {
+            Self {
+                numerator: p0 + k * p1,
+                denominator: bound_denominator,
+            }
+        }
+    }
+
+    /// Raises the fraction to an integer power.
+    fn pow_int(&self, exponent: &BigInt, heap: &Heap<impl ResourceTracker>) -> RunResult<Self> {
+        let power = match exponent.magnitude().to_u64() {
+            Some(power) => power,
+            // 0, 1 and -1 only depend on the parity of a huge exponent
+            None if self.numerator.bits() <= 1 && self.denominator.is_one() => {
+                if exponent.is_even() {
+                    2
+                } else {
+                    1
+                }
+            }
+            None => return Err(SimpleException::new_msg(ExcType::OverflowError, "exponent too large").into()),
+        };
+        check_pow_size(self.numerator.bits() + self.denominator.bits(), power, heap.tracker())?;
+        let numerator = bigint_pow(self.numerator.clone(), power);
+        let denominator = bigint_pow(self.denominator.clone(), power);
+        if !exponent.is_negative() {
+            Ok(Self { numerator, denominator })
+        } else if numerator.is_zero() {
+            Err(zero_denominator_error(&denominator))
+        } else {
+            Ok(Self::new(denominator, numerator))
+        }
+    }
+
+    /// Applies an operator other than `**` to two fractions.
+    fn binary_op(&self, other: &Self, op: FractionOp, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
+        check_mult_size(self.bits(), other.bits(), heap.tracker())?;
+        let (a, b) = (self, other);
+        let result = match op {
+            FractionOp::Add => Self::new(
+                &a.numerator * &b.denominator + &b.numerator * &a.denominator,
+                &a.denominator * &b.denominator,
+            ),
+            FractionOp::Sub => Self::new(
+                &a.numerator * &b.denominator - &b.numerator * &a.denominator,
+                &a.denominator * &b.denominator,
+            ),
+            FractionOp::Mult => Self::new(&a.numerator * &b.numerator, &a.denominator * &b.denominator),
+            FractionOp::Div => {
+                let numerator = &a.numerator * &b.denominator;
+                if b.numerator.is_zero() {
+                    return Err(zero_denominator_error(&numerator));
+                }
+                Self::new(numerator, &a.denominator * &b.numerator)
+            }
+            FractionOp::FloorDiv => {
+                if b.numerator.is_zero() {
+                    return Err(ExcType::zero_division().into());
+                }
+                let quotient = (&a.numerator * &b.denominator).div_floor(&(&a.denominator * &b.numerator));
+                return Ok(LongInt::new(quotient).into_value(heap)?);
+            }
+            FractionOp::Mod => {
+                if b.numerator.is_zero() {
+                    return Err(ExcType::zero_division().into());
+                }
+                let remainder = (&a.numerator * &b.denominator).mod_floor(&(&b.numerator * &a.denominator));
+                Self::new(remainder, &a.denominator * &b.denominator)
+            }
+            FractionOp::Pow => unreachable!("powers are handled by rational_pow"),
+        };
+        Ok(result.into_value(heap)?)
+    }
+
+    /// Number of significant bits in the numerator and denominator together.
+    fn bits(&self) -> u64 {
+        self.numerator.bits() + self.denominator.bits()
+    }
+}
+
+impl From<BigInt> for Fraction {
+    fn from(value: BigInt) -> Self {
+        Self {
+            numerator: value,
+            denominator: BigInt::one(),
+        }
+    }
+}
+
+impl PartialOrd for Fraction {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Fraction {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (&self.numerator * &other.denominator).cmp(&(&other.numerator * &self.denominator))
+    }
+}
+
+impl Neg for Fraction {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self {
+            numerator: -self.numerator,
+            denominator: self.denominator,
+        }
+    }
+}
+
+/// Formats the fraction as `str()` does: `3/4`, or just `3` for a whole number.
+impl Display for Fraction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.denominator.is_one() {
+            write!(f, "{}", self.numerator)
+        } else {
+            write!(f, "{}/{}", self.numerator, self.denominator)
+        }
+    }
+}
+
+impl PyTrait for Fraction {
+    fn py_type(&self, _heap: &Heap<impl ResourceTracker>) -> Type {
+        Type::Fraction
+    }
+
+    fn py_estimate_size(&self) -> usize {
+        // Round each part up to whole bytes, as `LongInt::estimate_size` does
+        let bit_bytes = usize::try_from(self.bits()).unwrap_or(usize::MAX).saturating_add(14) / 8;
+        bit_bytes + std::mem::size_of::<Self>()
+    }
+
+    fn py_len(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> Option<usize> {
+        None
+    }
+
+    fn py_eq(
+        &self,
+        other: &Self,
+        _heap: &mut Heap<impl ResourceTracker>,
+        _guard: &mut DepthGuard,
+        _interns: &Interns,
+    ) -> Result<bool, ResourceError> {
+        Ok(self == other)
+    }
+
+    fn py_bool(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> bool {
+        !self.numerator.is_zero()
+    }
+
+    fn py_repr_fmt(
+        &self,
+        f: &mut impl Write,
+        _heap: &Heap<impl ResourceTracker>,
+        _heap_ids: &mut AHashSet<HeapId>,
+        _guard: &mut DepthGuard,
+        _interns: &Interns,
+    ) -> std::fmt::Result {
+        write!(f, "Fraction({}, {})", self.numerator, self.denominator)
+    }
+
+    fn py_dec_ref_ids(&mut self, _stack: &mut Vec<HeapId>) {
+        // Fractions hold plain integers, nothing to do
+    }
+
+    fn py_call_attr(
+        &mut self,
+        heap: &mut Heap<impl ResourceTracker>,
+        attr: &EitherStr,
+        args: ArgValues,
+        interns: &Interns,
+    ) -> RunResult<Value> {
+        match attr.static_string() {
+            Some(StaticStrings::LimitDenominator) => {
+                let max_denominator = args.get_zero_one_arg("limit_denominator", heap)?;
+                defer_drop!(max_denominator, heap);
+                let max_denominator = match max_denominator {
+                    None => BigInt::from(1_000_000),
+                    Some(value) => match number(value, heap) {
+                        Some(Number::Rational(fraction)) if fraction.denominator.is_one() => fraction.numerator,
+                        _ => return Err(ExcType::type_error_not_integer(value.py_type(heap))),
+                    },
+                };
+                if max_denominator < BigInt::one() {
+                    return Err(
+                        SimpleException::new_msg(ExcType::ValueError, "max_denominator should be at least 1").into(),
+                    );
+                }
+                Ok(self.limit_denominator(&max_denominator).into_value(heap)?)
+            }
+            Some(StaticStrings::AsIntegerRatio) => {
+                args.check_zero_args("as_integer_ratio", heap)?;
+                let numerator = LongInt::new(self.numerator.clone()).into_value(heap)?;
+                let denominator = LongInt::new(self.denominator.clone()).into_value(heap)?;
+                Ok(allocate_tuple(smallvec![numerator, denominator], heap)?)
+            }
+            Some(StaticStrings::IsInteger) => {
+                args.check_zero_args("is_integer", heap)?;
+                Ok(Value::Bool(self.denominator.is_one()))
+            }
+            _ => {
+                args.drop_with_heap(heap);
+                Err(ExcType::attribute_error(Type::Fraction, attr.as_str(interns)))
+            }
+        }
+    }
+
+    fn py_getattr(
+        &self,
+        attr_id: StringId,
+        heap: &mut Heap<impl ResourceTracker>,
+        _interns: &Interns,
+    ) -> RunResult<Option<AttrCallResult>> {
+        let value = if attr_id == StaticStrings::Numerator {
+            &self.numerator
+        } else if attr_id == StaticStrings::Denominator {
+            &self.denominator
+        } else {
+            return Ok(None);
+        };
+        let value = LongInt::new(value.clone()).into_value(heap)?;
+        Ok(Some(AttrCallResult::Value(value)))
+    }
+}
+
+/// Applies `op` when either operand is a fraction and the other is a number.
+///
+/// This is the fallback for operators ints and floats don't support themselves. Returns
+/// `Ok(None)` if neither operand is a fraction or the other operand isn't a number, so the
+/// caller raises its usual `TypeError`.
+///
+/// # Errors
+/// Returns `ZeroDivisionError` for division by zero, `OverflowError` if a fraction is too
+/// large to combine with a float, and a resource error if the result would be too large.
+pub(crate) fn fraction_binary_op(
+    lhs: &Value,
+    rhs: &Value,
+    op: FractionOp,
+    heap: &mut Heap<impl ResourceTracker>,
+) -> RunResult<Option<Value>> {
+    if !is_fraction(lhs, heap) && !is_fraction(rhs, heap) {
+        return Ok(None);
+    }
+    let (Some(a), Some(b)) = (number(lhs, heap), number(rhs, heap)) else {
+        return Ok(None);
+    };
+    match (a, b) {
+        (Number::Rational(base), Number::Rational(exponent)) if op == FractionOp::Pow => {
+            rational_pow(is_fraction(lhs, heap), &base, &exponent, heap).map(Some)
+        }
+        (Number::Rational(a), Number::Rational(b)) => a.binary_op(&b, op, heap).map(Some),
+        (a, b) => float_op(a.to_f64()?, b.to_f64()?, op, heap),
+    }
+}
+
+/// Compares two numbers when at least one is a fraction or a long int.
+///
+/// Returns `None` if neither is a fraction or long int, the other isn't a number, or it's NaN.
+/// Fractions and long ints are compared with finite floats exactly, and with infinities as if
+/// they were zero.
+pub(crate) fn fraction_cmp(lhs: &Value, rhs: &Value, heap: &Heap<impl ResourceTracker>) -> Option<Ordering> {
+    let is_exact = |value: &Value| matches!(value, Value::Ref(id) if matches!(heap.get(*id), HeapData::Fraction(_) | HeapData::LongInt(_)));
+    if !is_exact(lhs) && !is_exact(rhs) {
+        return None;
+    }
+    // NaN has no exact value, so it's unordered
+    let exact = |value: Number| match value {
+        Number::Rational(fraction) => Some(fraction),
+        Number::Float(f) => Fraction::from_f64(f).ok(),
+    };
+    match (number(lhs, heap)?, number(rhs, heap)?) {
+        (Number::Float(a), Number::Rational(_)) if a.is_infinite() => a.partial_cmp(&0.0),
+        (Number::Rational(_), Number::Float(b)) if b.is_infinite() => 0.0_f64.partial_cmp(&b),
+        (a, b) => Some(exact(a)?.cmp(&exact(b)?)),
+    }
+}
+
+/// Returns true if `value` is a fraction.
+pub(crate) fn is_fraction(value: &Value, heap: &Heap<impl ResourceTracker>) -> bool {
+    matches!(value, Value::Ref(id) if matches!(heap.get(*id), HeapData::Fraction(_)))
+}
+
+/// Converts an int, bool, fraction or float to an operand of a fraction operator.
+fn number(value: &Value, heap: &Heap<impl ResourceTracker>) -> Option<Number> {
+    match value {
+        Value::Bool(b) => Some(Number::Rational(Fraction::from(BigInt::from(u8::from(*b))))),
+        Value::Int(i) => Some(Number::Rational(Fraction::from(BigInt::from(*i)))),
+        Value::Float(f) => Some(Number::Float(*f)),
+        Value::Ref(id) => match heap.get(*id) {
+            HeapData::LongInt(li) => Some(Number::Rational(Fraction::from(li.inner().clone()))),
+            HeapData::Fraction(fraction) => Some(Number::Rational(fraction.clone())),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+impl Number {
+    fn to_f64(&self) -> RunResult<f64> {
+        match self {
+            Self::Rational(fraction) => fraction.to_f64(),
+            Self::Float(f) => Ok(*f),
+        }
+    }
+}
+
+/// Implements `**` between rational numbers, following CPython's `Fraction.__pow__` and
+/// `__rpow__`.
+///
+/// Integral exponents give exact results: an int when an int base is raised to a
+/// non-negative power, a fraction otherwise. Other exponents give a float.
+fn rational_pow(
+    base_is_fraction: bool,
+    base: &Fraction,
+    exponent: &Fraction,
+    heap: &mut Heap<impl ResourceTracker>,
+) -> RunResult<Value> {
+    if !exponent.denominator.is_one() {
+        return Ok(
+            float_op(base.to_f64()?, exponent.to_f64()?, FractionOp::Pow, heap)?.expect("float ** float is supported")
+        );
+    }
+    let result = base.pow_int(&exponent.numerator, heap)?;
+    if !base_is_fraction && result.denominator.is_one() && !exponent.numerator.is_negative() {
+        Ok(LongInt::new(result.numerator).into_value(heap)?)
+    } else {
+        Ok(result.into_value(heap)?)
+    }
+}
+
+/// Applies `op` to two floats, as when a fraction meets a float.
+fn float_op(a: f64, b: f64, op: FractionOp, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Option<Value>> {
+    let (lhs, rhs) = (Value::Float(a), Value::Float(b));
+    match op {
+        FractionOp::Add => Ok(Some(Value::Float(a + b))),
+        FractionOp::Sub => Ok(Some(Value::Float(a - b))),
+        FractionOp::Mult => Ok(Some(Value::Float(a * b))),
+        FractionOp::Div if b == 0.0 => Err(ExcType::zero_division().into()),
+        FractionOp::Div => Ok(Some(Value::Float(a / b))),
+        FractionOp::FloorDiv => lhs.py_floordiv(&rhs, heap),
+        FractionOp::Mod => lhs.py_mod(&rhs, heap),
+        FractionOp::Pow => lhs.py_pow(&rhs, heap),
+    }
+}
+
+/// Rounds `numerator / denominator` to the nearest int, ties to even.
+fn round_half_even(numerator: &BigInt, denominator: &BigInt) -> BigInt {
+    let (floor, remainder) = numerator.div_mod_floor(denominator);
+    let twice_remainder = remainder * 2;
+    match twice_remainder.cmp(denominator) {
+        Ordering::Less => floor,
+        Ordering::Greater => floor + 1,
+        Ordering::Equal if floor.is_even() => floor,
+        Ordering::Equal => floor + 1,
+    }
+}
+
+/// Parses a string argument to `Fraction()`: `'3/4'`, `'-1.5'`, `'2e-3'` and so on, with
+/// optional surrounding whitespace and single underscores between digits.
+fn parse_fraction(s: &str, heap: &Heap<impl ResourceTracker>) -> RunResult<Fraction> {
+    let invalid = || -> RunError {
+        SimpleException::new_msg(
+            ExcType::ValueError,
+            format!("Invalid literal for Fraction: {}", StringRepr(s)),
+        )
+        .into()
+    };
+    let text = s.trim_matches(char::is_whitespace);
+    let (negative, text) = match text.as_bytes().first() {
+        Some(b'-') => (true, &text[1..]),
+        Some(b'+') => (false, &text[1..]),
+        _ => (false, text),
+    };
+    let starts_with_digit = |text: &str| text.as_bytes().first().is_some_and(u8::is_ascii_digit);
+    if !starts_with_digit(text) && !(text.starts_with('.') && starts_with_digit(&text[1..])) {
+        return Err(invalid());
+    }
+
+    let (integer, rest) = split_digits(text);
+    let mut numerator = parse_digits(&integer);
+    if negative {
+        numerator = -numerator;
+    }
+
+    let after_space = rest.trim_start_matches(char::is_whitespace);
+    if let Some(denominator) = after_space.strip_prefix('/') {
+        let denominator = denominator.trim_start_matches(char::is_whitespace);
+        let (digits, rest) = split_digits(denominator);
+        if digits.is_empty() || !rest.is_empty() {
+            return Err(invalid());
+        }
+        let denominator = parse_digits(&digits);
+        if denominator.is_zero() {
+            return Err(zero_denominator_error(&numerator));
+        }
+        return Ok(Fraction::new(numerator, denominator));
+    }
+
+    let mut denominator = BigInt::one();
+    let mut rest = rest;
+    if let Some(decimal) = rest.strip_prefix('.') {
+        let (digits, after) = split_digits(decimal);
+        let scale = bigint_pow(BigInt::from(10), digits.len() as u64);
+        numerator = numerator * &scale
+            + if negative {
+                -parse_digits(&digits)
+            } else {
+                parse_digits(&digits)
+            };
+        denominator = scale;
+        rest = after;
+    }
+    if let Some(exponent) = rest.strip_prefix(['e', 'E']) {
+        let (exponent_negative, exponent) = match exponent.as_bytes().first() {
+            Some(b'-') => (true, &exponent[1..]),
+            Some(b'+') => (false, &exponent[1..]),
+            _ => (false, exponent),
+        };
+        let (digits, after) = split_digits(exponent);
+        if digits.is_empty() {
+            return Err(invalid());
+        }
+        rest = after;
+        let power = parse_digits(&digits).to_u64().unwrap_or(u64::MAX);
+        check_pow_size(4, power, heap.tracker())?;
+        let scale = bigint_pow(BigInt::from(10), power);
+        if exponent_negative {
+            denominator *= scale;
+        } else {
+            numerator *= scale;
+        }
+    }
+    if !rest.is_empty() {
+        return Err(invalid());
+    }
+    Ok(Fraction::new(numerator, denominator))
+}
+
+/// Splits leading ASCII digits, with single underscores between them, from `text`.
+///
+/// Returns the digits without underscores and the rest of the text. An underscore that
+/// isn't followed by a digit is left in the rest, so parsing fails.
+fn split_digits(text: &str) -> (String, &str) {
+    let bytes = text.as_bytes();
+    let mut digits = String::new();
+    let mut end = 0;
+    while end < bytes.len() {
+        if bytes[end].is_ascii_digit() {
+            digits.push(char::from(bytes[end]));
+            end += 1;
+        } else if bytes[end] == b'_' && !digits.is_empty() && bytes.get(end + 1).is_some_and(u8::is_ascii_digit) {
+            end += 1;
+        } else {
+            break;
+        }
+    }
+    (digits, &text[end..])
+}
+
+/// Parses ASCII digits as an int, where no digits is zero.
+fn parse_digits(digits: &str) -> BigInt {
+    if digits.is_empty() {
+        BigInt::zero()
+    } else {
+        digits.parse().expect("only ASCII digits")
+    }
+}
+
+/// Creates the `ZeroDivisionError` raised when a fraction would have a zero denominator.
+fn zero_denominator_error(numerator: &BigInt) -> RunError {
+    SimpleException::new_msg(ExcType::ZeroDivisionError, format!("Fraction({numerator}, 0)")).into()
+}
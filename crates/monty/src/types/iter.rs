@@ -26,6 +26,25 @@
 //! This allows `advance_on_heap()` to coordinate access without extracting
 //! the iterator from the heap (avoiding `std::mem::replace` overhead).
 //!
+//! ## Iterator Protocol
+//!
+//! `MontyIter::get_iter()` is the single entry point for turning a value into an
+//! iterator: it is used by the `iter()` builtin and the VM's `GetIter` opcode, so
+//! `for` loops and explicit `iter()` calls behave identically. Calling it on a value
+//! that is already an iterator returns that same iterator, per Python semantics.
+//!
+//! When an existing iterator is consumed by Rust-side code (e.g. `list(it)`, `sum(it)`),
+//! `MontyIter::new()` wraps it in an `IterValue::Iterator` that delegates to the
+//! underlying iterator, so items consumed there are also consumed from the original.
+//! New lazily-produced iterable types only need to be reachable from `advance_on_heap()`
+//! to work with every consumer.
+//!
+//! ## Lazy Sequences
+//!
+//! Iterating a `LazySequence` yields items from its most recently fetched chunk. Reaching
+//! an unfetched item raises `NotImplementedError` for synchronous consumers; the VM's
+//! `ForIter` instead uses `lazy_fetch_range()` to suspend and ask the host for the chunk.
+//!
 //! ## Builtin Support
 //!
 //! The `iterator_next()` helper implements the `next()` builtin.
@@ -36,7 +55,7 @@ use crate::{
     heap::{DropWithHeap, Heap, HeapData, HeapGuard, HeapId},
     intern::{BytesId, Interns, StringId},
     resource::ResourceTracker,
-    types::{PyTrait, Range, str::allocate_char},
+    types::{PyTrait, Range, lazy_sequence::LazyItem, str::allocate_char, string_io},
     value::Value,
 };
 
@@ -73,26 +92,58 @@ impl MontyIter {
             return Err(ExcType::type_error("iter(callable, sentinel) is not yet supported"));
         }
 
-        // Check if already an iterator - return self
-        if let Value::Ref(id) = &iterable
+        Self::get_iter(iterable, heap, interns)
+    }
+
+    /// Returns an iterator for `value`, implementing Python's `iter(value)`.
+    ///
+    /// If the value is already an iterator it is returned unchanged (its refcount is
+    /// already owned by the caller); otherwise a new heap-allocated iterator is created.
+    /// Returns a `TypeError` if the value is not iterable.
+    pub fn get_iter(value: Value, heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<Value> {
+        if let Value::Ref(id) = &value
             && matches!(heap.get(*id), HeapData::Iter(_))
         {
-            // Already an iterator - return it (refcount already correct from caller)
-            return Ok(iterable);
+            return Ok(value);
         }
 
-        // Create new iterator
-        let iter = Self::new(iterable, heap, interns)?;
+        let iter = Self::new(value, heap, interns)?;
         let id = heap.allocate(HeapData::Iter(iter))?;
         Ok(Value::Ref(id))
     }
 
+    /// Creates an iterator over the elements of `range` in reverse order.
+    ///
+    /// Counts down from the last element without building a reversed range, whose
+    /// stop or step may not fit in an `i64`.
+    pub fn new_reversed_range(range: &Range) -> Self {
+        Self {
+            index: 0,
+            iter_value: IterValue::Range {
+                next: range.last().unwrap_or(range.start),
+                // Iteration adds with wrapping, so negating `i64::MIN` to itself still
+                // reaches the right elements
+                step: range.step.wrapping_neg(),
+                len: range.len(),
+            },
+            value: Value::None,
+        }
+    }
+
     /// Creates a new MontyIter from a Value.
     ///
     /// Returns an error if the value is not iterable.
     /// For strings, copies the string content for byte-offset based iteration.
     /// For ranges, the data is copied so the heap reference is dropped immediately.
     pub fn new(mut value: Value, heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<Self> {
+        // Streams iterate over a snapshot of their remaining lines
+        if let Value::Ref(heap_id) = &value
+            && matches!(heap.get(*heap_id), HeapData::StringIO(_))
+        {
+            let lines = string_io::iter_lines(*heap_id, heap);
+            value.drop_with_heap(heap);
+            value = lines?;
+        }
         if let Some(iter_value) = IterValue::new(&value, heap, interns) {
             // For Range, we copy next/step/len into ForIterValue::Range, so we don't need
             // to keep the heap object alive during iteration. Drop it immediately to avoid
@@ -153,6 +204,9 @@ impl MontyIter {
             IterValue::Range { .. } | IterValue::InternBytes { .. } => {
                 unreachable!("Range and InternBytes use fast path, not iter_state")
             }
+            IterValue::Iterator { .. } => {
+                unreachable!("Iterator delegates to the wrapped iterator, not iter_state")
+            }
             IterValue::IterStr {
                 string,
                 byte_offset,
@@ -173,6 +227,10 @@ impl MontyIter {
                     })
                 }
             }
+            IterValue::Lazy { heap_id } => Some(IterState::Lazy {
+                heap_id: *heap_id,
+                index: self.index,
+            }),
             IterValue::HeapRef {
                 heap_id,
                 len,
@@ -227,7 +285,8 @@ impl MontyIter {
                     Some(Ok(None))
                 } else {
                     let value = *next;
-                    *next += *step;
+                    // Wraps only past the last element, which is never yielded
+                    *next = next.wrapping_add(*step);
                     self.index += 1;
                     Some(Ok(Some(Value::Int(value))))
                 }
@@ -259,7 +318,16 @@ impl MontyIter {
                     Some(Ok(Some(Value::Int(i64::from(bytes[i])))))
                 }
             }
-            IterValue::HeapRef { .. } => None,
+            IterValue::HeapRef { .. } | IterValue::Lazy { .. } | IterValue::Iterator { .. } => None,
+        }
+    }
+
+    /// Returns the wrapped iterator's `HeapId` if this iterator delegates to another one.
+    #[inline]
+    fn delegate_id(&self) -> Option<HeapId> {
+        match self.iter_value {
+            IterValue::Iterator { heap_id } => Some(heap_id),
+            _ => None,
         }
     }
 
@@ -280,7 +348,8 @@ impl MontyIter {
                     return Ok(None);
                 }
                 let value = *next;
-                *next += *step;
+                // Wraps only past the last element, which is never yielded
+                *next = next.wrapping_add(*step);
                 self.index += 1;
                 Ok(Some(Value::Int(value)))
             }
@@ -338,26 +407,46 @@ impl MontyIter {
                 self.index += 1;
                 Ok(Some(clone_and_inc_ref(item, heap)))
             }
+            IterValue::Lazy { heap_id } => {
+                let item = get_lazy_item(heap, *heap_id, self.index)?;
+                if item.is_some() {
+                    self.index += 1;
+                }
+                Ok(item.map(|item| clone_and_inc_ref(item, heap)))
+            }
+            IterValue::Iterator { heap_id } => advance_on_heap(heap, *heap_id, interns),
         }
     }
 
     /// Returns the remaining size for iterables based on current state.
     ///
     /// For immutable types (Range, Tuple, Str, Bytes, FrozenSet), returns the exact remaining count.
-    /// For List, returns current length minus index (may change if list is mutated).
+    /// For List and Array, returns current length minus index (may change if they are mutated).
     /// For Dict and Set, returns the captured length minus index (used for size-change detection).
     pub fn size_hint(&self, heap: &Heap<impl ResourceTracker>) -> usize {
         let len = match &self.iter_value {
             IterValue::Range { len, .. } | IterValue::IterStr { len, .. } | IterValue::InternBytes { len, .. } => *len,
             IterValue::HeapRef { heap_id, len, .. } => {
-                // For List (len=None), check current length dynamically
-                len.unwrap_or_else(|| {
-                    let HeapData::List(list) = heap.get(*heap_id) else {
-                        panic!("HeapRef with len=None should only be List")
-                    };
-                    list.len()
+                // For List and Array (len=None), check current length dynamically
+                len.unwrap_or_else(|| match heap.get(*heap_id) {
+                    HeapData::List(list) => list.len(),
+                    HeapData::Array(array) => array.len(),
+                    _ => panic!("HeapRef with len=None should only be List or Array"),
                 })
             }
+            IterValue::Lazy { heap_id } => {
+                let HeapData::LazySequence(seq) = heap.get(*heap_id) else {
+                    panic!("Lazy iterator should wrap a lazy sequence")
+                };
+                // Unknown lengths give no hint rather than guessing
+                seq.length().unwrap_or(0)
+            }
+            IterValue::Iterator { heap_id } => {
+                let HeapData::Iter(inner) = heap.get(*heap_id) else {
+                    panic!("Iterator delegate should wrap an iterator")
+                };
+                return inner.size_hint(heap);
+            }
         };
         len.saturating_sub(self.index)
     }
@@ -416,16 +505,22 @@ pub(crate) fn advance_on_heap(
 ) -> RunResult<Option<Value>> {
     // Fast path: Range and InternBytes don't need additional heap access,
     // so we can handle them with a single mutable borrow.
-    {
+    let delegate_id = {
         let HeapData::Iter(iter) = heap.get_mut(iter_id) else {
             panic!("advance_on_heap: expected Iterator on heap");
         };
         if let Some(result) = iter.try_advance_simple(interns) {
             return result;
         }
-    }
+        iter.delegate_id()
+    };
     // Mutable borrow ends here, allowing the multi-phase approach below
 
+    // Delegating iterators simply advance the iterator they wrap
+    if let Some(inner_id) = delegate_id {
+        return advance_on_heap(heap, inner_id, interns);
+    }
+
     // Multi-phase approach for IterStr and HeapRef (need heap access during value retrieval)
     // Phase 1: Get iterator state (immutable borrow ends after this block)
     let HeapData::Iter(iter) = heap.get(iter_id) else {
@@ -457,6 +552,12 @@ pub(crate) fn advance_on_heap(
             }
             (item, None)
         }
+        IterState::Lazy { heap_id, index } => {
+            let Some(item) = get_lazy_item(heap, heap_id, index)? else {
+                return Ok(None);
+            };
+            (clone_and_inc_ref(item, heap), None)
+        }
     };
 
     // Phase 3: Advance the iterator
@@ -486,6 +587,8 @@ fn get_heap_item(
             }
             Ok(Some(list.as_slice()[index].copy_for_extend()))
         }
+        // Arrays can shrink during iteration too, and their items are never refs
+        HeapData::Array(array) => Ok(array.get(index)),
         HeapData::Tuple(tuple) => Ok(Some(tuple.as_slice()[index].copy_for_extend())),
         HeapData::NamedTuple(namedtuple) => Ok(Some(namedtuple.as_vec()[index].copy_for_extend())),
         HeapData::Dict(dict) => {
@@ -525,6 +628,46 @@ fn get_heap_item(
     }
 }
 
+/// Gets an item from a lazy sequence at the given index, copied without a refcount increment.
+///
+/// Returns `Ok(None)` past the end of the sequence. Items that haven't been fetched yet
+/// raise `NotImplementedError`; the VM's `ForIter` recognizes this case via
+/// `lazy_fetch_range()` and suspends to fetch them instead.
+fn get_lazy_item(heap: &Heap<impl ResourceTracker>, heap_id: HeapId, index: usize) -> RunResult<Option<Value>> {
+    let HeapData::LazySequence(seq) = heap.get(heap_id) else {
+        panic!("get_lazy_item: expected LazySequence on heap");
+    };
+    match seq.get(index) {
+        LazyItem::Ready(item) => Ok(Some(item.copy_for_extend())),
+        LazyItem::Exhausted => Ok(None),
+        LazyItem::Fetch { .. } => Err(ExcType::lazy_sequence_sync_fetch(seq.name())),
+    }
+}
+
+/// Returns the lazy sequence and the `start..stop` range an iterator needs fetched
+/// before it can yield its next item.
+///
+/// Follows delegating iterators, so `for x in iter(seq)` fetches just like `for x in seq`.
+/// Returns `None` if the iterator isn't over a lazy sequence or its next item is available.
+pub(crate) fn lazy_fetch_range(heap: &Heap<impl ResourceTracker>, iter_id: HeapId) -> Option<(HeapId, usize, usize)> {
+    let HeapData::Iter(iter) = heap.get(iter_id) else {
+        return None;
+    };
+    match iter.iter_value {
+        IterValue::Lazy { heap_id } => {
+            let HeapData::LazySequence(seq) = heap.get(heap_id) else {
+                return None;
+            };
+            match seq.get(iter.index) {
+                LazyItem::Fetch { start, stop } => Some((heap_id, start, stop)),
+                LazyItem::Ready(_) | LazyItem::Exhausted => None,
+            }
+        }
+        IterValue::Iterator { heap_id } => lazy_fetch_range(heap, heap_id),
+        _ => None,
+    }
+}
+
 /// Gets the next item from an iterator.
 ///
 /// If the iterator is exhausted:
@@ -547,25 +690,18 @@ pub fn iterator_next(
     heap: &mut Heap<impl ResourceTracker>,
     interns: &Interns,
 ) -> RunResult<Value> {
-    let Value::Ref(iter_id) = iter_value else {
-        // Not a heap value - can't be an iterator
-        if let Some(d) = default {
-            d.drop_with_heap(heap);
+    let iter_id = match iter_value {
+        Value::Ref(id) if matches!(heap.get(*id), HeapData::Iter(_)) => *id,
+        _ => {
+            if let Some(d) = default {
+                d.drop_with_heap(heap);
+            }
+            return Err(ExcType::type_error_not_iterator(iter_value.py_type(heap)));
         }
-        return Err(ExcType::type_error_not_iterable(iter_value.py_type(heap)));
     };
 
-    // Check that it's actually an iterator
-    if !matches!(heap.get(*iter_id), HeapData::Iter(_)) {
-        if let Some(d) = default {
-            d.drop_with_heap(heap);
-        }
-        let data_type = heap.get(*iter_id).py_type(heap);
-        return Err(ExcType::type_error(format!("'{data_type}' object is not an iterator")));
-    }
-
     // Get next item using the MontyIter::advance_on_heap method
-    match advance_on_heap(heap, *iter_id, interns)? {
+    match advance_on_heap(heap, iter_id, interns)? {
         Some(item) => {
             // Drop default if provided since we don't need it
             if let Some(d) = default {
@@ -604,6 +740,8 @@ enum IterState {
         index: usize,
         expected_len: Option<usize>,
     },
+    /// Lazy sequence iterator, the item at `index` may not have been fetched yet.
+    Lazy { heap_id: HeapId, index: usize },
 }
 
 /// Increments the reference count for a value copied via `copy_for_extend()`.
@@ -651,9 +789,9 @@ enum IterValue {
     },
     /// Iterating over interned bytes, yields `Value::Int` for each byte.
     InternBytes { bytes_id: BytesId, len: usize },
-    /// Iterating over a heap-allocated container (List, Tuple, NamedTuple, Dict, Bytes, Set, FrozenSet).
+    /// Iterating over a heap-allocated container (List, Array, Tuple, NamedTuple, Dict, Bytes, Set, FrozenSet).
     ///
-    /// - `len`: `None` for List (checked dynamically since lists can mutate during iteration),
+    /// - `len`: `None` for List and Array (checked dynamically since they can mutate during iteration),
     ///   `Some(n)` for other types (captured at construction for exhaustion checking).
     /// - `checks_mutation`: `true` for Dict/Set (raises RuntimeError if size changes),
     ///   `false` for other types.
@@ -662,6 +800,16 @@ enum IterValue {
         len: Option<usize>,
         checks_mutation: bool,
     },
+    /// Consuming an existing heap iterator, yields whatever the wrapped iterator yields.
+    ///
+    /// The wrapped iterator's state is shared, so items consumed here are no longer
+    /// available from the original iterator (e.g. `list(it)` exhausts `it`).
+    Iterator { heap_id: HeapId },
+    /// Iterating over a host-provided lazy sequence, yields items from its fetched chunk.
+    ///
+    /// The sequence's length may only become known once the host returns a short chunk,
+    /// so exhaustion is checked against the sequence on every step.
+    Lazy { heap_id: HeapId },
 }
 
 impl IterValue {
@@ -709,8 +857,8 @@ impl IterValue {
     /// Creates an iterator value from heap data.
     fn from_heap_data(heap_id: HeapId, heap: &Heap<impl ResourceTracker>) -> Option<Self> {
         match heap.get(heap_id) {
-            // List: no captured len (checked dynamically), no mutation check
-            HeapData::List(_) => Some(Self::HeapRef {
+            // List/Array: no captured len (checked dynamically), no mutation check
+            HeapData::List(_) | HeapData::Array(_) => Some(Self::HeapRef {
                 heap_id,
                 len: None,
                 checks_mutation: false,
@@ -751,20 +899,28 @@ impl IterValue {
             HeapData::Str(s) => Some(Self::from_str(s.as_str())),
             // Range: copy values for iteration
             HeapData::Range(range) => Some(Self::from_range(range)),
-            // Closures, FunctionDefaults, Cells, Exceptions, Dataclasses, Iterators, LongInts, Slices, Modules,
-            // Paths, and async types are not iterable
+            // Iterator: delegate to the existing iterator, sharing its state
+            HeapData::Iter(_) => Some(Self::Iterator { heap_id }),
+            // Lazy sequence: items are fetched from the host as iteration reaches them
+            HeapData::LazySequence(_) => Some(Self::Lazy { heap_id }),
+            // Closures, FunctionDefaults, Cells, Exceptions, Dataclasses, LongInts, Fractions, Slices,
+            // Modules, Paths, async types and csv writers are not iterable. StringIO is swapped for a list of its
+            // lines by `MontyIter::new` before getting here
             HeapData::Closure(_, _, _)
             | HeapData::FunctionDefaults(_, _)
             | HeapData::Cell(_)
             | HeapData::Exception(_)
             | HeapData::Dataclass(_)
-            | HeapData::Iter(_)
             | HeapData::LongInt(_)
             | HeapData::Slice(_)
             | HeapData::Module(_)
             | HeapData::Path(_)
+            | HeapData::UnionType(_)
             | HeapData::Coroutine(_)
-            | HeapData::GatherFuture(_) => None,
+            | HeapData::GatherFuture(_)
+            | HeapData::StringIO(_)
+            | HeapData::CsvWriter(_)
+            | HeapData::Fraction(_) => None,
         }
     }
 }
@@ -0,0 +1,188 @@
+//! Host-provided sequences whose items are fetched on demand.
+//!
+//! A `LazySequence` is created from `MontyObject::LazySequence` and stands in for a host
+//! dataset that is too large to convert upfront. Its items stay on the host until they're
+//! needed: when a `for` loop or comprehension runs past the items fetched so far, the VM
+//! pauses with a `__getitem__` method call whose arguments are the sequence, the start
+//! index and the stop index of the next chunk. The host resumes with a list of the items
+//! in that range. A chunk shorter than requested marks the end of a sequence whose length
+//! wasn't known upfront.
+//!
+//! Only the most recent chunk is kept, so memory use is bounded by the chunk size no
+//! matter how large the host dataset is. Builtins that consume iterables synchronously
+//! (`list()`, `sum()`, ...) can't pause mid-call, so they raise `NotImplementedError` once
+//! they run past the fetched items.
+
+use std::fmt::Write;
+
+use ahash::AHashSet;
+
+use crate::{
+    exception_private::{ExcType, RunResult},
+    heap::{DropWithHeap, Heap, HeapId},
+    intern::Interns,
+    resource::{DepthGuard, ResourceError, ResourceTracker},
+    types::{PyTrait, Type},
+    value::Value,
+};
+
+/// A host sequence that is fetched chunk by chunk while it is iterated.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub(crate) struct LazySequence {
+    /// Name the host uses to identify the sequence.
+    name: String,
+    /// Total number of items, if known upfront or once the host returned a short chunk.
+    length: Option<usize>,
+    /// Maximum number of items requested per fetch.
+    chunk_size: usize,
+    /// Index of the first item in `chunk`.
+    chunk_start: usize,
+    /// Most recently fetched items.
+    chunk: Vec<Value>,
+}
+
+/// Result of looking up an item of a `LazySequence` by index.
+pub(crate) enum LazyItem<'a> {
+    /// The item has already been fetched.
+    Ready(&'a Value),
+    /// The index is past the end of the sequence.
+    Exhausted,
+    /// The item must be fetched from the host first.
+    Fetch { start: usize, stop: usize },
+}
+
+impl LazySequence {
+    /// Creates a sequence with nothing fetched yet.
+    ///
+    /// A `chunk_size` of zero is treated as one so iteration always makes progress.
+    #[must_use]
+    pub fn new(name: String, length: Option<usize>, chunk_size: usize) -> Self {
+        Self {
+            name,
+            length,
+            chunk_size: chunk_size.max(1),
+            chunk_start: 0,
+            chunk: Vec::new(),
+        }
+    }
+
+    /// Returns the name the host uses to identify the sequence.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the length given by the host, or discovered at the end of iteration.
+    #[must_use]
+    pub fn length(&self) -> Option<usize> {
+        self.length
+    }
+
+    /// Returns the maximum number of items requested per fetch.
+    #[must_use]
+    pub fn chunk_size(&self) -> usize {
+        self.chunk_size
+    }
+
+    /// Looks up the item at `index`, or the range that has to be fetched to reach it.
+    #[must_use]
+    pub fn get(&self, index: usize) -> LazyItem<'_> {
+        if self.length.is_some_and(|length| index >= length) {
+            return LazyItem::Exhausted;
+        }
+        if let Some(item) = index.checked_sub(self.chunk_start).and_then(|i| self.chunk.get(i)) {
+            return LazyItem::Ready(item);
+        }
+        let stop = index + self.chunk_size;
+        LazyItem::Fetch {
+            start: index,
+            stop: self.length.map_or(stop, |length| stop.min(length)),
+        }
+    }
+
+    /// Replaces the fetched chunk with the host's `items` for `start..stop`.
+    ///
+    /// Returns a `ValueError` if the host returned more items than requested, or fewer
+    /// than requested for a sequence whose length is known.
+    pub fn store_chunk(
+        &mut self,
+        start: usize,
+        stop: usize,
+        items: Vec<Value>,
+        heap: &mut Heap<impl ResourceTracker>,
+    ) -> RunResult<()> {
+        let requested = stop - start;
+        if items.len() > requested || (self.length.is_some() && items.len() < requested) {
+            let count = items.len();
+            items.drop_with_heap(heap);
+            return Err(ExcType::lazy_sequence_chunk_error(&self.name, start, stop, count));
+        }
+        if items.len() < requested {
+            self.length = Some(start + items.len());
+        }
+        if items.iter().any(|v| matches!(v, Value::Ref(_))) {
+            heap.mark_potential_cycle();
+        }
+        std::mem::replace(&mut self.chunk, items).drop_with_heap(heap);
+        self.chunk_start = start;
+        Ok(())
+    }
+
+    /// Returns whether the fetched chunk holds heap references.
+    #[must_use]
+    pub fn has_refs(&self) -> bool {
+        self.chunk.iter().any(|v| matches!(v, Value::Ref(_)))
+    }
+
+    /// Returns the fetched items, for GC traversal.
+    #[must_use]
+    pub fn chunk(&self) -> &[Value] {
+        &self.chunk
+    }
+}
+
+impl PyTrait for LazySequence {
+    fn py_type(&self, _heap: &Heap<impl ResourceTracker>) -> Type {
+        Type::LazySequence
+    }
+
+    fn py_estimate_size(&self) -> usize {
+        std::mem::size_of::<Self>() + self.name.len() + self.chunk.len() * std::mem::size_of::<Value>()
+    }
+
+    fn py_len(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> Option<usize> {
+        self.length
+    }
+
+    fn py_eq(
+        &self,
+        _other: &Self,
+        _heap: &mut Heap<impl ResourceTracker>,
+        _guard: &mut DepthGuard,
+        _interns: &Interns,
+    ) -> Result<bool, ResourceError> {
+        // Distinct sequences are never equal, identity is checked before this is called
+        Ok(false)
+    }
+
+    fn py_dec_ref_ids(&mut self, stack: &mut Vec<HeapId>) {
+        for value in &mut self.chunk {
+            value.py_dec_ref_ids(stack);
+        }
+    }
+
+    fn py_bool(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> bool {
+        self.length != Some(0)
+    }
+
+    fn py_repr_fmt(
+        &self,
+        f: &mut impl Write,
+        _heap: &Heap<impl ResourceTracker>,
+        _heap_ids: &mut AHashSet<HeapId>,
+        _guard: &mut DepthGuard,
+        _interns: &Interns,
+    ) -> std::fmt::Result {
+        write!(f, "<LazySequence '{}'>", self.name)
+    }
+}
@@ -1,4 +1,4 @@
-use std::{cmp::Ordering, fmt::Write};
+use std::fmt::Write;
 
 use ahash::AHashSet;
 use smallvec::SmallVec;
@@ -6,9 +6,9 @@ use smallvec::SmallVec;
 use super::{AttrCallResult, MontyIter, PyTrait};
 use crate::{
     args::ArgValues,
-    builtins::Builtins,
+    builtins::{BuiltinKeyCaller, KeyCaller},
     defer_drop, defer_drop_mut,
-    exception_private::{ExcType, RunError, RunResult},
+    exception_private::{ExcType, RunResult},
     heap::{DropWithHeap, Heap, HeapData, HeapGuard, HeapId},
     intern::{Interns, StaticStrings},
     io::PrintWriter,
@@ -35,9 +35,9 @@ use crate::{
 /// - `reverse()` - Reverse in place
 /// - `sort([key][, reverse])` - Sort in place
 ///
-/// Note: `sort(key=...)` supports builtin key functions (len, abs, etc.)
-/// but not user-defined functions. This is handled at VM level for access
-/// to function calling machinery.
+/// Note: `sort(key=...)` is dispatched by the VM to [`list_sort_in_place`] so
+/// user-defined key functions can be called; without VM access only builtin
+/// key functions (len, abs, etc.) are supported.
 ///
 /// All list methods from Python's builtins are implemented.
 ///
@@ -714,17 +714,11 @@ fn normalize_list_index(index: i64, len: usize) -> usize {
     }
 }
 
-/// Performs an in-place sort on a list with optional key function and reverse flag.
+/// Performs an in-place sort on a list whose data has been taken out of the heap.
 ///
-/// This is called from `call_list_attr_raw` when `list.sort()` is invoked.
-/// The function lives here to keep list-related logic together.
-///
-/// Uses a staged approach to avoid borrow checker issues:
-/// 1. Parse and validate arguments
-/// 2. Extract items from the list (temporarily empties it)
-/// 3. Compute key values if a key function is provided
-/// 4. Sort indices based on items or key values
-/// 5. Rearrange items in sorted order and put back into the list
+/// This is called from `py_call_attr_raw` when `list.sort()` is invoked without VM
+/// access, so only builtin key functions can be used. The VM normally dispatches
+/// `list.sort()` to [`list_sort_in_place`] instead, which supports any callable.
 ///
 /// # Arguments
 /// * `list` - The list to sort (data already taken out of the heap via `take_data!`)
@@ -738,11 +732,62 @@ fn do_list_sort(
     heap: &mut Heap<impl ResourceTracker>,
     interns: &Interns,
     print_writer: &mut PrintWriter<'_>,
-) -> Result<(), RunError> {
-    // Parse keyword-only arguments: key and reverse
+) -> RunResult<()> {
     let (key_arg, reverse_arg) = args.extract_two_kwargs_only("list.sort", "key", "reverse", heap, interns)?;
+    let (key_fn, reverse) = sort_options(key_arg, reverse_arg, heap, interns);
+    defer_drop!(key_fn, heap);
+    let mut caller = BuiltinKeyCaller::new(heap, print_writer);
+    sort_values(list.as_vec_mut(), key_fn.as_ref(), reverse, &mut caller, interns)
+}
+
+/// Sorts the list at `list_id` in place, calling key functions through `caller`.
+///
+/// Like CPython, the list is emptied while sorting so key functions never see it
+/// partially sorted. If a key function adds items to it anyway, those are discarded
+/// and `ValueError('list modified during sort')` is raised after the sort completes.
+pub(crate) fn list_sort_in_place(
+    list_id: HeapId,
+    args: ArgValues,
+    caller: &mut impl KeyCaller,
+    interns: &Interns,
+) -> RunResult<()> {
+    let heap = caller.heap_mut();
+    let (key_arg, reverse_arg) = args.extract_two_kwargs_only("list.sort", "key", "reverse", heap, interns)?;
+    let (key_fn, reverse) = sort_options(key_arg, reverse_arg, heap, interns);
+    defer_drop!(key_fn, caller);
+
+    let mut items = caller.heap_mut().with_entry_mut(list_id, |_, data| match data {
+        HeapData::List(list) => std::mem::take(list.as_vec_mut()),
+        _ => Vec::new(),
+    });
+    let result = sort_values(&mut items, key_fn.as_ref(), reverse, caller, interns);
+
+    let modified = caller.heap_mut().with_entry_mut(list_id, |heap, data| {
+        let HeapData::List(list) = data else {
+            unreachable!("list_sort_in_place: heap entry is not a list");
+        };
+        let added = std::mem::replace(list.as_vec_mut(), items);
+        let modified = !added.is_empty();
+        added.drop_with_heap(heap);
+        modified
+    });
+
+    result?;
+    if modified {
+        return Err(ExcType::value_error_list_modified_during_sort());
+    }
+    Ok(())
+}
 
-    // Convert reverse to bool (default false)
+/// Resolves the `key` and `reverse` arguments shared by `list.sort()` and `sorted()`.
+///
+/// `key=None` means no key function, and `reverse` is interpreted by truthiness.
+pub(crate) fn sort_options(
+    key_arg: Option<Value>,
+    reverse_arg: Option<Value>,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> (Option<Value>, bool) {
     let reverse = if let Some(v) = reverse_arg {
         let result = v.py_bool(heap, interns);
         v.drop_with_heap(heap);
@@ -751,7 +796,6 @@ fn do_list_sort(
         false
     };
 
-    // Handle key function (None means no key function)
     let key_fn = match key_arg {
         Some(v) if matches!(v, Value::None) => {
             v.drop_with_heap(heap);
@@ -759,179 +803,195 @@ fn do_list_sort(
         }
         other => other,
     };
+    (key_fn, reverse)
+}
 
-    // Step 1: Extract items from the list (temporarily empties it)
-    let mut items: Vec<Value> = list.as_vec_mut().drain(..).collect();
-
-    // Step 2: Compute key values if key function provided
-    let key_values: Option<Vec<Value>> = if let Some(ref key) = key_fn {
+/// Sorts `items` in place by Python's `<`, comparing `key_fn(item)` results when given.
+///
+/// The sort is stable, and equal elements keep their original order when `reverse` is
+/// set. If a key function raises or two values can't be compared, the error is returned
+/// and `items` is left intact (in its original order) for the caller to restore or drop.
+/// Shared by `list.sort()` and `sorted()` so both have identical ordering semantics.
+pub(crate) fn sort_values(
+    items: &mut Vec<Value>,
+    key_fn: Option<&Value>,
+    reverse: bool,
+    caller: &mut impl KeyCaller,
+    interns: &Interns,
+) -> RunResult<()> {
+    let indices = if let Some(key_fn) = key_fn {
         let mut keys: Vec<Value> = Vec::with_capacity(items.len());
-        for item in &items {
-            let elem = item.clone_with_heap(heap);
-            match call_key_function(key, elem, heap, interns, print_writer) {
+        for item in items.iter() {
+            let elem = item.clone_with_heap(caller.heap_mut());
+            match caller.call_key(key_fn, elem, interns) {
                 Ok(key_value) => keys.push(key_value),
                 Err(e) => {
-                    // Clean up and restore items to list on error
-                    for k in keys {
-                        k.drop_with_heap(heap);
-                    }
-                    if let Some(k) = key_fn {
-                        k.drop_with_heap(heap);
-                    }
-                    // Restore items to the list
-                    for item in items {
-                        list.as_vec_mut().push(item);
-                    }
+                    keys.drop_with_heap(caller.heap_mut());
                     return Err(e);
                 }
             }
         }
-        Some(keys)
+        let result = sort_indices(&keys, reverse, caller.heap_mut(), interns);
+        keys.drop_with_heap(caller.heap_mut());
+        result?
     } else {
-        None
+        sort_indices(items, reverse, caller.heap_mut(), interns)?
     };
 
-    // Drop the key function - we're done with it
-    if let Some(k) = key_fn {
-        k.drop_with_heap(heap);
+    // Move values out in sorted order, leaving Undefined placeholders that need no cleanup
+    let sorted_items: Vec<Value> = indices
+        .into_iter()
+        .map(|i| std::mem::replace(&mut items[i], Value::Undefined))
+        .collect();
+    *items = sorted_items;
+    Ok(())
+}
+
+/// Computes the stable sorted order of `values` using Python's `<` comparison.
+///
+/// Returns the permutation of indices that sorts `values`; the values themselves are
+/// left untouched so callers can apply the permutation to a parallel vec (e.g. when
+/// `values` are key-function results). Elements that compare equal keep their original
+/// relative order even when `reverse` is set, matching CPython. Shared by `list.sort()`
+/// and `sorted()` so both have identical ordering semantics and error messages.
+///
+/// Like CPython, only `<` is ever evaluated, so values that are merely unordered (such
+/// as NaN) are sorted without error. The sort is a merge sort over binary insertion
+/// sorted runs rather than `slice::sort_by`, which may panic when the comparison isn't
+/// a total order, and it stops at the first comparison that fails.
+pub(crate) fn sort_indices(
+    values: &[Value],
+    reverse: bool,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<Vec<usize>> {
+    let mut indices: Vec<usize> = (0..values.len()).collect();
+    let mut guard = DepthGuard::default();
+
+    // CPython sorts reversed lists by reversing, sorting and reversing again,
+    // which keeps equal elements in their original order
+    if reverse {
+        indices.reverse();
     }
+    merge_sort(&mut indices, &mut |a, b| {
+        heap.check_time()?;
+        sort_lt(&values[a], &values[b], heap, &mut guard, interns)
+    })?;
+    if reverse {
+        indices.reverse();
+    }
+    Ok(indices)
+}
 
-    // Step 3: Sort indices based on items or key values
-    let len = items.len();
-    let mut indices: Vec<usize> = (0..len).collect();
-    let mut sort_error: Option<RunError> = None;
-    // Create a guard for py_cmp calls. We use a RefCell to allow mutable borrows inside the closure.
-    let guard = std::cell::RefCell::new(DepthGuard::default());
-
-    if let Some(ref keys) = key_values {
-        indices.sort_by(|&a, &b| {
-            if sort_error.is_some() {
-                return Ordering::Equal;
-            }
-            if let Err(e) = heap.check_time() {
-                sort_error = Some(e.into());
-                return Ordering::Equal;
-            }
-            match keys[a].py_cmp(&keys[b], heap, &mut guard.borrow_mut(), interns) {
-                Ok(Some(ord)) => {
-                    if reverse {
-                        ord.reverse()
-                    } else {
-                        ord
-                    }
-                }
-                Ok(None) => {
-                    sort_error = Some(ExcType::type_error(format!(
-                        "'<' not supported between instances of '{}' and '{}'",
-                        keys[a].py_type(heap),
-                        keys[b].py_type(heap)
-                    )));
-                    Ordering::Equal
-                }
-                Err(e) => {
-                    sort_error = Some(e.into());
-                    Ordering::Equal
-                }
-            }
-        });
-    } else {
-        indices.sort_by(|&a, &b| {
-            if sort_error.is_some() {
-                return Ordering::Equal;
-            }
-            if let Err(e) = heap.check_time() {
-                sort_error = Some(e.into());
-                return Ordering::Equal;
-            }
-            match items[a].py_cmp(&items[b], heap, &mut guard.borrow_mut(), interns) {
-                Ok(Some(ord)) => {
-                    if reverse {
-                        ord.reverse()
-                    } else {
-                        ord
-                    }
-                }
-                Ok(None) => {
-                    sort_error = Some(ExcType::type_error(format!(
-                        "'<' not supported between instances of '{}' and '{}'",
-                        items[a].py_type(heap),
-                        items[b].py_type(heap)
-                    )));
-                    Ordering::Equal
-                }
-                Err(e) => {
-                    sort_error = Some(e.into());
-                    Ordering::Equal
-                }
-            }
-        });
+/// Slices up to this length are sorted by binary insertion rather than merged.
+const MIN_MERGE: usize = 32;
+
+/// Stable merge sort of `items` using only the strict `lt` comparison.
+fn merge_sort(items: &mut [usize], lt: &mut impl FnMut(usize, usize) -> RunResult<bool>) -> RunResult<()> {
+    if items.len() <= MIN_MERGE {
+        return binary_insertion_sort(items, lt);
+    }
+    let mid = items.len() / 2;
+    merge_sort(&mut items[..mid], lt)?;
+    merge_sort(&mut items[mid..], lt)?;
+    if !lt(items[mid], items[mid - 1])? {
+        // The halves are already in order
+        return Ok(());
     }
 
-    // Clean up key values
-    if let Some(keys) = key_values {
-        for k in keys {
-            k.drop_with_heap(heap);
+    let left = items[..mid].to_vec();
+    let (mut i, mut j, mut k) = (0, mid, 0);
+    // Once the left copy is used up, the rest of the right half is already in place
+    while i < left.len() {
+        // Take from the right half only when strictly smaller, so equal items stay in order
+        if j < items.len() && lt(items[j], left[i])? {
+            items[k] = items[j];
+            j += 1;
+        } else {
+            items[k] = left[i];
+            i += 1;
         }
+        k += 1;
     }
+    Ok(())
+}
 
-    // Check for sort error
-    if let Some(err) = sort_error {
-        // Restore items to list before returning error
-        for item in items {
-            list.as_vec_mut().push(item);
+/// Stable binary insertion sort, the same as CPython's `binarysort` for short runs.
+fn binary_insertion_sort(items: &mut [usize], lt: &mut impl FnMut(usize, usize) -> RunResult<bool>) -> RunResult<()> {
+    for i in 1..items.len() {
+        let pivot = items[i];
+        let (mut lo, mut hi) = (0, i);
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            if lt(pivot, items[mid])? {
+                hi = mid;
+            } else {
+                lo = mid + 1;
+            }
         }
-        return Err(err);
+        items[lo..=i].rotate_right(1);
     }
+    Ok(())
+}
 
-    // Step 4: Rearrange items in sorted order using index permutation
-    let mut sorted_items: Vec<Value> = Vec::with_capacity(len);
-    for &i in &indices {
-        // Move the value out, replacing with Undefined as placeholder
-        sorted_items.push(std::mem::replace(&mut items[i], Value::Undefined));
+/// Evaluates `a < b` for sorting.
+///
+/// Lists and tuples are compared lexicographically: the first pair of elements that
+/// aren't equal decides, so an error names the element types, as in CPython. A NaN
+/// is never less than anything, and values with no ordering raise the usual TypeError.
+fn sort_lt(
+    a: &Value,
+    b: &Value,
+    heap: &mut Heap<impl ResourceTracker>,
+    guard: &mut DepthGuard,
+    interns: &Interns,
+) -> RunResult<bool> {
+    if let (Value::Ref(id_a), Value::Ref(id_b)) = (a, b) {
+        let sequence_lt = heap.with_two(*id_a, *id_b, |heap, left, right| match (left, right) {
+            (HeapData::List(left), HeapData::List(right)) => {
+                Some(sequence_lt(left.as_slice(), right.as_slice(), heap, guard, interns))
+            }
+            (HeapData::Tuple(left), HeapData::Tuple(right)) => {
+                Some(sequence_lt(left.as_slice(), right.as_slice(), heap, guard, interns))
+            }
+            _ => None,
+        });
+        if let Some(result) = sequence_lt {
+            return result;
+        }
     }
 
-    // Put sorted items back into the list
-    for item in sorted_items {
-        list.as_vec_mut().push(item);
+    match a.py_cmp(b, heap, guard, interns)? {
+        Some(ordering) => Ok(ordering.is_lt()),
+        None if matches!(a, Value::Float(f) if f.is_nan()) || matches!(b, Value::Float(f) if f.is_nan()) => Ok(false),
+        None => Err(ExcType::type_error(format!(
+            "'<' not supported between instances of '{}' and '{}'",
+            a.py_type(heap),
+            b.py_type(heap)
+        ))),
     }
-
-    // items now contains Undefined values - no cleanup needed
-    Ok(())
 }
 
-/// Calls a key function on a single element for sorting.
-///
-/// Currently supports builtin functions directly. User-defined functions return
-/// an error since they would require VM frame management for proper execution.
-fn call_key_function(
-    key_fn: &Value,
-    elem: Value,
+/// Lexicographic `<` for the items of two lists or two tuples.
+fn sequence_lt(
+    left: &[Value],
+    right: &[Value],
     heap: &mut Heap<impl ResourceTracker>,
+    guard: &mut DepthGuard,
     interns: &Interns,
-    print_writer: &mut PrintWriter<'_>,
-) -> Result<Value, RunError> {
-    match key_fn {
-        Value::Builtin(Builtins::Function(builtin)) => {
-            let args = ArgValues::One(elem);
-            builtin.call(heap, args, interns, print_writer)
-        }
-        Value::Builtin(Builtins::Type(t)) => {
-            // Type constructors (int, str, float, etc.) are callable key functions
-            let args = ArgValues::One(elem);
-            t.call(heap, args, interns)
-        }
-        Value::DefFunction(_) | Value::ExtFunction(_) | Value::Ref(_) => {
-            // User-defined or external functions require VM frame management
-            elem.drop_with_heap(heap);
-            Err(ExcType::type_error(
-                "list.sort() key argument must be a builtin function (user-defined functions not yet supported)",
-            ))
-        }
-        _ => {
-            elem.drop_with_heap(heap);
-            Err(ExcType::type_error("list.sort() key must be callable or None"))
+) -> RunResult<bool> {
+    guard.increase_err()?;
+    for (l, r) in left.iter().zip(right) {
+        // Like CPython, identical items are equal without calling `==`
+        if l.is(r) || l.py_eq(r, heap, guard, interns)? {
+            continue;
         }
+        let result = sort_lt(l, r, heap, guard, interns);
+        guard.decrease();
+        return result;
     }
+    guard.decrease();
+    Ok(left.len() < right.len())
 }
 
 /// Writes a formatted sequence of values to a formatter.
@@ -9,21 +9,101 @@
 //! having freestanding functions scattered across the codebase.
 
 use std::{
-    collections::hash_map::DefaultHasher,
     fmt::{self, Display},
-    hash::{Hash, Hasher},
     ops::{Add, Mul, Neg, Sub},
 };
 
 use num_bigint::BigInt;
+use num_integer::Integer;
 use num_traits::{Signed, ToPrimitive, Zero};
 
 use crate::{
     heap::{Heap, HeapData},
     resource::{ResourceError, ResourceTracker},
-    value::Value,
+    value::{NUMERIC_HASH_MODULUS, Value, signed_numeric_hash},
 };
 
+/// Computes CPython's numeric hash of an arbitrary precision int.
+///
+/// See `hash_int` for the scheme; the magnitude is reduced modulo `2**61 - 1`.
+pub(crate) fn hash_big_int(value: &BigInt) -> u64 {
+    let reduced = value.magnitude() % NUMERIC_HASH_MODULUS;
+    let magnitude = reduced.to_i64().expect("value reduced modulo 2**61 - 1 fits in i64");
+    signed_numeric_hash(value.is_negative(), magnitude)
+}
+
+/// Converts the exact ratio `num / den` to the nearest float, rounding half to even.
+///
+/// Unlike converting both sides to floats first, the result is correctly rounded however
+/// large `num` and `den` are, like CPython's true division of ints. Returns `None` if the
+/// result is too large for a float, where CPython raises `OverflowError`.
+///
+/// # Panics
+/// Panics if `den` is zero.
+#[expect(
+    clippy::cast_possible_wrap,
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation
+)]
+pub(crate) fn ratio_to_f64(num: &BigInt, den: &BigInt) -> Option<f64> {
+    // f64's MANTISSA_DIGITS, MIN_EXP and MAX_EXP
+    const MANT_DIG: i64 = 53;
+    const MIN_EXP: i64 = -1021;
+    const MAX_EXP: i64 = 1024;
+
+    assert!(!den.is_zero(), "ratio_to_f64 called with zero denominator");
+    let sign = if num.is_negative() == den.is_negative() {
+        1.0
+    } else {
+        -1.0
+    };
+    let (a, b) = (num.magnitude(), den.magnitude());
+    if a.is_zero() {
+        return Some(sign * 0.0);
+    }
+
+    // 2**(diff - 1) < a / b < 2**(diff + 1)
+    let diff = a.bits() as i64 - b.bits() as i64;
+    if diff > MAX_EXP {
+        return None;
+    } else if diff < MIN_EXP - MANT_DIG - 1 {
+        return Some(sign * 0.0);
+    }
+
+    // Scale so the quotient has two or three more bits than the result needs, noting
+    // whether any bits were lost so ties can be told apart from values just above them
+    let shift = diff.max(MIN_EXP) - MANT_DIG - 2;
+    let mut inexact = false;
+    let x = if shift <= 0 {
+        a << shift.unsigned_abs()
+    } else {
+        inexact = a.trailing_zeros().is_some_and(|zeros| zeros < shift.unsigned_abs());
+        a >> shift.unsigned_abs()
+    };
+    let (q, r) = x.div_rem(b);
+    inexact |= !r.is_zero();
+    let mut q = q.to_u64().expect("quotient has at most 56 bits");
+
+    // Round away the extra bits, half to even
+    let q_bits = i64::from(64 - q.leading_zeros());
+    let extra_bits = q_bits.max(MIN_EXP - shift) - MANT_DIG;
+    let mask = 1u64 << (extra_bits - 1);
+    let low = q | u64::from(inexact);
+    if low & mask != 0 && low & (3 * mask - 1) != 0 {
+        q += mask;
+    }
+    q &= !(2 * mask - 1);
+    if shift + i64::from(64 - q.leading_zeros()) > MAX_EXP {
+        return None;
+    }
+
+    // q has at most 53 significant bits so converting it is exact, as is scaling it by a
+    // power of two, done in two steps so neither factor underflows
+    let half = (shift / 2) as i32;
+    let rest = (shift - shift / 2) as i32;
+    Some(sign * (q as f64) * 2f64.powi(half) * 2f64.powi(rest))
+}
+
 /// Wrapper around `num_bigint::BigInt` for arbitrary precision integers.
 ///
 /// Named `LongInt` to avoid confusion with the external `BigInt` type from `num_bigint`.
@@ -57,28 +137,12 @@ impl LongInt {
         }
     }
 
-    /// Computes a hash consistent with i64 hashing.
+    /// Computes CPython's numeric hash, consistent with `Value::Int` and `Value::Float`.
     ///
-    /// Critical: For values that fit in i64, this must return the same hash as
-    /// hashing the i64 directly. This ensures dict key consistency - e.g.,
-    /// `hash(5)` must equal `hash(LongInt(5))`.
+    /// Critical: equal numbers must hash equally for dict key consistency - e.g.,
+    /// `hash(5)` must equal `hash(LongInt(5))` and `hash(2**64)` must equal `hash(2.0**64)`.
     pub fn hash(&self) -> u64 {
-        // If the LongInt fits in i64, hash as i64 for consistency
-        if let Some(i) = self.0.to_i64() {
-            let mut hasher = DefaultHasher::new();
-            // Hash the i64 discriminant and value to match Value::Int hashing
-            std::mem::discriminant(&Value::Int(0)).hash(&mut hasher);
-            i.hash(&mut hasher);
-            hasher.finish()
-        } else {
-            // For LongInts outside i64 range, use byte representation
-            let mut hasher = DefaultHasher::new();
-            // Use a unique discriminant for LongInt (we use the LongInt's sign and bytes)
-            let (sign, bytes) = self.0.to_bytes_le();
-            sign.hash(&mut hasher);
-            bytes.hash(&mut hasher);
-            hasher.finish()
-        }
+        hash_big_int(&self.0)
     }
 
     /// Estimates memory size in bytes.
@@ -5,10 +5,14 @@
 ///
 /// The `AbstractValue` trait provides a common interface for all heap-allocated
 /// types, enabling efficient dispatch via `enum_dispatch`.
+pub mod array;
 pub mod bytes;
+pub mod csv_writer;
 pub mod dataclass;
 pub mod dict;
+pub mod fraction;
 pub mod iter;
+pub mod lazy_sequence;
 pub mod list;
 pub mod long_int;
 pub mod module;
@@ -20,13 +24,19 @@ pub mod range;
 pub mod set;
 pub mod slice;
 pub mod str;
+pub mod string_io;
 pub mod tuple;
 pub mod r#type;
+pub mod union_type;
 
+pub(crate) use array::Array;
 pub(crate) use bytes::Bytes;
+pub(crate) use csv_writer::CsvWriter;
 pub(crate) use dataclass::Dataclass;
 pub(crate) use dict::Dict;
+pub(crate) use fraction::Fraction;
 pub(crate) use iter::MontyIter;
+pub(crate) use lazy_sequence::LazySequence;
 pub(crate) use list::List;
 pub(crate) use long_int::LongInt;
 pub(crate) use module::Module;
@@ -38,5 +48,7 @@ pub(crate) use range::Range;
 pub(crate) use set::{FrozenSet, Set};
 pub(crate) use slice::Slice;
 pub(crate) use str::Str;
+pub(crate) use string_io::StringIO;
 pub(crate) use tuple::{Tuple, allocate_tuple};
 pub(crate) use r#type::Type;
+pub(crate) use union_type::UnionType;
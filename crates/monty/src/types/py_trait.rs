@@ -70,6 +70,10 @@ pub enum AttrCallResult {
     /// Used by `asyncio.run()` to execute a coroutine without an explicit `await`.
     /// The VM will push the value onto the stack and execute `exec_get_awaitable`.
     AwaitValue(Value),
+    /// The call hands a partial result to the host. VM should yield `FrameExit::Yield`.
+    ///
+    /// Used by `monty_yield()` to stream values before execution completes.
+    Yield(Value),
 }
 
 /// Common operations for heap-allocated Python values.
@@ -6,16 +6,17 @@
 use std::fmt::Write;
 
 use ahash::AHashSet;
+use num_bigint::BigInt;
 
 use crate::{
     args::ArgValues,
     defer_drop,
-    exception_private::{ExcType, RunResult},
-    heap::{Heap, HeapData, HeapId},
-    intern::Interns,
-    resource::{DepthGuard, ResourceError, ResourceTracker},
-    types::{PyTrait, Type},
-    value::Value,
+    exception_private::{ExcType, RunResult, SimpleException},
+    heap::{DropWithHeap, Heap, HeapData, HeapId},
+    intern::{Interns, StaticStrings, StringId},
+    resource::{DepthGuard, ResourceError, ResourceTracker, check_repeat_size},
+    types::{AttrCallResult, List, LongInt, PyTrait, Type},
+    value::{EitherStr, Value},
 };
 
 /// Python range object representing an immutable sequence of integers.
@@ -64,24 +65,12 @@ impl Range {
     }
 
     /// Returns the length of the range (number of elements it will yield).
+    ///
+    /// Computed in `i128` so ranges spanning most of `i64` don't overflow.
     #[must_use]
     pub fn len(&self) -> usize {
-        if self.step > 0 {
-            if self.stop > self.start {
-                let len_i64 = (self.stop - self.start - 1) / self.step + 1;
-                usize::try_from(len_i64).expect("range length guaranteed non-negative")
-            } else {
-                0
-            }
-        } else {
-            // step < 0
-            if self.start > self.stop {
-                let len_i64 = (self.start - self.stop - 1) / (-self.step) + 1;
-                usize::try_from(len_i64).expect("range length guaranteed non-negative")
-            } else {
-                0
-            }
-        }
+        let len = progression_len(i128::from(self.start), i128::from(self.stop), i128::from(self.step));
+        usize::try_from(len).expect("range length fits in usize")
     }
 
     #[must_use]
@@ -95,19 +84,73 @@ impl Range {
     /// with the step (i.e., `(n - start) % step == 0`).
     #[must_use]
     pub fn contains(&self, n: i64) -> bool {
+        self.position(n).is_some()
+    }
+
+    /// Returns the index of `n` in the range, or `None` if it isn't an element (O(1)).
+    ///
+    /// The index is an `i128` since a range can hold more than `i64::MAX` elements.
+    #[must_use]
+    pub fn position(&self, n: i64) -> Option<i128> {
         if self.step > 0 {
             // Forward range: start <= n < stop
             if n < self.start || n >= self.stop {
-                return false;
+                return None;
             }
         } else {
             // Backward range: stop < n <= start
             if n > self.start || n <= self.stop {
-                return false;
+                return None;
             }
         }
         // Check if n is on the step grid
-        (n - self.start) % self.step == 0
+        let offset = i128::from(n) - i128::from(self.start);
+        let step = i128::from(self.step);
+        if offset % step == 0 { Some(offset / step) } else { None }
+    }
+
+    /// Checks if `item` is an element of the range without iterating it.
+    ///
+    /// Only ints, bools and whole floats can be equal to a range element.
+    #[must_use]
+    pub fn contains_value(&self, item: &Value) -> bool {
+        element_int(item).is_some_and(|n| self.contains(n))
+    }
+
+    /// Returns the last element, or `None` if the range is empty.
+    #[must_use]
+    pub fn last(&self) -> Option<i64> {
+        let len = i128::try_from(self.len()).expect("range length fits in i128");
+        let last = i128::from(self.start) + len.checked_sub(1)? * i128::from(self.step);
+        Some(i64::try_from(last).expect("range element fits in i64"))
+    }
+
+    /// Builds a range with `len` elements counting from `first` by `step`.
+    ///
+    /// Used when a slice's natural bounds don't fit in `i64`: a single element gets a
+    /// step of 1 (or -1 at `i64::MAX`), and longer ranges stop at the `i64` limit in
+    /// their direction. Returns `None` when no `i64` range holds exactly these elements,
+    /// i.e. the step doesn't fit or the last element is the limit itself.
+    fn from_progression(first: i128, step: i128, len: i128) -> Option<Self> {
+        if len == 0 {
+            let start = clamp_to_i64(first);
+            return Some(Self::new(start, start, 1));
+        }
+        let start = i64::try_from(first).expect("range element fits in i64");
+        if len == 1 {
+            return Some(match start.checked_add(1) {
+                Some(stop) => Self::new(start, stop, 1),
+                None => Self::new(start, start - 1, -1),
+            });
+        }
+        let step = i64::try_from(step).ok()?;
+        let last = i64::try_from(first + (len - 1) * i128::from(step)).expect("range element fits in i64");
+        let limit = if step > 0 { i64::MAX } else { i64::MIN };
+        if last == limit {
+            return None;
+        }
+        let stop = last.checked_add(step).unwrap_or(limit);
+        Some(Self::new(start, stop, step))
     }
 
     /// Creates a range from the `range()` constructor call.
@@ -156,46 +199,112 @@ impl Range {
             .indices(range_len)
             .map_err(|()| ExcType::value_error_slice_step_zero())?;
 
-        // Calculate the new range parameters
-        // new_start = self.start + start * self.step
-        // new_step = self.step * slice_step
-        // new_stop needs to be computed based on the number of elements
-
-        let new_step = self.step.saturating_mul(step);
-        let start_i64 = i64::try_from(start).expect("start index fits in i64");
-        let new_start = self.start.saturating_add(start_i64.saturating_mul(self.step));
-
-        // Calculate the number of elements in the sliced range
-        // try_from succeeds for non-negative step; step==0 rejected by slice.indices()
-        let num_elements = if let Ok(step_usize) = usize::try_from(step) {
-            // Forward iteration
-            if start >= stop {
-                0
-            } else {
-                ((stop - start - 1) / step_usize) + 1
-            }
+        // Like CPython, the new bounds are the elements at the slice's start and stop indices,
+        // so `range(10)[2:5:2]` is `range(2, 5, 2)`. A stop past the start of a backward
+        // slice is the index -1.
+        let start_index = i128::try_from(start).expect("slice index fits in i128");
+        let stop_index = if step < 0 && stop > range_len {
+            -1
         } else {
-            // Backward iteration
-            let step_abs = usize::try_from(-step).expect("step is negative so -step is positive");
-            if stop > range_len {
-                // stop sentinel means "go to the beginning"
-                (start / step_abs) + 1
-            } else if start <= stop {
-                0
-            } else {
-                ((start - stop - 1) / step_abs) + 1
+            i128::try_from(stop).expect("slice index fits in i128")
+        };
+        let element_at = |index: i128| i128::from(self.start) + index * i128::from(self.step);
+        let (new_start, new_stop) = (element_at(start_index), element_at(stop_index));
+        let new_step = i128::from(self.step) * i128::from(step);
+
+        let new_range = if let (Ok(start), Ok(stop), Ok(step)) = (
+            i64::try_from(new_start),
+            i64::try_from(new_stop),
+            i64::try_from(new_step),
+        ) {
+            Self::new(start, stop, step)
+        } else {
+            // A bound or the step is past the `i64` limits, so describe the same elements
+            // another way, or list them when no `i64` range can
+            let len = progression_len(new_start, new_stop, new_step);
+            match Self::from_progression(new_start, new_step, len) {
+                Some(range) => range,
+                None => return Self::progression_list(new_start, new_step, len, heap),
             }
         };
+        Ok(Value::Ref(heap.allocate(HeapData::Range(new_range))?))
+    }
 
-        // new_stop = new_start + num_elements * new_step
-        let num_elements_i64 = i64::try_from(num_elements).expect("num_elements fits in i64");
-        let new_stop = new_start.saturating_add(num_elements_i64.saturating_mul(new_step));
+    /// Materializes a slice of a range that no `i64` range can represent as a list.
+    fn progression_list(first: i128, step: i128, len: i128, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
+        let len = usize::try_from(len).expect("range length fits in usize");
+        check_repeat_size(std::mem::size_of::<Value>(), len, heap.tracker())?;
+        let items = (0..len)
+            .map(|i| {
+                let i = i128::try_from(i).expect("range index fits in i128");
+                Value::Int(i64::try_from(first + i * step).expect("range element fits in i64"))
+            })
+            .collect();
+        Ok(Value::Ref(heap.allocate(HeapData::List(List::new(items)))?))
+    }
+}
 
-        let new_range = Self::new(new_start, new_stop, new_step);
-        Ok(Value::Ref(heap.allocate(HeapData::Range(new_range))?))
+/// Returns the number of elements from `start` towards `stop` (exclusive) by `step`.
+fn progression_len(start: i128, stop: i128, step: i128) -> i128 {
+    if step > 0 && stop > start {
+        (stop - start - 1) / step + 1
+    } else if step < 0 && start > stop {
+        (start - stop - 1) / -step + 1
+    } else {
+        0
+    }
+}
+
+/// Returns the integer `item` equals, if it's a value that can equal a range element.
+fn element_int(item: &Value) -> Option<i64> {
+    match item {
+        Value::Int(i) => Some(*i),
+        Value::Bool(b) => Some(i64::from(*b)),
+        // e.g., 3.0 in range(5) is True, but 3.5 in range(5) is False
+        Value::Float(f) if f.fract() == 0.0 && *f >= i64::MIN as f64 && *f < i64::MAX as f64 => {
+            // Safe conversion: we've verified it's a whole number in i64 range
+            #[expect(clippy::cast_possible_truncation)]
+            let n = *f as i64;
+            Some(n)
+        }
+        // Ranges are bounded by i64, so long ints are never elements
+        _ => None,
     }
 }
 
+/// Clamps an `i128` bound to the `i64` range.
+fn clamp_to_i64(value: i128) -> i64 {
+    i64::try_from(value).unwrap_or(if value < 0 { i64::MIN } else { i64::MAX })
+}
+
+/// Implements Python's `range.index(value)` method.
+///
+/// Ints are located in O(1); other values that equal an element (bools and whole floats)
+/// are found the same way but report CPython's generic sequence error when missing.
+fn range_index(range: &Range, args: ArgValues, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
+    let value = args.get_one_arg("range.index", heap)?;
+    defer_drop!(value, heap);
+    if let Some(index) = element_int(value).and_then(|n| range.position(n)) {
+        return match i64::try_from(index) {
+            Ok(index) => Ok(Value::Int(index)),
+            Err(_) => Ok(LongInt::new(BigInt::from(index)).into_value(heap)?),
+        };
+    }
+    let msg = match value {
+        Value::Int(n) => format!("{n} is not in range"),
+        _ => "sequence.index(x): x not in sequence".to_owned(),
+    };
+    Err(SimpleException::new_msg(ExcType::ValueError, msg).into())
+}
+
+/// Implements Python's `range.count(value)` method, which is always 0 or 1.
+fn range_count(range: &Range, args: ArgValues, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
+    let value = args.get_one_arg("range.count", heap)?;
+    let count = range.contains_value(&value);
+    value.drop_with_heap(heap);
+    Ok(Value::Int(i64::from(count)))
+}
+
 impl Default for Range {
     fn default() -> Self {
         Self::from_stop(0)
@@ -224,8 +333,9 @@ impl PyTrait for Range {
         // Extract integer index, accepting Int, Bool (True=1, False=0), and LongInt
         let index = key.as_index(heap, Type::Range)?;
 
-        // Get range length for normalization
-        let len = i64::try_from(self.len()).expect("range length exceeds i64::MAX");
+        // Get range length for normalization, in i128 as a range can hold more than i64::MAX elements
+        let len = i128::try_from(self.len()).expect("range length fits in i128");
+        let index = i128::from(index);
         let normalized = if index < 0 { index + len } else { index };
 
         // Bounds check
@@ -233,13 +343,9 @@ impl PyTrait for Range {
             return Err(ExcType::range_index_error());
         }
 
-        // Calculate: start + normalized * step
-        // Use checked arithmetic to avoid overflow in intermediate calculations
-        let offset = normalized
-            .checked_mul(self.step)
-            .and_then(|v| self.start.checked_add(v))
-            .expect("range element calculation overflowed");
-        Ok(Value::Int(offset))
+        // Calculate: start + normalized * step, which lies between start and stop
+        let element = i128::from(self.start) + normalized * i128::from(self.step);
+        Ok(Value::Int(i64::try_from(element).expect("range element fits in i64")))
     }
 
     fn py_eq(
@@ -256,11 +362,44 @@ impl PyTrait for Range {
         if len1 != len2 {
             return Ok(false);
         }
-        // Same length - compare first element and step (if non-empty)
-        if len1 == 0 {
-            return Ok(true); // Both empty
+        // Same length - compare first element, and step if there's more than one
+        match len1 {
+            0 => Ok(true),
+            1 => Ok(self.start == other.start),
+            _ => Ok(self.start == other.start && self.step == other.step),
+        }
+    }
+
+    fn py_call_attr(
+        &mut self,
+        heap: &mut Heap<impl ResourceTracker>,
+        attr: &EitherStr,
+        args: ArgValues,
+        interns: &Interns,
+    ) -> RunResult<Value> {
+        match attr.static_string() {
+            Some(StaticStrings::Index) => range_index(self, args, heap),
+            Some(StaticStrings::Count) => range_count(self, args, heap),
+            _ => {
+                args.drop_with_heap(heap);
+                Err(ExcType::attribute_error(Type::Range, attr.as_str(interns)))
+            }
         }
-        Ok(self.start == other.start && self.step == other.step)
+    }
+
+    fn py_getattr(
+        &self,
+        attr_id: StringId,
+        _heap: &mut Heap<impl ResourceTracker>,
+        _interns: &Interns,
+    ) -> RunResult<Option<AttrCallResult>> {
+        let value = match StaticStrings::from_string_id(attr_id) {
+            Some(StaticStrings::Start) => self.start,
+            Some(StaticStrings::Stop) => self.stop,
+            Some(StaticStrings::Step) => self.step,
+            _ => return Ok(None),
+        };
+        Ok(Some(AttrCallResult::Value(Value::Int(value))))
     }
 
     fn py_bool(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> bool {
@@ -1,4 +1,4 @@
-use std::fmt::Write;
+use std::{cmp::Ordering, fmt::Write};
 
 use ahash::AHashSet;
 use hashbrown::HashTable;
@@ -12,7 +12,7 @@ use crate::{
     intern::{Interns, StaticStrings},
     resource::{DepthGuard, ResourceError, ResourceTracker},
     types::Type,
-    value::{EitherStr, Value},
+    value::{BitwiseOp, EitherStr, Value},
 };
 
 /// Entry in the set storage, containing a value and its cached hash.
@@ -121,7 +121,8 @@ impl SetStorage {
     /// the set, it will be dropped.
     fn add(&mut self, value: Value, heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<bool> {
         let Some(hash) = value.py_hash(heap, interns) else {
-            let err = ExcType::type_error_unhashable_set_element(value.py_type(heap));
+            let err =
+                ExcType::type_error_unhashable_set_element(value.py_type(heap), value.unhashable_type(heap, interns));
             value.drop_with_heap(heap);
             return Err(err);
         };
@@ -152,9 +153,9 @@ impl SetStorage {
     /// Returns `Ok(true)` if the element was removed, `Ok(false)` if not found.
     /// Returns `Err` if the key is unhashable.
     fn remove(&mut self, value: &Value, heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<bool> {
-        let hash = value
-            .py_hash(heap, interns)
-            .ok_or_else(|| ExcType::type_error_unhashable_set_element(value.py_type(heap)))?;
+        let hash = value.py_hash(heap, interns).ok_or_else(|| {
+            ExcType::type_error_unhashable_set_element(value.py_type(heap), value.unhashable_type(heap, interns))
+        })?;
 
         // Create a local guard for equality comparisons.
         let mut guard = DepthGuard::default();
@@ -241,9 +242,9 @@ impl SetStorage {
 
     /// Checks if the set contains a value.
     pub fn contains(&self, value: &Value, heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<bool> {
-        let hash = value
-            .py_hash(heap, interns)
-            .ok_or_else(|| ExcType::type_error_unhashable_set_element(value.py_type(heap)))?;
+        let hash = value.py_hash(heap, interns).ok_or_else(|| {
+            ExcType::type_error_unhashable_set_element(value.py_type(heap), value.unhashable_type(heap, interns))
+        })?;
 
         // Create a guard for value equality comparisons. Set values are typically
         // shallow (strings, ints, tuples of primitives), so recursion errors
@@ -252,9 +253,9 @@ impl SetStorage {
         Ok(self
             .indices
             .find(hash, |&idx| {
-                value
-                    .py_eq(&self.entries[idx].value, heap, &mut guard, interns)
-                    .unwrap_or(false)
+                let entry = &self.entries[idx].value;
+                // Like CPython, the element itself matches without calling `==` (e.g. a NaN)
+                value.is(entry) || value.py_eq(entry, heap, &mut guard, interns).unwrap_or(false)
             })
             .is_some())
     }
@@ -283,7 +284,7 @@ impl SetStorage {
     }
 
     /// Compares two sets for equality.
-    fn eq(
+    pub(crate) fn eq(
         &self,
         other: &Self,
         heap: &mut Heap<impl ResourceTracker>,
@@ -754,10 +755,8 @@ impl PyTrait for Set {
         _other: &Self,
         _heap: &mut Heap<impl ResourceTracker>,
     ) -> Result<Option<Value>, crate::resource::ResourceError> {
-        // This is called from heap.rs with two Sets
-        // We need interns for contains check, but py_sub doesn't have it
-        // This is a limitation - we'll need to handle this differently
-        // For now, return None to indicate not supported via this path
+        // Membership checks need interns, which py_sub doesn't have; the VM applies
+        // set difference through `set_binary_op` instead.
         Ok(None)
     }
 }
@@ -1243,7 +1242,7 @@ impl PyTrait for FrozenSet {
         _other: &Self,
         _heap: &mut Heap<impl ResourceTracker>,
     ) -> Result<Option<Value>, crate::resource::ResourceError> {
-        // Same limitation as Set - needs interns
+        // Same as Set: the VM applies set difference through `set_binary_op`
         Ok(None)
     }
 }
@@ -1350,6 +1349,186 @@ impl FrozenSet {
     }
 }
 
+/// Set algebra operator applied by `|`, `&`, `-` and `^` between two sets.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum SetOp {
+    Union,
+    Intersection,
+    Difference,
+    SymmetricDifference,
+}
+
+impl SetOp {
+    /// Maps a bitwise operator to its set algebra counterpart, if it has one.
+    pub fn from_bitwise(op: BitwiseOp) -> Option<Self> {
+        match op {
+            BitwiseOp::Or => Some(Self::Union),
+            BitwiseOp::And => Some(Self::Intersection),
+            BitwiseOp::Xor => Some(Self::SymmetricDifference),
+            BitwiseOp::LShift | BitwiseOp::RShift => None,
+        }
+    }
+
+    /// Computes the operation on two storages, returning a new storage.
+    fn apply(
+        self,
+        lhs: &SetStorage,
+        rhs: &SetStorage,
+        heap: &mut Heap<impl ResourceTracker>,
+        interns: &Interns,
+    ) -> RunResult<SetStorage> {
+        match self {
+            Self::Union => lhs.union(rhs, heap, interns),
+            Self::Intersection => lhs.intersection(rhs, heap, interns),
+            Self::Difference => lhs.difference(rhs, heap, interns),
+            Self::SymmetricDifference => lhs.symmetric_difference(rhs, heap, interns),
+        }
+    }
+}
+
+/// Applies a set operator (`a | b`, `a & b`, `a - b`, `a ^ b`) between two values.
+///
+/// Both operands must be a set or frozenset; unlike the named methods, the operators
+/// do not accept arbitrary iterables. The result has the type of the left operand,
+/// so `frozenset | set` is a frozenset and `set | frozenset` is a set.
+///
+/// Returns `Ok(None)` if either operand is not a set, so the caller can raise the
+/// usual "unsupported operand type(s)" error.
+pub(crate) fn set_binary_op(
+    lhs: &Value,
+    rhs: &Value,
+    op: SetOp,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<Option<Value>> {
+    let Some(storage) = apply_to_operands(lhs, rhs, op, heap, interns)? else {
+        return Ok(None);
+    };
+    let data = if is_frozenset(lhs, heap) {
+        HeapData::FrozenSet(FrozenSet(storage))
+    } else {
+        HeapData::Set(Set(storage))
+    };
+    Ok(Some(Value::Ref(heap.allocate(data)?)))
+}
+
+/// Applies an in-place set operator (`a |= b`, `a &= b`, `a -= b`, `a ^= b`).
+///
+/// Only a mutable `set` on the left is updated in place, so every other reference to it
+/// observes the change. Returns `Ok(false)` for anything else (including frozensets,
+/// which rebind to a new object), letting the caller fall back to the binary operator.
+pub(crate) fn set_inplace_op(
+    lhs: &Value,
+    rhs: &Value,
+    op: SetOp,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<bool> {
+    let Value::Ref(lhs_id) = lhs else {
+        return Ok(false);
+    };
+    if !matches!(heap.get(*lhs_id), HeapData::Set(_)) {
+        return Ok(false);
+    }
+    let Some(storage) = apply_to_operands(lhs, rhs, op, heap, interns)? else {
+        return Ok(false);
+    };
+    let has_refs = storage.has_refs();
+    let HeapData::Set(set) = heap.get_mut(*lhs_id) else {
+        panic!("set_inplace_op: expected Set on heap");
+    };
+    let old = std::mem::replace(&mut set.0, storage);
+    old.drop_all_values(heap);
+    if has_refs {
+        heap.mark_potential_cycle();
+    }
+    Ok(true)
+}
+
+/// Compares two sets by inclusion for `<`, `<=`, `>` and `>=`.
+///
+/// Sets are only partially ordered: `Less` means a proper subset, `Greater` a proper
+/// superset, and `None` means neither contains the other. Returns `Ok(None)` as well
+/// when either operand is not a set; use `is_set_pair` to tell the cases apart.
+pub(crate) fn set_partial_cmp(
+    lhs: &Value,
+    rhs: &Value,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<Option<Ordering>> {
+    let Some(lhs_storage) = copy_set_operand(lhs, heap) else {
+        return Ok(None);
+    };
+    let Some(rhs_storage) = copy_set_operand(rhs, heap) else {
+        lhs_storage.drop_all_values(heap);
+        return Ok(None);
+    };
+    let result = match lhs_storage.len().cmp(&rhs_storage.len()) {
+        Ordering::Greater => rhs_storage.is_subset(&lhs_storage, heap, interns),
+        _ => lhs_storage.is_subset(&rhs_storage, heap, interns),
+    }
+    .map(|contained| {
+        let ordering = lhs_storage.len().cmp(&rhs_storage.len());
+        contained.then_some(ordering)
+    });
+    lhs_storage.drop_all_values(heap);
+    rhs_storage.drop_all_values(heap);
+    result
+}
+
+/// Returns true if both values are sets or frozensets.
+pub(crate) fn is_set_pair(lhs: &Value, rhs: &Value, heap: &Heap<impl ResourceTracker>) -> bool {
+    is_set_like(lhs, heap) && is_set_like(rhs, heap)
+}
+
+/// Returns true if the value is a set or frozenset.
+fn is_set_like(value: &Value, heap: &Heap<impl ResourceTracker>) -> bool {
+    matches!(value, Value::Ref(id) if matches!(heap.get(*id), HeapData::Set(_) | HeapData::FrozenSet(_)))
+}
+
+/// Returns true if the value is a frozenset.
+fn is_frozenset(value: &Value, heap: &Heap<impl ResourceTracker>) -> bool {
+    matches!(value, Value::Ref(id) if matches!(heap.get(*id), HeapData::FrozenSet(_)))
+}
+
+/// Copies the storage of a set or frozenset, or returns `None` for any other value.
+///
+/// The copy owns a reference to each element so it stays valid while the heap is
+/// borrowed mutably; release it with `drop_all_values`.
+fn copy_set_operand(value: &Value, heap: &mut Heap<impl ResourceTracker>) -> Option<SetStorage> {
+    let entries = match value {
+        Value::Ref(id) => match heap.get(*id) {
+            HeapData::Set(set) => set.0.copy_entries(),
+            HeapData::FrozenSet(set) => set.0.copy_entries(),
+            _ => return None,
+        },
+        _ => return None,
+    };
+    SetStorage::inc_refs_for_entries(&entries, heap);
+    Some(SetStorage::from_entries(entries))
+}
+
+/// Applies `op` to copies of both operands, or returns `None` if either is not a set.
+fn apply_to_operands(
+    lhs: &Value,
+    rhs: &Value,
+    op: SetOp,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<Option<SetStorage>> {
+    let Some(lhs_storage) = copy_set_operand(lhs, heap) else {
+        return Ok(None);
+    };
+    let Some(rhs_storage) = copy_set_operand(rhs, heap) else {
+        lhs_storage.drop_all_values(heap);
+        return Ok(None);
+    };
+    let result = op.apply(&lhs_storage, &rhs_storage, heap, interns);
+    lhs_storage.drop_all_values(heap);
+    rhs_storage.drop_all_values(heap);
+    result.map(Some)
+}
+
 // Custom serde implementations for SetStorage, Set, and FrozenSet.
 // Only serialize entries; rebuild the indices hash table on deserialize.
 
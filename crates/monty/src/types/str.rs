@@ -8,15 +8,24 @@ use std::{borrow::Cow, fmt};
 use ahash::AHashSet;
 use smallvec::smallvec;
 
-use super::{Bytes, MontyIter, PyTrait};
+use super::{
+    Bytes, MontyIter, PyTrait,
+    bytes::{decode_bytes, get_codec_str_arg},
+};
 use crate::{
     args::ArgValues,
     defer_drop, defer_drop_mut,
     exception_private::{ExcType, RunResult},
+    hashing::stable_hash,
     heap::{DropWithHeap, Heap, HeapData, HeapGuard, HeapId},
     intern::{Interns, StaticStrings, StringId},
+    object::SharedStr,
     resource::{DepthGuard, ResourceError, ResourceTracker},
     types::Type,
+    unicode_tables::{
+        ALPHA, CASE_IGNORABLE, CASEFOLD_MAPPING, DECIMAL, DIGIT, LOWER, NUMERIC, PRINTABLE, SPACE, TITLE,
+        TITLE_MAPPING, UPPER, XID_CONTINUE, XID_START, in_ranges, lookup_mapping,
+    },
     value::{EitherStr, Value},
 };
 
@@ -24,77 +33,191 @@ use crate::{
 ///
 /// Wraps a Rust `String` and provides Python-compatible operations.
 /// `len()` returns the number of Unicode codepoints (characters), matching Python semantics.
-#[derive(Debug, Clone, PartialEq, Default, serde::Serialize, serde::Deserialize)]
-pub(crate) struct Str(String);
+///
+/// Strings passed in as `MontyObject::SharedString` reference the embedder's buffer instead of
+/// copying it, the buffer is only copied if the string is mutated in place (e.g. by `+=`).
+/// Serialization doesn't distinguish the two, a deserialized string always owns its data.
+#[derive(Debug, Clone)]
+pub(crate) struct Str(StrBuf);
+
+/// Storage of a [`Str`].
+#[derive(Debug, Clone)]
+enum StrBuf {
+    /// String owned by the heap.
+    Owned(String),
+    /// String buffer shared with the embedder.
+    Shared(SharedStr),
+}
 
 impl Str {
     /// Creates a new Str from a Rust String.
     #[must_use]
     pub fn new(s: String) -> Self {
-        Self(s)
+        Self(StrBuf::Owned(s))
+    }
+
+    /// Creates a Str referencing a shared buffer without copying it.
+    #[must_use]
+    pub fn shared(s: SharedStr) -> Self {
+        Self(StrBuf::Shared(s))
     }
 
     /// Returns a reference to the inner string.
     #[must_use]
     pub fn as_str(&self) -> &str {
-        &self.0
+        match &self.0 {
+            StrBuf::Owned(s) => s,
+            StrBuf::Shared(s) => s,
+        }
     }
 
-    /// Returns a mutable reference to the inner string.
+    /// Returns a mutable reference to the inner string, copying a shared buffer first.
     pub fn as_string_mut(&mut self) -> &mut String {
-        &mut self.0
+        if let StrBuf::Shared(s) = &self.0 {
+            self.0 = StrBuf::Owned(s.to_string());
+        }
+        match &mut self.0 {
+            StrBuf::Owned(s) => s,
+            StrBuf::Shared(_) => unreachable!("shared buffer was just copied"),
+        }
     }
 
     /// Creates a string from the `str()` constructor call.
     ///
     /// - `str()` with no args returns an empty string
     /// - `str(x)` converts x to its string representation using `py_str`
+    /// - `str(b, encoding[, errors])` decodes a bytes object (see `decode_bytes`)
     pub fn init(heap: &mut Heap<impl ResourceTracker>, args: ArgValues, interns: &Interns) -> RunResult<Value> {
-        let value = args.get_zero_one_arg("str", heap)?;
-        match value {
-            None => Ok(Value::InternString(StaticStrings::EmptyString.into())),
-            Some(v) => {
-                defer_drop!(v, heap);
-                let mut guard = DepthGuard::default();
-                let s = v.py_str(heap, &mut guard, interns).into_owned();
-                allocate_string(s, heap)
+        let pos = args.into_pos_only("str", heap)?;
+        defer_drop_mut!(pos, heap);
+        if pos.len() > 3 {
+            return Err(ExcType::type_error_at_most("str", 3, pos.len()));
+        }
+        let Some(v) = pos.next() else {
+            return Ok(Value::InternString(StaticStrings::EmptyString.into()));
+        };
+        defer_drop!(v, heap);
+        let encoding = pos.next();
+        defer_drop!(encoding, heap);
+        let errors = pos.next();
+        defer_drop!(errors, heap);
+
+        if encoding.is_none() && errors.is_none() {
+            // Like CPython, `str()` of a str returns the same object
+            if v.is_str(heap) {
+                return Ok(v.clone_with_heap(heap));
             }
+            let mut guard = DepthGuard::default();
+            let s = v.py_str(heap, &mut guard, interns).into_owned();
+            return allocate_string(s, heap);
         }
+
+        // str(object, encoding, errors) decodes a bytes object
+        let encoding = match encoding {
+            Some(encoding) => get_codec_str_arg(encoding, "str", "encoding", heap, interns)?,
+            None => "utf-8",
+        };
+        let errors = match errors {
+            Some(errors) => get_codec_str_arg(errors, "str", "errors", heap, interns)?,
+            None => "strict",
+        };
+        let bytes = match v {
+            Value::InternBytes(bytes_id) => Some(interns.get_bytes(*bytes_id)),
+            Value::Ref(heap_id) => match heap.get(*heap_id) {
+                HeapData::Bytes(b) => Some(b.as_slice()),
+                _ => None,
+            },
+            _ => None,
+        };
+        let Some(bytes) = bytes else {
+            if v.is_str(heap) {
+                return Err(ExcType::type_error("decoding str is not supported"));
+            }
+            return Err(ExcType::type_error(format!(
+                "decoding to str: need a bytes-like object, {} found",
+                v.py_type(heap)
+            )));
+        };
+        let decoded = decode_bytes(bytes, encoding, errors)?;
+        allocate_string(decoded, heap)
     }
 
     /// Handles slice-based indexing for strings.
     ///
     /// Returns a new string containing the selected characters (Unicode-aware).
     fn getitem_slice(&self, slice: &crate::types::Slice, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
-        let char_count = self.0.chars().count();
+        let char_count = self.as_str().chars().count();
         let (start, stop, step) = slice
             .indices(char_count)
             .map_err(|()| ExcType::value_error_slice_step_zero())?;
 
-        let result_str = get_str_slice(&self.0, start, stop, step);
+        let result_str = get_str_slice(self.as_str(), start, stop, step);
         let heap_id = heap.allocate(HeapData::Str(Self::from(result_str)))?;
         Ok(Value::Ref(heap_id))
     }
 }
 
+impl Default for Str {
+    fn default() -> Self {
+        Self::new(String::new())
+    }
+}
+
+impl PartialEq for Str {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+/// Serialized as a newtype around the string, the same whether or not the buffer is shared.
+impl serde::Serialize for Str {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_newtype_struct("Str", self.as_str())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for Str {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        /// Mirror of the serialized form.
+        #[derive(serde::Deserialize)]
+        #[serde(rename = "Str")]
+        struct OwnedStr(String);
+
+        OwnedStr::deserialize(deserializer).map(|OwnedStr(s)| Self::new(s))
+    }
+}
+
 impl From<String> for Str {
     fn from(s: String) -> Self {
-        Self(s)
+        Self::new(s)
     }
 }
 
 impl From<&str> for Str {
     fn from(s: &str) -> Self {
-        Self(s.to_string())
+        Self::new(s.to_string())
     }
 }
 
 impl From<Str> for String {
     fn from(value: Str) -> Self {
-        value.0
+        match value.0 {
+            StrBuf::Owned(s) => s,
+            StrBuf::Shared(s) => s.to_string(),
+        }
     }
 }
 
+/// Hashes string content for dict keys, sets and `hash()`.
+///
+/// Every string representation (ASCII, static and interned `InternString`s, and heap `Str`s)
+/// must hash through this function so equal strings hash equally however they're stored.
+/// The result doesn't include the heap's hash seed, callers apply it with `hashing::apply_seed`.
+#[must_use]
+pub(crate) fn hash_str(s: &str) -> u64 {
+    stable_hash(s)
+}
+
 /// Allocates a string, using interned versions when possible.
 ///
 /// Optimizations:
@@ -200,10 +323,10 @@ pub(crate) fn get_str_slice(s: &str, start: usize, stop: usize, step: i64) -> St
 }
 
 impl std::ops::Deref for Str {
-    type Target = String;
+    type Target = str;
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        self.as_str()
     }
 }
 
@@ -213,12 +336,12 @@ impl PyTrait for Str {
     }
 
     fn py_estimate_size(&self) -> usize {
-        std::mem::size_of::<Self>() + self.0.len()
+        std::mem::size_of::<Self>() + self.as_str().len()
     }
 
     fn py_len(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> Option<usize> {
         // Count Unicode characters, not bytes, to match Python semantics
-        Some(self.0.chars().count())
+        Some(self.as_str().chars().count())
     }
 
     fn py_getitem(&self, key: &Value, heap: &mut Heap<impl ResourceTracker>, _interns: &Interns) -> RunResult<Value> {
@@ -235,7 +358,7 @@ impl PyTrait for Str {
         let index = key.as_index(heap, Type::Str)?;
 
         // Use single-pass indexing to avoid Vec<char> allocation
-        let c = get_char_at_index(&self.0, index).ok_or_else(ExcType::str_index_error)?;
+        let c = get_char_at_index(self.as_str(), index).ok_or_else(ExcType::str_index_error)?;
         Ok(allocate_char(c, heap)?)
     }
 
@@ -246,7 +369,7 @@ impl PyTrait for Str {
         _guard: &mut DepthGuard,
         _interns: &Interns,
     ) -> Result<bool, ResourceError> {
-        Ok(self.0 == other.0)
+        Ok(self.as_str() == other.as_str())
     }
 
     /// Interns don't contain nested heap references.
@@ -255,7 +378,7 @@ impl PyTrait for Str {
     }
 
     fn py_bool(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> bool {
-        !self.0.is_empty()
+        !self.as_str().is_empty()
     }
 
     fn py_repr_fmt(
@@ -266,7 +389,7 @@ impl PyTrait for Str {
         _guard: &mut DepthGuard,
         _interns: &Interns,
     ) -> fmt::Result {
-        string_repr_fmt(&self.0, f)
+        string_repr_fmt(self.as_str(), f)
     }
 
     fn py_str(
@@ -275,7 +398,7 @@ impl PyTrait for Str {
         _guard: &mut DepthGuard,
         _interns: &Interns,
     ) -> Cow<'static, str> {
-        self.0.clone().into()
+        self.as_str().to_owned().into()
     }
 
     fn py_add(
@@ -284,7 +407,7 @@ impl PyTrait for Str {
         heap: &mut Heap<impl ResourceTracker>,
         _interns: &Interns,
     ) -> Result<Option<Value>, crate::resource::ResourceError> {
-        let result = format!("{}{}", self.0, other.0);
+        let result = format!("{}{}", self.as_str(), other.as_str());
         let id = heap.allocate(HeapData::Str(result.into()))?;
         Ok(Some(Value::Ref(id)))
     }
@@ -299,10 +422,10 @@ impl PyTrait for Str {
         match &other {
             Value::Ref(other_id) => {
                 if Some(*other_id) == self_id {
-                    let rhs = self.0.clone();
-                    self.0.push_str(&rhs);
+                    let rhs = self.as_str().to_owned();
+                    self.as_string_mut().push_str(&rhs);
                 } else if let HeapData::Str(rhs) = heap.get(*other_id) {
-                    self.0.push_str(rhs.as_str());
+                    self.as_string_mut().push_str(rhs.as_str());
                 } else {
                     return Ok(false);
                 }
@@ -311,7 +434,7 @@ impl PyTrait for Str {
                 Ok(true)
             }
             Value::InternString(string_id) => {
-                self.0.push_str(interns.get_str(*string_id));
+                self.as_string_mut().push_str(interns.get_str(*string_id));
                 Ok(true)
             }
             _ => Ok(false),
@@ -331,7 +454,7 @@ impl PyTrait for Str {
         };
 
         let (args, heap) = args_guard.into_parts();
-        call_str_method_impl(&self.0, method, args, heap, interns)
+        call_str_method_impl(self.as_str(), method, args, heap, interns)
     }
 }
 
@@ -443,6 +566,10 @@ fn call_str_method_impl(
             args.check_zero_args("str.isdecimal", heap)?;
             Ok(Value::Bool(str_isdecimal(s)))
         }
+        StaticStrings::Isprintable => {
+            args.check_zero_args("str.isprintable", heap)?;
+            Ok(Value::Bool(str_isprintable(s)))
+        }
         // Search methods
         StaticStrings::Find => str_find(s, args, heap, interns),
         StaticStrings::Rfind => str_rfind(s, args, heap, interns),
@@ -606,7 +733,11 @@ impl fmt::Display for StringRepr<'_> {
 
 /// Implements Python's `str.lower()` method.
 fn str_lower(s: &str, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
-    allocate_string(s.to_lowercase(), heap)
+    let mut result = String::with_capacity(s.len());
+    for (index, c) in s.char_indices() {
+        push_lower(&mut result, s, index, c);
+    }
+    allocate_string(result, heap)
 }
 
 /// Implements Python's `str.upper()` method.
@@ -616,37 +747,34 @@ fn str_upper(s: &str, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value>
 
 /// Implements Python's `str.capitalize()` method.
 ///
-/// Returns a copy of the string with its first character capitalized and the rest lowercased.
+/// Returns a copy of the string with its first character titlecased and the rest lowercased.
 fn str_capitalize(s: &str, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
-    let mut chars = s.chars();
-    let result = match chars.next() {
-        None => String::new(),
-        Some(first) => {
-            let mut result = first.to_uppercase().to_string();
-            for c in chars {
-                result.extend(c.to_lowercase());
-            }
-            result
+    let mut result = String::with_capacity(s.len());
+    for (index, c) in s.char_indices() {
+        if index == 0 {
+            push_title(&mut result, c);
+        } else {
+            push_lower(&mut result, s, index, c);
         }
-    };
+    }
     allocate_string(result, heap)
 }
 
 /// Implements Python's `str.title()` method.
 ///
-/// Returns a titlecased version of the string where words start with an uppercase
+/// Returns a titlecased version of the string where words start with a titlecase
 /// character and the remaining characters are lowercase.
 fn str_title(s: &str, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
     let mut result = String::with_capacity(s.len());
     let mut prev_is_cased = false;
 
-    for c in s.chars() {
+    for (index, c) in s.char_indices() {
         if prev_is_cased {
-            result.extend(c.to_lowercase());
+            push_lower(&mut result, s, index, c);
         } else {
-            result.extend(c.to_uppercase());
+            push_title(&mut result, c);
         }
-        prev_is_cased = c.is_alphabetic();
+        prev_is_cased = is_cased(c);
     }
 
     allocate_string(result, heap)
@@ -655,13 +783,14 @@ fn str_title(s: &str, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value>
 /// Implements Python's `str.swapcase()` method.
 ///
 /// Returns a copy of the string with uppercase characters converted to lowercase and vice versa.
+/// Titlecase characters such as `ǅ` are neither, so they are left unchanged.
 fn str_swapcase(s: &str, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
     let mut result = String::with_capacity(s.len());
 
-    for c in s.chars() {
-        if c.is_uppercase() {
-            result.extend(c.to_lowercase());
-        } else if c.is_lowercase() {
+    for (index, c) in s.char_indices() {
+        if is_upper(c) {
+            push_lower(&mut result, s, index, c);
+        } else if is_lower(c) {
             result.extend(c.to_uppercase());
         } else {
             result.push(c);
@@ -674,10 +803,76 @@ fn str_swapcase(s: &str, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Val
 /// Implements Python's `str.casefold()` method.
 ///
 /// Returns a casefolded copy of the string. Casefolding is similar to lowercasing
-/// but more aggressive because it is intended for caseless string matching.
+/// but more aggressive because it is intended for caseless string matching,
+/// e.g. `'ß'` folds to `'ss'` and the final sigma `'ς'` to `'σ'`.
 fn str_casefold(s: &str, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
-    // Rust's to_lowercase() is equivalent to Unicode casefolding for most purposes
-    allocate_string(s.to_lowercase(), heap)
+    let mut result = String::with_capacity(s.len());
+    for c in s.chars() {
+        match lookup_mapping(CASEFOLD_MAPPING, c) {
+            Some(folded) => result.push_str(folded),
+            None => result.extend(c.to_lowercase()),
+        }
+    }
+    allocate_string(result, heap)
+}
+
+/// Appends the lowercase form of `c`, the character at byte `index` of `s`.
+///
+/// A capital sigma becomes the final form `ς` when it ends a word, as in CPython.
+fn push_lower(result: &mut String, s: &str, index: usize, c: char) {
+    if c == 'Σ' && is_final_sigma(s, index) {
+        result.push('ς');
+    } else {
+        result.extend(c.to_lowercase());
+    }
+}
+
+/// Returns whether the capital sigma at byte `index` of `s` ends a word.
+///
+/// That is when a cased character precedes it and none follows it, skipping
+/// case-ignorable characters such as apostrophes and combining marks in both directions.
+fn is_final_sigma(s: &str, index: usize) -> bool {
+    let not_ignorable = |c: &char| !in_ranges(CASE_IGNORABLE, *c);
+    let before = s[..index].chars().rev().find(not_ignorable);
+    let after = s[index + 'Σ'.len_utf8()..].chars().find(not_ignorable);
+    before.is_some_and(is_cased) && !after.is_some_and(is_cased)
+}
+
+/// Appends the titlecase form of `c`, which differs from its uppercase for digraphs
+/// like `ǆ` (titlecased to `ǅ`) and ligatures like `ﬁ` (titlecased to `Fi`).
+fn push_title(result: &mut String, c: char) {
+    match lookup_mapping(TITLE_MAPPING, c) {
+        Some(title) => result.push_str(title),
+        None => result.extend(c.to_uppercase()),
+    }
+}
+
+/// Returns whether `c` has the Unicode Lowercase property.
+fn is_lower(c: char) -> bool {
+    in_ranges(LOWER, c)
+}
+
+/// Returns whether `c` has the Unicode Uppercase property.
+fn is_upper(c: char) -> bool {
+    in_ranges(UPPER, c)
+}
+
+/// Returns whether `c` is a titlecase letter (category Lt).
+fn is_titlecase(c: char) -> bool {
+    in_ranges(TITLE, c)
+}
+
+/// Returns whether `c` is cased: lowercase, uppercase or titlecase.
+fn is_cased(c: char) -> bool {
+    is_lower(c) || is_upper(c) || is_titlecase(c)
+}
+
+/// Returns whether `c` is whitespace according to `str.isspace()`.
+///
+/// This is also what `str.split()` and `str.strip()` split and strip on by default. It differs
+/// from `char::is_whitespace` in including the separators U+001C to U+001F.
+fn is_space(c: char) -> bool {
+    in_ranges(SPACE, c)
 }
 
 // =============================================================================
@@ -687,8 +882,10 @@ fn str_casefold(s: &str, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Val
 /// Implements Python's `str.isalpha()` method.
 ///
 /// Returns True if all characters in the string are alphabetic and there is at least one character.
+/// In Python, alphabetic means a letter category (Lu, Ll, Lt, Lm, Lo), so unlike `char::is_alphabetic`
+/// it excludes letter numerals like `'Ⅻ'` and combining marks.
 fn str_isalpha(s: &str) -> bool {
-    !s.is_empty() && s.chars().all(char::is_alphabetic)
+    !s.is_empty() && s.chars().all(|c| in_ranges(ALPHA, c))
 }
 
 /// Implements Python's `str.isdigit()` method.
@@ -697,30 +894,32 @@ fn str_isalpha(s: &str) -> bool {
 /// In Python, digits include decimal digits (Nd) plus characters with Numeric_Type=Digit
 /// (superscripts, subscripts, circled digits, etc.).
 fn str_isdigit(s: &str) -> bool {
-    !s.is_empty() && s.chars().all(is_unicode_digit)
+    !s.is_empty() && s.chars().all(|c| in_ranges(DIGIT, c))
 }
 
 /// Implements Python's `str.isalnum()` method.
 ///
 /// Returns True if all characters in the string are alphanumeric and there is at least one character.
+/// A character is alphanumeric if `isalpha()` or `isnumeric()` is true for it; numeric
+/// characters include all decimal characters and digits.
 fn str_isalnum(s: &str) -> bool {
-    !s.is_empty() && s.chars().all(char::is_alphanumeric)
+    !s.is_empty() && s.chars().all(|c| in_ranges(ALPHA, c) || in_ranges(NUMERIC, c))
 }
 
 /// Implements Python's `str.isnumeric()` method.
 ///
 /// Returns True if all characters in the string are numeric and there is at least one character.
-/// In Python, numeric includes decimal digits (Nd), letter numerals (Nl), and other numerals (No).
-/// Rust's `char::is_numeric()` checks for all of these categories.
+/// In Python, numeric characters are those with a Numeric_Type of Decimal, Digit or Numeric,
+/// which includes vulgar fractions, Roman numerals and CJK ideographs like `'五'`.
 fn str_isnumeric(s: &str) -> bool {
-    !s.is_empty() && s.chars().all(char::is_numeric)
+    !s.is_empty() && s.chars().all(|c| in_ranges(NUMERIC, c))
 }
 
 /// Implements Python's `str.isspace()` method.
 ///
 /// Returns True if all characters in the string are whitespace and there is at least one character.
 fn str_isspace(s: &str) -> bool {
-    !s.is_empty() && s.chars().all(char::is_whitespace)
+    !s.is_empty() && s.chars().all(is_space)
 }
 
 /// Implements Python's `str.islower()` method.
@@ -729,10 +928,10 @@ fn str_isspace(s: &str) -> bool {
 fn str_islower(s: &str) -> bool {
     let mut has_cased = false;
     for c in s.chars() {
-        if c.is_uppercase() {
+        if is_upper(c) || is_titlecase(c) {
             return false;
         }
-        if c.is_lowercase() {
+        if is_lower(c) {
             has_cased = true;
         }
     }
@@ -745,10 +944,10 @@ fn str_islower(s: &str) -> bool {
 fn str_isupper(s: &str) -> bool {
     let mut has_cased = false;
     for c in s.chars() {
-        if c.is_lowercase() {
+        if is_lower(c) || is_titlecase(c) {
             return false;
         }
-        if c.is_uppercase() {
+        if is_upper(c) {
             has_cased = true;
         }
     }
@@ -761,175 +960,16 @@ fn str_isupper(s: &str) -> bool {
 /// Decimal characters are those in Unicode category Nd (Decimal_Number) - digits that can be used
 /// to form numbers in base 10.
 fn str_isdecimal(s: &str) -> bool {
-    !s.is_empty() && s.chars().all(is_unicode_decimal)
-}
-
-/// Checks if a character is a Unicode decimal digit (Nd category).
-///
-/// This covers decimal digit ranges from various scripts including ASCII, Arabic-Indic,
-/// Devanagari, Bengali, Thai, Fullwidth, and many others.
-fn is_unicode_decimal(c: char) -> bool {
-    let cp = c as u32;
-    matches!(
-        cp,
-        // Basic Latin (ASCII digits)
-        0x0030..=0x0039
-        // Arabic-Indic digits
-        | 0x0660..=0x0669
-        // Extended Arabic-Indic digits
-        | 0x06F0..=0x06F9
-        // NKo digits
-        | 0x07C0..=0x07C9
-        // Devanagari digits
-        | 0x0966..=0x096F
-        // Bengali digits
-        | 0x09E6..=0x09EF
-        // Gurmukhi digits
-        | 0x0A66..=0x0A6F
-        // Gujarati digits
-        | 0x0AE6..=0x0AEF
-        // Oriya digits
-        | 0x0B66..=0x0B6F
-        // Tamil digits
-        | 0x0BE6..=0x0BEF
-        // Telugu digits
-        | 0x0C66..=0x0C6F
-        // Kannada digits
-        | 0x0CE6..=0x0CEF
-        // Malayalam digits
-        | 0x0D66..=0x0D6F
-        // Sinhala Lith digits
-        | 0x0DE6..=0x0DEF
-        // Thai digits
-        | 0x0E50..=0x0E59
-        // Lao digits
-        | 0x0ED0..=0x0ED9
-        // Tibetan digits
-        | 0x0F20..=0x0F29
-        // Myanmar digits
-        | 0x1040..=0x1049
-        // Myanmar Shan digits
-        | 0x1090..=0x1099
-        // Khmer digits
-        | 0x17E0..=0x17E9
-        // Mongolian digits
-        | 0x1810..=0x1819
-        // Limbu digits
-        | 0x1946..=0x194F
-        // New Tai Lue digits
-        | 0x19D0..=0x19D9
-        // Tai Tham Hora digits
-        | 0x1A80..=0x1A89
-        // Tai Tham Tham digits
-        | 0x1A90..=0x1A99
-        // Balinese digits
-        | 0x1B50..=0x1B59
-        // Sundanese digits
-        | 0x1BB0..=0x1BB9
-        // Lepcha digits
-        | 0x1C40..=0x1C49
-        // Ol Chiki digits
-        | 0x1C50..=0x1C59
-        // Vai digits
-        | 0xA620..=0xA629
-        // Saurashtra digits
-        | 0xA8D0..=0xA8D9
-        // Kayah Li digits
-        | 0xA900..=0xA909
-        // Javanese digits
-        | 0xA9D0..=0xA9D9
-        // Myanmar Tai Laing digits
-        | 0xA9F0..=0xA9F9
-        // Cham digits
-        | 0xAA50..=0xAA59
-        // Meetei Mayek digits
-        | 0xABF0..=0xABF9
-        // Fullwidth digits
-        | 0xFF10..=0xFF19
-        // Osmanya digits
-        | 0x104A0..=0x104A9
-        // Hanifi Rohingya digits
-        | 0x10D30..=0x10D39
-        // Brahmi digits
-        | 0x11066..=0x1106F
-        // Sora Sompeng digits
-        | 0x110F0..=0x110F9
-        // Chakma digits
-        | 0x11136..=0x1113F
-        // Sharada digits
-        | 0x111D0..=0x111D9
-        // Khudawadi digits
-        | 0x112F0..=0x112F9
-        // Newa digits
-        | 0x11450..=0x11459
-        // Tirhuta digits
-        | 0x114D0..=0x114D9
-        // Modi digits
-        | 0x11650..=0x11659
-        // Takri digits
-        | 0x116C0..=0x116C9
-        // Ahom digits
-        | 0x11730..=0x11739
-        // Warang Citi digits
-        | 0x118E0..=0x118E9
-        // Dives Akuru digits
-        | 0x11950..=0x11959
-        // Bhaiksuki digits
-        | 0x11C50..=0x11C59
-        // Masaram Gondi digits
-        | 0x11D50..=0x11D59
-        // Gunjala Gondi digits
-        | 0x11DA0..=0x11DA9
-        // Adlam digits
-        | 0x1E950..=0x1E959
-        // Segmented digits
-        | 0x1FBF0..=0x1FBF9
-    )
-}
-
-/// Checks if a character is a Unicode digit (isdigit).
-///
-/// This includes decimal digits (Nd) plus characters with Numeric_Type=Digit
-/// such as superscripts, subscripts, and circled digits.
-fn is_unicode_digit(c: char) -> bool {
-    // First check if it's a decimal digit
-    if is_unicode_decimal(c) {
-        return true;
-    }
-
-    let cp = c as u32;
-    matches!(
-        cp,
-        // Superscripts (², ³)
-        0x00B2..=0x00B3
-        // Superscript 1
-        | 0x00B9
-        // Superscript digits 0, 4-9
-        | 0x2070
-        | 0x2074..=0x2079
-        // Subscript digits 0-9
-        | 0x2080..=0x2089
-        // Circled digits 1-9
-        | 0x2460..=0x2468
-        // Circled digit 0
-        | 0x24EA
-        // Circled digits 10-20
-        | 0x2469..=0x2473
-        // Parenthesized digits 1-9
-        | 0x2474..=0x247C
-        // Period digits 1-9
-        | 0x2488..=0x2490
-        // Double circled digits 1-10
-        | 0x24F5..=0x24FE
-        // Dingbat circled sans-serif digits 1-10
-        | 0x2780..=0x2789
-        // Dingbat negative circled digits 1-10
-        | 0x278A..=0x2793
-        // Dingbat circled sans-serif digits 1-10
-        | 0x24FF
-        // Fullwidth digit zero (already in decimal, but include for completeness)
-        // | 0xFF10..=0xFF19  // Already covered by is_unicode_decimal
-    )
+    !s.is_empty() && s.chars().all(|c| in_ranges(DECIMAL, c))
+}
+
+/// Implements Python's `str.isprintable()` method.
+///
+/// Returns True if all characters in the string are printable, which is also the case for an
+/// empty string. Nonprintable characters are control, format, private use, unassigned and
+/// separator characters, except for the ASCII space.
+fn str_isprintable(s: &str) -> bool {
+    s.chars().all(|c| in_ranges(PRINTABLE, c))
 }
 
 // =============================================================================
@@ -1248,7 +1288,7 @@ fn str_strip(s: &str, args: ArgValues, heap: &mut Heap<impl ResourceTracker>, in
     let chars = parse_strip_arg("str.strip", args, heap, interns)?;
     let result = match &chars {
         Some(c) => s.trim_matches(|ch| c.contains(ch)).to_owned(),
-        None => s.trim().to_owned(),
+        None => s.trim_matches(is_space).to_owned(),
     };
     allocate_string(result, heap)
 }
@@ -1260,7 +1300,7 @@ fn str_lstrip(s: &str, args: ArgValues, heap: &mut Heap<impl ResourceTracker>, i
     let chars = parse_strip_arg("str.lstrip", args, heap, interns)?;
     let result = match &chars {
         Some(c) => s.trim_start_matches(|ch| c.contains(ch)).to_owned(),
-        None => s.trim_start().to_owned(),
+        None => s.trim_start_matches(is_space).to_owned(),
     };
     allocate_string(result, heap)
 }
@@ -1272,7 +1312,7 @@ fn str_rstrip(s: &str, args: ArgValues, heap: &mut Heap<impl ResourceTracker>, i
     let chars = parse_strip_arg("str.rstrip", args, heap, interns)?;
     let result = match &chars {
         Some(c) => s.trim_end_matches(|ch| c.contains(ch)).to_owned(),
-        None => s.trim_end().to_owned(),
+        None => s.trim_end_matches(is_space).to_owned(),
     };
     allocate_string(result, heap)
 }
@@ -1361,7 +1401,7 @@ fn str_split(s: &str, args: ArgValues, heap: &mut Heap<impl ResourceTracker>, in
         None => {
             // Split on whitespace, filtering empty strings
             if maxsplit < 0 {
-                s.split_whitespace().collect()
+                s.split(is_space).filter(|part| !part.is_empty()).collect()
             } else {
                 // Safe cast: we've checked maxsplit >= 0
                 let max = usize::try_from(maxsplit).unwrap_or(usize::MAX);
@@ -1408,7 +1448,7 @@ fn str_rsplit(s: &str, args: ArgValues, heap: &mut Heap<impl ResourceTracker>, i
         None => {
             // Split on whitespace from right
             if maxsplit < 0 {
-                s.split_whitespace().collect()
+                s.split(is_space).filter(|part| !part.is_empty()).collect()
             } else {
                 // Safe cast: we've checked maxsplit >= 0
                 let max = usize::try_from(maxsplit).unwrap_or(usize::MAX);
@@ -1520,13 +1560,13 @@ fn parse_split_args(
 /// Split string on whitespace, returning at most `maxsplit + 1` parts.
 fn split_whitespace_n(s: &str, maxsplit: usize) -> Vec<&str> {
     let mut parts = Vec::new();
-    let mut remaining = s.trim_start();
+    let mut remaining = s.trim_start_matches(is_space);
     let mut count = 0;
 
     while !remaining.is_empty() && count < maxsplit {
-        if let Some(end) = remaining.find(|c: char| c.is_whitespace()) {
+        if let Some(end) = remaining.find(is_space) {
             parts.push(&remaining[..end]);
-            remaining = remaining[end..].trim_start();
+            remaining = remaining[end..].trim_start_matches(is_space);
             count += 1;
         } else {
             break;
@@ -1543,13 +1583,13 @@ fn split_whitespace_n(s: &str, maxsplit: usize) -> Vec<&str> {
 /// Split string on whitespace from the right, returning at most `maxsplit + 1` parts.
 fn rsplit_whitespace_n(s: &str, maxsplit: usize) -> Vec<&str> {
     let mut parts = Vec::new();
-    let mut remaining = s.trim_end();
+    let mut remaining = s.trim_end_matches(is_space);
     let mut count = 0;
 
     while !remaining.is_empty() && count < maxsplit {
-        if let Some(start) = remaining.rfind(|c: char| c.is_whitespace()) {
-            parts.push(&remaining[start + 1..]);
-            remaining = remaining[..start].trim_end();
+        if let Some((start, space)) = remaining.char_indices().rev().find(|&(_, c)| is_space(c)) {
+            parts.push(&remaining[start + space.len_utf8()..]);
+            remaining = remaining[..start].trim_end_matches(is_space);
             count += 1;
         } else {
             break;
@@ -2050,39 +2090,11 @@ fn parse_encode_args(
 /// Implements Python's `str.isidentifier()` predicate.
 ///
 /// Returns True if the string is a valid Python identifier according to
-/// the language definition (starts with letter or underscore, followed by
-/// letters, digits, or underscores). Empty strings return False.
+/// the language definition: an XID_Start character or underscore, followed by
+/// XID_Continue characters. Empty strings return False.
 fn str_isidentifier(s: &str) -> bool {
-    if s.is_empty() {
-        return false;
-    }
-
     let mut chars = s.chars();
-
-    // First character must be a letter (Unicode) or underscore
-    let first = chars.next().unwrap();
-    if !is_xid_start(first) && first != '_' {
-        return false;
-    }
-
-    // Remaining characters must be letters, digits (Unicode), or underscores
-    chars.all(is_xid_continue)
-}
-
-/// Checks if a character is valid at the start of an identifier (XID_Start).
-///
-/// This is a simplified implementation that covers ASCII and common Unicode letters.
-/// Python uses the full Unicode XID_Start property.
-fn is_xid_start(c: char) -> bool {
-    c.is_alphabetic()
-}
-
-/// Checks if a character is valid in the continuation of an identifier (XID_Continue).
-///
-/// This is a simplified implementation that covers ASCII and common Unicode.
-/// Python uses the full Unicode XID_Continue property.
-fn is_xid_continue(c: char) -> bool {
-    c.is_alphanumeric() || c == '_'
+    chars.next().is_some_and(|first| in_ranges(XID_START, first)) && chars.all(|c| in_ranges(XID_CONTINUE, c))
 }
 
 /// Implements Python's `str.istitle()` predicate.
@@ -2099,14 +2111,14 @@ fn str_istitle(s: &str) -> bool {
     let mut has_cased = false;
 
     for c in s.chars() {
-        if c.is_uppercase() {
-            // Uppercase must follow uncased
+        if is_upper(c) || is_titlecase(c) {
+            // Uppercase and titlecase must follow uncased
             if prev_cased {
                 return false;
             }
             prev_cased = true;
             has_cased = true;
-        } else if c.is_lowercase() {
+        } else if is_lower(c) {
             // Lowercase must follow cased
             if !prev_cased {
                 return false;
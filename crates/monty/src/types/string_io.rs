@@ -0,0 +1,452 @@
+//! Python `io.StringIO`, an in-memory text stream.
+//!
+//! Monty has no file objects, so `StringIO` is the stream `csv` writers write to and the
+//! usual way to build up or re-read text. Positions count characters, as in CPython.
+//!
+//! Iterating a `StringIO` yields its remaining lines: `MontyIter::new` snapshots them with
+//! [`StringIO::take_lines`], which moves the position to the end straight away.
+
+use std::fmt::Write;
+
+use ahash::AHashSet;
+
+use crate::{
+    args::ArgValues,
+    defer_drop, defer_drop_mut,
+    exception_private::{ExcType, RunError, RunResult, SimpleException},
+    heap::{DropWithHeap, Heap, HeapData, HeapId},
+    intern::{Interns, StaticStrings, StringId},
+    resource::{DepthGuard, ResourceError, ResourceTracker, check_repeat_size},
+    types::{AttrCallResult, List, MontyIter, PyTrait, Type, str::allocate_string},
+    value::{EitherStr, Value},
+};
+
+/// An in-memory text stream created by `io.StringIO()`.
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+pub(crate) struct StringIO {
+    /// The text of the stream.
+    buffer: String,
+    /// Number of characters in `buffer`.
+    char_len: usize,
+    /// Stream position in characters, which may be past the end after `seek()`.
+    pos: usize,
+    /// Set by `close()`, after which every other operation raises `ValueError`.
+    closed: bool,
+}
+
+impl StringIO {
+    /// Creates a stream holding `initial_value`, positioned at the start.
+    #[must_use]
+    pub fn new(initial_value: &str) -> Self {
+        Self {
+            buffer: initial_value.to_owned(),
+            char_len: initial_value.chars().count(),
+            pos: 0,
+            closed: false,
+        }
+    }
+
+    /// Implements `io.StringIO(initial_value='')`.
+    ///
+    /// The `newline` parameter isn't supported: text is stored exactly as written.
+    pub fn init(heap: &mut Heap<impl ResourceTracker>, args: ArgValues, interns: &Interns) -> RunResult<Value> {
+        let (mut pos, kwargs) = args.into_parts();
+        let given = pos.len();
+        if given > 1 {
+            pos.drop_with_heap(heap);
+            kwargs.drop_with_heap(heap);
+            return Err(ExcType::type_error_too_many_positional("StringIO", 0, 1, given, 0));
+        }
+        let positional = pos.next();
+        defer_drop!(positional, heap);
+        let keyword = kwargs.extract_one("StringIO", "initial_value", heap, interns)?;
+        defer_drop!(keyword, heap);
+        if positional.is_some() && keyword.is_some() {
+            return Err(ExcType::type_error_duplicate_arg("StringIO", "initial_value"));
+        }
+
+        let initial_value = match positional.as_ref().or(keyword.as_ref()) {
+            None | Some(Value::None) => "",
+            Some(value) => str_arg(value, heap, interns).ok_or_else(|| {
+                ExcType::type_error(format!(
+                    "initial_value must be str or None, not {}",
+                    value.py_type(heap)
+                ))
+            })?,
+        };
+        let string_io = Self::new(initial_value);
+        Ok(Value::Ref(heap.allocate(HeapData::StringIO(string_io))?))
+    }
+
+    /// Writes `s` at the current position, overwriting any text there, and returns the
+    /// number of characters written.
+    ///
+    /// Writing after seeking past the end fills the gap with NUL characters, like CPython.
+    pub fn write_str(&mut self, s: &str, tracker: &impl ResourceTracker) -> RunResult<usize> {
+        self.check_open()?;
+        let count = s.chars().count();
+        if count == 0 {
+            return Ok(0);
+        }
+        if self.pos > self.char_len {
+            let gap = self.pos - self.char_len;
+            check_repeat_size(1, gap, tracker)?;
+            self.buffer.extend(std::iter::repeat_n('\0', gap));
+            self.char_len = self.pos;
+        }
+        if self.pos == self.char_len {
+            self.buffer.push_str(s);
+            self.char_len += count;
+        } else {
+            let overwritten_end = (self.pos + count).min(self.char_len);
+            let range = self.byte_offset(self.pos)..self.byte_offset(overwritten_end);
+            self.buffer.replace_range(range, s);
+            self.char_len += count - (overwritten_end - self.pos);
+        }
+        self.pos += count;
+        Ok(count)
+    }
+
+    /// Reads the remaining lines for iteration, leaving the position at the end.
+    ///
+    /// # Errors
+    /// Returns `ValueError` if the stream is closed.
+    pub fn take_lines(&mut self) -> RunResult<Vec<String>> {
+        if self.closed {
+            return Err(SimpleException::new_msg(ExcType::ValueError, "I/O operation on closed file.").into());
+        }
+        let mut lines = Vec::new();
+        loop {
+            let line = self.read_line(None);
+            if line.is_empty() {
+                return Ok(lines);
+            }
+            lines.push(line);
+        }
+    }
+
+    /// Returns the byte offset of the character at `index`, or the buffer length past the end.
+    fn byte_offset(&self, index: usize) -> usize {
+        if self.buffer.len() == self.char_len {
+            index.min(self.buffer.len())
+        } else {
+            self.buffer
+                .char_indices()
+                .nth(index)
+                .map_or(self.buffer.len(), |(offset, _)| offset)
+        }
+    }
+
+    fn check_open(&self) -> RunResult<()> {
+        if self.closed { Err(closed_file_error()) } else { Ok(()) }
+    }
+
+    /// Reads up to `limit` characters (all remaining ones for `None`).
+    fn read_chars(&mut self, limit: Option<usize>) -> String {
+        if self.pos >= self.char_len {
+            return String::new();
+        }
+        let end = limit.map_or(self.char_len, |limit| self.pos.saturating_add(limit).min(self.char_len));
+        let text = self.buffer[self.byte_offset(self.pos)..self.byte_offset(end)].to_owned();
+        self.pos = end;
+        text
+    }
+
+    /// Reads up to and including the next `\n`, stopping early after `limit` characters.
+    fn read_line(&mut self, limit: Option<usize>) -> String {
+        if self.pos >= self.char_len {
+            return String::new();
+        }
+        let rest = &self.buffer[self.byte_offset(self.pos)..];
+        let mut count = 0;
+        let mut end = rest.len();
+        for (offset, c) in rest.char_indices() {
+            if limit == Some(count) {
+                end = offset;
+                break;
+            }
+            count += 1;
+            if c == '\n' {
+                end = offset + 1;
+                break;
+            }
+        }
+        let line = rest[..end].to_owned();
+        self.pos += count;
+        line
+    }
+}
+
+/// Replaces a `StringIO` about to be iterated with a list of its remaining lines.
+///
+/// Streams are iterated by snapshotting their lines up front, so writes made to the stream
+/// during the loop aren't seen.
+pub(crate) fn iter_lines(heap_id: HeapId, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
+    let HeapData::StringIO(string_io) = heap.get_mut(heap_id) else {
+        unreachable!("iter_lines called on a value which isn't a StringIO")
+    };
+    let lines = string_io.take_lines()?;
+    allocate_lines(lines, heap)
+}
+
+/// Allocates a list of str values holding `lines`.
+fn allocate_lines(lines: Vec<String>, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
+    let mut values = Vec::with_capacity(lines.len());
+    for line in lines {
+        match allocate_string(line, heap) {
+            Ok(value) => values.push(value),
+            Err(err) => {
+                values.drop_with_heap(heap);
+                return Err(err);
+            }
+        }
+    }
+    Ok(Value::Ref(heap.allocate(HeapData::List(List::new(values)))?))
+}
+
+/// Returns the `ValueError` raised by operations on a closed stream.
+fn closed_file_error() -> RunError {
+    SimpleException::new_msg(ExcType::ValueError, "I/O operation on closed file").into()
+}
+
+/// Returns the text of a str value, or `None` for other types.
+fn str_arg<'a>(value: &'a Value, heap: &'a Heap<impl ResourceTracker>, interns: &'a Interns) -> Option<&'a str> {
+    match value {
+        Value::InternString(string_id) => Some(interns.get_str(*string_id)),
+        Value::Ref(heap_id) => match heap.get(*heap_id) {
+            HeapData::Str(s) => Some(s.as_str()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Returns the `TypeError` raised when writing a value which isn't a str.
+fn type_error_not_str(value: &Value, heap: &Heap<impl ResourceTracker>) -> RunError {
+    ExcType::type_error(format!("string argument expected, got '{}'", value.py_type(heap)))
+}
+
+/// Converts an optional size argument, where `None` and negative sizes mean no limit.
+fn size_arg(size: Option<&Value>, heap: &Heap<impl ResourceTracker>) -> RunResult<Option<usize>> {
+    match size {
+        None | Some(Value::None) => Ok(None),
+        Some(size) => Ok(usize::try_from(size.as_int(heap)?).ok()),
+    }
+}
+
+/// Converts a stream position to a Python int.
+fn position_value(pos: usize) -> Value {
+    Value::Int(i64::try_from(pos).unwrap_or(i64::MAX))
+}
+
+impl PyTrait for StringIO {
+    fn py_type(&self, _heap: &Heap<impl ResourceTracker>) -> Type {
+        Type::StringIO
+    }
+
+    fn py_estimate_size(&self) -> usize {
+        std::mem::size_of::<Self>() + self.buffer.capacity()
+    }
+
+    fn py_len(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> Option<usize> {
+        None
+    }
+
+    fn py_eq(
+        &self,
+        _other: &Self,
+        _heap: &mut Heap<impl ResourceTracker>,
+        _guard: &mut DepthGuard,
+        _interns: &Interns,
+    ) -> Result<bool, ResourceError> {
+        // Streams compare by identity, which is handled at the Value level
+        Ok(false)
+    }
+
+    fn py_bool(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> bool {
+        true
+    }
+
+    fn py_repr_fmt(
+        &self,
+        f: &mut impl Write,
+        _heap: &Heap<impl ResourceTracker>,
+        _heap_ids: &mut AHashSet<HeapId>,
+        _guard: &mut DepthGuard,
+        _interns: &Interns,
+    ) -> std::fmt::Result {
+        f.write_str("<_io.StringIO object>")
+    }
+
+    fn py_dec_ref_ids(&mut self, _stack: &mut Vec<HeapId>) {
+        // The buffer is plain text, nothing to do
+    }
+
+    fn py_call_attr(
+        &mut self,
+        heap: &mut Heap<impl ResourceTracker>,
+        attr: &EitherStr,
+        args: ArgValues,
+        interns: &Interns,
+    ) -> RunResult<Value> {
+        let Some(method) = attr.static_string() else {
+            args.drop_with_heap(heap);
+            return Err(ExcType::attribute_error(Type::StringIO, attr.as_str(interns)));
+        };
+
+        match method {
+            StaticStrings::Write => {
+                let s = args.get_one_arg("write", heap)?;
+                defer_drop!(s, heap);
+                let Some(text) = str_arg(s, heap, interns) else {
+                    return Err(type_error_not_str(s, heap));
+                };
+                let count = self.write_str(text, heap.tracker())?;
+                Ok(position_value(count))
+            }
+            StaticStrings::Writelines => {
+                let lines = args.get_one_arg("writelines", heap)?;
+                if let Err(err) = self.check_open() {
+                    lines.drop_with_heap(heap);
+                    return Err(err);
+                }
+                let iter = MontyIter::new(lines, heap, interns)?;
+                defer_drop_mut!(iter, heap);
+                while let Some(line) = iter.for_next(heap, interns)? {
+                    defer_drop!(line, heap);
+                    let Some(text) = str_arg(line, heap, interns) else {
+                        return Err(type_error_not_str(line, heap));
+                    };
+                    self.write_str(text, heap.tracker())?;
+                }
+                Ok(Value::None)
+            }
+            StaticStrings::Getvalue => {
+                args.check_zero_args("getvalue", heap)?;
+                self.check_open()?;
+                allocate_string(self.buffer.clone(), heap)
+            }
+            StaticStrings::Read => {
+                let size = args.get_zero_one_arg("read", heap)?;
+                defer_drop!(size, heap);
+                let limit = size_arg(size.as_ref(), heap)?;
+                self.check_open()?;
+                allocate_string(self.read_chars(limit), heap)
+            }
+            StaticStrings::Readline => {
+                let size = args.get_zero_one_arg("readline", heap)?;
+                defer_drop!(size, heap);
+                let limit = size_arg(size.as_ref(), heap)?;
+                self.check_open()?;
+                allocate_string(self.read_line(limit), heap)
+            }
+            StaticStrings::Readlines => {
+                let hint = args.get_zero_one_arg("readlines", heap)?;
+                defer_drop!(hint, heap);
+                let hint = size_arg(hint.as_ref(), heap)?.filter(|hint| *hint > 0);
+                self.check_open()?;
+                let mut lines = Vec::new();
+                let mut total = 0;
+                loop {
+                    let line = self.read_line(None);
+                    if line.is_empty() {
+                        break;
+                    }
+                    total += line.chars().count();
+                    lines.push(line);
+                    if hint.is_some_and(|hint| total >= hint) {
+                        break;
+                    }
+                }
+                allocate_lines(lines, heap)
+            }
+            StaticStrings::Seek => {
+                let (offset, whence) = args.get_one_two_args("seek", heap)?;
+                defer_drop!(offset, heap);
+                defer_drop!(whence, heap);
+                let offset = offset.as_int(heap)?;
+                let whence = match whence {
+                    Some(whence) => whence.as_int(heap)?,
+                    None => 0,
+                };
+                self.check_open()?;
+                if !(0..=2).contains(&whence) {
+                    return Err(SimpleException::new_msg(
+                        ExcType::ValueError,
+                        format!("Invalid whence ({whence}, should be 0, 1 or 2)"),
+                    )
+                    .into());
+                }
+                if whence == 0 {
+                    let Ok(pos) = usize::try_from(offset) else {
+                        return Err(SimpleException::new_msg(
+                            ExcType::ValueError,
+                            format!("Negative seek position {offset}"),
+                        )
+                        .into());
+                    };
+                    self.pos = pos;
+                } else if offset != 0 {
+                    return Err(
+                        SimpleException::new_msg(ExcType::OSError, "Can't do nonzero cur-relative seeks").into(),
+                    );
+                } else if whence == 2 {
+                    self.pos = self.char_len;
+                }
+                Ok(position_value(self.pos))
+            }
+            StaticStrings::Tell => {
+                args.check_zero_args("tell", heap)?;
+                self.check_open()?;
+                Ok(position_value(self.pos))
+            }
+            StaticStrings::Truncate => {
+                let size = args.get_zero_one_arg("truncate", heap)?;
+                defer_drop!(size, heap);
+                let size = match size {
+                    None | Some(Value::None) => self.pos,
+                    Some(size) => {
+                        let size = size.as_int(heap)?;
+                        usize::try_from(size).map_err(|_| {
+                            RunError::from(SimpleException::new_msg(
+                                ExcType::ValueError,
+                                format!("Negative size value {size}"),
+                            ))
+                        })?
+                    }
+                };
+                self.check_open()?;
+                if size < self.char_len {
+                    let end = self.byte_offset(size);
+                    self.buffer.truncate(end);
+                    self.char_len = size;
+                }
+                Ok(position_value(size))
+            }
+            StaticStrings::Close => {
+                args.check_zero_args("close", heap)?;
+                self.closed = true;
+                self.buffer = String::new();
+                self.char_len = 0;
+                Ok(Value::None)
+            }
+            _ => {
+                args.drop_with_heap(heap);
+                Err(ExcType::attribute_error(Type::StringIO, attr.as_str(interns)))
+            }
+        }
+    }
+
+    fn py_getattr(
+        &self,
+        attr_id: StringId,
+        _heap: &mut Heap<impl ResourceTracker>,
+        _interns: &Interns,
+    ) -> RunResult<Option<AttrCallResult>> {
+        if attr_id == StaticStrings::Closed {
+            Ok(Some(AttrCallResult::Value(Value::Bool(self.closed))))
+        } else {
+            Ok(None)
+        }
+    }
+}
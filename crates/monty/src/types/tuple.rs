@@ -106,6 +106,8 @@ impl Tuple {
                 // Use empty tuple singleton
                 Ok(heap.get_empty_tuple())
             }
+            // Like CPython, `tuple()` of a tuple returns the same object
+            Some(v @ Value::Ref(id)) if matches!(heap.get(id), HeapData::Tuple(_)) => Ok(v),
             Some(v) => {
                 let items = MontyIter::new(v, heap, interns)?.collect(heap, interns)?;
                 Ok(allocate_tuple(items, heap)?)
@@ -153,6 +155,38 @@ pub fn allocate_tuple(
     }
 }
 
+/// Multiplier constants from CPython's xxHash-based `tuplehash`.
+const XXPRIME_1: u64 = 11_400_714_785_074_694_791;
+const XXPRIME_2: u64 = 14_029_467_366_897_019_727;
+const XXPRIME_5: u64 = 2_870_177_450_012_600_261;
+
+/// Computes the hash of a tuple's items using CPython's `tuplehash` algorithm.
+///
+/// Returns `None` if any element is unhashable. Shared by `Tuple` and `NamedTuple`
+/// so a named tuple hashes the same as the plain tuple it compares equal to, and
+/// since numbers use CPython's numeric hash, tuples of numbers match CPython exactly.
+pub(crate) fn hash_tuple_items(
+    items: &[Value],
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> Option<u64> {
+    let mut acc = XXPRIME_5;
+    for item in items {
+        let lane = item.py_hash(heap, interns)?;
+        acc = acc.wrapping_add(lane.wrapping_mul(XXPRIME_2));
+        acc = acc.rotate_left(31);
+        acc = acc.wrapping_mul(XXPRIME_1);
+    }
+    let len = u64::try_from(items.len()).expect("tuple length exceeds u64::MAX");
+    acc = acc.wrapping_add(len ^ (XXPRIME_5 ^ 3_527_539));
+    // -1 is reserved as an error marker in CPython, so it's remapped to a fixed value
+    if acc == u64::MAX {
+        Some(1_546_275_796)
+    } else {
+        Some(acc)
+    }
+}
+
 impl PyTrait for Tuple {
     fn py_type(&self, _heap: &Heap<impl ResourceTracker>) -> Type {
         Type::Tuple
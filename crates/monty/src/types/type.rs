@@ -1,17 +1,20 @@
-use std::fmt;
+use std::{borrow::Cow, fmt};
 
 use num_bigint::BigInt;
+use num_traits::{FromPrimitive, ToPrimitive};
 
 use crate::{
     args::ArgValues,
     defer_drop,
     exception_private::{ExcType, RunError, RunResult, SimpleException},
     heap::{Heap, HeapData},
-    intern::Interns,
+    intern::{Interns, StaticStrings},
     resource::ResourceTracker,
     types::{
-        Bytes, Dict, FrozenSet, List, LongInt, MontyIter, Path, PyTrait, Range, Set, Slice, Str, Tuple, str::StringRepr,
+        Array, Bytes, Dict, Fraction, FrozenSet, List, LongInt, MontyIter, Path, PyTrait, Range, Set, Slice, Str,
+        StringIO, Tuple, bytes::bytes_repr, str::StringRepr,
     },
+    unicode_tables::decimal_value,
     value::Value,
 };
 
@@ -57,6 +60,20 @@ pub enum Type {
     Path,
     /// A property descriptor - displays as "property"
     Property,
+    /// A union of classes from `int | str` - displays as "types.UnionType"
+    UnionType,
+    /// A host-provided sequence fetched in chunks - displays as "LazySequence"
+    LazySequence,
+    /// An in-memory text stream from `io.StringIO` - displays as "_io.StringIO"
+    StringIO,
+    /// A row writer from `csv.writer` - displays as "_csv.writer"
+    CsvWriter,
+    /// A row writer from `csv.DictWriter` - displays as "csv.DictWriter"
+    CsvDictWriter,
+    /// A rational number from `fractions.Fraction` - displays as "Fraction"
+    Fraction,
+    /// A typed numeric array from `array.array` - displays as "array.array"
+    Array,
 }
 
 impl fmt::Display for Type {
@@ -90,6 +107,13 @@ impl fmt::Display for Type {
             Self::SpecialForm => f.write_str("typing._SpecialForm"),
             Self::Path => f.write_str("PosixPath"),
             Self::Property => f.write_str("property"),
+            Self::UnionType => f.write_str("types.UnionType"),
+            Self::LazySequence => f.write_str("LazySequence"),
+            Self::StringIO => f.write_str("_io.StringIO"),
+            Self::CsvWriter => f.write_str("_csv.writer"),
+            Self::CsvDictWriter => f.write_str("csv.DictWriter"),
+            Self::Fraction => f.write_str("Fraction"),
+            Self::Array => f.write_str("array.array"),
         }
     }
 }
@@ -141,6 +165,244 @@ impl Type {
         }
     }
 
+    /// Returns the names of the attributes and methods Monty supports on values of this type.
+    ///
+    /// Used by `dir()`; dunder names are left out, as are names that depend on the
+    /// instance (dataclass and namedtuple fields, module attributes). Keep in sync with
+    /// the types' `py_call_attr` and `py_getattr` implementations.
+    #[must_use]
+    pub fn attribute_names(self) -> &'static [StaticStrings] {
+        match self {
+            Self::List => &[
+                StaticStrings::Append,
+                StaticStrings::Clear,
+                StaticStrings::Copy,
+                StaticStrings::Count,
+                StaticStrings::Extend,
+                StaticStrings::Index,
+                StaticStrings::Insert,
+                StaticStrings::Pop,
+                StaticStrings::Remove,
+                StaticStrings::Reverse,
+                StaticStrings::Sort,
+            ],
+            Self::Tuple => &[StaticStrings::Count, StaticStrings::Index],
+            Self::Dict => &[
+                StaticStrings::Clear,
+                StaticStrings::Copy,
+                StaticStrings::Fromkeys,
+                StaticStrings::Get,
+                StaticStrings::Items,
+                StaticStrings::Keys,
+                StaticStrings::Pop,
+                StaticStrings::Popitem,
+                StaticStrings::Setdefault,
+                StaticStrings::Update,
+                StaticStrings::Values,
+            ],
+            Self::Set => &[
+                StaticStrings::Add,
+                StaticStrings::Clear,
+                StaticStrings::Copy,
+                StaticStrings::Difference,
+                StaticStrings::Discard,
+                StaticStrings::Intersection,
+                StaticStrings::Isdisjoint,
+                StaticStrings::Issubset,
+                StaticStrings::Issuperset,
+                StaticStrings::Pop,
+                StaticStrings::Remove,
+                StaticStrings::SymmetricDifference,
+                StaticStrings::Union,
+                StaticStrings::Update,
+            ],
+            Self::FrozenSet => &[
+                StaticStrings::Copy,
+                StaticStrings::Difference,
+                StaticStrings::Intersection,
+                StaticStrings::Isdisjoint,
+                StaticStrings::Issubset,
+                StaticStrings::Issuperset,
+                StaticStrings::SymmetricDifference,
+                StaticStrings::Union,
+            ],
+            Self::Str => &[
+                StaticStrings::Capitalize,
+                StaticStrings::Casefold,
+                StaticStrings::Center,
+                StaticStrings::Count,
+                StaticStrings::Encode,
+                StaticStrings::Endswith,
+                StaticStrings::Find,
+                StaticStrings::Index,
+                StaticStrings::Isalnum,
+                StaticStrings::Isalpha,
+                StaticStrings::Isascii,
+                StaticStrings::Isdecimal,
+                StaticStrings::Isdigit,
+                StaticStrings::Isidentifier,
+                StaticStrings::Islower,
+                StaticStrings::Isnumeric,
+                StaticStrings::Isprintable,
+                StaticStrings::Isspace,
+                StaticStrings::Istitle,
+                StaticStrings::Isupper,
+                StaticStrings::Join,
+                StaticStrings::Ljust,
+                StaticStrings::Lower,
+                StaticStrings::Lstrip,
+                StaticStrings::Partition,
+                StaticStrings::Removeprefix,
+                StaticStrings::Removesuffix,
+                StaticStrings::Replace,
+                StaticStrings::Rfind,
+                StaticStrings::Rindex,
+                StaticStrings::Rjust,
+                StaticStrings::Rpartition,
+                StaticStrings::Rsplit,
+                StaticStrings::Rstrip,
+                StaticStrings::Split,
+                StaticStrings::Splitlines,
+                StaticStrings::Startswith,
+                StaticStrings::Strip,
+                StaticStrings::Swapcase,
+                StaticStrings::Title,
+                StaticStrings::Upper,
+                StaticStrings::Zfill,
+            ],
+            Self::Bytes => &[
+                StaticStrings::Capitalize,
+                StaticStrings::Center,
+                StaticStrings::Count,
+                StaticStrings::Decode,
+                StaticStrings::Endswith,
+                StaticStrings::Find,
+                StaticStrings::Fromhex,
+                StaticStrings::Hex,
+                StaticStrings::Index,
+                StaticStrings::Isalnum,
+                StaticStrings::Isalpha,
+                StaticStrings::Isascii,
+                StaticStrings::Isdigit,
+                StaticStrings::Islower,
+                StaticStrings::Isspace,
+                StaticStrings::Istitle,
+                StaticStrings::Isupper,
+                StaticStrings::Join,
+                StaticStrings::Ljust,
+                StaticStrings::Lower,
+                StaticStrings::Lstrip,
+                StaticStrings::Partition,
+                StaticStrings::Removeprefix,
+                StaticStrings::Removesuffix,
+                StaticStrings::Replace,
+                StaticStrings::Rfind,
+                StaticStrings::Rindex,
+                StaticStrings::Rjust,
+                StaticStrings::Rpartition,
+                StaticStrings::Rsplit,
+                StaticStrings::Rstrip,
+                StaticStrings::Split,
+                StaticStrings::Splitlines,
+                StaticStrings::Startswith,
+                StaticStrings::Strip,
+                StaticStrings::Swapcase,
+                StaticStrings::Title,
+                StaticStrings::Upper,
+                StaticStrings::Zfill,
+            ],
+            Self::Range => &[
+                StaticStrings::Count,
+                StaticStrings::Index,
+                StaticStrings::Start,
+                StaticStrings::Step,
+                StaticStrings::Stop,
+            ],
+            Self::Slice => &[StaticStrings::Start, StaticStrings::Step, StaticStrings::Stop],
+            Self::Exception(_) => &[StaticStrings::AddNote, StaticStrings::Args],
+            Self::Path => &[
+                StaticStrings::Absolute,
+                StaticStrings::AsPosix,
+                StaticStrings::Chmod,
+                StaticStrings::Copy,
+                StaticStrings::Exists,
+                StaticStrings::Glob,
+                StaticStrings::IsAbsolute,
+                StaticStrings::IsDir,
+                StaticStrings::IsFile,
+                StaticStrings::IsSymlink,
+                StaticStrings::Iterdir,
+                StaticStrings::Joinpath,
+                StaticStrings::Lstat,
+                StaticStrings::Mkdir,
+                StaticStrings::Name,
+                StaticStrings::Parent,
+                StaticStrings::Parts,
+                StaticStrings::ReadBytes,
+                StaticStrings::ReadText,
+                StaticStrings::Readlink,
+                StaticStrings::Rename,
+                StaticStrings::Resolve,
+                StaticStrings::Rglob,
+                StaticStrings::Rmdir,
+                StaticStrings::StatMethod,
+                StaticStrings::Stem,
+                StaticStrings::Suffix,
+                StaticStrings::Suffixes,
+                StaticStrings::SymlinkTo,
+                StaticStrings::Touch,
+                StaticStrings::Unlink,
+                StaticStrings::Walk,
+                StaticStrings::WithName,
+                StaticStrings::WithStem,
+                StaticStrings::WithSuffix,
+                StaticStrings::WriteBytes,
+                StaticStrings::WriteText,
+            ],
+            Self::StringIO => &[
+                StaticStrings::Close,
+                StaticStrings::Closed,
+                StaticStrings::Getvalue,
+                StaticStrings::Read,
+                StaticStrings::Readline,
+                StaticStrings::Readlines,
+                StaticStrings::Seek,
+                StaticStrings::Tell,
+                StaticStrings::Truncate,
+                StaticStrings::Write,
+                StaticStrings::Writelines,
+            ],
+            Self::CsvWriter => &[StaticStrings::Writerow, StaticStrings::Writerows],
+            Self::CsvDictWriter => &[
+                StaticStrings::Fieldnames,
+                StaticStrings::Writeheader,
+                StaticStrings::Writerow,
+                StaticStrings::Writerows,
+            ],
+            Self::Fraction => &[
+                StaticStrings::AsIntegerRatio,
+                StaticStrings::Denominator,
+                StaticStrings::IsInteger,
+                StaticStrings::LimitDenominator,
+                StaticStrings::Numerator,
+            ],
+            Self::Array => &[
+                StaticStrings::Append,
+                StaticStrings::Count,
+                StaticStrings::Extend,
+                StaticStrings::Index,
+                StaticStrings::Insert,
+                StaticStrings::Itemsize,
+                StaticStrings::Pop,
+                StaticStrings::Remove,
+                StaticStrings::Reverse,
+                StaticStrings::Tolist,
+                StaticStrings::Typecode,
+            ],
+            _ => &[],
+        }
+    }
+
     /// Converts a callable type to a u8 for the `CallBuiltinType` opcode.
     ///
     /// Returns `Some(u8)` for types that can be called as constructors,
@@ -213,51 +475,13 @@ impl Type {
             Self::Slice => Slice::init(heap, args),
             Self::Iterator => MontyIter::init(heap, args, interns),
             Self::Path => Path::init(heap, args, interns),
+            Self::StringIO => StringIO::init(heap, args, interns),
+            Self::Fraction => Fraction::init(heap, args, interns),
+            Self::Array => Array::init(heap, args, interns),
 
             // Primitive types - inline implementation
-            Self::Int => {
-                let Some(v) = args.get_zero_one_arg("int", heap)? else {
-                    return Ok(Value::Int(0));
-                };
-                defer_drop!(v, heap);
-                match v {
-                    Value::Int(i) => Ok(Value::Int(*i)),
-                    Value::Float(f) => Ok(Value::Int(f64_to_i64_truncate(*f))),
-                    Value::Bool(b) => Ok(Value::Int(i64::from(*b))),
-                    Value::InternString(string_id) => parse_int_from_str(interns.get_str(*string_id), heap),
-                    Value::Ref(heap_id) => {
-                        // Clone data to release the borrow on heap before mutation
-                        match heap.get(*heap_id) {
-                            HeapData::Str(s) => {
-                                let s = s.to_string();
-                                parse_int_from_str(&s, heap)
-                            }
-                            HeapData::LongInt(li) => li.clone().into_value(heap).map_err(Into::into),
-                            _ => Err(ExcType::type_error_int_conversion(v.py_type(heap))),
-                        }
-                    }
-                    _ => Err(ExcType::type_error_int_conversion(v.py_type(heap))),
-                }
-            }
-            Self::Float => {
-                let Some(v) = args.get_zero_one_arg("float", heap)? else {
-                    return Ok(Value::Float(0.0));
-                };
-                defer_drop!(v, heap);
-                match v {
-                    Value::Float(f) => Ok(Value::Float(*f)),
-                    Value::Int(i) => Ok(Value::Float(*i as f64)),
-                    Value::Bool(b) => Ok(Value::Float(if *b { 1.0 } else { 0.0 })),
-                    Value::InternString(string_id) => {
-                        Ok(Value::Float(parse_f64_from_str(interns.get_str(*string_id))?))
-                    }
-                    Value::Ref(heap_id) => match heap.get(*heap_id) {
-                        HeapData::Str(s) => Ok(Value::Float(parse_f64_from_str(s.as_str())?)),
-                        _ => Err(ExcType::type_error_float_conversion(v.py_type(heap))),
-                    },
-                    _ => Err(ExcType::type_error_float_conversion(v.py_type(heap))),
-                }
-            }
+            Self::Int => int_init(heap, args, interns),
+            Self::Float => float_init(heap, args, interns),
             Self::Bool => {
                 let Some(v) = args.get_zero_one_arg("bool", heap)? else {
                     return Ok(Value::Bool(false));
@@ -272,52 +496,250 @@ impl Type {
     }
 }
 
-/// Truncates f64 to i64 with clamping for out-of-range values.
+/// Implements the `int()` constructor: `int()`, `int(x)` and `int(x, base)`.
+///
+/// Numbers are converted directly (floats truncate toward zero, promoting to a `LongInt`
+/// when needed). Strings and bytes are parsed as integer literals in `base`, which must be
+/// 0 (infer from a `0x`/`0o`/`0b` prefix) or 2..=36.
+fn int_init(heap: &mut Heap<impl ResourceTracker>, args: ArgValues, interns: &Interns) -> RunResult<Value> {
+    let (value, base) = args.get_zero_one_two_args("int", heap)?;
+    defer_drop!(base, heap);
+    let Some(v) = value else {
+        if base.is_some() {
+            return Err(ExcType::type_error("int() missing string argument"));
+        }
+        return Ok(Value::Int(0));
+    };
+    defer_drop!(v, heap);
+
+    if let Some(base) = base {
+        let base = match base {
+            Value::Int(n) => *n,
+            Value::Bool(b) => i64::from(*b),
+            _ => return Err(ExcType::type_error_not_integer(base.py_type(heap))),
+        };
+        if base != 0 && !(2..=36).contains(&base) {
+            return Err(
+                SimpleException::new_msg(ExcType::ValueError, "int() base must be >= 2 and <= 36, or 0").into(),
+            );
+        }
+        let base = u32::try_from(base).expect("base validated in 0..=36");
+        return match v {
+            Value::InternString(string_id) => parse_int_from_str(interns.get_str(*string_id), base, heap),
+            Value::InternBytes(bytes_id) => parse_int_from_bytes(interns.get_bytes(*bytes_id), base, heap),
+            Value::Ref(heap_id) => match heap.get(*heap_id) {
+                HeapData::Str(s) => {
+                    let s = s.as_str().to_owned();
+                    parse_int_from_str(&s, base, heap)
+                }
+                HeapData::Bytes(b) => {
+                    let b = b.as_slice().to_vec();
+                    parse_int_from_bytes(&b, base, heap)
+                }
+                _ => Err(ExcType::type_error("int() can't convert non-string with explicit base")),
+            },
+            _ => Err(ExcType::type_error("int() can't convert non-string with explicit base")),
+        };
+    }
+
+    match v {
+        Value::Int(i) => Ok(Value::Int(*i)),
+        Value::Float(f) => float_to_int(*f, heap),
+        Value::Bool(b) => Ok(Value::Int(i64::from(*b))),
+        Value::InternLongInt(long_int_id) => {
+            Ok(LongInt::new(interns.get_long_int(*long_int_id).clone()).into_value(heap)?)
+        }
+        Value::InternString(string_id) => parse_int_from_str(interns.get_str(*string_id), 10, heap),
+        Value::InternBytes(bytes_id) => parse_int_from_bytes(interns.get_bytes(*bytes_id), 10, heap),
+        Value::Ref(heap_id) => {
+            // Clone data to release the borrow on heap before mutation
+            match heap.get(*heap_id) {
+                HeapData::Str(s) => {
+                    let s = s.as_str().to_owned();
+                    parse_int_from_str(&s, 10, heap)
+                }
+                HeapData::Bytes(b) => {
+                    let b = b.as_slice().to_vec();
+                    parse_int_from_bytes(&b, 10, heap)
+                }
+                HeapData::LongInt(li) => li.clone().into_value(heap).map_err(Into::into),
+                HeapData::Fraction(f) => LongInt::new(f.trunc()).into_value(heap).map_err(Into::into),
+                _ => Err(ExcType::type_error_int_conversion(v.py_type(heap))),
+            }
+        }
+        _ => Err(ExcType::type_error_int_conversion(v.py_type(heap))),
+    }
+}
+
+/// Implements the `float()` constructor: `float()` and `float(x)`.
+fn float_init(heap: &mut Heap<impl ResourceTracker>, args: ArgValues, interns: &Interns) -> RunResult<Value> {
+    let Some(v) = args.get_zero_one_arg("float", heap)? else {
+        return Ok(Value::Float(0.0));
+    };
+    defer_drop!(v, heap);
+    match v {
+        Value::Float(f) => Ok(Value::Float(*f)),
+        Value::Int(i) => Ok(Value::Float(*i as f64)),
+        Value::Bool(b) => Ok(Value::Float(if *b { 1.0 } else { 0.0 })),
+        Value::InternLongInt(long_int_id) => Ok(Value::Float(big_int_to_f64(interns.get_long_int(*long_int_id))?)),
+        Value::InternString(string_id) => Ok(Value::Float(parse_f64_from_str(interns.get_str(*string_id))?)),
+        Value::InternBytes(bytes_id) => Ok(Value::Float(parse_f64_from_bytes(interns.get_bytes(*bytes_id))?)),
+        Value::Ref(heap_id) => match heap.get(*heap_id) {
+            HeapData::Str(s) => Ok(Value::Float(parse_f64_from_str(s.as_str())?)),
+            HeapData::Bytes(b) => Ok(Value::Float(parse_f64_from_bytes(b.as_slice())?)),
+            HeapData::LongInt(li) => Ok(Value::Float(big_int_to_f64(li.inner())?)),
+            HeapData::Fraction(f) => Ok(Value::Float(f.to_f64()?)),
+            _ => Err(ExcType::type_error_float_conversion(v.py_type(heap))),
+        },
+        _ => Err(ExcType::type_error_float_conversion(v.py_type(heap))),
+    }
+}
+
+/// Converts a float to an int, truncating toward zero like Python's `int(float)`.
 ///
-/// Python's `int(float)` truncates toward zero. For values outside i64 range,
-/// we clamp to i64::MAX/MIN (Python would use arbitrary precision ints, which
-/// we don't support).
-fn f64_to_i64_truncate(value: f64) -> i64 {
+/// Values outside the `i64` range become a `LongInt`. NaN raises `ValueError` and
+/// infinities raise `OverflowError`, matching CPython.
+fn float_to_int(value: f64, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
+    if value.is_nan() {
+        return Err(SimpleException::new_msg(ExcType::ValueError, "cannot convert float NaN to integer").into());
+    }
+    if value.is_infinite() {
+        return Err(
+            SimpleException::new_msg(ExcType::OverflowError, "cannot convert float infinity to integer").into(),
+        );
+    }
     // trunc() rounds toward zero, matching Python's int(float) behavior
     let truncated = value.trunc();
-    if truncated >= i64::MAX as f64 {
-        i64::MAX
-    } else if truncated <= i64::MIN as f64 {
-        i64::MIN
-    } else {
-        // SAFETY for clippy: truncated is guaranteed to be in (i64::MIN, i64::MAX)
-        // after the bounds checks above, so truncation cannot overflow
-        #[expect(clippy::cast_possible_truncation, reason = "bounds checked above")]
-        let result = truncated as i64;
-        result
+    let bi = BigInt::from_f64(truncated).expect("finite float converts to BigInt");
+    Ok(LongInt::new(bi).into_value(heap)?)
+}
+
+/// Converts an arbitrary precision int to the nearest `f64`.
+///
+/// Raises `OverflowError` if the value is outside the float range, matching CPython.
+pub(crate) fn big_int_to_f64(value: &BigInt) -> RunResult<f64> {
+    value
+        .to_f64()
+        .filter(|f| f.is_finite())
+        .ok_or_else(|| SimpleException::new_msg(ExcType::OverflowError, "int too large to convert to float").into())
+}
+
+/// Returns true for the characters Python's `str.strip()` removes by default.
+///
+/// This is Unicode whitespace plus the ASCII information separators `\x1c`-`\x1f`,
+/// which Python (unlike Rust) treats as whitespace.
+fn is_py_whitespace(c: char) -> bool {
+    c.is_whitespace() || ('\x1c'..='\x1f').contains(&c)
+}
+
+/// Converts the text of an `int()` or `float()` string argument to ASCII, as CPython does
+/// before parsing it.
+///
+/// Non-ASCII decimal digits become the matching ASCII digit (so `int('١٢٣') == 123`) and
+/// non-ASCII whitespace becomes a space. Any other non-ASCII character becomes `?`, which
+/// no literal accepts.
+fn decimal_to_ascii(value: &str) -> Cow<'_, str> {
+    if value.is_ascii() {
+        return Cow::Borrowed(value);
     }
+    let ascii = value
+        .chars()
+        .map(|c| {
+            if c.is_ascii() {
+                c
+            } else if is_py_whitespace(c) {
+                ' '
+            } else if let Some(digit) = decimal_value(c) {
+                char::from_digit(digit, 10).expect("decimal value is below 10")
+            } else {
+                '?'
+            }
+        })
+        .collect();
+    Cow::Owned(ascii)
+}
+
+/// Removes underscores used as digit separators, or returns `None` if any underscore
+/// is not directly between two characters accepted by `is_digit`.
+fn strip_digit_separators(digits: &str, is_digit: impl Fn(char) -> bool) -> Option<String> {
+    let mut result = String::with_capacity(digits.len());
+    let mut prev_is_digit = false;
+    let mut chars = digits.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '_' {
+            let next_is_digit = chars.peek().is_some_and(|&next| is_digit(next));
+            if !prev_is_digit || !next_is_digit {
+                return None;
+            }
+            prev_is_digit = false;
+        } else {
+            prev_is_digit = is_digit(c);
+            result.push(c);
+        }
+    }
+    Some(result)
 }
 
 /// Parses a Python `float()` string argument into an `f64`.
 ///
 /// This supports:
 /// - Leading/trailing whitespace (e.g. `"  1.5  "`)
-/// - The special values `inf`, `-inf`, `infinity`, and `nan` (case-insensitive)
-///
-/// Underscore digit separators are not currently supported.
-fn parse_f64_from_str(value: &str) -> RunResult<f64> {
-    let trimmed = value.trim();
+/// - Exponents and a leading sign (e.g. `"-1e-3"`)
+/// - Underscore digit separators between digits (e.g. `"1_000.5"`)
+/// - The special values `inf`, `infinity`, and `nan` with an optional sign (case-insensitive)
+/// - Decimal digits from any script (e.g. `"١.٥"` is `1.5`)
+pub(crate) fn parse_f64_from_str(value: &str) -> RunResult<f64> {
+    let ascii = decimal_to_ascii(value);
+    let trimmed = ascii.trim_matches(is_py_whitespace);
+    let error = || value_error_could_not_convert_string_to_float(value);
     if trimmed.is_empty() {
-        return Err(value_error_could_not_convert_string_to_float(value));
+        return Err(error());
     }
 
-    let lower = trimmed.to_ascii_lowercase();
-    let parsed = match lower.as_str() {
-        "inf" | "+inf" | "infinity" | "+infinity" => f64::INFINITY,
-        "-inf" | "-infinity" => f64::NEG_INFINITY,
-        "nan" | "+nan" => f64::NAN,
-        "-nan" => -f64::NAN,
-        _ => trimmed
-            .parse::<f64>()
-            .map_err(|_| value_error_could_not_convert_string_to_float(value))?,
+    let (negative, unsigned) = match trimmed.as_bytes()[0] {
+        b'-' => (true, &trimmed[1..]),
+        b'+' => (false, &trimmed[1..]),
+        _ => (false, trimmed),
+    };
+    let magnitude = match unsigned.to_ascii_lowercase().as_str() {
+        "inf" | "infinity" => f64::INFINITY,
+        "nan" => f64::NAN,
+        lower => {
+            // Only digits, '.', exponent markers and exponent signs are valid here;
+            // this rejects forms Rust's parser accepts but Python doesn't
+            if !lower
+                .chars()
+                .all(|c| c.is_ascii_digit() || matches!(c, '.' | 'e' | '+' | '-' | '_'))
+            {
+                return Err(error());
+            }
+            let normalized = strip_digit_separators(lower, |c| c.is_ascii_digit()).ok_or_else(error)?;
+            if normalized.starts_with(['+', '-']) {
+                return Err(error());
+            }
+            normalized.parse::<f64>().map_err(|_| error())?
+        }
     };
 
-    Ok(parsed)
+    Ok(if negative { -magnitude } else { magnitude })
+}
+
+/// Parses a Python `float()` bytes argument, which must be an ASCII float literal.
+fn parse_f64_from_bytes(value: &[u8]) -> RunResult<f64> {
+    match std::str::from_utf8(value) {
+        Ok(s) if s.is_ascii() => parse_f64_from_str(s).map_err(|_| {
+            SimpleException::new_msg(
+                ExcType::ValueError,
+                format!("could not convert string to float: {}", bytes_repr(value)),
+            )
+            .into()
+        }),
+        _ => Err(SimpleException::new_msg(
+            ExcType::ValueError,
+            format!("could not convert string to float: {}", bytes_repr(value)),
+        )
+        .into()),
+    }
 }
 
 /// Creates the `ValueError` raised by `float()` when a string cannot be parsed.
@@ -333,40 +755,82 @@ fn value_error_could_not_convert_string_to_float(value: &str) -> RunError {
 
 /// Parses a Python `int()` string argument into an `Int` or `LongInt`.
 ///
-/// Handles whitespace stripping and removing `_` separators. Returns `Value::Int` if the value
-/// fits in i64, otherwise allocates a `LongInt` on the heap. Returns `ValueError` on failure.
-fn parse_int_from_str(value: &str, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
-    // Try parsing as i64 first (fast path)
-    if let Ok(int) = value.parse::<i64>() {
+/// Follows Python's integer literal rules: surrounding whitespace and a sign are allowed,
+/// underscores may separate digits, and with base 0 the base is inferred from a
+/// `0x`/`0o`/`0b` prefix (which is also accepted when it matches an explicit base 16, 8 or 2).
+/// Decimal digits from any script are accepted too, e.g. `int('١٢٣')` is `123`.
+/// Returns `Value::Int` if the value fits in i64, otherwise allocates a `LongInt` on the heap.
+/// Returns `ValueError` on failure.
+fn parse_int_from_str(value: &str, base: u32, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
+    // Fast path for plain decimal literals
+    if base == 10
+        && let Ok(int) = value.parse::<i64>()
+    {
         return Ok(Value::Int(int));
     }
-    let trimmed = value.trim();
+    let bi = parse_int_literal(&decimal_to_ascii(value), base)
+        .ok_or_else(|| value_error_invalid_literal_for_int(StringRepr(value), base))?;
+    Ok(LongInt::new(bi).into_value(heap)?)
+}
 
-    if let Ok(int) = trimmed.parse::<i64>() {
-        return Ok(Value::Int(int));
-    }
+/// Parses a Python `int()` bytes argument, which must be an ASCII integer literal.
+fn parse_int_from_bytes(value: &[u8], base: u32, heap: &mut Heap<impl ResourceTracker>) -> RunResult<Value> {
+    let parsed = std::str::from_utf8(value)
+        .ok()
+        .filter(|s| s.is_ascii())
+        .and_then(|s| parse_int_literal(s, base));
+    let bi = parsed.ok_or_else(|| value_error_invalid_literal_for_int(bytes_repr(value), base))?;
+    Ok(LongInt::new(bi).into_value(heap)?)
+}
 
-    // Try with underscores removed
-    let normalized = trimmed.replace('_', "");
-    if let Ok(int) = normalized.parse::<i64>() {
-        return Ok(Value::Int(int));
-    }
+/// Parses an integer literal in the given base (0 means infer from the prefix).
+///
+/// Returns `None` if the text is not a valid literal.
+fn parse_int_literal(value: &str, base: u32) -> Option<BigInt> {
+    let trimmed = value.trim_matches(is_py_whitespace);
+    let (negative, unsigned) = match trimmed.as_bytes().first() {
+        Some(b'-') => (true, &trimmed[1..]),
+        Some(b'+') => (false, &trimmed[1..]),
+        _ => (false, trimmed),
+    };
 
-    // Try parsing as BigInt for values too large for i64
-    if let Ok(bi) = normalized.parse::<BigInt>() {
-        return Ok(LongInt::new(bi).into_value(heap)?);
-    }
+    let prefix_base = match unsigned.get(..2).map(str::to_ascii_lowercase).as_deref() {
+        Some("0x") => Some(16),
+        Some("0o") => Some(8),
+        Some("0b") => Some(2),
+        _ => None,
+    };
+    let (base, digits) = match (base, prefix_base) {
+        // A prefix may be followed by a single separator: `0x_ff`
+        (0, Some(prefix_base)) => (prefix_base, unsigned[2..].strip_prefix('_').unwrap_or(&unsigned[2..])),
+        (base, Some(prefix_base)) if base == prefix_base => {
+            (base, unsigned[2..].strip_prefix('_').unwrap_or(&unsigned[2..]))
+        }
+        (0, None) => {
+            // Base 0 decimal literals can't have leading zeros, except for zero itself
+            if unsigned.starts_with('0') && unsigned.chars().any(|c| c != '0' && c != '_') {
+                return None;
+            }
+            (10, unsigned)
+        }
+        (base, _) => (base, unsigned),
+    };
 
-    Err(value_error_invalid_literal_for_int(value))
+    let digits = strip_digit_separators(digits, |c| c.is_ascii_alphanumeric())?;
+    if digits.is_empty() || !digits.chars().all(|c| c.to_digit(base).is_some()) {
+        return None;
+    }
+    let magnitude = BigInt::parse_bytes(digits.as_bytes(), base)?;
+    Some(if negative { -magnitude } else { magnitude })
 }
 
 /// Creates the `ValueError` raised by `int()` when a string cannot be parsed.
 ///
 /// Matches CPython's message format: `invalid literal for int() with base 10: '...'`.
-fn value_error_invalid_literal_for_int(value: &str) -> RunError {
+fn value_error_invalid_literal_for_int(value: impl fmt::Display, base: u32) -> RunError {
     SimpleException::new_msg(
         ExcType::ValueError,
-        format!("invalid literal for int() with base 10: {}", StringRepr(value)),
+        format!("invalid literal for int() with base {base}: {value}"),
     )
     .into()
 }
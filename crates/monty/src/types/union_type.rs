@@ -0,0 +1,162 @@
+//! Python `types.UnionType`, created by applying `|` to classes (e.g. `int | None`).
+//!
+//! Unions are mainly used as the second argument of `isinstance()` and `issubclass()`.
+//! Every member is a class, so members are stored as `Type`s and a union never holds
+//! heap references.
+
+use std::fmt::Write;
+
+use ahash::AHashSet;
+
+use crate::{
+    builtins::Builtins,
+    exception_private::RunResult,
+    hashing::stable_hash,
+    heap::{Heap, HeapData, HeapId},
+    intern::{Interns, StaticStrings, StringId},
+    resource::{DepthGuard, ResourceError, ResourceTracker},
+    types::{AttrCallResult, PyTrait, Type, allocate_tuple},
+    value::Value,
+};
+
+/// A union of classes such as `int | str | None`.
+///
+/// Members are kept in first-seen order without duplicates, and nested unions are
+/// flattened, so `(int | str) | int` has members `int, str` like in CPython.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub(crate) struct UnionType {
+    members: Vec<Type>,
+}
+
+impl UnionType {
+    /// Implements `lhs | rhs` when both operands are classes, unions or `None`.
+    ///
+    /// Returns `Ok(None)` if either operand can't be part of a union, so the caller can
+    /// report the usual unsupported operand error. A union with a single distinct member
+    /// collapses to that member (`int | int` is `int`).
+    pub fn or(lhs: &Value, rhs: &Value, heap: &mut Heap<impl ResourceTracker>) -> Result<Option<Value>, ResourceError> {
+        // `None | None` isn't a union: NoneType doesn't implement `|`
+        if matches!((lhs, rhs), (Value::None, Value::None)) {
+            return Ok(None);
+        }
+        let mut members = Vec::new();
+        if !add_members(lhs, &mut members, heap) || !add_members(rhs, &mut members, heap) {
+            return Ok(None);
+        }
+        if let [member] = members.as_slice() {
+            return Ok(Some(class_value(*member)));
+        }
+        let id = heap.allocate(HeapData::UnionType(Self { members }))?;
+        Ok(Some(Value::Ref(id)))
+    }
+
+    /// Returns the member classes in order.
+    #[must_use]
+    pub fn members(&self) -> &[Type] {
+        &self.members
+    }
+
+    /// Computes an order-independent hash, since `int | str == str | int`.
+    #[must_use]
+    pub fn compute_hash(&self) -> u64 {
+        self.members.iter().fold(0, |acc, member| acc ^ stable_hash(member))
+    }
+}
+
+/// Appends the classes in `value` to `members`, skipping duplicates.
+///
+/// Returns false if `value` is not a class, a union or `None`.
+fn add_members(value: &Value, members: &mut Vec<Type>, heap: &Heap<impl ResourceTracker>) -> bool {
+    let mut add = |member: Type| {
+        if !members.contains(&member) {
+            members.push(member);
+        }
+    };
+    match value {
+        Value::Builtin(Builtins::Type(t)) => add(*t),
+        Value::Builtin(Builtins::ExcType(exc_type)) => add(Type::Exception(*exc_type)),
+        Value::None => add(Type::NoneType),
+        Value::Ref(id) => match heap.get(*id) {
+            HeapData::UnionType(union) => union.members.iter().copied().for_each(add),
+            _ => return false,
+        },
+        _ => return false,
+    }
+    true
+}
+
+/// Returns the class object for a union member.
+fn class_value(member: Type) -> Value {
+    match member {
+        Type::Exception(exc_type) => Value::Builtin(Builtins::ExcType(exc_type)),
+        t => Value::Builtin(Builtins::Type(t)),
+    }
+}
+
+impl PyTrait for UnionType {
+    fn py_type(&self, _heap: &Heap<impl ResourceTracker>) -> Type {
+        Type::UnionType
+    }
+
+    fn py_estimate_size(&self) -> usize {
+        std::mem::size_of::<Self>() + self.members.len() * std::mem::size_of::<Type>()
+    }
+
+    fn py_len(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> Option<usize> {
+        None
+    }
+
+    fn py_eq(
+        &self,
+        other: &Self,
+        _heap: &mut Heap<impl ResourceTracker>,
+        _guard: &mut DepthGuard,
+        _interns: &Interns,
+    ) -> Result<bool, ResourceError> {
+        // Members are unique, so equal lengths plus containment means the same set
+        Ok(self.members.len() == other.members.len() && self.members.iter().all(|m| other.members.contains(m)))
+    }
+
+    fn py_bool(&self, _heap: &Heap<impl ResourceTracker>, _interns: &Interns) -> bool {
+        true
+    }
+
+    fn py_repr_fmt(
+        &self,
+        f: &mut impl Write,
+        _heap: &Heap<impl ResourceTracker>,
+        _heap_ids: &mut AHashSet<HeapId>,
+        _guard: &mut DepthGuard,
+        _interns: &Interns,
+    ) -> std::fmt::Result {
+        for (i, member) in self.members.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" | ")?;
+            }
+            // CPython shows NoneType as `None` inside unions
+            match member {
+                Type::NoneType => f.write_str("None")?,
+                t => write!(f, "{t}")?,
+            }
+        }
+        Ok(())
+    }
+
+    fn py_dec_ref_ids(&mut self, _stack: &mut Vec<HeapId>) {
+        // Members are plain types, nothing to do
+    }
+
+    fn py_getattr(
+        &self,
+        attr_id: StringId,
+        heap: &mut Heap<impl ResourceTracker>,
+        _interns: &Interns,
+    ) -> RunResult<Option<AttrCallResult>> {
+        if attr_id == StaticStrings::DunderArgs {
+            let args = self.members.iter().map(|m| class_value(*m)).collect();
+            Ok(Some(AttrCallResult::Value(allocate_tuple(args, heap)?)))
+        } else {
+            Ok(None)
+        }
+    }
+}
@@ -1,7 +1,6 @@
 use std::{
     borrow::Cow,
     cmp::Ordering,
-    collections::hash_map::DefaultHasher,
     fmt::{self, Write},
     hash::{Hash, Hasher},
     mem::discriminant,
@@ -16,8 +15,11 @@ use num_traits::{ToPrimitive, Zero};
 use crate::{
     asyncio::CallId,
     builtins::Builtins,
+    defer_drop,
     exception_private::{ExcType, RunError, RunResult, SimpleException},
-    heap::{Heap, HeapData, HeapId},
+    fstring::float_repr,
+    hashing::{StableHasher, apply_seed, stable_hash},
+    heap::{Heap, HeapData, HeapGuard, HeapId},
     intern::{BytesId, ExtFunctionId, FunctionId, Interns, LongIntId, StaticStrings, StringId},
     modules::ModuleFunctions,
     resource::{
@@ -25,10 +27,13 @@ use crate::{
         check_repeat_size,
     },
     types::{
-        AttrCallResult, LongInt, Property, PyTrait, Str, Type,
-        bytes::{bytes_repr_fmt, get_byte_at_index, get_bytes_slice},
+        Array, AttrCallResult, LongInt, MontyIter, Property, PyTrait, Str, Type,
+        bytes::{bytes_contains, bytes_repr_fmt, get_byte_at_index, get_bytes_slice},
+        fraction::fraction_cmp,
+        long_int::hash_big_int,
         path,
         str::{allocate_char, get_char_at_index, get_str_slice, string_repr_fmt},
+        r#type::big_int_to_f64,
     },
 };
 
@@ -183,7 +188,7 @@ impl PyTrait for Value {
                 if let HeapData::LongInt(li) = heap.get(*id) {
                     Ok(BigInt::from(*a) == *li.inner())
                 } else {
-                    Ok(false)
+                    Ok(fraction_cmp(self, other, heap) == Some(Ordering::Equal))
                 }
             }
             // LongInt == Int comparison
@@ -191,9 +196,13 @@ impl PyTrait for Value {
                 if let HeapData::LongInt(li) = heap.get(*id) {
                     Ok(*li.inner() == BigInt::from(*b))
                 } else {
-                    Ok(false)
+                    Ok(fraction_cmp(self, other, heap) == Some(Ordering::Equal))
                 }
             }
+            // Bools and floats can only equal heap values that are fractions
+            (Self::Bool(_) | Self::Float(_), Self::Ref(_)) | (Self::Ref(_), Self::Bool(_) | Self::Float(_)) => {
+                Ok(fraction_cmp(self, other, heap) == Some(Ordering::Equal))
+            }
 
             // For interned interns, compare by StringId first (fast path for same interned string)
             (Self::InternString(s1), Self::InternString(s2)) => Ok(s1 == s2),
@@ -275,29 +284,33 @@ impl PyTrait for Value {
             // to at most 2 levels (Bool→Int, then Int matches directly above).
             (Self::Bool(s), _) => Self::Int(i64::from(*s)).py_cmp(other, heap, guard, interns),
             (_, Self::Bool(s)) => self.py_cmp(&Self::Int(i64::from(*s)), heap, guard, interns),
-            // Int vs LongInt comparison
+            // Int vs LongInt or Fraction comparison
             (Self::Int(a), Self::Ref(id)) => {
                 if let HeapData::LongInt(li) = heap.get(*id) {
                     Ok(BigInt::from(*a).partial_cmp(li.inner()))
                 } else {
-                    Ok(None)
+                    Ok(fraction_cmp(self, other, heap))
                 }
             }
-            // LongInt vs Int comparison
+            // LongInt or Fraction vs Int comparison
             (Self::Ref(id), Self::Int(b)) => {
                 if let HeapData::LongInt(li) = heap.get(*id) {
                     Ok(li.inner().partial_cmp(&BigInt::from(*b)))
                 } else {
-                    Ok(None)
+                    Ok(fraction_cmp(self, other, heap))
                 }
             }
-            // Ref vs Ref comparison: handles LongInt and Str
+            // Float vs Fraction comparison
+            (Self::Float(_), Self::Ref(_)) | (Self::Ref(_), Self::Float(_)) => Ok(fraction_cmp(self, other, heap)),
+            // Ref vs Ref comparison: handles LongInt, Str, Bytes and Fraction
             (Self::Ref(id1), Self::Ref(id2)) => {
-                Ok(heap.with_two(*id1, *id2, |_heap, left, right| match (left, right) {
+                let ordering = heap.with_two(*id1, *id2, |_heap, left, right| match (left, right) {
                     (HeapData::LongInt(a), HeapData::LongInt(b)) => a.inner().partial_cmp(b.inner()),
                     (HeapData::Str(a), HeapData::Str(b)) => a.as_str().partial_cmp(b.as_str()),
+                    (HeapData::Bytes(a), HeapData::Bytes(b)) => a.as_slice().partial_cmp(b.as_slice()),
                     _ => None,
-                }))
+                });
+                Ok(ordering.or_else(|| fraction_cmp(self, other, heap)))
             }
             // Interned string comparisons
             (Self::InternString(s1), Self::InternString(s2)) => {
@@ -321,6 +334,20 @@ impl PyTrait for Value {
             (Self::InternBytes(b1), Self::InternBytes(b2)) => {
                 Ok(interns.get_bytes(*b1).partial_cmp(interns.get_bytes(*b2)))
             }
+            (Self::InternBytes(b1), Self::Ref(id2)) => {
+                if let HeapData::Bytes(b2) = heap.get(*id2) {
+                    Ok(interns.get_bytes(*b1).partial_cmp(b2.as_slice()))
+                } else {
+                    Ok(None)
+                }
+            }
+            (Self::Ref(id1), Self::InternBytes(b2)) => {
+                if let HeapData::Bytes(b1) = heap.get(*id1) {
+                    Ok(b1.as_slice().partial_cmp(interns.get_bytes(*b2)))
+                } else {
+                    Ok(None)
+                }
+            }
             _ => Ok(None),
         }
     }
@@ -373,14 +400,7 @@ impl PyTrait for Value {
             Self::Bool(false) => f.write_str("False"),
             Self::Int(v) => write!(f, "{v}"),
             Self::InternLongInt(long_int_id) => write!(f, "{}", interns.get_long_int(*long_int_id)),
-            Self::Float(v) => {
-                let s = v.to_string();
-                if s.contains('.') {
-                    f.write_str(&s)
-                } else {
-                    write!(f, "{s}.0")
-                }
-            }
+            Self::Float(v) => f.write_str(&float_repr(*v)),
             Self::Builtin(b) => b.py_repr_fmt(f),
             Self::ModuleFunction(mf) => mf.py_repr_fmt(f, self.id()),
             Self::DefFunction(f_id) => interns.get_function(*f_id).py_repr_fmt(f, interns, self.id()),
@@ -666,23 +686,34 @@ impl PyTrait for Value {
                 if *v2 == 0.0 {
                     Err(ExcType::zero_division().into())
                 } else {
-                    Ok(Some(Self::Float(v1 % v2)))
+                    Ok(Some(Self::Float(float_divmod(*v1, *v2).1)))
                 }
             }
             (Self::Float(v1), Self::Int(v2)) => {
                 if *v2 == 0 {
                     Err(ExcType::zero_division().into())
                 } else {
-                    Ok(Some(Self::Float(v1 % (*v2 as f64))))
+                    Ok(Some(Self::Float(float_divmod(*v1, *v2 as f64).1)))
                 }
             }
             (Self::Int(v1), Self::Float(v2)) => {
                 if *v2 == 0.0 {
                     Err(ExcType::zero_division().into())
                 } else {
-                    Ok(Some(Self::Float((*v1 as f64) % v2)))
+                    Ok(Some(Self::Float(float_divmod(*v1 as f64, *v2).1)))
                 }
             }
+            // LongInt % Float and Float % LongInt
+            (Self::Ref(_), Self::Float(_)) | (Self::Float(_), Self::Ref(_)) => {
+                match long_int_float_operands(self, other, heap)? {
+                    Some((_, b)) if b == 0.0 => Err(ExcType::zero_division().into()),
+                    Some((a, b)) => Ok(Some(Self::Float(float_divmod(a, b).1))),
+                    None => Ok(None),
+                }
+            }
+            // Bools behave as the ints 0 and 1
+            (Self::Bool(a), _) => Self::Int(i64::from(*a)).py_mod(other, heap),
+            (_, Self::Bool(b)) => self.py_mod(&Self::Int(i64::from(*b)), heap),
             _ => Ok(None),
         }
     }
@@ -699,9 +730,12 @@ impl PyTrait for Value {
                     (*v2 != 0).then_some(0 == right_value)
                 }
             }
-            (Self::Float(v1), Self::Float(v2)) => Some(v1 % v2 == right_value as f64),
-            (Self::Float(v1), Self::Int(v2)) => Some(v1 % (*v2 as f64) == right_value as f64),
-            (Self::Int(v1), Self::Float(v2)) => Some((*v1 as f64) % v2 == right_value as f64),
+            // A zero divisor falls back to `py_mod` so it raises ZeroDivisionError
+            (Self::Float(v1), Self::Float(v2)) if *v2 != 0.0 => Some(float_divmod(*v1, *v2).1 == right_value as f64),
+            (Self::Float(v1), Self::Int(v2)) if *v2 != 0 => Some(float_divmod(*v1, *v2 as f64).1 == right_value as f64),
+            (Self::Int(v1), Self::Float(v2)) if *v2 != 0.0 => {
+                Some(float_divmod(*v1 as f64, *v2).1 == right_value as f64)
+            }
             _ => None,
         }
     }
@@ -1125,64 +1159,34 @@ impl PyTrait for Value {
                 if *b == 0.0 {
                     Err(ExcType::zero_division().into())
                 } else {
-                    Ok(Some(Self::Float((a / b).floor())))
+                    Ok(Some(Self::Float(float_divmod(*a, *b).0)))
                 }
             }
             (Self::Int(a), Self::Float(b)) => {
                 if *b == 0.0 {
                     Err(ExcType::zero_division().into())
                 } else {
-                    Ok(Some(Self::Float((*a as f64 / b).floor())))
+                    Ok(Some(Self::Float(float_divmod(*a as f64, *b).0)))
                 }
             }
             (Self::Float(a), Self::Int(b)) => {
                 if *b == 0 {
                     Err(ExcType::zero_division().into())
                 } else {
-                    Ok(Some(Self::Float((a / *b as f64).floor())))
-                }
-            }
-            // Bool floor division (True=1, False=0)
-            (Self::Bool(a), Self::Int(b)) => {
-                if *b == 0 {
-                    Err(ExcType::zero_division().into())
-                } else {
-                    let a_int = i64::from(*a);
-                    // Use same floor division logic as Int // Int
-                    let d = a_int / b;
-                    let r = a_int % b;
-                    let result = if r != 0 && (a_int < 0) != (*b < 0) { d - 1 } else { d };
-                    Ok(Some(Self::Int(result)))
+                    Ok(Some(Self::Float(float_divmod(*a, *b as f64).0)))
                 }
             }
-            (Self::Int(a), Self::Bool(b)) => {
-                if *b {
-                    Ok(Some(Self::Int(*a))) // a // 1 = a
-                } else {
-                    Err(ExcType::zero_division().into())
-                }
-            }
-            (Self::Bool(a), Self::Float(b)) => {
-                if *b == 0.0 {
-                    Err(ExcType::zero_division().into())
-                } else {
-                    Ok(Some(Self::Float((f64::from(*a) / b).floor())))
-                }
-            }
-            (Self::Float(a), Self::Bool(b)) => {
-                if *b {
-                    Ok(Some(Self::Float(a.floor()))) // a // 1.0 = floor(a)
-                } else {
-                    Err(ExcType::zero_division().into())
-                }
-            }
-            (Self::Bool(a), Self::Bool(b)) => {
-                if *b {
-                    Ok(Some(Self::Int(i64::from(*a)))) // a // 1 = a
-                } else {
-                    Err(ExcType::zero_division().into())
+            // LongInt // Float and Float // LongInt
+            (Self::Ref(_), Self::Float(_)) | (Self::Float(_), Self::Ref(_)) => {
+                match long_int_float_operands(self, other, heap)? {
+                    Some((_, b)) if b == 0.0 => Err(ExcType::zero_division().into()),
+                    Some((a, b)) => Ok(Some(Self::Float(float_divmod(a, b).0))),
+                    None => Ok(None),
                 }
             }
+            // Bools behave as the ints 0 and 1
+            (Self::Bool(a), _) => Self::Int(i64::from(*a)).py_floordiv(other, heap),
+            (_, Self::Bool(b)) => self.py_floordiv(&Self::Int(i64::from(*b)), heap),
             _ => Ok(None),
         }
     }
@@ -1485,6 +1489,8 @@ impl Value {
     /// For immediate values (Int, Float, Builtins), this computes a deterministic ID
     /// based on the value's hash, avoiding heap allocation. This means `id(5) == id(5)` will
     /// return True (unlike CPython for large integers outside the interning range).
+    /// `is` follows the same rule, so `x is 1000` is true whenever `x == 1000`; code relying on
+    /// that can be rejected up front with [`MontyRun::check_identity_literals`](crate::MontyRun::check_identity_literals).
     ///
     /// Singletons (None, True, False, etc.) return IDs from a dedicated tagged range.
     /// Interned strings/bytes use their interner index for stable identity.
@@ -1572,39 +1578,27 @@ impl Value {
             // Hash just the actual string or bytes content for consistency with heap Str/Bytes
             // hence we don't include the discriminant
             Self::InternString(string_id) => {
-                let mut hasher = DefaultHasher::new();
-                interns.get_str(*string_id).hash(&mut hasher);
-                return Some(hasher.finish());
+                return Some(apply_seed(interns.string_hash(*string_id), heap.hash_seed()));
             }
             Self::InternBytes(bytes_id) => {
-                let mut hasher = DefaultHasher::new();
-                interns.get_bytes(*bytes_id).hash(&mut hasher);
-                return Some(hasher.finish());
-            }
-            // Hash BigInt consistently with LongInt (using sign and bytes for large values)
-            Self::InternLongInt(long_int_id) => {
-                let bi = interns.get_long_int(*long_int_id);
-                let mut hasher = DefaultHasher::new();
-                let (sign, bytes) = bi.to_bytes_le();
-                sign.hash(&mut hasher);
-                bytes.hash(&mut hasher);
-                return Some(hasher.finish());
+                return Some(apply_seed(stable_hash(interns.get_bytes(*bytes_id)), heap.hash_seed()));
             }
+            // Numbers use CPython's numeric hash so equal ints, floats and bools hash equally
+            Self::Bool(b) => return Some(hash_int(i64::from(*b))),
+            Self::Int(i) => return Some(hash_int(*i)),
+            Self::Float(f) => return Some(hash_float(*f)),
+            Self::InternLongInt(long_int_id) => return Some(hash_big_int(interns.get_long_int(*long_int_id))),
             // For heap-allocated values (includes Range and Exception), compute hash lazily and cache it
             Self::Ref(id) => return heap.get_or_compute_hash(*id, interns),
             _ => {}
         }
 
-        let mut hasher = DefaultHasher::new();
+        let mut hasher = StableHasher::default();
         // hash based on discriminant to avoid collisions with different types
         discriminant(self).hash(&mut hasher);
         match self {
             // Immediate values can be hashed directly
             Self::Undefined | Self::Ellipsis | Self::None => {}
-            Self::Bool(b) => b.hash(&mut hasher),
-            Self::Int(i) => i.hash(&mut hasher),
-            // Hash the bit representation of float for consistency
-            Self::Float(f) => f.to_bits().hash(&mut hasher),
             Self::Builtin(b) => b.hash(&mut hasher),
             Self::ModuleFunction(mf) => mf.hash(&mut hasher),
             // Hash functions based on function ID
@@ -1616,7 +1610,13 @@ impl Value {
             Self::Property(p) => p.hash(&mut hasher),
             // ExternalFutures are hashable based on their call ID
             Self::ExternalFuture(call_id) => call_id.raw().hash(&mut hasher),
-            Self::InternString(_) | Self::InternBytes(_) | Self::InternLongInt(_) | Self::Ref(_) => {
+            Self::InternString(_)
+            | Self::InternBytes(_)
+            | Self::Bool(_)
+            | Self::Int(_)
+            | Self::Float(_)
+            | Self::InternLongInt(_)
+            | Self::Ref(_) => {
                 unreachable!("covered above")
             }
             #[cfg(feature = "ref-count-panic")]
@@ -1625,14 +1625,43 @@ impl Value {
         Some(hasher.finish())
     }
 
-    /// TODO this doesn't have many tests!!! also doesn't cover bytes
+    /// Returns the type to report when this value turns out to be unhashable.
+    ///
+    /// For tuples (and named tuples) this is the type of the first unhashable element,
+    /// searching nested tuples, so `hash((1, []))` reports `'list'` as CPython does.
+    /// Any other value reports its own type.
+    pub fn unhashable_type(&self, heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> Type {
+        let Self::Ref(id) = self else {
+            return self.py_type(heap);
+        };
+        let items: Vec<Self> = match heap.get(*id) {
+            HeapData::Tuple(t) => t.as_slice().iter().map(Self::copy_for_extend).collect(),
+            HeapData::NamedTuple(nt) => nt.as_vec().iter().map(Self::copy_for_extend).collect(),
+            _ => return self.py_type(heap),
+        };
+        for item in &items {
+            if let Self::Ref(item_id) = item {
+                heap.inc_ref(*item_id);
+            }
+        }
+        defer_drop!(items, heap);
+        for item in items.iter() {
+            if item.py_hash(heap, interns).is_none() {
+                return item.unhashable_type(heap, interns);
+            }
+        }
+        self.py_type(heap)
+    }
+
     /// Checks if `item` is contained in `self` (the container).
     ///
     /// Implements Python's `in` operator for various container types:
-    /// - List/Tuple: linear search with equality
+    /// - List/Tuple/NamedTuple/Array: linear search by identity, then equality
     /// - Dict: key lookup
     /// - Set/FrozenSet: element lookup
-    /// - Str: substring search
+    /// - Str/Bytes: substring search (or a single byte for `int in bytes`)
+    /// - Range: O(1) bounds and step check
+    /// - Iterators, lazy sequences and streams: consumed until the item is found
     pub fn py_contains(
         &self,
         item: &Self,
@@ -1644,74 +1673,48 @@ impl Value {
                 // Use with_entry_mut to temporarily take ownership of the container.
                 // This allows iterating over container elements while calling py_eq
                 // (which needs &mut Heap for comparing nested heap values).
-                heap.with_entry_mut(*heap_id, |heap, data| match data {
-                    HeapData::List(list) => {
-                        let mut guard = DepthGuard::default();
-                        for el in list.as_slice() {
-                            if item.py_eq(el, heap, &mut guard, interns)? {
-                                return Ok(true);
-                            }
-                        }
-                        Ok(false)
-                    }
-                    HeapData::Tuple(tuple) => {
-                        let mut guard = DepthGuard::default();
-                        for el in tuple.as_slice() {
-                            if item.py_eq(el, heap, &mut guard, interns)? {
-                                return Ok(true);
-                            }
-                        }
-                        Ok(false)
-                    }
-                    HeapData::Dict(dict) => dict.get(item, heap, interns).map(|m| m.is_some()),
-                    HeapData::Set(set) => set.contains(item, heap, interns),
-                    HeapData::FrozenSet(fset) => fset.contains(item, heap, interns),
-                    HeapData::Str(s) => str_contains(s.as_str(), item, heap, interns),
-                    HeapData::Range(range) => {
-                        // Range containment is O(1) - check bounds and step alignment
-                        let n = match item {
-                            Self::Int(i) => *i,
-                            Self::Bool(b) => i64::from(*b),
-                            Self::Float(f) => {
-                                // Floats are contained if they equal an integer in the range
-                                // e.g., 3.0 in range(5) is True, but 3.5 in range(5) is False
-                                if f.fract() != 0.0 {
-                                    return Ok(false);
-                                }
-                                // Check if float is within i64 range and convert safely
-                                // f64 can represent integers up to 2^53 exactly
-                                let int_val = f.trunc();
-                                if int_val < i64::MIN as f64 || int_val > i64::MAX as f64 {
-                                    return Ok(false);
-                                }
-                                // Safe conversion: we've verified it's a whole number in i64 range
-                                #[expect(clippy::cast_possible_truncation)]
-                                let n = int_val as i64;
-                                n
-                            }
-                            _ => return Ok(false),
-                        };
-                        Ok(range.contains(n))
-                    }
-                    other => {
-                        let type_name = other.py_type(heap);
-                        Err(ExcType::type_error(format!(
-                            "argument of type '{type_name}' is not iterable"
-                        )))
-                    }
-                })
+                let contained = heap.with_entry_mut(*heap_id, |heap, data| match data {
+                    HeapData::List(list) => Some(sequence_contains(list.as_slice(), item, heap, interns)),
+                    HeapData::Tuple(tuple) => Some(sequence_contains(tuple.as_slice(), item, heap, interns)),
+                    HeapData::NamedTuple(nt) => Some(sequence_contains(nt.as_vec(), item, heap, interns)),
+                    HeapData::Array(array) => Some(array_contains(array, item, heap, interns)),
+                    HeapData::Dict(dict) => Some(dict.get(item, heap, interns).map(|m| m.is_some())),
+                    HeapData::Set(set) => Some(set.contains(item, heap, interns)),
+                    HeapData::FrozenSet(fset) => Some(fset.contains(item, heap, interns)),
+                    HeapData::Str(s) => Some(str_contains(s.as_str(), item, heap, interns)),
+                    HeapData::Bytes(b) => Some(bytes_contains(b.as_slice(), item, heap, interns)),
+                    HeapData::Range(range) => Some(Ok(range.contains_value(item))),
+                    // These have no lookup of their own, so they're searched by iterating them
+                    HeapData::Iter(_) | HeapData::LazySequence(_) | HeapData::StringIO(_) => None,
+                    other => Some(Err(ExcType::type_error_argument_not_iterable(other.py_type(heap)))),
+                });
+                match contained {
+                    Some(result) => result,
+                    None => self.iter_contains(item, heap, interns),
+                }
             }
             Self::InternString(string_id) => {
                 let container_str = interns.get_str(*string_id);
                 str_contains(container_str, item, heap, interns)
             }
-            _ => {
-                let type_name = self.py_type(heap);
-                Err(ExcType::type_error(format!(
-                    "argument of type '{type_name}' is not iterable"
-                )))
+            Self::InternBytes(bytes_id) => bytes_contains(interns.get_bytes(*bytes_id), item, heap, interns),
+            _ => Err(ExcType::type_error_argument_not_iterable(self.py_type(heap))),
+        }
+    }
+
+    /// Searches for `item` by iterating `self`, consuming it if it's an iterator.
+    fn iter_contains(&self, item: &Self, heap: &mut Heap<impl ResourceTracker>, interns: &Interns) -> RunResult<bool> {
+        let iter = MontyIter::new(self.clone_with_heap(heap), heap, interns)?;
+        let mut iter_guard = HeapGuard::new(iter, heap);
+        let (iter, heap) = iter_guard.as_parts_mut();
+        let mut guard = DepthGuard::default();
+        while let Some(el) = iter.for_next(heap, interns)? {
+            defer_drop!(el, heap);
+            if item.is(el) || item.py_eq(el, heap, &mut guard, interns)? {
+                return Ok(true);
             }
         }
+        Ok(false)
     }
 
     /// Gets an attribute from this value.
@@ -1742,6 +1745,10 @@ impl Value {
                     return Ok(AttrCallResult::Value(Self::Ref(str_id)));
                 }
             }
+            Self::DefFunction(f_id) if name_id == StaticStrings::DunderName => {
+                let func_name_id = interns.get_function(*f_id).name.name_id;
+                return Ok(AttrCallResult::Value(Self::InternString(func_name_id)));
+            }
             _ => {}
         }
         let type_name = self.py_type(heap);
@@ -1852,6 +1859,7 @@ impl Value {
     /// Performs a binary bitwise operation on two values.
     ///
     /// Python only supports bitwise operations on integers (and bools, which coerce to int).
+    /// `&`, `|` and `^` between two bools return a bool, as `bool` overrides them.
     /// Returns a `TypeError` if either operand is not an integer, bool, or LongInt.
     ///
     /// For shift operations:
@@ -1864,6 +1872,15 @@ impl Value {
         op: BitwiseOp,
         heap: &mut Heap<impl ResourceTracker>,
     ) -> Result<Self, RunError> {
+        if let (Self::Bool(l), Self::Bool(r)) = (self, other) {
+            match op {
+                BitwiseOp::And => return Ok(Self::Bool(l & r)),
+                BitwiseOp::Or => return Ok(Self::Bool(l | r)),
+                BitwiseOp::Xor => return Ok(Self::Bool(l ^ r)),
+                BitwiseOp::LShift | BitwiseOp::RShift => {}
+            }
+        }
+
         // Capture types for error messages
         let lhs_type = self.py_type(heap);
         let rhs_type = other.py_type(heap);
@@ -2059,6 +2076,51 @@ impl Value {
             _ => false,
         }
     }
+
+    /// Resolves what calling this value runs, or `None` if the value isn't callable.
+    ///
+    /// The VM dispatches every call through the returned `Callee`, so this is the one
+    /// place that decides which values are callable.
+    pub(crate) fn callee(&self, heap: &Heap<impl ResourceTracker>) -> Option<Callee> {
+        match self {
+            Self::Builtin(builtin) => Some(Callee::Builtin(*builtin)),
+            Self::ModuleFunction(mf) => Some(Callee::ModuleFunction(*mf)),
+            Self::ExtFunction(ext_id) => Some(Callee::ExtFunction(*ext_id)),
+            Self::DefFunction(func_id) => Some(Callee::DefFunction(*func_id)),
+            Self::Ref(heap_id) => match heap.get(*heap_id) {
+                HeapData::Closure(..) | HeapData::FunctionDefaults(..) => Some(Callee::HeapFunction(*heap_id)),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Returns true if calling the value is supported, as reported by `callable()`.
+    ///
+    /// Every other value raises "'{type}' object is not callable" when called.
+    pub fn is_callable(&self, heap: &Heap<impl ResourceTracker>) -> bool {
+        self.callee(heap).is_some()
+    }
+}
+
+/// What a callable value runs when called, as resolved by `Value::callee`.
+///
+/// Shared by `callable()` and the VM's call dispatch so the two can't disagree about
+/// which values are callable. Supporting a new kind of callable (e.g. bound methods or
+/// instances with `__call__`) means adding a variant here and implementing the VM's
+/// `Callable` trait for it.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum Callee {
+    /// A builtin function, type or exception class.
+    Builtin(Builtins),
+    /// A function from a built-in module, e.g. `asyncio.gather`.
+    ModuleFunction(ModuleFunctions),
+    /// A host-provided function; calling it suspends the VM.
+    ExtFunction(ExtFunctionId),
+    /// A defined function without defaults or captured variables.
+    DefFunction(FunctionId),
+    /// A closure or function with defaults stored on the heap.
+    HeapFunction(HeapId),
 }
 
 /// Interned or heap-owned string identifier.
@@ -2284,6 +2346,62 @@ const fn singleton_id(slot: SingletonSlot) -> usize {
     SINGLETON_ID_TAG | ((slot as usize) & SINGLETON_ID_MASK)
 }
 
+/// Modulus of CPython's numeric hash, the Mersenne prime `2**61 - 1`.
+///
+/// Numeric hashes are defined as the value reduced modulo this prime, which makes
+/// `hash(x) == hash(y)` whenever `x == y` across int, float and bool.
+pub(crate) const NUMERIC_HASH_MODULUS: u64 = (1 << 61) - 1;
+
+/// Hash of `float('inf')`, matching CPython's `sys.hash_info.inf`.
+pub(crate) const NUMERIC_HASH_INF: i64 = 314_159;
+
+/// Computes CPython's hash of an int.
+///
+/// The result is `abs(value) % (2**61 - 1)` with the sign of `value`, except that
+/// `-1` maps to `-2` (CPython reserves `-1` as an error marker). The signed hash is
+/// returned reinterpreted as `u64`, so `hash()` and dict keys agree with CPython.
+pub(crate) fn hash_int(value: i64) -> u64 {
+    let magnitude = i64::try_from(value.unsigned_abs() % NUMERIC_HASH_MODULUS).expect("reduced hash fits in i64");
+    signed_numeric_hash(value < 0, magnitude)
+}
+
+/// Computes CPython's hash of a float.
+///
+/// Finite floats hash like the rational number they represent, so integral floats hash
+/// like the equal int. Infinities hash to `±314159` and NaN hashes to `0` (CPython uses
+/// the object identity for NaN, which would make hashes differ between runs).
+pub(crate) fn hash_float(value: f64) -> u64 {
+    if value.is_nan() {
+        return 0;
+    }
+    if value.is_infinite() {
+        return signed_numeric_hash(value < 0.0, NUMERIC_HASH_INF);
+    }
+    // Decompose |value| into mantissa * 2**exponent with an integer mantissa
+    let bits = value.to_bits();
+    let raw_exponent = i32::try_from((bits >> 52) & 0x7ff).expect("11-bit exponent fits in i32");
+    let fraction = bits & ((1 << 52) - 1);
+    let (mantissa, exponent) = if raw_exponent == 0 {
+        // Subnormal: no implicit leading bit
+        (fraction, -1074)
+    } else {
+        (fraction | (1 << 52), raw_exponent - 1075)
+    };
+    // 2**61 == 1 (mod 2**61 - 1), so multiplying by 2**exponent is a rotation by exponent mod 61;
+    // the mantissa (< 2**53) is already reduced
+    let shift = exponent.rem_euclid(61);
+    let rotated = (u128::from(mantissa) << shift) % u128::from(NUMERIC_HASH_MODULUS);
+    let magnitude = i64::try_from(rotated).expect("reduced hash fits in i64");
+    signed_numeric_hash(value < 0.0, magnitude)
+}
+
+/// Applies the sign to a reduced numeric hash and maps `-1` to `-2`, matching CPython.
+pub(crate) fn signed_numeric_hash(negative: bool, magnitude: i64) -> u64 {
+    let hash = if negative { -magnitude } else { magnitude };
+    let hash = if hash == -1 { -2 } else { hash };
+    u64::from_ne_bytes(hash.to_ne_bytes())
+}
+
 /// Computes Python-style floor division and modulo.
 ///
 /// Python's division rounds toward negative infinity (floor division),
@@ -2302,6 +2420,58 @@ pub(crate) fn floor_divmod(a: i64, b: i64) -> Option<(i64, i64)> {
     }
 }
 
+/// Computes Python-style floor division and modulo for floats.
+///
+/// Mirrors CPython's `float_divmod`: the remainder has the same sign as the divisor,
+/// and the quotient is computed from the exact remainder rather than by flooring
+/// `a / b`, which can be off by one due to rounding (e.g. `1 // 0.1 == 9.0`).
+///
+/// The caller is responsible for rejecting a zero divisor.
+pub(crate) fn float_divmod(a: f64, b: f64) -> (f64, f64) {
+    let mut rem = a % b;
+    let mut div = (a - rem) / b;
+    if rem == 0.0 {
+        // Zero remainder takes the sign of the divisor
+        rem = 0.0_f64.copysign(b);
+    } else if (rem < 0.0) != (b < 0.0) {
+        rem += b;
+        div -= 1.0;
+    }
+    let quot = if div == 0.0 {
+        0.0_f64.copysign(a / b)
+    } else {
+        // Snap the quotient to the nearest integer to absorb rounding error in `div`
+        let floor = div.floor();
+        if div - floor > 0.5 { floor + 1.0 } else { floor }
+    };
+    (quot, rem)
+}
+
+/// Converts a `LongInt`/`float` operand pair (in either order) to two `f64`s.
+///
+/// Returns `Ok(None)` if the `Ref` operand is not a `LongInt`, and raises `OverflowError`
+/// if the `LongInt` is too large to represent as a float, matching CPython.
+pub(crate) fn long_int_float_operands(
+    a: &Value,
+    b: &Value,
+    heap: &Heap<impl ResourceTracker>,
+) -> RunResult<Option<(f64, f64)>> {
+    let to_f64 = |value: &Value| -> RunResult<Option<f64>> {
+        match value {
+            Value::Float(f) => Ok(Some(*f)),
+            Value::Ref(id) => match heap.get(*id) {
+                HeapData::LongInt(li) => big_int_to_f64(li.inner()).map(Some),
+                _ => Ok(None),
+            },
+            _ => Ok(None),
+        }
+    };
+    match (to_f64(a)?, to_f64(b)?) {
+        (Some(a), Some(b)) => Ok(Some((a, b))),
+        _ => Ok(None),
+    }
+}
+
 /// Converts a heap `HeapId` into its tagged `id()` value, ensuring it never collides with other spaces.
 #[inline]
 pub fn heap_tagged_id(heap_id: HeapId) -> usize {
@@ -2312,7 +2482,7 @@ pub fn heap_tagged_id(heap_id: HeapId) -> usize {
 /// Uses the value's hash combined with a type tag to ensure uniqueness across types.
 #[inline]
 fn int_value_id(value: i64) -> usize {
-    let mut hasher = DefaultHasher::new();
+    let mut hasher = StableHasher::default();
     value.hash(&mut hasher);
     let hash_u64 = hasher.finish();
     // Mask to usize range before conversion to handle 32-bit platforms
@@ -2325,7 +2495,7 @@ fn int_value_id(value: i64) -> usize {
 /// Uses the bit representation's hash for consistency (handles NaN, infinities, etc.).
 #[inline]
 fn float_value_id(value: f64) -> usize {
-    let mut hasher = DefaultHasher::new();
+    let mut hasher = StableHasher::default();
     value.to_bits().hash(&mut hasher);
     let hash_u64 = hasher.finish();
     // Mask to usize range before conversion to handle 32-bit platforms
@@ -2337,7 +2507,7 @@ fn float_value_id(value: f64) -> usize {
 /// Computes a deterministic ID for a builtin based on its discriminant.
 #[inline]
 fn builtin_value_id(b: Builtins) -> usize {
-    let mut hasher = DefaultHasher::new();
+    let mut hasher = StableHasher::default();
     b.hash(&mut hasher);
     let hash_u64 = hasher.finish();
     // wrapping here is fine
@@ -2382,7 +2552,7 @@ fn external_future_value_id(call_id: CallId) -> usize {
 /// Computes a deterministic ID for a module function based on its discriminant.
 #[inline]
 fn module_function_value_id(mf: ModuleFunctions) -> usize {
-    let mut hasher = DefaultHasher::new();
+    let mut hasher = StableHasher::default();
     mf.hash(&mut hasher);
     let hash_u64 = hasher.finish();
     // wrapping here is fine
@@ -2449,19 +2619,53 @@ fn str_contains(
     interns: &Interns,
 ) -> RunResult<bool> {
     match item {
-        Value::InternString(item_id) => {
-            let item_str = interns.get_str(*item_id);
-            Ok(container_str.contains(item_str))
-        }
+        Value::InternString(item_id) => return Ok(container_str.contains(interns.get_str(*item_id))),
         Value::Ref(item_heap_id) => {
             if let HeapData::Str(item_str) = heap.get(*item_heap_id) {
-                Ok(container_str.contains(item_str.as_str()))
-            } else {
-                Err(ExcType::type_error("'in <str>' requires string as left operand"))
+                return Ok(container_str.contains(item_str.as_str()));
             }
         }
-        _ => Err(ExcType::type_error("'in <str>' requires string as left operand")),
+        _ => {}
+    }
+    Err(ExcType::type_error(format!(
+        "'in <string>' requires string as left operand, not {}",
+        item.py_type(heap)
+    )))
+}
+
+/// Checks whether `item` is in a list, tuple or namedtuple.
+///
+/// Like CPython, an element that is the item itself matches without calling `==`.
+fn sequence_contains(
+    items: &[Value],
+    item: &Value,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<bool> {
+    let mut guard = DepthGuard::default();
+    for el in items {
+        if item.is(el) || item.py_eq(el, heap, &mut guard, interns)? {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Checks whether `item` is in an array, comparing it with each element by value.
+fn array_contains(
+    array: &Array,
+    item: &Value,
+    heap: &mut Heap<impl ResourceTracker>,
+    interns: &Interns,
+) -> RunResult<bool> {
+    let mut guard = DepthGuard::default();
+    for i in 0..array.len() {
+        let el = array.get(i).expect("index is in range");
+        if item.py_eq(&el, heap, &mut guard, interns)? {
+            return Ok(true);
+        }
     }
+    Ok(false)
 }
 
 /// Computes the number of significant bits in an i64.
@@ -2481,7 +2685,7 @@ fn i64_bits(value: i64) -> u64 {
 ///
 /// Uses repeated squaring for efficiency. This is needed when the exponent
 /// doesn't fit in a u32, which is required by the `num-bigint` pow method.
-fn bigint_pow(base: BigInt, exp: u64) -> BigInt {
+pub(crate) fn bigint_pow(base: BigInt, exp: u64) -> BigInt {
     if exp == 0 {
         return BigInt::from(1);
     }
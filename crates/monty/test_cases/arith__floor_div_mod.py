@@ -0,0 +1,98 @@
+# Tests for floor division, modulo and divmod() with negative operands.
+# Python rounds the quotient toward negative infinity and the remainder
+# takes the sign of the divisor, unlike Rust's truncating `/` and `%`.
+
+# === Int // and % ===
+assert 7 // 2 == 3, 'positive // positive'
+assert -7 // 2 == -4, 'negative // positive'
+assert 7 // -2 == -4, 'positive // negative'
+assert -7 // -2 == 3, 'negative // negative'
+assert 7 % 3 == 1, 'positive % positive'
+assert -7 % 3 == 2, 'negative % positive'
+assert 7 % -3 == -2, 'positive % negative'
+assert -7 % -3 == -1, 'negative % negative'
+assert -6 % 3 == 0, 'exact negative division'
+assert 0 % -5 == 0, 'zero % negative'
+
+# === Int identity a == (a // b) * b + a % b ===
+for a in [-7, -6, -1, 0, 1, 6, 7]:
+    for b in [-3, -2, 2, 3]:
+        assert (a // b) * b + a % b == a, f'identity for {a}, {b}'
+        assert divmod(a, b) == (a // b, a % b), f'divmod matches for {a}, {b}'
+
+# === Float // and % ===
+assert 7.5 // 2 == 3.0, 'float // int'
+assert -7.5 // 2 == -4.0, 'negative float // int'
+assert 7.5 // -2 == -4.0, 'float // negative int'
+assert -7.5 % 2 == 0.5, 'negative float % int'
+assert 7.5 % -2 == -0.5, 'float % negative int'
+assert -7.5 % -2 == -1.5, 'negative float % negative int'
+assert -7 % 2.5 == 0.5, 'negative int % float'
+assert 7 // -2.5 == -3.0, 'int // negative float'
+assert 1 // 0.1 == 9.0, 'floor division uses exact remainder'
+assert 1 % 0.1 == 0.09999999999999995, 'modulo uses exact remainder'
+assert str(-6.0 % 3) == '0.0', 'zero remainder takes sign of positive divisor'
+assert str(6.0 % -3) == '-0.0', 'zero remainder takes sign of negative divisor'
+assert str(0.0 // -1.0) == '-0.0', 'zero quotient keeps sign'
+
+# === divmod() with floats ===
+assert divmod(7.5, 2) == (3.0, 1.5), 'float divmod'
+assert divmod(-7.5, 2) == (-4.0, 0.5), 'negative float divmod'
+assert divmod(7.5, -2) == (-4.0, -0.5), 'float divmod negative divisor'
+assert divmod(-7, 2.5) == (-3.0, 0.5), 'int and float divmod'
+assert divmod(1, 0.1) == (9.0, 0.09999999999999995), 'divmod uses exact remainder'
+for a in [-7.5, -1.0, 0.5, 7.5]:
+    for b in [-2.5, -2, 2, 2.5]:
+        assert divmod(a, b) == (a // b, a % b), f'float divmod matches for {a}, {b}'
+
+# === Bools ===
+assert True // 2 == 0, 'bool // int'
+assert -5 // True == -5, 'int // bool'
+assert True % 2 == 1, 'bool % int'
+assert -5 % True == 0, 'int % bool'
+assert True % -2 == -1, 'bool % negative int'
+assert -2.5 % True == 0.5, 'float % bool'
+assert True // -0.5 == -2.0, 'bool // negative float'
+assert True % True == 0, 'bool % bool'
+assert divmod(True, -2) == (-1, -1), 'divmod with bool'
+
+# === Modulo comparisons ===
+n = -7
+assert n % 3 == 2, 'mod equality with negative int'
+assert -7.5 % 2 == 0.5, 'mod equality with negative float'
+assert not (n % 3 == -1), 'mod equality uses floor semantics'
+
+# === Big ints ===
+big = 10**20
+assert -big // 3 == -33333333333333333334, 'negative big int // int'
+assert -big % 3 == 2, 'negative big int % int'
+assert big % -3 == -2, 'big int % negative int'
+assert divmod(-big, 7) == (-big // 7, -big % 7), 'big int divmod'
+assert big // 3.0 == 3.333333333333333e19, 'big int // float'
+assert -big % 3.0 == 2.0, 'negative big int % float'
+assert divmod(big, -3.0) == (big // -3.0, big % -3.0), 'big int float divmod'
+
+# === Division by zero ===
+for a, b in [(1, 0), (1.5, 0), (1, 0.0), (True, False), (big, 0.0)]:
+    try:
+        a % b
+        assert False, f'{a} % {b} should raise'
+    except ZeroDivisionError:
+        pass
+    try:
+        a // b
+        assert False, f'{a} // {b} should raise'
+    except ZeroDivisionError:
+        pass
+    try:
+        divmod(a, b)
+        assert False, f'divmod({a}, {b}) should raise'
+    except ZeroDivisionError:
+        pass
+
+x = 1.5
+try:
+    x % 0.0 == 1
+    assert False, 'mod comparison by zero should raise'
+except ZeroDivisionError:
+    pass
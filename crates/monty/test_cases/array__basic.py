@@ -0,0 +1,242 @@
+from array import array
+
+# === construction ===
+a = array('i', [1, 2, 3])
+assert len(a) == 3, 'len'
+assert a.typecode == 'i', 'typecode'
+assert a.itemsize == 4, 'itemsize'
+assert array('b').itemsize == 1, 'itemsize of b'
+assert array('d').itemsize == 8, 'itemsize of d'
+assert len(array('d')) == 0, 'empty array'
+assert array('i', range(4)).tolist() == [0, 1, 2, 3], 'from a range'
+assert array('i', (5, 6)).tolist() == [5, 6], 'from a tuple'
+assert array('i', (x * x for x in range(3))).tolist() == [0, 1, 4], 'from a generator'
+assert array('d', [1, 2.5, True]).tolist() == [1.0, 2.5, 1.0], 'float array converts ints and bools'
+assert array('i', [True, False]).tolist() == [1, 0], 'int array accepts bools'
+assert array('d', array('i', [1, 2])).tolist() == [1.0, 2.0], 'from another array'
+assert array('q', [2**63 - 1]).tolist() == [2**63 - 1], 'largest q'
+
+# === construction errors ===
+try:
+    array('x')
+    assert False, 'bad typecode should fail'
+except ValueError:
+    pass
+try:
+    array(1)
+    assert False, 'non-str typecode should fail'
+except TypeError as e:
+    assert str(e) == 'array() argument 1 must be a unicode character, not int', 'typecode type message'
+try:
+    array('ii')
+    assert False, 'long typecode should fail'
+except TypeError as e:
+    assert str(e) == 'array() argument 1 must be a unicode character, not str', 'long typecode message'
+try:
+    array('i', 'abc')
+    assert False, 'str initializer should fail'
+except TypeError as e:
+    assert str(e) == "cannot use a str to initialize an array with typecode 'i'", 'str initializer message'
+try:
+    array('i', [1.5])
+    assert False, 'float in an int array should fail'
+except TypeError as e:
+    assert str(e) == "'float' object cannot be interpreted as an integer", 'float item message'
+try:
+    array('d', ['a'])
+    assert False, 'str in a float array should fail'
+except TypeError as e:
+    assert str(e) == 'must be real number, not str', 'str item message'
+try:
+    array('i', array('d', [1.0]))
+    assert False, 'float array into an int array should fail'
+except TypeError:
+    pass
+
+# === ranges ===
+try:
+    array('b', [128])
+    assert False, 'b overflow should fail'
+except OverflowError as e:
+    assert str(e) == 'signed char is greater than maximum', 'b max message'
+try:
+    array('b', [-129])
+    assert False, 'b underflow should fail'
+except OverflowError as e:
+    assert str(e) == 'signed char is less than minimum', 'b min message'
+try:
+    array('B', [-1])
+    assert False, 'B negative should fail'
+except OverflowError as e:
+    assert str(e) == 'unsigned byte integer is less than minimum', 'B min message'
+try:
+    array('h', [40000])
+    assert False, 'h overflow should fail'
+except OverflowError as e:
+    assert str(e) == 'signed short integer is greater than maximum', 'h max message'
+try:
+    array('I', [-1])
+    assert False, 'I negative should fail'
+except OverflowError as e:
+    assert str(e) == "can't convert negative value to unsigned int", 'I min message'
+try:
+    array('l', [2**70])
+    assert False, 'huge l should fail'
+except OverflowError:
+    pass
+assert array('b', [-128, 127]).tolist() == [-128, 127], 'b bounds'
+assert array('B', [0, 255]).tolist() == [0, 255], 'B bounds'
+assert array('f', [0.1])[0] == 0.10000000149011612, 'f rounds to single precision'
+assert array('d', [0.1])[0] == 0.1, 'd keeps double precision'
+
+# === indexing ===
+a = array('i', [10, 20, 30, 40])
+assert a[0] == 10, 'first item'
+assert a[-1] == 40, 'negative index'
+assert type(a[0]) is int, 'int items'
+assert type(array('d', [1])[0]) is float, 'float items'
+assert a[1:3].tolist() == [20, 30], 'slice'
+assert a[::-1].tolist() == [40, 30, 20, 10], 'reversed slice'
+assert a[::2].tolist() == [10, 30], 'step slice'
+assert a[-2::-2].tolist() == [30, 10], 'negative step from the end'
+assert a[5:].tolist() == [], 'empty slice'
+assert a[1:3].typecode == 'i', 'slice keeps the typecode'
+try:
+    a[4]
+    assert False, 'index out of range should fail'
+except IndexError as e:
+    assert str(e) == 'array index out of range', 'index message'
+try:
+    a['x']
+    assert False, 'str index should fail'
+except TypeError as e:
+    assert str(e) == 'array indices must be integers', 'index type message'
+
+# === assignment ===
+a[0] = 11
+a[-1] = 44
+assert a.tolist() == [11, 20, 30, 44], 'setitem'
+a[1] = True
+assert a[1] == 1, 'setitem with a bool'
+try:
+    a[10] = 1
+    assert False, 'assignment out of range should fail'
+except IndexError as e:
+    assert str(e) == 'array assignment index out of range', 'assignment message'
+try:
+    a[0] = 1.5
+    assert False, 'float assignment should fail'
+except TypeError:
+    pass
+b = array('b', [0])
+try:
+    b[0] = 1000
+    assert False, 'out of range assignment should fail'
+except OverflowError:
+    pass
+assert b[0] == 0, 'failed assignment leaves the item'
+
+# === methods ===
+a = array('i')
+a.append(1)
+a.append(2)
+a.extend([3, 4])
+a.extend(array('i', [5]))
+assert a.tolist() == [1, 2, 3, 4, 5], 'append and extend'
+a.insert(0, 0)
+a.insert(-1, 9)
+a.insert(100, 6)
+assert a.tolist() == [0, 1, 2, 3, 4, 9, 5, 6], 'insert'
+assert a.pop() == 6, 'pop'
+assert a.pop(0) == 0, 'pop index'
+a.remove(9)
+assert a.tolist() == [1, 2, 3, 4, 5], 'remove'
+assert a.index(3) == 2, 'index'
+assert a.index(3.0) == 2, 'index with an equal float'
+assert a.count(2) == 1, 'count'
+assert array('d', [1, 1, 2]).count(1) == 2, 'count in a float array'
+a.reverse()
+assert a.tolist() == [5, 4, 3, 2, 1], 'reverse'
+a.extend(a)
+assert a.tolist() == [5, 4, 3, 2, 1, 5, 4, 3, 2, 1], 'extend with itself'
+assert type(a.tolist()) is list, 'tolist gives a list'
+try:
+    array('i').pop()
+    assert False, 'pop from empty should fail'
+except IndexError as e:
+    assert str(e) == 'pop from empty array', 'pop empty message'
+try:
+    array('i', [1]).pop(5)
+    assert False, 'pop out of range should fail'
+except IndexError as e:
+    assert str(e) == 'pop index out of range', 'pop range message'
+try:
+    array('i', [1]).remove(2)
+    assert False, 'remove missing should fail'
+except ValueError as e:
+    assert str(e) == 'array.remove(x): x not in array', 'remove message'
+try:
+    array('i', [1]).index(2)
+    assert False, 'index missing should fail'
+except ValueError as e:
+    assert str(e) == 'array.index(x): x not in array', 'index missing message'
+try:
+    array('i').extend(array('d'))
+    assert False, 'extend with another typecode should fail'
+except TypeError as e:
+    assert str(e) == 'can only extend with array of same kind', 'extend kind message'
+c = array('i', [1])
+try:
+    c.extend([2, 'x', 3])
+    assert False, 'extend with a bad item should fail'
+except TypeError:
+    pass
+assert c.tolist() == [1, 2], 'extend keeps items before a bad one'
+
+# === iteration and containment ===
+a = array('i', [1, 2, 3])
+assert list(a) == [1, 2, 3], 'list()'
+assert [x * 2 for x in a] == [2, 4, 6], 'comprehension'
+assert sum(a) == 6, 'sum'
+assert max(array('d', [1.5, 0.5])) == 1.5, 'max'
+assert sorted(array('i', [3, 1, 2])) == [1, 2, 3], 'sorted'
+assert 2 in a, 'in'
+assert 2.0 in a, 'in with an equal float'
+assert 5 not in a, 'not in'
+total = 0
+for x in a:
+    total += x
+assert total == 6, 'for loop'
+
+# === operators and comparison ===
+assert (array('i', [1]) + array('i', [2])).tolist() == [1, 2], 'concatenation'
+a = array('i', [1])
+a += array('i', [2])
+assert a.tolist() == [1, 2], 'augmented concatenation'
+assert array('i', [1, 2]) == array('i', [1, 2]), 'equal arrays'
+assert array('i', [1, 2]) != array('i', [2, 1]), 'unequal arrays'
+assert array('i', [1]) == array('d', [1.0]), 'equal across typecodes'
+assert array('i', [1]) != [1], 'not equal to a list'
+assert bool(array('i', [0])), 'nonempty is truthy'
+assert not array('i'), 'empty is falsy'
+try:
+    array('i') + array('d')
+    assert False, 'adding different typecodes should fail'
+except TypeError:
+    pass
+try:
+    hash(array('i'))
+    assert False, 'arrays are unhashable'
+except TypeError as e:
+    assert str(e) == "unhashable type: 'array.array'", 'hash message'
+
+# === repr ===
+assert repr(array('i')) == "array('i')", 'empty repr'
+assert repr(array('i', [1, 2])) == "array('i', [1, 2])", 'int repr'
+assert repr(array('d', [1, 0.5])) == "array('d', [1.0, 0.5])", 'float repr'
+assert str(array('b', [-1])) == "array('b', [-1])", 'str'
+
+# === types ===
+assert isinstance(array('i'), array), 'isinstance'
+assert type(array('i')) is array, 'type'
+assert repr(array) == "<class 'array.array'>", 'class repr'
@@ -0,0 +1,32 @@
+# === __debug__ is true without optimization ===
+assert __debug__ is True, '__debug__ is True'
+assert type(__debug__) is bool, '__debug__ is a bool'
+
+
+def debug_in_function():
+    return __debug__
+
+
+assert debug_in_function() is True, '__debug__ inside a function'
+
+ran = []
+if __debug__:
+    ran.append('debug')
+assert ran == ['debug'], 'code guarded by __debug__ runs'
+
+# === assert message is only evaluated on failure ===
+calls = []
+
+
+def message():
+    calls.append('message')
+    return 'failed'
+
+
+assert True, message()
+assert calls == [], 'message is not evaluated when the assert passes'
+try:
+    assert False, message()
+except AssertionError as e:
+    assert str(e) == 'failed', 'message is used for the error'
+assert calls == ['message'], 'message is evaluated once when the assert fails'
@@ -0,0 +1,9 @@
+__debug__ = False
+"""
+TRACEBACK:
+Traceback (most recent call last):
+  File "assert__debug_assign_error.py", line 1
+    __debug__ = False
+    ^^^^^^^^^
+SyntaxError: cannot assign to __debug__
+"""
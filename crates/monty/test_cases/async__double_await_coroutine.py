@@ -11,6 +11,6 @@ TRACEBACK:
 Traceback (most recent call last):
   File "async__double_await_coroutine.py", line 8, in <module>
     await coro  # pyright: ignore
-    ~~~~~~~~~~
+    ^^^^^^^^^^
 RuntimeError: cannot reuse already awaited coroutine
 """
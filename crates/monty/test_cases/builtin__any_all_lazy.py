@@ -0,0 +1,29 @@
+# === any() stops at the first truthy item ===
+it = iter([0, '', 3, 4, 5])
+assert any(it) is True, 'any finds truthy item'
+assert next(it) == 4, 'any leaves the rest of the iterator unconsumed'
+assert list(it) == [5], 'remaining items still available'
+
+it = iter([0, 0])
+assert any(it) is False, 'any of falsy iterator'
+assert next(it, 'done') == 'done', 'any exhausts iterator without a match'
+
+# === all() stops at the first falsy item ===
+it = iter([1, 'a', [], 'b', 'c'])
+assert all(it) is False, 'all finds falsy item'
+assert next(it) == 'b', 'all leaves the rest of the iterator unconsumed'
+
+it = iter(range(1, 4))
+assert all(it) is True, 'all of truthy iterator'
+assert list(it) == [], 'all exhausts iterator without a match'
+
+# === remaining items are untouched ===
+it = iter([1, 0, 'x'])
+assert not all(it), 'all stops before the remaining items'
+assert next(it) == 'x', 'remaining item untouched'
+
+# === other lazy inputs ===
+assert any(iter({'a': 0})), 'iterator over dict keys'
+assert any(range(10**12)), 'any over huge range stops at second item'
+assert not all(range(10**12)), 'all over huge range stops at first item'
+assert any(reversed([0, 0, 1])), 'reversed input'
@@ -0,0 +1,87 @@
+# === Functions ===
+def plain():
+    return 1
+
+
+def with_default(x=1):
+    return x
+
+
+def make_closure():
+    captured = 2
+
+    def inner():
+        return captured
+
+    return inner
+
+
+assert callable(plain), 'def function is callable'
+assert callable(with_default), 'function with defaults is callable'
+assert callable(make_closure()), 'closure is callable'
+assert callable(lambda: None), 'lambda is callable'
+
+# === Builtins and types ===
+assert callable(len), 'builtin function is callable'
+assert callable(callable), 'callable itself is callable'
+assert callable(int), 'int type is callable'
+assert callable(list), 'list type is callable'
+assert callable(type(None)), 'NoneType is callable'
+assert callable(ValueError), 'exception class is callable'
+
+# === Non-callables ===
+assert not callable(1), 'int is not callable'
+assert not callable('abc'), 'str is not callable'
+assert not callable(None), 'None is not callable'
+assert not callable([plain]), 'list of functions is not callable'
+assert not callable(ValueError('x')), 'exception instance is not callable'
+
+# === Functions as values ===
+funcs = [plain, len, make_closure()]
+assert all(callable(f) for f in funcs), 'functions stored in a list stay callable'
+
+
+def apply(f, value):
+    return f(value)
+
+
+assert apply(len, 'abcd') == 4, 'builtin passed as argument'
+assert apply(with_default, 5) == 5, 'function passed as argument'
+assert apply(lambda v: v * 2, 3) == 6, 'lambda passed as argument'
+
+# === callable() agrees with calling ===
+candidates = [plain, with_default, make_closure(), len, int, ValueError, 1, 'abc', None, [1], (1,), {}]
+for c in candidates:
+    try:
+        c()
+        called = True
+    except TypeError as e:
+        called = 'not callable' not in str(e)
+    assert called == callable(c), f'callable() disagrees with calling {c!r}'
+
+args = (with_default, 7)
+assert apply(*args) == 7, 'function passed through star args'
+assert with_default(**{'x': 3}) == 3, 'function with defaults called with star kwargs'
+assert make_closure()(*()) == 2, 'closure called with empty star args'
+
+# === Calling a non-callable ===
+not_a_function = 42
+try:
+    not_a_function()
+    assert False, 'calling an int should raise'
+except TypeError as e:
+    assert str(e) == "'int' object is not callable", 'not callable message'
+
+items = [1, 2]
+try:
+    items()
+    assert False, 'calling a list should raise'
+except TypeError as e:
+    assert str(e) == "'list' object is not callable", 'not callable message for heap value'
+
+# === Argument errors ===
+try:
+    callable()
+    assert False, 'callable() with no args should raise'
+except TypeError as e:
+    assert str(e) == 'callable() takes exactly one argument (0 given)', 'callable() arg count message'
@@ -0,0 +1,2 @@
+enumerate([], foo=1)
+# Raise=TypeError("'foo' is an invalid keyword argument for enumerate()")
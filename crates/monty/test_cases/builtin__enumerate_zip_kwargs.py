@@ -0,0 +1,27 @@
+# === enumerate start ===
+assert list(enumerate('ab', start=1)) == [(1, 'a'), (2, 'b')], 'start by keyword'
+assert list(enumerate('ab', 5)) == [(5, 'a'), (6, 'b')], 'start by position'
+assert list(enumerate([10], start=-3)) == [(-3, 10)], 'negative start'
+assert list(enumerate('ab', start=True)) == [(1, 'a'), (2, 'b')], 'bool start'
+assert list(enumerate([], start=7)) == [], 'empty iterable with start'
+
+# === zip strict ===
+assert list(zip([1, 2], 'ab', strict=True)) == [(1, 'a'), (2, 'b')], 'strict with equal lengths'
+assert list(zip(strict=True)) == [], 'strict with no iterables'
+assert list(zip([1], [2], strict=0)) == [(1, 2)], 'strict is interpreted by truthiness'
+assert list(zip([1, 2], [3], strict=False)) == [(1, 3)], 'non-strict truncates'
+
+
+def zip_error(*iterables):
+    try:
+        list(zip(*iterables, strict=True))
+    except ValueError as e:
+        return str(e)
+    return None
+
+
+assert zip_error([1], [1, 2]) == 'zip() argument 2 is longer than argument 1', 'second longer'
+assert zip_error([1, 2], [1]) == 'zip() argument 2 is shorter than argument 1', 'second shorter'
+assert zip_error([1, 2], [1, 2], [1]) == 'zip() argument 3 is shorter than arguments 1-2', 'third shorter'
+assert zip_error([1], [1], [1, 2]) == 'zip() argument 3 is longer than arguments 1-2', 'third longer'
+assert zip_error([], [], []) is None, 'all empty'
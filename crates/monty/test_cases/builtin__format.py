@@ -0,0 +1,201 @@
+# === default spec ===
+assert format(42) == '42', 'format int without spec'
+assert format(3.5) == '3.5', 'format float without spec'
+assert format('hi') == 'hi', 'format str without spec'
+assert format(True) == 'True', 'format bool without spec'
+assert format(None) == 'None', 'format None without spec'
+assert format([1, 2], '') == '[1, 2]', 'empty spec is str()'
+assert format(1.0) == '1.0', 'float repr keeps .0'
+assert format(1e16) == str(1e16), 'format large float matches str()'
+
+# === format matches f-strings ===
+x = 1234.5678
+for spec in ['', '.2f', '>12', '+,.1f', 'e', '%', '012.3f']:
+    assert format(x, spec) == f'{x:{spec}}', 'format() equals f-string for ' + spec
+
+# === fill and align ===
+assert format('hi', '*^7') == '**hi***', 'center with fill'
+assert format('hi', '5') == 'hi   ', 'strings left-align by default'
+assert format(42, '5') == '   42', 'ints right-align by default'
+assert format(42, '<5') == '42   ', 'int left align'
+assert format(-42, '=8') == '-     42', 'sign-aware padding'
+assert format(-42, '08') == '-0000042', 'zero padding after sign'
+assert format(42, 'x<08') == '42xxxxxx', 'explicit fill wins over zero pad'
+assert format(3.5, '08.2f') == '00003.50', 'zero pad float'
+assert format('ab', '.1') == 'a', 'string precision truncates'
+assert format('abc', '>5.2') == '   ab', 'truncate then pad'
+
+# === sign ===
+assert format(5, '+') == '+5', 'plus sign on positive int'
+assert format(5, ' ') == ' 5', 'space sign on positive int'
+assert format(-5, ' ') == '-5', 'space sign on negative int'
+assert format(2.5, '+.1f') == '+2.5', 'plus sign on float'
+assert format(-0.0, '.1f') == '-0.0', 'negative zero keeps sign'
+
+# === thousands separators ===
+assert format(1234567, ',') == '1,234,567', 'comma grouping'
+assert format(1234567, '_') == '1_234_567', 'underscore grouping'
+assert format(-1234567, ',d') == '-1,234,567', 'negative grouping'
+assert format(1234, '010,') == '00,001,234', 'zero padding is grouped'
+assert format(1234, '09,') == '0,001,234', 'zero padding never starts with separator'
+assert format(255, '_b') == '1111_1111', 'binary groups of four'
+assert format(0xABCDEF, '#_x') == '0xab_cdef', 'hex groups with prefix'
+assert format(1234567.891, ',.2f') == '1,234,567.89', 'float grouping'
+assert format(1234567.5, ',') == '1,234,567.5', 'float grouping without type'
+assert format(12345678901234567890, ',') == '12,345,678,901,234,567,890', 'long int grouping'
+
+# === integer presentation types ===
+assert format(255, 'b') == '11111111', 'binary'
+assert format(255, 'o') == '377', 'octal'
+assert format(255, 'x') == 'ff', 'hex lower'
+assert format(255, 'X') == 'FF', 'hex upper'
+assert format(255, '#b') == '0b11111111', 'binary with prefix'
+assert format(255, '#o') == '0o377', 'octal with prefix'
+assert format(255, '#X') == '0XFF', 'hex upper with prefix'
+assert format(-255, '#x') == '-0xff', 'negative hex with prefix'
+assert format(255, '#010x') == '0x000000ff', 'zero pad after prefix'
+assert format(97, 'c') == 'a', 'char'
+assert format(65, '^3c') == ' A ', 'char centered'
+assert format(42, 'n') == '42', 'n type on int'
+assert format(10, '.2f') == '10.00', 'int with float type'
+assert format(1, '%') == '100.000000%', 'int with percent type'
+assert format(True, 'd') == '1', 'bool with int type'
+assert format(True, '>5') == '    1', 'bool with spec formats as int'
+assert format(2**70, 'x') == '400000000000000000', 'long int hex'
+assert format(-(2**70), '#b') == '-0b1' + '0' * 70, 'negative long int binary'
+
+# === float presentation types ===
+assert format(3.14159, '.2f') == '3.14', 'fixed precision'
+assert format(3.14159, 'f') == '3.141590', 'fixed default precision'
+assert format(2.5, '.0f') == '2', 'fixed rounds half to even'
+assert format(2.0, '#.0f') == '2.', 'alternate keeps point'
+assert format(12345.678, 'e') == '1.234568e+04', 'exponent'
+assert format(12345.678, '.2E') == '1.23E+04', 'exponent upper'
+assert format(0.00012, '.1e') == '1.2e-04', 'negative exponent'
+assert format(0.5, '%') == '50.000000%', 'percent'
+assert format(0.256, '.1%') == '25.6%', 'percent precision'
+assert format(1234.5, 'g') == '1234.5', 'general fixed'
+assert format(1234567.0, 'g') == '1.23457e+06', 'general exponent'
+assert format(0.00001, 'g') == '1e-05', 'general small exponent'
+assert format(0.0001, 'g') == '0.0001', 'general small fixed'
+assert format(100.0, 'g') == '100', 'general strips zeros'
+assert format(100.0, '#g') == '100.000', 'alternate general keeps zeros'
+assert format(1e10, 'G') == '1E+10', 'general upper'
+assert format(123.456, '.2') == '1.2e+02', 'no type with precision'
+assert format(1.0, '.3') == '1.0', 'no type keeps one decimal'
+assert format(12.0, '.2') == '1.2e+01', 'no type switches to exponent at precision - 1'
+assert format(12.0, '.3') == '12.0', 'no type stays fixed below precision - 1'
+assert format(0.1, '10') == '       0.1', 'float width'
+assert format(1.5, 'n') == '1.5', 'n type on float'
+
+# === nan and inf ===
+nan = float('nan')
+inf = float('inf')
+assert format(nan) == 'nan', 'nan'
+assert format(inf, 'f') == 'inf', 'inf fixed'
+assert format(-inf, 'e') == '-inf', 'negative inf'
+assert format(inf, 'F') == 'INF', 'inf upper'
+assert format(nan, 'G') == 'NAN', 'nan upper'
+assert format(inf, '+') == '+inf', 'inf with sign'
+assert format(inf, '%') == 'inf%', 'inf percent'
+assert format(inf, '08') == '00000inf', 'inf zero padded'
+assert repr(nan) == 'nan', 'nan repr'
+assert repr(-inf) == '-inf', 'negative inf repr'
+assert str([inf]) == '[inf]', 'inf repr in list'
+
+# === errors ===
+try:
+    format(1, 3)
+    assert False, 'non-str spec should raise'
+except TypeError as e:
+    assert str(e) == 'format() argument 2 must be str, not int', 'non-str spec message'
+
+try:
+    format()
+    assert False, 'no args should raise'
+except TypeError as e:
+    assert str(e) == 'format expected at least 1 argument, got 0', 'no args message'
+
+try:
+    format(1, 'xyz')
+    assert False, 'invalid spec should raise'
+except ValueError as e:
+    assert str(e) == "Invalid format specifier 'xyz' for object of type 'int'", 'invalid spec message'
+
+try:
+    format(1.5, 'd')
+    assert False, 'd on float should raise'
+except ValueError as e:
+    assert str(e) == "Unknown format code 'd' for object of type 'float'", 'd on float message'
+
+try:
+    format('s', 'd')
+    assert False, 'd on str should raise'
+except ValueError as e:
+    assert str(e) == "Unknown format code 'd' for object of type 'str'", 'd on str message'
+
+try:
+    format('s', '+')
+    assert False, 'sign on str should raise'
+except ValueError as e:
+    assert str(e) == 'Sign not allowed in string format specifier', 'sign on str message'
+
+try:
+    format('s', '#')
+    assert False, 'alternate on str should raise'
+except ValueError as e:
+    assert str(e) == 'Alternate form (#) not allowed in string format specifier', 'alternate on str message'
+
+try:
+    format('s', ',')
+    assert False, 'grouping on str should raise'
+except ValueError as e:
+    assert str(e) == "Cannot specify ',' with 's'.", 'grouping on str message'
+
+try:
+    format(1, '.2')
+    assert False, 'precision on int should raise'
+except ValueError as e:
+    assert str(e) == 'Precision not allowed in integer format specifier', 'precision on int message'
+
+try:
+    format(255, ',x')
+    assert False, 'comma with hex should raise'
+except ValueError as e:
+    assert str(e) == "Cannot specify ',' with 'x'.", 'comma with hex message'
+
+try:
+    format(1, '_n')
+    assert False, 'underscore with n should raise'
+except ValueError as e:
+    assert str(e) == "Cannot specify '_' with 'n'.", 'underscore with n message'
+
+try:
+    format(65, '+c')
+    assert False, 'sign with c should raise'
+except ValueError as e:
+    assert str(e) == "Sign not allowed with integer format specifier 'c'", 'sign with c message'
+
+try:
+    format(0x110000, 'c')
+    assert False, 'char out of range should raise'
+except OverflowError as e:
+    assert str(e) == '%c arg not in range(0x110000)', 'char out of range message'
+
+try:
+    format(10**400, 'f')
+    assert False, 'huge int as float should raise'
+except OverflowError as e:
+    assert str(e) == 'int too large to convert to float', 'huge int message'
+
+try:
+    format([1], '>5')
+    assert False, 'spec on list should raise'
+except TypeError as e:
+    assert str(e) == 'unsupported format string passed to list.__format__', 'spec on list message'
+
+try:
+    format(None, 's')
+    assert False, 'spec on None should raise'
+except TypeError as e:
+    assert str(e) == 'unsupported format string passed to NoneType.__format__', 'spec on None message'
@@ -0,0 +1,147 @@
+# === globals() ===
+alpha = 1
+beta = 'two'
+g = globals()
+assert g['alpha'] == 1, 'globals() contains module int'
+assert g['beta'] == 'two', 'globals() contains module str'
+assert locals()['alpha'] == 1, 'module-level locals() sees globals'
+
+
+def read_global():
+    return globals()['alpha']
+
+
+assert read_global() == 1, 'globals() from a function sees module vars'
+
+# === assigning through globals() ===
+gamma = 1
+globals()['gamma'] = 2
+assert gamma == 2, 'assigning through globals() rebinds the variable'
+g = globals()
+g['gamma'] = 3
+assert gamma == 3, 'assigning through a saved globals() dict rebinds the variable'
+locals()['gamma'] = 4
+assert gamma == 4, 'module-level locals() is the globals dict'
+
+
+def set_global(value):
+    globals()['gamma'] = value
+
+
+set_global(5)
+assert gamma == 5, 'assigning through globals() in a function rebinds the module variable'
+globals().copy()['gamma'] = 6
+assert gamma == 5, 'a copy of globals() is an ordinary dict'
+
+# === locals() in functions ===
+
+
+def simple(x, y=2):
+    z = x + y
+    return locals()
+
+
+assert simple(1) == {'x': 1, 'y': 2, 'z': 3}, 'locals() includes params and locals'
+
+
+def unused_param(unused):
+    return locals()
+
+
+assert unused_param(5) == {'unused': 5}, 'locals() includes unreferenced params'
+
+
+def before_assignment():
+    before = locals()
+    w = 1
+    return before, w
+
+
+assert before_assignment() == ({}, 1), 'locals() skips unbound names'
+
+
+def snapshot():
+    x = 1
+    locals()['x'] = 2
+    return x
+
+
+assert snapshot() == 1, 'mutating locals() does not change variables'
+
+# === locals() with closures ===
+
+
+def outer():
+    v = 10
+
+    def inner():
+        return v
+
+    return locals()
+
+
+outer_locals = outer()
+assert sorted(outer_locals.keys()) == ['inner', 'v'], 'locals() includes cell vars'
+assert outer_locals['v'] == 10, 'cell var value is dereferenced'
+
+
+def outer_free():
+    v = 10
+
+    def inner():
+        x = v
+        return locals()
+
+    return inner()
+
+
+assert outer_free() == {'v': 10, 'x': 10}, 'locals() includes referenced free vars'
+
+# === dir() ===
+
+
+def dir_names(b, a):
+    c = 1
+    return dir()
+
+
+assert dir_names(1, 2) == ['a', 'b', 'c'], 'dir() lists sorted local names'
+assert 'alpha' in dir(), 'module-level dir() lists globals'
+
+# === dir(obj) ===
+assert 'append' in dir([1]), 'dir(list) lists methods'
+assert 'keys' in dir({}), 'dir(dict) lists methods'
+assert 'upper' in dir('abc'), 'dir(str) lists methods'
+assert 'count' in dir((1, 2)), 'dir(tuple) lists methods'
+assert 'start' in dir(range(3)), 'dir(range) lists attributes'
+assert 'args' in dir(ValueError('x')), 'dir(exception) lists args'
+assert dir([]) == sorted(dir([])), 'dir(obj) is sorted'
+assert 'append' in dir(list), 'dir(type) lists the methods of the type'
+
+import sys
+
+assert 'version_info' in dir(sys), 'dir(module) lists module attributes'
+
+# === aliased calls ===
+f = dir
+assert f([]) == dir([]), 'aliased dir(obj)'
+assert 'alpha' in f(), 'aliased dir()'
+get_globals = globals
+assert get_globals()['alpha'] == 1, 'aliased globals()'
+
+
+def aliased_locals():
+    q = 1
+    get_locals = locals
+    return get_locals()
+
+
+assert aliased_locals()['q'] == 1, 'aliased locals()'
+assert list(map(dir, [[]])) == [dir([])], 'dir(obj) called by map()'
+
+# === argument errors ===
+try:
+    locals(1)
+    assert False, 'locals(1) should raise'
+except TypeError as e:
+    assert str(e) == 'locals() takes no arguments (1 given)', 'locals() arg count message'
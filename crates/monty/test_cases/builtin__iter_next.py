@@ -64,3 +64,49 @@ assert next(it1) == 2, 'independent iterators: it1 second should be 2'
 assert next(it2) == 1, 'independent iterators: it2 first should be 1 (independent)'
 assert next(it1) == 3, 'independent iterators: it1 third should be 3'
 assert next(it2) == 2, 'independent iterators: it2 second should be 2'
+
+# === for loops over iterators ===
+# A for loop over an iterator consumes the iterator itself
+
+it = iter([1, 2, 3, 4])
+assert next(it) == 1, 'for over iterator: consume first item manually'
+seen = []
+for x in it:
+    seen.append(x)
+assert seen == [2, 3, 4], 'for over iterator: continues from current position'
+assert next(it, 'empty') == 'empty', 'for over iterator: iterator is exhausted afterwards'
+
+it = iter('abc')
+for ch in it:
+    assert next(it) == 'b', 'next inside for loop advances the same iterator'
+    break
+assert next(it) == 'c', 'for loop and next share iterator state'
+
+# === consuming iterators with builtins ===
+it = iter(range(5))
+next(it)
+assert list(it) == [1, 2, 3, 4], 'list() consumes remaining items of an iterator'
+assert list(it) == [], 'list() on exhausted iterator is empty'
+
+it = iter((1, 2, 3))
+assert sum(it) == 6, 'sum() over an iterator'
+assert next(it, None) is None, 'sum() exhausts the iterator'
+
+it = iter([1, 2, 3, 4])
+assert list(zip(it, it)) == [(1, 2), (3, 4)], 'zip() over the same iterator shares state'
+
+assert tuple(iter({'a': 1, 'b': 2})) == ('a', 'b'), 'tuple() over a dict key iterator'
+assert [x * 2 for x in iter([1, 2])] == [2, 4], 'comprehension over an iterator'
+
+# === next() errors ===
+try:
+    next([1, 2])
+    assert False, 'next() on a list should raise'
+except TypeError as e:
+    assert str(e) == "'list' object is not an iterator", 'next() on a list message'
+
+try:
+    next(1)
+    assert False, 'next() on an int should raise'
+except TypeError as e:
+    assert str(e) == "'int' object is not an iterator", 'next() on an int message'
@@ -96,6 +96,42 @@ assert isinstance(round(1.5, 1), float), 'round with ndigits returns float'
 assert round(1.25, 1) == 1.2, 'round 1.25 to 1 decimal (bankers rounding)'
 assert round(1.35, 1) == 1.4, 'round 1.35 to 1 decimal'
 
+# round uses the exact binary value of the float
+assert round(2.675, 2) == 2.67, 'round 2.675 (stored as 2.67499...) to 2 decimals'
+assert round(0.125, 2) == 0.12, 'round exact tie 0.125 to even'
+assert round(0.375, 2) == 0.38, 'round exact tie 0.375 to even'
+assert round(1.005, 2) == 1.0, 'round 1.005 (stored below the tie) to 2 decimals'
+assert round(0.1 + 0.2, 10) == 0.3, 'round removes float noise'
+assert round(5e-324, 323) == 0.0, 'round subnormal at max digits'
+assert round(123456.789, 5) == 123456.789, 'round with more digits than needed'
+
+# round with negative ndigits on floats
+assert round(1250.0, -2) == 1200.0, 'round float tie 1250 to even hundreds'
+assert round(1350.0, -2) == 1400.0, 'round float tie 1350 to even hundreds'
+assert round(1250.5, -2) == 1300.0, 'round float above tie rounds up'
+assert round(-1250.5, -2) == -1300.0, 'round negative float above tie rounds away'
+assert repr(round(-40.0, -2)) == '-0.0', 'round negative float to zero keeps sign'
+assert round(1.5e300, -300) == 2e300, 'round huge float to exponent digits'
+
+# round of large values
+assert round(1e20) == 100000000000000000000, 'round large float returns exact int'
+assert round(-2.5e19) == -25000000000000000000, 'round large negative float returns exact int'
+assert round(2**70 + 1, -1) == 1180591620717411303420, 'round long int tie to even tens'
+assert round(10**30 + 5 * 10**14, -15) == 10**30, 'round long int tie to even'
+assert round(10**30 + 15 * 10**14, -15) == 10**30 + 2 * 10**15, 'round long int tie up to even'
+assert round(2**70, 5) == 2**70, 'round long int with positive digits is unchanged'
+assert round(9223372036854775807, -1) == 9223372036854775810, 'round near i64 max promotes'
+assert round(123, -50) == 0, 'round int with huge negative digits'
+assert round(1.5, 10**30) == 1.5, 'round with long int ndigits'
+
+threw = False
+try:
+    round(1.7976931348623157e308, -308)
+except OverflowError as e:
+    threw = True
+    assert str(e) == 'rounded value too large to represent', 'round overflow message'
+assert threw, 'round overflowing float raises OverflowError'
+
 # === divmod() ===
 # Basic divmod operations
 assert divmod(17, 5) == (3, 2), 'divmod 17, 5'
@@ -0,0 +1,2 @@
+max([])
+# Raise=ValueError('max() iterable argument is empty')
@@ -0,0 +1,2 @@
+max([1, 2], foo=1)
+# Raise=TypeError("max() got an unexpected keyword argument 'foo'")
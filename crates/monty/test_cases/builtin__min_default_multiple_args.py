@@ -0,0 +1,2 @@
+min(1, 2, default=0)
+# Raise=TypeError('Cannot specify a default for min() with multiple positional arguments')
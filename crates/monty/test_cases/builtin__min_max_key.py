@@ -0,0 +1,83 @@
+# === min/max with key ===
+words = ['banana', 'kiwi', 'apple', 'fig']
+assert min(words, key=len) == 'fig', 'min with builtin key'
+assert max(words, key=len) == 'banana', 'max with builtin key'
+assert min(words, key=lambda w: w[-1]) == 'banana', 'min with lambda key'
+assert max(3, -7, 5, key=abs) == -7, 'max of args with key'
+assert min(3, -7, 5, key=lambda x: -x) == 5, 'min of args with lambda key'
+assert min([2, 1, 3], key=None) == 1, 'key=None compares items directly'
+
+
+def last_digit(n):
+    return n % 10
+
+
+assert min([19, 21, 35], key=last_digit) == 21, 'min with def key'
+
+offset = 10
+assert max([1, 12, 8], key=lambda x: abs(x - offset)) == 1, 'closure key'
+
+# === ties keep the first item ===
+assert min(['bb', 'aa', 'cc'], key=len) == 'bb', 'min tie returns first'
+assert max(['bb', 'aa', 'cc'], key=len) == 'bb', 'max tie returns first'
+assert max(1, 1.0) == 1 and type(max(1, 1.0)) is int, 'max tie without key returns first'
+
+# === default ===
+assert min([], default=None) is None, 'min default on empty'
+assert max([], default='empty') == 'empty', 'max default on empty'
+assert max([4], default=0) == 4, 'default ignored when not empty'
+assert min((), key=len, default=-1) == -1, 'default with key'
+
+# === sorted and list.sort with key ===
+assert sorted(words, key=len) == ['fig', 'kiwi', 'apple', 'banana'], 'sorted with builtin key'
+assert sorted(words, key=lambda w: w[1]) == ['banana', 'kiwi', 'fig', 'apple'], 'sorted with lambda key'
+assert sorted([3, 1, 2], key=lambda x: -x, reverse=True) == [1, 2, 3], 'sorted key with reverse'
+pairs = [(2, 'b'), (1, 'a'), (2, 'a'), (1, 'b')]
+assert sorted(pairs, key=lambda p: p[0]) == [(1, 'a'), (1, 'b'), (2, 'b'), (2, 'a')], 'key sort is stable'
+
+items = [5, 2, 8]
+items.sort(key=lambda x: -x)
+assert items == [8, 5, 2], 'list.sort with lambda key'
+items.sort(key=last_digit, reverse=True)
+assert items == [8, 5, 2], 'list.sort with def key and reverse'
+
+# === nested calls ===
+groups = [[3, 1], [2], [9, 0, 4]]
+assert sorted(groups, key=lambda g: sorted(g)[0]) == [[9, 0, 4], [3, 1], [2]], 'sorted inside key'
+assert max(groups, key=lambda g: min(g, key=lambda x: -x)) == [9, 0, 4], 'min inside key'
+
+# === key errors propagate ===
+def bad_key(x):
+    if x == 2:
+        raise ValueError('bad item')
+    return x
+
+
+try:
+    sorted([1, 2, 3], key=bad_key)
+    assert False, 'sorted should raise'
+except ValueError as e:
+    assert str(e) == 'bad item', 'error from key function'
+
+try:
+    max([1, 2, 3], key=bad_key)
+    assert False, 'max should raise'
+except ValueError as e:
+    assert str(e) == 'bad item', 'error from max key function'
+
+original = [3, 2, 1]
+try:
+    original.sort(key=bad_key)
+except ValueError:
+    pass
+assert original == [3, 2, 1], 'list unchanged when key raises'
+
+
+def safe_key(x):
+    try:
+        return bad_key(x)
+    except ValueError:
+        return 0
+
+
+assert sorted([3, 2, 1], key=safe_key) == [2, 1, 3], 'key handles its own exception'
@@ -0,0 +1,2 @@
+print('x', file=1)
+# Raise=AttributeError("'int' object has no attribute 'write'")
@@ -10,3 +10,13 @@ assert result is None, 'print returns None with dynamic sep'
 dynamic_end = 'e' + 'n' + 'd'
 result2 = print('line', **{dynamic_end: ''})
 assert result2 is None, 'print returns None with dynamic end'
+
+
+# === file and flush ===
+import sys
+
+assert print('to stdout', file=sys.stdout) is None, 'print to sys.stdout'
+assert print('to stderr', file=sys.stderr) is None, 'print to sys.stderr'
+assert print('default file', file=None) is None, 'file=None means stdout'
+assert print('flushed', flush=True) is None, 'flush=True'
+assert print('flush truthiness', flush=1) is None, 'flush accepts any truthy value'
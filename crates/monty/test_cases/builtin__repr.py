@@ -1,3 +1,25 @@
 # === repr of built-in functions ===
 assert repr(len) == '<built-in function len>', 'repr(len)'
 assert repr(print) == '<built-in function print>', 'repr(print)'
+
+# === repr of floats ===
+assert repr(1.0) == '1.0', 'repr of whole float'
+assert repr(0.1) == '0.1', 'repr uses shortest round-trip digits'
+assert repr(0.1 + 0.2) == '0.30000000000000004', 'repr shows float noise'
+assert repr(1 / 3) == '0.3333333333333333', 'repr of one third'
+assert repr(-2.5) == '-2.5', 'repr of negative float'
+assert repr(-0.0) == '-0.0', 'repr of negative zero'
+assert repr(1e15) == '1000000000000000.0', 'repr below exponent threshold'
+assert repr(1e16) == '1e+16', 'repr switches to exponent at 1e16'
+assert repr(1.5e16) == '1.5e+16', 'repr exponent with fraction'
+assert repr(123456789012345678.0) == '1.2345678901234568e+17', 'repr large float'
+assert repr(0.0001) == '0.0001', 'repr small float stays fixed'
+assert repr(0.00001) == '1e-05', 'repr switches to exponent below 1e-4'
+assert repr(1.5e-7) == '1.5e-07', 'repr small float exponent'
+assert repr(1e100) == '1e+100', 'repr three digit exponent'
+assert repr(5e-324) == '5e-324', 'repr smallest subnormal'
+assert repr(1.7976931348623157e308) == '1.7976931348623157e+308', 'repr max float'
+assert str(1e16) == '1e+16', 'str matches repr for floats'
+assert str([1e-5, 2.0]) == '[1e-05, 2.0]', 'float repr inside containers'
+assert repr(float('nan')) == 'nan', 'repr of nan'
+assert repr(float('-inf')) == '-inf', 'repr of negative infinity'
@@ -0,0 +1,70 @@
+# === Builtin used before a later module-level assignment ===
+before = len([1, 2, 3])
+assert before == 3, 'builtin is used until the name is assigned'
+len = lambda x: 'shadowed'
+assert len([1]) == 'shadowed', 'assignment creates a global that shadows the builtin'
+
+# === del restores the builtin ===
+del len
+assert len([1, 2]) == 2, 'del removes the global so the builtin is found again'
+
+# === Conditional assignment that never runs ===
+if False:
+    sum = 'never'
+assert sum([1, 2]) == 3, 'unexecuted assignment leaves the builtin visible'
+
+# === Functions see later module-level assignments ===
+def get_max():
+    return max
+
+
+assert get_max() is not None and get_max()([1, 5]) == 5, 'function falls back to the builtin'
+max = 'global max'
+assert get_max() == 'global max', 'function sees the global assigned after its definition'
+del max
+assert get_max()([4, 2]) == 4, 'function sees the builtin again after del'
+
+# === Local assignment shadows only inside the function ===
+def local_shadow():
+    abs = 'local'
+    return abs
+
+
+assert local_shadow() == 'local', 'local assignment shadows the builtin'
+assert abs(-3) == 3, 'module level still sees the builtin'
+
+# === Parameters shadow builtins ===
+def param_shadow(str):
+    return str * 2
+
+
+assert param_shadow(4) == 8, 'parameter shadows the builtin'
+assert str(4) == '4', 'builtin unaffected outside the function'
+
+# === Loop variable shadows and del restores ===
+for min in range(2):
+    pass
+assert min == 1, 'loop variable is a global'
+del min
+assert min([3, 1]) == 1, 'builtin restored after deleting the loop variable'
+
+# === Shadowing with try/except binding ===
+try:
+    raise ValueError('x')
+except ValueError as repr:
+    assert isinstance(repr, ValueError), 'exception name shadows the builtin'
+assert repr(1) == '1', 'except binding is cleared, restoring the builtin'
+
+# === Shadowing an exception type ===
+KeyError = ValueError
+try:
+    raise KeyError('k')
+except ValueError:
+    caught = 'value'
+assert caught == 'value', 'shadowed exception name raises the global'
+del KeyError
+try:
+    {}['missing']
+except KeyError:
+    caught = 'key'
+assert caught == 'key', 'builtin exception restored after del'
@@ -0,0 +1,2 @@
+sorted([1, 2], key=5)
+# Raise=TypeError("'int' object is not callable")
@@ -0,0 +1,2 @@
+sorted([3, 'a', 1])
+# Raise=TypeError("'<' not supported between instances of 'str' and 'int'")
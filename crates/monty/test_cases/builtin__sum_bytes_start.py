@@ -0,0 +1,2 @@
+sum([b'a'], start=b'')
+# Raise=TypeError("sum() can't sum bytes [use b''.join(seq) instead]")
@@ -0,0 +1,25 @@
+# === start keyword ===
+assert sum([1, 2, 3], start=10) == 16, 'start by keyword'
+assert sum([], start=5) == 5, 'empty iterable with keyword start'
+assert sum([[1], [2]], start=[0]) == [0, 1, 2], 'list concatenation with keyword start'
+assert sum(iter([1, 2]), start=0.5) == 3.5, 'iterator with float start'
+
+# === bools ===
+assert sum([True, True, False]) == 2, 'bools sum to int'
+assert type(sum([True, True])) is int, 'bool sum is int'
+assert sum([], start=True) is True, 'start returned unchanged for empty iterable'
+assert sum([True], start=False) == 1, 'bool start with bool item'
+
+# === float accumulation ===
+assert sum([0.1] * 10) == 1.0, 'compensated float sum'
+assert sum([1e100, 1.0, -1e100]) == 1.0, 'compensation survives cancellation'
+assert sum([1, 0.1, 0.2, 3, True]) == 5.3, 'mixed int, float and bool'
+assert sum([1.5, 2], start=1) == 4.5, 'int start switches to float'
+assert sum([0.1, 0.2], 0.0) == 0.30000000000000004, 'float start'
+assert sum([float('inf'), 1.0]) == float('inf'), 'infinite total'
+total = sum([float('inf'), float('-inf')])
+assert total != total, 'inf plus -inf is nan'
+
+# === generators ===
+assert sum((x * 0.1 for x in range(4)), start=1) == 1.6, 'generator with keyword start'
+assert sum(map(abs, [-1, -2])) == 3, 'map object'
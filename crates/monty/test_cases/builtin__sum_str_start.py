@@ -0,0 +1,2 @@
+sum(['a', 'b'], '')
+# Raise=TypeError("sum() can't sum strings [use ''.join(seq) instead]")
@@ -0,0 +1,2 @@
+sum([1], foo=1)
+# Raise=TypeError("sum() got an unexpected keyword argument 'foo'")
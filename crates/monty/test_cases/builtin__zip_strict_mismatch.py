@@ -0,0 +1,2 @@
+list(zip([1, 2], [3], strict=True))
+# Raise=ValueError('zip() argument 2 is shorter than argument 1')
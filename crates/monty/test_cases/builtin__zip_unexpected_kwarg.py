@@ -0,0 +1,2 @@
+zip([1], foo=1)
+# Raise=TypeError("zip() got an unexpected keyword argument 'foo'")
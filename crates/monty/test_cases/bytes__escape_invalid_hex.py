@@ -0,0 +1,2 @@
+x = b'\x4'
+# Raise=SyntaxError('(value error) invalid \\x escape at position 0')
@@ -71,6 +71,77 @@ assert (a is b is c) == True, 'is chain same object'
 # === Containment checks ===
 assert (1 in [1, 2] in [[1, 2], [3]]) == True, 'in chain'
 
+# === Negated operators ===
+assert (1 not in [2] not in [[1]]) == True, 'not in chain'
+assert (a is not None is not False) == True, 'is not chain'
+assert (1 < 2 > 0 != 5) == True, 'mixed directions'
+
+
+# === Evaluation order ===
+def test_evaluation_order():
+    calls = []
+
+    def v(x):
+        calls.append(x)
+        return x
+
+    assert v(1) < v(2) < v(3) < v(4), 'all comparisons true'
+    assert calls == [1, 2, 3, 4], 'operands evaluated left to right once'
+
+    calls.clear()
+    assert not (v(1) < v(2) > v(3) < v(4)), 'fails at second comparison'
+    assert calls == [1, 2, 3], 'stops after the first false comparison'
+
+
+test_evaluation_order()
+
+# === Result value ===
+assert (1 < 2 < 3) is True, 'result is the last comparison'
+assert (1 < 0 < 3) is False, 'result is the failing comparison'
+assert (1 < 2 < 3) + 1 == 2, 'result usable in arithmetic'
+
+# === Mixed types ===
+assert 1 < 2.5 < 3, 'int and float'
+assert 'a' < 'b' <= 'b' < 'c', 'strings'
+assert 0 <= 7 % 3 == 1, 'modulo in chain'
+
+# === In control flow ===
+n = 3
+if 0 < n < 5:
+    in_range = True
+else:
+    in_range = False
+assert in_range, 'chain in if'
+
+count = 0
+while 0 <= count < 3:
+    count += 1
+assert count == 3, 'chain in while'
+
+assert [i for i in range(10) if 2 < i <= 5] == [3, 4, 5], 'chain in comprehension filter'
+assert ('yes' if 1 < n < 4 else 'no') == 'yes', 'chain in conditional expression'
+assert (lambda v: 0 < v < 10)(5), 'chain in lambda'
+assert (0 < n < 5 and n != 4) == True, 'chain in boolean expression'
+assert (walrus_val := 5) and 0 < (walrus_inner := walrus_val * 2) < 20, 'walrus in chain'
+assert walrus_inner == 10, 'walrus assigned in chain'
+
+
+# === Errors mid-chain ===
+def test_error_in_chain():
+    values = [1, 2]
+    try:
+        assert values[0] < 'a' < values[1], 'unreachable'
+    except TypeError:
+        caught = True
+    else:
+        caught = False
+    assert caught, 'TypeError raised by second operand comparison'
+    assert values == [1, 2], 'state intact after error'
+    assert 0 < len(values) < 3, 'chains still work after error'
+
+
+test_error_in_chain()
+
 
 # === Verify no namespace pollution ===
 # Note: The old implementation used _chain_cmp_N variables which would leak.
@@ -0,0 +1,120 @@
+# === Late binding in a for loop ===
+def late_binding():
+    funcs = []
+    for i in range(3):
+        funcs.append(lambda: i)
+    return [f() for f in funcs]
+
+
+assert late_binding() == [2, 2, 2], 'closures created in a loop share one cell'
+
+
+def late_binding_def():
+    funcs = []
+    for i in range(3):
+
+        def f():
+            return i * 10
+
+        funcs.append(f)
+    return [f() for f in funcs]
+
+
+assert late_binding_def() == [20, 20, 20], 'def in a loop also shares the loop variable cell'
+
+
+# === Cell updated after the loop ===
+def after_loop():
+    funcs = []
+    for i in range(3):
+        funcs.append(lambda: i)
+    i = 'done'
+    return [f() for f in funcs]
+
+
+assert after_loop() == ['done', 'done', 'done'], 'closures see rebinding after the loop'
+
+
+# === Default argument idiom ===
+def early_binding():
+    funcs = []
+    for i in range(3):
+        funcs.append(lambda i=i: i)
+    return [f() for f in funcs]
+
+
+assert early_binding() == [0, 1, 2], 'default arguments capture the current value'
+
+
+# === Factory function idiom ===
+def factory():
+    def make(n):
+        return lambda: n
+
+    return [make(i)() for i in range(3)]
+
+
+assert factory() == [0, 1, 2], 'a factory call creates a fresh cell per iteration'
+
+
+# === While loop ===
+def while_loop():
+    funcs = []
+    n = 0
+    while n < 3:
+        funcs.append(lambda: n)
+        n += 1
+    return [f() for f in funcs]
+
+
+assert while_loop() == [3, 3, 3], 'while loop variable is a single cell'
+
+
+# === Comprehensions ===
+# the comprehension variable is bound early via a default argument
+def comp_default():
+    funcs = [lambda x=x: x for x in range(3)]
+    return [f() for f in funcs]
+
+
+assert comp_default() == [0, 1, 2], 'default arguments inside a comprehension'
+
+
+# === Nested loops ===
+def nested_loops():
+    funcs = []
+    for i in range(2):
+        for j in range(2):
+            funcs.append(lambda: (i, j))
+    return [f() for f in funcs]
+
+
+assert nested_loops() == [(1, 1)] * 4, 'both loop variables are late bound'
+
+
+def nested_loops_partial():
+    funcs = []
+    for i in range(2):
+        for j in range(2):
+            funcs.append(lambda j=j: (i, j))
+    return [f() for f in funcs]
+
+
+assert nested_loops_partial() == [(1, 0), (1, 1), (1, 0), (1, 1)], 'mixed early and late binding'
+
+
+# === Loop variable rebound by a closure ===
+def loop_nonlocal():
+    seen = []
+    for i in range(3):
+
+        def bump():
+            nonlocal i
+            i += 100
+
+        bump()
+        seen.append(i)
+    return seen
+
+
+assert loop_nonlocal() == [100, 101, 102], 'loop reassigns the cell each iteration'
@@ -0,0 +1,184 @@
+# === Simple capture ===
+def make_adder(n):
+    def add(x):
+        return x + n
+
+    return add
+
+
+add5 = make_adder(5)
+assert add5(1) == 6, 'closure reads captured parameter'
+assert make_adder(10)(1) == 11, 'each call creates a fresh cell'
+assert add5(2) == 7, 'earlier closure keeps its own cell'
+
+
+# === Capture sees later assignment ===
+def late():
+    def get():
+        return value
+
+    value = 'assigned after def'
+    return get
+
+
+assert late()() == 'assigned after def', 'closure reads the cell at call time'
+
+
+# === Pass-through capture ===
+# `mid` never mentions `x`, but must still forward the cell to `inner`
+def outer_pass():
+    x = 'outer'
+
+    def mid():
+        def inner():
+            return x
+
+        return inner
+
+    return mid()
+
+
+assert outer_pass()() == 'outer', 'three-level capture through a non-referencing scope'
+
+
+def outer_pass_rebind():
+    x = 1
+
+    def mid():
+        def inner():
+            return x
+
+        return inner
+
+    f = mid()
+    x = 2
+    return f
+
+
+assert outer_pass_rebind()() == 2, 'pass-through capture shares the outer cell'
+
+
+# === Four levels ===
+def level1():
+    a = 'a'
+
+    def level2():
+        b = 'b'
+
+        def level3():
+            def level4():
+                return a + b
+
+            return level4
+
+        return level3
+
+    return level2()()
+
+
+assert level1()() == 'ab', 'captures from two different enclosing scopes'
+
+
+# === Defaults in nested definitions ===
+# default expressions are evaluated in the enclosing scope
+def outer_default():
+    y = 'captured'
+
+    def mid():
+        def inner(arg=y):
+            return arg
+
+        return inner()
+
+    return mid()
+
+
+assert outer_default() == 'captured', 'nested default reads an outer cell'
+
+
+def outer_default_time():
+    y = 1
+
+    def mid():
+        def inner(arg=y):
+            return arg
+
+        return inner
+
+    f = mid()
+    y = 2
+    return f
+
+
+assert outer_default_time()() == 1, 'defaults are bound when the def executes'
+
+
+# === Lambdas ===
+def lambda_chain():
+    base = 3
+    return lambda a: lambda b: a + b + base
+
+
+assert lambda_chain()(1)(2) == 6, 'nested lambdas capture through each level'
+
+
+def def_in_lambda_scope():
+    n = 4
+    return (lambda: [n * i for i in range(3)])()
+
+
+assert def_in_lambda_scope() == [0, 4, 8], 'comprehension inside lambda captures outer local'
+
+
+# === Shadowing ===
+def shadow():
+    x = 'outer'
+
+    def inner():
+        x = 'inner'
+        return x
+
+    return inner(), x
+
+
+assert shadow() == ('inner', 'outer'), 'assignment in nested scope creates a local'
+
+
+def shadow_param():
+    x = 'outer'
+
+    def inner(x):
+        return x
+
+    return inner('param')
+
+
+assert shadow_param() == 'param', 'parameter shadows captured name'
+
+
+# === Global in nested scope ===
+g = 'global'
+
+
+def global_inner():
+    g = 'local'
+
+    def inner():
+        global g
+        return g
+
+    return inner()
+
+
+assert global_inner() == 'global', 'global declaration skips enclosing locals'
+
+
+# === Recursion through a cell ===
+def make_fact():
+    def fact(n):
+        return 1 if n <= 1 else n * fact(n - 1)
+
+    return fact
+
+
+assert make_fact()(5) == 120, 'nested function recurses through its own cell'
@@ -0,0 +1,159 @@
+# === Counter ===
+def make_counter():
+    count = 0
+
+    def inc():
+        nonlocal count
+        count += 1
+        return count
+
+    return inc
+
+
+c1 = make_counter()
+c2 = make_counter()
+assert c1() == 1, 'first increment'
+assert c1() == 2, 'second increment'
+assert c2() == 1, 'independent counters have independent cells'
+
+
+# === Shared cell between sibling closures ===
+def make_pair():
+    value = 0
+
+    def get():
+        return value
+
+    def set_(v):
+        nonlocal value
+        value = v
+
+    return get, set_
+
+
+get, set_ = make_pair()
+set_(42)
+assert get() == 42, 'setter and getter share one cell'
+set_('text')
+assert get() == 'text', 'rebinding through nonlocal changes type'
+
+
+# === Rebinding is visible to the enclosing function ===
+def rebind_outer():
+    x = 1
+
+    def change():
+        nonlocal x
+        x = 2
+
+    change()
+    return x
+
+
+assert rebind_outer() == 2, 'enclosing scope sees nonlocal rebinding'
+
+
+# === Nonlocal through a pass-through scope ===
+def outer():
+    total = 0
+
+    def mid():
+        def inner(n):
+            nonlocal total
+            total += n
+
+        return inner
+
+    add = mid()
+    add(3)
+    add(4)
+    return total
+
+
+assert outer() == 7, 'nonlocal reaches past an intermediate scope'
+
+
+# === Nonlocal in the middle scope ===
+def outer_mid():
+    x = 'a'
+
+    def mid():
+        nonlocal x
+        x = x + 'b'
+
+        def inner():
+            nonlocal x
+            x = x + 'c'
+
+        inner()
+
+    mid()
+    return x
+
+
+assert outer_mid() == 'abc', 'nonlocal chains through nested scopes'
+
+
+# === Nearest enclosing binding wins ===
+def nearest():
+    x = 'outer'
+
+    def mid():
+        x = 'mid'
+
+        def inner():
+            nonlocal x
+            x = 'inner'
+
+        inner()
+        return x
+
+    return mid(), x
+
+
+assert nearest() == ('inner', 'outer'), 'nonlocal binds to the nearest enclosing scope'
+
+
+# === Nonlocal parameter ===
+def param_cell(n):
+    def double():
+        nonlocal n
+        n *= 2
+
+    double()
+    double()
+    return n
+
+
+assert param_cell(3) == 12, 'parameters can be rebound through nonlocal'
+
+
+# === Mutation vs rebinding ===
+def mutate():
+    items = []
+
+    def push(v):
+        items.append(v)
+
+    push(1)
+    push(2)
+    return items
+
+
+assert mutate() == [1, 2], 'mutating a captured object needs no nonlocal'
+
+
+# === Nonlocal in loops ===
+def loop_nonlocal():
+    acc = ''
+
+    def add(s):
+        nonlocal acc
+        acc += s
+
+    for ch in 'xyz':
+        add(ch)
+    return acc
+
+
+assert loop_nonlocal() == 'xyz', 'nonlocal updates accumulate across loop iterations'
@@ -0,0 +1,93 @@
+import io
+import sys
+
+# === str ===
+assert 'ab' in 'cabbage', 'substring'
+assert 'abc' not in 'cabbage', 'missing substring'
+assert '' in 'abc' and '' in '', 'empty substring'
+s = 'cab' + 'bage'
+assert 'bb' in s and s[1:3] in s, 'heap strings'
+
+# === bytes ===
+assert b'a' in b'abc', 'bytes substring'
+assert b'bc' in b'abc' and b'' in b'abc', 'bytes subsequence'
+assert b'ca' not in b'abc', 'missing bytes subsequence'
+assert 97 in b'abc' and 100 not in b'abc', 'int in bytes'
+assert True not in b'abc' and True in b'\x01', 'bool in bytes'
+data = bytes([1, 2, 3])
+assert bytes([2, 3]) in data and 3 in data, 'heap bytes'
+
+# === sequences ===
+assert 2 in [1, 2, 3] and 4 not in [1, 2, 3], 'list'
+assert 2.0 in (1, 2) and 'a' in ('a',), 'tuple compares by value'
+assert [1] in [[1], [2]] and (1, 2) in [(1, 2)], 'nested'
+assert sys.version_info.major in sys.version_info, 'namedtuple'
+nan = float('nan')
+assert nan in [nan] and nan in (1, nan), 'identical items match without =='
+assert nan in {nan} and nan in {nan: 1}, 'identical keys match'
+
+# === mappings and sets ===
+d = {'a': 1, (1, 2): 2}
+assert 'a' in d and 1 not in d and (1, 2) in d, 'dict keys'
+assert 'a' in d.keys() and 1 in d.values() and ('a', 1) in d.items(), 'dict views'
+assert 1 in {1, 2} and 1.0 in frozenset({1}), 'sets'
+
+# === range ===
+assert 3 in range(10) and 10 not in range(10), 'range bounds'
+assert 4 in range(0, 10, 2) and 5 not in range(0, 10, 2), 'range step'
+assert -3 in range(0, -10, -3) and -4 not in range(0, -10, -3), 'negative step'
+assert 3.0 in range(5) and 3.5 not in range(5), 'floats'
+assert True in range(2) and 'a' not in range(2), 'other types'
+assert 10**12 in range(0, 10**13, 10**6), 'large range is not iterated'
+assert 2**70 not in range(10), 'long int'
+
+# === iterators ===
+it = iter([1, 2, 3, 4])
+assert 2 in it, 'found in iterator'
+assert next(it) == 3, 'iterator consumed up to the item'
+assert 9 not in it, 'missing from iterator'
+assert list(it) == [], 'iterator exhausted'
+assert 4 in (x * 2 for x in range(5)), 'generator expression'
+assert 'b\n' in io.StringIO('a\nb\n'), 'lines of a stream'
+
+# === errors ===
+try:
+    1 in 'abc'
+    assert False, 'int in str should fail'
+except TypeError as e:
+    assert str(e) == "'in <string>' requires string as left operand, not int", 'str message'
+try:
+    'a' in b'abc'
+    assert False, 'str in bytes should fail'
+except TypeError as e:
+    assert str(e) == "a bytes-like object is required, not 'str'", 'bytes message'
+try:
+    256 in b'abc'
+    assert False, 'large int in bytes should fail'
+except ValueError as e:
+    assert str(e) == 'byte must be in range(0, 256)', 'byte range message'
+try:
+    -1 in b'abc'
+    assert False, 'negative int in bytes should fail'
+except ValueError as e:
+    assert str(e) == 'byte must be in range(0, 256)', 'negative byte message'
+try:
+    [1] in {1}
+    assert False, 'unhashable in set should fail'
+except TypeError as e:
+    assert str(e) == "unhashable type: 'list'", 'set message'
+try:
+    {} in {1: 2}
+    assert False, 'unhashable in dict should fail'
+except TypeError as e:
+    assert str(e) == "unhashable type: 'dict'", 'dict message'
+try:
+    3 in None
+    assert False, 'in None should fail'
+except TypeError as e:
+    assert str(e) == "argument of type 'NoneType' is not iterable", 'None message'
+try:
+    'a' in 5
+    assert False, 'in int should fail'
+except TypeError as e:
+    assert str(e) == "argument of type 'int' is not iterable", 'int message'
@@ -9,6 +9,6 @@ TRACEBACK:
 Traceback (most recent call last):
   File "comprehension__unbound_local.py", line 6, in <module>
     result = [x for x in [1] for y in z for z in [[2], [3]]]
-                                      ~
+                                      ^
 UnboundLocalError: cannot access local variable 'z' where it is not associated with a value
 """
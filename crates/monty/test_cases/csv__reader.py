@@ -0,0 +1,124 @@
+import csv
+import io
+
+# === basic parsing ===
+rows = list(csv.reader(['a,b,c', '1,2,3']))
+assert rows == [['a', 'b', 'c'], ['1', '2', '3']], 'reader splits lines into fields'
+rows = list(csv.reader(io.StringIO('name,qty\r\nwidget,4\r\n')))
+assert rows == [['name', 'qty'], ['widget', '4']], 'reader over a StringIO'
+rows = list(csv.reader('x,y\n1,2\n'.splitlines(keepends=True)))
+assert rows == [['x', 'y'], ['1', '2']], 'reader over splitlines'
+assert list(csv.reader([])) == [], 'no lines, no rows'
+assert list(csv.reader(['a,b\r\n', '', 'c'])) == [['a', 'b'], [], ['c']], 'blank line is an empty row'
+assert list(csv.reader([',,'])) == [['', '', '']], 'empty fields'
+
+# === quoting ===
+rows = list(csv.reader(['"a,b","say ""hi""",c']))
+assert rows == [['a,b', 'say "hi"', 'c']], 'quoted delimiters and doubled quotes'
+rows = list(csv.reader(['"multi', 'line",x']))
+assert rows == [['multiline', 'x']], 'quoted field spans lines without line endings'
+rows = list(csv.reader(io.StringIO('"a\r\nb",c\r\n')))
+assert rows == [['a\r\nb', 'c']], 'quoted line break is kept'
+rows = list(csv.reader(['"a"b,c']))
+assert rows == [['ab', 'c']], 'text after a closing quote is kept'
+rows = list(csv.reader(['a"b,c']))
+assert rows == [['a"b', 'c']], 'quote inside an unquoted field'
+
+# === formatting parameters ===
+assert list(csv.reader(['a;b'], delimiter=';')) == [['a', 'b']], 'delimiter'
+assert list(csv.reader(['a\tb'], dialect='excel-tab')) == [['a', 'b']], 'excel-tab dialect'
+assert list(csv.reader(["'a,b',c"], quotechar="'")) == [['a,b', 'c']], 'quotechar'
+rows = list(csv.reader(['a, b,  "c"'], skipinitialspace=True))
+assert rows == [['a', 'b', 'c']], 'skipinitialspace'
+rows = list(csv.reader(['a\\,b,c'], escapechar='\\'))
+assert rows == [['a,b', 'c']], 'escaped delimiter'
+rows = list(csv.reader(['"a\\"b",c'], escapechar='\\', doublequote=False))
+assert rows == [['a"b', 'c']], 'escaped quote without doublequote'
+rows = list(csv.reader(['"a",b'], quoting=csv.QUOTE_NONE))
+assert rows == [['"a"', 'b']], 'QUOTE_NONE keeps quotes'
+
+# === conversions ===
+rows = list(csv.reader(['1,"2",,'], quoting=csv.QUOTE_NONNUMERIC))
+assert rows == [[1.0, '2', '', '']], 'QUOTE_NONNUMERIC converts unquoted fields'
+rows = list(csv.reader(['1,"2",,'], quoting=csv.QUOTE_STRINGS))
+assert rows == [[1.0, '2', None, None]], 'QUOTE_STRINGS converts empty fields to None'
+rows = list(csv.reader(['1,"",,'], quoting=csv.QUOTE_NOTNULL))
+assert rows == [['1', '', None, None]], 'QUOTE_NOTNULL converts empty fields to None'
+try:
+    next(csv.reader(['1,"2",,x'], quoting=csv.QUOTE_NONNUMERIC))
+    assert False, 'non-numeric unquoted field should fail'
+except ValueError as e:
+    assert str(e) == "could not convert string to float: 'x'", 'conversion error message'
+
+# === errors ===
+try:
+    list(csv.reader(['"a"b'], strict=True))
+    assert False, 'strict should reject text after a closing quote'
+except csv.Error as e:
+    assert str(e) == "',' expected after '\"'", 'strict error message'
+try:
+    list(csv.reader(['"abc'], strict=True))
+    assert False, 'strict should reject an unterminated quote'
+except csv.Error as e:
+    assert str(e) == 'unexpected end of data', 'unexpected end message'
+try:
+    list(csv.reader(['a\nb']))
+    assert False, 'line break inside an unquoted field should fail'
+except csv.Error as e:
+    assert str(e).startswith('new-line character seen in unquoted field'), 'new-line message'
+try:
+    list(csv.reader([1]))
+    assert False, 'non-str lines should fail'
+except csv.Error as e:
+    assert str(e).startswith('iterator should return strings, not int'), 'non-str line message'
+try:
+    csv.reader([], dialect='nope')
+    assert False, 'unknown dialect should fail'
+except csv.Error as e:
+    assert str(e) == 'unknown dialect', 'unknown dialect message'
+try:
+    csv.reader([], delimiter='ab')
+    assert False, 'long delimiter should fail'
+except TypeError as e:
+    assert str(e) == '"delimiter" must be a 1-character string', 'delimiter length message'
+try:
+    csv.reader([], delimiter=1)
+    assert False, 'non-str delimiter should fail'
+except TypeError as e:
+    assert str(e) == '"delimiter" must be string, not int', 'delimiter type message'
+try:
+    csv.reader([], quoting=9)
+    assert False, 'unknown quoting should fail'
+except TypeError as e:
+    assert str(e) == 'bad "quoting" value', 'quoting value message'
+try:
+    csv.reader([], quotechar=None, quoting=csv.QUOTE_ALL)
+    assert False, 'quoting without a quotechar should fail'
+except TypeError as e:
+    assert str(e) == 'quotechar must be set if quoting enabled', 'missing quotechar message'
+try:
+    csv.reader([], delimiter='\n')
+    assert False, 'line break delimiter should fail'
+except ValueError as e:
+    assert str(e) == 'bad delimiter value', 'bad delimiter message'
+try:
+    csv.reader([], delimiter='"')
+    assert False, 'delimiter equal to quotechar should fail'
+except ValueError as e:
+    assert str(e) == 'bad delimiter or quotechar value', 'equal characters message'
+try:
+    csv.reader([], bogus=1)
+    assert False, 'unknown keyword should fail'
+except TypeError as e:
+    assert str(e).endswith("got an unexpected keyword argument 'bogus'"), 'unknown keyword message'
+
+# === DictReader ===
+rows = list(csv.DictReader(io.StringIO('a,b\r\n1,2\r\n3,4\r\n')))
+assert rows == [{'a': '1', 'b': '2'}, {'a': '3', 'b': '4'}], 'fieldnames from the first row'
+rows = list(csv.DictReader(['a,b', '1,2,3', '', '4']))
+assert rows == [{'a': '1', 'b': '2', None: ['3']}, {'a': '4', 'b': None}], 'extra and missing fields'
+rows = list(csv.DictReader(['1,2,3', '4'], fieldnames=['x', 'y'], restkey='more', restval='-'))
+assert rows == [{'x': '1', 'y': '2', 'more': ['3']}, {'x': '4', 'y': '-'}], 'fieldnames, restkey and restval'
+rows = list(csv.DictReader(['a;b', '1;2'], delimiter=';'))
+assert rows == [{'a': '1', 'b': '2'}], 'DictReader formatting parameters'
+assert list(csv.DictReader([])) == [], 'empty input'
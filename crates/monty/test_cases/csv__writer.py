@@ -0,0 +1,155 @@
+import csv
+import io
+
+# === writerow ===
+buf = io.StringIO()
+w = csv.writer(buf)
+n = w.writerow(['a', 'b,c', 'd"e', None, 1, 1.5, True, 'x\ny'])
+assert buf.getvalue() == 'a,"b,c","d""e",,1,1.5,True,"x\ny"\r\n', 'minimal quoting'
+assert n == 34, 'writerow returns the number of characters written'
+assert buf.tell() == 34, 'writer writes to the stream'
+
+buf = io.StringIO()
+w = csv.writer(buf)
+assert w.writerow(['']) == 4, 'single empty field is quoted'
+assert w.writerow([None]) == 4, 'single None field is quoted'
+assert w.writerow([]) == 2, 'empty row is just the line terminator'
+assert buf.getvalue() == '""\r\n""\r\n\r\n', 'empty rows'
+
+buf = io.StringIO()
+csv.writer(buf).writerow([1.0, 10**20, -0.0, b'x'])
+assert buf.getvalue() == "1.0,100000000000000000000,-0.0,b'x'\r\n", 'values are written with str()'
+
+buf = io.StringIO()
+csv.writer(buf).writerow(('t', 'u'))
+csv.writer(buf).writerow(x for x in 'vw')
+assert buf.getvalue() == 't,u\r\nv,w\r\n', 'rows can be any iterable'
+
+# === writerows ===
+buf = io.StringIO()
+w = csv.writer(buf)
+assert w.writerows([['a', 1], ['b', 2]]) is None, 'writerows returns None'
+assert buf.getvalue() == 'a,1\r\nb,2\r\n', 'writerows writes each row'
+
+# === quoting styles ===
+buf = io.StringIO()
+w = csv.writer(buf, quoting=csv.QUOTE_ALL)
+w.writerow(['a', 1, None])
+assert buf.getvalue() == '"a","1",""\r\n', 'QUOTE_ALL'
+
+buf = io.StringIO()
+w = csv.writer(buf, quoting=csv.QUOTE_NONNUMERIC)
+assert w.writerow([1, 'a', None, 2.5, True]) == 19, 'QUOTE_NONNUMERIC count'
+assert buf.getvalue() == '1,"a","",2.5,True\r\n', 'QUOTE_NONNUMERIC'
+
+buf = io.StringIO()
+csv.writer(buf, quoting=csv.QUOTE_STRINGS).writerow([1, 'a', None])
+assert buf.getvalue() == '1,"a",\r\n', 'QUOTE_STRINGS'
+
+buf = io.StringIO()
+csv.writer(buf, quoting=csv.QUOTE_NOTNULL).writerow([1, 'a', None])
+assert buf.getvalue() == '"1","a",\r\n', 'QUOTE_NOTNULL'
+
+buf = io.StringIO()
+w = csv.writer(buf, quoting=csv.QUOTE_NONE, escapechar='\\')
+assert w.writerow(['a,b', 'c']) == 8, 'QUOTE_NONE escapes the delimiter'
+assert buf.getvalue() == 'a\\,b,c\r\n', 'escaped output'
+
+# === dialects and formatting parameters ===
+buf = io.StringIO()
+csv.writer(buf, dialect='unix').writerow(['a', 1])
+assert buf.getvalue() == '"a","1"\n', 'unix dialect'
+
+buf = io.StringIO()
+csv.writer(buf, 'excel-tab').writerow(['a', 'b c'])
+assert buf.getvalue() == 'a\tb c\r\n', 'excel-tab dialect'
+
+buf = io.StringIO()
+csv.writer(buf, delimiter=';', lineterminator='\n').writerow(['a;b', 'c'])
+assert buf.getvalue() == '"a;b";c\n', 'delimiter and lineterminator'
+
+buf = io.StringIO()
+csv.writer(buf, lineterminator='x\n').writerow(['x'])
+assert buf.getvalue() == '"x"x\n', 'fields holding lineterminator characters are quoted'
+
+buf = io.StringIO()
+csv.writer(buf, doublequote=False, escapechar='\\').writerow(['a"b'])
+assert buf.getvalue() == 'a\\"b\r\n', 'escaped quote without doublequote'
+
+# === round trip ===
+buf = io.StringIO()
+rows = [['id', 'note'], ['1', 'comma, here'], ['2', 'quote " here'], ['3', 'line\nbreak']]
+csv.writer(buf).writerows(rows)
+buf.seek(0)
+assert list(csv.reader(buf)) == rows, 'rows read back unchanged'
+
+# === errors ===
+buf = io.StringIO()
+try:
+    csv.writer(buf, quoting=csv.QUOTE_NONE).writerow(['a,b'])
+    assert False, 'QUOTE_NONE without escapechar should fail'
+except csv.Error as e:
+    assert str(e) == 'need to escape, but no escapechar set', 'escape error message'
+try:
+    csv.writer(buf, quoting=csv.QUOTE_NONE).writerow([''])
+    assert False, 'QUOTE_NONE with a single empty field should fail'
+except csv.Error as e:
+    assert str(e) == 'single empty field record must be quoted', 'empty field error message'
+try:
+    csv.writer(buf).writerow(1)
+    assert False, 'non-iterable row should fail'
+except csv.Error as e:
+    assert str(e) == "iterable expected, not int", 'non-iterable row message'
+try:
+    csv.writer(1)
+    assert False, 'target without write should fail'
+except TypeError as e:
+    assert str(e) == 'argument 1 must have a "write" method', 'target error message'
+
+# === DictWriter ===
+buf = io.StringIO()
+dw = csv.DictWriter(buf, fieldnames=['a', 'b'])
+assert dw.writeheader() == 5, 'writeheader returns the number of characters written'
+assert dw.writerow({'a': 1, 'b': 'x,y'}) == 9, 'DictWriter writerow count'
+dw.writerow({'b': 2})
+dw.writerows([{'a': 3}, {'a': 4, 'b': 5}])
+assert buf.getvalue() == 'a,b\r\n1,"x,y"\r\n,2\r\n3,\r\n4,5\r\n', 'DictWriter output'
+
+buf = io.StringIO()
+dw = csv.DictWriter(buf, ['a', 'b'], restval='?', extrasaction='ignore')
+dw.writerow({'a': 1, 'c': 3})
+assert buf.getvalue() == '1,?\r\n', 'restval and ignored extra keys'
+
+buf = io.StringIO()
+dw = csv.DictWriter(buf, ['a'])
+try:
+    dw.writerow({'a': 1, 'b': 2})
+    assert False, 'extra keys should fail'
+except ValueError as e:
+    assert str(e) == "dict contains fields not in fieldnames: 'b'", 'extra keys message'
+try:
+    dw.writerow(['a'])
+    assert False, 'non-dict row should fail'
+except AttributeError as e:
+    assert str(e) == "'list' object has no attribute 'keys'", 'non-dict row message'
+try:
+    csv.DictWriter(buf, ['a'], extrasaction='skip')
+    assert False, 'unknown extrasaction should fail'
+except ValueError as e:
+    assert str(e) == "extrasaction (skip) must be 'raise' or 'ignore'", 'extrasaction message'
+try:
+    csv.DictWriter(buf)
+    assert False, 'missing fieldnames should fail'
+except TypeError as e:
+    assert str(e).endswith("missing 1 required positional argument: 'fieldnames'"), 'missing fieldnames message'
+
+# === types ===
+assert str(type(csv.writer(io.StringIO()))) == "<class '_csv.writer'>", 'writer type'
+assert str(type(csv.DictWriter(io.StringIO(), ['a']))) == "<class 'csv.DictWriter'>", 'DictWriter type'
+assert csv.QUOTE_MINIMAL == 0, 'QUOTE_MINIMAL'
+assert csv.QUOTE_ALL == 1, 'QUOTE_ALL'
+assert csv.QUOTE_NONNUMERIC == 2, 'QUOTE_NONNUMERIC'
+assert csv.QUOTE_NONE == 3, 'QUOTE_NONE'
+assert csv.QUOTE_STRINGS == 4, 'QUOTE_STRINGS'
+assert csv.QUOTE_NOTNULL == 5, 'QUOTE_NOTNULL'
+assert issubclass(csv.Error, Exception), 'csv.Error is an Exception'
@@ -7,6 +7,6 @@ TRACEBACK:
 Traceback (most recent call last):
   File "dataclass__call_field_error.py", line 4, in <module>
     point.x()
-    ~~~~~~~~~
+    ~~~~~~~^^
 TypeError: 'int' object is not callable
 """
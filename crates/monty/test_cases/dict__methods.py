@@ -143,9 +143,5 @@ try:
     d = {}
     d.update([('a', 1), 'x', ('c', 3)])  # 'x' at index 1 is not a 2-tuple
     assert False, 'should raise ValueError'
-except (ValueError, TypeError) as e:
-    msg = str(e)
-    # Error message should mention 'length' requirement
-    assert 'length' in msg.lower(), f'error should mention length, got: {e}'
-    # TODO: CPython includes element index (#N) in error message
-    # assert '#1' in msg, 'error should mention element index'
+except ValueError as e:
+    assert str(e) == 'dictionary update sequence element #1 has length 1; 2 is required', f'got: {e}'
@@ -0,0 +1,67 @@
+# === Insertion order ===
+d = {'a': 1, 'b': 2, 'c': 3}
+assert list(d) == ['a', 'b', 'c'], 'dict iterates in insertion order'
+d['z'] = 26
+d['m'] = 13
+assert list(d) == ['a', 'b', 'c', 'z', 'm'], 'new keys append to the end'
+
+# === Overwrite keeps position ===
+d = {'a': 1, 'b': 2, 'c': 3}
+d['a'] = 100
+assert list(d.items()) == [('a', 100), ('b', 2), ('c', 3)], 'overwriting a key keeps its position'
+
+# === Pop and re-insert ===
+d = {'a': 1, 'b': 2, 'c': 3, 'd': 4}
+d.pop('b')
+assert list(d) == ['a', 'c', 'd'], 'pop removes key and keeps order'
+assert d['c'] == 3, 'lookup after pop finds later key'
+assert d['d'] == 4, 'lookup after pop finds last key'
+d['b'] = 20
+assert list(d) == ['a', 'c', 'd', 'b'], 're-inserted key moves to the end'
+assert d['b'] == 20, 're-inserted value'
+
+# === Pop from the middle ===
+d = {i: i * 10 for i in range(8)}
+assert d.pop(2) == 20, 'pop returns value'
+assert d.pop(5) == 50, 'second pop returns value'
+assert list(d) == [0, 1, 3, 4, 6, 7], 'pop keeps remaining order'
+for k in [0, 1, 3, 4, 6, 7]:
+    assert d[k] == k * 10, 'every remaining key is still reachable'
+assert 2 not in d, 'popped key is gone'
+assert 5 not in d, 'second popped key is gone'
+d[2] = 'two'
+assert list(d) == [0, 1, 3, 4, 6, 7, 2], 're-inserted popped key goes last'
+assert d.get(7) == 70, 'get after re-insert'
+
+# === Many pops ===
+d = {}
+for i in range(50):
+    d[i] = i
+for i in range(0, 50, 3):
+    d.pop(i)
+expected = [i for i in range(50) if i % 3 != 0]
+assert list(d) == expected, 'order after many pops'
+assert all(d[k] == k for k in expected), 'all keys reachable after many pops'
+for i in range(0, 50, 3):
+    d[i] = -i
+assert list(d)[-17:] == list(range(0, 50, 3)), 're-inserted keys appended in order'
+assert d[48] == -48, 'lookup of re-inserted key'
+
+# === popitem ===
+d = {'x': 1, 'y': 2, 'z': 3}
+d.pop('x')
+assert d.popitem() == ('z', 3), 'popitem removes the last inserted item'
+d['w'] = 4
+assert list(d.items()) == [('y', 2), ('w', 4)], 'order after popitem and insert'
+assert d['y'] == 2, 'lookup after popitem'
+
+# === reversed ===
+d = {'a': 1, 'b': 2, 'c': 3}
+assert list(reversed(d)) == ['c', 'b', 'a'], 'reversed dict yields keys in reverse'
+assert list(reversed(d.keys())) == ['c', 'b', 'a'], 'reversed keys'
+assert list(reversed(d.values())) == [3, 2, 1], 'reversed values'
+assert list(reversed(d.items())) == [('c', 3), ('b', 2), ('a', 1)], 'reversed items'
+d.pop('b')
+d['b'] = 22
+assert list(reversed(d)) == ['b', 'c', 'a'], 'reversed follows insertion order after re-insert'
+assert list(reversed({})) == [], 'reversed empty dict'
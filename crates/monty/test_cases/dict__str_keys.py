@@ -0,0 +1,29 @@
+# Tests dict lookups mixing interned string literals and runtime-built strings
+
+# === Literal keys found by built strings ===
+d = {'alpha': 1, 'x': 2, 'name': 3, 'a longer key with spaces': 4}
+assert d['al' + 'pha'] == 1, 'interned key found by concatenated string'
+assert d[chr(120)] == 2, 'single-char key found by built string'
+assert d[''.join(['na', 'me'])] == 3, 'common identifier key found by built string'
+assert d['a longer key ' + 'with spaces'] == 4, 'long key found by built string'
+
+# === Built keys found by literals ===
+built = {}
+built['be' + 'ta'] = 10
+built[str(7)] = 11
+assert built['beta'] == 10, 'built key found by literal'
+assert built['7'] == 11, 'built single-char key found by literal'
+assert 'beta' in built, 'membership with literal key'
+
+# === Overwriting through either representation ===
+d['alpha'] = 100
+d['alp' + 'ha'] = 200
+assert len(d) == 4, 'no duplicate keys'
+assert d['alpha'] == 200, 'overwritten through built string'
+
+# === Hot loop ===
+counts = {'hit': 0, 'miss': 0}
+for i in range(100):
+    key = 'hit' if i % 3 else 'miss'
+    counts[key] = counts[key] + 1
+assert counts == {'hit': 66, 'miss': 34}, 'counting with literal keys'
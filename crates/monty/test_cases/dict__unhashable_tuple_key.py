@@ -0,0 +1,2 @@
+{(1, [2]): 'value'}
+# Raise=TypeError("cannot use 'tuple' as a dict key (unhashable type: 'list')")
@@ -14,3 +14,68 @@ try:
     raise TypeError('type error')
 except TypeError as e:
     assert e.args[0] == 'type error', 'works for other exception types'
+
+# === non-string and multiple arguments ===
+e = ValueError(1, [2])
+assert e.args == (1, [2]), 'several args'
+assert e.args[0] == 1, 'args keep their types'
+assert e.args[1][0] == 2, 'nested subscription'
+assert ValueError(None).args == (None,), 'None argument'
+assert ValueError(1.5).args[0] == 1.5, 'float argument'
+assert len(RuntimeError('a', 'b', 'c').args) == 3, 'three args'
+try:
+    raise ValueError('bad value', 42)
+except ValueError as e:
+    assert e.args == ('bad value', 42), 'args survive raising'
+    msg, code = e.args
+    assert code == 42, 'unpacking args'
+try:
+    ValueError(x=1)
+    assert False, 'keyword arguments should fail'
+except TypeError as e:
+    assert str(e) == 'ValueError() takes no keyword arguments', 'keyword message'
+
+# === KeyError ===
+try:
+    {}[1]
+except KeyError as e:
+    assert e.args == (1,), 'int key'
+    assert e.args[0] + 1 == 2, 'int key is an int'
+try:
+    {}[(1, 'a')]
+except KeyError as e:
+    assert e.args[0] == (1, 'a'), 'tuple key'
+try:
+    {'a': 1}['b']
+except KeyError as e:
+    assert e.args == ('b',), 'str key'
+try:
+    {1, 2}.remove(3)
+except KeyError as e:
+    assert e.args == (3,), 'set remove key'
+
+# === OSError ===
+e = OSError(2, 'No such file', 'data.txt')
+assert type(e) is FileNotFoundError, 'errno picks the subclass'
+assert isinstance(e, OSError), 'subclass is still an OSError'
+assert e.args == (2, 'No such file'), 'filename is not in args'
+assert e.errno == 2, 'errno'
+assert e.strerror == 'No such file', 'strerror'
+assert e.filename == 'data.txt', 'filename'
+e = OSError(5, 'Input/output error')
+assert type(e) is OSError, 'unknown errno stays OSError'
+assert e.errno == 5, 'errno without filename'
+assert e.filename is None, 'no filename'
+e = OSError(1, 'x', None)
+assert e.args == (1, 'x', None), 'None filename stays in args'
+e = OSError('just a message')
+assert e.errno is None, 'no errno'
+assert e.strerror is None, 'no strerror'
+assert e.filename is None, 'no filename for a message'
+assert OSError(1, 2, 3, 4, 5, 6).errno is None, 'too many args for errno'
+assert FileExistsError(17, 'exists', 'f').filename == 'f', 'subclass attributes'
+try:
+    ValueError('x').errno
+    assert False, 'errno only exists on OSError'
+except AttributeError as e:
+    assert str(e) == "'ValueError' object has no attribute 'errno'", 'errno message'
@@ -0,0 +1,49 @@
+# === add_note and __notes__ ===
+try:
+    raise ValueError('bad value')
+except ValueError as e:
+    e.add_note('first note')
+    e.add_note('second note')
+    assert e.__notes__ == ['first note', 'second note'], 'notes are stored in the order they were added'
+    assert str(e) == 'bad value', 'notes do not change str()'
+    assert e.args == ('bad value',), 'notes do not change args'
+
+# === add_note returns None ===
+e = KeyError('k')
+assert e.add_note('note') is None, 'add_note returns None'
+assert e.__notes__ == ['note'], 'notes can be added to an exception that was never raised'
+
+# === __notes__ only exists once a note is added ===
+e = TypeError('no notes')
+try:
+    e.__notes__
+    assert False, '__notes__ should not exist yet'
+except AttributeError as attr_err:
+    assert str(attr_err) == "'TypeError' object has no attribute '__notes__'", 'missing __notes__ message'
+
+# === notes must be strings ===
+try:
+    ValueError().add_note(42)
+    assert False, 'add_note should reject non-str'
+except TypeError as type_err:
+    assert str(type_err) == "note must be a str, not 'int'", 'add_note non-str message'
+
+# === notes survive re-raising ===
+try:
+    try:
+        raise RuntimeError('inner')
+    except RuntimeError as e:
+        e.add_note('added while handling')
+        raise
+except RuntimeError as outer:
+    assert outer.__notes__ == ['added while handling'], 'bare raise keeps notes'
+
+try:
+    try:
+        raise RuntimeError('inner')
+    except RuntimeError as e:
+        e.add_note('one')
+        raise e
+except RuntimeError as outer:
+    outer.add_note('two')
+    assert outer.__notes__ == ['one', 'two'], 'raise e keeps notes and more can be added'
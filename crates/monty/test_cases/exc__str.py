@@ -13,3 +13,48 @@ except ValueError as e:
     assert repr(e) == 'ValueError()'
 else:
     raise AssertionError('should raise an error')
+
+# === str and repr with arguments ===
+e = ValueError('msg')
+assert str(e) == 'msg', 'str of a message'
+assert repr(e) == "ValueError('msg')", 'repr of a message'
+e = ValueError(1, [2])
+assert str(e) == '(1, [2])', 'str of several args'
+assert repr(e) == 'ValueError(1, [2])', 'repr of several args'
+e = ValueError(None)
+assert str(e) == 'None', 'str of None'
+assert repr(e) == 'ValueError(None)', 'repr of None'
+e = TypeError(3)
+assert str(e) == '3', 'str of an int'
+assert repr(e) == 'TypeError(3)', 'repr of an int'
+assert f'{RuntimeError(1, 2)}' == '(1, 2)', 'f-string'
+assert repr([ValueError(1)]) == '[ValueError(1)]', 'repr in a list'
+
+# === KeyError ===
+e = KeyError('a')
+assert str(e) == "'a'", 'KeyError str uses the repr of the key'
+assert repr(e) == "KeyError('a')", 'KeyError repr'
+e = KeyError(1)
+assert str(e) == '1', 'KeyError str of an int'
+assert repr(e) == 'KeyError(1)', 'KeyError repr of an int'
+e = KeyError('a', 'b')
+assert str(e) == "('a', 'b')", 'KeyError str of several args'
+assert str(KeyError()) == '', 'KeyError without args'
+try:
+    {}[(1, 2)]
+except KeyError as e:
+    assert str(e) == '(1, 2)', 'str of a missing tuple key'
+    assert repr(e) == 'KeyError((1, 2))', 'repr of a missing tuple key'
+try:
+    {}[5]
+except KeyError as e:
+    assert str(e) == '5', 'str of a missing int key'
+
+# === OSError ===
+e = OSError(2, 'No such file', 'data.txt')
+assert str(e) == "[Errno 2] No such file: 'data.txt'", 'OSError str with filename'
+assert repr(e) == "FileNotFoundError(2, 'No such file')", 'OSError repr'
+assert str(OSError(5, 'Input/output error')) == '[Errno 5] Input/output error', 'OSError str'
+assert str(OSError(1, 'x', None)) == '[Errno 1] x', 'None filename'
+assert str(OSError('a', 'b', 'c')) == "[Errno a] b: 'c'", 'any errno'
+assert str(OSError('plain')) == 'plain', 'OSError message'
@@ -0,0 +1,207 @@
+from fractions import Fraction
+
+# === construction ===
+assert Fraction() == 0, 'no arguments is zero'
+assert Fraction(3) == 3, 'from an int'
+assert Fraction(6, 4) == Fraction(3, 2), 'reduced to lowest terms'
+assert Fraction(6, 4).numerator == 3, 'reduced numerator'
+assert Fraction(6, 4).denominator == 2, 'reduced denominator'
+assert Fraction(3, -6).numerator == -1, 'sign moves to the numerator'
+assert Fraction(3, -6).denominator == 2, 'denominator is positive'
+assert Fraction(Fraction(1, 2), Fraction(1, 3)) == Fraction(3, 2), 'from two fractions'
+assert Fraction(True) == 1, 'from a bool'
+assert Fraction(0.5) == Fraction(1, 2), 'from a float'
+assert Fraction(0.1) == Fraction(3602879701896397, 36028797018963968), 'floats are exact'
+assert Fraction(-2.0) == -2, 'from a whole float'
+assert Fraction(2**70, 2**68) == 4, 'from big ints'
+
+# === strings ===
+assert Fraction('3/4') == Fraction(3, 4), 'numerator and denominator'
+assert Fraction(' -3 / 4 ') == Fraction(-3, 4), 'whitespace and sign'
+assert Fraction('1.25') == Fraction(5, 4), 'decimal'
+assert Fraction('-.5') == Fraction(-1, 2), 'leading point'
+assert Fraction('2.') == 2, 'trailing point'
+assert Fraction('1e-3') == Fraction(1, 1000), 'negative exponent'
+assert Fraction('1.5E2') == 150, 'positive exponent'
+assert Fraction('1_000/3') == Fraction(1000, 3), 'underscores'
+for s in ['', '1/', '/2', '1.5/2', '1__0', '_1', 'x', '1e', '1 2', '3/-4', '.']:
+    try:
+        Fraction(s)
+        assert False, f'{s!r} should be invalid'
+    except ValueError as e:
+        assert str(e) == f'Invalid literal for Fraction: {s!r}', f'invalid message for {s!r}'
+
+# === construction errors ===
+try:
+    Fraction(1, 0)
+    assert False, 'zero denominator should fail'
+except ZeroDivisionError as e:
+    assert str(e) == 'Fraction(1, 0)', 'zero denominator message'
+try:
+    Fraction('5/0')
+    assert False, 'zero denominator string should fail'
+except ZeroDivisionError as e:
+    assert str(e) == 'Fraction(5, 0)', 'zero denominator string message'
+try:
+    Fraction(1.5, 2)
+    assert False, 'float numerator with a denominator should fail'
+except TypeError as e:
+    assert str(e) == 'both arguments should be Rational instances', 'two arguments message'
+try:
+    Fraction([1])
+    assert False, 'a list should fail'
+except TypeError as e:
+    assert str(e) == 'argument should be a string or a Rational instance', 'bad argument message'
+try:
+    Fraction(float('nan'))
+    assert False, 'nan should fail'
+except ValueError as e:
+    assert str(e) == 'cannot convert NaN to integer ratio', 'nan message'
+try:
+    Fraction(float('inf'))
+    assert False, 'inf should fail'
+except OverflowError as e:
+    assert str(e) == 'cannot convert Infinity to integer ratio', 'inf message'
+
+# === str and repr ===
+assert str(Fraction(3, 4)) == '3/4', 'str'
+assert str(Fraction(-6, 3)) == '-2', 'str of a whole fraction'
+assert repr(Fraction(3, 4)) == 'Fraction(3, 4)', 'repr'
+assert repr(Fraction(5)) == 'Fraction(5, 1)', 'repr of a whole fraction'
+assert repr([Fraction(1, 2)]) == '[Fraction(1, 2)]', 'repr in a list'
+assert f'{Fraction(1, 3)}' == '1/3', 'f-string'
+
+# === arithmetic ===
+a = Fraction(1, 2)
+b = Fraction(1, 3)
+assert a + b == Fraction(5, 6), 'add'
+assert a - b == Fraction(1, 6), 'sub'
+assert a * b == Fraction(1, 6), 'mult'
+assert a / b == Fraction(3, 2), 'div'
+assert a // b == 1, 'floordiv'
+assert type(a // b) is int, 'floordiv gives an int'
+assert a % b == Fraction(1, 6), 'mod'
+assert -a == Fraction(-1, 2), 'neg'
+assert +a == a, 'pos'
+assert abs(Fraction(-3, 4)) == Fraction(3, 4), 'abs'
+assert Fraction(-7, 2) // 1 == -4, 'floordiv rounds down'
+assert Fraction(-7, 2) % 2 == Fraction(1, 2), 'mod takes the sign of the divisor'
+assert divmod(Fraction(7, 2), Fraction(1, 3)) == (10, Fraction(1, 6)), 'divmod'
+x = Fraction(1, 4)
+x += Fraction(1, 4)
+assert x == Fraction(1, 2), 'augmented add'
+x *= 4
+assert x == 2, 'augmented mult'
+
+# === mixed with int and float ===
+assert a + 1 == Fraction(3, 2), 'fraction + int'
+assert 1 + a == Fraction(3, 2), 'int + fraction'
+assert 1 - a == Fraction(1, 2), 'int - fraction'
+assert 2 / Fraction(4) == Fraction(1, 2), 'int / fraction'
+assert type(1 + a) is Fraction, 'int + fraction is a fraction'
+assert True + a == Fraction(3, 2), 'bool + fraction'
+assert a + 2**70 == Fraction(2**71 + 1, 2), 'fraction + big int'
+assert a + 0.25 == 0.75, 'fraction + float'
+assert type(a + 0.25) is float, 'fraction + float is a float'
+assert 1.5 * a == 0.75, 'float * fraction'
+assert Fraction(7, 2) // 0.5 == 7.0, 'fraction // float'
+assert sum([Fraction(1, 3), Fraction(1, 3), Fraction(1, 3)]) == 1, 'sum of fractions'
+assert sum([1, Fraction(1, 2), 0.25]) == 1.75, 'sum of mixed numbers'
+try:
+    a + 'x'
+    assert False, 'fraction + str should fail'
+except TypeError as e:
+    assert str(e) == "unsupported operand type(s) for +: 'Fraction' and 'str'", 'add str message'
+try:
+    a / 0
+    assert False, 'division by zero should fail'
+except ZeroDivisionError as e:
+    assert str(e) == 'Fraction(1, 0)', 'division by zero message'
+
+# === powers ===
+assert Fraction(2, 3) ** 2 == Fraction(4, 9), 'int power'
+assert Fraction(2, 3) ** -2 == Fraction(9, 4), 'negative power'
+assert Fraction(4, 9) ** Fraction(1, 2) == 2 / 3, 'fractional power is a float'
+assert 2 ** Fraction(3) == 8, 'int ** whole fraction'
+assert type(2 ** Fraction(3)) is int, 'int ** whole fraction is an int'
+assert 2 ** Fraction(-1) == Fraction(1, 2), 'int ** negative fraction'
+assert 4 ** Fraction(1, 2) == 2.0, 'int ** fractional fraction'
+assert pow(Fraction(1, 2), 3) == Fraction(1, 8), 'pow()'
+try:
+    Fraction(0) ** -1
+    assert False, 'zero to a negative power should fail'
+except ZeroDivisionError as e:
+    assert str(e) == 'Fraction(1, 0)', 'zero power message'
+
+# === comparison ===
+assert Fraction(1, 2) == 0.5, 'equal to a float'
+assert 0.5 == Fraction(1, 2), 'float equal to a fraction'
+assert Fraction(2) == 2, 'equal to an int'
+assert Fraction(1) == True, 'equal to a bool'
+assert Fraction(1, 3) != 0.3333333333333333, 'not equal to a rounded float'
+assert Fraction(1, 3) < Fraction(1, 2), 'less than'
+assert Fraction(1, 3) < 0.5, 'less than a float'
+assert 1 > Fraction(1, 3), 'int greater than'
+assert Fraction(2**70 + 1, 2) > 2**69, 'greater than a big int'
+assert Fraction(10**400) < float('inf'), 'less than infinity'
+assert Fraction(-10**400) > float('-inf'), 'greater than negative infinity'
+assert not Fraction(1, 2) < float('nan'), 'nan is unordered'
+assert Fraction(1, 2) != float('nan'), 'nan is not equal'
+assert sorted([Fraction(1, 2), 0.25, 1, Fraction(1, 3)]) == [0.25, Fraction(1, 3), Fraction(1, 2), 1], 'sorted'
+assert max(Fraction(1, 2), Fraction(2, 3)) == Fraction(2, 3), 'max'
+
+# === hashing ===
+assert hash(Fraction(1, 2)) == hash(0.5), 'hash matches float'
+assert hash(Fraction(5)) == hash(5), 'hash matches int'
+assert hash(Fraction(2**70)) == hash(2**70), 'hash matches big int'
+assert hash(Fraction(-1)) == -2, 'hash of -1'
+assert hash(Fraction(1, 3)) == 1537228672809129301, 'hash of a fraction'
+assert hash(Fraction(1, 2**61 - 1)) == 314159, 'hash without an inverse'
+d = {Fraction(1, 2): 'half', 3: 'three'}
+assert d[0.5] == 'half', 'float looks up a fraction key'
+assert d[Fraction(3)] == 'three', 'fraction looks up an int key'
+assert len({Fraction(1, 2), 0.5, Fraction(2, 4)}) == 1, 'equal values in a set'
+
+# === conversion ===
+assert int(Fraction(7, 2)) == 3, 'int truncates'
+assert int(Fraction(-7, 2)) == -3, 'int truncates toward zero'
+assert float(Fraction(1, 3)) == 1 / 3, 'float'
+assert float(Fraction(10**400, 10**399)) == 10.0, 'float of big parts'
+assert bool(Fraction(0)) is False, 'zero is falsy'
+assert bool(Fraction(1, 2)) is True, 'nonzero is truthy'
+assert round(Fraction(5, 2)) == 2, 'round half to even'
+assert round(Fraction(7, 2)) == 4, 'round half to even upward'
+assert type(round(Fraction(7, 2))) is int, 'round gives an int'
+assert round(Fraction(-5, 2)) == -2, 'round negative half'
+assert round(Fraction(1, 3), 2) == Fraction(33, 100), 'round to digits'
+assert round(Fraction(1234), -2) == Fraction(1200), 'round to tens'
+assert round(Fraction(25, 1000), 2) == Fraction(1, 50), 'round digits half to even'
+try:
+    float(Fraction(10**400))
+    assert False, 'huge float should fail'
+except OverflowError as e:
+    assert str(e) == 'integer division result too large for a float', 'float overflow message'
+
+# === methods ===
+assert Fraction(3, 4).as_integer_ratio() == (3, 4), 'as_integer_ratio'
+assert Fraction(4, 2).is_integer(), 'is_integer'
+assert not Fraction(1, 2).is_integer(), 'is_integer false'
+assert Fraction('3.1415926535897932').limit_denominator(1000) == Fraction(355, 113), 'limit_denominator'
+assert Fraction(0.1).limit_denominator() == Fraction(1, 10), 'limit_denominator default'
+assert Fraction(1, 3).limit_denominator(10) == Fraction(1, 3), 'small denominator unchanged'
+assert Fraction(-7, 5).limit_denominator(2) == Fraction(-3, 2), 'negative limit_denominator'
+try:
+    Fraction(1, 3).limit_denominator(0)
+    assert False, 'max_denominator 0 should fail'
+except ValueError as e:
+    assert str(e) == 'max_denominator should be at least 1', 'limit_denominator message'
+try:
+    Fraction(1, 2).nope
+    assert False, 'unknown attribute should fail'
+except AttributeError:
+    pass
+
+# === types ===
+assert isinstance(Fraction(1, 2), Fraction), 'isinstance'
+assert type(Fraction(1, 2)) is Fraction, 'type'
+assert type(Fraction(1, 2) * 2) is Fraction, 'whole product is still a fraction'
@@ -77,3 +77,31 @@ fs = frozenset([1, 2])
 d[fs] = 'value'
 assert d[fs] == 'value', 'frozenset as dict key'
 assert d[frozenset([2, 1])] == 'value', 'frozenset key lookup order-independent'
+
+# === Operators ===
+fa = frozenset([1, 2, 3])
+fb = frozenset([3, 4])
+assert fa | fb == frozenset([1, 2, 3, 4]), 'frozenset | frozenset'
+assert fa & fb == frozenset([3]), 'frozenset & frozenset'
+assert fa - fb == frozenset([1, 2]), 'frozenset - frozenset'
+assert fa ^ fb == frozenset([1, 2, 4]), 'frozenset ^ frozenset'
+assert type(fa | {5}) == frozenset, 'frozenset | set is a frozenset'
+
+# In-place operators rebind rather than mutate
+f = frozenset([1])
+alias = f
+f |= {2}
+assert f == frozenset([1, 2]), '|= on frozenset result'
+assert alias == frozenset([1]), '|= on frozenset leaves the original unchanged'
+assert type(f) == frozenset, '|= on frozenset keeps the type'
+
+# === Comparisons ===
+assert frozenset([1]) < frozenset([1, 2]), 'frozenset proper subset'
+assert frozenset([1, 2]) >= {1}, 'frozenset superset of set'
+assert frozenset([1, 2]) == {2, 1}, 'frozenset equals set with same elements'
+
+# === As set member ===
+s = {frozenset([1, 2]), frozenset([2, 1]), frozenset()}
+assert len(s) == 2, 'equal frozensets deduplicate in a set'
+assert frozenset([1, 2]) in s, 'frozenset membership'
+assert d[frozenset({1}) | {2}] == 'value', 'computed frozenset as dict key'
@@ -0,0 +1,120 @@
+# === Underscores in numeric literals ===
+assert 1_000_000 == 1000000, 'decimal literal'
+assert 0x_FF_FF == 65535, 'hex literal with leading separator'
+assert 0o_7_7 == 63, 'octal literal'
+assert 0b_1111_0000 == 240, 'binary literal'
+assert 1_000.000_1 == 1000.0001, 'float literal'
+assert 1_0e1_0 == 1e11, 'float literal exponent'
+assert 1_000_000_000_000_000_000_000 == 10**21, 'long int literal'
+
+# === f-string grouping ===
+n = 1234567
+assert f'{n:,}' == '1,234,567', 'comma'
+assert f'{n:_}' == '1_234_567', 'underscore'
+assert f'{-n:,}' == '-1,234,567', 'negative comma'
+assert f'{n:+,d}' == '+1,234,567', 'sign and type'
+assert f'{n:>12,}' == '   1,234,567', 'width and comma'
+assert f'{n:*^13_}' == '**1_234_567**', 'fill, center and underscore'
+assert f'{n:012,}' == '0,001,234,567', 'zero padding is grouped'
+assert f'{123:,}' == '123', 'no separator below a thousand'
+assert f'{0:_}' == '0', 'zero'
+assert f'{True:,}' == '1', 'bool'
+assert f'{10**20:_}' == '100_000_000_000_000_000_000', 'long int'
+assert f'{n:_x}' == '12_d687', 'hex groups of four'
+assert f'{n:#_o}' == '0o455_3207', 'octal groups of four with prefix'
+assert f'{n:_b}' == '1_0010_1101_0110_1000_0111', 'binary groups of four'
+
+x = 1234567.891
+assert f'{x:,}' == '1,234,567.891', 'float comma'
+assert f'{x:_.2f}' == '1_234_567.89', 'float underscore fixed'
+assert f'{x:,.3e}' == '1.235e+06', 'float exponent has one integer digit'
+assert f'{x:,.0%}' == '123,456,789%', 'float percent'
+assert f'{-x:15,.1f}' == '   -1,234,567.9', 'float width'
+assert f'{1e20:,}' == '1e+20', 'float repr exponent'
+assert f'{float("inf"):,}' == 'inf', 'float infinity'
+
+# === Dynamic specs and format() ===
+sep = '_'
+assert f'{n:{sep}}' == '1_234_567', 'nested separator'
+assert format(n, ',') == f'{n:,}', 'format matches f-string'
+
+# === 'n' uses no separators ===
+assert f'{n:n}' == '1234567', 'n type is locale independent'
+assert f'{x:n}' == '1.23457e+06', 'n type for float'
+
+# === Grouping errors ===
+try:
+    f'{n:,_}'
+    assert False, 'both separators should raise'
+except ValueError as e:
+    assert str(e) == "Cannot specify both ',' and '_'.", 'both separators message'
+
+try:
+    format(n, '_,d')
+    assert False, 'both separators reversed should raise'
+except ValueError as e:
+    assert str(e) == "Cannot specify both ',' and '_'.", 'both separators reversed message'
+
+try:
+    f'{n:,,}'
+    assert False, 'repeated comma should raise'
+except ValueError as e:
+    assert str(e) == "Cannot specify ',' with ','.", 'repeated comma message'
+
+try:
+    format(x, '_.2_')
+    assert False, 'separator as type should raise'
+except ValueError as e:
+    assert str(e) == "Cannot specify '_' with '_'.", 'separator as type message'
+
+try:
+    f'{n:,q}'
+    assert False, 'unknown type with comma should raise'
+except ValueError as e:
+    assert str(e) == "Cannot specify ',' with 'q'.", 'unknown type with comma message'
+
+try:
+    f'{x:,b}'
+    assert False, 'comma with binary should raise'
+except ValueError as e:
+    assert str(e) == "Cannot specify ',' with 'b'.", 'grouping is checked before the value type'
+
+try:
+    format(n, ',,d')
+    assert False, 'invalid spec should raise'
+except ValueError as e:
+    assert str(e) == "Invalid format specifier ',,d' for object of type 'int'", 'invalid spec message'
+
+# === int() and float() parsing ===
+assert int('1_000') == 1000, 'int with underscores'
+assert int('-1_000_000') == -1000000, 'negative int with underscores'
+assert int('0x_ff', 16) == 255, 'int with prefix separator'
+assert int('0b1_0', 0) == 2, 'int base 0 with underscores'
+assert int('ff_ff', 16) == 65535, 'int hex with underscores'
+assert float('1_000.5') == 1000.5, 'float with underscores'
+assert float('1e1_0') == 1e10, 'float exponent with underscores'
+assert int('١٢٣') == 123, 'int arabic-indic digits'
+assert int('１_０') == 10, 'int fullwidth digits with underscore'
+assert int('٣٣', 8) == 27, 'int arabic-indic digits base 8'
+assert int('　४२　') == 42, 'int devanagari digits with unicode whitespace'
+assert float('١٢.٥') == 12.5, 'float arabic-indic digits'
+assert float('𝟙𝟘') == 10.0, 'float mathematical digits'
+
+for bad in ['_1', '1_', '1__0', '0x_', '1_000_']:
+    try:
+        int(bad, 0 if bad.startswith('0x') else 10)
+        assert False, 'int should reject ' + bad
+    except ValueError:
+        pass
+
+try:
+    int('١٫٥')
+    assert False, 'arabic decimal separator should raise'
+except ValueError as e:
+    assert str(e) == "invalid literal for int() with base 10: '١٫٥'", 'non-digit error keeps original text'
+
+try:
+    float('²')
+    assert False, 'superscript should raise'
+except ValueError as e:
+    assert str(e) == "could not convert string to float: '²'", 'superscript is not decimal'
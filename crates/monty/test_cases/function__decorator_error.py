@@ -0,0 +1,9 @@
+# Test: an exception raised by a decorator propagates from the definition
+def bad(func):
+    raise ValueError('nope')
+
+
+@bad
+def f():
+    pass
+# Raise=ValueError('nope')
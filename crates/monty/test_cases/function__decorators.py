@@ -0,0 +1,179 @@
+# === Simple decorator ===
+def double_result(func):
+    def wrapper(*args, **kwargs):
+        return func(*args, **kwargs) * 2
+
+    return wrapper
+
+
+@double_result
+def add(a, b):
+    return a + b
+
+
+assert add(1, 2) == 6, 'decorator wraps the function'
+assert add(a=2, b=3) == 10, 'wrapper forwards keyword arguments'
+
+
+# === Decorator factory ===
+def multiply_by(factor):
+    def decorator(func):
+        def wrapper(x):
+            return func(x) * factor
+
+        return wrapper
+
+    return decorator
+
+
+@multiply_by(3)
+def identity(x):
+    return x
+
+
+assert identity(5) == 15, 'decorator factory with argument'
+
+
+# === Stacked decorators apply bottom-up ===
+def tag(label):
+    def decorator(func):
+        def wrapper():
+            return label + '(' + func() + ')'
+
+        return wrapper
+
+    return decorator
+
+
+@tag('outer')
+@tag('inner')
+def base():
+    return 'x'
+
+
+assert base() == 'outer(inner(x))', 'innermost decorator is applied first'
+
+# === Evaluation order ===
+events = []
+
+
+def record(name):
+    events.append('eval ' + name)
+
+    def decorator(func):
+        events.append('apply ' + name)
+        return func
+
+    return decorator
+
+
+def default_value():
+    events.append('default')
+    return 1
+
+
+@record('first')
+@record('second')
+def ordered(x=default_value()):
+    return x
+
+
+assert events == [
+    'eval first',
+    'eval second',
+    'default',
+    'apply second',
+    'apply first',
+], 'decorators are evaluated top-down before defaults and applied bottom-up'
+assert ordered() == 1, 'decorated function keeps its defaults'
+
+# === Registration decorator ===
+registry = {}
+
+
+def register(func):
+    registry[func.__name__] = func
+    return func
+
+
+@register
+def handler():
+    return 'handled'
+
+
+assert registry['handler']() == 'handled', 'decorator can register and return the function'
+assert handler() == 'handled', 'returned function is bound to the name'
+
+
+# === Decorator returning a non-function ===
+def to_value(func):
+    return func()
+
+
+@to_value
+def computed():
+    return 42
+
+
+assert computed == 42, 'decorator result replaces the function'
+
+# === Lambda and attribute decorators ===
+@(lambda func: lambda: func() + 1)
+def plus_one():
+    return 1
+
+
+assert plus_one() == 2, 'lambda expression as decorator'
+
+decorators = {'double': double_result}
+
+
+@decorators['double']
+def sub(a, b):
+    return a - b
+
+
+assert sub(5, 2) == 6, 'subscript expression as decorator'
+
+
+# === Decorated nested function and closures ===
+def make_counter_decorator():
+    calls = []
+
+    def count_calls(func):
+        def wrapper():
+            calls.append(1)
+            return func()
+
+        return wrapper
+
+    @count_calls
+    def inner():
+        return len(calls)
+
+    inner()
+    inner()
+    return inner(), len(calls)
+
+
+assert make_counter_decorator() == (3, 3), 'decorator defined in enclosing function captures its cells'
+
+
+def decorate_with_local():
+    suffix = '!'
+
+    @(lambda func: lambda: func() + suffix)
+    def shout():
+        return 'hi'
+
+    return shout()
+
+
+assert decorate_with_local() == 'hi!', 'lambda decorator captures enclosing local'
+
+# === Function __name__ ===
+assert handler.__name__ == 'handler', 'plain function __name__'
+assert add.__name__ == 'wrapper', 'decorated function takes the wrapper name'
+assert identity.__name__ == 'wrapper', 'closure __name__'
+assert ordered.__name__ == 'ordered', 'function with defaults __name__'
+assert (lambda: 0).__name__ == '<lambda>', 'lambda __name__'
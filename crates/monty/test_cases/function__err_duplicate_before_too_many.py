@@ -0,0 +1,7 @@
+# Test: a duplicate keyword is reported before too many positional arguments
+def f(a, b=1):
+    return a + b
+
+
+f(1, 2, 3, b=4)
+# Raise=TypeError("f() got multiple values for argument 'b'")
@@ -0,0 +1,7 @@
+# Test: a single extra positional argument uses "was given"
+def f(*, a=1):
+    return a
+
+
+f(1)
+# Raise=TypeError('f() takes 0 positional arguments but 1 was given')
@@ -0,0 +1,7 @@
+# Test: three missing keyword-only arguments are joined with a serial comma
+def f(*, a, b, c):
+    return a + b + c
+
+
+f()
+# Raise=TypeError("f() missing 3 required keyword-only arguments: 'a', 'b', and 'c'")
@@ -0,0 +1,7 @@
+# Test: parameters with defaults are not reported as missing
+def f(a, b, c=1):
+    return a + b + c
+
+
+f()
+# Raise=TypeError("f() missing 2 required positional arguments: 'a' and 'b'")
@@ -0,0 +1,7 @@
+# Test: too many positional arguments when some parameters have defaults
+def f(a, b=1):
+    return a + b
+
+
+f(1, 2, 3)
+# Raise=TypeError('f() takes from 1 to 2 positional arguments but 3 were given')
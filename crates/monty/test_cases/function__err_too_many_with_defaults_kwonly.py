@@ -0,0 +1,7 @@
+# Test: too many positional arguments with defaults and a keyword-only argument given
+def f(a, b=2, *, c):
+    return a + b + c
+
+
+f(1, 2, 3, c=4)
+# Raise=TypeError('f() takes from 1 to 2 positional arguments but 3 positional arguments (and 1 keyword-only argument) were given')
@@ -0,0 +1,7 @@
+# Test: an unexpected keyword is reported before too many positional arguments
+def f(a, b=1):
+    return a + b
+
+
+f(1, 2, 3, x=4)
+# Raise=TypeError("f() got an unexpected keyword argument 'x'")
@@ -45,3 +45,39 @@ assert collect_all(*source_tuple) == (1, 2, 3), 'tuple unpacked with *args'
 
 source_list = [4, 5]
 assert collect_all(0, *source_list) == (0, 4, 5), 'positional args followed by *args'
+
+
+# === Positional-only names in **kwargs ===
+def pos_only_kwargs(a, /, **kwargs):
+    return a, kwargs
+
+
+assert pos_only_kwargs(1, a=2) == (1, {'a': 2}), 'positional-only name is collected by **kwargs'
+assert pos_only_kwargs(1) == (1, {}), 'empty **kwargs alongside positional-only'
+
+
+# === Keyword-only parameters ===
+def kw_only(a, *, b, c=3):
+    return a + b + c
+
+
+assert kw_only(1, b=2) == 6, 'keyword-only with default'
+assert kw_only(1, c=10, b=2) == 13, 'keyword-only in any order'
+
+
+def args_then_kw_only(*args, sep='-'):
+    return sep.join(args)
+
+
+assert args_then_kw_only('a', 'b') == 'a-b', 'keyword-only default after *args'
+assert args_then_kw_only('a', 'b', sep='+') == 'a+b', 'keyword-only override after *args'
+
+
+# === All parameter kinds ===
+def everything(a, b=2, /, c=3, *args, d, e=5, **kwargs):
+    return (a, b, c, args, d, e, kwargs)
+
+
+assert everything(1, d=4) == (1, 2, 3, (), 4, 5, {}), 'all kinds with defaults'
+assert everything(1, 20, 30, 40, 50, d=4, f=6) == (1, 20, 30, (40, 50), 4, 5, {'f': 6}), 'all kinds fully passed'
+assert everything(1, c=30, d=4, b=7) == (1, 2, 30, (), 4, 5, {'b': 7}), 'positional-only name goes to **kwargs'
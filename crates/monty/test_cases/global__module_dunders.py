@@ -0,0 +1,28 @@
+# Tests for module-level dunder variables
+"""Module docstring."""
+
+# === __name__ ===
+assert __name__ == '__main__', '__name__ is __main__'
+
+ran_main = False
+if __name__ == '__main__':
+    ran_main = True
+assert ran_main, '__main__ guard runs'
+
+
+def get_name():
+    return __name__
+
+
+assert get_name() == '__main__', '__name__ readable from functions'
+
+# === __file__ ===
+assert isinstance(__file__, str), '__file__ is a str'
+assert __file__.endswith('global__module_dunders.py'), '__file__ is the script name'
+
+# === __doc__ ===
+assert __doc__ == 'Module docstring.', '__doc__ is the module docstring'
+
+# === assignment ===
+__name__ = 'other'
+assert get_name() == 'other', '__name__ can be reassigned'
@@ -0,0 +1,75 @@
+import gzip
+import zlib
+
+# === round trips ===
+data = b'hello world ' * 100
+assert gzip.decompress(gzip.compress(data)) == data, 'gzip round trip'
+assert len(gzip.compress(data)) < len(data), 'repetitive data compresses'
+for level in range(10):
+    assert gzip.decompress(gzip.compress(data, level)) == data, 'round trip at every level'
+assert gzip.decompress(gzip.compress(b'')) == b'', 'empty data'
+assert gzip.decompress(b'') == b'', 'no members'
+assert gzip.decompress(zlib.compress(data, wbits=31)) == data, 'member written by zlib'
+
+# === data compressed by CPython ===
+gz = b'\x1f\x8b\x08\x00\x00\x00\x00\x00\x02\xff\xcbH\xcd\xc9\xc9W(\xcf/\xcaI\x01\x00\x85\x11J\r\x0b\x00\x00\x00'
+assert gzip.decompress(gz) == b'hello world', 'gzip member'
+assert gzip.decompress(gz + gz) == b'hello worldhello world', 'concatenated members'
+assert gzip.decompress(gz + b'\x00\x00\x00') == b'hello world', 'null padding after a member'
+named = b'\x1f\x8b\x08\x08\x00\x00\x00\x00\x02\xffa.txt\x00K\x04\x00C\xbe\xb7\xe8\x01\x00\x00\x00'
+assert gzip.decompress(named) == b'a', 'header with a file name'
+
+# === headers ===
+assert gzip.compress(b'hello', mtime=0)[:10] == b'\x1f\x8b\x08\x00\x00\x00\x00\x00\x02\xff', 'default header'
+assert gzip.compress(b'hello', 1, mtime=0)[8:10] == b'\x04\xff', 'best speed header'
+assert gzip.compress(b'hello', 5, mtime=0)[8:10] == b'\x00\xff', 'other level header'
+assert gzip.compress(b'hello', mtime=1234567890)[4:8] == b'\xd2\x02\x96I', 'mtime in the header'
+assert gzip.compress(b'hello', compresslevel=9, mtime=None)[:4] == b'\x1f\x8b\x08\x00', 'keyword arguments'
+
+# === errors ===
+try:
+    gzip.decompress(b'garbage')
+    assert False, 'non-gzip data should fail'
+except gzip.BadGzipFile as e:
+    assert str(e) == "Not a gzipped file (b'ga')", 'bad magic message'
+try:
+    gzip.decompress(gz + b'xx')
+    assert False, 'trailing garbage should fail'
+except gzip.BadGzipFile as e:
+    assert str(e) == "Not a gzipped file (b'xx')", 'trailing garbage message'
+try:
+    gzip.decompress(b'\x1f\x8b\x09' + b'\x00' * 20)
+    assert False, 'unknown method should fail'
+except gzip.BadGzipFile as e:
+    assert str(e) == 'Unknown compression method', 'unknown method message'
+try:
+    gzip.decompress(gz[:-1])
+    assert False, 'truncated data should fail'
+except EOFError as e:
+    assert str(e) == 'Compressed file ended before the end-of-stream marker was reached', 'truncated message'
+try:
+    gzip.decompress(gz[:-8] + b'\x00\x00\x00\x00' + gz[-4:])
+    assert False, 'bad CRC should fail'
+except gzip.BadGzipFile as e:
+    assert str(e) == 'CRC check failed', 'bad CRC message'
+try:
+    gzip.decompress(gz[:-4] + b'\x00\x00\x00\x00')
+    assert False, 'bad length should fail'
+except gzip.BadGzipFile as e:
+    assert str(e) == 'Incorrect length of data produced', 'bad length message'
+try:
+    gzip.decompress(gz + b'x')
+    assert False, 'trailing byte should fail'
+except OSError as e:
+    assert str(e) == "Not a gzipped file (b'x')", 'BadGzipFile is an OSError'
+try:
+    gzip.compress(b'abc', 12)
+    assert False, 'bad level should fail'
+except zlib.error as e:
+    assert str(e) == 'Bad compression level', 'level message'
+try:
+    gzip.compress('abc')
+    assert False, 'str data should fail'
+except TypeError as e:
+    assert str(e) == "a bytes-like object is required, not 'str'", 'str data message'
+assert issubclass(gzip.BadGzipFile, OSError), 'BadGzipFile subclasses OSError'
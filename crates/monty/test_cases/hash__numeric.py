@@ -0,0 +1,63 @@
+# Tests that numeric hashes match CPython exactly (modulo 2**61 - 1 scheme),
+# so equal ints, floats and bools hash equally and hashes are stable across runs.
+
+# === Int hashes ===
+assert hash(0) == 0, 'hash(0)'
+assert hash(1) == 1, 'hash(1)'
+assert hash(42) == 42, 'small ints hash to themselves'
+assert hash(-1) == -2, 'hash(-1) is -2'
+assert hash(-2) == -2, 'hash(-2)'
+assert hash(-42) == -42, 'negative ints hash to themselves'
+assert hash(2**61 - 1) == 0, 'modulus hashes to zero'
+assert hash(2**61) == 1, 'wraps around modulus'
+assert hash(2**62) == 2, 'power of two above modulus'
+assert hash(-(2**61)) == -2, 'negative wrap maps -1 to -2'
+assert hash(9223372036854775807) == 3, 'i64 max'
+assert hash(-9223372036854775808) == -4, 'i64 min'
+
+# === Big int hashes ===
+assert hash(2**64) == 8, '2**64'
+assert hash(10**30) == 465258685558744706, '10**30'
+assert hash(-(10**30)) == -465258685558744706, '-(10**30)'
+big = 2**100 + 7
+assert hash(big) == hash(big), 'big int hash consistent'
+
+# === Bool hashes ===
+assert hash(True) == 1, 'hash(True)'
+assert hash(False) == 0, 'hash(False)'
+
+# === Float hashes ===
+assert hash(0.0) == 0, 'hash(0.0)'
+assert hash(-0.0) == 0, 'hash(-0.0)'
+assert hash(1.0) == 1, 'integral float hashes like int'
+assert hash(-1.0) == -2, 'hash(-1.0)'
+assert hash(1.5) == 1152921504606846977, 'hash(1.5)'
+assert hash(-1.5) == -1152921504606846977, 'hash(-1.5)'
+assert hash(0.1) == 230584300921369408, 'hash(0.1)'
+assert hash(3.14) == 322818021289917443, 'hash(3.14)'
+assert hash(1e100) == 1822893315824342674, 'hash(1e100)'
+assert hash(5e-324) == 16777216, 'smallest subnormal'
+assert hash(2.0**64) == hash(2**64), 'large integral float hashes like int'
+assert hash(float('inf')) == 314159, 'hash(inf)'
+assert hash(float('-inf')) == -314159, 'hash(-inf)'
+
+# === Equal numbers hash equally ===
+for n in [0, 1, -1, 7, -123456, 2**52, 2**53]:
+    assert hash(n) == hash(float(n)), f'int and float hash equally for {n}'
+
+# === Mixed numeric dict keys ===
+d = {1: 'a'}
+assert d[1.0] == 'a', 'float key finds int entry'
+assert d[True] == 'a', 'bool key finds int entry'
+d[1.0] = 'b'
+assert len(d) == 1, 'equal float key replaces int entry'
+assert d == {1: 'b'}, 'original key kept, value replaced'
+d2 = {0.5: 'half', -2: 'neg'}
+assert d2[-2.0] == 'neg', 'negative float key finds int entry'
+assert 0.5 in d2, 'float key lookup'
+
+# === Mixed numeric sets ===
+s = {1, 1.0, True}
+assert len(s) == 1, 'equal numbers deduplicate in sets'
+assert {0, False, 0.0, -0.0} == {0}, 'zeros deduplicate'
+assert 2.0 in {1, 2, 3}, 'float membership in int set'
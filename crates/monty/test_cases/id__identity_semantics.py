@@ -0,0 +1,53 @@
+# Identity in Monty: singletons and containers behave like CPython. Equal ints, floats and
+# interned strings share an id, so `is` on them is true whenever `==` is; CPython only
+# guarantees that for cached objects, and `MontyRun::check_identity_literals` rejects
+# `is` comparisons against literals for code that should not depend on either behaviour.
+
+# === Singletons ===
+x = None
+assert x is None, 'None singleton'
+flag = 1 > 0
+assert flag is True, 'comparison returns the True singleton'
+assert (not flag) is False, 'not returns the False singleton'
+assert bool([]) is False, 'bool() returns singletons'
+e = ...
+assert e is ..., 'ellipsis singleton'
+
+# === Names bound to the same object ===
+a = [1, 2]
+b = a
+assert a is b, 'assignment shares identity'
+b.append(3)
+assert a is b and a == [1, 2, 3], 'mutation keeps identity'
+assert id(a) == id(b), 'id agrees with is'
+
+# === New containers are distinct ===
+assert [] is not [], 'list displays create new lists'
+assert {} is not {}, 'dict displays create new dicts'
+c = [1, 2, 3]
+assert c == a and c is not a, 'equal lists are distinct'
+assert list(a) is not a, 'list() copies'
+assert a[:] is not a, 'slicing copies'
+assert a.copy() is not a, 'copy() copies'
+
+# === Immutable values pass through ===
+t = (1, 2)
+assert tuple(t) is t, 'tuple() of a tuple returns it'
+s = 'monty'
+assert str(s) is s, 'str() of a str returns it'
+u = s + '!'
+assert str(u) is u, 'str() of a computed str returns it'
+n = 12
+m = n
+assert m is n, 'int assignment shares identity'
+
+# === Dict keys keep their objects ===
+k = 'key'
+d = {k: 1}
+assert next(iter(d)) is k, 'dict keeps the key object'
+
+# === is agrees with id ===
+values = [None, True, 1, 'a', (), [], a]
+for v in values:
+    w = v
+    assert (v is w) == (id(v) == id(w)), 'is matches id for ' + repr(v)
@@ -0,0 +1,106 @@
+# === Negative operands ===
+assert -6 & 3 == 2, 'and negative left'
+assert 6 & -3 == 4, 'and negative right'
+assert -6 & -3 == -8, 'and both negative'
+assert -6 | 3 == -5, 'or negative left'
+assert 6 | -3 == -1, 'or negative right'
+assert -6 | -3 == -1, 'or both negative'
+assert -6 ^ 3 == -7, 'xor negative left'
+assert 6 ^ -3 == -5, 'xor negative right'
+assert -6 ^ -3 == 7, 'xor both negative'
+assert -1 & 0xFF == 255, 'mask negative'
+
+# === Shifts ===
+assert 1 << 10 == 1024, 'lshift'
+assert -1 << 3 == -8, 'lshift negative'
+assert 1024 >> 3 == 128, 'rshift'
+assert -9 >> 1 == -5, 'rshift negative rounds toward negative infinity'
+assert -1 >> 100 == -1, 'rshift negative large count'
+assert 5 >> 100 == 0, 'rshift positive large count'
+assert 0 << 1000 == 0, 'lshift zero'
+
+# === Overflow to big ints ===
+assert 1 << 63 == 9223372036854775808, 'lshift past i64'
+assert 1 << 100 == 1267650600228229401496703205376, 'lshift large'
+assert -1 << 64 == -18446744073709551616, 'lshift negative past i64'
+assert (1 << 100) >> 98 == 4, 'rshift big int back to small'
+assert (1 << 64) - 1 & -(1 << 32) == 18446744069414584320, 'and big ints'
+assert (1 << 64) | 1 == 18446744073709551617, 'or big int'
+assert (1 << 64) ^ (1 << 64) == 0, 'xor big ints to zero'
+assert -(1 << 70) >> 69 == -2, 'rshift negative big int'
+i64_max = 9223372036854775807
+assert i64_max + 1 >> 1 == 4611686018427387904, 'rshift after overflow'
+assert ~i64_max == -9223372036854775808, 'invert i64 max'
+assert ~(-i64_max - 1) == i64_max, 'invert i64 min'
+assert ~(1 << 64) == -18446744073709551617, 'invert big int'
+assert ~-(1 << 64) == 18446744073709551615, 'invert negative big int'
+
+# === Unary invert ===
+assert ~5 == -6, 'invert positive'
+assert ~-6 == 5, 'invert negative'
+assert ~~7 == 7, 'double invert'
+x = 12
+assert ~x + 1 == -x, 'invert is negate minus one'
+
+# === Bool operands ===
+assert (True & False) is False, 'bool and bool is bool'
+assert (True | False) is True, 'bool or bool is bool'
+assert (True ^ True) is False, 'bool xor bool is bool'
+assert (False ^ True) is True, 'bool xor bool true'
+assert repr(True & True) == 'True', 'bool and repr'
+assert (True & 3) == 1 and type(True & 3) is int, 'bool and int is int'
+assert (2 | True) == 3 and type(2 | True) is int, 'int or bool is int'
+assert (True ^ 1) == 0 and type(True ^ 1) is int, 'bool xor int is int'
+assert (True << 3) == 8 and type(True << 3) is int, 'bool lshift is int'
+assert (True >> 1) == 0 and type(True >> 1) is int, 'bool rshift is int'
+assert (1 << True) == 2, 'shift by bool'
+flag = True
+flag &= False
+assert flag is False, 'inplace and on bools'
+flag |= True
+assert flag is True, 'inplace or on bools'
+flag ^= 1
+assert flag == 0 and type(flag) is int, 'inplace xor with int'
+
+# === Errors ===
+try:
+    1 << -1
+    assert False, 'negative shift should raise'
+except ValueError as e:
+    assert str(e) == 'negative shift count', 'negative lshift message'
+
+try:
+    1 >> -(1 << 70)
+    assert False, 'huge negative shift should raise'
+except ValueError as e:
+    assert str(e) == 'negative shift count', 'huge negative rshift message'
+
+try:
+    1.0 & 1
+    assert False, 'float and should raise'
+except TypeError as e:
+    assert str(e) == "unsupported operand type(s) for &: 'float' and 'int'", 'float and message'
+
+try:
+    1 | None
+    assert False, 'int or None should raise'
+except TypeError as e:
+    assert str(e) == "unsupported operand type(s) for |: 'int' and 'NoneType'", 'int or none message'
+
+try:
+    1 << 1.0
+    assert False, 'float shift should raise'
+except TypeError as e:
+    assert str(e) == "unsupported operand type(s) for <<: 'int' and 'float'", 'float shift message'
+
+try:
+    ~1.5
+    assert False, 'invert float should raise'
+except TypeError as e:
+    assert str(e) == "bad operand type for unary ~: 'float'", 'invert float message'
+
+try:
+    ~'a'
+    assert False, 'invert str should raise'
+except TypeError as e:
+    assert str(e) == "bad operand type for unary ~: 'str'", 'invert str message'
@@ -0,0 +1,87 @@
+import io
+
+# === write and getvalue ===
+buf = io.StringIO()
+assert buf.getvalue() == '', 'new stream is empty'
+assert buf.write('hello') == 5, 'write returns the number of characters'
+assert buf.write(' wörld') == 6, 'counts characters, not bytes'
+assert buf.getvalue() == 'hello wörld', 'getvalue returns everything written'
+assert buf.tell() == 11, 'position is after the last write'
+assert buf.writelines(['a', 'b']) is None, 'writelines returns None'
+assert buf.getvalue() == 'hello wörldab', 'writelines appends each string'
+
+# === initial value ===
+buf = io.StringIO('line 1\nline 2\nline 3')
+assert buf.tell() == 0, 'initial value starts at position 0'
+assert buf.readline() == 'line 1\n', 'readline includes the newline'
+assert buf.read(4) == 'line', 'read with a size'
+assert buf.read() == ' 2\nline 3', 'read without a size reads the rest'
+assert buf.read() == '', 'read at the end is empty'
+assert buf.readline() == '', 'readline at the end is empty'
+
+buf = io.StringIO('abc')
+buf.write('X')
+assert buf.getvalue() == 'Xbc', 'write at position 0 overwrites the initial value'
+
+# === readlines and iteration ===
+buf = io.StringIO('a\nb\nc')
+assert buf.readlines() == ['a\n', 'b\n', 'c'], 'readlines splits after each newline'
+assert list(io.StringIO('a\nb\n')) == ['a\n', 'b\n'], 'iteration yields lines'
+lines = []
+for line in io.StringIO('x\ny'):
+    lines.append(line)
+assert lines == ['x\n', 'y'], 'for loop over a stream'
+
+# === seek, tell, truncate ===
+buf = io.StringIO('abcdef')
+assert buf.seek(2) == 2, 'seek returns the new position'
+assert buf.read(2) == 'cd', 'read after seek'
+assert buf.truncate(3) == 3, 'truncate returns the new size'
+assert buf.getvalue() == 'abc', 'truncate cuts the text'
+assert buf.tell() == 4, 'truncate leaves the position alone'
+buf.seek(0)
+assert buf.truncate() == 0, 'truncate defaults to the position'
+assert buf.getvalue() == '', 'truncate at 0 empties the stream'
+buf = io.StringIO()
+buf.seek(3)
+buf.write('x')
+assert buf.getvalue() == '\x00\x00\x00x', 'writing past the end pads with NUL'
+
+# === close ===
+buf = io.StringIO('data')
+assert not buf.closed, 'new stream is open'
+buf.close()
+assert buf.closed, 'closed after close()'
+buf.close()
+try:
+    buf.getvalue()
+    assert False, 'getvalue on a closed stream should fail'
+except ValueError as e:
+    assert str(e) == 'I/O operation on closed file', 'closed stream message'
+try:
+    list(buf)
+    assert False, 'iterating a closed stream should fail'
+except ValueError as e:
+    assert str(e) == 'I/O operation on closed file.', 'closed stream iteration message'
+
+# === errors ===
+buf = io.StringIO()
+try:
+    buf.write(1)
+    assert False, 'write of a non-str should fail'
+except TypeError as e:
+    assert str(e) == "string argument expected, got 'int'", 'write type error message'
+try:
+    io.StringIO(1)
+    assert False, 'non-str initial value should fail'
+except TypeError as e:
+    assert str(e) == 'initial_value must be str or None, not int', 'initial value type error message'
+try:
+    buf.seek(-1)
+    assert False, 'negative seek should fail'
+except ValueError as e:
+    assert str(e) == 'Negative seek position -1', 'negative seek message'
+
+# === type ===
+assert str(type(io.StringIO())) == "<class '_io.StringIO'>", 'type repr'
+assert isinstance(io.StringIO(), io.StringIO), 'isinstance of StringIO'
@@ -0,0 +1,40 @@
+# === isinstance with tuples ===
+assert isinstance(1, (int, float)), 'int in (int, float)'
+assert isinstance(1.5, (int, float)), 'float in (int, float)'
+assert not isinstance('a', (int, float)), 'str not in (int, float)'
+assert isinstance(True, (str, int)), 'bool is an int subclass'
+assert isinstance(KeyError('k'), (ValueError, LookupError)), 'exception hierarchy in tuple'
+
+# === isinstance with unions ===
+assert isinstance(1, int | str), 'int in int | str'
+assert isinstance('a', int | str), 'str in int | str'
+assert not isinstance(1.5, int | str), 'float not in int | str'
+assert isinstance(None, int | None), 'None in int | None'
+assert not isinstance(0, str | None), 'int not in str | None'
+assert isinstance(KeyError('k'), ValueError | LookupError), 'exception hierarchy in union'
+assert isinstance([], (int | str, list)), 'union nested in tuple'
+
+# === union repr and equality ===
+assert repr(int | str) == 'int | str', 'union repr'
+assert repr(int | None) == 'int | None', 'None shown as None'
+assert repr(ValueError | KeyError) == 'ValueError | KeyError', 'exception union repr'
+assert repr((int | str) | float) == 'int | str | float', 'nested unions are flattened'
+assert repr(int | str | int) == 'int | str', 'duplicates are removed'
+assert (int | int) is int, 'single member collapses to the class'
+assert (int | str) == (str | int), 'equality ignores order'
+assert (int | str) != (int | float), 'different members are not equal'
+assert hash(int | str) == hash(str | int), 'hash ignores order'
+assert (int | None).__args__ == (int, type(None)), '__args__ holds the classes'
+assert str(type(int | str)) == "<class 'types.UnionType'>", 'type of a union'
+
+# === issubclass ===
+assert issubclass(bool, int), 'bool subclasses int'
+assert not issubclass(int, bool), 'int does not subclass bool'
+assert issubclass(int, int), 'a class subclasses itself'
+assert issubclass(KeyError, LookupError), 'exception hierarchy'
+assert not issubclass(LookupError, KeyError), 'exception hierarchy is one way'
+assert issubclass(KeyError, Exception), 'exceptions subclass Exception'
+assert not issubclass(int, Exception), 'int is not an exception'
+assert issubclass(str, (int, str)), 'tuple of classes'
+assert issubclass(IndexError, ValueError | LookupError), 'union of exceptions'
+assert not issubclass(float, int | str), 'not in union'
@@ -0,0 +1,2 @@
+issubclass(1, int)
+# Raise=TypeError('issubclass() arg 1 must be a class')
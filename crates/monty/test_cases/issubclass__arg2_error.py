@@ -0,0 +1,2 @@
+issubclass(int, 'int')
+# Raise=TypeError('issubclass() arg 2 must be a class, a tuple of classes, or a union')
@@ -282,6 +282,30 @@ lst = ['10', '2', '1', '100']
 lst.sort(key=int, reverse=True)
 assert lst == ['100', '10', '2', '1'], 'sort numeric strings by int reverse'
 
+# === Sort stability ===
+lst = ['bb', 'a', 'cc', 'd', 'ee', 'f']
+lst.sort(key=len)
+assert lst == ['a', 'd', 'f', 'bb', 'cc', 'ee'], 'sort by key keeps equal elements in order'
+
+lst = ['bb', 'a', 'cc', 'd', 'ee', 'f']
+lst.sort(key=len, reverse=True)
+assert lst == ['bb', 'cc', 'ee', 'a', 'd', 'f'], 'reverse sort keeps equal elements in order'
+
+lst = [1, 1.0, True, 0, 0.0, False]
+lst.sort()
+assert [type(x).__name__ for x in lst] == ['int', 'float', 'bool', 'int', 'float', 'bool'], 'equal numbers keep order'
+assert lst == [0, 0.0, False, 1, 1.0, True], 'mixed numeric sort'
+
+lst = [3, 1, 2]
+result = sorted(lst)
+assert result == [1, 2, 3], 'sorted returns a sorted list'
+assert lst == [3, 1, 2], 'sorted does not modify its input'
+lst.sort()
+assert lst == result, 'list.sort and sorted agree'
+
+lst = [1.0, 1, True]
+assert [type(x).__name__ for x in sorted(lst)] == ['float', 'int', 'bool'], 'sorted is stable'
+
 # === List assignment (setitem) ===
 # Basic assignment
 lst = [1, 2, 3]
@@ -8,6 +8,6 @@ TRACEBACK:
 Traceback (most recent call last):
   File "list__setitem_dict_index.py", line 5, in <module>
     lst[d] = 42
-    ~~~~~~
+    ~~~^^^
 TypeError: list indices must be integers or slices, not dict
 """
@@ -8,6 +8,6 @@ TRACEBACK:
 Traceback (most recent call last):
   File "list__setitem_huge_int_index.py", line 5, in <module>
     lst[huge] = 42
-    ~~~~~~~~~
+    ~~~^^^^^^
 IndexError: cannot fit 'int' into an index-sized integer
 """
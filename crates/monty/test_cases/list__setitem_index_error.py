@@ -5,6 +5,6 @@ TRACEBACK:
 Traceback (most recent call last):
   File "list__setitem_index_error.py", line 2, in <module>
     lst[10] = 'value'
-    ~~~~~~~
+    ~~~^^^^
 IndexError: list assignment index out of range
 """
@@ -0,0 +1,10 @@
+items = [3, 1, 2]
+
+
+def key(x):
+    items.append(x)
+    return x
+
+
+items.sort(key=key)
+# Raise=ValueError('list modified during sort')
@@ -0,0 +1,60 @@
+# === stability ===
+pairs = [('b', 1), ('a', 2), ('b', 0), ('a', 1)]
+assert sorted(pairs, key=lambda p: p[0]) == [('a', 2), ('a', 1), ('b', 1), ('b', 0)], 'stable by key'
+assert sorted(pairs, key=lambda p: p[0], reverse=True) == [('b', 1), ('b', 0), ('a', 2), ('a', 1)], 'stable reversed'
+assert sorted([1, 1.0, True], reverse=True) == [1, 1.0, True], 'equal values keep their order reversed'
+words = ['bb', 'a', 'ccc', 'dd', 'e']
+words.sort(key=len)
+assert words == ['a', 'e', 'bb', 'dd', 'ccc'], 'list.sort is stable'
+big = [(i % 7, i) for i in range(200)]
+assert sorted(big, key=lambda p: p[0]) == [(k, i) for k in range(7) for i in range(200) if i % 7 == k], 'stable long'
+assert sorted(range(100, 0, -1)) == list(range(1, 101)), 'long descending'
+
+# === sequences ===
+assert sorted([(2, 'a'), (1, 'b'), (1, 'a')]) == [(1, 'a'), (1, 'b'), (2, 'a')], 'tuples'
+assert sorted([[2], [1, 5], [1]]) == [[1], [1, 5], [2]], 'lists'
+assert sorted([(1, 'x'), (1,)]) == [(1,), (1, 'x')], 'prefix sorts first'
+assert sorted([(1, 'b'), (2, 3)]) == [(1, 'b'), (2, 3)], 'mixed later elements are not compared'
+assert sorted([b'b', b'a' * 2, bytes([97])]) == [b'a', b'aa', b'b'], 'bytes'
+
+# === mixed numbers and nan ===
+assert sorted([3, 1.5, True, 2**70, -2]) == [-2, True, 1.5, 3, 2**70], 'mixed numbers'
+assert sorted([1e30, 2**70, -1.5, -(2**70)]) == [-(2**70), -1.5, 2**70, 1e30], 'long ints and floats'
+assert 2**70 == float(2**70) and 2**70 < float('inf'), 'long int compared with float'
+nan = float('nan')
+result = sorted([3, nan, 1])
+assert len(result) == 3, 'nan does not raise'
+
+# === errors ===
+try:
+    sorted([1, 'a'])
+    assert False, 'int and str should fail'
+except TypeError as e:
+    assert str(e) == "'<' not supported between instances of 'str' and 'int'", 'int and str message'
+try:
+    sorted([(1, 2), (1, 'a')])
+    assert False, 'tuples with mixed elements should fail'
+except TypeError as e:
+    assert str(e) == "'<' not supported between instances of 'str' and 'int'", 'element types in message'
+try:
+    sorted([(1,), [1]])
+    assert False, 'tuple and list should fail'
+except TypeError as e:
+    assert str(e) == "'<' not supported between instances of 'list' and 'tuple'", 'tuple and list message'
+try:
+    sorted([None, None])
+    assert False, 'None should fail'
+except TypeError as e:
+    assert str(e) == "'<' not supported between instances of 'NoneType' and 'NoneType'", 'None message'
+try:
+    sorted([{'a': 1}, {'b': 2}])
+    assert False, 'dicts should fail'
+except TypeError as e:
+    assert str(e) == "'<' not supported between instances of 'dict' and 'dict'", 'dict message'
+lst = [2, 'x', 1]
+try:
+    lst.sort()
+    assert False, 'list.sort with mixed types should fail'
+except TypeError:
+    pass
+assert lst == [2, 'x', 1], 'failed sort leaves the list unchanged'
@@ -0,0 +1,3 @@
+lst = [3, 'a', 1]
+lst.sort()
+# Raise=TypeError("'<' not supported between instances of 'str' and 'int'")
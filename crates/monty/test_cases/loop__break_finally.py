@@ -67,3 +67,108 @@ for i in [1, 2]:
 assert result == [10, 'after-inner', 'outer-finally', 10, 'after-inner', 'outer-finally'], (
     f'inner break with outer finally: {result}'
 )
+
+# === Break in while loop runs finally ===
+result = []
+n = 0
+while True:
+    n += 1
+    try:
+        if n == 3:
+            break
+    finally:
+        result.append(n)
+assert result == [1, 2, 3], f'break in while with finally: {result}'
+
+# === Break in else block runs finally ===
+result = []
+for x in [1, 2]:
+    try:
+        result.append('try')
+    except ValueError:
+        result.append('except')
+    else:
+        result.append('else')
+        break
+    finally:
+        result.append('finally')
+assert result == ['try', 'else', 'finally'], f'break in else with finally: {result}'
+
+# === Break inside nested loop in except handler keeps the handler's exception ===
+result = []
+for x in [1, 2]:
+    try:
+        raise ValueError('outer')
+    except ValueError as e:
+        for y in [1, 2, 3]:
+            if y == 2:
+                break
+            result.append(y)
+        result.append(str(e))
+assert result == [1, 'outer', 1, 'outer'], f'break in loop nested in handler: {result}'
+
+# === Break from except handler through an outer finally ===
+result = []
+for x in [1, 2]:
+    try:
+        try:
+            raise ValueError('inner')
+        except ValueError:
+            result.append('except')
+            break
+        finally:
+            result.append('inner-finally')
+    finally:
+        result.append('outer-finally')
+assert result == ['except', 'inner-finally', 'outer-finally'], f'break through two finally: {result}'
+
+# === Break from try/finally inside an except handler ===
+result = []
+for x in [1, 2]:
+    try:
+        raise ValueError('handled')
+    except ValueError:
+        try:
+            break
+        finally:
+            result.append('finally')
+assert result == ['finally'], f'break through finally inside handler: {result}'
+
+# === Break in finally discards the pending exception ===
+result = []
+for x in [1, 2]:
+    try:
+        raise ValueError('discarded')
+    finally:
+        result.append('finally')
+        break  # type: ignore
+assert result == ['finally'], f'break in finally should run finally once: {result}'
+try:
+    raise
+except RuntimeError as e:
+    assert str(e) == 'No active exception to reraise', 'discarded exception is no longer active'
+
+# === Break in finally of a try with a handler ===
+result = []
+for x in [1, 2]:
+    try:
+        raise ValueError('handled')
+    except ValueError:
+        result.append('except')
+    finally:
+        result.append('finally')
+        break  # type: ignore
+assert result == ['except', 'finally'], f'break in finally after handler: {result}'
+
+# === Exception in finally during break propagates without rerunning finally ===
+result = []
+try:
+    for x in [1, 2]:
+        try:
+            break
+        finally:
+            result.append('finally')
+            raise KeyError('from finally')
+except KeyError:
+    result.append('caught')
+assert result == ['finally', 'caught'], f'exception in finally during break: {result}'
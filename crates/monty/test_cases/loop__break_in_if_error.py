@@ -6,6 +6,6 @@ TRACEBACK:
 Traceback (most recent call last):
   File "loop__break_in_if_error.py", line 3
     break
-    ~~~~~
+    ^^^^^
 SyntaxError: 'break' outside loop
 """
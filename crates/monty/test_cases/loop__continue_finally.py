@@ -79,3 +79,53 @@ for i in [1, 2]:
 assert result == [10, 30, 'after-inner', 'outer-finally', 10, 30, 'after-inner', 'outer-finally'], (
     f'inner continue with outer finally: {result}'
 )
+
+# === Continue in while loop runs finally ===
+result = []
+n = 0
+while n < 3:
+    n += 1
+    try:
+        if n == 2:
+            continue
+        result.append(n)
+    finally:
+        result.append('finally')
+assert result == [1, 'finally', 'finally', 3, 'finally'], f'continue in while with finally: {result}'
+
+# === Continue in else block runs finally ===
+result = []
+for x in [1, 2]:
+    try:
+        pass
+    except ValueError:
+        pass
+    else:
+        result.append(x)
+        continue
+    finally:
+        result.append('finally')
+    result.append('unreachable')
+assert result == [1, 'finally', 2, 'finally'], f'continue in else with finally: {result}'
+
+# === Continue inside nested loop in except handler keeps the handler's exception ===
+result = []
+try:
+    raise ValueError('outer')
+except ValueError as e:
+    for y in [1, 2, 3]:
+        if y == 2:
+            continue
+        result.append(y)
+    result.append(str(e))
+assert result == [1, 3, 'outer'], f'continue in loop nested in handler: {result}'
+
+# === Continue in finally discards the pending exception ===
+result = []
+for x in [1, 2]:
+    try:
+        raise ValueError('discarded')
+    finally:
+        result.append(x)
+        continue  # type: ignore
+assert result == [1, 2], f'continue in finally should run finally once: {result}'
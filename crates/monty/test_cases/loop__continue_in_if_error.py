@@ -6,6 +6,6 @@ TRACEBACK:
 Traceback (most recent call last):
   File "loop__continue_in_if_error.py", line 3
     continue
-    ~~~~~~~~
+    ^^^^^^^^
 SyntaxError: 'continue' not properly in loop
 """
@@ -0,0 +1,19 @@
+# Deleting a local that was already deleted raises UnboundLocalError
+def foo():
+    x = 1
+    del x
+    del x
+
+
+foo()
+"""
+TRACEBACK:
+Traceback (most recent call last):
+  File "name_error__del_unbound.py", line 8, in <module>
+    foo()
+    ~~~^^
+  File "name_error__del_unbound.py", line 5, in foo
+    del x
+        ^
+UnboundLocalError: cannot access local variable 'x' where it is not associated with a value
+"""
@@ -11,9 +11,9 @@ TRACEBACK:
 Traceback (most recent call last):
   File "name_error__unbound_local_func.py", line 8, in <module>
     foo()
-    ~~~~~
+    ~~~^^
   File "name_error__unbound_local_func.py", line 4, in foo
     print(x)
-          ~
+          ^
 UnboundLocalError: cannot access local variable 'x' where it is not associated with a value
 """
@@ -7,6 +7,6 @@ TRACEBACK:
 Traceback (most recent call last):
   File "name_error__unbound_local_module.py", line 3, in <module>
     print(x)
-          ~
+          ^
 NameError: name 'x' is not defined
 """
@@ -5,6 +5,6 @@ TRACEBACK:
 Traceback (most recent call last):
   File "name_error__undefined_global.py", line 2, in <module>
     unknown_func()
-    ~~~~~~~~~~~~
+    ^^^^^^^^^^^^
 NameError: name 'unknown_func' is not defined
 """
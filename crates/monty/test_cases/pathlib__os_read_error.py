@@ -7,6 +7,6 @@ TRACEBACK:
 Traceback (most recent call last):
   File "pathlib__os_read_error.py", line 4, in <module>
     Path('/nonexistent').read_text()
-    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~^^
 FileNotFoundError: [Errno 2] No such file or directory: '/nonexistent'
 """
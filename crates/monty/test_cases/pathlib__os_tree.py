@@ -0,0 +1,119 @@
+# call-external
+from pathlib import Path
+
+# === glob() ===
+assert sorted(p.name for p in Path('/virtual').glob('*.txt')) == [
+    'empty.txt',
+    'file.txt',
+    'readonly.txt',
+], 'glob matches entries of the directory'
+assert sorted(str(p) for p in Path('/virtual').glob('*/*.txt')) == ['/virtual/subdir/nested.txt'], 'glob nested pattern'
+assert sorted(str(p) for p in Path('/virtual').glob('**/file.txt')) == [
+    '/virtual/file.txt',
+    '/virtual/subdir/deep/file.txt',
+], 'glob ** matches any depth'
+assert [p.name for p in Path('/virtual').glob('data.?in')] == ['data.bin'], 'glob ? wildcard'
+assert list(Path('/virtual').glob('*.nope')) == [], 'glob without matches'
+assert list(Path('/nonexistent').glob('*')) == [], 'glob in missing directory'
+assert isinstance(list(Path('/virtual').glob('file.*'))[0], Path), 'glob returns Path objects'
+
+# === rglob() ===
+assert sorted(str(p) for p in Path('/virtual').rglob('*.txt')) == [
+    '/virtual/empty.txt',
+    '/virtual/file.txt',
+    '/virtual/readonly.txt',
+    '/virtual/subdir/deep/file.txt',
+    '/virtual/subdir/nested.txt',
+], 'rglob searches subdirectories'
+assert sorted(p.name for p in Path('/virtual/subdir').rglob('*')) == ['deep', 'file.txt', 'nested.txt'], 'rglob all'
+
+# === walk() ===
+tree = [(str(root), sorted(dirs), sorted(files)) for root, dirs, files in Path('/virtual').walk()]
+assert tree == [
+    ('/virtual', ['subdir'], ['data.bin', 'empty.txt', 'file.txt', 'readonly.txt']),
+    ('/virtual/subdir', ['deep'], ['nested.txt']),
+    ('/virtual/subdir/deep', [], ['file.txt']),
+], 'walk is top down by default'
+bottom_up = [str(root) for root, _, _ in Path('/virtual').walk(top_down=False)]
+assert bottom_up == ['/virtual/subdir/deep', '/virtual/subdir', '/virtual'], 'walk bottom up'
+root, dirs, files = list(Path('/virtual/subdir/deep').walk())[0]
+assert isinstance(root, Path), 'walk yields Path objects for directories'
+assert files == ['file.txt'], 'walk yields file names'
+
+# === copy() ===
+copied = Path('/virtual/readonly.txt').copy(Path('/virtual/copy.txt'))
+assert str(copied) == '/virtual/copy.txt', 'copy returns the target'
+assert Path('/virtual/copy.txt').read_text() == 'readonly', 'copy copies content'
+assert Path('/virtual/copy.txt').stat().st_mode & 0o777 == 0o444, 'copy keeps permissions'
+assert Path('/virtual/readonly.txt').exists(), 'copy keeps the source'
+try:
+    Path('/virtual/subdir').copy(Path('/virtual/other'))
+    assert False, 'copy of a directory should fail'
+except IsADirectoryError as e:
+    assert str(e) == "[Errno 21] Is a directory: '/virtual/subdir'", 'copy directory error'
+try:
+    Path('/nonexistent/file.txt').copy(Path('/virtual/other.txt'))
+    assert False, 'copy of a missing file should fail'
+except FileNotFoundError as e:
+    assert str(e) == "[Errno 2] No such file or directory: '/nonexistent/file.txt'", 'copy missing error'
+    assert e.errno == 2, 'host error errno'
+    assert e.strerror == 'No such file or directory', 'host error strerror'
+    assert e.filename == '/nonexistent/file.txt', 'host error filename'
+
+# === touch() ===
+Path('/virtual/touched.txt').touch()
+assert Path('/virtual/touched.txt').read_text() == '', 'touch creates an empty file'
+assert Path('/virtual/touched.txt').stat().st_mode & 0o777 == 0o644, 'touch default permissions'
+Path('/virtual/file.txt').touch()
+assert Path('/virtual/file.txt').read_text() == 'hello world\n', 'touch keeps existing content'
+try:
+    Path('/virtual/file.txt').touch(exist_ok=False)
+    assert False, 'touch with exist_ok=False should fail'
+except FileExistsError as e:
+    assert str(e) == "[Errno 17] File exists: '/virtual/file.txt'", 'touch exists error'
+try:
+    Path('/nonexistent/new.txt').touch()
+    assert False, 'touch in a missing directory should fail'
+except FileNotFoundError as e:
+    assert str(e) == "[Errno 2] No such file or directory: '/nonexistent/new.txt'", 'touch missing parent error'
+
+# === chmod() ===
+Path('/virtual/empty.txt').chmod(0o600)
+assert Path('/virtual/empty.txt').stat().st_mode == 0o100600, 'chmod changes permissions'
+try:
+    Path('/nonexistent').chmod(0o600)
+    assert False, 'chmod of a missing path should fail'
+except FileNotFoundError:
+    pass
+
+# === symlink_to(), is_symlink(), readlink(), lstat() ===
+link = Path('/virtual/link.txt')
+link.symlink_to(Path('/virtual/file.txt'))
+assert link.is_symlink(), 'is_symlink true for link'
+assert link.exists() and link.is_file(), 'link follows to its target'
+assert link.read_text() == 'hello world\n', 'read through link'
+assert str(link.readlink()) == '/virtual/file.txt', 'readlink returns target'
+assert link.stat().st_size == 12, 'stat follows the link'
+assert link.lstat().st_mode & 0o170000 == 0o120000, 'lstat describes the link itself'
+assert Path('/virtual/file.txt').lstat().st_size == 12, 'lstat of a regular file'
+assert 'link.txt' in [p.name for p in Path('/virtual').iterdir()], 'link listed in directory'
+
+dangling = Path('/virtual/dangling')
+dangling.symlink_to('/virtual/missing.txt')
+assert dangling.is_symlink(), 'dangling link is a symlink'
+assert not dangling.exists(), 'dangling link target does not exist'
+
+try:
+    Path('/virtual/file.txt').readlink()
+    assert False, 'readlink of a file should fail'
+except OSError as e:
+    assert str(e) == "[Errno 22] Invalid argument: '/virtual/file.txt'", 'readlink not a link error'
+try:
+    link.symlink_to('/virtual/data.bin')
+    assert False, 'symlink_to an existing path should fail'
+except FileExistsError as e:
+    assert str(e) == "[Errno 17] File exists: '/virtual/link.txt'", 'symlink_to exists error'
+
+link.unlink()
+assert not link.is_symlink(), 'unlink removes the link'
+assert Path('/virtual/file.txt').exists(), 'unlink of link keeps the target'
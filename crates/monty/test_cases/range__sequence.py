@@ -0,0 +1,76 @@
+# === attributes ===
+r = range(0, 10, 3)
+assert (r.start, r.stop, r.step) == (0, 10, 3), 'start, stop and step'
+assert (range(5).start, range(5).step) == (0, 1), 'defaults'
+
+# === index and count ===
+assert r.index(6) == 2 and r.index(0) == 0, 'index'
+assert r.index(6.0) == 2 and range(3).index(True) == 1, 'index of equal values'
+assert range(10, 0, -2).index(4) == 3, 'index with a negative step'
+assert r.count(6) == 1 and r.count(7) == 0 and r.count('a') == 0, 'count'
+assert r.count(3.0) == 1, 'count of an equal float'
+try:
+    r.index(7)
+    assert False, 'missing int should fail'
+except ValueError as e:
+    assert str(e) == '7 is not in range', 'index message'
+try:
+    r.index('a')
+    assert False, 'missing str should fail'
+except ValueError as e:
+    assert str(e) == 'sequence.index(x): x not in sequence', 'generic index message'
+try:
+    r.index()
+    assert False, 'index without an argument should fail'
+except TypeError as e:
+    assert str(e) == 'range.index() takes exactly one argument (0 given)', 'index arity message'
+
+# === slicing matches CPython's bounds ===
+assert repr(r[1:]) == 'range(3, 12, 3)', 'open slice'
+assert repr(range(10)[2:5:2]) == 'range(2, 5, 2)', 'stepped slice keeps the stop'
+assert repr(range(10)[::-1]) == 'range(9, -1, -1)', 'reversed slice'
+assert repr(range(10)[5:2]) == 'range(5, 2)', 'empty slice'
+assert repr(r[::-2]) == 'range(9, -3, -6)', 'negative step slice'
+assert repr(range(0, -10, -2)[1::2]) == 'range(-2, -10, -4)', 'slice of a backward range'
+assert list(range(10)[1:8:3]) == [1, 4, 7], 'slice elements'
+
+# === equality and hashing ===
+assert range(0, 1, 1) == range(0, 1, 2), 'single element ranges ignore the step'
+assert range(1, 2, -1) == range(0), 'empty ranges are equal'
+assert hash(range(0, 3, 2)) == hash(range(0, 4, 2)), 'equal ranges hash equally'
+assert hash(range(0, 1, 1)) == hash(range(0, 1, 2)), 'single element hashes'
+assert hash(range(0)) == hash(range(5, 5)), 'empty hashes'
+assert len({range(0, 3, 2), range(0, 4, 2)}) == 1, 'set of equal ranges'
+assert range(3) != [0, 1, 2] and range(3) != (0, 1, 2), 'ranges never equal other sequences'
+
+# === reversed ===
+assert list(reversed(range(1, 10, 3))) == [7, 4, 1], 'reversed stepped range'
+assert list(reversed(range(5, 0, -2))) == [1, 3, 5], 'reversed backward range'
+assert list(reversed(range(0))) == [], 'reversed empty range'
+big = reversed(range(10**15))
+assert next(big) == 10**15 - 1 and next(big) == 10**15 - 2, 'reversed without building a list'
+
+# === ranges near the i64 limits ===
+MAX = 9223372036854775807
+wide = range(-MAX - 1, MAX)
+assert wide[-1] == MAX - 1 and wide[0] == -MAX - 1, 'wide indexing'
+assert MAX - 1 in wide and MAX not in wide, 'wide containment'
+assert wide.index(0) == MAX + 1, 'wide index'
+assert list(range(MAX - 2, MAX, 5)) == [MAX - 2], 'iteration stops before overflowing'
+assert list(range(0, MAX, MAX - 1)) == [0, MAX - 1], 'large step'
+MIN = -MAX - 1
+low = range(MIN, MIN + 3)
+assert list(reversed(low)) == [MIN + 2, MIN + 1, MIN], 'reversed keeps i64 min'
+assert list(low[::-1]) == [MIN + 2, MIN + 1, MIN], 'backward slice keeps i64 min'
+high = range(MAX, MAX - 3, -1)
+assert list(reversed(high)) == [MAX - 2, MAX - 1, MAX], 'reversed keeps i64 max'
+assert list(high[::-1]) == [MAX - 2, MAX - 1, MAX], 'backward slice keeps i64 max'
+assert list(reversed(range(MIN, MAX, MAX))) == [MAX - 1, -1, MIN], 'reversed wide step'
+assert list(reversed(range(0, MIN, MIN))) == [0], 'reversed step of i64 min'
+assert list(low[1::-1]) == [MIN + 1, MIN], 'partial backward slice'
+assert list(high[2::-2]) == [MAX - 2, MAX], 'backward slice with step'
+assert len(range(MIN, MAX, 2**62)[::4]) == 1, 'slice with step past i64'
+assert list(range(MIN, MAX, 2**62)[::4]) == [MIN], 'slice with step past i64 elements'
+assert list(range(MAX, MIN, -(2**62))[::4]) == [MAX], 'backward slice with step past i64'
+assert list(wide[::-(2**62)]) == [MAX - 1, MAX - 1 - 2**62, -2, -2 - 2**62], 'wide backward slice'
+assert len(low[5:]) == 0 and len(high[5::-1]) == 3, 'slices past the end'
@@ -0,0 +1,22 @@
+# Tests reference counting correctness for star unpacking in literals
+
+# === Heap values copied into new containers ===
+# All heap objects must be directly referenced by variables for strict matching
+inner = [1, 2]
+source = [inner, 'x']
+mapping = {'k': inner}
+combined = [*source, *source]
+combined_tuple = (*source, inner)
+merged = {**mapping, **mapping}
+assert combined[0] is inner, 'list unpack shares items'
+assert merged['k'] is inner, 'dict unpack shares values'
+
+# inner: 8 refs (inner var, source[0], mapping['k'], combined[0], combined[2],
+#                combined_tuple[0], combined_tuple[2], merged['k'])
+# source: 1 ref
+# mapping: 1 ref
+# combined: 1 ref
+# combined_tuple: 1 ref
+# merged: 2 refs (merged var, final expr)
+merged
+# ref-counts={'inner': 8, 'source': 1, 'mapping': 1, 'combined': 1, 'combined_tuple': 1, 'merged': 2}
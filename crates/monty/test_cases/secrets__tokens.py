@@ -0,0 +1,66 @@
+import secrets
+
+# === token_bytes ===
+token = secrets.token_bytes()
+assert type(token) is bytes, 'token_bytes returns bytes'
+assert len(token) == 32, 'default token size'
+assert len(secrets.token_bytes(5)) == 5, 'explicit token size'
+assert len(secrets.token_bytes(nbytes=3)) == 3, 'nbytes keyword'
+assert secrets.token_bytes(0) == b'', 'empty token'
+assert secrets.token_bytes() != secrets.token_bytes(), 'tokens differ'
+
+# === token_hex ===
+token = secrets.token_hex(16)
+assert len(token) == 32, 'two hex digits per byte'
+assert all(c in '0123456789abcdef' for c in token), 'lowercase hex digits'
+assert len(secrets.token_hex()) == 64, 'default hex token size'
+assert len(secrets.token_hex(None)) == 64, 'None means the default size'
+
+# === token_urlsafe ===
+token = secrets.token_urlsafe(16)
+assert len(token) == 22, 'unpadded base64 length'
+alphabet = 'ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_'
+assert all(c in alphabet for c in token), 'url-safe alphabet'
+assert len(secrets.token_urlsafe(3)) == 4, 'no padding needed'
+assert len(secrets.token_urlsafe(1)) == 2, 'padding stripped'
+
+# === choice ===
+assert secrets.choice('abc') in 'abc', 'choice from str'
+assert secrets.choice([1, 2, 3]) in [1, 2, 3], 'choice from list'
+assert secrets.choice((7,)) == 7, 'single element'
+assert secrets.choice(range(10, 20)) in range(10, 20), 'choice from range'
+assert len({secrets.choice(range(1000)) for _ in range(20)}) > 1, 'choice varies'
+
+# === randbelow ===
+assert secrets.randbelow(1) == 0, 'only value below 1'
+values = [secrets.randbelow(10) for _ in range(200)]
+assert all(0 <= v < 10 for v in values), 'values in range'
+assert len(set(values)) > 1, 'randbelow varies'
+assert 0 <= secrets.randbelow(2**62) < 2**62, 'large bound'
+
+# === errors ===
+try:
+    secrets.token_bytes(-1)
+    assert False, 'negative size should fail'
+except ValueError as e:
+    assert str(e) == 'negative argument not allowed', 'negative size message'
+try:
+    secrets.choice([])
+    assert False, 'empty sequence should fail'
+except IndexError as e:
+    assert str(e) == 'Cannot choose from an empty sequence', 'empty sequence message'
+try:
+    secrets.randbelow(0)
+    assert False, 'zero bound should fail'
+except ValueError as e:
+    assert str(e) == 'Upper bound must be positive.', 'zero bound message'
+try:
+    secrets.randbelow(-5)
+    assert False, 'negative bound should fail'
+except ValueError as e:
+    assert str(e) == 'Upper bound must be positive.', 'negative bound message'
+try:
+    secrets.token_hex('a')
+    assert False, 'str size should fail'
+except TypeError as e:
+    assert str(e) == "'str' object cannot be interpreted as an integer", 'str size message'
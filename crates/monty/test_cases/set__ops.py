@@ -104,3 +104,50 @@ assert len(s) == 3, 'set literal deduplication'
 x = 5
 s = {x, x + 1, x + 2}
 assert len(s) == 3, 'set literal with expressions'
+
+# === Operators ===
+a = {1, 2, 3}
+b = {3, 4}
+assert a | b == {1, 2, 3, 4}, 'set | set'
+assert a & b == {3}, 'set & set'
+assert a - b == {1, 2}, 'set - set'
+assert a ^ b == {1, 2, 4}, 'set ^ set'
+assert a == {1, 2, 3}, 'operators do not mutate the left operand'
+assert type(a | frozenset(b)) == set, 'set | frozenset is a set'
+
+try:
+    a | [4]
+    assert False, 'set | list should raise'
+except TypeError as e:
+    assert str(e) == "unsupported operand type(s) for |: 'set' and 'list'", 'set | list message'
+
+try:
+    a - [4]
+    assert False, 'set - list should raise'
+except TypeError as e:
+    assert str(e) == "unsupported operand type(s) for -: 'set' and 'list'", 'set - list message'
+
+# === In-place operators ===
+s = {1, 2}
+alias = s
+s |= {3}
+assert alias == {1, 2, 3}, '|= mutates in place'
+s &= {2, 3, 4}
+assert alias == {2, 3}, '&= mutates in place'
+s -= frozenset({2})
+assert alias == {3}, '-= mutates in place'
+s ^= {3, 5}
+assert alias == {5}, '^= mutates in place'
+assert s is alias, 'in-place operators keep identity'
+
+# === Comparisons ===
+assert {1} < {1, 2}, 'proper subset'
+assert not {1, 2} < {1, 2}, 'equal sets are not proper subsets'
+assert {1, 2} <= {1, 2}, 'subset or equal'
+assert {1, 2} > {2}, 'proper superset'
+assert {1, 2} >= {1, 2}, 'superset or equal'
+assert not {1} <= {2}, 'disjoint sets are not subsets'
+assert not {1} >= {2}, 'disjoint sets are not supersets'
+assert set() < frozenset([1]), 'set compares with frozenset'
+assert {1, 2} == frozenset([1, 2]), 'set equals frozenset with same elements'
+assert {1} != frozenset([2]), 'set differs from frozenset with other elements'
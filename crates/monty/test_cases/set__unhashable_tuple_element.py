@@ -0,0 +1,2 @@
+{(1, {'a': 2})}
+# Raise=TypeError("cannot use 'tuple' as a set element (unhashable type: 'dict')")
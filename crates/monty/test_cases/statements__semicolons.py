@@ -0,0 +1,60 @@
+# === Simple statements separated by semicolons ===
+x = 1; y = 2
+assert (x, y) == (1, 2), 'assignments on one line'
+
+a = 1; a += 2; a *= 3
+assert a == 9, 'statements run in order'
+
+items = []; items.append(1); items.append(2);
+assert items == [1, 2], 'trailing semicolon'
+
+del x; y = 'deleted'
+assert y == 'deleted', 'del before another statement'
+
+# === Single-line compound statements ===
+big = False
+if a > 5: big = True
+assert big, 'single-line if body'
+
+if a < 5: small = True
+else: small = False
+assert not small, 'single-line else body'
+
+count = 0
+for i in range(4): count += i
+assert count == 6, 'single-line for body'
+
+n = 3
+while n: n -= 1
+assert n == 0, 'single-line while body'
+
+p = q = 0
+if True: p = 1; q = 2
+assert (p, q) == (1, 2), 'semicolons in a single-line body'
+
+seen = []
+for i in range(3): seen.append(i); seen.append(-i)
+assert seen == [0, 0, 1, -1, 2, -2], 'every body statement runs per iteration'
+
+
+def double(v): return v * 2
+
+
+assert double(4) == 8, 'single-line def'
+
+
+def first_positive(values):
+    for v in values:
+        if v > 0: return v
+    return None
+
+
+assert first_positive([-1, 0, 3, 4]) == 3, 'single-line if returning from a loop'
+
+try: z = 1 / 0
+except ZeroDivisionError: z = None
+assert z is None, 'single-line try and except'
+
+# the last expression is still the result
+total = 1; total + 41
+# Return=42
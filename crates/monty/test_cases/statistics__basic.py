@@ -0,0 +1,100 @@
+import statistics
+
+# === mean ===
+assert statistics.mean([1, 2, 3, 4, 4]) == 2.8, 'mean of ints'
+assert statistics.mean([1, 2, 3]) == 2, 'whole mean of ints'
+assert type(statistics.mean([1, 2, 3])) is int, 'whole mean of ints is an int'
+assert type(statistics.mean([1.0, 2.0, 3.0])) is float, 'mean of floats is a float'
+assert statistics.mean([0.1, 0.2, 0.3]) == 0.2, 'mean is exact before rounding'
+assert statistics.mean([1e300, 1e300]) == 1e300, 'no intermediate overflow'
+assert statistics.mean([True, False]) == 0.5, 'bools are ints'
+assert statistics.mean(x for x in [2, 4]) == 3, 'any iterable'
+assert statistics.mean([-1, -2]) == -1.5, 'negative mean'
+assert statistics.mean([10**30, 10**30 + 2]) == 10**30 + 1, 'big int mean'
+assert statistics.mean([1, float('inf')]) == float('inf'), 'infinite mean'
+
+# === median ===
+assert statistics.median([3, 1, 2]) == 2, 'odd median'
+assert statistics.median([4, 1, 3, 2]) == 2.5, 'even median'
+assert statistics.median([1, 3]) == 2.0, 'even median is a float'
+assert type(statistics.median([1, 3])) is float, 'even median divides'
+assert statistics.median(['b', 'c', 'a']) == 'b', 'median of strings'
+assert statistics.median([1.5]) == 1.5, 'single value'
+
+# === mode ===
+assert statistics.mode([1, 1, 2, 3, 3, 3, 3, 4]) == 3, 'mode of ints'
+assert statistics.mode(['red', 'blue', 'blue', 'red', 'green', 'red']) == 'red', 'mode of strings'
+assert statistics.mode(['red', 'red', 'green', 'blue', 'blue']) == 'red', 'first mode wins a tie'
+assert statistics.mode([(1, 2), (1, 2), (3,)]) == (1, 2), 'mode of tuples'
+assert statistics.mode([1, 1.0, True, 2, 2]) == 1, 'equal numbers count together'
+
+# === variance and stdev ===
+assert statistics.variance([1, 2, 3]) == 1, 'whole variance'
+assert type(statistics.variance([1, 2, 3])) is int, 'whole variance of ints is an int'
+assert statistics.variance([1, 2, 3, 4]) == 1.6666666666666667, 'fractional variance'
+assert statistics.variance([0.1, 0.2, 0.3]) == 0.009999999999999998, 'variance of floats'
+assert statistics.variance([True, False, True]) == 0.3333333333333333, 'variance of bools'
+assert statistics.variance([2.75, 1.75, 1.25, 0.25, 0.5, 1.25, 3.5]) == 1.3720238095238095, 'docs example'
+assert statistics.stdev([1, 2, 3]) == 1.0, 'stdev is a float'
+assert statistics.stdev([1.5, 2.5, 2.5, 2.75, 3.25, 4.75]) == 1.0810874155219827, 'stdev docs example'
+assert statistics.stdev([1, 2, 3, 4]) == 1.2909944487358056, 'stdev is correctly rounded'
+assert statistics.stdev([5, 5, 5]) == 0.0, 'zero stdev'
+assert statistics.stdev([10**200, 0]) == 7.071067811865475e199, 'stdev of big ints'
+
+# === quantiles ===
+assert statistics.quantiles([1, 2, 3, 4, 5]) == [1.5, 3.0, 4.5], 'quartiles'
+data = [105, 129, 87, 86, 111, 111, 89, 81, 108, 92, 110, 100, 75, 105, 103, 109, 76, 119, 99, 91, 103, 129]
+assert statistics.quantiles(data, n=10) == [77.5, 86.6, 90.8, 99.2, 103.0, 105.0, 109.1, 111.0, 126.0], 'deciles'
+q = statistics.quantiles([1.5, 2, 3], n=3, method='inclusive')
+assert q == [1.8333333333333333, 2.3333333333333335], 'inclusive'
+assert statistics.quantiles([1, 2], n=8) == [0.375, 0.75, 1.125, 1.5, 1.875, 2.25, 2.625], 'extrapolated'
+assert statistics.quantiles([7], n=3) == [7, 7], 'single value is repeated'
+assert statistics.quantiles([3, 1, 2], n=1) == [], 'one interval has no cut points'
+
+# === errors ===
+errors = [
+    (lambda: statistics.mean([]), 'mean requires at least one data point'),
+    (lambda: statistics.median([]), 'no median for empty data'),
+    (lambda: statistics.mode([]), 'no mode for empty data'),
+    (lambda: statistics.variance([1]), 'variance requires at least two data points'),
+    (lambda: statistics.stdev([1]), 'stdev requires at least two data points'),
+    (lambda: statistics.quantiles([1, 2], n=0), 'n must be at least 1'),
+    (lambda: statistics.quantiles([]), 'must have at least one data point'),
+]
+for f, msg in errors:
+    try:
+        f()
+        assert False, 'should raise StatisticsError: ' + msg
+    except statistics.StatisticsError as e:
+        assert str(e) == msg, 'StatisticsError message: ' + msg
+try:
+    statistics.mean([])
+except ValueError:
+    pass
+else:
+    assert False, 'StatisticsError is a ValueError'
+try:
+    statistics.mean([1, 'a'])
+    assert False, 'mean of a str should fail'
+except TypeError as e:
+    assert str(e) == "can't convert type 'str' to numerator/denominator", 'mean type error message'
+try:
+    statistics.quantiles([1, 2], method='middle')
+    assert False, 'unknown method should fail'
+except ValueError as e:
+    assert str(e) == "Unknown method: 'middle'", 'unknown method message'
+try:
+    statistics.median([1, 'a'])
+    assert False, 'unorderable data should fail'
+except TypeError as e:
+    assert str(e) == "'<' not supported between instances of 'str' and 'int'", 'median comparison message'
+try:
+    statistics.mode([[1], [1]])
+    assert False, 'unhashable data should fail'
+except TypeError as e:
+    assert str(e) == "unhashable type: 'list'", 'mode unhashable message'
+try:
+    statistics.mean(1)
+    assert False, 'non-iterable data should fail'
+except TypeError as e:
+    assert str(e) == "'int' object is not iterable", 'non-iterable message'
@@ -0,0 +1,2 @@
+x = 'ab\x1g'
+# Raise=SyntaxError("(unicode error) 'unicodeescape' codec can't decode bytes in position 2-4: truncated \\xXX escape")
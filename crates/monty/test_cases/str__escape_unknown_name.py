@@ -0,0 +1,2 @@
+x = 'caf\N{LATIN SMALL LETTER E WITH ACUTE}\N{NOT A REAL NAME}'
+# Raise=SyntaxError("(unicode error) 'unicodeescape' codec can't decode bytes in position 38-56: unknown Unicode character name")
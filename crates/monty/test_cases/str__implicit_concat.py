@@ -0,0 +1,42 @@
+# === plain literals ===
+assert 'abc' 'def' == 'abcdef', 'two literals'
+assert 'a' "b" '''c''' == 'abc', 'mixed quotes'
+s = ('one '
+     'two '
+     'three')
+assert s == 'one two three', 'literals across lines'
+assert r'\d' '\n' == '\\d\n', 'raw and escaped'
+assert '\x41' r'\x41' == 'A\\x41', 'escaped and raw'
+assert 'a' '' 'b' == 'ab', 'empty literal in the middle'
+assert len('ab' 'cd') == 4, 'length of the merged literal'
+assert 'é' 'ü' == 'éü', 'non-ascii literals'
+assert b'ab' b'cd' == b'abcd', 'bytes literals'
+assert b'\x00' rb'\x00' == b'\x00\\x00', 'raw bytes'
+assert ['a' 'b', 'c'] == ['ab', 'c'], 'in a list'
+assert {'k' 'ey': 1}['key'] == 1, 'dict key'
+
+
+def f(x):
+    return x
+
+
+assert f('a' 'b') == 'ab', 'call argument'
+
+# === f-strings ===
+x = 5
+assert f'a{x}' 'b' == 'a5b', 'f-string then literal'
+assert 'a' f'{x}b' == 'a5b', 'literal then f-string'
+assert f'{x}' f'{x}' == '55', 'two f-strings'
+assert f'a' 'b' == 'ab', 'f-string without interpolations'
+assert f'{x}' '{x}' == '5{x}', 'braces in a plain literal are not interpolated'
+assert f'{x:>3}' '|' f'{x!r}' == '  5|5', 'format spec and conversion'
+assert rf'\d{x}' '\n' == '\\d5\n', 'raw f-string'
+assert f'{x=}' ' done' == 'x=5 done', 'debug expression'
+assert f'' '' == '', 'empty f-string'
+name = 'world'
+msg = ('hello '
+       f'{name}'
+       '!')
+assert msg == 'hello world!', 'f-string across lines'
+assert f'''{x}
+''' 'y' == '5\ny', 'triple-quoted f-string'
@@ -0,0 +1,2 @@
+x = b'a' 'b'
+# Raise=SyntaxError('cannot mix bytes and nonbytes literals')
@@ -0,0 +1,87 @@
+# === Simple escapes ===
+assert '\a\b\f\n\r\t\v' == chr(7) + chr(8) + chr(12) + chr(10) + chr(13) + chr(9) + chr(11), 'single char escapes'
+assert '\\' == chr(92), 'escaped backslash'
+assert '\'' == "'", 'escaped single quote'
+assert "\"" == '"', 'escaped double quote'
+assert len('\0') == 1 and ord('\0') == 0, 'null escape'
+
+# === Octal escapes ===
+assert '\101' == 'A', 'three digit octal'
+assert '\7' == chr(7), 'one digit octal'
+assert '\1012' == 'A2', 'octal stops after three digits'
+assert '\0a' == chr(0) + 'a', 'octal stops at non octal digit'
+
+# === Hex and unicode escapes ===
+assert '\x41\x61' == 'Aa', 'hex escapes'
+assert '\xe9' == 'é', 'hex escape above ascii'
+assert '\u00e9' == 'é', 'four digit unicode escape'
+assert '\U0001F600' == '😀', 'eight digit unicode escape'
+assert '\u20AC' == '€', 'uppercase hex digits'
+
+# === Named unicode escapes ===
+assert '\N{LATIN SMALL LETTER A}' == 'a', 'named escape'
+assert '\N{latin small letter e with acute}' == 'é', 'named escape is case insensitive'
+assert '\N{EURO SIGN}5' == '€5', 'named escape followed by text'
+
+# === Raw strings ===
+assert r'\n' == '\\n', 'raw string keeps backslash'
+assert R'\x41' == '\\x41', 'uppercase raw prefix'
+assert r'\'' == "\\'", 'raw string escaped quote keeps backslash'
+assert len(r'\N{EURO SIGN}') == 13, 'raw string does not decode named escapes'
+
+# === Bytes literals ===
+assert b'\x41\x00' == bytes([65, 0]), 'bytes hex escapes'
+assert b'\101' == b'A', 'bytes octal escape'
+assert b'\n\t' == bytes([10, 9]), 'bytes simple escapes'
+assert len(rb'\u00e9') == 6, 'raw bytes keep unicode escapes'
+
+# === Raw bytes prefix combinations ===
+assert rb'\x41' == b'\\x41', 'rb prefix'
+assert br'\x41' == b'\\x41', 'br prefix'
+assert Rb'\n' == b'\\n', 'Rb prefix'
+assert bR'\n' == b'\\n', 'bR prefix'
+assert RB'\t' == b'\\t', 'RB prefix'
+
+# === Unicode prefix ===
+assert u'\x41' == 'A', 'u prefix decodes escapes'
+assert U'abc' == 'abc', 'uppercase u prefix'
+
+# === F-string prefix combinations ===
+n = 5
+assert f'\x41{n}' == 'A5', 'f-string decodes escapes'
+assert rf'\x41{n}' == '\\x415', 'rf prefix'
+assert fr'\n{n}' == '\\n5', 'fr prefix'
+assert Rf'\t{n}' == '\\t5', 'Rf prefix'
+assert F'{n}\N{EURO SIGN}' == '5€', 'F prefix with named escape'
+assert f'{{\x41}}' == '{A}', 'escapes next to doubled braces'
+
+# === Line continuations ===
+s = 'abc\
+def'
+assert s == 'abcdef', 'backslash newline is removed'
+s = r'abc\
+def'
+assert s == 'abc\\\ndef', 'raw string keeps backslash newline'
+s = b'ab\
+cd'
+assert s == b'abcd', 'bytes backslash newline is removed'
+
+# === Triple-quoted strings ===
+s = """a
+b"""
+assert s == 'a\nb', 'triple quoted keeps newline'
+s = '''it's "quoted"'''
+assert s == 'it\'s "quoted"', 'triple quoted allows quotes'
+s = """\
+first
+second\
+"""
+assert s == 'first\nsecond', 'triple quoted line continuations'
+s = '''\x41\tB'''
+assert s == 'A\tB', 'triple quoted escapes'
+s = r'''a\nb
+c'''
+assert s == 'a\\nb\nc', 'raw triple quoted'
+s = b'''x
+y'''
+assert s == b'x\ny', 'bytes triple quoted'
@@ -0,0 +1,239 @@
+# === basic conversions ===
+name = 'apples'
+n = 3
+assert '%s: %d' % (name, n) == 'apples: 3', 'tuple of arguments'
+assert 'n=%d' % n == 'n=3', 'single argument'
+assert '%s' % [1, 2] == '[1, 2]', 'single non-tuple argument'
+assert '%s' % ((1, 2),) == '(1, 2)', 'tuple wrapped in a tuple'
+assert '%s %s' % (True, None) == 'True None', 'str of other values'
+assert '%r %r' % ('a', 1) == "'a' 1", 'repr'
+assert '%a' % 'é' == "'\\xe9'", 'ascii'
+assert '%s' % 'é' == 'é', 'non-ascii str'
+assert '100%%' % () == '100%', 'literal percent'
+assert '%.2f%%' % 12.345 == '12.35%', 'percent after a conversion'
+assert 'x' % () == 'x', 'no conversions and no arguments'
+n += 1
+msg = 'count: %d'
+msg %= n
+assert msg == 'count: 4', 'augmented assignment'
+
+# === integers ===
+assert '%d %i %u' % (1, -2, 3) == '1 -2 3', 'decimal'
+assert '%o %#o' % (8, 8) == '10 0o10', 'octal'
+assert '%x %#x %X %#X' % (255, 255, 255, 255) == 'ff 0xff FF 0XFF', 'hex'
+assert '%d' % 2**70 == '1180591620717411303424', 'big int'
+assert '%x' % -(2**70) == '-400000000000000000', 'negative big hex'
+assert '%d' % True == '1', 'bool'
+assert '%d %i' % (3.9, -3.9) == '3 -3', 'floats truncate'
+assert '%5d|%-5d|%05d' % (42, 42, 42) == '   42|42   |00042', 'width'
+assert '%+d|% d|%+ d|% 5d' % (3, 3, 3, -3) == '+3| 3|+3|   -3', 'sign flags'
+assert '%-05d|' % 3 == '3    |', 'left alignment wins over zero padding'
+assert '%.3d|%.3d' % (5, -5) == '005|-005', 'precision is the minimum digits'
+assert '%05.3d' % 5 == '00005', 'zero padding with precision'
+assert '%#5.3x' % 5 == '0x005', 'prefix with precision'
+assert '%05x' % -255 == '-00ff', 'zero padding after the sign'
+assert '%+.3x' % 5 == '+005', 'plus with precision'
+assert '%#x %#o' % (0, 0) == '0x0 0o0', 'prefix on zero'
+assert '%ld %hd' % (1, 2) == '1 2', 'length modifiers are ignored'
+
+# === floats ===
+assert '%f' % 3.14159 == '3.141590', 'default precision'
+assert '%.2f' % 3.14159 == '3.14', 'precision'
+assert '%e %E' % (12345.678, 0.00012) == '1.234568e+04 1.200000E-04', 'exponent'
+assert '%g %G' % (1e20, 1e-20) == '1e+20 1E-20', 'general'
+assert '%10.4g|' % 3.14159 == '     3.142|', 'general with width'
+assert '%#g' % 1.0 == '1.00000', 'alternate general keeps zeros'
+assert '%.0f %#.0f' % (2.5, 2.5) == '2 2.', 'alternate keeps the point'
+assert '%010.3f' % -3.14159 == '-00003.142', 'zero padding after the sign'
+assert '%-+8.2f|' % 3.14159 == '+3.14   |', 'left aligned with sign'
+assert '%08.2e' % -1.5 == '-1.50e+00', 'exponent wider than the width'
+assert '%F %f' % (float('inf'), float('-inf')) == 'INF -inf', 'infinities'
+assert '%010f|%-010f|%+f' % (float('inf'), float('inf'), float('nan')) == '0000000inf|inf       |+nan', 'special'
+assert '%f' % 2 == '2.000000', 'int as a float'
+assert '%f' % 2**70 == '1180591620717411303424.000000', 'big int as a float'
+assert '%.0e' % 12345 == '1e+04', 'int in exponent form'
+assert '%5.1e' % 0 == '0.0e+00', 'zero'
+
+# === strings ===
+assert '%5s|%-5s|%.2s|%5.1s|' % ('ab', 'cd', 'xyz', 'q') == '   ab|cd   |xy|    q|', 'width and precision'
+assert '%.3s' % 12345 == '123', 'precision truncates str()'
+assert '%05s' % 'a' == '    a', 'zero flag is ignored for strings'
+assert '%3s|' % 'éé' == ' éé|', 'width counts characters'
+assert '%c%c' % ('é', 233) == 'éé', 'char'
+assert '%c' % True == '\x01', 'char from a bool'
+
+# === star width and precision ===
+assert '%*d|%-*.*f|' % (5, 1, 8, 2, 3.14159) == '    1|3.14    |', 'star'
+assert '%*s|' % (-5, 'a') == 'a    |', 'negative star width left aligns'
+assert '%.*s|' % (-1, 'abc') == '|', 'negative star precision'
+assert '%0*d' % (5, 3) == '00003', 'zero padded star'
+
+# === mappings ===
+assert '%(a)s %(b)d' % {'a': 'x', 'b': 2} == 'x 2', 'mapping keys'
+assert '%(a)s %(a)r' % {'a': 'q'} == "q 'q'", 'repeated key'
+assert '%(a)s' % {'a': 1, 'b': 2} == '1', 'unused keys are fine'
+assert '%(a)05.1f' % {'a': 2.25} == '002.2', 'key with a spec'
+assert '%((a))s' % {'(a)': 1} == '1', 'nested parentheses in a key'
+assert '%s %(a)s' % {'a': 1} == "{'a': 1} 1", 'whole mapping then a key'
+assert 'x' % {'a': 1} == 'x', 'unused mapping'
+assert 'x' % [1] == 'x', 'lists count as mappings'
+
+# === errors ===
+try:
+    '%d' % 'x'
+    assert False, '%d with a str should fail'
+except TypeError as e:
+    assert str(e) == '%d format: a real number is required, not str', '%d message'
+try:
+    '%i' % [1]
+    assert False, '%i with a list should fail'
+except TypeError as e:
+    assert str(e) == '%i format: a real number is required, not list', '%i message'
+try:
+    '%x' % 1.0
+    assert False, '%x with a float should fail'
+except TypeError as e:
+    assert str(e) == '%x format: an integer is required, not float', '%x message'
+try:
+    '%f' % 'x'
+    assert False, '%f with a str should fail'
+except TypeError as e:
+    assert str(e) == 'must be real number, not str', '%f message'
+try:
+    '%d' % float('nan')
+    assert False, '%d with nan should fail'
+except ValueError as e:
+    assert str(e) == 'cannot convert float NaN to integer', 'nan message'
+try:
+    '%d' % float('inf')
+    assert False, '%d with inf should fail'
+except OverflowError as e:
+    assert str(e) == 'cannot convert float infinity to integer', 'inf message'
+try:
+    '%s %s' % (1,)
+    assert False, 'too few arguments should fail'
+except TypeError as e:
+    assert str(e) == 'not enough arguments for format string', 'too few message'
+try:
+    '%s' % ()
+    assert False, 'empty tuple should fail'
+except TypeError as e:
+    assert str(e) == 'not enough arguments for format string', 'empty tuple message'
+try:
+    '%s' % (1, 2)
+    assert False, 'too many arguments should fail'
+except TypeError as e:
+    assert str(e) == 'not all arguments converted during string formatting', 'too many message'
+try:
+    'x' % 'y'
+    assert False, 'unused str argument should fail'
+except TypeError as e:
+    assert str(e) == 'not all arguments converted during string formatting', 'unused argument message'
+try:
+    '%' % ()
+    assert False, 'trailing percent should fail'
+except ValueError as e:
+    assert str(e) == 'incomplete format', 'incomplete message'
+try:
+    '%5' % 1
+    assert False, 'missing conversion should fail'
+except ValueError as e:
+    assert str(e) == 'incomplete format', 'missing conversion message'
+try:
+    'aé%y' % 1
+    assert False, 'unknown conversion should fail'
+except ValueError as e:
+    assert str(e) == "unsupported format character 'y' (0x79) at index 3", 'unknown conversion message'
+try:
+    'a%é' % 1
+    assert False, 'non-ascii conversion should fail'
+except ValueError as e:
+    assert str(e) == "unsupported format character '?' (0xe9) at index 2", 'non-ascii conversion message'
+try:
+    '%5%' % (1,)
+    assert False, 'percent with a width should fail'
+except ValueError as e:
+    assert str(e) == "unsupported format character '%' (0x25) at index 2", 'percent with a width message'
+try:
+    '%(a' % {'a': 1}
+    assert False, 'unclosed key should fail'
+except ValueError as e:
+    assert str(e) == 'incomplete format key', 'unclosed key message'
+try:
+    '%(a)s' % 5
+    assert False, 'key without a mapping should fail'
+except TypeError as e:
+    assert str(e) == 'format requires a mapping', 'mapping message'
+try:
+    '%(b)s' % {'a': 1}
+    assert False, 'missing key should fail'
+except KeyError as e:
+    assert str(e) == "'b'", 'missing key message'
+try:
+    '%(a)s %s' % {'a': 1}
+    assert False, 'positional after a key should fail'
+except TypeError as e:
+    assert str(e) == 'not enough arguments for format string', 'positional after a key message'
+try:
+    '%*d' % ('x', 1)
+    assert False, 'non-int star should fail'
+except TypeError as e:
+    assert str(e) == '* wants int', 'star message'
+try:
+    '%c' % 'ab'
+    assert False, 'long str for %c should fail'
+except TypeError as e:
+    assert str(e) == '%c requires int or char', 'char message'
+try:
+    '%c' % -1
+    assert False, 'negative %c should fail'
+except OverflowError as e:
+    assert str(e) == '%c arg not in range(0x110000)', 'char range message'
+try:
+    '%b' % 1
+    assert False, '%b on a str should fail'
+except ValueError as e:
+    assert str(e) == "unsupported format character 'b' (0x62) at index 1", '%b message'
+
+# === bytes ===
+assert b'%s %d' % (b'x', 5) == b'x 5', 'bytes'
+assert b'%b|%5b|%-4c|' % (b'a', b'ab', 65) == b'a|   ab|A   |', 'bytes width'
+assert b'%c%c' % (b'a', 98) == b'ab', 'bytes char'
+assert b'%r %a' % ('\xe9', 1) == b"'\\xe9' 1", 'bytes repr is ascii'
+assert b'%x|%5.2f' % (255, 2.5) == b'ff| 2.50', 'bytes numbers'
+assert b'%(k)s' % {b'k': b'v'} == b'v', 'bytes mapping keys'
+assert b'%.1s' % b'xyz' == b'x', 'bytes precision'
+try:
+    b'%s' % 'x'
+    assert False, 'str into bytes should fail'
+except TypeError as e:
+    expected = "%b requires a bytes-like object, or an object that implements __bytes__, not 'str'"
+    assert str(e) == expected, 'bytes %s message'
+try:
+    b'%c' % 256
+    assert False, 'large bytes %c should fail'
+except OverflowError as e:
+    assert str(e) == '%c arg not in range(256)', 'bytes char range message'
+try:
+    b'%c' % 'a'
+    assert False, 'str for bytes %c should fail'
+except TypeError as e:
+    assert str(e) == '%c requires an integer in range(256) or a single byte', 'bytes char message'
+try:
+    b'x' % b'y'
+    assert False, 'unused bytes argument should fail'
+except TypeError as e:
+    assert str(e) == 'not all arguments converted during bytes formatting', 'bytes unused message'
+try:
+    b'%(k)s' % {'k': b'v'}
+    assert False, 'str key for a bytes format should fail'
+except KeyError:
+    pass
+
+# === other operands ===
+try:
+    [1] % 2
+    assert False, 'list % int should fail'
+except TypeError as e:
+    assert str(e) == "unsupported operand type(s) for %: 'list' and 'int'", 'list message'
+assert 7 % 3 == 1, 'int modulo is unchanged'
@@ -0,0 +1,134 @@
+# === Case mapping ===
+assert 'ÀÉÎÕÜ'.lower() == 'àéîõü', 'lower latin accents'
+assert 'àéîõü'.upper() == 'ÀÉÎÕÜ', 'upper latin accents'
+assert 'ПРИВЕТ мир'.lower() == 'привет мир', 'lower cyrillic'
+assert 'straße'.upper() == 'STRASSE', 'upper expands sharp s'
+assert 'ﬁ'.upper() == 'FI', 'upper expands ligature'
+assert 'İ'.lower() == 'i\u0307', 'lower dotted capital i keeps the dot'
+assert len('İ'.lower()) == 2, 'lower dotted capital i is two characters'
+
+# === Final sigma ===
+assert 'ΟΔΟΣ'.lower() == 'οδος', 'lower final sigma'
+assert 'ΟΔΟΣ ΟΔΟΣ'.lower() == 'οδος οδος', 'lower final sigma per word'
+assert 'Σ'.lower() == 'σ', 'lower lone sigma is not final'
+assert 'ΣΑ'.lower() == 'σα', 'lower initial sigma'
+assert "ΟΔΟΣ'".lower() == "οδος'", 'lower final sigma before case-ignorable'
+assert "ΑΣ'Α".lower() == "ασ'α", 'lower medial sigma across case-ignorable'
+assert 'ΟΔΟΣ'.capitalize() == 'Οδος', 'capitalize final sigma'
+assert 'ΟΔΟΣ'.title() == 'Οδος', 'title final sigma'
+assert 'οδοΣ'.swapcase() == 'ΟΔΟς', 'swapcase final sigma'
+
+# === Casefold ===
+assert 'straße'.casefold() == 'strasse', 'casefold sharp s'
+assert 'Straße'.casefold() == 'STRASSE'.casefold(), 'casefold caseless match'
+assert 'ς'.casefold() == 'σ', 'casefold final sigma'
+assert 'ﬁ'.casefold() == 'fi', 'casefold ligature'
+assert 'ΣΊΣΥΦΟΣ'.casefold() == 'σίσυφοσ', 'casefold has no final sigma'
+assert 'ǅ'.casefold() == 'ǆ', 'casefold titlecase digraph'
+assert 'ﬀ'.lower() == 'ﬀ', 'lower keeps ligature'
+
+# === Titlecase ===
+assert 'ǆemal'.title() == 'ǅemal', 'title uses titlecase digraph'
+assert 'ǆemal'.capitalize() == 'ǅemal', 'capitalize uses titlecase digraph'
+assert 'ǆemal'.upper() == 'ǄEMAL', 'upper uses uppercase digraph'
+assert 'ﬁsh ﬂow'.title() == 'Fish Flow', 'title expands ligatures'
+assert 'ßa'.title() == 'Ssa', 'title sharp s'
+assert 'ßa'.capitalize() == 'Ssa', 'capitalize sharp s'
+assert 'ǅ'.swapcase() == 'ǅ', 'swapcase leaves titlecase unchanged'
+assert 'ǅ'.istitle(), 'istitle titlecase digraph'
+assert 'ǅemal'.istitle(), 'istitle titlecase digraph word'
+assert not 'ǅǅ'.istitle(), 'istitle titlecase after cased'
+assert 'Élan Vital'.istitle(), 'istitle accented'
+assert not 'élan'.istitle(), 'istitle accented lowercase start'
+assert 'ab cd'.title() == 'Ab Cd', 'title ascii words'
+assert 'ʰa'.title() == 'ʰa', 'title modifier letter is cased'
+
+# === islower / isupper ===
+assert 'ñandú'.islower(), 'islower accented'
+assert 'ÑANDÚ'.isupper(), 'isupper accented'
+assert not 'ǅ'.islower(), 'islower titlecase is not lower'
+assert not 'ǅ'.isupper(), 'isupper titlecase is not upper'
+assert 'ª'.islower(), 'islower feminine ordinal'
+assert 'ⅰⅱ'.islower(), 'islower small roman numerals'
+assert 'ⅠⅡ'.isupper(), 'isupper roman numerals'
+assert not '中文'.islower(), 'islower uncased'
+assert not '中文'.isupper(), 'isupper uncased'
+
+# === isalpha ===
+assert 'héllo'.isalpha(), 'isalpha accented'
+assert 'привет'.isalpha(), 'isalpha cyrillic'
+assert '中文'.isalpha(), 'isalpha cjk'
+assert 'ǅ'.isalpha(), 'isalpha titlecase'
+assert 'ʰ'.isalpha(), 'isalpha modifier letter'
+assert not 'Ⅻ'.isalpha(), 'isalpha roman numeral is not alpha'
+assert not 'e\u0301'.isalpha(), 'isalpha combining mark is not alpha'
+assert not '\u0345'.isalpha(), 'isalpha alphabetic combining mark is not alpha'
+
+# === isdecimal / isdigit / isnumeric ===
+assert '١٢٣'.isdecimal(), 'isdecimal arabic-indic'
+assert '१२३'.isdecimal(), 'isdecimal devanagari'
+assert '๑๒๓'.isdecimal(), 'isdecimal thai'
+assert '１２３'.isdecimal(), 'isdecimal fullwidth'
+assert '𝟘𝟙'.isdecimal(), 'isdecimal mathematical double-struck'
+assert not '²'.isdecimal(), 'isdecimal superscript'
+assert '²'.isdigit(), 'isdigit superscript'
+assert '①'.isdigit(), 'isdigit circled'
+assert '१२३'.isdigit(), 'isdigit devanagari'
+assert not '½'.isdigit(), 'isdigit vulgar fraction'
+assert not '⑩'.isdigit(), 'isdigit circled ten'
+assert '½'.isnumeric(), 'isnumeric vulgar fraction'
+assert 'Ⅻ'.isnumeric(), 'isnumeric roman numeral'
+assert '五'.isnumeric(), 'isnumeric cjk five'
+assert '五'.isalpha(), 'isalpha cjk five'
+assert not '五'.isdigit(), 'isdigit cjk five'
+assert '⑩'.isnumeric(), 'isnumeric circled ten'
+assert not 'x½'.isnumeric(), 'isnumeric letter'
+
+# === isalnum ===
+assert 'abc١٢٣'.isalnum(), 'isalnum arabic-indic'
+assert 'Ⅻ½²'.isalnum(), 'isalnum numeric'
+assert '中五'.isalnum(), 'isalnum cjk'
+assert not 'e\u0301'.isalnum(), 'isalnum combining mark'
+assert not 'a b'.isalnum(), 'isalnum space'
+
+# === isspace ===
+assert '\xa0\u2003\u3000'.isspace(), 'isspace unicode spaces'
+assert '\u2028\u2029\x85'.isspace(), 'isspace line separators'
+assert '\x1c\x1d\x1e\x1f'.isspace(), 'isspace information separators'
+assert not '\u200b'.isspace(), 'isspace zero width space'
+assert not '\ufeff'.isspace(), 'isspace byte order mark'
+
+# === split / strip on unicode whitespace ===
+assert 'a\u3000b\x1fc'.split() == ['a', 'b', 'c'], 'split unicode whitespace'
+assert 'a\u3000b\u3000c'.split(maxsplit=1) == ['a', 'b\u3000c'], 'split unicode whitespace maxsplit'
+assert 'a\u3000b\u3000c'.rsplit(maxsplit=1) == ['a\u3000b', 'c'], 'rsplit unicode whitespace maxsplit'
+assert '\u3000\x1ca\u2003'.strip() == 'a', 'strip unicode whitespace'
+assert '\u3000a'.lstrip() == 'a', 'lstrip unicode whitespace'
+assert 'a\u3000'.rstrip() == 'a', 'rstrip unicode whitespace'
+assert '\u200ba\u200b'.strip() == '\u200ba\u200b', 'strip keeps zero width space'
+
+# === isprintable ===
+assert ''.isprintable(), 'isprintable empty'
+assert 'héllo wörld'.isprintable(), 'isprintable accented'
+assert '中文 ǅ ½'.isprintable(), 'isprintable non-latin'
+assert not 'a\nb'.isprintable(), 'isprintable newline'
+assert not '\t'.isprintable(), 'isprintable tab'
+assert not '\xa0'.isprintable(), 'isprintable no-break space'
+assert not '\u200b'.isprintable(), 'isprintable zero width space'
+assert not '\u2028'.isprintable(), 'isprintable line separator'
+assert not '\ue000'.isprintable(), 'isprintable private use'
+assert not '\U000e0001'.isprintable(), 'isprintable language tag'
+
+# === isidentifier ===
+assert 'héllo'.isidentifier(), 'isidentifier accented'
+assert 'переменная'.isidentifier(), 'isidentifier cyrillic'
+assert '变量'.isidentifier(), 'isidentifier cjk'
+assert '_ñ1'.isidentifier(), 'isidentifier underscore start'
+assert 'e\u0301'.isidentifier(), 'isidentifier combining mark continues'
+assert not '\u0301e'.isidentifier(), 'isidentifier combining mark cannot start'
+assert not '١x'.isidentifier(), 'isidentifier cannot start with digit'
+assert 'x١'.isidentifier(), 'isidentifier arabic-indic digit continues'
+assert 'Ⅻ'.isidentifier(), 'isidentifier letter numeral'
+assert not '½'.isidentifier(), 'isidentifier vulgar fraction'
+assert not 'a-b'.isidentifier(), 'isidentifier hyphen'
+assert not 'a\xa0'.isidentifier(), 'isidentifier no-break space'
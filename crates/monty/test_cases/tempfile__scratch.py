@@ -0,0 +1,55 @@
+# call-external
+import tempfile
+from pathlib import Path
+
+# === TemporaryDirectory() ===
+tmp = tempfile.TemporaryDirectory()
+assert isinstance(tmp.name, str), 'name is a str'
+scratch = Path(tmp.name)
+assert scratch.is_dir(), 'TemporaryDirectory creates a directory'
+assert list(scratch.iterdir()) == [], 'new directory is empty'
+(scratch / 'stage.txt').write_text('intermediate')
+assert (scratch / 'stage.txt').read_text() == 'intermediate', 'files can be staged in the directory'
+
+other = tempfile.TemporaryDirectory()
+assert other.name != tmp.name, 'each call gets a new directory'
+assert Path(other.name).parent == scratch.parent, 'default directories share a parent'
+
+named = tempfile.TemporaryDirectory(suffix='.d', prefix='job-')
+assert Path(named.name).name.startswith('job-'), 'prefix starts the name'
+assert named.name.endswith('.d'), 'suffix ends the name'
+
+nested = tempfile.TemporaryDirectory(dir=scratch)
+assert Path(nested.name).parent == scratch, 'dir is the parent directory'
+assert Path(nested.name).is_dir(), 'directory created in dir'
+
+positional = tempfile.TemporaryDirectory('.out', 'pos-', tmp.name)
+assert Path(positional.name).name.startswith('pos-'), 'positional prefix'
+assert Path(positional.name).parent == scratch, 'positional dir'
+
+# === NamedTemporaryFile() ===
+f = tempfile.NamedTemporaryFile(suffix='.csv', dir=tmp.name)
+path = Path(f.name)
+assert path.is_file(), 'NamedTemporaryFile creates a file'
+assert path.read_bytes() == b'', 'new file is empty'
+assert path.suffix == '.csv', 'suffix ends the file name'
+assert path.parent == scratch, 'file created in dir'
+assert path.stat().st_mode & 0o777 == 0o600, 'file is only accessible to the owner'
+path.write_text('a,b\n1,2\n')
+assert path.read_text() == 'a,b\n1,2\n', 'file can be written and read back'
+
+g = tempfile.NamedTemporaryFile()
+assert g.name != f.name, 'each call gets a new file'
+assert Path(g.name).exists(), 'default file exists'
+
+# === missing dir ===
+try:
+    tempfile.TemporaryDirectory(dir='/nonexistent')
+    assert False, 'missing dir should fail'
+except FileNotFoundError:
+    pass
+try:
+    tempfile.NamedTemporaryFile(dir='/nonexistent')
+    assert False, 'missing dir should fail'
+except FileNotFoundError:
+    pass
@@ -0,0 +1,24 @@
+def ratio(total, count):
+    scaled = total * 100
+    return scaled  //  (count)
+
+
+def percent(stats, key):
+    return str(ratio(stats[key], stats['n'])) + '%'
+
+
+print(percent({'hits': 3, 'n': 0}, 'hits'))
+"""
+TRACEBACK:
+Traceback (most recent call last):
+  File "traceback__binary_op_anchors.py", line 10, in <module>
+    print(percent({'hits': 3, 'n': 0}, 'hits'))
+          ~~~~~~~^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+  File "traceback__binary_op_anchors.py", line 7, in percent
+    return str(ratio(stats[key], stats['n'])) + '%'
+               ~~~~~^^^^^^^^^^^^^^^^^^^^^^^^
+  File "traceback__binary_op_anchors.py", line 3, in ratio
+    return scaled  //  (count)
+           ~~~~~~~~^^~~~~~~~~~
+ZeroDivisionError: integer division or modulo by zero
+"""
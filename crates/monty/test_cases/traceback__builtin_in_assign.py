@@ -0,0 +1,22 @@
+def parse(text):
+    value = int(text)
+    return value
+
+
+def load(text):
+    return parse(text) + 1
+
+
+total = load('x')
+"""
+TRACEBACK:
+Traceback (most recent call last):
+  File "traceback__builtin_in_assign.py", line 10, in <module>
+    total = load('x')
+  File "traceback__builtin_in_assign.py", line 7, in load
+    return parse(text) + 1
+           ~~~~~^^^^^^
+  File "traceback__builtin_in_assign.py", line 2, in parse
+    value = int(text)
+ValueError: invalid literal for int() with base 10: 'x'
+"""
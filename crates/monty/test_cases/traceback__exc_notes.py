@@ -0,0 +1,21 @@
+def check(value):
+    if value < 0:
+        err = ValueError('value must be positive')
+        err.add_note(f'got {value}')
+        err.add_note('hint: use abs()')
+        raise err
+
+
+check(-3)
+"""
+TRACEBACK:
+Traceback (most recent call last):
+  File "traceback__exc_notes.py", line 9, in <module>
+    check(-3)
+    ~~~~~^^^^
+  File "traceback__exc_notes.py", line 6, in check
+    raise err
+ValueError: value must be positive
+got -3
+hint: use abs()
+"""
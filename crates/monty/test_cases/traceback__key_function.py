@@ -0,0 +1,21 @@
+def bad(x):
+    return 1 // x
+
+
+def outer(items):
+    return sorted(items, key=bad)
+
+
+result = outer([1, 0])
+"""
+TRACEBACK:
+Traceback (most recent call last):
+  File "traceback__key_function.py", line 9, in <module>
+    result = outer([1, 0])
+  File "traceback__key_function.py", line 6, in outer
+    return sorted(items, key=bad)
+  File "traceback__key_function.py", line 2, in bad
+    return 1 // x
+           ~~^^~~
+ZeroDivisionError: integer division or modulo by zero
+"""
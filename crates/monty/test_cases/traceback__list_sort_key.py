@@ -0,0 +1,17 @@
+def key(x):
+    return x + 'a'
+
+
+data = [3, 1]
+data.sort(key=key)
+"""
+TRACEBACK:
+Traceback (most recent call last):
+  File "traceback__list_sort_key.py", line 6, in <module>
+    data.sort(key=key)
+    ~~~~~~~~~^^^^^^^^^
+  File "traceback__list_sort_key.py", line 2, in key
+    return x + 'a'
+           ~~^~~~~
+TypeError: unsupported operand type(s) for +: 'int' and 'str'
+"""
@@ -0,0 +1,24 @@
+def key(x):
+    if x > 1:
+        raise ValueError(x)
+    return x
+
+
+def biggest(items):
+    found = max(items, key=key)
+    return found
+
+
+print(biggest([1, 2]))
+"""
+TRACEBACK:
+Traceback (most recent call last):
+  File "traceback__max_key_raise.py", line 12, in <module>
+    print(biggest([1, 2]))
+          ~~~~~~~^^^^^^^^
+  File "traceback__max_key_raise.py", line 8, in biggest
+    found = max(items, key=key)
+  File "traceback__max_key_raise.py", line 3, in key
+    raise ValueError(x)
+ValueError: 2
+"""
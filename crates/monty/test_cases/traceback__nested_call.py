@@ -16,13 +16,13 @@ TRACEBACK:
 Traceback (most recent call last):
   File "traceback__nested_call.py", line 13, in <module>
     baz()
-    ~~~~~
+    ~~~^^
   File "traceback__nested_call.py", line 10, in baz
     bar()
-    ~~~~~
+    ~~~^^
   File "traceback__nested_call.py", line 6, in bar
     foo()
-    ~~~~~
+    ~~~^^
   File "traceback__nested_call.py", line 2, in foo
     raise ValueError('xxx')
 ValueError: xxx
@@ -5,6 +5,6 @@ TRACEBACK:
 Traceback (most recent call last):
   File "traceback__nonlocal_module_scope.py", line 2
     nonlocal x  # type: ignore
-    ~~~~~~~~~~
+    ^^^^^^^^^^
 SyntaxError: nonlocal declaration not allowed at module level
 """
@@ -0,0 +1,9 @@
+x = 1; y = 0; z = x / y
+"""
+TRACEBACK:
+Traceback (most recent call last):
+  File "traceback__semicolon_line.py", line 1, in <module>
+    x = 1; y = 0; z = x / y
+                      ~~^~~
+ZeroDivisionError: division by zero
+"""
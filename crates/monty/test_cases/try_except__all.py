@@ -333,6 +333,17 @@ except RuntimeError:
     caught_recursion_by_runtime = True
 assert caught_recursion_by_runtime, 'RuntimeError should catch RecursionError'
 
+# === Exception hierarchy: EOFError ===
+caught_eof_by_exception = False
+try:
+    raise EOFError('EOF when reading a line')
+except Exception as e:
+    caught_eof_by_exception = True
+    assert str(e) == 'EOF when reading a line', 'EOFError message should be kept'
+assert caught_eof_by_exception, 'Exception should catch EOFError'
+assert issubclass(EOFError, Exception), 'EOFError should be a subclass of Exception'
+assert not issubclass(EOFError, OSError), 'EOFError should not be a subclass of OSError'
+
 # === Exception hierarchy in tuple ===
 # Tuple containing base class should catch derived
 caught_by_tuple_base = False
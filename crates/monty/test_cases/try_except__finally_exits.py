@@ -0,0 +1,138 @@
+# === Return from a loop inside try/finally ===
+def return_from_loop():
+    result = []
+    try:
+        for x in [1, 2, 3]:
+            for y in [10, 20]:
+                return x + y, result
+    finally:
+        result.append('finally')
+        # a handled exception inside finally must not disturb the pending return value
+        try:
+            raise ValueError('inside finally')
+        except ValueError:
+            result.append('handled')
+
+
+assert return_from_loop() == (11, ['finally', 'handled']), 'return from nested loops through finally'
+
+
+# === Return from except handler through finally ===
+def return_from_handler():
+    result = []
+    try:
+        raise ValueError('x')
+    except ValueError:
+        return 'handler', result
+    finally:
+        result.append('finally')
+
+
+assert return_from_handler() == ('handler', ['finally']), 'return from handler through finally'
+
+
+# === Return from else block runs finally ===
+def return_from_else():
+    result = []
+    try:
+        pass
+    except ValueError:
+        pass
+    else:
+        return 'else', result
+    finally:
+        result.append('finally')
+
+
+assert return_from_else() == ('else', ['finally']), 'return from else through finally'
+
+
+# === Return through nested finally blocks inside a loop ===
+def return_through_nested():
+    result = []
+    for x in [1, 2]:
+        try:
+            try:
+                return x, result
+            finally:
+                result.append('inner')
+        finally:
+            result.append('outer')
+
+
+assert return_through_nested() == (1, ['inner', 'outer']), 'return through nested finally'
+
+
+# === Return in finally runs finally once ===
+def return_in_finally(result):
+    try:
+        raise ValueError('discarded')
+    finally:
+        result.append('finally')
+        return 'finally'  # type: ignore[returnInFinally]
+
+
+calls = []
+assert return_in_finally(calls) == 'finally', 'return in finally discards the exception'
+assert calls == ['finally'], f'finally should run once: {calls}'
+
+
+# === Exception in finally during return propagates without rerunning finally ===
+def raise_in_finally(result):
+    try:
+        return 1
+    finally:
+        result.append('finally')
+        raise KeyError('from finally')
+
+
+calls = []
+try:
+    raise_in_finally(calls)
+    assert False, 'exception in finally should propagate'
+except KeyError:
+    pass
+assert calls == ['finally'], f'finally should run once: {calls}'
+
+
+# === Exception state is cleared when returning from a handler ===
+def return_inside_handler():
+    try:
+        raise ValueError('x')
+    except ValueError:
+        return 'handled'
+
+
+assert return_inside_handler() == 'handled', 'return from handler'
+try:
+    raise
+except RuntimeError as e:
+    assert str(e) == 'No active exception to reraise', 'returning from a handler ends the handled exception'
+
+
+# === Break in finally during return discards the return ===
+def break_in_finally():
+    for x in [1, 2]:
+        try:
+            return 'try'
+        finally:
+            break  # type: ignore
+    return 'after loop'
+
+
+assert break_in_finally() == 'after loop', 'break in finally overrides return'
+
+
+# === Continue in finally during return discards the return ===
+def continue_in_finally():
+    seen = []
+    for x in [1, 2]:
+        try:
+            return 'try'
+        finally:
+            seen.append(x)
+            continue  # type: ignore
+    return seen
+
+
+assert continue_in_finally() == [1, 2], 'continue in finally overrides return'
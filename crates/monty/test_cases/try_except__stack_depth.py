@@ -0,0 +1,162 @@
+# Tests that exceptions unwind the stack correctly after expressions and statements with
+# branching stack effects. Each case raises while a heap temporary is on the stack, so an
+# incorrect unwind depth would leak the temporary or drop a loop iterator.
+
+
+def boom():
+    raise ValueError('boom')
+
+
+# === Ternary before try ===
+flag = True
+value = [1] if flag else [2]
+try:
+    [value, boom()]
+except ValueError:
+    pass
+assert value == [1], 'ternary true branch'
+
+value = [1] if not flag else [2]
+try:
+    [value, boom()]
+except ValueError:
+    pass
+assert value == [2], 'ternary false branch'
+
+# === Nested ternaries ===
+n = 5
+label = 'small' if n < 3 else 'medium' if n < 10 else 'large'
+try:
+    [label, boom()]
+except ValueError:
+    pass
+assert label == 'medium', 'nested ternary'
+
+# === Boolean operators with heap temporaries ===
+a = [] or [1]
+b = [1] and [2]
+c = ([1] and []) or [3]
+try:
+    [a, b, c, boom()]
+except ValueError:
+    pass
+assert a == [1], 'or with falsy list'
+assert b == [2], 'and with truthy list'
+assert c == [3], 'and/or combination'
+
+# === Ternaries in f-strings ===
+name = None
+assert f'{name if name else "anon"}' == 'anon', 'ternary in f-string'
+assert f'{"yes" if flag else "no":>5}' == '  yes', 'ternary with format spec'
+assert f'{[1] if flag else [2]!r}' == '[1]', 'ternary with conversion'
+width = 6
+assert f'{n if n > 3 else -n:>{width}}' == '     5', 'ternary with dynamic format spec'
+assert f'{(a and b) or c}' == '[2]', 'boolean operators in f-string'
+try:
+    [f'{n:>4}', f'{n:{width}}', boom()]
+except ValueError:
+    pass
+
+# === Extended calls ===
+
+
+def collect(*args, **kwargs):
+    return [args, kwargs]
+
+
+args = [1, 2]
+kwargs = {'k': 3}
+collected = collect(*args, **kwargs)
+method_result = ' '.join(*[['x', 'y']])
+try:
+    [collected, boom()]
+except ValueError:
+    pass
+assert collected == [(1, 2), {'k': 3}], 'extended call'
+assert method_result == 'x y', 'extended method call'
+
+# === Comprehensions before try ===
+squares = [x * x for x in range(4)]
+pairs = {x: [x] for x in range(3) if x}
+try:
+    [squares, boom()]
+except ValueError:
+    pass
+assert squares == [0, 1, 4, 9], 'list comprehension'
+assert pairs == {1: [1], 2: [2]}, 'dict comprehension'
+
+# === For loop before try ===
+total = 0
+for x in [1, 2, 3]:
+    total += x
+try:
+    [[total], boom()]
+except ValueError:
+    pass
+assert total == 6, 'for loop'
+
+# === Try after break in loop body ===
+caught = []
+for i in range(3):
+    if i == 10:
+        break
+    try:
+        [[i], boom()]
+    except ValueError:
+        caught.append(i)
+assert caught == [0, 1, 2], 'loop continues after caught exception following break'
+
+# === Try after continue in loop body ===
+caught = []
+for i in range(4):
+    if i % 2:
+        continue
+    try:
+        [[i], boom()]
+    except ValueError:
+        caught.append(i)
+assert caught == [0, 2], 'loop continues after caught exception following continue'
+
+# === Nested loops with break ===
+found = []
+for i in range(3):
+    for j in range(3):
+        if j > i:
+            break
+        try:
+            [[i, j], boom()]
+        except ValueError:
+            found.append((i, j))
+assert found == [(0, 0), (1, 0), (1, 1), (2, 0), (2, 1), (2, 2)], 'nested loops'
+
+# === Break inside except handler ===
+seen = []
+for i in range(3):
+    try:
+        boom()
+    except ValueError:
+        seen.append(i)
+        if i == 1:
+            break
+    try:
+        [[i], boom()]
+    except ValueError:
+        seen.append(-i)
+assert seen == [0, 0, 1], 'break inside handler'
+
+
+# === In functions ===
+def in_function(items):
+    out = []
+    for item in items:
+        if item is None:
+            continue
+        label = 'even' if item % 2 == 0 else 'odd'
+        try:
+            [[label], boom()]
+        except ValueError:
+            out.append(f'{item}:{label:>4}')
+    return out
+
+
+assert in_function([1, None, 2]) == ['1: odd', '2:even'], 'function with loop, ternary and f-string'
@@ -0,0 +1,35 @@
+# === Hash values match CPython ===
+assert hash(()) == 5740354900026072187, 'empty tuple hash'
+assert hash((1,)) == -6644214454873602895, 'single element tuple hash'
+assert hash((1, 2)) == -3550055125485641917, 'pair hash'
+assert hash((1, (2, 3))) == 7267574591690527098, 'nested tuple hash'
+assert hash((-1, 2.5, True)) == -2523124364571858525, 'mixed numeric tuple hash'
+assert hash(((), ())) == 9028247024705308198, 'tuple of empty tuples hash'
+
+# === Equal tuples hash equally ===
+assert hash((1, 2.0)) == hash((1.0, 2)), 'numerically equal tuples hash equally'
+assert hash((True, 0)) == hash((1, False)), 'bool and int elements hash equally'
+assert hash(('a', (1, 'b'))) == hash(('a', (1, 'b'))), 'nested string tuples hash consistently'
+
+# === Tuples as dict keys ===
+d = {(1, 2): 'a', ((1, 2), (3, 4)): 'b', (): 'empty'}
+assert d[(1, 2)] == 'a', 'tuple dict key lookup'
+assert d[(1.0, 2.0)] == 'a', 'lookup with numerically equal tuple'
+assert d[((1, 2), (3, 4))] == 'b', 'nested tuple dict key lookup'
+assert d[()] == 'empty', 'empty tuple dict key lookup'
+d[('x', ('y', 'z'))] = 'c'
+assert d[('x', ('y', 'z'))] == 'c', 'nested string tuple key'
+assert (1, 2) in d, 'tuple key membership'
+assert (2, 1) not in d, 'reordered tuple is a different key'
+
+# === Tuples as set members ===
+s = {(1, 2), (1, 2), (2, 1), ((1,), (2,))}
+assert len(s) == 3, 'duplicate tuples collapse in a set'
+assert (1, 2) in s, 'tuple set membership'
+assert ((1,), (2,)) in s, 'nested tuple set membership'
+assert (1, 3) not in s, 'missing tuple not in set'
+s.add((1.0, 2.0))
+assert len(s) == 3, 'numerically equal tuple is not added twice'
+fs = frozenset([(1, (2, 3))])
+assert (1, (2, 3)) in fs, 'nested tuple in frozenset'
+assert hash(frozenset([(1, 2)])) == hash(frozenset([(1, 2)])), 'frozenset of tuples is hashable'
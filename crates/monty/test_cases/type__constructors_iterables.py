@@ -0,0 +1,88 @@
+# === list, tuple, set and frozenset from any iterable ===
+assert list(x * 2 for x in range(3)) == [0, 2, 4], 'list from generator'
+assert list(range(3)) == [0, 1, 2], 'list from range'
+assert list('ab') == ['a', 'b'], 'list from str'
+assert list({'a': 1, 'b': 2}) == ['a', 'b'], 'list from dict keys'
+assert list({'a': 1}.items()) == [('a', 1)], 'list from items view'
+assert tuple(map(str, [1, 2])) == ('1', '2'), 'tuple from map'
+assert tuple(enumerate('ab')) == ((0, 'a'), (1, 'b')), 'tuple from enumerate'
+assert set(zip([1, 1], [2, 2])) == {(1, 2)}, 'set from zip'
+assert frozenset(reversed([1, 2, 2])) == frozenset({1, 2}), 'frozenset from reversed'
+assert list() == [] and tuple() == () and set() == set(), 'no arguments'
+
+# === dict from pairs ===
+assert dict(zip('ab', [1, 2])) == {'a': 1, 'b': 2}, 'dict from zip'
+assert dict((k, k * k) for k in range(3)) == {0: 0, 1: 1, 2: 4}, 'dict from generator'
+assert dict(enumerate('xy')) == {0: 'x', 1: 'y'}, 'dict from enumerate'
+assert dict({'a': 1}.items()) == {'a': 1}, 'dict from items view'
+assert dict([['a', 1], ('b', 2)]) == {'a': 1, 'b': 2}, 'pairs can be lists'
+assert dict(['ab', 'cd']) == {'a': 'b', 'c': 'd'}, 'pairs can be strings'
+assert dict([('a', 1), ('a', 2)]) == {'a': 2}, 'later pairs win'
+assert dict([('a', 1)], b=2, a=3) == {'a': 3, 'b': 2}, 'keywords after pairs'
+assert dict(a=1) == {'a': 1}, 'keywords only'
+d = {'a': 1}
+copy = dict(d)
+copy['b'] = 2
+assert d == {'a': 1}, 'dict(d) is a copy'
+
+# === errors ===
+try:
+    dict(5)
+    assert False, 'dict(int) should fail'
+except TypeError as e:
+    assert str(e) == "'int' object is not iterable", 'dict(int) message'
+try:
+    dict('ab')
+    assert False, 'dict of one-character strings should fail'
+except ValueError as e:
+    assert str(e) == 'dictionary update sequence element #0 has length 1; 2 is required', 'short pair message'
+try:
+    dict([(1, 2), (1, 2, 3)])
+    assert False, 'long pair should fail'
+except ValueError as e:
+    assert str(e) == 'dictionary update sequence element #1 has length 3; 2 is required', 'long pair message'
+try:
+    dict([1])
+    assert False, 'non-iterable pair should fail'
+except TypeError as e:
+    assert str(e) == 'cannot convert dictionary update sequence element #0 to a sequence', 'non-iterable pair message'
+try:
+    dict([([], 1)])
+    assert False, 'unhashable key should fail'
+except TypeError as e:
+    assert str(e) == "unhashable type: 'list'", 'unhashable key message'
+try:
+    dict([], [])
+    assert False, 'two positional arguments should fail'
+except TypeError as e:
+    assert str(e) == 'dict expected at most 1 argument, got 2', 'dict arity message'
+try:
+    {}.update([], [])
+    assert False, 'update with two positional arguments should fail'
+except TypeError as e:
+    assert str(e) == 'update expected at most 1 argument, got 2', 'update arity message'
+try:
+    list(5)
+    assert False, 'list(int) should fail'
+except TypeError as e:
+    assert str(e) == "'int' object is not iterable", 'list(int) message'
+try:
+    list([], [])
+    assert False, 'list with two arguments should fail'
+except TypeError as e:
+    assert str(e) == 'list expected at most 1 argument, got 2', 'list arity message'
+try:
+    tuple(x=1)
+    assert False, 'tuple with a keyword should fail'
+except TypeError as e:
+    assert str(e) == 'tuple() takes no keyword arguments', 'tuple keyword message'
+try:
+    set([], x=1)
+    assert False, 'set with a keyword should fail'
+except TypeError as e:
+    assert str(e) == 'set() takes no keyword arguments', 'set keyword message'
+try:
+    frozenset(None)
+    assert False, 'frozenset(None) should fail'
+except TypeError as e:
+    assert str(e) == "'NoneType' object is not iterable", 'frozenset(None) message'
@@ -0,0 +1,172 @@
+# === int() from strings ===
+assert int('42') == 42, 'int of decimal string'
+assert int(' 42\n') == 42, 'int strips whitespace'
+assert int('\t-7 ') == -7, 'int with sign and whitespace'
+assert int('+7') == 7, 'int with plus sign'
+assert int('1_000_000') == 1000000, 'int with underscores'
+assert int('007') == 7, 'int base 10 allows leading zeros'
+assert int('123456789012345678901234567890') == 123456789012345678901234567890, 'int of long string'
+assert int(b'42') == 42, 'int of bytes'
+
+# === int() with a base ===
+assert int('0x1A', 16) == 26, 'int hex with prefix'
+assert int('1a', 16) == 26, 'int hex without prefix'
+assert int('FF', 16) == 255, 'int uppercase hex digits'
+assert int('0o17', 8) == 15, 'int octal with prefix'
+assert int('0b101', 2) == 5, 'int binary with prefix'
+assert int('0b1', 16) == 177, 'binary prefix is hex digits in base 16'
+assert int('z', 36) == 35, 'int base 36'
+assert int('-0x_ff', 16) == -255, 'underscore after prefix'
+assert int('0x1A', 0) == 26, 'base 0 infers hex'
+assert int('0o17', 0) == 15, 'base 0 infers octal'
+assert int('0B11', 0) == 3, 'base 0 infers binary case-insensitively'
+assert int('42', 0) == 42, 'base 0 defaults to decimal'
+assert int('000', 0) == 0, 'base 0 allows zero with leading zeros'
+assert int(' 1_0 ', 0) == 10, 'base 0 with underscore'
+assert int('ffffffffffffffffffffffff', 16) == 2**96 - 1, 'int long hex'
+assert int(b'ff', 16) == 255, 'int bytes with base'
+
+# === int() errors ===
+for text, base in [('', 10), ('  ', 10), ('abc', 10), ('1.5', 10), ('1__0', 10), ('_1', 10), ('1_', 10), ('0x', 16), ('12', 2), ('010', 0), ('0x1A', 10), ('- 1', 10)]:
+    try:
+        int(text, base)
+        assert False, 'int should reject ' + repr(text)
+    except ValueError as e:
+        assert str(e) == 'invalid literal for int() with base ' + str(base) + ': ' + repr(text), 'message for ' + repr(text)
+
+try:
+    int(b'1.5')
+    assert False, 'int of invalid bytes should raise'
+except ValueError as e:
+    assert str(e) == "invalid literal for int() with base 10: b'1.5'", 'bytes literal message'
+
+try:
+    int('10', 1)
+    assert False, 'base 1 should raise'
+except ValueError as e:
+    assert str(e) == 'int() base must be >= 2 and <= 36, or 0', 'base range message'
+
+try:
+    int('10', 37)
+    assert False, 'base 37 should raise'
+except ValueError as e:
+    assert str(e) == 'int() base must be >= 2 and <= 36, or 0', 'base range message upper'
+
+try:
+    int(10, 16)
+    assert False, 'non-string with base should raise'
+except TypeError as e:
+    assert str(e) == "int() can't convert non-string with explicit base", 'explicit base message'
+
+try:
+    int('10', '16')
+    assert False, 'str base should raise'
+except TypeError as e:
+    assert str(e) == "'str' object cannot be interpreted as an integer", 'base type message'
+
+try:
+    int(float('nan'))
+    assert False, 'int(nan) should raise'
+except ValueError as e:
+    assert str(e) == 'cannot convert float NaN to integer', 'int nan message'
+
+try:
+    int(float('inf'))
+    assert False, 'int(inf) should raise'
+except OverflowError as e:
+    assert str(e) == 'cannot convert float infinity to integer', 'int inf message'
+
+# === float() from strings ===
+assert float('1.5') == 1.5, 'float of decimal string'
+assert float(' 2.5\n') == 2.5, 'float strips whitespace'
+assert float('1e-3') == 0.001, 'float with negative exponent'
+assert float('1E3') == 1000.0, 'float with uppercase exponent'
+assert float('-1.5e+2') == -150.0, 'float with signed exponent'
+assert float('.5') == 0.5, 'float without integer part'
+assert float('5.') == 5.0, 'float without fraction'
+assert float('1_000.000_1') == 1000.0001, 'float with underscores'
+assert float('42') == 42.0, 'float of integer string'
+assert float(b'1.5') == 1.5, 'float of bytes'
+assert float(10**20) == 1e20, 'float of long int'
+
+# === float() special values ===
+assert float('inf') == float('infinity'), 'inf spellings'
+assert float('-inf') < 0, 'negative inf'
+assert float('+Infinity') > 1e308, 'signed mixed-case infinity'
+assert float('INF') > 1e308, 'uppercase inf'
+nan = float('nan')
+assert nan != nan, 'float nan'
+assert str(float(' -NaN ')) == 'nan', 'signed nan with whitespace'
+
+# === float() errors ===
+for text in ['', ' ', 'abc', '1.5.5', '1e', 'e5', '1_', '_1', '1__0', '1._5', '--1', '+-1', 'inf inity', 'infx', '0x10', '1 0']:
+    try:
+        float(text)
+        assert False, 'float should reject ' + repr(text)
+    except ValueError as e:
+        assert str(e) == 'could not convert string to float: ' + repr(text), 'message for ' + repr(text)
+
+try:
+    float(b'x')
+    assert False, 'float of invalid bytes should raise'
+except ValueError as e:
+    assert str(e) == "could not convert string to float: b'x'", 'bytes float message'
+
+try:
+    float(10**400)
+    assert False, 'float of huge int should raise'
+except OverflowError as e:
+    assert str(e) == 'int too large to convert to float', 'huge int message'
+
+# === str() decoding bytes ===
+assert str(b'hello', 'utf-8') == 'hello', 'str decodes bytes'
+assert str(b'caf\xc3\xa9', 'UTF8') == 'café', 'str decodes utf-8 with alias'
+assert str(b'a\xffb', 'utf-8', 'replace') == 'a�b', 'str replace error handler'
+assert str(b'a\xffb', 'utf-8', 'ignore') == 'ab', 'str ignore error handler'
+assert str(b'abc', 'utf-8', 'bogus') == 'abc', 'error handler only checked on bad data'
+assert str(b'abc') == "b'abc'", 'str of bytes without encoding is repr'
+assert b'a\xffb'.decode('utf-8', 'replace') == 'a�b', 'decode replace error handler'
+
+# === str() decoding errors ===
+for data, message in [
+    (b'\xff', "'utf-8' codec can't decode byte 0xff in position 0: invalid start byte"),
+    (b'ab\x80', "'utf-8' codec can't decode byte 0x80 in position 2: invalid start byte"),
+    (b'\xe2\x28\xa1', "'utf-8' codec can't decode byte 0xe2 in position 0: invalid continuation byte"),
+    (b'x\xe2\x82', "'utf-8' codec can't decode bytes in position 1-2: unexpected end of data"),
+    (b'x\xe2', "'utf-8' codec can't decode byte 0xe2 in position 1: unexpected end of data"),
+]:
+    try:
+        str(data, 'utf-8')
+        assert False, 'invalid utf-8 should raise'
+    except UnicodeDecodeError as e:
+        assert str(e) == message, 'decode error message for ' + repr(data)
+
+try:
+    str(b'\xff', 'utf-8', 'bogus')
+    assert False, 'unknown error handler should raise'
+except LookupError as e:
+    assert str(e) == "unknown error handler name 'bogus'", 'error handler message'
+
+try:
+    str(b'abc', 'no-such-codec')
+    assert False, 'unknown encoding should raise'
+except LookupError as e:
+    assert str(e) == 'unknown encoding: no-such-codec', 'unknown encoding message'
+
+try:
+    str('abc', 'utf-8')
+    assert False, 'decoding str should raise'
+except TypeError as e:
+    assert str(e) == 'decoding str is not supported', 'decoding str message'
+
+try:
+    str(1, 'utf-8')
+    assert False, 'decoding int should raise'
+except TypeError as e:
+    assert str(e) == 'decoding to str: need a bytes-like object, int found', 'decoding int message'
+
+try:
+    str(b'abc', 1)
+    assert False, 'non-str encoding should raise'
+except TypeError as e:
+    assert str(e) == "str() argument 'encoding' must be str, not int", 'encoding type message'
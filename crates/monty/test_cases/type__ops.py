@@ -99,10 +99,11 @@ assert int(3.0) == 3, 'int(whole float)'
 assert int(True) == 1, 'int(True)'
 assert int(False) == 0, 'int(False)'
 
-# int() with extreme float values (should clamp to i64 range in Monty)
-# Note: Python uses arbitrary precision; Monty clamps to i64
+# int() with extreme float values
 assert isinstance(int(1e18), int), 'int(large float) returns int'
 assert isinstance(int(-1e18), int), 'int(large negative float) returns int'
+assert int(1e20) == 100000000000000000000, 'int(float beyond i64) is exact'
+assert int(-1.5e19) == -15000000000000000000, 'int(negative float beyond i64) is exact'
 assert int(0.0) == 0, 'int(0.0) is zero'
 assert int(-0.0) == 0, 'int(-0.0) is zero'
 assert int(0.9) == 0, 'int(0.9) truncates to 0'
@@ -0,0 +1,2 @@
+int | 'a'
+# Raise=TypeError("unsupported operand type(s) for |: 'type' and 'str'")
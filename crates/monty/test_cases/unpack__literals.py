@@ -0,0 +1,96 @@
+# Tests for star unpacking inside list, tuple, set and dict literals
+
+a = [1, 2]
+b = (3, 4)
+
+# === List literals ===
+assert [*a] == [1, 2], 'single list unpack'
+assert [*a, *b] == [1, 2, 3, 4], 'unpack list and tuple'
+assert [0, *a, 5] == [0, 1, 2, 5], 'plain items around unpack'
+assert [*a, 5, 6, *b, 7] == [1, 2, 5, 6, 3, 4, 7], 'interleaved runs'
+assert [*[]] == [], 'empty unpack'
+assert [*range(3)] == [0, 1, 2], 'unpack range'
+assert [*'ab'] == ['a', 'b'], 'unpack str'
+assert [*{'x': 1, 'y': 2}] == ['x', 'y'], 'unpack dict keys'
+assert [*iter([7, 8])] == [7, 8], 'unpack iterator'
+assert [*(x * 2 for x in a)] == [2, 4], 'unpack generator expression'
+copy = [*a]
+assert copy is not a, 'unpack builds a new list'
+
+# === Tuple literals ===
+assert (*a,) == (1, 2), 'single tuple unpack'
+assert (*a, *b) == (1, 2, 3, 4), 'tuple of two unpacks'
+assert (0, *b) == (0, 3, 4), 'plain item before unpack'
+t = *a, 9
+assert t == (1, 2, 9), 'unparenthesized tuple'
+assert type((*a,)) is tuple, 'result is a tuple'
+
+# === Set literals ===
+assert {*a, *b} == {1, 2, 3, 4}, 'set of two unpacks'
+assert {*a, 2, 3} == {1, 2, 3}, 'duplicates removed'
+assert {0, *range(2)} == {0, 1}, 'plain item before unpack'
+assert type({*a}) is set, 'result is a set'
+
+# === Dict literals ===
+d1 = {'x': 1, 'y': 2}
+d2 = {'y': 20, 'z': 30}
+assert {**d1} == {'x': 1, 'y': 2}, 'single dict unpack'
+assert {**d1, **d2} == {'x': 1, 'y': 20, 'z': 30}, 'later unpack overwrites'
+assert {**d1, 'x': 100} == {'x': 100, 'y': 2}, 'later pair overwrites'
+assert {'x': 100, **d1} == {'x': 1, 'y': 2}, 'later unpack overwrites pair'
+assert list({'a': 0, **d2, 'b': 1}) == ['a', 'y', 'z', 'b'], 'insertion order'
+assert {**{1: 'one'}, (2, 3): 'pair'} == {1: 'one', (2, 3): 'pair'}, 'non-string keys'
+assert {**{}} == {}, 'empty dict unpack'
+merged = {**d1}
+assert merged is not d1, 'unpack builds a new dict'
+
+# === Evaluation order ===
+order = []
+
+
+def track(value):
+    order.append(value)
+    return value
+
+
+[track(1), *track([2]), track(3)]
+assert order == [1, [2], 3], 'list elements evaluated left to right'
+order = []
+{track('k'): track('v'), **track({}), track('k2'): track('v2')}
+assert order == ['k', 'v', {}, 'k2', 'v2'], 'dict items evaluated left to right'
+
+
+# === Calls ===
+def collect(*args):
+    return args
+
+
+assert collect(*a, *b) == (1, 2, 3, 4), 'multiple star args'
+assert collect(*a, 5) == (1, 2, 5), 'positional after star args'
+assert collect(0, *a, 5, *b) == (0, 1, 2, 5, 3, 4), 'mixed star and positional'
+assert collect(*range(2), *'xy') == (0, 1, 'x', 'y'), 'star args from range and str'
+
+# === Errors ===
+try:
+    [*a, *1]
+    assert False, 'list unpack of int should fail'
+except TypeError as e:
+    assert str(e) == 'Value after * must be an iterable, not int', 'list unpack error message'
+
+try:
+    {*a, *None}
+    assert False, 'set unpack of None should fail'
+except TypeError as e:
+    assert str(e) == "'NoneType' object is not iterable", 'set unpack error message'
+
+try:
+    {**d1, **[1]}
+    assert False, 'dict unpack of list should fail'
+except TypeError as e:
+    assert str(e) == "'list' object is not a mapping", 'dict unpack error message'
+
+try:
+    {*a, *[[1]]}
+    assert False, 'unhashable element should fail'
+except TypeError as e:
+    assert str(e) == "unhashable type: 'list'", 'unhashable error message'
@@ -102,11 +102,10 @@ assert (cmp := 10) > 5, 'walrus in comparison'
 assert cmp == 10, 'walrus assigned in comparison'
 
 # === Walrus in chained comparisons ===
-# Note: Chained comparisons like `0 < (mid := 5) < 10` are not yet supported
-# Testing a simpler comparison chain
-mid = (chain := 5)
-assert 0 < chain and chain < 10, 'walrus result used in comparison chain'
+assert 0 < (mid := 5) < 10, 'walrus in comparison chain'
 assert mid == 5, 'walrus assigned correctly'
+assert not (10 < (skipped := 1) < (never := 2)), 'walrus in short-circuited chain'
+assert skipped == 1, 'walrus before short circuit assigned'
 
 # === Walrus in boolean expressions ===
 # Short-circuit with and
@@ -0,0 +1,114 @@
+import zlib
+
+# === round trips ===
+data = b'hello world ' * 100
+assert zlib.decompress(zlib.compress(data)) == data, 'zlib round trip'
+assert len(zlib.compress(data)) < len(data), 'repetitive data compresses'
+for level in range(-1, 10):
+    assert zlib.decompress(zlib.compress(data, level)) == data, 'round trip at every level'
+assert zlib.decompress(zlib.compress(data, wbits=-15), wbits=-15) == data, 'raw deflate round trip'
+assert zlib.decompress(zlib.compress(data, wbits=31), wbits=31) == data, 'gzip round trip'
+assert zlib.decompress(zlib.compress(data, wbits=31), wbits=47) == data, 'auto-detected gzip'
+assert zlib.decompress(zlib.compress(data), wbits=47) == data, 'auto-detected zlib'
+assert zlib.decompress(zlib.compress(data), wbits=0) == data, 'window size from the header'
+assert zlib.decompress(zlib.compress(b'')) == b'', 'empty data'
+
+# === data compressed by zlib itself ===
+assert zlib.decompress(b'x\x9c\xcbH\xcd\xc9\xc9W(\xcf/\xcaI\x01\x00\x1a\x0b\x04]') == b'hello world', 'zlib stream'
+assert zlib.decompress(b'\xcbH\xcd\xc9\xc9W(\xcf/\xcaI\x01\x00', -15) == b'hello world', 'raw stream'
+gz = b'\x1f\x8b\x08\x00\x00\x00\x00\x00\x00\x03\xcbH\xcd\xc9\xc9W(\xcf/\xcaI\x01\x00\x85\x11J\r\x0b\x00\x00\x00'
+assert zlib.decompress(gz, 31) == b'hello world', 'gzip member'
+assert zlib.decompress(zlib.compress(b'abc') + b'junk') == b'abc', 'trailing data is ignored'
+
+# === gzip headers ===
+assert zlib.compress(b'hello', wbits=31)[:10] == b'\x1f\x8b\x08\x00\x00\x00\x00\x00\x00\x03', 'default level header'
+assert zlib.compress(b'hello', 9, 31)[8:10] == b'\x02\x03', 'best compression header'
+assert zlib.compress(b'hello', 1, 31)[8:10] == b'\x04\x03', 'best speed header'
+
+# === checksums ===
+assert zlib.crc32(b'hello') == 907060870, 'crc32'
+assert zlib.crc32(b'hello', 5) == 4277152246, 'crc32 with a starting value'
+assert zlib.crc32(b'lo', zlib.crc32(b'hel')) == zlib.crc32(b'hello'), 'running crc32'
+assert zlib.crc32(b'') == 0, 'crc32 of nothing'
+assert zlib.crc32(b'', -1) == 4294967295, 'negative starting value is truncated'
+assert zlib.adler32(b'hello') == 103547413, 'adler32'
+assert zlib.adler32(b'hello', 5) == 104858137, 'adler32 with a starting value'
+assert zlib.adler32(b'lo', zlib.adler32(b'hel')) == zlib.adler32(b'hello'), 'running adler32'
+assert zlib.adler32(b'') == 1, 'adler32 of nothing'
+assert zlib.adler32(b'x' * 100000) == 2116691386, 'adler32 of a long input'
+
+# === constants ===
+assert zlib.MAX_WBITS == 15, 'MAX_WBITS'
+assert zlib.DEFLATED == 8, 'DEFLATED'
+assert zlib.Z_BEST_COMPRESSION == 9, 'Z_BEST_COMPRESSION'
+assert zlib.Z_BEST_SPEED == 1, 'Z_BEST_SPEED'
+assert zlib.Z_DEFAULT_COMPRESSION == -1, 'Z_DEFAULT_COMPRESSION'
+assert zlib.Z_NO_COMPRESSION == 0, 'Z_NO_COMPRESSION'
+assert issubclass(zlib.error, Exception), 'zlib.error is an Exception'
+
+# === errors ===
+try:
+    zlib.decompress(b'')
+    assert False, 'empty input should fail'
+except zlib.error as e:
+    assert str(e) == 'Error -5 while decompressing data: incomplete or truncated stream', 'empty input message'
+try:
+    zlib.decompress(zlib.compress(b'abc')[:-1])
+    assert False, 'truncated input should fail'
+except zlib.error as e:
+    assert str(e) == 'Error -5 while decompressing data: incomplete or truncated stream', 'truncated message'
+try:
+    zlib.decompress(b'garbage')
+    assert False, 'bad header should fail'
+except zlib.error as e:
+    assert str(e) == 'Error -3 while decompressing data: incorrect header check', 'bad header message'
+try:
+    zlib.decompress(b'x\x9cgarbage')
+    assert False, 'corrupt data should fail'
+except zlib.error as e:
+    assert str(e).startswith('Error -3 while decompressing data: '), 'corrupt data message'
+try:
+    zlib.decompress(zlib.compress(b'abc')[:-1] + b'\x00')
+    assert False, 'bad checksum should fail'
+except zlib.error as e:
+    assert str(e) == 'Error -3 while decompressing data: incorrect data check', 'bad checksum message'
+try:
+    zlib.decompress(zlib.compress(b'abc'), wbits=31)
+    assert False, 'zlib stream is not gzip'
+except zlib.error as e:
+    assert str(e) == 'Error -3 while decompressing data: incorrect header check', 'gzip header message'
+try:
+    zlib.decompress(zlib.compress(b'abc'), wbits=9)
+    assert False, 'window larger than wbits should fail'
+except zlib.error as e:
+    assert str(e) == 'Error -3 while decompressing data: invalid window size', 'window size message'
+try:
+    zlib.decompress(zlib.compress(b'abc'), wbits=5)
+    assert False, 'bad wbits should fail'
+except zlib.error as e:
+    assert str(e) == 'Error -2 while preparing to decompress data: inconsistent stream state', 'wbits message'
+try:
+    zlib.compress(b'abc', 10)
+    assert False, 'bad level should fail'
+except zlib.error as e:
+    assert str(e) == 'Bad compression level', 'level message'
+try:
+    zlib.compress(b'abc', wbits=5)
+    assert False, 'bad compression wbits should fail'
+except zlib.error as e:
+    assert str(e) == 'Bad compression level', 'compression wbits message'
+try:
+    zlib.decompress(b'abc', bufsize=-1)
+    assert False, 'negative bufsize should fail'
+except ValueError as e:
+    assert str(e) == 'bufsize must be non-negative', 'bufsize message'
+try:
+    zlib.compress('abc')
+    assert False, 'str data should fail'
+except TypeError as e:
+    assert str(e) == "a bytes-like object is required, not 'str'", 'str data message'
+try:
+    zlib.crc32(1)
+    assert False, 'int data should fail'
+except TypeError as e:
+    assert str(e) == "a bytes-like object is required, not 'int'", 'int data message'
@@ -3,7 +3,10 @@
 //! These tests verify the behavior of the async execution model, specifically around
 //! resolving external futures incrementally via `FutureSnapshot::resume()`.
 
-use monty::{ExcType, ExternalResult, MontyException, MontyObject, MontyRun, NoLimitTracker, PrintWriter, RunProgress};
+use monty::{
+    ExcType, ExtFunctionSignature, ExtParam, ExtParamKind, ExternalFunctions, ExternalResult, MontyException,
+    MontyObject, MontyRun, NoLimitTracker, NoOsAccess, PrintWriter, RunProgress,
+};
 
 /// Helper to create a MontyRun for async external function tests.
 ///
@@ -66,12 +69,21 @@ fn drive_to_resolve_futures<T: monty::ResourceTracker>(
             RunProgress::ResolveFutures(state) => {
                 return (state, collected_call_ids);
             }
-            RunProgress::Complete(_) => {
+            RunProgress::Complete { .. } => {
                 panic!("unexpected Complete before ResolveFutures");
             }
             RunProgress::OsCall { function, .. } => {
                 panic!("unexpected OsCall: {function:?}");
             }
+            RunProgress::Yield { value, .. } => {
+                panic!("unexpected Yield: {value:?}");
+            }
+            RunProgress::Checkpoint(_) => {
+                panic!("unexpected Checkpoint");
+            }
+            RunProgress::FunctionCallBatch { .. } => {
+                panic!("unexpected FunctionCallBatch");
+            }
         }
     }
 }
@@ -768,12 +780,21 @@ fn drive_collecting_calls<T: monty::ResourceTracker>(
             RunProgress::ResolveFutures(state) => {
                 return (state, collected);
             }
-            RunProgress::Complete(_) => {
+            RunProgress::Complete { .. } => {
                 panic!("unexpected Complete before ResolveFutures");
             }
             RunProgress::OsCall { function, .. } => {
                 panic!("unexpected OsCall: {function:?}");
             }
+            RunProgress::Yield { value, .. } => {
+                panic!("unexpected Yield: {value:?}");
+            }
+            RunProgress::Checkpoint(_) => {
+                panic!("unexpected Checkpoint");
+            }
+            RunProgress::FunctionCallBatch { .. } => {
+                panic!("unexpected FunctionCallBatch");
+            }
         }
     }
 }
@@ -942,3 +963,65 @@ await main()
     let result = progress.into_complete().expect("should complete");
     assert_eq!(result, MontyObject::Int(333));
 }
+
+/// Creates a runner batching calls to `fetch`, which is declared `async`.
+fn create_batching_runner(code: &str) -> MontyRun {
+    let mut runner = MontyRun::new(code.to_owned(), "test.py", vec![], vec!["fetch".to_owned()]).unwrap();
+    let signature = ExtFunctionSignature::new("fetch")
+        .param(ExtParam::new("i", ExtParamKind::PositionalOrKeyword))
+        .is_async();
+    runner.register_external_signature(signature).unwrap();
+    runner.batch_async_calls()
+}
+
+#[test]
+fn batched_calls_surface_together() {
+    let code = r"
+import asyncio
+
+futures = [fetch(i) for i in range(3)]
+results = await asyncio.gather(*futures)
+sum(results)
+";
+    let progress = create_batching_runner(code)
+        .start(vec![], NoLimitTracker, &mut PrintWriter::Stdout)
+        .unwrap();
+    let (calls, state) = progress.into_function_call_batch().expect("expected FunctionCallBatch");
+    let args: Vec<Vec<MontyObject>> = calls.iter().map(|call| call.args.clone()).collect();
+    assert_eq!(
+        args,
+        vec![
+            vec![MontyObject::Int(0)],
+            vec![MontyObject::Int(1)],
+            vec![MontyObject::Int(2)]
+        ]
+    );
+    assert!(calls.iter().all(|call| call.function_name == "fetch"));
+
+    let results = calls
+        .iter()
+        .map(|call| {
+            let MontyObject::Int(i) = call.args[0] else {
+                panic!("unexpected args {:?}", call.args);
+            };
+            (call.call_id, ExternalResult::Return(MontyObject::Int(i * 10)))
+        })
+        .collect();
+    let progress = state.resume(results, &mut PrintWriter::Stdout).unwrap();
+    assert_eq!(progress.into_complete(), Some(MontyObject::Int(30)));
+}
+
+#[test]
+fn batched_calls_only_for_async_signatures() {
+    // without `batch_async_calls()` each call suspends on its own
+    let runner = MontyRun::new("await fetch(1)".to_owned(), "test.py", vec![], vec!["fetch".to_owned()]).unwrap();
+    let progress = runner.start(vec![], NoLimitTracker, &mut PrintWriter::Stdout).unwrap();
+    assert!(matches!(progress, RunProgress::FunctionCall { .. }));
+
+    // synchronous dispatchers resolve batched calls one by one
+    let functions = ExternalFunctions::new().register("fetch", |i: i64| i * 10);
+    let result = create_batching_runner("import asyncio\nsum(await asyncio.gather(fetch(1), fetch(2)))")
+        .run_with_handlers(vec![], NoLimitTracker, &mut PrintWriter::Stdout, &functions, NoOsAccess)
+        .unwrap();
+    assert_eq!(result, MontyObject::Int(30));
+}
@@ -0,0 +1,160 @@
+//! Tests for audit events reported to an `AuditHook` through an `AuditTracker`.
+
+use std::sync::{Arc, Mutex};
+
+use monty::{
+    AuditEvent, AuditEventKind, AuditHook, AuditTracker, ExcType, LimitedTracker, MontyObject, MontyRun,
+    NoLimitTracker, PrintWriter, ResourceLimits, ResourceTracker, RunProgress,
+};
+
+/// Hook recording a one-line summary of every event.
+#[derive(Debug, Default, Clone)]
+struct Recorder(Arc<Mutex<Vec<String>>>);
+
+impl AuditHook for Recorder {
+    fn on_event(&self, event: &AuditEvent<'_>) {
+        let summary = match &event.kind {
+            AuditEventKind::ExternalCall {
+                function_name,
+                method_call,
+                args,
+                ..
+            } => format!("external_call {function_name} method={method_call} args={args:?}"),
+            AuditEventKind::OsCall { function, args, .. } => format!("os_call {function} args={args:?}"),
+            AuditEventKind::Import { module, allowed } => format!("import {module} allowed={allowed}"),
+            AuditEventKind::ResourceLimit { exc_type, message } => format!("resource_limit {exc_type}: {message}"),
+            AuditEventKind::CapabilityDenied { capability, name } => format!("capability_denied {capability} {name}"),
+        };
+        self.0.lock().unwrap().push(summary);
+    }
+}
+
+impl Recorder {
+    /// Takes the events recorded so far.
+    fn take(&self) -> Vec<String> {
+        std::mem::take(&mut *self.0.lock().unwrap())
+    }
+}
+
+#[test]
+fn external_and_os_calls() {
+    let code = "
+from pathlib import Path
+Path('/secret.txt').read_text() + fetch('a', n=1)
+";
+    let recorder = Recorder::default();
+    let runner = MontyRun::new(code.to_owned(), "main.py", vec![], vec!["fetch".to_owned()]).unwrap();
+    let tracker = NoLimitTracker.chain(AuditTracker::new(recorder.clone()));
+
+    let progress = runner.start(vec![], tracker, &mut PrintWriter::Disabled).unwrap();
+    let RunProgress::OsCall { state, .. } = progress else {
+        panic!("expected OsCall, got {progress:?}");
+    };
+    let progress = state
+        .run(MontyObject::String("x".to_owned()), &mut PrintWriter::Disabled)
+        .unwrap();
+    let RunProgress::FunctionCall { state, .. } = progress else {
+        panic!("expected FunctionCall, got {progress:?}");
+    };
+    state
+        .run(MontyObject::String("y".to_owned()), &mut PrintWriter::Disabled)
+        .unwrap();
+
+    assert_eq!(
+        recorder.take(),
+        [
+            "import pathlib allowed=true",
+            r#"os_call Path.read_text args=[Path("/secret.txt")]"#,
+            r#"external_call fetch method=false args=[String("a")]"#,
+        ]
+    );
+}
+
+#[test]
+fn denied_import() {
+    let recorder = Recorder::default();
+    let runner = MontyRun::new("import socket".to_owned(), "main.py", vec![], vec![]).unwrap();
+    let err = runner
+        .run(vec![], AuditTracker::new(recorder.clone()), &mut PrintWriter::Disabled)
+        .unwrap_err();
+    assert_eq!(err.exc_type(), ExcType::ModuleNotFoundError);
+    assert_eq!(recorder.take(), ["import socket allowed=false"]);
+}
+
+#[test]
+fn resource_limit_breach() {
+    let recorder = Recorder::default();
+    let runner = MontyRun::new(
+        "x = []\nwhile True:\n    x.append(1)".to_owned(),
+        "main.py",
+        vec![],
+        vec![],
+    )
+    .unwrap();
+    let tracker =
+        LimitedTracker::new(ResourceLimits::new().max_allocations(10)).chain(AuditTracker::new(recorder.clone()));
+    let err = runner.run(vec![], tracker, &mut PrintWriter::Disabled).unwrap_err();
+    assert_eq!(err.exc_type(), ExcType::MemoryError);
+    let events = recorder.take();
+    assert_eq!(events.len(), 1, "{events:?}");
+    assert!(events[0].starts_with("resource_limit MemoryError: "), "{events:?}");
+}
+
+#[test]
+fn capability_denied_with_standard_execution() {
+    let recorder = Recorder::default();
+    let runner = MontyRun::new("fetch(1)".to_owned(), "main.py", vec![], vec!["fetch".to_owned()]).unwrap();
+    let err = runner
+        .run(vec![], AuditTracker::new(recorder.clone()), &mut PrintWriter::Disabled)
+        .unwrap_err();
+    assert_eq!(err.exc_type(), ExcType::NotImplementedError);
+    assert_eq!(recorder.take(), ["capability_denied external_function fetch"]);
+}
+
+#[test]
+fn entropy_denied_by_default() {
+    let recorder = Recorder::default();
+    let runner = MontyRun::new(
+        "import secrets\nsecrets.token_hex()".to_owned(),
+        "main.py",
+        vec![],
+        vec![],
+    )
+    .unwrap();
+    let err = runner
+        .run(vec![], AuditTracker::new(recorder.clone()), &mut PrintWriter::Disabled)
+        .unwrap_err();
+    assert_eq!(err.exc_type(), ExcType::NotImplementedError);
+    assert_eq!(
+        recorder.take(),
+        [
+            "import secrets allowed=true",
+            "capability_denied entropy secrets.token_hex"
+        ]
+    );
+}
+
+#[test]
+fn args_digest() {
+    fn event(args: &[MontyObject]) -> AuditEvent<'_> {
+        AuditEvent::now(AuditEventKind::ExternalCall {
+            function_name: "f",
+            method_call: false,
+            args,
+            kwargs: &[],
+        })
+    }
+
+    let args = [MontyObject::Int(1), MontyObject::List(vec![MontyObject::None])];
+    let digest = event(&args).args_digest().unwrap();
+    assert_eq!(event(&args).args_digest(), Some(digest));
+    assert_ne!(event(&args[..1]).args_digest(), Some(digest));
+    assert_eq!(event(&args).name(), "external_call");
+
+    let import = AuditEvent::now(AuditEventKind::Import {
+        module: "sys",
+        allowed: true,
+    });
+    assert_eq!(import.args_digest(), None);
+    assert!(import.unix_timestamp() > 0.0);
+}
@@ -3,8 +3,16 @@
 //! These tests verify that execution state can be serialized with postcard for:
 //! - Caching parsed code to avoid re-parsing
 //! - Snapshotting execution state for external function calls
+//! - Comparing restored execution state with `state_eq`
+//! - Keeping the hash seed, so `hash()` results don't change on resume
+//! - Keeping the entropy stream, so seeded randomness doesn't repeat on resume
 
-use monty::{MontyObject, MontyRun, NoLimitTracker, PrintWriter, RunProgress};
+use std::time::Duration;
+
+use monty::{
+    EntropyPolicy, LimitedTracker, LoadError, MontyObject, MontyRun, NoLimitTracker, PrintWriter, ResourceLimits,
+    RunProgress, SIGNATURE_LEN,
+};
 
 // === MontyRun dump/load Tests ===
 
@@ -19,6 +27,55 @@ fn monty_run_dump_load_simple() {
     assert_eq!(result, MontyObject::Int(3));
 }
 
+#[test]
+fn monty_run_signed_dump_load() {
+    let runner = MontyRun::new("1 + 2".to_owned(), "test.py", vec![], vec![]).unwrap();
+    let bytes = runner.dump_signed(b"secret").unwrap();
+    assert_eq!(bytes.len(), runner.dump().unwrap().len() + SIGNATURE_LEN);
+
+    let loaded = MontyRun::load_signed(&bytes, b"secret").unwrap();
+    assert_eq!(loaded.run_no_limits(vec![]).unwrap(), MontyObject::Int(3));
+
+    // wrong key, modified data, unsigned and truncated data are all rejected
+    assert!(matches!(
+        MontyRun::load_signed(&bytes, b"other"),
+        Err(LoadError::InvalidSignature)
+    ));
+    let mut tampered = bytes.clone();
+    tampered[0] ^= 1;
+    assert!(matches!(
+        MontyRun::load_signed(&tampered, b"secret"),
+        Err(LoadError::InvalidSignature)
+    ));
+    assert!(matches!(
+        MontyRun::load_signed(&runner.dump().unwrap(), b"secret"),
+        Err(LoadError::InvalidSignature)
+    ));
+    assert!(matches!(
+        MontyRun::load_signed(&bytes[..10], b"secret"),
+        Err(LoadError::InvalidSignature)
+    ));
+}
+
+#[test]
+fn monty_run_code_hash() {
+    let runner = MontyRun::new("1 + 2".to_owned(), "test.py", vec![], vec![]).unwrap();
+    let loaded = MontyRun::load(&runner.dump().unwrap()).unwrap();
+    assert_eq!(loaded.code_hash(), runner.code_hash());
+
+    // the hash only depends on the code, it's the SHA-256 of the source
+    let other_name = MontyRun::new("1 + 2".to_owned(), "other.py", vec![], vec![]).unwrap();
+    assert_eq!(other_name.code_hash(), runner.code_hash());
+    let other_code = MontyRun::new("1 + 3".to_owned(), "test.py", vec![], vec![]).unwrap();
+    assert_ne!(other_code.code_hash(), runner.code_hash());
+    let empty = MontyRun::new(String::new(), "test.py", vec![], vec![]).unwrap();
+    assert_eq!(
+        empty.code_hash()[..4],
+        [0xe3, 0xb0, 0xc4, 0x42],
+        "SHA-256 of empty input starts with e3b0c442"
+    );
+}
+
 #[test]
 fn monty_run_dump_load_with_inputs() {
     // Test that input names are preserved across dump/load
@@ -181,3 +238,184 @@ fn run_progress_complete_roundtrip() {
 
     assert_eq!(loaded.into_complete().unwrap(), MontyObject::Int(3));
 }
+
+// === state_eq Tests ===
+
+/// Starts `code` with `x` as input and returns the progress at the first pause.
+fn start_with_input(code: &str, x: i64) -> RunProgress<NoLimitTracker> {
+    let runner = MontyRun::new(
+        code.to_owned(),
+        "test.py",
+        vec!["x".to_owned()],
+        vec!["ext_fn".to_owned()],
+    )
+    .unwrap();
+    runner
+        .start(vec![MontyObject::Int(x)], NoLimitTracker, &mut PrintWriter::Stdout)
+        .unwrap()
+}
+
+#[test]
+fn state_eq_after_roundtrip() {
+    let code = "
+d = {'a': [x], 'b': {1, 2, 3}}
+s = frozenset(range(20))
+ext_fn(d)";
+    let progress = start_with_input(code, 1);
+    let bytes = progress.dump().unwrap();
+    let loaded: RunProgress<NoLimitTracker> = RunProgress::load(&bytes).unwrap();
+    assert!(loaded.state_eq(&progress));
+    assert!(progress.state_eq(&loaded));
+    // serialization is deterministic, dumping the loaded state gives the same bytes
+    assert_eq!(loaded.dump().unwrap(), bytes);
+}
+
+#[test]
+fn state_eq_detects_differences() {
+    let code = "y = [x, x + 1]\next_fn(1)";
+    assert!(start_with_input(code, 1).state_eq(&start_with_input(code, 1)));
+    // different values on the heap
+    assert!(!start_with_input(code, 1).state_eq(&start_with_input(code, 2)));
+    // different code
+    assert!(!start_with_input(code, 1).state_eq(&start_with_input("y = [x, x + 2]\next_fn(1)", 1)));
+    // same state, different arguments reported to the host
+    assert!(!start_with_input("ext_fn(1)", 1).state_eq(&start_with_input("ext_fn(2)", 1)));
+}
+
+#[test]
+fn state_eq_pending_futures() {
+    let code = "
+import asyncio
+
+async def main():
+    return sum(await asyncio.gather(ext_fn(1), ext_fn(2), ext_fn(3)))
+
+await main()";
+    let runner = MontyRun::new(code.to_owned(), "test.py", vec![], vec!["ext_fn".to_owned()]).unwrap();
+    let mut progress = runner.start(vec![], NoLimitTracker, &mut PrintWriter::Stdout).unwrap();
+    while let RunProgress::FunctionCall { state, .. } = progress {
+        progress = state.run_pending(&mut PrintWriter::Stdout).unwrap();
+    }
+    let state = progress.into_resolve_futures().expect("expected pending futures");
+    assert_eq!(state.pending_call_ids().len(), 3);
+
+    let progress = RunProgress::ResolveFutures(state);
+    let bytes = progress.dump().unwrap();
+    let loaded: RunProgress<NoLimitTracker> = RunProgress::load(&bytes).unwrap();
+    assert!(loaded.state_eq(&progress));
+    assert_eq!(loaded.dump().unwrap(), bytes);
+}
+
+#[test]
+fn state_eq_ignores_tracker() {
+    let start = |seconds: u64| {
+        let runner = MontyRun::new("ext_fn(1)".to_owned(), "test.py", vec![], vec!["ext_fn".to_owned()]).unwrap();
+        let limits = ResourceLimits::new().max_duration(Duration::from_secs(seconds));
+        runner
+            .start(vec![], LimitedTracker::new(limits), &mut PrintWriter::Stdout)
+            .unwrap()
+    };
+    assert!(start(10).state_eq(&start(20)));
+}
+
+// === Hash seed Tests ===
+
+/// Runs `code` with `limits`, returning the result.
+fn run_with_seed(code: &str, limits: ResourceLimits) -> MontyObject {
+    let runner = MontyRun::new(code.to_owned(), "test.py", vec![], vec![]).unwrap();
+    runner
+        .run(vec![], LimitedTracker::new(limits), &mut PrintWriter::Stdout)
+        .unwrap()
+}
+
+#[test]
+fn hash_seed_only_changes_str_and_bytes_hashes() {
+    let code = "[hash('abc'), hash(b'abc'), hash((1, 2)), hash(42), list({'c', 'a', 'b'})]";
+    let default = run_with_seed(code, ResourceLimits::new());
+    assert_eq!(run_with_seed(code, ResourceLimits::new()), default);
+    assert_eq!(run_with_seed(code, ResourceLimits::new().hash_seed(0)), default);
+
+    let seeded = run_with_seed(code, ResourceLimits::new().hash_seed(1234));
+    assert_eq!(run_with_seed(code, ResourceLimits::new().hash_seed(1234)), seeded);
+    let (MontyObject::List(default), MontyObject::List(seeded)) = (default, seeded) else {
+        panic!("expected lists");
+    };
+    assert_ne!(seeded[0], default[0]);
+    assert_ne!(seeded[1], default[1]);
+    // non-string hashes and set iteration order don't depend on the seed
+    assert_eq!(seeded[2..], default[2..]);
+}
+
+#[test]
+fn random_hash_seed() {
+    let code = "hash('abc')";
+    let first = run_with_seed(code, ResourceLimits::new().random_hash_seed());
+    let second = run_with_seed(code, ResourceLimits::new().random_hash_seed());
+    assert_ne!(first, second);
+}
+
+#[test]
+fn hash_seed_stored_in_snapshot() {
+    let code = "
+d = {'key': 1, b'bytes': 2}
+h = ext_fn(hash('key'))
+[h == hash('key'), d['key'] + d[b'bytes']]";
+    let runner = MontyRun::new(code.to_owned(), "test.py", vec![], vec!["ext_fn".to_owned()]).unwrap();
+    let tracker = LimitedTracker::new(ResourceLimits::new().random_hash_seed());
+    let progress = runner.start(vec![], tracker, &mut PrintWriter::Stdout).unwrap();
+    let bytes = progress.dump().unwrap();
+
+    let loaded: RunProgress<LimitedTracker> = RunProgress::load(&bytes).unwrap();
+    let (_, args, _, _, _, state) = loaded.into_function_call().expect("should be at function call");
+    let result = state.run(args[0].clone(), &mut PrintWriter::Stdout).unwrap();
+    assert_eq!(
+        result.into_complete().unwrap(),
+        MontyObject::List(vec![MontyObject::Bool(true), MontyObject::Int(3)])
+    );
+}
+
+// === Entropy Tests ===
+
+#[test]
+fn seeded_entropy_is_deterministic() {
+    let code = "import secrets\n[secrets.token_hex(), secrets.randbelow(1000), secrets.choice('abc')]";
+    let seeded = run_with_seed(code, ResourceLimits::new().entropy(EntropyPolicy::Seeded(7)));
+    assert_eq!(
+        run_with_seed(code, ResourceLimits::new().entropy(EntropyPolicy::Seeded(7))),
+        seeded
+    );
+    assert_ne!(
+        run_with_seed(code, ResourceLimits::new().entropy(EntropyPolicy::Seeded(8))),
+        seeded
+    );
+    assert_ne!(
+        run_with_seed(code, ResourceLimits::new().entropy(EntropyPolicy::Os)),
+        seeded
+    );
+}
+
+#[test]
+fn seeded_entropy_continues_after_snapshot() {
+    let code = "
+import secrets
+before = secrets.token_hex(8)
+ext_fn()
+[before, secrets.token_hex(8)]";
+    let runner = MontyRun::new(code.to_owned(), "test.py", vec![], vec!["ext_fn".to_owned()]).unwrap();
+    let tracker = LimitedTracker::new(ResourceLimits::new().entropy(EntropyPolicy::Seeded(7)));
+    let progress = runner.start(vec![], tracker, &mut PrintWriter::Stdout).unwrap();
+    let bytes = progress.dump().unwrap();
+
+    let loaded: RunProgress<LimitedTracker> = RunProgress::load(&bytes).unwrap();
+    let (_, _, _, _, _, state) = loaded.into_function_call().expect("should be at function call");
+    let result = state.run(MontyObject::None, &mut PrintWriter::Stdout).unwrap();
+    let MontyObject::List(tokens) = result.into_complete().unwrap() else {
+        panic!("expected a list");
+    };
+    assert_ne!(tokens[0], tokens[1], "the stream shouldn't restart after loading");
+    let uninterrupted = run_with_seed(
+        "import secrets\n[secrets.token_hex(8), secrets.token_hex(8)]",
+        ResourceLimits::new().entropy(EntropyPolicy::Seeded(7)),
+    );
+    assert_eq!(MontyObject::List(tokens), uninterrupted);
+}
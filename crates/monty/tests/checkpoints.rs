@@ -0,0 +1,103 @@
+//! Tests for automatic checkpoints every N instructions via `ResourceLimits::checkpoint_interval`.
+
+use monty::{LimitedTracker, MontyObject, MontyRun, PrintWriter, ResourceLimits, RunProgress};
+
+const LOOP_CODE: &str = "
+total = 0
+for i in range(1000):
+    total += i
+total";
+
+fn limits(interval: usize) -> LimitedTracker {
+    LimitedTracker::new(ResourceLimits::new().checkpoint_interval(interval))
+}
+
+/// Runs `code` to completion, serializing and reloading the progress at every checkpoint.
+///
+/// Returns the final result and the number of checkpoints.
+fn run_with_checkpoints(code: &str, interval: usize) -> (MontyObject, usize) {
+    let runner = MontyRun::new(code.to_owned(), "main.py", vec![], vec!["double".to_owned()]).unwrap();
+    let mut progress = runner
+        .start(vec![], limits(interval), &mut PrintWriter::Stdout)
+        .unwrap();
+    let mut checkpoints = 0;
+    loop {
+        match progress {
+            RunProgress::Complete { value: result, .. } => return (result, checkpoints),
+            RunProgress::Checkpoint(state) => {
+                checkpoints += 1;
+                let bytes = RunProgress::Checkpoint(state).dump().unwrap();
+                let state = RunProgress::<LimitedTracker>::load(&bytes)
+                    .unwrap()
+                    .into_checkpoint()
+                    .expect("expected Checkpoint after load");
+                progress = state.run(&mut PrintWriter::Stdout).unwrap();
+            }
+            RunProgress::FunctionCall { args, state, .. } => {
+                let MontyObject::Int(n) = args[0] else {
+                    panic!("unexpected args: {args:?}");
+                };
+                progress = state.run(MontyObject::Int(n * 2), &mut PrintWriter::Stdout).unwrap();
+            }
+            _ => panic!("unexpected progress"),
+        }
+    }
+}
+
+#[test]
+fn checkpoints_are_emitted_periodically() {
+    let (result, checkpoints) = run_with_checkpoints(LOOP_CODE, 100);
+    assert_eq!(result, MontyObject::Int(499_500));
+    assert!(checkpoints >= 10, "expected at least 10 checkpoints, got {checkpoints}");
+}
+
+#[test]
+fn smaller_interval_gives_more_checkpoints() {
+    let (_, coarse) = run_with_checkpoints(LOOP_CODE, 1000);
+    let (_, fine) = run_with_checkpoints(LOOP_CODE, 100);
+    assert!(
+        fine > coarse,
+        "{fine} checkpoints with interval 100, {coarse} with 1000"
+    );
+}
+
+#[test]
+fn every_instruction_checkpoint() {
+    let code = "
+def f(x):
+    return double(x) + 1
+
+[f(i) for i in range(3)]";
+    let (result, checkpoints) = run_with_checkpoints(code, 1);
+    assert_eq!(
+        result,
+        MontyObject::List(vec![MontyObject::Int(1), MontyObject::Int(3), MontyObject::Int(5)])
+    );
+    assert!(checkpoints > 0);
+}
+
+#[test]
+fn no_checkpoints_without_interval() {
+    let runner = MontyRun::new(LOOP_CODE.to_owned(), "main.py", vec![], vec![]).unwrap();
+    let progress = runner
+        .start(
+            vec![],
+            LimitedTracker::new(ResourceLimits::new()),
+            &mut PrintWriter::Stdout,
+        )
+        .unwrap();
+    assert_eq!(progress.into_complete(), Some(MontyObject::Int(499_500)));
+}
+
+#[test]
+fn run_ignores_checkpoint_interval() {
+    let runner = MontyRun::new(LOOP_CODE.to_owned(), "main.py", vec![], vec![]).unwrap();
+    let result = runner.run(vec![], limits(10), &mut PrintWriter::Stdout).unwrap();
+    assert_eq!(result, MontyObject::Int(499_500));
+}
+
+#[test]
+#[should_panic(expected = "checkpoint interval must be greater than zero")]
+fn zero_interval_panics() {
+    let _ = ResourceLimits::new().checkpoint_interval(0);
+}
@@ -0,0 +1,109 @@
+//! Tests for host-provided constants baked in with `MontyRun::new_with_constants`.
+
+use monty::{ExcType, MontyException, MontyObject, MontyRun};
+use num_bigint::BigInt;
+
+fn compile(code: &str, constants: Vec<(&str, MontyObject)>) -> Result<MontyRun, MontyException> {
+    let constants = constants
+        .into_iter()
+        .map(|(name, value)| (name.to_owned(), value))
+        .collect();
+    MontyRun::new_with_constants(code.to_owned(), "main.py", vec![], vec![], constants)
+}
+
+fn run(code: &str, constants: Vec<(&str, MontyObject)>) -> MontyObject {
+    compile(code, constants).unwrap().run_no_limits(vec![]).unwrap()
+}
+
+#[test]
+fn constants_are_visible_everywhere() {
+    let code = "\
+def scaled(x):
+    return x * FACTOR
+
+squares = [n * n for n in range(LIMIT)]
+(scaled(2), squares, (lambda: NAME)(), DATA, HUGE > 2 ** 64)
+";
+    let constants = vec![
+        ("FACTOR", MontyObject::Float(1.5)),
+        ("LIMIT", MontyObject::Int(3)),
+        ("NAME", MontyObject::String("monty".to_owned())),
+        ("DATA", MontyObject::Bytes(b"ab".to_vec())),
+        ("HUGE", MontyObject::BigInt(BigInt::from(2).pow(70))),
+    ];
+    assert_eq!(
+        run(code, constants),
+        MontyObject::Tuple(vec![
+            MontyObject::Float(3.0),
+            MontyObject::List(vec![MontyObject::Int(0), MontyObject::Int(1), MontyObject::Int(4)]),
+            MontyObject::String("monty".to_owned()),
+            MontyObject::Bytes(b"ab".to_vec()),
+            MontyObject::Bool(true),
+        ])
+    );
+}
+
+#[test]
+fn constants_shadow_builtins() {
+    assert_eq!(run("len", vec![("len", MontyObject::Int(3))]), MontyObject::Int(3));
+}
+
+#[test]
+fn calling_a_constant_raises_type_error() {
+    let runner = compile("MAX()", vec![("MAX", MontyObject::Int(100))]).unwrap();
+    let exc = runner.run_no_limits(vec![]).unwrap_err();
+    assert_eq!(exc.exc_type(), ExcType::TypeError);
+    assert_eq!(exc.message(), Some("'int' object is not callable"));
+}
+
+#[test]
+fn rebinding_a_constant_is_a_syntax_error() {
+    let cases = [
+        "MAX = 1",
+        "MAX += 1",
+        "a, MAX = 1, 2",
+        "for MAX in range(3):\n    pass",
+        "def MAX():\n    pass",
+        "def f(MAX):\n    return MAX",
+        "f = lambda MAX: MAX",
+        "def f():\n    MAX = 2",
+        "[MAX for MAX in range(3)]",
+        "import math as MAX",
+        "try:\n    pass\nexcept ValueError as MAX:\n    pass",
+        "(MAX := 3)",
+    ];
+    for code in cases {
+        let exc = compile(code, vec![("MAX", MontyObject::Int(100))]).unwrap_err();
+        assert_eq!(exc.exc_type(), ExcType::SyntaxError, "{code}");
+        assert_eq!(exc.message(), Some("cannot assign to constant 'MAX'"), "{code}");
+    }
+}
+
+#[test]
+fn unsupported_constant_types_are_rejected() {
+    let exc = compile("X", vec![("X", MontyObject::List(vec![]))]).unwrap_err();
+    assert_eq!(exc.exc_type(), ExcType::TypeError);
+    assert_eq!(
+        exc.message(),
+        Some("constant 'X' must be None, bool, int, float, str or bytes, not list")
+    );
+}
+
+#[test]
+fn constants_cannot_also_be_inputs() {
+    let constants = vec![("x".to_owned(), MontyObject::Int(1))];
+    let exc =
+        MontyRun::new_with_constants("x".to_owned(), "main.py", vec!["x".to_owned()], vec![], constants).unwrap_err();
+    assert_eq!(exc.exc_type(), ExcType::ValueError);
+    assert_eq!(
+        exc.message(),
+        Some("constant 'x' is also an input or external function")
+    );
+}
+
+#[test]
+fn constants_survive_dump_and_load() {
+    let runner = compile("MAX * 2", vec![("MAX", MontyObject::Int(21))]).unwrap();
+    let runner = MontyRun::load(&runner.dump().unwrap()).unwrap();
+    assert_eq!(runner.run_no_limits(vec![]).unwrap(), MontyObject::Int(42));
+}
@@ -16,8 +16,9 @@ use std::{
 
 use ahash::AHashMap;
 use monty::{
-    ExcType, ExternalResult, LimitedTracker, MontyException, MontyFuture, MontyObject, MontyRun, OsFunction,
-    PrintWriter, ResourceLimits, RunProgress, dir_stat, file_stat,
+    EntropyPolicy, ExcType, ExternalResult, LimitedTracker, MontyException, MontyFuture, MontyObject, MontyRun,
+    OsFunction, PrintWriter, ResourceLimits, RunProgress, dir_stat, file_stat, named_temporary_file, object,
+    symlink_stat, temporary_directory,
 };
 use pyo3::{prelude::*, types::PyDict};
 use similar::TextDiff;
@@ -322,9 +323,7 @@ fn dispatch_external_call(name: &str, args: Vec<MontyObject>) -> DispatchResult
         }
         "get_list" => {
             assert!(args.is_empty(), "get_list requires no arguments");
-            DispatchResult::Sync(
-                MontyObject::List(vec![MontyObject::Int(1), MontyObject::Int(2), MontyObject::Int(3)]).into(),
-            )
+            DispatchResult::Sync(object!([1, 2, 3]).into())
         }
         "raise_error" => {
             // raise_error(exc_type: str, message: str) -> raises exception
@@ -679,6 +678,10 @@ struct MutableVfs {
     deleted_files: HashSet<String>,
     /// Directories deleted during test execution.
     deleted_dirs: HashSet<String>,
+    /// Symbolic links created during test execution.
+    symlinks: HashMap<String, String>, // link path -> target
+    /// Number of `tempfile` scratch paths handed out, used to name the next one.
+    scratch_count: usize,
 }
 
 thread_local! {
@@ -761,7 +764,7 @@ fn get_virtual_dir_entries(path: &str) -> Option<Vec<String>> {
                 }
             }
         }
-        for dir_path in &vfs.dirs {
+        for dir_path in vfs.dirs.iter().chain(vfs.symlinks.keys()) {
             if dir_path.starts_with(&prefix) {
                 let rest = &dir_path[prefix.len()..];
                 if !rest.contains('/') {
@@ -774,6 +777,82 @@ fn get_virtual_dir_entries(path: &str) -> Option<Vec<String>> {
     })
 }
 
+/// Gets the target of a virtual symlink, if the path is one.
+fn get_virtual_symlink(path: &str) -> Option<String> {
+    MUTABLE_VFS.with(|vfs| vfs.borrow().symlinks.get(path).cloned())
+}
+
+/// Follows virtual symlinks until reaching a path which isn't one.
+fn follow_virtual_symlinks(path: &str) -> String {
+    let mut path = path.to_owned();
+    while let Some(target) = get_virtual_symlink(&path) {
+        path = target;
+    }
+    path
+}
+
+/// Walks a virtual directory tree, like `Path.walk()` without following symlinks.
+///
+/// Returns `(dirpath, dirnames, filenames)` for each directory, parents first when `top_down`.
+fn walk_virtual_dir(path: &str, top_down: bool, out: &mut Vec<(String, Vec<String>, Vec<String>)>) {
+    let mut entries = get_virtual_dir_entries(path).unwrap_or_default();
+    entries.sort();
+    let (dirs, files): (Vec<String>, Vec<String>) = entries.into_iter().partition(|entry| is_virtual_dir(entry));
+    let name = |entry: &String| {
+        entry
+            .rsplit_once('/')
+            .map_or(entry.clone(), |(_, name)| name.to_owned())
+    };
+    let mut this = Some((
+        path.to_owned(),
+        dirs.iter().map(name).collect(),
+        files.iter().map(name).collect(),
+    ));
+    if top_down {
+        out.extend(this.take());
+    }
+    for dir in &dirs {
+        walk_virtual_dir(dir, top_down, out);
+    }
+    out.extend(this);
+}
+
+/// Finds the paths below a virtual directory matching a glob pattern.
+fn glob_virtual_dir(path: &str, pattern: &str) -> Vec<String> {
+    let mut tree = Vec::new();
+    walk_virtual_dir(path, true, &mut tree);
+    let pattern: Vec<&str> = pattern.split('/').collect();
+    tree.into_iter()
+        .flat_map(|(dir, dirs, files)| dirs.into_iter().chain(files).map(move |name| format!("{dir}/{name}")))
+        .filter(|entry| {
+            let relative: Vec<&str> = entry[path.len() + 1..].split('/').collect();
+            glob_match_parts(&pattern, &relative)
+        })
+        .collect()
+}
+
+/// Matches path components against glob pattern components, where `**` matches any number of
+/// components.
+fn glob_match_parts(pattern: &[&str], parts: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => parts.is_empty(),
+        Some((&"**", rest)) => (0..=parts.len()).any(|skip| glob_match_parts(rest, &parts[skip..])),
+        Some((first, rest)) => parts.split_first().is_some_and(|(part, parts)| {
+            glob_match_name(first.as_bytes(), part.as_bytes()) && glob_match_parts(rest, parts)
+        }),
+    }
+}
+
+/// Matches a single path component against a pattern using `*` and `?` wildcards.
+fn glob_match_name(pattern: &[u8], name: &[u8]) -> bool {
+    match pattern.split_first() {
+        None => name.is_empty(),
+        Some((b'*', rest)) => (0..=name.len()).any(|skip| glob_match_name(rest, &name[skip..])),
+        Some((b'?', rest)) => !name.is_empty() && glob_match_name(rest, &name[1..]),
+        Some((c, rest)) => name.first() == Some(c) && glob_match_name(rest, &name[1..]),
+    }
+}
+
 /// Helper to get a boolean kwarg by name.
 fn get_kwarg_bool(kwargs: &[(MontyObject, MontyObject)], name: &str) -> bool {
     for (key, value) in kwargs {
@@ -786,6 +865,74 @@ fn get_kwarg_bool(kwargs: &[(MontyObject, MontyObject)], name: &str) -> bool {
     false
 }
 
+/// Helper to get a boolean kwarg by name, with a default for when it isn't passed.
+fn get_kwarg_bool_or(kwargs: &[(MontyObject, MontyObject)], name: &str, default: bool) -> bool {
+    kwargs
+        .iter()
+        .find(|(key, _)| matches!(key, MontyObject::String(k) if k == name))
+        .map_or(default, |(_, value)| matches!(value, MontyObject::Bool(true)))
+}
+
+/// Extracts a path positional argument.
+fn get_path_arg(args: &[MontyObject], index: usize) -> String {
+    match &args[index] {
+        MontyObject::Path(p) | MontyObject::String(p) => p.clone(),
+        other => panic!("OS call: argument {index} must be path, got {other:?}"),
+    }
+}
+
+/// The `FileNotFoundError` CPython raises for a missing path.
+fn file_not_found(path: &str) -> ExternalResult {
+    MontyException::new(
+        ExcType::FileNotFoundError,
+        Some(format!("[Errno 2] No such file or directory: '{path}'")),
+    )
+    .into()
+}
+
+/// Default directory for `tempfile` scratch space, created when first used.
+const VFS_SCRATCH_DIR: &str = "/virtual/tmp";
+
+/// Creates an empty virtual directory or file for `tempfile`, called with
+/// `(is_dir, suffix, prefix, dir)`.
+///
+/// Names come from a per-test counter rather than CPython's random characters, so they're stable.
+fn create_virtual_scratch(args: &[MontyObject]) -> ExternalResult {
+    let [MontyObject::Bool(is_dir), suffix, prefix, dir] = args else {
+        panic!("tempfile: unexpected args {args:?}");
+    };
+    let text = |arg: &MontyObject, default: &str| match arg {
+        MontyObject::None => default.to_owned(),
+        MontyObject::String(s) | MontyObject::Path(s) => s.clone(),
+        other => panic!("tempfile: unexpected argument {other:?}"),
+    };
+    let dir = text(dir, VFS_SCRATCH_DIR);
+    let count = MUTABLE_VFS.with(|vfs| {
+        let mut vfs = vfs.borrow_mut();
+        vfs.scratch_count += 1;
+        vfs.scratch_count
+    });
+    let path = format!("{dir}/{}{count:08}{}", text(prefix, "tmp"), text(suffix, ""));
+    if dir == VFS_SCRATCH_DIR {
+        MUTABLE_VFS.with(|vfs| vfs.borrow_mut().dirs.insert(dir));
+    } else if !is_virtual_dir(&dir) {
+        return file_not_found(&path);
+    }
+    MUTABLE_VFS.with(|vfs| {
+        let mut vfs = vfs.borrow_mut();
+        if *is_dir {
+            vfs.dirs.insert(path.clone());
+        } else {
+            vfs.files.insert(path.clone(), (Vec::new(), 0o600));
+        }
+    });
+    if *is_dir {
+        temporary_directory(path).into()
+    } else {
+        named_temporary_file(path).into()
+    }
+}
+
 /// Dispatches an OS function call using the virtual filesystem.
 ///
 /// Returns an `ExternalResult` to pass back to the Monty interpreter.
@@ -816,33 +963,41 @@ fn dispatch_os_call(
         return MontyObject::Dict(env_dict.into()).into();
     }
 
+    if function == OsFunction::ScratchPath {
+        return create_virtual_scratch(args);
+    }
+
+    // There is no stdin in tests, so input() always reaches end of input
+    if function == OsFunction::Input {
+        return MontyException::new(ExcType::EOFError, Some("EOF when reading a line".to_owned())).into();
+    }
+
     // Extract path from MontyObject::Path (or String for backwards compatibility)
     let path = match &args[0] {
         MontyObject::Path(p) => p.clone(),
         MontyObject::String(s) => s.clone(),
         other => panic!("OS call: first arg must be path, got {other:?}"),
     };
+    // Most calls follow symlinks, only `is_symlink`, `lstat`, `readlink` and `unlink` don't
+    let resolved = follow_virtual_symlinks(&path);
 
     match function {
-        OsFunction::GetEnviron => unreachable!("handled above"),
+        OsFunction::GetEnviron | OsFunction::Input | OsFunction::ScratchPath => unreachable!("handled above"),
         OsFunction::Exists => {
-            let exists = get_virtual_file(&path).is_some() || is_virtual_dir(&path);
+            let exists = get_virtual_file(&resolved).is_some() || is_virtual_dir(&resolved);
             MontyObject::Bool(exists).into()
         }
         OsFunction::IsFile => {
-            let is_file = get_virtual_file(&path).is_some();
+            let is_file = get_virtual_file(&resolved).is_some();
             MontyObject::Bool(is_file).into()
         }
         OsFunction::IsDir => {
-            let is_dir = is_virtual_dir(&path);
+            let is_dir = is_virtual_dir(&resolved);
             MontyObject::Bool(is_dir).into()
         }
-        OsFunction::IsSymlink => {
-            // Virtual filesystem doesn't have symlinks
-            MontyObject::Bool(false).into()
-        }
+        OsFunction::IsSymlink => MontyObject::Bool(get_virtual_symlink(&path).is_some()).into(),
         OsFunction::ReadText => {
-            if let Some(file) = get_virtual_file(&path) {
+            if let Some(file) = get_virtual_file(&resolved) {
                 match std::str::from_utf8(&file.content) {
                     Ok(text) => MontyObject::String(text.to_owned()).into(),
                     Err(_) => MontyException::new(
@@ -860,7 +1015,7 @@ fn dispatch_os_call(
             }
         }
         OsFunction::ReadBytes => {
-            if let Some(file) = get_virtual_file(&path) {
+            if let Some(file) = get_virtual_file(&resolved) {
                 MontyObject::Bytes(file.content).into()
             } else {
                 MontyException::new(
@@ -870,10 +1025,12 @@ fn dispatch_os_call(
                 .into()
             }
         }
-        OsFunction::Stat => {
-            if let Some(file) = get_virtual_file(&path) {
+        OsFunction::Stat | OsFunction::Lstat => {
+            if function == OsFunction::Lstat && get_virtual_symlink(&path).is_some() {
+                symlink_stat(0o777, VFS_MTIME).into()
+            } else if let Some(file) = get_virtual_file(&resolved) {
                 file_stat(file.mode, file.content.len() as i64, VFS_MTIME).into()
-            } else if is_virtual_dir(&path) {
+            } else if is_virtual_dir(&resolved) {
                 dir_stat(0o755, VFS_MTIME).into()
             } else {
                 MontyException::new(
@@ -990,7 +1147,10 @@ fn dispatch_os_call(
         }
         OsFunction::Unlink => {
             // args[0] is path
-            if get_virtual_file(&path).is_some() {
+            if get_virtual_symlink(&path).is_some() {
+                MUTABLE_VFS.with(|vfs| vfs.borrow_mut().symlinks.remove(&path));
+                MontyObject::None.into()
+            } else if get_virtual_file(&path).is_some() {
                 MUTABLE_VFS.with(|vfs| {
                     let mut vfs = vfs.borrow_mut();
                     vfs.files.remove(&path);
@@ -1056,6 +1216,114 @@ fn dispatch_os_call(
                 .into()
             }
         }
+        OsFunction::Copy => {
+            // args[0] is src path, args[1] is dest path; the mode is copied like `shutil.copy`
+            let dest = get_path_arg(args, 1);
+            if let Some(file) = get_virtual_file(&resolved) {
+                MUTABLE_VFS.with(|vfs| {
+                    let mut vfs = vfs.borrow_mut();
+                    vfs.deleted_files.remove(&dest);
+                    vfs.files.insert(dest.clone(), (file.content, file.mode));
+                });
+                MontyObject::Path(dest).into()
+            } else if is_virtual_dir(&resolved) {
+                MontyException::new(
+                    ExcType::IsADirectoryError,
+                    Some(format!("[Errno 21] Is a directory: '{path}'")),
+                )
+                .into()
+            } else {
+                file_not_found(&path)
+            }
+        }
+        OsFunction::Touch => {
+            if get_virtual_file(&resolved).is_some() || is_virtual_dir(&resolved) {
+                if get_kwarg_bool_or(kwargs, "exist_ok", true) {
+                    MontyObject::None.into()
+                } else {
+                    MontyException::new(
+                        ExcType::FileExistsError,
+                        Some(format!("[Errno 17] File exists: '{path}'")),
+                    )
+                    .into()
+                }
+            } else if std::path::Path::new(&resolved)
+                .parent()
+                .is_some_and(|parent| is_virtual_dir(&parent.to_string_lossy()))
+            {
+                MUTABLE_VFS.with(|vfs| {
+                    let mut vfs = vfs.borrow_mut();
+                    vfs.deleted_files.remove(&resolved);
+                    vfs.files.insert(resolved, (Vec::new(), 0o644));
+                });
+                MontyObject::None.into()
+            } else {
+                file_not_found(&path)
+            }
+        }
+        OsFunction::Chmod => {
+            // Directories keep their fixed mode, the virtual filesystem only stores file modes
+            let MontyObject::Int(mode) = args[1] else {
+                panic!("chmod: second arg must be int, got {:?}", args[1]);
+            };
+            if let Some(file) = get_virtual_file(&resolved) {
+                MUTABLE_VFS.with(|vfs| vfs.borrow_mut().files.insert(resolved, (file.content, mode)));
+                MontyObject::None.into()
+            } else if is_virtual_dir(&resolved) {
+                MontyObject::None.into()
+            } else {
+                file_not_found(&path)
+            }
+        }
+        OsFunction::Glob | OsFunction::Rglob => {
+            let pattern = String::try_from(&args[1]).expect("glob: second arg must be string");
+            let pattern = if function == OsFunction::Rglob {
+                format!("**/{pattern}")
+            } else {
+                pattern
+            };
+            let matches = if is_virtual_dir(&resolved) {
+                glob_virtual_dir(&resolved, &pattern)
+            } else {
+                Vec::new()
+            };
+            MontyObject::List(matches.into_iter().map(MontyObject::Path).collect()).into()
+        }
+        OsFunction::Walk => {
+            let mut tree = Vec::new();
+            if is_virtual_dir(&resolved) {
+                walk_virtual_dir(&resolved, get_kwarg_bool_or(kwargs, "top_down", true), &mut tree);
+            }
+            let names = |names: Vec<String>| MontyObject::List(names.into_iter().map(MontyObject::String).collect());
+            let list = tree
+                .into_iter()
+                .map(|(dir, dirs, files)| MontyObject::Tuple(vec![MontyObject::Path(dir), names(dirs), names(files)]))
+                .collect();
+            MontyObject::List(list).into()
+        }
+        OsFunction::Readlink => {
+            if let Some(target) = get_virtual_symlink(&path) {
+                MontyObject::Path(target).into()
+            } else if get_virtual_file(&path).is_some() || is_virtual_dir(&path) {
+                MontyException::new(ExcType::OSError, Some(format!("[Errno 22] Invalid argument: '{path}'"))).into()
+            } else {
+                file_not_found(&path)
+            }
+        }
+        OsFunction::SymlinkTo => {
+            // args[0] is the link path, args[1] is its target, which doesn't need to exist
+            let target = get_path_arg(args, 1);
+            if get_virtual_symlink(&path).is_some() || get_virtual_file(&path).is_some() || is_virtual_dir(&path) {
+                MontyException::new(
+                    ExcType::FileExistsError,
+                    Some(format!("[Errno 17] File exists: '{path}'")),
+                )
+                .into()
+            } else {
+                MUTABLE_VFS.with(|vfs| vfs.borrow_mut().symlinks.insert(path, target));
+                MontyObject::None.into()
+            }
+        }
     }
 }
 
@@ -1167,7 +1435,9 @@ fn try_run_test(path: &Path, code: &str, expectation: &Expectation) -> Result<()
 
     match MontyRun::new(code.to_owned(), &test_name, vec![], vec![]) {
         Ok(ex) => {
-            let limits = ResourceLimits::new().max_recursion_depth(Some(TEST_RECURSION_LIMIT));
+            let limits = ResourceLimits::new()
+                .max_recursion_depth(Some(TEST_RECURSION_LIMIT))
+                .entropy(EntropyPolicy::Os);
             let result = ex.run(vec![], LimitedTracker::new(limits), &mut PrintWriter::Stdout);
             match result {
                 Ok(obj) => match expectation {
@@ -1436,13 +1706,15 @@ fn try_run_iter_test(path: &Path, code: &str, expectation: &Expectation) -> Resu
 ///
 /// When `ref-count-panic` feature is NOT enabled, this function also tests
 /// serialization round-trips by dumping and loading the execution state at
-/// each external function call boundary.
+/// each external function call boundary, checking the loaded state with `state_eq`.
 ///
 /// Supports both synchronous and asynchronous external functions:
 /// - Sync functions: result is passed immediately via `state.run()`
 /// - Async functions: `state.run_pending()` creates a future, resolved via `ResolveFutures`
 fn run_iter_loop(exec: MontyRun) -> Result<MontyObject, MontyException> {
-    let limits = ResourceLimits::new().max_recursion_depth(Some(TEST_RECURSION_LIMIT));
+    let limits = ResourceLimits::new()
+        .max_recursion_depth(Some(TEST_RECURSION_LIMIT))
+        .entropy(EntropyPolicy::Os);
     let mut progress = exec.start(vec![], LimitedTracker::new(limits), &mut PrintWriter::Stdout)?;
 
     // Track pending async calls: (call_id, result_value)
@@ -1454,11 +1726,16 @@ fn run_iter_loop(exec: MontyRun) -> Result<MontyObject, MontyException> {
         #[cfg(not(feature = "ref-count-panic"))]
         {
             let bytes = progress.dump().expect("failed to dump RunProgress");
-            progress = RunProgress::load(&bytes).expect("failed to load RunProgress");
+            let loaded = RunProgress::load(&bytes).expect("failed to load RunProgress");
+            assert!(
+                loaded.state_eq(&progress),
+                "execution state changed in serialization round-trip"
+            );
+            progress = loaded;
         }
 
         match progress {
-            RunProgress::Complete(result) => return Ok(result),
+            RunProgress::Complete { value: result, .. } => return Ok(result),
             RunProgress::FunctionCall {
                 function_name,
                 args,
@@ -1518,6 +1795,13 @@ fn run_iter_loop(exec: MontyRun) -> Result<MontyObject, MontyException> {
                 let result = dispatch_os_call(function, &args, &kwargs);
                 progress = state.run(result, &mut PrintWriter::Stdout)?;
             }
+            RunProgress::Yield { state, .. } => {
+                progress = state.run(MontyObject::None, &mut PrintWriter::Stdout)?;
+            }
+            RunProgress::Checkpoint(state) => {
+                progress = state.run(&mut PrintWriter::Stdout)?;
+            }
+            RunProgress::FunctionCallBatch { .. } => panic!("call batching is not enabled"),
         }
     }
 }
@@ -1753,11 +2037,14 @@ fn try_run_cpython_test(
         // Execute statements at module level
         let globals = PyDict::new(py);
 
-        // For iter mode tests, inject external function implementations into globals
+        // For iter mode tests, inject external function implementations into globals, along with
+        // the builtins that give test code the virtual `tempfile` module
         if iter_mode {
             let ext_funcs_cstr = CString::new(ITER_EXT_FUNCTIONS_PYTHON).expect("Invalid C string in ext funcs");
             py.run(&ext_funcs_cstr, Some(&globals), None)
                 .expect("Failed to define external functions for iter mode");
+            py.run(c"globals().update(ITER_MODE_GLOBALS)", Some(&globals), None)
+                .expect("Failed to apply iter mode globals");
         }
 
         // Run the statements
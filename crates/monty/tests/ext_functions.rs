@@ -0,0 +1,146 @@
+//! Tests for registering typed Rust functions with `ExternalFunctions`.
+
+use monty::{
+    ExcType, ExternalFunctions, ExternalResult, MontyException, MontyObject, MontyRun, NoLimitTracker, NoOsAccess,
+    OsFunction, PrintWriter,
+};
+
+fn functions() -> ExternalFunctions {
+    ExternalFunctions::new()
+        .register("add", |a: i64, b: i64| a + b)
+        .register("scale", |values: Vec<f64>, factor: f64| {
+            values.into_iter().map(|v| v * factor).collect::<Vec<_>>()
+        })
+        .register("greet", |name: Option<String>| {
+            format!("hello {}", name.unwrap_or_else(|| "world".to_owned()))
+        })
+        .register("log", |_message: String| {})
+        .register("check", |value: i64| {
+            if value < 0 {
+                Err(MontyException::new(ExcType::ValueError, Some("negative".to_owned())))
+            } else {
+                Ok(value)
+            }
+        })
+}
+
+fn run(code: &str) -> Result<MontyObject, MontyException> {
+    let functions = functions();
+    let runner = MontyRun::new(code.to_owned(), "main.py", vec![], functions.names()).unwrap();
+    let progress = runner.start(vec![], NoLimitTracker, &mut PrintWriter::Stdout).unwrap();
+    functions.run(progress, &mut PrintWriter::Stdout)
+}
+
+#[test]
+fn converts_arguments_and_return_values() {
+    assert_eq!(run("add(1, 2)").unwrap(), MontyObject::Int(3));
+    assert_eq!(
+        run("scale([1.0, 2.5], 2.0)").unwrap(),
+        MontyObject::List(vec![MontyObject::Float(2.0), MontyObject::Float(5.0)])
+    );
+    assert_eq!(
+        run("greet('monty')").unwrap(),
+        MontyObject::String("hello monty".to_owned())
+    );
+    assert_eq!(
+        run("greet(None)").unwrap(),
+        MontyObject::String("hello world".to_owned())
+    );
+    assert_eq!(run("log('x')").unwrap(), MontyObject::None);
+}
+
+#[test]
+fn errors_raise_inside_the_sandbox() {
+    let exc = run("check(-1)").unwrap_err();
+    assert_eq!(exc.exc_type(), ExcType::ValueError);
+    assert_eq!(exc.message(), Some("negative"));
+
+    let code = "try:\n    check(-1)\nexcept ValueError as e:\n    r = str(e)\nr";
+    assert_eq!(run(code).unwrap(), MontyObject::String("negative".to_owned()));
+}
+
+#[test]
+fn wrong_arity_is_a_type_error() {
+    let exc = run("add(1)").unwrap_err();
+    assert_eq!(exc.exc_type(), ExcType::TypeError);
+    assert_eq!(exc.message(), Some("add expected 2 arguments, got 1"));
+
+    let exc = run("check()").unwrap_err();
+    assert_eq!(exc.message(), Some("check expected 1 argument, got 0"));
+}
+
+#[test]
+fn wrong_type_is_a_type_error() {
+    let exc = run("add(1, 'two')").unwrap_err();
+    assert_eq!(exc.exc_type(), ExcType::TypeError);
+    assert_eq!(exc.message(), Some("add() argument 2 must be int, not str"));
+
+    let exc = run("scale([1.0, 'x'], 2.0)").unwrap_err();
+    assert_eq!(exc.message(), Some("scale() argument 1 must be float, not str"));
+
+    let code = "try:\n    add('1', 2)\nexcept TypeError:\n    r = 'caught'\nr";
+    assert_eq!(run(code).unwrap(), MontyObject::String("caught".to_owned()));
+}
+
+#[test]
+fn keyword_arguments_are_rejected() {
+    let exc = run("add(1, b=2)").unwrap_err();
+    assert_eq!(exc.exc_type(), ExcType::TypeError);
+    assert_eq!(exc.message(), Some("add() takes no keyword arguments"));
+}
+
+#[test]
+fn call_returns_none_for_unknown_functions() {
+    let functions = functions();
+    assert!(functions.call("missing", vec![], vec![]).is_none());
+    assert!(functions.contains("add"));
+    assert_eq!(functions.names(), vec!["add", "scale", "greet", "log", "check"]);
+}
+
+#[test]
+fn os_calls_raise_not_implemented() {
+    let exc = run("from pathlib import Path\nPath('x').exists()").unwrap_err();
+    assert_eq!(exc.exc_type(), ExcType::NotImplementedError);
+}
+
+#[test]
+fn run_with_handlers_dispatches_to_closures() {
+    let code = "from pathlib import Path\nr = Path('a.txt').exists()\n(lookup('x'), r)";
+    let runner = MontyRun::new(code.to_owned(), "main.py", vec![], vec!["lookup".to_owned()]).unwrap();
+    let mut calls = Vec::new();
+    let ext = |name: &str, args: Vec<MontyObject>, _kwargs: Vec<(MontyObject, MontyObject)>| {
+        calls.push(name.to_owned());
+        ExternalResult::Return(MontyObject::List(args))
+    };
+    let os = |function: OsFunction, _args: Vec<MontyObject>, _kwargs: Vec<(MontyObject, MontyObject)>| {
+        assert_eq!(function, OsFunction::Exists);
+        ExternalResult::Return(MontyObject::Bool(true))
+    };
+    let result = runner
+        .run_with_handlers(vec![], NoLimitTracker, &mut PrintWriter::Stdout, ext, os)
+        .unwrap();
+    assert_eq!(
+        result,
+        MontyObject::Tuple(vec![
+            MontyObject::List(vec![MontyObject::String("x".to_owned())]),
+            MontyObject::Bool(true),
+        ])
+    );
+    assert_eq!(calls, vec!["lookup"]);
+}
+
+#[test]
+fn run_with_handlers_accepts_registry() {
+    let functions = functions();
+    let runner = MontyRun::new("add(add(1, 2), 3)".to_owned(), "main.py", vec![], functions.names()).unwrap();
+    let result = runner
+        .run_with_handlers(vec![], NoLimitTracker, &mut PrintWriter::Stdout, &functions, NoOsAccess)
+        .unwrap();
+    assert_eq!(result, MontyObject::Int(6));
+
+    // the runner is reusable since `run_with_handlers` borrows it
+    let result = runner
+        .run_with_handlers(vec![], NoLimitTracker, &mut PrintWriter::Stdout, functions, NoOsAccess)
+        .unwrap();
+    assert_eq!(result, MontyObject::Int(6));
+}
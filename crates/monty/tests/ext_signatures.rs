@@ -0,0 +1,124 @@
+use monty::{
+    ExcType, ExtFunctionSignature, ExtParam, ExtParamKind, MontyObject, MontyRun, NoLimitTracker, PrintWriter,
+    RunProgress,
+};
+
+/// `fetch(url: str, /, timeout: float = ..., *, retries: int) -> bytes`
+fn fetch_signature() -> ExtFunctionSignature {
+    ExtFunctionSignature::new("fetch")
+        .param(ExtParam::new("url", ExtParamKind::PositionalOnly).type_hint("str"))
+        .param(
+            ExtParam::new("timeout", ExtParamKind::PositionalOrKeyword)
+                .type_hint("float")
+                .with_default(),
+        )
+        .param(ExtParam::new("retries", ExtParamKind::KeywordOnly).type_hint("int"))
+        .return_type("bytes")
+}
+
+/// Creates a runner with `fetch` registered, and starts it.
+fn start(code: &str) -> Result<RunProgress<NoLimitTracker>, monty::MontyException> {
+    let mut runner = MontyRun::new(code.to_owned(), "test.py", vec![], vec!["fetch".to_owned()]).unwrap();
+    runner.register_external_signature(fetch_signature()).unwrap();
+    runner.start(vec![], NoLimitTracker, &mut PrintWriter::Stdout)
+}
+
+/// Runs code that is expected to raise inside the sandbox, returning the exception message.
+fn type_error_message(code: &str) -> String {
+    let exc = start(code).expect_err("call should raise before suspending");
+    assert_eq!(exc.exc_type(), ExcType::TypeError);
+    exc.message().unwrap().to_owned()
+}
+
+#[test]
+fn valid_call_suspends() {
+    let progress = start("fetch('a', 1.5, retries=2)").unwrap();
+    let (name, args, kwargs, ..) = progress.into_function_call().expect("external call");
+    assert_eq!(name, "fetch");
+    assert_eq!(args, vec![MontyObject::String("a".to_owned()), MontyObject::Float(1.5)]);
+    assert_eq!(
+        kwargs,
+        vec![(MontyObject::String("retries".to_owned()), MontyObject::Int(2))]
+    );
+}
+
+#[test]
+fn missing_keyword_only() {
+    assert_eq!(
+        type_error_message("fetch('a')"),
+        "fetch() missing 1 required keyword-only argument: 'retries'"
+    );
+}
+
+#[test]
+fn missing_positional() {
+    assert_eq!(
+        type_error_message("fetch(retries=1)"),
+        "fetch() missing 1 required positional argument: 'url'"
+    );
+}
+
+#[test]
+fn too_many_positional() {
+    assert_eq!(
+        type_error_message("fetch('a', 1.0, 2, retries=1)"),
+        "fetch() takes from 1 to 2 positional arguments but 3 positional arguments (and 1 keyword-only argument) were given"
+    );
+}
+
+#[test]
+fn unexpected_keyword() {
+    assert_eq!(
+        type_error_message("fetch('a', retries=1, verbose=True)"),
+        "fetch() got an unexpected keyword argument 'verbose'"
+    );
+}
+
+#[test]
+fn positional_only_as_keyword() {
+    assert_eq!(
+        type_error_message("fetch(url='a', retries=1)"),
+        "fetch() got some positional-only arguments passed as keyword arguments: 'url'"
+    );
+}
+
+#[test]
+fn multiple_values() {
+    assert_eq!(
+        type_error_message("fetch('a', 1.0, timeout=2.0, retries=1)"),
+        "fetch() got multiple values for argument 'timeout'"
+    );
+}
+
+#[test]
+fn error_is_catchable_in_sandbox() {
+    let progress = start("try:\n    fetch()\nexcept TypeError as e:\n    r = str(e)\nr").unwrap();
+    assert_eq!(
+        progress.into_complete(),
+        Some(MontyObject::String(
+            "fetch() missing 1 required positional argument: 'url'".to_owned()
+        ))
+    );
+}
+
+#[test]
+fn unknown_function_rejected() {
+    let mut runner = MontyRun::new("1".to_owned(), "test.py", vec![], vec![]).unwrap();
+    let exc = runner.register_external_signature(fetch_signature()).unwrap_err();
+    assert_eq!(exc.exc_type(), ExcType::ValueError);
+    assert_eq!(exc.message(), Some("'fetch' is not an external function"));
+}
+
+#[test]
+fn type_stub() {
+    assert_eq!(
+        fetch_signature().type_stub(),
+        "def fetch(url: str, /, timeout: float = ..., *, retries: int) -> bytes: ..."
+    );
+    let variadic = ExtFunctionSignature::new("log")
+        .param(ExtParam::new("args", ExtParamKind::VarPositional))
+        .param(ExtParam::new("level", ExtParamKind::KeywordOnly).with_default())
+        .param(ExtParam::new("extra", ExtParamKind::VarKeyword))
+        .is_async();
+    assert_eq!(variadic.type_stub(), "async def log(*args, level = ..., **extra): ...");
+}
@@ -0,0 +1,82 @@
+#![cfg(feature = "heap-validate")]
+//! Tests running code with heap validation before every instruction.
+//!
+//! Validation panics on the first inconsistency, so these tests pass as long as the VM keeps
+//! refcounts, references and cached hashes consistent.
+
+use monty::{
+    HeapValidator, LimitedTracker, MontyObject, MontyRun, PrintWriter, ResourceLimits, ResourceTracker, RunProgress,
+};
+
+fn run_validated(code: &str) -> MontyObject {
+    let runner = MontyRun::new(code.to_owned(), "main.py", vec![], vec![]).unwrap();
+    let tracker = LimitedTracker::new(ResourceLimits::new()).chain(HeapValidator);
+    runner.run(vec![], tracker, &mut PrintWriter::Stdout).unwrap()
+}
+
+#[test]
+fn containers_and_hashes() {
+    let code = "
+d = {(1, 'a'): [1, 2], 'key': {'nested': (3, 4)}}
+s = {frozenset([1, 2]), (5, 6), 'x' * 3}
+d[(1, 'a')].append(d['key'])
+del d['key']
+len(d) + len(s)";
+    assert_eq!(run_validated(code), MontyObject::Int(4));
+}
+
+#[test]
+fn closures_exceptions_and_cycles() {
+    let code = "
+def counter():
+    count = 0
+    def inc():
+        nonlocal count
+        count += 1
+        return count
+    return inc
+
+inc = counter()
+inc()
+try:
+    raise ValueError([inc()])
+except ValueError as e:
+    err = e
+items = []
+items.append(items)
+sorted([3, 1, 2], key=lambda x: -x)[0] + inc()";
+    assert_eq!(run_validated(code), MontyObject::Int(6));
+}
+
+#[test]
+fn suspension_points() {
+    let code = "
+data = [fetch([i]) for i in range(3)]
+{'total': sum(data), 'items': data}";
+    let runner = MontyRun::new(code.to_owned(), "main.py", vec![], vec!["fetch".to_owned()]).unwrap();
+    let tracker = LimitedTracker::new(ResourceLimits::new()).chain(HeapValidator);
+    let mut progress = runner.start(vec![], tracker, &mut PrintWriter::Stdout).unwrap();
+    let result = loop {
+        match progress {
+            RunProgress::Complete { value: result, .. } => break result,
+            RunProgress::FunctionCall { args, state, .. } => {
+                let MontyObject::List(items) = &args[0] else {
+                    panic!("unexpected args {args:?}");
+                };
+                let MontyObject::Int(i) = items[0] else {
+                    panic!("unexpected args {args:?}");
+                };
+                progress = state.run(MontyObject::Int(i * 10), &mut PrintWriter::Stdout).unwrap();
+            }
+            _ => panic!("unexpected progress"),
+        }
+    };
+    let MontyObject::Dict(pairs) = result else {
+        panic!("expected dict, got {result:?}");
+    };
+    let pairs: Vec<(MontyObject, MontyObject)> = pairs.into_iter().collect();
+    assert_eq!(
+        pairs[0],
+        (MontyObject::String("total".to_owned()), MontyObject::Int(30))
+    );
+}
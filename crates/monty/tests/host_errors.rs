@@ -0,0 +1,110 @@
+use monty::{ExcType, HostError, MontyException, MontyObject, MontyRun, NoLimitTracker, PrintWriter, RunProgress};
+
+/// Stand-in for an embedder's exception object, e.g. a `PyErr` or a JS `Error` handle.
+#[derive(Debug, PartialEq)]
+struct OriginalError(u32);
+
+/// The exception an embedder would build when its `fetch` implementation raised.
+fn host_exception() -> MontyException {
+    MontyException::new(ExcType::ValueError, Some("bad url".to_owned())).with_host_error(
+        HostError::new("InvalidURL", Some("bad url".to_owned()))
+            .with_traceback(
+                "Traceback (most recent call last):\n  File \"host.py\", line 3, in fetch\nInvalidURL: bad url",
+            )
+            .with_original(OriginalError(42)),
+    )
+}
+
+/// Runs `code` until its first `fetch()` call, fails that call with [`host_exception`],
+/// and returns the exception that escapes the sandbox.
+fn run_with_failing_fetch(code: &str) -> MontyException {
+    let runner = MontyRun::new(code.to_owned(), "test.py", vec![], vec!["fetch".to_owned()]).unwrap();
+    let progress = runner.start(vec![], NoLimitTracker, &mut PrintWriter::Stdout).unwrap();
+    let (name, .., state) = progress.into_function_call().expect("external call");
+    assert_eq!(name, "fetch");
+    state
+        .run(host_exception(), &mut PrintWriter::Stdout)
+        .expect_err("host exception should propagate")
+}
+
+#[test]
+fn unhandled_host_error_is_preserved() {
+    let exc = run_with_failing_fetch("fetch('x')");
+    assert_eq!(exc.exc_type(), ExcType::ValueError);
+    let host_error = exc.host_error().expect("host error attached");
+    assert_eq!(host_error.class_name(), "InvalidURL");
+    assert_eq!(host_error.message(), Some("bad url"));
+    assert!(host_error.traceback().unwrap().ends_with("InvalidURL: bad url"));
+    assert_eq!(host_error.original::<OriginalError>(), Some(&OriginalError(42)));
+    assert_eq!(host_error.original::<String>(), None);
+}
+
+#[test]
+fn reraised_host_error_is_preserved() {
+    let code = "
+try:
+    fetch('x')
+except ValueError as e:
+    err = e
+raise err
+";
+    let exc = run_with_failing_fetch(code);
+    let host_error = exc.host_error().expect("host error attached");
+    assert_eq!(host_error.original::<OriginalError>(), Some(&OriginalError(42)));
+}
+
+#[test]
+fn new_exception_has_no_host_error() {
+    let code = "
+try:
+    fetch('x')
+except ValueError:
+    raise TypeError('replaced')
+";
+    let exc = run_with_failing_fetch(code);
+    assert_eq!(exc.exc_type(), ExcType::TypeError);
+    assert!(exc.host_error().is_none());
+}
+
+#[test]
+fn handled_host_error_does_not_escape() {
+    let code = "
+try:
+    fetch('x')
+except ValueError as e:
+    result = str(e)
+result
+";
+    let runner = MontyRun::new(code.to_owned(), "test.py", vec![], vec!["fetch".to_owned()]).unwrap();
+    let progress = runner.start(vec![], NoLimitTracker, &mut PrintWriter::Stdout).unwrap();
+    let (.., state) = progress.into_function_call().unwrap();
+    let progress = state.run(host_exception(), &mut PrintWriter::Stdout).unwrap();
+    assert_eq!(
+        progress.into_complete(),
+        Some(MontyObject::String("bad url".to_owned()))
+    );
+}
+
+#[test]
+fn serialized_snapshot_keeps_description_only() {
+    let code = "
+try:
+    fetch('x')
+except ValueError as e:
+    err = e
+fetch('y')
+raise err
+";
+    let runner = MontyRun::new(code.to_owned(), "test.py", vec![], vec!["fetch".to_owned()]).unwrap();
+    let progress = runner.start(vec![], NoLimitTracker, &mut PrintWriter::Stdout).unwrap();
+    let (.., state) = progress.into_function_call().unwrap();
+    let progress = state.run(host_exception(), &mut PrintWriter::Stdout).unwrap();
+
+    // the exception is stored in `err` while suspended at the second call
+    let progress = RunProgress::<NoLimitTracker>::load(&progress.dump().unwrap()).unwrap();
+    let (.., state) = progress.into_function_call().unwrap();
+    let exc = state.run(MontyObject::None, &mut PrintWriter::Stdout).unwrap_err();
+    let host_error = exc.host_error().expect("host error survives serialization");
+    assert_eq!(host_error.class_name(), "InvalidURL");
+    assert_eq!(host_error.original::<OriginalError>(), None);
+}
@@ -3,6 +3,8 @@
 //! These tests verify that `MontyObject` inputs are correctly converted to `Object`
 //! and can be used in Python code execution.
 
+use std::{collections::HashSet, sync::Arc};
+
 use indexmap::IndexMap;
 use monty::{ExcType, MontyObject, MontyRun};
 
@@ -147,6 +149,39 @@ fn input_dict_get() {
     assert_eq!(result, MontyObject::Int(42));
 }
 
+#[test]
+fn input_shared_string() {
+    let ex = MontyRun::new("x.upper()".to_owned(), "test.py", vec!["x".to_owned()], vec![]).unwrap();
+    let text: Arc<str> = Arc::from("hello");
+    let result = ex.run_no_limits(vec![MontyObject::from(text.clone())]).unwrap();
+    assert_eq!(result, MontyObject::String("HELLO".to_string()));
+    // the interpreter's reference is released once the run completes
+    assert_eq!(Arc::strong_count(&text), 1);
+}
+
+#[test]
+fn input_shared_string_mutated() {
+    // in-place concatenation copies the buffer rather than modifying the embedder's string
+    let ex = MontyRun::new("x += '!'\nx".to_owned(), "test.py", vec!["x".to_owned()], vec![]).unwrap();
+    let text: Arc<str> = Arc::from("hello");
+    let result = ex.run_no_limits(vec![MontyObject::from(text.clone())]).unwrap();
+    assert_eq!(result, MontyObject::String("hello!".to_string()));
+    assert_eq!(&*text, "hello");
+}
+
+#[test]
+fn shared_string_equals_string() {
+    let shared = MontyObject::from(Arc::<str>::from("key"));
+    let owned = MontyObject::String("key".to_string());
+    assert_eq!(shared, owned);
+    assert_eq!(shared.type_name(), "str");
+    assert_eq!(shared.py_repr(), "'key'");
+
+    let mut set = HashSet::new();
+    set.insert(owned);
+    assert!(set.contains(&shared));
+}
+
 // === Multiple Inputs ===
 
 #[test]
@@ -431,3 +466,46 @@ double(2)
     let result = ex.run_no_limits(vec![MontyObject::Int(7)]).unwrap();
     assert_eq!(result, MontyObject::Int(4));
 }
+
+// === Module Dunder Tests ===
+
+#[test]
+fn module_dunders_after_inputs() {
+    let ex = MontyRun::new(
+        "(x, __name__, __file__, __doc__)".to_owned(),
+        "script.py",
+        vec!["x".to_owned()],
+        vec![],
+    )
+    .unwrap();
+    let result = ex.run_no_limits(vec![MontyObject::Int(1)]).unwrap();
+    assert_eq!(
+        result,
+        MontyObject::Tuple(vec![
+            MontyObject::Int(1),
+            MontyObject::String("__main__".to_owned()),
+            MontyObject::String("script.py".to_owned()),
+            MontyObject::None,
+        ])
+    );
+}
+
+#[test]
+fn module_name_configured() {
+    let code = "
+if __name__ == '__main__':
+    x = 'script'
+else:
+    x = __name__
+x
+";
+    let ex = MontyRun::new(code.to_owned(), "test.py", vec![], vec![])
+        .unwrap()
+        .with_module_name("my_module");
+    let result = ex.run_no_limits(vec![]).unwrap();
+    assert_eq!(result, MontyObject::String("my_module".to_owned()));
+
+    let loaded = MontyRun::load(&ex.dump().unwrap()).unwrap();
+    let result = loaded.run_no_limits(vec![]).unwrap();
+    assert_eq!(result, MontyObject::String("my_module".to_owned()));
+}
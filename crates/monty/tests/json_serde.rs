@@ -2,8 +2,10 @@
 //!
 //! `MontyObject` uses derived serde with externally tagged enum format.
 //! This means each variant is wrapped in an object with the variant name as key.
+//! `to_json_value()` and `from_json_value()` use the natural JSON mapping instead.
 
 use monty::{ExcType, MontyObject, MontyRun};
+use serde_json::json;
 
 // === JSON Serialization Tests ===
 
@@ -60,7 +62,23 @@ fn json_output_tuple() {
 fn json_output_bytes() {
     let ex = MontyRun::new("b'hi'".to_owned(), "test.py", vec![], vec![]).unwrap();
     let result = ex.run_no_limits(vec![]).unwrap();
-    assert_eq!(serde_json::to_string(&result).unwrap(), r#"{"Bytes":[104,105]}"#);
+    assert_eq!(serde_json::to_string(&result).unwrap(), r#"{"Bytes":"aGk="}"#);
+}
+
+#[test]
+fn json_bytes_base64_roundtrip() {
+    for len in 0..6 {
+        let obj = MontyObject::Bytes((0..len).map(|i| 250 + i).collect());
+        let json = serde_json::to_string(&obj).unwrap();
+        let parsed: MontyObject = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, obj, "{json}");
+    }
+    assert_eq!(
+        serde_json::to_string(&MontyObject::Bytes(vec![0xfb, 0xff, 0xbf])).unwrap(),
+        r#"{"Bytes":"+/+/"}"#
+    );
+    let err = serde_json::from_str::<MontyObject>(r#"{"Bytes":"a$=="}"#).unwrap_err();
+    assert!(err.to_string().contains("invalid base64"), "{err}");
 }
 
 #[test]
@@ -189,6 +207,125 @@ fn json_roundtrip_empty() {
     assert_eq!(serde_json::to_string(&dict).unwrap(), r#"{"Dict":[]}"#);
 }
 
+// === Natural JSON Tests ===
+
+#[test]
+fn natural_json_output() {
+    let ex = MontyRun::new(
+        "{'a': [1, 2.5, 'x', None, True], 'b': (1, b'hi'), 'c': {1: 2}, 'd': ..., 'e': 2**70, 'f': float('nan')}"
+            .to_owned(),
+        "test.py",
+        vec![],
+        vec![],
+    )
+    .unwrap();
+    let result = ex.run_no_limits(vec![]).unwrap();
+    assert_eq!(
+        result.to_json_value(),
+        json!({
+            "a": [1, 2.5, "x", null, true],
+            "b": {"$tuple": [1, {"$bytes": "aGk="}]},
+            "c": {"$dict": [[1, 2]]},
+            "d": {"$ellipsis": true},
+            "e": {"$bigint": "1180591620717411303424"},
+            "f": {"$float": "nan"},
+        })
+    );
+}
+
+#[test]
+fn natural_json_roundtrip() {
+    let values = [
+        MontyObject::None,
+        MontyObject::Int(-3),
+        MontyObject::Float(f64::NEG_INFINITY),
+        MontyObject::String("$tuple".to_string()),
+        MontyObject::Bytes(b"\x00\xffmonty".to_vec()),
+        MontyObject::Tuple(vec![MontyObject::Int(1), MontyObject::List(vec![])]),
+        MontyObject::Set(vec![MontyObject::Int(1)]),
+        MontyObject::FrozenSet(vec![]),
+        MontyObject::Path("/tmp/x".to_string()),
+        MontyObject::dict(vec![(MontyObject::String("$set".to_string()), MontyObject::Int(1))]),
+        MontyObject::NamedTuple {
+            type_name: "Point".to_string(),
+            field_names: vec!["x".to_string(), "y".to_string()],
+            values: vec![MontyObject::Int(1), MontyObject::Int(2)],
+        },
+        MontyObject::Exception {
+            exc_type: ExcType::KeyError,
+            arg: None,
+        },
+    ];
+    for value in values {
+        let json = value.to_json_value();
+        assert_eq!(MontyObject::from_json_value(json.clone()).unwrap(), value, "{json}");
+    }
+}
+
+#[test]
+fn natural_json_dataclass() {
+    let point = MontyObject::Dataclass {
+        name: "Point".to_string(),
+        type_id: 7,
+        field_names: vec!["x".to_string(), "y".to_string()],
+        attrs: vec![
+            (MontyObject::String("x".to_string()), MontyObject::Int(1)),
+            (MontyObject::String("y".to_string()), MontyObject::Bytes(vec![1])),
+        ]
+        .into(),
+        frozen: true,
+    };
+    let json = point.to_json_value();
+    assert_eq!(
+        json,
+        json!({"$dataclass": {
+            "name": "Point",
+            "type_id": 7,
+            "field_names": ["x", "y"],
+            "attrs": {"x": 1, "y": {"$bytes": "AQ=="}},
+            "frozen": true,
+        }})
+    );
+    assert_eq!(MontyObject::from_json_value(json).unwrap(), point);
+}
+
+#[test]
+fn natural_json_big_numbers() {
+    assert_eq!(
+        MontyObject::from_json_value(json!(u64::MAX)).unwrap(),
+        MontyObject::BigInt(u64::MAX.into())
+    );
+    assert_eq!(
+        MontyObject::from_json_value(json!({"$bigint": "-12"})).unwrap(),
+        MontyObject::Int(-12)
+    );
+}
+
+#[test]
+fn natural_json_errors() {
+    let err = MontyObject::from_json_value(json!({"$repr": "<object>"})).unwrap_err();
+    assert_eq!(err.to_string(), "'$repr' values are output-only");
+    let err = MontyObject::from_json_value(json!({"$nope": 1})).unwrap_err();
+    assert_eq!(err.to_string(), "unknown tag '$nope'");
+    let err = MontyObject::from_json_value(json!({"$tuple": 1})).unwrap_err();
+    assert_eq!(err.to_string(), "'$tuple' value must be an array");
+    let err = MontyObject::from_json_value(json!({"$exception": {"type": "NopeError"}})).unwrap_err();
+    assert_eq!(err.to_string(), "unknown exception type 'NopeError'");
+}
+
+#[test]
+fn natural_json_schema() {
+    let schema = MontyObject::json_schema();
+    assert_eq!(schema["$ref"], "#/$defs/value");
+    let variants = schema["$defs"]["value"]["anyOf"].as_array().unwrap();
+    let tags: Vec<&str> = variants
+        .iter()
+        .filter_map(|variant| variant["required"][0].as_str())
+        .collect();
+    assert!(tags.contains(&"$dataclass"), "{tags:?}");
+    assert!(tags.contains(&"$bytes"), "{tags:?}");
+}
+
 // === Cycle Equality Tests ===
 
 #[test]
@@ -0,0 +1,207 @@
+//! Tests for iterating host datasets passed in as `MontyObject::LazySequence`.
+//!
+//! The host serves each `__getitem__` fetch from a `Vec`, recording the requested ranges.
+
+use monty::{ExcType, MontyException, MontyObject, MontyRun, NoLimitTracker, PrintWriter, RunProgress};
+
+/// Runs `code` with `data` passed in as the lazy sequence `seq`.
+///
+/// Returns the result and the `(start, stop)` ranges the sandbox fetched.
+fn run(
+    code: &str,
+    data: &[i64],
+    length: Option<usize>,
+    chunk_size: usize,
+) -> (Result<MontyObject, MontyException>, Vec<(usize, usize)>) {
+    let seq = MontyObject::LazySequence {
+        name: "rows".to_owned(),
+        length,
+        chunk_size,
+    };
+    let runner = MontyRun::new(code.to_owned(), "main.py", vec!["seq".to_owned()], vec![]).unwrap();
+    let mut fetches = Vec::new();
+    let mut progress = match runner.start(vec![seq], NoLimitTracker, &mut PrintWriter::Stdout) {
+        Ok(progress) => progress,
+        Err(exc) => return (Err(exc), fetches),
+    };
+    loop {
+        match progress {
+            RunProgress::Complete { value: result, .. } => return (Ok(result), fetches),
+            RunProgress::FunctionCall {
+                function_name,
+                args,
+                method_call,
+                state,
+                ..
+            } => {
+                assert_eq!(function_name, "__getitem__");
+                assert!(method_call, "fetches are method calls on the sequence");
+                let [
+                    MontyObject::LazySequence { name, .. },
+                    MontyObject::Int(start),
+                    MontyObject::Int(stop),
+                ] = args.as_slice()
+                else {
+                    panic!("unexpected fetch arguments: {args:?}");
+                };
+                assert_eq!(name, "rows");
+                let (start, stop) = (usize::try_from(*start).unwrap(), usize::try_from(*stop).unwrap());
+                fetches.push((start, stop));
+                let chunk = data[start.min(data.len())..stop.min(data.len())]
+                    .iter()
+                    .map(|&i| MontyObject::Int(i))
+                    .collect();
+                progress = match state.run(MontyObject::List(chunk), &mut PrintWriter::Stdout) {
+                    Ok(progress) => progress,
+                    Err(exc) => return (Err(exc), fetches),
+                };
+            }
+            _ => panic!("unexpected progress"),
+        }
+    }
+}
+
+const SUM_LOOP: &str = "
+total = 0
+for x in seq:
+    total += x
+total
+";
+
+#[test]
+fn for_loop_fetches_chunks_of_known_length() {
+    let data: Vec<i64> = (1..=10).collect();
+    let (result, fetches) = run(SUM_LOOP, &data, Some(10), 4);
+    assert_eq!(result.unwrap(), MontyObject::Int(55));
+    assert_eq!(fetches, vec![(0, 4), (4, 8), (8, 10)]);
+}
+
+#[test]
+fn short_chunk_ends_sequence_of_unknown_length() {
+    let data: Vec<i64> = (1..=10).collect();
+    let (result, fetches) = run(SUM_LOOP, &data, None, 4);
+    assert_eq!(result.unwrap(), MontyObject::Int(55));
+    assert_eq!(fetches, vec![(0, 4), (4, 8), (8, 12)]);
+
+    // an exact multiple of the chunk size needs an empty chunk to end
+    let (result, fetches) = run(SUM_LOOP, &data[..8], None, 4);
+    assert_eq!(result.unwrap(), MontyObject::Int(36));
+    assert_eq!(fetches, vec![(0, 4), (4, 8), (8, 12)]);
+}
+
+#[test]
+fn comprehension_and_break() {
+    let data: Vec<i64> = (0..100).collect();
+    let (result, fetches) = run("[x * 2 for x in seq if x % 3 == 0][:3]", &data, Some(100), 50);
+    assert_eq!(
+        result.unwrap(),
+        MontyObject::List(vec![MontyObject::Int(0), MontyObject::Int(6), MontyObject::Int(12)])
+    );
+    assert_eq!(fetches, vec![(0, 50), (50, 100)]);
+
+    let code = "
+for x in seq:
+    if x == 3:
+        break
+x
+";
+    let (result, fetches) = run(code, &data, Some(100), 2);
+    assert_eq!(result.unwrap(), MontyObject::Int(3));
+    assert_eq!(fetches, vec![(0, 2), (2, 4)], "break stops fetching");
+}
+
+#[test]
+fn len_bool_and_repr() {
+    let (result, fetches) = run("(len(seq), bool(seq), repr(seq))", &[], Some(7), 3);
+    assert_eq!(
+        result.unwrap(),
+        MontyObject::Tuple(vec![
+            MontyObject::Int(7),
+            MontyObject::Bool(true),
+            MontyObject::String("<LazySequence 'rows'>".to_owned()),
+        ])
+    );
+    assert!(fetches.is_empty());
+
+    let (result, _) = run("len(seq)", &[], None, 3);
+    let exc = result.unwrap_err();
+    assert_eq!(exc.exc_type(), ExcType::TypeError);
+}
+
+#[test]
+fn sequence_round_trips_to_host() {
+    let (result, _) = run("seq", &[], Some(7), 3);
+    assert_eq!(
+        result.unwrap(),
+        MontyObject::LazySequence {
+            name: "rows".to_owned(),
+            length: Some(7),
+            chunk_size: 3,
+        }
+    );
+}
+
+#[test]
+fn synchronous_builtins_cannot_fetch() {
+    let data: Vec<i64> = (1..=10).collect();
+    let (result, fetches) = run("sum(seq)", &data, Some(10), 4);
+    let exc = result.unwrap_err();
+    assert_eq!(exc.exc_type(), ExcType::NotImplementedError);
+    assert_eq!(
+        exc.message(),
+        Some("lazy sequence 'rows' can only be fetched by a for loop or comprehension")
+    );
+    assert!(fetches.is_empty());
+}
+
+#[test]
+fn wrong_chunk_size_is_a_value_error() {
+    let seq = MontyObject::LazySequence {
+        name: "rows".to_owned(),
+        length: Some(10),
+        chunk_size: 4,
+    };
+    let runner = MontyRun::new(SUM_LOOP.to_owned(), "main.py", vec!["seq".to_owned()], vec![]).unwrap();
+    let progress = runner
+        .start(vec![seq], NoLimitTracker, &mut PrintWriter::Stdout)
+        .unwrap();
+    let RunProgress::FunctionCall { state, .. } = progress else {
+        panic!("expected a fetch");
+    };
+    let exc = state
+        .run(MontyObject::List(vec![MontyObject::Int(1)]), &mut PrintWriter::Stdout)
+        .unwrap_err();
+    assert_eq!(exc.exc_type(), ExcType::ValueError);
+    assert_eq!(exc.message(), Some("lazy sequence 'rows' chunk 0:4 returned 1 items"));
+}
+
+#[test]
+fn fetch_error_is_catchable() {
+    let code = "
+try:
+    for x in seq:
+        pass
+except KeyError as e:
+    r = 'caught ' + str(e)
+r
+";
+    let seq = MontyObject::LazySequence {
+        name: "rows".to_owned(),
+        length: None,
+        chunk_size: 4,
+    };
+    let runner = MontyRun::new(code.to_owned(), "main.py", vec!["seq".to_owned()], vec![]).unwrap();
+    let progress = runner
+        .start(vec![seq], NoLimitTracker, &mut PrintWriter::Stdout)
+        .unwrap();
+    let RunProgress::FunctionCall { state, .. } = progress else {
+        panic!("expected a fetch");
+    };
+    let exc = MontyException::new(ExcType::KeyError, Some("gone".to_owned()));
+    let result = state
+        .run(exc, &mut PrintWriter::Stdout)
+        .unwrap()
+        .into_complete()
+        .unwrap();
+    assert_eq!(result, MontyObject::String("caught 'gone'".to_owned()));
+}
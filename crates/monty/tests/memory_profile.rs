@@ -0,0 +1,140 @@
+//! Tests for the per-type heap usage breakdown returned by `memory_profile()`.
+
+use monty::{
+    LimitedTracker, MontyObject, MontyRepl, MontyRun, NoLimitTracker, PrintWriter, ResourceLimits, RunProgress,
+};
+
+fn new_repl<T: monty::ResourceTracker>(code: &str, tracker: T) -> MontyRepl<T> {
+    let (repl, _) = MontyRepl::new(
+        code.to_owned(),
+        "main.py",
+        vec![],
+        vec![],
+        vec![],
+        tracker,
+        &mut PrintWriter::Stdout,
+    )
+    .unwrap();
+    repl
+}
+
+fn count_of<T: monty::ResourceTracker>(repl: &MontyRepl<T>, type_name: &str) -> usize {
+    repl.memory_profile().get(type_name).map_or(0, |usage| usage.count)
+}
+
+#[test]
+fn counts_live_objects_per_type() {
+    let mut repl = new_repl("x = 1", NoLimitTracker);
+    let lists_before = count_of(&repl, "list");
+    let strs_before = count_of(&repl, "str");
+
+    repl.feed("items = [[i] for i in range(10)]", &mut PrintWriter::Stdout)
+        .unwrap();
+    repl.feed("names = ['a' * i for i in range(2, 5)]", &mut PrintWriter::Stdout)
+        .unwrap();
+
+    assert_eq!(count_of(&repl, "list") - lists_before, 12);
+    assert_eq!(count_of(&repl, "str") - strs_before, 3);
+}
+
+#[test]
+fn freed_objects_are_not_counted() {
+    let mut repl = new_repl("x = 1", NoLimitTracker);
+    let before = count_of(&repl, "list");
+
+    repl.feed("items = [[i] for i in range(10)]", &mut PrintWriter::Stdout)
+        .unwrap();
+    repl.feed("del items", &mut PrintWriter::Stdout).unwrap();
+
+    assert_eq!(count_of(&repl, "list"), before);
+}
+
+#[test]
+fn totals_and_ordering() {
+    let mut repl = new_repl("x = 1", NoLimitTracker);
+    repl.feed("big = 'x' * 10000\nsmall = [1]", &mut PrintWriter::Stdout)
+        .unwrap();
+
+    let profile = repl.memory_profile();
+    assert_eq!(profile.types[0].type_name, "str");
+    assert!(profile.types.windows(2).all(|w| w[0].bytes >= w[1].bytes));
+    assert_eq!(
+        profile.total_objects(),
+        profile.types.iter().map(|usage| usage.count).sum::<usize>()
+    );
+    assert!(profile.total_bytes() >= 10000);
+    assert!(profile.allocation_sites.is_empty());
+}
+
+#[test]
+fn allocation_sites_when_tracing() {
+    let tracker = LimitedTracker::new(ResourceLimits::new().trace_allocations(true));
+    let mut repl = new_repl("x = 1", tracker);
+    repl.feed("y = 2\nitems = [[i] for i in range(100)]", &mut PrintWriter::Stdout)
+        .unwrap();
+
+    let profile = repl.memory_profile();
+    let site = &profile.allocation_sites[0];
+    assert_eq!(site.filename, "<python-input-0>");
+    assert_eq!(site.line, 2);
+    assert!(
+        site.count >= 101,
+        "expected at least 101 allocations, got {}",
+        site.count
+    );
+}
+
+#[test]
+fn snapshot_memory_profile() {
+    let code = "data = [1, 2, 3]\nfetch(data)";
+    let runner = MontyRun::new(code.to_owned(), "main.py", vec![], vec!["fetch".to_owned()]).unwrap();
+    let progress = runner.start(vec![], NoLimitTracker, &mut PrintWriter::Stdout).unwrap();
+    let (_, args, _, _, _, state) = progress.into_function_call().expect("expected function call");
+    assert_eq!(
+        args,
+        vec![MontyObject::List(vec![
+            MontyObject::Int(1),
+            MontyObject::Int(2),
+            MontyObject::Int(3)
+        ])]
+    );
+    assert!(state.memory_profile().get("list").is_some_and(|usage| usage.count >= 1));
+}
+
+#[test]
+fn complete_memory_profile() {
+    let code = "data = [[i] for i in range(5)]\nlen(data)";
+    let runner = MontyRun::new(code.to_owned(), "main.py", vec![], vec![]).unwrap();
+    let progress = runner.start(vec![], NoLimitTracker, &mut PrintWriter::Stdout).unwrap();
+    let RunProgress::Complete {
+        value, memory_profile, ..
+    } = progress
+    else {
+        panic!("expected completion");
+    };
+    assert_eq!(value, MontyObject::Int(5));
+    let profile = memory_profile.expect("expected memory profile");
+    assert!(profile.get("list").is_some_and(|usage| usage.count >= 6));
+}
+
+#[test]
+fn loaded_complete_has_no_memory_profile() {
+    let runner = MontyRun::new("1 + 2".to_owned(), "main.py", vec![], vec![]).unwrap();
+    let progress = runner.start(vec![], NoLimitTracker, &mut PrintWriter::Stdout).unwrap();
+    assert!(matches!(
+        &progress,
+        RunProgress::Complete {
+            memory_profile: Some(_),
+            ..
+        }
+    ));
+
+    let loaded: RunProgress<NoLimitTracker> = RunProgress::load(&progress.dump().unwrap()).unwrap();
+    assert!(matches!(
+        loaded,
+        RunProgress::Complete {
+            memory_profile: None,
+            ..
+        }
+    ));
+}
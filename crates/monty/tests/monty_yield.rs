@@ -0,0 +1,105 @@
+//! Tests for streaming partial results to the host with `monty_yield()`.
+
+use monty::{ExcType, MontyObject, MontyRun, NoLimitTracker, PrintWriter, RunProgress};
+
+/// Runs `code` to completion, resuming every yield with `None`.
+///
+/// Returns the yielded values in order and the final result.
+fn collect_yields(code: &str) -> (Vec<MontyObject>, MontyObject) {
+    let runner = MontyRun::new(code.to_owned(), "main.py", vec![], vec![]).unwrap();
+    let mut progress = runner.start(vec![], NoLimitTracker, &mut PrintWriter::Stdout).unwrap();
+    let mut yielded = Vec::new();
+    loop {
+        match progress {
+            RunProgress::Complete { value: result, .. } => return (yielded, result),
+            RunProgress::Yield { value, state } => {
+                yielded.push(value);
+                progress = state.run(MontyObject::None, &mut PrintWriter::Stdout).unwrap();
+            }
+            _ => panic!("unexpected progress"),
+        }
+    }
+}
+
+#[test]
+fn yields_stream_in_order() {
+    let code = "
+total = 0
+for i in range(3):
+    total += i
+    monty_yield(total)
+total";
+    let (yielded, result) = collect_yields(code);
+    assert_eq!(
+        yielded,
+        vec![MontyObject::Int(0), MontyObject::Int(1), MontyObject::Int(3)]
+    );
+    assert_eq!(result, MontyObject::Int(3));
+}
+
+#[test]
+fn yield_from_nested_function() {
+    let code = "
+def report(items):
+    for item in items:
+        monty_yield(item)
+    return len(items)
+
+report(['a', 'b'])";
+    let (yielded, result) = collect_yields(code);
+    assert_eq!(
+        yielded,
+        vec![MontyObject::String("a".to_owned()), MontyObject::String("b".to_owned())]
+    );
+    assert_eq!(result, MontyObject::Int(2));
+}
+
+#[test]
+fn resume_value_is_returned() {
+    let code = "monty_yield('ready?') + '!'";
+    let runner = MontyRun::new(code.to_owned(), "main.py", vec![], vec![]).unwrap();
+    let progress = runner.start(vec![], NoLimitTracker, &mut PrintWriter::Stdout).unwrap();
+    let (value, state) = progress.into_yield().expect("expected Yield");
+    assert_eq!(value, MontyObject::String("ready?".to_owned()));
+    let result = state
+        .run(MontyObject::String("go".to_owned()), &mut PrintWriter::Stdout)
+        .unwrap();
+    assert_eq!(result.into_complete(), Some(MontyObject::String("go!".to_owned())));
+}
+
+#[test]
+fn yield_survives_dump_and_load() {
+    let code = "x = [1, 2]\nmonty_yield(x)\nx.append(3)\nx";
+    let runner = MontyRun::new(code.to_owned(), "main.py", vec![], vec![]).unwrap();
+    let progress = runner.start(vec![], NoLimitTracker, &mut PrintWriter::Stdout).unwrap();
+    let progress = RunProgress::<NoLimitTracker>::load(&progress.dump().unwrap()).unwrap();
+    let (value, state) = progress.into_yield().expect("expected Yield");
+    assert_eq!(value, MontyObject::List(vec![MontyObject::Int(1), MontyObject::Int(2)]));
+    let result = state.run(MontyObject::None, &mut PrintWriter::Stdout).unwrap();
+    assert_eq!(
+        result.into_complete(),
+        Some(MontyObject::List(vec![
+            MontyObject::Int(1),
+            MontyObject::Int(2),
+            MontyObject::Int(3)
+        ]))
+    );
+}
+
+#[test]
+fn yield_requires_one_argument() {
+    let runner = MontyRun::new("monty_yield()".to_owned(), "main.py", vec![], vec![]).unwrap();
+    let err = runner
+        .start(vec![], NoLimitTracker, &mut PrintWriter::Stdout)
+        .unwrap_err();
+    assert_eq!(err.exc_type(), ExcType::TypeError);
+}
+
+#[test]
+fn yield_not_supported_by_run() {
+    let runner = MontyRun::new("monty_yield(1)".to_owned(), "main.py", vec![], vec![]).unwrap();
+    let err = runner
+        .run(vec![], NoLimitTracker, &mut PrintWriter::Stdout)
+        .unwrap_err();
+    assert_eq!(err.exc_type(), ExcType::NotImplementedError);
+}
@@ -0,0 +1,87 @@
+//! Tests for code compiled like `python -O` with `MontyRun::new_optimized`.
+
+use monty::{ExcType, MontyObject, MontyRun};
+
+fn run_optimized(code: &str) -> MontyObject {
+    MontyRun::new_optimized(code.to_owned(), "main.py", vec![], vec![], vec![])
+        .unwrap()
+        .run_no_limits(vec![])
+        .unwrap()
+}
+
+#[test]
+fn asserts_are_removed() {
+    let code = "\
+calls = []
+
+def check():
+    calls.append('check')
+    return False
+
+def f():
+    assert check(), calls.append('message')
+    return 'done'
+
+assert False
+(f(), calls)
+";
+    assert_eq!(
+        run_optimized(code),
+        MontyObject::Tuple(vec![MontyObject::String("done".to_owned()), MontyObject::List(vec![])])
+    );
+}
+
+#[test]
+fn debug_is_false() {
+    let code = "\
+def f():
+    return __debug__
+
+x = 'debug' if __debug__ else 'optimized'
+(__debug__, f(), x)
+";
+    assert_eq!(
+        run_optimized(code),
+        MontyObject::Tuple(vec![
+            MontyObject::Bool(false),
+            MontyObject::Bool(false),
+            MontyObject::String("optimized".to_owned()),
+        ])
+    );
+}
+
+#[test]
+fn asserts_run_without_optimize() {
+    let runner = MontyRun::new(
+        "assert __debug__\nassert False, 'checked'".to_owned(),
+        "main.py",
+        vec![],
+        vec![],
+    )
+    .unwrap();
+    let exc = runner.run_no_limits(vec![]).unwrap_err();
+    assert_eq!(exc.exc_type(), ExcType::AssertionError);
+    assert_eq!(exc.message(), Some("checked"));
+}
+
+#[test]
+fn debug_cannot_be_assigned() {
+    let err =
+        MontyRun::new_optimized("def f(__debug__): pass".to_owned(), "main.py", vec![], vec![], vec![]).unwrap_err();
+    assert_eq!(err.exc_type(), ExcType::SyntaxError);
+    assert_eq!(err.message(), Some("cannot assign to __debug__"));
+}
+
+#[test]
+fn optimize_keeps_constants() {
+    let constants = vec![("LIMIT".to_owned(), MontyObject::Int(3))];
+    let runner = MontyRun::new_optimized(
+        "assert LIMIT > 5\nLIMIT".to_owned(),
+        "main.py",
+        vec![],
+        vec![],
+        constants,
+    )
+    .unwrap();
+    assert_eq!(runner.run_no_limits(vec![]).unwrap(), MontyObject::Int(3));
+}
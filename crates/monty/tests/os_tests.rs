@@ -4,7 +4,10 @@
 //! `RunProgress::OsCall` with the correct `OsFunction` variant and arguments,
 //! and that return values are correctly used by Python code.
 
-use monty::{MontyObject, MontyRun, NoLimitTracker, OsFunction, PrintWriter, RunProgress, file_stat};
+use monty::{
+    ExcType, MontyException, MontyObject, MontyRun, NoLimitTracker, OsFunction, PrintWriter, RunProgress, file_stat,
+    temporary_directory,
+};
 
 /// Helper to run code and extract the OsCall progress.
 ///
@@ -28,16 +31,24 @@ fn run_to_oscall(code: &str) -> (OsFunction, Vec<MontyObject>) {
                     MontyObject::String("mock".to_owned())
                 }
                 OsFunction::ReadBytes => MontyObject::Bytes(vec![]),
-                OsFunction::Stat => MontyObject::None,
-                OsFunction::Iterdir => MontyObject::List(vec![]),
+                OsFunction::Stat | OsFunction::Lstat => MontyObject::None,
+                OsFunction::Iterdir | OsFunction::Glob | OsFunction::Rglob | OsFunction::Walk => {
+                    MontyObject::List(vec![])
+                }
+                OsFunction::Copy | OsFunction::Readlink => MontyObject::Path("mock".to_owned()),
                 OsFunction::WriteText
                 | OsFunction::WriteBytes
                 | OsFunction::Mkdir
                 | OsFunction::Unlink
                 | OsFunction::Rmdir
-                | OsFunction::Rename => MontyObject::None,
+                | OsFunction::Rename
+                | OsFunction::Touch
+                | OsFunction::Chmod
+                | OsFunction::SymlinkTo => MontyObject::None,
                 OsFunction::Getenv => MontyObject::String("mock_env_value".to_owned()),
                 OsFunction::GetEnviron => MontyObject::Dict(vec![].into()),
+                OsFunction::Input => MontyObject::String("mock_input".to_owned()),
+                OsFunction::ScratchPath => temporary_directory("/tmp/mock"),
             };
             let _ = state.run(mock_result, &mut PrintWriter::Stdout);
             (function, args)
@@ -123,6 +134,96 @@ fn path_iterdir() {
     assert_eq!(args, vec![MontyObject::Path("/tmp".to_owned())]);
 }
 
+#[test]
+fn path_lstat() {
+    let (func, args) = run_to_oscall("from pathlib import Path; Path('/tmp/link').lstat()");
+    assert_eq!(func, OsFunction::Lstat);
+    assert_eq!(args, vec![MontyObject::Path("/tmp/link".to_owned())]);
+}
+
+#[test]
+fn path_glob() {
+    let (func, args) = run_to_oscall("from pathlib import Path; Path('/tmp').glob('*.txt')");
+    assert_eq!(func, OsFunction::Glob);
+    assert_eq!(
+        args,
+        vec![
+            MontyObject::Path("/tmp".to_owned()),
+            MontyObject::String("*.txt".to_owned())
+        ]
+    );
+}
+
+#[test]
+fn path_rglob() {
+    let (func, args) = run_to_oscall("from pathlib import Path; Path('/tmp').rglob('*.py')");
+    assert_eq!(func, OsFunction::Rglob);
+    assert_eq!(
+        args,
+        vec![
+            MontyObject::Path("/tmp".to_owned()),
+            MontyObject::String("*.py".to_owned())
+        ]
+    );
+}
+
+#[test]
+fn path_walk() {
+    let (func, args) = run_to_oscall("from pathlib import Path; Path('/tmp').walk()");
+    assert_eq!(func, OsFunction::Walk);
+    assert_eq!(args, vec![MontyObject::Path("/tmp".to_owned())]);
+}
+
+#[test]
+fn path_copy() {
+    let (func, args) = run_to_oscall("from pathlib import Path; Path('/tmp/a.txt').copy(Path('/tmp/b.txt'))");
+    assert_eq!(func, OsFunction::Copy);
+    assert_eq!(
+        args,
+        vec![
+            MontyObject::Path("/tmp/a.txt".to_owned()),
+            MontyObject::Path("/tmp/b.txt".to_owned())
+        ]
+    );
+}
+
+#[test]
+fn path_touch() {
+    let (func, args) = run_to_oscall("from pathlib import Path; Path('/tmp/new.txt').touch()");
+    assert_eq!(func, OsFunction::Touch);
+    assert_eq!(args, vec![MontyObject::Path("/tmp/new.txt".to_owned())]);
+}
+
+#[test]
+fn path_chmod() {
+    let (func, args) = run_to_oscall("from pathlib import Path; Path('/tmp/file.txt').chmod(0o600)");
+    assert_eq!(func, OsFunction::Chmod);
+    assert_eq!(
+        args,
+        vec![MontyObject::Path("/tmp/file.txt".to_owned()), MontyObject::Int(0o600)]
+    );
+}
+
+#[test]
+fn path_readlink() {
+    let (func, args) = run_to_oscall("from pathlib import Path; Path('/tmp/link').readlink()");
+    assert_eq!(func, OsFunction::Readlink);
+    assert_eq!(args, vec![MontyObject::Path("/tmp/link".to_owned())]);
+}
+
+#[test]
+fn path_symlink_to() {
+    let (func, args) = run_to_oscall("from pathlib import Path; Path('/tmp/link').symlink_to('/tmp/file.txt')");
+    assert_eq!(func, OsFunction::SymlinkTo);
+    assert_eq!(
+        args,
+        vec![
+            MontyObject::Path("/tmp/link".to_owned()),
+            MontyObject::String("/tmp/file.txt".to_owned())
+        ]
+    );
+}
+
 #[test]
 fn path_resolve() {
     let (func, args) = run_to_oscall("from pathlib import Path; Path('./relative').resolve()");
@@ -311,6 +412,46 @@ info[6]  # st_size is at index 6
     assert_eq!(result, MontyObject::Int(2048));
 }
 
+#[test]
+fn walk_result_unpacked() {
+    let code = r"
+from pathlib import Path
+[(str(root), dirs, files) for root, dirs, files in Path('/data').walk()]
+";
+    let names =
+        |names: &[&str]| MontyObject::List(names.iter().map(|n| MontyObject::String((*n).to_owned())).collect());
+    let mock_tree = MontyObject::List(vec![
+        MontyObject::Tuple(vec![
+            MontyObject::Path("/data".to_owned()),
+            names(&["sub"]),
+            names(&["a.txt"]),
+        ]),
+        MontyObject::Tuple(vec![
+            MontyObject::Path("/data/sub".to_owned()),
+            names(&[]),
+            names(&["b.txt"]),
+        ]),
+    ]);
+    let (func, _, result) = run_oscall_with_result(code, mock_tree);
+
+    assert_eq!(func, OsFunction::Walk);
+    assert_eq!(
+        result,
+        MontyObject::List(vec![
+            MontyObject::Tuple(vec![
+                MontyObject::String("/data".to_owned()),
+                names(&["sub"]),
+                names(&["a.txt"]),
+            ]),
+            MontyObject::Tuple(vec![
+                MontyObject::String("/data/sub".to_owned()),
+                names(&[]),
+                names(&["b.txt"]),
+            ]),
+        ])
+    );
+}
+
 // =============================================================================
 // os.getenv tests
 // =============================================================================
@@ -457,3 +598,156 @@ import os
     assert_eq!(func, OsFunction::GetEnviron);
     assert_eq!(result, MontyObject::Bool(true));
 }
+
+// =============================================================================
+// input() tests
+// =============================================================================
+
+#[test]
+fn input_without_prompt() {
+    let (func, args) = run_to_oscall("input()");
+    assert_eq!(func, OsFunction::Input);
+    assert!(args.is_empty());
+}
+
+#[test]
+fn input_prompt_converted_to_str() {
+    let (func, args) = run_to_oscall("input(42)");
+    assert_eq!(func, OsFunction::Input);
+    assert_eq!(args, vec![MontyObject::String("42".to_owned())]);
+}
+
+#[test]
+fn input_result_used_by_code() {
+    let code = "'Hello ' + input('name: ')";
+    let (func, args, result) = run_oscall_with_result(code, MontyObject::String("Alice".to_owned()));
+    assert_eq!(func, OsFunction::Input);
+    assert_eq!(args, vec![MontyObject::String("name: ".to_owned())]);
+    assert_eq!(result, MontyObject::String("Hello Alice".to_owned()));
+}
+
+#[test]
+fn input_eof_error_caught() {
+    let code = r"
+try:
+    input()
+except EOFError as e:
+    result = 'eof: ' + str(e)
+result
+";
+    let runner = MontyRun::new(code.to_owned(), "test.py", vec![], vec![]).unwrap();
+    let progress = runner.start(vec![], NoLimitTracker, &mut PrintWriter::Stdout).unwrap();
+    let RunProgress::OsCall { function, state, .. } = progress else {
+        panic!("expected OsCall, got {progress:?}");
+    };
+    assert_eq!(function, OsFunction::Input);
+    let eof = MontyException::new(ExcType::EOFError, Some("EOF when reading a line".to_owned()));
+    let result = state.run(eof, &mut PrintWriter::Stdout).unwrap().into_complete();
+    assert_eq!(
+        result,
+        Some(MontyObject::String("eof: EOF when reading a line".to_owned()))
+    );
+}
+
+// =============================================================================
+// tempfile tests
+// =============================================================================
+
+#[test]
+fn tempfile_temporary_directory_defaults() {
+    let (func, args) = run_to_oscall("import tempfile; tempfile.TemporaryDirectory()");
+    assert_eq!(func, OsFunction::ScratchPath);
+    assert_eq!(
+        args,
+        vec![
+            MontyObject::Bool(true),
+            MontyObject::None,
+            MontyObject::None,
+            MontyObject::None
+        ]
+    );
+}
+
+#[test]
+fn tempfile_temporary_directory_positional_args() {
+    let (func, args) = run_to_oscall("import tempfile; tempfile.TemporaryDirectory('.d', prefix='job-')");
+    assert_eq!(func, OsFunction::ScratchPath);
+    assert_eq!(
+        args,
+        vec![
+            MontyObject::Bool(true),
+            MontyObject::String(".d".to_owned()),
+            MontyObject::String("job-".to_owned()),
+            MontyObject::None
+        ]
+    );
+}
+
+#[test]
+fn tempfile_named_temporary_file_kwargs() {
+    let code = "
+import tempfile
+from pathlib import Path
+tempfile.NamedTemporaryFile(dir=Path('/data'), suffix='.csv')
+";
+    let (func, args) = run_to_oscall(code);
+    assert_eq!(func, OsFunction::ScratchPath);
+    assert_eq!(
+        args,
+        vec![
+            MontyObject::Bool(false),
+            MontyObject::String(".csv".to_owned()),
+            MontyObject::None,
+            MontyObject::Path("/data".to_owned())
+        ]
+    );
+}
+
+#[test]
+fn tempfile_result_name_used() {
+    let code = "
+from tempfile import TemporaryDirectory
+TemporaryDirectory().name + '/out.txt'
+";
+    let (_, _, result) = run_oscall_with_result(code, temporary_directory("/tmp/tmpabc"));
+    assert_eq!(result, MontyObject::String("/tmp/tmpabc/out.txt".to_owned()));
+}
+
+#[test]
+fn tempfile_argument_errors() {
+    let cases = [
+        (
+            "tempfile.NamedTemporaryFile('w')",
+            "NamedTemporaryFile() takes 0 positional arguments but 1 was given",
+        ),
+        (
+            "tempfile.TemporaryDirectory('a', 'b', 'c', 'd')",
+            "TemporaryDirectory() takes from 0 to 3 positional arguments but 4 were given",
+        ),
+        (
+            "tempfile.TemporaryDirectory(mode=0o700)",
+            "TemporaryDirectory() got an unexpected keyword argument 'mode'",
+        ),
+        (
+            "tempfile.TemporaryDirectory('.x', suffix='.y')",
+            "TemporaryDirectory() got multiple values for argument 'suffix'",
+        ),
+        (
+            "tempfile.NamedTemporaryFile(prefix=1)",
+            "NamedTemporaryFile() argument 'prefix' must be str or None, not int",
+        ),
+        (
+            "tempfile.TemporaryDirectory(dir=b'/tmp')",
+            "TemporaryDirectory() argument 'dir' must be str, Path or None, not bytes",
+        ),
+    ];
+    for (call, message) in cases {
+        let code = format!("import tempfile\n{call}");
+        let runner = MontyRun::new(code, "test.py", vec![], vec![]).unwrap();
+        let exc = runner
+            .start(vec![], NoLimitTracker, &mut PrintWriter::Stdout)
+            .unwrap_err();
+        assert_eq!(exc.exc_type(), ExcType::TypeError, "{call}");
+        assert_eq!(exc.message(), Some(message), "{call}");
+    }
+}
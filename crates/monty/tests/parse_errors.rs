@@ -1,6 +1,6 @@
 use std::fmt::Write;
 
-use monty::{ExcType, MontyException, MontyRun};
+use monty::{ExcType, MontyException, MontyObject, MontyRun, ResourceLimits};
 
 /// Helper to extract the exception type from a parse error.
 fn get_exc_type(result: Result<MontyRun, MontyException>) -> ExcType {
@@ -398,6 +398,82 @@ fn deeply_nested_boolean_or_exceed_limit() {
     assert_eq!(get_exc_type(result), ExcType::SyntaxError);
 }
 
+// === String literal escape tests ===
+// Malformed escapes are reported with CPython's messages and codec positions.
+
+/// Helper to get the SyntaxError message for code with a malformed escape.
+fn escape_error_message(code: &str) -> String {
+    let exc = MontyRun::new(code.to_owned(), "test.py", vec![], vec![]).expect_err("expected parse error");
+    assert_eq!(exc.exc_type(), ExcType::SyntaxError);
+    exc.message().expect("SyntaxError should have a message").to_owned()
+}
+
+#[test]
+fn truncated_str_escapes_match_cpython() {
+    let cases = [
+        (r"'\x1'", "position 0-2: truncated \\xXX escape"),
+        (r"'ab\x1g'", "position 2-4: truncated \\xXX escape"),
+        (r"'\u12'", "position 0-3: truncated \\uXXXX escape"),
+        (r"'\U1234'", "position 0-5: truncated \\UXXXXXXXX escape"),
+        (r"'\U0011ffff'", "position 0-9: illegal Unicode character"),
+        // non-ASCII characters count as 10 bytes, like CPython's `\UXXXXXXXX` re-encoding
+        (r"'é\x1'", "position 10-12: truncated \\xXX escape"),
+        // positions restart after an f-string replacement field
+        (r"f'{1}\x1'", "position 0-2: truncated \\xXX escape"),
+    ];
+    for (code, expected) in cases {
+        assert_eq!(
+            escape_error_message(code),
+            format!("(unicode error) 'unicodeescape' codec can't decode bytes in {expected}"),
+            "code: {code}"
+        );
+    }
+}
+
+#[test]
+fn malformed_unicode_name_escapes_match_cpython() {
+    let cases = [
+        (r"'\N'", "position 0-1: malformed \\N character escape"),
+        (r"'\N{}'", "position 0-2: malformed \\N character escape"),
+        (r"'\N{abc'", "position 0-5: malformed \\N character escape"),
+        (r"'\N{foo}'", "position 0-6: unknown Unicode character name"),
+        (
+            r"'\N{LATIN SMALL LETTER A}\N{nope}'",
+            "position 24-31: unknown Unicode character name",
+        ),
+    ];
+    for (code, expected) in cases {
+        assert_eq!(
+            escape_error_message(code),
+            format!("(unicode error) 'unicodeescape' codec can't decode bytes in {expected}"),
+            "code: {code}"
+        );
+    }
+}
+
+#[test]
+fn invalid_bytes_escape_matches_cpython() {
+    assert_eq!(
+        escape_error_message(r"b'ab\xzz'"),
+        "(value error) invalid \\x escape at position 2"
+    );
+}
+
+#[test]
+fn raw_strings_do_not_decode_escapes() {
+    let runner = MontyRun::new(r"r'\x1' + rb'\N'.decode()".to_owned(), "test.py", vec![], vec![]).unwrap();
+    let result = runner.run_no_limits(vec![]).unwrap();
+    assert_eq!(result, MontyObject::String(r"\x1\N".to_owned()));
+}
+
+#[test]
+fn unknown_escapes_are_kept_verbatim() {
+    // CPython keeps these too, but also emits a SyntaxWarning
+    let runner = MontyRun::new(r"'\d\q'".to_owned(), "test.py", vec![], vec![]).unwrap();
+    let result = runner.run_no_limits(vec![]).unwrap();
+    assert_eq!(result, MontyObject::String(r"\d\q".to_owned()));
+}
+
 // === Runtime NotImplementedError tests ===
 // These test that unimplemented features return proper errors instead of panicking.
 
@@ -434,8 +510,111 @@ fn matrix_multiplication_augmented_assignment_has_descriptive_message() {
 }
 
 #[test]
-fn del_statement_returns_not_implemented_error() {
-    // The del statement is not supported at parse time
-    let result = MontyRun::new("x = 1\ndel x".to_owned(), "test.py", vec![], vec![]);
+fn del_subscript_returns_not_implemented_error() {
+    // Only `del name` is supported, deleting subscripts and attributes is not
+    let result = MontyRun::new("x = [1]\ndel x[0]".to_owned(), "test.py", vec![], vec![]);
     assert_eq!(get_exc_type(result), ExcType::NotImplementedError);
 }
+
+/// Helper to run the strict identity check on `code`, returning the error message if it fails.
+fn identity_literal_error(code: &str) -> Option<String> {
+    let runner = MontyRun::new(code.to_owned(), "test.py", vec!["x".to_owned()], vec![]).unwrap();
+    let exc = runner.check_identity_literals("test.py").err()?;
+    assert_eq!(exc.exc_type(), ExcType::SyntaxError, "unexpected exception: {exc}");
+    exc.message().map(str::to_owned)
+}
+
+#[test]
+fn identity_literals_are_allowed_by_default() {
+    let runner = MontyRun::new("x is 1000".to_owned(), "test.py", vec!["x".to_owned()], vec![]).unwrap();
+    let result = runner.run_no_limits(vec![MontyObject::Int(1000)]).unwrap();
+    assert_eq!(result, MontyObject::Bool(true));
+}
+
+#[test]
+fn identity_literals_match_cpython_warnings() {
+    let cases = [
+        ("x is 1", r#""is" with 'int' literal. Did you mean "=="?"#),
+        ("x is not 'a'", r#""is not" with 'str' literal. Did you mean "!="?"#),
+        ("1.5 is x", r#""is" with 'float' literal. Did you mean "=="?"#),
+        ("x is b''", r#""is" with 'bytes' literal. Did you mean "=="?"#),
+        ("x is -1", r#""is" with 'int' literal. Did you mean "=="?"#),
+        ("x is (1, None)", r#""is" with 'tuple' literal. Did you mean "=="?"#),
+        ("x < 2 is x", r#""is" with 'int' literal. Did you mean "=="?"#),
+        (
+            "def f():\n    return x is not ()",
+            r#""is not" with 'tuple' literal. Did you mean "!="?"#,
+        ),
+    ];
+    for (code, expected) in cases {
+        assert_eq!(identity_literal_error(code).as_deref(), Some(expected), "code: {code}");
+    }
+}
+
+#[test]
+fn identity_singletons_and_names_pass_check() {
+    for code in [
+        "x is None",
+        "x is not True",
+        "x is ...",
+        "x is x",
+        "x is [1]",
+        "x is (x,)",
+        "x is not (not 1)",
+        "x == 1",
+        "1 < x is None",
+    ] {
+        assert_eq!(identity_literal_error(code), None, "code: {code}");
+    }
+}
+
+/// Compiles `code` with `limits`, returning the error message if it's refused.
+fn compile_limit_error(code: &str, limits: &ResourceLimits) -> Option<String> {
+    let err = MontyRun::new_with_limits(code.to_owned(), "test.py", vec![], vec![], limits).err()?;
+    assert_eq!(err.exc_type(), ExcType::SyntaxError);
+    err.message().map(str::to_owned)
+}
+
+#[test]
+fn max_parse_depth_limit() {
+    let limits = ResourceLimits::new().max_parse_depth(5);
+    assert_eq!(compile_limit_error("((1,),)", &limits), None);
+    assert_eq!(
+        compile_limit_error("[[[[[[1]]]]]]", &limits).as_deref(),
+        Some("too many nested parentheses")
+    );
+    // a higher limit can't raise the built-in one
+    let mut code = "x".to_string();
+    for _ in 0..250 {
+        code = format!("({code},)");
+    }
+    let limits = ResourceLimits::new().max_parse_depth(10_000);
+    assert_eq!(
+        compile_limit_error(&code, &limits).as_deref(),
+        Some("too many nested parentheses")
+    );
+}
+
+#[test]
+fn max_ast_nodes_limit() {
+    // one statement and three expressions
+    let limits = ResourceLimits::new().max_ast_nodes(4);
+    assert_eq!(compile_limit_error("1 + 2", &limits), None);
+    let err = compile_limit_error("1 + 2\n3", &limits).expect("expected too many nodes");
+    assert_eq!(err, "code is too complex, more than 4 statements and expressions");
+}
+
+#[test]
+fn max_bytecode_size_limit() {
+    let code = "def f(a):\n    return a * 2\nf(1) + f(2)";
+    let runner = MontyRun::new_with_limits(code.to_owned(), "test.py", vec![], vec![], &ResourceLimits::new())
+        .expect("no compile-time limits set");
+    assert_eq!(runner.run_no_limits(vec![]).unwrap(), MontyObject::Int(6));
+
+    let limits = ResourceLimits::new().max_bytecode_size(10);
+    let err = compile_limit_error(code, &limits).expect("expected bytecode size error");
+    assert!(
+        err.starts_with("code is too complex, ") && err.ends_with(" bytes of bytecode exceeds the limit of 10"),
+        "unexpected message: {err}"
+    );
+}
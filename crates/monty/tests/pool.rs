@@ -0,0 +1,182 @@
+//! Tests for `SandboxPool` slot accounting, tenant quotas, queueing and metrics hooks.
+
+use std::{
+    sync::{Arc, Mutex},
+    thread,
+    time::Duration,
+};
+
+use monty::{
+    ExcType, MontyObject, MontyRun, PoolError, PoolMetrics, PoolStats, PrintWriter, ResourceLimits, SandboxPool,
+    TenantQuota,
+};
+
+#[test]
+fn lease_runs_with_tenant_limits() {
+    let pool =
+        SandboxPool::new(2).with_tenant_quota("small", TenantQuota::new(1, ResourceLimits::new().max_allocations(5)));
+    let runner = MontyRun::new("[i for i in range(100)]".to_owned(), "main.py", vec![], vec![]).unwrap();
+
+    let mut lease = pool.acquire("small").unwrap();
+    let err = lease.run(&runner, vec![], &mut PrintWriter::Disabled).unwrap_err();
+    assert_eq!(err.exc_type(), ExcType::MemoryError);
+    drop(lease);
+
+    // other tenants get the default quota, without the allocation limit
+    let mut lease = pool.acquire("big").unwrap();
+    let result = lease.run(&runner, vec![], &mut PrintWriter::Disabled).unwrap();
+    assert!(matches!(result, MontyObject::List(items) if items.len() == 100));
+}
+
+#[test]
+fn lease_reuses_arena_between_runs() {
+    let pool = SandboxPool::new(1).with_default_quota(TenantQuota::new(1, ResourceLimits::new().max_allocations(150)));
+    let runner = MontyRun::new(
+        "data = [str(i) for i in range(n)]\nlen(data)".to_owned(),
+        "main.py",
+        vec!["n".to_owned()],
+        vec![],
+    )
+    .unwrap();
+
+    let mut lease = pool.acquire("a").unwrap();
+    // each run is limited on its own, the objects of earlier runs don't count against later ones
+    for _ in 0..3 {
+        let result = lease.run(&runner, vec![MontyObject::Int(100)], &mut PrintWriter::Disabled);
+        assert_eq!(result.unwrap(), MontyObject::Int(100));
+    }
+    let err = lease
+        .run(&runner, vec![MontyObject::Int(200)], &mut PrintWriter::Disabled)
+        .unwrap_err();
+    assert_eq!(err.exc_type(), ExcType::MemoryError);
+    drop(lease);
+
+    // the arena goes back to the pool with the slot and serves the next lease
+    let mut lease = pool.acquire("b").unwrap();
+    let result = lease.run(&runner, vec![MontyObject::Int(10)], &mut PrintWriter::Disabled);
+    assert_eq!(result.unwrap(), MontyObject::Int(10));
+}
+
+#[test]
+fn stats_track_leases() {
+    let pool = SandboxPool::new(3);
+    let a = pool.acquire("a").unwrap();
+    let b = pool.acquire("b").unwrap();
+    assert_eq!(
+        pool.stats(),
+        PoolStats {
+            size: 3,
+            in_use: 2,
+            waiting: 0
+        }
+    );
+    drop(a);
+    drop(b);
+    assert_eq!(pool.stats().in_use, 0);
+}
+
+#[test]
+fn timeout_when_pool_full() {
+    let pool = SandboxPool::new(1).with_timeout(Duration::from_millis(20));
+    let _lease = pool.acquire("a").unwrap();
+    let err = pool.acquire("b").unwrap_err();
+    assert!(matches!(err, PoolError::Timeout { waited } if waited >= Duration::from_millis(20)));
+    assert_eq!(pool.stats().waiting, 0);
+}
+
+#[test]
+fn tenant_quota_limits_concurrency() {
+    let pool = SandboxPool::new(4)
+        .with_default_quota(TenantQuota::new(1, ResourceLimits::new()))
+        .with_timeout(Duration::from_millis(20));
+    let _first = pool.acquire("a").unwrap();
+    assert!(matches!(pool.acquire("a"), Err(PoolError::Timeout { .. })));
+    // the quota is per tenant, so others still get slots
+    let _other = pool.acquire("b").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "sandbox pool size must be at least 1")]
+fn empty_pool_panics() {
+    let _ = SandboxPool::new(0);
+}
+
+#[test]
+fn zero_quota_rejects_immediately() {
+    // no timeout, so this would block forever if the pool waited for a slot
+    let pool = SandboxPool::new(2).with_tenant_quota("blocked", TenantQuota::new(0, ResourceLimits::new()));
+    assert_eq!(
+        pool.acquire("blocked").unwrap_err(),
+        PoolError::NoQuota {
+            tenant: "blocked".to_owned()
+        }
+    );
+    assert_eq!(pool.stats().waiting, 0);
+    let _other = pool.acquire("a").unwrap();
+}
+
+#[test]
+fn queue_full_rejects_immediately() {
+    let pool = SandboxPool::new(1).with_max_queue(0);
+    let _lease = pool.acquire("a").unwrap();
+    assert_eq!(pool.acquire("b").unwrap_err(), PoolError::QueueFull { max_queue: 0 });
+}
+
+#[test]
+fn waiters_get_released_slots() {
+    let pool = SandboxPool::new(2);
+    let runner = MontyRun::new("sum(range(x))".to_owned(), "main.py", vec!["x".to_owned()], vec![]).unwrap();
+    let results: Vec<MontyObject> = thread::scope(|s| {
+        let handles: Vec<_> = (0..8)
+            .map(|i| {
+                let (pool, runner) = (&pool, &runner);
+                s.spawn(move || {
+                    let mut lease = pool.acquire("tenant").unwrap();
+                    assert!(pool.stats().in_use <= 2);
+                    lease
+                        .run(runner, vec![MontyObject::Int(i)], &mut PrintWriter::Disabled)
+                        .unwrap()
+                })
+            })
+            .collect();
+        handles.into_iter().map(|h| h.join().unwrap()).collect()
+    });
+    let expected: Vec<MontyObject> = (0..8).map(|i| MontyObject::Int(i * (i - 1) / 2)).collect();
+    assert_eq!(results, expected);
+    assert_eq!(pool.stats().in_use, 0);
+}
+
+/// Metrics hook recording every event as a string.
+#[derive(Debug, Default, Clone)]
+struct Recorder(Arc<Mutex<Vec<String>>>);
+
+impl PoolMetrics for Recorder {
+    fn on_acquire(&self, tenant: &str, _waited: Duration) {
+        self.0.lock().unwrap().push(format!("acquire {tenant}"));
+    }
+
+    fn on_release(&self, tenant: &str, _held: Duration) {
+        self.0.lock().unwrap().push(format!("release {tenant}"));
+    }
+
+    fn on_reject(&self, tenant: &str, error: &PoolError) {
+        self.0.lock().unwrap().push(format!("reject {tenant}: {error}"));
+    }
+}
+
+#[test]
+fn metrics_hooks() {
+    let recorder = Recorder::default();
+    let pool = SandboxPool::new(1).with_max_queue(0).with_metrics(recorder.clone());
+    let lease = pool.acquire("a").unwrap();
+    assert!(pool.acquire("b").is_err());
+    drop(lease);
+    assert_eq!(
+        *recorder.0.lock().unwrap(),
+        [
+            "acquire a",
+            "reject b: sandbox pool queue is full (max_queue=0)",
+            "release a"
+        ]
+    );
+}
@@ -1,4 +1,6 @@
-use monty::{MontyRun, NoLimitTracker, PrintWriter};
+use std::borrow::Cow;
+
+use monty::{MontyException, MontyRun, NoLimitTracker, PrintWriter, PrintWriterCallback};
 
 #[test]
 fn print_single_string() {
@@ -188,8 +190,8 @@ fn print_end_none() {
 }
 
 #[test]
-fn print_flush_ignored() {
-    // flush=True should be accepted but ignored
+fn print_flush_collect() {
+    // flush=True has nothing to flush when collecting output
     let ex = MontyRun::new("print('test', flush=True)".to_owned(), "test.py", vec![], vec![]).unwrap();
     let mut writer = PrintWriter::Collect(String::new());
     ex.run(vec![], NoLimitTracker, &mut writer).unwrap();
@@ -220,3 +222,108 @@ fn print_multiline_sep() {
     ex.run(vec![], NoLimitTracker, &mut writer).unwrap();
     assert_eq!(writer.collected_output().unwrap(), "1\n2\n3\n");
 }
+
+// === print() file and flush tests ===
+
+/// Records every callback so tests can check which stream was written and when flushes happen.
+#[derive(Default)]
+struct RecordingCallback {
+    events: Vec<String>,
+}
+
+impl PrintWriterCallback for RecordingCallback {
+    fn stdout_write(&mut self, output: Cow<'_, str>) -> Result<(), MontyException> {
+        self.events.push(format!("stdout:{output}"));
+        Ok(())
+    }
+
+    fn stdout_push(&mut self, end: char) -> Result<(), MontyException> {
+        self.events.push(format!("stdout:{end}"));
+        Ok(())
+    }
+
+    fn stderr_write(&mut self, output: Cow<'_, str>) -> Result<(), MontyException> {
+        self.events.push(format!("stderr:{output}"));
+        Ok(())
+    }
+
+    fn stderr_push(&mut self, end: char) -> Result<(), MontyException> {
+        self.events.push(format!("stderr:{end}"));
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<(), MontyException> {
+        self.events.push("flush".to_owned());
+        Ok(())
+    }
+}
+
+fn run_recording(code: &str) -> Vec<String> {
+    let ex = MontyRun::new(code.to_owned(), "test.py", vec![], vec![]).unwrap();
+    let mut callback = RecordingCallback::default();
+    ex.run(vec![], NoLimitTracker, &mut PrintWriter::Callback(&mut callback))
+        .unwrap();
+    callback.events
+}
+
+#[test]
+fn print_file_stderr() {
+    let events = run_recording("import sys\nprint('a', 'b', file=sys.stderr)");
+    assert_eq!(events, ["stderr:a", "stderr: ", "stderr:b", "stderr:\n"]);
+}
+
+#[test]
+fn print_file_stdout_and_none() {
+    let events = run_recording("import sys\nprint('a', file=sys.stdout)\nprint('b', file=None)");
+    assert_eq!(events, ["stdout:a", "stdout:\n", "stdout:b", "stdout:\n"]);
+}
+
+#[test]
+fn print_flush_calls_callback() {
+    let events = run_recording("print('a', end='', flush=True)\nprint('b', flush=False)");
+    assert_eq!(events, ["stdout:a", "stdout:", "flush", "stdout:b", "stdout:\n"]);
+}
+
+#[test]
+fn print_stderr_collected() {
+    let ex = MontyRun::new(
+        "import sys\nprint('out')\nprint('err', file=sys.stderr)".to_owned(),
+        "test.py",
+        vec![],
+        vec![],
+    )
+    .unwrap();
+    let mut writer = PrintWriter::Collect(String::new());
+    ex.run(vec![], NoLimitTracker, &mut writer).unwrap();
+    assert_eq!(writer.collected_output().unwrap(), "out\nerr\n");
+}
+
+/// Callbacks that only implement the stdout methods receive stderr output too.
+struct StdoutOnlyCallback(String);
+
+impl PrintWriterCallback for StdoutOnlyCallback {
+    fn stdout_write(&mut self, output: Cow<'_, str>) -> Result<(), MontyException> {
+        self.0.push_str(&output);
+        Ok(())
+    }
+
+    fn stdout_push(&mut self, end: char) -> Result<(), MontyException> {
+        self.0.push(end);
+        Ok(())
+    }
+}
+
+#[test]
+fn print_stderr_default_callback() {
+    let ex = MontyRun::new(
+        "import sys\nprint('err', file=sys.stderr, flush=True)".to_owned(),
+        "test.py",
+        vec![],
+        vec![],
+    )
+    .unwrap();
+    let mut callback = StdoutOnlyCallback(String::new());
+    ex.run(vec![], NoLimitTracker, &mut PrintWriter::Callback(&mut callback))
+        .unwrap();
+    assert_eq!(callback.0, "err\n");
+}
@@ -0,0 +1,121 @@
+//! Tests for counting executed instructions per line and function with `Profiler`.
+
+use monty::{
+    LimitedTracker, MontyObject, MontyRepl, MontyRun, PrintWriter, Profiler, ResourceLimits, ResourceTracker,
+    RunProgress,
+};
+
+const CODE: &str = "
+def square(x):
+    return x * x
+
+total = 0
+for i in range(100):
+    total += square(i)
+total";
+
+fn instructions_on_line(profiler: &Profiler, line: u16) -> u64 {
+    profiler
+        .report()
+        .lines
+        .iter()
+        .find(|l| l.filename == "main.py" && l.line == line)
+        .map_or(0, |l| l.instructions)
+}
+
+#[test]
+fn counts_lines_and_functions() {
+    let profiler = Profiler::new();
+    let runner = MontyRun::new(CODE.to_owned(), "main.py", vec![], vec![]).unwrap();
+    let result = runner.run(vec![], profiler.clone(), &mut PrintWriter::Stdout).unwrap();
+    assert_eq!(result, MontyObject::Int(328_350));
+
+    let report = profiler.report();
+    assert_eq!(
+        report.total_instructions,
+        report.lines.iter().map(|l| l.instructions).sum::<u64>()
+    );
+    assert!(report.lines.windows(2).all(|w| w[0].instructions >= w[1].instructions));
+
+    // the loop body and the function body run 100 times, the assignment before the loop once
+    assert!(instructions_on_line(&profiler, 7) >= 100);
+    assert!(instructions_on_line(&profiler, 3) >= 100);
+    assert!(instructions_on_line(&profiler, 5) < 10);
+
+    let names: Vec<&str> = report.functions.iter().map(|f| f.name.as_str()).collect();
+    assert!(names.contains(&"square"), "{names:?}");
+    assert!(names.contains(&"<module>"), "{names:?}");
+    let square = report.functions.iter().find(|f| f.name == "square").unwrap();
+    assert!(square.instructions >= instructions_on_line(&profiler, 3));
+}
+
+#[test]
+fn deterministic_counts() {
+    let run = || {
+        let profiler = Profiler::new();
+        let runner = MontyRun::new(CODE.to_owned(), "main.py", vec![], vec![]).unwrap();
+        runner.run(vec![], profiler.clone(), &mut PrintWriter::Stdout).unwrap();
+        profiler.report()
+    };
+    assert_eq!(run(), run());
+}
+
+#[test]
+fn chained_with_limits_and_external_calls() {
+    let code = "
+x = fetch(1)
+y = fetch(2)
+x + y";
+    let profiler = Profiler::new();
+    let tracker = LimitedTracker::new(ResourceLimits::new()).chain(profiler.clone());
+    let runner = MontyRun::new(code.to_owned(), "main.py", vec![], vec!["fetch".to_owned()]).unwrap();
+    let mut progress = runner.start(vec![], tracker, &mut PrintWriter::Stdout).unwrap();
+    let result = loop {
+        match progress {
+            RunProgress::Complete { value: result, .. } => break result,
+            RunProgress::FunctionCall { args, state, .. } => {
+                progress = state.run(args[0].clone(), &mut PrintWriter::Stdout).unwrap();
+            }
+            _ => panic!("unexpected progress"),
+        }
+    };
+    assert_eq!(result, MontyObject::Int(3));
+    for line in 2..=4 {
+        assert!(instructions_on_line(&profiler, line) > 0, "line {line} not counted");
+    }
+}
+
+#[test]
+fn reset_and_repl_snippets() {
+    let profiler = Profiler::new();
+    let (mut repl, _) = MontyRepl::new(
+        "x = 1".to_owned(),
+        "main.py",
+        vec![],
+        vec![],
+        vec![],
+        profiler.clone(),
+        &mut PrintWriter::Stdout,
+    )
+    .unwrap();
+    profiler.reset();
+    assert_eq!(profiler.report().total_instructions, 0);
+
+    repl.feed("y = [i for i in range(10)]", &mut PrintWriter::Stdout)
+        .unwrap();
+    let report = profiler.report();
+    assert!(report.total_instructions > 10);
+    assert!(report.lines.iter().all(|l| l.filename == "<python-input-0>"));
+}
+
+#[test]
+fn report_display() {
+    let profiler = Profiler::new();
+    let runner = MontyRun::new(CODE.to_owned(), "main.py", vec![], vec![]).unwrap();
+    runner.run(vec![], profiler.clone(), &mut PrintWriter::Stdout).unwrap();
+    let report = profiler.report();
+    let text = report.to_string();
+    assert!(text.starts_with(&format!("{} instructions executed", report.total_instructions)));
+    assert!(text.contains("square (main.py)"), "{text}");
+    assert!(text.contains("main.py:7"), "{text}");
+}
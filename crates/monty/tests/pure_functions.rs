@@ -0,0 +1,129 @@
+//! Tests for memoizing calls to external functions marked pure with `MontyRun::mark_pure`.
+
+use monty::{ExcType, MontyException, MontyObject, MontyRun, NoLimitTracker, PrintWriter, RunProgress};
+
+/// Creates a runner with `lookup` marked pure and `fetch` left as a plain external function.
+fn pure_runner(code: &str) -> MontyRun {
+    let mut runner = MontyRun::new(
+        code.to_owned(),
+        "test.py",
+        vec![],
+        vec!["lookup".to_owned(), "fetch".to_owned()],
+    )
+    .unwrap();
+    runner.mark_pure("lookup").unwrap();
+    runner
+}
+
+/// Runs the code, answering each call with `respond`, and returns the result with the calls made.
+fn run_recording(
+    runner: MontyRun,
+    mut respond: impl FnMut(&[MontyObject]) -> Result<MontyObject, MontyException>,
+) -> (MontyObject, Vec<(String, Vec<MontyObject>)>) {
+    let mut calls = Vec::new();
+    let mut progress = runner.start(vec![], NoLimitTracker, &mut PrintWriter::Stdout).unwrap();
+    loop {
+        match progress {
+            RunProgress::Complete { value: result, .. } => return (result, calls),
+            RunProgress::FunctionCall {
+                function_name,
+                args,
+                state,
+                ..
+            } => {
+                let result = respond(&args);
+                calls.push((function_name, args));
+                progress = match result {
+                    Ok(value) => state.run(value, &mut PrintWriter::Stdout),
+                    Err(exc) => state.run(exc, &mut PrintWriter::Stdout),
+                }
+                .unwrap();
+            }
+            _ => panic!("unexpected progress"),
+        }
+    }
+}
+
+/// Responds with ten times the first argument, which must be an int.
+fn times_ten(args: &[MontyObject]) -> Result<MontyObject, MontyException> {
+    let MontyObject::Int(i) = args[0] else {
+        panic!("unexpected args {args:?}");
+    };
+    Ok(MontyObject::Int(i * 10))
+}
+
+#[test]
+fn repeated_calls_hit_the_cache() {
+    let runner = pure_runner("sum(lookup(i % 2) for i in range(6))");
+    let (result, calls) = run_recording(runner, times_ten);
+    assert_eq!(result, MontyObject::Int(30));
+    let args: Vec<_> = calls.into_iter().map(|(_, args)| args).collect();
+    assert_eq!(args, vec![vec![MontyObject::Int(0)], vec![MontyObject::Int(1)]]);
+}
+
+#[test]
+fn only_pure_functions_are_cached() {
+    let runner = pure_runner("[lookup(1), lookup(1), fetch(1), fetch(1)]");
+    let (result, calls) = run_recording(runner, times_ten);
+    assert_eq!(result, MontyObject::List(vec![MontyObject::Int(10); 4]));
+    let names: Vec<_> = calls.into_iter().map(|(name, _)| name).collect();
+    assert_eq!(names, vec!["lookup", "fetch", "fetch"]);
+}
+
+#[test]
+fn keyword_and_container_arguments_are_part_of_the_key() {
+    let code = "[lookup([1, 2], k=1), lookup([1, 2], k=1), lookup([1, 2], k=2), lookup([2, 1], k=1)]";
+    let mut count = 0;
+    let (result, calls) = run_recording(pure_runner(code), |_| {
+        count += 1;
+        Ok(MontyObject::Int(count))
+    });
+    assert_eq!(
+        result,
+        MontyObject::List(vec![
+            MontyObject::Int(1),
+            MontyObject::Int(1),
+            MontyObject::Int(2),
+            MontyObject::Int(3),
+        ])
+    );
+    assert_eq!(calls.len(), 3);
+}
+
+#[test]
+fn exceptions_are_not_cached() {
+    let code = "
+results = []
+for _ in range(3):
+    try:
+        results.append(lookup(1))
+    except ValueError as e:
+        results.append(str(e))
+results";
+    let mut failed = false;
+    let (result, calls) = run_recording(pure_runner(code), |args| {
+        if failed {
+            times_ten(args)
+        } else {
+            failed = true;
+            Err(MontyException::new(ExcType::ValueError, Some("flaky".to_owned())))
+        }
+    });
+    assert_eq!(
+        result,
+        MontyObject::List(vec![
+            MontyObject::String("flaky".to_owned()),
+            MontyObject::Int(10),
+            MontyObject::Int(10),
+        ])
+    );
+    assert_eq!(calls.len(), 2);
+}
+
+#[test]
+fn mark_pure_unknown_function() {
+    let mut runner = MontyRun::new("1".to_owned(), "test.py", vec![], vec!["lookup".to_owned()]).unwrap();
+    let exc = runner.mark_pure("missing").unwrap_err();
+    assert_eq!(exc.exc_type(), ExcType::ValueError);
+    assert_eq!(exc.message(), Some("'missing' is not an external function"));
+}
@@ -4,8 +4,8 @@
 //! only the newly fed snippet each time.
 
 use monty::{
-    ExternalResult, MontyObject, MontyRepl, NoLimitTracker, PrintWriter, ReplContinuationMode, ReplProgress,
-    detect_repl_continuation_mode,
+    ExcType, ExternalResult, LimitedTracker, MontyException, MontyObject, MontyRepl, NoLimitTracker, OsFunction,
+    PrintWriter, ReplContinuationMode, ReplProgress, ResourceLimits, detect_repl_continuation_mode,
 };
 
 fn init_repl(code: &str, external_functions: Vec<String>) -> (MontyRepl<NoLimitTracker>, MontyObject) {
@@ -83,6 +83,21 @@ fn repl_runtime_error_keeps_partial_state_consistent() {
     assert_eq!(repl.feed_no_print("x").unwrap(), MontyObject::Int(1));
 }
 
+#[test]
+fn repl_module_dunders_visible_in_snippets() {
+    let (mut repl, _) = init_repl("\"\"\"Session docstring.\"\"\"", vec![]);
+
+    let output = repl.feed_no_print("(__name__, __file__, __doc__)").unwrap();
+    assert_eq!(
+        output,
+        MontyObject::Tuple(vec![
+            MontyObject::String("__main__".to_owned()),
+            MontyObject::String("repl.py".to_owned()),
+            MontyObject::String("Session docstring.".to_owned()),
+        ])
+    );
+}
+
 #[test]
 fn repl_heap_mutations_are_not_replayed() {
     let (mut repl, _) = init_repl("items = []", vec![]);
@@ -116,6 +131,27 @@ fn repl_detects_continuation_mode_for_common_cases() {
     );
 }
 
+#[test]
+fn repl_runs_several_statements_per_line() {
+    let (mut repl, _) = init_repl("", vec![]);
+    assert_eq!(
+        detect_repl_continuation_mode("x = 1; y = 2\n"),
+        ReplContinuationMode::Complete
+    );
+    assert_eq!(
+        detect_repl_continuation_mode("if x: y = 3\n"),
+        ReplContinuationMode::Complete
+    );
+
+    assert_eq!(repl.feed_no_print("x = 1; y = 2").unwrap(), MontyObject::None);
+    assert_eq!(repl.feed_no_print("if x: y = 3").unwrap(), MontyObject::None);
+    assert_eq!(repl.feed_no_print("z = x + y; z").unwrap(), MontyObject::Int(4));
+    assert_eq!(
+        repl.feed_no_print("for i in range(3): z += i\nz").unwrap(),
+        MontyObject::Int(7)
+    );
+}
+
 #[test]
 fn repl_tracebacks_use_incrementing_python_input_filenames() {
     let (mut repl, init_output) = init_repl("", vec![]);
@@ -278,3 +314,302 @@ fn repl_dataclass_method_call_yields_function_call_with_method_flag() {
     // Verify REPL state is preserved after method call
     assert_eq!(repl.feed_no_print("1 + 1").unwrap(), MontyObject::Int(2));
 }
+
+#[test]
+fn repl_interned_size_limit_refuses_snippet() {
+    let limits = ResourceLimits::new().max_interned_size(100);
+    let (mut repl, _) = MontyRepl::new(
+        "x = 'a' * 10".to_owned(),
+        "repl.py",
+        vec![],
+        vec![],
+        vec![],
+        LimitedTracker::new(limits),
+        &mut PrintWriter::Stdout,
+    )
+    .unwrap();
+
+    // interned literals accumulate across snippets
+    repl.feed_no_print("y = 'abcdefghij' * 4").unwrap();
+    let exc = repl.feed_no_print(&format!("x = '{}'", "z".repeat(100))).unwrap_err();
+    assert_eq!(exc.exc_type(), ExcType::MemoryError);
+    assert!(
+        exc.message()
+            .is_some_and(|m| m.starts_with("interned data limit exceeded:")),
+        "unexpected message: {exc}"
+    );
+
+    // the refused snippet didn't run and the session is still usable
+    assert_eq!(repl.feed_no_print("len(x) + len(y)").unwrap(), MontyObject::Int(50));
+}
+
+#[test]
+fn repl_feed_with_os_handler_resolves_input() {
+    let (mut repl, _) = init_repl("", vec![]);
+    let mut lines = vec!["Bob", "Alice"];
+    let mut prompts = Vec::new();
+
+    let output = repl
+        .feed_with_os_handler(
+            "names = [input('first: '), input()]",
+            &mut PrintWriter::Stdout,
+            |function, args, _| {
+                assert_eq!(function, OsFunction::Input);
+                prompts.push(args.to_vec());
+                ExternalResult::Return(MontyObject::String(lines.pop().unwrap().to_owned()))
+            },
+        )
+        .unwrap();
+    assert_eq!(output, MontyObject::None);
+    assert_eq!(prompts, vec![vec![MontyObject::String("first: ".to_owned())], vec![]]);
+
+    // State from the snippet is kept for later snippets.
+    let output = repl.feed_no_print("names").unwrap();
+    assert_eq!(
+        output,
+        MontyObject::List(vec![
+            MontyObject::String("Alice".to_owned()),
+            MontyObject::String("Bob".to_owned()),
+        ])
+    );
+}
+
+#[test]
+fn repl_feed_with_os_handler_error_keeps_session() {
+    let (mut repl, _) = init_repl("x = 1", vec![]);
+
+    let err = repl
+        .feed_with_os_handler("input()", &mut PrintWriter::Stdout, |_, _, _| {
+            ExternalResult::Error(MontyException::new(
+                ExcType::EOFError,
+                Some("EOF when reading a line".to_owned()),
+            ))
+        })
+        .unwrap_err();
+    assert_eq!(err.exc_type(), ExcType::EOFError);
+
+    let output = repl.feed_no_print("x").unwrap();
+    assert_eq!(output, MontyObject::Int(1));
+}
+
+#[test]
+fn repl_feed_without_os_handler_rejects_input() {
+    let (mut repl, _) = init_repl("", vec![]);
+
+    let err = repl.feed_no_print("input()").unwrap_err();
+    assert_eq!(err.exc_type(), ExcType::NotImplementedError);
+}
+
+#[test]
+fn repl_named_namespaces_are_isolated() {
+    let (mut repl, _) = init_repl("x = 'default'", vec![]);
+    let out = &mut PrintWriter::Stdout;
+
+    repl.feed_in_namespace("analysis", "x = 'analysis'\ny = [1, 2]", out)
+        .unwrap();
+    repl.feed_in_namespace("session", "x = 'session'", out).unwrap();
+
+    assert_eq!(
+        repl.feed_no_print("x").unwrap(),
+        MontyObject::String("default".to_owned())
+    );
+    assert_eq!(
+        repl.feed_in_namespace("analysis", "y.append(3)\n(x, y)", out).unwrap(),
+        MontyObject::Tuple(vec![
+            MontyObject::String("analysis".to_owned()),
+            MontyObject::List(vec![MontyObject::Int(1), MontyObject::Int(2), MontyObject::Int(3)]),
+        ])
+    );
+    assert_eq!(
+        repl.feed_in_namespace("session", "x", out).unwrap(),
+        MontyObject::String("session".to_owned())
+    );
+
+    let exc = repl.feed_in_namespace("session", "y", out).unwrap_err();
+    assert_eq!(exc.exc_type(), ExcType::NameError);
+
+    let mut names = repl.namespace_names();
+    names.sort();
+    assert_eq!(names, vec!["analysis", "session"]);
+}
+
+#[test]
+fn repl_named_namespace_has_external_functions_and_dunders() {
+    let (repl, _) = init_repl("", vec!["fetch".to_owned()]);
+    let progress = repl
+        .start_in_namespace("cell", "r = fetch(1)\n(r, __name__)", &mut PrintWriter::Stdout)
+        .unwrap();
+    let (function_name, args, _, _, _, state) = progress.into_function_call().unwrap();
+    assert_eq!(function_name, "fetch");
+    assert_eq!(args, vec![MontyObject::Int(1)]);
+
+    let progress = state.run(MontyObject::Int(2), &mut PrintWriter::Stdout).unwrap();
+    let (mut repl, value) = progress.into_complete().unwrap();
+    assert_eq!(
+        value,
+        MontyObject::Tuple(vec![MontyObject::Int(2), MontyObject::String("__main__".to_owned())])
+    );
+
+    // the completed REPL is back in the default namespace
+    let exc = repl.feed_no_print("r").unwrap_err();
+    assert_eq!(exc.exc_type(), ExcType::NameError);
+    assert_eq!(
+        repl.feed_in_namespace("cell", "r", &mut PrintWriter::Stdout).unwrap(),
+        MontyObject::Int(2)
+    );
+}
+
+#[test]
+fn repl_named_namespace_survives_errors_and_dump() {
+    let (mut repl, _) = init_repl("", vec![]);
+    let out = &mut PrintWriter::Stdout;
+    repl.feed_in_namespace("a", "items = {'k': 1}", out).unwrap();
+    repl.feed_in_namespace("a", "items['j'] = 2\n1 / 0", out).unwrap_err();
+    repl.feed_in_namespace("a", "def broken(:", out).unwrap_err();
+    assert!(repl.feed_no_print("items").is_err());
+
+    let mut repl: MontyRepl<NoLimitTracker> = MontyRepl::load(&repl.dump().unwrap()).unwrap();
+    assert_eq!(
+        repl.feed_in_namespace("a", "len(items)", &mut PrintWriter::Stdout)
+            .unwrap(),
+        MontyObject::Int(2)
+    );
+}
+
+#[test]
+fn repl_underscore_is_last_result() {
+    let (mut repl, _) = init_repl("", vec![]);
+    let exc = repl.feed_no_print("_").unwrap_err();
+    assert_eq!(exc.exc_type(), ExcType::NameError);
+
+    repl.feed_no_print("[1, 2]").unwrap();
+    assert_eq!(repl.feed_no_print("len(_)").unwrap(), MontyObject::Int(2));
+    // `None` results leave `_` alone
+    repl.feed_no_print("x = _ * 10").unwrap();
+    repl.feed_no_print("print('hi')").unwrap();
+    assert_eq!(repl.feed_no_print("_ + 1").unwrap(), MontyObject::Int(3));
+    assert_eq!(repl.feed_no_print("_").unwrap(), MontyObject::Int(3));
+    assert_eq!(repl.feed_no_print("x").unwrap(), MontyObject::Int(20));
+}
+
+#[test]
+fn repl_underscore_after_external_call() {
+    let (repl, _) = init_repl("", vec!["ext_fn".to_owned()]);
+    let progress = repl.start("ext_fn() + 1", &mut PrintWriter::Stdout).unwrap();
+    let (.., state) = progress.into_function_call().expect("expected function call");
+    let progress = state.run(MontyObject::Int(41), &mut PrintWriter::Stdout).unwrap();
+    let (mut repl, _) = progress.into_complete().expect("expected completion");
+
+    assert_eq!(repl.feed_no_print("_").unwrap(), MontyObject::Int(42));
+    assert_eq!(repl.history(5), [MontyObject::Int(42), MontyObject::Int(42)]);
+}
+
+#[test]
+fn repl_history_keeps_recent_results() {
+    let (mut repl, _) = init_repl("", vec![]);
+    for i in 0..5 {
+        repl.feed_no_print(&format!("{i} * 2")).unwrap();
+        repl.feed_no_print("y = 1").unwrap();
+    }
+    assert_eq!(repl.history(2), [MontyObject::Int(6), MontyObject::Int(8)]);
+    assert_eq!(repl.history(10).len(), 5);
+
+    repl.set_history_size(3);
+    assert_eq!(
+        repl.history(10),
+        [MontyObject::Int(4), MontyObject::Int(6), MontyObject::Int(8)]
+    );
+
+    let repl: MontyRepl<NoLimitTracker> = MontyRepl::load(&repl.dump().unwrap()).unwrap();
+    assert_eq!(repl.history(1), [MontyObject::Int(8)]);
+}
+
+#[test]
+fn repl_soft_keywords_are_names_outside_their_statements() {
+    assert_eq!(
+        detect_repl_continuation_mode("match = [1]\n"),
+        ReplContinuationMode::Complete
+    );
+    assert_eq!(
+        detect_repl_continuation_mode("match(1)\n"),
+        ReplContinuationMode::Complete
+    );
+    assert_eq!(
+        detect_repl_continuation_mode("match command:\n"),
+        ReplContinuationMode::IncompleteBlock
+    );
+
+    let (mut repl, _) = init_repl("", vec![]);
+    repl.feed_no_print("match = 5\ncase = 2").unwrap();
+    repl.feed_no_print("type = [match, case]").unwrap();
+    assert_eq!(repl.feed_no_print("type[0] * case").unwrap(), MontyObject::Int(10));
+    assert_eq!(repl.feed_no_print("_ = 1; _ + match").unwrap(), MontyObject::Int(6));
+}
+
+fn feed_collect(repl: &mut MontyRepl<NoLimitTracker>, code: &str) -> String {
+    let mut writer = PrintWriter::Collect(String::new());
+    repl.feed(code, &mut writer).unwrap();
+    writer.collected_output().unwrap().to_owned()
+}
+
+#[test]
+fn repl_help_describes_functions() {
+    let (mut repl, _) = init_repl("", vec!["fetch".to_owned()]);
+    repl.feed_no_print(
+        "def scale(values, /, factor=2, *rest, clamp, limit=None, **opts):
+    \"\"\"Scale values.
+
+    Multiplies each value by factor.
+    \"\"\"
+    return [v * factor for v in values]",
+    )
+    .unwrap();
+    assert_eq!(
+        feed_collect(&mut repl, "help(scale)"),
+        "Help on function scale:\n\n\
+         scale(values, /, factor=2, *rest, clamp, limit=None, **opts)\n    \
+         Scale values.\n\n    \
+         Multiplies each value by factor.\n"
+    );
+
+    repl.feed_no_print("def outer(n):\n    async def inner(x='a'):\n        return n\n    return inner")
+        .unwrap();
+    assert_eq!(
+        feed_collect(&mut repl, "help(outer(1))"),
+        "Help on function inner:\n\nasync inner(x='a')\n"
+    );
+
+    assert_eq!(
+        feed_collect(&mut repl, "help(len)"),
+        "Help on built-in function len:\n\nlen(obj, /)\n    Return the number of items in a container.\n"
+    );
+    assert_eq!(
+        feed_collect(&mut repl, "help(fetch)"),
+        "Help on external function fetch:\n\nfetch(...)\n"
+    );
+    assert_eq!(
+        feed_collect(&mut repl, "help([1])"),
+        "Help on list object:\n\nclass list\n"
+    );
+    assert_eq!(
+        repl.feed_no_print("help(len)").unwrap(),
+        MontyObject::None,
+        "help() returns None"
+    );
+}
+
+#[test]
+fn repl_start_error_keeps_session() {
+    let (repl, _) = init_repl("x = 1", vec!["ext_fn".to_owned()]);
+    let err = repl.start_no_print("def broken(:").unwrap_err();
+    assert_eq!(err.error.exc_type(), ExcType::SyntaxError);
+
+    let progress = err.repl.start_no_print("y = ext_fn()\n1 / 0").unwrap();
+    let (.., state) = progress.into_function_call().expect("expected function call");
+    let err = state.run(MontyObject::Int(2), &mut PrintWriter::Stdout).unwrap_err();
+    assert_eq!(err.error.exc_type(), ExcType::ZeroDivisionError);
+
+    // globals assigned before the error are kept, as with `feed`
+    let mut repl = err.repl;
+    assert_eq!(repl.feed_no_print("x + y").unwrap(), MontyObject::Int(3));
+}
@@ -2,9 +2,18 @@
 ///
 /// These tests verify that the `ResourceTracker` system correctly enforces
 /// allocation limits, time limits, and triggers garbage collection.
-use std::time::{Duration, Instant};
-
-use monty::{ExcType, LimitedTracker, MontyObject, MontyRun, PrintWriter, ResourceLimits};
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use monty::{
+    ExcType, InterruptCheck, InterruptTracker, LimitedTracker, MontyException, MontyObject, MontyRun, NoLimitTracker,
+    PrintWriter, ResourceError, ResourceLimits, ResourceTracker,
+};
 
 /// Test that GC properly collects dict cycles via the has_refs() check in allocate().
 ///
@@ -495,6 +504,72 @@ recurse(5)
     assert!(result.is_ok(), "should not exceed recursion depth limit");
 }
 
+/// Test that the traceback of a `RecursionError` reports the depth of each frame.
+#[test]
+fn recursion_depth_reported_on_frames() {
+    let code = r"
+def recurse(n):
+    if n > 0:
+        return recurse(n - 1)
+    return 0
+recurse(100)
+";
+    let ex = MontyRun::new(code.to_owned(), "test.py", vec![], vec![]).unwrap();
+
+    let limits = ResourceLimits::new().max_recursion_depth(Some(10));
+    let exc = ex
+        .run(vec![], LimitedTracker::new(limits), &mut PrintWriter::Stdout)
+        .unwrap_err();
+
+    let depths: Vec<usize> = exc.traceback().iter().map(|frame| frame.depth).collect();
+    assert_eq!(depths, (0..=10).collect::<Vec<_>>());
+}
+
+/// Test that exceeding `max_stack_size` raises `RecursionError` instead of overflowing the stack.
+///
+/// A zero limit fails on the first function call, since calling it always uses some stack.
+#[test]
+fn stack_size_limit_exceeded() {
+    let code = r"
+def f():
+    return 1
+f()
+";
+    let ex = MontyRun::new(code.to_owned(), "test.py", vec![], vec![]).unwrap();
+
+    let limits = ResourceLimits::new().max_stack_size(Some(0));
+    let exc = ex
+        .run(vec![], LimitedTracker::new(limits), &mut PrintWriter::Stdout)
+        .unwrap_err();
+
+    assert_eq!(exc.exc_type(), ExcType::RecursionError);
+    assert!(
+        exc.message()
+            .is_some_and(|m| m.starts_with("maximum recursion depth exceeded: stack usage")),
+        "expected stack usage error, got: {exc}"
+    );
+}
+
+/// Test that the default stack limit leaves room for deep recursion up to the recursion limit.
+#[test]
+fn stack_size_limit_default_allows_recursion_limit() {
+    let code = r"
+def recurse(n):
+    if n > 0:
+        return recurse(n - 1)
+    return 0
+recurse(900)
+";
+    let ex = MontyRun::new(code.to_owned(), "test.py", vec![], vec![]).unwrap();
+
+    let result = ex.run(
+        vec![],
+        LimitedTracker::new(ResourceLimits::new()),
+        &mut PrintWriter::Stdout,
+    );
+    assert_eq!(result.unwrap(), MontyObject::Int(0));
+}
+
 // === BigInt large result pre-check tests ===
 // These tests verify that operations that would produce very large BigInt results
 // are rejected before the computation begins, preventing DoS attacks.
@@ -1369,3 +1444,165 @@ repr(x)
 ";
     assert_repr_timeout(code, "set repr");
 }
+
+/// Test that a result larger than `max_output_size` raises `MemoryError` instead of being returned.
+///
+/// The list holds the same 1KB string 1000 times, so it's cheap inside the sandbox but would
+/// be converted to ~1MB of output.
+#[test]
+fn max_output_size_exceeded_by_result() {
+    let code = "s = 'x' * 1000\n[s] * 1000";
+    let ex = MontyRun::new(code.to_owned(), "test.py", vec![], vec![]).unwrap();
+
+    let limits = ResourceLimits::new().max_output_size(100_000);
+    let result = ex.run(vec![], LimitedTracker::new(limits), &mut PrintWriter::Stdout);
+
+    let exc = result.unwrap_err();
+    assert_eq!(exc.exc_type(), ExcType::MemoryError);
+    assert_eq!(
+        exc.message(),
+        Some("output size limit exceeded: value is larger than 100000 bytes")
+    );
+}
+
+/// Test that results within `max_output_size` are returned as normal.
+#[test]
+fn max_output_size_not_exceeded() {
+    let code = "['x' * 100] * 10";
+    let ex = MontyRun::new(code.to_owned(), "test.py", vec![], vec![]).unwrap();
+
+    let limits = ResourceLimits::new().max_output_size(100_000);
+    let result = ex.run(vec![], LimitedTracker::new(limits), &mut PrintWriter::Stdout);
+
+    let expected = MontyObject::List(vec![MontyObject::String("x".repeat(100)); 10]);
+    assert_eq!(result.unwrap(), expected);
+}
+
+/// Test that arguments to external functions are checked against `max_output_size` together,
+/// and that the error can't be caught by the script.
+#[test]
+fn max_output_size_exceeded_by_external_call_args() {
+    let code = r"
+s = 'x' * 600
+try:
+    send(s, data=s)
+except MemoryError:
+    pass
+";
+    let ex = MontyRun::new(code.to_owned(), "test.py", vec![], vec!["send".to_owned()]).unwrap();
+
+    let limits = ResourceLimits::new().max_output_size(1_000);
+    let result = ex.start(vec![], LimitedTracker::new(limits), &mut PrintWriter::Stdout);
+
+    let exc = result.unwrap_err();
+    assert_eq!(exc.exc_type(), ExcType::MemoryError);
+    assert_eq!(
+        exc.message(),
+        Some("output size limit exceeded: value is larger than 1000 bytes")
+    );
+}
+
+/// Interrupt check that stops execution once a shared flag is set, like a cancellation token.
+#[derive(Debug)]
+struct CancelFlag(Arc<AtomicBool>);
+
+impl InterruptCheck for CancelFlag {
+    fn check(&self) -> Result<(), ResourceError> {
+        if self.0.load(Ordering::Relaxed) {
+            Err(ResourceError::Exception(MontyException::new(
+                ExcType::KeyboardInterrupt,
+                Some("cancelled".to_owned()),
+            )))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Test that chained trackers enforce the limits of both trackers.
+#[test]
+fn chained_trackers_enforce_both_limits() {
+    let code = "[i for i in range(1000)]";
+    let ex = MontyRun::new(code.to_owned(), "test.py", vec![], vec![]).unwrap();
+
+    // the limit of the second tracker applies
+    let tracker =
+        LimitedTracker::new(ResourceLimits::new()).chain(LimitedTracker::new(ResourceLimits::new().max_memory(100)));
+    let exc = ex.run(vec![], tracker, &mut PrintWriter::Stdout).unwrap_err();
+    assert_eq!(exc.exc_type(), ExcType::MemoryError);
+
+    // and so does the limit of the first
+    let tracker = LimitedTracker::new(ResourceLimits::new().max_memory(100)).chain(NoLimitTracker);
+    let exc = ex.run(vec![], tracker, &mut PrintWriter::Stdout).unwrap_err();
+    assert_eq!(exc.exc_type(), ExcType::MemoryError);
+
+    let tracker = LimitedTracker::new(ResourceLimits::new()).chain(NoLimitTracker);
+    let result = ex.run(vec![], tracker, &mut PrintWriter::Stdout).unwrap();
+    assert!(matches!(result, MontyObject::List(items) if items.len() == 1000));
+}
+
+/// Test that an `InterruptTracker` chained to a limited tracker can cancel execution.
+#[test]
+fn interrupt_tracker_cancels_execution() {
+    let code = r"
+x = 0
+while True:
+    try:
+        x += 1
+    except BaseException:
+        pass
+";
+    let ex = MontyRun::new(code.to_owned(), "test.py", vec![], vec![]).unwrap();
+
+    let cancelled = Arc::new(AtomicBool::new(true));
+    let tracker = LimitedTracker::new(ResourceLimits::new()).chain(InterruptTracker::new(CancelFlag(cancelled), 10));
+    let exc = ex.run(vec![], tracker, &mut PrintWriter::Stdout).unwrap_err();
+
+    assert_eq!(exc.exc_type(), ExcType::KeyboardInterrupt);
+    assert_eq!(exc.message(), Some("cancelled"));
+}
+
+/// Test that an `InterruptTracker` doesn't stop execution until its check fails.
+#[test]
+fn interrupt_tracker_allows_execution_until_cancelled() {
+    let code = "sum(range(1000))";
+    let ex = MontyRun::new(code.to_owned(), "test.py", vec![], vec![]).unwrap();
+
+    let cancelled = Arc::new(AtomicBool::new(false));
+    let tracker = NoLimitTracker.chain(InterruptTracker::new(CancelFlag(cancelled), 1));
+    let result = ex.run(vec![], tracker, &mut PrintWriter::Stdout).unwrap();
+    assert_eq!(result, MontyObject::Int(499_500));
+}
+
+/// Test that a decompression bomb is rejected by memory limits before it's fully inflated.
+///
+/// Each gzip member holds 1MB of zeros in about 1KB, so 50 concatenated members would
+/// decompress to 50MB from an input of around 50KB.
+#[test]
+fn decompression_bomb_memory_limit() {
+    let code = "import gzip\nmember = gzip.compress(b'\\x00' * 1_000_000)\ngzip.decompress(member * 50)";
+    let ex = MontyRun::new(code.to_owned(), "test.py", vec![], vec![]).unwrap();
+
+    let limits = ResourceLimits::new().max_memory(5_000_000);
+    let result = ex.run(vec![], LimitedTracker::new(limits), &mut PrintWriter::Stdout);
+
+    let exc = result.unwrap_err();
+    assert_eq!(exc.exc_type(), ExcType::MemoryError);
+    assert!(
+        exc.message().is_some_and(|m| m.contains("memory limit exceeded")),
+        "expected memory limit error, got: {exc}"
+    );
+}
+
+/// Test that decompressed data within the memory limit is allowed.
+#[test]
+fn decompression_within_limit() {
+    let code = "import zlib\nlen(zlib.decompress(zlib.compress(b'\\x00' * 1_000_000)))";
+    let ex = MontyRun::new(code.to_owned(), "test.py", vec![], vec![]).unwrap();
+
+    let limits = ResourceLimits::new().max_memory(5_000_000);
+    let result = ex
+        .run(vec![], LimitedTracker::new(limits), &mut PrintWriter::Stdout)
+        .unwrap();
+    assert_eq!(result, MontyObject::Int(1_000_000));
+}
@@ -0,0 +1,158 @@
+//! Tests for rendering tracebacks with `TracebackOptions`.
+
+use monty::{MontyException, MontyRepl, MontyRun, NoLimitTracker, PrintWriter, TracebackOptions, TracebackStyle};
+
+const CODE: &str = "\
+def ratio(total, count):
+    return total // count
+
+
+def percent(total, count):
+    return ratio(total, count) * 100
+
+
+percent(3, 0)
+";
+
+fn run_error(code: &str) -> MontyException {
+    let run = MontyRun::new(code.to_owned(), "main.py", vec![], vec![]).unwrap();
+    run.run_no_limits(vec![]).unwrap_err()
+}
+
+#[test]
+fn default_options_match_display() {
+    let exc = run_error(CODE);
+    assert_eq!(exc.render(&TracebackOptions::default()), exc.to_string());
+    assert_eq!(
+        exc.render(&TracebackOptions::new()),
+        r#"Traceback (most recent call last):
+  File "main.py", line 9, in <module>
+    percent(3, 0)
+    ~~~~~~~^^^^^^
+  File "main.py", line 6, in percent
+    return ratio(total, count) * 100
+           ~~~~~^^^^^^^^^^^^^^
+  File "main.py", line 2, in ratio
+    return total // count
+           ~~~~~~^^~~~~~~
+ZeroDivisionError: integer division or modulo by zero"#
+    );
+}
+
+#[test]
+fn short_style_omits_carets() {
+    let exc = run_error(CODE);
+    let options = TracebackOptions::new().style(TracebackStyle::Short);
+    assert_eq!(
+        exc.render(&options),
+        r#"Traceback (most recent call last):
+  File "main.py", line 9, in <module>
+    percent(3, 0)
+  File "main.py", line 6, in percent
+    return ratio(total, count) * 100
+  File "main.py", line 2, in ratio
+    return total // count
+ZeroDivisionError: integer division or modulo by zero"#
+    );
+}
+
+#[test]
+fn line_style_uses_innermost_frame() {
+    let exc = run_error(CODE);
+    let options = TracebackOptions::new().style(TracebackStyle::Line);
+    assert_eq!(
+        exc.render(&options),
+        "main.py:2: ZeroDivisionError: integer division or modulo by zero"
+    );
+}
+
+#[test]
+fn max_frames_keeps_innermost_frames() {
+    let exc = run_error(CODE);
+    let options = TracebackOptions::new().style(TracebackStyle::Short).max_frames(1);
+    assert_eq!(
+        exc.render(&options),
+        r#"Traceback (most recent call last):
+  File "main.py", line 2, in ratio
+    return total // count
+ZeroDivisionError: integer division or modulo by zero"#
+    );
+
+    let options = TracebackOptions::new().max_frames(0);
+    assert_eq!(
+        exc.render(&options),
+        "ZeroDivisionError: integer division or modulo by zero"
+    );
+
+    let options = TracebackOptions::new().style(TracebackStyle::Line).max_frames(0);
+    assert_eq!(
+        exc.render(&options),
+        "ZeroDivisionError: integer division or modulo by zero"
+    );
+}
+
+#[test]
+fn max_frames_applies_before_collapsing_repeats() {
+    let exc = run_error("def f(n):\n    return f(n + 1) if n < 10 else 1 // 0\n\nf(0)\n");
+    let rendered = exc.render(&TracebackOptions::new().style(TracebackStyle::Short).max_frames(5));
+    assert_eq!(
+        rendered,
+        r#"Traceback (most recent call last):
+  File "main.py", line 2, in f
+    return f(n + 1) if n < 10 else 1 // 0
+  File "main.py", line 2, in f
+    return f(n + 1) if n < 10 else 1 // 0
+  File "main.py", line 2, in f
+    return f(n + 1) if n < 10 else 1 // 0
+  [Previous line repeated 2 more times]
+ZeroDivisionError: integer division or modulo by zero"#
+    );
+}
+
+#[test]
+fn notes_follow_the_message() {
+    let exc = run_error("e = ValueError('bad')\ne.add_note('hint')\nraise e\n");
+    let options = TracebackOptions::new().style(TracebackStyle::Short);
+    assert_eq!(
+        exc.render(&options),
+        "Traceback (most recent call last):\n  File \"main.py\", line 3, in <module>\n    raise e\nValueError: bad\nhint"
+    );
+    let options = TracebackOptions::new().style(TracebackStyle::Line);
+    assert_eq!(exc.render(&options), "main.py:3: ValueError: bad");
+}
+
+#[test]
+fn internal_files_are_hidden() {
+    let (mut repl, _) = MontyRepl::new(
+        "def check(x):\n    if x < 0:\n        raise ValueError('negative')\n    return x\n".to_owned(),
+        "helpers.py",
+        vec![],
+        vec![],
+        vec![],
+        NoLimitTracker,
+        &mut PrintWriter::Stdout,
+    )
+    .unwrap();
+    let exc = repl.feed_no_print("check(-1)").unwrap_err();
+    assert_eq!(exc.traceback().len(), 2);
+
+    let options = TracebackOptions::new()
+        .style(TracebackStyle::Short)
+        .internal_file("helpers.py");
+    assert_eq!(
+        exc.render(&options),
+        "Traceback (most recent call last):\n  File \"<python-input-0>\", line 1, in <module>\n    check(-1)\nValueError: negative"
+    );
+}
+
+#[test]
+fn innermost_frame_kept_when_all_frames_are_internal() {
+    let exc = run_error(CODE);
+    let options = TracebackOptions::new()
+        .style(TracebackStyle::Line)
+        .internal_file("main.py");
+    assert_eq!(
+        exc.render(&options),
+        "main.py:2: ZeroDivisionError: integer division or modulo by zero"
+    );
+}
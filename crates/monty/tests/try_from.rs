@@ -1,4 +1,6 @@
-use monty::MontyRun;
+use std::collections::{BTreeMap, HashMap};
+
+use monty::{MontyObject, MontyRun, object};
 
 /// Tests for successful TryFrom conversions from Python values to Rust types.
 ///
@@ -165,3 +167,101 @@ fn try_from_err_none_to_bool() {
     let err = TryInto::<bool>::try_into(&result).expect_err("conversion should fail");
     assert_eq!(err.to_string(), "expected bool, got NoneType");
 }
+
+/// Tests for converting nested Python structures to Rust collections and tuples.
+
+#[test]
+fn try_from_ok_nested_structures() {
+    let ex = MontyRun::new(
+        "{'a': [1, 2], 'b': [3]}, [('x', 1.5), ('y', 2)]".to_owned(),
+        "test.py",
+        vec![],
+        vec![],
+    )
+    .unwrap();
+    let result = ex.run_no_limits(vec![]).unwrap();
+    let (groups, points): (HashMap<String, Vec<i64>>, Vec<(String, f64)>) =
+        (&result).try_into().expect("conversion should succeed");
+    assert_eq!(
+        groups,
+        HashMap::from([("a".to_string(), vec![1, 2]), ("b".to_string(), vec![3])])
+    );
+    assert_eq!(points, vec![("x".to_string(), 1.5), ("y".to_string(), 2.0)]);
+}
+
+#[test]
+fn try_from_err_nested_item() {
+    let ex = MontyRun::new("[1, 'two']".to_owned(), "test.py", vec![], vec![]).unwrap();
+    let result = ex.run_no_limits(vec![]).unwrap();
+    let err = TryInto::<Vec<i64>>::try_into(&result).expect_err("conversion should fail");
+    assert_eq!(err.to_string(), "expected int, got str");
+}
+
+#[test]
+fn try_from_err_tuple_length() {
+    let ex = MontyRun::new("(1, 2, 3)".to_owned(), "test.py", vec![], vec![]).unwrap();
+    let result = ex.run_no_limits(vec![]).unwrap();
+    let err = TryInto::<(i64, i64)>::try_into(&result).expect_err("conversion should fail");
+    assert_eq!(err.to_string(), "expected tuple of length 2, got tuple");
+}
+
+/// Tests for building `MontyObject` inputs with `From` and the `object!` macro.
+
+#[test]
+fn from_rust_values() {
+    assert_eq!(MontyObject::from(7u8), MontyObject::Int(7));
+    assert_eq!(MontyObject::from(u64::MAX), MontyObject::BigInt(u64::MAX.into()));
+    assert_eq!(MontyObject::from("hi"), MontyObject::String("hi".to_string()));
+    assert_eq!(MontyObject::from(None::<i64>), MontyObject::None);
+    assert_eq!(
+        MontyObject::from(vec![Some(1), None]),
+        MontyObject::List(vec![MontyObject::Int(1), MontyObject::None])
+    );
+    assert_eq!(
+        MontyObject::from((1, "a", true)),
+        MontyObject::Tuple(vec![
+            MontyObject::Int(1),
+            MontyObject::String("a".to_string()),
+            MontyObject::Bool(true)
+        ])
+    );
+    assert_eq!(
+        MontyObject::from(BTreeMap::from([("b", 2), ("a", 1)])),
+        MontyObject::dict(vec![
+            (MontyObject::String("a".to_string()), MontyObject::Int(1)),
+            (MontyObject::String("b".to_string()), MontyObject::Int(2)),
+        ])
+    );
+}
+
+#[test]
+fn object_macro() {
+    let limit = 10;
+    let obj = object!({
+        "name": "monty",
+        "limit": limit * 2,
+        "offset": -1,
+        "tags": ["a", ["b", None], {}],
+        "pair": (1, 2.5),
+        "empty": [],
+    });
+    assert_eq!(
+        obj.py_repr(),
+        "{'name': 'monty', 'limit': 20, 'offset': -1, 'tags': ['a', ['b', None], {}], 'pair': (1, 2.5), 'empty': []}"
+    );
+    assert_eq!(object!(None), MontyObject::None);
+    assert_eq!(object!([1, 2,]), MontyObject::from(vec![1, 2]));
+}
+
+#[test]
+fn object_macro_as_input() {
+    let ex = MontyRun::new(
+        "data['xs'][1] + len(data)".to_owned(),
+        "test.py",
+        vec!["data".to_owned()],
+        vec![],
+    )
+    .unwrap();
+    let result = ex.run_no_limits(vec![object!({"xs": [1, 41], 2: None})]).unwrap();
+    assert_eq!(result, MontyObject::Int(43));
+}
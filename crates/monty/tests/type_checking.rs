@@ -0,0 +1,131 @@
+#![cfg(feature = "type-checking")]
+
+use monty::{
+    ExtFunctionSignature, ExtParam, ExtParamKind, MontyObject, MontyRun, NoLimitTracker, PrintWriter, RunProgress,
+    TypeCheckError, TypeCheckMode,
+};
+
+/// Code with a type error that still runs fine, since the bad call is never executed.
+const BAD_CODE: &str = "def f(x: int) -> int:\n    return x\n\ndef g():\n    return f('a')\n\n1";
+
+#[test]
+fn off_skips_type_checking() {
+    let runner = MontyRun::new_checked(BAD_CODE.to_owned(), "main.py", vec![], vec![], None, TypeCheckMode::Off)
+        .expect("type checking is off");
+    assert_eq!(runner.type_check_warnings(), None);
+    assert_eq!(runner.run_no_limits(vec![]).unwrap(), MontyObject::Int(1));
+}
+
+#[test]
+fn warn_records_diagnostics() {
+    let runner = MontyRun::new_checked(
+        BAD_CODE.to_owned(),
+        "main.py",
+        vec![],
+        vec![],
+        None,
+        TypeCheckMode::Warn,
+    )
+    .expect("warn mode still creates the runner");
+    let warnings = runner.type_check_warnings().expect("expected warnings");
+    assert!(warnings.contains("invalid-argument-type"), "got: {warnings}");
+    assert_eq!(runner.run_no_limits(vec![]).unwrap(), MontyObject::Int(1));
+}
+
+#[test]
+fn warn_diagnostics_reach_snapshots_and_completion() {
+    let runner = MontyRun::new_checked(
+        format!("{BAD_CODE} + fetch()"),
+        "main.py",
+        vec![],
+        vec!["fetch".to_owned()],
+        Some("def fetch() -> int: ..."),
+        TypeCheckMode::Warn,
+    )
+    .expect("warn mode still creates the runner");
+    let expected = runner.type_check_warnings().expect("expected warnings").to_owned();
+
+    let progress = runner.start(vec![], NoLimitTracker, &mut PrintWriter::Stdout).unwrap();
+    let loaded: RunProgress<NoLimitTracker> = RunProgress::load(&progress.dump().unwrap()).unwrap();
+    let (_, _, _, _, _, state) = loaded.into_function_call().expect("expected fetch call");
+    assert_eq!(state.type_check_warnings(), Some(expected.as_str()));
+
+    let progress = state.run(MontyObject::Int(2), &mut PrintWriter::Stdout).unwrap();
+    let RunProgress::Complete {
+        value,
+        type_check_warnings,
+        ..
+    } = progress
+    else {
+        panic!("expected completion");
+    };
+    assert_eq!(value, MontyObject::Int(3));
+    assert_eq!(type_check_warnings, Some(expected));
+}
+
+#[test]
+fn strict_rejects_diagnostics() {
+    let err = MontyRun::new_checked(
+        BAD_CODE.to_owned(),
+        "main.py",
+        vec![],
+        vec![],
+        None,
+        TypeCheckMode::Strict,
+    )
+    .expect_err("strict mode should fail");
+    assert!(matches!(err, TypeCheckError::Failed(_)), "got: {err}");
+}
+
+#[test]
+fn strict_accepts_clean_code() {
+    let runner = MontyRun::new_checked(
+        "1 + 2".to_owned(),
+        "main.py",
+        vec![],
+        vec![],
+        None,
+        TypeCheckMode::Strict,
+    )
+    .expect("clean code passes");
+    assert_eq!(runner.type_check_warnings(), None);
+    assert_eq!(runner.run_no_limits(vec![]).unwrap(), MontyObject::Int(3));
+}
+
+#[test]
+fn stubs_declare_inputs() {
+    let runner = MontyRun::new_checked(
+        "x + 1".to_owned(),
+        "main.py",
+        vec!["x".to_owned()],
+        vec![],
+        Some("x: int"),
+        TypeCheckMode::Strict,
+    )
+    .expect("stubs declare the input");
+    assert_eq!(
+        runner.run_no_limits(vec![MontyObject::Int(41)]).unwrap(),
+        MontyObject::Int(42)
+    );
+}
+
+#[test]
+fn parse_errors_are_exceptions() {
+    let err = MontyRun::new_checked("1 +".to_owned(), "main.py", vec![], vec![], None, TypeCheckMode::Warn)
+        .expect_err("syntax error");
+    assert!(matches!(err, TypeCheckError::Exception(_)), "got: {err}");
+}
+
+#[test]
+fn registered_signatures_are_declared() {
+    let mut runner = MontyRun::new("fetch(1)".to_owned(), "main.py", vec![], vec!["fetch".to_owned()]).unwrap();
+    let signature = ExtFunctionSignature::new("fetch")
+        .param(ExtParam::new("url", ExtParamKind::PositionalOrKeyword).type_hint("str"))
+        .return_type("str");
+    runner.register_external_signature(signature).unwrap();
+
+    runner.type_check("main.py", None, TypeCheckMode::Warn).unwrap();
+    let warnings = runner.type_check_warnings().expect("expected warnings");
+    assert!(warnings.contains("invalid-argument-type"), "got: {warnings}");
+    assert!(!warnings.contains("unresolved-reference"), "got: {warnings}");
+}
@@ -0,0 +1,84 @@
+#![cfg(feature = "vm-metrics")]
+
+use monty::{MontyObject, MontyRun, NoLimitTracker, PrintWriter, VmMetrics};
+
+fn run_with_metrics(code: &str) -> (MontyObject, VmMetrics) {
+    let runner = MontyRun::new(code.to_owned(), "main.py", vec![], vec![]).unwrap();
+    let (result, metrics) = runner.run_with_metrics(vec![], NoLimitTracker, &mut PrintWriter::Stdout);
+    (result.unwrap(), metrics)
+}
+
+#[test]
+fn counts_opcodes() {
+    let (result, metrics) = run_with_metrics("x = 1\ny = 2\nx + y");
+    assert_eq!(result, MontyObject::Int(3));
+    assert_eq!(metrics.opcode_count("BinaryAdd"), 1);
+    assert_eq!(
+        metrics.instructions,
+        metrics.opcodes.iter().map(|(_, count)| count).sum::<u64>()
+    );
+    assert!(metrics.opcodes.windows(2).all(|w| w[0].1 >= w[1].1));
+    assert_eq!(metrics.opcode_count("NotAnOpcode"), 0);
+}
+
+#[test]
+fn counts_function_calls() {
+    let code = "
+def fib(n):
+    if n < 2:
+        return n
+    return fib(n - 1) + fib(n - 2)
+
+def main():
+    return fib(10)
+
+main()";
+    let (result, metrics) = run_with_metrics(code);
+    assert_eq!(result, MontyObject::Int(55));
+    assert_eq!(metrics.call_count("fib"), 177);
+    assert_eq!(metrics.call_count("main"), 1);
+    assert_eq!(metrics.function_calls[0].0, "fib");
+}
+
+#[test]
+fn counts_branches() {
+    // `i % 2` alternates, so the one-bit predictor misses every time after the first
+    let code = "
+odd = 0
+for i in range(10):
+    if i % 2:
+        odd += 1
+odd";
+    let (result, metrics) = run_with_metrics(code);
+    assert_eq!(result, MontyObject::Int(5));
+    assert_eq!(metrics.branches_taken + metrics.branches_not_taken, 10);
+    assert_eq!(metrics.branches_taken, 5);
+    assert_eq!(metrics.branch_mispredictions, 10);
+}
+
+#[test]
+fn predictable_branches() {
+    let code = "
+n = 0
+for i in range(100):
+    if i >= 0:
+        n += 1
+n";
+    let (result, metrics) = run_with_metrics(code);
+    assert_eq!(result, MontyObject::Int(100));
+    assert!(metrics.branch_mispredictions <= 1);
+}
+
+#[test]
+fn deterministic() {
+    let code = "sorted([x * 3 % 7 for x in range(20)])";
+    assert_eq!(run_with_metrics(code).1, run_with_metrics(code).1);
+}
+
+#[test]
+fn metrics_returned_on_error() {
+    let runner = MontyRun::new("x = 1\n1 / 0".to_owned(), "main.py", vec![], vec![]).unwrap();
+    let (result, metrics) = runner.run_with_metrics(vec![], NoLimitTracker, &mut PrintWriter::Stdout);
+    assert!(result.is_err());
+    assert!(metrics.instructions > 0);
+}
@@ -0,0 +1,164 @@
+#!/usr/bin/env python3
+"""Generate `crates/monty/src/unicode_tables.rs` from CPython's `unicodedata`.
+
+The tables record exactly which characters CPython's `str` predicates accept and where its
+case mappings differ from Rust's, so Monty's `str` methods follow the Unicode version of the
+Python running this script. Re-run it with the CPython version Monty targets and commit the output.
+"""
+
+from __future__ import annotations
+
+import sys
+import unicodedata
+from collections.abc import Callable
+from pathlib import Path
+
+OUTPUT = Path(__file__).parent.parent / 'crates' / 'monty' / 'src' / 'unicode_tables.rs'
+
+
+HELPERS = """/// Returns whether `c` falls in one of the sorted, inclusive ranges of `table`.
+pub(crate) fn in_ranges(table: &[(u32, u32)], c: char) -> bool {
+    let cp = u32::from(c);
+    table
+        .binary_search_by(|&(lo, hi)| {
+            if hi < cp {
+                Ordering::Less
+            } else if lo > cp {
+                Ordering::Greater
+            } else {
+                Ordering::Equal
+            }
+        })
+        .is_ok()
+}
+
+/// Returns the value of the decimal digit `c`, like `unicodedata.decimal()`.
+///
+/// Decimal digits come in runs of ten from zero to nine, so the value is the offset into
+/// the run. The generator checks that every range in [`DECIMAL`] is made of such runs.
+pub(crate) fn decimal_value(c: char) -> Option<u32> {
+    let cp = u32::from(c);
+    let &(lo, hi) = DECIMAL.get(DECIMAL.partition_point(|&(_, hi)| hi < cp))?;
+    (lo..=hi).contains(&cp).then(|| (cp - lo) % 10)
+}
+
+/// Returns the mapping of `c` in `table`, if it has one.
+pub(crate) fn lookup_mapping(table: &[(char, &'static str)], c: char) -> Option<&'static str> {
+    table
+        .binary_search_by_key(&c, |&(key, _)| key)
+        .ok()
+        .map(|index| table[index].1)
+}
+"""
+
+
+def is_surrogate(cp: int) -> bool:
+    return 0xD800 <= cp <= 0xDFFF
+
+
+def is_cased(c: str) -> bool:
+    return c.islower() or c.isupper() or unicodedata.category(c) == 'Lt'
+
+
+def is_case_ignorable(c: str) -> bool:
+    """Recover CPython's private Case_Ignorable property from its final sigma handling.
+
+    A capital sigma lowercases to a final `ς` when a cased character precedes it, skipping
+    case-ignorable characters. So `c` is case-ignorable exactly when it doesn't change that
+    outcome: after a cased letter for uncased `c`, and after a space for cased `c`.
+    """
+    if is_cased(c):
+        return (' ' + c + 'Σ').lower()[-1] == 'σ'
+    return ('A' + c + 'Σ').lower()[-1] == 'ς'
+
+
+def ranges_matching(pred: Callable[[str], bool]) -> list[tuple[int, int]]:
+    """Collect the characters matching `pred` into sorted, inclusive code point ranges."""
+    ranges: list[tuple[int, int]] = []
+    for cp in range(0x110000):
+        if is_surrogate(cp) or not pred(chr(cp)):
+            continue
+        if ranges and ranges[-1][1] == cp - 1:
+            ranges[-1] = (ranges[-1][0], cp)
+        else:
+            ranges.append((cp, cp))
+    return ranges
+
+
+def check_decimal_runs() -> None:
+    """Check the assumption `decimal_value` relies on: decimal ranges are runs of 0 to 9."""
+    for lo, hi in ranges_matching(str.isdecimal):
+        for cp in range(lo, hi + 1):
+            assert unicodedata.decimal(chr(cp)) == (cp - lo) % 10, f'unexpected decimal digit U+{cp:04X}'
+
+
+def range_table(name: str, doc: str, pred: Callable[[str], bool]) -> str:
+    """Render the characters matching `pred` as sorted, inclusive code point ranges."""
+    ranges = ranges_matching(pred)
+    items = ''.join(f'    (0x{lo:04X}, 0x{hi:04X}),\n' for lo, hi in ranges)
+    return f'/// {doc}\npub(crate) static {name}: &[(u32, u32)] = &[\n{items}];\n'
+
+
+def mapping_table(name: str, doc: str, mapping: Callable[[str], str | None]) -> str:
+    """Render the characters for which `mapping` returns a string, sorted by character."""
+    items = []
+    for cp in range(0x110000):
+        if is_surrogate(cp):
+            continue
+        mapped = mapping(chr(cp))
+        if mapped is not None:
+            escaped = ''.join(m if m.isascii() and m.isalnum() else f'\\u{{{ord(m):04X}}}' for m in mapped)
+            items.append(f"    ('\\u{{{cp:04X}}}', \"{escaped}\"),\n")
+    return f'/// {doc}\npub(crate) static {name}: &[(char, &str)] = &[\n{"".join(items)}];\n'
+
+
+def main() -> int:
+    check_decimal_runs()
+    python_version = f'{sys.version_info.major}.{sys.version_info.minor}'
+    sections = [
+        f'//! Unicode character tables matching CPython {python_version} (Unicode {unicodedata.unidata_version}).\n'
+        '//!\n'
+        '//! Generated by `scripts/generate_unicode_tables.py`, do not edit by hand.\n'
+        '//! Range tables hold sorted, inclusive code point ranges; mapping tables are sorted by character.\n'
+        '//! Other case mappings come from Rust\'s `char` methods, which may know characters added in\n'
+        '//! later Unicode versions.\n'
+        '\nuse std::cmp::Ordering;\n',
+        HELPERS,
+        range_table(
+            'ALPHA', 'Characters for which `str.isalpha()` is true (categories Lu, Ll, Lt, Lm, Lo).', str.isalpha
+        ),
+        range_table('DECIMAL', 'Characters for which `str.isdecimal()` is true.', str.isdecimal),
+        range_table('DIGIT', 'Characters for which `str.isdigit()` is true.', str.isdigit),
+        range_table('NUMERIC', 'Characters for which `str.isnumeric()` is true.', str.isnumeric),
+        range_table('SPACE', 'Characters for which `str.isspace()` is true.', str.isspace),
+        range_table('PRINTABLE', 'Characters for which `str.isprintable()` is true.', str.isprintable),
+        range_table('LOWER', 'Characters with the Lowercase property, as tested by `str.islower()`.', str.islower),
+        range_table('UPPER', 'Characters with the Uppercase property, as tested by `str.isupper()`.', str.isupper),
+        range_table(
+            'TITLE', 'Titlecase letters (category Lt), such as `ǅ`.', lambda c: unicodedata.category(c) == 'Lt'
+        ),
+        range_table(
+            'CASE_IGNORABLE', 'Characters skipped when deciding whether a capital sigma is final.', is_case_ignorable
+        ),
+        range_table('XID_START', 'Characters allowed at the start of an identifier.', str.isidentifier),
+        range_table(
+            'XID_CONTINUE', 'Characters allowed in an identifier after the first.', lambda c: ('a' + c).isidentifier()
+        ),
+        mapping_table(
+            'TITLE_MAPPING',
+            'Titlecase mappings of the characters whose titlecase differs from their uppercase.',
+            lambda c: c.title() if c.title() != c.upper() else None,
+        ),
+        mapping_table(
+            'CASEFOLD_MAPPING',
+            'Case folding of the characters whose casefold differs from their lowercase.',
+            lambda c: c.casefold() if c.casefold() != c.lower() else None,
+        ),
+    ]
+    OUTPUT.write_text('\n'.join(sections))
+    print(f'wrote {OUTPUT}')
+    return 0
+
+
+if __name__ == '__main__':
+    sys.exit(main())
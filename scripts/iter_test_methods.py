@@ -11,10 +11,14 @@ This module is shared between:
 
 from __future__ import annotations
 
+import builtins
 import os
 import stat as stat_module
 from dataclasses import dataclass
+from fnmatch import fnmatchcase
 from pathlib import Path
+from types import ModuleType
+from typing import Iterator
 
 
 def add_ints(a: int, b: int) -> int:
@@ -146,6 +150,9 @@ VIRTUAL_DIR_CONTENTS: dict[str, list[str]] = {
     '/virtual/subdir/deep': ['/virtual/subdir/deep/file.txt'],
 }
 
+# Symbolic links: link path -> target path, all created by tests
+VIRTUAL_SYMLINKS: dict[str, str] = {}
+
 
 class VirtualStatResult:
     """Mock stat_result for virtual filesystem.
@@ -157,8 +164,8 @@ class VirtualStatResult:
         self.st_mode = st_mode
         self.st_ino = 0
         self.st_dev = 0
-        # nlink is 1 for files, 2 for directories
-        self.st_nlink = 1 if stat_module.S_ISREG(st_mode) else 2
+        # nlink is 2 for directories, 1 for files and symlinks
+        self.st_nlink = 2 if stat_module.S_ISDIR(st_mode) else 1
         self.st_uid = 0
         self.st_gid = 0
         self.st_size = st_size
@@ -188,6 +195,33 @@ def is_virtual_path(path: str) -> bool:
     return path.startswith('/virtual') or path.startswith('/nonexistent')
 
 
+def resolve_virtual_symlinks(path: str) -> str:
+    """Follow virtual symlinks until reaching a path which isn't one."""
+    while path in VIRTUAL_SYMLINKS:
+        path = VIRTUAL_SYMLINKS[path]
+    return path
+
+
+def walk_virtual_dir(path: str, top_down: bool) -> list[tuple[str, list[str], list[str]]]:
+    """Walk a virtual directory tree without following symlinks, with entries sorted by name."""
+    entries = sorted(VIRTUAL_DIR_CONTENTS.get(path, []))
+    dirs = [Path(entry).name for entry in entries if entry in VIRTUAL_DIRS]
+    files = [Path(entry).name for entry in entries if entry not in VIRTUAL_DIRS]
+    subtrees = [tree for name in dirs for tree in walk_virtual_dir(f'{path}/{name}', top_down)]
+    this = (path, dirs, files)
+    return [this, *subtrees] if top_down else [*subtrees, this]
+
+
+def glob_match_parts(pattern: list[str], parts: list[str]) -> bool:
+    """Match path components against glob pattern components, where `**` matches any number of components."""
+    if not pattern:
+        return not parts
+    first, *rest = pattern
+    if first == '**':
+        return any(glob_match_parts(rest, parts[skip:]) for skip in range(len(parts) + 1))
+    return bool(parts) and fnmatchcase(parts[0], first) and glob_match_parts(rest, parts[1:])
+
+
 class VirtualPath(type(Path())):
     """Path subclass that uses virtual filesystem for /virtual/ and /nonexistent paths.
 
@@ -198,32 +232,33 @@ class VirtualPath(type(Path())):
     def exists(self, *, follow_symlinks: bool = True) -> bool:
         path_str = str(self)
         if is_virtual_path(path_str):
+            path_str = resolve_virtual_symlinks(path_str)
             return path_str in VIRTUAL_FILES or path_str in VIRTUAL_DIRS
         return super().exists(follow_symlinks=follow_symlinks)
 
     def is_file(self, *, follow_symlinks: bool = True) -> bool:
         path_str = str(self)
         if is_virtual_path(path_str):
-            return path_str in VIRTUAL_FILES
+            return resolve_virtual_symlinks(path_str) in VIRTUAL_FILES
         return super().is_file(follow_symlinks=follow_symlinks)
 
     def is_dir(self, *, follow_symlinks: bool = True) -> bool:
         path_str = str(self)
         if is_virtual_path(path_str):
-            return path_str in VIRTUAL_DIRS
+            return resolve_virtual_symlinks(path_str) in VIRTUAL_DIRS
         return super().is_dir(follow_symlinks=follow_symlinks)
 
     def is_symlink(self) -> bool:
         path_str = str(self)
         if is_virtual_path(path_str):
-            return False  # No symlinks in virtual fs
+            return path_str in VIRTUAL_SYMLINKS
         return super().is_symlink()
 
     def read_text(self, encoding: str | None = None, errors: str | None = None, newline: str | None = None) -> str:
         path_str = str(self)
         if is_virtual_path(path_str):
-            if path_str in VIRTUAL_FILES:
-                content, _ = VIRTUAL_FILES[path_str]
+            if (resolved := resolve_virtual_symlinks(path_str)) in VIRTUAL_FILES:
+                content, _ = VIRTUAL_FILES[resolved]
                 return content.decode('utf-8')
             raise FileNotFoundError(2, 'No such file or directory', path_str)
         return super().read_text(encoding=encoding, errors=errors, newline=newline)
@@ -231,8 +266,8 @@ class VirtualPath(type(Path())):
     def read_bytes(self) -> bytes:
         path_str = str(self)
         if is_virtual_path(path_str):
-            if path_str in VIRTUAL_FILES:
-                content, _ = VIRTUAL_FILES[path_str]
+            if (resolved := resolve_virtual_symlinks(path_str)) in VIRTUAL_FILES:
+                content, _ = VIRTUAL_FILES[resolved]
                 return content
             raise FileNotFoundError(2, 'No such file or directory', path_str)
         return super().read_bytes()
@@ -242,18 +277,28 @@ class VirtualPath(type(Path())):
     ) -> VirtualStatResult | os.stat_result:
         path_str = str(self)
         if is_virtual_path(path_str):
-            if path_str in VIRTUAL_FILES:
-                content, mode = VIRTUAL_FILES[path_str]
+            resolved = resolve_virtual_symlinks(path_str)
+            if resolved in VIRTUAL_FILES:
+                content, mode = VIRTUAL_FILES[resolved]
                 # Add regular file type bits
                 st_mode = mode | stat_module.S_IFREG
                 return VirtualStatResult(st_mode, len(content))
-            if path_str in VIRTUAL_DIRS:
+            if resolved in VIRTUAL_DIRS:
                 # Directory: 0o755 with directory type bits
                 st_mode = 0o755 | stat_module.S_IFDIR
                 return VirtualStatResult(st_mode, 4096)
             raise FileNotFoundError(2, 'No such file or directory', path_str)
         return super().stat(follow_symlinks=follow_symlinks)
 
+    def lstat(self) -> VirtualStatResult | os.stat_result:  # pyright: ignore[reportIncompatibleMethodOverride]
+        path_str = str(self)
+        if path_str in VIRTUAL_SYMLINKS:
+            # Symlinks describe themselves, with full permissions and no size
+            return VirtualStatResult(0o777 | stat_module.S_IFLNK, 0)
+        if is_virtual_path(path_str):
+            return self.stat()
+        return super().lstat()
+
     def iterdir(self):  # pyright: ignore[reportUnknownParameterType]
         path_str = str(self)
         if is_virtual_path(path_str):
@@ -332,6 +377,10 @@ class VirtualPath(type(Path())):
     def unlink(self, missing_ok: bool = False) -> None:
         path_str = str(self)
         if is_virtual_path(path_str):
+            if path_str in VIRTUAL_SYMLINKS:
+                del VIRTUAL_SYMLINKS[path_str]
+                _remove_from_parent_dir(path_str)
+                return
             if path_str in VIRTUAL_FILES:
                 del VIRTUAL_FILES[path_str]
                 _remove_from_parent_dir(path_str)
@@ -373,6 +422,99 @@ class VirtualPath(type(Path())):
             raise FileNotFoundError(2, 'No such file or directory', path_str)
         return VirtualPath(super().rename(target))
 
+    def copy(self, target: 'VirtualPath | str', **kwargs: object) -> 'VirtualPath':
+        path_str = str(self)
+        target_str = str(target)
+        if is_virtual_path(path_str):
+            resolved = resolve_virtual_symlinks(path_str)
+            if resolved in VIRTUAL_FILES:
+                # Content and mode are copied, like shutil.copy
+                VIRTUAL_FILES[target_str] = VIRTUAL_FILES[resolved]
+                _add_to_parent_dir(target_str)
+                return VirtualPath(target_str)
+            if resolved in VIRTUAL_DIRS:
+                raise IsADirectoryError(21, 'Is a directory', path_str)
+            raise FileNotFoundError(2, 'No such file or directory', path_str)
+        return VirtualPath(super().copy(target, **kwargs))  # pyright: ignore[reportAttributeAccessIssue,reportUnknownMemberType,reportUnknownArgumentType]
+
+    def touch(self, mode: int = 0o666, exist_ok: bool = True) -> None:
+        path_str = str(self)
+        if is_virtual_path(path_str):
+            resolved = resolve_virtual_symlinks(path_str)
+            if resolved in VIRTUAL_FILES or resolved in VIRTUAL_DIRS:
+                if not exist_ok:
+                    raise FileExistsError(17, 'File exists', path_str)
+                return
+            if str(Path(resolved).parent) not in VIRTUAL_DIRS:
+                raise FileNotFoundError(2, 'No such file or directory', path_str)
+            VIRTUAL_FILES[resolved] = (b'', 0o644)
+            _add_to_parent_dir(resolved)
+            return
+        super().touch(mode=mode, exist_ok=exist_ok)
+
+    def chmod(self, mode: int, *, follow_symlinks: bool = True) -> None:
+        path_str = str(self)
+        if is_virtual_path(path_str):
+            resolved = resolve_virtual_symlinks(path_str)
+            if resolved in VIRTUAL_FILES:
+                content, _ = VIRTUAL_FILES[resolved]
+                VIRTUAL_FILES[resolved] = (content, mode)
+                return
+            # Directories keep their fixed mode, the virtual filesystem only stores file modes
+            if resolved in VIRTUAL_DIRS:
+                return
+            raise FileNotFoundError(2, 'No such file or directory', path_str)
+        super().chmod(mode, follow_symlinks=follow_symlinks)
+
+    def glob(self, pattern: str, **kwargs: object) -> Iterator['VirtualPath']:  # pyright: ignore[reportIncompatibleMethodOverride]
+        path_str = str(self)
+        if is_virtual_path(path_str):
+            resolved = resolve_virtual_symlinks(path_str)
+            pattern_parts = pattern.split('/')
+            for dir_path, dirs, files in walk_virtual_dir(resolved, True):
+                for name in dirs + files:
+                    entry = f'{dir_path}/{name}'
+                    if glob_match_parts(pattern_parts, entry[len(resolved) + 1 :].split('/')):
+                        yield VirtualPath(entry)
+            return
+        yield from super().glob(pattern, **kwargs)  # pyright: ignore[reportArgumentType]
+
+    def rglob(self, pattern: str, **kwargs: object) -> Iterator['VirtualPath']:  # pyright: ignore[reportIncompatibleMethodOverride]
+        if is_virtual_path(str(self)):
+            return self.glob(f'**/{pattern}')
+        return super().rglob(pattern, **kwargs)  # pyright: ignore[reportArgumentType,reportReturnType]
+
+    def walk(  # pyright: ignore[reportIncompatibleMethodOverride]
+        self, top_down: bool = True, on_error: object = None, follow_symlinks: bool = False
+    ) -> Iterator[tuple['VirtualPath', list[str], list[str]]]:
+        path_str = str(self)
+        if is_virtual_path(path_str):
+            resolved = resolve_virtual_symlinks(path_str)
+            for dir_path, dirs, files in walk_virtual_dir(resolved, top_down):
+                yield VirtualPath(dir_path), dirs, files
+            return
+        yield from super().walk(top_down, on_error, follow_symlinks)  # pyright: ignore[reportAttributeAccessIssue,reportUnknownMemberType]
+
+    def readlink(self) -> 'VirtualPath':
+        path_str = str(self)
+        if is_virtual_path(path_str):
+            if path_str in VIRTUAL_SYMLINKS:
+                return VirtualPath(VIRTUAL_SYMLINKS[path_str])
+            if path_str in VIRTUAL_FILES or path_str in VIRTUAL_DIRS:
+                raise OSError(22, 'Invalid argument', path_str)
+            raise FileNotFoundError(2, 'No such file or directory', path_str)
+        return VirtualPath(super().readlink())
+
+    def symlink_to(self, target: 'VirtualPath | str', target_is_directory: bool = False) -> None:
+        path_str = str(self)
+        if is_virtual_path(path_str):
+            if path_str in VIRTUAL_SYMLINKS or path_str in VIRTUAL_FILES or path_str in VIRTUAL_DIRS:
+                raise FileExistsError(17, 'File exists', path_str)
+            VIRTUAL_SYMLINKS[path_str] = str(target)
+            _add_to_parent_dir(path_str)
+            return
+        super().symlink_to(target, target_is_directory)
+
     # __truediv__ is NOT overridden - the parent class already uses type(self)
     # to create new paths, which will be VirtualPath instances
 
@@ -515,6 +657,81 @@ class VirtualEnviron:
 os.environ = VirtualEnviron()
 
 
+# =============================================================================
+# Virtual Scratch Space for tempfile Tests
+# =============================================================================
+
+# Default directory for tempfile scratch space (matches Rust constant), created when first used
+VFS_SCRATCH_DIR = '/virtual/tmp'
+
+# Number of scratch paths handed out, which names them instead of random characters
+_scratch_count = 0
+
+
+@dataclass(frozen=True)
+class VirtualScratch:
+    """Stand-in for the objects tempfile functions return, Monty's only have `name`."""
+
+    name: str
+
+
+def _create_virtual_scratch(
+    is_dir: bool, suffix: str | None, prefix: str | None, dir: str | os.PathLike[str] | None
+) -> VirtualScratch:
+    """Create an empty virtual directory or file, mirroring `create_virtual_scratch` in the Rust runner."""
+    global _scratch_count
+    _scratch_count += 1
+    parent = VFS_SCRATCH_DIR if dir is None else str(dir)
+    name = f'{parent}/{"tmp" if prefix is None else prefix}{_scratch_count:08}{suffix or ""}'
+    if dir is None:
+        VirtualPath(parent).mkdir(exist_ok=True)
+    elif parent not in VIRTUAL_DIRS:
+        raise FileNotFoundError(2, 'No such file or directory', name)
+    if is_dir:
+        VirtualPath(name).mkdir()
+    else:
+        VIRTUAL_FILES[name] = (b'', 0o600)
+        _add_to_parent_dir(name)
+    return VirtualScratch(name)
+
+
+def _virtual_temporary_directory(
+    suffix: str | None = None, prefix: str | None = None, dir: str | os.PathLike[str] | None = None
+) -> VirtualScratch:
+    return _create_virtual_scratch(True, suffix, prefix, dir)
+
+
+def _virtual_named_temporary_file(
+    *, suffix: str | None = None, prefix: str | None = None, dir: str | os.PathLike[str] | None = None
+) -> VirtualScratch:
+    return _create_virtual_scratch(False, suffix, prefix, dir)
+
+
+# Stand-in `tempfile` module handing out scratch space in the virtual filesystem. The real module
+# is left alone since the test harness itself needs it (e.g. run_traceback.py writes the test file
+# with `tempfile.NamedTemporaryFile`), test code gets this one through `ITER_MODE_BUILTINS` instead.
+ITER_MODE_TEMPFILE = ModuleType('tempfile')
+ITER_MODE_TEMPFILE.TemporaryDirectory = _virtual_temporary_directory  # pyright: ignore[reportAttributeAccessIssue]
+ITER_MODE_TEMPFILE.NamedTemporaryFile = _virtual_named_temporary_file  # pyright: ignore[reportAttributeAccessIssue]
+
+
+def _iter_mode_import(
+    name: str,
+    globals: dict[str, object] | None = None,
+    locals: dict[str, object] | None = None,
+    fromlist: tuple[str, ...] = (),
+    level: int = 0,
+) -> ModuleType:
+    """`__import__` for iter mode test code, resolving `tempfile` to `ITER_MODE_TEMPFILE`."""
+    if name == 'tempfile' and level == 0:
+        return ITER_MODE_TEMPFILE
+    return builtins.__import__(name, globals, locals, fromlist, level)
+
+
+# Builtins for iter mode test code, only differing from the real ones in how `tempfile` is imported
+ITER_MODE_BUILTINS: dict[str, object] = {**vars(builtins), '__import__': _iter_mode_import}
+
+
 # All external functions available to iter mode tests
 ITER_MODE_GLOBALS: dict[str, object] = {
     'add_ints': add_ints,
@@ -527,4 +744,5 @@ ITER_MODE_GLOBALS: dict[str, object] = {
     'make_user': make_user,
     'make_empty': make_empty,
     'async_call': async_call,
+    '__builtins__': ITER_MODE_BUILTINS,
 }
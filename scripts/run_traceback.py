@@ -29,7 +29,7 @@ def run_file_and_get_traceback(
     Execute a Python file and return the formatted traceback if an exception occurs.
 
     The traceback will have the basename as the filename for the executed code,
-    with caret lines (`~~~~~`) properly shown for all frames.
+    with caret lines (`~~~^^^`) properly shown for all frames.
 
     Args:
         fixture_file_path: Path to the Python file to execute.
@@ -42,7 +42,7 @@ def run_file_and_get_traceback(
             top-level await that Monty supports but CPython doesn't.
 
     Returns:
-        Formatted traceback string with '^' replaced by '~', or None if no exception.
+        Formatted traceback string, or None if no exception.
     """
     # Get absolute path for consistent replacement
     abs_path = os.path.abspath(fixture_file_path)
@@ -120,7 +120,7 @@ def run_file_and_get_traceback(
                 # Restore a high limit for traceback formatting
                 sys.setrecursionlimit(previous_recursion_limit)
                 lines = (''.join(result_frames)).splitlines()
-                return '\n'.join(map(normalize_debug_range, lines)).rstrip()
+                return '\n'.join(map(normalize_line, lines)).rstrip()
 
 
 def _adjust_async_frame(frame: str, tmp_path: str, file_name: str, line_offset: int) -> str | None:
@@ -152,15 +152,11 @@ def format_full_traceback(e: Exception):
     stack = traceback.format_exception(type(e), e, e.__traceback__)
 
     lines = (''.join(stack)).splitlines()
-    return '\n'.join(map(normalize_debug_range, lines)).rstrip()
+    return '\n'.join(map(normalize_line, lines)).rstrip()
 
 
-def normalize_debug_range(line: str) -> str:
-    line = line.replace('dataclasses.FrozenInstanceError:', 'FrozenInstanceError:')
-    if re.fullmatch(r' +[\~\^]+', line):
-        return line.replace('^', '~')
-    else:
-        return line
+def normalize_line(line: str) -> str:
+    return line.replace('dataclasses.FrozenInstanceError:', 'FrozenInstanceError:')
 
 
 if __name__ == '__main__':